@@ -0,0 +1,369 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, fs, path::Path};
+
+use common::{
+    objects::{DisconnectPolicy, StoredWorkloadSpec, WorkloadSpec},
+    persistence::PersistenceFormat,
+};
+use serde::Deserialize;
+
+use crate::{
+    process_priority::ProcessPriority,
+    runtime_connectors::{PodmanStorage, RuntimeConcurrencyLimits},
+};
+
+#[derive(Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AgentConfig {
+    pub local_workloads: HashMap<String, StoredWorkloadSpec>,
+    // [impl->swdd~agent-persists-in-configured-format~1]
+    pub persistence_format: PersistenceFormat,
+    // [impl->swdd~agent-applies-configured-process-priority~1]
+    pub agent_priority: ProcessPriority,
+    pub state_checker_priority: ProcessPriority,
+    // [impl->swdd~agent-passes-podman-storage-to-all-podman-calls~1]
+    pub podman_storage: PodmanStorage,
+    // [impl->swdd~agent-limits-concurrent-operations-per-runtime~1]
+    pub runtime_concurrency: RuntimeConcurrencyLimits,
+}
+
+impl AgentConfig {
+    // [impl->swdd~agent-loads-local-workloads-from-config~1]
+    pub fn from_file(path: &Path, agent_name: &str) -> Result<AgentConfig, String> {
+        let content = fs::read_to_string(path).map_err(|err| {
+            format!(
+                "Could not read the agent config '{}': '{}'",
+                path.display(),
+                err
+            )
+        })?;
+
+        let config: AgentConfig = serde_yaml::from_str(&content).map_err(|err| {
+            format!(
+                "Could not parse the agent config '{}': '{}'",
+                path.display(),
+                err
+            )
+        })?;
+
+        config.verify_local_workloads(agent_name)?;
+        config.verify_priorities(path)?;
+        config.verify_runtime_concurrency(path)?;
+        Ok(config)
+    }
+
+    pub fn local_workload_specs(&self) -> Vec<WorkloadSpec> {
+        self.local_workloads
+            .iter()
+            .map(|(name, workload)| (name.clone(), workload.clone()).into())
+            .collect()
+    }
+
+    fn verify_priorities(&self, path: &Path) -> Result<(), String> {
+        for (name, priority) in [
+            ("agentPriority", &self.agent_priority),
+            ("stateCheckerPriority", &self.state_checker_priority),
+        ] {
+            priority.verify().map_err(|err| {
+                format!(
+                    "Invalid '{}' in the agent config '{}': '{}'",
+                    name,
+                    path.display(),
+                    err
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    fn verify_runtime_concurrency(&self, path: &Path) -> Result<(), String> {
+        match self
+            .runtime_concurrency
+            .iter()
+            .find(|(_, max_concurrent_operations)| **max_concurrent_operations == 0)
+        {
+            Some((runtime_name, _)) => Err(format!(
+                "Invalid 'runtimeConcurrency' in the agent config '{}': runtime '{}' must allow at least one operation",
+                path.display(),
+                runtime_name
+            )),
+            None => Ok(()),
+        }
+    }
+
+    // Local workloads are managed without the server, hence they cannot use any feature
+    // that requires the server to resolve or to supervise it.
+    fn verify_local_workloads(&self, agent_name: &str) -> Result<(), String> {
+        for (workload_name, workload) in &self.local_workloads {
+            let error = if workload.agent != agent_name {
+                format!("is assigned to agent '{}'", workload.agent)
+            } else if workload.runtime.is_empty() {
+                "has no runtime".to_string()
+            } else if !workload.dependencies.is_empty()
+                || workload.dependency_expression.is_some()
+                || !workload.agent_dependencies.is_empty()
+            {
+                "has dependencies".to_string()
+            } else if !workload.configs.is_empty() || !workload.template.is_empty() {
+                "references configs or templates".to_string()
+            } else if workload.disconnect_policy != DisconnectPolicy::KeepRunning {
+                "has a disconnect policy other than 'KEEP_RUNNING'".to_string()
+            } else {
+                continue;
+            };
+
+            return Err(format!(
+                "Local workload '{}' of agent '{}' {}.",
+                workload_name, agent_name, error
+            ));
+        }
+        Ok(())
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use common::persistence::PersistenceFormat;
+
+    use super::AgentConfig;
+    use crate::{
+        process_priority::{IoClass, ProcessPriority},
+        runtime_connectors::{PodmanStorage, RuntimeConcurrencyLimits},
+    };
+
+    const AGENT_NAME: &str = "agent_A";
+
+    fn write_config(content: &str) -> tempfile::NamedTempFile {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), content).unwrap();
+        file
+    }
+
+    // [utest->swdd~agent-loads-local-workloads-from-config~1]
+    #[test]
+    fn utest_agent_config_loads_local_workloads() {
+        let file = write_config(
+            r#"
+localWorkloads:
+  watchdog:
+    agent: agent_A
+    runtime: podman
+    restartPolicy: ALWAYS
+    runtimeConfig: |
+      image: ghcr.io/eclipse-ankaios/watchdog:latest
+"#,
+        );
+
+        let config = AgentConfig::from_file(file.path(), AGENT_NAME).unwrap();
+
+        let workload_specs = config.local_workload_specs();
+        assert_eq!(workload_specs.len(), 1);
+        assert_eq!(workload_specs[0].instance_name.workload_name(), "watchdog");
+        assert_eq!(workload_specs[0].instance_name.agent_name(), AGENT_NAME);
+        assert_eq!(workload_specs[0].runtime, "podman");
+    }
+
+    // [utest->swdd~agent-loads-local-workloads-from-config~1]
+    #[test]
+    fn utest_agent_config_without_local_workloads() {
+        let file = write_config("{}");
+
+        assert_eq!(
+            AgentConfig::from_file(file.path(), AGENT_NAME),
+            Ok(AgentConfig::default())
+        );
+    }
+
+    // [utest->swdd~agent-persists-in-configured-format~1]
+    #[test]
+    fn utest_agent_config_loads_persistence_format() {
+        let file = write_config("persistenceFormat: cbor");
+
+        assert_eq!(
+            AgentConfig::from_file(file.path(), AGENT_NAME).map(|config| config.persistence_format),
+            Ok(PersistenceFormat::Cbor)
+        );
+    }
+
+    // [utest->swdd~agent-applies-configured-process-priority~1]
+    #[test]
+    fn utest_agent_config_loads_priorities() {
+        let file = write_config(
+            r#"
+agentPriority:
+  nice: 10
+  ioClass: idle
+  cgroup: /sys/fs/cgroup/ankaios.slice
+stateCheckerPriority:
+  nice: 19
+  ioClass: bestEffort
+  ioLevel: 7
+"#,
+        );
+
+        let config = AgentConfig::from_file(file.path(), AGENT_NAME).unwrap();
+
+        assert_eq!(
+            config.agent_priority,
+            ProcessPriority {
+                nice: Some(10),
+                io_class: Some(IoClass::Idle),
+                io_level: None,
+                cgroup: Some(PathBuf::from("/sys/fs/cgroup/ankaios.slice")),
+            }
+        );
+        assert_eq!(
+            config.state_checker_priority,
+            ProcessPriority {
+                nice: Some(19),
+                io_class: Some(IoClass::BestEffort),
+                io_level: Some(7),
+                cgroup: None,
+            }
+        );
+    }
+
+    // [utest->swdd~agent-passes-podman-storage-to-all-podman-calls~1]
+    #[test]
+    fn utest_agent_config_loads_podman_storage() {
+        let file = write_config(
+            r#"
+podmanStorage:
+  root: /data/containers/storage
+  runRoot: /run/ankaios/containers
+"#,
+        );
+
+        let config = AgentConfig::from_file(file.path(), AGENT_NAME).unwrap();
+
+        assert_eq!(
+            config.podman_storage,
+            PodmanStorage {
+                root: Some(PathBuf::from("/data/containers/storage")),
+                run_root: Some(PathBuf::from("/run/ankaios/containers")),
+            }
+        );
+    }
+
+    // [utest->swdd~agent-limits-concurrent-operations-per-runtime~1]
+    #[test]
+    fn utest_agent_config_loads_runtime_concurrency() {
+        let file = write_config("runtimeConcurrency:\n  podman: 4\n");
+
+        let config = AgentConfig::from_file(file.path(), AGENT_NAME).unwrap();
+
+        assert_eq!(
+            config.runtime_concurrency,
+            RuntimeConcurrencyLimits::from([("podman".to_string(), 4)])
+        );
+    }
+
+    // [utest->swdd~agent-limits-concurrent-operations-per-runtime~1]
+    #[test]
+    fn utest_agent_config_fails_on_zero_runtime_concurrency() {
+        let file = write_config("runtimeConcurrency:\n  podman: 0\n");
+
+        assert!(AgentConfig::from_file(file.path(), AGENT_NAME).is_err());
+    }
+
+    // [utest->swdd~agent-applies-configured-process-priority~1]
+    #[test]
+    fn utest_agent_config_fails_on_invalid_priority() {
+        let file = write_config("agentPriority:\n  nice: -21\n");
+
+        assert!(AgentConfig::from_file(file.path(), AGENT_NAME).is_err());
+    }
+
+    // [utest->swdd~agent-loads-local-workloads-from-config~1]
+    #[test]
+    fn utest_agent_config_fails_on_local_workload_of_other_agent() {
+        let file = write_config(
+            r#"
+localWorkloads:
+  watchdog:
+    agent: agent_B
+    runtime: podman
+    runtimeConfig: "image: alpine:latest"
+"#,
+        );
+
+        assert_eq!(
+            AgentConfig::from_file(file.path(), AGENT_NAME),
+            Err(
+                "Local workload 'watchdog' of agent 'agent_A' is assigned to agent 'agent_B'."
+                    .to_string()
+            )
+        );
+    }
+
+    // [utest->swdd~agent-loads-local-workloads-from-config~1]
+    #[test]
+    fn utest_agent_config_fails_on_local_workload_with_dependencies() {
+        let file = write_config(
+            r#"
+localWorkloads:
+  watchdog:
+    agent: agent_A
+    runtime: podman
+    runtimeConfig: "image: alpine:latest"
+    dependencies:
+      logger: ADD_COND_RUNNING
+"#,
+        );
+
+        assert!(AgentConfig::from_file(file.path(), AGENT_NAME).is_err());
+    }
+
+    // [utest->swdd~agent-loads-local-workloads-from-config~1]
+    #[test]
+    fn utest_agent_config_fails_on_local_workload_with_agent_dependencies() {
+        let file = write_config(
+            r#"
+localWorkloads:
+  watchdog:
+    agent: agent_A
+    runtime: podman
+    runtimeConfig: "image: alpine:latest"
+    agentDependencies: [agent_B]
+"#,
+        );
+
+        assert_eq!(
+            AgentConfig::from_file(file.path(), AGENT_NAME),
+            Err("Local workload 'watchdog' of agent 'agent_A' has dependencies.".to_string())
+        );
+    }
+
+    // [utest->swdd~agent-loads-local-workloads-from-config~1]
+    #[test]
+    fn utest_agent_config_fails_on_missing_file() {
+        let folder = tempfile::tempdir().unwrap();
+
+        assert!(
+            AgentConfig::from_file(&folder.path().join("ank-agent.yaml"), AGENT_NAME).is_err()
+        );
+    }
+}
@@ -12,19 +12,36 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use std::time::Duration;
+
 use common::{
+    commands::PendingWorkloadOperation,
     from_server_interface::{FromServer, FromServerReceiver},
+    helpers::now_ms,
     objects::WorkloadState,
     std_extensions::{GracefulExitResult, IllegalStateResult},
     to_server_interface::{ToServerInterface, ToServerSender},
 };
+use tokio::time::Instant;
 
+use crate::agent_metrics::{publish_subsystem_health, SERVER_CONNECTION_SUBSYSTEM};
+use crate::workload_scheduler::queue_storage::QueueStorage;
 #[cfg_attr(test, mockall_double::double)]
 use crate::workload_state::workload_state_store::WorkloadStateStore;
 
 #[cfg_attr(test, mockall_double::double)]
 use crate::runtime_manager::RuntimeManager;
 use crate::workload_state::WorkloadStateReceiver;
+
+pub const DEFAULT_DISCONNECT_THRESHOLD_SECS: u64 = 30;
+
+async fn wait_for_deadline(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
 // [impl->swdd~agent-shall-use-interfaces-to-server~1]
 pub struct AgentManager {
     agent_name: String,
@@ -34,6 +51,13 @@ pub struct AgentManager {
     to_server: ToServerSender,
     workload_state_receiver: WorkloadStateReceiver,
     workload_state_store: WorkloadStateStore,
+    last_update_workload_sequence_number: u64,
+    disconnect_threshold: Duration,
+    degraded_mode_deadline: Option<Instant>,
+    degraded_mode: bool,
+    pending_operations_deadline: Option<Instant>,
+    reported_pending_operations: Vec<PendingWorkloadOperation>,
+    reported_workload_state_subscription: Option<Vec<String>>,
 }
 
 impl AgentManager {
@@ -51,9 +75,25 @@ impl AgentManager {
             to_server,
             workload_state_receiver,
             workload_state_store: WorkloadStateStore::new(),
+            last_update_workload_sequence_number: 0,
+            disconnect_threshold: Duration::from_secs(DEFAULT_DISCONNECT_THRESHOLD_SECS),
+            degraded_mode_deadline: None,
+            degraded_mode: false,
+            pending_operations_deadline: None,
+            reported_pending_operations: Vec::new(),
+            reported_workload_state_subscription: None,
         }
     }
 
+    pub fn set_disconnect_threshold(&mut self, disconnect_threshold: Duration) {
+        self.disconnect_threshold = disconnect_threshold;
+    }
+
+    // [impl->swdd~agent-restores-last-known-workload-states~1]
+    pub fn restore_last_known_workload_states(&mut self, storage: QueueStorage) {
+        self.workload_state_store.restore_last_known_states(storage);
+    }
+
     pub async fn start(&mut self) {
         log::info!("Awaiting commands from the server ...");
         loop {
@@ -76,6 +116,19 @@ impl AgentManager {
 
                     self.store_and_forward_own_workload_states(workload_state).await;
                 }
+                // [impl->swdd~agent-enters-degraded-mode-after-disconnect-threshold~1]
+                _ = wait_for_deadline(self.degraded_mode_deadline) => {
+                    self.enter_degraded_mode().await;
+                }
+                // [impl->swdd~agent-reports-exceeded-update-deadline~1]
+                _ = wait_for_deadline(self.pending_operations_deadline) => {
+                    self.runtime_manager
+                        .expire_pending_workload_operations(&self.workload_state_store)
+                        .await;
+                    self.pending_operations_deadline =
+                        self.runtime_manager.next_pending_operations_deadline();
+                    self.report_pending_workload_operations().await;
+                }
             }
         }
     }
@@ -91,14 +144,76 @@ impl AgentManager {
                     method_obj.added_workloads,
                     method_obj.deleted_workloads);
 
-                // [impl->swdd~agent-handles-update-workload-requests~1]
-                self.runtime_manager
-                    .handle_update_workload(
-                        method_obj.added_workloads,
-                        method_obj.deleted_workloads,
-                        &self.workload_state_store,
-                    )
-                    .await;
+                let sequence_number = method_obj.sequence_number;
+                // [impl->swdd~agent-skips-redelivered-update-workload~1]
+                let is_redelivered = !method_obj.initial
+                    && sequence_number != 0
+                    && sequence_number <= self.last_update_workload_sequence_number;
+                if is_redelivered {
+                    log::debug!(
+                        "Ignoring re-delivered UpdateWorkload with sequence number '{}'.",
+                        sequence_number
+                    );
+                } else {
+                    let mut deleted_workloads = method_obj.deleted_workloads;
+                    if method_obj.initial {
+                        // [impl->swdd~agent-reports-subsystem-health~1]
+                        publish_subsystem_health(SERVER_CONNECTION_SUBSYSTEM, true);
+                        // [impl->swdd~agent-stops-fallback-workloads-after-reconnect~1]
+                        self.leave_degraded_mode().await;
+                        // the server dropped the reported queue when the agent disconnected
+                        self.reported_pending_operations.clear();
+                        // a new connection starts without a subscription
+                        self.reported_workload_state_subscription = None;
+
+                        // [impl->swdd~agent-deletes-workloads-missing-in-initial-list-after-reconnect~1]
+                        deleted_workloads
+                            .extend(self.runtime_manager.get_workloads_missing_in_initial_list(
+                                &method_obj.added_workloads,
+                            ));
+                    }
+
+                    // the deadline counts from the receipt of the update
+                    let deadline = method_obj
+                        .deadline_ms
+                        .map(|deadline_ms| Instant::now() + Duration::from_millis(deadline_ms));
+                    let added_workload_names: Vec<String> = method_obj
+                        .added_workloads
+                        .iter()
+                        .map(|workload_spec| workload_spec.instance_name.workload_name().to_owned())
+                        .collect();
+
+                    // [impl->swdd~agent-handles-update-workload-requests~1]
+                    self.runtime_manager
+                        .handle_update_workload(
+                            method_obj.added_workloads,
+                            deleted_workloads,
+                            &self.workload_state_store,
+                        )
+                        .await;
+
+                    // [impl->swdd~agent-reports-exceeded-update-deadline~1]
+                    if let Some(deadline) = deadline {
+                        self.runtime_manager
+                            .set_pending_operations_deadline(&added_workload_names, deadline);
+                    }
+                    // [impl->swdd~agent-times-out-pending-dependency-waits~1]
+                    self.pending_operations_deadline =
+                        self.runtime_manager.next_pending_operations_deadline();
+                    self.report_pending_workload_operations().await;
+                    self.last_update_workload_sequence_number = sequence_number;
+                }
+
+                // [impl->swdd~agent-acknowledges-update-workload~1]
+                if sequence_number != 0 {
+                    self.to_server
+                        .update_workload_ack(self.agent_name.clone(), sequence_number)
+                        .await
+                        .unwrap_or_illegal_state();
+                }
+                if !is_redelivered {
+                    self.report_workload_state_subscription().await;
+                }
                 Some(())
             }
             FromServer::UpdateWorkloadState(method_obj) => {
@@ -111,6 +226,12 @@ impl AgentManager {
                 let new_workload_states = method_obj.workload_states;
 
                 if !new_workload_states.is_empty() {
+                    let changed_workload_names: Vec<String> = new_workload_states
+                        .iter()
+                        .map(|workload_state| {
+                            workload_state.instance_name.workload_name().to_owned()
+                        })
+                        .collect();
                     // [impl->swdd~agent-manager-stores-all-workload-states~1]
                     for new_workload_state in new_workload_states {
                         log::debug!("The server reports workload state '{:?}' for the workload '{}' in the agent '{}'", new_workload_state.execution_state,
@@ -120,16 +241,42 @@ impl AgentManager {
                     }
                     // [impl->swdd~agent-handles-update-workload-state-requests~1]
                     self.runtime_manager
-                        .update_workloads_on_fulfilled_dependencies(&self.workload_state_store)
+                        .update_workloads_on_fulfilled_dependencies(
+                            &changed_workload_names,
+                            &self.workload_state_store,
+                        )
                         .await;
+                    self.pending_operations_deadline =
+                        self.runtime_manager.next_pending_operations_deadline();
+                    self.report_pending_workload_operations().await;
                 }
 
                 Some(())
             }
+            FromServer::UpdateConnectedAgents(method_obj) => {
+                log::debug!(
+                    "Agent '{}' received UpdateConnectedAgents: {:?}",
+                    self.agent_name,
+                    method_obj
+                );
+
+                // [impl->swdd~agent-evaluates-agent-dependencies~1]
+                self.workload_state_store
+                    .update_connected_agents(method_obj.connected_agents);
+                self.runtime_manager
+                    .update_workloads_on_connected_agents(&self.workload_state_store)
+                    .await;
+                self.pending_operations_deadline =
+                    self.runtime_manager.next_pending_operations_deadline();
+                self.report_pending_workload_operations().await;
+
+                Some(())
+            }
             FromServer::Response(method_obj) => {
                 log::debug!(
-                    "Agent '{}' received Response: {:?}",
+                    "Agent '{}' received Response with trace id '{}': {:?}",
                     self.agent_name,
+                    method_obj.trace_id,
                     method_obj
                 );
 
@@ -142,6 +289,44 @@ impl AgentManager {
                 log::debug!("Agent '{}' received Stop from server", self.agent_name);
                 None
             }
+            FromServer::ServerGone(_method_obj) => {
+                // [impl->swdd~agent-reports-subsystem-health~1]
+                publish_subsystem_health(SERVER_CONNECTION_SUBSYSTEM, false);
+                // [impl->swdd~agent-enters-degraded-mode-after-disconnect-threshold~1]
+                if !self.degraded_mode && self.degraded_mode_deadline.is_none() {
+                    log::info!(
+                        "Agent '{}' lost the connection to the server. Entering the degraded mode in {:?}.",
+                        self.agent_name,
+                        self.disconnect_threshold
+                    );
+                    self.degraded_mode_deadline = Some(Instant::now() + self.disconnect_threshold);
+                }
+                Some(())
+            }
+        }
+    }
+
+    async fn enter_degraded_mode(&mut self) {
+        log::warn!(
+            "Agent '{}' is disconnected from the server for longer than {:?}. Entering the degraded mode.",
+            self.agent_name,
+            self.disconnect_threshold
+        );
+        self.degraded_mode_deadline = None;
+        self.degraded_mode = true;
+        // [impl->swdd~agent-applies-disconnect-policies-in-degraded-mode~1]
+        self.runtime_manager.enter_degraded_mode().await;
+    }
+
+    async fn leave_degraded_mode(&mut self) {
+        self.degraded_mode_deadline = None;
+        if self.degraded_mode {
+            log::info!(
+                "Agent '{}' reconnected to the server. Leaving the degraded mode.",
+                self.agent_name
+            );
+            self.degraded_mode = false;
+            self.runtime_manager.leave_degraded_mode().await;
         }
     }
 
@@ -171,14 +356,55 @@ impl AgentManager {
         // notify the runtime manager s.t. dependencies and restarts can be handled
         // [impl->swdd~agent-handles-update-workload-state-requests~1]
         self.runtime_manager
-            .update_workloads_on_fulfilled_dependencies(&self.workload_state_store)
+            .update_workloads_on_fulfilled_dependencies(
+                &[new_workload_state.instance_name.workload_name().to_owned()],
+                &self.workload_state_store,
+            )
             .await;
+        // [impl->swdd~agent-times-out-pending-dependency-waits~1]
+        self.pending_operations_deadline = self.runtime_manager.next_pending_operations_deadline();
+
+        // the reporting time is informative only, timeouts of the agent use the monotonic clock
+        // [impl->swdd~agent-stamps-reported-workload-states~1]
+        new_workload_state.agent_timestamp = Some(now_ms());
 
         // [impl->swdd~agent-sends-workload-states-of-its-workloads-to-server~2]
         self.to_server
             .update_workload_state(vec![new_workload_state])
             .await
             .unwrap_or_illegal_state();
+
+        self.report_pending_workload_operations().await;
+    }
+
+    // [impl->swdd~agent-reports-scheduler-queue-to-server~1]
+    async fn report_pending_workload_operations(&mut self) {
+        let pending_operations = self
+            .runtime_manager
+            .pending_workload_operations(&self.workload_state_store);
+        if pending_operations != self.reported_pending_operations {
+            self.to_server
+                .update_scheduler_queue(self.agent_name.clone(), pending_operations.clone())
+                .await
+                .unwrap_or_illegal_state();
+            self.reported_pending_operations = pending_operations;
+        }
+    }
+
+    // [impl->swdd~agent-subscribes-to-workload-states-of-dependencies~1]
+    async fn report_workload_state_subscription(&mut self) {
+        let workload_names = self.runtime_manager.workload_state_subscription();
+        if self.reported_workload_state_subscription.as_ref() != Some(&workload_names) {
+            log::debug!(
+                "Subscribing to the workload states of '{:?}'.",
+                workload_names
+            );
+            self.to_server
+                .subscribe_workload_states(workload_names.clone())
+                .await
+                .unwrap_or_illegal_state();
+            self.reported_workload_state_subscription = Some(workload_names);
+        }
     }
 }
 
@@ -199,7 +425,10 @@ mod tests {
         WorkloadStateSenderInterface,
     };
     use common::{
-        commands::{Response, ResponseContent, UpdateWorkloadState},
+        commands::{
+            PendingOperation, Response, ResponseContent, SubscribeWorkloadStates,
+            UpdateSchedulerQueue, UpdateWorkloadAck, UpdateWorkloadState,
+        },
         from_server_interface::FromServerInterface,
         objects::{generate_test_workload_spec_with_param, CompleteState, ExecutionState},
         to_server_interface::ToServer,
@@ -212,11 +441,13 @@ mod tests {
     const WORKLOAD_1_NAME: &str = "workload1";
     const WORKLOAD_2_NAME: &str = "workload2";
     const REQUEST_ID: &str = "request_id";
+    const TRACE_ID: &str = "trace_id";
     const RUNTIME_NAME: &str = "runtime_name";
 
     // [utest->swdd~agent-manager-listens-requests-from-server~1]
     // [utest->swdd~agent-uses-async-channels~1]
     // [utest->swdd~agent-handles-update-workload-requests~1]
+    // [utest->swdd~agent-acknowledges-update-workload~1]
     #[tokio::test]
     async fn utest_agent_manager_update_workload() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
@@ -227,13 +458,22 @@ mod tests {
         mock_parameter_storage_new_returns(mock_wl_state_store_context);
 
         let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
-        let (to_server, _) = channel(BUFFER_SIZE);
+        let (to_server, mut to_server_receiver) = channel(BUFFER_SIZE);
         let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
         let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_next_pending_operations_deadline()
+            .return_const(None);
+        mock_runtime_manager
+            .expect_pending_workload_operations()
+            .return_const(Vec::new());
         mock_runtime_manager
             .expect_handle_update_workload()
             .once()
             .return_const(());
+        mock_runtime_manager
+            .expect_workload_state_subscription()
+            .return_const(Vec::new());
 
         let mut agent_manager = AgentManager::new(
             AGENT_NAME.to_string(),
@@ -261,6 +501,9 @@ mod tests {
             .update_workload(
                 vec![workload_spec_1.clone(), workload_spec_2.clone()],
                 vec![],
+                1,
+                false,
+                None,
             )
             .await;
         assert!(update_workload_result.is_ok());
@@ -268,6 +511,399 @@ mod tests {
         // Terminate the infinite receiver loop
         to_manager.stop().await.unwrap();
         assert!(join!(handle).0.is_ok());
+
+        assert_eq!(
+            to_server_receiver.try_recv(),
+            Ok(ToServer::UpdateWorkloadAck(UpdateWorkloadAck {
+                agent_name: AGENT_NAME.to_string(),
+                sequence_number: 1,
+            }))
+        );
+    }
+
+    // [utest->swdd~agent-subscribes-to-workload-states-of-dependencies~1]
+    #[tokio::test]
+    async fn utest_agent_manager_subscribes_to_workload_states_on_change_and_reconnect() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store_context = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store_context);
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, mut to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_next_pending_operations_deadline()
+            .return_const(None);
+        mock_runtime_manager
+            .expect_pending_workload_operations()
+            .return_const(Vec::new());
+        mock_runtime_manager
+            .expect_get_workloads_missing_in_initial_list()
+            .return_const(Vec::new());
+        mock_runtime_manager
+            .expect_handle_update_workload()
+            .times(3)
+            .return_const(());
+        mock_runtime_manager
+            .expect_workload_state_subscription()
+            .times(3)
+            .return_const(vec![WORKLOAD_2_NAME.to_owned()]);
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start().await });
+
+        for initial in [true, false, true] {
+            assert!(to_manager
+                .update_workload(vec![], vec![], 0, initial, None)
+                .await
+                .is_ok());
+        }
+
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+
+        // the unchanged subscription is only sent again after a reconnect
+        for _ in 0..2 {
+            assert_eq!(
+                to_server_receiver.try_recv(),
+                Ok(ToServer::SubscribeWorkloadStates(SubscribeWorkloadStates {
+                    workload_names: vec![WORKLOAD_2_NAME.to_owned()],
+                }))
+            );
+        }
+        assert!(to_server_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~agent-reports-exceeded-update-deadline~1]
+    #[tokio::test]
+    async fn utest_agent_manager_update_workload_expires_pending_operations_at_deadline() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store_context = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store_context);
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_handle_update_workload()
+            .once()
+            .return_const(());
+        mock_runtime_manager
+            .expect_workload_state_subscription()
+            .return_const(Vec::new());
+        mock_runtime_manager
+            .expect_set_pending_operations_deadline()
+            .with(
+                function(|workload_names: &[String]| workload_names == [WORKLOAD_1_NAME]),
+                always(),
+            )
+            .once()
+            .return_const(());
+        let mut next_deadlines = vec![None, Some(Instant::now())];
+        mock_runtime_manager
+            .expect_next_pending_operations_deadline()
+            .times(2)
+            .returning(move || next_deadlines.pop().unwrap());
+        mock_runtime_manager
+            .expect_pending_workload_operations()
+            .return_const(Vec::new());
+        mock_runtime_manager
+            .expect_expire_pending_workload_operations()
+            .once()
+            .return_const(());
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+        );
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.into(),
+            WORKLOAD_1_NAME.into(),
+            RUNTIME_NAME.into(),
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start().await });
+
+        assert!(to_manager
+            .update_workload(vec![workload_spec], vec![], 1, false, Some(1))
+            .await
+            .is_ok());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+    }
+
+    // [utest->swdd~agent-skips-redelivered-update-workload~1]
+    // [utest->swdd~agent-acknowledges-update-workload~1]
+    #[tokio::test]
+    async fn utest_agent_manager_update_workload_skips_redelivered_message() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store_context = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store_context);
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, mut to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_next_pending_operations_deadline()
+            .return_const(None);
+        mock_runtime_manager
+            .expect_pending_workload_operations()
+            .return_const(Vec::new());
+        mock_runtime_manager
+            .expect_handle_update_workload()
+            .once()
+            .return_const(());
+        mock_runtime_manager
+            .expect_workload_state_subscription()
+            .return_const(Vec::new());
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+        );
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.into(),
+            WORKLOAD_1_NAME.into(),
+            RUNTIME_NAME.into(),
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start().await });
+
+        for _ in 0..2 {
+            assert!(to_manager
+                .update_workload(vec![workload_spec.clone()], vec![], 3, false, None)
+                .await
+                .is_ok());
+        }
+
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+
+        let ack = Ok(ToServer::UpdateWorkloadAck(UpdateWorkloadAck {
+            agent_name: AGENT_NAME.to_string(),
+            sequence_number: 3,
+        }));
+        assert_eq!(to_server_receiver.try_recv(), ack);
+        assert_eq!(
+            to_server_receiver.try_recv(),
+            Ok(ToServer::SubscribeWorkloadStates(SubscribeWorkloadStates {
+                workload_names: vec![],
+            }))
+        );
+        assert_eq!(to_server_receiver.try_recv(), ack);
+    }
+
+    // [utest->swdd~agent-deletes-workloads-missing-in-initial-list-after-reconnect~1]
+    #[tokio::test]
+    async fn utest_agent_manager_update_workload_initial_list_deletes_missing_workloads() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store_context = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store_context);
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.into(),
+            WORKLOAD_1_NAME.into(),
+            RUNTIME_NAME.into(),
+        );
+        let missing_workload = common::test_utils::generate_test_deleted_workload(
+            AGENT_NAME.into(),
+            WORKLOAD_2_NAME.into(),
+        );
+
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_next_pending_operations_deadline()
+            .return_const(None);
+        mock_runtime_manager
+            .expect_pending_workload_operations()
+            .return_const(Vec::new());
+        let expected_added_workloads = vec![workload_spec.clone()];
+        mock_runtime_manager
+            .expect_get_workloads_missing_in_initial_list()
+            .once()
+            .withf(move |added_workloads| added_workloads == expected_added_workloads)
+            .return_const(vec![missing_workload.clone()]);
+        mock_runtime_manager
+            .expect_handle_update_workload()
+            .once()
+            .withf(move |_, deleted_workloads, _| {
+                deleted_workloads.len() == 1 && deleted_workloads[0] == missing_workload
+            })
+            .return_const(());
+        mock_runtime_manager
+            .expect_workload_state_subscription()
+            .return_const(Vec::new());
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start().await });
+
+        assert!(to_manager
+            .update_workload(vec![workload_spec], vec![], 5, true, None)
+            .await
+            .is_ok());
+
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+    }
+
+    // [utest->swdd~agent-enters-degraded-mode-after-disconnect-threshold~1]
+    // [utest->swdd~agent-applies-disconnect-policies-in-degraded-mode~1]
+    // [utest->swdd~agent-reports-subsystem-health~1]
+    #[tokio::test]
+    async fn utest_agent_manager_enters_degraded_mode_after_disconnect_threshold() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store_context = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store_context);
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+        let (degraded_mode_sender, mut degraded_mode_receiver) = channel(BUFFER_SIZE);
+
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_enter_degraded_mode()
+            .once()
+            .returning(move || {
+                degraded_mode_sender.try_send(()).unwrap();
+            });
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+        );
+        agent_manager.set_disconnect_threshold(Duration::ZERO);
+
+        let handle = tokio::spawn(async move { agent_manager.start().await });
+
+        assert!(to_manager.server_gone().await.is_ok());
+        // a second connection loss does not restart the threshold
+        assert!(to_manager.server_gone().await.is_ok());
+        assert_eq!(degraded_mode_receiver.recv().await, Some(()));
+
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+        assert!(
+            !crate::agent_metrics::get_subsystem_health(SERVER_CONNECTION_SUBSYSTEM)
+                .unwrap()
+                .healthy
+        );
+    }
+
+    // [utest->swdd~agent-stops-fallback-workloads-after-reconnect~1]
+    // [utest->swdd~agent-reports-subsystem-health~1]
+    #[tokio::test]
+    async fn utest_agent_manager_leaves_degraded_mode_on_initial_update_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store_context = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store_context);
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+
+        let mut seq = mockall::Sequence::new();
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_next_pending_operations_deadline()
+            .return_const(None);
+        mock_runtime_manager
+            .expect_pending_workload_operations()
+            .return_const(Vec::new());
+        mock_runtime_manager
+            .expect_leave_degraded_mode()
+            .once()
+            .in_sequence(&mut seq)
+            .return_const(());
+        mock_runtime_manager
+            .expect_get_workloads_missing_in_initial_list()
+            .once()
+            .in_sequence(&mut seq)
+            .return_const(vec![]);
+        mock_runtime_manager
+            .expect_handle_update_workload()
+            .once()
+            .in_sequence(&mut seq)
+            .return_const(());
+        mock_runtime_manager
+            .expect_workload_state_subscription()
+            .return_const(Vec::new());
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+        );
+        agent_manager.degraded_mode = true;
+
+        let handle = tokio::spawn(async move { agent_manager.start().await });
+
+        assert!(to_manager
+            .update_workload(vec![], vec![], 7, true, None)
+            .await
+            .is_ok());
+
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+        assert!(
+            crate::agent_metrics::get_subsystem_health(SERVER_CONNECTION_SUBSYSTEM)
+                .unwrap()
+                .healthy
+        );
     }
 
     // [utest->swdd~agent-manager-listens-requests-from-server~1]
@@ -291,6 +927,12 @@ mod tests {
         );
 
         let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_next_pending_operations_deadline()
+            .return_const(None);
+        mock_runtime_manager
+            .expect_pending_workload_operations()
+            .return_const(Vec::new());
         mock_runtime_manager.expect_handle_update_workload().never();
         mock_runtime_manager
             .expect_update_workloads_on_fulfilled_dependencies()
@@ -321,6 +963,124 @@ mod tests {
         assert!(join!(handle).0.is_ok());
     }
 
+    // [utest->swdd~agent-evaluates-agent-dependencies~1]
+    #[tokio::test]
+    async fn utest_agent_manager_update_connected_agents() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_next_pending_operations_deadline()
+            .return_const(None);
+        mock_runtime_manager
+            .expect_pending_workload_operations()
+            .return_const(Vec::new());
+        mock_runtime_manager
+            .expect_update_workloads_on_connected_agents()
+            .once()
+            .withf(|workload_state_store| workload_state_store.is_agent_connected("agent_B"))
+            .return_const(());
+
+        mock_parameter_storage_new_returns(MockWorkloadStateStore::default());
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start().await });
+
+        assert!(to_manager
+            .update_connected_agents(vec!["agent_B".to_string()])
+            .await
+            .is_ok());
+
+        // Terminate the infinite receiver loop
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+    }
+
+    // [utest->swdd~agent-reports-scheduler-queue-to-server~1]
+    #[tokio::test]
+    async fn utest_agent_manager_reports_changed_scheduler_queue() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, mut to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+
+        let workload_state = common::objects::generate_test_workload_state_with_agent(
+            WORKLOAD_1_NAME,
+            AGENT_NAME,
+            ExecutionState::running(),
+        );
+        let pending_operations = vec![PendingWorkloadOperation {
+            workload_name: WORKLOAD_2_NAME.to_string(),
+            agent: AGENT_NAME.to_string(),
+            operation: PendingOperation::Create,
+            unfulfilled_dependencies: vec![],
+        }];
+
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_next_pending_operations_deadline()
+            .return_const(None);
+        mock_runtime_manager
+            .expect_pending_workload_operations()
+            .times(2)
+            .return_const(pending_operations.clone());
+        mock_runtime_manager
+            .expect_update_workloads_on_fulfilled_dependencies()
+            .times(2)
+            .return_const(());
+
+        let mut mock_wl_state_store = MockWorkloadStateStore::default();
+        mock_wl_state_store
+            .expected_update_workload_state_parameters
+            .extend([workload_state.clone(), workload_state.clone()]);
+        mock_parameter_storage_new_returns(mock_wl_state_store);
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start().await });
+
+        for _ in 0..2 {
+            assert!(to_manager
+                .update_workload_state(vec![workload_state.clone()])
+                .await
+                .is_ok());
+        }
+
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+
+        assert_eq!(
+            to_server_receiver.try_recv(),
+            Ok(ToServer::UpdateSchedulerQueue(UpdateSchedulerQueue {
+                agent_name: AGENT_NAME.to_string(),
+                pending_operations,
+            }))
+        );
+        assert!(to_server_receiver.try_recv().is_err());
+    }
+
     // [utest->swdd~agent-manager-listens-requests-from-server~1]
     // [utest->swdd~agent-uses-async-channels~1]
     #[tokio::test]
@@ -379,6 +1139,7 @@ mod tests {
 
         let response = Response {
             request_id: request_id.clone(),
+            trace_id: TRACE_ID.to_string(),
             response_content: ResponseContent::CompleteState(Box::new(complete_state.clone())),
         };
 
@@ -399,7 +1160,9 @@ mod tests {
 
         let handle = tokio::spawn(async move { agent_manager.start().await });
 
-        let complete_state_result = to_manager.complete_state(request_id, complete_state).await;
+        let complete_state_result = to_manager
+            .complete_state(request_id, TRACE_ID.to_string(), complete_state)
+            .await;
         assert!(complete_state_result.is_ok());
 
         // Terminate the infinite receiver loop
@@ -412,6 +1175,7 @@ mod tests {
     // [utest->swdd~agent-sends-workload-states-of-its-workloads-to-server~2]
     // [utest->swdd~agent-handles-update-workload-state-requests~1]
     // [utest->swdd~agent-manager-hysteresis_on-workload-states-of-its-workloads~1]
+    // [utest->swdd~agent-stamps-reported-workload-states~1]
     #[tokio::test]
     async fn utest_agent_manager_receives_own_workload_states() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
@@ -448,6 +1212,12 @@ mod tests {
         mock_parameter_storage_new_returns(mock_wl_state_store);
 
         let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_next_pending_operations_deadline()
+            .return_const(None);
+        mock_runtime_manager
+            .expect_pending_workload_operations()
+            .return_const(Vec::new());
         mock_runtime_manager
             .expect_update_workloads_on_fulfilled_dependencies()
             .once()
@@ -473,14 +1243,19 @@ mod tests {
         let expected_workload_states = ToServer::UpdateWorkloadState(UpdateWorkloadState {
             workload_states: vec![wl_state_after_hysteresis],
         });
-        assert_eq!(
-            Ok(Some(expected_workload_states)),
-            tokio::time::timeout(
-                tokio::time::Duration::from_millis(200),
-                to_server_receiver.recv()
-            )
-            .await
-        );
+        let received = tokio::time::timeout(
+            tokio::time::Duration::from_millis(200),
+            to_server_receiver.recv(),
+        )
+        .await;
+        assert_eq!(Ok(Some(expected_workload_states)), received);
+
+        let Ok(Some(ToServer::UpdateWorkloadState(UpdateWorkloadState { workload_states }))) =
+            received
+        else {
+            unreachable!()
+        };
+        assert!(workload_states[0].agent_timestamp.is_some());
 
         // Terminate the infinite receiver loop
         to_manager.stop().await.unwrap();
@@ -0,0 +1,433 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use crate::workload_operation::WorkloadOperation;
+
+pub const SERVER_CONNECTION_SUBSYSTEM: &str = "server_connection";
+// The agent manager also runs the workload scheduler.
+pub const AGENT_MANAGER_SUBSYSTEM: &str = "agent_manager";
+pub const CONTROL_INTERFACE_SUBSYSTEM: &str = "control_interface";
+const RUNTIME_SUBSYSTEM_PREFIX: &str = "runtime:";
+
+// The upper bounds in seconds of the buckets of the pending duration histogram.
+const PENDING_DURATION_BUCKETS: [f64; 5] = [0.1, 1.0, 10.0, 60.0, 600.0];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Histogram {
+    // The counts per bucket are not cumulative,
+    // the last one counts the observations above all bounds.
+    bucket_counts: [u64; PENDING_DURATION_BUCKETS.len() + 1],
+    sum_secs: f64,
+    count: u64,
+}
+
+impl Histogram {
+    const fn new() -> Self {
+        Histogram {
+            bucket_counts: [0; PENDING_DURATION_BUCKETS.len() + 1],
+            sum_secs: 0.0,
+            count: 0,
+        }
+    }
+
+    pub fn observe(&mut self, duration: Duration) {
+        let secs = duration.as_secs_f64();
+        let bucket = PENDING_DURATION_BUCKETS
+            .iter()
+            .position(|upper_bound| secs <= *upper_bound)
+            .unwrap_or(PENDING_DURATION_BUCKETS.len());
+        self.bucket_counts[bucket] += 1;
+        self.sum_secs += secs;
+        self.count += 1;
+    }
+
+    #[cfg(test)]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    fn render(&self, name: &str, output: &mut String) {
+        let mut cumulative_count = 0;
+        for (upper_bound, bucket_count) in PENDING_DURATION_BUCKETS.iter().zip(self.bucket_counts) {
+            cumulative_count += bucket_count;
+            let _ = writeln!(
+                output,
+                "{name}_bucket{{le=\"{upper_bound}\"}} {cumulative_count}"
+            );
+        }
+        let _ = writeln!(output, "{name}_bucket{{le=\"+Inf\"}} {}", self.count);
+        let _ = writeln!(output, "{name}_sum {}", self.sum_secs);
+        let _ = writeln!(output, "{name}_count {}", self.count);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReleasedOperations {
+    pub create: u64,
+    pub update: u64,
+    pub update_delete_only: u64,
+    pub delete: u64,
+}
+
+impl ReleasedOperations {
+    const fn new() -> Self {
+        ReleasedOperations {
+            create: 0,
+            update: 0,
+            update_delete_only: 0,
+            delete: 0,
+        }
+    }
+
+    fn count(&mut self, workload_operation: &WorkloadOperation) {
+        match workload_operation {
+            WorkloadOperation::Create(_) => self.create += 1,
+            WorkloadOperation::Update(_, _) => self.update += 1,
+            WorkloadOperation::UpdateDeleteOnly(_) => self.update_delete_only += 1,
+            WorkloadOperation::Delete(_) => self.delete += 1,
+        }
+    }
+}
+
+// [impl->swdd~agent-collects-scheduler-metrics~1]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SchedulerMetrics {
+    pub queue_size: usize,
+    pub max_queue_size: usize,
+    pub pending_duration: Histogram,
+    pub released_operations: ReleasedOperations,
+}
+
+impl Default for SchedulerMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SchedulerMetrics {
+    pub const fn new() -> Self {
+        SchedulerMetrics {
+            queue_size: 0,
+            max_queue_size: 0,
+            pending_duration: Histogram::new(),
+            released_operations: ReleasedOperations::new(),
+        }
+    }
+
+    pub fn set_queue_size(&mut self, queue_size: usize) {
+        self.queue_size = queue_size;
+        self.max_queue_size = self.max_queue_size.max(queue_size);
+    }
+
+    // The pending duration is only known for operations released from the queue.
+    pub fn record_release(
+        &mut self,
+        workload_operation: &WorkloadOperation,
+        pending_duration: Option<Duration>,
+    ) {
+        self.released_operations.count(workload_operation);
+        if let Some(pending_duration) = pending_duration {
+            self.pending_duration.observe(pending_duration);
+        }
+    }
+
+    fn render(&self, output: &mut String) {
+        output.push_str(
+            "# HELP ankaios_scheduler_queue_size The number of pending workload operations.\n\
+             # TYPE ankaios_scheduler_queue_size gauge\n",
+        );
+        let _ = writeln!(output, "ankaios_scheduler_queue_size {}", self.queue_size);
+        output.push_str(
+            "# HELP ankaios_scheduler_max_queue_size The highest number of pending workload operations since the start.\n\
+             # TYPE ankaios_scheduler_max_queue_size gauge\n",
+        );
+        let _ = writeln!(
+            output,
+            "ankaios_scheduler_max_queue_size {}",
+            self.max_queue_size
+        );
+        output.push_str(
+            "# HELP ankaios_scheduler_pending_duration_seconds The time workload operations waited for their dependencies.\n\
+             # TYPE ankaios_scheduler_pending_duration_seconds histogram\n",
+        );
+        self.pending_duration
+            .render("ankaios_scheduler_pending_duration_seconds", output);
+        output.push_str(
+            "# HELP ankaios_scheduler_released_operations_total The workload operations released from the queue.\n\
+             # TYPE ankaios_scheduler_released_operations_total counter\n",
+        );
+        for (operation, count) in [
+            ("create", self.released_operations.create),
+            ("update", self.released_operations.update),
+            (
+                "update_delete_only",
+                self.released_operations.update_delete_only,
+            ),
+            ("delete", self.released_operations.delete),
+        ] {
+            let _ = writeln!(
+                output,
+                "ankaios_scheduler_released_operations_total{{operation=\"{operation}\"}} {count}"
+            );
+        }
+    }
+}
+
+// [impl->swdd~agent-reports-subsystem-health~1]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubsystemHealth {
+    pub healthy: bool,
+    pub restarts: u64,
+}
+
+fn render_subsystem_health(
+    subsystem_health: &BTreeMap<String, SubsystemHealth>,
+    output: &mut String,
+) {
+    output.push_str(
+        "# HELP ankaios_agent_subsystem_healthy Whether the subsystem of the agent is healthy.\n\
+         # TYPE ankaios_agent_subsystem_healthy gauge\n",
+    );
+    for (subsystem, health) in subsystem_health {
+        let _ = writeln!(
+            output,
+            "ankaios_agent_subsystem_healthy{{subsystem=\"{subsystem}\"}} {}",
+            u8::from(health.healthy)
+        );
+    }
+    output.push_str(
+        "# HELP ankaios_agent_subsystem_restarts_total The restarts of crashed tasks of the subsystem.\n\
+         # TYPE ankaios_agent_subsystem_restarts_total counter\n",
+    );
+    for (subsystem, health) in subsystem_health {
+        let _ = writeln!(
+            output,
+            "ankaios_agent_subsystem_restarts_total{{subsystem=\"{subsystem}\"}} {}",
+            health.restarts
+        );
+    }
+}
+
+// The subsystems publish snapshots of their metrics, which are exported independent of them.
+static SCHEDULER_METRICS: Mutex<SchedulerMetrics> = Mutex::new(SchedulerMetrics::new());
+static SUBSYSTEM_HEALTH: Mutex<BTreeMap<String, SubsystemHealth>> = Mutex::new(BTreeMap::new());
+
+pub fn runtime_subsystem(runtime_name: &str) -> String {
+    format!("{RUNTIME_SUBSYSTEM_PREFIX}{runtime_name}")
+}
+
+// [impl->swdd~agent-reports-subsystem-health~1]
+pub fn publish_subsystem_health(subsystem: &str, healthy: bool) {
+    if let Ok(mut subsystem_health) = SUBSYSTEM_HEALTH.lock() {
+        let health = subsystem_health.entry(subsystem.to_owned()).or_default();
+        if health.healthy && !healthy {
+            log::warn!("The subsystem '{}' of the agent is unhealthy.", subsystem);
+        }
+        health.healthy = healthy;
+    }
+}
+
+// [impl->swdd~agent-reports-subsystem-health~1]
+pub fn count_subsystem_restart(subsystem: &str) {
+    if let Ok(mut subsystem_health) = SUBSYSTEM_HEALTH.lock() {
+        subsystem_health
+            .entry(subsystem.to_owned())
+            .or_insert(SubsystemHealth {
+                healthy: true,
+                restarts: 0,
+            })
+            .restarts += 1;
+    }
+}
+
+#[cfg(test)]
+pub fn get_subsystem_health(subsystem: &str) -> Option<SubsystemHealth> {
+    SUBSYSTEM_HEALTH
+        .lock()
+        .ok()
+        .and_then(|subsystem_health| subsystem_health.get(subsystem).copied())
+}
+
+// [impl->swdd~agent-collects-scheduler-metrics~1]
+pub fn publish_scheduler_metrics(scheduler_metrics: &SchedulerMetrics) {
+    if let Ok(mut published) = SCHEDULER_METRICS.lock() {
+        *published = *scheduler_metrics;
+    }
+}
+
+/// Renders the published metrics in the Prometheus text exposition format.
+pub fn render_metrics() -> String {
+    let scheduler_metrics = SCHEDULER_METRICS
+        .lock()
+        .map(|published| *published)
+        .unwrap_or_default();
+    let subsystem_health = SUBSYSTEM_HEALTH
+        .lock()
+        .map(|published| published.clone())
+        .unwrap_or_default();
+    let mut output = String::new();
+    scheduler_metrics.render(&mut output);
+    render_subsystem_health(&subsystem_health, &mut output);
+    output
+}
+
+// The file is replaced atomically, thus a collector never reads a partially written file.
+pub fn write_metrics_file(path: &Path) -> std::io::Result<()> {
+    let mut temporary_path = path.as_os_str().to_owned();
+    temporary_path.push(".tmp");
+    std::fs::write(&temporary_path, render_metrics())?;
+    std::fs::rename(&temporary_path, path)
+}
+
+// [impl->swdd~agent-exports-metrics-to-file~1]
+/// Writes the published metrics to the given file in the given interval.
+pub async fn export_periodically(path: PathBuf, interval: Duration) {
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        interval.tick().await;
+        if let Err(err) = write_metrics_file(&path) {
+            log::warn!(
+                "Could not export the agent metrics to '{}': {}",
+                path.display(),
+                err
+            );
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common::test_utils::generate_test_deleted_workload;
+
+    use super::{render_subsystem_health, Histogram, SchedulerMetrics, SubsystemHealth};
+    use crate::workload_operation::WorkloadOperation;
+
+    // [utest->swdd~agent-collects-scheduler-metrics~1]
+    #[test]
+    fn utest_histogram_counts_observations_in_first_matching_bucket() {
+        let mut histogram = Histogram::new();
+        histogram.observe(Duration::from_millis(500));
+        histogram.observe(Duration::from_secs(1));
+        histogram.observe(Duration::from_secs(3600));
+
+        assert_eq!(histogram.bucket_counts, [0, 2, 0, 0, 0, 1]);
+        assert_eq!(histogram.count, 3);
+        assert_eq!(histogram.sum_secs, 3601.5);
+    }
+
+    // [utest->swdd~agent-collects-scheduler-metrics~1]
+    #[test]
+    fn utest_scheduler_metrics_keep_max_queue_size_and_count_releases() {
+        let mut scheduler_metrics = SchedulerMetrics::new();
+        scheduler_metrics.set_queue_size(3);
+        scheduler_metrics.set_queue_size(1);
+
+        let deleted_workload =
+            generate_test_deleted_workload("agent_A".to_owned(), "workload_1".to_owned());
+        scheduler_metrics.record_release(
+            &WorkloadOperation::UpdateDeleteOnly(deleted_workload.clone()),
+            None,
+        );
+        scheduler_metrics.record_release(
+            &WorkloadOperation::Delete(deleted_workload),
+            Some(Duration::from_secs(5)),
+        );
+
+        assert_eq!(scheduler_metrics.queue_size, 1);
+        assert_eq!(scheduler_metrics.max_queue_size, 3);
+        assert_eq!(scheduler_metrics.released_operations.update_delete_only, 1);
+        assert_eq!(scheduler_metrics.released_operations.delete, 1);
+        assert_eq!(scheduler_metrics.released_operations.create, 0);
+        assert_eq!(scheduler_metrics.pending_duration.count, 1);
+    }
+
+    // [utest->swdd~agent-exports-metrics-to-file~1]
+    #[test]
+    fn utest_scheduler_metrics_rendered_in_prometheus_text_format() {
+        let mut scheduler_metrics = SchedulerMetrics::new();
+        scheduler_metrics.set_queue_size(2);
+        scheduler_metrics
+            .pending_duration
+            .observe(Duration::from_secs(5));
+
+        let mut output = String::new();
+        scheduler_metrics.render(&mut output);
+
+        assert!(output.contains("# TYPE ankaios_scheduler_queue_size gauge\n"));
+        assert!(output.contains("\nankaios_scheduler_queue_size 2\n"));
+        assert!(output.contains("\nankaios_scheduler_max_queue_size 2\n"));
+        assert!(output.contains("ankaios_scheduler_pending_duration_seconds_bucket{le=\"1\"} 0\n"));
+        assert!(output.contains("ankaios_scheduler_pending_duration_seconds_bucket{le=\"10\"} 1\n"));
+        assert!(
+            output.contains("ankaios_scheduler_pending_duration_seconds_bucket{le=\"+Inf\"} 1\n")
+        );
+        assert!(output.contains("ankaios_scheduler_pending_duration_seconds_sum 5\n"));
+        assert!(output
+            .contains("ankaios_scheduler_released_operations_total{operation=\"create\"} 0\n"));
+    }
+
+    // [utest->swdd~agent-reports-subsystem-health~1]
+    #[test]
+    fn utest_subsystem_health_rendered_in_prometheus_text_format() {
+        let subsystem_health = [
+            (
+                "runtime:podman".to_owned(),
+                SubsystemHealth {
+                    healthy: false,
+                    restarts: 0,
+                },
+            ),
+            (
+                "control_interface".to_owned(),
+                SubsystemHealth {
+                    healthy: true,
+                    restarts: 2,
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut output = String::new();
+        render_subsystem_health(&subsystem_health, &mut output);
+
+        assert!(output.contains("# TYPE ankaios_agent_subsystem_healthy gauge\n"));
+        assert!(output
+            .contains("\nankaios_agent_subsystem_healthy{subsystem=\"control_interface\"} 1\n"));
+        assert!(
+            output.contains("\nankaios_agent_subsystem_healthy{subsystem=\"runtime:podman\"} 0\n")
+        );
+        assert!(output.contains(
+            "\nankaios_agent_subsystem_restarts_total{subsystem=\"control_interface\"} 2\n"
+        ));
+    }
+}
@@ -14,6 +14,7 @@
 
 use std::path::Path;
 
+use crate::agent_manager::DEFAULT_DISCONNECT_THRESHOLD_SECS;
 #[cfg_attr(test, mockall_double::double)]
 use crate::control_interface::Directory;
 use crate::control_interface::FileSystemError;
@@ -24,6 +25,19 @@ use url::Url;
 const DEFAULT_RUN_FOLDER: &str = "/tmp/ankaios/";
 const RUNFOLDER_SUFFIX: &str = "_io";
 
+// [impl->swdd~agent-reports-attributes~1]
+fn parse_attribute(attribute: &str) -> Result<(String, String), String> {
+    match attribute.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_owned(), value.trim().to_owned()))
+        }
+        _ => Err(format!(
+            "Invalid attribute '{}', expected the format 'key=value'",
+            attribute
+        )),
+    }
+}
+
 #[derive(Parser, Debug)]
 #[clap( author="The Ankaios team",
         version=env!("CARGO_PKG_VERSION"),
@@ -37,9 +51,49 @@ pub struct Arguments {
     /// The server url.
     pub server_url: Url,
 
+    /// The url of a fallback server the agent fails over to if the previous server is not reachable. Can be given multiple times, the fallback servers are tried in the given order.
+    #[clap(long = "fallback-server-url")]
+    pub fallback_server_urls: Vec<Url>,
+
     /// An existing path where to manage the fifo files.
     #[clap(short = 'r', long = "run-folder", default_value_t = DEFAULT_RUN_FOLDER.into())]
     pub run_folder: String,
+
+    /// The rollout group of the agent. Used by the server to apply staged rollouts group by group.
+    #[clap(long = "rollout-group")]
+    pub rollout_group: Option<String>,
+
+    /// An attribute of the agent in the format 'key=value', e.g. 'camera=true'. Used by the server to evaluate the enabledIf expressions of workloads. Can be given multiple times.
+    #[clap(long = "attribute", value_parser = parse_attribute)]
+    pub attributes: Vec<(String, String)>,
+
+    /// Validates the workloads assigned by the server on this target, prints a report and exits without creating any workload.
+    #[clap(long = "dry-run")]
+    pub dry_run: bool,
+
+    /// The time in seconds the agent waits after losing the connection to the server before it applies the disconnect policies of its workloads.
+    #[clap(long = "disconnect-threshold", default_value_t = DEFAULT_DISCONNECT_THRESHOLD_SECS)]
+    pub disconnect_threshold: u64,
+
+    /// The path to the agent config file defining the local workloads the agent always runs independent of the server.
+    #[clap(short = 'c', long = "config")]
+    pub config: Option<String>,
+
+    /// The maximal length in bytes of the runtime config of a workload. Workloads exceeding it are rejected. Without this option the length is not limited.
+    #[clap(long = "max-runtime-config-length")]
+    pub max_runtime_config_length: Option<usize>,
+
+    /// The maximal nesting depth of the mappings and sequences of a runtime config. Workloads exceeding it are rejected. Without this option the depth is not limited.
+    #[clap(long = "max-runtime-config-depth")]
+    pub max_runtime_config_depth: Option<usize>,
+
+    /// A hook command the agent executes for its workloads, e.g. '/opt/hooks/mount-data.sh'. Hooks of workloads not given here are rejected. Can be given multiple times. Without this option no hooks are executed.
+    #[clap(long = "allowed-hook")]
+    pub allowed_hooks: Vec<String>,
+
+    /// The path of a file the agent regularly writes its metrics to in the Prometheus text format, e.g. for the textfile collector of a node exporter.
+    #[clap(long = "metrics-file")]
+    pub metrics_file: Option<String>,
 }
 
 impl Arguments {
@@ -54,6 +108,13 @@ impl Arguments {
 
         Directory::new(run_folder)
     }
+
+    // [impl->swdd~agent-fails-over-to-fallback-servers~1]
+    pub fn get_server_urls(&self) -> Vec<Url> {
+        std::iter::once(self.server_url.clone())
+            .chain(self.fallback_server_urls.iter().cloned())
+            .collect()
+    }
 }
 
 pub fn parse() -> Arguments {
@@ -82,7 +143,17 @@ mod tests {
         let args = Arguments {
             agent_name: "test_agent_name".to_owned(),
             server_url: DEFAULT_SERVER_ADDRESS.parse().unwrap(),
+            fallback_server_urls: vec![],
             run_folder: DEFAULT_RUN_FOLDER.to_owned(),
+            rollout_group: None,
+            attributes: vec![],
+            dry_run: false,
+            disconnect_threshold: DEFAULT_DISCONNECT_THRESHOLD_SECS,
+            config: None,
+            max_runtime_config_length: None,
+            max_runtime_config_depth: None,
+            allowed_hooks: vec![],
+            metrics_file: None,
         };
 
         let _directory_mock_context =
@@ -98,7 +169,17 @@ mod tests {
         let args = Arguments {
             agent_name: "test_agent_name".to_owned(),
             server_url: DEFAULT_SERVER_ADDRESS.parse().unwrap(),
+            fallback_server_urls: vec![],
             run_folder: "/tmp/x".to_owned(),
+            rollout_group: None,
+            attributes: vec![],
+            dry_run: false,
+            disconnect_threshold: DEFAULT_DISCONNECT_THRESHOLD_SECS,
+            config: None,
+            max_runtime_config_length: None,
+            max_runtime_config_depth: None,
+            allowed_hooks: vec![],
+            metrics_file: None,
         };
 
         let _directory_mock_context = generate_test_directory_mock("/tmp/x", "test_agent_name_io");
@@ -112,4 +193,60 @@ mod tests {
             ))
         );
     }
+
+    // [utest->swdd~agent-fails-over-to-fallback-servers~1]
+    #[test]
+    fn utest_arguments_get_server_urls_lists_fallback_servers_after_server() {
+        let args = Arguments::try_parse_from([
+            "ank-agent",
+            "--name",
+            "test_agent_name",
+            "--server-url",
+            "http://primary:25551",
+            "--fallback-server-url",
+            "http://fallback_1:25551",
+            "--fallback-server-url",
+            "http://fallback_2:25551",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            args.get_server_urls(),
+            vec![
+                Url::parse("http://primary:25551").unwrap(),
+                Url::parse("http://fallback_1:25551").unwrap(),
+                Url::parse("http://fallback_2:25551").unwrap(),
+            ]
+        );
+    }
+
+    // [utest->swdd~agent-fails-over-to-fallback-servers~1]
+    #[test]
+    fn utest_arguments_get_server_urls_without_fallback_servers() {
+        let args = Arguments::try_parse_from(["ank-agent", "--name", "test_agent_name"]).unwrap();
+
+        assert_eq!(
+            args.get_server_urls(),
+            vec![Url::parse(DEFAULT_SERVER_ADDRESS).unwrap()]
+        );
+    }
+
+    // [utest->swdd~agent-reports-attributes~1]
+    #[test]
+    fn utest_parse_attribute() {
+        assert_eq!(
+            parse_attribute("camera=true"),
+            Ok(("camera".to_owned(), "true".to_owned()))
+        );
+        assert_eq!(
+            parse_attribute("hw.variant = premium"),
+            Ok(("hw.variant".to_owned(), "premium".to_owned()))
+        );
+        assert_eq!(
+            parse_attribute("flag="),
+            Ok(("flag".to_owned(), "".to_owned()))
+        );
+        assert!(parse_attribute("camera").is_err());
+        assert!(parse_attribute("=true").is_err());
+    }
 }
@@ -16,7 +16,7 @@ use std::path::PathBuf;
 
 #[cfg_attr(test, mockall_double::double)]
 use super::FileSystem;
-use super::FileSystemError;
+use super::{remove_log_level_file, FileSystemError};
 
 #[derive(Debug, PartialEq)]
 pub struct Directory {
@@ -44,6 +44,8 @@ impl Directory {
 impl Drop for Directory {
     fn drop(&mut self) {
         log::debug!("Deleting directory '{:?}'", self.path);
+        // [impl->swdd~agent-renders-log-level-file~1]
+        remove_log_level_file(&self.path);
         if let Err(err) = self.filesystem.remove_dir(&self.path) {
             log::warn!("Could not delete {:?}: {err}", self.path);
         }
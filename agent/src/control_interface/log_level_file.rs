@@ -0,0 +1,73 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fs, io, path::Path};
+
+// The file is placed next to the control interface pipes and is hence visible to the workload
+// as '/run/ankaios/control_interface/log_level'.
+pub const LOG_LEVEL_FILE_NAME: &str = "log_level";
+
+// [impl->swdd~agent-renders-log-level-file~1]
+pub fn write_log_level_file(control_interface_path: &Path, level: &str) -> io::Result<()> {
+    // the file is replaced atomically such that a workload watching it never reads a partial level
+    let tmp_path = control_interface_path.join(format!(".{LOG_LEVEL_FILE_NAME}.tmp"));
+    fs::write(&tmp_path, format!("{level}\n"))?;
+    fs::rename(tmp_path, control_interface_path.join(LOG_LEVEL_FILE_NAME))
+}
+
+pub fn remove_log_level_file(control_interface_path: &Path) {
+    match fs::remove_file(control_interface_path.join(LOG_LEVEL_FILE_NAME)) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => {
+            log::warn!(
+                "Could not remove the log level file in '{:?}': '{}'",
+                control_interface_path,
+                err
+            )
+        }
+        _ => {}
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{remove_log_level_file, write_log_level_file, LOG_LEVEL_FILE_NAME};
+
+    // [utest->swdd~agent-renders-log-level-file~1]
+    #[test]
+    fn utest_write_log_level_file_replaces_level() {
+        let dir = tempfile::tempdir().unwrap();
+
+        write_log_level_file(dir.path(), "info").unwrap();
+        write_log_level_file(dir.path(), "debug").unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join(LOG_LEVEL_FILE_NAME)).unwrap(),
+            "debug\n"
+        );
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        remove_log_level_file(dir.path());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+}
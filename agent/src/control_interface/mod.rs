@@ -18,10 +18,13 @@ mod fifo;
 mod filesystem;
 mod from_server_channels;
 mod input_output;
+mod log_level_file;
 mod pipes_channel_context;
 mod pipes_channel_context_info;
 mod pipes_channel_task;
 mod reopen_file;
+mod request_metrics;
+mod stale_pipes_folders;
 
 pub use to_ankaios::ToAnkaios;
 pub use directory::*;
@@ -30,8 +33,10 @@ pub use filesystem::*;
 pub use from_server_channels::*;
 #[cfg(test)]
 pub use input_output::*;
+pub use log_level_file::*;
 pub use pipes_channel_context::*;
 pub use pipes_channel_context_info::*;
 #[cfg(test)]
 pub use pipes_channel_task::*;
 pub use reopen_file::*;
+pub use stale_pipes_folders::*;
@@ -21,17 +21,34 @@ use mockall::automock;
 use super::input_output::InputOutput;
 #[cfg_attr(test, mockall_double::double)]
 use super::pipes_channel_task::PipesChannelTask;
+use super::pipes_channel_task::{
+    PreShutdownReceiver, PreShutdownRequest, PreShutdownSender, INPUT_PIPE_BUFFER_SIZE,
+};
 #[cfg_attr(test, mockall_double::double)]
 use super::reopen_file::ReopenFile;
 #[cfg_attr(test, mockall_double::double)]
 use super::FromServerChannels;
-use common::{from_server_interface::FromServerSender, to_server_interface::ToServerSender};
+use crate::agent_metrics::{count_subsystem_restart, CONTROL_INTERFACE_SUBSYSTEM};
+use common::{
+    commands::{AgentEvent, EventKind},
+    from_server_interface::{FromServerReceiver, FromServerSender},
+    to_server_interface::{ToServer, ToServerSender},
+};
 use std::{
     fmt::{self, Display},
     path::{Path, PathBuf},
+    time::Duration,
 };
 
-use tokio::task::JoinHandle;
+use tokio::{
+    select,
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+
+// The number of times a crashed pipes channel task of a workload is restarted.
+const MAX_TASK_RESTARTS: u32 = 3;
+const TASK_RESTART_DELAY: Duration = Duration::from_millis(500);
 
 #[derive(Debug)]
 pub enum PipesChannelContextError {
@@ -52,6 +69,7 @@ impl Display for PipesChannelContextError {
 pub struct PipesChannelContext {
     pipes: InputOutput,
     input_pipe_sender: FromServerSender,
+    pre_shutdown_sender: PreShutdownSender,
     task_handle: JoinHandle<()>,
 }
 
@@ -61,26 +79,30 @@ impl PipesChannelContext {
         run_directory: &Path,
         execution_instance_name: &WorkloadInstanceName,
         output_pipe_channel: ToServerSender,
+        requests_per_minute: Option<u32>,
     ) -> Result<Self, PipesChannelContextError> {
         // [impl->swdd~agent-control-interface-pipes-path-naming~1]
         match InputOutput::new(execution_instance_name.pipes_folder_name(run_directory)) {
             Ok(pipes) => {
-                let input_stream = ReopenFile::open(pipes.get_output().get_path());
-                let output_stream = ReopenFile::create(pipes.get_input().get_path());
-                let request_id_prefix = [execution_instance_name.workload_name(), ""].join("@");
-                let input_pipe_channels = FromServerChannels::new(1024);
+                // [impl->swdd~agent-limits-buffered-control-interface-messages~1]
+                let input_pipe_channels = FromServerChannels::new(INPUT_PIPE_BUFFER_SIZE);
+                let input_pipe_sender = input_pipe_channels.get_sender();
+                let (pre_shutdown_sender, pre_shutdown_receiver) = mpsc::channel(1);
+                let supervisor = PipesChannelSupervisor {
+                    input_path: pipes.get_output().get_path().clone(),
+                    output_path: pipes.get_input().get_path().clone(),
+                    input_pipe_receiver: input_pipe_channels.move_receiver(),
+                    output_pipe_channel,
+                    workload_name: execution_instance_name.workload_name().to_owned(),
+                    pre_shutdown_receiver,
+                    requests_per_minute,
+                };
 
                 Ok(PipesChannelContext {
                     pipes,
-                    input_pipe_sender: input_pipe_channels.get_sender(),
-                    task_handle: PipesChannelTask::new(
-                        output_stream,
-                        input_stream,
-                        input_pipe_channels.move_receiver(),
-                        output_pipe_channel,
-                        request_id_prefix,
-                    )
-                    .run_task(),
+                    input_pipe_sender,
+                    pre_shutdown_sender,
+                    task_handle: tokio::spawn(supervisor.run()),
                 })
             }
             Err(e) => Err(PipesChannelContextError::CouldNotCreateFifo(e.to_string())),
@@ -96,6 +118,29 @@ impl PipesChannelContext {
         self.input_pipe_sender.clone()
     }
 
+    // Returns true if the workload acknowledged the notification within the timeout.
+    // [impl->swdd~agent-notifies-workload-before-shutdown~1]
+    pub async fn notify_pre_shutdown(&self, timeout_ms: u64) -> bool {
+        let (acknowledged_sender, acknowledged_receiver) = oneshot::channel();
+        let pre_shutdown_request = PreShutdownRequest {
+            timeout_ms,
+            acknowledged: acknowledged_sender,
+        };
+        if self
+            .pre_shutdown_sender
+            .send(pre_shutdown_request)
+            .await
+            .is_err()
+        {
+            return false;
+        }
+
+        matches!(
+            tokio::time::timeout(Duration::from_millis(timeout_ms), acknowledged_receiver).await,
+            Ok(Ok(()))
+        )
+    }
+
     pub fn abort_pipes_channel_task(&self) {
         self.task_handle.abort();
     }
@@ -107,6 +152,99 @@ impl Drop for PipesChannelContext {
     }
 }
 
+// Aborts the pipes channel task when the supervisor is aborted.
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+// [impl->swdd~agent-restarts-crashed-control-interface-task~1]
+// Keeps the channels to the pipes channel task of a workload, s.t. a crashed task can be
+// replaced by a new one without invalidating the senders handed out to the workload.
+struct PipesChannelSupervisor {
+    input_path: PathBuf,
+    output_path: PathBuf,
+    input_pipe_receiver: FromServerReceiver,
+    output_pipe_channel: ToServerSender,
+    workload_name: String,
+    pre_shutdown_receiver: PreShutdownReceiver,
+    requests_per_minute: Option<u32>,
+}
+
+impl PipesChannelSupervisor {
+    async fn run(mut self) {
+        let mut restarts = 0;
+        loop {
+            // the messages are relayed one by one to not buffer more messages for the workload
+            let (task_input_sender, task_input_receiver) = mpsc::channel(1);
+            let (task_pre_shutdown_sender, task_pre_shutdown_receiver) = mpsc::channel(1);
+            let mut task_handle = AbortOnDrop(
+                PipesChannelTask::new(
+                    ReopenFile::create(&self.output_path),
+                    ReopenFile::open(&self.input_path),
+                    task_input_receiver,
+                    self.output_pipe_channel.clone(),
+                    self.workload_name.clone(),
+                    task_pre_shutdown_receiver,
+                    self.requests_per_minute,
+                )
+                .run_task(),
+            );
+
+            let task_result = loop {
+                select! {
+                    Some(from_server) = self.input_pipe_receiver.recv() => {
+                        let _ = task_input_sender.send(from_server).await;
+                    }
+                    Some(pre_shutdown_request) = self.pre_shutdown_receiver.recv() => {
+                        let _ = task_pre_shutdown_sender.send(pre_shutdown_request).await;
+                    }
+                    task_result = &mut task_handle.0 => break task_result,
+                }
+            };
+
+            match task_result {
+                Err(error) if error.is_panic() && restarts < MAX_TASK_RESTARTS => {
+                    restarts += 1;
+                    // [impl->swdd~agent-reports-subsystem-health~1]
+                    count_subsystem_restart(CONTROL_INTERFACE_SUBSYSTEM);
+                    log::warn!(
+                        "The control interface task of workload '{}' crashed, restarting it ({}/{}).",
+                        self.workload_name,
+                        restarts,
+                        MAX_TASK_RESTARTS
+                    );
+                    tokio::time::sleep(TASK_RESTART_DELAY).await;
+                }
+                Err(error) if error.is_panic() => {
+                    log::error!(
+                        "The control interface task of workload '{}' crashed repeatedly, closing its control interface.",
+                        self.workload_name
+                    );
+                    let _ = self
+                        .output_pipe_channel
+                        .send(ToServer::AgentEvent(AgentEvent {
+                            // set by the server from the agent connection
+                            agent_name: String::new(),
+                            kind: EventKind::ControlInterfaceClosed,
+                            workload_name: self.workload_name.clone(),
+                            message: format!(
+                                "The control interface task crashed {} times",
+                                MAX_TASK_RESTARTS + 1
+                            ),
+                        }))
+                        .await;
+                    return;
+                }
+                _ => return,
+            }
+        }
+    }
+}
+
 //////////////////////////////////////////////////////////////////////////////
 //                 ########  #######    #########  #########                //
 //                    ##     ##        ##             ##                    //
@@ -119,14 +257,19 @@ impl Drop for PipesChannelContext {
 mod tests {
     use std::path::Path;
 
-    use common::from_server_interface::FromServer;
+    use common::{
+        commands::{AgentEvent, EventKind},
+        from_server_interface::FromServer,
+        to_server_interface::ToServer,
+    };
     use tokio::sync::mpsc;
 
     const CONFIG: &str = "config";
 
+    use super::{CONTROL_INTERFACE_SUBSYSTEM, MAX_TASK_RESTARTS};
     use crate::control_interface::{
         generate_test_input_output_mock, generate_test_pipes_channel_task_mock,
-        MockFromServerChannels, MockReopenFile, PipesChannelContext,
+        MockFromServerChannels, MockPipesChannelTask, MockReopenFile, PipesChannelContext,
     };
     use common::objects::WorkloadInstanceName;
 
@@ -167,6 +310,7 @@ mod tests {
                 .config(&String::from(CONFIG))
                 .build(),
             mpsc::channel(1).0,
+            None,
         )
         .unwrap();
 
@@ -216,29 +360,222 @@ mod tests {
                 .config(&String::from(CONFIG))
                 .build(),
             mpsc::channel(1).0,
+            None,
         )
         .unwrap();
 
         let _ = pipes_channel_context
             .get_input_pipe_sender()
             .send(FromServer::UpdateWorkload(
-                common::commands::UpdateWorkload {
-                    added_workloads: vec![],
-                    deleted_workloads: vec![],
-                },
+                common::commands::UpdateWorkload::default(),
             ))
             .await;
 
         assert_eq!(
             Some(FromServer::UpdateWorkload(
-                common::commands::UpdateWorkload {
-                    added_workloads: vec![],
-                    deleted_workloads: vec![],
-                }
+                common::commands::UpdateWorkload::default()
             )),
             receiver.recv().await
         );
 
         pipes_channel_context.abort_pipes_channel_task();
     }
+
+    // [utest->swdd~agent-notifies-workload-before-shutdown~1]
+    #[tokio::test]
+    async fn utest_notify_pre_shutdown_waits_for_acknowledgement_until_timeout() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let reopen_file_mock_open = MockReopenFile::open_context();
+        reopen_file_mock_open
+            .expect()
+            .returning(|_| MockReopenFile::default());
+        let reopen_file_mock_create = MockReopenFile::create_context();
+        reopen_file_mock_create
+            .expect()
+            .returning(|_| MockReopenFile::default());
+
+        let _input_output_mock = generate_test_input_output_mock();
+
+        let ex_com_ch_mock_context = MockFromServerChannels::new_context();
+        ex_com_ch_mock_context.expect().return_once(|_| {
+            let mut mock = MockFromServerChannels::default();
+            mock.expect_get_sender().return_const(mpsc::channel(1).0);
+            mock.expect_move_receiver()
+                .return_once(|| mpsc::channel(1).1);
+            mock
+        });
+
+        // acknowledges the first notification and ignores the second one
+        let pipes_channel_task_mock_context = MockPipesChannelTask::new_context();
+        pipes_channel_task_mock_context
+            .expect()
+            .return_once(|_, _, _, _, _, mut pre_shutdown_receiver, _| {
+                let mut pipes_channel_task_mock = MockPipesChannelTask::default();
+                pipes_channel_task_mock
+                    .expect_run_task()
+                    .return_once(|| {
+                        tokio::spawn(async move {
+                            let first_request = pre_shutdown_receiver.recv().await.unwrap();
+                            first_request.acknowledged.send(()).unwrap();
+                            let _second_request = pre_shutdown_receiver.recv().await;
+                            std::future::pending::<()>().await;
+                        })
+                    });
+                pipes_channel_task_mock
+            });
+
+        let pipes_channel_context = PipesChannelContext::new(
+            Path::new("api_pipes_location"),
+            &WorkloadInstanceName::builder()
+                .workload_name("workload_name_1")
+                .config(&String::from(CONFIG))
+                .build(),
+            mpsc::channel(1).0,
+            None,
+        )
+        .unwrap();
+
+        assert!(pipes_channel_context.notify_pre_shutdown(1000).await);
+        assert!(!pipes_channel_context.notify_pre_shutdown(10).await);
+
+        pipes_channel_context.abort_pipes_channel_task();
+    }
+
+    // [utest->swdd~agent-restarts-crashed-control-interface-task~1]
+    // [utest->swdd~agent-reports-subsystem-health~1]
+    #[tokio::test]
+    async fn utest_pipes_channel_context_restarts_crashed_task() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let reopen_file_mock_open = MockReopenFile::open_context();
+        reopen_file_mock_open
+            .expect()
+            .returning(|_| MockReopenFile::default());
+        let reopen_file_mock_create = MockReopenFile::create_context();
+        reopen_file_mock_create
+            .expect()
+            .returning(|_| MockReopenFile::default());
+
+        let _input_output_mock = generate_test_input_output_mock();
+
+        let ex_com_ch_mock_context = MockFromServerChannels::new_context();
+        ex_com_ch_mock_context.expect().return_once(|_| {
+            let mut mock = MockFromServerChannels::default();
+            mock.expect_get_sender().return_const(mpsc::channel(1).0);
+            mock.expect_move_receiver()
+                .return_once(|| mpsc::channel(1).1);
+            mock
+        });
+
+        // the first task crashes, the second one acknowledges the notification
+        let (created_sender, mut created_receiver) = mpsc::unbounded_channel();
+        let mut crashed = false;
+        let pipes_channel_task_mock_context = MockPipesChannelTask::new_context();
+        pipes_channel_task_mock_context.expect().times(2).returning(
+            move |_, _, _, _, _, mut pre_shutdown_receiver, _| {
+                let crash = !crashed;
+                crashed = true;
+                created_sender.send(()).unwrap();
+                let mut pipes_channel_task_mock = MockPipesChannelTask::default();
+                pipes_channel_task_mock
+                    .expect_run_task()
+                    .return_once(move || {
+                        tokio::spawn(async move {
+                            assert!(!crash, "crash of the pipes channel task");
+                            let request = pre_shutdown_receiver.recv().await.unwrap();
+                            request.acknowledged.send(()).unwrap();
+                            std::future::pending::<()>().await;
+                        })
+                    });
+                pipes_channel_task_mock
+            },
+        );
+
+        let pipes_channel_context = PipesChannelContext::new(
+            Path::new("api_pipes_location"),
+            &WorkloadInstanceName::builder()
+                .workload_name("workload_name_1")
+                .config(&String::from(CONFIG))
+                .build(),
+            mpsc::channel(1).0,
+            None,
+        )
+        .unwrap();
+
+        created_receiver.recv().await.unwrap();
+        created_receiver.recv().await.unwrap();
+        assert!(pipes_channel_context.notify_pre_shutdown(1000).await);
+        // the restarts of other tests are counted as well
+        assert!(
+            crate::agent_metrics::get_subsystem_health(CONTROL_INTERFACE_SUBSYSTEM)
+                .unwrap()
+                .restarts
+                >= 1
+        );
+
+        pipes_channel_context.abort_pipes_channel_task();
+    }
+
+    // [utest->swdd~agent-restarts-crashed-control-interface-task~1]
+    #[tokio::test]
+    async fn utest_pipes_channel_context_closes_control_interface_after_repeated_crashes() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let reopen_file_mock_open = MockReopenFile::open_context();
+        reopen_file_mock_open
+            .expect()
+            .returning(|_| MockReopenFile::default());
+        let reopen_file_mock_create = MockReopenFile::create_context();
+        reopen_file_mock_create
+            .expect()
+            .returning(|_| MockReopenFile::default());
+
+        let _input_output_mock = generate_test_input_output_mock();
+
+        let ex_com_ch_mock_context = MockFromServerChannels::new_context();
+        ex_com_ch_mock_context.expect().return_once(|_| {
+            let mut mock = MockFromServerChannels::default();
+            mock.expect_get_sender().return_const(mpsc::channel(1).0);
+            mock.expect_move_receiver()
+                .return_once(|| mpsc::channel(1).1);
+            mock
+        });
+
+        let pipes_channel_task_mock_context = MockPipesChannelTask::new_context();
+        pipes_channel_task_mock_context
+            .expect()
+            .times(MAX_TASK_RESTARTS as usize + 1)
+            .returning(|_, _, _, _, _, _, _| {
+                let mut pipes_channel_task_mock = MockPipesChannelTask::default();
+                pipes_channel_task_mock.expect_run_task().return_once(|| {
+                    tokio::spawn(async { panic!("crash of the pipes channel task") })
+                });
+                pipes_channel_task_mock
+            });
+
+        let (output_pipe_sender, mut output_pipe_receiver) = mpsc::channel(1);
+        let _pipes_channel_context = PipesChannelContext::new(
+            Path::new("api_pipes_location"),
+            &WorkloadInstanceName::builder()
+                .workload_name("workload_name_1")
+                .config(&String::from(CONFIG))
+                .build(),
+            output_pipe_sender,
+            None,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            output_pipe_receiver.recv().await,
+            Some(ToServer::AgentEvent(AgentEvent {
+                kind: EventKind::ControlInterfaceClosed,
+                ..
+            }))
+        ));
+    }
 }
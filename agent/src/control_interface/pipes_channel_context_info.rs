@@ -27,6 +27,7 @@ pub struct PipesChannelContextInfo {
     run_folder: PathBuf,
     workload_instance_name: WorkloadInstanceName,
     control_interface_to_server_sender: ToServerSender,
+    requests_per_minute: Option<u32>,
 }
 
 #[cfg_attr(test, automock)]
@@ -35,11 +36,13 @@ impl PipesChannelContextInfo {
         run_folder: &Path,
         control_interface_to_server_sender: ToServerSender,
         workload_instance_name: &WorkloadInstanceName,
+        requests_per_minute: Option<u32>,
     ) -> Self {
         Self {
             run_folder: run_folder.to_path_buf(),
             workload_instance_name: workload_instance_name.clone(),
             control_interface_to_server_sender,
+            requests_per_minute,
         }
     }
 
@@ -56,6 +59,7 @@ impl PipesChannelContextInfo {
             &self.run_folder,
             &self.workload_instance_name,
             self.control_interface_to_server_sender.clone(),
+            self.requests_per_minute,
         ) {
             Ok(res) => Some(res),
             _ => None,
@@ -91,6 +95,7 @@ mod tests {
             Path::new(PIPES_LOCATION),
             tokio::sync::mpsc::channel::<ToServer>(1).0,
             &workload_instance_name,
+            None,
         );
 
         assert_eq!(
@@ -112,6 +117,7 @@ mod tests {
             &WorkloadInstanceName::builder()
                 .workload_name(WORKLOAD_1_NAME)
                 .build(),
+            None,
         );
 
         assert_eq!(&path.to_path_buf(), new_context_info.get_run_folder());
@@ -127,6 +133,7 @@ mod tests {
             Path::new(PIPES_LOCATION),
             tokio::sync::mpsc::channel::<ToServer>(1).0,
             &workload_instance_name,
+            None,
         );
 
         assert_eq!(
@@ -135,6 +142,7 @@ mod tests {
         );
     }
 
+    // [utest->swdd~agent-limits-control-interface-requests-by-quota~1]
     #[tokio::test]
     async fn utest_create_control_interface_ok() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
@@ -147,13 +155,15 @@ mod tests {
             &WorkloadInstanceName::builder()
                 .workload_name(WORKLOAD_1_NAME)
                 .build(),
+            Some(60),
         );
 
         let pipes_channel_context_mock = MockPipesChannelContext::new_context();
         pipes_channel_context_mock
             .expect()
+            .withf(|_, _, _, requests_per_minute| *requests_per_minute == Some(60))
             .once()
-            .return_once(|_, _, _| Ok(MockPipesChannelContext::default()));
+            .return_once(|_, _, _, _| Ok(MockPipesChannelContext::default()));
 
         assert!(new_context_info.create_control_interface().is_some());
     }
@@ -170,13 +180,14 @@ mod tests {
             &WorkloadInstanceName::builder()
                 .workload_name(WORKLOAD_1_NAME)
                 .build(),
+            None,
         );
 
         let pipes_channel_context_mock = MockPipesChannelContext::new_context();
         pipes_channel_context_mock
             .expect()
             .once()
-            .return_once(|_, _, _| {
+            .return_once(|_, _, _, _| {
                 Err(PipesChannelContextError::CouldNotCreateFifo(String::from(
                     "error",
                 )))
@@ -14,17 +14,36 @@
 
 use crate::control_interface::ToAnkaios;
 
+use super::request_metrics::{RequestMetrics, RequestQuota};
+
 #[cfg_attr(test, mockall_double::double)]
 use super::ReopenFile;
+use std::{collections::HashSet, time::Duration};
+
 use api::control_api;
 use common::{
-    commands::Response,
+    commands::{
+        AgentEvent, CancelWatchRequest, Error, EventKind, Request, RequestContent, Response,
+        ResponseContent, UpdateControlInterfaceMetrics,
+    },
     from_server_interface::{FromServer, FromServerReceiver},
     to_server_interface::{ToServer, ToServerSender},
 };
 
 use prost::Message;
-use tokio::{io, select, task::JoinHandle};
+use tokio::{
+    io, select,
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+    time::Instant,
+};
+
+// The maximal number of messages buffered for a workload until it reads its input pipe.
+pub const INPUT_PIPE_BUFFER_SIZE: usize = 64;
+// The time a workload has to read a message written to its input pipe.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+// The interval in which changed control interface request metrics are reported to the server.
+const METRICS_REPORT_INTERVAL: Duration = Duration::from_secs(10);
 
 fn decode_to_server(protobuf_data: io::Result<Box<[u8]>>) -> io::Result<control_api::ToAnkaios> {
     Ok(control_api::ToAnkaios::decode(&mut Box::new(
@@ -32,32 +51,63 @@ fn decode_to_server(protobuf_data: io::Result<Box<[u8]>>) -> io::Result<control_
     ))?)
 }
 
+// A request to notify the workload about its upcoming removal.
+// The acknowledgement of the workload is signaled via the oneshot sender.
+#[derive(Debug)]
+pub struct PreShutdownRequest {
+    pub timeout_ms: u64,
+    pub acknowledged: oneshot::Sender<()>,
+}
+
+pub type PreShutdownSender = mpsc::Sender<PreShutdownRequest>;
+pub type PreShutdownReceiver = mpsc::Receiver<PreShutdownRequest>;
+
 pub struct PipesChannelTask {
     output_stream: ReopenFile,
     input_stream: ReopenFile,
     input_pipe_receiver: FromServerReceiver,
     output_pipe_channel: ToServerSender,
+    workload_name: String,
     request_id_prefix: String,
+    active_watches: HashSet<String>,
+    pre_shutdown_receiver: PreShutdownReceiver,
+    pre_shutdown_acknowledged: Option<oneshot::Sender<()>>,
+    closed: bool,
+    request_metrics: RequestMetrics,
+    request_quota: Option<RequestQuota>,
 }
 
 #[cfg_attr(test, mockall::automock)]
 impl PipesChannelTask {
+    // The constructor itself stays within the limit of clippy, but the attribute is copied to
+    // the mock whose generated matcher takes the expectation in addition to the seven arguments.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         output_stream: ReopenFile,
         input_stream: ReopenFile,
         input_pipe_receiver: FromServerReceiver,
         output_pipe_channel: ToServerSender,
-        request_id_prefix: String,
+        workload_name: String,
+        pre_shutdown_receiver: PreShutdownReceiver,
+        requests_per_minute: Option<u32>,
     ) -> Self {
         Self {
             output_stream,
             input_stream,
             input_pipe_receiver,
             output_pipe_channel,
-            request_id_prefix,
+            request_id_prefix: format!("{workload_name}@"),
+            workload_name,
+            active_watches: HashSet::new(),
+            pre_shutdown_receiver,
+            pre_shutdown_acknowledged: None,
+            closed: false,
+            request_metrics: RequestMetrics::default(),
+            request_quota: requests_per_minute.map(RequestQuota::new),
         }
     }
     pub async fn run(mut self) {
+        let mut metrics_report_interval = tokio::time::interval(METRICS_REPORT_INTERVAL);
         loop {
             select! {
                 // [impl->swdd~agent-ensures-control-interface-output-pipe-read~1]
@@ -68,21 +118,27 @@ impl PipesChannelTask {
                         log::warn!("The server is sending unrequested messages to a workload: '{:?}'", from_server);
                     }
                 }
+                // [impl->swdd~agent-notifies-workload-before-shutdown~1]
+                Some(pre_shutdown_request) = self.pre_shutdown_receiver.recv() => {
+                    let _ = self.forward_pre_shutdown(pre_shutdown_request).await;
+                }
                 // [impl->swdd~agent-listens-for-requests-from-pipe~1]
                 // [impl->swdd~agent-forward-request-from-control-interface-pipe-to-server~1]
-                to_ankaios_binary = self.input_stream.read_protobuf_data() => {
+                // [impl->swdd~agent-closes-control-interface-of-workload-not-reading~1]
+                to_ankaios_binary = self.input_stream.read_protobuf_data(), if !self.closed => {
                     if let Ok(to_ankaios) = decode_to_server(to_ankaios_binary) {
                         match to_ankaios.try_into() {
-                            Ok(ToAnkaios::Request(mut request)) => {
-                                request.prefix_request_id(&self.request_id_prefix);
-                                let _ = self.output_pipe_channel.send(ToServer::Request(request)).await;
-                            }
+                            Ok(ToAnkaios::Request(request)) => self.forward_request(request).await,
+                            // [impl->swdd~agent-notifies-workload-before-shutdown~1]
+                            Ok(ToAnkaios::PreShutdownAck) => self.acknowledge_pre_shutdown(),
                             Err(error) => {
                                 log::warn!("Could not convert protobuf in internal data structure: {}", error)
                             }
                         }
                     }
                 }
+                // [impl->swdd~agent-reports-control-interface-metrics~1]
+                _ = metrics_report_interval.tick() => self.report_metrics().await,
             }
         }
     }
@@ -90,17 +146,205 @@ impl PipesChannelTask {
         tokio::spawn(self.run())
     }
 
+    // Cancelling a watch is not limited by the quota, as it reduces the load of the server.
+    // [impl->swdd~agent-limits-control-interface-requests-by-quota~1]
+    // [impl->swdd~agent-collects-control-interface-metrics~1]
+    async fn forward_request(&mut self, mut request: Request) {
+        let now = Instant::now();
+        let is_cancel_watch = matches!(
+            request.request_content,
+            RequestContent::CancelWatchRequest(_)
+        );
+        if let Some(request_quota) = self.request_quota.as_mut() {
+            if !is_cancel_watch && !request_quota.try_acquire(now) {
+                let requests_per_minute = request_quota.requests_per_minute();
+                self.request_metrics.request_rejected();
+                let _ = self
+                    .reject_request(request.request_id, requests_per_minute)
+                    .await;
+                return;
+            }
+        }
+
+        self.request_metrics
+            .request_forwarded(&request.request_id, now);
+        request.prefix_request_id(&self.request_id_prefix);
+        self.track_watch(&request);
+        let _ = self
+            .output_pipe_channel
+            .send(ToServer::Request(request))
+            .await;
+    }
+
+    // [impl->swdd~agent-limits-control-interface-requests-by-quota~1]
+    async fn reject_request(
+        &mut self,
+        request_id: String,
+        requests_per_minute: u32,
+    ) -> io::Result<()> {
+        use control_api::from_ankaios::FromAnkaiosEnum;
+        log::debug!(
+            "Workload '{}' exceeded its control interface quota of {} requests per minute, rejecting request '{}'.",
+            self.workload_name,
+            requests_per_minute,
+            request_id
+        );
+        let response = Response {
+            request_id,
+            trace_id: String::new(),
+            response_content: ResponseContent::Error(Error {
+                message: format!(
+                    "The control interface quota of {requests_per_minute} requests per minute is exceeded"
+                ),
+            }),
+        };
+        let message = control_api::FromAnkaios {
+            from_ankaios_enum: Some(FromAnkaiosEnum::Response(response.into())),
+        };
+
+        self.write_to_workload(message).await
+    }
+
+    // [impl->swdd~agent-reports-control-interface-metrics~1]
+    async fn report_metrics(&mut self) {
+        if let Some(metrics) = self.request_metrics.take_changed(&self.workload_name) {
+            let _ = self
+                .output_pipe_channel
+                .send(ToServer::UpdateControlInterfaceMetrics(
+                    UpdateControlInterfaceMetrics { metrics },
+                ))
+                .await;
+        }
+    }
+
+    // [impl->swdd~agent-cancels-state-watches-of-removed-workload~1]
+    fn track_watch(&mut self, request: &Request) {
+        match request.request_content {
+            RequestContent::WatchCompleteStateRequest(_) => {
+                self.active_watches.insert(request.request_id.clone());
+            }
+            RequestContent::CancelWatchRequest(_) => {
+                self.active_watches.remove(&request.request_id);
+            }
+            _ => {}
+        }
+    }
+
     async fn forward_from_server(&mut self, response: Response) -> io::Result<()> {
         use control_api::from_ankaios::FromAnkaiosEnum;
+        // [impl->swdd~agent-collects-control-interface-metrics~1]
+        self.request_metrics
+            .response_received(&response.request_id, Instant::now());
+        // the server ends a watch with an error
+        if let ResponseContent::Error(_) = response.response_content {
+            self.active_watches.remove(&format!(
+                "{}{}",
+                self.request_id_prefix, response.request_id
+            ));
+        }
         let message = control_api::FromAnkaios {
             from_ankaios_enum: Some(FromAnkaiosEnum::Response(response.into())),
         };
 
+        self.write_to_workload(message).await
+    }
+
+    // [impl->swdd~agent-notifies-workload-before-shutdown~1]
+    async fn forward_pre_shutdown(
+        &mut self,
+        pre_shutdown_request: PreShutdownRequest,
+    ) -> io::Result<()> {
+        use control_api::from_ankaios::FromAnkaiosEnum;
+        self.pre_shutdown_acknowledged = Some(pre_shutdown_request.acknowledged);
+        let message = control_api::FromAnkaios {
+            from_ankaios_enum: Some(FromAnkaiosEnum::PreShutdown(control_api::PreShutdown {
+                timeout_ms: pre_shutdown_request.timeout_ms,
+            })),
+        };
+
+        self.write_to_workload(message).await
+    }
+
+    // An acknowledgement without a preceding notification is ignored.
+    fn acknowledge_pre_shutdown(&mut self) {
+        if let Some(acknowledged) = self.pre_shutdown_acknowledged.take() {
+            let _ = acknowledged.send(());
+        }
+    }
+
+    // Messages are dropped after the control interface has been closed. This keeps the buffered
+    // messages bounded, as the channel to the task is still drained.
+    // [impl->swdd~agent-closes-control-interface-of-workload-not-reading~1]
+    async fn write_to_workload(&mut self, message: control_api::FromAnkaios) -> io::Result<()> {
+        if self.closed {
+            return Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "the control interface of the workload is closed",
+            ));
+        }
+
         // [impl->swdd~agent-uses-length-delimited-protobuf-for-pipes~1]
         let binary = message.encode_length_delimited_to_vec();
-        self.output_stream.write_all(&binary).await?;
+        match tokio::time::timeout(WRITE_TIMEOUT, self.output_stream.write_all(&binary)).await {
+            Ok(result) => result,
+            Err(_) => {
+                self.close().await;
+                Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "the workload did not read its input pipe in time",
+                ))
+            }
+        }
+    }
+
+    // A partially written message cannot be recovered, as the workload would read the rest of it
+    // as the start of the next message. Hence, the control interface is closed for good.
+    // [impl->swdd~agent-closes-control-interface-of-workload-not-reading~1]
+    async fn close(&mut self) {
+        log::warn!(
+            "Workload '{}' did not read its control interface input pipe within {} ms, closing its control interface.",
+            self.workload_name,
+            WRITE_TIMEOUT.as_millis()
+        );
+        self.closed = true;
+        self.cancel_active_watches();
+        let _ = self
+            .output_pipe_channel
+            .send(ToServer::AgentEvent(AgentEvent {
+                // set by the server from the agent connection
+                agent_name: String::new(),
+                kind: EventKind::ControlInterfaceClosed,
+                workload_name: self.workload_name.clone(),
+                message: format!(
+                    "The workload did not read its input pipe within {} ms",
+                    WRITE_TIMEOUT.as_millis()
+                ),
+            }))
+            .await;
+    }
 
-        Ok(())
+    // [impl->swdd~agent-cancels-state-watches-of-removed-workload~1]
+    fn cancel_active_watches(&mut self) {
+        for request_id in self.active_watches.drain() {
+            if let Err(error) = self
+                .output_pipe_channel
+                .try_send(ToServer::Request(Request {
+                    request_id,
+                    request_content: RequestContent::CancelWatchRequest(CancelWatchRequest {}),
+                }))
+            {
+                log::warn!("Could not cancel a watch of a workload: '{}'", error);
+            }
+        }
+    }
+}
+
+// The task is aborted when the workload is removed, the still open watches are cancelled
+// as the workload cannot receive the state updates anymore.
+// [impl->swdd~agent-cancels-state-watches-of-removed-workload~1]
+impl Drop for PipesChannelTask {
+    fn drop(&mut self) {
+        self.cancel_active_watches();
     }
 }
 
@@ -117,7 +361,7 @@ pub fn generate_test_pipes_channel_task_mock() -> __mock_MockPipesChannelTask::_
     let pipes_channel_task_mock_context = MockPipesChannelTask::new_context();
     pipes_channel_task_mock_context
         .expect()
-        .return_once(|_, _, _, _, _| {
+        .return_once(|_, _, _, _, _, _, _| {
             let mut pipes_channel_task_mock = MockPipesChannelTask::default();
             pipes_channel_task_mock
                 .expect_run_task()
@@ -131,7 +375,7 @@ pub fn generate_test_pipes_channel_task_mock() -> __mock_MockPipesChannelTask::_
 mod tests {
     use common::commands;
     use mockall::predicate;
-    use tokio::sync::mpsc;
+    use tokio::sync::{mpsc, oneshot};
 
     use super::*;
     use api::{ank_base, control_api};
@@ -146,6 +390,7 @@ mod tests {
 
         let response = commands::Response {
             request_id: "req_id".to_owned(),
+            trace_id: "trace_id".to_owned(),
             response_content: commands::ResponseContent::CompleteState(Default::default()),
         };
 
@@ -166,14 +411,16 @@ mod tests {
         let input_stream_mock = MockReopenFile::default();
         let (_, input_pipe_receiver) = mpsc::channel(1);
         let (output_pipe_sender, _) = mpsc::channel(1);
-        let request_id_prefix = String::from("prefix@");
+        let workload_name = String::from("prefix");
 
         let mut pipes_channel_task = PipesChannelTask::new(
             output_stream_mock,
             input_stream_mock,
             input_pipe_receiver,
             output_pipe_sender,
-            request_id_prefix,
+            workload_name,
+            mpsc::channel(1).1,
+            None,
         );
 
         assert!(pipes_channel_task
@@ -213,6 +460,7 @@ mod tests {
 
         let response = commands::Response {
             request_id: "req_id".to_owned(),
+            trace_id: "trace_id".to_owned(),
             response_content: commands::ResponseContent::CompleteState(Default::default()),
         };
 
@@ -233,14 +481,16 @@ mod tests {
 
         let (input_pipe_sender, input_pipe_receiver) = mpsc::channel(1);
         let (output_pipe_sender, mut output_pipe_receiver) = mpsc::channel(1);
-        let request_id_prefix = String::from("prefix@");
+        let workload_name = String::from("prefix");
 
         let pipes_channel_task = PipesChannelTask::new(
             output_stream_mock,
             input_stream_mock,
             input_pipe_receiver,
             output_pipe_sender,
-            request_id_prefix,
+            workload_name,
+            mpsc::channel(1).1,
+            None,
         );
 
         let handle = pipes_channel_task.run_task();
@@ -258,4 +508,310 @@ mod tests {
 
         handle.abort();
     }
+
+    // [utest->swdd~agent-limits-control-interface-requests-by-quota~1]
+    // [utest->swdd~agent-collects-control-interface-metrics~1]
+    // [utest->swdd~agent-reports-control-interface-metrics~1]
+    #[tokio::test]
+    async fn utest_pipes_channel_task_rejects_requests_exceeding_quota_and_reports_metrics() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let rejection_binary = control_api::FromAnkaios {
+            from_ankaios_enum: Some(control_api::from_ankaios::FromAnkaiosEnum::Response(
+                commands::Response {
+                    request_id: "req_id_2".to_owned(),
+                    trace_id: String::new(),
+                    response_content: commands::ResponseContent::Error(commands::Error {
+                        message: "The control interface quota of 1 requests per minute is exceeded"
+                            .to_owned(),
+                    }),
+                }
+                .into(),
+            )),
+        }
+        .encode_length_delimited_to_vec();
+
+        let mut output_stream_mock = MockReopenFile::default();
+        output_stream_mock
+            .expect_write_all()
+            .with(predicate::eq(rejection_binary))
+            .once()
+            .return_once(|_| Ok(()));
+        let (_, input_pipe_receiver) = mpsc::channel(1);
+        let (output_pipe_sender, mut output_pipe_receiver) = mpsc::channel(3);
+
+        let mut pipes_channel_task = PipesChannelTask::new(
+            output_stream_mock,
+            MockReopenFile::default(),
+            input_pipe_receiver,
+            output_pipe_sender,
+            String::from("prefix"),
+            mpsc::channel(1).1,
+            Some(1),
+        );
+        let complete_state_request = |request_id: &str| commands::Request {
+            request_id: request_id.to_owned(),
+            request_content: commands::RequestContent::CompleteStateRequest(
+                commands::CompleteStateRequest { field_mask: vec![] },
+            ),
+        };
+
+        pipes_channel_task
+            .forward_request(complete_state_request("req_id_1"))
+            .await;
+        pipes_channel_task
+            .forward_request(complete_state_request("req_id_2"))
+            .await;
+        // cancelling a watch is not limited by the quota
+        pipes_channel_task
+            .forward_request(commands::Request {
+                request_id: "watch_id".to_owned(),
+                request_content: commands::RequestContent::CancelWatchRequest(
+                    commands::CancelWatchRequest {},
+                ),
+            })
+            .await;
+        pipes_channel_task.report_metrics().await;
+        // unchanged metrics are not reported again
+        pipes_channel_task.report_metrics().await;
+
+        assert_eq!(
+            output_pipe_receiver.try_recv(),
+            Ok(ToServer::Request(complete_state_request("prefix@req_id_1")))
+        );
+        assert_eq!(
+            output_pipe_receiver.try_recv(),
+            Ok(ToServer::Request(commands::Request {
+                request_id: "prefix@watch_id".to_owned(),
+                request_content: commands::RequestContent::CancelWatchRequest(
+                    commands::CancelWatchRequest {}
+                ),
+            }))
+        );
+        assert!(matches!(
+            output_pipe_receiver.try_recv(),
+            Ok(ToServer::UpdateControlInterfaceMetrics(commands::UpdateControlInterfaceMetrics {
+                metrics
+            })) if metrics.workload_name == "prefix"
+                && metrics.forwarded_requests == 2
+                && metrics.rejected_requests == 1
+        ));
+        assert!(output_pipe_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~agent-cancels-state-watches-of-removed-workload~1]
+    #[tokio::test]
+    async fn utest_pipes_channel_task_cancels_open_watches_on_drop() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let (_, input_pipe_receiver) = mpsc::channel(1);
+        let (output_pipe_sender, mut output_pipe_receiver) = mpsc::channel(2);
+
+        let mut pipes_channel_task = PipesChannelTask::new(
+            MockReopenFile::default(),
+            MockReopenFile::default(),
+            input_pipe_receiver,
+            output_pipe_sender,
+            String::from("prefix"),
+            mpsc::channel(1).1,
+            None,
+        );
+        pipes_channel_task.track_watch(&commands::Request {
+            request_id: "prefix@watch_id".to_owned(),
+            request_content: commands::RequestContent::WatchCompleteStateRequest(
+                commands::WatchCompleteStateRequest { field_mask: vec![] },
+            ),
+        });
+        pipes_channel_task.track_watch(&commands::Request {
+            request_id: "prefix@cancelled_watch_id".to_owned(),
+            request_content: commands::RequestContent::WatchCompleteStateRequest(
+                commands::WatchCompleteStateRequest { field_mask: vec![] },
+            ),
+        });
+        pipes_channel_task.track_watch(&commands::Request {
+            request_id: "prefix@cancelled_watch_id".to_owned(),
+            request_content: commands::RequestContent::CancelWatchRequest(
+                commands::CancelWatchRequest {},
+            ),
+        });
+
+        drop(pipes_channel_task);
+
+        assert_eq!(
+            output_pipe_receiver.try_recv(),
+            Ok(ToServer::Request(commands::Request {
+                request_id: "prefix@watch_id".to_owned(),
+                request_content: commands::RequestContent::CancelWatchRequest(
+                    commands::CancelWatchRequest {}
+                ),
+            }))
+        );
+        assert!(output_pipe_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~agent-cancels-state-watches-of-removed-workload~1]
+    #[tokio::test]
+    async fn utest_pipes_channel_task_does_not_cancel_watch_ended_by_server() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut output_stream_mock = MockReopenFile::default();
+        output_stream_mock
+            .expect_write_all()
+            .return_once(|_| Ok(()));
+        let (_, input_pipe_receiver) = mpsc::channel(1);
+        let (output_pipe_sender, mut output_pipe_receiver) = mpsc::channel(1);
+
+        let mut pipes_channel_task = PipesChannelTask::new(
+            output_stream_mock,
+            MockReopenFile::default(),
+            input_pipe_receiver,
+            output_pipe_sender,
+            String::from("prefix"),
+            mpsc::channel(1).1,
+            None,
+        );
+        pipes_channel_task.track_watch(&commands::Request {
+            request_id: "prefix@watch_id".to_owned(),
+            request_content: commands::RequestContent::WatchCompleteStateRequest(
+                commands::WatchCompleteStateRequest { field_mask: vec![] },
+            ),
+        });
+        assert!(pipes_channel_task
+            .forward_from_server(commands::Response {
+                request_id: "watch_id".to_owned(),
+                trace_id: "trace_id".to_owned(),
+                response_content: commands::ResponseContent::Error(commands::Error {
+                    message: "error".to_owned(),
+                }),
+            })
+            .await
+            .is_ok());
+
+        drop(pipes_channel_task);
+
+        assert!(output_pipe_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~agent-closes-control-interface-of-workload-not-reading~1]
+    #[tokio::test]
+    async fn utest_pipes_channel_task_close_reports_event_and_drops_messages() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut output_stream_mock = MockReopenFile::default();
+        output_stream_mock.expect_write_all().never();
+        let (_, input_pipe_receiver) = mpsc::channel(1);
+        let (output_pipe_sender, mut output_pipe_receiver) = mpsc::channel(2);
+
+        let mut pipes_channel_task = PipesChannelTask::new(
+            output_stream_mock,
+            MockReopenFile::default(),
+            input_pipe_receiver,
+            output_pipe_sender,
+            String::from("prefix"),
+            mpsc::channel(1).1,
+            None,
+        );
+        pipes_channel_task.track_watch(&commands::Request {
+            request_id: "prefix@watch_id".to_owned(),
+            request_content: commands::RequestContent::WatchCompleteStateRequest(
+                commands::WatchCompleteStateRequest { field_mask: vec![] },
+            ),
+        });
+
+        pipes_channel_task.close().await;
+
+        assert_eq!(
+            output_pipe_receiver.try_recv(),
+            Ok(ToServer::Request(commands::Request {
+                request_id: "prefix@watch_id".to_owned(),
+                request_content: commands::RequestContent::CancelWatchRequest(
+                    commands::CancelWatchRequest {}
+                ),
+            }))
+        );
+        assert_eq!(
+            output_pipe_receiver.try_recv(),
+            Ok(ToServer::AgentEvent(commands::AgentEvent {
+                agent_name: String::new(),
+                kind: commands::EventKind::ControlInterfaceClosed,
+                workload_name: "prefix".to_owned(),
+                message: format!(
+                    "The workload did not read its input pipe within {} ms",
+                    WRITE_TIMEOUT.as_millis()
+                ),
+            }))
+        );
+
+        assert_eq!(
+            pipes_channel_task
+                .forward_from_server(commands::Response {
+                    request_id: "req_id".to_owned(),
+                    trace_id: "trace_id".to_owned(),
+                    response_content: commands::ResponseContent::CompleteState(
+                        Default::default()
+                    ),
+                })
+                .await
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::BrokenPipe
+        );
+
+        drop(pipes_channel_task);
+        assert!(output_pipe_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~agent-notifies-workload-before-shutdown~1]
+    #[tokio::test]
+    async fn utest_pipes_channel_task_forwards_pre_shutdown_and_signals_acknowledgement() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let pre_shutdown_binary = control_api::FromAnkaios {
+            from_ankaios_enum: Some(control_api::from_ankaios::FromAnkaiosEnum::PreShutdown(
+                control_api::PreShutdown { timeout_ms: 500 },
+            )),
+        }
+        .encode_length_delimited_to_vec();
+
+        let mut output_stream_mock = MockReopenFile::default();
+        output_stream_mock
+            .expect_write_all()
+            .with(predicate::eq(pre_shutdown_binary))
+            .return_once(|_| Ok(()));
+        let (_, input_pipe_receiver) = mpsc::channel(1);
+        let (output_pipe_sender, _) = mpsc::channel(1);
+
+        let mut pipes_channel_task = PipesChannelTask::new(
+            output_stream_mock,
+            MockReopenFile::default(),
+            input_pipe_receiver,
+            output_pipe_sender,
+            String::from("prefix"),
+            mpsc::channel(1).1,
+            None,
+        );
+
+        let (acknowledged_sender, mut acknowledged_receiver) = oneshot::channel();
+        assert!(pipes_channel_task
+            .forward_pre_shutdown(PreShutdownRequest {
+                timeout_ms: 500,
+                acknowledged: acknowledged_sender,
+            })
+            .await
+            .is_ok());
+        assert!(acknowledged_receiver.try_recv().is_err());
+
+        pipes_channel_task.acknowledge_pre_shutdown();
+        assert_eq!(acknowledged_receiver.try_recv(), Ok(()));
+    }
 }
@@ -86,7 +86,10 @@ impl ReopenFile {
         let varint_data = Self::try_read_varint_data(file).await?;
         let mut varint_data = Box::new(&varint_data[..]);
 
-        let size = prost::encoding::decode_varint(&mut varint_data)? as usize;
+        let size = prost::encoding::decode_varint(&mut varint_data)?;
+        // [impl->swdd~common-limits-message-size~1]
+        let size = common::input_limits::check_message_size(size)
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
 
         let mut buf = vec![0; size];
         file.read_exact(&mut buf[..]).await?;
@@ -309,6 +312,34 @@ mod tests {
         jh.await.unwrap();
     }
 
+    // [utest->swdd~common-limits-message-size~1]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_read_with_too_large_size() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let fifo = tmpdir.path().join("fifo");
+        mkfifo(&fifo, Mode::S_IRWXU).unwrap();
+        let fifo2 = fifo.clone();
+
+        let jh = tokio::spawn(async move {
+            let mut f = super::ReopenFile::open(&fifo2);
+            let data = f.read_protobuf_data().await;
+            assert_eq!(data.unwrap_err().kind(), ErrorKind::InvalidData);
+        });
+
+        {
+            let mut f = std::fs::File::create(&fifo).unwrap();
+            let mut data = Vec::new();
+            prost::encoding::encode_varint(
+                common::input_limits::MAX_MESSAGE_SIZE as u64 + 1,
+                &mut data,
+            );
+            f.write_all(&data).unwrap();
+            f.flush().unwrap();
+        }
+
+        jh.await.unwrap();
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_read_empty() {
         let tmpdir = tempfile::tempdir().unwrap();
@@ -0,0 +1,202 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, time::Duration};
+
+use common::objects::ControlInterfaceMetrics;
+use tokio::time::Instant;
+
+// The window the request quota of a workload applies to.
+const QUOTA_WINDOW: Duration = Duration::from_secs(60);
+// The maximal number of requests waiting for their first response. Requests beyond are still
+// forwarded, but not considered for the latency, as a workload could send requests the server
+// never answers.
+const MAX_PENDING_REQUESTS: usize = 256;
+
+// [impl->swdd~agent-collects-control-interface-metrics~1]
+// The requests a workload sent via its control interface and the time until their first response.
+#[derive(Default)]
+pub struct RequestMetrics {
+    forwarded_requests: u64,
+    rejected_requests: u64,
+    answered_requests: u64,
+    total_latency_ms: u64,
+    max_latency_ms: u64,
+    pending_requests: HashMap<String, Instant>,
+    changed: bool,
+}
+
+impl RequestMetrics {
+    pub fn request_forwarded(&mut self, request_id: &str, now: Instant) {
+        self.forwarded_requests += 1;
+        self.changed = true;
+        if self.pending_requests.len() < MAX_PENDING_REQUESTS {
+            self.pending_requests.insert(request_id.to_owned(), now);
+        }
+    }
+
+    pub fn request_rejected(&mut self) {
+        self.rejected_requests += 1;
+        self.changed = true;
+    }
+
+    // Only the first response to a request is considered, e.g., for a watch.
+    pub fn response_received(&mut self, request_id: &str, now: Instant) {
+        if let Some(forwarded_at) = self.pending_requests.remove(request_id) {
+            let latency_ms = now.duration_since(forwarded_at).as_millis() as u64;
+            self.answered_requests += 1;
+            self.total_latency_ms += latency_ms;
+            self.max_latency_ms = self.max_latency_ms.max(latency_ms);
+            self.changed = true;
+        }
+    }
+
+    // Returns the metrics only if they changed since the last call.
+    pub fn take_changed(&mut self, workload_name: &str) -> Option<ControlInterfaceMetrics> {
+        if !std::mem::take(&mut self.changed) {
+            return None;
+        }
+        Some(ControlInterfaceMetrics {
+            // set by the server from the agent connection
+            agent_name: String::new(),
+            workload_name: workload_name.to_owned(),
+            forwarded_requests: self.forwarded_requests,
+            rejected_requests: self.rejected_requests,
+            mean_latency_ms: self
+                .total_latency_ms
+                .checked_div(self.answered_requests)
+                .unwrap_or_default(),
+            max_latency_ms: self.max_latency_ms,
+        })
+    }
+}
+
+// [impl->swdd~agent-limits-control-interface-requests-by-quota~1]
+// A fixed window of one minute starting with the first request after the previous window.
+pub struct RequestQuota {
+    requests_per_minute: u32,
+    window_start: Option<Instant>,
+    requests_in_window: u32,
+}
+
+impl RequestQuota {
+    pub fn new(requests_per_minute: u32) -> Self {
+        Self {
+            requests_per_minute,
+            window_start: None,
+            requests_in_window: 0,
+        }
+    }
+
+    pub fn requests_per_minute(&self) -> u32 {
+        self.requests_per_minute
+    }
+
+    // Returns false if the request exceeds the quota of the current window.
+    pub fn try_acquire(&mut self, now: Instant) -> bool {
+        if self
+            .window_start
+            .is_none_or(|window_start| now.duration_since(window_start) >= QUOTA_WINDOW)
+        {
+            self.window_start = Some(now);
+            self.requests_in_window = 0;
+        }
+        if self.requests_in_window >= self.requests_per_minute {
+            return false;
+        }
+        self.requests_in_window += 1;
+        true
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common::objects::ControlInterfaceMetrics;
+    use tokio::time::Instant;
+
+    use super::{RequestMetrics, RequestQuota, MAX_PENDING_REQUESTS};
+
+    const WORKLOAD_NAME: &str = "workload_1";
+
+    // [utest->swdd~agent-collects-control-interface-metrics~1]
+    #[test]
+    fn utest_request_metrics_counts_requests_and_measures_latency() {
+        let start = Instant::now();
+        let mut request_metrics = RequestMetrics::default();
+        assert!(request_metrics.take_changed(WORKLOAD_NAME).is_none());
+
+        request_metrics.request_forwarded("request_1", start);
+        request_metrics.request_forwarded("request_2", start);
+        request_metrics.request_rejected();
+        request_metrics.response_received("request_1", start + Duration::from_millis(10));
+        request_metrics.response_received("request_2", start + Duration::from_millis(30));
+        // only the first response of a request is considered
+        request_metrics.response_received("request_2", start + Duration::from_millis(90));
+
+        assert_eq!(
+            request_metrics.take_changed(WORKLOAD_NAME),
+            Some(ControlInterfaceMetrics {
+                agent_name: String::new(),
+                workload_name: WORKLOAD_NAME.to_string(),
+                forwarded_requests: 2,
+                rejected_requests: 1,
+                mean_latency_ms: 20,
+                max_latency_ms: 30,
+            })
+        );
+        assert!(request_metrics.take_changed(WORKLOAD_NAME).is_none());
+    }
+
+    // [utest->swdd~agent-collects-control-interface-metrics~1]
+    #[test]
+    fn utest_request_metrics_limits_pending_requests() {
+        let start = Instant::now();
+        let mut request_metrics = RequestMetrics::default();
+        for i in 0..=MAX_PENDING_REQUESTS {
+            request_metrics.request_forwarded(&format!("request_{i}"), start);
+        }
+
+        assert_eq!(request_metrics.pending_requests.len(), MAX_PENDING_REQUESTS);
+        assert_eq!(
+            request_metrics
+                .take_changed(WORKLOAD_NAME)
+                .map(|metrics| metrics.forwarded_requests),
+            Some(MAX_PENDING_REQUESTS as u64 + 1)
+        );
+    }
+
+    // [utest->swdd~agent-limits-control-interface-requests-by-quota~1]
+    #[test]
+    fn utest_request_quota_rejects_requests_exceeding_quota_within_window() {
+        let start = Instant::now();
+        let mut request_quota = RequestQuota::new(2);
+
+        assert!(request_quota.try_acquire(start));
+        assert!(request_quota.try_acquire(start + Duration::from_secs(10)));
+        assert!(!request_quota.try_acquire(start + Duration::from_secs(59)));
+        assert!(request_quota.try_acquire(start + Duration::from_secs(60)));
+        assert!(request_quota.try_acquire(start + Duration::from_secs(61)));
+        assert!(!request_quota.try_acquire(start + Duration::from_secs(62)));
+    }
+}
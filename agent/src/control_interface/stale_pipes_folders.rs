@@ -0,0 +1,136 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const PIPE_NAMES: [&str; 2] = ["input", "output"];
+
+// The run folder also contains other files of the agent, e.g. the persisted workload queue.
+// Only folders containing a control interface pipe are considered as pipes folders.
+fn is_pipes_folder(path: &Path) -> bool {
+    path.is_dir()
+        && PIPE_NAMES
+            .iter()
+            .any(|pipe_name| path.join(pipe_name).symlink_metadata().is_ok())
+}
+
+/// Removes the control interface pipes folder of a workload if it still exists.
+///
+/// The pipes folder is normally removed together with the control interface of the workload.
+/// A workload the agent did not create in this run, e.g. one left over after an agent crash,
+/// has no control interface and its pipes folder is removed with this function.
+///
+/// # Arguments
+///
+/// * `pipes_folder` - The path of the control interface pipes folder of the workload
+///
+// [impl->swdd~agent-removes-stale-control-interface-pipes~1]
+pub fn remove_pipes_folder(pipes_folder: &Path) {
+    if !is_pipes_folder(pipes_folder) {
+        return;
+    }
+    log::debug!("Removing control interface pipes folder '{:?}'", pipes_folder);
+    if let Err(err) = fs::remove_dir_all(pipes_folder) {
+        log::warn!(
+            "Could not remove the control interface pipes folder '{:?}': '{}'",
+            pipes_folder,
+            err
+        );
+    }
+}
+
+/// Removes all control interface pipes folders in the run folder not used by a workload.
+///
+/// # Arguments
+///
+/// * `run_folder` - The run folder of the agent containing the pipes folders
+/// * `used_pipes_folders` - The pipes folders of the workloads with a control interface
+///
+// [impl->swdd~agent-removes-stale-control-interface-pipes~1]
+pub fn remove_stale_pipes_folders(run_folder: &Path, used_pipes_folders: &HashSet<PathBuf>) {
+    let entries = match fs::read_dir(run_folder) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return,
+        Err(err) => {
+            log::warn!(
+                "Could not search the run folder '{:?}' for stale control interface pipes: '{}'",
+                run_folder,
+                err
+            );
+            return;
+        }
+    };
+
+    entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| !used_pipes_folders.contains(path))
+        .for_each(|path| remove_pipes_folder(&path));
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, fs};
+
+    use super::{remove_pipes_folder, remove_stale_pipes_folders};
+
+    // [utest->swdd~agent-removes-stale-control-interface-pipes~1]
+    #[test]
+    fn utest_remove_stale_pipes_folders_keeps_used_folders_and_other_files() {
+        let run_folder = tempfile::tempdir().unwrap();
+        let used_folder = run_folder.path().join("workload_1.1234");
+        let stale_folder = run_folder.path().join("workload_2.5678");
+        let other_folder = run_folder.path().join("manifests");
+        for folder in [&used_folder, &stale_folder, &other_folder] {
+            fs::create_dir(folder).unwrap();
+        }
+        fs::write(used_folder.join("input"), "").unwrap();
+        fs::write(stale_folder.join("output"), "").unwrap();
+        fs::write(stale_folder.join("log_level"), "info\n").unwrap();
+        fs::write(run_folder.path().join("queue"), "").unwrap();
+
+        remove_stale_pipes_folders(run_folder.path(), &HashSet::from([used_folder.clone()]));
+
+        assert!(used_folder.exists());
+        assert!(!stale_folder.exists());
+        assert!(other_folder.exists());
+        assert!(run_folder.path().join("queue").exists());
+    }
+
+    // [utest->swdd~agent-removes-stale-control-interface-pipes~1]
+    #[test]
+    fn utest_remove_pipes_folder_ignores_missing_folder() {
+        let run_folder = tempfile::tempdir().unwrap();
+        let pipes_folder = run_folder.path().join("workload_1.1234");
+
+        remove_pipes_folder(&pipes_folder);
+        remove_stale_pipes_folders(&run_folder.path().join("missing"), &HashSet::new());
+
+        fs::create_dir(&pipes_folder).unwrap();
+        fs::write(pipes_folder.join("input"), "").unwrap();
+        remove_pipes_folder(&pipes_folder);
+        assert!(!pipes_folder.exists());
+    }
+}
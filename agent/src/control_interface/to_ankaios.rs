@@ -20,6 +20,7 @@ use common::commands;
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum ToAnkaios {
     Request(commands::Request),
+    PreShutdownAck,
 }
 
 impl TryFrom<control_api::ToAnkaios> for ToAnkaios {
@@ -33,6 +34,7 @@ impl TryFrom<control_api::ToAnkaios> for ToAnkaios {
 
         Ok(match to_ankaios {
             ToAnkaiosEnum::Request(content) => ToAnkaios::Request(content.try_into()?),
+            ToAnkaiosEnum::PreShutdownAck(_) => ToAnkaios::PreShutdownAck,
         })
     }
 }
@@ -0,0 +1,279 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use common::from_server_interface::FromServer;
+use common::objects::WorkloadSpec;
+use tokio::sync::mpsc::Receiver;
+
+use crate::runtime_connectors::RuntimeFacade;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DryRunResult {
+    pub workload_name: String,
+    pub runtime: String,
+    pub result: Result<(), String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DryRunReport {
+    agent_name: String,
+    results: Vec<DryRunResult>,
+}
+
+impl DryRunReport {
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|result| result.result.is_ok())
+    }
+}
+
+impl Display for DryRunReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Dry run of {} workload(s) assigned to agent '{}':",
+            self.results.len(),
+            self.agent_name
+        )?;
+        for result in &self.results {
+            match &result.result {
+                Ok(()) => writeln!(f, "  {} ({}): OK", result.workload_name, result.runtime)?,
+                Err(error) => writeln!(
+                    f,
+                    "  {} ({}): FAILED - {}",
+                    result.workload_name, result.runtime, error
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// [impl->swdd~agent-dry-run-waits-for-assigned-workloads~1]
+// Returns None if the connection to the server ends before the workloads are received.
+pub async fn receive_assigned_workloads(
+    receiver: &mut Receiver<FromServer>,
+) -> Option<Vec<WorkloadSpec>> {
+    loop {
+        match receiver.recv().await? {
+            FromServer::UpdateWorkload(update_workload) => {
+                return Some(update_workload.added_workloads)
+            }
+            FromServer::Stop(_) => return None,
+            _ => log::trace!("Ignoring message from the server while waiting for the workloads."),
+        }
+    }
+}
+
+// [impl->swdd~agent-dry-run-validates-assigned-workloads~1]
+pub async fn dry_run_workloads(
+    agent_name: &str,
+    workloads: Vec<WorkloadSpec>,
+    runtime_facade_map: &HashMap<String, Box<dyn RuntimeFacade>>,
+) -> DryRunReport {
+    let mut results = Vec::with_capacity(workloads.len());
+    for workload in workloads {
+        let result = match runtime_facade_map.get(&workload.runtime) {
+            Some(runtime) => runtime
+                .dry_run_workload(&workload)
+                .await
+                .map_err(|err| err.to_string()),
+            None => Err(format!("Unknown runtime '{}'", workload.runtime)),
+        };
+        results.push(DryRunResult {
+            workload_name: workload.instance_name.workload_name().to_owned(),
+            runtime: workload.runtime,
+            result,
+        });
+    }
+
+    DryRunReport {
+        agent_name: agent_name.to_owned(),
+        results,
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use common::commands::{Stop, UpdateWorkload, UpdateWorkloadState};
+    use common::from_server_interface::FromServer;
+    use common::objects::generate_test_workload_spec_with_param;
+
+    use super::{dry_run_workloads, receive_assigned_workloads, DryRunReport, DryRunResult};
+    use crate::runtime_connectors::{MockRuntimeFacade, RuntimeError, RuntimeFacade};
+
+    const AGENT_NAME: &str = "agent_A";
+    const RUNTIME_NAME: &str = "runtime_1";
+    const WORKLOAD_1_NAME: &str = "workload_1";
+    const WORKLOAD_2_NAME: &str = "workload_2";
+
+    // [utest->swdd~agent-dry-run-waits-for-assigned-workloads~1]
+    #[tokio::test]
+    async fn utest_receive_assigned_workloads_skips_other_messages() {
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(common::CHANNEL_CAPACITY);
+        let workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+
+        sender
+            .send(FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                workload_states: vec![],
+            }))
+            .await
+            .unwrap();
+        sender
+            .send(FromServer::UpdateWorkload(UpdateWorkload {
+                added_workloads: vec![workload.clone()],
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            receive_assigned_workloads(&mut receiver).await,
+            Some(vec![workload])
+        );
+    }
+
+    // [utest->swdd~agent-dry-run-waits-for-assigned-workloads~1]
+    #[tokio::test]
+    async fn utest_receive_assigned_workloads_returns_none_on_stop() {
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(common::CHANNEL_CAPACITY);
+        sender.send(FromServer::Stop(Stop {})).await.unwrap();
+        drop(sender);
+
+        assert_eq!(receive_assigned_workloads(&mut receiver).await, None);
+    }
+
+    // [utest->swdd~agent-dry-run-validates-assigned-workloads~1]
+    #[tokio::test]
+    async fn utest_dry_run_workloads_reports_result_per_workload() {
+        let workload_1 = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        let workload_2 = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_2_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        let workload_3 = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            "workload_3".to_owned(),
+            "unknown_runtime".to_owned(),
+        );
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock
+            .expect_dry_run_workload()
+            .withf(|workload| workload.instance_name.workload_name() == WORKLOAD_1_NAME)
+            .once()
+            .return_once(|_| Box::pin(async { Ok(()) }));
+        runtime_facade_mock
+            .expect_dry_run_workload()
+            .withf(|workload| workload.instance_name.workload_name() == WORKLOAD_2_NAME)
+            .once()
+            .return_once(|_| {
+                Box::pin(async { Err(RuntimeError::NotFound("image not found".to_owned())) })
+            });
+
+        let runtime_facade_map = HashMap::from([(
+            RUNTIME_NAME.to_owned(),
+            Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+        )]);
+
+        let report = dry_run_workloads(
+            AGENT_NAME,
+            vec![workload_1, workload_2, workload_3],
+            &runtime_facade_map,
+        )
+        .await;
+
+        assert!(!report.is_success());
+        assert_eq!(
+            report,
+            DryRunReport {
+                agent_name: AGENT_NAME.to_owned(),
+                results: vec![
+                    DryRunResult {
+                        workload_name: WORKLOAD_1_NAME.to_owned(),
+                        runtime: RUNTIME_NAME.to_owned(),
+                        result: Ok(()),
+                    },
+                    DryRunResult {
+                        workload_name: WORKLOAD_2_NAME.to_owned(),
+                        runtime: RUNTIME_NAME.to_owned(),
+                        result: Err("NotFound: image not found".to_owned()),
+                    },
+                    DryRunResult {
+                        workload_name: "workload_3".to_owned(),
+                        runtime: "unknown_runtime".to_owned(),
+                        result: Err("Unknown runtime 'unknown_runtime'".to_owned()),
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn utest_dry_run_report_display() {
+        let report = DryRunReport {
+            agent_name: AGENT_NAME.to_owned(),
+            results: vec![
+                DryRunResult {
+                    workload_name: WORKLOAD_1_NAME.to_owned(),
+                    runtime: RUNTIME_NAME.to_owned(),
+                    result: Ok(()),
+                },
+                DryRunResult {
+                    workload_name: WORKLOAD_2_NAME.to_owned(),
+                    runtime: RUNTIME_NAME.to_owned(),
+                    result: Err("image not found".to_owned()),
+                },
+            ],
+        };
+
+        assert_eq!(
+            report.to_string(),
+            "Dry run of 2 workload(s) assigned to agent 'agent_A':\n  \
+             workload_1 (runtime_1): OK\n  \
+             workload_2 (runtime_1): FAILED - image not found\n"
+        );
+    }
+
+    #[test]
+    fn utest_dry_run_report_without_workloads_is_success() {
+        let report = DryRunReport {
+            agent_name: AGENT_NAME.to_owned(),
+            results: vec![],
+        };
+
+        assert!(report.is_success());
+    }
+}
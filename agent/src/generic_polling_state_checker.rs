@@ -6,10 +6,14 @@ use crate::{
     runtime_connectors::{RuntimeStateGetter, StateChecker},
     workload_state::{WorkloadStateSender, WorkloadStateSenderInterface},
 };
-use common::objects::{ExecutionState, ExecutionStateEnum, WorkloadSpec};
+use common::objects::{ExecutionState, ExecutionStateEnum, FailedSubstate, WorkloadSpec};
 
 // [impl->swdd~agent-provides-generic-state-checker-implementation~1]
 const STATUS_CHECK_INTERVAL_MS: u64 = 500;
+// [impl->swdd~generic-state-checker-refreshes-workload-state~1]
+const STATUS_REFRESH_INTERVAL_MS: u64 = common::WORKLOAD_STATE_REFRESH_INTERVAL_MS;
+// bounds the size of the workload states sent to the server
+const MAX_CAPTURED_OUTPUT_BYTES: u64 = 4096;
 
 #[derive(Debug)]
 pub struct GenericPollingStateChecker {
@@ -33,24 +37,42 @@ where
         let workload_name = workload_spec.instance_name.workload_name().to_owned();
         let task_handle = tokio::spawn(async move {
             let mut last_state = ExecutionState::unknown("Never received an execution state.");
+            let mut reported_state = last_state.clone();
             let mut interval = time::interval(Duration::from_millis(STATUS_CHECK_INTERVAL_MS));
+            let mut last_report = time::Instant::now();
             loop {
                 interval.tick().await;
                 let current_state = state_getter.get_state(&workload_id).await;
 
-                if current_state != last_state {
+                // [impl->swdd~generic-state-checker-refreshes-workload-state~1]
+                let refresh_due =
+                    last_report.elapsed() >= Duration::from_millis(STATUS_REFRESH_INTERVAL_MS);
+                let state_changed = current_state != last_state;
+                if state_changed || refresh_due {
                     log::debug!(
-                        "The workload {} has changed its state to {:?}",
+                        "The workload {} has the state {:?}",
                         workload_spec.instance_name.workload_name(),
                         current_state
                     );
                     last_state = current_state.clone();
+                    last_report = time::Instant::now();
+
+                    // a refresh keeps the output captured on the change of the state
+                    if state_changed {
+                        reported_state = with_captured_output(
+                            current_state,
+                            &workload_spec,
+                            &workload_id,
+                            &state_getter,
+                        )
+                        .await;
+                    }
 
                     // [impl->swdd~generic-state-checker-sends-workload-state~2]
                     workload_state_sender
                         .report_workload_execution_state(
                             &workload_spec.instance_name,
-                            current_state,
+                            reported_state.clone(),
                         )
                         .await;
 
@@ -72,6 +94,53 @@ where
     }
 }
 
+// [impl->swdd~generic-state-checker-attaches-captured-output~1]
+async fn with_captured_output<WorkloadId>(
+    mut execution_state: ExecutionState,
+    workload_spec: &WorkloadSpec,
+    workload_id: &WorkloadId,
+    state_getter: &impl RuntimeStateGetter<WorkloadId>,
+) -> ExecutionState
+where
+    WorkloadId: ToString + Send + Sync + 'static,
+{
+    let Some(max_bytes) = workload_spec.capture_output_bytes else {
+        return execution_state;
+    };
+    if !matches!(
+        execution_state.state,
+        ExecutionStateEnum::Succeeded(_) | ExecutionStateEnum::Failed(FailedSubstate::ExecFailed)
+    ) {
+        return execution_state;
+    }
+    let Some(output) = state_getter.get_output(workload_id).await else {
+        return execution_state;
+    };
+
+    let output = last_bytes(
+        output.trim_end(),
+        max_bytes.min(MAX_CAPTURED_OUTPUT_BYTES) as usize,
+    );
+    if !output.is_empty() {
+        if execution_state.additional_info.is_empty() {
+            execution_state.additional_info = output.to_owned();
+        } else {
+            execution_state.additional_info =
+                format!("{}\n{}", execution_state.additional_info, output);
+        }
+    }
+    execution_state
+}
+
+// The end of the text with at most the given number of bytes, not splitting a character.
+fn last_bytes(text: &str, max_bytes: usize) -> &str {
+    let mut start = text.len().saturating_sub(max_bytes);
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    &text[start..]
+}
+
 impl Drop for GenericPollingStateChecker {
     fn drop(&mut self) {
         self.task_handle.abort();
@@ -94,7 +163,7 @@ mod tests {
     use common::{objects::generate_test_workload_spec_with_param, objects::ExecutionState};
 
     use crate::{
-        generic_polling_state_checker::GenericPollingStateChecker,
+        generic_polling_state_checker::{last_bytes, GenericPollingStateChecker},
         runtime_connectors::{MockRuntimeStateGetter, StateChecker},
     };
 
@@ -148,4 +217,60 @@ mod tests {
         let state_update_1 = state_receiver.recv().await.unwrap();
         assert_eq!(state_update_1, expected_state);
     }
+
+    // [utest->swdd~generic-state-checker-attaches-captured-output~1]
+    #[tokio::test]
+    async fn utest_generic_polling_state_checker_attaches_captured_output() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_runtime_getter = MockRuntimeStateGetter::default();
+        mock_runtime_getter
+            .expect_get_state()
+            .returning(|_: &String| Box::pin(async { ExecutionState::failed("Exit code: '1'") }));
+        mock_runtime_getter
+            .expect_get_output()
+            .once()
+            .returning(|_: &String| Box::pin(async { Some("line 1\nresult: 42\n".to_string()) }));
+
+        let (state_sender, mut state_receiver) = tokio::sync::mpsc::channel(20);
+
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+        workload_spec.capture_output_bytes = Some(10);
+
+        let generic_state_state_checker = GenericPollingStateChecker::start_checker(
+            &workload_spec,
+            WORKLOAD_ID.to_string(),
+            state_sender.clone(),
+            mock_runtime_getter,
+        );
+
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+
+        <GenericPollingStateChecker as StateChecker<String>>::stop_checker::<'_>(
+            generic_state_state_checker,
+        )
+        .await;
+
+        let expected_state = common::objects::generate_test_workload_state_with_workload_spec(
+            &workload_spec,
+            ExecutionState::failed("Exit code: '1'\nresult: 42"),
+        );
+
+        let state_update_1 = state_receiver.recv().await.unwrap();
+        assert_eq!(state_update_1, expected_state);
+    }
+
+    // [utest->swdd~generic-state-checker-attaches-captured-output~1]
+    #[test]
+    fn utest_last_bytes_does_not_split_characters() {
+        assert_eq!(last_bytes("result: 42", 2), "42");
+        assert_eq!(last_bytes("result: 42", 100), "result: 42");
+        assert_eq!(last_bytes("größe", 2), "e");
+    }
 }
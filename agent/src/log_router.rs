@@ -0,0 +1,325 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    net::UdpSocket,
+    os::unix::net::UnixDatagram,
+    path::{Path, PathBuf},
+};
+
+use common::objects::{LogRoute, LogSink};
+
+use crate::runtime_connectors::{LogLine, LogLineReceiver, LogStream};
+
+const JOURNALD_SOCKET: &str = "/run/systemd/journal/socket";
+const DEFAULT_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+const DEFAULT_MAX_FILES: u32 = 3;
+
+trait LogWriter: Send {
+    fn write_line(&mut self, log_line: &LogLine) -> io::Result<()>;
+}
+
+// The syslog severities "informational" for stdout and "error" for stderr.
+fn severity(stream: LogStream) -> u8 {
+    match stream {
+        LogStream::Stdout => 6,
+        LogStream::Stderr => 3,
+    }
+}
+
+// [impl->swdd~agent-forwards-workload-logs-to-journald~1]
+struct JournaldWriter {
+    socket: UnixDatagram,
+    identifier: String,
+}
+
+impl JournaldWriter {
+    fn new(workload_name: &str) -> io::Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        socket.connect(JOURNALD_SOCKET)?;
+        Ok(JournaldWriter {
+            socket,
+            identifier: workload_name.to_owned(),
+        })
+    }
+
+    // Encodes the entry in the native journal protocol. Values containing a newline
+    // are sent in the binary form prefixed with their length.
+    fn format(identifier: &str, log_line: &LogLine) -> Vec<u8> {
+        let mut entry = Vec::new();
+        let priority = severity(log_line.stream).to_string();
+        for (key, value) in [
+            ("SYSLOG_IDENTIFIER", identifier),
+            ("PRIORITY", priority.as_str()),
+            ("MESSAGE", log_line.line.as_str()),
+        ] {
+            entry.extend_from_slice(key.as_bytes());
+            if value.contains('\n') {
+                entry.push(b'\n');
+                entry.extend_from_slice(&(value.len() as u64).to_le_bytes());
+            } else {
+                entry.push(b'=');
+            }
+            entry.extend_from_slice(value.as_bytes());
+            entry.push(b'\n');
+        }
+        entry
+    }
+}
+
+impl LogWriter for JournaldWriter {
+    fn write_line(&mut self, log_line: &LogLine) -> io::Result<()> {
+        self.socket
+            .send(&Self::format(&self.identifier, log_line))
+            .map(|_| ())
+    }
+}
+
+enum SyslogSocket {
+    Udp(UdpSocket),
+    Unix(UnixDatagram),
+}
+
+// [impl->swdd~agent-forwards-workload-logs-to-syslog~1]
+struct SyslogWriter {
+    socket: SyslogSocket,
+    app_name: String,
+}
+
+impl SyslogWriter {
+    // A target starting with '/' is a local syslog socket, otherwise a 'host:port' UDP endpoint.
+    fn new(workload_name: &str, target: &str) -> io::Result<Self> {
+        let socket = if target.starts_with('/') {
+            let socket = UnixDatagram::unbound()?;
+            socket.connect(target)?;
+            SyslogSocket::Unix(socket)
+        } else {
+            let socket = UdpSocket::bind("0.0.0.0:0")?;
+            socket.connect(target)?;
+            SyslogSocket::Udp(socket)
+        };
+        Ok(SyslogWriter {
+            socket,
+            app_name: workload_name.to_owned(),
+        })
+    }
+
+    // RFC 5424 message with the facility "user" and without timestamp and hostname,
+    // which are filled in by the syslog daemon.
+    fn format(app_name: &str, log_line: &LogLine) -> String {
+        const FACILITY_USER: u8 = 1;
+        format!(
+            "<{}>1 - - {} - - - {}",
+            FACILITY_USER * 8 + severity(log_line.stream),
+            app_name,
+            log_line.line
+        )
+    }
+}
+
+impl LogWriter for SyslogWriter {
+    fn write_line(&mut self, log_line: &LogLine) -> io::Result<()> {
+        let message = Self::format(&self.app_name, log_line);
+        match &self.socket {
+            SyslogSocket::Udp(socket) => socket.send(message.as_bytes()),
+            SyslogSocket::Unix(socket) => socket.send(message.as_bytes()),
+        }
+        .map(|_| ())
+    }
+}
+
+// [impl->swdd~agent-forwards-workload-logs-to-rotating-files~1]
+struct RotatingFileWriter {
+    path: PathBuf,
+    max_file_size: u64,
+    max_files: u32,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFileWriter {
+    fn new(path: &Path, max_file_size: u64, max_files: u32) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(RotatingFileWriter {
+            path: path.to_path_buf(),
+            max_file_size: if max_file_size == 0 {
+                DEFAULT_MAX_FILE_SIZE
+            } else {
+                max_file_size
+            },
+            max_files: if max_files == 0 {
+                DEFAULT_MAX_FILES
+            } else {
+                max_files
+            },
+            file,
+            size,
+        })
+    }
+
+    fn rotated_path(&self, index: u32) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        path.into()
+    }
+
+    // Shifts 'path.1' .. 'path.N-1' to 'path.2' .. 'path.N' dropping the oldest file
+    // and continues with an empty file at 'path'.
+    fn rotate(&mut self) -> io::Result<()> {
+        for index in (1..self.max_files).rev() {
+            let rotated_path = self.rotated_path(index);
+            if rotated_path.exists() {
+                fs::rename(rotated_path, self.rotated_path(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated_path(1))?;
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl LogWriter for RotatingFileWriter {
+    fn write_line(&mut self, log_line: &LogLine) -> io::Result<()> {
+        let line_size = log_line.line.len() as u64 + 1;
+        if self.size > 0 && self.size + line_size > self.max_file_size {
+            self.rotate()?;
+        }
+        writeln!(self.file, "{}", log_line.line)?;
+        self.size += line_size;
+        Ok(())
+    }
+}
+
+fn open_writer(workload_name: &str, route: &LogRoute) -> io::Result<Box<dyn LogWriter>> {
+    Ok(match route.sink {
+        LogSink::Journald => Box::new(JournaldWriter::new(workload_name)?),
+        LogSink::Syslog => Box::new(SyslogWriter::new(workload_name, &route.target)?),
+        LogSink::File => Box::new(RotatingFileWriter::new(
+            Path::new(&route.target),
+            route.max_file_size,
+            route.max_files,
+        )?),
+    })
+}
+
+// [impl->swdd~agent-forwards-workload-logs~1]
+pub fn forward_logs(workload_name: String, routes: &[LogRoute], mut receiver: LogLineReceiver) {
+    let mut writers: Vec<Box<dyn LogWriter>> = routes
+        .iter()
+        .filter_map(|route| {
+            open_writer(&workload_name, route)
+                .map_err(|err| {
+                    log::warn!(
+                        "Could not open the log sink '{:?}' of workload '{}': '{}'",
+                        route.sink,
+                        workload_name,
+                        err
+                    )
+                })
+                .ok()
+        })
+        .collect();
+
+    if writers.is_empty() {
+        return;
+    }
+
+    // The sinks are written synchronously, hence the forwarding runs on the blocking pool.
+    // It ends when the runtime closes the log stream of the workload.
+    tokio::task::spawn_blocking(move || {
+        while let Some(log_line) = receiver.blocking_recv() {
+            for writer in writers.iter_mut() {
+                if let Err(err) = writer.write_line(&log_line) {
+                    log::warn!(
+                        "Could not forward a log line of workload '{}': '{}'",
+                        workload_name,
+                        err
+                    );
+                }
+            }
+        }
+        log::debug!("Stopped forwarding the logs of workload '{}'", workload_name);
+    });
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{JournaldWriter, LogWriter, RotatingFileWriter, SyslogWriter};
+    use crate::runtime_connectors::{LogLine, LogStream};
+
+    fn log_line(stream: LogStream, line: &str) -> LogLine {
+        LogLine {
+            stream,
+            line: line.to_owned(),
+        }
+    }
+
+    // [utest->swdd~agent-forwards-workload-logs-to-syslog~1]
+    #[test]
+    fn utest_syslog_writer_formats_rfc5424_message_with_stream_severity() {
+        assert_eq!(
+            SyslogWriter::format("workload_A", &log_line(LogStream::Stdout, "hello")),
+            "<14>1 - - workload_A - - - hello"
+        );
+        assert_eq!(
+            SyslogWriter::format("workload_A", &log_line(LogStream::Stderr, "failed")),
+            "<11>1 - - workload_A - - - failed"
+        );
+    }
+
+    // [utest->swdd~agent-forwards-workload-logs-to-journald~1]
+    #[test]
+    fn utest_journald_writer_formats_native_protocol_entry() {
+        assert_eq!(
+            JournaldWriter::format("workload_A", &log_line(LogStream::Stderr, "failed")),
+            b"SYSLOG_IDENTIFIER=workload_A\nPRIORITY=3\nMESSAGE=failed\n".to_vec()
+        );
+    }
+
+    // [utest->swdd~agent-forwards-workload-logs-to-rotating-files~1]
+    #[test]
+    fn utest_rotating_file_writer_rotates_and_drops_oldest_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("workload.log");
+        let mut writer = RotatingFileWriter::new(&path, 6, 2).unwrap();
+
+        for line in ["one", "two", "three", "four"] {
+            writer.write_line(&log_line(LogStream::Stdout, line)).unwrap();
+        }
+
+        let read = |name: &str| fs::read_to_string(dir.path().join(name)).unwrap();
+        assert_eq!(read("workload.log"), "four\n");
+        assert_eq!(read("workload.log.1"), "three\n");
+        assert_eq!(read("workload.log.2"), "two\n");
+        assert!(!dir.path().join("workload.log.3").exists());
+    }
+}
@@ -13,15 +13,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use common::communications_client::CommunicationsClient;
+use common::input_limits::InputLimits;
 use common::objects::{AgentName, WorkloadState};
-use common::to_server_interface::ToServer;
-use generic_polling_state_checker::GenericPollingStateChecker;
-use std::collections::HashMap;
-use tokio::try_join;
+use common::to_server_interface::{ToServer, ToServerInterface};
+use tokio::select;
 
+mod agent_config;
 mod agent_manager;
+mod agent_metrics;
 mod cli;
 mod control_interface;
+mod dry_run;
+mod log_router;
+mod process_priority;
+mod resource_monitor;
 mod runtime_connectors;
 #[cfg(test)]
 pub mod test_helper;
@@ -37,17 +42,40 @@ use common::from_server_interface::FromServer;
 use common::std_extensions::{GracefulExitResult, IllegalStateResult, UnreachableResult};
 use grpc::client::GRPCCommunicationsClient;
 
+use agent_config::AgentConfig;
 use agent_manager::AgentManager;
+use agent_metrics::{
+    publish_subsystem_health, AGENT_MANAGER_SUBSYSTEM, SERVER_CONNECTION_SUBSYSTEM,
+};
 
 #[cfg_attr(test, mockall_double::double)]
 use crate::runtime_manager::RuntimeManager;
-use runtime_connectors::{
-    podman::{PodmanRuntime, PodmanWorkloadId},
-    podman_kube::{PodmanKubeRuntime, PodmanKubeWorkloadId},
-    GenericRuntimeFacade, RuntimeConnector, RuntimeFacade,
-};
+use runtime_connectors::RuntimeRegistry;
+use workload::workload_hooks::AllowedHooks;
+use workload_scheduler::queue_storage::QueueStorage;
+use workload_state::workload_state_store::LAST_KNOWN_STATES_FILE_NAME;
 
 const BUFFER_SIZE: usize = 20;
+const GOODBYE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+const METRICS_EXPORT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+const RESOURCE_REPORT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+
+// [impl->swdd~agent-tracks-heap-usage~1]
+#[cfg(feature = "memory_profiling")]
+#[global_allocator]
+static ALLOCATOR: common::memory_profiling::TrackingAllocator =
+    common::memory_profiling::TrackingAllocator;
+#[cfg(feature = "memory_profiling")]
+const MEMORY_REPORT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+async fn wait_for_shutdown_signal() {
+    let mut terminate = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+        .unwrap_or_illegal_state();
+    select! {
+        _ = tokio::signal::ctrl_c() => log::debug!("Received SIGINT"),
+        _ = terminate.recv() => log::debug!("Received SIGTERM"),
+    }
+}
 
 #[tokio::main]
 async fn main() {
@@ -55,6 +83,13 @@ async fn main() {
 
     let args = cli::parse();
 
+    // [impl->swdd~common-configurable-input-limits~1]
+    InputLimits::configure(InputLimits {
+        max_runtime_config_length: args.max_runtime_config_length,
+        max_runtime_config_depth: args.max_runtime_config_depth,
+        ..Default::default()
+    });
+
     log::debug!(
         "Starting the Ankaios agent with \n\tname: '{}', \n\tserver url: '{}', \n\trun directory: '{}'",
         args.agent_name,
@@ -68,43 +103,160 @@ async fn main() {
     let (workload_state_sender, workload_state_receiver) =
         tokio::sync::mpsc::channel::<WorkloadState>(BUFFER_SIZE);
 
+    // [impl->swdd~agent-tracks-heap-usage~1]
+    #[cfg(feature = "memory_profiling")]
+    {
+        use common::memory_profiling::{report_periodically, ChannelUsage};
+        // weak senders do not keep the channels open on shutdown
+        let to_manager = to_manager.downgrade();
+        let to_server = to_server.downgrade();
+        let workload_state_sender = workload_state_sender.downgrade();
+        tokio::spawn(report_periodically(MEMORY_REPORT_INTERVAL, move || {
+            [
+                to_manager
+                    .upgrade()
+                    .map(|sender| ChannelUsage::of("to agent manager", &sender)),
+                to_server
+                    .upgrade()
+                    .map(|sender| ChannelUsage::of("to server", &sender)),
+                workload_state_sender
+                    .upgrade()
+                    .map(|sender| ChannelUsage::of("workload states", &sender)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect()
+        }));
+    }
+
+    // [impl->swdd~agent-exports-metrics-to-file~1]
+    if let Some(metrics_file) = &args.metrics_file {
+        tokio::spawn(agent_metrics::export_periodically(
+            metrics_file.into(),
+            METRICS_EXPORT_INTERVAL,
+        ));
+    }
+
     let run_directory = args
         .get_run_directory()
         .unwrap_or_exit("Run folder creation failed. Cannot continue without run folder.");
 
-    // [impl->swdd~agent-supports-podman~2]
-    let podman_runtime = Box::new(PodmanRuntime {});
-    let podman_runtime_name = podman_runtime.name();
-    let podman_facade = Box::new(GenericRuntimeFacade::<
-        PodmanWorkloadId,
-        GenericPollingStateChecker,
-    >::new(podman_runtime));
-    let mut runtime_facade_map: HashMap<String, Box<dyn RuntimeFacade>> = HashMap::new();
-    runtime_facade_map.insert(podman_runtime_name, podman_facade);
-
-    // [impl->swdd~agent-supports-podman-kube-runtime~1]
-    let podman_kube_runtime = Box::new(PodmanKubeRuntime {});
-    let podman_kube_runtime_name = podman_kube_runtime.name();
-    let podman_kube_facade = Box::new(GenericRuntimeFacade::<
-        PodmanKubeWorkloadId,
-        GenericPollingStateChecker,
-    >::new(podman_kube_runtime));
-    runtime_facade_map.insert(podman_kube_runtime_name, podman_kube_facade);
+    // [impl->swdd~agent-loads-local-workloads-from-config~1]
+    let agent_config = match &args.config {
+        Some(config_path) => {
+            AgentConfig::from_file(std::path::Path::new(config_path), &args.agent_name)
+                .unwrap_or_exit("Cannot continue with an invalid agent config")
+        }
+        None => AgentConfig::default(),
+    };
+
+    // [impl->swdd~agent-applies-configured-process-priority~1]
+    if agent_config.agent_priority.is_configured() {
+        agent_config
+            .agent_priority
+            .apply_to_own_process()
+            .unwrap_or_exit("Cannot apply the configured agent priority");
+    }
+    process_priority::set_state_checker_priority(agent_config.state_checker_priority.clone());
+
+    // [impl->swdd~agent-verifies-podman-storage~1]
+    agent_config
+        .podman_storage
+        .prepare()
+        .unwrap_or_exit("Cannot continue with an unusable podman storage");
+    // [impl->swdd~agent-passes-podman-storage-to-all-podman-calls~1]
+    runtime_connectors::set_podman_storage(&agent_config.podman_storage);
+
+    // [impl->swdd~agent-fails-over-to-fallback-servers~1]
+    let server_urls = args.get_server_urls();
+
+    // [impl->swdd~agent-registers-compiled-in-runtimes~1]
+    // [impl->swdd~agent-limits-concurrent-operations-per-runtime~1]
+    let runtime_registry = RuntimeRegistry::with_compiled_in_runtimes(
+        &run_directory.get_path(),
+        &agent_config.runtime_concurrency,
+        // [impl->swdd~agent-executes-only-allowed-hooks~1]
+        AllowedHooks::new(args.allowed_hooks),
+    );
+    let runtimes = runtime_registry.runtime_names();
+    let runtime_facade_map = runtime_registry.into_facades();
+
+    let mut grpc_communications_client = GRPCCommunicationsClient::new_agent_communication(
+        args.agent_name.clone(),
+        server_urls,
+        args.rollout_group,
+        runtimes,
+        args.attributes.into_iter().collect(),
+        // [impl->swdd~agent-reports-local-workloads~1]
+        agent_config.local_workloads.clone(),
+    );
+
+    // [impl->swdd~agent-dry-run-validates-assigned-workloads~1]
+    if args.dry_run {
+        let mut manager_receiver = manager_receiver;
+        let mut communications_task = tokio::spawn(async move {
+            grpc_communications_client
+                .run(server_receiver, to_manager.clone())
+                .await
+        });
+
+        let assigned_workloads = select! {
+            assigned_workloads = dry_run::receive_assigned_workloads(&mut manager_receiver) => assigned_workloads,
+            _ = wait_for_shutdown_signal() => None,
+        };
+
+        let success = match assigned_workloads {
+            Some(workloads) => {
+                let report =
+                    dry_run::dry_run_workloads(&args.agent_name, workloads, &runtime_facade_map)
+                        .await;
+                print!("{report}");
+                report.is_success()
+            }
+            None => {
+                log::error!("Dry run aborted before the assigned workloads were received.");
+                false
+            }
+        };
+
+        to_server.stop().await.unwrap_or_illegal_state();
+        if tokio::time::timeout(GOODBYE_TIMEOUT, &mut communications_task)
+            .await
+            .is_err()
+        {
+            log::warn!("Could not say goodbye to the server in time.");
+        }
+        drop(run_directory);
+        std::process::exit(if success { 0 } else { 1 });
+    }
 
     // The RuntimeManager currently directly gets the server ToServerInterface, but it shall get the agent manager interface
     // This is needed to be able to filter/authorize the commands towards the Ankaios server
     // The pipe connecting the workload to Ankaios must be in the runtime adapter
-    let runtime_manager = RuntimeManager::new(
+    let mut runtime_manager = RuntimeManager::new(
         AgentName::from(args.agent_name.as_str()),
         run_directory.get_path(),
         to_server.clone(),
         runtime_facade_map,
         workload_state_sender,
     );
+    // [impl->swdd~agent-restores-pending-workload-operations~1]
+    runtime_manager.restore_pending_workload_operations(agent_config.persistence_format);
+    // [impl->swdd~agent-starts-local-workloads~1]
+    runtime_manager
+        .start_local_workloads(agent_config.local_workload_specs())
+        .await;
 
-    let mut grpc_communications_client =
-        GRPCCommunicationsClient::new_agent_communication(args.agent_name.clone(), args.server_url);
+    // [impl->swdd~agent-reports-free-resources~1]
+    // the weak sender does not keep the channel to the server open on shutdown
+    tokio::spawn(resource_monitor::report_periodically(
+        resource_monitor::ResourceMonitor::new(),
+        args.agent_name.clone(),
+        to_server.downgrade(),
+        RESOURCE_REPORT_INTERVAL,
+    ));
 
+    let shutdown_to_server = to_server.clone();
     let mut agent_manager = AgentManager::new(
         args.agent_name,
         manager_receiver,
@@ -112,18 +264,75 @@ async fn main() {
         to_server,
         workload_state_receiver,
     );
+    // [impl->swdd~agent-enters-degraded-mode-after-disconnect-threshold~1]
+    agent_manager
+        .set_disconnect_threshold(std::time::Duration::from_secs(args.disconnect_threshold));
+    // [impl->swdd~agent-restores-last-known-workload-states~1]
+    agent_manager.restore_last_known_workload_states(QueueStorage::new(
+        run_directory.get_path().join(LAST_KNOWN_STATES_FILE_NAME),
+        agent_config.persistence_format,
+    ));
 
+    // [impl->swdd~agent-reports-subsystem-health~1]
+    publish_subsystem_health(SERVER_CONNECTION_SUBSYSTEM, false);
+    publish_subsystem_health(AGENT_MANAGER_SUBSYSTEM, true);
     let manager_task = tokio::spawn(async move { agent_manager.start().await });
     // [impl->swdd~agent-sends-hello~1]
     // [impl->swdd~agent-default-communication-grpc~1]
-    let communications_task = tokio::spawn(async move {
+    let mut communications_task = tokio::spawn(async move {
         grpc_communications_client
             .run(server_receiver, to_manager.clone())
             .await
     });
 
-    let (_, communication_task_result) =
-        try_join!(manager_task, communications_task).unwrap_or_illegal_state();
+    // [impl->swdd~agent-exits-on-failed-subsystem~1]
+    let (shutdown_requested, failed_subsystem) = select! {
+        result = manager_task => match result {
+            Ok(()) => (false, None),
+            Err(err) => {
+                log::error!("The agent manager failed: '{err}'");
+                (true, Some(AGENT_MANAGER_SUBSYSTEM))
+            }
+        },
+        result = &mut communications_task => match result {
+            Ok(result) => {
+                result.unwrap_or_unreachable();
+                (false, None)
+            }
+            Err(err) => {
+                log::error!("The connection to the server failed: '{err}'");
+                (false, Some(SERVER_CONNECTION_SUBSYSTEM))
+            }
+        },
+        _ = wait_for_shutdown_signal() => (true, None),
+    };
+
+    // [impl->swdd~agent-says-goodbye-on-shutdown~1]
+    if shutdown_requested {
+        log::info!("Shutting down the Ankaios agent.");
+        shutdown_to_server.stop().await.unwrap_or_illegal_state();
+        // the communications task completes after the goodbye has been sent to the server,
+        // but the server might not be reachable at all
+        if tokio::time::timeout(GOODBYE_TIMEOUT, communications_task)
+            .await
+            .is_err()
+        {
+            log::warn!("Could not say goodbye to the server in time.");
+        }
+    }
 
-    communication_task_result.unwrap_or_unreachable();
+    // [impl->swdd~agent-exits-on-failed-subsystem~1]
+    if let Some(subsystem) = failed_subsystem {
+        // [impl->swdd~agent-reports-subsystem-health~1]
+        publish_subsystem_health(subsystem, false);
+        if let Some(metrics_file) = &args.metrics_file {
+            if let Err(err) = agent_metrics::write_metrics_file(std::path::Path::new(metrics_file))
+            {
+                log::warn!("Could not export the agent metrics to '{metrics_file}': {err}");
+            }
+        }
+        log::error!("Exiting the Ankaios agent after the '{subsystem}' subsystem failed.");
+        drop(run_directory);
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,243 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use nix::libc;
+use serde::Deserialize;
+
+const MIN_NICE: i32 = -20;
+const MAX_NICE: i32 = 19;
+const MAX_IO_LEVEL: u8 = 7;
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+const IOPRIO_CLASS_SHIFT: u32 = 13;
+const CGROUP_PROCS_FILE: &str = "cgroup.procs";
+const OWN_THREADS_FOLDER: &str = "/proc/self/task";
+
+static STATE_CHECKER_PRIORITY: OnceLock<ProcessPriority> = OnceLock::new();
+
+// The values are the IO scheduling classes of the Linux kernel.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum IoClass {
+    Realtime = 1,
+    BestEffort = 2,
+    Idle = 3,
+}
+
+// [impl->swdd~agent-applies-configured-process-priority~1]
+#[derive(Debug, Default, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ProcessPriority {
+    pub nice: Option<i32>,
+    pub io_class: Option<IoClass>,
+    pub io_level: Option<u8>,
+    pub cgroup: Option<PathBuf>,
+}
+
+impl ProcessPriority {
+    pub fn verify(&self) -> Result<(), String> {
+        if let Some(nice) = self.nice {
+            if !(MIN_NICE..=MAX_NICE).contains(&nice) {
+                return Err(format!(
+                    "The nice value {nice} is not between {MIN_NICE} and {MAX_NICE}."
+                ));
+            }
+        }
+        if let Some(io_level) = self.io_level {
+            if self.io_class.is_none() {
+                return Err("The IO level requires an IO class.".to_string());
+            }
+            if io_level > MAX_IO_LEVEL {
+                return Err(format!(
+                    "The IO level {io_level} is not between 0 and {MAX_IO_LEVEL}."
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_configured(&self) -> bool {
+        *self != ProcessPriority::default()
+    }
+
+    /// Applies the priority to all threads of the agent process.
+    ///
+    /// The nice value and the IO class are attributes of the threads on Linux. The threads
+    /// already started, e.g. the ones of the async runtime, are therefore changed one by one.
+    /// Threads started later inherit the priority.
+    ///
+    pub fn apply_to_own_process(&self) -> Result<(), String> {
+        if let Some(cgroup) = &self.cgroup {
+            move_to_cgroup(cgroup, std::process::id())?;
+        }
+        let threads = fs::read_dir(OWN_THREADS_FOLDER)
+            .map_err(|err| format!("Could not list the threads of the agent: '{err}'"))?;
+        for thread_id in threads
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+        {
+            self.apply_scheduling(thread_id)?;
+        }
+        Ok(())
+    }
+
+    /// Applies the priority to a child process started by the agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `pid` - The process id of the child process
+    ///
+    pub fn apply_to_child(&self, pid: u32) -> Result<(), String> {
+        if let Some(cgroup) = &self.cgroup {
+            move_to_cgroup(cgroup, pid)?;
+        }
+        self.apply_scheduling(pid)
+    }
+
+    fn apply_scheduling(&self, id: u32) -> Result<(), String> {
+        if let Some(nice) = self.nice {
+            // SAFETY: setpriority only reads its integer arguments.
+            let result = unsafe { libc::setpriority(libc::PRIO_PROCESS, id, nice) };
+            if result != 0 {
+                return Err(format!(
+                    "Could not set the nice value {nice}: '{}'",
+                    io::Error::last_os_error()
+                ));
+            }
+        }
+        if let Some(io_class) = self.io_class {
+            let io_level = self.io_level.unwrap_or_default() as libc::c_int;
+            let io_priority = ((io_class as libc::c_int) << IOPRIO_CLASS_SHIFT) | io_level;
+            // SAFETY: ioprio_set only reads its integer arguments.
+            let result = unsafe {
+                libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, id, io_priority)
+            };
+            if result != 0 {
+                return Err(format!(
+                    "Could not set the IO class {io_class:?}: '{}'",
+                    io::Error::last_os_error()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn move_to_cgroup(cgroup: &Path, pid: u32) -> Result<(), String> {
+    fs::write(cgroup.join(CGROUP_PROCS_FILE), pid.to_string()).map_err(|err| {
+        format!(
+            "Could not move the process {pid} to the cgroup '{}': '{err}'",
+            cgroup.display()
+        )
+    })
+}
+
+// [impl->swdd~agent-applies-configured-process-priority~1]
+pub fn set_state_checker_priority(priority: ProcessPriority) {
+    if STATE_CHECKER_PRIORITY.set(priority).is_err() {
+        log::warn!("The priority of the state checkers is already set.");
+    }
+}
+
+pub fn state_checker_priority() -> Option<&'static ProcessPriority> {
+    STATE_CHECKER_PRIORITY
+        .get()
+        .filter(|priority| priority.is_configured())
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{IoClass, ProcessPriority};
+
+    // [utest->swdd~agent-applies-configured-process-priority~1]
+    #[test]
+    fn utest_process_priority_verify() {
+        assert!(ProcessPriority::default().verify().is_ok());
+        assert!(ProcessPriority {
+            nice: Some(19),
+            io_class: Some(IoClass::BestEffort),
+            io_level: Some(7),
+            cgroup: None,
+        }
+        .verify()
+        .is_ok());
+        assert!(ProcessPriority {
+            nice: Some(20),
+            ..Default::default()
+        }
+        .verify()
+        .is_err());
+        assert!(ProcessPriority {
+            io_level: Some(4),
+            ..Default::default()
+        }
+        .verify()
+        .is_err());
+        assert!(ProcessPriority {
+            io_class: Some(IoClass::Idle),
+            io_level: Some(8),
+            ..Default::default()
+        }
+        .verify()
+        .is_err());
+    }
+
+    // [utest->swdd~agent-applies-configured-process-priority~1]
+    #[test]
+    fn utest_process_priority_moves_child_to_cgroup() {
+        let cgroup = tempfile::tempdir().unwrap();
+        let priority = ProcessPriority {
+            cgroup: Some(cgroup.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        priority.apply_to_child(1234).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(cgroup.path().join("cgroup.procs")).unwrap(),
+            "1234"
+        );
+    }
+
+    // [utest->swdd~agent-applies-configured-process-priority~1]
+    #[tokio::test]
+    async fn utest_process_priority_sets_nice_of_child() {
+        let mut child = tokio::process::Command::new("sleep")
+            .arg("1")
+            .spawn()
+            .unwrap();
+        let priority = ProcessPriority {
+            nice: Some(19),
+            ..Default::default()
+        };
+
+        assert!(priority.apply_to_child(child.id().unwrap()).is_ok());
+        let _ = child.kill().await;
+    }
+}
@@ -0,0 +1,317 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fs, path::PathBuf, thread, time::Duration};
+
+use common::{
+    objects::AgentResources,
+    to_server_interface::{ToServer, ToServerInterface},
+};
+use tokio::sync::mpsc::WeakSender;
+
+const MEMINFO_FILE: &str = "/proc/meminfo";
+const STAT_FILE: &str = "/proc/stat";
+const MEM_AVAILABLE_KEY: &str = "MemAvailable:";
+const CPU_TIMES_KEY: &str = "cpu";
+// user, nice, system, idle, iowait, irq, softirq and steal, the guest times are part of user
+const CPU_TIME_FIELDS: usize = 8;
+const IDLE_FIELD: usize = 3;
+const IOWAIT_FIELD: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FreeResources {
+    pub cpu_millis: u64,
+    pub memory_bytes: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct CpuTimes {
+    idle: u64,
+    total: u64,
+}
+
+// [impl->swdd~agent-monitors-free-resources~1]
+// Collects the free resources of the host of the agent from the Linux proc filesystem.
+pub struct ResourceMonitor {
+    meminfo_file: PathBuf,
+    stat_file: PathBuf,
+    cpu_count: u64,
+    last_cpu_times: Option<CpuTimes>,
+    last_free_cpu_millis: u64,
+}
+
+impl Default for ResourceMonitor {
+    fn default() -> Self {
+        let cpu_count = thread::available_parallelism()
+            .map(|cpu_count| cpu_count.get() as u64)
+            .unwrap_or(1);
+        Self::with_files(MEMINFO_FILE.into(), STAT_FILE.into(), cpu_count)
+    }
+}
+
+impl ResourceMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_files(meminfo_file: PathBuf, stat_file: PathBuf, cpu_count: u64) -> Self {
+        ResourceMonitor {
+            meminfo_file,
+            stat_file,
+            cpu_count,
+            last_cpu_times: None,
+            last_free_cpu_millis: 0,
+        }
+    }
+
+    // The free cpu is the idle share of the cpu time since the previous call, or since the boot
+    // on the first call. Returns None if the free resources cannot be determined.
+    pub fn free_resources(&mut self) -> Option<FreeResources> {
+        let memory_bytes = match fs::read_to_string(&self.meminfo_file) {
+            Ok(meminfo) => parse_available_memory(&meminfo)?,
+            Err(err) => {
+                log::debug!("Could not read the available memory: '{}'", err);
+                return None;
+            }
+        };
+        let cpu_times = match fs::read_to_string(&self.stat_file) {
+            Ok(stat) => parse_cpu_times(&stat)?,
+            Err(err) => {
+                log::debug!("Could not read the cpu times: '{}'", err);
+                return None;
+            }
+        };
+
+        let previous_cpu_times = self.last_cpu_times.replace(cpu_times).unwrap_or_default();
+        let total = cpu_times.total.saturating_sub(previous_cpu_times.total);
+        let idle = cpu_times.idle.saturating_sub(previous_cpu_times.idle);
+        // no cpu time has passed since the previous call
+        if let Some(free_cpu_millis) = (idle * self.cpu_count * 1000).checked_div(total) {
+            self.last_free_cpu_millis = free_cpu_millis;
+        }
+
+        Some(FreeResources {
+            cpu_millis: self.last_free_cpu_millis,
+            memory_bytes,
+        })
+    }
+}
+
+// [impl->swdd~agent-reports-free-resources~1]
+/// Reports the free resources of the host to the server in the given interval until the channel
+/// to the server is closed. A report is skipped while the previous messages to the server are
+/// still waiting, e.g. as the agent is disconnected, such that outdated reports do not pile up.
+pub async fn report_periodically(
+    mut resource_monitor: ResourceMonitor,
+    agent_name: String,
+    to_server: WeakSender<ToServer>,
+    interval: Duration,
+) {
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        interval.tick().await;
+        let Some(to_server) = to_server.upgrade() else {
+            return;
+        };
+        if to_server.capacity() < to_server.max_capacity() {
+            continue;
+        }
+        let Some(free_resources) = resource_monitor.free_resources() else {
+            continue;
+        };
+        let resources = AgentResources {
+            free_cpu_millis: free_resources.cpu_millis,
+            free_memory_bytes: free_resources.memory_bytes,
+        };
+        if to_server
+            .update_agent_resources(agent_name.clone(), resources)
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+fn parse_available_memory(meminfo: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix(MEM_AVAILABLE_KEY))
+        .and_then(|value| value.trim().strip_suffix("kB"))
+        .and_then(|kilobytes| kilobytes.trim().parse::<u64>().ok())
+        .map(|kilobytes| kilobytes * 1024)
+}
+
+fn parse_cpu_times(stat: &str) -> Option<CpuTimes> {
+    let fields = stat.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        (fields.next() == Some(CPU_TIMES_KEY)).then_some(fields)
+    })?;
+    let times = fields
+        .take(CPU_TIME_FIELDS)
+        .map(str::parse::<u64>)
+        .collect::<Result<Vec<u64>, _>>()
+        .ok()?;
+    if times.len() <= IOWAIT_FIELD {
+        return None;
+    }
+    Some(CpuTimes {
+        idle: times[IDLE_FIELD] + times[IOWAIT_FIELD],
+        total: times.iter().sum(),
+    })
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+pub fn generate_test_resource_monitor(
+    folder: &std::path::Path,
+    free_resources: FreeResources,
+) -> ResourceMonitor {
+    let meminfo_file = folder.join("meminfo");
+    let stat_file = folder.join("stat");
+    fs::write(
+        &meminfo_file,
+        format!(
+            "MemTotal:        8000000 kB\nMemAvailable:    {} kB\n",
+            free_resources.memory_bytes / 1024
+        ),
+    )
+    .unwrap();
+    // a single cpu with the given idle share of 1000 ticks
+    fs::write(
+        &stat_file,
+        format!(
+            "cpu  {} 0 0 {} 0 0 0 0 0 0\n",
+            1000 - free_resources.cpu_millis,
+            free_resources.cpu_millis
+        ),
+    )
+    .unwrap();
+    ResourceMonitor::with_files(meminfo_file, stat_file, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        generate_test_resource_monitor, parse_available_memory, parse_cpu_times,
+        report_periodically, CpuTimes, FreeResources,
+    };
+    use common::{
+        commands::UpdateAgentResources, objects::AgentResources, to_server_interface::ToServer,
+    };
+    use std::{fs, time::Duration};
+
+    const STAT: &str = "cpu  100 10 50 700 40 0 0 0 0 0\ncpu0 50 5 25 350 20 0 0 0 0 0\nintr 1\n";
+
+    // [utest->swdd~agent-monitors-free-resources~1]
+    #[test]
+    fn utest_parse_proc_files() {
+        assert_eq!(
+            parse_available_memory("MemTotal: 2048 kB\nMemFree: 512 kB\nMemAvailable: 1024 kB\n"),
+            Some(1024 * 1024)
+        );
+        assert_eq!(parse_available_memory("MemTotal: 2048 kB\n"), None);
+
+        assert_eq!(
+            parse_cpu_times(STAT),
+            Some(CpuTimes {
+                idle: 740,
+                total: 900
+            })
+        );
+        assert_eq!(parse_cpu_times("cpu  x 10 50\n"), None);
+    }
+
+    // [utest->swdd~agent-monitors-free-resources~1]
+    #[test]
+    fn utest_resource_monitor_reports_idle_cpu_since_previous_call() {
+        let folder = tempfile::tempdir().unwrap();
+        let mut resource_monitor = generate_test_resource_monitor(
+            folder.path(),
+            FreeResources {
+                cpu_millis: 750,
+                memory_bytes: 4096,
+            },
+        );
+        assert_eq!(
+            resource_monitor.free_resources(),
+            Some(FreeResources {
+                cpu_millis: 750,
+                memory_bytes: 4096
+            })
+        );
+
+        // 100 ticks more of which 20 are idle
+        fs::write(folder.path().join("stat"), "cpu  330 0 0 770 0 0 0 0\n").unwrap();
+        assert_eq!(
+            resource_monitor
+                .free_resources()
+                .map(|free| free.cpu_millis),
+            Some(200)
+        );
+        // no ticks have passed, the previous value is kept
+        assert_eq!(
+            resource_monitor
+                .free_resources()
+                .map(|free| free.cpu_millis),
+            Some(200)
+        );
+
+        fs::remove_file(folder.path().join("meminfo")).unwrap();
+        assert_eq!(resource_monitor.free_resources(), None);
+    }
+
+    // [utest->swdd~agent-reports-free-resources~1]
+    #[tokio::test]
+    async fn utest_report_periodically_skips_reports_while_previous_messages_wait() {
+        let folder = tempfile::tempdir().unwrap();
+        let resource_monitor = generate_test_resource_monitor(
+            folder.path(),
+            FreeResources {
+                cpu_millis: 750,
+                memory_bytes: 4096,
+            },
+        );
+        let (to_server, mut server_receiver) = tokio::sync::mpsc::channel(5);
+
+        let report_task = tokio::spawn(report_periodically(
+            resource_monitor,
+            "agent_A".to_string(),
+            to_server.downgrade(),
+            Duration::from_millis(1),
+        ));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(to_server);
+        assert!(report_task.await.is_ok());
+
+        assert_eq!(
+            server_receiver.try_recv(),
+            Ok(ToServer::UpdateAgentResources(UpdateAgentResources {
+                agent_name: "agent_A".to_string(),
+                resources: AgentResources {
+                    free_cpu_millis: 750,
+                    free_memory_bytes: 4096,
+                },
+            }))
+        );
+        assert!(server_receiver.try_recv().is_err());
+    }
+}
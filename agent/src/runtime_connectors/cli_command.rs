@@ -2,12 +2,15 @@ use std::process::Stdio;
 use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 
+use crate::process_priority::ProcessPriority;
+
 #[cfg(test)]
 pub use tests::MockCliCommand;
 
 pub struct CliCommand<'a> {
     command: Command,
     stdin: Option<&'a [u8]>,
+    priority: Option<&'a ProcessPriority>,
 }
 
 impl<'a> CliCommand<'a> {
@@ -16,10 +19,13 @@ impl<'a> CliCommand<'a> {
         command
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .stdin(Stdio::piped());
+            .stdin(Stdio::piped())
+            // a cancelled command shall not keep running in the background
+            .kill_on_drop(true);
         Self {
             command,
             stdin: None,
+            priority: None,
         }
     }
 
@@ -33,12 +39,24 @@ impl<'a> CliCommand<'a> {
         self
     }
 
+    pub fn priority(&mut self, priority: Option<&'a ProcessPriority>) -> &mut Self {
+        self.priority = priority;
+        self
+    }
+
     pub async fn exec(&mut self) -> Result<String, String> {
         let mut child = self
             .command
             .spawn()
             .map_err(|err| format!("Could not execute command: {}", err))?;
 
+        // [impl->swdd~agent-applies-configured-process-priority~1]
+        if let (Some(priority), Some(pid)) = (self.priority, child.id()) {
+            if let Err(err) = priority.apply_to_child(pid) {
+                log::warn!("Could not apply the priority to the command: '{}'", err);
+            }
+        }
+
         if let Some(stdin) = self.stdin {
             child
                 .stdin
@@ -76,6 +94,7 @@ mod tests {
     };
 
     use super::CliCommand;
+    use crate::process_priority::ProcessPriority;
 
     #[tokio::test]
     async fn utest_cli_command_simple_output() {
@@ -96,6 +115,21 @@ mod tests {
         assert!(matches!(result, Ok(x) if x.eq("Hello World\n")));
     }
 
+    // [utest->swdd~agent-applies-configured-process-priority~1]
+    #[tokio::test]
+    async fn utest_cli_command_with_priority() {
+        let priority = ProcessPriority {
+            nice: Some(19),
+            ..Default::default()
+        };
+        let result = CliCommand::new("echo")
+            .args(&["Hello"])
+            .priority(Some(&priority))
+            .exec()
+            .await;
+        assert!(matches!(result, Ok(x) if x.eq("Hello\n")));
+    }
+
     #[tokio::test]
     async fn utest_cli_command_fail_on_not_existing_command() {
         let result = CliCommand::new("non_existing_command").exec().await;
@@ -221,6 +255,10 @@ mod tests {
             self
         }
 
+        pub fn priority(&mut self, _priority: Option<&ProcessPriority>) -> &mut Self {
+            self
+        }
+
         pub async fn exec(&mut self) -> Result<String, String> {
             assert!(self.args.is_empty());
             assert_eq!(self.stdin, None);
@@ -0,0 +1,242 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{path::PathBuf, sync::Arc};
+
+use async_trait::async_trait;
+use common::objects::{AgentName, WorkloadInstanceName, WorkloadSpec, WorkloadState};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use super::{LogLineReceiver, RuntimeConnector, RuntimeError, StateChecker};
+use crate::workload_state::WorkloadStateSender;
+
+// Wraps a runtime connector and limits the number of concurrent calls to it. The clones
+// handed out to the workload control loops share the permits, thus a slow runtime only
+// delays the workloads of that runtime and not the ones of the other runtimes.
+// [impl->swdd~agent-limits-concurrent-operations-per-runtime~1]
+#[derive(Clone)]
+pub struct ConcurrencyLimitedRuntime<R> {
+    runtime: R,
+    permits: Arc<Semaphore>,
+}
+
+impl<R> ConcurrencyLimitedRuntime<R> {
+    pub fn new(runtime: R, max_concurrent_operations: usize) -> Self {
+        ConcurrencyLimitedRuntime {
+            runtime,
+            permits: Arc::new(Semaphore::new(max_concurrent_operations)),
+        }
+    }
+
+    async fn acquire(&self) -> Result<SemaphorePermit<'_>, RuntimeError> {
+        self.permits
+            .acquire()
+            .await
+            .map_err(|err| RuntimeError::internal_with_source("No runtime permit available", err))
+    }
+}
+
+#[async_trait]
+impl<R, WorkloadId, StChecker> RuntimeConnector<WorkloadId, StChecker>
+    for ConcurrencyLimitedRuntime<R>
+where
+    R: RuntimeConnector<WorkloadId, StChecker>,
+    StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    WorkloadId: ToString + Send + Sync + 'static,
+{
+    fn name(&self) -> String {
+        self.runtime.name()
+    }
+
+    async fn get_reusable_workloads(
+        &self,
+        agent_name: &AgentName,
+    ) -> Result<Vec<WorkloadState>, RuntimeError> {
+        let _permit = self.acquire().await?;
+        self.runtime.get_reusable_workloads(agent_name).await
+    }
+
+    async fn create_workload(
+        &self,
+        runtime_workload_config: WorkloadSpec,
+        control_interface_path: Option<PathBuf>,
+        update_state_tx: WorkloadStateSender,
+    ) -> Result<(WorkloadId, StChecker), RuntimeError> {
+        let _permit = self.acquire().await?;
+        self.runtime
+            .create_workload(
+                runtime_workload_config,
+                control_interface_path,
+                update_state_tx,
+            )
+            .await
+    }
+
+    async fn get_workload_id(
+        &self,
+        instance_name: &WorkloadInstanceName,
+    ) -> Result<WorkloadId, RuntimeError> {
+        let _permit = self.acquire().await?;
+        self.runtime.get_workload_id(instance_name).await
+    }
+
+    // The state checker polls on its own, only starting it is limited.
+    async fn start_checker(
+        &self,
+        workload_id: &WorkloadId,
+        runtime_workload_config: WorkloadSpec,
+        update_state_tx: WorkloadStateSender,
+    ) -> Result<StChecker, RuntimeError> {
+        let _permit = self.acquire().await?;
+        self.runtime
+            .start_checker(workload_id, runtime_workload_config, update_state_tx)
+            .await
+    }
+
+    async fn delete_workload(&self, workload_id: &WorkloadId) -> Result<(), RuntimeError> {
+        let _permit = self.acquire().await?;
+        self.runtime.delete_workload(workload_id).await
+    }
+
+    async fn dry_run_workload(
+        &self,
+        runtime_workload_config: &WorkloadSpec,
+    ) -> Result<(), RuntimeError> {
+        let _permit = self.acquire().await?;
+        self.runtime.dry_run_workload(runtime_workload_config).await
+    }
+
+    fn supports_create_cancellation(&self) -> bool {
+        self.runtime.supports_create_cancellation()
+    }
+
+    // Following the logs runs until the workload is gone, it must not occupy a permit.
+    async fn follow_logs(&self, workload_id: &WorkloadId) -> Result<LogLineReceiver, RuntimeError> {
+        self.runtime.follow_logs(workload_id).await
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common::objects::generate_test_workload_spec_with_param;
+    use tokio::sync::mpsc;
+
+    use super::ConcurrencyLimitedRuntime;
+    use crate::runtime_connectors::{
+        test::{MockRuntimeConnector, RuntimeCall},
+        RuntimeConnector,
+    };
+
+    const AGENT_NAME: &str = "agent_A";
+    const RUNTIME_NAME: &str = "mock-runtime";
+    const WORKLOAD_ID: &str = "workload_id_1";
+    const WAIT_TIME: Duration = Duration::from_millis(50);
+
+    // [utest->swdd~agent-limits-concurrent-operations-per-runtime~1]
+    #[tokio::test]
+    async fn utest_concurrency_limited_runtime_waits_for_free_permit() {
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            "workload_1".to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::CreateWorkloadPending(workload_spec.clone(), None),
+                RuntimeCall::DeleteWorkload(WORKLOAD_ID.to_string(), Ok(())),
+            ])
+            .await;
+
+        let runtime = ConcurrencyLimitedRuntime::new(runtime_mock.clone(), 1);
+
+        let create_runtime = runtime.clone();
+        let (state_change_tx, _state_change_rx) = mpsc::channel(1);
+        let create_task = tokio::spawn(async move {
+            create_runtime
+                .create_workload(workload_spec, None, state_change_tx)
+                .await
+        });
+        tokio::time::sleep(WAIT_TIME).await;
+
+        // the pending create holds the only permit
+        assert!(
+            tokio::time::timeout(WAIT_TIME, runtime.delete_workload(&WORKLOAD_ID.to_string()))
+                .await
+                .is_err()
+        );
+
+        create_task.abort();
+        assert!(create_task.await.is_err());
+
+        assert_eq!(
+            runtime.delete_workload(&WORKLOAD_ID.to_string()).await,
+            Ok(())
+        );
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-limits-concurrent-operations-per-runtime~1]
+    #[tokio::test]
+    async fn utest_concurrency_limited_runtime_runs_operations_up_to_the_limit() {
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            "workload_1".to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::CreateWorkloadPending(workload_spec.clone(), None),
+                RuntimeCall::DeleteWorkload(WORKLOAD_ID.to_string(), Ok(())),
+            ])
+            .await;
+
+        let runtime = ConcurrencyLimitedRuntime::new(runtime_mock.clone(), 2);
+        assert_eq!(
+            RuntimeConnector::<String, _>::name(&runtime),
+            RUNTIME_NAME.to_string()
+        );
+
+        let create_runtime = runtime.clone();
+        let (state_change_tx, _state_change_rx) = mpsc::channel(1);
+        let create_task = tokio::spawn(async move {
+            create_runtime
+                .create_workload(workload_spec, None, state_change_tx)
+                .await
+        });
+        tokio::time::sleep(WAIT_TIME).await;
+
+        assert_eq!(
+            tokio::time::timeout(WAIT_TIME, runtime.delete_workload(&WORKLOAD_ID.to_string()))
+                .await,
+            Ok(Ok(()))
+        );
+
+        create_task.abort();
+        runtime_mock.assert_all_expectations().await;
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use common::objects::{AgentName, WorkloadInstanceName, WorkloadSpec, WorkloadState};
+
+use super::{LogLineReceiver, RuntimeConnector, RuntimeError, StateChecker};
+use crate::{
+    agent_metrics::{publish_subsystem_health, runtime_subsystem},
+    workload_state::WorkloadStateSender,
+};
+
+// Wraps a runtime connector and publishes the health of the runtime derived from the results
+// of the calls. Errors caused by a single workload, e.g., a missing image, do not change it.
+// [impl->swdd~agent-reports-subsystem-health~1]
+#[derive(Clone)]
+pub struct HealthReportingRuntime<R> {
+    runtime: R,
+    subsystem: String,
+}
+
+impl<R> HealthReportingRuntime<R> {
+    pub fn new(runtime: R, runtime_name: &str) -> Self {
+        let subsystem = runtime_subsystem(runtime_name);
+        publish_subsystem_health(&subsystem, true);
+        HealthReportingRuntime { runtime, subsystem }
+    }
+
+    fn report<T>(&self, result: Result<T, RuntimeError>) -> Result<T, RuntimeError> {
+        match &result {
+            Ok(_) => publish_subsystem_health(&self.subsystem, true),
+            Err(RuntimeError::Unavailable(_) | RuntimeError::Timeout(_)) => {
+                publish_subsystem_health(&self.subsystem, false)
+            }
+            Err(_) => {}
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<R, WorkloadId, StChecker> RuntimeConnector<WorkloadId, StChecker> for HealthReportingRuntime<R>
+where
+    R: RuntimeConnector<WorkloadId, StChecker>,
+    StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    WorkloadId: ToString + Send + Sync + 'static,
+{
+    fn name(&self) -> String {
+        self.runtime.name()
+    }
+
+    async fn get_reusable_workloads(
+        &self,
+        agent_name: &AgentName,
+    ) -> Result<Vec<WorkloadState>, RuntimeError> {
+        self.report(self.runtime.get_reusable_workloads(agent_name).await)
+    }
+
+    async fn create_workload(
+        &self,
+        runtime_workload_config: WorkloadSpec,
+        control_interface_path: Option<PathBuf>,
+        update_state_tx: WorkloadStateSender,
+    ) -> Result<(WorkloadId, StChecker), RuntimeError> {
+        self.report(
+            self.runtime
+                .create_workload(
+                    runtime_workload_config,
+                    control_interface_path,
+                    update_state_tx,
+                )
+                .await,
+        )
+    }
+
+    async fn get_workload_id(
+        &self,
+        instance_name: &WorkloadInstanceName,
+    ) -> Result<WorkloadId, RuntimeError> {
+        self.report(self.runtime.get_workload_id(instance_name).await)
+    }
+
+    async fn start_checker(
+        &self,
+        workload_id: &WorkloadId,
+        runtime_workload_config: WorkloadSpec,
+        update_state_tx: WorkloadStateSender,
+    ) -> Result<StChecker, RuntimeError> {
+        self.report(
+            self.runtime
+                .start_checker(workload_id, runtime_workload_config, update_state_tx)
+                .await,
+        )
+    }
+
+    async fn delete_workload(&self, workload_id: &WorkloadId) -> Result<(), RuntimeError> {
+        self.report(self.runtime.delete_workload(workload_id).await)
+    }
+
+    async fn dry_run_workload(
+        &self,
+        runtime_workload_config: &WorkloadSpec,
+    ) -> Result<(), RuntimeError> {
+        self.report(self.runtime.dry_run_workload(runtime_workload_config).await)
+    }
+
+    fn supports_create_cancellation(&self) -> bool {
+        self.runtime.supports_create_cancellation()
+    }
+
+    async fn follow_logs(&self, workload_id: &WorkloadId) -> Result<LogLineReceiver, RuntimeError> {
+        self.report(self.runtime.follow_logs(workload_id).await)
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::HealthReportingRuntime;
+    use crate::{
+        agent_metrics::{get_subsystem_health, runtime_subsystem},
+        runtime_connectors::{
+            test::{MockRuntimeConnector, RuntimeCall},
+            RuntimeConnector, RuntimeError,
+        },
+    };
+
+    const RUNTIME_NAME: &str = "health-reporting-runtime";
+
+    fn is_healthy() -> bool {
+        get_subsystem_health(&runtime_subsystem(RUNTIME_NAME))
+            .unwrap()
+            .healthy
+    }
+
+    // [utest->swdd~agent-reports-subsystem-health~1]
+    #[tokio::test]
+    async fn utest_health_reporting_runtime_publishes_health_of_runtime() {
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::DeleteWorkload(
+                    "workload_id_1".to_string(),
+                    Err(RuntimeError::Unavailable("cannot connect".to_string())),
+                ),
+                RuntimeCall::DeleteWorkload(
+                    "workload_id_2".to_string(),
+                    Err(RuntimeError::NotFound("no such container".to_string())),
+                ),
+                RuntimeCall::DeleteWorkload("workload_id_3".to_string(), Ok(())),
+            ])
+            .await;
+
+        let runtime = HealthReportingRuntime::new(runtime_mock.clone(), RUNTIME_NAME);
+        assert!(is_healthy());
+
+        assert!(runtime
+            .delete_workload(&"workload_id_1".to_string())
+            .await
+            .is_err());
+        assert!(!is_healthy());
+
+        // a missing workload does not tell anything about the runtime
+        assert!(runtime
+            .delete_workload(&"workload_id_2".to_string())
+            .await
+            .is_err());
+        assert!(!is_healthy());
+
+        assert_eq!(
+            runtime.delete_workload(&"workload_id_3".to_string()).await,
+            Ok(())
+        );
+        assert!(is_healthy());
+        runtime_mock.assert_all_expectations().await;
+    }
+}
@@ -14,14 +14,33 @@
 
 mod cli_command;
 
+// shared by the podman runtimes, parts of it are unused if only one of them is compiled in
+#[cfg_attr(
+    not(all(feature = "podman", feature = "podman_kube")),
+    allow(dead_code)
+)]
 mod podman_cli;
 
+mod podman_storage;
+pub use podman_storage::{set_podman_storage, PodmanStorage};
+
+#[cfg(feature = "podman")]
 pub(crate) mod podman;
 
+#[cfg(feature = "podman_kube")]
 pub(crate) mod podman_kube;
 
+mod concurrency_limited_runtime;
+
+mod health_reporting_runtime;
+
+mod runtime_registry;
+pub use runtime_registry::{RuntimeConcurrencyLimits, RuntimeRegistry};
+
 mod runtime_connector;
-pub use runtime_connector::{OwnableRuntime, RuntimeConnector, RuntimeError};
+pub use runtime_connector::{
+    LogLine, LogLineReceiver, LogStream, OwnableRuntime, RuntimeConnector, RuntimeError,
+};
 
 #[cfg(test)]
 pub use runtime_connector::test;
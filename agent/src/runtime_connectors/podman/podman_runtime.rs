@@ -9,7 +9,9 @@ use common::{
 
 use crate::{
     generic_polling_state_checker::GenericPollingStateChecker,
-    runtime_connectors::{RuntimeConnector, RuntimeError, RuntimeStateGetter, StateChecker},
+    runtime_connectors::{
+        LogLineReceiver, RuntimeConnector, RuntimeError, RuntimeStateGetter, StateChecker,
+    },
     workload_state::WorkloadStateSender,
 };
 
@@ -19,10 +21,12 @@ use mockall_double::double;
 // [impl->swdd~podman-uses-podman-cli~1]
 #[cfg_attr(test, double)]
 use crate::runtime_connectors::podman_cli::PodmanCli;
+use crate::runtime_connectors::podman_cli::PodmanRunConfig;
 
 use super::podman_runtime_config::PodmanRuntimeConfig;
 
 pub const PODMAN_RUNTIME_NAME: &str = "podman";
+const LOG_LEVEL_ENV_VAR: &str = "ANKAIOS_LOG_LEVEL";
 
 #[derive(Debug, Clone)]
 pub struct PodmanRuntime {}
@@ -75,6 +79,20 @@ impl RuntimeStateGetter<PodmanWorkloadId> for PodmanStateGetter {
         );
         exec_state
     }
+
+    // [impl->swdd~podman-state-getter-captures-output~1]
+    async fn get_output(&self, workload_id: &PodmanWorkloadId) -> Option<String> {
+        PodmanCli::get_logs(&workload_id.id)
+            .await
+            .map_err(|err| {
+                log::warn!(
+                    "Could not capture the output of workload '{}': '{}'",
+                    workload_id.id,
+                    err
+                )
+            })
+            .ok()
+    }
 }
 
 impl PodmanRuntime {
@@ -89,14 +107,21 @@ impl PodmanRuntime {
                 Ok(Some(execution_state)) => workload_states.push(WorkloadState {
                     instance_name: instance_name.clone(),
                     execution_state,
+                    agent_timestamp: None,
+                    server_timestamp: None,
                 }),
                 Ok(None) => {
-                    return Err(RuntimeError::List(format!(
+                    return Err(RuntimeError::NotFound(format!(
                         "Could not get execution state for workload '{}'",
                         instance_name
                     )))
                 }
-                Err(err) => return Err(RuntimeError::List(err)),
+                Err(err) => {
+                    return Err(RuntimeError::from_error_output(
+                        "Could not list the workload states",
+                        err,
+                    ))
+                }
             }
         }
         Ok(workload_states)
@@ -111,6 +136,12 @@ impl RuntimeConnector<PodmanWorkloadId, GenericPollingStateChecker> for PodmanRu
         PODMAN_RUNTIME_NAME.to_string()
     }
 
+    // A cancelled podman call is killed, a container created until then is found by its label.
+    // [impl->swdd~agent-workload-control-loop-cancels-create-on-superseding-command~1]
+    fn supports_create_cancellation(&self) -> bool {
+        true
+    }
+
     async fn get_reusable_workloads(
         &self,
         agent_name: &AgentName,
@@ -118,7 +149,7 @@ impl RuntimeConnector<PodmanWorkloadId, GenericPollingStateChecker> for PodmanRu
         // [impl->swdd~podman-list-of-existing-workloads-uses-labels~1]
         let res = PodmanCli::list_workload_names_by_label("agent", agent_name.get())
             .await
-            .map_err(|err| RuntimeError::List(err.to_string()))?;
+            .map_err(|err| RuntimeError::from_error_output("Could not list the workloads", err))?;
 
         log::debug!("Found {} reusable workload(s): '{:?}'", res.len(), &res);
 
@@ -139,10 +170,19 @@ impl RuntimeConnector<PodmanWorkloadId, GenericPollingStateChecker> for PodmanRu
         update_state_tx: WorkloadStateSender,
     ) -> Result<(PodmanWorkloadId, GenericPollingStateChecker), RuntimeError> {
         let workload_cfg = PodmanRuntimeConfig::try_from(&workload_spec)
-            .map_err(|err| RuntimeError::Create(err.into()))?;
+            .map_err(|err| RuntimeError::InvalidConfig(err.into()))?;
+
+        let mut run_config: PodmanRunConfig = workload_cfg.into();
+        // [impl->swdd~podman-passes-log-level-as-environment-variable~1]
+        if let Some(log_level) = &workload_spec.log_level {
+            // the option comes first such that an environment variable set by the user wins
+            run_config
+                .command_options
+                .insert(0, format!("--env={LOG_LEVEL_ENV_VAR}={}", log_level.level));
+        }
 
         match PodmanCli::podman_run(
-            workload_cfg.into(),
+            run_config,
             &workload_spec.instance_name.to_string(),
             workload_spec.instance_name.agent_name(),
             control_interface_path,
@@ -175,7 +215,10 @@ impl RuntimeConnector<PodmanWorkloadId, GenericPollingStateChecker> for PodmanRu
                 }
 
                 // No matter if we have deleted the broken container or not, we have to report that the "workload create" failed.
-                Err(RuntimeError::Create(err))
+                Err(RuntimeError::from_error_output(
+                    "Could not create the container",
+                    err,
+                ))
             }
         }
     }
@@ -187,7 +230,9 @@ impl RuntimeConnector<PodmanWorkloadId, GenericPollingStateChecker> for PodmanRu
         // [impl->swdd~podman-get-workload-id-uses-label~1]
         let res = PodmanCli::list_workload_ids_by_label("name", instance_name.to_string().as_str())
             .await
-            .map_err(|err| RuntimeError::List(err.to_string()))?;
+            .map_err(|err| {
+                RuntimeError::from_error_output("Could not list the workload ids", err)
+            })?;
 
         if 1 == res.len() {
             let id = res.first().unwrap_or_unreachable();
@@ -198,7 +243,7 @@ impl RuntimeConnector<PodmanWorkloadId, GenericPollingStateChecker> for PodmanRu
                 "get_workload_id returned unexpected number of workloads {:?}",
                 res
             );
-            Err(RuntimeError::List(
+            Err(RuntimeError::NotFound(
                 "Unexpected number of workloads".to_string(),
             ))
         }
@@ -233,7 +278,27 @@ impl RuntimeConnector<PodmanWorkloadId, GenericPollingStateChecker> for PodmanRu
         log::debug!("Deleting workload with id '{}'", workload_id.id);
         PodmanCli::remove_workloads_by_id(&workload_id.id)
             .await
-            .map_err(|err| RuntimeError::Delete(err.to_string()))
+            .map_err(|err| RuntimeError::from_error_output("Could not delete the container", err))
+    }
+
+    // [impl->swdd~podman-dry-run-workload-validates-config-and-image~1]
+    async fn dry_run_workload(&self, workload_spec: &WorkloadSpec) -> Result<(), RuntimeError> {
+        let workload_cfg = PodmanRuntimeConfig::try_from(workload_spec)
+            .map_err(|err| RuntimeError::InvalidConfig(err.into()))?;
+
+        PodmanCli::check_image_available(&workload_cfg.image)
+            .await
+            .map_err(RuntimeError::NotFound)
+    }
+
+    // [impl->swdd~podman-follows-logs~1]
+    async fn follow_logs(
+        &self,
+        workload_id: &PodmanWorkloadId,
+    ) -> Result<LogLineReceiver, RuntimeError> {
+        log::debug!("Following the logs of workload with id '{}'", workload_id.id);
+        PodmanCli::follow_logs(&workload_id.id)
+            .map_err(|err| RuntimeError::from_error_output("Could not follow the logs", err))
     }
 }
 
@@ -253,6 +318,7 @@ mod tests {
     use common::objects::{
         generate_test_workload_spec_with_param, AgentName, ExecutionState, WorkloadInstanceName,
     };
+    use common::test_utils::TestWorkloadSpecBuilder;
     use mockall::Sequence;
 
     use super::PodmanCli;
@@ -353,9 +419,10 @@ mod tests {
 
         assert_eq!(
             podman_runtime.get_reusable_workloads(&agent_name).await,
-            Err(crate::runtime_connectors::RuntimeError::List(
-                "Simulated error".into()
-            ))
+            Err(crate::runtime_connectors::RuntimeError::Internal {
+                message: "Could not list the workloads".into(),
+                source: Some("Simulated error".into())
+            })
         );
     }
 
@@ -392,6 +459,38 @@ mod tests {
         assert_eq!(workload_id.id, "test_id".to_string());
     }
 
+    // [utest->swdd~podman-passes-log-level-as-environment-variable~1]
+    #[tokio::test]
+    async fn utest_create_workload_passes_log_level_as_environment_variable() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let run_context = PodmanCli::podman_run_context();
+        run_context
+            .expect()
+            .withf(|run_config, _, _, _| {
+                run_config.command_options.first()
+                    == Some(&"--env=ANKAIOS_LOG_LEVEL=debug".to_string())
+            })
+            .return_const(Ok("test_id".into()));
+
+        let resest_cache_context = PodmanCli::reset_ps_cache_context();
+        resest_cache_context.expect().return_const(());
+
+        let workload_spec = TestWorkloadSpecBuilder::new(WORKLOAD_1_NAME)
+            .agent(AGENT_NAME)
+            .runtime(PODMAN_RUNTIME_NAME)
+            .log_level("debug", true)
+            .build();
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let podman_runtime = PodmanRuntime {};
+        let res = podman_runtime
+            .create_workload(workload_spec, None, state_change_tx)
+            .await;
+
+        assert!(res.is_ok());
+    }
+
     // [utest->swdd~podman-state-getter-reset-cache~1]
     #[tokio::test]
     async fn utest_state_getter_resets_cache() {
@@ -487,7 +586,12 @@ mod tests {
             )
             .await;
 
-        assert!(res.is_err_and(|x| { x == RuntimeError::Create("podman run failed".into()) }))
+        assert!(res.is_err_and(|x| {
+            x == RuntimeError::Internal {
+                message: "Could not create the container".into(),
+                source: Some("podman run failed".into()),
+            }
+        }))
     }
 
     #[tokio::test]
@@ -521,7 +625,12 @@ mod tests {
             )
             .await;
 
-        assert!(res.is_err_and(|x| { x == RuntimeError::Create("podman run failed".into()) }))
+        assert!(res.is_err_and(|x| {
+            x == RuntimeError::Internal {
+                message: "Could not create the container".into(),
+                source: Some("podman run failed".into()),
+            }
+        }))
     }
 
     #[tokio::test]
@@ -586,7 +695,7 @@ mod tests {
 
         assert_eq!(
             res,
-            Err(RuntimeError::List(
+            Err(RuntimeError::NotFound(
                 "Unexpected number of workloads".to_owned()
             ))
         )
@@ -604,7 +713,13 @@ mod tests {
         let podman_runtime = PodmanRuntime {};
         let res = podman_runtime.get_workload_id(&workload_name).await;
 
-        assert_eq!(res, Err(RuntimeError::List("simulated error".to_owned())))
+        assert_eq!(
+            res,
+            Err(RuntimeError::Internal {
+                message: "Could not list the workload ids".to_owned(),
+                source: Some("simulated error".to_owned())
+            })
+        )
     }
 
     // [utest->podman-state-getter-uses-podmancli~1]
@@ -690,6 +805,58 @@ mod tests {
 
         let podman_runtime = PodmanRuntime {};
         let res = podman_runtime.delete_workload(&workload_id).await;
-        assert_eq!(res, Err(RuntimeError::Delete("simulated error".into())));
+        assert_eq!(
+            res,
+            Err(RuntimeError::Internal {
+                message: "Could not delete the container".into(),
+                source: Some("simulated error".into())
+            })
+        );
+    }
+
+    // [utest->swdd~podman-dry-run-workload-validates-config-and-image~1]
+    #[tokio::test]
+    async fn utest_dry_run_workload_checks_image() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = PodmanCli::check_image_available_context();
+        context
+            .expect()
+            .with(mockall::predicate::eq("alpine:latest"))
+            .return_const(Ok(()));
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_RUNTIME_NAME.to_string(),
+        );
+
+        let podman_runtime = PodmanRuntime {};
+        assert_eq!(
+            podman_runtime.dry_run_workload(&workload_spec).await,
+            Ok(())
+        );
+    }
+
+    // [utest->swdd~podman-dry-run-workload-validates-config-and-image~1]
+    #[tokio::test]
+    async fn utest_dry_run_workload_fails_on_invalid_config() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = PodmanCli::check_image_available_context();
+        context.expect().never();
+
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config = "broken: [".to_string();
+
+        let podman_runtime = PodmanRuntime {};
+        assert!(matches!(
+            podman_runtime.dry_run_workload(&workload_spec).await,
+            Err(RuntimeError::InvalidConfig(_))
+        ));
     }
 }
@@ -1,4 +1,4 @@
-use common::objects::WorkloadSpec;
+use common::{input_limits::InputLimits, objects::WorkloadSpec};
 
 use crate::runtime_connectors::podman_cli::PodmanRunConfig;
 
@@ -39,10 +39,13 @@ impl TryFrom<&WorkloadSpec> for PodmanRuntimeConfig {
                 workload_spec.runtime
             )));
         }
-        match serde_yaml::from_str(workload_spec.runtime_config.as_str()) {
-            Ok(workload_cfg) => Ok(workload_cfg),
-            Err(e) => Err(TryFromWorkloadSpecError(e.to_string())),
-        }
+        // [impl->swdd~agent-rejects-runtime-config-exceeding-input-limits~1]
+        InputLimits::configured()
+            .parse_runtime_config(
+                workload_spec.instance_name.workload_name(),
+                workload_spec.runtime_config.as_str(),
+            )
+            .map_err(|e| TryFromWorkloadSpecError(e.to_string()))
     }
 }
 
@@ -86,6 +89,22 @@ mod tests {
         assert!(PodmanRuntimeConfig::try_from(&workload_spec).is_err());
     }
 
+    // [utest->swdd~agent-rejects-runtime-config-exceeding-input-limits~1]
+    #[test]
+    fn utest_podman_config_accepts_long_runtime_config_without_configured_limit() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_RUNTIME_NAME.to_string(),
+        );
+
+        let image = "x".repeat(1024 * 1024);
+        workload_spec.runtime_config = format!("image: '{image}'");
+
+        let podman_config = PodmanRuntimeConfig::try_from(&workload_spec).unwrap();
+        assert_eq!(podman_config.image, image);
+    }
+
     #[test]
     fn utest_podman_config_failure_wrong_runtime() {
         let workload_spec = generate_test_workload_spec_with_param(
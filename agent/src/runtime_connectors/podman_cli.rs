@@ -5,19 +5,29 @@ use mockall::automock;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
     collections::HashMap,
+    process::Stdio,
     ops::Deref,
     path::PathBuf,
     sync::Arc,
     time::{Duration, Instant},
 };
-use tokio::sync::Mutex;
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    sync::Mutex,
+};
+
+use crate::process_priority;
+use crate::runtime_connectors::{LogLine, LogLineReceiver, LogStream};
 
 #[cfg_attr(test, mockall_double::double)]
 use crate::runtime_connectors::cli_command::CliCommand;
+use crate::runtime_connectors::podman_storage::storage_options;
 
 const PODMAN_CMD: &str = "podman";
 const API_PIPES_MOUNT_POINT: &str = "/run/ankaios/control_interface";
 const PODMAN_PS_CACHE_MAX_AGE: Duration = Duration::from_millis(1000);
+#[cfg_attr(test, allow(dead_code))]
+const LOG_LINE_BUFFER_SIZE: usize = 100;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum ContainerState {
@@ -29,6 +39,13 @@ pub enum ContainerState {
     Stopping,
 }
 
+// [impl->swdd~podman-kube-state-getter-uses-container-states~1]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PodContainerState {
+    pub name: String,
+    pub state: ContainerState,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct PodmanRunConfig {
     pub general_options: Vec<String>,
@@ -143,7 +160,7 @@ impl Deref for TimedPodmanPsResult {
 #[derive(Debug)]
 struct PodmanPsResult {
     container_states: Result<HashMap<String, ExecutionState>, String>,
-    pod_states: Result<HashMap<String, Vec<ContainerState>>, String>,
+    pod_states: Result<HashMap<String, Vec<PodContainerState>>, String>,
 }
 
 impl From<Result<Vec<PodmanContainerInfo>, String>> for PodmanPsResult {
@@ -151,7 +168,7 @@ impl From<Result<Vec<PodmanContainerInfo>, String>> for PodmanPsResult {
         match value {
             Ok(container_infos) => {
                 let mut container_states = HashMap::new();
-                let mut pod_states: HashMap<String, Vec<ContainerState>> = HashMap::new();
+                let mut pod_states: HashMap<String, Vec<PodContainerState>> = HashMap::new();
 
                 for container_entry in container_infos {
                     container_states
@@ -159,7 +176,10 @@ impl From<Result<Vec<PodmanContainerInfo>, String>> for PodmanPsResult {
                     pod_states
                         .entry(container_entry.pod.clone())
                         .or_default()
-                        .push(container_entry.into());
+                        .push(PodContainerState {
+                            name: container_entry.name().to_owned(),
+                            state: container_entry.into(),
+                        });
                 }
                 Self {
                     container_states: Ok(container_states),
@@ -195,6 +215,7 @@ impl PodmanCli {
         args.push("-");
         log::debug!("Executing play kube with args: {args:?}");
         let result = CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
             .args(&args)
             .stdin(kube_yml)
             .exec()
@@ -228,6 +249,7 @@ impl PodmanCli {
         args.push("-");
 
         CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
             .args(&args)
             .stdin(kube_yml)
             .exec()
@@ -238,6 +260,7 @@ impl PodmanCli {
     pub async fn list_workload_ids_by_label(key: &str, value: &str) -> Result<Vec<String>, String> {
         log::debug!("Listing workload ids for: {}='{}'", key, value,);
         let output = CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
             .args(&[
                 "ps",
                 "--all",
@@ -260,6 +283,7 @@ impl PodmanCli {
     ) -> Result<Vec<String>, String> {
         log::trace!("Listing workload names for: '{}'='{}'", key, value,);
         let output = CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
             .args(&[
                 "ps",
                 "--all",
@@ -332,6 +356,7 @@ impl PodmanCli {
 
         log::debug!("The args are: '{:?}'", args);
         let id = CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
             .args(&args.iter().map(|x| &**x).collect::<Vec<&str>>())
             .exec()
             .await?
@@ -355,7 +380,7 @@ impl PodmanCli {
 
     // [impl->swdd~podmancli-uses-container-state-cache~1]
     // [impl->swdd~podman-kube-state-getter-treats-missing-pods-as-unknown~1]
-    pub async fn list_states_from_pods(pods: &[String]) -> Result<Vec<ContainerState>, String> {
+    pub async fn list_states_from_pods(pods: &[String]) -> Result<Vec<PodContainerState>, String> {
         let ps_result = LAST_PS_RESULT.get().await;
         let all_pod_states = ps_result
             .as_ref()
@@ -367,15 +392,21 @@ impl PodmanCli {
             .flat_map(|key| {
                 all_pod_states.get(key).cloned().unwrap_or_else(|| {
                     log::warn!("The pod '{}' is missing.", key);
-                    vec![ContainerState::Unknown]
+                    vec![PodContainerState {
+                        name: key.to_owned(),
+                        state: ContainerState::Unknown,
+                    }]
                 })
             })
             .collect())
     }
 
     async fn list_states_internal() -> Result<Vec<PodmanContainerInfo>, String> {
+        // [impl->swdd~agent-applies-configured-process-priority~1]
         let output = CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
             .args(&["ps", "--all", "--format=json"])
+            .priority(process_priority::state_checker_priority())
             .exec()
             .await?;
 
@@ -385,6 +416,7 @@ impl PodmanCli {
 
     pub async fn list_volumes_by_name(name: &str) -> Result<Vec<String>, String> {
         let output = CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
             .args(&[
                 "volume",
                 "ls",
@@ -409,6 +441,7 @@ impl PodmanCli {
         let mut label = "--label=data=".into();
         base64::engine::general_purpose::STANDARD_NO_PAD.encode_string(data.as_bytes(), &mut label);
         CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
             .args(&["volume", "create", &label, volume_name])
             .exec()
             .await?;
@@ -417,6 +450,7 @@ impl PodmanCli {
 
     pub async fn read_data_from_volume(volume_name: &str) -> Result<String, String> {
         let result = CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
             .args(&["volume", "inspect", volume_name])
             .exec()
             .await?;
@@ -439,20 +473,109 @@ impl PodmanCli {
 
     pub async fn remove_volume(volume_name: &str) -> Result<(), String> {
         CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
             .args(&["volume", "rm", volume_name])
             .exec()
             .await?;
         Ok(())
     }
 
+    // [impl->swdd~podman-dry-run-checks-image-availability~1]
+    pub async fn check_image_available(image: &str) -> Result<(), String> {
+        if CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
+            .args(&["image", "exists", image])
+            .exec()
+            .await
+            .is_ok()
+        {
+            return Ok(());
+        }
+        // queries the registry without pulling the image
+        CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
+            .args(&["manifest", "inspect", image])
+            .exec()
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                format!(
+                    "The image '{image}' is neither available locally nor in a registry: '{err}'"
+                )
+            })
+    }
+
     pub async fn remove_workloads_by_id(workload_id: &str) -> Result<(), String> {
         // Containers may have "--rm" flag -> it can happen, that they already do not exist.
         let args = vec!["stop", "--ignore", workload_id];
-        CliCommand::new(PODMAN_CMD).args(&args).exec().await?;
+        CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
+            .args(&args)
+            .exec()
+            .await?;
         let args = vec!["rm", "--ignore", workload_id];
-        CliCommand::new(PODMAN_CMD).args(&args).exec().await?;
+        CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
+            .args(&args)
+            .exec()
+            .await?;
         Ok(())
     }
+
+    // [impl->swdd~podman-state-getter-captures-output~1]
+    pub async fn get_logs(workload_id: &str) -> Result<String, String> {
+        CliCommand::new(PODMAN_CMD)
+            .args(&storage_options())
+            .args(&["logs", workload_id])
+            .exec()
+            .await
+    }
+
+    // [impl->swdd~podman-follows-logs~1]
+    #[cfg_attr(test, allow(dead_code))]
+    pub fn follow_logs(workload_id: &str) -> Result<LogLineReceiver, String> {
+        let mut child = tokio::process::Command::new(PODMAN_CMD)
+            .args(storage_options())
+            .args(["logs", "--follow", workload_id])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| format!("Could not follow the logs of '{workload_id}': '{err}'"))?;
+
+        let stdout = child.stdout.take().map(|out| BufReader::new(out).lines());
+        let stderr = child.stderr.take().map(|err| BufReader::new(err).lines());
+        let (Some(mut stdout), Some(mut stderr)) = (stdout, stderr) else {
+            return Err(format!("Could not capture the logs of '{workload_id}'"));
+        };
+
+        let (sender, receiver) = tokio::sync::mpsc::channel(LOG_LINE_BUFFER_SIZE);
+        tokio::spawn(async move {
+            // keep the child alive until both streams are closed
+            let _child = child;
+            let (mut stdout_open, mut stderr_open) = (true, true);
+            while stdout_open || stderr_open {
+                let (stream, line) = tokio::select! {
+                    line = stdout.next_line(), if stdout_open => (LogStream::Stdout, line),
+                    line = stderr.next_line(), if stderr_open => (LogStream::Stderr, line),
+                };
+                match line {
+                    Ok(Some(line)) => {
+                        if sender.send(LogLine { stream, line }).await.is_err() {
+                            break;
+                        }
+                    }
+                    _ => match stream {
+                        LogStream::Stdout => stdout_open = false,
+                        LogStream::Stderr => stderr_open = false,
+                    },
+                }
+            }
+        });
+
+        Ok(receiver)
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -477,6 +600,14 @@ struct PodmanContainerInfo {
     id: String,
     #[serde(deserialize_with = "nullable_labels")]
     pod: String,
+    #[serde(default, deserialize_with = "nullable_labels")]
+    names: Vec<String>,
+}
+
+impl PodmanContainerInfo {
+    fn name(&self) -> &str {
+        self.names.first().unwrap_or(&self.id)
+    }
 }
 
 fn nullable_labels<'a, D, V>(deserializer: D) -> Result<V, D::Error>
@@ -499,7 +630,7 @@ where
 // [utest->swdd~podman-kube-uses-podman-cli~1]
 #[cfg(test)]
 mod tests {
-    use super::{ContainerState, PodmanCli, PodmanPsCache};
+    use super::{ContainerState, PodContainerState, PodmanCli, PodmanPsCache};
 
     use super::PodmanContainerInfo;
     use crate::test_helper::MOCKALL_CONTEXT_SYNC;
@@ -519,6 +650,7 @@ mod tests {
             labels: Default::default(),
             pod: "".into(),
             id: "".into(),
+            names: Default::default(),
         }
         .into();
 
@@ -533,6 +665,7 @@ mod tests {
             labels: Default::default(),
             pod: "".into(),
             id: "".into(),
+            names: Default::default(),
         }
         .into();
 
@@ -547,6 +680,7 @@ mod tests {
             labels: Default::default(),
             pod: "".into(),
             id: "".into(),
+            names: Default::default(),
         }
         .into();
 
@@ -561,6 +695,7 @@ mod tests {
             labels: Default::default(),
             pod: "".into(),
             id: "".into(),
+            names: Default::default(),
         }
         .into();
 
@@ -575,6 +710,7 @@ mod tests {
             labels: Default::default(),
             pod: "".into(),
             id: "".into(),
+            names: Default::default(),
         }
         .into();
 
@@ -589,6 +725,7 @@ mod tests {
             labels: Default::default(),
             pod: "".into(),
             id: "".into(),
+            names: Default::default(),
         }
         .into();
 
@@ -603,6 +740,7 @@ mod tests {
             labels: Default::default(),
             pod: "".into(),
             id: "".into(),
+            names: Default::default(),
         }
         .into();
 
@@ -617,6 +755,7 @@ mod tests {
             labels: Default::default(),
             pod: "".into(),
             id: "".into(),
+            names: Default::default(),
         }
         .into();
 
@@ -631,6 +770,7 @@ mod tests {
             labels: Default::default(),
             pod: "".into(),
             id: "".into(),
+            names: Default::default(),
         }
         .into();
 
@@ -645,6 +785,7 @@ mod tests {
             labels: Default::default(),
             pod: "".into(),
             id: "".into(),
+            names: Default::default(),
         }
         .into();
 
@@ -659,6 +800,7 @@ mod tests {
             labels: Default::default(),
             pod: "".into(),
             id: "".into(),
+            names: Default::default(),
         }
         .into();
 
@@ -932,6 +1074,23 @@ mod tests {
         assert_eq!(res, Err("simulated error".to_string()));
     }
 
+    // [utest->swdd~podman-state-getter-captures-output~1]
+    #[tokio::test]
+    async fn utest_get_logs_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["logs", "test_id"])
+                .exec_returns(Ok("result: 42\n".to_string())),
+        );
+
+        let res = PodmanCli::get_logs("test_id").await;
+        assert_eq!(res, Ok("result: 42\n".to_string()));
+    }
+
     #[tokio::test]
     async fn utest_list_workload_names_broken_response() {
         let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
@@ -1485,6 +1644,7 @@ mod tests {
                     TestPodmanContainerInfo {
                         pod: "pod1",
                         state: "running",
+                        names: &["container1"],
                         ..Default::default()
                     },
                     TestPodmanContainerInfo {
@@ -1496,19 +1656,35 @@ mod tests {
                         pod: "pod2",
                         state: "exited",
                         exit_code: 42,
+                        names: &["container2"],
                         ..Default::default()
                     },
                     TestPodmanContainerInfo {
                         pod: "pod2",
                         state: "unknown",
+                        id: "container3_id",
                         ..Default::default()
                     },
                 ]
                 .to_json())),
         );
         let res = PodmanCli::list_states_from_pods(&["pod1".into(), "pod2".into()]).await;
-        assert!(
-            matches!(res, Ok(states) if states == [ContainerState::Running, ContainerState::Exited(42), ContainerState::Unknown] )
+        assert_eq!(
+            res,
+            Ok(vec![
+                PodContainerState {
+                    name: "container1".into(),
+                    state: ContainerState::Running
+                },
+                PodContainerState {
+                    name: "container2".into(),
+                    state: ContainerState::Exited(42)
+                },
+                PodContainerState {
+                    name: "container3_id".into(),
+                    state: ContainerState::Unknown
+                },
+            ])
         );
     }
 
@@ -1546,7 +1722,7 @@ mod tests {
         let res =
             PodmanCli::list_states_from_pods(&["pod1".into(), "pod2".into(), "pod3".into()]).await;
         assert!(
-            matches!(res, Ok(states) if states == [ContainerState::Running, ContainerState::Unknown, ContainerState::Exited(42), ContainerState::Unknown] )
+            matches!(res, Ok(states) if states_of(&states) == [ContainerState::Running, ContainerState::Unknown, ContainerState::Exited(42), ContainerState::Unknown] )
         );
     }
 
@@ -1613,7 +1789,7 @@ mod tests {
 
         let res = PodmanCli::list_states_from_pods(&["pod1".into(), "pod2".into()]).await;
         assert!(
-            matches!(res, Ok(states) if states == [ContainerState::Running, ContainerState::Exited(42), ContainerState::Unknown] )
+            matches!(res, Ok(states) if states_of(&states) == [ContainerState::Running, ContainerState::Exited(42), ContainerState::Unknown] )
         );
     }
 
@@ -1680,7 +1856,7 @@ mod tests {
 
         let res = PodmanCli::list_states_from_pods(&["pod1".into(), "pod2".into()]).await;
         assert!(
-            matches!(res, Ok(states) if states == [ContainerState::Running, ContainerState::Exited(42), ContainerState::Unknown] )
+            matches!(res, Ok(states) if states_of(&states) == [ContainerState::Running, ContainerState::Exited(42), ContainerState::Unknown] )
         );
     }
 
@@ -1744,7 +1920,7 @@ mod tests {
             matches!(PodmanCli::list_states_by_id("id2").await, Ok(Some(state)) if state == ExecutionState::succeeded() )
         );
         assert!(
-            matches!(PodmanCli::list_states_from_pods(&["pod2".into()]).await, Ok(states) if states == [ContainerState::Exited(0)] )
+            matches!(PodmanCli::list_states_from_pods(&["pod2".into()]).await, Ok(states) if states_of(&states) == [ContainerState::Exited(0)] )
         );
     }
 
@@ -2050,6 +2226,13 @@ mod tests {
         assert_eq!(res, Ok(()));
     }
 
+    fn states_of(containers: &[PodContainerState]) -> Vec<ContainerState> {
+        containers
+            .iter()
+            .map(|container| container.state.clone())
+            .collect()
+    }
+
     #[derive(Serialize, Clone, Default)]
     #[serde(rename_all = "PascalCase")]
     struct TestPodmanContainerInfo<'a> {
@@ -2059,6 +2242,7 @@ mod tests {
         labels: &'a [(&'a str, &'a str)],
         id: &'a str,
         pod: &'a str,
+        names: &'a [&'a str],
     }
 
     impl<'a> ToJson for [TestPodmanContainerInfo<'a>] {
@@ -2070,4 +2254,73 @@ mod tests {
     trait ToJson {
         fn to_json(&self) -> String;
     }
+
+    // [utest->swdd~podman-dry-run-checks-image-availability~1]
+    #[tokio::test]
+    async fn utest_check_image_available_locally() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["image", "exists", "alpine:latest"])
+                .exec_returns(Ok("".to_string())),
+        );
+
+        assert_eq!(
+            PodmanCli::check_image_available("alpine:latest").await,
+            Ok(())
+        );
+    }
+
+    // [utest->swdd~podman-dry-run-checks-image-availability~1]
+    #[tokio::test]
+    async fn utest_check_image_available_in_registry() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["image", "exists", "alpine:latest"])
+                .exec_returns(Err("".to_string())),
+        );
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["manifest", "inspect", "alpine:latest"])
+                .exec_returns(Ok("{}".to_string())),
+        );
+
+        assert_eq!(
+            PodmanCli::check_image_available("alpine:latest").await,
+            Ok(())
+        );
+    }
+
+    // [utest->swdd~podman-dry-run-checks-image-availability~1]
+    #[tokio::test]
+    async fn utest_check_image_available_fails_on_unknown_image() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["image", "exists", "unknown:latest"])
+                .exec_returns(Err("".to_string())),
+        );
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["manifest", "inspect", "unknown:latest"])
+                .exec_returns(Err("manifest unknown".to_string())),
+        );
+
+        assert!(PodmanCli::check_image_available("unknown:latest")
+            .await
+            .unwrap_err()
+            .contains("manifest unknown"));
+    }
 }
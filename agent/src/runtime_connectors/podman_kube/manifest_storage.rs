@@ -0,0 +1,249 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use common::objects::WorkloadInstanceName;
+
+const MANIFEST_FILE_EXTENSION: &str = "yaml";
+const TEMPORARY_FILE_EXTENSION: &str = "yaml.tmp";
+
+// Keeps the rendered manifests of the podman-kube workloads as files named after the workload
+// instance names. A manifest is first written to a temporary file and then renamed, such that an
+// agent crash never leaves a partially written manifest behind.
+#[derive(Debug, Clone)]
+pub struct ManifestStorage {
+    path: PathBuf,
+}
+
+impl ManifestStorage {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn manifest_path(&self, instance_name: &WorkloadInstanceName) -> PathBuf {
+        self.path
+            .join(format!("{}.{}", instance_name, MANIFEST_FILE_EXTENSION))
+    }
+
+    // [impl->swdd~podman-kube-stores-manifest-per-workload~1]
+    pub fn store(
+        &self,
+        instance_name: &WorkloadInstanceName,
+        manifest: &str,
+    ) -> Result<PathBuf, String> {
+        fs::create_dir_all(&self.path).map_err(|err| {
+            format!(
+                "Could not create manifest storage '{}': '{}'",
+                self.path.display(),
+                err
+            )
+        })?;
+
+        let manifest_path = self.manifest_path(instance_name);
+        let temporary_path = self
+            .path
+            .join(format!("{}.{}", instance_name, TEMPORARY_FILE_EXTENSION));
+        fs::write(&temporary_path, manifest)
+            .and_then(|_| fs::rename(&temporary_path, &manifest_path))
+            .map_err(|err| {
+                format!(
+                    "Could not store manifest '{}': '{}'",
+                    manifest_path.display(),
+                    err
+                )
+            })?;
+        Ok(manifest_path)
+    }
+
+    pub fn read(&self, instance_name: &WorkloadInstanceName) -> Result<String, String> {
+        let manifest_path = self.manifest_path(instance_name);
+        fs::read_to_string(&manifest_path).map_err(|err| {
+            format!(
+                "Could not read manifest '{}': '{}'",
+                manifest_path.display(),
+                err
+            )
+        })
+    }
+
+    // [impl->swdd~podman-kube-removes-stored-manifest~1]
+    pub fn remove(&self, instance_name: &WorkloadInstanceName) -> Result<(), String> {
+        let manifest_path = self.manifest_path(instance_name);
+        remove_file_if_exists(&manifest_path).map_err(|err| {
+            format!(
+                "Could not remove manifest '{}': '{}'",
+                manifest_path.display(),
+                err
+            )
+        })
+    }
+
+    // [impl->swdd~podman-kube-cleans-up-orphaned-manifests~1]
+    pub fn remove_all_except(&self, instance_names: &[WorkloadInstanceName]) -> Result<(), String> {
+        let entries = match fs::read_dir(&self.path) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => {
+                return Err(format!(
+                    "Could not read manifest storage '{}': '{}'",
+                    self.path.display(),
+                    err
+                ))
+            }
+        };
+
+        let manifests_to_keep: Vec<PathBuf> = instance_names
+            .iter()
+            .map(|instance_name| self.manifest_path(instance_name))
+            .collect();
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() || manifests_to_keep.contains(&path) {
+                continue;
+            }
+            log::debug!("Removing orphaned manifest '{}'", path.display());
+            remove_file_if_exists(&path).map_err(|err| {
+                format!(
+                    "Could not remove orphaned manifest '{}': '{}'",
+                    path.display(),
+                    err
+                )
+            })?;
+        }
+        Ok(())
+    }
+}
+
+fn remove_file_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use common::objects::WorkloadInstanceName;
+
+    use super::ManifestStorage;
+
+    const MANIFEST: &str = "apiVersion: v1\nkind: Pod\n";
+
+    fn instance_name(workload_name: &str) -> WorkloadInstanceName {
+        WorkloadInstanceName::builder()
+            .agent_name("agent_A")
+            .workload_name(workload_name)
+            .config(&String::from("config"))
+            .build()
+    }
+
+    // [utest->swdd~podman-kube-stores-manifest-per-workload~1]
+    #[test]
+    fn utest_manifest_storage_stores_and_reads_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = ManifestStorage::new(dir.path().join("manifests"));
+
+        let manifest_path = storage.store(&instance_name("workload_1"), MANIFEST);
+
+        assert_eq!(
+            manifest_path,
+            Ok(storage.manifest_path(&instance_name("workload_1")))
+        );
+        assert_eq!(
+            storage.read(&instance_name("workload_1")),
+            Ok(MANIFEST.to_string())
+        );
+        assert!(storage.read(&instance_name("workload_2")).is_err());
+    }
+
+    // [utest->swdd~podman-kube-stores-manifest-per-workload~1]
+    #[test]
+    fn utest_manifest_storage_overwrites_existing_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = ManifestStorage::new(dir.path().to_path_buf());
+
+        assert!(storage.store(&instance_name("workload_1"), "old").is_ok());
+        assert!(storage
+            .store(&instance_name("workload_1"), MANIFEST)
+            .is_ok());
+
+        assert_eq!(
+            storage.read(&instance_name("workload_1")),
+            Ok(MANIFEST.to_string())
+        );
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    // [utest->swdd~podman-kube-removes-stored-manifest~1]
+    #[test]
+    fn utest_manifest_storage_removes_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = ManifestStorage::new(dir.path().to_path_buf());
+        assert!(storage
+            .store(&instance_name("workload_1"), MANIFEST)
+            .is_ok());
+
+        assert!(storage.remove(&instance_name("workload_1")).is_ok());
+        assert!(storage.read(&instance_name("workload_1")).is_err());
+        assert!(storage.remove(&instance_name("workload_1")).is_ok());
+    }
+
+    // [utest->swdd~podman-kube-cleans-up-orphaned-manifests~1]
+    #[test]
+    fn utest_manifest_storage_removes_orphaned_manifests() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = ManifestStorage::new(dir.path().to_path_buf());
+        assert!(storage
+            .store(&instance_name("workload_1"), MANIFEST)
+            .is_ok());
+        assert!(storage
+            .store(&instance_name("workload_2"), MANIFEST)
+            .is_ok());
+        fs::write(dir.path().join("workload_3.yaml.tmp"), MANIFEST).unwrap();
+
+        assert!(storage
+            .remove_all_except(&[instance_name("workload_1")])
+            .is_ok());
+
+        assert_eq!(
+            storage.read(&instance_name("workload_1")),
+            Ok(MANIFEST.to_string())
+        );
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    // [utest->swdd~podman-kube-cleans-up-orphaned-manifests~1]
+    #[test]
+    fn utest_manifest_storage_cleanup_ignores_missing_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = ManifestStorage::new(dir.path().join("not_existing"));
+
+        assert!(storage.remove_all_except(&[]).is_ok());
+    }
+}
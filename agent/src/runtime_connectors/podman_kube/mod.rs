@@ -12,6 +12,7 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+mod manifest_storage;
 mod podman_kube_runtime;
 mod podman_kube_runtime_config;
-pub use podman_kube_runtime::{PodmanKubeRuntime, PodmanKubeWorkloadId};
+pub use podman_kube_runtime::{PodmanKubeRuntime, PodmanKubeWorkloadId, MANIFESTS_FOLDER};
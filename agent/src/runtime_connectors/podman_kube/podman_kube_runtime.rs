@@ -6,6 +6,7 @@ use common::objects::{
 
 use async_trait::async_trait;
 use futures_util::TryFutureExt;
+use serde::Deserialize;
 
 #[cfg(test)]
 use mockall_double::double;
@@ -16,19 +17,26 @@ use crate::runtime_connectors::podman_cli::PodmanCli;
 use crate::{
     generic_polling_state_checker::GenericPollingStateChecker,
     runtime_connectors::{
-        podman_cli, RuntimeConnector, RuntimeError, RuntimeStateGetter, StateChecker,
+        podman_cli::{self, PodContainerState},
+        RuntimeConnector, RuntimeError, RuntimeStateGetter, StateChecker,
     },
     workload_state::WorkloadStateSender,
 };
 
+use super::manifest_storage::ManifestStorage;
 use super::podman_kube_runtime_config::PodmanKubeRuntimeConfig;
 
 pub const PODMAN_KUBE_RUNTIME_NAME: &str = "podman-kube";
+// The folder is placed in the run folder of the agent. The leading dot prevents clashes
+// with the folders of the workloads as it is not allowed in workload names.
+pub const MANIFESTS_FOLDER: &str = ".podman-kube-manifests";
 const CONFIG_VOLUME_SUFFIX: &str = ".config";
 const PODS_VOLUME_SUFFIX: &str = ".pods";
 
 #[derive(Debug, Clone)]
-pub struct PodmanKubeRuntime {}
+pub struct PodmanKubeRuntime {
+    manifest_storage: ManifestStorage,
+}
 
 #[derive(Clone, Debug)]
 
@@ -36,9 +44,9 @@ pub struct PodmanKubeRuntime {}
 pub struct PodmanKubeWorkloadId {
     // Podman currently does not provide an Id for a created manifest
     // and one needs the complete manifest to tear down the deployed resources.
+    // The manifest is kept in the manifest storage of the runtime under the instance name.
     pub name: WorkloadInstanceName,
     pub pods: Option<Vec<String>>,
-    pub manifest: String,
     pub down_options: Vec<String>,
 }
 
@@ -59,6 +67,12 @@ pub struct PlayKubeOutput {}
 pub struct PlayKubeError {}
 
 impl PodmanKubeRuntime {
+    pub fn new(manifests_path: PathBuf) -> Self {
+        Self {
+            manifest_storage: ManifestStorage::new(manifests_path),
+        }
+    }
+
     async fn workload_instance_names_to_workload_states(
         &self,
         workload_instance_names: &Vec<WorkloadInstanceName>,
@@ -71,6 +85,8 @@ impl PodmanKubeRuntime {
             workload_states.push(WorkloadState {
                 instance_name: instance_name.clone(),
                 execution_state,
+                agent_timestamp: None,
+                server_timestamp: None,
             });
         }
         Ok(workload_states)
@@ -99,10 +115,7 @@ impl RuntimeConnector<PodmanKubeWorkloadId, GenericPollingStateChecker> for Podm
             PodmanCli::list_volumes_by_name(&name_filter)
                 .await
                 .map_err(|err| {
-                    RuntimeError::Create(format!(
-                        "Could not list volume containing config: '{}'",
-                        err
-                    ))
+                    RuntimeError::from_error_output("Could not list volume containing config", err)
                 })?
                 .into_iter()
                 .map(|volume_name| {
@@ -119,6 +132,11 @@ impl RuntimeConnector<PodmanKubeWorkloadId, GenericPollingStateChecker> for Podm
                 })
                 .collect();
 
+        // [impl->swdd~podman-kube-cleans-up-orphaned-manifests~1]
+        self.manifest_storage
+            .remove_all_except(&workload_instance_names)
+            .unwrap_or_else(|err| log::warn!("{}", err));
+
         self.workload_instance_names_to_workload_states(&workload_instance_names)
             .await
     }
@@ -131,8 +149,8 @@ impl RuntimeConnector<PodmanKubeWorkloadId, GenericPollingStateChecker> for Podm
     ) -> Result<(PodmanKubeWorkloadId, GenericPollingStateChecker), RuntimeError> {
         let instance_name = workload_spec.instance_name.clone();
 
-        let workload_config =
-            PodmanKubeRuntimeConfig::try_from(&workload_spec).map_err(RuntimeError::Create)?;
+        let workload_config = PodmanKubeRuntimeConfig::try_from(&workload_spec)
+            .map_err(RuntimeError::InvalidConfig)?;
 
         // [impl->swdd~podman-kube-create-workload-creates-config-volume~1]
         // [impl->swdd~podman-kube-create-continues-if-cannot-create-volume~1]
@@ -149,6 +167,13 @@ impl RuntimeConnector<PodmanKubeWorkloadId, GenericPollingStateChecker> for Podm
             )
         });
 
+        // [impl->swdd~podman-kube-stores-manifest-per-workload~1]
+        self.manifest_storage
+            .store(&instance_name, &workload_config.manifest)
+            .map_err(|err| {
+                RuntimeError::internal_with_source("Could not store the manifest", err)
+            })?;
+
         // [impl->swdd~podman-kube-create-workload-apply-manifest~1]
         let created_pods = PodmanCli::play_kube(
             &workload_config.general_options,
@@ -156,7 +181,7 @@ impl RuntimeConnector<PodmanKubeWorkloadId, GenericPollingStateChecker> for Podm
             workload_config.manifest.as_bytes(),
         )
         .await
-        .map_err(RuntimeError::Create)?;
+        .map_err(|err| RuntimeError::from_error_output("Could not play the manifest", err))?;
 
         // [impl->swdd~podman-kube-create-workload-creates-pods-volume~1]
         // [impl->swdd~podman-kube-create-continues-if-cannot-create-volume~1]
@@ -181,7 +206,6 @@ impl RuntimeConnector<PodmanKubeWorkloadId, GenericPollingStateChecker> for Podm
         let workload_id = PodmanKubeWorkloadId {
             name: instance_name,
             pods: Some(created_pods),
-            manifest: workload_config.manifest,
             down_options: workload_config.down_options,
         };
 
@@ -213,7 +237,9 @@ impl RuntimeConnector<PodmanKubeWorkloadId, GenericPollingStateChecker> for Podm
                         format!("Could not parse config read from volume: {:?}", err)
                     })
                 })
-                .map_err(RuntimeError::Create)?;
+                .map_err(|err| {
+                    RuntimeError::from_error_output("Could not get the workload id", err)
+                })?;
         let pods =
             PodmanCli::read_data_from_volume(&(instance_name.to_string() + PODS_VOLUME_SUFFIX))
                 .await
@@ -232,10 +258,17 @@ impl RuntimeConnector<PodmanKubeWorkloadId, GenericPollingStateChecker> for Podm
             }
         };
 
+        // restores the manifest in case it got lost, e.g., as the run folder has been cleaned up
+        // [impl->swdd~podman-kube-stores-manifest-per-workload~1]
+        self.manifest_storage
+            .store(instance_name, &runtime_config.manifest)
+            .map_err(|err| {
+                RuntimeError::internal_with_source("Could not store the manifest", err)
+            })?;
+
         Ok(PodmanKubeWorkloadId {
             name: instance_name.clone(),
             pods,
-            manifest: runtime_config.manifest,
             down_options: runtime_config.down_options,
         })
     }
@@ -256,7 +289,7 @@ impl RuntimeConnector<PodmanKubeWorkloadId, GenericPollingStateChecker> for Podm
             &workload_spec,
             workload_id.clone(),
             update_state_tx,
-            PodmanKubeRuntime {},
+            self.clone(),
         ))
     }
 
@@ -269,9 +302,14 @@ impl RuntimeConnector<PodmanKubeWorkloadId, GenericPollingStateChecker> for Podm
             workload_id.name
         );
 
-        // [impl->swdd~podman-kube-delete-workload-downs-manifest-file~1]
-        PodmanCli::down_kube(&workload_id.down_options, workload_id.manifest.as_bytes())
-            .map_err(RuntimeError::Delete)
+        let manifest = self
+            .manifest_storage
+            .read(&workload_id.name)
+            .map_err(|err| RuntimeError::from_error_output("Could not read the manifest", err))?;
+
+        // [impl->swdd~podman-kube-delete-workload-downs-manifest-file~2]
+        PodmanCli::down_kube(&workload_id.down_options, manifest.as_bytes())
+            .map_err(|err| RuntimeError::from_error_output("Could not down the manifest", err))
             .await?;
         // [impl->swdd~podman-kube-delete-removes-volumes~1]
         PodmanCli::remove_volume(&(workload_id.name.to_string() + PODS_VOLUME_SUFFIX))
@@ -282,6 +320,26 @@ impl RuntimeConnector<PodmanKubeWorkloadId, GenericPollingStateChecker> for Podm
         PodmanCli::remove_volume(&(workload_id.name.to_string() + CONFIG_VOLUME_SUFFIX))
             .await
             .unwrap_or_else(|err| log::warn!("Could not remove configs volume: '{}'", err));
+
+        // The manifest is removed last. If the agent stops before, the orphaned manifest is
+        // cleaned up when the agent lists the reusable workloads after its restart.
+        // [impl->swdd~podman-kube-removes-stored-manifest~1]
+        self.manifest_storage
+            .remove(&workload_id.name)
+            .unwrap_or_else(|err| log::warn!("{}", err));
+        Ok(())
+    }
+
+    // [impl->swdd~podman-kube-dry-run-workload-validates-config-and-manifest~1]
+    async fn dry_run_workload(&self, workload_spec: &WorkloadSpec) -> Result<(), RuntimeError> {
+        let workload_config = PodmanKubeRuntimeConfig::try_from(workload_spec)
+            .map_err(RuntimeError::InvalidConfig)?;
+
+        for document in serde_yaml::Deserializer::from_str(&workload_config.manifest) {
+            serde_yaml::Value::deserialize(document).map_err(|err| {
+                RuntimeError::InvalidConfig(format!("Could not parse the manifest: '{err}'"))
+            })?;
+        }
         Ok(())
     }
 }
@@ -294,19 +352,13 @@ impl RuntimeStateGetter<PodmanKubeWorkloadId> for PodmanKubeRuntime {
         if let Some(pods) = &id.pods {
             // [impl->swdd~podman-kube-state-getter-uses-container-states~1]
             match PodmanCli::list_states_from_pods(pods).await {
-                // [impl->swdd~podman-kube-state-getter-removed-if-no-container~1]
-                // [impl->swdd~podman-kube-state-getter-combines-states~2]
                 Ok(container_states) => {
                     log::trace!(
                         "Received following states for workload '{}': '{:?}'",
                         id.name,
                         container_states
                     );
-                    container_states
-                        .into_iter()
-                        .map(OrderedExecutionState::from)
-                        .fold(OrderedExecutionState::Lost, min)
-                        .into()
+                    combine_container_states(&container_states)
                 }
 
                 Err(err) => {
@@ -321,11 +373,56 @@ impl RuntimeStateGetter<PodmanKubeWorkloadId> for PodmanKubeRuntime {
     }
 }
 
+// [impl->swdd~podman-kube-state-getter-removed-if-no-container~1]
+// [impl->swdd~podman-kube-state-getter-combines-states~3]
+fn combine_container_states(container_states: &[PodContainerState]) -> ExecutionState {
+    let combined_state = container_states
+        .iter()
+        .map(|container| OrderedExecutionState::from(container.state.clone()))
+        .fold(OrderedExecutionState::Lost, min);
+
+    // [impl->swdd~podman-kube-state-getter-names-containers-in-combined-state~1]
+    let containers = container_states
+        .iter()
+        .filter(|container| OrderedExecutionState::from(container.state.clone()) == combined_state)
+        .map(describe_container)
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    match combined_state {
+        OrderedExecutionState::Failed => {
+            ExecutionState::failed(format!("Failed containers: {}", containers))
+        }
+        OrderedExecutionState::Starting => {
+            ExecutionState::starting(format!("Starting containers: {}", containers))
+        }
+        OrderedExecutionState::Unknown => {
+            ExecutionState::unknown(format!("Containers in unknown state: {}", containers))
+        }
+        OrderedExecutionState::Running => ExecutionState::running(),
+        OrderedExecutionState::Stopping => {
+            ExecutionState::stopping(format!("Stopping containers: {}", containers))
+        }
+        OrderedExecutionState::Succeeded => ExecutionState::succeeded(),
+        OrderedExecutionState::Lost => ExecutionState::lost(),
+    }
+}
+
+fn describe_container(container: &PodContainerState) -> String {
+    match container.state {
+        podman_cli::ContainerState::Exited(exit_code) => {
+            format!("'{}' (exit code: '{}')", container.name, exit_code)
+        }
+        podman_cli::ContainerState::Paused => format!("'{}' (paused)", container.name),
+        _ => format!("'{}'", container.name),
+    }
+}
+
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
 
 // [impl->swdd~podman-kube-state-getter-removed-if-no-container~1]
 enum OrderedExecutionState {
-    Failed(String),
+    Failed,
     Starting,
     Unknown,
     Running,
@@ -340,9 +437,7 @@ impl From<podman_cli::ContainerState> for OrderedExecutionState {
         match value {
             podman_cli::ContainerState::Starting => OrderedExecutionState::Starting,
             podman_cli::ContainerState::Exited(0) => OrderedExecutionState::Succeeded,
-            podman_cli::ContainerState::Exited(value) => {
-                OrderedExecutionState::Failed(format!("Exit code: '{value}'"))
-            }
+            podman_cli::ContainerState::Exited(_) => OrderedExecutionState::Failed,
             podman_cli::ContainerState::Paused => OrderedExecutionState::Unknown,
             podman_cli::ContainerState::Running => OrderedExecutionState::Running,
             podman_cli::ContainerState::Stopping => OrderedExecutionState::Stopping,
@@ -351,21 +446,6 @@ impl From<podman_cli::ContainerState> for OrderedExecutionState {
     }
 }
 
-// [impl->swdd~podman-kube-state-getter-maps-state~2]
-impl From<OrderedExecutionState> for ExecutionState {
-    fn from(value: OrderedExecutionState) -> Self {
-        match value {
-            OrderedExecutionState::Failed(value) => ExecutionState::failed(value),
-            OrderedExecutionState::Starting => ExecutionState::starting("starting container"),
-            OrderedExecutionState::Unknown => ExecutionState::unknown("unknown container state"),
-            OrderedExecutionState::Running => ExecutionState::running(),
-            OrderedExecutionState::Stopping => ExecutionState::stopping("stopping container"),
-            OrderedExecutionState::Succeeded => ExecutionState::succeeded(),
-            OrderedExecutionState::Lost => ExecutionState::lost(),
-        }
-    }
-}
-
 //////////////////////////////////////////////////////////////////////////////
 //                 ########  #######    #########  #########                //
 //                    ##     ##        ##             ##                    //
@@ -389,7 +469,10 @@ mod tests {
 
     use super::PodmanCli;
     use crate::runtime_connectors::podman_cli::__mock_MockPodmanCli as podman_cli_mock;
-    use crate::runtime_connectors::{podman_cli::ContainerState, RuntimeConnector, RuntimeError};
+    use crate::runtime_connectors::{
+        podman_cli::{ContainerState, PodContainerState},
+        RuntimeConnector, RuntimeError,
+    };
 
     use super::{
         PodmanKubeRuntime, PodmanKubeWorkloadId, CONFIG_VOLUME_SUFFIX, PODMAN_KUBE_RUNTIME_NAME,
@@ -421,15 +504,32 @@ mod tests {
         pub static ref WORKLOAD_ID: PodmanKubeWorkloadId = PodmanKubeWorkloadId {
             name: WORKLOAD_INSTANCE_NAME.clone(),
             pods: Some(SAMPLE_POD_LIST.clone()),
-            manifest: SAMPLE_KUBE_CONFIG.into(),
             down_options: SAMPLE_DOWN_OPTIONS.clone(),
         };
     }
 
+    // names the containers after their position, i.e., 'container_0', 'container_1', ...
+    fn containers(states: Vec<ContainerState>) -> Vec<PodContainerState> {
+        states
+            .into_iter()
+            .enumerate()
+            .map(|(index, state)| PodContainerState {
+                name: format!("container_{index}"),
+                state,
+            })
+            .collect()
+    }
+
+    fn create_runtime() -> (PodmanKubeRuntime, tempfile::TempDir) {
+        let manifests_dir = tempfile::tempdir().unwrap();
+        let runtime = PodmanKubeRuntime::new(manifests_dir.path().to_path_buf());
+        (runtime, manifests_dir)
+    }
+
     // [utest->swdd~podman-kube-name-returns-podman-kube~1]
     #[test]
     fn utest_name_podman_kube() {
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         assert_eq!(runtime.name(), "podman-kube");
     }
 
@@ -458,7 +558,7 @@ mod tests {
             .expect()
             .return_const(Ok(workload_spec.runtime_config));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
 
         let workloads = runtime.get_reusable_workloads(&SAMPLE_AGENT.into()).await;
 
@@ -467,16 +567,55 @@ mod tests {
         );
     }
 
+    // [utest->swdd~podman-kube-cleans-up-orphaned-manifests~1]
+    #[tokio::test]
+    async fn utest_get_reusable_workloads_removes_orphaned_manifests() {
+        let workload_instance_1 = "workload_1.hash_1.agent_A";
+        let workload_instance_2 = "workload_2.hash_2.agent_A";
+
+        let mock_context = MockContext::new().await;
+        mock_context
+            .list_agent_config_volumes_returns(Ok(vec![workload_instance_1.as_config_volume()]));
+        mock_context
+            .read_data
+            .expect()
+            .return_const(Ok(SAMPLE_RUNTIME_CONFIG.to_string()));
+
+        let (runtime, _manifests_dir) = create_runtime();
+        let instance_name_1: WorkloadInstanceName = workload_instance_1.try_into().unwrap();
+        let instance_name_2: WorkloadInstanceName = workload_instance_2.try_into().unwrap();
+        runtime
+            .manifest_storage
+            .store(&instance_name_1, SAMPLE_KUBE_CONFIG)
+            .unwrap();
+        runtime
+            .manifest_storage
+            .store(&instance_name_2, SAMPLE_KUBE_CONFIG)
+            .unwrap();
+
+        let workloads = runtime.get_reusable_workloads(&SAMPLE_AGENT.into()).await;
+
+        assert!(workloads.is_ok());
+        assert_eq!(
+            runtime.manifest_storage.read(&instance_name_1),
+            Ok(SAMPLE_KUBE_CONFIG.to_string())
+        );
+        assert!(runtime.manifest_storage.read(&instance_name_2).is_err());
+    }
+
     #[tokio::test]
     async fn utest_get_reusable_running_workloads_request_fails() {
         let mock_context = MockContext::new().await;
         mock_context.list_agent_config_volumes_returns(Err(SAMPLE_ERROR.into()));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
 
         let workloads = runtime.get_reusable_workloads(&SAMPLE_AGENT.into()).await;
 
-        assert!(matches!(workloads, Err(RuntimeError::Create(msg)) if msg.contains(SAMPLE_ERROR)));
+        assert!(matches!(
+            workloads,
+            Err(RuntimeError::Internal { source: Some(msg), .. }) if msg == SAMPLE_ERROR
+        ));
     }
 
     #[tokio::test]
@@ -503,7 +642,7 @@ mod tests {
             .expect()
             .return_const(Ok(workload_spec.runtime_config));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
 
         let workloads = runtime.get_reusable_workloads(&SAMPLE_AGENT.into()).await;
 
@@ -540,9 +679,9 @@ mod tests {
         mock_context
             .list_states_from_pods
             .expect()
-            .return_const(Ok(vec![ContainerState::Unknown]));
+            .return_const(Ok(containers(vec![ContainerState::Unknown])));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
 
         let workloads = runtime.get_reusable_workloads(&SAMPLE_AGENT.into()).await;
         println!("{:?}", workloads);
@@ -583,7 +722,7 @@ mod tests {
 
         mock_context.reset_ps_cache.expect().return_const(());
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
 
         let workload_spec = generate_test_workload_spec_with_runtime_config(
             SAMPLE_AGENT.to_string(),
@@ -597,9 +736,13 @@ mod tests {
         // [utest->swdd~podman-kube-create-workload-returns-workload-id~1]
         assert!(matches!(workload, Ok((workload_id, _)) if
                 workload_id.name == *WORKLOAD_INSTANCE_NAME &&
-                workload_id.manifest == SAMPLE_KUBE_CONFIG &&
                 workload_id.pods == Some(SAMPLE_POD_LIST.clone()) &&
                 workload_id.down_options == *SAMPLE_DOWN_OPTIONS));
+        // [utest->swdd~podman-kube-stores-manifest-per-workload~1]
+        assert_eq!(
+            runtime.manifest_storage.read(&WORKLOAD_INSTANCE_NAME),
+            Ok(SAMPLE_KUBE_CONFIG.to_string())
+        );
     }
 
     // [utest->swdd~podman-kube-create-continues-if-cannot-create-volume~1]
@@ -631,7 +774,7 @@ mod tests {
 
         mock_context.reset_ps_cache.expect().return_const(());
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
 
         let workload_spec = generate_test_workload_spec_with_runtime_config(
             SAMPLE_AGENT.to_string(),
@@ -644,9 +787,13 @@ mod tests {
         let workload = runtime.create_workload(workload_spec, None, sender).await;
         assert!(matches!(workload, Ok((workload_id, _)) if
                 workload_id.name == *WORKLOAD_INSTANCE_NAME &&
-                workload_id.manifest == SAMPLE_KUBE_CONFIG &&
                 workload_id.pods == Some(SAMPLE_POD_LIST.clone()) &&
                 workload_id.down_options == *SAMPLE_DOWN_OPTIONS));
+        // [utest->swdd~podman-kube-stores-manifest-per-workload~1]
+        assert_eq!(
+            runtime.manifest_storage.read(&WORKLOAD_INSTANCE_NAME),
+            Ok(SAMPLE_KUBE_CONFIG.to_string())
+        );
     }
 
     // [utest->swdd~podman-kube-create-continues-if-cannot-create-volume~1]
@@ -678,7 +825,7 @@ mod tests {
 
         mock_context.reset_ps_cache.expect().return_const(());
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
 
         let workload_spec = generate_test_workload_spec_with_runtime_config(
             SAMPLE_AGENT.to_string(),
@@ -691,9 +838,13 @@ mod tests {
         let workload = runtime.create_workload(workload_spec, None, sender).await;
         assert!(matches!(workload, Ok((workload_id, _)) if
                 workload_id.name == *WORKLOAD_INSTANCE_NAME &&
-                workload_id.manifest == SAMPLE_KUBE_CONFIG &&
                 workload_id.pods == Some(SAMPLE_POD_LIST.clone()) &&
                 workload_id.down_options == *SAMPLE_DOWN_OPTIONS));
+        // [utest->swdd~podman-kube-stores-manifest-per-workload~1]
+        assert_eq!(
+            runtime.manifest_storage.read(&WORKLOAD_INSTANCE_NAME),
+            Ok(SAMPLE_KUBE_CONFIG.to_string())
+        );
     }
 
     // [utest->swdd~podman-kube-state-getter-reset-cache~1]
@@ -736,10 +887,10 @@ mod tests {
             .expect()
             .once()
             .with(eq(SAMPLE_POD_LIST.clone()))
-            .return_const(Ok(vec![ContainerState::Running]))
+            .return_const(Ok(containers(vec![ContainerState::Running])))
             .in_sequence(&mut seq);
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
 
         let workload_spec = generate_test_workload_spec_with_runtime_config(
             SAMPLE_AGENT.to_string(),
@@ -774,7 +925,7 @@ mod tests {
             )
             .returns(Err(SAMPLE_ERROR.into()));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
 
         let workload_spec = generate_test_workload_spec_with_runtime_config(
             SAMPLE_AGENT.to_string(),
@@ -786,7 +937,10 @@ mod tests {
         let (sender, _) = tokio::sync::mpsc::channel(1);
         let workload = runtime.create_workload(workload_spec, None, sender).await;
 
-        assert!(matches!(workload, Err(RuntimeError::Create(msg)) if msg == SAMPLE_ERROR));
+        assert!(matches!(
+            workload,
+            Err(RuntimeError::Internal { source: Some(msg), .. }) if msg == SAMPLE_ERROR
+        ));
     }
 
     // [utest->swdd~podman-kube-get-workload-id-uses-volumes~1]
@@ -801,15 +955,19 @@ mod tests {
             .read_data(WORKLOAD_INSTANCE_NAME.as_pods_volume())
             .returns(Ok(r#"["pod1","pod2"]"#.into()));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         let workload = runtime.get_workload_id(&WORKLOAD_INSTANCE_NAME).await;
 
         assert!(matches!(workload, Ok(workload) if
             workload.name == *WORKLOAD_INSTANCE_NAME &&
             workload.pods == Some(SAMPLE_POD_LIST.clone()) &&
-            workload.manifest == SAMPLE_KUBE_CONFIG &&
             workload.down_options == *SAMPLE_DOWN_OPTIONS
         ));
+        // [utest->swdd~podman-kube-stores-manifest-per-workload~1]
+        assert_eq!(
+            runtime.manifest_storage.read(&WORKLOAD_INSTANCE_NAME),
+            Ok(SAMPLE_KUBE_CONFIG.to_string())
+        );
     }
 
     #[tokio::test]
@@ -823,15 +981,19 @@ mod tests {
             .read_data(WORKLOAD_INSTANCE_NAME.as_pods_volume())
             .returns(Err(SAMPLE_ERROR.into()));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         let workload = runtime.get_workload_id(&WORKLOAD_INSTANCE_NAME).await;
 
         assert!(matches!(workload, Ok(workload) if
             workload.name == *WORKLOAD_INSTANCE_NAME &&
             workload.pods.is_none() &&
-            workload.manifest == SAMPLE_KUBE_CONFIG &&
             workload.down_options == *SAMPLE_DOWN_OPTIONS
         ));
+        // [utest->swdd~podman-kube-stores-manifest-per-workload~1]
+        assert_eq!(
+            runtime.manifest_storage.read(&WORKLOAD_INSTANCE_NAME),
+            Ok(SAMPLE_KUBE_CONFIG.to_string())
+        );
     }
 
     #[tokio::test]
@@ -845,15 +1007,19 @@ mod tests {
             .read_data(WORKLOAD_INSTANCE_NAME.as_pods_volume())
             .returns(Ok(r#"{"#.into()));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         let workload = runtime.get_workload_id(&WORKLOAD_INSTANCE_NAME).await;
 
         assert!(matches!(workload, Ok(workload) if
             workload.name == *WORKLOAD_INSTANCE_NAME &&
             workload.pods.is_none() &&
-            workload.manifest == SAMPLE_KUBE_CONFIG &&
             workload.down_options == *SAMPLE_DOWN_OPTIONS
         ));
+        // [utest->swdd~podman-kube-stores-manifest-per-workload~1]
+        assert_eq!(
+            runtime.manifest_storage.read(&WORKLOAD_INSTANCE_NAME),
+            Ok(SAMPLE_KUBE_CONFIG.to_string())
+        );
     }
 
     #[tokio::test]
@@ -864,7 +1030,7 @@ mod tests {
             .read_data(WORKLOAD_INSTANCE_NAME.as_config_volume())
             .returns(Err(SAMPLE_ERROR.into()));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         let workload = runtime.get_workload_id(&WORKLOAD_INSTANCE_NAME).await;
 
         assert!(matches!(workload, Err(..)));
@@ -878,7 +1044,7 @@ mod tests {
             .read_data(WORKLOAD_INSTANCE_NAME.as_config_volume())
             .returns(Ok("{".into()));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         let workload = runtime.get_workload_id(&WORKLOAD_INSTANCE_NAME).await;
 
         assert!(matches!(workload, Err(..)));
@@ -888,7 +1054,7 @@ mod tests {
     async fn utest_delete_workload_success() {
         let mock_context = MockContext::new().await;
 
-        // [utest->swdd~podman-kube-delete-workload-downs-manifest-file~1]
+        // [utest->swdd~podman-kube-delete-workload-downs-manifest-file~2]
         mock_context
             .down_kube(&*SAMPLE_DOWN_OPTIONS, SAMPLE_KUBE_CONFIG)
             .returns(Ok(()));
@@ -901,10 +1067,19 @@ mod tests {
             .remove_volume(WORKLOAD_INSTANCE_NAME.as_pods_volume())
             .returns(Ok(()));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
+        runtime
+            .manifest_storage
+            .store(&WORKLOAD_INSTANCE_NAME, SAMPLE_KUBE_CONFIG)
+            .unwrap();
         let workload = runtime.delete_workload(&WORKLOAD_ID).await;
 
         assert!(matches!(workload, Ok(())));
+        // [utest->swdd~podman-kube-removes-stored-manifest~1]
+        assert!(runtime
+            .manifest_storage
+            .read(&WORKLOAD_INSTANCE_NAME)
+            .is_err());
     }
 
     #[tokio::test]
@@ -921,7 +1096,11 @@ mod tests {
             .remove_volume(WORKLOAD_INSTANCE_NAME.as_pods_volume())
             .returns(Err(SAMPLE_ERROR.into()));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
+        runtime
+            .manifest_storage
+            .store(&WORKLOAD_INSTANCE_NAME, SAMPLE_KUBE_CONFIG)
+            .unwrap();
         let workload = runtime.delete_workload(&WORKLOAD_ID).await;
 
         assert!(matches!(workload, Ok(())));
@@ -935,14 +1114,68 @@ mod tests {
             .down_kube(&*SAMPLE_DOWN_OPTIONS, SAMPLE_KUBE_CONFIG)
             .returns(Err(SAMPLE_ERROR.into()));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
+        runtime
+            .manifest_storage
+            .store(&WORKLOAD_INSTANCE_NAME, SAMPLE_KUBE_CONFIG)
+            .unwrap();
         let workload = runtime.delete_workload(&WORKLOAD_ID).await;
 
         assert!(matches!(workload, Err(..)));
+        assert_eq!(
+            runtime.manifest_storage.read(&WORKLOAD_INSTANCE_NAME),
+            Ok(SAMPLE_KUBE_CONFIG.to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn utest_delete_workload_fails_if_manifest_is_missing() {
+        let _mock_context = MockContext::new().await;
+
+        let (runtime, _manifests_dir) = create_runtime();
+        let workload = runtime.delete_workload(&WORKLOAD_ID).await;
+
+        assert!(matches!(workload, Err(RuntimeError::NotFound(..))));
+    }
+
+    // [utest->swdd~podman-kube-dry-run-workload-validates-config-and-manifest~1]
+    #[tokio::test]
+    async fn utest_dry_run_workload_success() {
+        let _mock_context = MockContext::new().await;
+
+        let workload_spec = generate_test_workload_spec_with_runtime_config(
+            SAMPLE_AGENT.to_string(),
+            SAMPLE_WORKLOAD_1.to_string(),
+            PODMAN_KUBE_RUNTIME_NAME.to_string(),
+            SAMPLE_RUNTIME_CONFIG.to_string(),
+        );
+
+        let (runtime, manifests_dir) = create_runtime();
+        assert_eq!(runtime.dry_run_workload(&workload_spec).await, Ok(()));
+        assert_eq!(manifests_dir.path().read_dir().unwrap().count(), 0);
+    }
+
+    // [utest->swdd~podman-kube-dry-run-workload-validates-config-and-manifest~1]
+    #[tokio::test]
+    async fn utest_dry_run_workload_fails_on_invalid_manifest() {
+        let _mock_context = MockContext::new().await;
+
+        let workload_spec = generate_test_workload_spec_with_runtime_config(
+            SAMPLE_AGENT.to_string(),
+            SAMPLE_WORKLOAD_1.to_string(),
+            PODMAN_KUBE_RUNTIME_NAME.to_string(),
+            r#"{"manifest": "kind: Pod\n---\nmetadata: ["}"#.to_string(),
+        );
+
+        let (runtime, _manifests_dir) = create_runtime();
+        assert!(matches!(
+            runtime.dry_run_workload(&workload_spec).await,
+            Err(RuntimeError::InvalidConfig(..))
+        ));
     }
 
     // [utest->swdd~podman-kube-state-getter-maps-state~2]
-    // [utest->swdd~podman-kube-state-getter-combines-states~2]
+    // [utest->swdd~podman-kube-state-getter-combines-states~3]
     #[tokio::test]
     async fn utest_get_state_failed() {
         let mock_context = MockContext::new().await;
@@ -960,14 +1193,17 @@ mod tests {
                 ContainerState::Stopping,
             ]));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         let execution_state = runtime.get_state(&WORKLOAD_ID).await;
 
-        assert_eq!(execution_state, ExecutionState::failed("Exit code: '1'"));
+        assert_eq!(
+            execution_state,
+            ExecutionState::failed("Failed containers: 'container_1' (exit code: '1')")
+        );
     }
 
     // [utest->swdd~podman-kube-state-getter-maps-state~2]
-    // [utest->swdd~podman-kube-state-getter-combines-states~2]
+    // [utest->swdd~podman-kube-state-getter-combines-states~3]
     #[tokio::test]
     async fn utest_get_state_starting() {
         let mock_context = MockContext::new().await;
@@ -984,17 +1220,17 @@ mod tests {
                 ContainerState::Stopping,
             ]));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         let execution_state = runtime.get_state(&WORKLOAD_ID).await;
 
         assert_eq!(
             execution_state,
-            ExecutionState::starting("starting container")
+            ExecutionState::starting("Starting containers: 'container_0'")
         );
     }
 
     // [utest->swdd~podman-kube-state-getter-maps-state~2]
-    // [utest->swdd~podman-kube-state-getter-combines-states~2]
+    // [utest->swdd~podman-kube-state-getter-combines-states~3]
     #[tokio::test]
     async fn utest_get_state_unknown() {
         let mock_context = MockContext::new().await;
@@ -1009,17 +1245,19 @@ mod tests {
                 ContainerState::Unknown,
             ]));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         let execution_state = runtime.get_state(&WORKLOAD_ID).await;
 
         assert_eq!(
             execution_state,
-            ExecutionState::unknown("unknown container state")
+            ExecutionState::unknown(
+                "Containers in unknown state: 'container_1' (paused), 'container_3'"
+            )
         );
     }
 
     // [utest->swdd~podman-kube-state-getter-maps-state~2]
-    // [utest->swdd~podman-kube-state-getter-combines-states~2]
+    // [utest->swdd~podman-kube-state-getter-combines-states~3]
     #[tokio::test]
     async fn utest_get_state_unknown_from_paused() {
         let mock_context = MockContext::new().await;
@@ -1033,17 +1271,17 @@ mod tests {
                 ContainerState::Running,
             ]));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         let execution_state = runtime.get_state(&WORKLOAD_ID).await;
 
         assert_eq!(
             execution_state,
-            ExecutionState::unknown("unknown container state")
+            ExecutionState::unknown("Containers in unknown state: 'container_1' (paused)")
         );
     }
 
     // [utest->swdd~podman-kube-state-getter-maps-state~2]
-    // [utest->swdd~podman-kube-state-getter-combines-states~2]
+    // [utest->swdd~podman-kube-state-getter-combines-states~3]
     #[tokio::test]
     async fn utest_get_state_running() {
         let mock_context = MockContext::new().await;
@@ -1053,14 +1291,62 @@ mod tests {
             .list_states_from_pods(&*SAMPLE_POD_LIST)
             .returns(Ok(vec![ContainerState::Exited(0), ContainerState::Running]));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         let execution_state = runtime.get_state(&WORKLOAD_ID).await;
 
         assert_eq!(execution_state, ExecutionState::running());
     }
 
+    // [utest->swdd~podman-kube-state-getter-combines-states~3]
+    // [utest->swdd~podman-kube-state-getter-names-containers-in-combined-state~1]
+    #[tokio::test]
+    async fn utest_get_state_failed_names_all_failed_containers() {
+        let mock_context = MockContext::new().await;
+
+        mock_context
+            .list_states_from_pods(&*SAMPLE_POD_LIST)
+            .returns(Ok(vec![
+                ContainerState::Exited(2),
+                ContainerState::Running,
+                ContainerState::Exited(1),
+            ]));
+
+        let (runtime, _manifests_dir) = create_runtime();
+        let execution_state = runtime.get_state(&WORKLOAD_ID).await;
+
+        assert_eq!(
+            execution_state,
+            ExecutionState::failed(
+                "Failed containers: 'container_0' (exit code: '2'), 'container_2' (exit code: '1')"
+            )
+        );
+    }
+
+    // [utest->swdd~podman-kube-state-getter-combines-states~3]
+    // [utest->swdd~podman-kube-state-getter-names-containers-in-combined-state~1]
+    #[tokio::test]
+    async fn utest_get_state_stopping() {
+        let mock_context = MockContext::new().await;
+
+        mock_context
+            .list_states_from_pods(&*SAMPLE_POD_LIST)
+            .returns(Ok(vec![
+                ContainerState::Exited(0),
+                ContainerState::Stopping,
+                ContainerState::Stopping,
+            ]));
+
+        let (runtime, _manifests_dir) = create_runtime();
+        let execution_state = runtime.get_state(&WORKLOAD_ID).await;
+
+        assert_eq!(
+            execution_state,
+            ExecutionState::stopping("Stopping containers: 'container_1', 'container_2'")
+        );
+    }
+
     // [utest->swdd~podman-kube-state-getter-maps-state~2]
-    // [utest->swdd~podman-kube-state-getter-combines-states~2]
+    // [utest->swdd~podman-kube-state-getter-combines-states~3]
     #[tokio::test]
     async fn utest_get_state_succeeded() {
         let mock_context = MockContext::new().await;
@@ -1070,14 +1356,14 @@ mod tests {
             .list_states_from_pods(&*SAMPLE_POD_LIST)
             .returns(Ok(vec![ContainerState::Exited(0)]));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         let execution_state = runtime.get_state(&WORKLOAD_ID).await;
 
         assert_eq!(execution_state, ExecutionState::succeeded());
     }
 
     // [utest->swdd~podman-kube-state-getter-removed-if-no-container~1]
-    // [utest->swdd~podman-kube-state-getter-combines-states~2]
+    // [utest->swdd~podman-kube-state-getter-combines-states~3]
     #[tokio::test]
     async fn utest_get_state_removed() {
         let mock_context = MockContext::new().await;
@@ -1087,7 +1373,7 @@ mod tests {
             .list_states_from_pods(&*SAMPLE_POD_LIST)
             .returns(Ok(vec![]));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         let execution_state = runtime.get_state(&WORKLOAD_ID).await;
 
         assert_eq!(execution_state, ExecutionState::lost())
@@ -1101,7 +1387,7 @@ mod tests {
             .list_states_from_pods(&*SAMPLE_POD_LIST)
             .returns(Err(SAMPLE_ERROR.into()));
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         let execution_state = runtime.get_state(&WORKLOAD_ID).await;
 
         assert_eq!(
@@ -1117,7 +1403,7 @@ mod tests {
             ..WORKLOAD_ID.clone()
         };
 
-        let runtime = PodmanKubeRuntime {};
+        let (runtime, _manifests_dir) = create_runtime();
         let execution_state = runtime.get_state(&workload_id).await;
 
         assert_eq!(execution_state, ExecutionState::succeeded());
@@ -1263,7 +1549,8 @@ mod tests {
             let list_states_from_pods = &self.list_states_from_pods;
             let pods: Vec<String> = pods.into_iter().map(|x| x.to_string()).collect();
             ReturnsStruct {
-                function: |result| {
+                function: |result: Result<Vec<ContainerState>, String>| {
+                    let result = result.map(containers);
                     list_states_from_pods
                         .expect()
                         .with(eq(pods))
@@ -1,7 +1,14 @@
-use common::objects::WorkloadSpec;
+use std::collections::{BTreeMap, HashMap};
+
+use common::{
+    input_limits::InputLimits,
+    objects::{ConfigObject, WorkloadSpec},
+};
 
 use super::podman_kube_runtime::PODMAN_KUBE_RUNTIME_NAME;
 
+const YAML_DOCUMENT_SEPARATOR: &str = "---";
+
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PodmanKubeRuntimeConfig {
@@ -14,6 +21,46 @@ pub struct PodmanKubeRuntimeConfig {
     pub manifest: String,
 }
 
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigMap<'a> {
+    api_version: &'static str,
+    kind: &'static str,
+    metadata: ConfigMapMetadata<'a>,
+    data: BTreeMap<&'a String, &'a String>,
+}
+
+#[derive(serde::Serialize)]
+struct ConfigMapMetadata<'a> {
+    name: &'a str,
+}
+
+// [impl->swdd~podman-kube-materializes-configs-as-config-maps~1]
+fn render_config_maps(configs: &HashMap<String, ConfigObject>) -> Result<String, String> {
+    let mut config_names: Vec<&String> = configs.keys().collect();
+    config_names.sort();
+
+    let mut rendered = String::new();
+    for config_name in config_names {
+        let config_map = ConfigMap {
+            api_version: "v1",
+            kind: "ConfigMap",
+            metadata: ConfigMapMetadata { name: config_name },
+            data: configs[config_name].iter().collect(),
+        };
+        let config_map = serde_yaml::to_string(&config_map).map_err(|err| {
+            format!(
+                "Could not render config '{}' as ConfigMap: '{}'",
+                config_name, err
+            )
+        })?;
+        rendered.push_str(&config_map);
+        rendered.push_str(YAML_DOCUMENT_SEPARATOR);
+        rendered.push('\n');
+    }
+    Ok(rendered)
+}
+
 impl TryFrom<&WorkloadSpec> for PodmanKubeRuntimeConfig {
     type Error = String;
     fn try_from(workload_spec: &WorkloadSpec) -> Result<Self, Self::Error> {
@@ -23,10 +70,25 @@ impl TryFrom<&WorkloadSpec> for PodmanKubeRuntimeConfig {
                 workload_spec.runtime
             ));
         }
-        match serde_yaml::from_str(workload_spec.runtime_config.as_str()) {
-            Ok(workload_cfg) => Ok(workload_cfg),
-            Err(e) => Err(e.to_string()),
+        // [impl->swdd~agent-rejects-runtime-config-exceeding-input-limits~1]
+        let mut workload_cfg: PodmanKubeRuntimeConfig = InputLimits::configured()
+            .parse_runtime_config(
+                workload_spec.instance_name.workload_name(),
+                workload_spec.runtime_config.as_str(),
+            )
+            .map_err(|e| e.to_string())?;
+
+        // The referenced configs are prepended to the manifest as ConfigMaps, such that the pods
+        // can use them, e.g., as environment variables or volumes.
+        if !workload_spec.configs.is_empty() {
+            let manifest = workload_cfg.manifest.trim_start();
+            let manifest = manifest
+                .strip_prefix(YAML_DOCUMENT_SEPARATOR)
+                .map(str::trim_start)
+                .unwrap_or(manifest);
+            workload_cfg.manifest = render_config_maps(&workload_spec.configs)? + manifest;
         }
+        Ok(workload_cfg)
     }
 }
 
@@ -40,6 +102,8 @@ impl TryFrom<&WorkloadSpec> for PodmanKubeRuntimeConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use common::objects::generate_test_workload_spec_with_param;
 
     use super::{PodmanKubeRuntimeConfig, PODMAN_KUBE_RUNTIME_NAME};
@@ -88,4 +152,53 @@ mod tests {
                 == *MANIFEST_CONTENT
         );
     }
+
+    // [utest->swdd~podman-kube-materializes-configs-as-config-maps~1]
+    #[tokio::test]
+    async fn utest_podman_kube_config_prepends_config_maps() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_KUBE_RUNTIME_NAME.to_string(),
+        );
+
+        workload_spec.runtime_config = "manifest: \"---\\nkind: Pod\\n\"".to_string();
+        workload_spec.configs = HashMap::from([
+            (
+                "config_b".to_string(),
+                HashMap::from([("key".to_string(), "value_b".to_string())]),
+            ),
+            (
+                "config_a".to_string(),
+                HashMap::from([
+                    ("key_2".to_string(), "value_2".to_string()),
+                    ("key_1".to_string(), "value_1".to_string()),
+                ]),
+            ),
+        ]);
+
+        assert_eq!(
+            PodmanKubeRuntimeConfig::try_from(&workload_spec)
+                .unwrap()
+                .manifest,
+            concat!(
+                "apiVersion: v1\n",
+                "kind: ConfigMap\n",
+                "metadata:\n",
+                "  name: config_a\n",
+                "data:\n",
+                "  key_1: value_1\n",
+                "  key_2: value_2\n",
+                "---\n",
+                "apiVersion: v1\n",
+                "kind: ConfigMap\n",
+                "metadata:\n",
+                "  name: config_b\n",
+                "data:\n",
+                "  key: value_b\n",
+                "---\n",
+                "kind: Pod\n",
+            )
+        );
+    }
 }
@@ -0,0 +1,186 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use serde::Deserialize;
+
+const ROOT_OPTION: &str = "--root";
+const RUN_ROOT_OPTION: &str = "--runroot";
+const WRITE_CHECK_FILE: &str = ".ankaios-write-check";
+
+static STORAGE_OPTIONS: OnceLock<Vec<String>> = OnceLock::new();
+
+// [impl->swdd~agent-passes-podman-storage-to-all-podman-calls~1]
+#[derive(Debug, Default, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PodmanStorage {
+    pub root: Option<PathBuf>,
+    pub run_root: Option<PathBuf>,
+}
+
+impl PodmanStorage {
+    /// Verifies that the configured storage folders are usable by podman.
+    ///
+    /// Missing folders are created. A folder is usable if it is given as an absolute path,
+    /// is a directory and is writable by the agent.
+    ///
+    // [impl->swdd~agent-verifies-podman-storage~1]
+    pub fn prepare(&self) -> Result<(), String> {
+        if let (Some(root), Some(run_root)) = (&self.root, &self.run_root) {
+            if root == run_root {
+                return Err(format!(
+                    "The podman storage root and run root must differ, both are '{}'.",
+                    root.display()
+                ));
+            }
+        }
+        for (name, folder) in [("root", &self.root), ("run root", &self.run_root)] {
+            if let Some(folder) = folder {
+                prepare_folder(folder).map_err(|err| {
+                    format!(
+                        "The podman storage {} '{}' is unusable: {}",
+                        name,
+                        folder.display(),
+                        err
+                    )
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    fn options(&self) -> Vec<String> {
+        [(ROOT_OPTION, &self.root), (RUN_ROOT_OPTION, &self.run_root)]
+            .into_iter()
+            .filter_map(|(option, folder)| {
+                folder
+                    .as_ref()
+                    .map(|folder| format!("{option}={}", folder.display()))
+            })
+            .collect()
+    }
+}
+
+fn prepare_folder(folder: &Path) -> Result<(), String> {
+    if !folder.is_absolute() {
+        return Err("the path is not absolute".to_string());
+    }
+    fs::create_dir_all(folder).map_err(|err| format!("could not create it: '{err}'"))?;
+    if !folder.is_dir() {
+        return Err("the path is not a directory".to_string());
+    }
+    let check_file = folder.join(WRITE_CHECK_FILE);
+    fs::write(&check_file, []).map_err(|err| format!("it is not writable: '{err}'"))?;
+    let _ = fs::remove_file(check_file);
+    Ok(())
+}
+
+/// Sets the podman storage passed to all podman calls of the agent.
+///
+/// # Arguments
+///
+/// * `storage` - The podman storage of the agent config
+///
+// [impl->swdd~agent-passes-podman-storage-to-all-podman-calls~1]
+pub fn set_podman_storage(storage: &PodmanStorage) {
+    if STORAGE_OPTIONS.set(storage.options()).is_err() {
+        log::warn!("The podman storage is already set.");
+    }
+}
+
+// The global options to be passed to podman before the podman command.
+pub(super) fn storage_options() -> Vec<&'static str> {
+    STORAGE_OPTIONS
+        .get()
+        .map(|options| options.iter().map(String::as_str).collect())
+        .unwrap_or_default()
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::PodmanStorage;
+
+    // [utest->swdd~agent-passes-podman-storage-to-all-podman-calls~1]
+    #[test]
+    fn utest_podman_storage_options() {
+        let storage = PodmanStorage {
+            root: Some(PathBuf::from("/data/containers/storage")),
+            run_root: Some(PathBuf::from("/run/ankaios/containers")),
+        };
+
+        assert_eq!(
+            storage.options(),
+            vec![
+                "--root=/data/containers/storage",
+                "--runroot=/run/ankaios/containers"
+            ]
+        );
+        assert!(PodmanStorage::default().options().is_empty());
+    }
+
+    // [utest->swdd~agent-verifies-podman-storage~1]
+    #[test]
+    fn utest_podman_storage_prepare_creates_missing_folders() {
+        let folder = tempfile::tempdir().unwrap();
+        let storage = PodmanStorage {
+            root: Some(folder.path().join("storage")),
+            run_root: Some(folder.path().join("run")),
+        };
+
+        assert_eq!(storage.prepare(), Ok(()));
+        assert!(folder.path().join("storage").is_dir());
+        assert!(folder.path().join("run").is_dir());
+        assert!(!folder.path().join("storage/.ankaios-write-check").exists());
+    }
+
+    // [utest->swdd~agent-verifies-podman-storage~1]
+    #[test]
+    fn utest_podman_storage_prepare_rejects_unusable_folders() {
+        let folder = tempfile::tempdir().unwrap();
+        let file = folder.path().join("file");
+        fs::write(&file, "").unwrap();
+
+        for storage in [
+            PodmanStorage {
+                root: Some(PathBuf::from("relative/storage")),
+                run_root: None,
+            },
+            PodmanStorage {
+                root: None,
+                run_root: Some(file.clone()),
+            },
+            PodmanStorage {
+                root: Some(folder.path().to_path_buf()),
+                run_root: Some(folder.path().to_path_buf()),
+            },
+        ] {
+            assert!(storage.prepare().is_err(), "{storage:?}");
+        }
+    }
+}
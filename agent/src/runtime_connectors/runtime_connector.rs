@@ -6,25 +6,118 @@ use common::objects::{AgentName, WorkloadInstanceName, WorkloadSpec, WorkloadSta
 
 use crate::{runtime_connectors::StateChecker, workload_state::WorkloadStateSender};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    pub stream: LogStream,
+    pub line: String,
+}
+
+pub type LogLineReceiver = tokio::sync::mpsc::Receiver<LogLine>;
+
+// [impl->swdd~agent-classifies-runtime-errors~1]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RuntimeError {
-    Create(String),
-    Delete(String),
-    List(String),
+    // The workload or a resource it refers to, e.g., its image, does not exist.
+    NotFound(String),
+    // The runtime cannot be reached or is temporarily not able to serve the request.
+    Unavailable(String),
+    // The runtime rejects the runtime config of the workload.
+    InvalidConfig(String),
+    // The runtime did not finish the request in time.
+    Timeout(String),
+    Internal {
+        message: String,
+        source: Option<String>,
+    },
+}
+
+// The runtime CLIs only report their error output, thus the kind of an error is derived from it.
+// The hints are checked in the given order, e.g., an unreachable socket also reports a missing file.
+type ErrorKind = fn(String) -> RuntimeError;
+
+const ERROR_OUTPUT_HINTS: [(&str, ErrorKind); 12] = [
+    ("cannot connect", RuntimeError::Unavailable),
+    ("connection refused", RuntimeError::Unavailable),
+    ("temporarily unavailable", RuntimeError::Unavailable),
+    ("timed out", RuntimeError::Timeout),
+    ("timeout", RuntimeError::Timeout),
+    ("deadline exceeded", RuntimeError::Timeout),
+    ("invalid reference format", RuntimeError::InvalidConfig),
+    ("unknown flag", RuntimeError::InvalidConfig),
+    ("no such", RuntimeError::NotFound),
+    ("not found", RuntimeError::NotFound),
+    ("does not exist", RuntimeError::NotFound),
+    ("manifest unknown", RuntimeError::NotFound),
+];
+
+impl RuntimeError {
+    // [impl->swdd~agent-classifies-runtime-errors~1]
+    pub fn from_error_output(context: impl ToString, error_output: impl ToString) -> Self {
+        let error_output = error_output.to_string();
+        let lowercase_output = error_output.to_lowercase();
+        match ERROR_OUTPUT_HINTS
+            .iter()
+            .find(|(hint, _)| lowercase_output.contains(hint))
+        {
+            Some((_, runtime_error)) => runtime_error(error_output),
+            None => RuntimeError::internal_with_source(context, error_output),
+        }
+    }
+
+    pub fn internal(message: impl ToString) -> Self {
+        RuntimeError::Internal {
+            message: message.to_string(),
+            source: None,
+        }
+    }
+
+    pub fn internal_with_source(message: impl ToString, source: impl ToString) -> Self {
+        RuntimeError::Internal {
+            message: message.to_string(),
+            source: Some(source.to_string()),
+        }
+    }
+
+    // [impl->swdd~agent-reports-reason-of-runtime-errors~1]
+    pub fn reason(&self) -> &'static str {
+        match self {
+            RuntimeError::NotFound(_) => "NotFound",
+            RuntimeError::Unavailable(_) => "Unavailable",
+            RuntimeError::InvalidConfig(_) => "InvalidConfig",
+            RuntimeError::Timeout(_) => "Timeout",
+            RuntimeError::Internal { .. } => "Internal",
+        }
+    }
+
+    // [impl->swdd~agent-does-not-retry-fatal-runtime-errors~1]
+    // A rejected runtime config fails again on every retry, whereas a missing image can still be
+    // pushed and the reason of an internal error is not known.
+    pub fn is_retriable(&self) -> bool {
+        !matches!(self, RuntimeError::InvalidConfig(_))
+    }
 }
 
 impl Display for RuntimeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            RuntimeError::Create(msg) => {
-                write!(f, "{}", msg)
-            }
-            RuntimeError::Delete(msg) => {
-                write!(f, "{}", msg)
-            }
-            RuntimeError::List(msg) => {
-                write!(f, "{}", msg)
-            }
+            RuntimeError::NotFound(msg)
+            | RuntimeError::Unavailable(msg)
+            | RuntimeError::InvalidConfig(msg)
+            | RuntimeError::Timeout(msg) => write!(f, "{}: {}", self.reason(), msg),
+            RuntimeError::Internal {
+                message,
+                source: Some(source),
+            } => write!(f, "{}: {}: {}", self.reason(), message, source),
+            RuntimeError::Internal {
+                message,
+                source: None,
+            } => write!(f, "{}: {}", self.reason(), message),
         }
     }
 }
@@ -63,6 +156,29 @@ where
     ) -> Result<StChecker, RuntimeError>;
 
     async fn delete_workload(&self, workload_id: &WorkloadId) -> Result<(), RuntimeError>;
+
+    // Validates the workload as far as possible without creating anything.
+    async fn dry_run_workload(
+        &self,
+        runtime_workload_config: &WorkloadSpec,
+    ) -> Result<(), RuntimeError>;
+
+    // Returns if a running create can be cancelled by dropping it. A workload the runtime
+    // created partially before the cancellation must be found with get_workload_id.
+    fn supports_create_cancellation(&self) -> bool {
+        false
+    }
+
+    // Streams the log lines of the workload until the workload is gone.
+    async fn follow_logs(
+        &self,
+        _workload_id: &WorkloadId,
+    ) -> Result<LogLineReceiver, RuntimeError> {
+        Err(RuntimeError::internal(format!(
+            "The runtime '{}' does not support following the logs of workloads.",
+            self.name()
+        )))
+    }
 }
 
 pub trait OwnableRuntime<WorkloadId, StChecker>: RuntimeConnector<WorkloadId, StChecker>
@@ -167,6 +283,8 @@ pub mod test {
             Option<PathBuf>,
             Result<(String, StubStateChecker), RuntimeError>,
         ),
+        // A create that does not finish until it is cancelled.
+        CreateWorkloadPending(WorkloadSpec, Option<PathBuf>),
         GetWorkloadId(WorkloadInstanceName, Result<String, RuntimeError>),
         StartChecker(
             String,
@@ -175,6 +293,7 @@ pub mod test {
             Result<StubStateChecker, RuntimeError>,
         ),
         DeleteWorkload(String, Result<(), RuntimeError>),
+        DryRunWorkload(WorkloadSpec, Result<(), RuntimeError>),
     }
 
     #[derive(Debug)]
@@ -204,6 +323,7 @@ pub mod test {
         CallType: std::fmt::Debug,
     {
         call_checker: Arc<Mutex<CallChecker<CallType>>>,
+        create_cancellation: bool,
     }
 
     impl<CallType> MockBase<CallType>
@@ -213,9 +333,14 @@ pub mod test {
         pub fn new() -> Self {
             MockBase {
                 call_checker: Arc::new(Mutex::new(CallChecker::new())),
+                create_cancellation: false,
             }
         }
 
+        pub fn support_create_cancellation(&mut self) {
+            self.create_cancellation = true;
+        }
+
         pub async fn expect(&mut self, calls: Vec<CallType>) {
             self.call_checker
                 .lock()
@@ -264,6 +389,7 @@ pub mod test {
         fn clone(&self) -> Self {
             Self {
                 call_checker: self.call_checker.clone(),
+                create_cancellation: self.create_cancellation,
             }
         }
     }
@@ -309,6 +435,14 @@ pub mod test {
                 {
                     return result;
                 }
+                RuntimeCall::CreateWorkloadPending(
+                    expected_runtime_workload_config,
+                    expected_control_interface_path,
+                ) if expected_runtime_workload_config == runtime_workload_config
+                    && expected_control_interface_path == control_interface_path =>
+                {
+                    return std::future::pending().await;
+                }
                 expected_call => {
                     self.unexpected_call().await;
                     panic!("Unexpected create_workload call. Expected: '{expected_call:?}'\n\nGot: {runtime_workload_config:?}, {control_interface_path:?}");
@@ -316,6 +450,10 @@ pub mod test {
             }
         }
 
+        fn supports_create_cancellation(&self) -> bool {
+            self.create_cancellation
+        }
+
         async fn get_workload_id(
             &self,
             instance_name: &WorkloadInstanceName,
@@ -371,5 +509,82 @@ pub mod test {
                 }
             }
         }
+
+        async fn dry_run_workload(
+            &self,
+            runtime_workload_config: &WorkloadSpec,
+        ) -> Result<(), RuntimeError> {
+            match self.get_expected_call().await {
+                RuntimeCall::DryRunWorkload(expected_runtime_workload_config, result)
+                    if expected_runtime_workload_config == *runtime_workload_config =>
+                {
+                    return result;
+                }
+                expected_call => {
+                    self.unexpected_call().await;
+                    panic!("Unexpected dry_run_workload call. Expected: '{expected_call:?}'\n\nGot: {runtime_workload_config:?}");
+                }
+            }
+        }
+    }
+
+    // [utest->swdd~agent-classifies-runtime-errors~1]
+    #[test]
+    fn utest_runtime_error_from_error_output_classifies_by_hints() {
+        assert_eq!(
+            RuntimeError::from_error_output(
+                "Could not create the container",
+                "Error: alpine:99: image not known: manifest unknown"
+            ),
+            RuntimeError::NotFound("Error: alpine:99: image not known: manifest unknown".into())
+        );
+        assert!(matches!(
+            RuntimeError::from_error_output(
+                "Could not list the workloads",
+                "Cannot connect to Podman: no such file or directory"
+            ),
+            RuntimeError::Unavailable(_)
+        ));
+        assert!(matches!(
+            RuntimeError::from_error_output(
+                "Could not delete the container",
+                "operation timed out"
+            ),
+            RuntimeError::Timeout(_)
+        ));
+        assert!(matches!(
+            RuntimeError::from_error_output(
+                "Could not create the container",
+                "Error: invalid reference format"
+            ),
+            RuntimeError::InvalidConfig(_)
+        ));
+        assert_eq!(
+            RuntimeError::from_error_output("Could not create the container", "exit code 125"),
+            RuntimeError::internal_with_source("Could not create the container", "exit code 125")
+        );
+    }
+
+    // [utest->swdd~agent-reports-reason-of-runtime-errors~1]
+    // [utest->swdd~agent-does-not-retry-fatal-runtime-errors~1]
+    #[test]
+    fn utest_runtime_error_reason_and_retriable() {
+        let internal_error =
+            RuntimeError::internal_with_source("Could not create the container", "exit code 125");
+        assert_eq!(
+            internal_error.to_string(),
+            "Internal: Could not create the container: exit code 125"
+        );
+        assert!(internal_error.is_retriable());
+
+        let invalid_config_error = RuntimeError::InvalidConfig("broken runtime config".into());
+        assert_eq!(
+            invalid_config_error.to_string(),
+            "InvalidConfig: broken runtime config"
+        );
+        assert!(!invalid_config_error.is_retriable());
+
+        assert!(RuntimeError::Unavailable("no socket".into()).is_retriable());
+        assert_eq!(RuntimeError::Timeout("slow".into()).reason(), "Timeout");
     }
 }
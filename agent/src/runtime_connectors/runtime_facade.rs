@@ -19,6 +19,7 @@ use crate::{
 use crate::workload::control_loop_state::ControlLoopState;
 #[cfg_attr(test, mockall_double::double)]
 use crate::workload::workload_control_loop::WorkloadControlLoop;
+use crate::workload::workload_hooks::AllowedHooks;
 #[cfg_attr(test, mockall_double::double)]
 use crate::workload::Workload;
 use crate::workload::WorkloadCommandSender;
@@ -53,6 +54,8 @@ pub trait RuntimeFacade: Send + Sync + 'static {
         update_state_tx: &WorkloadStateSender,
         report_workload_states_for_workload: bool,
     );
+
+    async fn dry_run_workload(&self, runtime_workload: &WorkloadSpec) -> Result<(), RuntimeError>;
 }
 
 pub struct GenericRuntimeFacade<
@@ -60,6 +63,7 @@ pub struct GenericRuntimeFacade<
     StChecker: StateChecker<WorkloadId> + Send + Sync,
 > {
     runtime: Box<dyn OwnableRuntime<WorkloadId, StChecker>>,
+    allowed_hooks: AllowedHooks,
 }
 
 impl<WorkloadId, StChecker> GenericRuntimeFacade<WorkloadId, StChecker>
@@ -67,8 +71,14 @@ where
     WorkloadId: ToString + Send + Sync + 'static,
     StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
 {
-    pub fn new(runtime: Box<dyn OwnableRuntime<WorkloadId, StChecker>>) -> Self {
-        GenericRuntimeFacade { runtime }
+    pub fn new(
+        runtime: Box<dyn OwnableRuntime<WorkloadId, StChecker>>,
+        allowed_hooks: AllowedHooks,
+    ) -> Self {
+        GenericRuntimeFacade {
+            runtime,
+            allowed_hooks,
+        }
     }
 }
 
@@ -91,6 +101,16 @@ impl<
         self.runtime.get_reusable_workloads(agent_name).await
     }
 
+    // [impl->swdd~agent-facade-forwards-dry-run-workload-call~1]
+    async fn dry_run_workload(&self, runtime_workload: &WorkloadSpec) -> Result<(), RuntimeError> {
+        log::debug!(
+            "Dry run of workload '{}' on runtime '{}'.",
+            runtime_workload.instance_name.workload_name(),
+            self.runtime.name()
+        );
+        self.runtime.dry_run_workload(runtime_workload).await
+    }
+
     // [impl->swdd~agent-create-workload~1]
     fn create_workload(
         &self,
@@ -176,6 +196,7 @@ impl<
         );
         let (workload_command_tx, workload_command_receiver) = WorkloadCommandSender::new();
         let workload_command_sender = workload_command_tx.clone();
+        let allowed_hooks = self.allowed_hooks.clone();
         let task_handle = tokio::spawn(async move {
             workload_command_sender
                 .create()
@@ -191,6 +212,7 @@ impl<
                 .runtime(runtime)
                 .workload_command_receiver(workload_command_receiver)
                 .retry_sender(workload_command_sender)
+                .allowed_hooks(allowed_hooks)
                 .build()
                 .unwrap_or_illegal_state();
 
@@ -222,6 +244,7 @@ impl<
 
         let (workload_command_tx, workload_command_receiver) = WorkloadCommandSender::new();
         let workload_command_sender = workload_command_tx.clone();
+        let allowed_hooks = self.allowed_hooks.clone();
         let task_handle = tokio::spawn(async move {
             // let instance_name = workload_spec.instance_name.clone();
             workload_command_sender
@@ -237,6 +260,7 @@ impl<
                 .runtime(runtime)
                 .workload_command_receiver(workload_command_receiver)
                 .retry_sender(workload_command_sender)
+                .allowed_hooks(allowed_hooks)
                 .build()
                 .unwrap_or_illegal_state();
 
@@ -324,8 +348,9 @@ mod tests {
         control_interface::MockPipesChannelContextInfo,
         runtime_connectors::{
             runtime_connector::test::{MockRuntimeConnector, RuntimeCall, StubStateChecker},
-            GenericRuntimeFacade, OwnableRuntime, RuntimeFacade,
+            GenericRuntimeFacade, OwnableRuntime, RuntimeError, RuntimeFacade,
         },
+        workload::workload_hooks::AllowedHooks,
         workload::ControlLoopState,
         workload::MockWorkload,
         workload::MockWorkloadControlLoop,
@@ -351,6 +376,8 @@ mod tests {
         let workload_state = WorkloadState {
             instance_name: workload_instance_name.clone(),
             execution_state: ExecutionState::initial(),
+            agent_timestamp: None,
+            server_timestamp: None,
         };
 
         runtime_mock
@@ -364,6 +391,7 @@ mod tests {
             Box::new(runtime_mock.clone());
         let test_runtime_facade = Box::new(GenericRuntimeFacade::<String, StubStateChecker>::new(
             ownable_runtime_mock,
+            AllowedHooks::default(),
         ));
 
         assert_eq!(
@@ -380,6 +408,39 @@ mod tests {
         runtime_mock.assert_all_expectations().await;
     }
 
+    // [utest->swdd~agent-facade-forwards-dry-run-workload-call~1]
+    #[tokio::test]
+    async fn utest_runtime_facade_dry_run_workload() {
+        let mut runtime_mock = MockRuntimeConnector::new();
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.into(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        runtime_mock
+            .expect(vec![RuntimeCall::DryRunWorkload(
+                workload_spec.clone(),
+                Err(RuntimeError::NotFound("image not found".to_string())),
+            )])
+            .await;
+
+        let ownable_runtime_mock: Box<dyn OwnableRuntime<String, StubStateChecker>> =
+            Box::new(runtime_mock.clone());
+        let test_runtime_facade = Box::new(GenericRuntimeFacade::<String, StubStateChecker>::new(
+            ownable_runtime_mock,
+            AllowedHooks::default(),
+        ));
+
+        assert_eq!(
+            test_runtime_facade.dry_run_workload(&workload_spec).await,
+            Err(RuntimeError::NotFound("image not found".to_string()))
+        );
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
     // [utest->swdd~agent-create-workload~1]
     #[tokio::test]
     async fn utest_runtime_facade_create_workload() {
@@ -424,6 +485,7 @@ mod tests {
             Box::new(runtime_mock.clone());
         let test_runtime_facade = Box::new(GenericRuntimeFacade::<String, StubStateChecker>::new(
             ownable_runtime_mock,
+            AllowedHooks::default(),
         ));
 
         let mock_control_loop = MockWorkloadControlLoop::run_context();
@@ -481,6 +543,7 @@ mod tests {
             Box::new(runtime_mock.clone());
         let test_runtime_facade = Box::new(GenericRuntimeFacade::<String, StubStateChecker>::new(
             ownable_runtime_mock,
+            AllowedHooks::default(),
         ));
 
         let (task_handle, _workload) = test_runtime_facade.resume_workload_non_blocking(
@@ -520,6 +583,7 @@ mod tests {
             Box::new(runtime_mock.clone());
         let test_runtime_facade = Box::new(GenericRuntimeFacade::<String, StubStateChecker>::new(
             ownable_runtime_mock,
+            AllowedHooks::default(),
         ));
 
         let report_workload_states_for_workload = true;
@@ -572,6 +636,7 @@ mod tests {
             Box::new(runtime_mock.clone());
         let test_runtime_facade = Box::new(GenericRuntimeFacade::<String, StubStateChecker>::new(
             ownable_runtime_mock,
+            AllowedHooks::default(),
         ));
 
         let report_workload_states_for_workload = false;
@@ -608,7 +673,7 @@ mod tests {
                 ),
                 RuntimeCall::DeleteWorkload(
                     WORKLOAD_ID.to_string(),
-                    Err(crate::runtime_connectors::RuntimeError::Delete(
+                    Err(crate::runtime_connectors::RuntimeError::internal(
                         "delete failed".to_owned(),
                     )),
                 ),
@@ -619,6 +684,7 @@ mod tests {
             Box::new(runtime_mock.clone());
         let test_runtime_facade = Box::new(GenericRuntimeFacade::<String, StubStateChecker>::new(
             ownable_runtime_mock,
+            AllowedHooks::default(),
         ));
 
         let report_workload_states_for_workload = true;
@@ -639,7 +705,7 @@ mod tests {
                 ),
                 (
                     &workload_instance_name,
-                    ExecutionState::delete_failed("delete failed".to_owned()),
+                    ExecutionState::delete_failed("Internal: delete failed".to_owned()),
                 ),
             ],
         )
@@ -0,0 +1,203 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, path::Path};
+
+#[cfg(feature = "podman")]
+use super::podman::{PodmanRuntime, PodmanWorkloadId};
+#[cfg(feature = "podman_kube")]
+use super::podman_kube::{PodmanKubeRuntime, PodmanKubeWorkloadId, MANIFESTS_FOLDER};
+use super::RuntimeFacade;
+#[cfg(any(feature = "podman", feature = "podman_kube"))]
+use super::{
+    concurrency_limited_runtime::ConcurrencyLimitedRuntime,
+    health_reporting_runtime::HealthReportingRuntime, GenericRuntimeFacade, OwnableRuntime,
+    RuntimeConnector,
+};
+#[cfg(any(feature = "podman", feature = "podman_kube"))]
+use crate::generic_polling_state_checker::GenericPollingStateChecker;
+use crate::workload::workload_hooks::AllowedHooks;
+
+#[cfg(not(any(feature = "podman", feature = "podman_kube")))]
+compile_error!(
+    "The agent needs at least one runtime, enable the feature 'podman' or 'podman_kube'."
+);
+
+pub type RuntimeFacadeMap = HashMap<String, Box<dyn RuntimeFacade>>;
+// The maximum number of concurrent operations per runtime name, runtimes without an entry
+// are not limited.
+pub type RuntimeConcurrencyLimits = HashMap<String, usize>;
+
+// The runtimes compiled into the agent. Every runtime is gated by a cargo feature, thus
+// builds for embedded targets can leave out the runtimes they do not use.
+#[derive(Default)]
+pub struct RuntimeRegistry {
+    facades: RuntimeFacadeMap,
+    allowed_hooks: AllowedHooks,
+}
+
+impl RuntimeRegistry {
+    // [impl->swdd~agent-registers-compiled-in-runtimes~1]
+    #[cfg_attr(not(feature = "podman_kube"), allow(unused_variables))]
+    pub fn with_compiled_in_runtimes(
+        run_folder: &Path,
+        concurrency_limits: &RuntimeConcurrencyLimits,
+        allowed_hooks: AllowedHooks,
+    ) -> Self {
+        let mut registry = RuntimeRegistry {
+            allowed_hooks,
+            ..Default::default()
+        };
+
+        // [impl->swdd~agent-supports-podman~2]
+        #[cfg(feature = "podman")]
+        registry.register_runtime::<PodmanWorkloadId, _>(PodmanRuntime {}, concurrency_limits);
+
+        // [impl->swdd~agent-supports-podman-kube-runtime~1]
+        #[cfg(feature = "podman_kube")]
+        registry.register_runtime::<PodmanKubeWorkloadId, _>(
+            PodmanKubeRuntime::new(run_folder.join(MANIFESTS_FOLDER)),
+            concurrency_limits,
+        );
+
+        for runtime_name in concurrency_limits.keys() {
+            if !registry.facades.contains_key(runtime_name) {
+                log::warn!(
+                    "Ignoring the concurrency limit of runtime '{}' as it is not compiled in.",
+                    runtime_name
+                );
+            }
+        }
+
+        registry
+    }
+
+    // [impl->swdd~agent-limits-concurrent-operations-per-runtime~1]
+    #[cfg(any(feature = "podman", feature = "podman_kube"))]
+    fn register_runtime<WorkloadId, R>(
+        &mut self,
+        runtime: R,
+        concurrency_limits: &RuntimeConcurrencyLimits,
+    ) where
+        WorkloadId: ToString + Send + Sync + 'static,
+        R: RuntimeConnector<WorkloadId, GenericPollingStateChecker> + Clone + 'static,
+    {
+        let runtime_name = runtime.name();
+        // [impl->swdd~agent-reports-subsystem-health~1]
+        let runtime = HealthReportingRuntime::new(runtime, &runtime_name);
+        let runtime: Box<dyn OwnableRuntime<WorkloadId, GenericPollingStateChecker>> =
+            match concurrency_limits.get(&runtime_name) {
+                Some(max_concurrent_operations) => {
+                    log::debug!(
+                        "Limiting runtime '{}' to {} concurrent operations.",
+                        runtime_name,
+                        max_concurrent_operations
+                    );
+                    Box::new(ConcurrencyLimitedRuntime::new(
+                        runtime,
+                        *max_concurrent_operations,
+                    ))
+                }
+                None => Box::new(runtime),
+            };
+        self.register(
+            runtime_name,
+            Box::new(GenericRuntimeFacade::new(
+                runtime,
+                self.allowed_hooks.clone(),
+            )),
+        );
+    }
+
+    fn register(&mut self, runtime_name: String, facade: Box<dyn RuntimeFacade>) {
+        self.facades.insert(runtime_name, facade);
+    }
+
+    // [impl->swdd~agent-registers-compiled-in-runtimes~1]
+    // The names are reported to the server with the AgentHello.
+    pub fn runtime_names(&self) -> Vec<String> {
+        let mut runtime_names: Vec<String> = self.facades.keys().cloned().collect();
+        runtime_names.sort();
+        runtime_names
+    }
+
+    pub fn into_facades(self) -> RuntimeFacadeMap {
+        self.facades
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{RuntimeConcurrencyLimits, RuntimeRegistry};
+    use crate::workload::workload_hooks::AllowedHooks;
+
+    // [utest->swdd~agent-registers-compiled-in-runtimes~1]
+    #[test]
+    fn utest_runtime_registry_contains_compiled_in_runtimes() {
+        let registry = RuntimeRegistry::with_compiled_in_runtimes(
+            Path::new("/tmp/run_folder"),
+            &RuntimeConcurrencyLimits::new(),
+            AllowedHooks::default(),
+        );
+
+        let expected_runtime_names: Vec<String> = vec![
+            #[cfg(feature = "podman")]
+            "podman".to_string(),
+            #[cfg(feature = "podman_kube")]
+            "podman-kube".to_string(),
+        ];
+
+        assert_eq!(registry.runtime_names(), expected_runtime_names);
+        assert_eq!(registry.into_facades().len(), expected_runtime_names.len());
+    }
+
+    // [utest->swdd~agent-limits-concurrent-operations-per-runtime~1]
+    #[test]
+    fn utest_runtime_registry_registers_limited_runtimes_under_their_name() {
+        let concurrency_limits = RuntimeConcurrencyLimits::from([
+            ("podman".to_string(), 4),
+            ("podman-kube".to_string(), 1),
+            ("unknown-runtime".to_string(), 2),
+        ]);
+
+        let registry = RuntimeRegistry::with_compiled_in_runtimes(
+            Path::new("/tmp/run_folder"),
+            &concurrency_limits,
+            AllowedHooks::default(),
+        );
+
+        assert!(!registry
+            .runtime_names()
+            .contains(&"unknown-runtime".to_string()));
+        assert_eq!(
+            registry.runtime_names(),
+            RuntimeRegistry::with_compiled_in_runtimes(
+                Path::new("/tmp/run_folder"),
+                &RuntimeConcurrencyLimits::new(),
+                AllowedHooks::default()
+            )
+            .runtime_names()
+        );
+    }
+}
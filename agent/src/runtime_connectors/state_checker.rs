@@ -16,6 +16,11 @@ where
 {
     // [impl->swdd~allowed-workload-states~2]
     async fn get_state(&self, workload_id: &WorkloadId) -> ExecutionState;
+
+    // The output of a terminated workload, None if the runtime does not support capturing it.
+    async fn get_output(&self, _workload_id: &WorkloadId) -> Option<String> {
+        None
+    }
 }
 
 // [impl->swdd~agent-general-state-checker-interface~1]
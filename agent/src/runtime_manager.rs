@@ -13,20 +13,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap, HashSet},
     path::{Path, PathBuf},
 };
+use tokio::time::Instant;
 
 use common::{
-    commands::Response,
+    commands::{AgentEvent, EventKind, PendingWorkloadOperation, Response},
     objects::{
-        AgentName, DeletedWorkload, ExecutionState, WorkloadInstanceName, WorkloadSpec,
-        WorkloadState,
+        AddCondition, AgentName, DeletedWorkload, DisconnectPolicy, ExecutionState,
+        ExecutionStateEnum, FulfilledBy, WorkloadInstanceName, WorkloadSpec, WorkloadState,
     },
+    persistence::PersistenceFormat,
     request_id_prepending::detach_prefix_from_request_id,
-    to_server_interface::ToServerSender,
+    to_server_interface::{ToServer, ToServerSender},
 };
 
+use crate::control_interface::{
+    remove_pipes_folder, remove_stale_pipes_folders, write_log_level_file,
+};
 #[cfg_attr(test, mockall_double::double)]
 use crate::control_interface::PipesChannelContext;
 
@@ -39,8 +44,16 @@ use crate::workload_scheduler::scheduler::WorkloadScheduler;
 #[cfg_attr(test, mockall_double::double)]
 use crate::workload_state::workload_state_store::WorkloadStateStore;
 use crate::{
+    resource_monitor::ResourceMonitor,
     runtime_connectors::RuntimeFacade,
     workload_operation::WorkloadOperation,
+    workload_scheduler::{
+        fair_dispatch::{interleave_by_owner_per_priority, owner_of},
+        queue_storage::{QueueStorage, QUEUE_FILE_NAME},
+        resource_gate::ResourceGate,
+        schedule_triggers::ScheduleTriggers,
+        startup_watch::StartupWatch,
+    },
     workload_state::{WorkloadStateSender, WorkloadStateSenderInterface},
 };
 
@@ -50,6 +63,54 @@ use crate::workload::Workload;
 #[cfg(test)]
 use mockall::automock;
 
+// [impl->swdd~agent-stops-workloads-with-running-excluded-dependency~1]
+// An unknown execution state does not stop the workload, as the excluded workload may not run.
+fn excluded_dependency_running(
+    workload_spec: &WorkloadSpec,
+    changed_workload_names: &[String],
+    workload_state_db: &WorkloadStateStore,
+) -> bool {
+    workload_spec
+        .dependencies
+        .iter()
+        .filter(|(dependency_name, add_condition)| {
+            **add_condition == AddCondition::AddCondNotRunning
+                && changed_workload_names.contains(dependency_name)
+        })
+        .any(|(dependency_name, add_condition)| {
+            workload_state_db
+                .get_state_of_workload(dependency_name)
+                .is_some_and(|wl_state| {
+                    !wl_state.is_unknown() && !add_condition.fulfilled_by(wl_state)
+                })
+        })
+}
+
+// [impl->swdd~agent-handles-dependency-failure-of-running-workloads~1]
+// A failed dependency fulfilling the add condition, e.g. ADD_COND_FAILED, does not count.
+// [impl->swdd~agent-reports-slow-workload-starts~1]
+// The start has ended once the workload runs or has terminated. The stopping of the replaced
+// instance of an updated workload does not end the start of the new one.
+fn start_ended(execution_state: &ExecutionState) -> bool {
+    matches!(
+        execution_state.state,
+        ExecutionStateEnum::Running(_)
+            | ExecutionStateEnum::Succeeded(_)
+            | ExecutionStateEnum::Failed(_)
+    )
+}
+
+fn dependency_failed(workload_spec: &WorkloadSpec, failed_dependency_names: &[String]) -> bool {
+    !workload_spec.on_dependency_failure.is_ignore()
+        && workload_spec
+            .dependencies
+            .iter()
+            .any(|(dependency_name, add_condition)| {
+                failed_dependency_names.contains(dependency_name)
+                    && *add_condition != AddCondition::AddCondFailed
+            })
+}
+
 fn flatten(
     mut runtime_workload_map: HashMap<String, HashMap<String, WorkloadSpec>>,
 ) -> Vec<WorkloadSpec> {
@@ -65,10 +126,27 @@ pub struct RuntimeManager {
     control_interface_tx: ToServerSender,
     initial_workload_list_received: bool,
     workloads: HashMap<String, Workload>,
+    // the specs of the workloads in the workloads map, used to detect re-delivered workloads
+    workload_specs: HashMap<String, WorkloadSpec>,
     // [impl->swdd~agent-supports-multiple-runtime-connectors~1]
     runtime_map: HashMap<String, Box<dyn RuntimeFacade>>,
     update_state_tx: WorkloadStateSender,
     workload_queue: WorkloadScheduler,
+    // existing workloads with a restored pending delete mapped to their runtime and instance name
+    restored_workloads_to_delete: HashMap<String, (String, WorkloadInstanceName)>,
+    // workloads with the disconnect policy 'Fallback', only started in degraded mode
+    fallback_workloads: HashMap<String, WorkloadSpec>,
+    // workloads with a schedule, only started at the times matching the schedule
+    schedule_triggers: ScheduleTriggers,
+    // workloads with resource requests, only started when the free resources cover the requests
+    resource_gate: ResourceGate,
+    resource_monitor: ResourceMonitor,
+    // started workloads with an expected startup time, reported as slow if not running in time
+    startup_watch: StartupWatch,
+    // names of the local workloads defined in the agent config, not managed by the server
+    local_workload_names: HashSet<String>,
+    // names of the workloads last reported as failed, used to detect the transition to failed
+    failed_workload_names: HashSet<String>,
 }
 
 #[cfg_attr(test, automock)]
@@ -86,14 +164,182 @@ impl RuntimeManager {
             control_interface_tx,
             initial_workload_list_received: false,
             workloads: HashMap::new(),
+            workload_specs: HashMap::new(),
             runtime_map,
             update_state_tx: update_state_tx.clone(),
             workload_queue: WorkloadScheduler::new(update_state_tx),
+            restored_workloads_to_delete: HashMap::new(),
+            fallback_workloads: HashMap::new(),
+            schedule_triggers: ScheduleTriggers::new(),
+            resource_gate: ResourceGate::new(),
+            resource_monitor: ResourceMonitor::new(),
+            startup_watch: StartupWatch::new(),
+            local_workload_names: HashSet::new(),
+            failed_workload_names: HashSet::new(),
+        }
+    }
+
+    // [impl->swdd~agent-restores-pending-workload-operations~1]
+    pub fn restore_pending_workload_operations(&mut self, persistence_format: PersistenceFormat) {
+        self.workload_queue.restore_queue(QueueStorage::new(
+            self.run_folder.join(QUEUE_FILE_NAME),
+            persistence_format,
+        ));
+    }
+
+    // [impl->swdd~agent-starts-local-workloads~1]
+    pub async fn start_local_workloads(&mut self, local_workloads: Vec<WorkloadSpec>) {
+        let mut local_workloads: HashMap<String, WorkloadSpec> = local_workloads
+            .into_iter()
+            .map(|workload_spec| {
+                (
+                    workload_spec.instance_name.workload_name().to_owned(),
+                    workload_spec,
+                )
+            })
+            .collect();
+        self.local_workload_names = local_workloads.keys().cloned().collect();
+
+        for (runtime_name, runtime) in &self.runtime_map {
+            let workload_states = match runtime.get_reusable_workloads(&self.agent_name).await {
+                Ok(workload_states) => workload_states,
+                Err(err) => {
+                    log::warn!("Could not get reusable running workloads: '{}'", err);
+                    continue;
+                }
+            };
+
+            for workload_state in workload_states {
+                let workload_name = workload_state.instance_name.workload_name().to_owned();
+                let is_resumable = local_workloads.get(&workload_name).is_some_and(|spec| {
+                    spec.runtime == *runtime_name
+                        && Self::is_resumable_workload(&workload_state, &spec.instance_name)
+                });
+
+                if is_resumable {
+                    if let Some(workload_spec) = local_workloads.remove(&workload_name) {
+                        log::info!("Resuming local workload '{}'", workload_name);
+                        let control_interface = Self::create_control_interface(
+                            &self.run_folder,
+                            self.control_interface_tx.clone(),
+                            &workload_spec,
+                        );
+                        self.workload_specs
+                            .insert(workload_name.clone(), workload_spec.clone());
+                        self.workloads.insert(
+                            workload_name,
+                            runtime.resume_workload(
+                                workload_spec,
+                                control_interface,
+                                &self.update_state_tx,
+                            ),
+                        );
+                    }
+                } else if self.local_workload_names.contains(&workload_name) {
+                    log::info!("Replacing existing local workload '{}'.", workload_name);
+                    const REPORT_WORKLOAD_STATES_FOR_WORKLOAD: bool = false;
+                    runtime.delete_workload(
+                        workload_state.instance_name,
+                        &self.update_state_tx,
+                        REPORT_WORKLOAD_STATES_FOR_WORKLOAD,
+                    );
+                }
+            }
+        }
+
+        for (_, workload_spec) in local_workloads {
+            log::info!(
+                "Starting local workload '{}'.",
+                workload_spec.instance_name.workload_name()
+            );
+            self.add_workload(workload_spec).await;
         }
     }
 
     // [impl->swdd~agent-handles-workloads-with-fulfilled-dependencies~1]
+    // [impl->swdd~agent-reevaluates-only-dependents-of-changed-workloads~1]
     pub async fn update_workloads_on_fulfilled_dependencies(
+        &mut self,
+        changed_workload_names: &[String],
+        workload_state_db: &WorkloadStateStore,
+    ) {
+        let mut workload_operations = self
+            .workload_queue
+            .next_workload_operations_of_dependents(changed_workload_names, workload_state_db)
+            .await;
+
+        // [impl->swdd~agent-stops-workloads-with-running-excluded-dependency~1]
+        let excluding_workload_specs: Vec<WorkloadSpec> = self
+            .workload_specs
+            .values()
+            .filter(|workload_spec| {
+                excluded_dependency_running(
+                    workload_spec,
+                    changed_workload_names,
+                    workload_state_db,
+                )
+            })
+            .cloned()
+            .collect();
+        if !excluding_workload_specs.is_empty() {
+            workload_operations.extend(
+                self.workload_queue
+                    .enqueue_workloads_with_running_excluded_dependencies(
+                        excluding_workload_specs,
+                        workload_state_db,
+                    )
+                    .await,
+            );
+        }
+
+        // [impl->swdd~agent-reports-slow-workload-starts~1]
+        for workload_name in changed_workload_names {
+            if workload_state_db
+                .get_state_of_workload(workload_name)
+                .is_some_and(start_ended)
+            {
+                self.startup_watch.remove(workload_name);
+            }
+        }
+
+        // [impl->swdd~agent-handles-dependency-failure-of-running-workloads~1]
+        let mut newly_failed_workload_names = Vec::new();
+        for workload_name in changed_workload_names {
+            if !workload_state_db
+                .get_state_of_workload(workload_name)
+                .is_some_and(ExecutionState::is_failed)
+            {
+                self.failed_workload_names.remove(workload_name);
+            } else if self.failed_workload_names.insert(workload_name.clone()) {
+                newly_failed_workload_names.push(workload_name.clone());
+            }
+        }
+        let failed_dependency_workload_specs: Vec<WorkloadSpec> = self
+            .workload_specs
+            .values()
+            .filter(|workload_spec| dependency_failed(workload_spec, &newly_failed_workload_names))
+            .cloned()
+            .collect();
+        if !failed_dependency_workload_specs.is_empty() {
+            workload_operations.extend(
+                self.workload_queue
+                    .enqueue_workloads_with_failed_dependencies(
+                        failed_dependency_workload_specs,
+                        workload_state_db,
+                    )
+                    .await,
+            );
+        }
+
+        if !workload_operations.is_empty() {
+            self.execute_workload_operations(workload_operations).await;
+        }
+    }
+
+    // [impl->swdd~agent-evaluates-agent-dependencies~1]
+    // The connected agents are not part of the dependents index of the queue, thus the whole
+    // queue is evaluated again.
+    pub async fn update_workloads_on_connected_agents(
         &mut self,
         workload_state_db: &WorkloadStateStore,
     ) {
@@ -101,16 +347,121 @@ impl RuntimeManager {
             .workload_queue
             .next_workload_operations(workload_state_db)
             .await;
-
         if !workload_operations.is_empty() {
             self.execute_workload_operations(workload_operations).await;
         }
     }
 
+    // [impl->swdd~agent-reports-exceeded-update-deadline~1]
+    pub fn set_pending_operations_deadline(
+        &mut self,
+        workload_names: &[String],
+        deadline: Instant,
+    ) {
+        self.workload_queue.set_deadline(workload_names, deadline);
+    }
+
+    // [impl->swdd~agent-starts-scheduled-workloads-at-matching-times~1]
+    // [impl->swdd~agent-holds-workload-starts-until-free-resources-cover-requests~1]
+    // [impl->swdd~agent-reports-slow-workload-starts~1]
+    pub fn next_pending_operations_deadline(&self) -> Option<Instant> {
+        [
+            self.workload_queue.next_deadline(),
+            self.schedule_triggers.next_trigger(),
+            self.resource_gate.next_check(),
+            self.startup_watch.next_deadline(),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+    }
+
+    // [impl->swdd~agent-reports-scheduler-queue-to-server~1]
+    pub fn pending_workload_operations(
+        &self,
+        workload_state_db: &WorkloadStateStore,
+    ) -> Vec<PendingWorkloadOperation> {
+        self.workload_queue
+            .pending_workload_operations(workload_state_db)
+    }
+
+    // [impl->swdd~agent-subscribes-to-workload-states-of-dependencies~1]
+    // The dependencies of all workloads of the agent, including the ones waiting in the queue,
+    // for a schedule, for free resources or for the degraded mode.
+    pub fn workload_state_subscription(&self) -> Vec<String> {
+        let workload_specs = self
+            .workload_specs
+            .values()
+            .chain(self.fallback_workloads.values())
+            .chain(
+                self.schedule_triggers
+                    .iter()
+                    .map(|(_, workload_spec)| workload_spec),
+            )
+            .chain(self.resource_gate.held_workloads());
+        let mut workload_names: BTreeSet<String> = workload_specs
+            .flat_map(|workload_spec| workload_spec.add_conditions())
+            .map(|(dependency_name, _)| dependency_name.clone())
+            .collect();
+        workload_names.extend(self.workload_queue.dependency_names());
+        workload_names.into_iter().collect()
+    }
+
+    // [impl->swdd~agent-reports-exceeded-update-deadline~1]
+    // [impl->swdd~agent-reevaluates-pending-workloads-after-running-for~1]
+    // [impl->swdd~agent-starts-scheduled-workloads-at-matching-times~1]
+    // [impl->swdd~agent-holds-workload-starts-until-free-resources-cover-requests~1]
+    // [impl->swdd~agent-reports-slow-workload-starts~1]
+    pub async fn expire_pending_workload_operations(
+        &mut self,
+        workload_state_db: &WorkloadStateStore,
+    ) {
+        let now = Instant::now();
+        self.workload_queue
+            .expire_pending_workload_operations(now)
+            .await;
+
+        for (instance_name, expected_startup_time_ms) in self.startup_watch.take_exceeded(now) {
+            self.report_slow_start(instance_name, expected_startup_time_ms)
+                .await;
+        }
+
+        let mut workload_operations = self
+            .workload_queue
+            .next_workload_operations_after_running_for(now, workload_state_db)
+            .await;
+
+        let scheduled_workload_operations: Vec<WorkloadOperation> = self
+            .schedule_triggers
+            .take_triggered(now)
+            .into_iter()
+            .map(|workload_spec| self.scheduled_workload_operation(workload_spec))
+            .collect();
+        if !scheduled_workload_operations.is_empty() {
+            workload_operations.extend(
+                self.workload_queue
+                    .enqueue_filtered_workload_operations(
+                        scheduled_workload_operations,
+                        workload_state_db,
+                    )
+                    .await,
+            );
+        }
+
+        // the held workloads are released on the execution if the free resources cover them
+        let resource_check_due = self
+            .resource_gate
+            .next_check()
+            .is_some_and(|next_check| next_check <= now);
+        if !workload_operations.is_empty() || resource_check_due {
+            self.execute_workload_operations(workload_operations).await;
+        }
+    }
+
     // [impl->swdd~agent-handles-update-workload-requests~1]
     pub async fn handle_update_workload(
         &mut self,
-        mut added_workloads: Vec<WorkloadSpec>,
+        added_workloads: Vec<WorkloadSpec>,
         deleted_workloads: Vec<DeletedWorkload>,
         workload_state_db: &WorkloadStateStore,
     ) {
@@ -120,7 +471,31 @@ impl RuntimeManager {
             deleted_workloads.len()
         );
 
-        if !self.initial_workload_list_received {
+        // [impl->swdd~agent-ignores-server-workloads-named-like-local-workloads~1]
+        let (added_workloads, deleted_workloads) =
+            self.remove_local_workloads(added_workloads, deleted_workloads);
+
+        // [impl->swdd~agent-keeps-fallback-workloads-until-degraded-mode~1]
+        let (added_workloads, deleted_workloads) = self
+            .take_fallback_workloads(added_workloads, deleted_workloads)
+            .await;
+
+        // [impl->swdd~agent-keeps-scheduled-workloads-until-matching-time~1]
+        let (added_workloads, deleted_workloads) = self
+            .take_scheduled_workloads(added_workloads, deleted_workloads)
+            .await;
+
+        // [impl->swdd~agent-holds-workload-starts-until-free-resources-cover-requests~1]
+        self.remove_held_workloads(&added_workloads, &deleted_workloads);
+
+        // [impl->swdd~agent-applies-log-level-live~1]
+        let (mut added_workloads, deleted_workloads) = self
+            .apply_live_log_level_updates(added_workloads, deleted_workloads, workload_state_db)
+            .await;
+
+        let mut workload_operations: Vec<WorkloadOperation> = Vec::new();
+        let is_initial_workload_list = !self.initial_workload_list_received;
+        if is_initial_workload_list {
             self.initial_workload_list_received = true;
             if !deleted_workloads.is_empty() {
                 log::error!(
@@ -129,54 +504,359 @@ impl RuntimeManager {
                 );
             }
 
+            // [impl->swdd~agent-reconciles-restored-pending-workload-operations~1]
+            let restored_pending_deletes = self
+                .workload_queue
+                .reconcile_restored_queue(&added_workloads);
+
             // [impl->swdd~agent-initial-list-existing-workloads~1]
             added_workloads = self
-                .resume_and_remove_from_added_workloads(added_workloads)
+                .resume_and_remove_from_added_workloads(added_workloads, &restored_pending_deletes)
                 .await;
+
+            workload_operations.extend(
+                restored_pending_deletes
+                    .into_iter()
+                    .map(WorkloadOperation::Delete),
+            );
+
+            // [impl->swdd~agent-removes-stale-control-interface-pipes~1]
+            remove_stale_pipes_folders(&self.run_folder, &self.used_pipes_folders());
         }
 
-        let workload_operations: Vec<WorkloadOperation> =
-            self.transform_into_workload_operations(added_workloads, deleted_workloads);
+        workload_operations
+            .extend(self.transform_into_workload_operations(added_workloads, deleted_workloads));
 
         // [impl->swdd~agent-handles-new-workload-operations]
         // [impl->swdd~agent-handles-workloads-with-fulfilled-dependencies~1]
-        let ready_workload_operations = self
-            .workload_queue
-            .enqueue_filtered_workload_operations(workload_operations, workload_state_db)
-            .await;
+        let ready_workload_operations = if is_initial_workload_list {
+            // [impl->swdd~agent-orders-initial-workload-operations-topologically~1]
+            self.workload_queue
+                .enqueue_initial_workload_operations(workload_operations, workload_state_db)
+                .await
+        } else {
+            self.workload_queue
+                .enqueue_filtered_workload_operations(workload_operations, workload_state_db)
+                .await
+        };
 
         self.execute_workload_operations(ready_workload_operations)
             .await;
     }
 
+    // [impl->swdd~agent-deletes-workloads-missing-in-initial-list-after-reconnect~1]
+    pub fn get_workloads_missing_in_initial_list(
+        &self,
+        added_workloads: &[WorkloadSpec],
+    ) -> Vec<DeletedWorkload> {
+        let held_workloads = self.resource_gate.held_workloads().map(|workload_spec| {
+            (
+                workload_spec.instance_name.workload_name().to_owned(),
+                workload_spec,
+            )
+        });
+        self.workload_specs
+            .iter()
+            .chain(self.fallback_workloads.iter())
+            .chain(
+                self.schedule_triggers
+                    .iter()
+                    .filter(|(workload_name, _)| !self.workload_specs.contains_key(*workload_name)),
+            )
+            .map(|(workload_name, workload_spec)| (workload_name.clone(), workload_spec))
+            .chain(held_workloads)
+            .filter(|(workload_name, _)| {
+                !self.local_workload_names.contains(workload_name.as_str())
+            })
+            .filter(|(workload_name, _)| {
+                !added_workloads.iter().any(|workload_spec| {
+                    workload_spec.instance_name.workload_name() == workload_name.as_str()
+                })
+            })
+            .map(|(_, workload_spec)| DeletedWorkload {
+                instance_name: workload_spec.instance_name.clone(),
+                dependencies: HashMap::default(),
+            })
+            .collect()
+    }
+
+    // [impl->swdd~agent-applies-disconnect-policies-in-degraded-mode~1]
+    pub async fn enter_degraded_mode(&mut self) {
+        let stopped_workloads: Vec<DeletedWorkload> = self
+            .workload_specs
+            .values()
+            .filter(|workload_spec| workload_spec.disconnect_policy == DisconnectPolicy::Stop)
+            .map(|workload_spec| DeletedWorkload {
+                instance_name: workload_spec.instance_name.clone(),
+                dependencies: HashMap::default(),
+            })
+            .collect();
+
+        for deleted_workload in stopped_workloads {
+            log::info!(
+                "Stopping workload '{}' in degraded mode.",
+                deleted_workload.instance_name.workload_name()
+            );
+            self.delete_workload(deleted_workload).await;
+        }
+
+        // The execution states of the workloads on other agents are not available while the
+        // agent is disconnected, therefore the fallback workloads are started directly.
+        let fallback_workloads: Vec<WorkloadSpec> =
+            self.fallback_workloads.values().cloned().collect();
+        for workload_spec in fallback_workloads {
+            log::info!(
+                "Starting fallback workload '{}' in degraded mode.",
+                workload_spec.instance_name.workload_name()
+            );
+            self.add_workload(workload_spec).await;
+        }
+    }
+
+    // [impl->swdd~agent-stops-fallback-workloads-after-reconnect~1]
+    pub async fn leave_degraded_mode(&mut self) {
+        let running_fallback_workloads: Vec<DeletedWorkload> = self
+            .fallback_workloads
+            .iter()
+            .filter(|(workload_name, _)| self.workloads.contains_key(workload_name.as_str()))
+            .map(|(_, workload_spec)| DeletedWorkload {
+                instance_name: workload_spec.instance_name.clone(),
+                dependencies: HashMap::default(),
+            })
+            .collect();
+
+        for deleted_workload in running_fallback_workloads {
+            log::info!(
+                "Stopping fallback workload '{}' after reconnecting to the server.",
+                deleted_workload.instance_name.workload_name()
+            );
+            self.delete_workload(deleted_workload).await;
+        }
+    }
+
     // [impl->swdd~agent-forward-responses-to-control-interface-pipe~1]
     pub async fn forward_response(&mut self, response: Response) {
         // [impl->swdd~agent-uses-id-prefix-forward-control-interface-response-correct-workload~1]
         // [impl->swdd~agent-remove-id-prefix-forwarding-control-interface-response~1]
         let (workload_name, request_id) = detach_prefix_from_request_id(&response.request_id);
+        // [impl->swdd~agent-forwards-trace-id-to-control-interface~1]
+        let trace_id = response.trace_id;
         if let Some(workload) = self.workloads.get_mut(&workload_name) {
+            log::debug!(
+                "Forwarding response to workload '{}' (trace id '{}')",
+                workload_name,
+                trace_id
+            );
             if let Err(err) = workload
-                .forward_response(request_id, response.response_content)
+                .forward_response(request_id, trace_id.clone(), response.response_content)
                 .await
             {
                 log::warn!(
-                    "Could not forward response to workload '{}': '{}'",
+                    "Could not forward response to workload '{}' (trace id '{}'): '{}'",
                     workload_name,
+                    trace_id,
                     err
                 );
             }
         } else {
             log::warn!(
-                "Could not forward response for unknown workload: '{}'",
-                workload_name
+                "Could not forward response for unknown workload: '{}' (trace id '{}')",
+                workload_name,
+                trace_id
             );
         }
     }
 
+    // [impl->swdd~agent-ignores-server-workloads-named-like-local-workloads~1]
+    fn remove_local_workloads(
+        &self,
+        mut added_workloads: Vec<WorkloadSpec>,
+        mut deleted_workloads: Vec<DeletedWorkload>,
+    ) -> (Vec<WorkloadSpec>, Vec<DeletedWorkload>) {
+        let is_local_workload = |workload_name: &str| {
+            let is_local = self.local_workload_names.contains(workload_name);
+            if is_local {
+                log::warn!(
+                    "Ignoring workload '{}' from the server as a local workload with the same name is defined in the agent config.",
+                    workload_name
+                );
+            }
+            is_local
+        };
+
+        added_workloads.retain(|workload_spec| {
+            !is_local_workload(workload_spec.instance_name.workload_name())
+        });
+        deleted_workloads.retain(|deleted_workload| {
+            !is_local_workload(deleted_workload.instance_name.workload_name())
+        });
+        (added_workloads, deleted_workloads)
+    }
+
+    // [impl->swdd~agent-keeps-fallback-workloads-until-degraded-mode~1]
+    async fn take_fallback_workloads(
+        &mut self,
+        added_workloads: Vec<WorkloadSpec>,
+        deleted_workloads: Vec<DeletedWorkload>,
+    ) -> (Vec<WorkloadSpec>, Vec<DeletedWorkload>) {
+        let mut removed_fallback_workloads = Vec::new();
+        let mut remaining_deleted_workloads = Vec::new();
+        for deleted_workload in deleted_workloads {
+            if self
+                .fallback_workloads
+                .remove(deleted_workload.instance_name.workload_name())
+                .is_some()
+            {
+                removed_fallback_workloads.push(deleted_workload.instance_name);
+            } else {
+                remaining_deleted_workloads.push(deleted_workload);
+            }
+        }
+
+        let mut remaining_added_workloads = Vec::new();
+        for workload_spec in added_workloads {
+            removed_fallback_workloads.retain(|instance_name| {
+                instance_name.workload_name() != workload_spec.instance_name.workload_name()
+            });
+
+            if workload_spec.disconnect_policy == DisconnectPolicy::Fallback {
+                log::debug!(
+                    "Keeping fallback workload '{}' until the agent enters the degraded mode.",
+                    workload_spec.instance_name.workload_name()
+                );
+                self.update_state_tx
+                    .report_workload_execution_state(
+                        &workload_spec.instance_name,
+                        ExecutionState::waiting_for_degraded_mode(),
+                    )
+                    .await;
+                self.fallback_workloads.insert(
+                    workload_spec.instance_name.workload_name().to_owned(),
+                    workload_spec,
+                );
+            } else {
+                remaining_added_workloads.push(workload_spec);
+            }
+        }
+
+        // As the sender of the delete expects a response, report the execution state as 'Removed'
+        for instance_name in removed_fallback_workloads {
+            self.update_state_tx
+                .report_workload_execution_state(&instance_name, ExecutionState::removed())
+                .await;
+        }
+
+        (remaining_added_workloads, remaining_deleted_workloads)
+    }
+
+    // [impl->swdd~agent-keeps-scheduled-workloads-until-matching-time~1]
+    async fn take_scheduled_workloads(
+        &mut self,
+        added_workloads: Vec<WorkloadSpec>,
+        deleted_workloads: Vec<DeletedWorkload>,
+    ) -> (Vec<WorkloadSpec>, Vec<DeletedWorkload>) {
+        // The deletes are still executed as an instance started at a previous time can exist.
+        for deleted_workload in &deleted_workloads {
+            self.schedule_triggers
+                .remove(deleted_workload.instance_name.workload_name());
+        }
+
+        let mut remaining_added_workloads = Vec::new();
+        for workload_spec in added_workloads {
+            let workload_name = workload_spec.instance_name.workload_name().to_owned();
+            if self.schedule_triggers.get(&workload_name) == Some(&workload_spec) {
+                log::debug!(
+                    "Scheduled workload '{}' is already known with an equal spec. Skipping.",
+                    workload_name
+                );
+            } else if self.schedule_triggers.add(workload_spec.clone()) {
+                log::debug!(
+                    "Keeping workload '{}' until the next time matching its schedule.",
+                    workload_name
+                );
+                self.update_state_tx
+                    .report_workload_execution_state(
+                        &workload_spec.instance_name,
+                        ExecutionState::waiting_for_schedule(),
+                    )
+                    .await;
+            } else {
+                remaining_added_workloads.push(workload_spec);
+            }
+        }
+
+        (remaining_added_workloads, deleted_workloads)
+    }
+
+    // A held workload is replaced by the added workload of the same name or deleted.
+    // [impl->swdd~agent-holds-workload-starts-until-free-resources-cover-requests~1]
+    fn remove_held_workloads(
+        &mut self,
+        added_workloads: &[WorkloadSpec],
+        deleted_workloads: &[DeletedWorkload],
+    ) {
+        for instance_name in added_workloads
+            .iter()
+            .map(|workload_spec| &workload_spec.instance_name)
+            .chain(
+                deleted_workloads
+                    .iter()
+                    .map(|deleted_workload| &deleted_workload.instance_name),
+            )
+        {
+            self.resource_gate.remove(instance_name.workload_name());
+        }
+    }
+
+    // [impl->swdd~agent-holds-workload-starts-until-free-resources-cover-requests~1]
+    async fn release_by_free_resources(
+        &mut self,
+        workload_operations: Vec<WorkloadOperation>,
+    ) -> Vec<WorkloadOperation> {
+        if !self.resource_gate.is_needed(&workload_operations) {
+            return workload_operations;
+        }
+
+        let free_resources = self.resource_monitor.free_resources();
+        let (released_workload_operations, newly_held_workloads) =
+            self.resource_gate
+                .release(workload_operations, free_resources, Instant::now());
+        for (instance_name, missing_resources) in newly_held_workloads {
+            self.update_state_tx
+                .report_workload_execution_state(
+                    &instance_name,
+                    ExecutionState::insufficient_resources(&missing_resources),
+                )
+                .await;
+        }
+        released_workload_operations
+    }
+
+    // A workload of a previous matching time which still exists is replaced.
+    // [impl->swdd~agent-starts-scheduled-workloads-at-matching-times~1]
+    fn scheduled_workload_operation(&self, workload_spec: WorkloadSpec) -> WorkloadOperation {
+        if self
+            .workloads
+            .contains_key(workload_spec.instance_name.workload_name())
+        {
+            let instance_name = workload_spec.instance_name.clone();
+            WorkloadOperation::Update(
+                workload_spec,
+                DeletedWorkload {
+                    instance_name,
+                    dependencies: HashMap::default(),
+                },
+            )
+        } else {
+            WorkloadOperation::Create(workload_spec)
+        }
+    }
+
     // [impl->swdd~agent-initial-list-existing-workloads~1]
     async fn resume_and_remove_from_added_workloads(
         &mut self,
         added_workloads: Vec<WorkloadSpec>,
+        restored_pending_deletes: &[DeletedWorkload],
     ) -> Vec<WorkloadSpec> {
         log::debug!("Handling initial workload list.");
 
@@ -214,6 +894,14 @@ impl RuntimeManager {
                     );
 
                     for workload_state in workload_states {
+                        // [impl->swdd~agent-starts-local-workloads~1]
+                        if self
+                            .local_workload_names
+                            .contains(workload_state.instance_name.workload_name())
+                        {
+                            continue;
+                        }
+
                         if let Some(new_workload_spec) = added_workloads_per_runtime
                             .get_mut(runtime_name)
                             .and_then(|map| {
@@ -229,7 +917,7 @@ impl RuntimeManager {
                                 let control_interface = Self::create_control_interface(
                                     &self.run_folder,
                                     self.control_interface_tx.clone(),
-                                    &new_workload_spec.instance_name,
+                                    &new_workload_spec,
                                 );
 
                                 log::info!(
@@ -238,6 +926,10 @@ impl RuntimeManager {
                                 );
 
                                 // [impl->swdd~agent-stores-running-workload~1]
+                                self.workload_specs.insert(
+                                    new_instance_name.workload_name().to_owned(),
+                                    new_workload_spec.clone(),
+                                );
                                 self.workloads.insert(
                                     new_instance_name.workload_name().to_owned(),
                                     runtime.resume_workload(
@@ -249,8 +941,9 @@ impl RuntimeManager {
                             } else {
                                 // [impl->swdd~agent-existing-workloads-replace-updated~2]
 
-                                log::info!("Replacing existing workload '{}'.",
-                                workload_state.instance_name.workload_name()
+                                log::info!(
+                                    "Replacing existing workload '{}'.",
+                                    workload_state.instance_name.workload_name()
                                 );
 
                                 /* Temporary workaround until direct start of bundles is implemented to prevent
@@ -265,6 +958,34 @@ impl RuntimeManager {
                                 );
                                 new_added_workloads.push(new_workload_spec);
                             }
+                        } else if restored_pending_deletes.iter().any(|deleted_workload| {
+                            deleted_workload.instance_name == workload_state.instance_name
+                        }) {
+                            // [impl->swdd~agent-reconciles-restored-pending-workload-operations~1]
+                            log::info!(
+                                "Keeping workload '{}' until its restored pending delete is ready.",
+                                workload_state.instance_name.workload_name()
+                            );
+                            self.restored_workloads_to_delete.insert(
+                                workload_state.instance_name.workload_name().to_owned(),
+                                (runtime_name.clone(), workload_state.instance_name),
+                            );
+                        } else if self
+                            .schedule_triggers
+                            .contains(workload_state.instance_name.workload_name())
+                        {
+                            // [impl->swdd~agent-keeps-scheduled-workloads-until-matching-time~1]
+                            log::info!(
+                                "Removing existing scheduled workload '{}' until its next start.",
+                                workload_state.instance_name.workload_name()
+                            );
+                            // do not overwrite the reported state waiting for the schedule
+                            const REPORT_WORKLOAD_STATES_FOR_WORKLOAD: bool = false;
+                            runtime.delete_workload(
+                                workload_state.instance_name,
+                                &self.update_state_tx,
+                                REPORT_WORKLOAD_STATES_FOR_WORKLOAD,
+                            );
                         } else {
                             // No added workload matches the found running one => delete it
                             // [impl->swdd~agent-existing-workloads-delete-unneeded~1]
@@ -301,15 +1022,77 @@ impl RuntimeManager {
                 .eq(new_instance_name)
     }
 
-    // [impl->swdd~agent-transforms-update-workload-message-to-workload-operations~1]
-    fn transform_into_workload_operations(
-        &self,
+    // [impl->swdd~agent-applies-log-level-live~1]
+    async fn apply_live_log_level_updates(
+        &mut self,
         added_workloads: Vec<WorkloadSpec>,
-        deleted_workloads: Vec<DeletedWorkload>,
-    ) -> Vec<WorkloadOperation> {
-        let mut workload_operations: Vec<WorkloadOperation> = Vec::new();
-        // transform into a hashmap to be able to search for updates
-        // [impl->swdd~agent-updates-deleted-and-added-workloads~1]
+        mut deleted_workloads: Vec<DeletedWorkload>,
+        workload_state_db: &WorkloadStateStore,
+    ) -> (Vec<WorkloadSpec>, Vec<DeletedWorkload>) {
+        let mut remaining_added_workloads = Vec::new();
+        for workload_spec in added_workloads {
+            let workload_name = workload_spec.instance_name.workload_name().to_owned();
+            let deleted_position = deleted_workloads
+                .iter()
+                .position(|deleted| deleted.instance_name == workload_spec.instance_name);
+            let (Some(deleted_position), Some(log_level)) =
+                (deleted_position, workload_spec.log_level.as_ref())
+            else {
+                remaining_added_workloads.push(workload_spec);
+                continue;
+            };
+            if !self
+                .workload_specs
+                .get(&workload_name)
+                .is_some_and(|current| workload_spec.is_live_log_level_update_of(current))
+            {
+                remaining_added_workloads.push(workload_spec);
+                continue;
+            }
+
+            let control_interface_path = workload_spec
+                .instance_name
+                .pipes_folder_name(&self.run_folder);
+            if let Err(err) = write_log_level_file(&control_interface_path, &log_level.level) {
+                log::warn!(
+                    "Could not update the log level of workload '{}', restarting it instead: '{}'",
+                    workload_name,
+                    err
+                );
+                remaining_added_workloads.push(workload_spec);
+                continue;
+            }
+
+            log::info!(
+                "Updated the log level of workload '{}' to '{}' without a restart.",
+                workload_name,
+                log_level.level
+            );
+            deleted_workloads.remove(deleted_position);
+            // the server reset the state of the updated workload and expects the current one
+            if let Some(execution_state) = workload_state_db.get_state_of_workload(&workload_name) {
+                self.update_state_tx
+                    .report_workload_execution_state(
+                        &workload_spec.instance_name,
+                        execution_state.clone(),
+                    )
+                    .await;
+            }
+            self.workload_specs.insert(workload_name, workload_spec);
+        }
+
+        (remaining_added_workloads, deleted_workloads)
+    }
+
+    // [impl->swdd~agent-transforms-update-workload-message-to-workload-operations~1]
+    fn transform_into_workload_operations(
+        &mut self,
+        added_workloads: Vec<WorkloadSpec>,
+        deleted_workloads: Vec<DeletedWorkload>,
+    ) -> Vec<WorkloadOperation> {
+        let mut workload_operations: Vec<WorkloadOperation> = Vec::new();
+        // transform into a hashmap to be able to search for updates
+        // [impl->swdd~agent-updates-deleted-and-added-workloads~1]
         let mut added_workloads: HashMap<String, WorkloadSpec> = added_workloads
             .into_iter()
             .map(|workload_spec| {
@@ -337,14 +1120,33 @@ impl RuntimeManager {
         }
 
         for (_, workload_spec) in added_workloads {
-            let workload_name = workload_spec.instance_name.workload_name();
-            if self.workloads.get(workload_name).is_some() {
+            let workload_name = workload_spec.instance_name.workload_name().to_owned();
+            let known_workload_spec = self.workload_specs.get(&workload_name);
+            if known_workload_spec == Some(&workload_spec) {
+                // [impl->swdd~agent-skips-known-workload-with-equal-spec~1]
+                log::debug!(
+                    "Added workload '{}' is already known with an equal spec. Skipping.",
+                    workload_name
+                );
+            } else if self.workloads.contains_key(&workload_name)
+                && known_workload_spec.map(|known| &known.instance_name)
+                    == Some(&workload_spec.instance_name)
+            {
+                // The same runtime config results in the same instance name, e.g. when a restarted
+                // server replays its startup config. Restarting the workload is not needed.
+                // [impl->swdd~agent-adopts-known-workload-with-equal-instance-name~1]
+                log::info!(
+                    "Added workload '{}' is already running with an equal instance name. Adopting the spec without a restart.",
+                    workload_name
+                );
+                self.workload_specs.insert(workload_name, workload_spec);
+            } else if self.workloads.contains_key(&workload_name) {
                 log::warn!(
                     "Added workload '{}' already exists. Updating without considering delete dependencies.",
                     workload_name
                 );
                 // We know this workload, seems the server is sending it again, try an update
-                // [impl->swdd~agent-update-on-add-known-workload~1]
+                // [impl->swdd~agent-update-on-add-known-workload~2]
                 let instance_name = workload_spec.instance_name.clone();
                 workload_operations.push(WorkloadOperation::Update(
                     workload_spec,
@@ -362,7 +1164,28 @@ impl RuntimeManager {
         workload_operations
     }
 
+    fn owner_of_workload_operation(&self, workload_operation: &WorkloadOperation) -> String {
+        match workload_operation {
+            WorkloadOperation::Create(workload_spec)
+            | WorkloadOperation::Update(workload_spec, _) => owner_of(workload_spec).to_owned(),
+            // deleted workloads carry no tags, the owner is taken from the running workload
+            WorkloadOperation::UpdateDeleteOnly(deleted_workload)
+            | WorkloadOperation::Delete(deleted_workload) => self
+                .workload_specs
+                .get(deleted_workload.instance_name.workload_name())
+                .map(|workload_spec| owner_of(workload_spec).to_owned())
+                .unwrap_or_default(),
+        }
+    }
+
     async fn execute_workload_operations(&mut self, workload_operations: Vec<WorkloadOperation>) {
+        let workload_operations = self.release_by_free_resources(workload_operations).await;
+        // [impl->swdd~agent-dispatches-ready-workload-operations-fair-across-owners~1]
+        // [impl->swdd~agent-releases-ready-workload-operations-by-priority~1]
+        let workload_operations =
+            interleave_by_owner_per_priority(workload_operations, |workload_operation| {
+                self.owner_of_workload_operation(workload_operation)
+            });
         for wl_operation in workload_operations {
             match wl_operation {
                 WorkloadOperation::Create(workload_spec) => {
@@ -385,21 +1208,56 @@ impl RuntimeManager {
         }
     }
 
+    // [impl->swdd~agent-reports-slow-workload-starts~1]
+    // The agent keeps waiting for the workload, the slow start is only reported.
+    async fn report_slow_start(
+        &self,
+        instance_name: WorkloadInstanceName,
+        expected_startup_time_ms: u64,
+    ) {
+        log::warn!(
+            "Workload '{}' is not running after its expected startup time of {} ms.",
+            instance_name.workload_name(),
+            expected_startup_time_ms
+        );
+        self.update_state_tx
+            .report_workload_execution_state(
+                &instance_name,
+                ExecutionState::starting_slow(expected_startup_time_ms),
+            )
+            .await;
+        if let Err(err) = self
+            .control_interface_tx
+            .send(ToServer::AgentEvent(AgentEvent {
+                // set by the server from the agent connection
+                agent_name: String::new(),
+                kind: EventKind::SlowStart,
+                workload_name: instance_name.workload_name().to_owned(),
+                message: format!(
+                    "Not running after the expected startup time of {expected_startup_time_ms} ms"
+                ),
+            }))
+            .await
+        {
+            log::warn!("Could not send the slow start event: '{}'", err);
+        }
+    }
+
     async fn add_workload(&mut self, workload_spec: WorkloadSpec) {
         let workload_name = workload_spec.instance_name.workload_name().to_owned();
-        let control_interface_info = PipesChannelContextInfo::new(
-            &self.run_folder,
-            self.control_interface_tx.clone(),
-            &workload_spec.instance_name,
-        );
+        let control_interface_info = self.control_interface_info(&workload_spec);
 
         // [impl->swdd~agent-uses-specified-runtime~1]
         // [impl->swdd~agent-skips-unknown-runtime~1]
         if let Some(runtime) = self.runtime_map.get(&workload_spec.runtime) {
+            self.workload_specs
+                .insert(workload_name.clone(), workload_spec.clone());
+            // [impl->swdd~agent-reports-slow-workload-starts~1]
+            self.startup_watch.watch(&workload_spec, Instant::now());
             // [impl->swdd~agent-executes-create-workload-operation~1]
             let workload = runtime.create_workload(
                 workload_spec,
-                Some(control_interface_info),
+                control_interface_info,
                 &self.update_state_tx,
             );
             // [impl->swdd~agent-stores-running-workload~1]
@@ -414,16 +1272,47 @@ impl RuntimeManager {
     }
 
     async fn delete_workload(&mut self, deleted_workload: DeletedWorkload) {
+        self.startup_watch
+            .remove(deleted_workload.instance_name.workload_name());
+        let pre_shutdown_timeout_ms = self
+            .workload_specs
+            .remove(deleted_workload.instance_name.workload_name())
+            .and_then(|workload_spec| workload_spec.pre_shutdown_timeout_ms);
         if let Some(workload) = self
             .workloads
             .remove(deleted_workload.instance_name.workload_name())
         {
-            // [impl->swdd~agent-executes-delete-workload-operation~1]
-            if let Err(err) = workload.delete().await {
-                log::error!(
-                    "Failed to delete workload '{}': '{}'",
-                    deleted_workload.instance_name.workload_name(),
-                    err
+            let workload_name = deleted_workload.instance_name.workload_name().to_owned();
+            match pre_shutdown_timeout_ms {
+                // [impl->swdd~agent-notifies-workload-before-shutdown~1]
+                Some(timeout_ms) => {
+                    // waiting for the acknowledgement must not block other operations
+                    tokio::spawn(async move {
+                        if let Err(err) = workload.delete_after_pre_shutdown(timeout_ms).await {
+                            log::error!("Failed to delete workload '{}': '{}'", workload_name, err);
+                        }
+                    });
+                }
+                None => {
+                    // [impl->swdd~agent-executes-delete-workload-operation~1]
+                    if let Err(err) = workload.delete().await {
+                        log::error!("Failed to delete workload '{}': '{}'", workload_name, err);
+                    }
+                }
+            }
+        } else if let Some((runtime_name, instance_name)) = self
+            .restored_workloads_to_delete
+            .remove(deleted_workload.instance_name.workload_name())
+        {
+            // [impl->swdd~agent-removes-stale-control-interface-pipes~1]
+            remove_pipes_folder(&instance_name.pipes_folder_name(&self.run_folder));
+            // [impl->swdd~agent-reconciles-restored-pending-workload-operations~1]
+            if let Some(runtime) = self.runtime_map.get(&runtime_name) {
+                const REPORT_WORKLOAD_STATES_FOR_WORKLOAD: bool = true;
+                runtime.delete_workload(
+                    instance_name,
+                    &self.update_state_tx,
+                    REPORT_WORKLOAD_STATES_FOR_WORKLOAD,
                 );
             }
         } else {
@@ -431,6 +1320,12 @@ impl RuntimeManager {
                 "Workload '{}' already gone.",
                 &deleted_workload.instance_name.workload_name()
             );
+            // [impl->swdd~agent-removes-stale-control-interface-pipes~1]
+            remove_pipes_folder(
+                &deleted_workload
+                    .instance_name
+                    .pipes_folder_name(&self.run_folder),
+            );
 
             // As the sender of this delete workload command expects a response,
             // report the execution state as 'Removed'
@@ -448,14 +1343,25 @@ impl RuntimeManager {
         let workload_name = workload_spec.instance_name.workload_name().to_owned();
 
         if let Some(workload) = self.workloads.get_mut(&workload_name) {
-            let pipes_channel_context_info = PipesChannelContextInfo::new(
-                &self.run_folder,
-                self.control_interface_tx.clone(),
-                &workload_spec.instance_name,
-            );
+            // [impl->swdd~agent-creates-control-interface-only-if-enabled~1]
+            let pipes_channel_context_info = workload_spec
+                .control_interface
+                .is_enabled()
+                .then(|| {
+                    PipesChannelContextInfo::new(
+                        &self.run_folder,
+                        self.control_interface_tx.clone(),
+                        &workload_spec.instance_name,
+                        workload_spec.control_interface_requests_per_minute,
+                    )
+                });
+            self.workload_specs
+                .insert(workload_name.clone(), workload_spec.clone());
+            // [impl->swdd~agent-reports-slow-workload-starts~1]
+            self.startup_watch.watch(&workload_spec, Instant::now());
             // [impl->swdd~agent-executes-update-workload-operation~1]
             if let Err(err) = workload
-                .update(Some(workload_spec), Some(pipes_channel_context_info))
+                .update(Some(workload_spec), pipes_channel_context_info)
                 .await
             {
                 log::error!("Failed to update workload '{}': '{}'", workload_name, err);
@@ -473,6 +1379,7 @@ impl RuntimeManager {
     // [impl->swdd~agent-executes-update-delete-only-workload-operation~1]
     async fn update_delete_only(&mut self, deleted_workload: DeletedWorkload) {
         let workload_name = deleted_workload.instance_name.workload_name().to_owned();
+        self.startup_watch.remove(&workload_name);
         if let Some(workload) = self.workloads.get_mut(&workload_name) {
             if let Err(err) = workload.update(None, None).await {
                 log::error!("Failed to update workload '{}': '{}'", workload_name, err);
@@ -480,18 +1387,63 @@ impl RuntimeManager {
         }
     }
 
+    // The pipes are only created by the runtime when the create operation of the workload
+    // is executed and not already when the workload is received.
+    // [impl->swdd~agent-creates-control-interface-only-if-enabled~1]
+    fn control_interface_info(
+        &self,
+        workload_spec: &WorkloadSpec,
+    ) -> Option<PipesChannelContextInfo> {
+        if !workload_spec.control_interface.is_enabled() {
+            log::debug!(
+                "The control interface of workload '{}' is disabled.",
+                workload_spec.instance_name.workload_name()
+            );
+            return None;
+        }
+        Some(PipesChannelContextInfo::new(
+            &self.run_folder,
+            self.control_interface_tx.clone(),
+            &workload_spec.instance_name,
+            workload_spec.control_interface_requests_per_minute,
+        ))
+    }
+
+    // The pipes folders of the workloads currently managed by the agent with a control interface
+    fn used_pipes_folders(&self) -> HashSet<PathBuf> {
+        self.workload_specs
+            .values()
+            .filter(|workload_spec| workload_spec.control_interface.is_enabled())
+            .map(|workload_spec| {
+                workload_spec
+                    .instance_name
+                    .pipes_folder_name(&self.run_folder)
+            })
+            .collect()
+    }
+
     // [impl->swdd~agent-create-control-interface-pipes-per-workload~1]
+    // [impl->swdd~agent-creates-control-interface-only-if-enabled~1]
     fn create_control_interface(
         run_folder: &Path,
         control_interface_tx: ToServerSender,
-        workload_instance_name: &WorkloadInstanceName,
+        workload_spec: &WorkloadSpec,
     ) -> Option<PipesChannelContext> {
+        if !workload_spec.control_interface.is_enabled() {
+            return None;
+        }
+        let workload_instance_name = &workload_spec.instance_name;
         log::debug!(
             "Creating control interface pipes for '{:?}'",
             workload_instance_name
         );
 
-        match PipesChannelContext::new(run_folder, workload_instance_name, control_interface_tx) {
+        match PipesChannelContext::new(
+            run_folder,
+            workload_instance_name,
+            control_interface_tx,
+            workload_spec.control_interface_requests_per_minute,
+        ) {
             Ok(pipes_channel_context) => Some(pipes_channel_context),
             Err(err) => {
                 log::warn!(
@@ -515,7 +1467,10 @@ impl RuntimeManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::control_interface::{MockPipesChannelContext, MockPipesChannelContextInfo};
+    use crate::control_interface::{
+        MockPipesChannelContext, MockPipesChannelContextInfo, LOG_LEVEL_FILE_NAME,
+    };
+    use crate::resource_monitor::{generate_test_resource_monitor, FreeResources};
     use crate::runtime_connectors::{MockRuntimeFacade, RuntimeError};
     use crate::workload::{MockWorkload, WorkloadError};
     use crate::workload_scheduler::scheduler::MockWorkloadScheduler;
@@ -524,11 +1479,12 @@ mod tests {
     use common::commands::ResponseContent;
     use common::objects::{
         generate_test_workload_spec_with_dependencies, generate_test_workload_spec_with_param,
-        AddCondition, WorkloadInstanceNameBuilder, WorkloadState,
+        AddCondition, ControlInterfaceMode, DependencyFailurePolicy, ResourceRequests, Resources,
+        WorkloadInstanceNameBuilder, WorkloadState,
     };
     use common::test_utils::{
         generate_test_complete_state, generate_test_deleted_workload,
-        generate_test_deleted_workload_with_dependencies,
+        generate_test_deleted_workload_with_dependencies, TestWorkloadSpecBuilder,
     };
     use common::to_server_interface::ToServerReceiver;
     use mockall::{predicate, Sequence};
@@ -540,7 +1496,9 @@ mod tests {
     const AGENT_NAME: &str = "agent_x";
     const WORKLOAD_1_NAME: &str = "workload1";
     const WORKLOAD_2_NAME: &str = "workload2";
+    const WORKLOAD_3_NAME: &str = "workload3";
     const REQUEST_ID: &str = "request_id";
+    const TRACE_ID: &str = "trace_id";
     const RUN_FOLDER: &str = "run/folder";
 
     #[derive(Default)]
@@ -586,7 +1544,7 @@ mod tests {
         pipes_channel_info_context_mock
             .expect()
             .times(2)
-            .returning(|_, _, _| MockPipesChannelContextInfo::default());
+            .returning(|_, _, _, _| MockPipesChannelContextInfo::default());
 
         let new_workload_1 = generate_test_workload_spec_with_param(
             AGENT_NAME.to_string(),
@@ -608,7 +1566,11 @@ mod tests {
 
         let mut mock_workload_scheduler = MockWorkloadScheduler::default();
         mock_workload_scheduler
-            .expect_enqueue_filtered_workload_operations()
+            .expect_reconcile_restored_queue()
+            .once()
+            .returning(|_| vec![]);
+        mock_workload_scheduler
+            .expect_enqueue_initial_workload_operations()
             .once()
             .return_const(workload_operations);
 
@@ -671,7 +1633,7 @@ mod tests {
         pipes_channel_info_context_mock
             .expect()
             .once()
-            .return_once(|_, _, _| MockPipesChannelContextInfo::default());
+            .return_once(|_, _, _, _| MockPipesChannelContextInfo::default());
 
         let workload_with_unknown_runtime = generate_test_workload_spec_with_param(
             AGENT_NAME.to_string(),
@@ -684,7 +1646,11 @@ mod tests {
 
         let mut mock_workload_scheduler = MockWorkloadScheduler::default();
         mock_workload_scheduler
-            .expect_enqueue_filtered_workload_operations()
+            .expect_reconcile_restored_queue()
+            .once()
+            .returning(|_| vec![]);
+        mock_workload_scheduler
+            .expect_enqueue_initial_workload_operations()
             .once()
             .return_const(workload_operations);
 
@@ -728,7 +1694,7 @@ mod tests {
         pipes_channel_info_context_mock
             .expect()
             .once()
-            .return_once(|_, _, _| MockPipesChannelContextInfo::default());
+            .return_once(|_, _, _, _| MockPipesChannelContextInfo::default());
 
         let workload = generate_test_workload_spec_with_param(
             AGENT_NAME.to_string(),
@@ -740,7 +1706,11 @@ mod tests {
         let workload_operations = vec![WorkloadOperation::Create(workload)];
         let mut mock_workload_scheduler = MockWorkloadScheduler::default();
         mock_workload_scheduler
-            .expect_enqueue_filtered_workload_operations()
+            .expect_reconcile_restored_queue()
+            .once()
+            .returning(|_| vec![]);
+        mock_workload_scheduler
+            .expect_enqueue_initial_workload_operations()
             .once()
             .return_const(workload_operations);
 
@@ -756,7 +1726,7 @@ mod tests {
             .once()
             .returning(|_| {
                 Box::pin(async {
-                    Err(RuntimeError::List(
+                    Err(RuntimeError::internal(
                         "failed to get reusable workloads".to_string(),
                     ))
                 })
@@ -802,12 +1772,16 @@ mod tests {
         pipes_channel_mock
             .expect()
             .once()
-            .returning(move |_, _, _| Ok(MockPipesChannelContext::default()));
+            .returning(move |_, _, _, _| Ok(MockPipesChannelContext::default()));
 
         let workload_operations = vec![];
         let mut mock_workload_scheduler = MockWorkloadScheduler::default();
         mock_workload_scheduler
-            .expect_enqueue_filtered_workload_operations()
+            .expect_reconcile_restored_queue()
+            .once()
+            .returning(|_| vec![]);
+        mock_workload_scheduler
+            .expect_enqueue_initial_workload_operations()
             .once()
             .return_const(workload_operations);
 
@@ -827,6 +1801,8 @@ mod tests {
         let workload_state_running = WorkloadState {
             instance_name: existing_workload_instance_name,
             execution_state: ExecutionState::running(),
+            agent_timestamp: None,
+            server_timestamp: None,
         };
 
         let mut runtime_facade_mock = MockRuntimeFacade::new();
@@ -889,6 +1865,8 @@ mod tests {
         let workload_state_running = WorkloadState {
             instance_name: existing_workload_with_other_config,
             execution_state: ExecutionState::running(),
+            agent_timestamp: None,
+            server_timestamp: None,
         };
 
         let mut runtime_facade_mock = MockRuntimeFacade::new();
@@ -911,7 +1889,7 @@ mod tests {
 
         let expected_new_added_workloads = added_workloads.clone();
         let new_added_workloads = runtime_manager
-            .resume_and_remove_from_added_workloads(added_workloads)
+            .resume_and_remove_from_added_workloads(added_workloads, &[])
             .await;
 
         assert_eq!(expected_new_added_workloads, new_added_workloads);
@@ -941,6 +1919,8 @@ mod tests {
         let workload_state_succeeded = WorkloadState {
             instance_name: existing_workload.instance_name,
             execution_state: ExecutionState::succeeded(),
+            agent_timestamp: None,
+            server_timestamp: None,
         };
 
         let mut runtime_facade_mock = MockRuntimeFacade::new();
@@ -962,7 +1942,7 @@ mod tests {
 
         let expected_added_workloads = added_workloads.clone();
         let new_added_workloads = runtime_manager
-            .resume_and_remove_from_added_workloads(added_workloads)
+            .resume_and_remove_from_added_workloads(added_workloads, &[])
             .await;
 
         assert_eq!(expected_added_workloads, new_added_workloads);
@@ -990,7 +1970,11 @@ mod tests {
         })];
         let mut mock_workload_scheduler = MockWorkloadScheduler::default();
         mock_workload_scheduler
-            .expect_enqueue_filtered_workload_operations()
+            .expect_reconcile_restored_queue()
+            .once()
+            .returning(|_| vec![]);
+        mock_workload_scheduler
+            .expect_enqueue_initial_workload_operations()
             .once()
             .return_const(workload_operations);
 
@@ -1050,7 +2034,11 @@ mod tests {
         let workload_operations = vec![];
         let mut mock_workload_scheduler = MockWorkloadScheduler::default();
         mock_workload_scheduler
-            .expect_enqueue_filtered_workload_operations()
+            .expect_reconcile_restored_queue()
+            .once()
+            .returning(|_| vec![]);
+        mock_workload_scheduler
+            .expect_enqueue_initial_workload_operations()
             .once()
             .return_const(workload_operations);
 
@@ -1097,7 +2085,11 @@ mod tests {
         let workload_operations = vec![];
         let mut mock_workload_scheduler = MockWorkloadScheduler::default();
         mock_workload_scheduler
-            .expect_enqueue_filtered_workload_operations()
+            .expect_reconcile_restored_queue()
+            .once()
+            .returning(|_| vec![]);
+        mock_workload_scheduler
+            .expect_enqueue_initial_workload_operations()
             .once()
             .return_const(workload_operations);
 
@@ -1175,7 +2167,7 @@ mod tests {
         pipes_channel_info_context_mock
             .expect()
             .once()
-            .return_once(|_, _, _| MockPipesChannelContextInfo::default());
+            .return_once(|_, _, _, _| MockPipesChannelContextInfo::default());
 
         let old_workload =
             generate_test_deleted_workload(AGENT_NAME.to_string(), WORKLOAD_1_NAME.to_string());
@@ -1246,6 +2238,68 @@ mod tests {
         assert!(runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
     }
 
+    // [utest->swdd~agent-creates-control-interface-only-if-enabled~1]
+    #[tokio::test]
+    async fn utest_handle_update_workload_no_control_interface_if_disabled() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut new_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+        new_workload.control_interface = ControlInterfaceMode::Disabled;
+
+        let pipes_channel_info_context_mock = MockPipesChannelContextInfo::new_context();
+        pipes_channel_info_context_mock.expect().never();
+
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_enqueue_filtered_workload_operations()
+            .once()
+            .return_const(vec![WorkloadOperation::Create(new_workload.clone())]);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock
+            .expect_create_workload()
+            .once()
+            .with(
+                predicate::always(),
+                predicate::function(|control_interface: &Option<PipesChannelContextInfo>| {
+                    control_interface.is_none()
+                }),
+                predicate::always(),
+            )
+            .returning(move |_, _, _| MockWorkload::default());
+
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default()
+                .with_runtime(
+                    RUNTIME_NAME,
+                    Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+                )
+                .build();
+        runtime_manager.initial_workload_list_received = true;
+
+        runtime_manager
+            .handle_update_workload(
+                vec![new_workload],
+                vec![],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert!(runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+    }
+
     // [utest->swdd~agent-deletes-workload~1]
     // [utest->swdd~agent-handle-deleted-before-added-workloads~1]
     #[tokio::test]
@@ -1258,7 +2312,7 @@ mod tests {
         pipes_channel_info_context_mock
             .expect()
             .once()
-            .return_once(|_, _, _| MockPipesChannelContextInfo::default());
+            .return_once(|_, _, _, _| MockPipesChannelContextInfo::default());
 
         let new_workload = generate_test_workload_spec_with_param(
             AGENT_NAME.to_string(),
@@ -1347,7 +2401,7 @@ mod tests {
         pipes_channel_info_context_mock
             .expect()
             .once()
-            .return_once(|_, _, _| MockPipesChannelContextInfo::default());
+            .return_once(|_, _, _, _| MockPipesChannelContextInfo::default());
 
         let new_workload = generate_test_workload_spec_with_param(
             AGENT_NAME.to_string(),
@@ -1399,7 +2453,7 @@ mod tests {
         assert!(runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
     }
 
-    // [utest->swdd~agent-update-on-add-known-workload~1]
+    // [utest->swdd~agent-update-on-add-known-workload~2]
     #[tokio::test]
     async fn utest_handle_update_workload_subsequent_update_known_added() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
@@ -1416,7 +2470,7 @@ mod tests {
         pipes_channel_info_context_mock
             .expect()
             .once()
-            .return_once(|_, _, _| MockPipesChannelContextInfo::default());
+            .return_once(|_, _, _, _| MockPipesChannelContextInfo::default());
 
         let old_workload = generate_test_deleted_workload_with_dependencies(
             AGENT_NAME.to_owned(),
@@ -1478,6 +2532,162 @@ mod tests {
         assert!(runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
     }
 
+    // [utest->swdd~agent-adopts-known-workload-with-equal-instance-name~1]
+    #[tokio::test]
+    async fn utest_handle_update_workload_server_restart_replay_keeps_running_workloads() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_enqueue_filtered_workload_operations()
+            .once()
+            .withf(|workload_operations, _| workload_operations.is_empty())
+            .return_const(vec![]);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let runtime_facade_mock = MockRuntimeFacade::new();
+        let (_, mut runtime_manager, _) = RuntimeManagerBuilder::default()
+            .with_runtime(
+                RUNTIME_NAME,
+                Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+            )
+            .build();
+        runtime_manager.initial_workload_list_received = true;
+
+        let running_workloads: Vec<WorkloadSpec> = [WORKLOAD_1_NAME, WORKLOAD_2_NAME]
+            .into_iter()
+            .map(|workload_name| {
+                generate_test_workload_spec_with_param(
+                    AGENT_NAME.to_owned(),
+                    workload_name.to_owned(),
+                    RUNTIME_NAME.to_owned(),
+                )
+            })
+            .collect();
+        for workload_spec in &running_workloads {
+            let workload_name = workload_spec.instance_name.workload_name().to_owned();
+            runtime_manager
+                .workload_specs
+                .insert(workload_name.clone(), workload_spec.clone());
+            // the workload mocks fail on any update or delete
+            runtime_manager
+                .workloads
+                .insert(workload_name, MockWorkload::default());
+        }
+
+        // the restarted server replays its startup config with an equal runtime config,
+        // but a spec field that is not part of the instance name has changed meanwhile
+        let mut replayed_workloads = running_workloads.clone();
+        replayed_workloads[1].restart_policy = common::objects::RestartPolicy::Always;
+
+        runtime_manager
+            .handle_update_workload(
+                replayed_workloads.clone(),
+                vec![],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert_eq!(runtime_manager.workloads.len(), 2);
+        assert_eq!(
+            runtime_manager.workload_specs.get(WORKLOAD_2_NAME),
+            Some(&replayed_workloads[1])
+        );
+    }
+
+    // [utest->swdd~agent-applies-log-level-live~1]
+    #[tokio::test]
+    async fn utest_handle_update_workload_applies_live_log_level_update_without_restart() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let current_workload = TestWorkloadSpecBuilder::new(WORKLOAD_1_NAME)
+            .agent(AGENT_NAME)
+            .runtime(RUNTIME_NAME)
+            .log_level("info", true)
+            .build();
+        let new_workload = TestWorkloadSpecBuilder::new(WORKLOAD_1_NAME)
+            .agent(AGENT_NAME)
+            .runtime(RUNTIME_NAME)
+            .log_level("debug", true)
+            .build();
+        let deleted_workload = DeletedWorkload {
+            instance_name: current_workload.instance_name.clone(),
+            dependencies: HashMap::new(),
+        };
+
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_enqueue_filtered_workload_operations()
+            .once()
+            .withf(|workload_operations, _| workload_operations.is_empty())
+            .return_const(vec![]);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let runtime_facade_mock = MockRuntimeFacade::new();
+        let (_, mut runtime_manager, mut wl_state_receiver) = RuntimeManagerBuilder::default()
+            .with_runtime(
+                RUNTIME_NAME,
+                Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+            )
+            .build();
+
+        let run_folder = tempfile::tempdir().unwrap();
+        let control_interface_path = new_workload
+            .instance_name
+            .pipes_folder_name(run_folder.path());
+        std::fs::create_dir(&control_interface_path).unwrap();
+        runtime_manager.run_folder = run_folder.path().to_path_buf();
+        runtime_manager.initial_workload_list_received = true;
+        runtime_manager
+            .workload_specs
+            .insert(WORKLOAD_1_NAME.to_owned(), current_workload);
+
+        let mut workload_state_db = MockWorkloadStateStore::default();
+        workload_state_db
+            .states_storage
+            .insert(WORKLOAD_1_NAME.to_owned(), ExecutionState::running());
+
+        runtime_manager
+            .handle_update_workload(
+                vec![new_workload.clone()],
+                vec![deleted_workload],
+                &workload_state_db,
+            )
+            .await;
+
+        assert_eq!(
+            std::fs::read_to_string(control_interface_path.join(LOG_LEVEL_FILE_NAME)).unwrap(),
+            "debug\n"
+        );
+        assert_eq!(
+            runtime_manager.workload_specs.get(WORKLOAD_1_NAME),
+            Some(&new_workload)
+        );
+        assert_eq!(
+            wl_state_receiver.recv().await,
+            Some(WorkloadState {
+                instance_name: new_workload.instance_name,
+                execution_state: ExecutionState::running(),
+                agent_timestamp: None,
+                server_timestamp: None,
+            })
+        );
+    }
+
     // [utest->swdd~agent-added-creates-workload~1]
     // [utest->swdd~agent-uses-specified-runtime~1]
     // [utest->swdd~agent-stores-running-workload~1]
@@ -1491,7 +2701,7 @@ mod tests {
         pipes_channel_info_context_mock
             .expect()
             .once()
-            .return_once(|_, _, _| MockPipesChannelContextInfo::default());
+            .return_once(|_, _, _, _| MockPipesChannelContextInfo::default());
 
         let new_workload = generate_test_workload_spec_with_param(
             AGENT_NAME.to_string(),
@@ -1716,6 +2926,7 @@ mod tests {
     // [utest->swdd~agent-forward-responses-to-control-interface-pipe~1]
     // [utest->swdd~agent-uses-id-prefix-forward-control-interface-response-correct-workload~1]
     // [utest->swdd~agent-remove-id-prefix-forwarding-control-interface-response~1]
+    // [utest->swdd~agent-forwards-trace-id-to-control-interface~1]
     #[tokio::test]
     async fn utest_forward_complete_state() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
@@ -1742,8 +2953,9 @@ mod tests {
         mock_workload
             .expect_forward_response()
             .once()
-            .withf(|request_id, response_content| {
+            .withf(|request_id, trace_id, response_content| {
                 request_id == REQUEST_ID
+                    && trace_id == TRACE_ID
                     && matches!(response_content, ResponseContent::CompleteState(complete_state) if complete_state
                         .workload_states
                         .first()
@@ -1751,7 +2963,7 @@ mod tests {
                         .instance_name.workload_name()
                         == WORKLOAD_1_NAME)
             })
-            .return_once(move |_, _| Ok(()));
+            .return_once(move |_, _, _| Ok(()));
 
         runtime_manager
             .workloads
@@ -1760,6 +2972,7 @@ mod tests {
         runtime_manager
             .forward_response(Response {
                 request_id: format!("{WORKLOAD_1_NAME}@{REQUEST_ID}"),
+                trace_id: TRACE_ID.to_string(),
                 response_content: ResponseContent::CompleteState(Box::new(
                     generate_test_complete_state(vec![generate_test_workload_spec_with_param(
                         AGENT_NAME.to_string(),
@@ -1798,8 +3011,9 @@ mod tests {
         mock_workload
             .expect_forward_response()
             .once()
-            .withf(|request_id, response_content| {
+            .withf(|request_id, trace_id, response_content| {
                 request_id == REQUEST_ID
+                    && trace_id == TRACE_ID
                     && matches!(response_content, ResponseContent::CompleteState(complete_state) if complete_state
                     .workload_states
                     .first()
@@ -1807,7 +3021,7 @@ mod tests {
                     .instance_name.workload_name()
                     == WORKLOAD_1_NAME)
             })
-            .return_once(move |_, _| {
+            .return_once(move |_, _, _| {
                 Err(WorkloadError::CompleteState(
                     "failed to send complete state".to_string(),
                 ))
@@ -1820,6 +3034,7 @@ mod tests {
         runtime_manager
             .forward_response(Response {
                 request_id: format!("{WORKLOAD_1_NAME}@{REQUEST_ID}"),
+                trace_id: TRACE_ID.to_string(),
                 response_content: ResponseContent::CompleteState(Box::new(
                     generate_test_complete_state(vec![generate_test_workload_spec_with_param(
                         AGENT_NAME.to_string(),
@@ -1860,6 +3075,7 @@ mod tests {
         runtime_manager
             .forward_response(Response {
                 request_id: format!("{WORKLOAD_1_NAME}@{REQUEST_ID}"),
+                trace_id: TRACE_ID.to_string(),
                 response_content: ResponseContent::CompleteState(Box::new(
                     generate_test_complete_state(vec![generate_test_workload_spec_with_param(
                         AGENT_NAME.to_string(),
@@ -1882,7 +3098,7 @@ mod tests {
         pipes_channel_mock
             .expect()
             .once()
-            .return_once(|_, _, _| MockPipesChannelContextInfo::default());
+            .return_once(|_, _, _, _| MockPipesChannelContextInfo::default());
 
         let next_workload_operations = vec![WorkloadOperation::Create(
             generate_test_workload_spec_with_dependencies(
@@ -1894,7 +3110,7 @@ mod tests {
         )];
         let mut mock_workload_scheduler = MockWorkloadScheduler::default();
         mock_workload_scheduler
-            .expect_next_workload_operations()
+            .expect_next_workload_operations_of_dependents()
             .once()
             .return_const(next_workload_operations);
 
@@ -1919,28 +3135,231 @@ mod tests {
                 .build();
 
         runtime_manager
-            .update_workloads_on_fulfilled_dependencies(&MockWorkloadStateStore::default())
+            .update_workloads_on_fulfilled_dependencies(
+                &[WORKLOAD_1_NAME.to_owned()],
+                &MockWorkloadStateStore::default(),
+            )
             .await;
         server_receiver.close();
 
         assert!(runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
     }
 
-    // [utest->swdd~agent-handles-workloads-with-fulfilled-dependencies~1]
+    // [utest->swdd~agent-evaluates-agent-dependencies~1]
     #[tokio::test]
-    async fn utest_update_workload_state_no_create_workload_when_dependencies_not_fulfilled() {
+    async fn utest_update_connected_agents_creates_workload_with_fulfilled_agent_dependencies() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
             .get_lock_async()
             .await;
 
-        let next_workload_operations = vec![];
-        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
-        mock_workload_scheduler
-            .expect_next_workload_operations()
+        let pipes_channel_mock = MockPipesChannelContextInfo::new_context();
+        pipes_channel_mock
+            .expect()
             .once()
-            .return_const(next_workload_operations);
+            .return_once(|_, _, _, _| MockPipesChannelContextInfo::default());
 
-        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+        workload_spec.agent_dependencies = vec!["agent_B".to_string()];
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_next_workload_operations()
+            .once()
+            .return_const(vec![WorkloadOperation::Create(workload_spec)]);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock
+            .expect_create_workload()
+            .once()
+            .return_once(|_, _, _| MockWorkload::default());
+
+        let (mut server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default()
+                .with_runtime(
+                    RUNTIME_NAME,
+                    Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+                )
+                .build();
+
+        runtime_manager
+            .update_workloads_on_connected_agents(&MockWorkloadStateStore::default())
+            .await;
+        server_receiver.close();
+
+        assert!(runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+    }
+
+    // [utest->swdd~agent-reevaluates-pending-workloads-after-running-for~1]
+    #[tokio::test]
+    async fn utest_expire_pending_workload_operations_creates_workload_running_for_reached() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let pipes_channel_mock = MockPipesChannelContextInfo::new_context();
+        pipes_channel_mock
+            .expect()
+            .once()
+            .return_once(|_, _, _, _| MockPipesChannelContextInfo::default());
+
+        let next_workload_operations = vec![WorkloadOperation::Create(
+            generate_test_workload_spec_with_dependencies(
+                AGENT_NAME,
+                WORKLOAD_1_NAME,
+                RUNTIME_NAME,
+                HashMap::from([(WORKLOAD_2_NAME.to_string(), AddCondition::AddCondRunningFor)]),
+            ),
+        )];
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_expire_pending_workload_operations()
+            .once()
+            .return_const(());
+        mock_workload_scheduler
+            .expect_next_workload_operations_after_running_for()
+            .once()
+            .return_const(next_workload_operations);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock
+            .expect_create_workload()
+            .once()
+            .return_once(|_, _, _| MockWorkload::default());
+
+        let (mut server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default()
+                .with_runtime(
+                    RUNTIME_NAME,
+                    Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+                )
+                .build();
+
+        runtime_manager
+            .expire_pending_workload_operations(&MockWorkloadStateStore::default())
+            .await;
+        server_receiver.close();
+
+        assert!(runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+    }
+
+    // [utest->swdd~agent-reports-slow-workload-starts~1]
+    #[tokio::test]
+    async fn utest_expire_pending_workload_operations_reports_slow_start() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_next_workload_operations_of_dependents()
+            .once()
+            .return_const(vec![]);
+        mock_workload_scheduler
+            .expect_expire_pending_workload_operations()
+            .once()
+            .return_const(());
+        mock_workload_scheduler
+            .expect_next_workload_operations_after_running_for()
+            .once()
+            .return_const(vec![]);
+        mock_workload_scheduler
+            .expect_next_deadline()
+            .once()
+            .return_const(None);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let (mut server_receiver, mut runtime_manager, mut wl_state_receiver) =
+            RuntimeManagerBuilder::default().build();
+
+        let mut slow_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        slow_workload.expected_startup_time_ms = Some(0);
+        let mut running_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_2_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        running_workload.expected_startup_time_ms = Some(0);
+        runtime_manager
+            .startup_watch
+            .watch(&slow_workload, Instant::now());
+        runtime_manager
+            .startup_watch
+            .watch(&running_workload, Instant::now());
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_2_NAME.to_owned(), ExecutionState::running());
+        runtime_manager
+            .update_workloads_on_fulfilled_dependencies(
+                &[WORKLOAD_2_NAME.to_owned()],
+                &wl_state_store_mock,
+            )
+            .await;
+
+        runtime_manager
+            .expire_pending_workload_operations(&wl_state_store_mock)
+            .await;
+
+        let workload_state = wl_state_receiver.try_recv().unwrap();
+        assert_eq!(workload_state.instance_name, slow_workload.instance_name);
+        assert_eq!(
+            workload_state.execution_state,
+            ExecutionState::starting_slow(0)
+        );
+        assert!(wl_state_receiver.try_recv().is_err());
+
+        assert_eq!(
+            server_receiver.try_recv(),
+            Ok(ToServer::AgentEvent(AgentEvent {
+                agent_name: String::new(),
+                kind: EventKind::SlowStart,
+                workload_name: WORKLOAD_1_NAME.to_owned(),
+                message: "Not running after the expected startup time of 0 ms".to_owned(),
+            }))
+        );
+        assert_eq!(runtime_manager.next_pending_operations_deadline(), None);
+    }
+
+    // [utest->swdd~agent-handles-workloads-with-fulfilled-dependencies~1]
+    #[tokio::test]
+    async fn utest_update_workload_state_no_create_workload_when_dependencies_not_fulfilled() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let next_workload_operations = vec![];
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_next_workload_operations_of_dependents()
+            .once()
+            .return_const(next_workload_operations);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
         mock_workload_scheduler_context
             .expect()
             .once()
@@ -1958,13 +3377,169 @@ mod tests {
                 .build();
 
         runtime_manager
-            .update_workloads_on_fulfilled_dependencies(&MockWorkloadStateStore::default())
+            .update_workloads_on_fulfilled_dependencies(
+                &[WORKLOAD_1_NAME.to_owned()],
+                &MockWorkloadStateStore::default(),
+            )
             .await;
         server_receiver.close();
 
         assert!(!runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
     }
 
+    // [utest->swdd~agent-stops-workloads-with-running-excluded-dependency~1]
+    #[tokio::test]
+    async fn utest_update_workload_state_stops_workload_with_running_excluded_dependency() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let workload_spec = generate_test_workload_spec_with_dependencies(
+            AGENT_NAME,
+            WORKLOAD_1_NAME,
+            RUNTIME_NAME,
+            HashMap::from([(WORKLOAD_2_NAME.to_string(), AddCondition::AddCondNotRunning)]),
+        );
+        let deleted_workload = DeletedWorkload {
+            instance_name: workload_spec.instance_name.clone(),
+            dependencies: HashMap::new(),
+        };
+
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_next_workload_operations_of_dependents()
+            .once()
+            .return_const(vec![]);
+        let expected_workload_spec = workload_spec.clone();
+        mock_workload_scheduler
+            .expect_enqueue_workloads_with_running_excluded_dependencies()
+            .once()
+            .withf(move |workload_specs, _| *workload_specs == [expected_workload_spec.clone()])
+            .return_const(vec![WorkloadOperation::UpdateDeleteOnly(deleted_workload)]);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let (mut server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default()
+                .with_runtime(
+                    RUNTIME_NAME,
+                    Box::new(MockRuntimeFacade::new()) as Box<dyn RuntimeFacade>,
+                )
+                .build();
+
+        let mut workload_mock = MockWorkload::default();
+        workload_mock
+            .expect_update()
+            .once()
+            .with(predicate::eq(None), predicate::always())
+            .return_once(move |_, _| Ok(()));
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_string(), workload_mock);
+        runtime_manager
+            .workload_specs
+            .insert(WORKLOAD_1_NAME.to_string(), workload_spec);
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_2_NAME.to_owned(), ExecutionState::running());
+
+        runtime_manager
+            .update_workloads_on_fulfilled_dependencies(
+                &[WORKLOAD_2_NAME.to_owned()],
+                &wl_state_store_mock,
+            )
+            .await;
+        server_receiver.close();
+
+        assert!(runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+    }
+
+    // [utest->swdd~agent-handles-dependency-failure-of-running-workloads~1]
+    #[tokio::test]
+    async fn utest_update_workload_state_stops_workload_once_on_failed_dependency() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut workload_spec = generate_test_workload_spec_with_dependencies(
+            AGENT_NAME,
+            WORKLOAD_1_NAME,
+            RUNTIME_NAME,
+            HashMap::from([(WORKLOAD_2_NAME.to_string(), AddCondition::AddCondRunning)]),
+        );
+        workload_spec.on_dependency_failure = DependencyFailurePolicy::Stop;
+        let deleted_workload = DeletedWorkload {
+            instance_name: workload_spec.instance_name.clone(),
+            dependencies: HashMap::new(),
+        };
+
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_next_workload_operations_of_dependents()
+            .times(2)
+            .returning(|_, _| vec![]);
+        let expected_workload_spec = workload_spec.clone();
+        mock_workload_scheduler
+            .expect_enqueue_workloads_with_failed_dependencies()
+            .once()
+            .withf(move |workload_specs, _| *workload_specs == [expected_workload_spec.clone()])
+            .return_const(vec![WorkloadOperation::UpdateDeleteOnly(deleted_workload)]);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let (mut server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default()
+                .with_runtime(
+                    RUNTIME_NAME,
+                    Box::new(MockRuntimeFacade::new()) as Box<dyn RuntimeFacade>,
+                )
+                .build();
+
+        let mut workload_mock = MockWorkload::default();
+        workload_mock
+            .expect_update()
+            .once()
+            .with(predicate::eq(None), predicate::always())
+            .return_once(move |_, _| Ok(()));
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_string(), workload_mock);
+        runtime_manager
+            .workload_specs
+            .insert(WORKLOAD_1_NAME.to_string(), workload_spec);
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_2_NAME.to_owned(), ExecutionState::failed("error"));
+
+        // the repeated failed state of the dependency does not stop the workload again
+        for _ in 0..2 {
+            runtime_manager
+                .update_workloads_on_fulfilled_dependencies(
+                    &[WORKLOAD_2_NAME.to_owned()],
+                    &wl_state_store_mock,
+                )
+                .await;
+        }
+        server_receiver.close();
+
+        assert!(runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+        assert!(runtime_manager
+            .failed_workload_names
+            .contains(WORKLOAD_2_NAME));
+    }
+
     // [utest->swdd~agent-handles-workloads-with-fulfilled-dependencies~1]
     #[tokio::test]
     async fn utest_update_workload_state_delete_workload_dependencies_with_fulfilled_dependencies()
@@ -1980,7 +3555,7 @@ mod tests {
 
         let mut mock_workload_scheduler = MockWorkloadScheduler::default();
         mock_workload_scheduler
-            .expect_next_workload_operations()
+            .expect_next_workload_operations_of_dependents()
             .once()
             .return_const(next_workload_operations);
 
@@ -2004,7 +3579,10 @@ mod tests {
             .insert(WORKLOAD_1_NAME.to_owned(), workload_mock);
 
         runtime_manager
-            .update_workloads_on_fulfilled_dependencies(&MockWorkloadStateStore::default())
+            .update_workloads_on_fulfilled_dependencies(
+                &[WORKLOAD_1_NAME.to_owned()],
+                &MockWorkloadStateStore::default(),
+            )
             .await;
         server_receiver.close();
 
@@ -2021,7 +3599,7 @@ mod tests {
         let next_workload_operations = vec![];
         let mut mock_workload_scheduler = MockWorkloadScheduler::default();
         mock_workload_scheduler
-            .expect_next_workload_operations()
+            .expect_next_workload_operations_of_dependents()
             .once()
             .return_const(next_workload_operations);
 
@@ -2042,7 +3620,10 @@ mod tests {
             .insert(WORKLOAD_1_NAME.to_owned(), workload_mock);
 
         runtime_manager
-            .update_workloads_on_fulfilled_dependencies(&MockWorkloadStateStore::default())
+            .update_workloads_on_fulfilled_dependencies(
+                &[WORKLOAD_1_NAME.to_owned()],
+                &MockWorkloadStateStore::default(),
+            )
             .await;
         server_receiver.close();
 
@@ -2086,26 +3667,193 @@ mod tests {
         let WorkloadState {
             instance_name: actual_instance_name,
             execution_state: actual_execution_state,
+            ..
         } = wl_state_msg.unwrap();
 
         assert_eq!(actual_instance_name.workload_name(), WORKLOAD_1_NAME);
         assert_eq!(actual_execution_state, ExecutionState::removed());
     }
 
-    // [utest->swdd~agent-transforms-update-workload-message-to-workload-operations~1]
+    // [utest->swdd~agent-removes-stale-control-interface-pipes~1]
     #[tokio::test]
-    async fn utest_transform_update_state_message_into_workload_operations_create() {
+    async fn utest_delete_workload_removes_pipes_folder_of_already_removed_workload() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
             .get_lock_async()
             .await;
 
+        let deleted_workload =
+            generate_test_deleted_workload(AGENT_NAME.to_string(), WORKLOAD_1_NAME.to_string());
+
+        let mock_workload_scheduler = MockWorkloadScheduler::default();
         let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
         mock_workload_scheduler_context
             .expect()
             .once()
-            .return_once(|_| MockWorkloadScheduler::default());
+            .return_once(|_| mock_workload_scheduler);
 
-        let (_server_receiver, runtime_manager, _wl_state_receiver) =
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default().build();
+
+        let run_folder = tempfile::tempdir().unwrap();
+        let pipes_folder = deleted_workload
+            .instance_name
+            .pipes_folder_name(run_folder.path());
+        std::fs::create_dir(&pipes_folder).unwrap();
+        std::fs::write(pipes_folder.join("input"), "").unwrap();
+        runtime_manager.run_folder = run_folder.path().to_path_buf();
+
+        runtime_manager.delete_workload(deleted_workload).await;
+
+        assert!(!pipes_folder.exists());
+    }
+
+    // [utest->swdd~agent-notifies-workload-before-shutdown~1]
+    #[tokio::test]
+    async fn utest_delete_workload_notifies_workload_with_pre_shutdown_timeout() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler = MockWorkloadScheduler::default();
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default().build();
+
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        workload_spec.pre_shutdown_timeout_ms = Some(500);
+        let instance_name = workload_spec.instance_name.clone();
+        runtime_manager
+            .workload_specs
+            .insert(WORKLOAD_1_NAME.to_owned(), workload_spec);
+
+        let (deleted_sender, deleted_receiver) = tokio::sync::oneshot::channel();
+        let mut workload_mock = MockWorkload::default();
+        workload_mock.expect_delete().never();
+        workload_mock
+            .expect_delete_after_pre_shutdown()
+            .once()
+            .with(predicate::eq(500))
+            .return_once(move |_| {
+                deleted_sender.send(()).unwrap();
+                Ok(())
+            });
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_owned(), workload_mock);
+
+        runtime_manager
+            .delete_workload(DeletedWorkload {
+                instance_name,
+                dependencies: HashMap::new(),
+            })
+            .await;
+
+        assert!(
+            tokio::time::timeout(std::time::Duration::from_millis(200), deleted_receiver)
+                .await
+                .is_ok()
+        );
+        assert!(!runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+        assert!(!runtime_manager.workload_specs.contains_key(WORKLOAD_1_NAME));
+    }
+
+    // [utest->swdd~agent-reconciles-restored-pending-workload-operations~1]
+    #[tokio::test]
+    async fn utest_handle_update_workload_initial_call_deletes_existing_workload_on_restored_pending_delete(
+    ) {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let restored_deleted_workload =
+            generate_test_deleted_workload(AGENT_NAME.to_string(), WORKLOAD_1_NAME.to_string());
+        let existing_instance_name = restored_deleted_workload.instance_name.clone();
+
+        let workload_operations =
+            vec![WorkloadOperation::Delete(restored_deleted_workload.clone())];
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        let pending_deletes = vec![restored_deleted_workload];
+        mock_workload_scheduler
+            .expect_reconcile_restored_queue()
+            .once()
+            .return_const(pending_deletes);
+        mock_workload_scheduler
+            .expect_enqueue_initial_workload_operations()
+            .once()
+            .with(
+                predicate::eq(workload_operations.clone()),
+                predicate::always(),
+            )
+            .return_const(workload_operations);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock
+            .expect_get_reusable_workloads()
+            .once()
+            .return_once(|_| {
+                Box::pin(async move {
+                    Ok(vec![WorkloadState {
+                        instance_name: existing_instance_name,
+                        execution_state: ExecutionState::running(),
+                        agent_timestamp: None,
+                        server_timestamp: None,
+                    }])
+                })
+            });
+
+        // the existing workload is deleted once the restored pending delete is executed
+        runtime_facade_mock
+            .expect_delete_workload()
+            .once()
+            .withf(|instance_name, _, report_workload_states| {
+                instance_name.workload_name() == WORKLOAD_1_NAME && *report_workload_states
+            })
+            .return_const(());
+
+        let (_, mut runtime_manager, _wl_state_receiver) = RuntimeManagerBuilder::default()
+            .with_runtime(
+                RUNTIME_NAME,
+                Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+            )
+            .build();
+
+        runtime_manager
+            .handle_update_workload(vec![], vec![], &MockWorkloadStateStore::default())
+            .await;
+
+        assert!(runtime_manager.workloads.is_empty());
+        assert!(runtime_manager.restored_workloads_to_delete.is_empty());
+    }
+
+    // [utest->swdd~agent-transforms-update-workload-message-to-workload-operations~1]
+    #[tokio::test]
+    async fn utest_transform_update_state_message_into_workload_operations_create() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
             RuntimeManagerBuilder::default().build();
 
         let new_workload = generate_test_workload_spec_with_param(
@@ -2137,7 +3885,7 @@ mod tests {
             .once()
             .return_once(|_| MockWorkloadScheduler::default());
 
-        let (_server_receiver, runtime_manager, _wl_state_receiver) =
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
             RuntimeManagerBuilder::default().build();
         let added_workloads = vec![];
         let deleted_workload =
@@ -2165,7 +3913,7 @@ mod tests {
             .once()
             .return_once(|_| MockWorkloadScheduler::default());
 
-        let (_server_receiver, runtime_manager, _wl_state_receiver) =
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
             RuntimeManagerBuilder::default().build();
 
         let new_workload = generate_test_workload_spec_with_param(
@@ -2186,6 +3934,868 @@ mod tests {
         );
     }
 
+    // [utest->swdd~agent-skips-known-workload-with-equal-spec~1]
+    // [utest->swdd~agent-adopts-known-workload-with-equal-instance-name~1]
+    // [utest->swdd~agent-update-on-add-known-workload~2]
+    #[tokio::test]
+    async fn utest_transform_update_state_message_into_workload_operations_skips_known_equal_spec()
+    {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default().build();
+
+        let known_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        let mut changed_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_2_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        let mut adopted_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_3_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        for workload_spec in [&known_workload, &changed_workload, &adopted_workload] {
+            let workload_name = workload_spec.instance_name.workload_name().to_owned();
+            runtime_manager
+                .workload_specs
+                .insert(workload_name.clone(), workload_spec.clone());
+            runtime_manager
+                .workloads
+                .insert(workload_name, MockWorkload::default());
+        }
+        changed_workload.runtime_config = "image: alpine:changed".to_owned();
+        changed_workload.instance_name = WorkloadInstanceName::builder()
+            .agent_name(AGENT_NAME)
+            .workload_name(WORKLOAD_2_NAME)
+            .config(&changed_workload.runtime_config)
+            .build();
+        adopted_workload.restart_policy = common::objects::RestartPolicy::Never;
+
+        let workload_operations = runtime_manager.transform_into_workload_operations(
+            vec![known_workload, changed_workload.clone(), adopted_workload.clone()],
+            vec![],
+        );
+
+        assert_eq!(
+            vec![WorkloadOperation::Update(
+                changed_workload.clone(),
+                DeletedWorkload {
+                    instance_name: changed_workload.instance_name,
+                    dependencies: HashMap::default(),
+                }
+            )],
+            workload_operations
+        );
+        assert_eq!(
+            runtime_manager.workload_specs.get(WORKLOAD_3_NAME),
+            Some(&adopted_workload)
+        );
+    }
+
+    // [utest->swdd~agent-deletes-workloads-missing-in-initial-list-after-reconnect~1]
+    #[tokio::test]
+    async fn utest_get_workloads_missing_in_initial_list() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default().build();
+
+        let workload_1 = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        let workload_2 = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_2_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        runtime_manager
+            .workload_specs
+            .insert(WORKLOAD_1_NAME.to_owned(), workload_1.clone());
+        runtime_manager
+            .workload_specs
+            .insert(WORKLOAD_2_NAME.to_owned(), workload_2.clone());
+
+        assert_eq!(
+            runtime_manager.get_workloads_missing_in_initial_list(&[workload_1]),
+            vec![DeletedWorkload {
+                instance_name: workload_2.instance_name,
+                dependencies: HashMap::default(),
+            }]
+        );
+    }
+
+    // [utest->swdd~agent-subscribes-to-workload-states-of-dependencies~1]
+    #[tokio::test]
+    async fn utest_workload_state_subscription_contains_dependencies_of_all_workloads() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_dependency_names()
+            .once()
+            .return_const(vec![
+                "queued_dependency".to_owned(),
+                "dependency".to_owned(),
+            ]);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default().build();
+
+        let mut workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        workload.dependencies = HashMap::from([
+            ("dependency".to_owned(), AddCondition::AddCondRunning),
+            ("other_dependency".to_owned(), AddCondition::AddCondRunning),
+        ]);
+        let mut fallback_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_2_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        fallback_workload.dependencies = HashMap::from([(
+            "fallback_dependency".to_owned(),
+            AddCondition::AddCondRunning,
+        )]);
+        runtime_manager
+            .workload_specs
+            .insert(WORKLOAD_1_NAME.to_owned(), workload);
+        runtime_manager
+            .fallback_workloads
+            .insert(WORKLOAD_2_NAME.to_owned(), fallback_workload);
+
+        assert_eq!(
+            runtime_manager.workload_state_subscription(),
+            vec![
+                "dependency".to_owned(),
+                "fallback_dependency".to_owned(),
+                "other_dependency".to_owned(),
+                "queued_dependency".to_owned(),
+            ]
+        );
+    }
+
+    // [utest->swdd~agent-restores-pending-workload-operations~1]
+    #[tokio::test]
+    async fn utest_restore_pending_workload_operations_restores_queue_from_run_folder() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let expected_queue_storage = QueueStorage::new(
+            Path::new(RUN_FOLDER).join(QUEUE_FILE_NAME),
+            PersistenceFormat::Cbor,
+        );
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_restore_queue()
+            .with(predicate::eq(expected_queue_storage))
+            .once()
+            .return_const(());
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default().build();
+
+        runtime_manager.restore_pending_workload_operations(PersistenceFormat::Cbor);
+    }
+
+    // [utest->swdd~agent-reports-exceeded-update-deadline~1]
+    #[tokio::test]
+    async fn utest_set_pending_operations_deadline_forwards_deadline_to_queue() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let deadline = Instant::now();
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_set_deadline()
+            .with(
+                predicate::eq(vec![WORKLOAD_1_NAME.to_owned()]),
+                predicate::eq(deadline),
+            )
+            .once()
+            .return_const(());
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default().build();
+
+        runtime_manager.set_pending_operations_deadline(&[WORKLOAD_1_NAME.to_owned()], deadline);
+    }
+
+    // [utest->swdd~agent-reports-scheduler-queue-to-server~1]
+    #[tokio::test]
+    async fn utest_pending_workload_operations_returns_operations_of_queue() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let pending_workload_operation = PendingWorkloadOperation {
+            workload_name: WORKLOAD_1_NAME.to_owned(),
+            agent: AGENT_NAME.to_owned(),
+            operation: common::commands::PendingOperation::Create,
+            unfulfilled_dependencies: vec![],
+        };
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_pending_workload_operations()
+            .once()
+            .return_const(vec![pending_workload_operation.clone()]);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let (_server_receiver, runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default().build();
+
+        assert_eq!(
+            runtime_manager.pending_workload_operations(&MockWorkloadStateStore::default()),
+            vec![pending_workload_operation]
+        );
+    }
+
+    // [utest->swdd~agent-keeps-fallback-workloads-until-degraded-mode~1]
+    #[tokio::test]
+    async fn utest_handle_update_workload_keeps_fallback_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_enqueue_filtered_workload_operations()
+            .once()
+            .withf(|workload_operations, _| workload_operations.is_empty())
+            .return_const(vec![]);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock.expect_create_workload().never();
+
+        let (_server_receiver, mut runtime_manager, mut wl_state_receiver) =
+            RuntimeManagerBuilder::default()
+                .with_runtime(
+                    RUNTIME_NAME,
+                    Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+                )
+                .build();
+        runtime_manager.initial_workload_list_received = true;
+
+        let mut fallback_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        fallback_workload.disconnect_policy = DisconnectPolicy::Fallback;
+
+        runtime_manager
+            .handle_update_workload(
+                vec![fallback_workload.clone()],
+                vec![],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert!(!runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+        assert_eq!(
+            runtime_manager.fallback_workloads.get(WORKLOAD_1_NAME),
+            Some(&fallback_workload)
+        );
+        assert_eq!(
+            wl_state_receiver.recv().await,
+            Some(WorkloadState {
+                instance_name: fallback_workload.instance_name,
+                execution_state: ExecutionState::waiting_for_degraded_mode(),
+                agent_timestamp: None,
+                server_timestamp: None,
+            })
+        );
+    }
+
+    // [utest->swdd~agent-keeps-fallback-workloads-until-degraded-mode~1]
+    #[tokio::test]
+    async fn utest_handle_update_workload_deletes_fallback_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_enqueue_filtered_workload_operations()
+            .once()
+            .withf(|workload_operations, _| workload_operations.is_empty())
+            .return_const(vec![]);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let (_server_receiver, mut runtime_manager, mut wl_state_receiver) =
+            RuntimeManagerBuilder::default().build();
+        runtime_manager.initial_workload_list_received = true;
+
+        let mut fallback_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        fallback_workload.disconnect_policy = DisconnectPolicy::Fallback;
+        runtime_manager
+            .fallback_workloads
+            .insert(WORKLOAD_1_NAME.to_owned(), fallback_workload.clone());
+
+        runtime_manager
+            .handle_update_workload(
+                vec![],
+                vec![DeletedWorkload {
+                    instance_name: fallback_workload.instance_name.clone(),
+                    dependencies: HashMap::default(),
+                }],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert!(runtime_manager.fallback_workloads.is_empty());
+        assert_eq!(
+            wl_state_receiver.recv().await,
+            Some(WorkloadState {
+                instance_name: fallback_workload.instance_name,
+                execution_state: ExecutionState::removed(),
+                agent_timestamp: None,
+                server_timestamp: None,
+            })
+        );
+    }
+
+    // [utest->swdd~agent-keeps-scheduled-workloads-until-matching-time~1]
+    #[tokio::test]
+    async fn utest_handle_update_workload_keeps_scheduled_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_enqueue_filtered_workload_operations()
+            .once()
+            .withf(|workload_operations, _| workload_operations.is_empty())
+            .return_const(vec![]);
+        mock_workload_scheduler
+            .expect_next_deadline()
+            .return_const(None);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock.expect_create_workload().never();
+
+        let (_server_receiver, mut runtime_manager, mut wl_state_receiver) =
+            RuntimeManagerBuilder::default()
+                .with_runtime(
+                    RUNTIME_NAME,
+                    Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+                )
+                .build();
+        runtime_manager.initial_workload_list_received = true;
+
+        let mut scheduled_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        scheduled_workload.schedule = Some("0 2 * * *".to_owned());
+
+        runtime_manager
+            .handle_update_workload(
+                vec![scheduled_workload.clone()],
+                vec![],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert!(!runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+        assert_eq!(
+            runtime_manager.schedule_triggers.get(WORKLOAD_1_NAME),
+            Some(&scheduled_workload)
+        );
+        assert!(runtime_manager.next_pending_operations_deadline().is_some());
+        assert_eq!(
+            wl_state_receiver.recv().await,
+            Some(WorkloadState {
+                instance_name: scheduled_workload.instance_name,
+                execution_state: ExecutionState::waiting_for_schedule(),
+                agent_timestamp: None,
+                server_timestamp: None,
+            })
+        );
+    }
+
+    // [utest->swdd~agent-starts-scheduled-workloads-at-matching-times~1]
+    #[tokio::test]
+    async fn utest_scheduled_workload_operation_replaces_existing_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default().build();
+
+        let mut scheduled_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        scheduled_workload.schedule = Some("0 2 * * *".to_owned());
+
+        assert_eq!(
+            runtime_manager.scheduled_workload_operation(scheduled_workload.clone()),
+            WorkloadOperation::Create(scheduled_workload.clone())
+        );
+
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_owned(), MockWorkload::default());
+        assert_eq!(
+            runtime_manager.scheduled_workload_operation(scheduled_workload.clone()),
+            WorkloadOperation::Update(
+                scheduled_workload.clone(),
+                DeletedWorkload {
+                    instance_name: scheduled_workload.instance_name,
+                    dependencies: HashMap::default(),
+                }
+            )
+        );
+    }
+
+    // [utest->swdd~agent-holds-workload-starts-until-free-resources-cover-requests~1]
+    #[tokio::test]
+    async fn utest_handle_update_workload_holds_workload_exceeding_free_resources() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut held_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        held_workload.resources = Some(Resources {
+            requests: ResourceRequests {
+                cpu: None,
+                memory: Some("2Mi".to_owned()),
+            },
+        });
+
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_enqueue_filtered_workload_operations()
+            .once()
+            .return_const(vec![WorkloadOperation::Create(held_workload.clone())]);
+        mock_workload_scheduler
+            .expect_next_deadline()
+            .return_const(None);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock.expect_create_workload().never();
+
+        let (_server_receiver, mut runtime_manager, mut wl_state_receiver) =
+            RuntimeManagerBuilder::default()
+                .with_runtime(
+                    RUNTIME_NAME,
+                    Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+                )
+                .build();
+        runtime_manager.initial_workload_list_received = true;
+        let proc_folder = tempfile::tempdir().unwrap();
+        runtime_manager.resource_monitor = generate_test_resource_monitor(
+            proc_folder.path(),
+            FreeResources {
+                cpu_millis: 1000,
+                memory_bytes: 1 << 20,
+            },
+        );
+
+        runtime_manager
+            .handle_update_workload(
+                vec![held_workload.clone()],
+                vec![],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert!(!runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+        assert!(runtime_manager.next_pending_operations_deadline().is_some());
+        assert_eq!(
+            runtime_manager.get_workloads_missing_in_initial_list(&[]),
+            vec![DeletedWorkload {
+                instance_name: held_workload.instance_name.clone(),
+                dependencies: HashMap::default(),
+            }]
+        );
+        assert_eq!(
+            wl_state_receiver.recv().await,
+            Some(WorkloadState {
+                instance_name: held_workload.instance_name,
+                execution_state: ExecutionState::insufficient_resources(
+                    "memory: requested 2097152 bytes, free 1048576 bytes"
+                ),
+                agent_timestamp: None,
+                server_timestamp: None,
+            })
+        );
+    }
+
+    // [utest->swdd~agent-applies-disconnect-policies-in-degraded-mode~1]
+    #[tokio::test]
+    async fn utest_enter_degraded_mode_stops_and_starts_workloads_by_disconnect_policy() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let pipes_channel_info_context_mock = MockPipesChannelContextInfo::new_context();
+        pipes_channel_info_context_mock
+            .expect()
+            .once()
+            .return_once(|_, _, _, _| MockPipesChannelContextInfo::default());
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock
+            .expect_create_workload()
+            .once()
+            .withf(|workload_spec, _, _| {
+                workload_spec.instance_name.workload_name() == WORKLOAD_2_NAME
+            })
+            .return_once(|_, _, _| MockWorkload::default());
+
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default()
+                .with_runtime(
+                    RUNTIME_NAME,
+                    Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+                )
+                .build();
+
+        let mut stopped_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        stopped_workload.disconnect_policy = DisconnectPolicy::Stop;
+        let mut stopped_workload_mock = MockWorkload::default();
+        stopped_workload_mock
+            .expect_delete()
+            .once()
+            .return_once(|| Ok(()));
+        runtime_manager
+            .workload_specs
+            .insert(WORKLOAD_1_NAME.to_owned(), stopped_workload);
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_owned(), stopped_workload_mock);
+
+        let mut fallback_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_2_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        fallback_workload.disconnect_policy = DisconnectPolicy::Fallback;
+        runtime_manager
+            .fallback_workloads
+            .insert(WORKLOAD_2_NAME.to_owned(), fallback_workload);
+
+        runtime_manager.enter_degraded_mode().await;
+
+        assert!(!runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+        assert!(!runtime_manager.workload_specs.contains_key(WORKLOAD_1_NAME));
+        assert!(runtime_manager.workloads.contains_key(WORKLOAD_2_NAME));
+    }
+
+    // [utest->swdd~agent-stops-fallback-workloads-after-reconnect~1]
+    #[tokio::test]
+    async fn utest_leave_degraded_mode_stops_fallback_workloads() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default().build();
+
+        let mut fallback_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        fallback_workload.disconnect_policy = DisconnectPolicy::Fallback;
+        let mut fallback_workload_mock = MockWorkload::default();
+        fallback_workload_mock
+            .expect_delete()
+            .once()
+            .return_once(|| Ok(()));
+        runtime_manager
+            .workload_specs
+            .insert(WORKLOAD_1_NAME.to_owned(), fallback_workload.clone());
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_owned(), fallback_workload_mock);
+        runtime_manager
+            .fallback_workloads
+            .insert(WORKLOAD_1_NAME.to_owned(), fallback_workload);
+
+        runtime_manager.leave_degraded_mode().await;
+
+        assert!(runtime_manager.workloads.is_empty());
+        assert!(runtime_manager
+            .fallback_workloads
+            .contains_key(WORKLOAD_1_NAME));
+    }
+
+    // [utest->swdd~agent-starts-local-workloads~1]
+    #[tokio::test]
+    async fn utest_start_local_workloads_creates_and_resumes_workloads() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let pipes_channel_mock = MockPipesChannelContext::new_context();
+        pipes_channel_mock
+            .expect()
+            .once()
+            .returning(move |_, _, _, _| Ok(MockPipesChannelContext::default()));
+
+        let pipes_channel_info_context_mock = MockPipesChannelContextInfo::new_context();
+        pipes_channel_info_context_mock
+            .expect()
+            .once()
+            .returning(|_, _, _, _| MockPipesChannelContextInfo::default());
+
+        let running_local_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        let new_local_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_2_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+
+        let workload_state_running = WorkloadState {
+            instance_name: running_local_workload.instance_name.clone(),
+            execution_state: ExecutionState::running(),
+            agent_timestamp: None,
+            server_timestamp: None,
+        };
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock
+            .expect_get_reusable_workloads()
+            .once()
+            .return_once(|_| Box::pin(async { Ok(vec![workload_state_running]) }));
+        runtime_facade_mock
+            .expect_resume_workload()
+            .once()
+            .return_once(|_, _, _| MockWorkload::default());
+        runtime_facade_mock
+            .expect_create_workload()
+            .once()
+            .return_once(|_, _, _| MockWorkload::default());
+        runtime_facade_mock.expect_delete_workload().never();
+
+        let (_, mut runtime_manager, _) = RuntimeManagerBuilder::default()
+            .with_runtime(
+                RUNTIME_NAME,
+                Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+            )
+            .build();
+
+        runtime_manager
+            .start_local_workloads(vec![running_local_workload, new_local_workload])
+            .await;
+
+        assert!(runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+        assert!(runtime_manager.workloads.contains_key(WORKLOAD_2_NAME));
+        assert_eq!(
+            runtime_manager.local_workload_names,
+            HashSet::from([WORKLOAD_1_NAME.to_owned(), WORKLOAD_2_NAME.to_owned()])
+        );
+    }
+
+    // [utest->swdd~agent-ignores-server-workloads-named-like-local-workloads~1]
+    #[tokio::test]
+    async fn utest_handle_update_workload_ignores_workloads_named_like_local_workloads() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let server_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_2_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+
+        let mut mock_workload_scheduler = MockWorkloadScheduler::default();
+        mock_workload_scheduler
+            .expect_enqueue_filtered_workload_operations()
+            .once()
+            .with(
+                predicate::eq(vec![WorkloadOperation::Create(server_workload.clone())]),
+                predicate::always(),
+            )
+            .return_const(vec![]);
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| mock_workload_scheduler);
+
+        let (_, mut runtime_manager, _) = RuntimeManagerBuilder::default().build();
+        runtime_manager.initial_workload_list_received = true;
+        runtime_manager
+            .local_workload_names
+            .insert(WORKLOAD_1_NAME.to_owned());
+
+        let workload_named_like_local_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+
+        runtime_manager
+            .handle_update_workload(
+                vec![workload_named_like_local_workload, server_workload],
+                vec![generate_test_deleted_workload(
+                    AGENT_NAME.to_owned(),
+                    WORKLOAD_1_NAME.to_owned(),
+                )],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+    }
+
+    // [utest->swdd~agent-ignores-server-workloads-named-like-local-workloads~1]
+    #[tokio::test]
+    async fn utest_get_workloads_missing_in_initial_list_skips_local_workloads() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default().build();
+
+        let local_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        runtime_manager
+            .workload_specs
+            .insert(WORKLOAD_1_NAME.to_owned(), local_workload);
+        runtime_manager
+            .local_workload_names
+            .insert(WORKLOAD_1_NAME.to_owned());
+
+        assert!(runtime_manager
+            .get_workloads_missing_in_initial_list(&[])
+            .is_empty());
+    }
+
     // [utest->swdd~agent-executes-create-workload-operation~1]
     #[tokio::test]
     async fn utest_execute_workload_operations_create() {
@@ -2203,7 +4813,7 @@ mod tests {
         pipes_channel_info_context_mock
             .expect()
             .once()
-            .return_once(|_, _, _| MockPipesChannelContextInfo::default());
+            .return_once(|_, _, _, _| MockPipesChannelContextInfo::default());
 
         let mut runtime_facade_mock = MockRuntimeFacade::new();
         runtime_facade_mock
@@ -2325,7 +4935,7 @@ mod tests {
         pipes_channel_info_context_mock
             .expect()
             .once()
-            .return_once(|_, _, _| MockPipesChannelContextInfo::default());
+            .return_once(|_, _, _, _| MockPipesChannelContextInfo::default());
 
         let mut workload_mock = MockWorkload::default();
         workload_mock
@@ -16,6 +16,7 @@
 pub mod control_loop_state;
 pub mod workload_command_channel;
 pub mod workload_control_loop;
+pub mod workload_hooks;
 
 // public api exports
 pub use control_loop_state::ControlLoopState;
@@ -170,10 +171,29 @@ impl Workload {
             .map_err(|err| WorkloadError::Communication(err.to_string()))
     }
 
+    // Gives the workload the chance to persist its state before it is deleted. The workload
+    // is deleted after its acknowledgement or at the latest when the timeout has expired.
+    // [impl->swdd~agent-notifies-workload-before-shutdown~1]
+    pub async fn delete_after_pre_shutdown(self, timeout_ms: u64) -> Result<(), WorkloadError> {
+        if let Some(control_interface) = &self.control_interface {
+            log::info!("Notifying workload '{}' about its removal.", self.name);
+            if !control_interface.notify_pre_shutdown(timeout_ms).await {
+                log::warn!(
+                    "Workload '{}' did not acknowledge its removal within '{}' ms.",
+                    self.name,
+                    timeout_ms
+                );
+            }
+        }
+
+        self.delete().await
+    }
+
     // [impl->swdd~agent-forward-responses-to-control-interface-pipe~1]
     pub async fn forward_response(
         &mut self,
         request_id: String,
+        trace_id: String,
         response_content: ResponseContent,
     ) -> Result<(), WorkloadError> {
         let control_interface =
@@ -182,13 +202,16 @@ impl Workload {
                 .ok_or(WorkloadError::CompleteState(
                     "control interface not available".to_string(),
                 ))?;
+        // The response is dropped if the workload does not read its messages, such that a stuck
+        // workload cannot block the agent.
+        // [impl->swdd~agent-limits-buffered-control-interface-messages~1]
         control_interface
             .get_input_pipe_sender()
-            .send(FromServer::Response(commands::Response {
+            .try_send(FromServer::Response(commands::Response {
                 request_id,
+                trace_id,
                 response_content,
             }))
-            .await
             .map_err(|err| WorkloadError::CompleteState(err.to_string()))
     }
 }
@@ -225,6 +248,7 @@ mod tests {
     const WORKLOAD_1_NAME: &str = "workload1";
     const PIPES_LOCATION: &str = "/some/path";
     const REQUEST_ID: &str = "request_id";
+    const TRACE_ID: &str = "trace_id";
 
     const TEST_WL_COMMAND_BUFFER_SIZE: usize = 5;
     const TEST_EXEC_COMMAND_BUFFER_SIZE: usize = 5;
@@ -536,7 +560,42 @@ mod tests {
         ));
     }
 
+    // [utest->swdd~agent-notifies-workload-before-shutdown~1]
+    #[tokio::test]
+    async fn utest_workload_obj_delete_after_pre_shutdown_notifies_control_interface() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let (workload_command_sender, mut workload_command_receiver) = WorkloadCommandSender::new();
+
+        let mut control_interface_mock = MockPipesChannelContext::default();
+        control_interface_mock
+            .expect_notify_pre_shutdown()
+            .once()
+            .with(mockall::predicate::eq(500))
+            .return_const(false);
+        control_interface_mock
+            .expect_abort_pipes_channel_task()
+            .once()
+            .return_const(());
+
+        let test_workload = Workload::new(
+            WORKLOAD_1_NAME.to_string(),
+            workload_command_sender,
+            Some(control_interface_mock),
+        );
+
+        test_workload.delete_after_pre_shutdown(500).await.unwrap();
+
+        assert!(matches!(
+            timeout(Duration::from_millis(200), workload_command_receiver.recv()).await,
+            Ok(Some(WorkloadCommand::Delete))
+        ));
+    }
+
     // [utest->swdd~agent-forward-responses-to-control-interface-pipe~1]
+    // [utest->swdd~agent-forwards-trace-id-to-control-interface~1]
     #[tokio::test]
     async fn utest_workload_obj_send_complete_state_success() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
@@ -567,6 +626,7 @@ mod tests {
         test_workload
             .forward_response(
                 format!("{WORKLOAD_1_NAME}@{REQUEST_ID}"),
+                TRACE_ID.to_owned(),
                 common::commands::ResponseContent::CompleteState(Box::new(complete_state.clone())),
             )
             .await
@@ -576,8 +636,8 @@ mod tests {
 
         assert!(matches!(
             timeout(Duration::from_millis(200), to_server_rx.recv()).await,
-            Ok(Some(FromServer::Response(Response{request_id: _, response_content: ResponseContent::CompleteState(complete_state)})))
-        if expected_complete_state == *complete_state));
+            Ok(Some(FromServer::Response(Response{request_id: _, trace_id, response_content: ResponseContent::CompleteState(complete_state)})))
+        if expected_complete_state == *complete_state && trace_id == TRACE_ID));
     }
 
     // [utest->swdd~agent-forward-responses-to-control-interface-pipe~1]
@@ -608,6 +668,7 @@ mod tests {
         assert!(matches!(
             test_workload
                 .forward_response(
+                    "".to_owned(),
                     "".to_owned(),
                     ResponseContent::CompleteState(Box::new(complete_state))
                 )
@@ -616,6 +677,51 @@ mod tests {
         ));
     }
 
+    // [utest->swdd~agent-limits-buffered-control-interface-messages~1]
+    #[tokio::test]
+    async fn utest_workload_obj_send_complete_state_drops_response_on_full_buffer() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let (workload_command_sender, _) = WorkloadCommandSender::new();
+        let (to_server_tx, mut to_server_rx) = mpsc::channel(1);
+        to_server_tx
+            .try_send(FromServer::Response(Response {
+                request_id: REQUEST_ID.to_owned(),
+                trace_id: TRACE_ID.to_owned(),
+                response_content: ResponseContent::CompleteState(Default::default()),
+            }))
+            .unwrap();
+
+        let mut control_interface_mock = MockPipesChannelContext::default();
+        control_interface_mock
+            .expect_get_input_pipe_sender()
+            .once()
+            .return_const(to_server_tx);
+
+        let mut test_workload = Workload::new(
+            WORKLOAD_1_NAME.to_string(),
+            workload_command_sender,
+            Some(control_interface_mock),
+        );
+
+        assert!(matches!(
+            timeout(
+                Duration::from_millis(200),
+                test_workload.forward_response(
+                    "".to_owned(),
+                    "".to_owned(),
+                    ResponseContent::CompleteState(Default::default())
+                )
+            )
+            .await,
+            Ok(Err(WorkloadError::CompleteState(_)))
+        ));
+        assert!(to_server_rx.try_recv().is_ok());
+        assert!(to_server_rx.try_recv().is_err());
+    }
+
     // [utest->swdd~agent-forward-responses-to-control-interface-pipe~1]
     #[tokio::test]
     async fn utest_workload_obj_send_complete_state_no_control_interface() {
@@ -632,6 +738,7 @@ mod tests {
         assert!(matches!(
             test_workload
                 .forward_response(
+                    "".to_owned(),
                     "".to_owned(),
                     ResponseContent::CompleteState(Box::new(complete_state))
                 )
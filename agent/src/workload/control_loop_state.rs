@@ -14,10 +14,24 @@
 use crate::runtime_connectors::{RuntimeConnector, StateChecker};
 use crate::workload::workload_command_channel::{WorkloadCommandReceiver, WorkloadCommandSender};
 use crate::workload::workload_control_loop::RetryCounter;
+use crate::workload::workload_hooks::AllowedHooks;
+use crate::workload::WorkloadCommand;
 use crate::workload_state::{WorkloadStateReceiver, WorkloadStateSender};
 use crate::BUFFER_SIZE;
-use common::objects::{WorkloadInstanceName, WorkloadSpec, WorkloadState};
-use std::path::PathBuf;
+use common::objects::{WorkloadHooks, WorkloadInstanceName, WorkloadSpec, WorkloadState};
+use std::{collections::VecDeque, path::PathBuf};
+
+// The old instance of a workload updated with the AT_LEAST_ONCE strategy.
+pub struct ReplacedWorkload<WorkloadId, StChecker>
+where
+    WorkloadId: ToString + Send + Sync + 'static,
+    StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+{
+    pub instance_name: WorkloadInstanceName,
+    pub workload_id: WorkloadId,
+    pub state_checker: Option<StChecker>,
+    pub hooks: Option<WorkloadHooks>,
+}
 
 pub struct ControlLoopState<WorkloadId, StChecker>
 where
@@ -28,13 +42,18 @@ where
     pub control_interface_path: Option<PathBuf>,
     pub workload_id: Option<WorkloadId>,
     pub state_checker: Option<StChecker>,
+    // deleted once the new instance of the workload is running
+    pub replaced_workload: Option<ReplacedWorkload<WorkloadId, StChecker>>,
     pub to_agent_workload_state_sender: WorkloadStateSender,
     pub state_checker_workload_state_sender: WorkloadStateSender,
     pub state_checker_workload_state_receiver: WorkloadStateReceiver,
     pub runtime: Box<dyn RuntimeConnector<WorkloadId, StChecker>>,
     pub command_receiver: WorkloadCommandReceiver,
+    // commands received while a create was running, executed before receiving new ones
+    pub deferred_commands: VecDeque<WorkloadCommand>,
     pub retry_sender: WorkloadCommandSender,
     pub retry_counter: RetryCounter,
+    pub allowed_hooks: AllowedHooks,
 }
 
 impl<WorkloadId, StChecker> ControlLoopState<WorkloadId, StChecker>
@@ -63,6 +82,7 @@ where
     workload_command_receiver: Option<WorkloadCommandReceiver>,
     retry_sender: Option<WorkloadCommandSender>,
     retry_counter: RetryCounter,
+    allowed_hooks: AllowedHooks,
 }
 
 impl<WorkloadId, StChecker> ControlLoopStateBuilder<WorkloadId, StChecker>
@@ -79,6 +99,7 @@ where
             workload_command_receiver: None,
             retry_sender: None,
             retry_counter: RetryCounter::new(),
+            allowed_hooks: AllowedHooks::default(),
         }
     }
 
@@ -112,6 +133,11 @@ where
         self
     }
 
+    pub fn allowed_hooks(mut self, allowed_hooks: AllowedHooks) -> Self {
+        self.allowed_hooks = allowed_hooks;
+        self
+    }
+
     pub fn build(self) -> Result<ControlLoopState<WorkloadId, StChecker>, String> {
         // new channel for receiving the workload states from the state checker
         let (state_checker_wl_state_sender, state_checker_wl_state_receiver) =
@@ -124,6 +150,7 @@ where
             control_interface_path: self.control_interface_path,
             workload_id: None,
             state_checker: None,
+            replaced_workload: None,
             to_agent_workload_state_sender: self
                 .workload_state_sender
                 .ok_or_else(|| "WorkloadStateSender is not set".to_string())?,
@@ -135,10 +162,12 @@ where
             command_receiver: self
                 .workload_command_receiver
                 .ok_or_else(|| "WorkloadCommandReceiver is not set".to_string())?,
+            deferred_commands: VecDeque::new(),
             retry_sender: self
                 .retry_sender
                 .ok_or_else(|| "WorkloadCommandSender is not set".to_string())?,
             retry_counter: self.retry_counter,
+            allowed_hooks: self.allowed_hooks,
         })
     }
 }
@@ -158,6 +187,7 @@ mod tests {
         runtime_connectors::test::{MockRuntimeConnector, StubStateChecker},
         workload::{
             workload_command_channel::WorkloadCommandSender, workload_control_loop::RetryCounter,
+            workload_hooks::AllowedHooks,
         },
         workload_state::WorkloadStateSenderInterface,
     };
@@ -274,13 +304,16 @@ mod tests {
             control_interface_path: None,
             workload_id: None,
             state_checker: None,
+            replaced_workload: None,
             to_agent_workload_state_sender: workload_state_sender,
             state_checker_workload_state_sender,
             state_checker_workload_state_receiver,
             runtime,
             command_receiver: workload_command_receiver,
+            deferred_commands: Default::default(),
             retry_sender,
             retry_counter: RetryCounter::new(),
+            allowed_hooks: AllowedHooks::default(),
         };
 
         assert_eq!(
@@ -12,15 +12,21 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::control_interface::write_log_level_file;
+use crate::log_router;
 use crate::runtime_connectors::StateChecker;
+use crate::workload::control_loop_state::ReplacedWorkload;
+use crate::workload::workload_command_channel::WorkloadCommandReceiver;
+use crate::workload::workload_hooks;
 use crate::workload::{ControlLoopState, WorkloadCommand};
 use crate::workload_state::{WorkloadStateSender, WorkloadStateSenderInterface};
 use common::objects::{
-    ExecutionState, RestartPolicy, WorkloadInstanceName, WorkloadSpec, WorkloadState,
+    ExecutionState, RestartPolicy, UpdateStrategy, WorkloadInstanceName, WorkloadSpec,
+    WorkloadState,
 };
 use common::std_extensions::IllegalStateResult;
 use futures_util::Future;
-use std::path::PathBuf;
+use std::{collections::VecDeque, path::PathBuf};
 
 #[cfg(not(test))]
 const MAX_RETRIES: usize = 20;
@@ -76,6 +82,17 @@ impl WorkloadControlLoop {
         StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
     {
         loop {
+            // [impl->swdd~agent-workload-control-loop-serializes-commands-during-create~1]
+            if let Some(workload_command) = control_loop_state.deferred_commands.pop_front() {
+                match Self::execute_workload_command(control_loop_state, Some(workload_command))
+                    .await
+                {
+                    Some(new_control_loop_state) => control_loop_state = new_control_loop_state,
+                    None => return,
+                }
+                continue;
+            }
+
             tokio::select! {
                 // [impl->swdd~workload-control-loop-receives-workload-states~1]
                 received_workload_state = control_loop_state.state_checker_workload_state_receiver.recv() => {
@@ -95,6 +112,11 @@ impl WorkloadControlLoop {
                         new_workload_state.execution_state.clone(),
                     ).await;
 
+                    // [impl->swdd~agent-workload-control-loop-executes-update-at-least-once~1]
+                    if Self::is_replacement_running(&control_loop_state, &new_workload_state) {
+                        control_loop_state = Self::delete_replaced_workload(control_loop_state).await;
+                    }
+
                     // [impl->swdd~workload-control-loop-handles-workload-restarts~1]
                     if Self::is_restart_required(&control_loop_state.workload_spec, &new_workload_state) {
                         control_loop_state = Self::restart_workload_on_runtime(control_loop_state).await;
@@ -103,69 +125,81 @@ impl WorkloadControlLoop {
                     log::trace!("Restart handling done.");
                 }
                 workload_command = control_loop_state.command_receiver.recv() => {
-                    match workload_command {
-                        // [impl->swdd~agent-workload-control-loop-executes-delete~2]
-                        Some(WorkloadCommand::Delete) => {
-                            log::debug!("Received WorkloadCommand::Delete.");
-
-                            if let Some(new_control_loop_state) = Self::delete_workload_on_runtime(control_loop_state).await {
-                                control_loop_state = new_control_loop_state;
-                            } else {
-                                // [impl->swdd~agent-workload-control-loop-prevents-retries-on-other-workload-commands~1]
-                                return;
-                            }
-                        }
-                        // [impl->swdd~agent-workload-control-loop-executes-update~2]
-                        Some(WorkloadCommand::Update(runtime_workload_config, control_interface_path)) => {
-                            log::debug!("Received WorkloadCommand::Update.");
-
-                            control_loop_state = Self::update_workload_on_runtime(
-                                control_loop_state,
-                                runtime_workload_config,
-                                control_interface_path,
-                            )
-                            .await;
-
-                            log::debug!("Update workload complete");
-                        }
-                        // [impl->swdd~agent-workload-control-loop-executes-retry~1]
-                        Some(WorkloadCommand::Retry(instance_name)) => {
-                            log::debug!("Received WorkloadCommand::Retry.");
-
-                            control_loop_state = Self::retry_create_workload_on_runtime(
-                                control_loop_state,
-                                *instance_name,
-                            )
-                            .await;
-                        }
-                        // [impl->swdd~agent-workload-control-loop-executes-create~2]
-                        Some(WorkloadCommand::Create) => {
-                            log::debug!("Received WorkloadCommand::Create.");
-
-                            control_loop_state = Self::create_workload_on_runtime(
-                                control_loop_state,
-                                Self::send_retry_for_workload,
-                            )
-                            .await;
-                        }
-                        // [impl->swdd~agent-workload-control-loop-executes-resume~1]
-                        Some(WorkloadCommand::Resume) => {
-                            log::debug!("Received WorkloadCommand::Resume.");
-                            control_loop_state = Self::resume_workload_on_runtime(control_loop_state).await;
-                        }
-                        _ => {
-                            log::warn!(
-                                "Could not wait for internal stop command for workload '{}'.",
-                                control_loop_state.instance_name().workload_name(),
-                            );
-                            return;
-                        }
+                    match Self::execute_workload_command(control_loop_state, workload_command).await {
+                        Some(new_control_loop_state) => control_loop_state = new_control_loop_state,
+                        None => return,
                     }
                 }
             }
         }
     }
 
+    async fn execute_workload_command<WorkloadId, StChecker>(
+        control_loop_state: ControlLoopState<WorkloadId, StChecker>,
+        workload_command: Option<WorkloadCommand>,
+    ) -> Option<ControlLoopState<WorkloadId, StChecker>>
+    where
+        WorkloadId: ToString + Send + Sync + 'static,
+        StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    {
+        match workload_command {
+            // [impl->swdd~agent-workload-control-loop-executes-delete~2]
+            Some(WorkloadCommand::Delete) => {
+                log::debug!("Received WorkloadCommand::Delete.");
+
+                // [impl->swdd~agent-workload-control-loop-prevents-retries-on-other-workload-commands~1]
+                Self::delete_workload_on_runtime(control_loop_state).await
+            }
+            // [impl->swdd~agent-workload-control-loop-executes-update~2]
+            Some(WorkloadCommand::Update(runtime_workload_config, control_interface_path)) => {
+                log::debug!("Received WorkloadCommand::Update.");
+
+                let control_loop_state = Self::update_workload_on_runtime(
+                    control_loop_state,
+                    runtime_workload_config,
+                    control_interface_path,
+                )
+                .await;
+
+                log::debug!("Update workload complete");
+                Some(control_loop_state)
+            }
+            // [impl->swdd~agent-workload-control-loop-executes-retry~1]
+            Some(WorkloadCommand::Retry(instance_name)) => {
+                log::debug!("Received WorkloadCommand::Retry.");
+
+                Some(
+                    Self::retry_create_workload_on_runtime(control_loop_state, *instance_name)
+                        .await,
+                )
+            }
+            // [impl->swdd~agent-workload-control-loop-executes-create~2]
+            Some(WorkloadCommand::Create) => {
+                log::debug!("Received WorkloadCommand::Create.");
+
+                Some(
+                    Self::create_workload_on_runtime(
+                        control_loop_state,
+                        Self::send_retry_for_workload,
+                    )
+                    .await,
+                )
+            }
+            // [impl->swdd~agent-workload-control-loop-executes-resume~1]
+            Some(WorkloadCommand::Resume) => {
+                log::debug!("Received WorkloadCommand::Resume.");
+                Some(Self::resume_workload_on_runtime(control_loop_state).await)
+            }
+            None => {
+                log::warn!(
+                    "Could not wait for internal stop command for workload '{}'.",
+                    control_loop_state.instance_name().workload_name(),
+                );
+                None
+            }
+        }
+    }
+
     async fn send_workload_state_to_agent(
         workload_state_sender: &WorkloadStateSender,
         instance_name: &WorkloadInstanceName,
@@ -335,27 +369,103 @@ impl WorkloadControlLoop {
 
         let new_instance_name = control_loop_state.workload_spec.instance_name.clone();
 
-        match control_loop_state
-            .runtime
-            .create_workload(
-                control_loop_state.workload_spec.clone(),
-                control_loop_state.control_interface_path.clone(),
-                control_loop_state
-                    .state_checker_workload_state_sender
-                    .clone(),
-            )
-            .await
+        // [impl->swdd~agent-renders-log-level-file~1]
+        if let (Some(log_level), Some(control_interface_path)) = (
+            &control_loop_state.workload_spec.log_level,
+            &control_loop_state.control_interface_path,
+        ) {
+            if let Err(err) = write_log_level_file(control_interface_path, &log_level.level) {
+                log::warn!(
+                    "Could not write the log level of workload '{}': '{}'",
+                    new_instance_name.workload_name(),
+                    err
+                );
+            }
+        }
+
+        // [impl->swdd~agent-executes-workload-hooks~1]
+        if let Err(err) = workload_hooks::run_pre_create(
+            &new_instance_name,
+            control_loop_state.workload_spec.hooks.as_ref(),
+            &control_loop_state.allowed_hooks,
+        )
+        .await
         {
-            Ok((new_workload_id, new_state_checker)) => {
+            Self::send_workload_state_to_agent(
+                &control_loop_state.to_agent_workload_state_sender,
+                &new_instance_name,
+                ExecutionState::starting_failed(&err),
+            )
+            .await;
+            return func_on_error(control_loop_state, new_instance_name, err).await;
+        }
+
+        let create = control_loop_state.runtime.create_workload(
+            control_loop_state.workload_spec.clone(),
+            control_loop_state.control_interface_path.clone(),
+            control_loop_state
+                .state_checker_workload_state_sender
+                .clone(),
+        );
+        // [impl->swdd~agent-workload-control-loop-cancels-create-on-superseding-command~1]
+        let create_result = if control_loop_state.runtime.supports_create_cancellation() {
+            // the create is always started before a queued command can supersede it
+            tokio::select! {
+                biased;
+                create_result = create => Some(create_result),
+                _ = Self::receive_superseding_command(
+                    &mut control_loop_state.command_receiver,
+                    &mut control_loop_state.deferred_commands,
+                ) => None,
+            }
+        } else {
+            Some(create.await)
+        };
+
+        match create_result {
+            Some(Ok((new_workload_id, new_state_checker))) => {
+                // [impl->swdd~agent-executes-workload-hooks~1]
+                if let Err(err) = workload_hooks::run_post_create(
+                    &new_instance_name,
+                    control_loop_state.workload_spec.hooks.as_ref(),
+                    &control_loop_state.allowed_hooks,
+                )
+                .await
+                {
+                    new_state_checker.stop_checker().await;
+                    Self::send_workload_state_to_agent(
+                        &control_loop_state.to_agent_workload_state_sender,
+                        &new_instance_name,
+                        ExecutionState::starting_failed(&err),
+                    )
+                    .await;
+                    if let Err(delete_err) = control_loop_state
+                        .runtime
+                        .delete_workload(&new_workload_id)
+                        .await
+                    {
+                        log::warn!(
+                            "Could not remove workload '{}' after its failed postCreate hook: '{}'",
+                            new_instance_name.workload_name(),
+                            delete_err
+                        );
+                        // the next delete or update removes the workload again
+                        control_loop_state.workload_id = Some(new_workload_id);
+                        return control_loop_state;
+                    }
+                    return func_on_error(control_loop_state, new_instance_name, err).await;
+                }
+
                 log::info!(
                     "Successfully created workload '{}'.",
                     new_instance_name.workload_name()
                 );
+                Self::forward_workload_logs(&control_loop_state, &new_workload_id).await;
                 control_loop_state.workload_id = Some(new_workload_id);
                 control_loop_state.state_checker = Some(new_state_checker);
                 control_loop_state
             }
-            Err(err) => {
+            Some(Err(err)) => {
                 Self::send_workload_state_to_agent(
                     &control_loop_state.to_agent_workload_state_sender,
                     &new_instance_name,
@@ -363,8 +473,109 @@ impl WorkloadControlLoop {
                 )
                 .await;
 
+                // [impl->swdd~agent-does-not-retry-fatal-runtime-errors~1]
+                if !err.is_retriable() {
+                    log::warn!(
+                        "Not retrying the creation of workload '{}' as the error is fatal: '{}'",
+                        new_instance_name.workload_name(),
+                        err
+                    );
+                    control_loop_state.workload_id = None;
+                    control_loop_state.state_checker = None;
+                    return control_loop_state;
+                }
+
                 func_on_error(control_loop_state, new_instance_name, err.to_string()).await
             }
+            None => Self::clean_up_cancelled_create(control_loop_state).await,
+        }
+    }
+
+    // Receives the commands for the workload until one of them supersedes the running create.
+    // All received commands are deferred to keep their order.
+    // [impl->swdd~agent-workload-control-loop-serializes-commands-during-create~1]
+    async fn receive_superseding_command(
+        command_receiver: &mut WorkloadCommandReceiver,
+        deferred_commands: &mut VecDeque<WorkloadCommand>,
+    ) {
+        loop {
+            match command_receiver.recv().await {
+                Some(
+                    workload_command @ (WorkloadCommand::Delete | WorkloadCommand::Update(..)),
+                ) => {
+                    deferred_commands.push_back(workload_command);
+                    return;
+                }
+                Some(workload_command) => deferred_commands.push_back(workload_command),
+                // without further commands the create is not cancelled
+                None => std::future::pending::<()>().await,
+            }
+        }
+    }
+
+    // [impl->swdd~agent-workload-control-loop-cancels-create-on-superseding-command~1]
+    async fn clean_up_cancelled_create<WorkloadId, StChecker>(
+        mut control_loop_state: ControlLoopState<WorkloadId, StChecker>,
+    ) -> ControlLoopState<WorkloadId, StChecker>
+    where
+        WorkloadId: ToString + Send + Sync + 'static,
+        StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    {
+        let instance_name = control_loop_state.instance_name().clone();
+        log::info!(
+            "Cancelled the creation of workload '{}' superseded by a new command.",
+            instance_name.workload_name()
+        );
+
+        // the pending retries belong to the cancelled create
+        control_loop_state
+            .deferred_commands
+            .retain(|workload_command| !matches!(workload_command, WorkloadCommand::Retry(_)));
+
+        // the runtime might have created the workload partially before the cancellation
+        if let Ok(workload_id) = control_loop_state
+            .runtime
+            .get_workload_id(&instance_name)
+            .await
+        {
+            if let Err(err) = control_loop_state.runtime.delete_workload(&workload_id).await {
+                log::warn!(
+                    "Could not remove the partially created workload '{}': '{}'",
+                    instance_name.workload_name(),
+                    err
+                );
+                // the superseding command deletes the workload again
+                control_loop_state.workload_id = Some(workload_id);
+            }
+        }
+        control_loop_state
+    }
+
+    // [impl->swdd~agent-forwards-workload-logs~1]
+    async fn forward_workload_logs<WorkloadId, StChecker>(
+        control_loop_state: &ControlLoopState<WorkloadId, StChecker>,
+        workload_id: &WorkloadId,
+    ) where
+        WorkloadId: ToString + Send + Sync + 'static,
+        StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    {
+        let workload_spec = &control_loop_state.workload_spec;
+        if workload_spec.log_forwarding.is_empty() {
+            return;
+        }
+
+        let workload_name = workload_spec.instance_name.workload_name();
+        match control_loop_state.runtime.follow_logs(workload_id).await {
+            Ok(receiver) => log_router::forward_logs(
+                workload_name.to_owned(),
+                &workload_spec.log_forwarding,
+                receiver,
+            ),
+            Err(err) => log::warn!(
+                "Could not forward the logs of workload '{}': '{}'",
+                workload_name,
+                err
+            ),
         }
     }
 
@@ -376,6 +587,12 @@ impl WorkloadControlLoop {
         WorkloadId: ToString + Send + Sync + 'static,
         StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
     {
+        // [impl->swdd~agent-workload-control-loop-executes-update-at-least-once~1]
+        control_loop_state = Self::delete_replaced_workload(control_loop_state).await;
+        if control_loop_state.replaced_workload.is_some() {
+            return Some(control_loop_state);
+        }
+
         Self::send_workload_state_to_agent(
             &control_loop_state.to_agent_workload_state_sender,
             control_loop_state.instance_name(),
@@ -384,6 +601,13 @@ impl WorkloadControlLoop {
         .await;
 
         if let Some(old_id) = control_loop_state.workload_id.take() {
+            // [impl->swdd~agent-executes-workload-hooks~1]
+            workload_hooks::run_pre_delete(
+                control_loop_state.instance_name(),
+                control_loop_state.workload_spec.hooks.as_ref(),
+                &control_loop_state.allowed_hooks,
+            )
+            .await;
             if let Err(err) = control_loop_state.runtime.delete_workload(&old_id).await {
                 Self::send_workload_state_to_agent(
                     &control_loop_state.to_agent_workload_state_sender,
@@ -435,6 +659,29 @@ impl WorkloadControlLoop {
         WorkloadId: ToString + Send + Sync + 'static,
         StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
     {
+        // [impl->swdd~agent-workload-control-loop-executes-update-at-least-once~1]
+        let at_least_once = Self::is_at_least_once_update(
+            control_loop_state.instance_name(),
+            new_workload_spec.as_deref(),
+        );
+        if at_least_once
+            && control_loop_state.replaced_workload.is_none()
+            && control_loop_state.workload_id.is_some()
+        {
+            if let Some(new_workload_spec) = new_workload_spec {
+                return Self::replace_workload_on_runtime(
+                    control_loop_state,
+                    *new_workload_spec,
+                    control_interface_path,
+                )
+                .await;
+            }
+        }
+        // a replaced instance is only kept while the next instance is not running yet
+        if !at_least_once {
+            control_loop_state = Self::delete_replaced_workload(control_loop_state).await;
+        }
+
         Self::send_workload_state_to_agent(
             &control_loop_state.to_agent_workload_state_sender,
             control_loop_state.instance_name(),
@@ -443,6 +690,13 @@ impl WorkloadControlLoop {
         .await;
 
         if let Some(old_id) = control_loop_state.workload_id.take() {
+            // [impl->swdd~agent-executes-workload-hooks~1]
+            workload_hooks::run_pre_delete(
+                control_loop_state.instance_name(),
+                control_loop_state.workload_spec.hooks.as_ref(),
+                &control_loop_state.allowed_hooks,
+            )
+            .await;
             if let Err(err) = control_loop_state.runtime.delete_workload(&old_id).await {
                 Self::send_workload_state_to_agent(
                     &control_loop_state.to_agent_workload_state_sender,
@@ -494,6 +748,123 @@ impl WorkloadControlLoop {
         control_loop_state
     }
 
+    // A restart keeps the instance name of the workload and is always done AT_MOST_ONCE.
+    fn is_at_least_once_update(
+        current_instance_name: &WorkloadInstanceName,
+        new_workload_spec: Option<&WorkloadSpec>,
+    ) -> bool {
+        new_workload_spec.is_some_and(|new_workload_spec| {
+            new_workload_spec.update_strategy == UpdateStrategy::AtLeastOnce
+                && new_workload_spec.instance_name != *current_instance_name
+        })
+    }
+
+    // [impl->swdd~agent-workload-control-loop-executes-update-at-least-once~1]
+    async fn replace_workload_on_runtime<WorkloadId, StChecker>(
+        mut control_loop_state: ControlLoopState<WorkloadId, StChecker>,
+        new_workload_spec: WorkloadSpec,
+        control_interface_path: Option<PathBuf>,
+    ) -> ControlLoopState<WorkloadId, StChecker>
+    where
+        WorkloadId: ToString + Send + Sync + 'static,
+        StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    {
+        if let Some(workload_id) = control_loop_state.workload_id.take() {
+            log::debug!(
+                "Keeping workload '{}' running until its new instance is running.",
+                control_loop_state.instance_name()
+            );
+            control_loop_state.replaced_workload = Some(ReplacedWorkload {
+                instance_name: control_loop_state.instance_name().clone(),
+                workload_id,
+                state_checker: control_loop_state.state_checker.take(),
+                hooks: control_loop_state.workload_spec.hooks.clone(),
+            });
+        }
+
+        // [impl->swdd~agent-workload-control-loop-reset-retry-attempts-on-update~1]
+        control_loop_state.retry_counter.reset();
+        control_loop_state.workload_spec = new_workload_spec;
+        control_loop_state.control_interface_path = control_interface_path;
+        Self::create_workload_on_runtime(control_loop_state, Self::send_retry_for_workload).await
+    }
+
+    fn is_replacement_running<WorkloadId, StChecker>(
+        control_loop_state: &ControlLoopState<WorkloadId, StChecker>,
+        workload_state: &WorkloadState,
+    ) -> bool
+    where
+        WorkloadId: ToString + Send + Sync + 'static,
+        StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    {
+        control_loop_state.replaced_workload.is_some()
+            && Self::is_same_workload(
+                control_loop_state.instance_name(),
+                &workload_state.instance_name,
+            )
+            && workload_state.execution_state.is_running()
+    }
+
+    // [impl->swdd~agent-workload-control-loop-executes-update-at-least-once~1]
+    async fn delete_replaced_workload<WorkloadId, StChecker>(
+        mut control_loop_state: ControlLoopState<WorkloadId, StChecker>,
+    ) -> ControlLoopState<WorkloadId, StChecker>
+    where
+        WorkloadId: ToString + Send + Sync + 'static,
+        StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    {
+        let Some(replaced_workload) = control_loop_state.replaced_workload.take() else {
+            return control_loop_state;
+        };
+
+        Self::send_workload_state_to_agent(
+            &control_loop_state.to_agent_workload_state_sender,
+            &replaced_workload.instance_name,
+            ExecutionState::stopping_requested(),
+        )
+        .await;
+
+        // [impl->swdd~agent-executes-workload-hooks~1]
+        workload_hooks::run_pre_delete(
+            &replaced_workload.instance_name,
+            replaced_workload.hooks.as_ref(),
+            &control_loop_state.allowed_hooks,
+        )
+        .await;
+        match control_loop_state
+            .runtime
+            .delete_workload(&replaced_workload.workload_id)
+            .await
+        {
+            Ok(()) => {
+                if let Some(old_checker) = replaced_workload.state_checker {
+                    old_checker.stop_checker().await;
+                }
+                Self::send_workload_state_to_agent(
+                    &control_loop_state.to_agent_workload_state_sender,
+                    &replaced_workload.instance_name,
+                    ExecutionState::removed(),
+                )
+                .await;
+            }
+            Err(err) => {
+                Self::send_workload_state_to_agent(
+                    &control_loop_state.to_agent_workload_state_sender,
+                    &replaced_workload.instance_name,
+                    ExecutionState::delete_failed(err.to_string()),
+                )
+                .await;
+                log::warn!(
+                    "Could not delete the replaced workload '{}': '{}'",
+                    replaced_workload.instance_name,
+                    err
+                );
+                control_loop_state.replaced_workload = Some(replaced_workload);
+            }
+        }
+        control_loop_state
+    }
+
     async fn retry_create_workload_on_runtime<WorkloadId, StChecker>(
         control_loop_state: ControlLoopState<WorkloadId, StChecker>,
         instance_name: WorkloadInstanceName,
@@ -562,6 +933,10 @@ impl WorkloadControlLoop {
             }
         };
 
+        if let Ok(wl_id) = workload_id.as_ref() {
+            Self::forward_workload_logs(&control_loop_state, wl_id).await;
+        }
+
         // assign the workload id and state checker to the control loop state
         control_loop_state.workload_id = workload_id.ok();
         control_loop_state.state_checker = state_checker;
@@ -589,51 +964,232 @@ mockall::mock! {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::WorkloadControlLoop;
-    use std::time::Duration;
+#[cfg(test)]
+mod tests {
+    use super::WorkloadControlLoop;
+    use std::time::Duration;
+
+    use common::objects::{
+        generate_test_workload_spec, generate_test_workload_spec_with_param, ExecutionState,
+        UpdateStrategy, WorkloadHooks, WorkloadInstanceName,
+    };
+    use common::objects::{generate_test_workload_state_with_workload_spec, RestartPolicy};
+
+    use tokio::{sync::mpsc, time::timeout};
+
+    use crate::workload_state::WorkloadStateSenderInterface;
+    use crate::{
+        runtime_connectors::test::{MockRuntimeConnector, RuntimeCall, StubStateChecker},
+        workload::{workload_hooks, ControlLoopState, WorkloadCommandSender},
+        workload_state::assert_execution_state_sequence,
+    };
+
+    const RUNTIME_NAME: &str = "runtime1";
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+    const WORKLOAD_ID: &str = "workload_id_1";
+    const WORKLOAD_ID_2: &str = "workload_id_2";
+    const WORKLOAD_ID_3: &str = "workload_id_3";
+    const PIPES_LOCATION: &str = "/some/path";
+    const OLD_WORKLOAD_ID: &str = "old_workload_id";
+
+    const TEST_EXEC_COMMAND_BUFFER_SIZE: usize = 20;
+
+    // Unfortunately this test also executes a delete of the newly updated workload.
+    // We could not avoid this as it is the only possibility to check the internal variables
+    // and to properly stop the control loop in the await new command method
+    // [utest->swdd~agent-workload-control-loop-executes-update~2]
+    #[tokio::test]
+    async fn utest_workload_obj_run_update_success() {
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let mut old_mock_state_checker = StubStateChecker::new();
+        old_mock_state_checker.panic_if_not_stopped();
+
+        // Since we also send a delete command to exit the control loop properly, the new state
+        // checker will also we stopped. This also tests if the new state checker was properly stored.
+        let mut new_mock_state_checker = StubStateChecker::new();
+        new_mock_state_checker.panic_if_not_stopped();
+
+        let old_workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let mut new_workload_spec = old_workload_spec.clone();
+        new_workload_spec.runtime_config = "changed config".to_owned();
+        new_workload_spec.instance_name = WorkloadInstanceName::builder()
+            .agent_name(old_workload_spec.instance_name.agent_name())
+            .workload_name(old_workload_spec.instance_name.workload_name())
+            .config(&new_workload_spec.runtime_config)
+            .build();
+
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::DeleteWorkload(OLD_WORKLOAD_ID.to_string(), Ok(())),
+                RuntimeCall::CreateWorkload(
+                    new_workload_spec.clone(),
+                    Some(PIPES_LOCATION.into()),
+                    Ok((WORKLOAD_ID.to_string(), new_mock_state_checker)),
+                ),
+                // Since we also send a delete command to exit the control loop properly, the new workload
+                // will also be deleted. This also tests if the new workload id was properly stored.
+                RuntimeCall::DeleteWorkload(WORKLOAD_ID.to_string(), Ok(())),
+            ])
+            .await;
+
+        // Send the update command now. It will be buffered until the await receives it.
+        workload_command_sender
+            .update(Some(new_workload_spec.clone()), Some(PIPES_LOCATION.into()))
+            .await
+            .unwrap();
+        // Send also a delete command so that we can properly get out of the loop
+        workload_command_sender.clone().delete().await.unwrap();
+
+        let old_instance_name = old_workload_spec.instance_name.clone();
+        let new_instance_name = new_workload_spec.instance_name.clone();
+
+        let mut control_loop_state = ControlLoopState::builder()
+            .workload_spec(old_workload_spec)
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .build()
+            .unwrap();
+
+        control_loop_state.workload_id = Some(OLD_WORKLOAD_ID.to_string());
+        control_loop_state.state_checker = Some(old_mock_state_checker);
+
+        assert!(timeout(
+            Duration::from_millis(200),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (&old_instance_name, ExecutionState::stopping_requested()),
+                (&old_instance_name, ExecutionState::removed()),
+                (&new_instance_name, ExecutionState::starting_triggered()),
+                (&new_instance_name, ExecutionState::stopping_requested()),
+                (&new_instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-workload-control-loop-executes-update-at-least-once~1]
+    #[tokio::test]
+    async fn utest_workload_obj_run_update_at_least_once_deletes_old_workload_when_running() {
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let mut old_mock_state_checker = StubStateChecker::new();
+        old_mock_state_checker.panic_if_not_stopped();
+
+        let mut new_mock_state_checker = StubStateChecker::new();
+        new_mock_state_checker.panic_if_not_stopped();
+
+        let old_workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let mut new_workload_spec = old_workload_spec.clone();
+        new_workload_spec.update_strategy = UpdateStrategy::AtLeastOnce;
+        new_workload_spec.runtime_config = "changed config".to_owned();
+        new_workload_spec.instance_name = WorkloadInstanceName::builder()
+            .agent_name(old_workload_spec.instance_name.agent_name())
+            .workload_name(old_workload_spec.instance_name.workload_name())
+            .config(&new_workload_spec.runtime_config)
+            .build();
+
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::CreateWorkload(
+                    new_workload_spec.clone(),
+                    Some(PIPES_LOCATION.into()),
+                    Ok((WORKLOAD_ID.to_string(), new_mock_state_checker)),
+                ),
+                // the old workload is only deleted after the new one is running
+                RuntimeCall::DeleteWorkload(OLD_WORKLOAD_ID.to_string(), Ok(())),
+                RuntimeCall::DeleteWorkload(WORKLOAD_ID.to_string(), Ok(())),
+            ])
+            .await;
+
+        workload_command_sender
+            .update(Some(new_workload_spec.clone()), Some(PIPES_LOCATION.into()))
+            .await
+            .unwrap();
+
+        let old_instance_name = old_workload_spec.instance_name.clone();
+        let new_instance_name = new_workload_spec.instance_name.clone();
 
-    use common::objects::{
-        generate_test_workload_spec, generate_test_workload_spec_with_param, ExecutionState,
-        WorkloadInstanceName,
-    };
-    use common::objects::{generate_test_workload_state_with_workload_spec, RestartPolicy};
+        let mut control_loop_state = ControlLoopState::builder()
+            .workload_spec(old_workload_spec)
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender.clone())
+            .build()
+            .unwrap();
 
-    use tokio::{sync::mpsc, time::timeout};
+        control_loop_state.workload_id = Some(OLD_WORKLOAD_ID.to_string());
+        control_loop_state.state_checker = Some(old_mock_state_checker);
 
-    use crate::workload_state::WorkloadStateSenderInterface;
-    use crate::{
-        runtime_connectors::test::{MockRuntimeConnector, RuntimeCall, StubStateChecker},
-        workload::{ControlLoopState, WorkloadCommandSender},
-        workload_state::assert_execution_state_sequence,
-    };
+        let state_checker_wl_state_sender = control_loop_state
+            .state_checker_workload_state_sender
+            .clone();
+        let running_instance_name = new_instance_name.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+            state_checker_wl_state_sender
+                .report_workload_execution_state(&running_instance_name, ExecutionState::running())
+                .await;
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+            workload_command_sender.delete().await.unwrap();
+        });
 
-    const RUNTIME_NAME: &str = "runtime1";
-    const AGENT_NAME: &str = "agent_x";
-    const WORKLOAD_1_NAME: &str = "workload1";
-    const WORKLOAD_ID: &str = "workload_id_1";
-    const WORKLOAD_ID_2: &str = "workload_id_2";
-    const WORKLOAD_ID_3: &str = "workload_id_3";
-    const PIPES_LOCATION: &str = "/some/path";
-    const OLD_WORKLOAD_ID: &str = "old_workload_id";
+        assert!(timeout(
+            Duration::from_millis(200),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
 
-    const TEST_EXEC_COMMAND_BUFFER_SIZE: usize = 20;
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (&new_instance_name, ExecutionState::starting_triggered()),
+                (&new_instance_name, ExecutionState::running()),
+                (&old_instance_name, ExecutionState::stopping_requested()),
+                (&old_instance_name, ExecutionState::removed()),
+                (&new_instance_name, ExecutionState::stopping_requested()),
+                (&new_instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
 
-    // Unfortunately this test also executes a delete of the newly updated workload.
-    // We could not avoid this as it is the only possibility to check the internal variables
-    // and to properly stop the control loop in the await new command method
-    // [utest->swdd~agent-workload-control-loop-executes-update~2]
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-workload-control-loop-cancels-create-on-superseding-command~1]
+    // [utest->swdd~agent-workload-control-loop-serializes-commands-during-create~1]
     #[tokio::test]
-    async fn utest_workload_obj_run_update_success() {
+    async fn utest_workload_obj_run_update_cancels_running_create() {
         let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
         let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
 
-        let mut old_mock_state_checker = StubStateChecker::new();
-        old_mock_state_checker.panic_if_not_stopped();
-
-        // Since we also send a delete command to exit the control loop properly, the new state
-        // checker will also we stopped. This also tests if the new state checker was properly stored.
         let mut new_mock_state_checker = StubStateChecker::new();
         new_mock_state_checker.panic_if_not_stopped();
 
@@ -652,32 +1208,36 @@ mod tests {
             .build();
 
         let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock.support_create_cancellation();
         runtime_mock
             .expect(vec![
+                RuntimeCall::CreateWorkloadPending(old_workload_spec.clone(), None),
+                // the partially created workload is removed after the cancellation
+                RuntimeCall::GetWorkloadId(
+                    old_workload_spec.instance_name.clone(),
+                    Ok(OLD_WORKLOAD_ID.to_string()),
+                ),
                 RuntimeCall::DeleteWorkload(OLD_WORKLOAD_ID.to_string(), Ok(())),
                 RuntimeCall::CreateWorkload(
                     new_workload_spec.clone(),
                     Some(PIPES_LOCATION.into()),
                     Ok((WORKLOAD_ID.to_string(), new_mock_state_checker)),
                 ),
-                // Since we also send a delete command to exit the control loop properly, the new workload
-                // will also be deleted. This also tests if the new workload id was properly stored.
                 RuntimeCall::DeleteWorkload(WORKLOAD_ID.to_string(), Ok(())),
             ])
             .await;
 
-        // Send the update command now. It will be buffered until the await receives it.
+        workload_command_sender.create().await.unwrap();
         workload_command_sender
             .update(Some(new_workload_spec.clone()), Some(PIPES_LOCATION.into()))
             .await
             .unwrap();
-        // Send also a delete command so that we can properly get out of the loop
         workload_command_sender.clone().delete().await.unwrap();
 
         let old_instance_name = old_workload_spec.instance_name.clone();
         let new_instance_name = new_workload_spec.instance_name.clone();
 
-        let mut control_loop_state = ControlLoopState::builder()
+        let control_loop_state = ControlLoopState::builder()
             .workload_spec(old_workload_spec)
             .workload_state_sender(state_change_tx)
             .runtime(Box::new(runtime_mock.clone()))
@@ -686,9 +1246,6 @@ mod tests {
             .build()
             .unwrap();
 
-        control_loop_state.workload_id = Some(OLD_WORKLOAD_ID.to_string());
-        control_loop_state.state_checker = Some(old_mock_state_checker);
-
         assert!(timeout(
             Duration::from_millis(200),
             WorkloadControlLoop::run(control_loop_state)
@@ -699,6 +1256,7 @@ mod tests {
         assert_execution_state_sequence(
             state_change_rx,
             vec![
+                (&old_instance_name, ExecutionState::starting_triggered()),
                 (&old_instance_name, ExecutionState::stopping_requested()),
                 (&old_instance_name, ExecutionState::removed()),
                 (&new_instance_name, ExecutionState::starting_triggered()),
@@ -976,7 +1534,7 @@ mod tests {
             .expect(vec![
                 RuntimeCall::DeleteWorkload(
                     OLD_WORKLOAD_ID.to_string(),
-                    Err(crate::runtime_connectors::RuntimeError::Delete(
+                    Err(crate::runtime_connectors::RuntimeError::internal(
                         "some delete error".to_string(),
                     )),
                 ),
@@ -1021,7 +1579,7 @@ mod tests {
                 (&old_instance_name, ExecutionState::stopping_requested()),
                 (
                     &old_instance_name,
-                    ExecutionState::delete_failed("some delete error"),
+                    ExecutionState::delete_failed("Internal: some delete error"),
                 ),
                 (&old_instance_name, ExecutionState::stopping_requested()),
                 (&old_instance_name, ExecutionState::removed()),
@@ -1062,7 +1620,7 @@ mod tests {
                 RuntimeCall::CreateWorkload(
                     new_workload_spec.clone(),
                     Some(PIPES_LOCATION.into()),
-                    Err(crate::runtime_connectors::RuntimeError::Create(
+                    Err(crate::runtime_connectors::RuntimeError::internal(
                         "some create error".to_string(),
                     )),
                 ),
@@ -1109,7 +1667,7 @@ mod tests {
                 (&new_instance_name, ExecutionState::starting_triggered()),
                 (
                     &new_instance_name,
-                    ExecutionState::starting_failed("some create error"),
+                    ExecutionState::starting_failed("Internal: some create error"),
                 ),
                 (&new_instance_name, ExecutionState::stopping_requested()),
                 (&new_instance_name, ExecutionState::removed()),
@@ -1193,7 +1751,7 @@ mod tests {
             .expect(vec![
                 RuntimeCall::DeleteWorkload(
                     OLD_WORKLOAD_ID.to_string(),
-                    Err(crate::runtime_connectors::RuntimeError::Delete(
+                    Err(crate::runtime_connectors::RuntimeError::internal(
                         "some delete error".to_string(),
                     )),
                 ),
@@ -1238,7 +1796,7 @@ mod tests {
                 (&instance_name, ExecutionState::stopping_requested()),
                 (
                     &instance_name,
-                    ExecutionState::delete_failed("some delete error"),
+                    ExecutionState::delete_failed("Internal: some delete error"),
                 ),
                 (&instance_name, ExecutionState::stopping_requested()),
                 (&instance_name, ExecutionState::removed()),
@@ -1366,7 +1924,7 @@ mod tests {
                 RuntimeCall::CreateWorkload(
                     workload_spec.clone(),
                     Some(PIPES_LOCATION.into()),
-                    Err(crate::runtime_connectors::RuntimeError::Create(
+                    Err(crate::runtime_connectors::RuntimeError::internal(
                         "some create error".to_string(),
                     )),
                 ),
@@ -1426,7 +1984,7 @@ mod tests {
         let runtime_expectations = vec![RuntimeCall::CreateWorkload(
             workload_spec.clone(),
             Some(PIPES_LOCATION.into()),
-            Err(crate::runtime_connectors::RuntimeError::Create(
+            Err(crate::runtime_connectors::RuntimeError::internal(
                 "some create error".to_string(),
             )),
         )];
@@ -1483,7 +2041,7 @@ mod tests {
                 RuntimeCall::CreateWorkload(
                     workload_spec.clone(),
                     Some(PIPES_LOCATION.into()),
-                    Err(crate::runtime_connectors::RuntimeError::Create(
+                    Err(crate::runtime_connectors::RuntimeError::internal(
                         "some create error".to_string(),
                     )),
                 ),
@@ -1554,7 +2112,7 @@ mod tests {
             runtime_expectations.push(RuntimeCall::CreateWorkload(
                 workload_spec.clone(),
                 Some(PIPES_LOCATION.into()),
-                Err(crate::runtime_connectors::RuntimeError::Create(
+                Err(crate::runtime_connectors::RuntimeError::internal(
                     "some create error".to_string(),
                 )),
             ));
@@ -1599,12 +2157,12 @@ mod tests {
                 (&instance_name, ExecutionState::starting_triggered()),
                 (
                     &instance_name,
-                    ExecutionState::starting_failed("some create error"),
+                    ExecutionState::starting_failed("Internal: some create error"),
                 ),
                 (&instance_name, ExecutionState::starting_triggered()),
                 (
                     &instance_name,
-                    ExecutionState::starting_failed("some create error"),
+                    ExecutionState::starting_failed("Internal: some create error"),
                 ),
                 (&instance_name, ExecutionState::retry_failed_no_retry()),
                 (&instance_name, ExecutionState::stopping_requested()),
@@ -1616,6 +2174,207 @@ mod tests {
         runtime_mock.assert_all_expectations().await;
     }
 
+    // [utest->swdd~agent-does-not-retry-fatal-runtime-errors~1]
+    // [utest->swdd~agent-reports-reason-of-runtime-errors~1]
+    #[tokio::test]
+    async fn utest_workload_obj_run_no_retry_on_invalid_config() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let instance_name = workload_spec.instance_name.clone();
+
+        // a second create call would be unexpected
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![RuntimeCall::CreateWorkload(
+                workload_spec.clone(),
+                Some(PIPES_LOCATION.into()),
+                Err(crate::runtime_connectors::RuntimeError::InvalidConfig(
+                    "broken runtime config".to_string(),
+                )),
+            )])
+            .await;
+
+        workload_command_sender.create().await.unwrap();
+
+        let workload_command_sender_clone = workload_command_sender.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            workload_command_sender_clone.delete().await.unwrap();
+        });
+
+        let control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .control_interface_path(Some(PIPES_LOCATION.into()))
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .build()
+            .unwrap();
+
+        assert!(timeout(
+            Duration::from_millis(150),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (&instance_name, ExecutionState::starting_triggered()),
+                (
+                    &instance_name,
+                    ExecutionState::starting_failed("InvalidConfig: broken runtime config"),
+                ),
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-executes-workload-hooks~1]
+    #[tokio::test]
+    async fn utest_workload_obj_run_failed_pre_create_hook_prevents_create() {
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+        workload_spec.hooks = Some(WorkloadHooks {
+            pre_create: Some("false".to_string()),
+            ..Default::default()
+        });
+        let instance_name = workload_spec.instance_name.clone();
+
+        // the runtime is not called at all
+        let runtime_mock = MockRuntimeConnector::new();
+
+        // the delete is executed before the retry of the create
+        workload_command_sender.create().await.unwrap();
+        workload_command_sender.clone().delete().await.unwrap();
+
+        let control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .control_interface_path(Some(PIPES_LOCATION.into()))
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .allowed_hooks(workload_hooks::generate_test_allowed_hooks())
+            .build()
+            .unwrap();
+
+        assert!(timeout(
+            Duration::from_millis(1000),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (&instance_name, ExecutionState::starting_triggered()),
+                (
+                    &instance_name,
+                    ExecutionState::starting_failed("preCreate hook failed: exit status: 1"),
+                ),
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-executes-workload-hooks~1]
+    #[tokio::test]
+    async fn utest_workload_obj_run_failed_post_create_hook_removes_created_workload() {
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+        workload_spec.hooks = Some(WorkloadHooks {
+            pre_create: Some("true".to_string()),
+            post_create: Some("false".to_string()),
+            pre_delete: None,
+        });
+        let instance_name = workload_spec.instance_name.clone();
+
+        let mut mock_state_checker = StubStateChecker::new();
+        mock_state_checker.panic_if_not_stopped();
+
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::CreateWorkload(
+                    workload_spec.clone(),
+                    Some(PIPES_LOCATION.into()),
+                    Ok((WORKLOAD_ID.to_string(), mock_state_checker)),
+                ),
+                RuntimeCall::DeleteWorkload(WORKLOAD_ID.to_string(), Ok(())),
+            ])
+            .await;
+
+        // the delete is executed before the retry of the create
+        workload_command_sender.create().await.unwrap();
+        workload_command_sender.clone().delete().await.unwrap();
+
+        let control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .control_interface_path(Some(PIPES_LOCATION.into()))
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .allowed_hooks(workload_hooks::generate_test_allowed_hooks())
+            .build()
+            .unwrap();
+
+        assert!(timeout(
+            Duration::from_millis(1000),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (&instance_name, ExecutionState::starting_triggered()),
+                (
+                    &instance_name,
+                    ExecutionState::starting_failed("postCreate hook failed: exit status: 1"),
+                ),
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
     // [utest->swdd~agent-workload-control-loop-executes-retry~1]
     #[tokio::test]
     async fn utest_workload_obj_run_retry_creation_workload_command_channel_closed() {
@@ -1634,7 +2393,7 @@ mod tests {
         let runtime_expectations = vec![RuntimeCall::CreateWorkload(
             workload_spec.clone(),
             Some(PIPES_LOCATION.into()),
-            Err(crate::runtime_connectors::RuntimeError::Create(
+            Err(crate::runtime_connectors::RuntimeError::internal(
                 "some create error".to_string(),
             )),
         )];
@@ -1694,7 +2453,7 @@ mod tests {
                 RuntimeCall::CreateWorkload(
                     workload_spec.clone(),
                     Some(PIPES_LOCATION.into()),
-                    Err(crate::runtime_connectors::RuntimeError::Create(
+                    Err(crate::runtime_connectors::RuntimeError::internal(
                         "some create error".to_string(),
                     )),
                 ),
@@ -1775,7 +2534,7 @@ mod tests {
                 RuntimeCall::CreateWorkload(
                     new_workload_spec.clone(),
                     Some(PIPES_LOCATION.into()),
-                    Err(crate::runtime_connectors::RuntimeError::Create(
+                    Err(crate::runtime_connectors::RuntimeError::internal(
                         "some create error".to_string(),
                     )),
                 ),
@@ -1859,7 +2618,7 @@ mod tests {
                 RuntimeCall::CreateWorkload(
                     new_workload_spec.clone(),
                     Some(PIPES_LOCATION.into()),
-                    Err(crate::runtime_connectors::RuntimeError::Create(
+                    Err(crate::runtime_connectors::RuntimeError::internal(
                         "some create error".to_string(),
                     )),
                 ),
@@ -1949,7 +2708,7 @@ mod tests {
                 RuntimeCall::CreateWorkload(
                     new_workload_spec_update1.clone(),
                     Some(PIPES_LOCATION.into()),
-                    Err(crate::runtime_connectors::RuntimeError::Create(
+                    Err(crate::runtime_connectors::RuntimeError::internal(
                         "some create error".to_string(),
                     )),
                 ),
@@ -2144,7 +2903,7 @@ mod tests {
         runtime_mock
             .expect(vec![RuntimeCall::GetWorkloadId(
                 workload_spec.instance_name.clone(),
-                Err(crate::runtime_connectors::RuntimeError::List(
+                Err(crate::runtime_connectors::RuntimeError::internal(
                     "some list workload error".to_string(),
                 )),
             )])
@@ -2196,7 +2955,7 @@ mod tests {
                     WORKLOAD_ID.to_string(),
                     workload_spec.clone(),
                     state_checker_workload_state_sender.clone(),
-                    Err(crate::runtime_connectors::RuntimeError::Create(
+                    Err(crate::runtime_connectors::RuntimeError::internal(
                         "some state checker error".to_string(),
                     )),
                 ),
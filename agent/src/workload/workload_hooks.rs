@@ -0,0 +1,242 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashSet, process::Stdio, sync::Arc, time::Duration};
+
+use common::objects::{WorkloadHooks, WorkloadInstanceName};
+use tokio::process::Command;
+
+// A hanging hook must not block the operations of the workload forever.
+const HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
+// The hooks are part of the desired state, but executed on the host of the agent.
+// Thus, only the hook commands allowed by the agent configuration are executed.
+#[derive(Debug, Clone, Default)]
+pub struct AllowedHooks(Arc<HashSet<String>>);
+
+impl AllowedHooks {
+    pub fn new(commands: Vec<String>) -> Self {
+        AllowedHooks(Arc::new(commands.into_iter().collect()))
+    }
+
+    fn contains(&self, command: &str) -> bool {
+        self.0.contains(command)
+    }
+}
+
+#[cfg(test)]
+pub fn generate_test_allowed_hooks() -> AllowedHooks {
+    AllowedHooks::new(
+        [
+            "true",
+            "false",
+            "test \"$ANKAIOS_WORKLOAD_NAME\" = workload_1 && test -n \"$ANKAIOS_AGENT_NAME\"",
+            "echo 'no such device' >&2; exit 3",
+        ]
+        .map(String::from)
+        .to_vec(),
+    )
+}
+
+// [impl->swdd~agent-executes-workload-hooks~1]
+pub async fn run_pre_create(
+    instance_name: &WorkloadInstanceName,
+    hooks: Option<&WorkloadHooks>,
+    allowed_hooks: &AllowedHooks,
+) -> Result<(), String> {
+    let command = hooks.and_then(|hooks| hooks.pre_create.as_deref());
+    run_hook("preCreate", command, instance_name, allowed_hooks).await
+}
+
+// [impl->swdd~agent-executes-workload-hooks~1]
+pub async fn run_post_create(
+    instance_name: &WorkloadInstanceName,
+    hooks: Option<&WorkloadHooks>,
+    allowed_hooks: &AllowedHooks,
+) -> Result<(), String> {
+    let command = hooks.and_then(|hooks| hooks.post_create.as_deref());
+    run_hook("postCreate", command, instance_name, allowed_hooks).await
+}
+
+// A failed preDelete hook does not keep the workload, otherwise it could never be removed.
+// [impl->swdd~agent-executes-workload-hooks~1]
+pub async fn run_pre_delete(
+    instance_name: &WorkloadInstanceName,
+    hooks: Option<&WorkloadHooks>,
+    allowed_hooks: &AllowedHooks,
+) {
+    let command = hooks.and_then(|hooks| hooks.pre_delete.as_deref());
+    if let Err(err) = run_hook("preDelete", command, instance_name, allowed_hooks).await {
+        log::warn!(
+            "Deleting workload '{}' despite the failure: '{}'",
+            instance_name.workload_name(),
+            err
+        );
+    }
+}
+
+async fn run_hook(
+    hook_name: &str,
+    command: Option<&str>,
+    instance_name: &WorkloadInstanceName,
+    allowed_hooks: &AllowedHooks,
+) -> Result<(), String> {
+    let Some(command) = command else {
+        return Ok(());
+    };
+    // [impl->swdd~agent-executes-only-allowed-hooks~1]
+    if !allowed_hooks.contains(command) {
+        return Err(format!(
+            "{} hook '{}' is not allowed by the agent configuration",
+            hook_name, command
+        ));
+    }
+    log::debug!(
+        "Executing the {} hook of workload '{}'.",
+        hook_name,
+        instance_name.workload_name()
+    );
+
+    let output = Command::new("sh")
+        .args(["-c", command])
+        .env("ANKAIOS_WORKLOAD_NAME", instance_name.workload_name())
+        .env("ANKAIOS_AGENT_NAME", instance_name.agent_name())
+        .env("ANKAIOS_WORKLOAD_INSTANCE_NAME", instance_name.to_string())
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+
+    match tokio::time::timeout(HOOK_TIMEOUT, output).await {
+        Ok(Ok(output)) if output.status.success() => Ok(()),
+        Ok(Ok(output)) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stderr = stderr.trim();
+            if stderr.is_empty() {
+                Err(format!("{} hook failed: {}", hook_name, output.status))
+            } else {
+                Err(format!(
+                    "{} hook failed: {}: {}",
+                    hook_name, output.status, stderr
+                ))
+            }
+        }
+        Ok(Err(err)) => Err(format!("{} hook could not be executed: {}", hook_name, err)),
+        Err(_) => Err(format!(
+            "{} hook timed out after {} s",
+            hook_name,
+            HOOK_TIMEOUT.as_secs()
+        )),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::{generate_test_workload_instance_name, WorkloadHooks};
+
+    use super::{generate_test_allowed_hooks, run_post_create, run_pre_create, AllowedHooks};
+
+    const WORKLOAD_NAME: &str = "workload_1";
+
+    fn hooks(pre_create: &str) -> WorkloadHooks {
+        WorkloadHooks {
+            pre_create: Some(pre_create.to_string()),
+            ..Default::default()
+        }
+    }
+
+    // [utest->swdd~agent-executes-workload-hooks~1]
+    #[tokio::test]
+    async fn utest_workload_hooks_succeed_without_configured_hook() {
+        let allowed_hooks = generate_test_allowed_hooks();
+        let instance_name = generate_test_workload_instance_name(WORKLOAD_NAME);
+
+        assert_eq!(
+            run_pre_create(&instance_name, None, &allowed_hooks).await,
+            Ok(())
+        );
+        assert_eq!(
+            run_post_create(&instance_name, Some(&hooks("false")), &allowed_hooks).await,
+            Ok(())
+        );
+    }
+
+    // [utest->swdd~agent-executes-workload-hooks~1]
+    #[tokio::test]
+    async fn utest_workload_hooks_pass_the_workload_to_the_command() {
+        let allowed_hooks = generate_test_allowed_hooks();
+        let instance_name = generate_test_workload_instance_name(WORKLOAD_NAME);
+
+        assert_eq!(
+            run_pre_create(
+                &instance_name,
+                Some(&hooks(
+                    "test \"$ANKAIOS_WORKLOAD_NAME\" = workload_1 && test -n \"$ANKAIOS_AGENT_NAME\""
+                )),
+                &allowed_hooks
+            )
+            .await,
+            Ok(())
+        );
+    }
+
+    // [utest->swdd~agent-executes-workload-hooks~1]
+    #[tokio::test]
+    async fn utest_workload_hooks_report_failed_command() {
+        let allowed_hooks = generate_test_allowed_hooks();
+        let instance_name = generate_test_workload_instance_name(WORKLOAD_NAME);
+
+        assert_eq!(
+            run_pre_create(&instance_name, Some(&hooks("false")), &allowed_hooks).await,
+            Err("preCreate hook failed: exit status: 1".to_string())
+        );
+        assert_eq!(
+            run_pre_create(
+                &instance_name,
+                Some(&hooks("echo 'no such device' >&2; exit 3")),
+                &allowed_hooks
+            )
+            .await,
+            Err("preCreate hook failed: exit status: 3: no such device".to_string())
+        );
+    }
+
+    // [utest->swdd~agent-executes-only-allowed-hooks~1]
+    #[tokio::test]
+    async fn utest_workload_hooks_reject_command_not_allowed_by_agent() {
+        let allowed_hooks = generate_test_allowed_hooks();
+        let instance_name = generate_test_workload_instance_name(WORKLOAD_NAME);
+
+        assert_eq!(
+            run_pre_create(&instance_name, Some(&hooks("exit 0")), &allowed_hooks).await,
+            Err("preCreate hook 'exit 0' is not allowed by the agent configuration".to_string())
+        );
+        assert_eq!(
+            run_pre_create(
+                &instance_name,
+                Some(&hooks("true")),
+                &AllowedHooks::default()
+            )
+            .await,
+            Err("preCreate hook 'true' is not allowed by the agent configuration".to_string())
+        );
+    }
+}
@@ -22,3 +22,28 @@ pub enum WorkloadOperation {
     UpdateDeleteOnly(DeletedWorkload),
     Delete(DeletedWorkload),
 }
+
+impl WorkloadOperation {
+    // Deletes carry no workload spec and have the lowest priority.
+    // [impl->swdd~agent-releases-ready-workload-operations-by-priority~1]
+    pub fn priority(&self) -> u8 {
+        match self {
+            WorkloadOperation::Create(workload_spec)
+            | WorkloadOperation::Update(workload_spec, _) => workload_spec.priority,
+            WorkloadOperation::UpdateDeleteOnly(_) | WorkloadOperation::Delete(_) => 0,
+        }
+    }
+
+    pub fn workload_name(&self) -> &str {
+        match self {
+            WorkloadOperation::Create(workload_spec)
+            | WorkloadOperation::Update(workload_spec, _) => {
+                workload_spec.instance_name.workload_name()
+            }
+            WorkloadOperation::UpdateDeleteOnly(deleted_workload)
+            | WorkloadOperation::Delete(deleted_workload) => {
+                deleted_workload.instance_name.workload_name()
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashSet};
+
+// The pending workloads mapped to the pending workloads they wait on.
+pub type PendingDependencies = BTreeMap<String, Vec<String>>;
+
+/// Returns the cycles of pending workloads waiting on each other.
+///
+/// Every cycle is returned as path of workload names starting and ending with the same
+/// workload, e.g. `["A", "B", "A"]`. The workloads are visited in sorted order, thus the
+/// same dependencies always result in the same cycles.
+///
+/// # Arguments
+///
+/// * `dependencies` - The pending workloads mapped to the pending workloads they wait on
+///
+// [impl->swdd~agent-detects-dependency-cycles-of-pending-workloads~1]
+pub fn find_dependency_cycles(dependencies: &PendingDependencies) -> Vec<Vec<String>> {
+    let mut visited = HashSet::new();
+    let mut cycles = Vec::new();
+    for workload_name in dependencies.keys() {
+        visit(
+            workload_name,
+            dependencies,
+            &mut Vec::new(),
+            &mut visited,
+            &mut cycles,
+        );
+    }
+    cycles
+}
+
+fn visit<'a>(
+    workload_name: &'a str,
+    dependencies: &'a PendingDependencies,
+    path: &mut Vec<&'a str>,
+    visited: &mut HashSet<&'a str>,
+    cycles: &mut Vec<Vec<String>>,
+) {
+    if let Some(cycle_start) = path.iter().position(|name| *name == workload_name) {
+        cycles.push(
+            path[cycle_start..]
+                .iter()
+                .chain([&workload_name])
+                .map(|name| name.to_string())
+                .collect(),
+        );
+        return;
+    }
+    if !visited.insert(workload_name) {
+        return;
+    }
+
+    path.push(workload_name);
+    for dependency_name in dependencies.get(workload_name).into_iter().flatten() {
+        visit(dependency_name, dependencies, path, visited, cycles);
+    }
+    path.pop();
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{find_dependency_cycles, PendingDependencies};
+
+    fn dependencies(edges: &[(&str, &[&str])]) -> PendingDependencies {
+        edges
+            .iter()
+            .map(|(workload_name, dependency_names)| {
+                (
+                    workload_name.to_string(),
+                    dependency_names
+                        .iter()
+                        .map(|name| name.to_string())
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    // [utest->swdd~agent-detects-dependency-cycles-of-pending-workloads~1]
+    #[test]
+    fn utest_find_dependency_cycles_returns_cycle_paths() {
+        let dependencies = dependencies(&[
+            ("A", &["B"]),
+            ("B", &["C"]),
+            ("C", &["A"]),
+            ("D", &["A"]),
+            ("E", &["E"]),
+        ]);
+
+        assert_eq!(
+            find_dependency_cycles(&dependencies),
+            vec![vec!["A", "B", "C", "A"], vec!["E", "E"]]
+        );
+    }
+
+    // [utest->swdd~agent-detects-dependency-cycles-of-pending-workloads~1]
+    #[test]
+    fn utest_find_dependency_cycles_ignores_acyclic_dependencies() {
+        let dependencies = dependencies(&[
+            ("A", &["B", "C"]),
+            ("B", &["C"]),
+            ("C", &[]),
+            ("D", &["unknown"]),
+        ]);
+
+        assert!(find_dependency_cycles(&dependencies).is_empty());
+    }
+}
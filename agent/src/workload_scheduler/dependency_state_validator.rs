@@ -12,7 +12,12 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use common::objects::{DeletedWorkload, FulfilledBy, WorkloadSpec};
+use common::commands::UnfulfilledDependency;
+use common::objects::{
+    AddCondition, DeleteCondition, DeletedWorkload, FulfilledBy, UnknownStatePolicy, WorkloadSpec,
+};
+
+use tokio::time::Instant;
 
 #[cfg_attr(test, mockall_double::double)]
 use crate::workload_state::workload_state_store::WorkloadStateStore;
@@ -22,6 +27,167 @@ use mockall::automock;
 
 pub struct DependencyStateValidator {}
 
+const AGENT_CONNECTED_CONDITION: &str = "AGENT_CONNECTED";
+
+fn add_condition_fulfilled(
+    workload: &WorkloadSpec,
+    dependency_name: &str,
+    add_condition: &AddCondition,
+    workload_state_db: &WorkloadStateStore,
+) -> bool {
+    let state_to_evaluate = match workload_state_db.get_state_of_workload(dependency_name) {
+        // [impl->swdd~agent-evaluates-unknown-dependency-states-by-policy~1]
+        Some(wl_state) if wl_state.is_unknown() => {
+            match workload.get_unknown_state_policy(dependency_name) {
+                UnknownStatePolicy::UnknownStateUnfulfilled => None,
+                UnknownStatePolicy::UnknownStateLastKnown => {
+                    workload_state_db.get_last_known_state_of_workload(dependency_name)
+                }
+            }
+        }
+        // [impl->swdd~agent-evaluates-not-running-add-condition~1]
+        // a workload without an execution state is not deployed and hence not running
+        None if *add_condition == AddCondition::AddCondNotRunning => return true,
+        wl_state => wl_state,
+    };
+
+    state_to_evaluate.map_or(false, |wl_state| {
+        // [impl->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~1]
+        add_condition.fulfilled_by(wl_state)
+    }) && (*add_condition != AddCondition::AddCondRunningFor
+        || running_long_enough(workload, dependency_name, workload_state_db))
+}
+
+// [impl->swdd~agent-evaluates-running-for-add-condition~1]
+fn running_long_enough(
+    workload: &WorkloadSpec,
+    dependency_name: &str,
+    workload_state_db: &WorkloadStateStore,
+) -> bool {
+    workload_state_db
+        .get_running_since_of_workload(dependency_name)
+        .is_some_and(|running_since| {
+            running_since + workload.get_running_for(dependency_name) <= Instant::now()
+        })
+}
+
+// [impl->swdd~agent-evaluates-dependency-expression~1]
+fn dependency_expression_fulfilled(
+    workload: &WorkloadSpec,
+    workload_state_db: &WorkloadStateStore,
+) -> bool {
+    workload
+        .dependency_expression
+        .as_ref()
+        .map_or(true, |expression| {
+            expression.evaluate(&|dependency_name: &str, add_condition: &AddCondition| {
+                add_condition_fulfilled(workload, dependency_name, add_condition, workload_state_db)
+            })
+        })
+}
+
+// [impl->swdd~agent-evaluates-agent-dependencies~1]
+fn disconnected_agent_dependencies<'a>(
+    workload: &'a WorkloadSpec,
+    workload_state_db: &WorkloadStateStore,
+) -> Vec<&'a String> {
+    workload
+        .agent_dependencies
+        .iter()
+        .filter(|agent_name| !workload_state_db.is_agent_connected(agent_name))
+        .collect()
+}
+
+fn delete_condition_fulfilled(
+    dependency_name: &str,
+    delete_condition: &DeleteCondition,
+    workload_state_db: &WorkloadStateStore,
+) -> bool {
+    workload_state_db
+        .get_state_of_workload(dependency_name)
+        .is_none_or(|wl_state| {
+            // [impl->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~1]
+            delete_condition.fulfilled_by(wl_state)
+        })
+}
+
+fn describe_dependency(
+    dependency_name: &str,
+    condition: &impl ToString,
+    workload_state_db: &WorkloadStateStore,
+) -> String {
+    let actual_state = workload_state_db
+        .get_state_of_workload(dependency_name)
+        .map_or_else(|| "none".to_owned(), |wl_state| wl_state.state.to_string());
+    format!(
+        "{dependency_name}: expected {}, actual {actual_state}",
+        condition.to_string()
+    )
+}
+
+// [impl->swdd~agent-reports-unfulfilled-dependencies-of-pending-workloads~1]
+// Describes the unfulfilled add conditions of the workload with the actual execution states of
+// the dependencies, e.g. 'backend: expected ADD_COND_RUNNING, actual Pending(Starting)'.
+pub fn describe_unfulfilled_create_dependencies(
+    workload: &WorkloadSpec,
+    workload_state_db: &WorkloadStateStore,
+) -> String {
+    let mut descriptions: Vec<String> = workload
+        .dependencies
+        .iter()
+        .filter(|(dependency_name, add_condition)| {
+            !add_condition_fulfilled(workload, dependency_name, add_condition, workload_state_db)
+        })
+        .map(|(dependency_name, add_condition)| {
+            describe_dependency(dependency_name, add_condition, workload_state_db)
+        })
+        .chain(
+            disconnected_agent_dependencies(workload, workload_state_db)
+                .into_iter()
+                .map(|agent_name| {
+                    format!(
+                        "{agent_name}: expected {AGENT_CONNECTED_CONDITION}, actual disconnected"
+                    )
+                }),
+        )
+        .collect();
+    descriptions.sort();
+    if let Some(expression) = workload
+        .dependency_expression
+        .as_ref()
+        .filter(|_| !dependency_expression_fulfilled(workload, workload_state_db))
+    {
+        descriptions.push(format!("dependency expression: expected {expression}"));
+    }
+    descriptions.join("; ")
+}
+
+// [impl->swdd~agent-reports-unfulfilled-dependencies-of-pending-workloads~1]
+pub fn describe_unfulfilled_delete_dependencies(
+    workload: &DeletedWorkload,
+    workload_state_db: &WorkloadStateStore,
+) -> String {
+    let mut descriptions: Vec<String> = workload
+        .dependencies
+        .iter()
+        .filter(|(dependency_name, delete_condition)| {
+            !delete_condition_fulfilled(dependency_name, delete_condition, workload_state_db)
+        })
+        .map(|(dependency_name, delete_condition)| {
+            describe_dependency(dependency_name, delete_condition, workload_state_db)
+        })
+        .collect();
+    descriptions.sort();
+    descriptions.join("; ")
+}
+
+fn sorted_by_workload_name(
+    mut unfulfilled_dependencies: Vec<UnfulfilledDependency>,
+) -> Vec<UnfulfilledDependency> {
+    unfulfilled_dependencies.sort_by(|a, b| a.workload_name.cmp(&b.workload_name));
+    unfulfilled_dependencies
+}
+
 #[cfg_attr(test, automock)]
 impl DependencyStateValidator {
     pub fn create_fulfilled(
@@ -33,13 +199,11 @@ impl DependencyStateValidator {
             .iter()
             // [impl->swdd~workload-ready-to-create-on-fulfilled-dependencies~1]
             .all(|(dependency_name, add_condition)| {
-                workload_state_db
-                    .get_state_of_workload(dependency_name)
-                    .map_or(false, |wl_state| {
-                        // [impl->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~1]
-                        add_condition.fulfilled_by(wl_state)
-                    })
+                add_condition_fulfilled(workload, dependency_name, add_condition, workload_state_db)
             })
+            && dependency_expression_fulfilled(workload, workload_state_db)
+            // [impl->swdd~agent-evaluates-agent-dependencies~1]
+            && disconnected_agent_dependencies(workload, workload_state_db).is_empty()
     }
 
     pub fn delete_fulfilled(
@@ -51,13 +215,84 @@ impl DependencyStateValidator {
             .iter()
             // [impl->swdd~workload-ready-to-delete-on-fulfilled-dependencies~1]
             .all(|(dependency_name, delete_condition)| {
-                workload_state_db
-                    .get_state_of_workload(dependency_name)
-                    .map_or(true, |wl_state| {
-                        // [impl->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~1]
-                        delete_condition.fulfilled_by(wl_state)
-                    })
+                delete_condition_fulfilled(dependency_name, delete_condition, workload_state_db)
+            })
+    }
+
+    // [impl->swdd~agent-reports-scheduler-queue-to-server~1]
+    pub fn unfulfilled_create_dependencies(
+        workload: &WorkloadSpec,
+        workload_state_db: &WorkloadStateStore,
+    ) -> Vec<UnfulfilledDependency> {
+        let mut unfulfilled_dependencies: Vec<UnfulfilledDependency> = workload
+            .dependencies
+            .iter()
+            .filter(|(dependency_name, add_condition)| {
+                !add_condition_fulfilled(
+                    workload,
+                    dependency_name,
+                    add_condition,
+                    workload_state_db,
+                )
+            })
+            .map(|(dependency_name, add_condition)| UnfulfilledDependency {
+                workload_name: dependency_name.clone(),
+                condition: add_condition.to_string(),
             })
+            // [impl->swdd~agent-evaluates-agent-dependencies~1]
+            .chain(
+                disconnected_agent_dependencies(workload, workload_state_db)
+                    .into_iter()
+                    .map(|agent_name| UnfulfilledDependency {
+                        workload_name: agent_name.clone(),
+                        condition: AGENT_CONNECTED_CONDITION.to_owned(),
+                    }),
+            )
+            .collect();
+        // [impl->swdd~agent-evaluates-dependency-expression~1]
+        // the expression is reported as a whole as it is not fulfilled by a single workload
+        if let Some(expression) = workload
+            .dependency_expression
+            .as_ref()
+            .filter(|_| !dependency_expression_fulfilled(workload, workload_state_db))
+        {
+            unfulfilled_dependencies.push(UnfulfilledDependency {
+                workload_name: expression
+                    .workload_names()
+                    .into_iter()
+                    .map(String::as_str)
+                    .collect::<Vec<&str>>()
+                    .join(", "),
+                condition: expression.to_string(),
+            });
+        }
+        sorted_by_workload_name(unfulfilled_dependencies)
+    }
+
+    // [impl->swdd~agent-reports-scheduler-queue-to-server~1]
+    pub fn unfulfilled_delete_dependencies(
+        workload: &DeletedWorkload,
+        workload_state_db: &WorkloadStateStore,
+    ) -> Vec<UnfulfilledDependency> {
+        sorted_by_workload_name(
+            workload
+                .dependencies
+                .iter()
+                .filter(|(dependency_name, delete_condition)| {
+                    !delete_condition_fulfilled(
+                        dependency_name,
+                        delete_condition,
+                        workload_state_db,
+                    )
+                })
+                .map(
+                    |(dependency_name, delete_condition)| UnfulfilledDependency {
+                        workload_name: dependency_name.clone(),
+                        condition: delete_condition.to_string(),
+                    },
+                )
+                .collect(),
+        )
     }
 }
 
@@ -71,23 +306,32 @@ impl DependencyStateValidator {
 
 #[cfg(test)]
 mod tests {
-    use super::DependencyStateValidator;
+    use super::{
+        describe_unfulfilled_create_dependencies, describe_unfulfilled_delete_dependencies,
+        DependencyStateValidator,
+    };
     use common::{
+        commands::UnfulfilledDependency,
         objects::{
             generate_test_workload_spec_with_dependencies, generate_test_workload_spec_with_param,
-            AddCondition, DeleteCondition, ExecutionState,
+            AddCondition, DeleteCondition, DependencyExpression, ExecutionState,
+            UnknownStatePolicy,
         },
         test_utils::{
             generate_test_deleted_workload, generate_test_deleted_workload_with_dependencies,
         },
     };
-    use std::collections::HashMap;
+    use std::{collections::HashMap, time::Duration};
+    use tokio::time::Instant;
 
     use crate::workload_state::workload_state_store::MockWorkloadStateStore;
 
     const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
     const WORKLOAD_NAME_1: &str = "workload_1";
     const WORKLOAD_NAME_2: &str = "workload_2";
+    const WORKLOAD_NAME_3: &str = "workload_3";
+    const WORKLOAD_NAME_4: &str = "workload_4";
     const RUNTIME: &str = "runtime";
 
     // [utest->swdd~workload-ready-to-create-on-fulfilled-dependencies~1]
@@ -173,6 +417,327 @@ mod tests {
         ));
     }
 
+    // [utest->swdd~agent-evaluates-unknown-dependency-states-by-policy~1]
+    #[test]
+    fn utest_create_fulfilled_unknown_state_unfulfilled_by_default() {
+        let workload_with_dependencies = generate_test_workload_spec_with_dependencies(
+            AGENT_A,
+            WORKLOAD_NAME_1,
+            RUNTIME,
+            HashMap::from([(WORKLOAD_NAME_2.to_string(), AddCondition::AddCondRunning)]),
+        );
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_2.to_owned(), ExecutionState::stale());
+        wl_state_store_mock
+            .last_known_states
+            .insert(WORKLOAD_NAME_2.to_owned(), ExecutionState::running());
+
+        assert!(!DependencyStateValidator::create_fulfilled(
+            &workload_with_dependencies,
+            &wl_state_store_mock
+        ));
+    }
+
+    // [utest->swdd~agent-evaluates-unknown-dependency-states-by-policy~1]
+    #[test]
+    fn utest_create_fulfilled_unknown_state_uses_last_known_state() {
+        let mut workload_with_dependencies = generate_test_workload_spec_with_dependencies(
+            AGENT_A,
+            WORKLOAD_NAME_1,
+            RUNTIME,
+            HashMap::from([(WORKLOAD_NAME_2.to_string(), AddCondition::AddCondRunning)]),
+        );
+        workload_with_dependencies.unknown_state_policies = HashMap::from([(
+            WORKLOAD_NAME_2.to_string(),
+            UnknownStatePolicy::UnknownStateLastKnown,
+        )]);
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        wl_state_store_mock.states_storage.insert(
+            WORKLOAD_NAME_2.to_owned(),
+            ExecutionState::agent_disconnected(),
+        );
+        wl_state_store_mock
+            .last_known_states
+            .insert(WORKLOAD_NAME_2.to_owned(), ExecutionState::running());
+
+        assert!(DependencyStateValidator::create_fulfilled(
+            &workload_with_dependencies,
+            &wl_state_store_mock
+        ));
+    }
+
+    // [utest->swdd~agent-evaluates-unknown-dependency-states-by-policy~1]
+    #[test]
+    fn utest_create_fulfilled_unknown_state_no_last_known_state() {
+        let mut workload_with_dependencies = generate_test_workload_spec_with_dependencies(
+            AGENT_A,
+            WORKLOAD_NAME_1,
+            RUNTIME,
+            HashMap::from([(WORKLOAD_NAME_2.to_string(), AddCondition::AddCondRunning)]),
+        );
+        workload_with_dependencies.unknown_state_policies = HashMap::from([(
+            WORKLOAD_NAME_2.to_string(),
+            UnknownStatePolicy::UnknownStateLastKnown,
+        )]);
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_2.to_owned(), ExecutionState::stale());
+
+        assert!(!DependencyStateValidator::create_fulfilled(
+            &workload_with_dependencies,
+            &wl_state_store_mock
+        ));
+    }
+
+    // [utest->swdd~agent-evaluates-running-for-add-condition~1]
+    #[test]
+    fn utest_create_fulfilled_running_for() {
+        let mut workload_with_dependencies = generate_test_workload_spec_with_dependencies(
+            AGENT_A,
+            WORKLOAD_NAME_1,
+            RUNTIME,
+            HashMap::from([(WORKLOAD_NAME_2.to_string(), AddCondition::AddCondRunningFor)]),
+        );
+        workload_with_dependencies.running_for_ms =
+            HashMap::from([(WORKLOAD_NAME_2.to_string(), 10000)]);
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_2.to_owned(), ExecutionState::running());
+        wl_state_store_mock.running_since.insert(
+            WORKLOAD_NAME_2.to_owned(),
+            Instant::now() - Duration::from_secs(5),
+        );
+
+        assert!(!DependencyStateValidator::create_fulfilled(
+            &workload_with_dependencies,
+            &wl_state_store_mock
+        ));
+
+        wl_state_store_mock.running_since.insert(
+            WORKLOAD_NAME_2.to_owned(),
+            Instant::now() - Duration::from_secs(10),
+        );
+
+        assert!(DependencyStateValidator::create_fulfilled(
+            &workload_with_dependencies,
+            &wl_state_store_mock
+        ));
+    }
+
+    // [utest->swdd~agent-evaluates-running-for-add-condition~1]
+    #[test]
+    fn utest_create_fulfilled_running_for_without_running_since() {
+        let workload_with_dependencies = generate_test_workload_spec_with_dependencies(
+            AGENT_A,
+            WORKLOAD_NAME_1,
+            RUNTIME,
+            HashMap::from([(WORKLOAD_NAME_2.to_string(), AddCondition::AddCondRunningFor)]),
+        );
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_2.to_owned(), ExecutionState::running());
+
+        assert!(!DependencyStateValidator::create_fulfilled(
+            &workload_with_dependencies,
+            &wl_state_store_mock
+        ));
+    }
+
+    // [utest->swdd~agent-evaluates-not-running-add-condition~1]
+    #[test]
+    fn utest_create_fulfilled_not_running() {
+        let workload_with_dependencies = generate_test_workload_spec_with_dependencies(
+            AGENT_A,
+            WORKLOAD_NAME_1,
+            RUNTIME,
+            HashMap::from([(WORKLOAD_NAME_2.to_string(), AddCondition::AddCondNotRunning)]),
+        );
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        assert!(DependencyStateValidator::create_fulfilled(
+            &workload_with_dependencies,
+            &wl_state_store_mock
+        ));
+
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_2.to_owned(), ExecutionState::running());
+        assert!(!DependencyStateValidator::create_fulfilled(
+            &workload_with_dependencies,
+            &wl_state_store_mock
+        ));
+
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_2.to_owned(), ExecutionState::stale());
+        assert!(!DependencyStateValidator::create_fulfilled(
+            &workload_with_dependencies,
+            &wl_state_store_mock
+        ));
+    }
+
+    // (workload_2 running AND workload_3 succeeded) OR NOT workload_4 running
+    fn generate_test_dependency_expression() -> DependencyExpression {
+        let condition = |workload: &str, condition| DependencyExpression::Condition {
+            workload: workload.to_string(),
+            condition,
+        };
+        DependencyExpression::Or {
+            or: vec![
+                DependencyExpression::And {
+                    and: vec![
+                        condition(WORKLOAD_NAME_2, AddCondition::AddCondRunning),
+                        condition(WORKLOAD_NAME_3, AddCondition::AddCondSucceeded),
+                    ],
+                },
+                DependencyExpression::Not {
+                    not: Box::new(condition(WORKLOAD_NAME_4, AddCondition::AddCondRunning)),
+                },
+            ],
+        }
+    }
+
+    // [utest->swdd~agent-evaluates-dependency-expression~1]
+    #[test]
+    fn utest_create_fulfilled_dependency_expression() {
+        let mut workload_spec = generate_test_workload_spec_with_dependencies(
+            AGENT_A,
+            WORKLOAD_NAME_1,
+            RUNTIME,
+            HashMap::new(),
+        );
+        workload_spec.dependency_expression = Some(generate_test_dependency_expression());
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_4.to_owned(), ExecutionState::running());
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_2.to_owned(), ExecutionState::running());
+        assert!(!DependencyStateValidator::create_fulfilled(
+            &workload_spec,
+            &wl_state_store_mock
+        ));
+
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_3.to_owned(), ExecutionState::succeeded());
+        assert!(DependencyStateValidator::create_fulfilled(
+            &workload_spec,
+            &wl_state_store_mock
+        ));
+
+        // the dependencies must be fulfilled in addition to the expression
+        workload_spec.dependencies =
+            HashMap::from([(WORKLOAD_NAME_4.to_string(), AddCondition::AddCondSucceeded)]);
+        assert!(!DependencyStateValidator::create_fulfilled(
+            &workload_spec,
+            &wl_state_store_mock
+        ));
+    }
+
+    // [utest->swdd~agent-evaluates-dependency-expression~1]
+    #[test]
+    fn utest_unfulfilled_create_dependencies_reports_dependency_expression() {
+        let mut workload_spec = generate_test_workload_spec_with_dependencies(
+            AGENT_A,
+            WORKLOAD_NAME_1,
+            RUNTIME,
+            HashMap::from([(WORKLOAD_NAME_2.to_string(), AddCondition::AddCondSucceeded)]),
+        );
+        workload_spec.dependency_expression = Some(generate_test_dependency_expression());
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_4.to_owned(), ExecutionState::running());
+
+        assert_eq!(
+            DependencyStateValidator::unfulfilled_create_dependencies(
+                &workload_spec,
+                &wl_state_store_mock
+            ),
+            vec![
+                UnfulfilledDependency {
+                    workload_name: WORKLOAD_NAME_2.to_string(),
+                    condition: "ADD_COND_SUCCEEDED".to_string(),
+                },
+                UnfulfilledDependency {
+                    workload_name: format!(
+                        "{WORKLOAD_NAME_2}, {WORKLOAD_NAME_3}, {WORKLOAD_NAME_4}"
+                    ),
+                    condition: generate_test_dependency_expression().to_string(),
+                },
+            ]
+        );
+    }
+
+    // [utest->swdd~agent-evaluates-agent-dependencies~1]
+    #[test]
+    fn utest_create_fulfilled_agent_dependencies() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_A.to_string(),
+            WORKLOAD_NAME_1.to_string(),
+            RUNTIME.to_string(),
+        );
+        workload_spec.dependencies.clear();
+        workload_spec.agent_dependencies = vec![AGENT_B.to_string()];
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        assert!(!DependencyStateValidator::create_fulfilled(
+            &workload_spec,
+            &wl_state_store_mock
+        ));
+
+        wl_state_store_mock
+            .connected_agents
+            .insert(AGENT_B.to_owned());
+        assert!(DependencyStateValidator::create_fulfilled(
+            &workload_spec,
+            &wl_state_store_mock
+        ));
+    }
+
+    // [utest->swdd~agent-evaluates-agent-dependencies~1]
+    #[test]
+    fn utest_unfulfilled_create_dependencies_reports_disconnected_agents() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_A.to_string(),
+            WORKLOAD_NAME_1.to_string(),
+            RUNTIME.to_string(),
+        );
+        workload_spec.dependencies.clear();
+        workload_spec.agent_dependencies = vec![AGENT_B.to_string()];
+
+        let wl_state_store_mock = MockWorkloadStateStore::default();
+
+        assert_eq!(
+            DependencyStateValidator::unfulfilled_create_dependencies(
+                &workload_spec,
+                &wl_state_store_mock
+            ),
+            vec![UnfulfilledDependency {
+                workload_name: AGENT_B.to_string(),
+                condition: "AGENT_CONNECTED".to_string(),
+            }]
+        );
+        assert_eq!(
+            describe_unfulfilled_create_dependencies(&workload_spec, &wl_state_store_mock),
+            "agent_B: expected AGENT_CONNECTED, actual disconnected"
+        );
+    }
+
     // [utest->swdd~workload-ready-to-delete-on-fulfilled-dependencies~1]
     // [utest->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~1]
     #[test]
@@ -258,4 +823,155 @@ mod tests {
             &wl_state_store_mock
         ));
     }
+
+    // [utest->swdd~agent-reports-scheduler-queue-to-server~1]
+    #[test]
+    fn utest_unfulfilled_create_dependencies() {
+        let workload_with_dependencies = generate_test_workload_spec_with_dependencies(
+            AGENT_A,
+            WORKLOAD_NAME_1,
+            RUNTIME,
+            HashMap::from([
+                (WORKLOAD_NAME_3.to_string(), AddCondition::AddCondSucceeded),
+                (WORKLOAD_NAME_2.to_string(), AddCondition::AddCondRunning),
+                (WORKLOAD_NAME_4.to_string(), AddCondition::AddCondRunning),
+            ]),
+        );
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_4.to_owned(), ExecutionState::running());
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_3.to_owned(), ExecutionState::running());
+
+        assert_eq!(
+            DependencyStateValidator::unfulfilled_create_dependencies(
+                &workload_with_dependencies,
+                &wl_state_store_mock
+            ),
+            vec![
+                UnfulfilledDependency {
+                    workload_name: WORKLOAD_NAME_2.to_string(),
+                    condition: "ADD_COND_RUNNING".to_string(),
+                },
+                UnfulfilledDependency {
+                    workload_name: WORKLOAD_NAME_3.to_string(),
+                    condition: "ADD_COND_SUCCEEDED".to_string(),
+                },
+            ]
+        );
+    }
+
+    // [utest->swdd~agent-reports-scheduler-queue-to-server~1]
+    #[test]
+    fn utest_unfulfilled_delete_dependencies() {
+        let deleted_workload_with_dependencies = generate_test_deleted_workload_with_dependencies(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            HashMap::from([
+                (
+                    WORKLOAD_NAME_2.to_owned(),
+                    DeleteCondition::DelCondNotPendingNorRunning,
+                ),
+                (
+                    WORKLOAD_NAME_3.to_owned(),
+                    DeleteCondition::DelCondNotPendingNorRunning,
+                ),
+            ]),
+        );
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_2.to_owned(), ExecutionState::running());
+
+        assert_eq!(
+            DependencyStateValidator::unfulfilled_delete_dependencies(
+                &deleted_workload_with_dependencies,
+                &wl_state_store_mock
+            ),
+            vec![UnfulfilledDependency {
+                workload_name: WORKLOAD_NAME_2.to_string(),
+                condition: "DEL_COND_NOT_PENDING_NOR_RUNNING".to_string(),
+            }]
+        );
+    }
+
+    // [utest->swdd~agent-reports-unfulfilled-dependencies-of-pending-workloads~1]
+    #[test]
+    fn utest_describe_unfulfilled_create_dependencies() {
+        let mut workload_spec = generate_test_workload_spec_with_dependencies(
+            AGENT_A,
+            WORKLOAD_NAME_1,
+            RUNTIME,
+            HashMap::from([
+                (WORKLOAD_NAME_3.to_string(), AddCondition::AddCondSucceeded),
+                (WORKLOAD_NAME_2.to_string(), AddCondition::AddCondRunning),
+                (WORKLOAD_NAME_4.to_string(), AddCondition::AddCondRunning),
+            ]),
+        );
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_4.to_owned(), ExecutionState::running());
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_3.to_owned(), ExecutionState::running());
+
+        assert_eq!(
+            describe_unfulfilled_create_dependencies(&workload_spec, &wl_state_store_mock),
+            "workload_2: expected ADD_COND_RUNNING, actual none; workload_3: expected ADD_COND_SUCCEEDED, actual Running(Ok)"
+        );
+
+        workload_spec.dependencies.clear();
+        workload_spec.dependency_expression = Some(generate_test_dependency_expression());
+        assert_eq!(
+            describe_unfulfilled_create_dependencies(&workload_spec, &wl_state_store_mock),
+            format!(
+                "dependency expression: expected {}",
+                generate_test_dependency_expression()
+            )
+        );
+
+        wl_state_store_mock.states_storage.remove(WORKLOAD_NAME_4);
+        assert!(
+            describe_unfulfilled_create_dependencies(&workload_spec, &wl_state_store_mock)
+                .is_empty()
+        );
+    }
+
+    // [utest->swdd~agent-reports-unfulfilled-dependencies-of-pending-workloads~1]
+    #[test]
+    fn utest_describe_unfulfilled_delete_dependencies() {
+        let deleted_workload_with_dependencies = generate_test_deleted_workload_with_dependencies(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            HashMap::from([
+                (
+                    WORKLOAD_NAME_2.to_owned(),
+                    DeleteCondition::DelCondNotPendingNorRunning,
+                ),
+                (
+                    WORKLOAD_NAME_3.to_owned(),
+                    DeleteCondition::DelCondNotPendingNorRunning,
+                ),
+            ]),
+        );
+
+        let mut wl_state_store_mock = MockWorkloadStateStore::default();
+        wl_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_NAME_2.to_owned(), ExecutionState::running());
+
+        assert_eq!(
+            describe_unfulfilled_delete_dependencies(
+                &deleted_workload_with_dependencies,
+                &wl_state_store_mock
+            ),
+            "workload_2: expected DEL_COND_NOT_PENDING_NOR_RUNNING, actual Running(Ok)"
+        );
+    }
 }
@@ -0,0 +1,229 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::VecDeque;
+
+use common::objects::WorkloadSpec;
+
+use crate::workload_operation::WorkloadOperation;
+
+pub const OWNER_TAG_KEY: &str = "owner";
+
+// Workloads without an owner tag share the empty owner.
+pub fn owner_of(workload_spec: &WorkloadSpec) -> &str {
+    workload_spec
+        .tags
+        .iter()
+        .find(|tag| tag.key == OWNER_TAG_KEY)
+        .map(|tag| tag.value.as_str())
+        .unwrap_or_default()
+}
+
+/// Reorders the ready workload operations round-robin across their owners.
+///
+/// The owners take turns in the order of their first operation and the operations
+/// of one owner keep their relative order.
+///
+/// # Arguments
+///
+/// * `workload_operations` - The ready workload operations to dispatch
+/// * `owner` - Returns the owner of a workload operation
+///
+// [impl->swdd~agent-dispatches-ready-workload-operations-fair-across-owners~1]
+pub fn interleave_by_owner<F>(
+    workload_operations: Vec<WorkloadOperation>,
+    owner: F,
+) -> Vec<WorkloadOperation>
+where
+    F: Fn(&WorkloadOperation) -> String,
+{
+    let total = workload_operations.len();
+    let mut queues: Vec<(String, VecDeque<WorkloadOperation>)> = Vec::new();
+    for workload_operation in workload_operations {
+        let operation_owner = owner(&workload_operation);
+        match queues.iter_mut().find(|(queue_owner, _)| *queue_owner == operation_owner) {
+            Some((_, queue)) => queue.push_back(workload_operation),
+            None => queues.push((operation_owner, VecDeque::from([workload_operation]))),
+        }
+    }
+
+    let mut interleaved = Vec::with_capacity(total);
+    while interleaved.len() < total {
+        for (_, queue) in queues.iter_mut() {
+            if let Some(workload_operation) = queue.pop_front() {
+                interleaved.push(workload_operation);
+            }
+        }
+    }
+    interleaved
+}
+
+/// Reorders the ready workload operations round-robin across their owners within each priority.
+///
+/// Consecutive operations of the same priority form a group. The groups keep their order and
+/// only the operations of a group are interleaved across their owners.
+///
+/// # Arguments
+///
+/// * `workload_operations` - The ready workload operations, ordered by priority
+/// * `owner` - Returns the owner of a workload operation
+///
+// [impl->swdd~agent-releases-ready-workload-operations-by-priority~1]
+pub fn interleave_by_owner_per_priority<F>(
+    workload_operations: Vec<WorkloadOperation>,
+    owner: F,
+) -> Vec<WorkloadOperation>
+where
+    F: Fn(&WorkloadOperation) -> String,
+{
+    let mut groups: Vec<Vec<WorkloadOperation>> = Vec::new();
+    for workload_operation in workload_operations {
+        match groups.last_mut() {
+            Some(group)
+                if group
+                    .first()
+                    .is_some_and(|first| first.priority() == workload_operation.priority()) =>
+            {
+                group.push(workload_operation)
+            }
+            _ => groups.push(vec![workload_operation]),
+        }
+    }
+    groups
+        .into_iter()
+        .flat_map(|group| interleave_by_owner(group, &owner))
+        .collect()
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::{generate_test_workload_spec_with_param, Tag, WorkloadSpec};
+
+    use super::{interleave_by_owner, interleave_by_owner_per_priority, owner_of};
+    use crate::workload_operation::WorkloadOperation;
+
+    const AGENT_NAME: &str = "agent_x";
+    const RUNTIME: &str = "runtime";
+
+    fn workload_spec(workload_name: &str, owner: Option<&str>) -> WorkloadSpec {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            workload_name.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        workload_spec.tags = owner
+            .map(|owner| Tag {
+                key: "owner".to_owned(),
+                value: owner.to_owned(),
+            })
+            .into_iter()
+            .collect();
+        workload_spec
+    }
+
+    fn workload_names(workload_operations: &[WorkloadOperation]) -> Vec<&str> {
+        workload_operations
+            .iter()
+            .map(|workload_operation| match workload_operation {
+                WorkloadOperation::Create(workload_spec)
+                | WorkloadOperation::Update(workload_spec, _) => {
+                    workload_spec.instance_name.workload_name()
+                }
+                WorkloadOperation::UpdateDeleteOnly(deleted_workload)
+                | WorkloadOperation::Delete(deleted_workload) => {
+                    deleted_workload.instance_name.workload_name()
+                }
+            })
+            .collect()
+    }
+
+    // [utest->swdd~agent-dispatches-ready-workload-operations-fair-across-owners~1]
+    #[test]
+    fn utest_interleave_by_owner_alternates_owners_in_order_of_appearance() {
+        let workload_operations = vec![
+            WorkloadOperation::Create(workload_spec("a_1", Some("team_a"))),
+            WorkloadOperation::Create(workload_spec("a_2", Some("team_a"))),
+            WorkloadOperation::Create(workload_spec("a_3", Some("team_a"))),
+            WorkloadOperation::Create(workload_spec("none_1", None)),
+            WorkloadOperation::Create(workload_spec("a_4", Some("team_a"))),
+            WorkloadOperation::Create(workload_spec("b_1", Some("team_b"))),
+        ];
+
+        let interleaved = interleave_by_owner(workload_operations, |workload_operation| {
+            match workload_operation {
+                WorkloadOperation::Create(workload_spec) => owner_of(workload_spec).to_owned(),
+                _ => unreachable!(),
+            }
+        });
+
+        assert_eq!(
+            workload_names(&interleaved),
+            vec!["a_1", "none_1", "b_1", "a_2", "a_3", "a_4"]
+        );
+    }
+
+    // [utest->swdd~agent-releases-ready-workload-operations-by-priority~1]
+    #[test]
+    fn utest_interleave_by_owner_per_priority_keeps_priority_order() {
+        let workload_spec_with_priority = |workload_name: &str, owner: &str, priority: u8| {
+            let mut workload_spec = workload_spec(workload_name, Some(owner));
+            workload_spec.priority = priority;
+            WorkloadOperation::Create(workload_spec)
+        };
+        let workload_operations = vec![
+            workload_spec_with_priority("a_1", "team_a", 10),
+            workload_spec_with_priority("a_2", "team_a", 10),
+            workload_spec_with_priority("b_1", "team_b", 10),
+            workload_spec_with_priority("b_2", "team_b", 0),
+            workload_spec_with_priority("a_3", "team_a", 0),
+        ];
+
+        let interleaved =
+            interleave_by_owner_per_priority(workload_operations, |workload_operation| {
+                match workload_operation {
+                    WorkloadOperation::Create(workload_spec) => owner_of(workload_spec).to_owned(),
+                    _ => unreachable!(),
+                }
+            });
+
+        assert_eq!(
+            workload_names(&interleaved),
+            vec!["a_1", "b_1", "a_2", "b_2", "a_3"]
+        );
+    }
+
+    // [utest->swdd~agent-dispatches-ready-workload-operations-fair-across-owners~1]
+    #[test]
+    fn utest_owner_of_uses_owner_tag() {
+        let mut owned_workload_spec = workload_spec("workload", Some("team_a"));
+        owned_workload_spec.tags.insert(
+            0,
+            Tag {
+                key: "tier".to_owned(),
+                value: "backend".to_owned(),
+            },
+        );
+
+        assert_eq!(owner_of(&owned_workload_spec), "team_a");
+        assert_eq!(owner_of(&workload_spec("workload", None)), "");
+    }
+}
@@ -12,5 +12,12 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+mod dependency_cycle;
 mod dependency_state_validator;
+pub mod fair_dispatch;
+pub mod queue_storage;
+pub mod resource_gate;
+pub mod schedule_triggers;
 pub mod scheduler;
+pub mod startup_watch;
+pub mod topological_order;
@@ -0,0 +1,163 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fs, io, path::PathBuf};
+
+use common::persistence::{self, PersistenceFormat};
+use serde::{de::DeserializeOwned, Serialize};
+
+pub const QUEUE_FILE_NAME: &str = "pending_operations.json";
+const TEMPORARY_FILE_SUFFIX: &str = ".tmp";
+
+// Keeps the pending workload operations of the WorkloadScheduler and the last known workload
+// states of the WorkloadStateStore in files inside the run folder.
+// The file is first written to a temporary file and then renamed, such that an agent crash never
+// leaves a partially written queue behind.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueStorage {
+    path: PathBuf,
+    format: PersistenceFormat,
+}
+
+impl QueueStorage {
+    pub fn new(path: PathBuf, format: PersistenceFormat) -> Self {
+        Self { path, format }
+    }
+
+    // [impl->swdd~agent-persists-pending-workload-operations~1]
+    // [impl->swdd~agent-persists-in-configured-format~1]
+    pub fn store<T: Serialize>(&self, queue: &T) -> Result<(), String> {
+        let content = persistence::encode(self.format, queue)
+            .map_err(|err| format!("Could not serialize '{}': '{}'", self.path.display(), err))?;
+
+        let mut temporary_path = self.path.clone().into_os_string();
+        temporary_path.push(TEMPORARY_FILE_SUFFIX);
+        fs::write(&temporary_path, content)
+            .and_then(|_| fs::rename(&temporary_path, &self.path))
+            .map_err(|err| format!("Could not store '{}': '{}'", self.path.display(), err))
+    }
+
+    // [impl->swdd~agent-restores-pending-workload-operations~1]
+    // [impl->swdd~agent-migrates-persisted-content-on-load~1]
+    pub fn load<T: DeserializeOwned + Serialize + Default>(&self) -> Result<T, String> {
+        let content = match fs::read(&self.path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(T::default()),
+            Err(err) => {
+                return Err(format!(
+                    "Could not read '{}': '{}'",
+                    self.path.display(),
+                    err
+                ))
+            }
+        };
+
+        let (queue, header) = persistence::decode(&content)
+            .map_err(|err| format!("Could not parse '{}': '{}'", self.path.display(), err))?;
+
+        if header.needs_migration_to(self.format) {
+            log::info!(
+                "Migrating '{}' from version '{}' in format '{}' to the current version in format '{}'.",
+                self.path.display(),
+                header.version,
+                header.format,
+                self.format
+            );
+            self.store(&queue)?;
+        }
+        Ok(queue)
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use common::persistence::PersistenceFormat;
+
+    use super::{QueueStorage, QUEUE_FILE_NAME};
+
+    // [utest->swdd~agent-persists-pending-workload-operations~1]
+    // [utest->swdd~agent-restores-pending-workload-operations~1]
+    #[test]
+    fn utest_queue_storage_stores_and_loads_queue() {
+        let run_folder = tempfile::tempdir().unwrap();
+        let storage = QueueStorage::new(
+            run_folder.path().join(QUEUE_FILE_NAME),
+            PersistenceFormat::Json,
+        );
+
+        let queue = HashMap::from([("workload_1".to_string(), vec![1, 2, 3])]);
+        assert_eq!(storage.store(&queue), Ok(()));
+
+        assert_eq!(storage.load::<HashMap<String, Vec<u32>>>(), Ok(queue));
+        assert_eq!(
+            std::fs::read_dir(run_folder.path()).unwrap().count(),
+            1,
+            "the temporary file shall be renamed"
+        );
+    }
+
+    // [utest->swdd~agent-restores-pending-workload-operations~1]
+    #[test]
+    fn utest_queue_storage_loads_empty_queue_if_file_does_not_exist() {
+        let run_folder = tempfile::tempdir().unwrap();
+        let storage = QueueStorage::new(
+            run_folder.path().join(QUEUE_FILE_NAME),
+            PersistenceFormat::Json,
+        );
+
+        assert_eq!(
+            storage.load::<HashMap<String, Vec<u32>>>(),
+            Ok(HashMap::new())
+        );
+    }
+
+    // [utest->swdd~agent-restores-pending-workload-operations~1]
+    #[test]
+    fn utest_queue_storage_fails_to_load_invalid_file() {
+        let run_folder = tempfile::tempdir().unwrap();
+        let path = run_folder.path().join(QUEUE_FILE_NAME);
+        std::fs::write(&path, "not a queue").unwrap();
+
+        assert!(QueueStorage::new(path, PersistenceFormat::Json)
+            .load::<HashMap<String, Vec<u32>>>()
+            .is_err());
+    }
+
+    // [utest->swdd~agent-persists-in-configured-format~1]
+    // [utest->swdd~agent-migrates-persisted-content-on-load~1]
+    #[test]
+    fn utest_queue_storage_migrates_legacy_json_to_configured_format() {
+        let run_folder = tempfile::tempdir().unwrap();
+        let path = run_folder.path().join(QUEUE_FILE_NAME);
+        std::fs::write(&path, r#"{"workload_1":[1,2,3]}"#).unwrap();
+        let storage = QueueStorage::new(path.clone(), PersistenceFormat::Cbor);
+
+        let queue = HashMap::from([("workload_1".to_string(), vec![1, 2, 3])]);
+        assert_eq!(storage.load::<HashMap<String, Vec<u32>>>(), Ok(queue.clone()));
+
+        let content = std::fs::read(&path).unwrap();
+        assert!(content.starts_with(b"ANKAIOS-PERSISTENCE/1 cbor\n"));
+        assert_eq!(storage.load::<HashMap<String, Vec<u32>>>(), Ok(queue));
+    }
+}
@@ -0,0 +1,341 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{cmp::Reverse, time::Duration};
+
+use common::objects::{WorkloadInstanceName, WorkloadSpec};
+use tokio::time::Instant;
+
+use crate::{resource_monitor::FreeResources, workload_operation::WorkloadOperation};
+
+const RECHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+// The requested cpu in thousandths of a core and memory in bytes, None if nothing is requested.
+// Invalid quantities are rejected by the server and count as no request.
+fn requested_resources(workload_spec: &WorkloadSpec) -> Option<FreeResources> {
+    let requests = &workload_spec.resources.as_ref()?.requests;
+    let cpu_millis = requests.cpu_millis().unwrap_or_default();
+    let memory_bytes = requests.memory_bytes().unwrap_or_default();
+    (cpu_millis != 0 || memory_bytes != 0).then_some(FreeResources {
+        cpu_millis,
+        memory_bytes,
+    })
+}
+
+fn missing_resources(requested: &FreeResources, free: &FreeResources) -> String {
+    let mut missing = Vec::new();
+    if requested.cpu_millis > free.cpu_millis {
+        missing.push(format!(
+            "cpu: requested {}m, free {}m",
+            requested.cpu_millis, free.cpu_millis
+        ));
+    }
+    if requested.memory_bytes > free.memory_bytes {
+        missing.push(format!(
+            "memory: requested {} bytes, free {} bytes",
+            requested.memory_bytes, free.memory_bytes
+        ));
+    }
+    missing.join("; ")
+}
+
+// [impl->swdd~agent-holds-workload-starts-until-free-resources-cover-requests~1]
+// The workloads whose create operation is held until the free resources cover their requests.
+// Updates are not held, as the replaced instance releases its resources.
+#[derive(Default)]
+pub struct ResourceGate {
+    held_workloads: Vec<WorkloadSpec>,
+    next_check: Option<Instant>,
+}
+
+impl ResourceGate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Returns true if the free resources are needed to release the workload operations.
+    pub fn is_needed(&self, workload_operations: &[WorkloadOperation]) -> bool {
+        !self.held_workloads.is_empty()
+            || workload_operations.iter().any(|workload_operation| {
+                matches!(workload_operation, WorkloadOperation::Create(workload_spec)
+                    if requested_resources(workload_spec).is_some())
+            })
+    }
+
+    // Returns the released workload operations, including the released held workloads, and the
+    // newly held workloads with their missing resources. The workloads with a higher priority are
+    // considered first, the previously held workloads before new ones of the same priority. The
+    // requests of the released workloads are deducted from the free resources, as the started
+    // workloads do not use them immediately. Without free resources, all workloads are released.
+    pub fn release(
+        &mut self,
+        workload_operations: Vec<WorkloadOperation>,
+        free_resources: Option<FreeResources>,
+        now: Instant,
+    ) -> (Vec<WorkloadOperation>, Vec<(WorkloadInstanceName, String)>) {
+        let previously_held: Vec<WorkloadInstanceName> = self
+            .held_workloads
+            .iter()
+            .map(|workload_spec| workload_spec.instance_name.clone())
+            .collect();
+        let mut candidates: Vec<WorkloadSpec> = self.held_workloads.drain(..).collect();
+        let mut released_operations = Vec::new();
+        for workload_operation in workload_operations {
+            match workload_operation {
+                WorkloadOperation::Create(workload_spec)
+                    if requested_resources(&workload_spec).is_some() =>
+                {
+                    candidates.push(workload_spec)
+                }
+                workload_operation => released_operations.push(workload_operation),
+            }
+        }
+        self.next_check = None;
+
+        let Some(mut free_resources) = free_resources else {
+            if !candidates.is_empty() {
+                log::warn!("Could not determine the free resources, starting the workloads with resource requests.");
+            }
+            released_operations.extend(candidates.into_iter().map(WorkloadOperation::Create));
+            return (released_operations, Vec::new());
+        };
+
+        candidates.sort_by_key(|workload_spec| Reverse(workload_spec.priority));
+        let mut newly_held = Vec::new();
+        for workload_spec in candidates {
+            let requested = requested_resources(&workload_spec).unwrap_or(FreeResources {
+                cpu_millis: 0,
+                memory_bytes: 0,
+            });
+            if requested.cpu_millis <= free_resources.cpu_millis
+                && requested.memory_bytes <= free_resources.memory_bytes
+            {
+                free_resources.cpu_millis -= requested.cpu_millis;
+                free_resources.memory_bytes -= requested.memory_bytes;
+                released_operations.push(WorkloadOperation::Create(workload_spec));
+                continue;
+            }
+
+            if !previously_held.contains(&workload_spec.instance_name) {
+                log::info!(
+                    "Holding the start of workload '{}' until the free resources cover its requests.",
+                    workload_spec.instance_name.workload_name()
+                );
+                newly_held.push((
+                    workload_spec.instance_name.clone(),
+                    missing_resources(&requested, &free_resources),
+                ));
+            }
+            self.held_workloads.push(workload_spec);
+        }
+
+        if !self.held_workloads.is_empty() {
+            self.next_check = Some(now + RECHECK_INTERVAL);
+        }
+        (released_operations, newly_held)
+    }
+
+    pub fn remove(&mut self, workload_name: &str) -> Option<WorkloadSpec> {
+        let index = self.held_workloads.iter().position(|workload_spec| {
+            workload_spec.instance_name.workload_name() == workload_name
+        })?;
+        let workload_spec = self.held_workloads.remove(index);
+        if self.held_workloads.is_empty() {
+            self.next_check = None;
+        }
+        Some(workload_spec)
+    }
+
+    pub fn held_workloads(&self) -> impl Iterator<Item = &WorkloadSpec> {
+        self.held_workloads.iter()
+    }
+
+    pub fn next_check(&self) -> Option<Instant> {
+        self.next_check
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{ResourceGate, RECHECK_INTERVAL};
+    use crate::{resource_monitor::FreeResources, workload_operation::WorkloadOperation};
+    use common::objects::{
+        generate_test_workload_spec_with_param, DeletedWorkload, ResourceRequests, Resources,
+        WorkloadSpec,
+    };
+    use std::collections::HashMap;
+    use tokio::time::Instant;
+
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+    const WORKLOAD_2_NAME: &str = "workload2";
+    const WORKLOAD_3_NAME: &str = "workload3";
+    const RUNTIME: &str = "runtime";
+    const MEBIBYTE: u64 = 1 << 20;
+
+    fn generate_test_workload_spec_with_requests(
+        workload_name: &str,
+        cpu: Option<&str>,
+        memory: Option<&str>,
+    ) -> WorkloadSpec {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            workload_name.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        workload_spec.resources = Some(Resources {
+            requests: ResourceRequests {
+                cpu: cpu.map(ToOwned::to_owned),
+                memory: memory.map(ToOwned::to_owned),
+            },
+        });
+        workload_spec
+    }
+
+    fn free_resources(cpu_millis: u64, memory_bytes: u64) -> Option<FreeResources> {
+        Some(FreeResources {
+            cpu_millis,
+            memory_bytes,
+        })
+    }
+
+    // [utest->swdd~agent-holds-workload-starts-until-free-resources-cover-requests~1]
+    #[test]
+    fn utest_resource_gate_holds_creates_exceeding_free_resources() {
+        let workload_1 =
+            generate_test_workload_spec_with_requests(WORKLOAD_1_NAME, None, Some("64Mi"));
+        let workload_2 =
+            generate_test_workload_spec_with_requests(WORKLOAD_2_NAME, None, Some("64Mi"));
+        let workload_3 = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_3_NAME.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        let deleted_workload = DeletedWorkload {
+            instance_name: workload_3.instance_name.clone(),
+            dependencies: HashMap::default(),
+        };
+
+        let mut resource_gate = ResourceGate::new();
+        let now = Instant::now();
+        let (released, newly_held) = resource_gate.release(
+            vec![
+                WorkloadOperation::Create(workload_1.clone()),
+                WorkloadOperation::Create(workload_2.clone()),
+                WorkloadOperation::Create(workload_3.clone()),
+                WorkloadOperation::Delete(deleted_workload.clone()),
+            ],
+            free_resources(1000, 100 * MEBIBYTE),
+            now,
+        );
+
+        // the first workload uses the free memory the second one needs
+        assert_eq!(
+            released,
+            vec![
+                WorkloadOperation::Create(workload_3),
+                WorkloadOperation::Delete(deleted_workload),
+                WorkloadOperation::Create(workload_1),
+            ]
+        );
+        assert_eq!(
+            newly_held,
+            vec![(
+                workload_2.instance_name.clone(),
+                format!(
+                    "memory: requested {} bytes, free {} bytes",
+                    64 * MEBIBYTE,
+                    36 * MEBIBYTE
+                )
+            )]
+        );
+        assert_eq!(resource_gate.next_check(), Some(now + RECHECK_INTERVAL));
+
+        // a held workload is reported only once
+        let (released, newly_held) =
+            resource_gate.release(vec![], free_resources(1000, 36 * MEBIBYTE), now);
+        assert!(released.is_empty());
+        assert!(newly_held.is_empty());
+
+        let (released, newly_held) =
+            resource_gate.release(vec![], free_resources(1000, 64 * MEBIBYTE), now);
+        assert_eq!(released, vec![WorkloadOperation::Create(workload_2)]);
+        assert!(newly_held.is_empty());
+        assert_eq!(resource_gate.next_check(), None);
+        assert!(!resource_gate.is_needed(&[]));
+    }
+
+    // [utest->swdd~agent-holds-workload-starts-until-free-resources-cover-requests~1]
+    #[test]
+    fn utest_resource_gate_releases_workload_with_higher_priority_first() {
+        let workload_1 =
+            generate_test_workload_spec_with_requests(WORKLOAD_1_NAME, Some("500m"), None);
+        let mut workload_2 =
+            generate_test_workload_spec_with_requests(WORKLOAD_2_NAME, Some("0.5"), None);
+        workload_2.priority = 10;
+
+        let mut resource_gate = ResourceGate::new();
+        let (released, newly_held) = resource_gate.release(
+            vec![
+                WorkloadOperation::Create(workload_1.clone()),
+                WorkloadOperation::Create(workload_2.clone()),
+            ],
+            free_resources(700, 0),
+            Instant::now(),
+        );
+
+        assert_eq!(released, vec![WorkloadOperation::Create(workload_2)]);
+        assert_eq!(
+            newly_held,
+            vec![(
+                workload_1.instance_name,
+                "cpu: requested 500m, free 200m".to_owned()
+            )]
+        );
+        assert_eq!(
+            resource_gate
+                .remove(WORKLOAD_1_NAME)
+                .map(|spec| spec.priority),
+            Some(0)
+        );
+        assert_eq!(resource_gate.next_check(), None);
+    }
+
+    // [utest->swdd~agent-holds-workload-starts-until-free-resources-cover-requests~1]
+    #[test]
+    fn utest_resource_gate_releases_all_workloads_without_free_resources() {
+        let workload_1 =
+            generate_test_workload_spec_with_requests(WORKLOAD_1_NAME, None, Some("1Ti"));
+
+        let mut resource_gate = ResourceGate::new();
+        assert!(resource_gate.is_needed(&[WorkloadOperation::Create(workload_1.clone())]));
+        let (released, newly_held) = resource_gate.release(
+            vec![WorkloadOperation::Create(workload_1.clone())],
+            None,
+            Instant::now(),
+        );
+
+        assert_eq!(released, vec![WorkloadOperation::Create(workload_1)]);
+        assert!(newly_held.is_empty());
+        assert_eq!(resource_gate.held_workloads().count(), 0);
+    }
+}
@@ -0,0 +1,215 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use common::objects::{CronSchedule, WorkloadSpec};
+use tokio::time::Instant;
+
+struct ScheduleTrigger {
+    workload_spec: WorkloadSpec,
+    schedule: CronSchedule,
+    // the next matching time in seconds since the Unix epoch, None if the schedule never matches
+    next_unix_seconds: Option<u64>,
+}
+
+// [impl->swdd~agent-starts-scheduled-workloads-at-matching-times~1]
+// The workloads with a schedule mapped to the next time their create operation is released.
+#[derive(Default)]
+pub struct ScheduleTriggers {
+    triggers: HashMap<String, ScheduleTrigger>,
+}
+
+impl ScheduleTriggers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Returns false if the workload has no valid schedule and is thus not added.
+    pub fn add(&mut self, workload_spec: WorkloadSpec) -> bool {
+        let Some(schedule) = workload_spec
+            .schedule
+            .as_deref()
+            .and_then(|schedule| schedule.parse::<CronSchedule>().ok())
+        else {
+            return false;
+        };
+
+        let next_unix_seconds = schedule.next_after(unix_seconds_now());
+        if next_unix_seconds.is_none() {
+            log::warn!(
+                "The schedule of workload '{}' never matches, the workload is never started.",
+                workload_spec.instance_name.workload_name()
+            );
+        }
+        self.triggers.insert(
+            workload_spec.instance_name.workload_name().to_owned(),
+            ScheduleTrigger {
+                workload_spec,
+                schedule,
+                next_unix_seconds,
+            },
+        );
+        true
+    }
+
+    pub fn remove(&mut self, workload_name: &str) -> Option<WorkloadSpec> {
+        self.triggers
+            .remove(workload_name)
+            .map(|trigger| trigger.workload_spec)
+    }
+
+    pub fn contains(&self, workload_name: &str) -> bool {
+        self.triggers.contains_key(workload_name)
+    }
+
+    pub fn get(&self, workload_name: &str) -> Option<&WorkloadSpec> {
+        self.triggers
+            .get(workload_name)
+            .map(|trigger| &trigger.workload_spec)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &WorkloadSpec)> {
+        self.triggers
+            .iter()
+            .map(|(workload_name, trigger)| (workload_name, &trigger.workload_spec))
+    }
+
+    pub fn next_trigger(&self) -> Option<Instant> {
+        self.triggers
+            .values()
+            .filter_map(|trigger| trigger.next_unix_seconds)
+            .min()
+            .map(instant_of)
+    }
+
+    // Returns the specs of the workloads whose next matching time is reached and moves their
+    // trigger to the following matching time.
+    pub fn take_triggered(&mut self, now: Instant) -> Vec<WorkloadSpec> {
+        let mut triggered_workload_specs = Vec::new();
+        for trigger in self.triggers.values_mut() {
+            let Some(unix_seconds) = trigger.next_unix_seconds else {
+                continue;
+            };
+            if instant_of(unix_seconds) <= now {
+                // continue from the reached time to not trigger the same time twice
+                trigger.next_unix_seconds = trigger.schedule.next_after(unix_seconds);
+                triggered_workload_specs.push(trigger.workload_spec.clone());
+            }
+        }
+        triggered_workload_specs
+    }
+}
+
+fn unix_seconds_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+// The schedule refers to the wall clock, the deadlines of the agent to the monotonic clock.
+fn instant_of(unix_seconds: u64) -> Instant {
+    let time = UNIX_EPOCH + Duration::from_secs(unix_seconds);
+    Instant::now() + time.duration_since(SystemTime::now()).unwrap_or_default()
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::ScheduleTriggers;
+    use common::objects::generate_test_workload_spec_with_param;
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+    const WORKLOAD_2_NAME: &str = "workload2";
+    const RUNTIME: &str = "runtime";
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    fn generate_test_scheduled_workload_spec(
+        workload_name: &str,
+        schedule: &str,
+    ) -> common::objects::WorkloadSpec {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            workload_name.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        workload_spec.schedule = Some(schedule.to_owned());
+        workload_spec
+    }
+
+    // [utest->swdd~agent-starts-scheduled-workloads-at-matching-times~1]
+    #[test]
+    fn utest_schedule_triggers_releases_workload_once_per_matching_time() {
+        let workload_spec = generate_test_scheduled_workload_spec(WORKLOAD_1_NAME, "* * * * *");
+        let mut schedule_triggers = ScheduleTriggers::new();
+        assert!(schedule_triggers.add(workload_spec.clone()));
+
+        let now = Instant::now();
+        let next_trigger = schedule_triggers.next_trigger().unwrap();
+        assert!(next_trigger > now && next_trigger <= now + MINUTE);
+
+        assert_eq!(
+            schedule_triggers.take_triggered(now + MINUTE),
+            vec![workload_spec]
+        );
+        // the next matching time is one minute later
+        assert!(schedule_triggers.take_triggered(now + MINUTE).is_empty());
+        assert!(schedule_triggers.next_trigger().unwrap() > now + MINUTE);
+    }
+
+    // [utest->swdd~agent-starts-scheduled-workloads-at-matching-times~1]
+    #[test]
+    fn utest_schedule_triggers_does_not_release_workload_before_matching_time() {
+        let mut schedule_triggers = ScheduleTriggers::new();
+        assert!(schedule_triggers.add(generate_test_scheduled_workload_spec(
+            WORKLOAD_1_NAME,
+            "* * * * *"
+        )));
+        // the 31st of February never matches
+        assert!(schedule_triggers.add(generate_test_scheduled_workload_spec(
+            WORKLOAD_2_NAME,
+            "0 0 31 2 *"
+        )));
+
+        assert!(schedule_triggers.take_triggered(Instant::now()).is_empty());
+        assert!(schedule_triggers.contains(WORKLOAD_2_NAME));
+
+        assert!(schedule_triggers.remove(WORKLOAD_1_NAME).is_some());
+        assert_eq!(schedule_triggers.next_trigger(), None);
+    }
+
+    // [utest->swdd~agent-starts-scheduled-workloads-at-matching-times~1]
+    #[test]
+    fn utest_schedule_triggers_ignores_workload_without_valid_schedule() {
+        let mut schedule_triggers = ScheduleTriggers::new();
+        let mut workload_spec = generate_test_scheduled_workload_spec(WORKLOAD_1_NAME, "* *");
+        assert!(!schedule_triggers.add(workload_spec.clone()));
+
+        workload_spec.schedule = None;
+        assert!(!schedule_triggers.add(workload_spec));
+        assert!(!schedule_triggers.contains(WORKLOAD_1_NAME));
+    }
+}
@@ -12,12 +12,30 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::agent_metrics::{self, SchedulerMetrics};
+use crate::workload_scheduler::dependency_cycle::{find_dependency_cycles, PendingDependencies};
 #[cfg_attr(test, mockall_double::double)]
 use crate::workload_scheduler::dependency_state_validator::DependencyStateValidator;
+use crate::workload_scheduler::dependency_state_validator::{
+    describe_unfulfilled_create_dependencies, describe_unfulfilled_delete_dependencies,
+};
+use crate::workload_scheduler::queue_storage::QueueStorage;
+use crate::workload_scheduler::topological_order::topologically_ordered;
 use crate::workload_state::{WorkloadStateSender, WorkloadStateSenderInterface};
-use common::objects::{DeletedWorkload, ExecutionState, WorkloadInstanceName, WorkloadSpec};
-use std::{collections::HashMap, fmt::Display};
-
+use common::commands::{PendingOperation, PendingWorkloadOperation};
+use common::memory_profiling::{self, Subsystem};
+use common::objects::{
+    AddCondition, DeletedWorkload, DependencyFailurePolicy, ExecutionState, UpdateStrategy,
+    WorkloadInstanceName, WorkloadSpec,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt::Display,
+    time::Duration,
+};
+use tokio::time::Instant;
 
 use crate::workload_operation::WorkloadOperation;
 #[cfg_attr(test, mockall_double::double)]
@@ -26,7 +44,7 @@ use crate::workload_state::workload_state_store::WorkloadStateStore;
 #[cfg(test)]
 use mockall::automock;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 enum PendingEntry {
     Create(WorkloadSpec),
     Delete(DeletedWorkload),
@@ -36,9 +54,123 @@ enum PendingEntry {
 
 type WorkloadOperationQueue = HashMap<String, PendingEntry>;
 
+// Only the creates wait on the start of their dependencies.
+fn pending_create_spec(pending_entry: &PendingEntry) -> Option<&WorkloadSpec> {
+    match pending_entry {
+        PendingEntry::Create(workload_spec) | PendingEntry::UpdateCreate(workload_spec, _) => {
+            Some(workload_spec)
+        }
+        PendingEntry::Delete(_) | PendingEntry::UpdateDelete(_, _) => None,
+    }
+}
+
+// The names of the workloads whose execution states decide if the pending entry is ready.
+fn dependency_names(pending_entry: &PendingEntry) -> Vec<&String> {
+    match pending_entry {
+        PendingEntry::Create(workload_spec) | PendingEntry::UpdateCreate(workload_spec, _) => {
+            workload_spec
+                .add_conditions()
+                .into_iter()
+                .map(|(dependency_name, _)| dependency_name)
+                .collect()
+        }
+        PendingEntry::Delete(deleted_workload) => deleted_workload.dependencies.keys().collect(),
+        PendingEntry::UpdateDelete(workload_spec, deleted_workload) => workload_spec
+            .add_conditions()
+            .into_iter()
+            .map(|(dependency_name, _)| dependency_name)
+            .chain(deleted_workload.dependencies.keys())
+            .collect(),
+    }
+}
+
+// The add conditions of the dependency expression are tried in all combinations, thus
+// expressions with more conditions are never considered blocked.
+const MAX_EXPRESSION_CONDITIONS_FOR_CYCLES: usize = 16;
+
+// A pending create is not running, thus it only fulfills a not running condition. The workload
+// waits on the pending create if no states of its other dependencies fulfill its conditions.
+fn waits_on_pending_create(workload_spec: &WorkloadSpec, pending_name: &str) -> bool {
+    let pending_fulfills =
+        |add_condition: &AddCondition| *add_condition == AddCondition::AddCondNotRunning;
+    if workload_spec
+        .dependencies
+        .get(pending_name)
+        .is_some_and(|add_condition| !pending_fulfills(add_condition))
+    {
+        return true;
+    }
+    let Some(dependency_expression) = &workload_spec.dependency_expression else {
+        return false;
+    };
+    let mut other_conditions: Vec<(&String, &AddCondition)> = Vec::new();
+    for other_condition in dependency_expression.conditions() {
+        if other_condition.0 != pending_name && !other_conditions.contains(&other_condition) {
+            other_conditions.push(other_condition);
+        }
+    }
+    if other_conditions.len() > MAX_EXPRESSION_CONDITIONS_FOR_CYCLES {
+        return false;
+    }
+    (0..1u32 << other_conditions.len()).all(|fulfilled_conditions| {
+        !dependency_expression.evaluate(&|dependency_name, add_condition| {
+            if dependency_name == pending_name {
+                return pending_fulfills(add_condition);
+            }
+            other_conditions
+                .iter()
+                .position(|(other_name, other_condition)| {
+                    *other_name == dependency_name && *other_condition == add_condition
+                })
+                .is_some_and(|index| fulfilled_conditions & (1 << index) != 0)
+        })
+    })
+}
+
+// [impl->swdd~agent-releases-ready-workload-operations-by-priority~1]
+// The sort is stable, thus operations of the same priority keep their order.
+fn sort_by_priority(workload_operations: &mut [WorkloadOperation]) {
+    workload_operations.sort_by_key(|workload_operation| Reverse(workload_operation.priority()));
+}
+
+// [impl->swdd~agent-reevaluates-pending-workloads-after-running-for~1]
+// The next time a dependency with the add condition running for has been running long enough.
+fn running_for_deadline(
+    workload_spec: &WorkloadSpec,
+    workload_state_db: &WorkloadStateStore,
+    now: Instant,
+) -> Option<Instant> {
+    workload_spec
+        .add_conditions()
+        .into_iter()
+        .filter(|(_, add_condition)| **add_condition == AddCondition::AddCondRunningFor)
+        .filter_map(|(dependency_name, _)| {
+            let running_since = workload_state_db.get_running_since_of_workload(dependency_name)?;
+            Some(running_since + workload_spec.get_running_for(dependency_name))
+        })
+        .filter(|deadline| *deadline > now)
+        .min()
+}
+
 pub struct WorkloadScheduler {
     queue: WorkloadOperationQueue,
     workload_state_sender: WorkloadStateSender,
+    queue_storage: Option<QueueStorage>,
+    restored_queue: WorkloadOperationQueue,
+    // The deadlines are relative to the receipt of the update and hence not persisted.
+    deadlines: HashMap<String, Instant>,
+    // The dependency timeouts start when the workload begins to wait for its dependencies.
+    dependency_deadlines: HashMap<String, (WorkloadInstanceName, Instant)>,
+    // The pending creates are evaluated again when a dependency has been running long enough,
+    // as no new execution state is received at that time.
+    running_for_deadlines: HashMap<String, Instant>,
+    // Maps the name of a dependency to the pending entries waiting on it. The index may contain
+    // entries no longer in the queue, which are skipped, but never misses a pending entry.
+    dependents: HashMap<String, HashSet<String>>,
+    // [impl->swdd~agent-collects-scheduler-metrics~1]
+    // The wait of a workload starts with its first pending entry and ends when it leaves the queue.
+    pending_since: HashMap<String, Instant>,
+    metrics: SchedulerMetrics,
 }
 
 #[cfg_attr(test, automock)]
@@ -47,15 +179,532 @@ impl WorkloadScheduler {
         WorkloadScheduler {
             queue: WorkloadOperationQueue::new(),
             workload_state_sender: workload_state_tx,
+            queue_storage: None,
+            restored_queue: WorkloadOperationQueue::new(),
+            deadlines: HashMap::new(),
+            dependency_deadlines: HashMap::new(),
+            running_for_deadlines: HashMap::new(),
+            dependents: HashMap::new(),
+            pending_since: HashMap::new(),
+            metrics: SchedulerMetrics::new(),
+        }
+    }
+
+    // [impl->swdd~agent-restores-pending-workload-operations~1]
+    pub fn restore_queue(&mut self, queue_storage: QueueStorage) {
+        match queue_storage.load::<WorkloadOperationQueue>() {
+            Ok(restored_queue) => {
+                if !restored_queue.is_empty() {
+                    log::info!(
+                        "Restored '{}' pending workload operation(s) from the last run.",
+                        restored_queue.len()
+                    );
+                }
+                self.restored_queue = restored_queue;
+            }
+            Err(err) => log::warn!("Could not restore pending workload operations: {}", err),
+        }
+        self.queue_storage = Some(queue_storage);
+    }
+
+    // [impl->swdd~agent-reconciles-restored-pending-workload-operations~1]
+    // The initial list of the server is authoritative for the workloads to create. Only the
+    // deletes of workloads no longer contained in it are kept, as the server does not resend them.
+    pub fn reconcile_restored_queue(
+        &mut self,
+        added_workloads: &[WorkloadSpec],
+    ) -> Vec<DeletedWorkload> {
+        let mut pending_deletes = Vec::new();
+        for (workload_name, restored_entry) in self.restored_queue.drain() {
+            let is_added = added_workloads
+                .iter()
+                .any(|workload| workload.instance_name.workload_name() == workload_name);
+            match restored_entry {
+                PendingEntry::Delete(deleted_workload)
+                | PendingEntry::UpdateDelete(_, deleted_workload)
+                    if !is_added =>
+                {
+                    log::debug!(
+                        "Keeping the restored pending delete of workload '{}'.",
+                        workload_name
+                    );
+                    pending_deletes.push(deleted_workload);
+                }
+                _ => log::debug!(
+                    "Dropping the restored pending operation of workload '{}' superseded by the server.",
+                    workload_name
+                ),
+            }
+        }
+        pending_deletes
+    }
+
+    // [impl->swdd~agent-persists-pending-workload-operations~1]
+    fn persist_queue(&self) {
+        if let Some(queue_storage) = &self.queue_storage {
+            if let Err(err) = queue_storage.store(&self.queue) {
+                log::warn!("Could not persist pending workload operations: {}", err);
+            }
+        }
+    }
+
+    // [impl->swdd~agent-collects-scheduler-metrics~1]
+    fn update_metrics(&mut self) {
+        self.pending_since
+            .retain(|workload_name, _| self.queue.contains_key(workload_name));
+        self.metrics.set_queue_size(self.queue.len());
+        agent_metrics::publish_scheduler_metrics(&self.metrics);
+    }
+
+    // [impl->swdd~agent-reports-exceeded-update-deadline~1]
+    pub fn set_deadline(&mut self, workload_names: &[String], deadline: Instant) {
+        for workload_name in workload_names {
+            if self.queue.contains_key(workload_name) {
+                self.deadlines.insert(workload_name.clone(), deadline);
+            }
+        }
+    }
+
+    // [impl->swdd~agent-reports-scheduler-queue-to-server~1]
+    // An update waiting on the delete of the old workload also reports the unfulfilled
+    // dependencies of the new workload as they are evaluated right after the delete.
+    pub fn pending_workload_operations(
+        &self,
+        workload_state_db: &WorkloadStateStore,
+    ) -> Vec<PendingWorkloadOperation> {
+        let mut pending_operations: Vec<PendingWorkloadOperation> = self
+            .queue
+            .values()
+            .map(|pending_entry| {
+                let (instance_name, operation, unfulfilled_dependencies) = match pending_entry {
+                    PendingEntry::Create(workload_spec) => (
+                        &workload_spec.instance_name,
+                        PendingOperation::Create,
+                        DependencyStateValidator::unfulfilled_create_dependencies(
+                            workload_spec,
+                            workload_state_db,
+                        ),
+                    ),
+                    PendingEntry::UpdateCreate(workload_spec, _) => (
+                        &workload_spec.instance_name,
+                        PendingOperation::Update,
+                        DependencyStateValidator::unfulfilled_create_dependencies(
+                            workload_spec,
+                            workload_state_db,
+                        ),
+                    ),
+                    PendingEntry::UpdateDelete(workload_spec, deleted_workload) => (
+                        &workload_spec.instance_name,
+                        PendingOperation::Update,
+                        DependencyStateValidator::unfulfilled_delete_dependencies(
+                            deleted_workload,
+                            workload_state_db,
+                        )
+                        .into_iter()
+                        .chain(DependencyStateValidator::unfulfilled_create_dependencies(
+                            workload_spec,
+                            workload_state_db,
+                        ))
+                        .collect(),
+                    ),
+                    PendingEntry::Delete(deleted_workload) => (
+                        &deleted_workload.instance_name,
+                        PendingOperation::Delete,
+                        DependencyStateValidator::unfulfilled_delete_dependencies(
+                            deleted_workload,
+                            workload_state_db,
+                        ),
+                    ),
+                };
+                PendingWorkloadOperation {
+                    workload_name: instance_name.workload_name().to_owned(),
+                    agent: instance_name.agent_name().to_owned(),
+                    operation,
+                    unfulfilled_dependencies,
+                }
+            })
+            .collect();
+        // the queue is a HashMap, sort for a stable report
+        pending_operations.sort_by(|a, b| a.workload_name.cmp(&b.workload_name));
+        pending_operations
+    }
+
+    // [impl->swdd~agent-subscribes-to-workload-states-of-dependencies~1]
+    pub fn dependency_names(&self) -> Vec<String> {
+        self.queue
+            .values()
+            .flat_map(dependency_names)
+            .cloned()
+            .collect()
+    }
+
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.deadlines
+            .values()
+            .copied()
+            .chain(
+                self.dependency_deadlines
+                    .values()
+                    .map(|(_, deadline)| *deadline),
+            )
+            .chain(self.running_for_deadlines.values().copied())
+            .min()
+    }
+
+    // [impl->swdd~agent-reports-exceeded-update-deadline~1]
+    // Only the pending creates are dropped, pending deletes are kept as the workloads
+    // shall not be left running after their removal from the desired state.
+    pub async fn expire_pending_workload_operations(&mut self, now: Instant) {
+        self.deadlines
+            .retain(|workload_name, _| self.queue.contains_key(workload_name));
+        let expired_workload_names: Vec<String> = self
+            .deadlines
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(workload_name, _)| workload_name.clone())
+            .collect();
+
+        for workload_name in expired_workload_names {
+            self.deadlines.remove(&workload_name);
+            match self.queue.remove(&workload_name) {
+                Some(PendingEntry::Create(workload_spec))
+                | Some(PendingEntry::UpdateCreate(workload_spec, _)) => {
+                    log::warn!(
+                        "The deadline of the update is exceeded for the pending workload '{}'.",
+                        workload_name
+                    );
+                    self.workload_state_sender
+                        .report_workload_execution_state(
+                            &workload_spec.instance_name,
+                            ExecutionState::deadline_exceeded(),
+                        )
+                        .await;
+                }
+                Some(pending_entry) => {
+                    self.queue.insert(workload_name, pending_entry);
+                }
+                None => {}
+            }
+        }
+
+        self.expire_dependency_timeouts(now).await;
+        self.persist_queue();
+        self.update_metrics();
+    }
+
+    // [impl->swdd~agent-times-out-pending-dependency-waits~1]
+    // As for the deadline of the update, only the pending creates are dropped.
+    async fn expire_dependency_timeouts(&mut self, now: Instant) {
+        self.dependency_deadlines
+            .retain(|workload_name, _| self.queue.contains_key(workload_name));
+        let expired_workload_names: Vec<String> = self
+            .dependency_deadlines
+            .iter()
+            .filter(|(_, (_, deadline))| *deadline <= now)
+            .map(|(workload_name, _)| workload_name.clone())
+            .collect();
+
+        for workload_name in expired_workload_names {
+            self.dependency_deadlines.remove(&workload_name);
+            match self.queue.remove(&workload_name) {
+                Some(PendingEntry::Create(workload_spec))
+                | Some(PendingEntry::UpdateCreate(workload_spec, _)) => {
+                    log::warn!(
+                        "The dependencies of the pending workload '{}' were not fulfilled within its dependency timeout.",
+                        workload_name
+                    );
+                    self.workload_state_sender
+                        .report_workload_execution_state(
+                            &workload_spec.instance_name,
+                            ExecutionState::dependency_timeout(),
+                        )
+                        .await;
+                }
+                Some(pending_entry) => {
+                    self.queue.insert(workload_name, pending_entry);
+                }
+                None => {}
+            }
+        }
+    }
+
+    // [impl->swdd~agent-times-out-pending-dependency-waits~1]
+    // The timeout keeps running while the same workload instance stays in the queue.
+    fn start_dependency_timeout(&mut self, workload_name: &str, pending_entry: &PendingEntry) {
+        let (PendingEntry::Create(workload_spec) | PendingEntry::UpdateCreate(workload_spec, _)) =
+            pending_entry
+        else {
+            return;
+        };
+        let Some(timeout_ms) = workload_spec.dependency_timeout_ms else {
+            return;
+        };
+        match self.dependency_deadlines.get(workload_name) {
+            Some((instance_name, _)) if *instance_name == workload_spec.instance_name => {}
+            _ => {
+                self.dependency_deadlines.insert(
+                    workload_name.to_owned(),
+                    (
+                        workload_spec.instance_name.clone(),
+                        Instant::now() + Duration::from_millis(timeout_ms),
+                    ),
+                );
+            }
+        }
+    }
+
+    // [impl->swdd~agent-reevaluates-pending-workloads-after-running-for~1]
+    fn start_running_for_wait(
+        &mut self,
+        workload_name: &str,
+        pending_entry: &PendingEntry,
+        workload_state_db: &WorkloadStateStore,
+    ) {
+        match pending_create_spec(pending_entry).and_then(|workload_spec| {
+            running_for_deadline(workload_spec, workload_state_db, Instant::now())
+        }) {
+            Some(deadline) => {
+                self.running_for_deadlines
+                    .insert(workload_name.to_owned(), deadline);
+            }
+            None => {
+                self.running_for_deadlines.remove(workload_name);
+            }
+        }
+    }
+
+    // [impl->swdd~agent-detects-dependency-cycles-of-pending-workloads~1]
+    // A pending create waiting on another pending create is never fulfilled before the other
+    // one is started, thus the creates of a cycle are dropped as none of them will ever start.
+    async fn drop_dependency_cycles(&mut self) {
+        let pending_dependencies: PendingDependencies = self
+            .queue
+            .iter()
+            .filter_map(|(workload_name, pending_entry)| {
+                let workload_spec = pending_create_spec(pending_entry)?;
+                let mut dependency_names: Vec<String> = workload_spec
+                    .add_conditions()
+                    .into_iter()
+                    .map(|(dependency_name, _)| dependency_name)
+                    .filter(|dependency_name| {
+                        self.queue
+                            .get(*dependency_name)
+                            .and_then(pending_create_spec)
+                            .is_some()
+                            && waits_on_pending_create(workload_spec, dependency_name)
+                    })
+                    .cloned()
+                    .collect();
+                dependency_names.sort();
+                dependency_names.dedup();
+                Some((workload_name.clone(), dependency_names))
+            })
+            .collect();
+
+        for cycle in find_dependency_cycles(&pending_dependencies) {
+            let cycle_path = cycle.join(" -> ");
+            log::warn!(
+                "The pending workloads form the dependency cycle '{}' and are not started.",
+                cycle_path
+            );
+            for workload_name in &cycle {
+                let pending_entry = self.queue.remove(workload_name);
+                if let Some(workload_spec) = pending_entry.as_ref().and_then(pending_create_spec) {
+                    self.workload_state_sender
+                        .report_workload_execution_state(
+                            &workload_spec.instance_name,
+                            ExecutionState::dependency_cycle(&cycle_path),
+                        )
+                        .await;
+                }
+            }
+        }
+    }
+
+    // The index of the dependents may keep the name, as it tolerates entries no longer queued.
+    fn take_pending_entry(&mut self, workload_name: &str) -> Option<PendingEntry> {
+        self.deadlines.remove(workload_name);
+        self.dependency_deadlines.remove(workload_name);
+        self.running_for_deadlines.remove(workload_name);
+        self.pending_since.remove(workload_name);
+        self.queue.remove(workload_name)
+    }
+
+    // [impl->swdd~agent-cancels-pending-workload-operations~1]
+    // The instance of a cancelled pending create never starts, thus it is reported as removed.
+    pub async fn cancel(&mut self, workload_name: &str) {
+        let pending_entry = self.take_pending_entry(workload_name);
+        if let Some(workload_spec) = pending_entry.as_ref().and_then(pending_create_spec) {
+            log::info!(
+                "Cancelling the pending start of workload instance '{}'.",
+                workload_spec.instance_name
+            );
+            self.workload_state_sender
+                .report_workload_execution_state(
+                    &workload_spec.instance_name,
+                    ExecutionState::removed(),
+                )
+                .await;
+        }
+
+        // [impl->swdd~agent-persists-pending-workload-operations~1]
+        self.persist_queue();
+        self.update_metrics();
+    }
+
+    // [impl->swdd~agent-cancels-superseded-pending-creates~1]
+    // A new workload operation replaces the pending create of the workload. Only the pending
+    // creates are replaced, as the workloads of pending deletes are still running. The removal
+    // of a pending create by a delete is reported when the delete is executed.
+    async fn cancel_superseded_pending_create(&mut self, workload_operation: &WorkloadOperation) {
+        let workload_name = workload_operation.workload_name();
+        let Some(pending_instance_name) = self
+            .queue
+            .get(workload_name)
+            .and_then(pending_create_spec)
+            .map(|workload_spec| workload_spec.instance_name.clone())
+        else {
+            return;
+        };
+        match workload_operation {
+            WorkloadOperation::Create(workload_spec)
+            | WorkloadOperation::Update(workload_spec, _)
+                if workload_spec.instance_name != pending_instance_name =>
+            {
+                self.cancel(workload_name).await;
+            }
+            // [impl->swdd~agent-shall-not-enqueue-update-delete-only-workload-operation~1]
+            WorkloadOperation::UpdateDeleteOnly(_) => {}
+            _ => {
+                self.take_pending_entry(workload_name);
+            }
         }
     }
 
-    fn put_on_queue<T>(&mut self, workload_name: T, pending_entry: PendingEntry)
-    where
+    fn put_on_queue<T>(
+        &mut self,
+        workload_name: T,
+        pending_entry: PendingEntry,
+        workload_state_db: &WorkloadStateStore,
+    ) where
         T: Into<String> + Display + 'static,
     {
         log::debug!("Putting workload '{}' on waiting queue.", workload_name);
-        self.queue.insert(workload_name.into(), pending_entry);
+        // [impl->swdd~agent-attributes-heap-usage-to-subsystems~1]
+        let _memory_scope = memory_profiling::enter(Subsystem::SchedulerQueues);
+        let workload_name = workload_name.into();
+        self.start_dependency_timeout(&workload_name, &pending_entry);
+        self.start_running_for_wait(&workload_name, &pending_entry, workload_state_db);
+        // [impl->swdd~agent-reevaluates-only-dependents-of-changed-workloads~1]
+        for dependency_name in dependency_names(&pending_entry) {
+            self.dependents
+                .entry(dependency_name.clone())
+                .or_default()
+                .insert(workload_name.clone());
+        }
+        self.pending_since
+            .entry(workload_name.clone())
+            .or_insert_with(Instant::now);
+        self.queue.insert(workload_name, pending_entry);
+    }
+
+    // [impl->swdd~agent-stops-workloads-with-running-excluded-dependency~1]
+    // The workloads are stopped and wait as pending updates until the workloads they exclude
+    // are not running anymore. Workloads already waiting in the queue are skipped.
+    pub async fn enqueue_workloads_with_running_excluded_dependencies(
+        &mut self,
+        workload_specs: Vec<WorkloadSpec>,
+        workload_state_db: &WorkloadStateStore,
+    ) -> Vec<WorkloadOperation> {
+        let mut ready_workload_operations = Vec::new();
+        for workload_spec in workload_specs {
+            if self
+                .queue
+                .contains_key(workload_spec.instance_name.workload_name())
+            {
+                continue;
+            }
+            log::info!(
+                "Stopping workload '{}' as a workload excluded by it is running.",
+                workload_spec.instance_name.workload_name()
+            );
+            ready_workload_operations.push(
+                self.stop_until_dependencies_fulfilled(workload_spec, workload_state_db)
+                    .await,
+            );
+        }
+
+        // [impl->swdd~agent-persists-pending-workload-operations~1]
+        self.persist_queue();
+        self.update_metrics();
+        ready_workload_operations
+    }
+
+    // [impl->swdd~agent-handles-dependency-failure-of-running-workloads~1]
+    // The workloads with the policy 'stop' wait as pending updates until their dependencies are
+    // fulfilled again, the ones with the policy 'restart' are replaced right away. Workloads
+    // already waiting in the queue are skipped.
+    pub async fn enqueue_workloads_with_failed_dependencies(
+        &mut self,
+        workload_specs: Vec<WorkloadSpec>,
+        workload_state_db: &WorkloadStateStore,
+    ) -> Vec<WorkloadOperation> {
+        let mut ready_workload_operations = Vec::new();
+        for workload_spec in workload_specs {
+            let workload_name = workload_spec.instance_name.workload_name().to_owned();
+            if self.queue.contains_key(&workload_name) {
+                continue;
+            }
+            match workload_spec.on_dependency_failure {
+                DependencyFailurePolicy::Ignore => {}
+                DependencyFailurePolicy::Restart => {
+                    log::info!(
+                        "Restarting workload '{}' as one of its dependencies failed.",
+                        workload_name
+                    );
+                    let deleted_workload = DeletedWorkload {
+                        instance_name: workload_spec.instance_name.clone(),
+                        dependencies: HashMap::new(),
+                    };
+                    ready_workload_operations
+                        .push(WorkloadOperation::Update(workload_spec, deleted_workload));
+                }
+                DependencyFailurePolicy::Stop => {
+                    log::info!(
+                        "Stopping workload '{}' as one of its dependencies failed.",
+                        workload_name
+                    );
+                    ready_workload_operations.push(
+                        self.stop_until_dependencies_fulfilled(workload_spec, workload_state_db)
+                            .await,
+                    );
+                }
+            }
+        }
+
+        // [impl->swdd~agent-persists-pending-workload-operations~1]
+        self.persist_queue();
+        self.update_metrics();
+        ready_workload_operations
+    }
+
+    async fn stop_until_dependencies_fulfilled(
+        &mut self,
+        workload_spec: WorkloadSpec,
+        workload_state_db: &WorkloadStateStore,
+    ) -> WorkloadOperation {
+        let deleted_workload = DeletedWorkload {
+            instance_name: workload_spec.instance_name.clone(),
+            dependencies: HashMap::new(),
+        };
+        self.report_pending_create_state(&workload_spec, workload_state_db)
+            .await;
+        self.put_on_queue(
+            workload_spec.instance_name.workload_name().to_owned(),
+            PendingEntry::UpdateCreate(workload_spec, deleted_workload.clone()),
+            workload_state_db,
+        );
+        WorkloadOperation::UpdateDeleteOnly(deleted_workload)
     }
 
     // [impl->swdd~agent-handles-new-workload-operations]
@@ -64,10 +713,51 @@ impl WorkloadScheduler {
         &mut self,
         new_workload_operations: Vec<WorkloadOperation>,
         workload_state_db: &WorkloadStateStore,
+    ) -> Vec<WorkloadOperation> {
+        let mut ready_workload_operations = self
+            .enqueue_new_workload_operations(new_workload_operations, workload_state_db)
+            .await;
+
+        // extend with existing pending update entries of the queue if their dependencies are fulfilled now
+        ready_workload_operations.extend(self.next_workload_operations(workload_state_db).await);
+        sort_by_priority(&mut ready_workload_operations);
+        ready_workload_operations
+    }
+
+    // [impl->swdd~agent-orders-initial-workload-operations-topologically~1]
+    // The workload operations of the initial list are enqueued along their dependencies. The
+    // entries already in the queue are not evaluated again, as the workload states did not change
+    // since their last evaluation.
+    pub async fn enqueue_initial_workload_operations(
+        &mut self,
+        new_workload_operations: Vec<WorkloadOperation>,
+        workload_state_db: &WorkloadStateStore,
+    ) -> Vec<WorkloadOperation> {
+        let mut ready_workload_operations = self
+            .enqueue_new_workload_operations(
+                topologically_ordered(new_workload_operations),
+                workload_state_db,
+            )
+            .await;
+        sort_by_priority(&mut ready_workload_operations);
+
+        self.drop_dependency_cycles().await;
+        // [impl->swdd~agent-persists-pending-workload-operations~1]
+        self.persist_queue();
+        self.update_metrics();
+        ready_workload_operations
+    }
+
+    async fn enqueue_new_workload_operations(
+        &mut self,
+        new_workload_operations: Vec<WorkloadOperation>,
+        workload_state_db: &WorkloadStateStore,
     ) -> Vec<WorkloadOperation> {
         let mut ready_workload_operations: Vec<WorkloadOperation> = Vec::new();
         let notify_on_new_entry = true;
         for workload_operation in new_workload_operations {
+            self.cancel_superseded_pending_create(&workload_operation)
+                .await;
             match workload_operation {
                 WorkloadOperation::Create(new_workload_spec) => {
                     ready_workload_operations.extend(
@@ -108,9 +798,6 @@ impl WorkloadScheduler {
                 }
             };
         }
-
-        // extend with existing pending update entries of the queue if their dependencies are fulfilled now
-        ready_workload_operations.extend(self.next_workload_operations(workload_state_db).await);
         ready_workload_operations
     }
 
@@ -126,8 +813,73 @@ impl WorkloadScheduler {
             .drain()
             .map(|(_, pending_workload_operation)| pending_workload_operation)
             .collect();
+        // the index is rebuilt while enqueuing the still pending workload operations again
+        self.dependents.clear();
+
+        self.evaluate_pending_entries(queue_entries, workload_state_db)
+            .await
+    }
+
+    // [impl->swdd~agent-reevaluates-only-dependents-of-changed-workloads~1]
+    // Only the pending entries depending on a workload with a changed execution state can
+    // become ready, thus the rest of the queue is not evaluated again.
+    pub async fn next_workload_operations_of_dependents(
+        &mut self,
+        changed_workload_names: &[String],
+        workload_state_db: &WorkloadStateStore,
+    ) -> Vec<WorkloadOperation> {
+        let dependent_names: BTreeSet<String> = changed_workload_names
+            .iter()
+            .filter_map(|workload_name| self.dependents.remove(workload_name))
+            .flatten()
+            .collect();
+        let queue_entries: Vec<PendingEntry> = dependent_names
+            .iter()
+            .filter_map(|dependent_name| self.queue.remove(dependent_name))
+            .collect();
+        if queue_entries.is_empty() {
+            return Vec::new();
+        }
+
+        self.evaluate_pending_entries(queue_entries, workload_state_db)
+            .await
+    }
+
+    // [impl->swdd~agent-reevaluates-pending-workloads-after-running-for~1]
+    // Only the pending entries whose dependencies have been running long enough by now can
+    // become ready without a new execution state.
+    pub async fn next_workload_operations_after_running_for(
+        &mut self,
+        now: Instant,
+        workload_state_db: &WorkloadStateStore,
+    ) -> Vec<WorkloadOperation> {
+        let reached_workload_names: Vec<String> = self
+            .running_for_deadlines
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(workload_name, _)| workload_name.clone())
+            .collect();
+        let queue_entries: Vec<PendingEntry> = reached_workload_names
+            .iter()
+            .filter_map(|workload_name| {
+                self.running_for_deadlines.remove(workload_name);
+                self.queue.remove(workload_name)
+            })
+            .collect();
+        if queue_entries.is_empty() {
+            return Vec::new();
+        }
 
-        // return ready workload operations and enqueue still pending workload operations again
+        self.evaluate_pending_entries(queue_entries, workload_state_db)
+            .await
+    }
+
+    // Returns the ready workload operations and enqueues the still pending ones again.
+    async fn evaluate_pending_entries(
+        &mut self,
+        queue_entries: Vec<PendingEntry>,
+        workload_state_db: &WorkloadStateStore,
+    ) -> Vec<WorkloadOperation> {
         let mut ready_workload_operations: Vec<WorkloadOperation> = Vec::new();
         let notify_on_new_entry = false;
         for queue_entry in queue_entries {
@@ -156,6 +908,7 @@ impl WorkloadScheduler {
                         self.put_on_queue(
                             new_workload_spec.instance_name.workload_name().to_owned(),
                             PendingEntry::UpdateCreate(new_workload_spec, deleted_workload),
+                            workload_state_db,
                         );
                     }
                 }
@@ -182,6 +935,34 @@ impl WorkloadScheduler {
                 }
             }
         }
+
+        self.drop_dependency_cycles().await;
+        self.dependency_deadlines
+            .retain(|workload_name, _| self.queue.contains_key(workload_name));
+        self.running_for_deadlines
+            .retain(|workload_name, _| self.queue.contains_key(workload_name));
+
+        // [impl->swdd~agent-collects-scheduler-metrics~1]
+        // The released delete of an update waiting for its create is still pending.
+        let now = Instant::now();
+        for workload_operation in &ready_workload_operations {
+            let workload_name = workload_operation.workload_name();
+            let pending_duration = if self.queue.contains_key(workload_name) {
+                None
+            } else {
+                self.pending_since
+                    .remove(workload_name)
+                    .map(|pending_since| now - pending_since)
+            };
+            self.metrics
+                .record_release(workload_operation, pending_duration);
+        }
+
+        sort_by_priority(&mut ready_workload_operations);
+
+        // [impl->swdd~agent-persists-pending-workload-operations~1]
+        self.persist_queue();
+        self.update_metrics();
         ready_workload_operations
     }
 
@@ -198,13 +979,14 @@ impl WorkloadScheduler {
             ready_workload_operations.push(WorkloadOperation::Create(new_workload_spec));
         } else {
             if notify_on_new_entry {
-                self.report_pending_create_state(&new_workload_spec.instance_name)
+                self.report_pending_create_state(&new_workload_spec, workload_state_db)
                     .await;
             }
 
             self.put_on_queue(
                 new_workload_spec.instance_name.workload_name().to_owned(),
                 PendingEntry::Create(new_workload_spec),
+                workload_state_db,
             );
         }
 
@@ -224,6 +1006,30 @@ impl WorkloadScheduler {
         let create_fulfilled =
             DependencyStateValidator::create_fulfilled(&new_workload_spec, workload_state_db);
 
+        // [impl->swdd~agent-handles-update-with-at-least-once-strategy~1]
+        if new_workload_spec.update_strategy == UpdateStrategy::AtLeastOnce {
+            /* The old workload keeps running until the new one is running, hence the update
+            only waits for the create dependencies of the new workload. */
+            if create_fulfilled {
+                ready_workload_operations.push(WorkloadOperation::Update(
+                    new_workload_spec,
+                    deleted_workload,
+                ));
+            } else {
+                if notify_on_new_entry {
+                    self.report_pending_create_state(&new_workload_spec, workload_state_db)
+                        .await;
+                }
+
+                self.put_on_queue(
+                    new_workload_spec.instance_name.workload_name().to_owned(),
+                    PendingEntry::UpdateCreate(new_workload_spec, deleted_workload),
+                    workload_state_db,
+                );
+            }
+            return ready_workload_operations;
+        }
+
         // [impl->swdd~workload-ready-to-delete-on-fulfilled-dependencies~1]
         let delete_fulfilled =
             DependencyStateValidator::delete_fulfilled(&deleted_workload, workload_state_db);
@@ -232,8 +1038,8 @@ impl WorkloadScheduler {
         if create_fulfilled && delete_fulfilled {
             // dependencies for create and delete are fulfilled, the update can be done immediately
             ready_workload_operations.push(WorkloadOperation::Update(
-                new_workload_spec.clone(),
-                deleted_workload.clone(),
+                new_workload_spec,
+                deleted_workload,
             ));
             return ready_workload_operations;
         }
@@ -248,12 +1054,13 @@ impl WorkloadScheduler {
             /* once the delete conditions are fulfilled the pending update delete is
             transformed into a pending create since the current update strategy is at most once.
             We notify a pending create state. */
-            self.report_pending_create_state(&new_workload_spec.instance_name)
+            self.report_pending_create_state(&new_workload_spec, workload_state_db)
                 .await;
 
             self.put_on_queue(
                 new_workload_spec.instance_name.workload_name().to_owned(),
                 PendingEntry::UpdateCreate(new_workload_spec, deleted_workload.clone()),
+                workload_state_db,
             );
 
             ready_workload_operations.push(WorkloadOperation::UpdateDeleteOnly(deleted_workload));
@@ -262,13 +1069,14 @@ impl WorkloadScheduler {
 
             // For an update with pending delete dependencies, the whole update is pending.
             if notify_on_new_entry {
-                self.report_pending_delete_state(&deleted_workload.instance_name)
+                self.report_pending_delete_state(&deleted_workload, workload_state_db)
                     .await;
             }
 
             self.put_on_queue(
                 new_workload_spec.instance_name.workload_name().to_owned(),
                 PendingEntry::UpdateDelete(new_workload_spec, deleted_workload),
+                workload_state_db,
             );
         }
         ready_workload_operations
@@ -288,28 +1096,53 @@ impl WorkloadScheduler {
             ready_workload_operations.push(WorkloadOperation::Delete(deleted_workload));
         } else {
             if notify_on_new_entry {
-                self.report_pending_delete_state(&deleted_workload.instance_name)
+                self.report_pending_delete_state(&deleted_workload, workload_state_db)
                     .await;
             }
 
             self.put_on_queue(
                 deleted_workload.instance_name.workload_name().to_owned(),
                 PendingEntry::Delete(deleted_workload),
+                workload_state_db,
             );
         }
 
         ready_workload_operations
     }
 
-    async fn report_pending_create_state(&self, instance_name: &WorkloadInstanceName) {
+    // [impl->swdd~agent-reports-unfulfilled-dependencies-of-pending-workloads~1]
+    async fn report_pending_create_state(
+        &self,
+        workload_spec: &WorkloadSpec,
+        workload_state_db: &WorkloadStateStore,
+    ) {
+        let execution_state = ExecutionState {
+            additional_info: describe_unfulfilled_create_dependencies(
+                workload_spec,
+                workload_state_db,
+            ),
+            ..ExecutionState::waiting_to_start()
+        };
         self.workload_state_sender
-            .report_workload_execution_state(instance_name, ExecutionState::waiting_to_start())
+            .report_workload_execution_state(&workload_spec.instance_name, execution_state)
             .await;
     }
 
-    async fn report_pending_delete_state(&self, instance_name: &WorkloadInstanceName) {
+    // [impl->swdd~agent-reports-unfulfilled-dependencies-of-pending-workloads~1]
+    async fn report_pending_delete_state(
+        &self,
+        deleted_workload: &DeletedWorkload,
+        workload_state_db: &WorkloadStateStore,
+    ) {
+        let execution_state = ExecutionState {
+            additional_info: describe_unfulfilled_delete_dependencies(
+                deleted_workload,
+                workload_state_db,
+            ),
+            ..ExecutionState::waiting_to_stop()
+        };
         self.workload_state_sender
-            .report_workload_execution_state(instance_name, ExecutionState::waiting_to_stop())
+            .report_workload_execution_state(&deleted_workload.instance_name, execution_state)
             .await;
     }
 }
@@ -325,19 +1158,28 @@ impl WorkloadScheduler {
 #[cfg(test)]
 mod tests {
     use common::{
+        commands::{PendingOperation, PendingWorkloadOperation, UnfulfilledDependency},
         objects::{
             generate_test_workload_spec, generate_test_workload_spec_with_param,
-            generate_test_workload_state_with_workload_spec, ExecutionState, WorkloadState,
+            generate_test_workload_spec_with_runtime_config,
+            generate_test_workload_state_with_workload_spec, AddCondition, DeleteCondition,
+            DeletedWorkload, DependencyExpression, DependencyFailurePolicy, ExecutionState,
+            UpdateStrategy, WorkloadState,
         },
+        persistence::PersistenceFormat,
         test_utils::generate_test_deleted_workload,
     };
     use tokio::sync::mpsc::channel;
 
-    use super::WorkloadScheduler;
+    use std::{collections::HashMap, time::Duration};
+
+    use super::{WorkloadOperationQueue, WorkloadScheduler};
     use crate::{
         workload_operation::WorkloadOperation,
         workload_scheduler::{
-            dependency_state_validator::MockDependencyStateValidator, scheduler::PendingEntry,
+            dependency_state_validator::MockDependencyStateValidator,
+            queue_storage::{QueueStorage, QUEUE_FILE_NAME},
+            scheduler::PendingEntry,
         },
         workload_state::{
             assert_execution_state_sequence, workload_state_store::MockWorkloadStateStore,
@@ -346,7 +1188,19 @@ mod tests {
 
     const AGENT_A: &str = "agent_A";
     const WORKLOAD_NAME_1: &str = "workload_1";
+    const WORKLOAD_NAME_2: &str = "workload_2";
+    const WORKLOAD_NAME_3: &str = "workload_3";
+    const WORKLOAD_NAME_4: &str = "workload_4";
     const RUNTIME: &str = "runtime";
+    // the dependencies of the generated test workload spec without execution states
+    const UNFULFILLED_TEST_DEPENDENCIES: &str = "workload A: expected ADD_COND_RUNNING, actual none; workload C: expected ADD_COND_SUCCEEDED, actual none";
+
+    fn waiting_to_start_on(unfulfilled_dependencies: &str) -> ExecutionState {
+        ExecutionState {
+            additional_info: unfulfilled_dependencies.to_owned(),
+            ..ExecutionState::waiting_to_start()
+        }
+    }
 
     // [utest->swdd~agent-handles-new-workload-operations]
     // [utest->swdd~agent-enqueues-unfulfilled-create~1]
@@ -381,7 +1235,7 @@ mod tests {
 
         let expected_workload_state = generate_test_workload_state_with_workload_spec(
             &pending_workload.clone(),
-            ExecutionState::waiting_to_start(),
+            waiting_to_start_on(UNFULFILLED_TEST_DEPENDENCIES),
         );
 
         assert_eq!(
@@ -400,102 +1254,954 @@ mod tests {
         assert!(ready_workload_operations.is_empty());
     }
 
-    // [utest->swdd~agent-handles-workloads-with-fulfilled-dependencies~1]
+    // [utest->swdd~agent-reports-exceeded-update-deadline~1]
     #[tokio::test]
-    async fn utest_no_enqueue_and_report_for_ready_create() {
-        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
-            .get_lock_async()
-            .await;
-        let (workload_state_sender, mut workload_state_receiver) = channel(1);
+    async fn utest_expire_pending_workload_operations_reports_deadline_exceeded_creates() {
+        let (workload_state_sender, mut workload_state_receiver) = channel(2);
         let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
 
-        let mock_dependency_state_validator_context =
-            MockDependencyStateValidator::create_fulfilled_context();
-        mock_dependency_state_validator_context
-            .expect()
-            .return_const(true);
-
-        let ready_workload = generate_test_workload_spec_with_param(
+        let pending_create = generate_test_workload_spec_with_param(
             AGENT_A.to_owned(),
             WORKLOAD_NAME_1.to_owned(),
             RUNTIME.to_owned(),
         );
-
-        let workload_operations = vec![WorkloadOperation::Create(ready_workload.clone())];
-
-        let ready_workload_operations = workload_scheduler
+        let pending_delete =
+            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_2.to_owned());
+        workload_scheduler.queue.insert(
+            WORKLOAD_NAME_1.to_owned(),
+            PendingEntry::Create(pending_create.clone()),
+        );
+        workload_scheduler.queue.insert(
+            WORKLOAD_NAME_2.to_owned(),
+            PendingEntry::Delete(pending_delete.clone()),
+        );
+
+        let deadline = tokio::time::Instant::now();
+        workload_scheduler.set_deadline(
+            &[
+                WORKLOAD_NAME_1.to_owned(),
+                WORKLOAD_NAME_2.to_owned(),
+                WORKLOAD_NAME_3.to_owned(),
+            ],
+            deadline,
+        );
+        assert_eq!(workload_scheduler.next_deadline(), Some(deadline));
+        assert_eq!(workload_scheduler.deadlines.len(), 2);
+
+        workload_scheduler
+            .expire_pending_workload_operations(deadline)
+            .await;
+
+        assert_eq!(
+            workload_state_receiver.try_recv(),
+            Ok(generate_test_workload_state_with_workload_spec(
+                &pending_create,
+                ExecutionState::deadline_exceeded(),
+            ))
+        );
+        assert!(workload_state_receiver.try_recv().is_err());
+        assert_eq!(
+            workload_scheduler.queue,
+            HashMap::from([(
+                WORKLOAD_NAME_2.to_owned(),
+                PendingEntry::Delete(pending_delete)
+            )])
+        );
+        assert_eq!(workload_scheduler.next_deadline(), None);
+    }
+
+    // [utest->swdd~agent-times-out-pending-dependency-waits~1]
+    #[tokio::test]
+    async fn utest_expire_pending_workload_operations_reports_dependency_timeout() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, mut workload_state_receiver) = channel(2);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mock_dependency_state_validator_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_context
+            .expect()
+            .return_const(false);
+
+        let mut pending_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        pending_workload.dependency_timeout_ms = Some(1000);
+
+        let enqueued_at = tokio::time::Instant::now();
+        workload_scheduler
+            .enqueue_filtered_workload_operations(
+                vec![WorkloadOperation::Create(pending_workload.clone())],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+        let dependency_deadline = workload_scheduler.next_deadline().unwrap();
+        assert!(dependency_deadline >= enqueued_at + Duration::from_millis(1000));
+
+        // the timeout is not restarted while the workload stays in the queue
+        workload_scheduler
+            .next_workload_operations(&MockWorkloadStateStore::default())
+            .await;
+        assert_eq!(
+            workload_scheduler.next_deadline(),
+            Some(dependency_deadline)
+        );
+
+        workload_scheduler
+            .expire_pending_workload_operations(dependency_deadline - Duration::from_millis(1))
+            .await;
+        assert!(workload_scheduler.queue.contains_key(WORKLOAD_NAME_1));
+
+        workload_scheduler
+            .expire_pending_workload_operations(dependency_deadline)
+            .await;
+
+        assert_eq!(
+            workload_state_receiver.try_recv(),
+            Ok(generate_test_workload_state_with_workload_spec(
+                &pending_workload,
+                waiting_to_start_on(UNFULFILLED_TEST_DEPENDENCIES),
+            ))
+        );
+        assert_eq!(
+            workload_state_receiver.try_recv(),
+            Ok(generate_test_workload_state_with_workload_spec(
+                &pending_workload,
+                ExecutionState::dependency_timeout(),
+            ))
+        );
+        assert!(workload_scheduler.queue.is_empty());
+        assert_eq!(workload_scheduler.next_deadline(), None);
+    }
+
+    // [utest->swdd~agent-reevaluates-pending-workloads-after-running-for~1]
+    #[tokio::test]
+    async fn utest_next_workload_operations_after_running_for_releases_pending_create() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, _workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        // the new entry is evaluated on enqueue and again with the rest of the queue
+        let mut create_fulfilled_results = vec![true, false, false];
+        let mock_dependency_state_validator_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_context
+            .expect()
+            .times(3)
+            .returning(move |_, _| create_fulfilled_results.pop().unwrap());
+
+        let mut pending_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        pending_workload.dependencies =
+            HashMap::from([(WORKLOAD_NAME_2.to_owned(), AddCondition::AddCondRunningFor)]);
+        pending_workload.running_for_ms = HashMap::from([(WORKLOAD_NAME_2.to_owned(), 1000)]);
+
+        let running_since = tokio::time::Instant::now();
+        let mut workload_state_db = MockWorkloadStateStore::default();
+        workload_state_db
+            .running_since
+            .insert(WORKLOAD_NAME_2.to_owned(), running_since);
+
+        let ready_workload_operations = workload_scheduler
+            .enqueue_filtered_workload_operations(
+                vec![WorkloadOperation::Create(pending_workload.clone())],
+                &workload_state_db,
+            )
+            .await;
+        assert!(ready_workload_operations.is_empty());
+        let running_for_deadline = running_since + Duration::from_millis(1000);
+        assert_eq!(
+            workload_scheduler.next_deadline(),
+            Some(running_for_deadline)
+        );
+
+        let ready_workload_operations = workload_scheduler
+            .next_workload_operations_after_running_for(
+                running_for_deadline - Duration::from_millis(1),
+                &workload_state_db,
+            )
+            .await;
+        assert!(ready_workload_operations.is_empty());
+        assert!(workload_scheduler.queue.contains_key(WORKLOAD_NAME_1));
+
+        let ready_workload_operations = workload_scheduler
+            .next_workload_operations_after_running_for(running_for_deadline, &workload_state_db)
+            .await;
+
+        assert_eq!(
+            ready_workload_operations,
+            vec![WorkloadOperation::Create(pending_workload)]
+        );
+        assert!(workload_scheduler.queue.is_empty());
+        assert_eq!(workload_scheduler.next_deadline(), None);
+    }
+
+    // [utest->swdd~agent-stops-workloads-with-running-excluded-dependency~1]
+    #[tokio::test]
+    async fn utest_enqueue_workloads_with_running_excluded_dependencies() {
+        let (workload_state_sender, mut workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        workload_spec.dependencies =
+            HashMap::from([(WORKLOAD_NAME_2.to_owned(), AddCondition::AddCondNotRunning)]);
+        let deleted_workload = DeletedWorkload {
+            instance_name: workload_spec.instance_name.clone(),
+            dependencies: HashMap::new(),
+        };
+
+        let ready_workload_operations = workload_scheduler
+            .enqueue_workloads_with_running_excluded_dependencies(
+                vec![workload_spec.clone()],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert_eq!(
+            ready_workload_operations,
+            vec![WorkloadOperation::UpdateDeleteOnly(
+                deleted_workload.clone()
+            )]
+        );
+        assert_eq!(
+            workload_scheduler.queue.get(WORKLOAD_NAME_1),
+            Some(&PendingEntry::UpdateCreate(
+                workload_spec.clone(),
+                deleted_workload
+            ))
+        );
+        assert_eq!(
+            workload_state_receiver.try_recv(),
+            Ok(generate_test_workload_state_with_workload_spec(
+                &workload_spec,
+                ExecutionState::waiting_to_start(),
+            ))
+        );
+
+        // the workload already waits in the queue and is not stopped again
+        let ready_workload_operations = workload_scheduler
+            .enqueue_workloads_with_running_excluded_dependencies(
+                vec![workload_spec],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+        assert!(ready_workload_operations.is_empty());
+    }
+
+    // [utest->swdd~agent-handles-dependency-failure-of-running-workloads~1]
+    #[tokio::test]
+    async fn utest_enqueue_workloads_with_failed_dependencies() {
+        let (workload_state_sender, mut workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mut stopped_workload_spec = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        stopped_workload_spec.on_dependency_failure = DependencyFailurePolicy::Stop;
+        let stopped_deleted_workload = DeletedWorkload {
+            instance_name: stopped_workload_spec.instance_name.clone(),
+            dependencies: HashMap::new(),
+        };
+        let mut restarted_workload_spec = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_2.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        restarted_workload_spec.on_dependency_failure = DependencyFailurePolicy::Restart;
+        let restarted_deleted_workload = DeletedWorkload {
+            instance_name: restarted_workload_spec.instance_name.clone(),
+            dependencies: HashMap::new(),
+        };
+        let ignoring_workload_spec = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_3.to_owned(),
+            RUNTIME.to_owned(),
+        );
+
+        let ready_workload_operations = workload_scheduler
+            .enqueue_workloads_with_failed_dependencies(
+                vec![
+                    stopped_workload_spec.clone(),
+                    restarted_workload_spec.clone(),
+                    ignoring_workload_spec,
+                ],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert_eq!(
+            ready_workload_operations,
+            vec![
+                WorkloadOperation::UpdateDeleteOnly(stopped_deleted_workload.clone()),
+                WorkloadOperation::Update(restarted_workload_spec, restarted_deleted_workload),
+            ]
+        );
+        assert_eq!(workload_scheduler.queue.len(), 1);
+        assert_eq!(
+            workload_scheduler.queue.get(WORKLOAD_NAME_1),
+            Some(&PendingEntry::UpdateCreate(
+                stopped_workload_spec.clone(),
+                stopped_deleted_workload
+            ))
+        );
+        assert_eq!(
+            workload_state_receiver.try_recv(),
+            Ok(generate_test_workload_state_with_workload_spec(
+                &stopped_workload_spec,
+                waiting_to_start_on(UNFULFILLED_TEST_DEPENDENCIES),
+            ))
+        );
+
+        // the stopped workload already waits in the queue and is not stopped again
+        let ready_workload_operations = workload_scheduler
+            .enqueue_workloads_with_failed_dependencies(
+                vec![stopped_workload_spec],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+        assert!(ready_workload_operations.is_empty());
+    }
+
+    // [utest->swdd~agent-detects-dependency-cycles-of-pending-workloads~1]
+    #[tokio::test]
+    async fn utest_enqueue_filtered_workload_operations_drops_dependency_cycle() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, mut workload_state_receiver) = channel(5);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mock_dependency_state_validator_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_context
+            .expect()
+            .return_const(false);
+
+        let pending_workload = |workload_name: &str, dependency_name: &str| {
+            let mut workload = generate_test_workload_spec_with_param(
+                AGENT_A.to_owned(),
+                workload_name.to_owned(),
+                RUNTIME.to_owned(),
+            );
+            workload.dependencies =
+                HashMap::from([(dependency_name.to_owned(), AddCondition::AddCondRunning)]);
+            workload
+        };
+        let workload_1 = pending_workload(WORKLOAD_NAME_1, WORKLOAD_NAME_2);
+        let workload_2 = pending_workload(WORKLOAD_NAME_2, WORKLOAD_NAME_1);
+        // waits on the cycle, but is not part of it
+        let workload_3 = pending_workload(WORKLOAD_NAME_3, WORKLOAD_NAME_1);
+
+        let ready_workload_operations = workload_scheduler
+            .enqueue_filtered_workload_operations(
+                vec![
+                    WorkloadOperation::Create(workload_1.clone()),
+                    WorkloadOperation::Create(workload_2.clone()),
+                    WorkloadOperation::Create(workload_3.clone()),
+                ],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert!(ready_workload_operations.is_empty());
+        for (workload, dependency_name) in [
+            (&workload_1, WORKLOAD_NAME_2),
+            (&workload_2, WORKLOAD_NAME_1),
+            (&workload_3, WORKLOAD_NAME_1),
+        ] {
+            assert_eq!(
+                workload_state_receiver.try_recv(),
+                Ok(generate_test_workload_state_with_workload_spec(
+                    workload,
+                    waiting_to_start_on(&format!(
+                        "{dependency_name}: expected ADD_COND_RUNNING, actual none"
+                    )),
+                ))
+            );
+        }
+        let cycle_path = "workload_1 -> workload_2 -> workload_1";
+        for workload in [&workload_1, &workload_2] {
+            assert_eq!(
+                workload_state_receiver.try_recv(),
+                Ok(generate_test_workload_state_with_workload_spec(
+                    workload,
+                    ExecutionState::dependency_cycle(cycle_path),
+                ))
+            );
+        }
+        assert!(workload_state_receiver.try_recv().is_err());
+        assert_eq!(
+            workload_scheduler.queue.keys().collect::<Vec<_>>(),
+            vec![WORKLOAD_NAME_3]
+        );
+    }
+
+    // [utest->swdd~agent-detects-dependency-cycles-of-pending-workloads~1]
+    #[tokio::test]
+    async fn utest_enqueue_filtered_workload_operations_drops_dependency_cycle_of_expressions() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, mut workload_state_receiver) = channel(10);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mock_dependency_state_validator_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_context
+            .expect()
+            .return_const(false);
+
+        let running = |workload_name: &str| DependencyExpression::Condition {
+            workload: workload_name.to_owned(),
+            condition: AddCondition::AddCondRunning,
+        };
+        let pending_workload = |workload_name: &str, dependency_expression| {
+            let mut workload = generate_test_workload_spec_with_param(
+                AGENT_A.to_owned(),
+                workload_name.to_owned(),
+                RUNTIME.to_owned(),
+            );
+            workload.dependencies = HashMap::new();
+            workload.dependency_expression = Some(dependency_expression);
+            workload
+        };
+        let workload_1 = pending_workload(WORKLOAD_NAME_1, running(WORKLOAD_NAME_2));
+        let workload_2 = pending_workload(
+            WORKLOAD_NAME_2,
+            DependencyExpression::And {
+                and: vec![running(WORKLOAD_NAME_1), running(WORKLOAD_NAME_4)],
+            },
+        );
+        // may start without the cycle once the other dependency is running
+        let workload_3 = pending_workload(
+            WORKLOAD_NAME_3,
+            DependencyExpression::Or {
+                or: vec![running(WORKLOAD_NAME_1), running(WORKLOAD_NAME_4)],
+            },
+        );
+
+        let ready_workload_operations = workload_scheduler
+            .enqueue_filtered_workload_operations(
+                vec![
+                    WorkloadOperation::Create(workload_1.clone()),
+                    WorkloadOperation::Create(workload_2.clone()),
+                    WorkloadOperation::Create(workload_3.clone()),
+                ],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert!(ready_workload_operations.is_empty());
+        let cycle_path = "workload_1 -> workload_2 -> workload_1";
+        let mut dropped_instance_names = Vec::new();
+        while let Ok(workload_state) = workload_state_receiver.try_recv() {
+            if workload_state.execution_state == ExecutionState::dependency_cycle(cycle_path) {
+                dropped_instance_names.push(workload_state.instance_name);
+            }
+        }
+        assert_eq!(
+            dropped_instance_names,
+            vec![workload_1.instance_name, workload_2.instance_name]
+        );
+        assert_eq!(
+            workload_scheduler.queue.keys().collect::<Vec<_>>(),
+            vec![WORKLOAD_NAME_3]
+        );
+    }
+
+    // [utest->swdd~agent-reports-scheduler-queue-to-server~1]
+    #[tokio::test]
+    async fn utest_pending_workload_operations_lists_queue_with_unfulfilled_dependencies() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, _workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let unfulfilled_dependency = |condition: &str| UnfulfilledDependency {
+            workload_name: WORKLOAD_NAME_3.to_owned(),
+            condition: condition.to_owned(),
+        };
+        let mock_create_context =
+            MockDependencyStateValidator::unfulfilled_create_dependencies_context();
+        mock_create_context
+            .expect()
+            .return_const(vec![unfulfilled_dependency("ADD_COND_RUNNING")]);
+        let mock_delete_context =
+            MockDependencyStateValidator::unfulfilled_delete_dependencies_context();
+        mock_delete_context
+            .expect()
+            .return_const(vec![unfulfilled_dependency(
+                "DEL_COND_NOT_PENDING_NOR_RUNNING",
+            )]);
+
+        let workload_1 = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        let deleted_workload_1 =
+            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_1.to_owned());
+        let deleted_workload_2 =
+            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_2.to_owned());
+        workload_scheduler.queue.insert(
+            WORKLOAD_NAME_2.to_owned(),
+            PendingEntry::Delete(deleted_workload_2),
+        );
+        workload_scheduler.queue.insert(
+            WORKLOAD_NAME_1.to_owned(),
+            PendingEntry::UpdateDelete(workload_1, deleted_workload_1),
+        );
+
+        assert_eq!(
+            workload_scheduler.pending_workload_operations(&MockWorkloadStateStore::default()),
+            vec![
+                PendingWorkloadOperation {
+                    workload_name: WORKLOAD_NAME_1.to_owned(),
+                    agent: AGENT_A.to_owned(),
+                    operation: PendingOperation::Update,
+                    unfulfilled_dependencies: vec![
+                        unfulfilled_dependency("DEL_COND_NOT_PENDING_NOR_RUNNING"),
+                        unfulfilled_dependency("ADD_COND_RUNNING"),
+                    ],
+                },
+                PendingWorkloadOperation {
+                    workload_name: WORKLOAD_NAME_2.to_owned(),
+                    agent: AGENT_A.to_owned(),
+                    operation: PendingOperation::Delete,
+                    unfulfilled_dependencies: vec![unfulfilled_dependency(
+                        "DEL_COND_NOT_PENDING_NOR_RUNNING"
+                    )],
+                },
+            ]
+        );
+    }
+
+    // [utest->swdd~agent-subscribes-to-workload-states-of-dependencies~1]
+    #[test]
+    fn utest_dependency_names_of_pending_entries() {
+        let (workload_state_sender, _workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mut workload_1 = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        workload_1.dependencies =
+            HashMap::from([(WORKLOAD_NAME_2.to_owned(), AddCondition::AddCondRunning)]);
+        let mut deleted_workload_3 =
+            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_3.to_owned());
+        deleted_workload_3.dependencies = HashMap::from([(
+            WORKLOAD_NAME_1.to_owned(),
+            DeleteCondition::DelCondNotPendingNorRunning,
+        )]);
+        workload_scheduler
+            .queue
+            .insert(WORKLOAD_NAME_1.to_owned(), PendingEntry::Create(workload_1));
+        workload_scheduler.queue.insert(
+            WORKLOAD_NAME_3.to_owned(),
+            PendingEntry::Delete(deleted_workload_3),
+        );
+
+        let mut dependency_names = workload_scheduler.dependency_names();
+        dependency_names.sort();
+        assert_eq!(
+            dependency_names,
+            vec![WORKLOAD_NAME_1.to_owned(), WORKLOAD_NAME_2.to_owned()]
+        );
+    }
+
+    // [utest->swdd~agent-handles-workloads-with-fulfilled-dependencies~1]
+    #[tokio::test]
+    async fn utest_no_enqueue_and_report_for_ready_create() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, mut workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mock_dependency_state_validator_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_context
+            .expect()
+            .return_const(true);
+
+        let ready_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+
+        let workload_operations = vec![WorkloadOperation::Create(ready_workload.clone())];
+
+        let ready_workload_operations = workload_scheduler
+            .enqueue_filtered_workload_operations(
+                workload_operations,
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert_eq!(
+            vec![WorkloadOperation::Create(ready_workload)],
+            ready_workload_operations
+        );
+
+        assert!(workload_scheduler.queue.is_empty());
+        assert!(workload_state_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~agent-enqueues-unfulfilled-create~1]
+    #[tokio::test]
+    #[should_panic]
+    async fn utest_report_pending_create_state_closed_receiver() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, workload_state_receiver) = channel(1);
+        let workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        drop(workload_state_receiver);
+
+        let pending_workload = generate_test_workload_spec();
+        workload_scheduler
+            .report_pending_create_state(&pending_workload, &MockWorkloadStateStore::default())
+            .await;
+    }
+
+    // [utest->swdd~agent-handles-new-workload-operations]
+    // [utest->swdd~agent-enqueues-unfulfilled-delete~1]
+    #[tokio::test]
+    async fn utest_enqueue_and_report_workload_state_for_pending_delete() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, mut workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mock_dependency_state_validator_context =
+            MockDependencyStateValidator::delete_fulfilled_context();
+        mock_dependency_state_validator_context
+            .expect()
+            .return_const(false);
+
+        let pending_deleted_workload =
+            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_1.to_owned());
+
+        let workload_operations = vec![WorkloadOperation::Delete(pending_deleted_workload.clone())];
+        let ready_workload_operations = workload_scheduler
+            .enqueue_filtered_workload_operations(
+                workload_operations,
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert!(ready_workload_operations.is_empty());
+
+        assert_eq!(
+            Some(&PendingEntry::Delete(pending_deleted_workload.clone())),
+            workload_scheduler
+                .queue
+                .get(pending_deleted_workload.instance_name.workload_name())
+        );
+
+        let expected_workload_state = WorkloadState {
+            instance_name: pending_deleted_workload.instance_name,
+            execution_state: ExecutionState::waiting_to_stop(),
+            agent_timestamp: None,
+            server_timestamp: None,
+        };
+
+        assert_eq!(
+            Ok(Some(expected_workload_state)),
+            tokio::time::timeout(
+                tokio::time::Duration::from_millis(100),
+                workload_state_receiver.recv()
+            )
+            .await
+        );
+    }
+
+    // [utest->swdd~agent-handles-workloads-with-fulfilled-dependencies~1]
+    #[tokio::test]
+    async fn utest_no_enqueue_and_report_workload_state_for_ready_delete() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, mut workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mock_dependency_state_validator_context =
+            MockDependencyStateValidator::delete_fulfilled_context();
+        mock_dependency_state_validator_context
+            .expect()
+            .return_const(true);
+
+        let ready_deleted_workload =
+            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_1.to_owned());
+
+        let workload_operations = vec![WorkloadOperation::Delete(ready_deleted_workload.clone())];
+        let ready_workload_operations = workload_scheduler
+            .enqueue_filtered_workload_operations(
+                workload_operations,
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert_eq!(
+            vec![WorkloadOperation::Delete(ready_deleted_workload)],
+            ready_workload_operations
+        );
+
+        assert!(workload_scheduler.queue.is_empty());
+
+        assert!(workload_state_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~agent-enqueues-unfulfilled-delete~1]
+    #[tokio::test]
+    #[should_panic]
+    async fn utest_report_pending_delete_state_closed_receiver() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, workload_state_receiver) = channel(1);
+        let workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        drop(workload_state_receiver);
+
+        let pending_workload =
+            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_1.to_owned());
+
+        workload_scheduler
+            .report_pending_delete_state(&pending_workload, &MockWorkloadStateStore::default())
+            .await;
+    }
+
+    // [utest->swdd~agent-handles-new-workload-operations]
+    // [utest->swdd~agent-enqueues-update-with-unfulfilled-delete~1]
+    #[tokio::test]
+    async fn utest_enqueue_and_report_workload_state_for_pending_update_delete_at_most_once() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, mut workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mock_dependency_state_validator_create_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_create_context
+            .expect()
+            .return_const(true);
+
+        let mock_dependency_state_validator_delete_context =
+            MockDependencyStateValidator::delete_fulfilled_context();
+        mock_dependency_state_validator_delete_context
+            .expect()
+            .return_const(false);
+
+        let ready_new_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+
+        let pending_deleted_workload = generate_test_deleted_workload(
+            ready_new_workload.instance_name.agent_name().to_owned(),
+            ready_new_workload.instance_name.workload_name().to_owned(),
+        );
+
+        let workload_operations = vec![WorkloadOperation::Update(
+            ready_new_workload.clone(),
+            pending_deleted_workload.clone(),
+        )];
+        let ready_workload_operations = workload_scheduler
             .enqueue_filtered_workload_operations(
                 workload_operations,
                 &MockWorkloadStateStore::default(),
             )
             .await;
 
+        assert!(ready_workload_operations.is_empty());
+
         assert_eq!(
-            vec![WorkloadOperation::Create(ready_workload)],
-            ready_workload_operations
+            Some(&PendingEntry::UpdateDelete(
+                ready_new_workload.clone(),
+                pending_deleted_workload.clone()
+            )),
+            workload_scheduler
+                .queue
+                .get(pending_deleted_workload.instance_name.workload_name())
         );
 
-        assert!(workload_scheduler.queue.is_empty());
-        assert!(workload_state_receiver.try_recv().is_err());
+        let expected_workload_state = WorkloadState {
+            instance_name: pending_deleted_workload.instance_name,
+            execution_state: ExecutionState::waiting_to_stop(),
+            agent_timestamp: None,
+            server_timestamp: None,
+        };
+
+        assert_eq!(
+            Ok(Some(expected_workload_state)),
+            tokio::time::timeout(
+                tokio::time::Duration::from_millis(100),
+                workload_state_receiver.recv()
+            )
+            .await
+        );
     }
 
-    // [utest->swdd~agent-enqueues-unfulfilled-create~1]
+    // [utest->swdd~agent-handles-new-workload-operations]
+    // [utest->swdd~agent-enqueues-update-with-unfulfilled-delete~1]
     #[tokio::test]
-    #[should_panic]
-    async fn utest_report_pending_create_state_closed_receiver() {
+    async fn utest_enqueue_and_report_workload_state_for_pending_update_at_most_once() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
             .get_lock_async()
             .await;
-        let (workload_state_sender, workload_state_receiver) = channel(1);
-        let workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+        let (workload_state_sender, mut workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
 
-        drop(workload_state_receiver);
+        let mock_dependency_state_validator_create_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_create_context
+            .expect()
+            .return_const(false);
 
-        let pending_workload = generate_test_workload_spec();
-        workload_scheduler
-            .report_pending_create_state(&pending_workload.instance_name)
+        let mock_dependency_state_validator_delete_context =
+            MockDependencyStateValidator::delete_fulfilled_context();
+        mock_dependency_state_validator_delete_context
+            .expect()
+            .return_const(false);
+
+        let ready_new_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+
+        let pending_deleted_workload = generate_test_deleted_workload(
+            ready_new_workload.instance_name.agent_name().to_owned(),
+            ready_new_workload.instance_name.workload_name().to_owned(),
+        );
+
+        let workload_operations = vec![WorkloadOperation::Update(
+            ready_new_workload.clone(),
+            pending_deleted_workload.clone(),
+        )];
+        let ready_workload_operations = workload_scheduler
+            .enqueue_filtered_workload_operations(
+                workload_operations,
+                &MockWorkloadStateStore::default(),
+            )
             .await;
+
+        assert!(ready_workload_operations.is_empty());
+
+        assert_eq!(
+            Some(&PendingEntry::UpdateDelete(
+                ready_new_workload.clone(),
+                pending_deleted_workload.clone()
+            )),
+            workload_scheduler
+                .queue
+                .get(pending_deleted_workload.instance_name.workload_name())
+        );
+
+        let expected_workload_state = WorkloadState {
+            instance_name: pending_deleted_workload.instance_name,
+            execution_state: ExecutionState::waiting_to_stop(),
+            agent_timestamp: None,
+            server_timestamp: None,
+        };
+
+        assert_eq!(
+            Ok(Some(expected_workload_state)),
+            tokio::time::timeout(
+                tokio::time::Duration::from_millis(100),
+                workload_state_receiver.recv()
+            )
+            .await
+        );
     }
 
     // [utest->swdd~agent-handles-new-workload-operations]
-    // [utest->swdd~agent-enqueues-unfulfilled-delete~1]
+    // [utest->swdd~agent-handles-update-with-fulfilled-delete~1]
     #[tokio::test]
-    async fn utest_enqueue_and_report_workload_state_for_pending_delete() {
+    async fn utest_enqueue_and_report_workload_state_for_pending_update_create_at_most_once() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
             .get_lock_async()
             .await;
         let (workload_state_sender, mut workload_state_receiver) = channel(1);
         let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
 
-        let mock_dependency_state_validator_context =
-            MockDependencyStateValidator::delete_fulfilled_context();
-        mock_dependency_state_validator_context
+        let mock_dependency_state_validator_create_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_create_context
             .expect()
             .return_const(false);
 
-        let pending_deleted_workload =
-            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_1.to_owned());
+        let mock_dependency_state_validator_delete_context =
+            MockDependencyStateValidator::delete_fulfilled_context();
+        mock_dependency_state_validator_delete_context
+            .expect()
+            .return_const(true);
 
-        let workload_operations = vec![WorkloadOperation::Delete(pending_deleted_workload.clone())];
-        let ready_workload_operations = workload_scheduler
+        let pending_new_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+
+        let ready_deleted_workload = generate_test_deleted_workload(
+            pending_new_workload.instance_name.agent_name().to_owned(),
+            pending_new_workload
+                .instance_name
+                .workload_name()
+                .to_owned(),
+        );
+
+        let workload_operations = vec![WorkloadOperation::Update(
+            pending_new_workload.clone(),
+            ready_deleted_workload.clone(),
+        )];
+
+        workload_scheduler
             .enqueue_filtered_workload_operations(
                 workload_operations,
                 &MockWorkloadStateStore::default(),
             )
             .await;
 
-        assert!(ready_workload_operations.is_empty());
-
         assert_eq!(
-            Some(&PendingEntry::Delete(pending_deleted_workload.clone())),
+            Some(&PendingEntry::UpdateCreate(
+                pending_new_workload.clone(),
+                ready_deleted_workload.clone()
+            )),
             workload_scheduler
                 .queue
-                .get(pending_deleted_workload.instance_name.workload_name())
+                .get(pending_new_workload.instance_name.workload_name())
         );
 
         let expected_workload_state = WorkloadState {
-            instance_name: pending_deleted_workload.instance_name,
-            execution_state: ExecutionState::waiting_to_stop(),
+            instance_name: pending_new_workload.instance_name,
+            execution_state: waiting_to_start_on(UNFULFILLED_TEST_DEPENDENCIES),
+            agent_timestamp: None,
+            server_timestamp: None,
         };
 
         assert_eq!(
@@ -508,25 +2214,141 @@ mod tests {
         );
     }
 
-    // [utest->swdd~agent-handles-workloads-with-fulfilled-dependencies~1]
+    // [utest->swdd~agent-handles-update-with-at-least-once-strategy~1]
     #[tokio::test]
-    async fn utest_no_enqueue_and_report_workload_state_for_ready_delete() {
+    async fn utest_ready_update_at_least_once_ignores_delete_dependencies() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
             .get_lock_async()
             .await;
         let (workload_state_sender, mut workload_state_receiver) = channel(1);
         let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
 
-        let mock_dependency_state_validator_context =
+        // the delete dependencies are not evaluated, the old workload keeps running
+        let mock_dependency_state_validator_create_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_create_context
+            .expect()
+            .return_const(true);
+
+        let mut new_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        new_workload.update_strategy = UpdateStrategy::AtLeastOnce;
+        let deleted_workload =
+            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_1.to_owned());
+
+        let ready_workload_operations = workload_scheduler
+            .enqueue_filtered_workload_operations(
+                vec![WorkloadOperation::Update(
+                    new_workload.clone(),
+                    deleted_workload.clone(),
+                )],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert_eq!(
+            ready_workload_operations,
+            vec![WorkloadOperation::Update(new_workload, deleted_workload)]
+        );
+        assert!(workload_scheduler.queue.is_empty());
+        assert!(workload_state_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~agent-handles-update-with-at-least-once-strategy~1]
+    #[tokio::test]
+    async fn utest_enqueue_pending_update_at_least_once_without_delete() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, mut workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mock_dependency_state_validator_create_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_create_context
+            .expect()
+            .return_const(false);
+
+        let mut new_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        new_workload.update_strategy = UpdateStrategy::AtLeastOnce;
+        let deleted_workload =
+            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_1.to_owned());
+
+        let ready_workload_operations = workload_scheduler
+            .enqueue_filtered_workload_operations(
+                vec![WorkloadOperation::Update(
+                    new_workload.clone(),
+                    deleted_workload.clone(),
+                )],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        // no delete only operation, the old workload keeps running while the update is pending
+        assert!(ready_workload_operations.is_empty());
+        assert_eq!(
+            workload_scheduler.queue.get(WORKLOAD_NAME_1),
+            Some(&PendingEntry::UpdateCreate(
+                new_workload.clone(),
+                deleted_workload
+            ))
+        );
+        assert_eq!(
+            workload_state_receiver.try_recv(),
+            Ok(generate_test_workload_state_with_workload_spec(
+                &new_workload,
+                waiting_to_start_on(UNFULFILLED_TEST_DEPENDENCIES),
+            ))
+        );
+    }
+
+    // [utest->swdd~agent-handles-update-with-fulfilled-delete~1]
+    #[tokio::test]
+    async fn utest_immediate_delete_for_pending_update_create_at_most_once() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, _workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mock_dependency_state_validator_create_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_create_context
+            .expect()
+            .return_const(false);
+
+        let mock_dependency_state_validator_delete_context =
             MockDependencyStateValidator::delete_fulfilled_context();
-        mock_dependency_state_validator_context
+        mock_dependency_state_validator_delete_context
             .expect()
             .return_const(true);
 
-        let ready_deleted_workload =
-            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_1.to_owned());
+        let pending_new_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+
+        let ready_deleted_workload = generate_test_deleted_workload(
+            pending_new_workload.instance_name.agent_name().to_owned(),
+            pending_new_workload
+                .instance_name
+                .workload_name()
+                .to_owned(),
+        );
+
+        let workload_operations = vec![WorkloadOperation::Update(
+            pending_new_workload,
+            ready_deleted_workload.clone(),
+        )];
 
-        let workload_operations = vec![WorkloadOperation::Delete(ready_deleted_workload.clone())];
         let ready_workload_operations = workload_scheduler
             .enqueue_filtered_workload_operations(
                 workload_operations,
@@ -535,39 +2357,14 @@ mod tests {
             .await;
 
         assert_eq!(
-            vec![WorkloadOperation::Delete(ready_deleted_workload)],
+            vec![WorkloadOperation::UpdateDeleteOnly(ready_deleted_workload)],
             ready_workload_operations
         );
-
-        assert!(workload_scheduler.queue.is_empty());
-
-        assert!(workload_state_receiver.try_recv().is_err());
-    }
-
-    // [utest->swdd~agent-enqueues-unfulfilled-delete~1]
-    #[tokio::test]
-    #[should_panic]
-    async fn utest_report_pending_delete_state_closed_receiver() {
-        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
-            .get_lock_async()
-            .await;
-        let (workload_state_sender, workload_state_receiver) = channel(1);
-        let workload_scheduler = WorkloadScheduler::new(workload_state_sender);
-
-        drop(workload_state_receiver);
-
-        let pending_workload =
-            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_1.to_owned());
-
-        workload_scheduler
-            .report_pending_delete_state(&pending_workload.instance_name)
-            .await;
     }
 
-    // [utest->swdd~agent-handles-new-workload-operations]
-    // [utest->swdd~agent-enqueues-update-with-unfulfilled-delete~1]
+    // [utest->swdd~agent-handles-workloads-with-fulfilled-dependencies~1]
     #[tokio::test]
-    async fn utest_enqueue_and_report_workload_state_for_pending_update_delete_at_most_once() {
+    async fn utest_no_enqueue_and_report_pending_state_on_fulfilled_update_at_most_once() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
             .get_lock_async()
             .await;
@@ -584,7 +2381,7 @@ mod tests {
             MockDependencyStateValidator::delete_fulfilled_context();
         mock_dependency_state_validator_delete_context
             .expect()
-            .return_const(false);
+            .return_const(true);
 
         let ready_new_workload = generate_test_workload_spec_with_param(
             AGENT_A.to_owned(),
@@ -592,14 +2389,14 @@ mod tests {
             RUNTIME.to_owned(),
         );
 
-        let pending_deleted_workload = generate_test_deleted_workload(
+        let ready_deleted_workload = generate_test_deleted_workload(
             ready_new_workload.instance_name.agent_name().to_owned(),
             ready_new_workload.instance_name.workload_name().to_owned(),
         );
 
         let workload_operations = vec![WorkloadOperation::Update(
             ready_new_workload.clone(),
-            pending_deleted_workload.clone(),
+            ready_deleted_workload.clone(),
         )];
         let ready_workload_operations = workload_scheduler
             .enqueue_filtered_workload_operations(
@@ -608,54 +2405,34 @@ mod tests {
             )
             .await;
 
-        assert!(ready_workload_operations.is_empty());
-
         assert_eq!(
-            Some(&PendingEntry::UpdateDelete(
-                ready_new_workload.clone(),
-                pending_deleted_workload.clone()
-            )),
-            workload_scheduler
-                .queue
-                .get(pending_deleted_workload.instance_name.workload_name())
+            vec![WorkloadOperation::Update(
+                ready_new_workload,
+                ready_deleted_workload
+            )],
+            ready_workload_operations
         );
 
-        let expected_workload_state = WorkloadState {
-            instance_name: pending_deleted_workload.instance_name,
-            execution_state: ExecutionState::waiting_to_stop(),
-        };
+        assert!(workload_scheduler.queue.is_empty());
 
-        assert_eq!(
-            Ok(Some(expected_workload_state)),
-            tokio::time::timeout(
-                tokio::time::Duration::from_millis(100),
-                workload_state_receiver.recv()
-            )
-            .await
-        );
+        assert!(workload_state_receiver.try_recv().is_err());
     }
 
-    // [utest->swdd~agent-handles-new-workload-operations]
-    // [utest->swdd~agent-enqueues-update-with-unfulfilled-delete~1]
+    // [utest->swdd~agent-handles-workloads-with-fulfilled-dependencies~1]
     #[tokio::test]
-    async fn utest_enqueue_and_report_workload_state_for_pending_update_at_most_once() {
+    async fn utest_enqueue_filtered_workload_operations_get_next_ready_workload_operations() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
             .get_lock_async()
             .await;
-        let (workload_state_sender, mut workload_state_receiver) = channel(1);
+        let (workload_state_sender, _workload_state_receiver) = channel(1);
         let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
 
         let mock_dependency_state_validator_create_context =
             MockDependencyStateValidator::create_fulfilled_context();
         mock_dependency_state_validator_create_context
             .expect()
-            .return_const(false);
-
-        let mock_dependency_state_validator_delete_context =
-            MockDependencyStateValidator::delete_fulfilled_context();
-        mock_dependency_state_validator_delete_context
-            .expect()
-            .return_const(false);
+            .once()
+            .return_const(true);
 
         let ready_new_workload = generate_test_workload_spec_with_param(
             AGENT_A.to_owned(),
@@ -663,15 +2440,18 @@ mod tests {
             RUNTIME.to_owned(),
         );
 
-        let pending_deleted_workload = generate_test_deleted_workload(
+        let ready_deleted_workload = generate_test_deleted_workload(
             ready_new_workload.instance_name.agent_name().to_owned(),
             ready_new_workload.instance_name.workload_name().to_owned(),
         );
 
-        let workload_operations = vec![WorkloadOperation::Update(
-            ready_new_workload.clone(),
-            pending_deleted_workload.clone(),
-        )];
+        let workload_operations = vec![];
+
+        workload_scheduler.queue.insert(
+            ready_new_workload.instance_name.workload_name().to_owned(),
+            PendingEntry::UpdateCreate(ready_new_workload.clone(), ready_deleted_workload.clone()),
+        );
+
         let ready_workload_operations = workload_scheduler
             .enqueue_filtered_workload_operations(
                 workload_operations,
@@ -679,37 +2459,82 @@ mod tests {
             )
             .await;
 
-        assert!(ready_workload_operations.is_empty());
-
         assert_eq!(
-            Some(&PendingEntry::UpdateDelete(
-                ready_new_workload.clone(),
-                pending_deleted_workload.clone()
-            )),
-            workload_scheduler
-                .queue
-                .get(pending_deleted_workload.instance_name.workload_name())
+            vec![WorkloadOperation::Update(
+                ready_new_workload,
+                ready_deleted_workload
+            )],
+            ready_workload_operations
         );
 
-        let expected_workload_state = WorkloadState {
-            instance_name: pending_deleted_workload.instance_name,
-            execution_state: ExecutionState::waiting_to_stop(),
-        };
+        assert!(workload_scheduler.queue.is_empty());
+    }
 
-        assert_eq!(
-            Ok(Some(expected_workload_state)),
-            tokio::time::timeout(
-                tokio::time::Duration::from_millis(100),
-                workload_state_receiver.recv()
+    // [utest->swdd~agent-orders-initial-workload-operations-topologically~1]
+    #[tokio::test]
+    async fn utest_enqueue_initial_workload_operations_in_topological_order() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, _workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        // only the new workload operations are evaluated, not the entry already in the queue
+        let mock_dependency_state_validator_create_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_create_context
+            .expect()
+            .times(2)
+            .return_const(true);
+
+        let queued_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        workload_scheduler.queue.insert(
+            WORKLOAD_NAME_1.to_owned(),
+            PendingEntry::Create(queued_workload),
+        );
+
+        let mut dependent_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_2.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        dependent_workload.dependencies =
+            HashMap::from([(WORKLOAD_NAME_3.to_owned(), AddCondition::AddCondRunning)]);
+        let mut dependency_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_3.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        dependency_workload.dependencies.clear();
+
+        let ready_workload_operations = workload_scheduler
+            .enqueue_initial_workload_operations(
+                vec![
+                    WorkloadOperation::Create(dependent_workload.clone()),
+                    WorkloadOperation::Create(dependency_workload.clone()),
+                ],
+                &MockWorkloadStateStore::default(),
             )
-            .await
+            .await;
+
+        assert_eq!(
+            ready_workload_operations,
+            vec![
+                WorkloadOperation::Create(dependency_workload),
+                WorkloadOperation::Create(dependent_workload),
+            ]
         );
+        assert!(workload_scheduler.queue.contains_key(WORKLOAD_NAME_1));
     }
 
-    // [utest->swdd~agent-handles-new-workload-operations]
-    // [utest->swdd~agent-handles-update-with-fulfilled-delete~1]
+    // [utest->swdd~agent-cancels-superseded-pending-creates~1]
+    // [utest->swdd~agent-cancels-pending-workload-operations~1]
     #[tokio::test]
-    async fn utest_enqueue_and_report_workload_state_for_pending_update_create_at_most_once() {
+    async fn utest_enqueue_filtered_workload_operations_cancels_superseded_pending_create() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
             .get_lock_async()
             .await;
@@ -720,34 +2545,39 @@ mod tests {
             MockDependencyStateValidator::create_fulfilled_context();
         mock_dependency_state_validator_create_context
             .expect()
-            .return_const(false);
-
+            .return_const(true);
         let mock_dependency_state_validator_delete_context =
             MockDependencyStateValidator::delete_fulfilled_context();
         mock_dependency_state_validator_delete_context
             .expect()
             .return_const(true);
 
-        let pending_new_workload = generate_test_workload_spec_with_param(
+        let pending_workload = generate_test_workload_spec_with_param(
             AGENT_A.to_owned(),
             WORKLOAD_NAME_1.to_owned(),
             RUNTIME.to_owned(),
         );
-
-        let ready_deleted_workload = generate_test_deleted_workload(
-            pending_new_workload.instance_name.agent_name().to_owned(),
-            pending_new_workload
-                .instance_name
-                .workload_name()
-                .to_owned(),
+        workload_scheduler.queue.insert(
+            WORKLOAD_NAME_1.to_owned(),
+            PendingEntry::Create(pending_workload.clone()),
         );
 
+        let updated_workload = generate_test_workload_spec_with_runtime_config(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+            "image: alpine:1.0".to_owned(),
+        );
+        let deleted_workload = DeletedWorkload {
+            instance_name: pending_workload.instance_name.clone(),
+            dependencies: HashMap::new(),
+        };
         let workload_operations = vec![WorkloadOperation::Update(
-            pending_new_workload.clone(),
-            ready_deleted_workload.clone(),
+            updated_workload.clone(),
+            deleted_workload.clone(),
         )];
 
-        workload_scheduler
+        let ready_workload_operations = workload_scheduler
             .enqueue_filtered_workload_operations(
                 workload_operations,
                 &MockWorkloadStateStore::default(),
@@ -755,22 +2585,18 @@ mod tests {
             .await;
 
         assert_eq!(
-            Some(&PendingEntry::UpdateCreate(
-                pending_new_workload.clone(),
-                ready_deleted_workload.clone()
-            )),
-            workload_scheduler
-                .queue
-                .get(pending_new_workload.instance_name.workload_name())
+            vec![WorkloadOperation::Update(
+                updated_workload,
+                deleted_workload
+            )],
+            ready_workload_operations
         );
-
-        let expected_workload_state = WorkloadState {
-            instance_name: pending_new_workload.instance_name,
-            execution_state: ExecutionState::waiting_to_start(),
-        };
-
+        assert!(workload_scheduler.queue.is_empty());
         assert_eq!(
-            Ok(Some(expected_workload_state)),
+            Ok(Some(generate_test_workload_state_with_workload_spec(
+                &pending_workload,
+                ExecutionState::removed(),
+            ))),
             tokio::time::timeout(
                 tokio::time::Duration::from_millis(100),
                 workload_state_receiver.recv()
@@ -779,43 +2605,66 @@ mod tests {
         );
     }
 
-    // [utest->swdd~agent-handles-update-with-fulfilled-delete~1]
+    // [utest->swdd~agent-cancels-superseded-pending-creates~1]
     #[tokio::test]
-    async fn utest_immediate_delete_for_pending_update_create_at_most_once() {
+    async fn utest_enqueue_filtered_workload_operations_drops_pending_create_of_deleted_workload() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
             .get_lock_async()
             .await;
-        let (workload_state_sender, _workload_state_receiver) = channel(1);
+        let (workload_state_sender, mut workload_state_receiver) = channel(1);
         let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
 
-        let mock_dependency_state_validator_create_context =
-            MockDependencyStateValidator::create_fulfilled_context();
-        mock_dependency_state_validator_create_context
-            .expect()
-            .return_const(false);
-
         let mock_dependency_state_validator_delete_context =
             MockDependencyStateValidator::delete_fulfilled_context();
         mock_dependency_state_validator_delete_context
             .expect()
             .return_const(true);
 
-        let pending_new_workload = generate_test_workload_spec_with_param(
+        let pending_workload = generate_test_workload_spec_with_param(
             AGENT_A.to_owned(),
             WORKLOAD_NAME_1.to_owned(),
             RUNTIME.to_owned(),
         );
+        workload_scheduler.queue.insert(
+            WORKLOAD_NAME_1.to_owned(),
+            PendingEntry::Create(pending_workload.clone()),
+        );
 
-        let ready_deleted_workload = generate_test_deleted_workload(
-            pending_new_workload.instance_name.agent_name().to_owned(),
-            pending_new_workload
-                .instance_name
-                .workload_name()
-                .to_owned(),
+        let deleted_workload = DeletedWorkload {
+            instance_name: pending_workload.instance_name.clone(),
+            dependencies: HashMap::new(),
+        };
+
+        let ready_workload_operations = workload_scheduler
+            .enqueue_filtered_workload_operations(
+                vec![WorkloadOperation::Delete(deleted_workload.clone())],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert_eq!(
+            vec![WorkloadOperation::Delete(deleted_workload)],
+            ready_workload_operations
         );
+        assert!(workload_scheduler.queue.is_empty());
+        // the removal is reported when the delete is executed
+        assert!(workload_state_receiver.try_recv().is_err());
+    }
 
-        let workload_operations = vec![WorkloadOperation::Update(
-            pending_new_workload,
+    // [utest->swdd~agent-shall-not-enqueue-update-delete-only-workload-operation~1]
+    #[tokio::test]
+    async fn utest_enqueue_filtered_workload_operations_ignore_update_delete_only_workload_operations(
+    ) {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, _workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let ready_deleted_workload =
+            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_1.to_owned());
+
+        let workload_operations = vec![WorkloadOperation::UpdateDeleteOnly(
             ready_deleted_workload.clone(),
         )];
 
@@ -826,19 +2675,18 @@ mod tests {
             )
             .await;
 
-        assert_eq!(
-            vec![WorkloadOperation::UpdateDeleteOnly(ready_deleted_workload)],
-            ready_workload_operations
-        );
+        assert!(ready_workload_operations.is_empty());
+
+        assert!(workload_scheduler.queue.is_empty());
     }
 
-    // [utest->swdd~agent-handles-workloads-with-fulfilled-dependencies~1]
+    // [utest->swdd~agent-releases-ready-workload-operations-by-priority~1]
     #[tokio::test]
-    async fn utest_no_enqueue_and_report_pending_state_on_fulfilled_update_at_most_once() {
+    async fn utest_next_workload_operations_sorted_by_priority() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
             .get_lock_async()
             .await;
-        let (workload_state_sender, mut workload_state_receiver) = channel(1);
+        let (workload_state_sender, _workload_state_receiver) = channel(1);
         let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
 
         let mock_dependency_state_validator_create_context =
@@ -853,44 +2701,50 @@ mod tests {
             .expect()
             .return_const(true);
 
-        let ready_new_workload = generate_test_workload_spec_with_param(
-            AGENT_A.to_owned(),
+        let workload_spec_with_priority = |workload_name: &str, priority: u8| {
+            let mut workload_spec = generate_test_workload_spec_with_param(
+                AGENT_A.to_owned(),
+                workload_name.to_owned(),
+                RUNTIME.to_owned(),
+            );
+            workload_spec.priority = priority;
+            workload_spec
+        };
+        let best_effort_workload = workload_spec_with_priority(WORKLOAD_NAME_1, 0);
+        let safety_workload = workload_spec_with_priority(WORKLOAD_NAME_2, 200);
+        let deleted_workload =
+            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_3.to_owned());
+
+        workload_scheduler.queue.insert(
             WORKLOAD_NAME_1.to_owned(),
-            RUNTIME.to_owned(),
+            PendingEntry::Create(best_effort_workload.clone()),
         );
-
-        let ready_deleted_workload = generate_test_deleted_workload(
-            ready_new_workload.instance_name.agent_name().to_owned(),
-            ready_new_workload.instance_name.workload_name().to_owned(),
+        workload_scheduler.queue.insert(
+            WORKLOAD_NAME_2.to_owned(),
+            PendingEntry::Create(safety_workload.clone()),
+        );
+        workload_scheduler.queue.insert(
+            WORKLOAD_NAME_3.to_owned(),
+            PendingEntry::Delete(deleted_workload),
         );
 
-        let workload_operations = vec![WorkloadOperation::Update(
-            ready_new_workload.clone(),
-            ready_deleted_workload.clone(),
-        )];
         let ready_workload_operations = workload_scheduler
-            .enqueue_filtered_workload_operations(
-                workload_operations,
-                &MockWorkloadStateStore::default(),
-            )
+            .next_workload_operations(&MockWorkloadStateStore::default())
             .await;
 
+        assert_eq!(ready_workload_operations.len(), 3);
         assert_eq!(
-            vec![WorkloadOperation::Update(
-                ready_new_workload,
-                ready_deleted_workload
-            )],
-            ready_workload_operations
+            ready_workload_operations[0],
+            WorkloadOperation::Create(safety_workload)
         );
-
-        assert!(workload_scheduler.queue.is_empty());
-
-        assert!(workload_state_receiver.try_recv().is_err());
+        assert!(ready_workload_operations[1..]
+            .iter()
+            .all(|workload_operation| workload_operation.priority() == 0));
     }
 
-    // [utest->swdd~agent-handles-workloads-with-fulfilled-dependencies~1]
+    // [utest->swdd~agent-releases-ready-workload-operations-by-priority~1]
     #[tokio::test]
-    async fn utest_enqueue_filtered_workload_operations_get_next_ready_workload_operations() {
+    async fn utest_enqueue_filtered_workload_operations_sorted_by_priority() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
             .get_lock_async()
             .await;
@@ -901,72 +2755,44 @@ mod tests {
             MockDependencyStateValidator::create_fulfilled_context();
         mock_dependency_state_validator_create_context
             .expect()
-            .once()
             .return_const(true);
 
-        let ready_new_workload = generate_test_workload_spec_with_param(
-            AGENT_A.to_owned(),
-            WORKLOAD_NAME_1.to_owned(),
-            RUNTIME.to_owned(),
-        );
-
-        let ready_deleted_workload = generate_test_deleted_workload(
-            ready_new_workload.instance_name.agent_name().to_owned(),
-            ready_new_workload.instance_name.workload_name().to_owned(),
-        );
-
-        let workload_operations = vec![];
+        let workload_spec_with_priority = |workload_name: &str, priority: u8| {
+            let mut workload_spec = generate_test_workload_spec_with_param(
+                AGENT_A.to_owned(),
+                workload_name.to_owned(),
+                RUNTIME.to_owned(),
+            );
+            workload_spec.priority = priority;
+            workload_spec
+        };
+        let best_effort_workload = workload_spec_with_priority(WORKLOAD_NAME_1, 0);
+        let safety_workload = workload_spec_with_priority(WORKLOAD_NAME_2, 200);
+        let pending_workload = workload_spec_with_priority(WORKLOAD_NAME_3, 100);
 
         workload_scheduler.queue.insert(
-            ready_new_workload.instance_name.workload_name().to_owned(),
-            PendingEntry::UpdateCreate(ready_new_workload.clone(), ready_deleted_workload.clone()),
+            WORKLOAD_NAME_3.to_owned(),
+            PendingEntry::Create(pending_workload.clone()),
         );
 
         let ready_workload_operations = workload_scheduler
             .enqueue_filtered_workload_operations(
-                workload_operations,
+                vec![
+                    WorkloadOperation::Create(best_effort_workload.clone()),
+                    WorkloadOperation::Create(safety_workload.clone()),
+                ],
                 &MockWorkloadStateStore::default(),
             )
             .await;
 
         assert_eq!(
-            vec![WorkloadOperation::Update(
-                ready_new_workload,
-                ready_deleted_workload
-            )],
+            vec![
+                WorkloadOperation::Create(safety_workload),
+                WorkloadOperation::Create(pending_workload),
+                WorkloadOperation::Create(best_effort_workload),
+            ],
             ready_workload_operations
         );
-
-        assert!(workload_scheduler.queue.is_empty());
-    }
-
-    // [utest->swdd~agent-shall-not-enqueue-update-delete-only-workload-operation~1]
-    #[tokio::test]
-    async fn utest_enqueue_filtered_workload_operations_ignore_update_delete_only_workload_operations(
-    ) {
-        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
-            .get_lock_async()
-            .await;
-        let (workload_state_sender, _workload_state_receiver) = channel(1);
-        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
-
-        let ready_deleted_workload =
-            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_1.to_owned());
-
-        let workload_operations = vec![WorkloadOperation::UpdateDeleteOnly(
-            ready_deleted_workload.clone(),
-        )];
-
-        let ready_workload_operations = workload_scheduler
-            .enqueue_filtered_workload_operations(
-                workload_operations,
-                &MockWorkloadStateStore::default(),
-            )
-            .await;
-
-        assert!(ready_workload_operations.is_empty());
-
-        assert!(workload_scheduler.queue.is_empty());
     }
 
     // [utest->swdd~agent-handles-update-with-fulfilled-delete~1]
@@ -1080,7 +2906,7 @@ mod tests {
             workload_state_receiver,
             vec![(
                 &instance_name_new_workload,
-                ExecutionState::waiting_to_start(),
+                waiting_to_start_on(UNFULFILLED_TEST_DEPENDENCIES),
             )],
         )
         .await;
@@ -1194,6 +3020,187 @@ mod tests {
             .contains_key(instance_name_create_workload.workload_name()));
     }
 
+    // [utest->swdd~agent-collects-scheduler-metrics~1]
+    #[tokio::test]
+    async fn utest_scheduler_metrics_track_queue_size_and_released_operations() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, _workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mock_dependency_state_validator_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_context
+            .expect()
+            .return_const(false);
+
+        let pending_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+
+        workload_scheduler
+            .enqueue_filtered_workload_operations(
+                vec![WorkloadOperation::Create(pending_workload.clone())],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert_eq!(workload_scheduler.metrics.queue_size, 1);
+        assert_eq!(workload_scheduler.metrics.released_operations.create, 0);
+
+        drop(mock_dependency_state_validator_context);
+        let mock_dependency_state_validator_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_context
+            .expect()
+            .return_const(true);
+
+        let ready_workload_operations = workload_scheduler
+            .next_workload_operations(&MockWorkloadStateStore::default())
+            .await;
+
+        assert_eq!(
+            vec![WorkloadOperation::Create(pending_workload)],
+            ready_workload_operations
+        );
+        assert_eq!(workload_scheduler.metrics.queue_size, 0);
+        assert_eq!(workload_scheduler.metrics.max_queue_size, 1);
+        assert_eq!(workload_scheduler.metrics.released_operations.create, 1);
+        assert_eq!(workload_scheduler.metrics.pending_duration.count(), 1);
+        assert!(workload_scheduler.pending_since.is_empty());
+    }
+
+    // [utest->swdd~agent-reevaluates-only-dependents-of-changed-workloads~1]
+    #[tokio::test]
+    async fn utest_next_workload_operations_of_dependents_evaluates_only_dependents() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, _workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mock_dependency_state_validator_create_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_create_context
+            .expect()
+            .once()
+            .return_const(true);
+
+        let mut dependent_workload_spec = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        dependent_workload_spec.dependencies =
+            HashMap::from([(WORKLOAD_NAME_3.to_owned(), AddCondition::AddCondRunning)]);
+        let mut other_workload_spec = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_2.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        other_workload_spec.dependencies =
+            HashMap::from([(WORKLOAD_NAME_1.to_owned(), AddCondition::AddCondRunning)]);
+
+        let workload_state_db = MockWorkloadStateStore::default();
+        workload_scheduler.put_on_queue(
+            WORKLOAD_NAME_1,
+            PendingEntry::Create(dependent_workload_spec.clone()),
+            &workload_state_db,
+        );
+        workload_scheduler.put_on_queue(
+            WORKLOAD_NAME_2,
+            PendingEntry::Create(other_workload_spec.clone()),
+            &workload_state_db,
+        );
+
+        let ready_workload_operations = workload_scheduler
+            .next_workload_operations_of_dependents(
+                &[WORKLOAD_NAME_3.to_owned()],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert_eq!(
+            ready_workload_operations,
+            vec![WorkloadOperation::Create(dependent_workload_spec)]
+        );
+        assert!(!workload_scheduler.queue.contains_key(WORKLOAD_NAME_1));
+        assert!(workload_scheduler.queue.contains_key(WORKLOAD_NAME_2));
+    }
+
+    // [utest->swdd~agent-reevaluates-only-dependents-of-changed-workloads~1]
+    #[tokio::test]
+    async fn utest_next_workload_operations_of_dependents_skips_entries_no_longer_queued() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, _workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mock_dependency_state_validator_create_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_create_context
+            .expect()
+            .never();
+
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        workload_spec.dependencies =
+            HashMap::from([(WORKLOAD_NAME_3.to_owned(), AddCondition::AddCondRunning)]);
+        workload_scheduler.put_on_queue(
+            WORKLOAD_NAME_1,
+            PendingEntry::Create(workload_spec),
+            &MockWorkloadStateStore::default(),
+        );
+        workload_scheduler.queue.remove(WORKLOAD_NAME_1);
+
+        let ready_workload_operations = workload_scheduler
+            .next_workload_operations_of_dependents(
+                &[WORKLOAD_NAME_3.to_owned(), WORKLOAD_NAME_2.to_owned()],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert!(ready_workload_operations.is_empty());
+        assert!(workload_scheduler.dependents.is_empty());
+    }
+
+    // [utest->swdd~agent-reevaluates-only-dependents-of-changed-workloads~1]
+    // [utest->swdd~agent-evaluates-dependency-expression~1]
+    #[tokio::test]
+    async fn utest_put_on_queue_registers_dependents_of_dependency_expression() {
+        let (workload_state_sender, _workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        workload_spec.dependencies =
+            HashMap::from([(WORKLOAD_NAME_2.to_owned(), AddCondition::AddCondRunning)]);
+        workload_spec.dependency_expression = Some(DependencyExpression::Not {
+            not: Box::new(DependencyExpression::Condition {
+                workload: WORKLOAD_NAME_3.to_owned(),
+                condition: AddCondition::AddCondRunning,
+            }),
+        });
+        workload_scheduler.put_on_queue(
+            WORKLOAD_NAME_1,
+            PendingEntry::Create(workload_spec),
+            &MockWorkloadStateStore::default(),
+        );
+
+        assert!(workload_scheduler.dependents[WORKLOAD_NAME_2].contains(WORKLOAD_NAME_1));
+        assert!(workload_scheduler.dependents[WORKLOAD_NAME_3].contains(WORKLOAD_NAME_1));
+    }
+
     // [utest->swdd~agent-keeps-workloads-with-unfulfilled-workload-dependencies-in-queue~1]
     #[tokio::test]
     async fn utest_next_workload_operations_no_report_pending_create_on_reenqueue() {
@@ -1617,4 +3624,110 @@ mod tests {
 
         assert!(workload_scheduler.queue.is_empty());
     }
+
+    // [utest->swdd~agent-persists-pending-workload-operations~1]
+    #[tokio::test]
+    async fn utest_enqueue_persists_pending_operations() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+        let (workload_state_sender, _workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let mock_dependency_state_validator_context =
+            MockDependencyStateValidator::create_fulfilled_context();
+        mock_dependency_state_validator_context
+            .expect()
+            .return_const(false);
+
+        let run_folder = tempfile::tempdir().unwrap();
+        let queue_storage = QueueStorage::new(
+            run_folder.path().join(QUEUE_FILE_NAME),
+            PersistenceFormat::Bincode,
+        );
+        workload_scheduler.restore_queue(queue_storage.clone());
+
+        let pending_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+
+        workload_scheduler
+            .enqueue_filtered_workload_operations(
+                vec![WorkloadOperation::Create(pending_workload.clone())],
+                &MockWorkloadStateStore::default(),
+            )
+            .await;
+
+        assert_eq!(
+            queue_storage.load::<WorkloadOperationQueue>(),
+            Ok(HashMap::from([(
+                WORKLOAD_NAME_1.to_owned(),
+                PendingEntry::Create(pending_workload)
+            )]))
+        );
+    }
+
+    // [utest->swdd~agent-restores-pending-workload-operations~1]
+    // [utest->swdd~agent-reconciles-restored-pending-workload-operations~1]
+    #[test]
+    fn utest_reconcile_restored_queue_keeps_deletes_of_workloads_not_added() {
+        let (workload_state_sender, _workload_state_receiver) = channel(1);
+        let mut workload_scheduler = WorkloadScheduler::new(workload_state_sender);
+
+        let added_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        let updated_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_2.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        let updated_deleted_workload =
+            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_2.to_owned());
+        let deleted_workload =
+            generate_test_deleted_workload(AGENT_A.to_owned(), WORKLOAD_NAME_3.to_owned());
+
+        let run_folder = tempfile::tempdir().unwrap();
+        let queue_storage = QueueStorage::new(
+            run_folder.path().join(QUEUE_FILE_NAME),
+            PersistenceFormat::Cbor,
+        );
+        queue_storage
+            .store(&WorkloadOperationQueue::from([
+                (
+                    WORKLOAD_NAME_1.to_owned(),
+                    PendingEntry::Create(added_workload.clone()),
+                ),
+                (
+                    WORKLOAD_NAME_2.to_owned(),
+                    PendingEntry::UpdateDelete(updated_workload, updated_deleted_workload.clone()),
+                ),
+                (
+                    WORKLOAD_NAME_3.to_owned(),
+                    PendingEntry::Delete(deleted_workload.clone()),
+                ),
+            ]))
+            .unwrap();
+
+        workload_scheduler.restore_queue(queue_storage);
+        assert_eq!(workload_scheduler.restored_queue.len(), 3);
+
+        let mut pending_deletes = workload_scheduler.reconcile_restored_queue(&[added_workload]);
+        pending_deletes.sort_by(|a, b| {
+            a.instance_name
+                .workload_name()
+                .cmp(b.instance_name.workload_name())
+        });
+
+        assert_eq!(
+            pending_deletes,
+            vec![updated_deleted_workload, deleted_workload]
+        );
+        assert!(workload_scheduler.restored_queue.is_empty());
+        assert!(workload_scheduler.queue.is_empty());
+    }
 }
@@ -0,0 +1,183 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, time::Duration};
+
+use common::objects::{WorkloadInstanceName, WorkloadSpec};
+use tokio::time::Instant;
+
+struct WatchedStart {
+    instance_name: WorkloadInstanceName,
+    expected_startup_time_ms: u64,
+    deadline: Instant,
+}
+
+// [impl->swdd~agent-reports-slow-workload-starts~1]
+// The started workloads with an expected startup time that are not running yet.
+#[derive(Default)]
+pub struct StartupWatch {
+    watched_starts: HashMap<String, WatchedStart>,
+}
+
+impl StartupWatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // A workload without an expected startup time is not watched.
+    pub fn watch(&mut self, workload_spec: &WorkloadSpec, now: Instant) {
+        let workload_name = workload_spec.instance_name.workload_name();
+        let Some(expected_startup_time_ms) = workload_spec.expected_startup_time_ms else {
+            self.watched_starts.remove(workload_name);
+            return;
+        };
+        self.watched_starts.insert(
+            workload_name.to_owned(),
+            WatchedStart {
+                instance_name: workload_spec.instance_name.clone(),
+                expected_startup_time_ms,
+                deadline: now + Duration::from_millis(expected_startup_time_ms),
+            },
+        );
+    }
+
+    pub fn remove(&mut self, workload_name: &str) {
+        self.watched_starts.remove(workload_name);
+    }
+
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.watched_starts
+            .values()
+            .map(|watched_start| watched_start.deadline)
+            .min()
+    }
+
+    // Returns the instance names and expected startup times of the workloads whose expected
+    // startup time has passed. These workloads are not watched any longer.
+    pub fn take_exceeded(&mut self, now: Instant) -> Vec<(WorkloadInstanceName, u64)> {
+        let exceeded_workload_names: Vec<String> = self
+            .watched_starts
+            .iter()
+            .filter(|(_, watched_start)| watched_start.deadline <= now)
+            .map(|(workload_name, _)| workload_name.clone())
+            .collect();
+        let mut exceeded_starts: Vec<(WorkloadInstanceName, u64)> = exceeded_workload_names
+            .iter()
+            .filter_map(|workload_name| self.watched_starts.remove(workload_name))
+            .map(|watched_start| {
+                (
+                    watched_start.instance_name,
+                    watched_start.expected_startup_time_ms,
+                )
+            })
+            .collect();
+        exceeded_starts
+            .sort_by(|(left, _), (right, _)| left.workload_name().cmp(right.workload_name()));
+        exceeded_starts
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::StartupWatch;
+    use common::objects::{generate_test_workload_spec_with_param, WorkloadSpec};
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+    const WORKLOAD_2_NAME: &str = "workload2";
+    const RUNTIME: &str = "runtime";
+
+    fn generate_test_workload_spec_with_expected_startup_time(
+        workload_name: &str,
+        expected_startup_time_ms: Option<u64>,
+    ) -> WorkloadSpec {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            workload_name.to_owned(),
+            RUNTIME.to_owned(),
+        );
+        workload_spec.expected_startup_time_ms = expected_startup_time_ms;
+        workload_spec
+    }
+
+    // [utest->swdd~agent-reports-slow-workload-starts~1]
+    #[test]
+    fn utest_startup_watch_returns_exceeded_starts_once() {
+        let workload_1 =
+            generate_test_workload_spec_with_expected_startup_time(WORKLOAD_1_NAME, Some(1000));
+        let workload_2 =
+            generate_test_workload_spec_with_expected_startup_time(WORKLOAD_2_NAME, Some(5000));
+
+        let mut startup_watch = StartupWatch::new();
+        let now = Instant::now();
+        startup_watch.watch(&workload_1, now);
+        startup_watch.watch(&workload_2, now);
+        assert_eq!(
+            startup_watch.next_deadline(),
+            Some(now + Duration::from_millis(1000))
+        );
+
+        assert!(startup_watch
+            .take_exceeded(now + Duration::from_millis(999))
+            .is_empty());
+        assert_eq!(
+            startup_watch.take_exceeded(now + Duration::from_millis(1000)),
+            vec![(workload_1.instance_name, 1000)]
+        );
+        assert!(startup_watch
+            .take_exceeded(now + Duration::from_millis(1000))
+            .is_empty());
+        assert_eq!(
+            startup_watch.next_deadline(),
+            Some(now + Duration::from_millis(5000))
+        );
+    }
+
+    // [utest->swdd~agent-reports-slow-workload-starts~1]
+    #[test]
+    fn utest_startup_watch_ignores_removed_and_unexpected_starts() {
+        let mut startup_watch = StartupWatch::new();
+        let now = Instant::now();
+        startup_watch.watch(
+            &generate_test_workload_spec_with_expected_startup_time(WORKLOAD_1_NAME, Some(1000)),
+            now,
+        );
+        startup_watch.remove(WORKLOAD_1_NAME);
+
+        startup_watch.watch(
+            &generate_test_workload_spec_with_expected_startup_time(WORKLOAD_2_NAME, Some(1000)),
+            now,
+        );
+        // the update of the workload has no expected startup time anymore
+        startup_watch.watch(
+            &generate_test_workload_spec_with_expected_startup_time(WORKLOAD_2_NAME, None),
+            now,
+        );
+
+        assert_eq!(startup_watch.next_deadline(), None);
+        assert!(startup_watch
+            .take_exceeded(now + Duration::from_secs(10))
+            .is_empty());
+    }
+}
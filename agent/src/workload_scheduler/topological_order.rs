@@ -0,0 +1,213 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+};
+
+use common::objects::WorkloadSpec;
+
+use crate::workload_operation::WorkloadOperation;
+
+fn created_workload_spec(workload_operation: &WorkloadOperation) -> Option<&WorkloadSpec> {
+    match workload_operation {
+        WorkloadOperation::Create(workload_spec) | WorkloadOperation::Update(workload_spec, _) => {
+            Some(workload_spec)
+        }
+        WorkloadOperation::UpdateDeleteOnly(_) | WorkloadOperation::Delete(_) => None,
+    }
+}
+
+/// Orders the workload operations of a batch along the dependencies of the created workloads.
+///
+/// The deletes come first and keep their order. A created workload follows the created
+/// workloads of the batch it depends on. Workloads without an order among each other are sorted
+/// by descending priority and then by name, thus the same batch always results in the same
+/// order. Workloads depending on each other in a cycle come last, sorted by name.
+///
+/// # Arguments
+///
+/// * `workload_operations` - The workload operations of the batch
+///
+// [impl->swdd~agent-orders-initial-workload-operations-topologically~1]
+pub fn topologically_ordered(
+    workload_operations: Vec<WorkloadOperation>,
+) -> Vec<WorkloadOperation> {
+    let mut ordered = Vec::with_capacity(workload_operations.len());
+    let mut creates: BTreeMap<String, WorkloadOperation> = BTreeMap::new();
+    for workload_operation in workload_operations {
+        match created_workload_spec(&workload_operation) {
+            Some(workload_spec) => {
+                creates.insert(
+                    workload_spec.instance_name.workload_name().to_owned(),
+                    workload_operation,
+                );
+            }
+            None => ordered.push(workload_operation),
+        }
+    }
+
+    // dependencies on workloads outside of the batch do not affect the order
+    let mut dependents: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut dependency_counts: BTreeMap<String, usize> = BTreeMap::new();
+    let mut ready: BTreeSet<(Reverse<u8>, String)> = BTreeSet::new();
+    for (workload_name, workload_operation) in &creates {
+        let dependency_names: BTreeSet<&String> = created_workload_spec(workload_operation)
+            .into_iter()
+            .flat_map(WorkloadSpec::add_conditions)
+            .map(|(dependency_name, _)| dependency_name)
+            .filter(|dependency_name| creates.contains_key(*dependency_name))
+            .collect();
+        if dependency_names.is_empty() {
+            ready.insert((
+                Reverse(workload_operation.priority()),
+                workload_name.clone(),
+            ));
+        }
+        dependency_counts.insert(workload_name.clone(), dependency_names.len());
+        for dependency_name in dependency_names {
+            dependents
+                .entry(dependency_name.clone())
+                .or_default()
+                .push(workload_name.clone());
+        }
+    }
+
+    while let Some((_, workload_name)) = ready.pop_first() {
+        for dependent_name in dependents.remove(&workload_name).into_iter().flatten() {
+            let Some(dependency_count) = dependency_counts.get_mut(&dependent_name) else {
+                continue;
+            };
+            *dependency_count -= 1;
+            if *dependency_count == 0 {
+                if let Some(dependent) = creates.get(&dependent_name) {
+                    ready.insert((Reverse(dependent.priority()), dependent_name));
+                }
+            }
+        }
+        ordered.extend(creates.remove(&workload_name));
+    }
+
+    // the scheduler detects the cycles of the remaining workloads
+    ordered.extend(creates.into_values());
+    ordered
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::topologically_ordered;
+    use crate::workload_operation::WorkloadOperation;
+    use common::objects::{
+        generate_test_workload_spec_with_dependencies, AddCondition, DeletedWorkload, WorkloadSpec,
+    };
+    use std::collections::HashMap;
+
+    const AGENT_NAME: &str = "agent_x";
+    const RUNTIME: &str = "runtime";
+
+    fn create(workload_name: &str, dependency_names: &[&str]) -> WorkloadOperation {
+        WorkloadOperation::Create(generate_test_workload_spec(workload_name, dependency_names))
+    }
+
+    fn generate_test_workload_spec(workload_name: &str, dependency_names: &[&str]) -> WorkloadSpec {
+        generate_test_workload_spec_with_dependencies(
+            AGENT_NAME,
+            workload_name,
+            RUNTIME,
+            dependency_names
+                .iter()
+                .map(|dependency_name| (dependency_name.to_string(), AddCondition::AddCondRunning))
+                .collect(),
+        )
+    }
+
+    fn workload_names(workload_operations: &[WorkloadOperation]) -> Vec<&str> {
+        workload_operations
+            .iter()
+            .map(|workload_operation| match workload_operation {
+                WorkloadOperation::Create(workload_spec)
+                | WorkloadOperation::Update(workload_spec, _) => {
+                    workload_spec.instance_name.workload_name()
+                }
+                WorkloadOperation::UpdateDeleteOnly(deleted_workload)
+                | WorkloadOperation::Delete(deleted_workload) => {
+                    deleted_workload.instance_name.workload_name()
+                }
+            })
+            .collect()
+    }
+
+    // [utest->swdd~agent-orders-initial-workload-operations-topologically~1]
+    #[test]
+    fn utest_topologically_ordered_creates_dependencies_first() {
+        let deleted_workload = DeletedWorkload {
+            instance_name: generate_test_workload_spec("old", &[]).instance_name,
+            dependencies: HashMap::default(),
+        };
+
+        let ordered = topologically_ordered(vec![
+            create("frontend", &["backend", "logger"]),
+            create("backend", &["database", "unknown"]),
+            create("logger", &[]),
+            WorkloadOperation::Delete(deleted_workload),
+            create("database", &[]),
+        ]);
+
+        assert_eq!(
+            workload_names(&ordered),
+            vec!["old", "database", "backend", "logger", "frontend"]
+        );
+    }
+
+    // [utest->swdd~agent-orders-initial-workload-operations-topologically~1]
+    #[test]
+    fn utest_topologically_ordered_is_independent_of_input_order() {
+        let mut high_priority = generate_test_workload_spec("b_high", &[]);
+        high_priority.priority = 10;
+        let workload_operations = vec![
+            create("c", &["a"]),
+            create("a", &[]),
+            WorkloadOperation::Create(high_priority),
+            create("d", &[]),
+        ];
+        let mut reversed_operations = workload_operations.clone();
+        reversed_operations.reverse();
+
+        let ordered = topologically_ordered(workload_operations);
+        assert_eq!(workload_names(&ordered), vec!["b_high", "a", "c", "d"]);
+        assert_eq!(topologically_ordered(reversed_operations), ordered);
+    }
+
+    // [utest->swdd~agent-orders-initial-workload-operations-topologically~1]
+    #[test]
+    fn utest_topologically_ordered_appends_workloads_in_cycle() {
+        let ordered = topologically_ordered(vec![
+            create("b", &["a"]),
+            create("a", &["b"]),
+            create("c", &[]),
+            create("d", &["a"]),
+        ]);
+
+        assert_eq!(workload_names(&ordered), vec!["c", "a", "b", "d"]);
+    }
+}
@@ -40,6 +40,8 @@ impl WorkloadStateSenderInterface for WorkloadStateSender {
         self.send(WorkloadState {
             instance_name: instance_name.to_owned(),
             execution_state,
+            agent_timestamp: None,
+            server_timestamp: None,
         })
         .await
         .unwrap_or_illegal_state()
@@ -70,7 +72,9 @@ pub async fn assert_execution_state_sequence(
             .unwrap(),
             WorkloadState {
                 instance_name: expected_state.0.clone(),
-                execution_state: expected_state.1
+                execution_state: expected_state.1,
+                agent_timestamp: None,
+                server_timestamp: None,
             }
         );
     }
@@ -103,6 +107,8 @@ mod tests {
         let expected_execution_state = WorkloadState {
             instance_name,
             execution_state: ExecutionState::running(),
+            agent_timestamp: None,
+            server_timestamp: None,
         };
 
         assert_eq!(
@@ -12,21 +12,63 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::workload_scheduler::queue_storage::QueueStorage;
 use common::objects::{ExecutionState, WorkloadState};
-use std::collections::HashMap;
 #[cfg(test)]
 use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet};
+use tokio::time::Instant;
+
+pub const LAST_KNOWN_STATES_FILE_NAME: &str = "last_known_states.json";
 
 type WorkloadStates = HashMap<String, common::objects::ExecutionState>;
 
 pub struct WorkloadStateStore {
     states_storage: WorkloadStates,
+    last_known_states: WorkloadStates,
+    // Only the last known states are persisted, the current states are resent by the server.
+    last_known_states_storage: Option<QueueStorage>,
+    // The time since when a workload is running without interruption, not persisted as the
+    // workloads are started again after a restart of the agent.
+    running_since: HashMap<String, Instant>,
+    // The agents currently connected to the server as distributed by the server.
+    connected_agents: HashSet<String>,
 }
 
 impl WorkloadStateStore {
     pub fn new() -> Self {
         Self {
             states_storage: HashMap::new(),
+            last_known_states: HashMap::new(),
+            last_known_states_storage: None,
+            running_since: HashMap::new(),
+            connected_agents: HashSet::new(),
+        }
+    }
+
+    // [impl->swdd~agent-restores-last-known-workload-states~1]
+    pub fn restore_last_known_states(&mut self, last_known_states_storage: QueueStorage) {
+        match last_known_states_storage.load::<WorkloadStates>() {
+            Ok(last_known_states) => {
+                if !last_known_states.is_empty() {
+                    log::info!(
+                        "Restored the last known states of '{}' workload(s) from the last run.",
+                        last_known_states.len()
+                    );
+                }
+                self.last_known_states = last_known_states;
+            }
+            Err(err) => log::warn!("Could not restore the last known workload states: {}", err),
+        }
+        self.last_known_states_storage = Some(last_known_states_storage);
+    }
+
+    // [impl->swdd~agent-persists-last-known-workload-states~1]
+    fn persist_last_known_states(&self) {
+        if let Some(last_known_states_storage) = &self.last_known_states_storage {
+            if let Err(err) = last_known_states_storage.store(&self.last_known_states) {
+                log::warn!("Could not persist the last known workload states: {}", err);
+            }
         }
     }
 
@@ -34,14 +76,62 @@ impl WorkloadStateStore {
         self.states_storage.get(workload_name)
     }
 
+    // [impl->swdd~agent-evaluates-unknown-dependency-states-by-policy~1]
+    pub fn get_last_known_state_of_workload<'a>(
+        &'a self,
+        workload_name: &str,
+    ) -> Option<&'a ExecutionState> {
+        self.last_known_states.get(workload_name)
+    }
+
+    // [impl->swdd~agent-evaluates-running-for-add-condition~1]
+    pub fn get_running_since_of_workload(&self, workload_name: &str) -> Option<Instant> {
+        self.running_since.get(workload_name).copied()
+    }
+
+    // [impl->swdd~agent-evaluates-agent-dependencies~1]
+    pub fn is_agent_connected(&self, agent_name: &str) -> bool {
+        self.connected_agents.contains(agent_name)
+    }
+
+    // [impl->swdd~agent-evaluates-agent-dependencies~1]
+    pub fn update_connected_agents(&mut self, connected_agents: Vec<String>) {
+        self.connected_agents = connected_agents.into_iter().collect();
+    }
+
     pub fn update_workload_state(&mut self, workload_state: WorkloadState) {
         let workload_name = workload_state.instance_name.workload_name().to_owned();
-        if !workload_state.execution_state.is_removed() {
-            self.states_storage
-                .insert(workload_name, workload_state.execution_state);
-        } else {
+        if workload_state.execution_state.is_removed() {
             self.states_storage.remove(&workload_name);
+            self.running_since.remove(&workload_name);
+            if self.last_known_states.remove(&workload_name).is_some() {
+                self.persist_last_known_states();
+            }
+            return;
+        }
+
+        if !workload_state.execution_state.is_unknown()
+            && self.last_known_states.get(&workload_name) != Some(&workload_state.execution_state)
+        {
+            self.last_known_states.insert(
+                workload_name.clone(),
+                workload_state.execution_state.clone(),
+            );
+            self.persist_last_known_states();
+        }
+
+        // [impl->swdd~agent-evaluates-running-for-add-condition~1]
+        // An unknown state does not interrupt the time, as it is evaluated by the unknown
+        // state policy like the last known state.
+        if workload_state.execution_state.is_running() {
+            self.running_since
+                .entry(workload_name.clone())
+                .or_insert_with(Instant::now);
+        } else if !workload_state.execution_state.is_unknown() {
+            self.running_since.remove(&workload_name);
         }
+        self.states_storage
+            .insert(workload_name, workload_state.execution_state);
     }
 }
 
@@ -59,6 +149,9 @@ pub fn mock_parameter_storage_new_returns(mock_parameter_storage: MockWorkloadSt
 pub struct MockWorkloadStateStore {
     pub expected_update_workload_state_parameters: VecDeque<WorkloadState>,
     pub states_storage: HashMap<String, ExecutionState>,
+    pub last_known_states: HashMap<String, ExecutionState>,
+    pub running_since: HashMap<String, Instant>,
+    pub connected_agents: HashSet<String>,
 }
 
 #[cfg(test)]
@@ -71,6 +164,8 @@ impl MockWorkloadStateStore {
             .expect("Return value for MockWorkloadStateStore::new() not set")
     }
 
+    pub fn restore_last_known_states(&mut self, _last_known_states_storage: QueueStorage) {}
+
     pub fn update_workload_state(&mut self, workload_state: WorkloadState) {
         let expected_workload_state = self
             .expected_update_workload_state_parameters
@@ -86,6 +181,25 @@ impl MockWorkloadStateStore {
     pub fn get_state_of_workload<'a>(&'a self, workload_name: &str) -> Option<&'a ExecutionState> {
         self.states_storage.get(workload_name)
     }
+
+    pub fn get_last_known_state_of_workload<'a>(
+        &'a self,
+        workload_name: &str,
+    ) -> Option<&'a ExecutionState> {
+        self.last_known_states.get(workload_name)
+    }
+
+    pub fn get_running_since_of_workload(&self, workload_name: &str) -> Option<Instant> {
+        self.running_since.get(workload_name).copied()
+    }
+
+    pub fn is_agent_connected(&self, agent_name: &str) -> bool {
+        self.connected_agents.contains(agent_name)
+    }
+
+    pub fn update_connected_agents(&mut self, connected_agents: Vec<String>) {
+        self.connected_agents = connected_agents.into_iter().collect();
+    }
 }
 
 #[cfg(test)]
@@ -97,8 +211,10 @@ impl Drop for MockWorkloadStateStore {
 
 #[cfg(test)]
 mod tests {
-    use super::WorkloadStateStore;
+    use super::{WorkloadStateStore, LAST_KNOWN_STATES_FILE_NAME};
+    use crate::workload_scheduler::queue_storage::QueueStorage;
     use common::objects::ExecutionState;
+    use common::persistence::PersistenceFormat;
 
     #[test]
     fn utest_update_storage_empty_storage_add_one() {
@@ -249,4 +365,126 @@ mod tests {
             .get_state_of_workload("unknown workload")
             .is_none());
     }
+
+    // [utest->swdd~agent-evaluates-unknown-dependency-states-by-policy~1]
+    #[test]
+    fn utest_update_storage_keeps_last_known_state_on_unknown_state() {
+        let mut storage = WorkloadStateStore::new();
+
+        let test_update = common::objects::generate_test_workload_state_with_agent(
+            "test_workload",
+            "test_agent",
+            ExecutionState::running(),
+        );
+        storage.update_workload_state(test_update.clone());
+
+        let mut stale_update = test_update.clone();
+        stale_update.execution_state = ExecutionState::stale();
+        storage.update_workload_state(stale_update);
+
+        assert_eq!(
+            storage.get_state_of_workload("test_workload"),
+            Some(&ExecutionState::stale())
+        );
+        assert_eq!(
+            storage.get_last_known_state_of_workload("test_workload"),
+            Some(&ExecutionState::running())
+        );
+
+        let mut removed_update = test_update;
+        removed_update.execution_state = ExecutionState::removed();
+        storage.update_workload_state(removed_update);
+
+        assert!(storage
+            .get_last_known_state_of_workload("test_workload")
+            .is_none());
+    }
+
+    // [utest->swdd~agent-persists-last-known-workload-states~1]
+    // [utest->swdd~agent-restores-last-known-workload-states~1]
+    #[test]
+    fn utest_last_known_states_are_restored_after_restart() {
+        let run_folder = tempfile::tempdir().unwrap();
+        let path = run_folder.path().join(LAST_KNOWN_STATES_FILE_NAME);
+
+        let mut storage = WorkloadStateStore::new();
+        storage.restore_last_known_states(QueueStorage::new(path.clone(), PersistenceFormat::Json));
+        storage.update_workload_state(common::objects::generate_test_workload_state_with_agent(
+            "test_workload",
+            "test_agent",
+            ExecutionState::running(),
+        ));
+
+        let mut restarted_storage = WorkloadStateStore::new();
+        restarted_storage
+            .restore_last_known_states(QueueStorage::new(path, PersistenceFormat::Json));
+
+        assert!(restarted_storage
+            .get_state_of_workload("test_workload")
+            .is_none());
+        assert_eq!(
+            restarted_storage.get_last_known_state_of_workload("test_workload"),
+            Some(&ExecutionState::running())
+        );
+    }
+
+    // [utest->swdd~agent-persists-last-known-workload-states~1]
+    #[test]
+    fn utest_last_known_states_are_not_persisted_on_unknown_state() {
+        let run_folder = tempfile::tempdir().unwrap();
+        let path = run_folder.path().join(LAST_KNOWN_STATES_FILE_NAME);
+
+        let mut storage = WorkloadStateStore::new();
+        storage.restore_last_known_states(QueueStorage::new(path.clone(), PersistenceFormat::Json));
+        storage.update_workload_state(common::objects::generate_test_workload_state_with_agent(
+            "test_workload",
+            "test_agent",
+            ExecutionState::stale(),
+        ));
+
+        assert!(!path.exists());
+    }
+
+    // [utest->swdd~agent-evaluates-running-for-add-condition~1]
+    #[test]
+    fn utest_running_since_is_kept_while_workload_is_running() {
+        let mut storage = WorkloadStateStore::new();
+        let test_update = common::objects::generate_test_workload_state_with_agent(
+            "test_workload",
+            "test_agent",
+            ExecutionState::running(),
+        );
+        storage.update_workload_state(test_update.clone());
+        let running_since = storage.get_running_since_of_workload("test_workload");
+        assert!(running_since.is_some());
+
+        storage.update_workload_state(test_update.clone());
+        let mut stale_update = test_update.clone();
+        stale_update.execution_state = ExecutionState::stale();
+        storage.update_workload_state(stale_update);
+        assert_eq!(
+            storage.get_running_since_of_workload("test_workload"),
+            running_since
+        );
+
+        let mut succeeded_update = test_update;
+        succeeded_update.execution_state = ExecutionState::succeeded();
+        storage.update_workload_state(succeeded_update);
+        assert!(storage
+            .get_running_since_of_workload("test_workload")
+            .is_none());
+    }
+
+    // [utest->swdd~agent-evaluates-agent-dependencies~1]
+    #[test]
+    fn utest_connected_agents_are_replaced_on_update() {
+        let mut storage = WorkloadStateStore::new();
+        assert!(!storage.is_agent_connected("agent_B"));
+
+        storage.update_connected_agents(vec!["agent_A".to_string(), "agent_B".to_string()]);
+        assert!(storage.is_agent_connected("agent_B"));
+
+        storage.update_connected_agents(vec!["agent_A".to_string()]);
+        assert!(!storage.is_agent_connected("agent_B"));
+    }
 }
@@ -16,12 +16,15 @@ use std::error::Error;
 
 use clap::{command, Parser, Subcommand};
 
-use common::DEFAULT_SERVER_ADDRESS;
+use common::{kube_conversion::ConversionRuntime, DEFAULT_SERVER_ADDRESS};
 use url::Url;
 
-const ANK_SERVER_URL_ENV_KEY: &str = "ANK_SERVER_URL";
+use crate::json_path::JsonPathTemplate;
 
-// [impl->swdd~cli-shall-support-environment-variables~1]
+pub(crate) const ANK_SERVER_URL_ENV_KEY: &str = "ANK_SERVER_URL";
+pub(crate) const ANK_RESPONSE_TIMEOUT_ENV_KEY: &str = "ANK_RESPONSE_TIMEOUT";
+
+// [impl->swdd~cli-shall-support-environment-variables~2]
 // [impl->swdd~cli-prioritizes-cli-argument-over-environment-variable~1]
 #[derive(Parser, Debug)] // requires `derive` feature
 #[command(name = "ank")]
@@ -34,7 +37,7 @@ pub struct AnkCli {
     #[clap(short = 's', long = "server-url", default_value_t = DEFAULT_SERVER_ADDRESS.parse().unwrap(), env = ANK_SERVER_URL_ENV_KEY)]
     /// The url to Ankaios server.
     pub server_url: Url,
-    #[clap(long = "response-timeout", default_value_t = 3000)]
+    #[clap(long = "response-timeout", default_value_t = 3000, env = ANK_RESPONSE_TIMEOUT_ENV_KEY)]
     /// The timeout in milliseconds to wait for a response.
     pub response_timeout_ms: u64,
     #[clap(short = 'v', long = "verbose")]
@@ -46,6 +49,12 @@ pub struct AnkCli {
     #[clap(long = "no-wait")]
     /// Do not wait for workloads to be created/deleted
     pub no_wait: bool,
+    #[clap(long = "wait-timeout")]
+    /// The timeout in seconds to wait for workloads to be created/deleted. Waits without limit if not given.
+    pub wait_timeout_s: Option<u64>,
+    #[clap(long = "force")]
+    /// Modify workloads even if they are managed by another workload or the cloud connector
+    pub force: bool,
 }
 
 /// Supported actions
@@ -61,6 +70,20 @@ pub enum Commands {
     Run(RunArgs),
     #[command(arg_required_else_help = true)]
     Apply(ApplyArgs),
+    /// Show the changes applying Ankaios manifest content or file(s) would make
+    #[command(arg_required_else_help = true)]
+    Diff(ApplyArgs),
+    SupportBundle(SupportBundleArgs),
+    #[command(arg_required_else_help = true)]
+    Drain(DrainArgs),
+    #[command(arg_required_else_help = true)]
+    Convert(ConvertArgs),
+    Graph(GraphArgs),
+    #[command(arg_required_else_help = true)]
+    Group(GroupArgs),
+    /// Run the plugin 'ank-<name>' found on PATH for any other subcommand
+    #[command(external_subcommand)]
+    Plugin(Vec<String>),
 }
 
 /// Retrieve information about the current Ankaios system
@@ -71,10 +94,25 @@ pub struct GetArgs {
     pub command: Option<GetCommands>,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OutputFormat {
     Yaml,
     Json,
+    JsonPath(JsonPathTemplate),
+}
+
+// [impl->swdd~cli-supports-jsonpath-output~1]
+fn parse_output_format(s: &str) -> Result<OutputFormat, String> {
+    match s {
+        "yaml" => Ok(OutputFormat::Yaml),
+        "json" => Ok(OutputFormat::Json),
+        _ => match s.strip_prefix("jsonpath=") {
+            Some(template) => Ok(OutputFormat::JsonPath(JsonPathTemplate::parse(template)?)),
+            None => Err(format!(
+                "invalid output format '{s}', expected 'yaml', 'json' or 'jsonpath=<template>'"
+            )),
+        },
+    }
 }
 
 /// Get commands
@@ -82,8 +120,9 @@ pub enum OutputFormat {
 pub enum GetCommands {
     /// State information of Ankaios system
     State {
-        /// Specify the output format
-        #[arg(short = 'o', value_enum, default_value_t = OutputFormat::Yaml)]
+        /// Specify the output format: yaml, json or jsonpath=<template>,
+        /// e.g. jsonpath='{.desiredState.workloads.nginx.agent}'
+        #[arg(short = 'o', default_value = "yaml", value_parser = parse_output_format)]
         output_format: OutputFormat,
         /// Select which parts of the state object shall be output e.g. 'desiredState.workloads.nginx' [default: empty = the complete state]
         object_field_mask: Vec<String>,
@@ -98,9 +137,150 @@ pub enum GetCommands {
         /// Only workloads in the given state shall be output
         #[arg(short = 's', long = "state", required = false)]
         state: Option<String>,
+        /// Also output the final states of the workloads removed within the retention period of the server
+        #[arg(long = "show-terminated")]
+        show_terminated: bool,
         /// Select which workload(s) shall be returned [default: empty = all workloads]
         workload_name: Vec<String>,
     },
+    /// Progress of the current staged rollout
+    Rollout {},
+    /// Information about the agents known to the Ankaios server
+    #[clap(visible_alias("agent"))]
+    Agents {},
+    /// Events recorded by the Ankaios server, oldest first
+    #[clap(visible_alias("event"))]
+    Events {
+        /// Only events of the last given number of seconds shall be output
+        #[arg(long = "since", required = false)]
+        since_secs: Option<u64>,
+        /// Only the given number of most recent events shall be output [default: all events]
+        #[arg(
+            short = 'n',
+            long = "limit",
+            default_value_t = 0,
+            hide_default_value = true
+        )]
+        limit: u32,
+    },
+    /// Workload operations waiting in the scheduler queues of the agents for their dependencies
+    Queue {
+        /// Only the pending operations of the given agent shall be output
+        #[arg(short = 'a', long = "agent", required = false)]
+        agent_name: Option<String>,
+    },
+}
+
+/// Collect diagnostic information about the Ankaios system into an archive for bug reports
+#[derive(clap::Args, Debug)]
+pub struct SupportBundleArgs {
+    /// Path of the created archive [default: ankaios-support-bundle-<timestamp>.tar]
+    #[arg(short = 'o', long = "output")]
+    pub output_file: Option<String>,
+}
+
+/// Move all workloads away from an agent, e.g. before its maintenance
+#[derive(clap::Args, Debug)]
+pub struct DrainArgs {
+    /// Name of the agent to be drained
+    #[arg(required = true)]
+    pub agent_name: String,
+    /// Agent to which the workloads shall be moved [default: the workloads are unscheduled]
+    #[arg(long = "to")]
+    pub target_agent: Option<String>,
+    /// Unregister the agent from the server after draining it
+    #[arg(long = "remove")]
+    pub remove: bool,
+}
+
+/// Convert Kubernetes Pods and Deployments or Docker Compose services into an Ankaios manifest
+#[derive(clap::Args, Debug)]
+pub struct ConvertArgs {
+    /// Kubernetes manifest or Compose file or '-' for stdin
+    #[arg(short = 'f', long = "file", required = true)]
+    pub manifest_file: String,
+    /// Name of the agent on which the converted workloads shall run
+    #[arg(long = "agent", required = true)]
+    pub agent_name: String,
+    /// Format of the converted file
+    #[arg(long = "from", value_enum, default_value_t = ConvertFormat::Kubernetes)]
+    pub format: ConvertFormat,
+    /// Runtime of the converted Kubernetes workloads [default: podman for Pods with a single container, podman-kube otherwise]
+    #[arg(long = "runtime", value_enum)]
+    pub runtime: Option<ConvertRuntime>,
+}
+
+/// Formats supported by the conversion into Ankaios manifests
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum ConvertFormat {
+    Kubernetes,
+    Compose,
+}
+
+/// Runtimes supported by the conversion of Kubernetes manifests
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum ConvertRuntime {
+    Podman,
+    PodmanKube,
+}
+
+impl From<ConvertRuntime> for ConversionRuntime {
+    fn from(value: ConvertRuntime) -> Self {
+        match value {
+            ConvertRuntime::Podman => ConversionRuntime::Podman,
+            ConvertRuntime::PodmanKube => ConversionRuntime::PodmanKube,
+        }
+    }
+}
+
+/// Export the dependency graph of the workloads together with their current states
+#[derive(clap::Args, Debug)]
+pub struct GraphArgs {
+    /// Format of the exported graph
+    #[arg(long = "format", value_enum, default_value_t = GraphFormat::Dot)]
+    pub format: GraphFormat,
+}
+
+/// Formats supported by the export of the dependency graph
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Json,
+}
+
+/// Start, stop, restart or delete the workloads of a workload group as a unit
+#[derive(clap::Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct GroupArgs {
+    #[command(subcommand)]
+    pub command: Option<GroupCommands>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum GroupCommands {
+    /// Start the workloads of a stopped group
+    Start {
+        /// The name of the workload group
+        group_name: String,
+    },
+    /// Stop the workloads of a group without removing them from the desired state
+    Stop {
+        /// The name of the workload group
+        group_name: String,
+    },
+    /// Stop the workloads of a group and start them again
+    Restart {
+        /// The name of the workload group
+        group_name: String,
+    },
+    /// Delete the group together with its workloads
+    Delete {
+        /// The name of the workload group
+        group_name: String,
+        /// Delete without asking for confirmation if other workloads depend on the deleted workload(s)
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
+    },
 }
 
 /// Update the state of Ankaios system
@@ -123,6 +303,11 @@ pub enum SetCommands {
         #[arg(short = 'f', long = "file")]
         state_object_file: Option<String>,
     },
+    /// Switch the active system mode, starting and stopping the workloads of the modes
+    Mode {
+        /// The name of the mode to activate, e.g. 'driving'
+        mode: String,
+    },
 }
 
 /// Delete the workload
@@ -141,6 +326,9 @@ pub enum DeleteCommands {
         /// One or more workload(s) to be deleted
         #[arg(required = true)]
         workload_name: Vec<String>,
+        /// Delete without asking for confirmation if other workloads depend on the deleted workload(s)
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
     },
 }
 
@@ -182,15 +370,25 @@ pub enum RunCommands {
 /// Apply Ankaios manifest content or file(s)
 #[derive(clap::Args, Debug)]
 pub struct ApplyArgs {
-    #[arg(value_name = "Ankaios manifest file(s) or '-' for stdin")]
+    #[arg(value_name = "Ankaios manifest file(s), 'oci://' reference(s) or '-' for stdin")]
     pub manifest_files: Vec<String>,
     /// Specify on which agent to apply the Ankaios manifests.
     /// If not specified, the agent(s) must be specified in the Ankaios manifest(s)
     #[arg(long = "agent")]
     pub agent_name: Option<String>,
+    /// Select the workload profile, e.g. 'dev' or 'prod', of the workloads in the Ankaios manifests.
+    /// If not specified, the profile in the Ankaios manifest(s) or the active profile is used
+    #[arg(long = "profile")]
+    pub profile: Option<String>,
     /// Delete mode activated
     #[arg(short)]
     pub delete_mode: bool,
+    /// Path to the public key used to verify the signature of Ankaios manifests pulled from an OCI registry
+    #[arg(long = "public-key")]
+    pub public_key: Option<String>,
+    /// Delete without asking for confirmation if other workloads depend on the deleted workload(s)
+    #[arg(short = 'y', long = "yes")]
+    pub yes: bool,
 }
 
 fn parse_key_val<K, V>(s: &str) -> Result<(K, V), Box<dyn Error + Send + Sync + 'static>>
@@ -13,16 +13,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt::{self, Display},
     time::Duration,
 };
 mod apply_manifests;
 mod server_connection;
+mod support_bundle;
 mod wait_list;
-use tokio::time::interval;
+use tokio::time::{interval, Instant};
 use wait_list::WaitList;
 
+pub use support_bundle::default_bundle_file_name;
+
 #[cfg(not(test))]
 async fn read_file_to_string(file: String) -> std::io::Result<String> {
     std::fs::read_to_string(file)
@@ -30,9 +33,35 @@ async fn read_file_to_string(file: String) -> std::io::Result<String> {
 #[cfg(test)]
 use tests::read_to_string_mock as read_file_to_string;
 
+#[cfg(not(test))]
+fn read_confirmation() -> std::io::Result<String> {
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(answer)
+}
+#[cfg(test)]
+use tests::read_confirmation_mock as read_confirmation;
+
+#[cfg(not(test))]
+fn stdin_is_terminal() -> bool {
+    use std::io::IsTerminal;
+    std::io::stdin().is_terminal()
+}
+#[cfg(test)]
+use tests::stdin_is_terminal_mock as stdin_is_terminal;
+
+use ankaios_model::objects::diff_states;
 use common::{
+    commands::{
+        DependencyGraph, DrainAgentRequest, Event, EventsRequest, ImpactedWorkload,
+        PendingWorkloadOperation, RejectedWorkload, RolloutGroupStatus, SchedulerQueueRequest,
+        UpdateStateSuccess,
+    },
     from_server_interface::FromServer,
-    objects::{CompleteState, State, StoredWorkloadSpec, Tag, WorkloadInstanceName},
+    objects::{
+        AddCondition, AgentInfo, CompleteState, State, StoredWorkloadSpec, Tag, TerminatedWorkload,
+        WorkloadGroup, WorkloadInstanceName, WorkloadState,
+    },
     state_manipulation::{Object, Path},
 };
 
@@ -43,19 +72,50 @@ use url::Url;
 use self::server_connection::ServerConnection;
 use self::wait_list::WaitListDisplayTrait;
 use crate::{
-    cli::{ApplyArgs, OutputFormat},
+    cli::{ApplyArgs, GraphFormat, OutputFormat},
     cli_commands::wait_list::ParsedUpdateStateSuccess,
     output, output_and_error, output_debug,
 };
 
 const SPINNER_SYMBOLS: [&str; 4] = ["|", "/", "-", "\\"];
 pub(crate) const COMPLETED_SYMBOL: &str = " ";
+const MEBIBYTE: u64 = 1024 * 1024;
+
+// [impl->swdd~cli-exits-with-wait-outcome~1]
+pub const EXIT_CODE_ERROR: i32 = 1;
+pub const EXIT_CODE_WORKLOADS_FAILED: i32 = 2;
+pub const EXIT_CODE_WAITING_ON_DEPENDENCIES: i32 = 3;
+pub const EXIT_CODE_WAIT_TIMEOUT: i32 = 4;
+// [impl->swdd~cli-reports-rejected-workloads~1]
+pub const EXIT_CODE_WORKLOADS_REJECTED: i32 = 5;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum CliError {
     YamlSerialization(String),
     JsonSerialization(String),
     ExecutionError(String),
+    WorkloadsFailed(Vec<String>),
+    WaitTimeout {
+        waiting_on_dependencies: Vec<String>,
+        pending: Vec<String>,
+    },
+    WorkloadsRejected(Vec<RejectedWorkload>),
+}
+
+impl CliError {
+    // [impl->swdd~cli-exits-with-wait-outcome~1]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::WorkloadsFailed(_) => EXIT_CODE_WORKLOADS_FAILED,
+            CliError::WaitTimeout { pending, .. } if pending.is_empty() => {
+                EXIT_CODE_WAITING_ON_DEPENDENCIES
+            }
+            CliError::WaitTimeout { .. } => EXIT_CODE_WAIT_TIMEOUT,
+            // [impl->swdd~cli-reports-rejected-workloads~1]
+            CliError::WorkloadsRejected(_) => EXIT_CODE_WORKLOADS_REJECTED,
+            _ => EXIT_CODE_ERROR,
+        }
+    }
 }
 
 impl fmt::Display for CliError {
@@ -70,6 +130,47 @@ impl fmt::Display for CliError {
             CliError::ExecutionError(message) => {
                 write!(f, "Command failed: '{}'", message)
             }
+            CliError::WorkloadsFailed(workload_names) => {
+                write!(
+                    f,
+                    "The workload(s) '{}' failed to reach their desired states.",
+                    workload_names.join("', '")
+                )
+            }
+            CliError::WaitTimeout {
+                waiting_on_dependencies,
+                pending,
+            } => {
+                write!(
+                    f,
+                    "Timed out waiting for the workload(s) to reach their desired states."
+                )?;
+                if !waiting_on_dependencies.is_empty() {
+                    write!(
+                        f,
+                        " Still waiting on dependencies: '{}'.",
+                        waiting_on_dependencies.join("', '")
+                    )?;
+                }
+                if !pending.is_empty() {
+                    write!(f, " Still pending: '{}'.", pending.join("', '"))?;
+                }
+                Ok(())
+            }
+            CliError::WorkloadsRejected(rejected_workloads) => {
+                write!(
+                    f,
+                    "The update has been applied partially, workload(s) not started:"
+                )?;
+                for rejected_workload in rejected_workloads {
+                    write!(
+                        f,
+                        "\n  '{}': {}",
+                        rejected_workload.workload_name, rejected_workload.reason
+                    )?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -102,11 +203,13 @@ fn generate_compact_state_output(
     output_format: OutputFormat,
 ) -> Result<String, CliError> {
     let convert_to_output = |map: serde_yaml::Value| -> Result<String, CliError> {
-        match output_format {
+        match &output_format {
             // [impl -> swdd~cli-shall-support-desired-state-yaml~1]
             OutputFormat::Yaml => Ok(serde_yaml::to_string(&map)?),
             // [impl -> swdd~cli-shall-support-desired-state-json~1]
             OutputFormat::Json => Ok(serde_json::to_string_pretty(&map)?),
+            // [impl->swdd~cli-supports-jsonpath-output~1]
+            OutputFormat::JsonPath(template) => Ok(template.render(&serde_json::to_value(map)?)),
         }
     };
 
@@ -172,6 +275,47 @@ fn update_compact_state(
     Some(())
 }
 
+// Only the double quote and the backslash need to be escaped in quoted DOT ids.
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// [impl->swdd~cli-exports-dependency-graph~1]
+fn dependency_graph_to_dot(dependency_graph: &DependencyGraph) -> String {
+    let mut dot = String::from("digraph workloads {\n");
+    for node in &dependency_graph.nodes {
+        let state = node
+            .execution_state
+            .as_ref()
+            .map(|execution_state| execution_state.state.to_string())
+            .unwrap_or_else(|| "Unknown".to_string());
+        dot.push_str(&format!(
+            "    \"{}\" [label=\"{}\\n{}\\n{}\"];\n",
+            escape_dot(&node.workload_name),
+            escape_dot(&node.workload_name),
+            escape_dot(&node.agent),
+            escape_dot(&state)
+        ));
+    }
+    for edge in &dependency_graph.edges {
+        let condition = match edge.condition {
+            AddCondition::AddCondRunning => "ADD_COND_RUNNING",
+            AddCondition::AddCondSucceeded => "ADD_COND_SUCCEEDED",
+            AddCondition::AddCondFailed => "ADD_COND_FAILED",
+            AddCondition::AddCondRunningFor => "ADD_COND_RUNNING_FOR",
+            AddCondition::AddCondNotRunning => "ADD_COND_NOT_RUNNING",
+        };
+        dot.push_str(&format!(
+            "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+            escape_dot(&edge.workload_name),
+            escape_dot(&edge.dependency),
+            condition
+        ));
+    }
+    dot.push('}');
+    dot
+}
+
 #[derive(Debug, Tabled, Clone)]
 #[tabled(rename_all = "UPPERCASE")]
 struct GetWorkloadTableDisplay {
@@ -185,6 +329,165 @@ struct GetWorkloadTableDisplay {
     additional_info: String,
 }
 
+#[derive(Debug, Tabled, Clone)]
+#[tabled(rename_all = "UPPERCASE")]
+struct GetRolloutGroupTableDisplay {
+    #[tabled(rename = "ROLLOUT GROUP")]
+    name: String,
+    state: String,
+    agents: String,
+    workloads: u32,
+    #[tabled(rename = "FAILED WORKLOADS")]
+    failed_workloads: u32,
+}
+
+impl From<RolloutGroupStatus> for GetRolloutGroupTableDisplay {
+    fn from(value: RolloutGroupStatus) -> Self {
+        GetRolloutGroupTableDisplay {
+            name: value.name,
+            state: value.state.to_string(),
+            agents: value.agents.join(", "),
+            workloads: value.workloads,
+            failed_workloads: value.failed_workloads,
+        }
+    }
+}
+
+#[derive(Debug, Tabled, Clone)]
+#[tabled(rename_all = "UPPERCASE")]
+struct ImpactedWorkloadTableDisplay {
+    #[tabled(rename = "WORKLOAD NAME")]
+    name: String,
+    agent: String,
+    #[tabled(rename = "DEPENDS ON")]
+    depends_on: String,
+}
+
+impl From<ImpactedWorkload> for ImpactedWorkloadTableDisplay {
+    fn from(value: ImpactedWorkload) -> Self {
+        ImpactedWorkloadTableDisplay {
+            name: value.workload_name,
+            agent: value.agent,
+            depends_on: value.depends_on.join(", "),
+        }
+    }
+}
+
+#[derive(Debug, Tabled, Clone)]
+#[tabled(rename_all = "UPPERCASE")]
+struct GetAgentTableDisplay {
+    #[tabled(rename = "AGENT NAME")]
+    name: String,
+    version: String,
+    status: String,
+    runtimes: String,
+    workloads: usize,
+    #[tabled(rename = "WORKLOAD STATES")]
+    workload_states: String,
+    #[tabled(rename = "FREE RESOURCES")]
+    free_resources: String,
+    #[tabled(rename = "LAST HEARTBEAT")]
+    last_heartbeat: String,
+}
+
+impl GetAgentTableDisplay {
+    fn new(agent_info: AgentInfo, workload_states: &[WorkloadState]) -> Self {
+        // only the main state is counted, e.g. 'Running(Ok)' is counted as 'Running'
+        let mut state_counts: BTreeMap<String, usize> = BTreeMap::new();
+        for workload_state in workload_states
+            .iter()
+            .filter(|x| x.instance_name.agent_name() == agent_info.agent_name)
+        {
+            let state = workload_state.execution_state.state.to_string();
+            let main_state = state.split('(').next().unwrap_or_default().to_string();
+            *state_counts.entry(main_state).or_default() += 1;
+        }
+
+        GetAgentTableDisplay {
+            name: agent_info.agent_name,
+            version: agent_info.version,
+            status: agent_info.connection_status.to_string(),
+            runtimes: agent_info.runtimes.join(", "),
+            workloads: state_counts.values().sum(),
+            workload_states: state_counts
+                .into_iter()
+                .map(|(state, count)| format!("{state}: {count}"))
+                .collect::<Vec<String>>()
+                .join(", "),
+            // the resources are empty until the agent reported them
+            free_resources: agent_info
+                .resources
+                .map(|resources| {
+                    format!(
+                        "cpu: {}m, memory: {}Mi",
+                        resources.free_cpu_millis,
+                        resources.free_memory_bytes / MEBIBYTE
+                    )
+                })
+                .unwrap_or_default(),
+            last_heartbeat: humantime::format_rfc3339_seconds(
+                std::time::UNIX_EPOCH + Duration::from_millis(agent_info.last_heartbeat),
+            )
+            .to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Tabled, Clone)]
+#[tabled(rename_all = "UPPERCASE")]
+struct GetEventTableDisplay {
+    time: String,
+    kind: String,
+    agent: String,
+    workload: String,
+    message: String,
+    #[tabled(rename = "TRACE ID")]
+    trace_id: String,
+}
+
+impl From<Event> for GetEventTableDisplay {
+    fn from(value: Event) -> Self {
+        GetEventTableDisplay {
+            time: humantime::format_rfc3339_seconds(
+                std::time::UNIX_EPOCH + Duration::from_millis(value.timestamp),
+            )
+            .to_string(),
+            kind: value.kind.to_string(),
+            agent: value.agent_name.unwrap_or_default(),
+            workload: value.workload_name.unwrap_or_default(),
+            message: value.message,
+            trace_id: value.trace_id.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Tabled, Clone)]
+#[tabled(rename_all = "UPPERCASE")]
+struct GetPendingOperationTableDisplay {
+    #[tabled(rename = "WORKLOAD NAME")]
+    name: String,
+    agent: String,
+    operation: String,
+    #[tabled(rename = "WAITING FOR")]
+    waiting_for: String,
+}
+
+impl From<PendingWorkloadOperation> for GetPendingOperationTableDisplay {
+    fn from(value: PendingWorkloadOperation) -> Self {
+        GetPendingOperationTableDisplay {
+            name: value.workload_name,
+            agent: value.agent,
+            operation: value.operation.to_string(),
+            waiting_for: value
+                .unfulfilled_dependencies
+                .into_iter()
+                .map(|dependency| format!("{}: {}", dependency.workload_name, dependency.condition))
+                .collect::<Vec<String>>()
+                .join(", "),
+        }
+    }
+}
+
 struct GetWorkloadTableDisplayWithSpinner<'a> {
     data: &'a GetWorkloadTableDisplay,
     spinner: &'a str,
@@ -302,12 +605,37 @@ impl GetWorkloadTableDisplay {
             additional_info: additional_info.to_string(),
         }
     }
+
+    // The final state of a removed workload, the time of the removal is added to the info.
+    // [impl->swdd~cli-shows-terminated-workloads~1]
+    fn from_terminated_workload(terminated_workload: &TerminatedWorkload) -> Self {
+        let final_state = &terminated_workload.final_state;
+        let removed_at = humantime::format_rfc3339_seconds(
+            std::time::UNIX_EPOCH + Duration::from_millis(terminated_workload.removed_at),
+        );
+        let additional_info = if final_state.execution_state.additional_info.is_empty() {
+            format!("removed at {removed_at}")
+        } else {
+            format!(
+                "{} (removed at {removed_at})",
+                final_state.execution_state.additional_info
+            )
+        };
+        GetWorkloadTableDisplay::new(
+            final_state.instance_name.workload_name(),
+            final_state.instance_name.agent_name(),
+            Default::default(),
+            &final_state.execution_state.state.to_string(),
+            &additional_info,
+        )
+    }
 }
 
 pub struct CliCommands {
     // Left here for the future use.
     _response_timeout_ms: u64,
     no_wait: bool,
+    wait_timeout: Option<Duration>,
     server_connection: ServerConnection,
 }
 
@@ -317,11 +645,18 @@ impl CliCommands {
         cli_name: String,
         server_url: Url,
         no_wait: bool,
+        wait_timeout: Option<Duration>,
+        force: bool,
     ) -> Self {
         Self {
             _response_timeout_ms: response_timeout_ms,
             no_wait,
-            server_connection: ServerConnection::new(cli_name.as_str(), server_url.clone()),
+            wait_timeout,
+            server_connection: ServerConnection::new(
+                cli_name.as_str(),
+                server_url.clone(),
+                force,
+            ),
         }
     }
 
@@ -442,15 +777,30 @@ impl CliCommands {
             .await
     }
 
+    // [impl->swdd~cli-switches-system-mode~1]
+    pub async fn set_mode(&mut self, mode: String) -> Result<(), CliError> {
+        let mut complete_state_update = CompleteState::default();
+        complete_state_update.desired_state.active_mode = mode;
+        let update_mask = vec!["desiredState.activeMode".to_string()];
+
+        output_debug!(
+            "Updating the active mode with the complete state {:?}",
+            complete_state_update
+        );
+        self.update_state_and_wait_for_complete(complete_state_update, update_mask)
+            .await
+    }
+
     // [impl->swdd~cli-provides-list-of-workloads~1]
     pub async fn get_workloads_table(
         &mut self,
         agent_name: Option<String>,
         state: Option<String>,
         workload_name: Vec<String>,
+        show_terminated: bool,
     ) -> Result<String, CliError> {
         // [impl->swdd~cli-blocks-until-ankaios-server-responds-list-workloads~1]
-        let mut workload_infos = self.get_workloads().await?;
+        let mut workload_infos = self.get_workloads(show_terminated).await?;
         output_debug!("The table before filtering:\n{:?}", workload_infos);
 
         // [impl->swdd~cli-shall-filter-list-of-workloads~1]
@@ -481,8 +831,146 @@ impl CliCommands {
             .to_string())
     }
 
+    // [impl->swdd~cli-provides-rollout-status~1]
+    pub async fn get_rollout_status(&mut self) -> Result<String, CliError> {
+        let rollout_status = self.server_connection.get_rollout_status().await?;
+        output_debug!("Got rollout status: {:?}", rollout_status);
+
+        let mut out_text = format!("Rollout state: {}\n", rollout_status.state);
+        if !rollout_status.message.is_empty() {
+            out_text.push_str(&format!("{}\n", rollout_status.message));
+        }
+
+        if !rollout_status.groups.is_empty() {
+            let groups: Vec<GetRolloutGroupTableDisplay> = rollout_status
+                .groups
+                .into_iter()
+                .map(GetRolloutGroupTableDisplay::from)
+                .collect();
+            out_text.push_str(&Table::new(groups).with(Style::blank()).to_string());
+        }
+
+        Ok(out_text)
+    }
+
+    // [impl->swdd~cli-provides-list-of-agents~2]
+    pub async fn get_agents_table(&mut self) -> Result<String, CliError> {
+        let complete_state = self
+            .server_connection
+            .get_complete_state(&vec!["system".to_string(), "workloadStates".to_string()])
+            .await?;
+        let CompleteState {
+            system,
+            workload_states,
+            ..
+        } = *complete_state;
+        output_debug!("Got system state: {:?}", system);
+
+        let agents: Vec<GetAgentTableDisplay> = system
+            .agents
+            .into_iter()
+            .map(|agent_info| GetAgentTableDisplay::new(agent_info, &workload_states))
+            .collect();
+        Ok(Table::new(agents).with(Style::blank()).to_string())
+    }
+
+    // [impl->swdd~cli-provides-events~1]
+    pub async fn get_events_table(&mut self, since: u64, limit: u32) -> Result<String, CliError> {
+        let events = self
+            .server_connection
+            .get_events(EventsRequest { since, limit })
+            .await?;
+        output_debug!("Got events: {:?}", events);
+
+        let events: Vec<GetEventTableDisplay> = events
+            .events
+            .into_iter()
+            .map(GetEventTableDisplay::from)
+            .collect();
+        Ok(Table::new(events).with(Style::blank()).to_string())
+    }
+
+    // [impl->swdd~cli-provides-scheduler-queue~1]
+    pub async fn get_scheduler_queue_table(
+        &mut self,
+        agent_name: Option<String>,
+    ) -> Result<String, CliError> {
+        let scheduler_queue = self
+            .server_connection
+            .get_scheduler_queue(SchedulerQueueRequest {
+                agent_name: agent_name.unwrap_or_default(),
+            })
+            .await?;
+        output_debug!("Got scheduler queue: {:?}", scheduler_queue);
+
+        let pending_operations: Vec<GetPendingOperationTableDisplay> = scheduler_queue
+            .pending_operations
+            .into_iter()
+            .map(GetPendingOperationTableDisplay::from)
+            .collect();
+        Ok(Table::new(pending_operations)
+            .with(Style::blank())
+            .to_string())
+    }
+
+    // [impl->swdd~cli-exports-dependency-graph~1]
+    pub async fn get_dependency_graph(&mut self, format: GraphFormat) -> Result<String, CliError> {
+        let dependency_graph = self.server_connection.get_dependency_graph().await?;
+        output_debug!("Got dependency graph: {:?}", dependency_graph);
+
+        match format {
+            GraphFormat::Dot => Ok(dependency_graph_to_dot(&dependency_graph)),
+            GraphFormat::Json => Ok(serde_json::to_string_pretty(&dependency_graph)?),
+        }
+    }
+
+    // [impl->swdd~cli-creates-support-bundle~1]
+    pub async fn create_support_bundle(&mut self, mtime: u64) -> Result<Vec<u8>, CliError> {
+        let complete_state = self
+            .server_connection
+            .get_complete_state(&Vec::new())
+            .await?;
+        let rollout_status = self.server_connection.get_rollout_status().await?;
+        let support_info = self.server_connection.get_support_info().await?;
+        output_debug!("Got support info: {:?}", support_info);
+        let events = self
+            .server_connection
+            .get_events(EventsRequest::default())
+            .await?;
+
+        let version_info = support_bundle::VersionInfo {
+            cli_version: env!("CARGO_PKG_VERSION").to_string(),
+            server_version: support_info.server_version,
+            agents: support_info.agents,
+        };
+
+        support_bundle::create_tar_archive(
+            &[
+                (
+                    support_bundle::COMPLETE_STATE_FILE_NAME,
+                    serde_yaml::to_string(&complete_state)?.into_bytes(),
+                ),
+                (
+                    support_bundle::ROLLOUT_STATUS_FILE_NAME,
+                    serde_yaml::to_string(&rollout_status)?.into_bytes(),
+                ),
+                (
+                    support_bundle::VERSIONS_FILE_NAME,
+                    serde_yaml::to_string(&version_info)?.into_bytes(),
+                ),
+                (
+                    support_bundle::EVENTS_FILE_NAME,
+                    serde_yaml::to_string(&events)?.into_bytes(),
+                ),
+            ],
+            mtime,
+        )
+        .map_err(CliError::ExecutionError)
+    }
+
     async fn get_workloads(
         &mut self,
+        show_terminated: bool,
     ) -> Result<Vec<(WorkloadInstanceName, GetWorkloadTableDisplay)>, CliError> {
         let res_complete_state = self
             .server_connection
@@ -519,12 +1007,78 @@ impl CliCommands {
             }
         }
 
+        // [impl->swdd~cli-shows-terminated-workloads~1]
+        if show_terminated {
+            workload_infos.extend(res_complete_state.system.terminated_workloads.iter().map(
+                |terminated_workload| {
+                    (
+                        terminated_workload.final_state.instance_name.clone(),
+                        GetWorkloadTableDisplay::from_terminated_workload(terminated_workload),
+                    )
+                },
+            ));
+        }
+
         Ok(workload_infos)
     }
 
+    // [impl->swdd~cli-confirms-removal-of-workloads-with-dependents~1]
+    async fn confirm_removal_of_workloads(
+        &mut self,
+        workload_names: Vec<String>,
+        yes: bool,
+        stdin_provides_manifest: bool,
+    ) -> Result<(), CliError> {
+        let impact_analysis = self
+            .server_connection
+            .get_impact_analysis(workload_names)
+            .await?;
+        if impact_analysis.impacted_workloads.is_empty() {
+            return Ok(());
+        }
+
+        let impacted_workloads: Vec<ImpactedWorkloadTableDisplay> = impact_analysis
+            .impacted_workloads
+            .into_iter()
+            .map(ImpactedWorkloadTableDisplay::from)
+            .collect();
+        output!(
+            "The following workloads depend on the workload(s) to be deleted:\n{}",
+            Table::new(impacted_workloads).with(Style::blank())
+        );
+        if yes {
+            return Ok(());
+        }
+
+        // [impl->swdd~cli-requires-yes-flag-without-interactive-stdin~1]
+        if stdin_provides_manifest || !stdin_is_terminal() {
+            return Err(CliError::ExecutionError(
+                "Cannot ask for a confirmation as stdin is not interactive. Use '--yes' to skip the confirmation.".to_string(),
+            ));
+        }
+
+        output!("Do you want to continue? [y/N]");
+        let answer = read_confirmation().map_err(|err| {
+            CliError::ExecutionError(format!("Could not read the confirmation: '{err}'"))
+        })?;
+        match answer.trim().to_lowercase().as_str() {
+            "y" | "yes" => Ok(()),
+            _ => Err(CliError::ExecutionError(
+                "Aborted by the user. Use '--yes' to skip the confirmation.".to_string(),
+            )),
+        }
+    }
+
     // [impl->swdd~cli-provides-delete-workload~1]
     // [impl->swdd~cli-blocks-until-ankaios-server-responds-delete-workload~2]
-    pub async fn delete_workloads(&mut self, workload_names: Vec<String>) -> Result<(), CliError> {
+    pub async fn delete_workloads(
+        &mut self,
+        workload_names: Vec<String>,
+        yes: bool,
+    ) -> Result<(), CliError> {
+        self.confirm_removal_of_workloads(workload_names.clone(), yes, false)
+            .await?;
+
         let complete_state_update = CompleteState::default();
 
         let update_mask = workload_names
@@ -543,6 +1097,100 @@ impl CliCommands {
             .await
     }
 
+    // [impl->swdd~cli-operates-on-workload-groups~1]
+    async fn get_group_members(&mut self, group_name: &str) -> Result<Vec<String>, CliError> {
+        let complete_state = self
+            .server_connection
+            .get_complete_state(&vec![
+                "desiredState.workloads".to_string(),
+                format!("desiredState.workloadGroups.{group_name}"),
+            ])
+            .await?;
+        complete_state
+            .desired_state
+            .members_of_group(group_name)
+            .ok_or_else(|| {
+                CliError::ExecutionError(format!("Workload group '{group_name}' does not exist."))
+            })
+    }
+
+    // [impl->swdd~cli-operates-on-workload-groups~1]
+    pub async fn set_group_stopped(
+        &mut self,
+        group_name: String,
+        stopped: bool,
+    ) -> Result<(), CliError> {
+        self.get_group_members(&group_name).await?;
+
+        let mut complete_state_update = CompleteState::default();
+        complete_state_update.desired_state.workload_groups.insert(
+            group_name.clone(),
+            WorkloadGroup {
+                stopped,
+                ..Default::default()
+            },
+        );
+        let update_mask = vec![format!("desiredState.workloadGroups.{group_name}.stopped")];
+
+        output_debug!(
+            "Updating the workload group with the complete state {:?}",
+            complete_state_update
+        );
+        self.update_state_and_wait_for_complete(complete_state_update, update_mask)
+            .await
+    }
+
+    // [impl->swdd~cli-operates-on-workload-groups~1]
+    pub async fn restart_group(&mut self, group_name: String) -> Result<(), CliError> {
+        self.set_group_stopped(group_name.clone(), true).await?;
+        self.set_group_stopped(group_name, false).await
+    }
+
+    // [impl->swdd~cli-operates-on-workload-groups~1]
+    pub async fn delete_group(&mut self, group_name: String, yes: bool) -> Result<(), CliError> {
+        let members = self.get_group_members(&group_name).await?;
+        if !members.is_empty() {
+            self.confirm_removal_of_workloads(members.clone(), yes, false)
+                .await?;
+        }
+
+        let update_mask = members
+            .into_iter()
+            .map(|member| format!("desiredState.workloads.{member}"))
+            .chain([format!("desiredState.workloadGroups.{group_name}")])
+            .collect();
+
+        output_debug!(
+            "Updating with empty complete state and update mask {:?}",
+            update_mask
+        );
+        self.update_state_and_wait_for_complete(CompleteState::default(), update_mask)
+            .await
+    }
+
+    // [impl->swdd~cli-drains-agent~1]
+    pub async fn drain_agent(
+        &mut self,
+        agent_name: String,
+        target_agent: Option<String>,
+        remove: bool,
+    ) -> Result<(), CliError> {
+        let drain_agent_request = DrainAgentRequest {
+            agent_name,
+            target_agent,
+            remove,
+        };
+        output_debug!("Request to drain agent: {:?}", drain_agent_request);
+
+        let update_state_success = self
+            .server_connection
+            .drain_agent(drain_agent_request)
+            .await?;
+
+        self.wait_for_update_state_success(update_state_success)
+            .await
+    }
+
     // [impl->swdd~cli-provides-run-workload~1]
     // [impl->swdd~cli-blocks-until-ankaios-server-responds-run-workload~2]
     pub async fn run_workload(
@@ -595,7 +1243,16 @@ impl CliCommands {
             .update_state(new_state, update_mask)
             .await?;
 
+        self.wait_for_update_state_success(update_state_success)
+            .await
+    }
+
+    async fn wait_for_update_state_success(
+        &mut self,
+        update_state_success: UpdateStateSuccess,
+    ) -> Result<(), CliError> {
         output_debug!("Got update success: {:?}", update_state_success);
+        let rejected_workloads = update_state_success.rejected_workloads.clone();
 
         // [impl->swdd~cli-requests-update-state-with-watch-error~1]
         let update_state_success = ParsedUpdateStateSuccess::try_from(update_state_success)
@@ -605,11 +1262,16 @@ impl CliCommands {
                 ))
             })?;
 
-        if self.no_wait {
+        if !self.no_wait {
+            // [impl->swdd~cli-requests-update-state-with-watch-success~1]
+            self.wait_for_complete(update_state_success).await?;
+        }
+
+        // [impl->swdd~cli-reports-rejected-workloads~1]
+        if rejected_workloads.is_empty() {
             Ok(())
         } else {
-            // [impl->swdd~cli-requests-update-state-with-watch-success~1]
-            self.wait_for_complete(update_state_success).await
+            Err(CliError::WorkloadsRejected(rejected_workloads))
         }
     }
 
@@ -629,7 +1291,7 @@ impl CliCommands {
             output!("Successfully applied the manifest(s).\nWaiting for workload(s) to reach desired states (press Ctrl+C to interrupt).\n");
         }
 
-        let states_of_all_workloads = self.get_workloads().await.unwrap();
+        let states_of_all_workloads = self.get_workloads(false).await.unwrap();
         let states_of_changed_workloads = states_of_all_workloads
             .into_iter()
             .filter(|x| changed_workloads.contains(&x.0))
@@ -659,8 +1321,24 @@ impl CliCommands {
 
         wait_list.update(missed_workload_states);
         let mut spinner_interval = interval(Duration::from_millis(100));
+        let deadline = self.wait_timeout.map(|timeout| Instant::now() + timeout);
 
         while !wait_list.is_empty() {
+            // [impl->swdd~cli-stops-waiting-after-timeout~1]
+            // [impl->swdd~cli-exits-with-wait-outcome~1]
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                let waiting_on_dependencies =
+                    wait_list.get_workload_names_waiting_on_dependencies();
+                let pending = wait_list
+                    .get_pending_workload_names()
+                    .into_iter()
+                    .filter(|workload_name| !waiting_on_dependencies.contains(workload_name))
+                    .collect();
+                return Err(CliError::WaitTimeout {
+                    waiting_on_dependencies,
+                    pending,
+                });
+            }
             tokio::select! {
                 update_workload_state = self.server_connection.read_next_update_workload_state() => {
                     let update_workload_state = update_workload_state?;
@@ -672,6 +1350,12 @@ impl CliCommands {
                 }
             }
         }
+
+        // [impl->swdd~cli-exits-with-wait-outcome~1]
+        let failed_workloads = wait_list.get_failed_workload_names();
+        if !failed_workloads.is_empty() {
+            return Err(CliError::WorkloadsFailed(failed_workloads));
+        }
         Ok(())
     }
 
@@ -684,6 +1368,20 @@ impl CliCommands {
                     generate_state_obj_and_filter_masks_from_manifests(&mut manifests, &apply_args)
                         .map_err(CliError::ExecutionError)?;
 
+                if apply_args.delete_mode {
+                    let workload_names = filter_masks
+                        .iter()
+                        .filter_map(|mask| mask.strip_prefix("desiredState.workloads."))
+                        .map(str::to_owned)
+                        .collect();
+                    self.confirm_removal_of_workloads(
+                        workload_names,
+                        apply_args.yes,
+                        apply_args.reads_manifest_from_stdin(),
+                    )
+                    .await?;
+                }
+
                 // [impl->swdd~cli-apply-send-update-state~1]
                 self.update_state_and_wait_for_complete(complete_state_req_obj, filter_masks)
                     .await
@@ -691,6 +1389,54 @@ impl CliCommands {
             Err(err) => Err(CliError::ExecutionError(err.to_string())),
         }
     }
+
+    // [impl->swdd~cli-diffs-manifests~1]
+    pub async fn diff_manifests(&mut self, apply_args: ApplyArgs) -> Result<String, CliError> {
+        use apply_manifests::*;
+        let mut manifests = apply_args
+            .get_input_sources()
+            .map_err(|err| CliError::ExecutionError(err.to_string()))?;
+        let (complete_state_req_obj, filter_masks) =
+            generate_state_obj_and_filter_masks_from_manifests(&mut manifests, &apply_args)
+                .map_err(CliError::ExecutionError)?;
+
+        let current_state = self
+            .server_connection
+            .get_complete_state(&vec!["desiredState".to_string()])
+            .await?
+            .desired_state;
+
+        // the manifests replace the entries selected by the filter masks, deleting absent ones
+        let requested_state = &complete_state_req_obj.desired_state;
+        let mut new_state = current_state.clone();
+        for filter_mask in &filter_masks {
+            let path: Path = filter_mask.into();
+            let [_, kind, name] = path.parts().as_slice() else {
+                continue;
+            };
+            match kind.as_str() {
+                "workloads" => match requested_state.workloads.get(name) {
+                    Some(workload) => {
+                        new_state.workloads.insert(name.clone(), workload.clone());
+                    }
+                    None => {
+                        new_state.workloads.remove(name);
+                    }
+                },
+                "configs" => match requested_state.configs.get(name) {
+                    Some(config) => {
+                        new_state.configs.insert(name.clone(), config.clone());
+                    }
+                    None => {
+                        new_state.configs.remove(name);
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        Ok(diff_states(&current_state, &new_state).to_string())
+    }
 }
 
 //////////////////////////////////////////////////////////////////////////////
@@ -703,37 +1449,51 @@ impl CliCommands {
 #[cfg(test)]
 mod tests {
     use common::{
-        commands::{Response, UpdateStateSuccess, UpdateWorkloadState},
+        commands::{
+            DependencyGraph, DependencyGraphEdge, DependencyGraphNode, DrainAgentRequest, Event,
+            EventKind, Events, EventsRequest, ImpactAnalysis, ImpactedWorkload, PendingOperation,
+            PendingWorkloadOperation, RejectedWorkload, Response, RolloutGroupStatus, RolloutState,
+            RolloutStatus, SchedulerQueue, SchedulerQueueRequest, SupportInfo,
+            UnfulfilledDependency, UpdateStateSuccess, UpdateWorkloadState,
+        },
         from_server_interface::{FromServer, FromServerSender},
         objects::{
-            self, generate_test_workload_spec_with_param, CompleteState, ExecutionState,
-            RunningSubstate, State, StoredWorkloadSpec, Tag, WorkloadState,
+            self, generate_test_workload_spec_with_param, generate_test_workload_state_with_agent,
+            AddCondition, AgentConnectionStatus, AgentInfo, AgentResources, CompleteState,
+            ExecutionState, RunningSubstate, State, StoredWorkloadSpec, SystemState, Tag,
+            WorkloadGroup, WorkloadState,
         },
         state_manipulation::{Object, Path},
         test_utils::{self, generate_test_complete_state},
         to_server_interface::ToServerReceiver,
     };
     use mockall::predicate::eq;
-    use std::io;
+    use std::{collections::HashMap, io};
     use tabled::{settings::Style, Table};
 
     use super::apply_manifests::{
         create_filter_masks_from_paths, generate_state_obj_and_filter_masks_from_manifests,
-        handle_agent_overwrite, parse_manifest, update_request_obj, InputSourcePair,
+        handle_agent_overwrite, handle_profile_selection, parse_manifest, update_request_obj,
+        InputSourcePair,
     };
     use crate::{
-        cli::OutputFormat,
+        cli::{GraphFormat, OutputFormat},
         cli_commands::{
             generate_compact_state_output, get_filtered_value,
-            server_connection::MockServerConnection, update_compact_state, ApplyArgs,
-            GetWorkloadTableDisplay,
+            server_connection::MockServerConnection, support_bundle, update_compact_state,
+            ApplyArgs, GetAgentTableDisplay, GetEventTableDisplay, GetPendingOperationTableDisplay,
+            GetRolloutGroupTableDisplay, GetWorkloadTableDisplay,
         },
     };
     use serde_yaml::Value;
     use std::io::Read;
 
-    use super::CliCommands;
+    use super::{
+        CliCommands, CliError, EXIT_CODE_WAITING_ON_DEPENDENCIES, EXIT_CODE_WAIT_TIMEOUT,
+        EXIT_CODE_WORKLOADS_FAILED, EXIT_CODE_WORKLOADS_REJECTED,
+    };
 
+    use std::time::Duration;
     use url::Url;
 
     const RESPONSE_TIMEOUT_MS: u64 = 3000;
@@ -759,6 +1519,24 @@ mod tests {
 
         pub static ref FAKE_OPEN_MANIFEST_MOCK_RESULT_LIST: std::sync::Mutex<std::collections::VecDeque<io::Result<InputSourcePair>>>  =
         std::sync::Mutex::new(std::collections::VecDeque::new());
+
+        pub static ref FAKE_READ_CONFIRMATION_MOCK_RESULT_LIST: std::sync::Mutex<std::collections::VecDeque<io::Result<String>>>  =
+        std::sync::Mutex::new(std::collections::VecDeque::new());
+    }
+
+    pub static FAKE_STDIN_IS_TERMINAL: std::sync::atomic::AtomicBool =
+        std::sync::atomic::AtomicBool::new(true);
+
+    pub fn stdin_is_terminal_mock() -> bool {
+        FAKE_STDIN_IS_TERMINAL.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    pub fn read_confirmation_mock() -> io::Result<String> {
+        FAKE_READ_CONFIRMATION_MOCK_RESULT_LIST
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap()
     }
 
     pub async fn read_to_string_mock(_file: String) -> io::Result<String> {
@@ -782,6 +1560,7 @@ mod tests {
 
     pub fn open_manifest_mock(
         _file_path: &str,
+        _public_key: Option<&str>,
     ) -> io::Result<(String, Box<dyn io::Read + Send + Sync + 'static>)> {
         FAKE_OPEN_MANIFEST_MOCK_RESULT_LIST
             .lock()
@@ -801,10 +1580,11 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
-        let cmd_text = cmd.get_workloads_table(None, None, Vec::new()).await;
+        let cmd_text = cmd.get_workloads_table(None, None, Vec::new(), false).await;
         assert!(cmd_text.is_ok());
 
         let expected_empty_table: Vec<GetWorkloadTableDisplay> = Vec::new();
@@ -815,6 +1595,356 @@ mod tests {
         assert_eq!(cmd_text.unwrap(), expected_table_text);
     }
 
+    // [utest->swdd~cli-provides-rollout-status~1]
+    #[tokio::test]
+    async fn utest_get_rollout_status() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_rollout_status()
+            .return_once(|| {
+                Ok(RolloutStatus {
+                    state: RolloutState::Halted,
+                    groups: vec![RolloutGroupStatus {
+                        name: "canary".to_string(),
+                        state: RolloutState::Halted,
+                        agents: vec!["agent_A".to_string(), "agent_B".to_string()],
+                        workloads: 2,
+                        failed_workloads: 1,
+                    }],
+                    message: "halted".to_string(),
+                })
+            });
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let cmd_text = cmd.get_rollout_status().await.unwrap();
+
+        let expected_table_text = Table::new(vec![GetRolloutGroupTableDisplay {
+            name: "canary".to_string(),
+            state: "Halted".to_string(),
+            agents: "agent_A, agent_B".to_string(),
+            workloads: 2,
+            failed_workloads: 1,
+        }])
+        .with(Style::blank())
+        .to_string();
+        assert_eq!(
+            cmd_text,
+            format!("Rollout state: Halted\nhalted\n{expected_table_text}")
+        );
+    }
+
+    // [utest->swdd~cli-provides-list-of-agents~2]
+    #[tokio::test]
+    async fn utest_get_agents_table() {
+        let test_data = CompleteState {
+            workload_states: vec![
+                generate_test_workload_state_with_agent(
+                    "workload_1",
+                    "agent_A",
+                    ExecutionState::running(),
+                ),
+                generate_test_workload_state_with_agent(
+                    "workload_2",
+                    "agent_A",
+                    ExecutionState::running(),
+                ),
+                generate_test_workload_state_with_agent(
+                    "workload_3",
+                    "agent_A",
+                    ExecutionState::failed("error"),
+                ),
+                generate_test_workload_state_with_agent(
+                    "workload_4",
+                    "agent_B",
+                    ExecutionState::agent_disconnected(),
+                ),
+            ],
+            system: SystemState {
+                agents: vec![
+                    AgentInfo {
+                        agent_name: "agent_A".to_string(),
+                        version: "0.4.0".to_string(),
+                        connection_status: AgentConnectionStatus::Connected,
+                        last_heartbeat: 1_700_000_000_000,
+                        runtimes: vec!["podman".to_string(), "podman-kube".to_string()],
+                        resources: Some(AgentResources {
+                            free_cpu_millis: 1500,
+                            free_memory_bytes: 512 * 1024 * 1024,
+                        }),
+                        ..Default::default()
+                    },
+                    AgentInfo {
+                        agent_name: "agent_B".to_string(),
+                        version: "0.3.1".to_string(),
+                        connection_status: AgentConnectionStatus::Disconnected,
+                        last_heartbeat: 1_700_000_000_000,
+                        runtimes: vec!["podman".to_string()],
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec!["system".to_string(), "workloadStates".to_string()]))
+            .return_once(|_| Ok(Box::new(test_data)));
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let cmd_text = cmd.get_agents_table().await.unwrap();
+
+        let expected_table_text = Table::new(vec![
+            GetAgentTableDisplay {
+                name: "agent_A".to_string(),
+                version: "0.4.0".to_string(),
+                status: "Connected".to_string(),
+                runtimes: "podman, podman-kube".to_string(),
+                workloads: 3,
+                workload_states: "Failed: 1, Running: 2".to_string(),
+                free_resources: "cpu: 1500m, memory: 512Mi".to_string(),
+                last_heartbeat: "2023-11-14T22:13:20Z".to_string(),
+            },
+            GetAgentTableDisplay {
+                name: "agent_B".to_string(),
+                version: "0.3.1".to_string(),
+                status: "Disconnected".to_string(),
+                runtimes: "podman".to_string(),
+                workloads: 1,
+                workload_states: "AgentDisconnected: 1".to_string(),
+                free_resources: "".to_string(),
+                last_heartbeat: "2023-11-14T22:13:20Z".to_string(),
+            },
+        ])
+        .with(Style::blank())
+        .to_string();
+        assert_eq!(cmd_text, expected_table_text);
+    }
+
+    // [utest->swdd~cli-provides-events~1]
+    #[tokio::test]
+    async fn utest_get_events_table() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_events()
+            .with(eq(EventsRequest {
+                since: 1000,
+                limit: 10,
+            }))
+            .return_once(|_| {
+                Ok(Events {
+                    events: vec![Event {
+                        timestamp: 1_700_000_000_000,
+                        kind: EventKind::WorkloadStateChanged,
+                        agent_name: Some("agent_A".to_string()),
+                        workload_name: Some("nginx".to_string()),
+                        message: "Running(Ok)".to_string(),
+                        trace_id: Some("trace_id".to_string()),
+                        agent_timestamp: None,
+                    }],
+                })
+            });
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let cmd_text = cmd.get_events_table(1000, 10).await.unwrap();
+
+        let expected_table_text = Table::new(vec![GetEventTableDisplay {
+            time: "2023-11-14T22:13:20Z".to_string(),
+            kind: "WorkloadStateChanged".to_string(),
+            agent: "agent_A".to_string(),
+            workload: "nginx".to_string(),
+            message: "Running(Ok)".to_string(),
+            trace_id: "trace_id".to_string(),
+        }])
+        .with(Style::blank())
+        .to_string();
+        assert_eq!(cmd_text, expected_table_text);
+    }
+
+    // [utest->swdd~cli-provides-scheduler-queue~1]
+    #[tokio::test]
+    async fn utest_get_scheduler_queue_table() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_scheduler_queue()
+            .with(eq(SchedulerQueueRequest {
+                agent_name: "agent_A".to_string(),
+            }))
+            .return_once(|_| {
+                Ok(SchedulerQueue {
+                    pending_operations: vec![PendingWorkloadOperation {
+                        workload_name: "frontend".to_string(),
+                        agent: "agent_A".to_string(),
+                        operation: PendingOperation::Create,
+                        unfulfilled_dependencies: vec![
+                            UnfulfilledDependency {
+                                workload_name: "backend".to_string(),
+                                condition: "ADD_COND_RUNNING".to_string(),
+                            },
+                            UnfulfilledDependency {
+                                workload_name: "init".to_string(),
+                                condition: "ADD_COND_SUCCEEDED".to_string(),
+                            },
+                        ],
+                    }],
+                })
+            });
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let cmd_text = cmd
+            .get_scheduler_queue_table(Some("agent_A".to_string()))
+            .await
+            .unwrap();
+
+        let expected_table_text = Table::new(vec![GetPendingOperationTableDisplay {
+            name: "frontend".to_string(),
+            agent: "agent_A".to_string(),
+            operation: "Create".to_string(),
+            waiting_for: "backend: ADD_COND_RUNNING, init: ADD_COND_SUCCEEDED".to_string(),
+        }])
+        .with(Style::blank())
+        .to_string();
+        assert_eq!(cmd_text, expected_table_text);
+    }
+
+    fn generate_test_dependency_graph() -> DependencyGraph {
+        DependencyGraph {
+            nodes: vec![
+                DependencyGraphNode {
+                    workload_name: "database".to_string(),
+                    agent: "agent_A".to_string(),
+                    execution_state: Some(ExecutionState::running()),
+                },
+                DependencyGraphNode {
+                    workload_name: "nginx".to_string(),
+                    agent: "agent_B".to_string(),
+                    execution_state: None,
+                },
+            ],
+            edges: vec![DependencyGraphEdge {
+                workload_name: "nginx".to_string(),
+                dependency: "database".to_string(),
+                condition: AddCondition::AddCondRunning,
+            }],
+        }
+    }
+
+    // [utest->swdd~cli-exports-dependency-graph~1]
+    #[tokio::test]
+    async fn utest_get_dependency_graph_as_dot() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_dependency_graph()
+            .return_once(|| Ok(generate_test_dependency_graph()));
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let cmd_text = cmd.get_dependency_graph(GraphFormat::Dot).await.unwrap();
+
+        assert_eq!(
+            cmd_text,
+            concat!(
+                "digraph workloads {\n",
+                "    \"database\" [label=\"database\\nagent_A\\nRunning(Ok)\"];\n",
+                "    \"nginx\" [label=\"nginx\\nagent_B\\nUnknown\"];\n",
+                "    \"nginx\" -> \"database\" [label=\"ADD_COND_RUNNING\"];\n",
+                "}"
+            )
+        );
+    }
+
+    // [utest->swdd~cli-exports-dependency-graph~1]
+    #[tokio::test]
+    async fn utest_get_dependency_graph_as_json() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_dependency_graph()
+            .return_once(|| Ok(generate_test_dependency_graph()));
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let cmd_text = cmd.get_dependency_graph(GraphFormat::Json).await.unwrap();
+
+        let dependency_graph: DependencyGraph = serde_json::from_str(&cmd_text).unwrap();
+        assert_eq!(dependency_graph, generate_test_dependency_graph());
+    }
+
+    // [utest->swdd~cli-creates-support-bundle~1]
+    #[tokio::test]
+    async fn utest_create_support_bundle() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![]))
+            .return_once(|_| Ok(Box::new(test_utils::generate_test_complete_state(vec![]))));
+        mock_server_connection
+            .expect_get_rollout_status()
+            .return_once(|| Ok(RolloutStatus::default()));
+        mock_server_connection
+            .expect_get_events()
+            .with(eq(EventsRequest::default()))
+            .return_once(|_| Ok(Events::default()));
+        mock_server_connection
+            .expect_get_support_info()
+            .return_once(|| {
+                Ok(SupportInfo {
+                    server_version: "0.3.1".to_string(),
+                    agents: vec![AgentInfo {
+                        agent_name: "agent_A".to_string(),
+                        version: "0.3.0".to_string(),
+                        ..Default::default()
+                    }],
+                })
+            });
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let archive = cmd.create_support_bundle(0).await.unwrap();
+
+        let archive_text = String::from_utf8_lossy(&archive);
+        assert!(archive_text.contains(support_bundle::COMPLETE_STATE_FILE_NAME));
+        assert!(archive_text.contains(support_bundle::ROLLOUT_STATUS_FILE_NAME));
+        assert!(archive_text.contains(support_bundle::VERSIONS_FILE_NAME));
+        assert!(archive_text.contains(support_bundle::EVENTS_FILE_NAME));
+        assert!(archive_text.contains(&format!("cliVersion: {}", env!("CARGO_PKG_VERSION"))));
+        assert!(archive_text.contains("serverVersion: 0.3.1"));
+        assert!(archive_text.contains("agentName: agent_A"));
+    }
+
     // [utest->swdd~cli-provides-list-of-workloads~1]
     // [utest->swdd~cli-blocks-until-ankaios-server-responds-list-workloads~1]
     // [utest->swdd~cli-shall-present-list-of-workloads~1]
@@ -848,10 +1978,11 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
-        let cmd_text = cmd.get_workloads_table(None, None, Vec::new()).await;
+        let cmd_text = cmd.get_workloads_table(None, None, Vec::new(), false).await;
         assert!(cmd_text.is_ok());
 
         let expected_table: Vec<GetWorkloadTableDisplay> = vec![
@@ -910,11 +2041,12 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
         let cmd_text = cmd
-            .get_workloads_table(None, None, vec!["name1".to_string()])
+            .get_workloads_table(None, None, vec!["name1".to_string()], false)
             .await;
         assert!(cmd_text.is_ok());
 
@@ -958,10 +2090,11 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
         let cmd_text = cmd
-            .get_workloads_table(Some("agent_B".to_string()), None, Vec::new())
+            .get_workloads_table(Some("agent_B".to_string()), None, Vec::new(), false)
             .await;
         assert!(cmd_text.is_ok());
 
@@ -1014,10 +2147,11 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
         let cmd_text = cmd
-            .get_workloads_table(None, Some("Failed".to_string()), Vec::new())
+            .get_workloads_table(None, Some("Failed".to_string()), Vec::new(), false)
             .await;
         assert!(cmd_text.is_ok());
 
@@ -1046,10 +2180,11 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
-        let cmd_text = cmd.get_workloads_table(None, None, Vec::new()).await;
+        let cmd_text = cmd.get_workloads_table(None, None, Vec::new(), false).await;
         assert!(cmd_text.is_ok());
 
         let expected_empty_table: Vec<GetWorkloadTableDisplay> =
@@ -1067,6 +2202,70 @@ mod tests {
         assert_eq!(cmd_text.unwrap(), expected_table_text);
     }
 
+    // [utest->swdd~cli-shows-terminated-workloads~1]
+    #[tokio::test]
+    async fn utest_get_workloads_show_terminated() {
+        let test_data = objects::CompleteState {
+            workload_states: vec![generate_test_workload_state_with_agent(
+                "Workload_1",
+                "agent_A",
+                ExecutionState::running(),
+            )],
+            system: SystemState {
+                terminated_workloads: vec![objects::TerminatedWorkload {
+                    final_state: generate_test_workload_state_with_agent(
+                        "Workload_2",
+                        "agent_A",
+                        ExecutionState::failed("exit code 1"),
+                    ),
+                    removed_at: 1_700_000_000_000,
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![]))
+            .times(2)
+            .returning(move |_| Ok(Box::new(test_data.clone())));
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let running_workload = GetWorkloadTableDisplay::new(
+            "Workload_1",
+            "agent_A",
+            Default::default(),
+            &ExecutionState::running().state.to_string(),
+            Default::default(),
+        );
+        let terminated_workload = GetWorkloadTableDisplay::new(
+            "Workload_2",
+            "agent_A",
+            Default::default(),
+            &ExecutionState::failed("").state.to_string(),
+            "exit code 1 (removed at 2023-11-14T22:13:20Z)",
+        );
+
+        let cmd_text = cmd.get_workloads_table(None, None, Vec::new(), false).await;
+        let expected_table_text = Table::new(vec![running_workload.clone()])
+            .with(Style::blank())
+            .to_string();
+        assert_eq!(cmd_text.unwrap(), expected_table_text);
+
+        let cmd_text = cmd.get_workloads_table(None, None, Vec::new(), true).await;
+        let expected_table_text = Table::new(vec![running_workload, terminated_workload])
+            .with(Style::blank())
+            .to_string();
+        assert_eq!(cmd_text.unwrap(), expected_table_text);
+    }
+
     // [utest->swdd~cli-provides-delete-workload~1]
     // [utest->swdd~cli-blocks-until-ankaios-server-responds-delete-workload~2]
     #[tokio::test]
@@ -1078,6 +2277,9 @@ mod tests {
         let complete_state_update = CompleteState::default();
 
         let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_impact_analysis()
+            .return_once(|_| Ok(ImpactAnalysis::default()));
         mock_server_connection
             .expect_update_state()
             .with(
@@ -1094,6 +2296,7 @@ mod tests {
                         "name1.abc.agent_B".to_string(),
                         "name2.abc.agent_B".to_string(),
                     ],
+                    rejected_workloads: vec![],
                 })
             });
         mock_server_connection
@@ -1112,6 +2315,8 @@ mod tests {
                                 state: objects::ExecutionStateEnum::Removed,
                                 additional_info: "".to_string(),
                             },
+                            agent_timestamp: None,
+                            server_timestamp: None,
                         },
                         WorkloadState {
                             instance_name: "name2.abc.agent_B".try_into().unwrap(),
@@ -1119,6 +2324,8 @@ mod tests {
                                 state: objects::ExecutionStateEnum::Removed,
                                 additional_info: "".to_string(),
                             },
+                            agent_timestamp: None,
+                            server_timestamp: None,
                         },
                     ],
                 })]
@@ -1127,49 +2334,500 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let delete_result = cmd
+            .delete_workloads(vec!["name1".to_string(), "name2".to_string()], false)
+            .await;
+        assert!(delete_result.is_ok());
+    }
+
+    // [utest->swdd~cli-stops-waiting-after-timeout~1]
+    #[tokio::test]
+    async fn utest_delete_workloads_wait_timeout() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let complete_state_update = CompleteState::default();
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_impact_analysis()
+            .return_once(|_| Ok(ImpactAnalysis::default()));
+        mock_server_connection
+            .expect_update_state()
+            .return_once(|_, _| {
+                Ok(UpdateStateSuccess {
+                    added_workloads: vec![],
+                    deleted_workloads: vec!["name1.abc.agent_B".to_string()],
+                    rejected_workloads: vec![],
+                })
+            });
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![]))
+            .return_once(|_| Ok(Box::new(complete_state_update)));
+        mock_server_connection
+            .expect_take_missed_from_server_messages()
+            .return_once(std::vec::Vec::new);
+        mock_server_connection
+            .expect_read_next_update_workload_state()
+            .returning(|| {
+                Ok(UpdateWorkloadState {
+                    workload_states: vec![],
+                })
+            });
+
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: Some(Duration::from_millis(10)),
+            server_connection: mock_server_connection,
+        };
+
+        let delete_result = cmd.delete_workloads(vec!["name1".to_string()], false).await;
+        assert_eq!(
+            delete_result,
+            Err(CliError::WaitTimeout {
+                waiting_on_dependencies: vec![],
+                pending: vec!["name1".to_string()],
+            })
+        );
+    }
+
+    // [utest->swdd~cli-drains-agent~1]
+    #[tokio::test]
+    async fn utest_drain_agent_no_wait() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_drain_agent()
+            .with(eq(DrainAgentRequest {
+                agent_name: "agent_A".to_string(),
+                target_agent: Some("agent_B".to_string()),
+                remove: true,
+            }))
+            .return_once(|_| {
+                Ok(UpdateStateSuccess {
+                    added_workloads: vec!["name1.abc.agent_B".to_string()],
+                    deleted_workloads: vec!["name1.abc.agent_A".to_string()],
+                    rejected_workloads: vec![],
+                })
+            });
+
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: true,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let drain_result = cmd
+            .drain_agent("agent_A".to_string(), Some("agent_B".to_string()), true)
+            .await;
+        assert!(drain_result.is_ok());
+    }
+
+    // [utest->swdd~cli-provides-delete-workload~1]
+    // [utest->swdd~cli-blocks-until-ankaios-server-responds-delete-workload~2]
+    #[tokio::test]
+    async fn utest_delete_workloads_unknown_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let complete_state_update = CompleteState::default();
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_impact_analysis()
+            .return_once(|_| Ok(ImpactAnalysis::default()));
+        mock_server_connection
+            .expect_update_state()
+            .with(
+                eq(complete_state_update),
+                eq(vec!["desiredState.workloads.unknown_workload".to_string()]),
+            )
+            .return_once(|_, _| {
+                Ok(UpdateStateSuccess {
+                    added_workloads: vec![],
+                    deleted_workloads: vec![],
+                    rejected_workloads: vec![],
+                })
+            });
+
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let delete_result = cmd
+            .delete_workloads(vec!["unknown_workload".to_string()], false)
+            .await;
+        assert!(delete_result.is_ok());
+    }
+
+    // [utest->swdd~cli-switches-system-mode~1]
+    #[tokio::test]
+    async fn utest_set_mode_updates_active_mode() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut complete_state_update = CompleteState::default();
+        complete_state_update.desired_state.active_mode = "driving".to_string();
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_update_state()
+            .with(
+                eq(complete_state_update),
+                eq(vec!["desiredState.activeMode".to_string()]),
+            )
+            .return_once(|_, _| {
+                Ok(UpdateStateSuccess {
+                    added_workloads: vec![],
+                    deleted_workloads: vec![],
+                    rejected_workloads: vec![],
+                })
+            });
+
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        assert!(cmd.set_mode("driving".to_string()).await.is_ok());
+    }
+
+    fn generate_test_state_with_group() -> CompleteState {
+        let mut complete_state = test_utils::generate_test_complete_state(vec![
+            generate_test_workload_spec_with_param(
+                "agent_A".to_string(),
+                "name1".to_string(),
+                "podman".to_string(),
+            ),
+            generate_test_workload_spec_with_param(
+                "agent_B".to_string(),
+                "name2".to_string(),
+                "podman".to_string(),
+            ),
+        ]);
+        complete_state.desired_state.workload_groups = HashMap::from([(
+            "group".to_string(),
+            WorkloadGroup {
+                workloads: vec!["name1".to_string()],
+                ..Default::default()
+            },
+        )]);
+        complete_state
+    }
+
+    fn group_state_mask() -> Vec<String> {
+        vec![
+            "desiredState.workloads".to_string(),
+            "desiredState.workloadGroups.group".to_string(),
+        ]
+    }
+
+    // [utest->swdd~cli-operates-on-workload-groups~1]
+    #[tokio::test]
+    async fn utest_set_group_stopped_updates_group() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut complete_state_update = CompleteState::default();
+        complete_state_update.desired_state.workload_groups = HashMap::from([(
+            "group".to_string(),
+            WorkloadGroup {
+                stopped: true,
+                ..Default::default()
+            },
+        )]);
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(group_state_mask()))
+            .return_once(|_| Ok(Box::new(generate_test_state_with_group())));
+        mock_server_connection
+            .expect_update_state()
+            .with(
+                eq(complete_state_update),
+                eq(vec!["desiredState.workloadGroups.group.stopped".to_string()]),
+            )
+            .return_once(|_, _| {
+                Ok(UpdateStateSuccess {
+                    added_workloads: vec![],
+                    deleted_workloads: vec![],
+                    rejected_workloads: vec![],
+                })
+            });
+
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        assert!(cmd
+            .set_group_stopped("group".to_string(), true)
+            .await
+            .is_ok());
+    }
+
+    // [utest->swdd~cli-operates-on-workload-groups~1]
+    #[tokio::test]
+    async fn utest_set_group_stopped_fails_for_unknown_group() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .return_once(|_| Ok(Box::new(test_utils::generate_test_complete_state(vec![]))));
+        mock_server_connection.expect_update_state().never();
+
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        assert_eq!(
+            cmd.set_group_stopped("group".to_string(), false).await,
+            Err(CliError::ExecutionError(
+                "Workload group 'group' does not exist.".to_string()
+            ))
+        );
+    }
+
+    // [utest->swdd~cli-operates-on-workload-groups~1]
+    #[tokio::test]
+    async fn utest_delete_group_deletes_members_and_group() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(group_state_mask()))
+            .return_once(|_| Ok(Box::new(generate_test_state_with_group())));
+        mock_server_connection
+            .expect_get_impact_analysis()
+            .with(eq(vec!["name1".to_string()]))
+            .return_once(|_| Ok(ImpactAnalysis::default()));
+        mock_server_connection
+            .expect_update_state()
+            .with(
+                eq(CompleteState::default()),
+                eq(vec![
+                    "desiredState.workloads.name1".to_string(),
+                    "desiredState.workloadGroups.group".to_string(),
+                ]),
+            )
+            .return_once(|_, _| {
+                Ok(UpdateStateSuccess {
+                    added_workloads: vec![],
+                    deleted_workloads: vec![],
+                    rejected_workloads: vec![],
+                })
+            });
+
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        assert!(cmd.delete_group("group".to_string(), false).await.is_ok());
+    }
+
+    fn generate_test_impact_analysis() -> ImpactAnalysis {
+        ImpactAnalysis {
+            impacted_workloads: vec![ImpactedWorkload {
+                workload_name: "name2".to_string(),
+                agent: "agent_B".to_string(),
+                depends_on: vec!["name1".to_string()],
+            }],
+        }
+    }
+
+    // [utest->swdd~cli-confirms-removal-of-workloads-with-dependents~1]
+    #[tokio::test]
+    async fn utest_delete_workloads_aborted_if_dependents_not_confirmed() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        FAKE_READ_CONFIRMATION_MOCK_RESULT_LIST
+            .lock()
+            .unwrap()
+            .push_back(Ok("n\n".to_string()));
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_impact_analysis()
+            .with(eq(vec!["name1".to_string()]))
+            .return_once(|_| Ok(generate_test_impact_analysis()));
+        mock_server_connection.expect_update_state().never();
+
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let delete_result = cmd.delete_workloads(vec!["name1".to_string()], false).await;
+        assert_eq!(
+            delete_result,
+            Err(CliError::ExecutionError(
+                "Aborted by the user. Use '--yes' to skip the confirmation.".to_string()
+            ))
+        );
+        assert!(FAKE_READ_CONFIRMATION_MOCK_RESULT_LIST
+            .lock()
+            .unwrap()
+            .is_empty());
+    }
+
+    // [utest->swdd~cli-confirms-removal-of-workloads-with-dependents~1]
+    #[tokio::test]
+    async fn utest_delete_workloads_with_dependents_without_confirmation_if_yes() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_impact_analysis()
+            .with(eq(vec!["name1".to_string()]))
+            .return_once(|_| Ok(generate_test_impact_analysis()));
+        mock_server_connection
+            .expect_update_state()
+            .with(
+                eq(CompleteState::default()),
+                eq(vec!["desiredState.workloads.name1".to_string()]),
+            )
+            .return_once(|_, _| {
+                Ok(UpdateStateSuccess {
+                    added_workloads: vec![],
+                    deleted_workloads: vec![],
+                    rejected_workloads: vec![],
+                })
+            });
+
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let delete_result = cmd.delete_workloads(vec!["name1".to_string()], true).await;
+        assert!(delete_result.is_ok());
+    }
+
+    // [utest->swdd~cli-requires-yes-flag-without-interactive-stdin~1]
+    #[tokio::test]
+    async fn utest_delete_workloads_with_dependents_aborted_without_yes_if_stdin_not_interactive() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_impact_analysis()
+            .with(eq(vec!["name1".to_string()]))
+            .return_once(|_| Ok(generate_test_impact_analysis()));
+        mock_server_connection.expect_update_state().never();
+
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
-        let delete_result = cmd
-            .delete_workloads(vec!["name1".to_string(), "name2".to_string()])
-            .await;
-        assert!(delete_result.is_ok());
+        FAKE_STDIN_IS_TERMINAL.store(false, std::sync::atomic::Ordering::SeqCst);
+        let delete_result = cmd.delete_workloads(vec!["name1".to_string()], false).await;
+        FAKE_STDIN_IS_TERMINAL.store(true, std::sync::atomic::Ordering::SeqCst);
+
+        assert_eq!(
+            delete_result,
+            Err(CliError::ExecutionError(
+                "Cannot ask for a confirmation as stdin is not interactive. Use '--yes' to skip the confirmation.".to_string()
+            ))
+        );
     }
 
-    // [utest->swdd~cli-provides-delete-workload~1]
-    // [utest->swdd~cli-blocks-until-ankaios-server-responds-delete-workload~2]
+    // [utest->swdd~cli-requires-yes-flag-without-interactive-stdin~1]
     #[tokio::test]
-    async fn utest_delete_workloads_unknown_workload() {
+    async fn utest_confirm_removal_of_workloads_requires_yes_if_stdin_provides_manifest() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
             .get_lock_async()
             .await;
 
-        let complete_state_update = CompleteState::default();
-
         let mut mock_server_connection = MockServerConnection::default();
         mock_server_connection
-            .expect_update_state()
-            .with(
-                eq(complete_state_update),
-                eq(vec!["desiredState.workloads.unknown_workload".to_string()]),
-            )
-            .return_once(|_, _| {
-                Ok(UpdateStateSuccess {
-                    added_workloads: vec![],
-                    deleted_workloads: vec![],
-                })
-            });
+            .expect_get_impact_analysis()
+            .with(eq(vec!["name1".to_string()]))
+            .times(2)
+            .returning(|_| Ok(generate_test_impact_analysis()));
 
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
-        let delete_result = cmd
-            .delete_workloads(vec!["unknown_workload".to_string()])
-            .await;
-        assert!(delete_result.is_ok());
+        assert!(cmd
+            .confirm_removal_of_workloads(vec!["name1".to_string()], false, true)
+            .await
+            .is_err());
+        assert!(cmd
+            .confirm_removal_of_workloads(vec!["name1".to_string()], true, true)
+            .await
+            .is_ok());
+        assert!(FAKE_READ_CONFIRMATION_MOCK_RESULT_LIST
+            .lock()
+            .unwrap()
+            .is_empty());
+    }
+
+    // [utest->swdd~cli-apply-accepts-ankaios-manifest-content-from-stdin~1]
+    #[test]
+    fn utest_apply_args_reads_manifest_from_stdin() {
+        let mut args = ApplyArgs {
+            manifest_files: vec!["-".to_owned()],
+            agent_name: None,
+            profile: None,
+            delete_mode: true,
+            public_key: None,
+            yes: false,
+        };
+        assert!(args.reads_manifest_from_stdin());
+
+        args.manifest_files = vec!["manifest.yaml".to_owned()];
+        assert!(!args.reads_manifest_from_stdin());
     }
 
     // [utest -> swdd~cli-returns-desired-state-from-server~1]
@@ -1205,6 +2863,7 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
@@ -1246,6 +2905,7 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: 0,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
@@ -1271,6 +2931,7 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
@@ -1319,6 +2980,7 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
@@ -1371,6 +3033,7 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
@@ -1401,6 +3064,7 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
@@ -1461,6 +3125,7 @@ mod tests {
                         TEST_WORKLOAD_NAME.to_string()
                     )],
                     deleted_workloads: vec![],
+                    rejected_workloads: vec![],
                 })
             });
         mock_server_connection
@@ -1479,6 +3144,8 @@ mod tests {
                             ),
                             additional_info: "".to_string(),
                         },
+                        agent_timestamp: None,
+                        server_timestamp: None,
                     }],
                 })]
             });
@@ -1486,6 +3153,7 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
@@ -1501,6 +3169,165 @@ mod tests {
         assert!(run_workload_result.is_ok());
     }
 
+    fn run_workload_with_reported_state(
+        execution_state: ExecutionState,
+        wait_timeout: Option<Duration>,
+    ) -> CliCommands {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_update_state()
+            .return_once(|_, _| {
+                Ok(UpdateStateSuccess {
+                    added_workloads: vec!["name4.abc.agent_B".to_string()],
+                    deleted_workloads: vec![],
+                    rejected_workloads: vec![],
+                })
+            });
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![]))
+            .return_once(|_| Ok(Box::new(CompleteState::default())));
+        mock_server_connection
+            .expect_take_missed_from_server_messages()
+            .return_once(|| {
+                vec![FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                    workload_states: vec![WorkloadState {
+                        instance_name: "name4.abc.agent_B".try_into().unwrap(),
+                        execution_state,
+                        agent_timestamp: None,
+                        server_timestamp: None,
+                    }],
+                })]
+            });
+        mock_server_connection
+            .expect_read_next_update_workload_state()
+            .returning(|| {
+                Ok(UpdateWorkloadState {
+                    workload_states: vec![],
+                })
+            });
+
+        CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout,
+            server_connection: mock_server_connection,
+        }
+    }
+
+    // [utest->swdd~cli-exits-with-wait-outcome~1]
+    #[tokio::test]
+    async fn utest_run_workload_failed_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut cmd = run_workload_with_reported_state(ExecutionState::failed("error"), None);
+
+        let run_workload_result = cmd
+            .run_workload(
+                "name4".into(),
+                "runtime2".into(),
+                "some config".into(),
+                "agent_B".into(),
+                vec![],
+            )
+            .await;
+
+        let error = run_workload_result.unwrap_err();
+        assert_eq!(error, CliError::WorkloadsFailed(vec!["name4".to_string()]));
+        assert_eq!(error.exit_code(), EXIT_CODE_WORKLOADS_FAILED);
+    }
+
+    // [utest->swdd~cli-reports-rejected-workloads~1]
+    #[tokio::test]
+    async fn utest_run_workload_rejected_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let rejected_workloads = vec![RejectedWorkload {
+            workload_name: "name4".to_string(),
+            reason: "The runtime 'runtime2' is not supported by agent 'agent_B'.".to_string(),
+        }];
+        let mut mock_server_connection = MockServerConnection::default();
+        let update_state_success = UpdateStateSuccess {
+            rejected_workloads: rejected_workloads.clone(),
+            ..Default::default()
+        };
+        mock_server_connection
+            .expect_update_state()
+            .return_once(|_, _| Ok(update_state_success));
+
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: true,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let run_workload_result = cmd
+            .run_workload(
+                "name4".into(),
+                "runtime2".into(),
+                "some config".into(),
+                "agent_B".into(),
+                vec![],
+            )
+            .await;
+
+        let error = run_workload_result.unwrap_err();
+        assert_eq!(error, CliError::WorkloadsRejected(rejected_workloads));
+        assert_eq!(error.exit_code(), EXIT_CODE_WORKLOADS_REJECTED);
+        assert_eq!(
+            error.to_string(),
+            "The update has been applied partially, workload(s) not started:\n  \
+             'name4': The runtime 'runtime2' is not supported by agent 'agent_B'."
+        );
+    }
+
+    // [utest->swdd~cli-exits-with-wait-outcome~1]
+    // [utest->swdd~cli-stops-waiting-after-timeout~1]
+    #[tokio::test]
+    async fn utest_run_workload_wait_timeout_waiting_on_dependencies() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut cmd = run_workload_with_reported_state(
+            ExecutionState::waiting_to_start(),
+            Some(Duration::from_millis(10)),
+        );
+
+        let run_workload_result = cmd
+            .run_workload(
+                "name4".into(),
+                "runtime2".into(),
+                "some config".into(),
+                "agent_B".into(),
+                vec![],
+            )
+            .await;
+
+        let error = run_workload_result.unwrap_err();
+        assert_eq!(
+            error,
+            CliError::WaitTimeout {
+                waiting_on_dependencies: vec!["name4".to_string()],
+                pending: vec![],
+            }
+        );
+        assert_eq!(error.exit_code(), EXIT_CODE_WAITING_ON_DEPENDENCIES);
+        assert_eq!(
+            CliError::WaitTimeout {
+                waiting_on_dependencies: vec!["name4".to_string()],
+                pending: vec!["name5".to_string()],
+            }
+            .exit_code(),
+            EXIT_CODE_WAIT_TIMEOUT
+        );
+    }
+
     #[test]
     fn utest_generate_compact_state_output_empty_filter_masks() {
         let input_state = generate_test_complete_state(vec![
@@ -1528,6 +3355,38 @@ mod tests {
         assert_eq!(cli_output, serde_yaml::to_string(&input_state).unwrap());
     }
 
+    // [utest->swdd~cli-supports-jsonpath-output~1]
+    #[test]
+    fn utest_generate_compact_state_output_json_path() {
+        let input_state = generate_test_complete_state(vec![
+            generate_test_workload_spec_with_param(
+                "agent_A".to_string(),
+                "name1".to_string(),
+                "podman".to_string(),
+            ),
+            generate_test_workload_spec_with_param(
+                "agent_B".to_string(),
+                "name2".to_string(),
+                "podman".to_string(),
+            ),
+            generate_test_workload_spec_with_param(
+                "agent_A".to_string(),
+                "name3".to_string(),
+                "podman".to_string(),
+            ),
+        ]);
+        let template = crate::json_path::JsonPathTemplate::parse(
+            r#"{.desiredState.workloads[?(@.agent=="agent_A")]~}"#,
+        )
+        .unwrap();
+
+        let cli_output =
+            generate_compact_state_output(&input_state, vec![], OutputFormat::JsonPath(template))
+                .unwrap();
+
+        assert_eq!(cli_output, "name1 name3");
+    }
+
     #[test]
     fn utest_generate_compact_state_output_single_filter_mask() {
         let input_state = generate_test_complete_state(vec![
@@ -1820,7 +3679,10 @@ mod tests {
         let args = ApplyArgs {
             manifest_files: vec!["manifest1.yml".to_owned(), "manifest2.yml".to_owned()],
             agent_name: None,
+            profile: None,
             delete_mode: false,
+            public_key: None,
+            yes: false,
         };
         let expected = vec!["manifest1.yml".to_owned(), "manifest2.yml".to_owned()];
         let actual = args.get_input_sources().unwrap();
@@ -1849,7 +3711,10 @@ mod tests {
         let args = ApplyArgs {
             manifest_files: vec!["manifest1.yml".to_owned()],
             agent_name: None,
+            profile: None,
             delete_mode: false,
+            public_key: None,
+            yes: false,
         };
 
         assert!(args.get_input_sources().is_err(), "Expected an error");
@@ -1861,7 +3726,10 @@ mod tests {
         let args = ApplyArgs {
             manifest_files: vec!["-".to_owned()],
             agent_name: None,
+            profile: None,
             delete_mode: false,
+            public_key: None,
+            yes: false,
         };
         let expected = vec!["stdin".to_owned()];
         let actual = args.get_input_sources().unwrap();
@@ -1893,6 +3761,36 @@ mod tests {
         .is_ok());
     }
 
+    // [utest->swdd~common-migrates-manifests-to-current-api-version~1]
+    #[test]
+    fn utest_parse_manifest_migrates_unversioned_manifest() {
+        let manifest_content = io::Cursor::new(
+            b"workloads:
+        simple:
+          runtime: podman
+          agent: agent_A
+          restart: true
+          runtimeConfig: |
+            image: docker.io/nginx:latest",
+        );
+
+        let (obj, _) = parse_manifest(&mut (
+            "unversioned_manifest_content".to_string(),
+            Box::new(manifest_content),
+        ))
+        .unwrap();
+
+        assert_eq!(
+            obj.get(&"apiVersion".into()),
+            Some(&Value::String("v0.1".to_string()))
+        );
+        assert_eq!(
+            obj.get(&"workloads.simple.restartPolicy".into()),
+            Some(&Value::String("ALWAYS".to_string()))
+        );
+        assert!(obj.get(&"workloads.simple.restart".into()).is_none());
+    }
+
     #[test]
     fn utest_parse_manifest_invalid_manifest_content() {
         let manifest_content = io::Cursor::new(b"invalid manifest content");
@@ -2114,6 +4012,48 @@ mod tests {
         );
     }
 
+    // [utest->swdd~cli-apply-selects-profile~1]
+    #[test]
+    fn utest_handle_profile_selection_profile_provided_through_profile_flag() {
+        let mut state = test_utils::generate_test_state_from_workloads(vec![
+            generate_test_workload_spec_with_param(
+                "agent_A".to_string(),
+                "wl1".to_string(),
+                "runtime_X".to_string(),
+            ),
+        ]);
+        let mut obj: Object = state.clone().try_into().unwrap();
+
+        assert!(handle_profile_selection(
+            &vec!["workloads.wl1".into()],
+            &Some("dev".to_string()),
+            &mut obj,
+        )
+        .is_ok());
+
+        state.workloads.get_mut("wl1").unwrap().profile = "dev".to_string();
+        let state_from_obj: State = obj.try_into().unwrap();
+        assert_eq!(state_from_obj, state);
+    }
+
+    // [utest->swdd~cli-apply-selects-profile~1]
+    #[test]
+    fn utest_handle_profile_selection_no_profile_provided() {
+        let state = test_utils::generate_test_state_from_workloads(vec![
+            generate_test_workload_spec_with_param(
+                "agent_A".to_string(),
+                "wl1".to_string(),
+                "runtime_X".to_string(),
+            ),
+        ]);
+        let mut obj: Object = state.clone().try_into().unwrap();
+
+        assert!(handle_profile_selection(&vec!["workloads.wl1".into()], &None, &mut obj).is_ok());
+
+        let state_from_obj: State = obj.try_into().unwrap();
+        assert_eq!(state_from_obj, state);
+    }
+
     // [utest->swdd~cli-apply-generates-state-object-from-ankaios-manifests~1]
     // [utest->swdd~cli-apply-generates-filter-masks-from-ankaios-manifests~1]
     #[test]
@@ -2155,8 +4095,11 @@ mod tests {
                 &mut manifests[..],
                 &ApplyArgs {
                     agent_name: None,
+                    profile: None,
                     manifest_files: vec![manifest_file_name.to_string()],
                     delete_mode: false,
+                    public_key: None,
+                    yes: false,
                 },
             )
         );
@@ -2192,8 +4135,11 @@ mod tests {
                 &mut manifests[..],
                 &ApplyArgs {
                     agent_name: None,
+                    profile: None,
                     manifest_files: vec![manifest_file_name.to_string()],
                     delete_mode: true,
+                    public_key: None,
+                    yes: false,
                 },
             )
         );
@@ -2212,8 +4158,11 @@ mod tests {
                 &mut manifests[..],
                 &ApplyArgs {
                     agent_name: None,
+                    profile: None,
                     manifest_files: vec![manifest_file_name.to_string()],
                     delete_mode: true,
+                    public_key: None,
+                    yes: false,
                 },
             )
         );
@@ -2249,6 +4198,9 @@ mod tests {
         };
 
         let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_impact_analysis()
+            .return_once(|_| Ok(ImpactAnalysis::default()));
         mock_server_connection
             .expect_update_state()
             .with(
@@ -2259,6 +4211,7 @@ mod tests {
                 Ok(UpdateStateSuccess {
                     added_workloads: vec![],
                     deleted_workloads: vec!["name4.abc.agent_B".to_string()],
+                    rejected_workloads: vec![],
                 })
             });
         let updated_state_clone = updated_state.clone();
@@ -2279,6 +4232,8 @@ mod tests {
                             state: objects::ExecutionStateEnum::Removed,
                             ..Default::default()
                         },
+                        agent_timestamp: None,
+                        server_timestamp: None,
                     }],
                 })
             });
@@ -2286,13 +4241,17 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
         let apply_result = cmd
             .apply_manifests(ApplyArgs {
                 agent_name: None,
+                profile: None,
                 delete_mode: true,
+                public_key: None,
+                yes: false,
                 manifest_files: vec!["manifest_yaml".to_string()],
             })
             .await;
@@ -2339,6 +4298,7 @@ mod tests {
                 Ok(UpdateStateSuccess {
                     added_workloads: vec!["simple_manifest1.abc.agent_B".to_string()],
                     deleted_workloads: vec![],
+                    rejected_workloads: vec![],
                 })
             });
         mock_server_connection
@@ -2356,6 +4316,7 @@ mod tests {
                 vec![
                     FromServer::Response(Response {
                         request_id: OTHER_REQUEST_ID.into(),
+                        trace_id: "trace_id".into(),
                         response_content: common::commands::ResponseContent::Error(
                             Default::default(),
                         ),
@@ -2367,6 +4328,8 @@ mod tests {
                                 state: objects::ExecutionStateEnum::Running(RunningSubstate::Ok),
                                 ..Default::default()
                             },
+                            agent_timestamp: None,
+                            server_timestamp: None,
                         }],
                     }),
                 ]
@@ -2381,6 +4344,8 @@ mod tests {
                             state: objects::ExecutionStateEnum::Running(RunningSubstate::Ok),
                             ..Default::default()
                         },
+                        agent_timestamp: None,
+                        server_timestamp: None,
                     }],
                 })
             });
@@ -2388,16 +4353,90 @@ mod tests {
         let mut cmd = CliCommands {
             _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
+            wait_timeout: None,
             server_connection: mock_server_connection,
         };
 
         let apply_result = cmd
             .apply_manifests(ApplyArgs {
                 agent_name: None,
+                profile: None,
                 delete_mode: false,
+                public_key: None,
+                yes: false,
                 manifest_files: vec!["manifest_yaml".to_string()],
             })
             .await;
         assert!(apply_result.is_ok());
     }
+
+    // [utest->swdd~cli-diffs-manifests~1]
+    #[tokio::test]
+    async fn utest_diff_manifests_shows_changed_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let manifest_content = io::Cursor::new(
+            b"apiVersion: \"v0.1\"\nworkloads:
+        simple_manifest1:
+          runtime: podman
+          agent: agent_A
+          runtimeConfig: \"image: b\"
+            ",
+        );
+
+        FAKE_OPEN_MANIFEST_MOCK_RESULT_LIST
+            .lock()
+            .unwrap()
+            .push_back(Ok(("manifest.yml".to_string(), Box::new(manifest_content))));
+
+        let current_state: State = serde_yaml::from_str(
+            "apiVersion: \"v0.1\"\nworkloads:
+        simple_manifest1:
+          runtime: podman
+          agent: agent_A
+          runtimeConfig: \"image: a\"
+        unrelated:
+          runtime: podman
+          agent: agent_B
+          runtimeConfig: \"image: c\"
+            ",
+        )
+        .unwrap();
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec!["desiredState".to_string()]))
+            .return_once(|_| {
+                Ok(Box::new(CompleteState {
+                    desired_state: current_state,
+                    ..Default::default()
+                }))
+            });
+        mock_server_connection.expect_update_state().never();
+
+        let mut cmd = CliCommands {
+            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
+            no_wait: false,
+            wait_timeout: None,
+            server_connection: mock_server_connection,
+        };
+
+        let diff_result = cmd
+            .diff_manifests(ApplyArgs {
+                agent_name: None,
+                profile: None,
+                delete_mode: false,
+                public_key: None,
+                yes: false,
+                manifest_files: vec!["manifest_yaml".to_string()],
+            })
+            .await;
+        assert_eq!(
+            diff_result.unwrap(),
+            "~ workload 'simple_manifest1'\n    runtimeConfig: 'image: a' -> 'image: b'\n"
+        );
+    }
 }
@@ -14,9 +14,14 @@
 
 use crate::cli_commands::State;
 use crate::{cli::ApplyArgs, output_debug};
+use ankaios_model::input_limits::{self, InputLimits};
+use ankaios_model::manifest_migration;
 use common::objects::CompleteState;
 use common::state_manipulation::{Object, Path};
-use std::{collections::HashSet, io};
+use std::{
+    collections::HashSet,
+    io::{self, Read},
+};
 
 pub type InputSourcePair = (String, Box<dyn io::Read + Send + Sync + 'static>);
 pub type InputSources = Result<Vec<InputSourcePair>, String>;
@@ -24,8 +29,22 @@ pub type InputSources = Result<Vec<InputSourcePair>, String>;
 #[cfg(not(test))]
 pub fn open_manifest(
     file_path: &str,
+    public_key: Option<&str>,
 ) -> io::Result<(String, Box<dyn io::Read + Send + Sync + 'static>)> {
+    use common::oci_artifact;
     use std::fs::File;
+    // [impl->swdd~cli-apply-accepts-oci-references~1]
+    if oci_artifact::is_oci_reference(file_path) {
+        return oci_artifact::pull_manifest(file_path, public_key)
+            .map(|content| {
+                (
+                    file_path.to_owned(),
+                    Box::new(io::Cursor::new(content.into_bytes()))
+                        as Box<dyn io::Read + Send + Sync + 'static>,
+                )
+            })
+            .map_err(io::Error::other);
+    }
     match File::open(file_path) {
         Ok(open_file) => Ok((file_path.to_owned(), Box::new(open_file))),
         Err(err) => Err(err),
@@ -36,8 +55,19 @@ use super::tests::open_manifest_mock as open_manifest;
 
 // [impl->swdd~cli-apply-supports-ankaios-manifest~1]
 pub fn parse_manifest(manifest: &mut InputSourcePair) -> Result<(Object, Vec<Path>), String> {
-    let state_obj_parsing_check: serde_yaml::Value = serde_yaml::from_reader(&mut manifest.1)
+    // [impl->swdd~common-limits-manifest-size-and-depth~2]
+    // one byte more than allowed is read to detect a too large manifest
+    let mut manifest_data = String::new();
+    manifest
+        .1
+        .by_ref()
+        .take(InputLimits::configured().max_manifest_size as u64 + 1)
+        .read_to_string(&mut manifest_data)
+        .map_err(|err| format!("Invalid manifest data provided: {}", err))?;
+    let state_obj_parsing_check: serde_yaml::Value = input_limits::parse_manifest(&manifest_data)
         .map_err(|err| format!("Invalid manifest data provided: {}", err))?;
+    // [impl->swdd~common-migrates-manifests-to-current-api-version~1]
+    let state_obj_parsing_check = manifest_migration::migrate_manifest(state_obj_parsing_check);
     match Object::try_from(&state_obj_parsing_check) {
         Err(err) => Err(format!(
             "Error while parsing the manifest data.\nError: {err}"
@@ -97,6 +127,30 @@ pub fn handle_agent_overwrite(
         .map_err(|err| format!("Invalid manifest data provided: {}", err))
 }
 
+// [impl->swdd~cli-apply-selects-profile~1]
+pub fn handle_profile_selection(
+    filter_masks: &Vec<common::state_manipulation::Path>,
+    desired_profile: &Option<String>,
+    state_obj: &mut Object,
+) -> Result<(), String> {
+    if let Some(desired_profile) = desired_profile {
+        for field in filter_masks {
+            let path = &format!("{}.profile", String::from(field));
+            if state_obj
+                .set(
+                    &path.into(),
+                    serde_yaml::Value::String(desired_profile.to_owned()),
+                )
+                .is_err()
+            {
+                return Err("Could not find workload to update.".to_owned());
+            }
+        }
+    }
+
+    Ok(())
+}
+
 pub fn update_request_obj(
     req_obj: &mut Object,
     cur_obj: &Object,
@@ -159,6 +213,7 @@ pub fn generate_state_obj_and_filter_masks_from_manifests(
             ..Default::default()
         }
     } else {
+        handle_profile_selection(&req_paths, &apply_args.profile, &mut req_obj)?;
         let state_from_req_obj =
             handle_agent_overwrite(&req_paths, &apply_args.agent_name, req_obj)?;
         CompleteState {
@@ -172,6 +227,10 @@ pub fn generate_state_obj_and_filter_masks_from_manifests(
 }
 
 impl ApplyArgs {
+    pub fn reads_manifest_from_stdin(&self) -> bool {
+        self.manifest_files.first().is_some_and(|arg| arg == "-")
+    }
+
     pub fn get_input_sources(&self) -> InputSources {
         if let Some(first_arg) = self.manifest_files.first() {
             match first_arg.as_str() {
@@ -181,7 +240,7 @@ impl ApplyArgs {
                 _ => {
                     let mut res: InputSources = Ok(vec![]);
                     for file_path in self.manifest_files.iter() {
-                        match open_manifest(file_path) {
+                        match open_manifest(file_path, self.public_key.as_deref()) {
                             Ok(open_file) => res.as_mut().unwrap().push(open_file),
                             Err(err) => {
                                 res = Err(match err.kind() {
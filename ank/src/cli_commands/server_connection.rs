@@ -19,7 +19,10 @@ use common::communications_client::CommunicationsClient;
 use common::to_server_interface::ToServer;
 use common::{
     commands::{
-        CompleteStateRequest, Response, ResponseContent, UpdateStateSuccess, UpdateWorkloadState,
+        CompleteStateRequest, DependencyGraph, DrainAgentRequest, Events, EventsRequest,
+        ImpactAnalysis, ImpactAnalysisRequest, Response, ResponseContent, RolloutStatus,
+        SchedulerQueue, SchedulerQueueRequest, SupportInfo, UpdateStateRequest, UpdateStateSuccess,
+        UpdateWorkloadState,
     },
     from_server_interface::{FromServer, FromServerReceiver},
     objects::CompleteState,
@@ -33,11 +36,21 @@ use url::Url;
 const BUFFER_SIZE: usize = 20;
 const WAIT_TIME_MS: Duration = Duration::from_millis(3000);
 
+// [impl->swdd~cli-outputs-trace-id-of-responses~1]
+fn output_trace_id(request_id: &str, trace_id: &str) {
+    output_debug!(
+        "The server handled the request '{}' with trace id '{}'.",
+        request_id,
+        trace_id
+    );
+}
+
 pub struct ServerConnection {
     to_server: ToServerSender,
     from_server: FromServerReceiver,
     task: tokio::task::JoinHandle<()>,
     missed_from_server_messages: Vec<FromServer>,
+    force: bool,
 }
 
 #[cfg_attr(test, automock)]
@@ -46,7 +59,7 @@ impl ServerConnection {
     // [impl->swdd~cli-communication-over-middleware~1]
     // testing the function does not bring any benefit so disable the dead code warning when building for test
     #[cfg_attr(test, allow(dead_code))]
-    pub fn new(cli_name: &str, server_url: Url) -> Self {
+    pub fn new(cli_name: &str, server_url: Url, force: bool) -> Self {
         let mut grpc_communications_client =
             GRPCCommunicationsClient::new_cli_communication(cli_name.to_owned(), server_url);
 
@@ -67,6 +80,7 @@ impl ServerConnection {
             from_server: cli_receiver,
             task,
             missed_from_server_messages: Vec::new(),
+            force,
         }
     }
 
@@ -104,8 +118,12 @@ impl ServerConnection {
                 match self.from_server.recv().await {
                     Some(FromServer::Response(Response {
                         request_id: received_request_id,
+                        trace_id,
                         response_content: ResponseContent::CompleteState(res),
-                    })) if received_request_id == request_id => return Ok(res),
+                    })) if received_request_id == request_id => {
+                        output_trace_id(&request_id, &trace_id);
+                        return Ok(res);
+                    }
                     None => return Err("Channel preliminary closed."),
                     Some(message) => {
                         // [impl->swdd~cli-stores-unexpected-message~1]
@@ -125,6 +143,258 @@ impl ServerConnection {
         }
     }
 
+    pub async fn get_rollout_status(&mut self) -> Result<RolloutStatus, ServerConnectionError> {
+        output_debug!("get_rollout_status");
+
+        let request_id = uuid::Uuid::new_v4().to_string();
+
+        self.to_server
+            .request_rollout_status(request_id.to_owned())
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+
+        let poll_rollout_status_response = async {
+            loop {
+                match self.from_server.recv().await {
+                    Some(FromServer::Response(Response {
+                        request_id: received_request_id,
+                        trace_id,
+                        response_content: ResponseContent::RolloutStatus(res),
+                    })) if received_request_id == request_id => {
+                        output_trace_id(&request_id, &trace_id);
+                        return Ok(res);
+                    }
+                    None => return Err("Channel preliminary closed."),
+                    Some(message) => {
+                        // [impl->swdd~cli-stores-unexpected-message~1]
+                        self.missed_from_server_messages.push(message);
+                    }
+                }
+            }
+        };
+        match tokio::time::timeout(WAIT_TIME_MS, poll_rollout_status_response).await {
+            Ok(Ok(res)) => Ok(res),
+            Ok(Err(err)) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get rollout status.\nError: {err}"
+            ))),
+            Err(_) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get rollout status in time (timeout={WAIT_TIME_MS:?})."
+            ))),
+        }
+    }
+
+    pub async fn get_support_info(&mut self) -> Result<SupportInfo, ServerConnectionError> {
+        output_debug!("get_support_info");
+
+        let request_id = uuid::Uuid::new_v4().to_string();
+
+        self.to_server
+            .request_support_info(request_id.to_owned())
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+
+        let poll_support_info_response = async {
+            loop {
+                match self.from_server.recv().await {
+                    Some(FromServer::Response(Response {
+                        request_id: received_request_id,
+                        trace_id,
+                        response_content: ResponseContent::SupportInfo(res),
+                    })) if received_request_id == request_id => {
+                        output_trace_id(&request_id, &trace_id);
+                        return Ok(res);
+                    }
+                    None => return Err("Channel preliminary closed."),
+                    Some(message) => {
+                        // [impl->swdd~cli-stores-unexpected-message~1]
+                        self.missed_from_server_messages.push(message);
+                    }
+                }
+            }
+        };
+        match tokio::time::timeout(WAIT_TIME_MS, poll_support_info_response).await {
+            Ok(Ok(res)) => Ok(res),
+            Ok(Err(err)) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get support info.\nError: {err}"
+            ))),
+            Err(_) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get support info in time (timeout={WAIT_TIME_MS:?})."
+            ))),
+        }
+    }
+
+    pub async fn get_events(
+        &mut self,
+        events_request: EventsRequest,
+    ) -> Result<Events, ServerConnectionError> {
+        output_debug!("get_events: {:?}", events_request);
+
+        let request_id = uuid::Uuid::new_v4().to_string();
+
+        self.to_server
+            .request_events(request_id.to_owned(), events_request)
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+
+        let poll_events_response = async {
+            loop {
+                match self.from_server.recv().await {
+                    Some(FromServer::Response(Response {
+                        request_id: received_request_id,
+                        trace_id,
+                        response_content: ResponseContent::Events(res),
+                    })) if received_request_id == request_id => {
+                        output_trace_id(&request_id, &trace_id);
+                        return Ok(res);
+                    }
+                    None => return Err("Channel preliminary closed."),
+                    Some(message) => {
+                        // [impl->swdd~cli-stores-unexpected-message~1]
+                        self.missed_from_server_messages.push(message);
+                    }
+                }
+            }
+        };
+        match tokio::time::timeout(WAIT_TIME_MS, poll_events_response).await {
+            Ok(Ok(res)) => Ok(res),
+            Ok(Err(err)) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get events.\nError: {err}"
+            ))),
+            Err(_) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get events in time (timeout={WAIT_TIME_MS:?})."
+            ))),
+        }
+    }
+
+    pub async fn get_impact_analysis(
+        &mut self,
+        workload_names: Vec<String>,
+    ) -> Result<ImpactAnalysis, ServerConnectionError> {
+        output_debug!("get_impact_analysis: {:?}", workload_names);
+
+        let request_id = uuid::Uuid::new_v4().to_string();
+
+        self.to_server
+            .request_impact_analysis(
+                request_id.to_owned(),
+                ImpactAnalysisRequest { workload_names },
+            )
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+
+        let poll_impact_analysis_response = async {
+            loop {
+                match self.from_server.recv().await {
+                    Some(FromServer::Response(Response {
+                        request_id: received_request_id,
+                        trace_id,
+                        response_content: ResponseContent::ImpactAnalysis(res),
+                    })) if received_request_id == request_id => {
+                        output_trace_id(&request_id, &trace_id);
+                        return Ok(res);
+                    }
+                    None => return Err("Channel preliminary closed."),
+                    Some(message) => {
+                        // [impl->swdd~cli-stores-unexpected-message~1]
+                        self.missed_from_server_messages.push(message);
+                    }
+                }
+            }
+        };
+        match tokio::time::timeout(WAIT_TIME_MS, poll_impact_analysis_response).await {
+            Ok(Ok(res)) => Ok(res),
+            Ok(Err(err)) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get impact analysis.\nError: {err}"
+            ))),
+            Err(_) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get impact analysis in time (timeout={WAIT_TIME_MS:?})."
+            ))),
+        }
+    }
+
+    pub async fn get_dependency_graph(&mut self) -> Result<DependencyGraph, ServerConnectionError> {
+        output_debug!("get_dependency_graph");
+
+        let request_id = uuid::Uuid::new_v4().to_string();
+
+        self.to_server
+            .request_dependency_graph(request_id.to_owned())
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+
+        let poll_dependency_graph_response = async {
+            loop {
+                match self.from_server.recv().await {
+                    Some(FromServer::Response(Response {
+                        request_id: received_request_id,
+                        trace_id,
+                        response_content: ResponseContent::DependencyGraph(res),
+                    })) if received_request_id == request_id => {
+                        output_trace_id(&request_id, &trace_id);
+                        return Ok(res);
+                    }
+                    None => return Err("Channel preliminary closed."),
+                    Some(message) => {
+                        // [impl->swdd~cli-stores-unexpected-message~1]
+                        self.missed_from_server_messages.push(message);
+                    }
+                }
+            }
+        };
+        match tokio::time::timeout(WAIT_TIME_MS, poll_dependency_graph_response).await {
+            Ok(Ok(res)) => Ok(res),
+            Ok(Err(err)) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get dependency graph.\nError: {err}"
+            ))),
+            Err(_) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get dependency graph in time (timeout={WAIT_TIME_MS:?})."
+            ))),
+        }
+    }
+
+    pub async fn get_scheduler_queue(
+        &mut self,
+        scheduler_queue_request: SchedulerQueueRequest,
+    ) -> Result<SchedulerQueue, ServerConnectionError> {
+        output_debug!("get_scheduler_queue: {:?}", scheduler_queue_request);
+
+        let request_id = uuid::Uuid::new_v4().to_string();
+
+        self.to_server
+            .request_scheduler_queue(request_id.to_owned(), scheduler_queue_request)
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+
+        let poll_scheduler_queue_response = async {
+            loop {
+                match self.from_server.recv().await {
+                    Some(FromServer::Response(Response {
+                        request_id: received_request_id,
+                        trace_id,
+                        response_content: ResponseContent::SchedulerQueue(res),
+                    })) if received_request_id == request_id => {
+                        output_trace_id(&request_id, &trace_id);
+                        return Ok(res);
+                    }
+                    None => return Err("Channel preliminary closed."),
+                    Some(message) => {
+                        // [impl->swdd~cli-stores-unexpected-message~1]
+                        self.missed_from_server_messages.push(message);
+                    }
+                }
+            }
+        };
+        match tokio::time::timeout(WAIT_TIME_MS, poll_scheduler_queue_response).await {
+            Ok(Ok(res)) => Ok(res),
+            Ok(Err(err)) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get scheduler queue.\nError: {err}"
+            ))),
+            Err(_) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get scheduler queue in time (timeout={WAIT_TIME_MS:?})."
+            ))),
+        }
+    }
+
     pub async fn update_state(
         &mut self,
         new_state: CompleteState,
@@ -133,7 +403,17 @@ impl ServerConnection {
         let request_id = uuid::Uuid::new_v4().to_string();
         output_debug!("Sending the new state {:?}", new_state);
         self.to_server
-            .update_state(request_id.clone(), new_state, update_mask)
+            .request_update_state(
+                request_id.clone(),
+                UpdateStateRequest {
+                    state: new_state,
+                    update_mask,
+                    deadline_ms: None,
+                    rollback_on_deadline_exceeded: false,
+                    // [impl->swdd~cli-sends-force-with-update-state~1]
+                    force: self.force,
+                },
+            )
             .await
             .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
 
@@ -147,13 +427,19 @@ impl ServerConnection {
                 match server_message {
                     FromServer::Response(Response {
                         request_id: received_request_id,
+                        trace_id,
                         response_content: ResponseContent::UpdateStateSuccess(update_state_success),
-                    }) if received_request_id == request_id => return Ok(update_state_success),
+                    }) if received_request_id == request_id => {
+                        output_trace_id(&request_id, &trace_id);
+                        return Ok(update_state_success);
+                    }
                     // [impl->swdd~cli-requests-update-state-with-watch-error~1]
                     FromServer::Response(Response {
                         request_id: received_request_id,
+                        trace_id,
                         response_content: ResponseContent::Error(error),
                     }) if received_request_id == request_id => {
+                        output_trace_id(&request_id, &trace_id);
                         return Err(ServerConnectionError::ExecutionError(format!(
                             "SetState failed with: '{}'",
                             error.message
@@ -181,6 +467,57 @@ impl ServerConnection {
         }
     }
 
+    pub async fn drain_agent(
+        &mut self,
+        drain_agent_request: DrainAgentRequest,
+    ) -> Result<UpdateStateSuccess, ServerConnectionError> {
+        output_debug!("drain_agent: {:?}", drain_agent_request);
+
+        let request_id = uuid::Uuid::new_v4().to_string();
+
+        self.to_server
+            .request_drain_agent(request_id.to_owned(), drain_agent_request)
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+
+        let poll_drain_agent_response = async {
+            loop {
+                match self.from_server.recv().await {
+                    Some(FromServer::Response(Response {
+                        request_id: received_request_id,
+                        trace_id,
+                        response_content: ResponseContent::UpdateStateSuccess(res),
+                    })) if received_request_id == request_id => {
+                        output_trace_id(&request_id, &trace_id);
+                        return Ok(res);
+                    }
+                    Some(FromServer::Response(Response {
+                        request_id: received_request_id,
+                        trace_id,
+                        response_content: ResponseContent::Error(error),
+                    })) if received_request_id == request_id => {
+                        output_trace_id(&request_id, &trace_id);
+                        return Err(error.message);
+                    }
+                    None => return Err("Channel preliminary closed.".to_string()),
+                    Some(message) => {
+                        // [impl->swdd~cli-stores-unexpected-message~1]
+                        self.missed_from_server_messages.push(message);
+                    }
+                }
+            }
+        };
+        match tokio::time::timeout(WAIT_TIME_MS, poll_drain_agent_response).await {
+            Ok(Ok(res)) => Ok(res),
+            Ok(Err(err)) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to drain agent.\nError: {err}"
+            ))),
+            Err(_) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to drain agent in time (timeout={WAIT_TIME_MS:?})."
+            ))),
+        }
+    }
+
     pub async fn read_next_update_workload_state(
         &mut self,
     ) -> Result<UpdateWorkloadState, ServerConnectionError> {
@@ -224,8 +561,12 @@ mod tests {
 
     use common::{
         commands::{
-            CompleteStateRequest, Error, RequestContent, Response, ResponseContent,
-            UpdateStateRequest, UpdateStateSuccess, UpdateWorkloadState,
+            CompleteStateRequest, DependencyGraph, DependencyGraphNode, DependencyGraphRequest,
+            DrainAgentRequest, Error, Event, EventKind, Events, EventsRequest, ImpactAnalysis,
+            ImpactAnalysisRequest, ImpactedWorkload, PendingOperation, PendingWorkloadOperation,
+            RequestContent, Response, ResponseContent, RolloutState, RolloutStatus,
+            RolloutStatusRequest, SchedulerQueue, SchedulerQueueRequest, SupportInfo,
+            SupportInfoRequest, UpdateStateRequest, UpdateStateSuccess, UpdateWorkloadState,
         },
         from_server_interface::FromServer,
         objects::{
@@ -244,12 +585,14 @@ mod tests {
     const RUNTIME: &str = "runtime";
     const REQUEST: &str = "request";
     const OTHER_REQUEST: &str = "other_request";
+    const TRACE_ID: &str = "trace_id";
     const FIELD_MASK: &str = "field_mask";
     const ID: &str = "id";
 
     #[derive(Default)]
     struct CommunicationSimulator {
         actions: Vec<CommunicationSimulatorAction>,
+        force: bool,
     }
 
     struct CorrectCommuncationChecker {
@@ -283,6 +626,7 @@ mod tests {
                             from_server
                                 .send(FromServer::Response(Response {
                                     request_id: request_id.to_owned(),
+                                    trace_id: TRACE_ID.to_owned(),
                                     response_content: response,
                                 }))
                                 .await
@@ -316,6 +660,7 @@ mod tests {
                     from_server: cli_receiver,
                     task: tokio::spawn(async {}),
                     missed_from_server_messages: Vec::new(),
+                    force: self.force,
                 },
             )
         }
@@ -407,6 +752,210 @@ mod tests {
         checker.check_communication();
     }
 
+    #[tokio::test]
+    async fn utest_get_rollout_status() {
+        let rollout_status = RolloutStatus {
+            state: RolloutState::InProgress,
+            ..Default::default()
+        };
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::RolloutStatusRequest(RolloutStatusRequest {}),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ResponseContent::RolloutStatus(rollout_status.clone()),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection.get_rollout_status().await;
+        assert_eq!(result.unwrap(), rollout_status);
+        checker.check_communication();
+    }
+
+    #[tokio::test]
+    async fn utest_get_support_info() {
+        let support_info = SupportInfo {
+            server_version: "0.4.0".to_string(),
+            ..Default::default()
+        };
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::SupportInfoRequest(SupportInfoRequest {}),
+        );
+        sim.will_send_response(REQUEST, ResponseContent::SupportInfo(support_info.clone()));
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection.get_support_info().await;
+        assert_eq!(result.unwrap(), support_info);
+        checker.check_communication();
+    }
+
+    #[tokio::test]
+    async fn utest_get_events() {
+        let events_request = EventsRequest {
+            since: 1000,
+            limit: 5,
+        };
+        let events = Events {
+            events: vec![Event {
+                timestamp: 2000,
+                kind: EventKind::AgentConnected,
+                agent_name: Some("agent_A".to_string()),
+                ..Default::default()
+            }],
+        };
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::EventsRequest(events_request.clone()),
+        );
+        sim.will_send_response(REQUEST, ResponseContent::Events(events.clone()));
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection.get_events(events_request).await;
+        assert_eq!(result.unwrap(), events);
+        checker.check_communication();
+    }
+
+    #[tokio::test]
+    async fn utest_get_impact_analysis() {
+        let impact_analysis = ImpactAnalysis {
+            impacted_workloads: vec![ImpactedWorkload {
+                workload_name: WORKLOAD_NAME_2.to_string(),
+                agent: AGENT_A.to_string(),
+                depends_on: vec![WORKLOAD_NAME_1.to_string()],
+            }],
+        };
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::ImpactAnalysisRequest(ImpactAnalysisRequest {
+                workload_names: vec![WORKLOAD_NAME_1.to_string()],
+            }),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ResponseContent::ImpactAnalysis(impact_analysis.clone()),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection
+            .get_impact_analysis(vec![WORKLOAD_NAME_1.to_string()])
+            .await;
+        assert_eq!(result.unwrap(), impact_analysis);
+        checker.check_communication();
+    }
+
+    #[tokio::test]
+    async fn utest_get_dependency_graph() {
+        let dependency_graph = DependencyGraph {
+            nodes: vec![DependencyGraphNode {
+                workload_name: WORKLOAD_NAME_1.to_string(),
+                agent: AGENT_A.to_string(),
+                execution_state: Some(ExecutionState::running()),
+            }],
+            edges: vec![],
+        };
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::DependencyGraphRequest(DependencyGraphRequest {}),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ResponseContent::DependencyGraph(dependency_graph.clone()),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection.get_dependency_graph().await;
+        assert_eq!(result.unwrap(), dependency_graph);
+        checker.check_communication();
+    }
+
+    #[tokio::test]
+    async fn utest_get_scheduler_queue() {
+        let scheduler_queue_request = SchedulerQueueRequest {
+            agent_name: AGENT_A.to_string(),
+        };
+        let scheduler_queue = SchedulerQueue {
+            pending_operations: vec![PendingWorkloadOperation {
+                workload_name: WORKLOAD_NAME_1.to_string(),
+                agent: AGENT_A.to_string(),
+                operation: PendingOperation::Create,
+                unfulfilled_dependencies: vec![],
+            }],
+        };
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::SchedulerQueueRequest(scheduler_queue_request.clone()),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ResponseContent::SchedulerQueue(scheduler_queue.clone()),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection
+            .get_scheduler_queue(scheduler_queue_request)
+            .await;
+        assert_eq!(result.unwrap(), scheduler_queue);
+        checker.check_communication();
+    }
+
+    #[tokio::test]
+    async fn utest_drain_agent() {
+        let drain_agent_request = DrainAgentRequest {
+            agent_name: AGENT_A.to_string(),
+            target_agent: None,
+            remove: true,
+        };
+        let update_state_success = UpdateStateSuccess {
+            added_workloads: vec![WORKLOAD_NAME_1.into()],
+            deleted_workloads: vec![WORKLOAD_NAME_1.into()],
+            rejected_workloads: vec![],
+        };
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::DrainAgentRequest(drain_agent_request.clone()),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ResponseContent::UpdateStateSuccess(update_state_success.clone()),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection.drain_agent(drain_agent_request).await;
+        assert_eq!(result.unwrap(), update_state_success);
+        checker.check_communication();
+    }
+
+    #[tokio::test]
+    async fn utest_drain_agent_fails_error_response() {
+        let drain_agent_request = DrainAgentRequest {
+            agent_name: AGENT_A.to_string(),
+            ..Default::default()
+        };
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::DrainAgentRequest(drain_agent_request.clone()),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ResponseContent::Error(Error { message: "".into() }),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection.drain_agent(drain_agent_request).await;
+        assert!(result.is_err());
+        checker.check_communication();
+    }
+
     #[tokio::test]
     async fn utest_get_complete_state_fails_at_request() {
         let sim = CommunicationSimulator::default();
@@ -463,6 +1012,7 @@ mod tests {
     async fn utest_get_complete_state_other_response_in_between() {
         let other_response = FromServer::Response(Response {
             request_id: OTHER_REQUEST.into(),
+            trace_id: TRACE_ID.into(),
             response_content: ResponseContent::CompleteState(Box::new(complete_state(
                 WORKLOAD_NAME_2,
             ))),
@@ -532,6 +1082,7 @@ mod tests {
         let update_state_success = UpdateStateSuccess {
             added_workloads: vec![WORKLOAD_NAME_1.into()],
             deleted_workloads: vec![],
+            rejected_workloads: vec![],
         };
 
         let mut sim = CommunicationSimulator::default();
@@ -540,6 +1091,9 @@ mod tests {
             RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
                 state: complete_state(WORKLOAD_NAME_1),
                 update_mask: vec![FIELD_MASK.into()],
+                deadline_ms: None,
+                rollback_on_deadline_exceeded: false,
+                force: false,
             })),
         );
         sim.will_send_response(
@@ -557,6 +1111,43 @@ mod tests {
         checker.check_communication();
     }
 
+    // [utest->swdd~cli-sends-force-with-update-state~1]
+    #[tokio::test]
+    async fn utest_update_state_forced() {
+        let update_state_success = UpdateStateSuccess {
+            added_workloads: vec![],
+            deleted_workloads: vec![],
+            rejected_workloads: vec![],
+        };
+
+        let mut sim = CommunicationSimulator {
+            force: true,
+            ..Default::default()
+        };
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
+                state: complete_state(WORKLOAD_NAME_1),
+                update_mask: vec![FIELD_MASK.into()],
+                deadline_ms: None,
+                rollback_on_deadline_exceeded: false,
+                force: true,
+            })),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ResponseContent::UpdateStateSuccess(update_state_success.clone()),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection
+            .update_state(complete_state(WORKLOAD_NAME_1), vec![FIELD_MASK.into()])
+            .await;
+
+        assert_eq!(result.unwrap(), update_state_success);
+        checker.check_communication();
+    }
+
     #[tokio::test]
     async fn utest_update_state_fails_at_request() {
         let sim = CommunicationSimulator::default();
@@ -580,6 +1171,9 @@ mod tests {
             RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
                 state: complete_state(WORKLOAD_NAME_1),
                 update_mask: vec![FIELD_MASK.into()],
+                deadline_ms: None,
+                rollback_on_deadline_exceeded: false,
+                force: false,
             })),
         );
 
@@ -600,6 +1194,9 @@ mod tests {
             RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
                 state: complete_state(WORKLOAD_NAME_1),
                 update_mask: vec![FIELD_MASK.into()],
+                deadline_ms: None,
+                rollback_on_deadline_exceeded: false,
+                force: false,
             })),
         );
         sim.will_send_response(
@@ -625,6 +1222,9 @@ mod tests {
             RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
                 state: complete_state(WORKLOAD_NAME_1),
                 update_mask: vec![FIELD_MASK.into()],
+                deadline_ms: None,
+                rollback_on_deadline_exceeded: false,
+                force: false,
             })),
         );
 
@@ -646,9 +1246,11 @@ mod tests {
         let update_state_success = UpdateStateSuccess {
             added_workloads: vec![WORKLOAD_NAME_1.into()],
             deleted_workloads: vec![],
+            rejected_workloads: vec![],
         };
         let other_response = FromServer::Response(Response {
             request_id: OTHER_REQUEST.into(),
+            trace_id: TRACE_ID.into(),
             response_content: ResponseContent::CompleteState(Box::new(complete_state(
                 WORKLOAD_NAME_2,
             ))),
@@ -660,6 +1262,9 @@ mod tests {
             RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
                 state: complete_state(WORKLOAD_NAME_1),
                 update_mask: vec![FIELD_MASK.into()],
+                deadline_ms: None,
+                rollback_on_deadline_exceeded: false,
+                force: false,
             })),
         );
         sim.will_send_message(other_response.clone());
@@ -688,6 +1293,7 @@ mod tests {
         let update_state_success = UpdateStateSuccess {
             added_workloads: vec![WORKLOAD_NAME_1.into()],
             deleted_workloads: vec![],
+            rejected_workloads: vec![],
         };
         let other_message = FromServer::UpdateWorkloadState(UpdateWorkloadState {
             workload_states: vec![],
@@ -699,6 +1305,9 @@ mod tests {
             RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
                 state: complete_state(WORKLOAD_NAME_1),
                 update_mask: vec![FIELD_MASK.into()],
+                deadline_ms: None,
+                rollback_on_deadline_exceeded: false,
+                force: false,
             })),
         );
         sim.will_send_message(other_message.clone());
@@ -727,6 +1336,8 @@ mod tests {
             workload_states: vec![WorkloadState {
                 instance_name: instance_name(WORKLOAD_NAME_1),
                 execution_state: ExecutionState::running(),
+                agent_timestamp: None,
+                server_timestamp: None,
             }],
         };
 
@@ -748,12 +1359,15 @@ mod tests {
     async fn utest_read_next_update_workload_state_other_message_in_between() {
         let other_message = FromServer::Response(Response {
             request_id: REQUEST.into(),
+            trace_id: TRACE_ID.into(),
             response_content: ResponseContent::Error(Error { message: "".into() }),
         });
         let update_workload_state = UpdateWorkloadState {
             workload_states: vec![WorkloadState {
                 instance_name: instance_name(WORKLOAD_NAME_1),
                 execution_state: ExecutionState::running(),
+                agent_timestamp: None,
+                server_timestamp: None,
             }],
         };
 
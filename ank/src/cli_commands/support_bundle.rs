@@ -0,0 +1,141 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use common::objects::AgentInfo;
+use serde::Serialize;
+
+pub const COMPLETE_STATE_FILE_NAME: &str = "complete_state.yaml";
+pub const ROLLOUT_STATUS_FILE_NAME: &str = "rollout_status.yaml";
+pub const VERSIONS_FILE_NAME: &str = "versions.yaml";
+pub const EVENTS_FILE_NAME: &str = "events.yaml";
+
+const BLOCK_SIZE: usize = 512;
+const MAX_FILE_NAME_LENGTH: usize = 99;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionInfo {
+    pub cli_version: String,
+    pub server_version: String,
+    pub agents: Vec<AgentInfo>,
+}
+
+pub fn default_bundle_file_name(timestamp_secs: u64) -> String {
+    format!("ankaios-support-bundle-{timestamp_secs}.tar")
+}
+
+// The bundle is written as an uncompressed ustar archive so that it can be unpacked
+// with standard tools on any host.
+// [impl->swdd~cli-creates-support-bundle~1]
+pub fn create_tar_archive(entries: &[(&str, Vec<u8>)], mtime: u64) -> Result<Vec<u8>, String> {
+    let mut archive = Vec::new();
+    for (file_name, content) in entries {
+        archive.extend_from_slice(&create_tar_header(file_name, content.len(), mtime)?);
+        archive.extend_from_slice(content);
+        archive.resize(archive.len().next_multiple_of(BLOCK_SIZE), 0);
+    }
+    // the end of the archive is marked by two empty blocks
+    archive.resize(archive.len() + 2 * BLOCK_SIZE, 0);
+    Ok(archive)
+}
+
+fn create_tar_header(file_name: &str, size: usize, mtime: u64) -> Result<[u8; BLOCK_SIZE], String> {
+    if file_name.len() > MAX_FILE_NAME_LENGTH {
+        return Err(format!(
+            "File name '{file_name}' is too long for the support bundle archive."
+        ));
+    }
+
+    let mut header = [0u8; BLOCK_SIZE];
+    let mut write_field = |offset: usize, value: &[u8]| {
+        header[offset..offset + value.len()].copy_from_slice(value);
+    };
+    write_field(0, file_name.as_bytes());
+    write_field(100, b"0000644\0");
+    write_field(108, b"0000000\0");
+    write_field(116, b"0000000\0");
+    write_field(124, format!("{size:011o}\0").as_bytes());
+    write_field(136, format!("{mtime:011o}\0").as_bytes());
+    // the checksum is calculated with the checksum field filled with spaces
+    write_field(148, b"        ");
+    write_field(156, b"0");
+    write_field(257, b"ustar\0");
+    write_field(263, b"00");
+
+    let checksum: u32 = header.iter().map(|byte| *byte as u32).sum();
+    header[148..156].copy_from_slice(format!("{checksum:06o}\0 ").as_bytes());
+    Ok(header)
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{create_tar_archive, BLOCK_SIZE};
+
+    const MTIME: u64 = 1700000000;
+
+    fn read_octal(field: &[u8]) -> u64 {
+        let text: String = field
+            .iter()
+            .take_while(|byte| **byte != 0 && **byte != b' ')
+            .map(|byte| *byte as char)
+            .collect();
+        u64::from_str_radix(&text, 8).unwrap()
+    }
+
+    // [utest->swdd~cli-creates-support-bundle~1]
+    #[test]
+    fn utest_create_tar_archive_writes_header_content_and_end_blocks() {
+        let content = b"desiredState: {}\n".to_vec();
+        let archive = create_tar_archive(&[("state.yaml", content.clone())], MTIME).unwrap();
+
+        assert_eq!(archive.len(), 4 * BLOCK_SIZE);
+
+        let header = &archive[..BLOCK_SIZE];
+        assert!(header.starts_with(b"state.yaml\0"));
+        assert_eq!(read_octal(&header[124..136]), content.len() as u64);
+        assert_eq!(read_octal(&header[136..148]), MTIME);
+        assert_eq!(&header[257..263], b"ustar\0");
+
+        let mut header_with_blank_checksum = header.to_vec();
+        header_with_blank_checksum[148..156].copy_from_slice(b"        ");
+        let expected_checksum: u64 = header_with_blank_checksum
+            .iter()
+            .map(|byte| *byte as u64)
+            .sum();
+        assert_eq!(read_octal(&header[148..156]), expected_checksum);
+
+        assert_eq!(
+            &archive[BLOCK_SIZE..BLOCK_SIZE + content.len()],
+            content.as_slice()
+        );
+        assert!(archive[BLOCK_SIZE + content.len()..]
+            .iter()
+            .all(|byte| *byte == 0));
+    }
+
+    // [utest->swdd~cli-creates-support-bundle~1]
+    #[test]
+    fn utest_create_tar_archive_fails_on_too_long_file_name() {
+        let file_name = "a".repeat(100);
+        assert!(create_tar_archive(&[(file_name.as_str(), vec![])], MTIME).is_err());
+    }
+}
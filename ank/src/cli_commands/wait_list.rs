@@ -74,14 +74,29 @@ mock! {
 pub struct WaitList<T> {
     pub added_workloads: HashSet<WorkloadInstanceName>,
     pub deleted_workloads: HashSet<WorkloadInstanceName>,
+    pub failed_workloads: HashSet<WorkloadInstanceName>,
+    pub waiting_on_dependencies: HashSet<WorkloadInstanceName>,
     display: T,
 }
 
+fn sorted_workload_names<'a>(
+    instance_names: impl Iterator<Item = &'a WorkloadInstanceName>,
+) -> Vec<String> {
+    let mut workload_names: Vec<String> = instance_names
+        .map(|instance_name| instance_name.workload_name().to_owned())
+        .collect();
+    workload_names.sort();
+    workload_names.dedup();
+    workload_names
+}
+
 impl<T: WaitListDisplayTrait> WaitList<T> {
     pub fn new(value: ParsedUpdateStateSuccess, display: T) -> Self {
         Self {
             added_workloads: value.added_workloads.into_iter().collect(),
             deleted_workloads: value.deleted_workloads.into_iter().collect(),
+            failed_workloads: HashSet::new(),
+            waiting_on_dependencies: HashSet::new(),
             display,
         }
     }
@@ -89,21 +104,35 @@ impl<T: WaitListDisplayTrait> WaitList<T> {
     pub fn update(&mut self, values: impl IntoIterator<Item = WorkloadState>) {
         for workload_state in values.into_iter() {
             self.display.update(&workload_state);
-            // [impl->swdd~cli-checks-for-final-workload-state~1]
+            self.waiting_on_dependencies.remove(&workload_state.instance_name);
+            // [impl->swdd~cli-checks-for-final-workload-state~2]
             match workload_state.execution_state.state {
                 common::objects::ExecutionStateEnum::Running(_)
                 | common::objects::ExecutionStateEnum::Succeeded(_)
-                | common::objects::ExecutionStateEnum::Failed(_)
-                | common::objects::ExecutionStateEnum::NotScheduled => {
+                | common::objects::ExecutionStateEnum::NotScheduled
+                | common::objects::ExecutionStateEnum::Pending(
+                    PendingSubstate::WaitingForSchedule,
+                ) => {
                     if self.added_workloads.remove(&workload_state.instance_name) {
                         self.display.set_complete(&workload_state.instance_name)
                     }
                 }
+                // [impl->swdd~cli-exits-with-wait-outcome~1]
+                common::objects::ExecutionStateEnum::Failed(_)
+                | common::objects::ExecutionStateEnum::Pending(
+                    PendingSubstate::DependencyTimeout | PendingSubstate::DependencyCycle,
+                ) => {
+                    self.set_failed(&workload_state.instance_name);
+                }
                 common::objects::ExecutionStateEnum::Pending(PendingSubstate::StartingFailed)
                     if workload_state.execution_state.additional_info == NO_MORE_RETRIES_MSG =>
                 {
-                    if self.added_workloads.remove(&workload_state.instance_name) {
-                        self.display.set_complete(&workload_state.instance_name)
+                    self.set_failed(&workload_state.instance_name);
+                }
+                // [impl->swdd~cli-shows-workloads-waiting-on-dependencies~1]
+                common::objects::ExecutionStateEnum::Pending(PendingSubstate::WaitingToStart) => {
+                    if self.added_workloads.contains(&workload_state.instance_name) {
+                        self.waiting_on_dependencies.insert(workload_state.instance_name.clone());
                     }
                 }
                 common::objects::ExecutionStateEnum::Removed => {
@@ -115,12 +144,46 @@ impl<T: WaitListDisplayTrait> WaitList<T> {
             };
         }
 
-        output_update!("{}", &self.display);
+        output_update!("{}{}", &self.display, self.progress_summary());
     }
 
     pub fn step_spinner(&mut self) {
         self.display.step_spinner();
-        output_update!("{}", &self.display);
+        output_update!("{}{}", &self.display, self.progress_summary());
+    }
+
+    fn set_failed(&mut self, instance_name: &WorkloadInstanceName) {
+        if self.added_workloads.remove(instance_name) {
+            self.failed_workloads.insert(instance_name.clone());
+            self.display.set_complete(instance_name)
+        }
+    }
+
+    // [impl->swdd~cli-shows-workloads-waiting-on-dependencies~1]
+    fn progress_summary(&self) -> String {
+        if self.waiting_on_dependencies.is_empty() {
+            return String::new();
+        }
+        format!(
+            "\nWaiting on dependencies: '{}'",
+            self.get_workload_names_waiting_on_dependencies().join("', '")
+        )
+    }
+
+    pub fn get_pending_workload_names(&self) -> Vec<String> {
+        sorted_workload_names(
+            self.added_workloads
+                .iter()
+                .chain(self.deleted_workloads.iter()),
+        )
+    }
+
+    pub fn get_workload_names_waiting_on_dependencies(&self) -> Vec<String> {
+        sorted_workload_names(self.waiting_on_dependencies.iter())
+    }
+
+    pub fn get_failed_workload_names(&self) -> Vec<String> {
+        sorted_workload_names(self.failed_workloads.iter())
     }
 
     pub fn is_empty(&self) -> bool {
@@ -201,6 +264,8 @@ mod tests {
         let workload_state = WorkloadState {
             instance_name: i_name_1.clone(),
             execution_state: ExecutionState::running(),
+            agent_timestamp: None,
+            server_timestamp: None,
         };
 
         let my_mock = prepare_wait_list_display_mock(&workload_state, &i_name_1);
@@ -225,6 +290,8 @@ mod tests {
         let workload_state = WorkloadState {
             instance_name: i_name_1.clone(),
             execution_state: ExecutionState::succeeded(),
+            agent_timestamp: None,
+            server_timestamp: None,
         };
 
         let my_mock = prepare_wait_list_display_mock(&workload_state, &i_name_1);
@@ -249,6 +316,8 @@ mod tests {
         let workload_state = WorkloadState {
             instance_name: i_name_2.clone(),
             execution_state: ExecutionState::not_scheduled(),
+            agent_timestamp: None,
+            server_timestamp: None,
         };
 
         let my_mock = prepare_wait_list_display_mock(&workload_state, &i_name_2);
@@ -266,6 +335,33 @@ mod tests {
         assert!(wait_list.deleted_workloads.contains(&i_name_3));
     }
 
+    // [utest->swdd~cli-checks-for-final-workload-state~2]
+    #[test]
+    fn utest_update_wait_list_added_waiting_for_schedule() {
+        let (i_name_1, i_name_2, i_name_3) = prepare_test_instance_names();
+
+        let workload_state = WorkloadState {
+            instance_name: i_name_1.clone(),
+            execution_state: ExecutionState::waiting_for_schedule(),
+            agent_timestamp: None,
+            server_timestamp: None,
+        };
+
+        let my_mock = prepare_wait_list_display_mock(&workload_state, &i_name_1);
+
+        let mut wait_list = generate_test_wait_list(
+            my_mock,
+            vec![i_name_1.clone(), i_name_2.clone()],
+            vec![i_name_3.clone()],
+        );
+
+        wait_list.update(vec![workload_state]);
+
+        assert!(!wait_list.added_workloads.contains(&i_name_1));
+        assert!(wait_list.added_workloads.contains(&i_name_2));
+        assert!(wait_list.deleted_workloads.contains(&i_name_3));
+    }
+
     #[test]
     fn utest_update_wait_list_added_failed() {
         let (i_name_1, i_name_2, i_name_3) = prepare_test_instance_names();
@@ -273,6 +369,8 @@ mod tests {
         let workload_state = WorkloadState {
             instance_name: i_name_2.clone(),
             execution_state: ExecutionState::failed("some info"),
+            agent_timestamp: None,
+            server_timestamp: None,
         };
 
         let my_mock = prepare_wait_list_display_mock(&workload_state, &i_name_2);
@@ -288,6 +386,7 @@ mod tests {
         assert!(wait_list.added_workloads.contains(&i_name_1));
         assert!(!wait_list.added_workloads.contains(&i_name_2));
         assert!(wait_list.deleted_workloads.contains(&i_name_3));
+        assert_eq!(wait_list.get_failed_workload_names(), vec![WORKLOAD_NAME_2]);
     }
 
     #[test]
@@ -297,6 +396,8 @@ mod tests {
         let workload_state = WorkloadState {
             instance_name: i_name_2.clone(),
             execution_state: ExecutionState::retry_failed_no_retry(),
+            agent_timestamp: None,
+            server_timestamp: None,
         };
 
         let my_mock = prepare_wait_list_display_mock(&workload_state, &i_name_2);
@@ -312,6 +413,34 @@ mod tests {
         assert!(wait_list.added_workloads.contains(&i_name_1));
         assert!(!wait_list.added_workloads.contains(&i_name_2));
         assert!(wait_list.deleted_workloads.contains(&i_name_3));
+        assert_eq!(wait_list.get_failed_workload_names(), vec![WORKLOAD_NAME_2]);
+    }
+
+    // [utest->swdd~cli-exits-with-wait-outcome~1]
+    #[test]
+    fn utest_update_wait_list_added_dependency_timeout() {
+        let (i_name_1, i_name_2, i_name_3) = prepare_test_instance_names();
+
+        let workload_state = WorkloadState {
+            instance_name: i_name_2.clone(),
+            execution_state: ExecutionState::dependency_timeout(),
+            agent_timestamp: None,
+            server_timestamp: None,
+        };
+
+        let my_mock = prepare_wait_list_display_mock(&workload_state, &i_name_2);
+
+        let mut wait_list = generate_test_wait_list(
+            my_mock,
+            vec![i_name_1.clone(), i_name_2.clone()],
+            vec![i_name_3.clone()],
+        );
+
+        wait_list.update(vec![workload_state]);
+
+        assert!(wait_list.added_workloads.contains(&i_name_1));
+        assert!(!wait_list.added_workloads.contains(&i_name_2));
+        assert_eq!(wait_list.get_failed_workload_names(), vec![WORKLOAD_NAME_2]);
     }
 
     #[test]
@@ -321,6 +450,8 @@ mod tests {
         let workload_state = WorkloadState {
             instance_name: i_name_3.clone(),
             execution_state: ExecutionState::removed(),
+            agent_timestamp: None,
+            server_timestamp: None,
         };
 
         let my_mock = prepare_wait_list_display_mock(&workload_state, &i_name_3);
@@ -337,4 +468,47 @@ mod tests {
         assert!(wait_list.added_workloads.contains(&i_name_2));
         assert!(!wait_list.deleted_workloads.contains(&i_name_3));
     }
+
+    // [utest->swdd~cli-shows-workloads-waiting-on-dependencies~1]
+    #[test]
+    fn utest_update_wait_list_added_waiting_on_dependencies() {
+        let (i_name_1, i_name_2, i_name_3) = prepare_test_instance_names();
+
+        let waiting_state = WorkloadState {
+            instance_name: i_name_1.clone(),
+            execution_state: ExecutionState::waiting_to_start(),
+            agent_timestamp: None,
+            server_timestamp: None,
+        };
+        let starting_state = WorkloadState {
+            instance_name: i_name_1.clone(),
+            execution_state: ExecutionState::starting("info"),
+            agent_timestamp: None,
+            server_timestamp: None,
+        };
+
+        let mut my_mock = MockMyWaitListDisplay::new();
+        my_mock.expect_update().times(2).return_const(());
+        my_mock.expect_fmt().times(2).return_const(Ok(()));
+        my_mock.expect_set_complete().never();
+
+        let mut wait_list = generate_test_wait_list(
+            my_mock,
+            vec![i_name_1.clone(), i_name_2.clone()],
+            vec![i_name_3.clone()],
+        );
+
+        wait_list.update(vec![waiting_state]);
+        assert_eq!(
+            wait_list.get_workload_names_waiting_on_dependencies(),
+            vec![WORKLOAD_NAME_1]
+        );
+        assert!(wait_list.added_workloads.contains(&i_name_1));
+
+        wait_list.update(vec![starting_state]);
+        assert!(wait_list
+            .get_workload_names_waiting_on_dependencies()
+            .is_empty());
+        assert!(wait_list.get_failed_workload_names().is_empty());
+    }
 }
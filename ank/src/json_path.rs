@@ -0,0 +1,542 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_json::Value;
+
+const ROOT: char = '$';
+const CURRENT: char = '@';
+const KEYS: char = '~';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Comparison {
+    Equal,
+    NotEqual,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Filter {
+    path: Vec<String>,
+    condition: Option<(Comparison, Value)>,
+}
+
+impl Filter {
+    fn matches(&self, value: &Value) -> bool {
+        let selected = self
+            .path
+            .iter()
+            .try_fold(value, |current, name| current.get(name));
+        match (&self.condition, selected) {
+            (None, selected) => selected.is_some(),
+            (Some((Comparison::Equal, expected)), Some(selected)) => selected == expected,
+            (Some((Comparison::Equal, _)), None) => false,
+            (Some((Comparison::NotEqual, expected)), selected) => selected != Some(expected),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Child(String),
+    Index(i64),
+    Wildcard,
+    Filter(Filter),
+    Keys,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Text(String),
+    Expression(Vec<Segment>),
+}
+
+// A matched value together with the key it is stored under in its parent object.
+struct Node<'a> {
+    key: Option<&'a str>,
+    value: &'a Value,
+}
+
+// Returns the children of a node, i.e. the elements of an array or the values of an object.
+fn children(value: &Value) -> Vec<Node<'_>> {
+    match value {
+        Value::Array(elements) => elements
+            .iter()
+            .map(|value| Node { key: None, value })
+            .collect(),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, value)| Node {
+                key: Some(key.as_str()),
+                value,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn select<'a>(node: Node<'a>, segment: &Segment) -> Vec<Node<'a>> {
+    match segment {
+        Segment::Child(name) => node
+            .value
+            .as_object()
+            .and_then(|fields| fields.get_key_value(name))
+            .map(|(key, value)| Node {
+                key: Some(key.as_str()),
+                value,
+            })
+            .into_iter()
+            .collect(),
+        Segment::Index(index) => {
+            let elements = node.value.as_array().map(Vec::as_slice).unwrap_or_default();
+            let index = if *index < 0 {
+                elements.len() as i64 + index
+            } else {
+                *index
+            };
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| elements.get(index))
+                .map(|value| Node { key: None, value })
+                .into_iter()
+                .collect()
+        }
+        Segment::Wildcard => children(node.value),
+        Segment::Filter(filter) => children(node.value)
+            .into_iter()
+            .filter(|child| filter.matches(child.value))
+            .collect(),
+        Segment::Keys => vec![node],
+    }
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// A parsed template like `{.desiredState.workloads[?(@.agent=="agent_A")]~}` to select
+/// parts of a JSON document.
+///
+/// The template consists of text and expressions in curly braces. Supported are a subset of
+/// the JSONPath expressions:
+/// * `.name` or `['name']` to select a field of an object
+/// * `[index]` to select an element of an array, negative indices count from the end
+/// * `.*` or `[*]` to select all elements of an array or all values of an object
+/// * `[?(@.path)]`, `[?(@.path=="value")]` and `[?(@.path!="value")]` to select the
+///   elements of an array or the values of an object matching the filter
+/// * a trailing `~` to select the keys of the matched values instead of the values
+/// * `{"\n"}` to output a quoted text
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPathTemplate {
+    parts: Vec<TemplatePart>,
+}
+
+impl JsonPathTemplate {
+    /// Parses the given template.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template, e.g. `{.desiredState.workloads.nginx.agent}`
+    ///
+    // [impl->swdd~cli-supports-jsonpath-output~1]
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            chars: template.chars().collect(),
+            position: 0,
+        };
+        let parts = parser.parse_template()?;
+        if parts.is_empty() {
+            return Err("The JSONPath template is empty.".to_owned());
+        }
+        Ok(JsonPathTemplate { parts })
+    }
+
+    /// Renders the template for the given JSON document.
+    ///
+    /// All values matched by an expression are output separated by spaces. Strings are
+    /// output without quotes, all other values as compact JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The JSON document the expressions are evaluated on
+    ///
+    // [impl->swdd~cli-supports-jsonpath-output~1]
+    pub fn render(&self, document: &Value) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Text(text) => text.clone(),
+                TemplatePart::Expression(segments) => {
+                    let root = Node {
+                        key: None,
+                        value: document,
+                    };
+                    let nodes = segments.iter().fold(vec![root], |nodes, segment| {
+                        nodes
+                            .into_iter()
+                            .flat_map(|node| select(node, segment))
+                            .collect()
+                    });
+                    let output_keys = segments.last() == Some(&Segment::Keys);
+                    nodes
+                        .into_iter()
+                        .filter_map(|node| {
+                            if output_keys {
+                                node.key.map(str::to_owned)
+                            } else {
+                                Some(format_value(node.value))
+                            }
+                        })
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                }
+            })
+            .collect()
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let next = self.peek();
+        self.position += 1;
+        next
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), String> {
+        for expected_char in expected.chars() {
+            if self.next() != Some(expected_char) {
+                return Err(format!(
+                    "Expected '{}' at position {} of the JSONPath template.",
+                    expected,
+                    self.position - 1
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_template(&mut self) -> Result<Vec<TemplatePart>, String> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        while let Some(next) = self.next() {
+            if next != '{' {
+                text.push(next);
+                continue;
+            }
+            if !text.is_empty() {
+                parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+            }
+            self.skip_whitespace();
+            if let Some(quote @ ('"' | '\'')) = self.peek() {
+                self.position += 1;
+                parts.push(TemplatePart::Text(self.parse_quoted(quote)?));
+            } else {
+                parts.push(TemplatePart::Expression(self.parse_expression()?));
+            }
+            self.skip_whitespace();
+            self.expect("}")?;
+        }
+        if !text.is_empty() {
+            parts.push(TemplatePart::Text(text));
+        }
+        Ok(parts)
+    }
+
+    // Parses a quoted text after the opening quote and resolves the escape sequences.
+    fn parse_quoted(&mut self, quote: char) -> Result<String, String> {
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                Some('\\') => match self.next() {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some(escaped @ ('\\' | '"' | '\'')) => text.push(escaped),
+                    _ => {
+                        return Err(format!(
+                            "Invalid escape sequence at position {} of the JSONPath template.",
+                            self.position - 1
+                        ))
+                    }
+                },
+                Some(next) if next == quote => return Ok(text),
+                Some(next) => text.push(next),
+                None => return Err("Unterminated quote in the JSONPath template.".to_owned()),
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String, String> {
+        let start = self.position;
+        while self
+            .peek()
+            .is_some_and(|next| next.is_alphanumeric() || next == '_' || next == '-')
+        {
+            self.position += 1;
+        }
+        if start == self.position {
+            return Err(format!(
+                "Expected a field name at position {} of the JSONPath template.",
+                start
+            ));
+        }
+        Ok(self.chars[start..self.position].iter().collect())
+    }
+
+    fn parse_expression(&mut self) -> Result<Vec<Segment>, String> {
+        let mut segments = Vec::new();
+        if self.peek() == Some(ROOT) {
+            self.position += 1;
+        }
+        loop {
+            match self.peek() {
+                Some('.') => {
+                    self.position += 1;
+                    match self.peek() {
+                        Some('*') => {
+                            self.position += 1;
+                            segments.push(Segment::Wildcard);
+                        }
+                        Some('.') => {
+                            return Err("Recursive descent is not supported in JSONPath templates."
+                                .to_owned())
+                        }
+                        Some('[' | '}' | KEYS) | None => {}
+                        Some(_) => segments.push(Segment::Child(self.parse_name()?)),
+                    }
+                }
+                Some('[') => {
+                    self.position += 1;
+                    segments.push(self.parse_bracket()?);
+                }
+                Some(KEYS) => {
+                    self.position += 1;
+                    segments.push(Segment::Keys);
+                    self.skip_whitespace();
+                    if self.peek() != Some('}') {
+                        return Err(
+                            "The key selector '~' must be the last part of an expression."
+                                .to_owned(),
+                        );
+                    }
+                    return Ok(segments);
+                }
+                _ => return Ok(segments),
+            }
+        }
+    }
+
+    // Parses the content of a bracket after the opening bracket.
+    fn parse_bracket(&mut self) -> Result<Segment, String> {
+        self.skip_whitespace();
+        let segment = match self.peek() {
+            Some('*') => {
+                self.position += 1;
+                Segment::Wildcard
+            }
+            Some(quote @ ('"' | '\'')) => {
+                self.position += 1;
+                Segment::Child(self.parse_quoted(quote)?)
+            }
+            Some('?') => {
+                self.position += 1;
+                self.skip_whitespace();
+                self.expect("(")?;
+                let filter = self.parse_filter()?;
+                self.skip_whitespace();
+                self.expect(")")?;
+                Segment::Filter(filter)
+            }
+            _ => {
+                let start = self.position;
+                while self
+                    .peek()
+                    .is_some_and(|next| next.is_ascii_digit() || next == '-')
+                {
+                    self.position += 1;
+                }
+                let index: String = self.chars[start..self.position].iter().collect();
+                Segment::Index(index.parse().map_err(|_| {
+                    format!(
+                        "Expected an index at position {} of the JSONPath template.",
+                        start
+                    )
+                })?)
+            }
+        };
+        self.skip_whitespace();
+        self.expect("]")?;
+        Ok(segment)
+    }
+
+    fn parse_filter(&mut self) -> Result<Filter, String> {
+        self.skip_whitespace();
+        self.expect(&CURRENT.to_string())?;
+        let mut path = Vec::new();
+        while self.peek() == Some('.') {
+            self.position += 1;
+            path.push(self.parse_name()?);
+        }
+        self.skip_whitespace();
+        let comparison = match self.peek() {
+            Some('=') => {
+                self.expect("==")?;
+                Comparison::Equal
+            }
+            Some('!') => {
+                self.expect("!=")?;
+                Comparison::NotEqual
+            }
+            _ => {
+                return Ok(Filter {
+                    path,
+                    condition: None,
+                })
+            }
+        };
+        self.skip_whitespace();
+        let expected = match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                self.position += 1;
+                Value::String(self.parse_quoted(quote)?)
+            }
+            _ => {
+                let start = self.position;
+                while self
+                    .peek()
+                    .is_some_and(|next| next != ')' && !next.is_whitespace())
+                {
+                    self.position += 1;
+                }
+                let literal: String = self.chars[start..self.position].iter().collect();
+                serde_json::from_str(&literal).map_err(|_| {
+                    format!(
+                        "Invalid value '{}' in the filter of the JSONPath template.",
+                        literal
+                    )
+                })?
+            }
+        };
+        Ok(Filter {
+            path,
+            condition: Some((comparison, expected)),
+        })
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::JsonPathTemplate;
+
+    fn document() -> serde_json::Value {
+        json!({
+            "desiredState": {
+                "workloads": {
+                    "nginx": {"agent": "agent_A", "runtime": "podman", "restart": true},
+                    "hello": {"agent": "agent_B", "runtime": "podman"},
+                    "api": {"agent": "agent_A", "runtime": "podman-kube"}
+                }
+            },
+            "items": [
+                {"name": "first", "count": 1},
+                {"name": "second", "count": 2},
+                {"name": "third", "count": 3}
+            ]
+        })
+    }
+
+    fn render(template: &str) -> String {
+        JsonPathTemplate::parse(template)
+            .unwrap()
+            .render(&document())
+    }
+
+    // [utest->swdd~cli-supports-jsonpath-output~1]
+    #[test]
+    fn utest_json_path_selects_fields_and_indices() {
+        assert_eq!(render("{.desiredState.workloads.nginx.agent}"), "agent_A");
+        assert_eq!(
+            render("{$['desiredState']['workloads']['hello'].runtime}"),
+            "podman"
+        );
+        assert_eq!(render("{.items[0].name}"), "first");
+        assert_eq!(render("{.items[-1].count}"), "3");
+        assert_eq!(render("{.items[*].name}"), "first second third");
+        assert_eq!(render("{.items[1]}"), r#"{"count":2,"name":"second"}"#);
+        assert_eq!(render("{.unknown.field}"), "");
+    }
+
+    // [utest->swdd~cli-supports-jsonpath-output~1]
+    #[test]
+    fn utest_json_path_filters_values_and_selects_keys() {
+        assert_eq!(
+            render(r#"{.desiredState.workloads[?(@.agent=="agent_A")]~}"#),
+            "api nginx"
+        );
+        assert_eq!(
+            render("{.desiredState.workloads[?(@.agent != 'agent_A')].runtime}"),
+            "podman"
+        );
+        assert_eq!(render("{.desiredState.workloads[?(@.restart)]~}"), "nginx");
+        assert_eq!(render("{.items[?(@.count==2)].name}"), "second");
+        assert_eq!(render("{.desiredState.workloads.*~}"), "api hello nginx");
+    }
+
+    // [utest->swdd~cli-supports-jsonpath-output~1]
+    #[test]
+    fn utest_json_path_renders_text_and_quoted_text() {
+        assert_eq!(
+            render(r#"nginx runs on {.desiredState.workloads.nginx.agent}{"\n"}"#),
+            "nginx runs on agent_A\n"
+        );
+    }
+
+    // [utest->swdd~cli-supports-jsonpath-output~1]
+    #[test]
+    fn utest_json_path_rejects_invalid_templates() {
+        assert!(JsonPathTemplate::parse("").is_err());
+        assert!(JsonPathTemplate::parse("{.items[0}").is_err());
+        assert!(JsonPathTemplate::parse("{.items").is_err());
+        assert!(JsonPathTemplate::parse("{..name}").is_err());
+        assert!(JsonPathTemplate::parse("{.items~.name}").is_err());
+        assert!(JsonPathTemplate::parse("{.items[?(@.count==abc)]}").is_err());
+        assert!(JsonPathTemplate::parse(r#"{"unterminated}"#).is_err());
+    }
+}
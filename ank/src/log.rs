@@ -27,6 +27,12 @@ macro_rules! output_and_error {
     ( $ ( $ arg : tt ) + ) => { $crate::log::output_and_error_fn ( format_args ! ( $ ( $ arg ) + ) ) }
 }
 
+/// Prints the error message and immediately terminates the application with the given exit code.
+#[macro_export]
+macro_rules! output_and_error_with_code {
+    ( $ code : expr , $ ( $ arg : tt ) + ) => { $crate::log::output_and_error_with_code_fn ( $ code , format_args ! ( $ ( $ arg ) + ) ) }
+}
+
 /// Prints the message and immediately terminates the application with the exit code `0`.
 #[macro_export]
 macro_rules! output_and_exit {
@@ -42,8 +48,12 @@ macro_rules! output_debug {
 }
 
 pub(crate) fn output_and_error_fn(args: fmt::Arguments<'_>) {
+    output_and_error_with_code_fn(1, args);
+}
+
+pub(crate) fn output_and_error_with_code_fn(exit_code: i32, args: fmt::Arguments<'_>) {
     eprintln!("{} {}", "error:".bold().red(), args);
-    exit(1);
+    exit(exit_code);
 }
 
 pub(crate) fn output_and_exit_fn(args: fmt::Arguments<'_>) {
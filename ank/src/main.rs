@@ -12,12 +12,17 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::env;
+use std::{
+    env,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 mod cli;
 mod cli_commands;
 use cli_commands::CliCommands;
+mod json_path;
 mod log;
+mod plugin;
 
 #[cfg(test)]
 pub mod test_helper;
@@ -37,11 +42,73 @@ async fn main() {
         args
     );
 
+    // the conversion does not need the Ankaios server and is therefore done without connecting to it
+    // [impl->swdd~cli-converts-kubernetes-manifests~1]
+    if let cli::Commands::Convert(convert_args) = &args.command {
+        output_debug!("Received convert with args '{:?}'", convert_args);
+        let read_result = if convert_args.manifest_file == "-" {
+            std::io::read_to_string(std::io::stdin())
+        } else {
+            std::fs::read_to_string(&convert_args.manifest_file)
+        };
+        let manifest = match read_result {
+            Ok(manifest) => manifest,
+            Err(error) => {
+                output_and_error!(
+                    "Could not read '{}': '{}'",
+                    convert_args.manifest_file,
+                    error
+                );
+                return;
+            }
+        };
+        // [impl->swdd~cli-converts-compose-files~1]
+        let conversion_result = match convert_args.format {
+            cli::ConvertFormat::Kubernetes => common::kube_conversion::convert_kube_manifest(
+                &manifest,
+                &convert_args.agent_name,
+                convert_args.runtime.map(Into::into),
+            ),
+            cli::ConvertFormat::Compose if convert_args.runtime.is_some() => {
+                Err("The runtime can only be selected for Kubernetes manifests.".to_owned())
+            }
+            cli::ConvertFormat::Compose => common::compose_conversion::convert_compose_file(
+                &manifest,
+                &convert_args.agent_name,
+            ),
+        };
+        match conversion_result
+            .and_then(|state| serde_yaml::to_string(&state).map_err(|error| error.to_string()))
+        {
+            Ok(ankaios_manifest) => output_and_exit!("{}", ankaios_manifest),
+            Err(error) => output_and_error!(
+                "Failed to convert '{}': '{}'",
+                convert_args.manifest_file,
+                error
+            ),
+        }
+    }
+
+    // plugins connect to the Ankaios server on their own and only get the connection context
+    // [impl->swdd~cli-runs-plugins~1]
+    if let cli::Commands::Plugin(plugin_args) = &args.command {
+        let context = plugin::PluginContext {
+            server_url: args.server_url.clone(),
+            response_timeout_ms: args.response_timeout_ms,
+        };
+        match plugin::run_plugin(plugin_args, &context).await {
+            Ok(exit_code) => std::process::exit(exit_code),
+            Err(error) => output_and_error!("{}", error),
+        }
+    }
+
     let mut cmd = CliCommands::init(
         args.response_timeout_ms,
         cli_name.to_string(),
         args.server_url,
         args.no_wait,
+        args.wait_timeout_s.map(Duration::from_secs),
+        args.force,
     );
 
     match args.command {
@@ -67,22 +134,57 @@ async fn main() {
                 workload_name,
                 agent_name,
                 state,
+                show_terminated,
             }) => {
                 output_debug!(
-                    "Received get workload with workload_name='{:?}', agent_name='{:?}', state='{:?}'",
+                    "Received get workload with workload_name='{:?}', agent_name='{:?}', state='{:?}', show_terminated='{}'",
                     workload_name,
                     agent_name,
                     state,
+                    show_terminated,
                 );
 
                 match cmd
-                    .get_workloads_table(agent_name, state, workload_name)
+                    .get_workloads_table(agent_name, state, workload_name, show_terminated)
                     .await
                 {
                     Ok(out_text) => output_and_exit!("{}", out_text),
                     Err(error) => output_and_error!("Failed to get workloads: '{}'", error),
                 }
             }
+            // [impl->swdd~cli-provides-rollout-status~1]
+            Some(cli::GetCommands::Rollout {}) => match cmd.get_rollout_status().await {
+                Ok(out_text) => output_and_exit!("{}", out_text),
+                Err(error) => output_and_error!("Failed to get rollout status: '{}'", error),
+            },
+            // [impl->swdd~cli-provides-list-of-agents~2]
+            Some(cli::GetCommands::Agents {}) => match cmd.get_agents_table().await {
+                Ok(out_text) => output_and_exit!("{}", out_text),
+                Err(error) => output_and_error!("Failed to get agents: '{}'", error),
+            },
+            // [impl->swdd~cli-provides-events~1]
+            Some(cli::GetCommands::Events { since_secs, limit }) => {
+                let since = since_secs
+                    .map(|since_secs| {
+                        SystemTime::now()
+                            .checked_sub(Duration::from_secs(since_secs))
+                            .and_then(|since| since.duration_since(UNIX_EPOCH).ok())
+                            .unwrap_or_default()
+                            .as_millis() as u64
+                    })
+                    .unwrap_or_default();
+                match cmd.get_events_table(since, limit).await {
+                    Ok(out_text) => output_and_exit!("{}", out_text),
+                    Err(error) => output_and_error!("Failed to get events: '{}'", error),
+                }
+            }
+            // [impl->swdd~cli-provides-scheduler-queue~1]
+            Some(cli::GetCommands::Queue { agent_name }) => {
+                match cmd.get_scheduler_queue_table(agent_name).await {
+                    Ok(out_text) => output_and_exit!("{}", out_text),
+                    Err(error) => output_and_error!("Failed to get scheduler queue: '{}'", error),
+                }
+            }
             None => unreachable!("Unreachable code."),
         },
         cli::Commands::Set(set_args) => match set_args.command {
@@ -99,19 +201,30 @@ async fn main() {
                 // [impl -> swdd~cli-provides-set-desired-state~1]
                 // [impl -> swdd~cli-blocks-until-ankaios-server-responds-set-desired-state~2]
                 if let Err(err) = cmd.set_state(object_field_mask, state_object_file).await {
-                    output_and_error!("Failed to set state: '{}'", err)
+                    output_and_error_with_code!(err.exit_code(), "Failed to set state: '{}'", err)
+                }
+            }
+            // [impl->swdd~cli-switches-system-mode~1]
+            Some(cli::SetCommands::Mode { mode }) => {
+                output_debug!("Received set mode with mode='{}'", mode);
+                if let Err(err) = cmd.set_mode(mode).await {
+                    output_and_error!("Failed to set mode: '{}'", err)
                 }
             }
             None => unreachable!("Unreachable code."),
         },
         cli::Commands::Delete(delete_args) => match delete_args.command {
-            Some(cli::DeleteCommands::Workload { workload_name }) => {
+            Some(cli::DeleteCommands::Workload { workload_name, yes }) => {
                 output_debug!(
                     "Received delete workload with workload_name = '{:?}'",
                     workload_name
                 );
-                if let Err(error) = cmd.delete_workloads(workload_name).await {
-                    output_and_error!("Failed to delete workloads: '{}'", error);
+                if let Err(error) = cmd.delete_workloads(workload_name, yes).await {
+                    output_and_error_with_code!(
+                        error.exit_code(),
+                        "Failed to delete workloads: '{}'",
+                        error
+                    );
                 }
             }
             None => unreachable!("Unreachable code."),
@@ -142,16 +255,92 @@ async fn main() {
                     )
                     .await
                 {
-                    output_and_error!("Failed to run workloads: '{}'", error);
+                    output_and_error_with_code!(
+                        error.exit_code(),
+                        "Failed to run workloads: '{}'",
+                        error
+                    );
                 }
             }
             None => unreachable!("Unreachable code."),
         },
         cli::Commands::Apply(apply_args) => {
+            // [impl->swdd~cli-exits-with-wait-outcome~1]
             if let Err(err) = cmd.apply_manifests(apply_args).await {
-                output_and_error!("{}", err);
+                output_and_error_with_code!(err.exit_code(), "{}", err);
+            }
+        }
+        // [impl->swdd~cli-diffs-manifests~1]
+        cli::Commands::Diff(apply_args) => match cmd.diff_manifests(apply_args).await {
+            Ok(out_text) => output_and_exit!("{}", out_text.trim_end()),
+            Err(err) => output_and_error!("Failed to diff manifests: '{}'", err),
+        },
+        // [impl->swdd~cli-creates-support-bundle~1]
+        cli::Commands::SupportBundle(support_bundle_args) => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let output_file = support_bundle_args
+                .output_file
+                .unwrap_or_else(|| cli_commands::default_bundle_file_name(now));
+            match cmd.create_support_bundle(now).await {
+                Ok(archive) => match std::fs::write(&output_file, archive) {
+                    Ok(()) => output_and_exit!("Support bundle written to '{}'", output_file),
+                    Err(error) => output_and_error!(
+                        "Failed to write support bundle to '{}': '{}'",
+                        output_file,
+                        error
+                    ),
+                },
+                Err(error) => output_and_error!("Failed to create support bundle: '{}'", error),
+            }
+        }
+        // [impl->swdd~cli-drains-agent~1]
+        cli::Commands::Drain(drain_args) => {
+            output_debug!("Received drain with args '{:?}'", drain_args);
+            if let Err(error) = cmd
+                .drain_agent(
+                    drain_args.agent_name,
+                    drain_args.target_agent,
+                    drain_args.remove,
+                )
+                .await
+            {
+                output_and_error!("Failed to drain agent: '{}'", error);
+            }
+        }
+        // [impl->swdd~cli-exports-dependency-graph~1]
+        cli::Commands::Graph(graph_args) => {
+            match cmd.get_dependency_graph(graph_args.format).await {
+                Ok(out_text) => output_and_exit!("{}", out_text),
+                Err(error) => output_and_error!("Failed to get dependency graph: '{}'", error),
+            }
+        }
+        // [impl->swdd~cli-operates-on-workload-groups~1]
+        cli::Commands::Group(group_args) => {
+            output_debug!("Received group with args '{:?}'", group_args);
+            let result = match group_args.command {
+                Some(cli::GroupCommands::Start { group_name }) => {
+                    cmd.set_group_stopped(group_name, false).await
+                }
+                Some(cli::GroupCommands::Stop { group_name }) => {
+                    cmd.set_group_stopped(group_name, true).await
+                }
+                Some(cli::GroupCommands::Restart { group_name }) => {
+                    cmd.restart_group(group_name).await
+                }
+                Some(cli::GroupCommands::Delete { group_name, yes }) => {
+                    cmd.delete_group(group_name, yes).await
+                }
+                None => unreachable!("Unreachable code."),
+            };
+            if let Err(error) = result {
+                output_and_error!("Failed to operate on workload group: '{}'", error);
             }
         }
+        cli::Commands::Convert(_) => unreachable!("Convert is handled without server connection."),
+        cli::Commands::Plugin(_) => unreachable!("Plugins are run without server connection."),
     }
 
     cmd.shut_down().await;
@@ -0,0 +1,197 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    env,
+    ffi::OsStr,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use url::Url;
+
+use crate::{
+    cli::{ANK_RESPONSE_TIMEOUT_ENV_KEY, ANK_SERVER_URL_ENV_KEY},
+    output_debug,
+};
+
+const PLUGIN_PREFIX: &str = "ank-";
+const CLI_PATH_ENV_KEY: &str = "ANK_CLI_PATH";
+// the other executables of Ankaios are installed next to the CLI and are no plugins
+const RESERVED_NAMES: [&str; 2] = ["server", "agent"];
+const EXIT_CODE_OF_KILLED_PLUGIN: i32 = 1;
+
+// The connection context of the CLI passed to a plugin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginContext {
+    pub server_url: Url,
+    pub response_timeout_ms: u64,
+}
+
+impl PluginContext {
+    fn environment(&self) -> Vec<(&'static str, String)> {
+        let mut environment = vec![
+            (ANK_SERVER_URL_ENV_KEY, self.server_url.to_string()),
+            (
+                ANK_RESPONSE_TIMEOUT_ENV_KEY,
+                self.response_timeout_ms.to_string(),
+            ),
+        ];
+        if let Ok(cli_path) = env::current_exe() {
+            environment.push((CLI_PATH_ENV_KEY, cli_path.to_string_lossy().into_owned()));
+        }
+        environment
+    }
+}
+
+fn is_executable(path: &Path) -> bool {
+    path.metadata()
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+/// Returns the first executable named `ank-<name>` in the folders of the given search path.
+///
+/// # Arguments
+///
+/// * `name` - The name of the plugin, i.e. the subcommand the user called
+/// * `search_path` - The folders to search in, formatted like the `PATH` environment variable
+///
+// [impl->swdd~cli-runs-plugins~1]
+pub fn find_plugin(name: &str, search_path: &OsStr) -> Option<PathBuf> {
+    if name.is_empty() || name.contains(std::path::MAIN_SEPARATOR) || RESERVED_NAMES.contains(&name)
+    {
+        return None;
+    }
+    env::split_paths(search_path)
+        .map(|folder| folder.join(format!("{PLUGIN_PREFIX}{name}")))
+        .find(|candidate| is_executable(candidate))
+}
+
+/// Runs the plugin for an unknown subcommand and returns its exit code.
+///
+/// The remaining arguments are passed to the plugin and the connection context of the CLI
+/// is passed in environment variables.
+///
+/// # Arguments
+///
+/// * `plugin_args` - The unknown subcommand followed by its arguments
+/// * `context` - The connection context of the CLI
+///
+// [impl->swdd~cli-runs-plugins~1]
+pub async fn run_plugin(plugin_args: &[String], context: &PluginContext) -> Result<i32, String> {
+    let Some((name, args)) = plugin_args.split_first() else {
+        return Err("No subcommand given.".to_owned());
+    };
+    let search_path = env::var_os("PATH").unwrap_or_default();
+    let plugin = find_plugin(name, &search_path).ok_or_else(|| {
+        format!("Unknown subcommand '{name}' and no plugin '{PLUGIN_PREFIX}{name}' found on PATH.")
+    })?;
+    output_debug!(
+        "Running plugin '{}' with args '{:?}'",
+        plugin.display(),
+        args
+    );
+
+    let status = tokio::process::Command::new(&plugin)
+        .args(args)
+        .envs(context.environment())
+        .status()
+        .await
+        .map_err(|err| format!("Could not run plugin '{}': '{}'", plugin.display(), err))?;
+    Ok(status.code().unwrap_or(EXIT_CODE_OF_KILLED_PLUGIN))
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, os::unix::fs::PermissionsExt, path::PathBuf};
+
+    use super::{find_plugin, run_plugin, PluginContext};
+
+    struct TestFolder(PathBuf);
+
+    impl TestFolder {
+        fn new() -> Self {
+            let folder = env::temp_dir().join(format!("ank-plugins-{}", uuid::Uuid::new_v4()));
+            fs::create_dir(&folder).unwrap();
+            TestFolder(folder)
+        }
+
+        fn add_file(&self, name: &str, mode: u32) -> PathBuf {
+            let file = self.0.join(name);
+            fs::write(&file, "#!/bin/sh\n").unwrap();
+            fs::set_permissions(&file, fs::Permissions::from_mode(mode)).unwrap();
+            file
+        }
+    }
+
+    impl Drop for TestFolder {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    // [utest->swdd~cli-runs-plugins~1]
+    #[test]
+    fn utest_find_plugin_returns_first_executable_on_search_path() {
+        let first_folder = TestFolder::new();
+        let second_folder = TestFolder::new();
+        first_folder.add_file("ank-fleet", 0o644);
+        let plugin = second_folder.add_file("ank-fleet", 0o755);
+        second_folder.add_file("ank-server", 0o755);
+        let search_path = env::join_paths([&first_folder.0, &second_folder.0]).unwrap();
+
+        assert_eq!(find_plugin("fleet", &search_path), Some(plugin));
+        assert_eq!(find_plugin("server", &search_path), None);
+        assert_eq!(find_plugin("unknown", &search_path), None);
+        assert_eq!(find_plugin("../ank-fleet", &search_path), None);
+    }
+
+    // [utest->swdd~cli-runs-plugins~1]
+    #[test]
+    fn utest_plugin_context_environment() {
+        let context = PluginContext {
+            server_url: "http://127.0.0.1:25551".parse().unwrap(),
+            response_timeout_ms: 5000,
+        };
+
+        let environment = context.environment();
+
+        assert!(environment.contains(&("ANK_SERVER_URL", "http://127.0.0.1:25551/".to_owned())));
+        assert!(environment.contains(&("ANK_RESPONSE_TIMEOUT", "5000".to_owned())));
+    }
+
+    // [utest->swdd~cli-runs-plugins~1]
+    #[tokio::test]
+    async fn utest_run_plugin_fails_for_unknown_plugin() {
+        let context = PluginContext {
+            server_url: "http://127.0.0.1:25551".parse().unwrap(),
+            response_timeout_ms: 5000,
+        };
+
+        assert!(
+            run_plugin(&["unknown-plugin-for-test".to_owned()], &context)
+                .await
+                .is_err()
+        );
+    }
+}
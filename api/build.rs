@@ -12,6 +12,33 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+const VERSIONED_PACKAGE: &str = "\npackage ank.v1;\n";
+const COMPAT_PACKAGE: &str = "\npackage ank_base;\noption deprecated = true;\n";
+
+// [impl->swdd~api-provides-deprecated-unversioned-packages~1]
+// The deprecated package 'ank_base' only differs from 'ank.v1' in its name and is therefore
+// generated from it instead of being kept as a copy.
+fn generate_compat_ank_base(compat_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let versioned = fs::read_to_string("proto/ank_base.proto")?;
+    if !versioned.contains(VERSIONED_PACKAGE) {
+        return Err("proto/ank_base.proto does not declare the package 'ank.v1'".into());
+    }
+    let compat = versioned.replacen(VERSIONED_PACKAGE, COMPAT_PACKAGE, 1);
+
+    fs::create_dir_all(compat_dir)?;
+    let compat_path = compat_dir.join("ank_base.proto");
+    // an unchanged file keeps cargo from rerunning the build script on every build
+    if fs::read_to_string(&compat_path).ok().as_deref() != Some(compat.as_str()) {
+        fs::write(compat_path, compat)?;
+    }
+    Ok(())
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     tonic_build::configure()
         .build_server(true)
@@ -20,5 +47,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             &["proto"],
         )
         .unwrap();
+    // the deprecated unversioned packages are compiled to check their wire format in the tests
+    let compat_dir = PathBuf::from(env::var("OUT_DIR")?).join("compat");
+    generate_compat_ank_base(&compat_dir)?;
+    tonic_build::configure()
+        .compile(
+            &["proto/compat/control_api.proto"],
+            &[PathBuf::from("proto/compat"), compat_dir],
+        )
+        .unwrap();
     Ok(())
 }
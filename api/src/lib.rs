@@ -12,13 +12,78 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-pub mod ank_base {
-
-    // [impl->swdd~ank-base-provides-object-definitions~1]
-    tonic::include_proto!("ank_base"); // The string specified here must match the proto package name
+pub mod ank {
+    // [impl->swdd~api-provides-versioned-package~1]
+    // prost generates the messages of a oneof inline into its enum, thus the variants differ in
+    // size. Boxing them in the build script would change the generated types for every user of
+    // the API, while the messages are only created to be (de)serialized and are short-lived.
+    #[allow(clippy::large_enum_variant)]
+    pub mod v1 {
+        // [impl->swdd~ank-base-provides-object-definitions~1]
+        // [impl->swdd~control-api-provides-control-interface-definitions~1]
+        tonic::include_proto!("ank.v1"); // The string specified here must match the proto package name
+    }
 }
 
-pub mod control_api {
-    // [impl->swdd~control-api-provides-control-interface-definitions~1]
-    tonic::include_proto!("control_api"); // The string specified here must match the proto package name
+// The unversioned modules map to the current version of the API and are used inside of Ankaios,
+// which converts the objects into its own domain objects anyway. They also keep Rust clients
+// written against the unversioned packages compiling.
+// External clients shall use the versioned package to rely on a stable wire contract.
+// [impl->swdd~api-provides-deprecated-unversioned-packages~1]
+pub use ank::v1 as ank_base;
+pub use ank::v1 as control_api;
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+
+    use crate::ank::v1;
+
+    // the deprecated unversioned packages, 'ank_base' is generated from 'ank.v1' by the build script
+    mod compat {
+        // only used to decode and encode messages, the enums are generated like for 'ank.v1'
+        #![allow(dead_code, clippy::enum_variant_names, clippy::large_enum_variant)]
+        pub mod ank_base {
+            tonic::include_proto!("ank_base");
+        }
+        pub mod control_api {
+            tonic::include_proto!("control_api");
+        }
+    }
+
+    // [utest->swdd~api-provides-deprecated-unversioned-packages~1]
+    #[test]
+    fn utest_compat_packages_have_same_wire_format() {
+        let to_ankaios = v1::ToAnkaios {
+            to_ankaios_enum: Some(v1::to_ankaios::ToAnkaiosEnum::Request(v1::Request {
+                request_id: "request_id".to_owned(),
+                request_content: Some(v1::request::RequestContent::CompleteStateRequest(
+                    v1::CompleteStateRequest {
+                        field_mask: vec!["desiredState".to_owned()],
+                    },
+                )),
+            })),
+        };
+
+        let compat_to_ankaios =
+            compat::control_api::ToAnkaios::decode(to_ankaios.encode_to_vec().as_slice()).unwrap();
+        let Some(compat::control_api::to_ankaios::ToAnkaiosEnum::Request(request)) =
+            &compat_to_ankaios.to_ankaios_enum
+        else {
+            panic!("Expected a request, got '{compat_to_ankaios:?}'");
+        };
+        assert_eq!(request.request_id, "request_id");
+        assert_eq!(
+            v1::ToAnkaios::decode(compat_to_ankaios.encode_to_vec().as_slice()).unwrap(),
+            to_ankaios
+        );
+    }
 }
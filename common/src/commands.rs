@@ -12,16 +12,26 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::objects::{CompleteState, DeletedWorkload, WorkloadSpec};
+use std::collections::HashMap;
+
+use crate::objects::{
+    AddCondition, AgentInfo, AgentResources, CompleteState, ControlInterfaceMetrics,
+    DeletedWorkload, ExecutionState, StoredWorkloadSpec, WorkloadSpec,
+};
 use api::ank_base;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
 pub struct AgentHello {
     pub agent_name: String,
+    pub rollout_group: Option<String>,
+    pub agent_version: String,
+    pub runtimes: Vec<String>,
+    pub attributes: HashMap<String, String>,
+    pub local_workloads: HashMap<String, StoredWorkloadSpec>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub struct AgentGone {
     pub agent_name: String,
 }
@@ -31,9 +41,15 @@ pub struct UpdateWorkloadState {
     pub workload_states: Vec<crate::objects::WorkloadState>,
 }
 
+// [impl->swdd~server-distributes-connected-agents~1]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct UpdateConnectedAgents {
+    pub connected_agents: Vec<String>,
+}
+
 
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Request {
     pub request_id: String,
     pub request_content: RequestContent,
@@ -67,10 +83,19 @@ impl TryFrom<ank_base::Request> for Request {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum RequestContent {
     CompleteStateRequest(CompleteStateRequest),
     UpdateStateRequest(Box<UpdateStateRequest>),
+    RolloutStatusRequest(RolloutStatusRequest),
+    SupportInfoRequest(SupportInfoRequest),
+    EventsRequest(EventsRequest),
+    DrainAgentRequest(DrainAgentRequest),
+    WatchCompleteStateRequest(WatchCompleteStateRequest),
+    CancelWatchRequest(CancelWatchRequest),
+    ImpactAnalysisRequest(ImpactAnalysisRequest),
+    DependencyGraphRequest(DependencyGraphRequest),
+    SchedulerQueueRequest(SchedulerQueueRequest),
 }
 
 impl From<RequestContent> for ank_base::request::RequestContent {
@@ -82,6 +107,33 @@ impl From<RequestContent> for ank_base::request::RequestContent {
             RequestContent::UpdateStateRequest(content) => {
                 ank_base::request::RequestContent::UpdateStateRequest((*content).into())
             }
+            RequestContent::RolloutStatusRequest(content) => {
+                ank_base::request::RequestContent::RolloutStatusRequest(content.into())
+            }
+            RequestContent::SupportInfoRequest(content) => {
+                ank_base::request::RequestContent::SupportInfoRequest(content.into())
+            }
+            RequestContent::EventsRequest(content) => {
+                ank_base::request::RequestContent::EventsRequest(content.into())
+            }
+            RequestContent::DrainAgentRequest(content) => {
+                ank_base::request::RequestContent::DrainAgentRequest(content.into())
+            }
+            RequestContent::WatchCompleteStateRequest(content) => {
+                ank_base::request::RequestContent::WatchCompleteStateRequest(content.into())
+            }
+            RequestContent::CancelWatchRequest(content) => {
+                ank_base::request::RequestContent::CancelWatchRequest(content.into())
+            }
+            RequestContent::ImpactAnalysisRequest(content) => {
+                ank_base::request::RequestContent::ImpactAnalysisRequest(content.into())
+            }
+            RequestContent::DependencyGraphRequest(content) => {
+                ank_base::request::RequestContent::DependencyGraphRequest(content.into())
+            }
+            RequestContent::SchedulerQueueRequest(content) => {
+                ank_base::request::RequestContent::SchedulerQueueRequest(content.into())
+            }
         }
     }
 }
@@ -96,11 +148,38 @@ impl TryFrom<ank_base::request::RequestContent> for RequestContent {
             ank_base::request::RequestContent::CompleteStateRequest(value) => {
                 RequestContent::CompleteStateRequest(value.into())
             }
+            ank_base::request::RequestContent::RolloutStatusRequest(value) => {
+                RequestContent::RolloutStatusRequest(value.into())
+            }
+            ank_base::request::RequestContent::SupportInfoRequest(value) => {
+                RequestContent::SupportInfoRequest(value.into())
+            }
+            ank_base::request::RequestContent::EventsRequest(value) => {
+                RequestContent::EventsRequest(value.into())
+            }
+            ank_base::request::RequestContent::DrainAgentRequest(value) => {
+                RequestContent::DrainAgentRequest(value.into())
+            }
+            ank_base::request::RequestContent::WatchCompleteStateRequest(value) => {
+                RequestContent::WatchCompleteStateRequest(value.into())
+            }
+            ank_base::request::RequestContent::CancelWatchRequest(value) => {
+                RequestContent::CancelWatchRequest(value.into())
+            }
+            ank_base::request::RequestContent::ImpactAnalysisRequest(value) => {
+                RequestContent::ImpactAnalysisRequest(value.into())
+            }
+            ank_base::request::RequestContent::DependencyGraphRequest(value) => {
+                RequestContent::DependencyGraphRequest(value.into())
+            }
+            ank_base::request::RequestContent::SchedulerQueueRequest(value) => {
+                RequestContent::SchedulerQueueRequest(value.into())
+            }
         })
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct CompleteStateRequest {
     pub field_mask: Vec<String>,
 }
@@ -121,10 +200,198 @@ impl From<ank_base::CompleteStateRequest> for CompleteStateRequest {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+pub struct WatchCompleteStateRequest {
+    pub field_mask: Vec<String>,
+}
+
+impl From<WatchCompleteStateRequest> for ank_base::WatchCompleteStateRequest {
+    fn from(item: WatchCompleteStateRequest) -> Self {
+        ank_base::WatchCompleteStateRequest {
+            field_mask: item.field_mask,
+        }
+    }
+}
+
+impl From<ank_base::WatchCompleteStateRequest> for WatchCompleteStateRequest {
+    fn from(item: ank_base::WatchCompleteStateRequest) -> Self {
+        WatchCompleteStateRequest {
+            field_mask: item.field_mask,
+        }
+    }
+}
+
+impl From<WatchCompleteStateRequest> for CompleteStateRequest {
+    fn from(item: WatchCompleteStateRequest) -> Self {
+        CompleteStateRequest {
+            field_mask: item.field_mask,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+pub struct CancelWatchRequest {}
+
+impl From<CancelWatchRequest> for ank_base::CancelWatchRequest {
+    fn from(_item: CancelWatchRequest) -> Self {
+        ank_base::CancelWatchRequest {}
+    }
+}
+
+impl From<ank_base::CancelWatchRequest> for CancelWatchRequest {
+    fn from(_item: ank_base::CancelWatchRequest) -> Self {
+        CancelWatchRequest {}
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+pub struct RolloutStatusRequest {}
+
+impl From<RolloutStatusRequest> for ank_base::RolloutStatusRequest {
+    fn from(_item: RolloutStatusRequest) -> Self {
+        ank_base::RolloutStatusRequest {}
+    }
+}
+
+impl From<ank_base::RolloutStatusRequest> for RolloutStatusRequest {
+    fn from(_item: ank_base::RolloutStatusRequest) -> Self {
+        RolloutStatusRequest {}
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+pub struct SupportInfoRequest {}
+
+impl From<SupportInfoRequest> for ank_base::SupportInfoRequest {
+    fn from(_item: SupportInfoRequest) -> Self {
+        ank_base::SupportInfoRequest {}
+    }
+}
+
+impl From<ank_base::SupportInfoRequest> for SupportInfoRequest {
+    fn from(_item: ank_base::SupportInfoRequest) -> Self {
+        SupportInfoRequest {}
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+pub struct EventsRequest {
+    pub since: u64,
+    pub limit: u32,
+}
+
+impl From<EventsRequest> for ank_base::EventsRequest {
+    fn from(item: EventsRequest) -> Self {
+        ank_base::EventsRequest {
+            since: item.since,
+            limit: item.limit,
+        }
+    }
+}
+
+impl From<ank_base::EventsRequest> for EventsRequest {
+    fn from(item: ank_base::EventsRequest) -> Self {
+        EventsRequest {
+            since: item.since,
+            limit: item.limit,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+pub struct ImpactAnalysisRequest {
+    pub workload_names: Vec<String>,
+}
+
+impl From<ImpactAnalysisRequest> for ank_base::ImpactAnalysisRequest {
+    fn from(item: ImpactAnalysisRequest) -> Self {
+        ank_base::ImpactAnalysisRequest {
+            workload_names: item.workload_names,
+        }
+    }
+}
+
+impl From<ank_base::ImpactAnalysisRequest> for ImpactAnalysisRequest {
+    fn from(item: ank_base::ImpactAnalysisRequest) -> Self {
+        ImpactAnalysisRequest {
+            workload_names: item.workload_names,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+pub struct DependencyGraphRequest {}
+
+impl From<DependencyGraphRequest> for ank_base::DependencyGraphRequest {
+    fn from(_item: DependencyGraphRequest) -> Self {
+        ank_base::DependencyGraphRequest {}
+    }
+}
+
+impl From<ank_base::DependencyGraphRequest> for DependencyGraphRequest {
+    fn from(_item: ank_base::DependencyGraphRequest) -> Self {
+        DependencyGraphRequest {}
+    }
+}
+
+// An empty agent name requests the pending operations of all agents.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+pub struct SchedulerQueueRequest {
+    pub agent_name: String,
+}
+
+impl From<SchedulerQueueRequest> for ank_base::SchedulerQueueRequest {
+    fn from(item: SchedulerQueueRequest) -> Self {
+        ank_base::SchedulerQueueRequest {
+            agent_name: item.agent_name,
+        }
+    }
+}
+
+impl From<ank_base::SchedulerQueueRequest> for SchedulerQueueRequest {
+    fn from(item: ank_base::SchedulerQueueRequest) -> Self {
+        SchedulerQueueRequest {
+            agent_name: item.agent_name,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+pub struct DrainAgentRequest {
+    pub agent_name: String,
+    pub target_agent: Option<String>,
+    pub remove: bool,
+}
+
+impl From<DrainAgentRequest> for ank_base::DrainAgentRequest {
+    fn from(item: DrainAgentRequest) -> Self {
+        ank_base::DrainAgentRequest {
+            agent_name: item.agent_name,
+            target_agent: item.target_agent.unwrap_or_default(),
+            remove: item.remove,
+        }
+    }
+}
+
+impl From<ank_base::DrainAgentRequest> for DrainAgentRequest {
+    fn from(item: ank_base::DrainAgentRequest) -> Self {
+        DrainAgentRequest {
+            agent_name: item.agent_name,
+            target_agent: Some(item.target_agent).filter(|agent| !agent.is_empty()),
+            remove: item.remove,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub struct UpdateStateRequest {
     pub state: CompleteState,
     pub update_mask: Vec<String>,
+    // [impl->swdd~server-propagates-update-deadline~1]
+    pub deadline_ms: Option<u64>,
+    pub rollback_on_deadline_exceeded: bool,
+    // [impl->swdd~server-protects-managed-workloads~1]
+    pub force: bool,
 }
 
 impl From<UpdateStateRequest> for ank_base::UpdateStateRequest {
@@ -132,6 +399,9 @@ impl From<UpdateStateRequest> for ank_base::UpdateStateRequest {
         Self {
             new_state: Some(value.state.into()),
             update_mask: value.update_mask,
+            deadline_ms: value.deadline_ms.unwrap_or_default(),
+            rollback_on_deadline_exceeded: value.rollback_on_deadline_exceeded,
+            force: value.force,
         }
     }
 }
@@ -143,20 +413,77 @@ impl TryFrom<ank_base::UpdateStateRequest> for UpdateStateRequest {
         Ok(UpdateStateRequest {
             state: item.new_state.unwrap_or_default().try_into()?,
             update_mask: item.update_mask,
+            deadline_ms: Some(item.deadline_ms).filter(|deadline_ms| *deadline_ms != 0),
+            rollback_on_deadline_exceeded: item.rollback_on_deadline_exceeded,
+            force: item.force,
         })
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct UpdateWorkload {
     pub added_workloads: Vec<WorkloadSpec>,
     pub deleted_workloads: Vec<DeletedWorkload>,
+    pub sequence_number: u64,
+    pub initial: bool,
+    // the time after which added workloads still waiting to start exceed the deadline
+    pub deadline_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct UpdateWorkloadAck {
+    pub agent_name: String,
+    pub sequence_number: u64,
+}
+
+// An event observed by an agent and recorded by the server.
+// The agent name is set by the server from the connection the event is received on.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct AgentEvent {
+    pub agent_name: String,
+    pub kind: EventKind,
+    pub workload_name: String,
+    pub message: String,
+}
+
+// The complete list of workload operations pending in the scheduler queue of an agent.
+// The agent name is set by the server from the connection the list is received on.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct UpdateSchedulerQueue {
+    pub agent_name: String,
+    pub pending_operations: Vec<PendingWorkloadOperation>,
+}
+
+// The names of the workloads an agent needs the execution states of, i.e., the dependencies of
+// its workloads. The subscription is kept by the communication middleware of the connection,
+// which forwards only the newly subscribed names to the server to get their current states.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct SubscribeWorkloadStates {
+    pub workload_names: Vec<String>,
+}
+
+// The control interface request metrics of a workload since the start of its control interface.
+// The agent name is set by the server from the connection the metrics are received on.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct UpdateControlInterfaceMetrics {
+    pub metrics: ControlInterfaceMetrics,
+}
+
+// The free resources of the host of an agent.
+// The agent name is set by the server from the connection the resources are received on.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct UpdateAgentResources {
+    pub agent_name: String,
+    pub resources: AgentResources,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub request_id: String,
+    // [impl->swdd~common-response-contains-trace-id~1]
+    #[serde(default)]
+    pub trace_id: String,
     pub response_content: ResponseContent,
 }
 
@@ -164,6 +491,7 @@ impl From<Response> for ank_base::Response {
     fn from(value: Response) -> Self {
         Self {
             request_id: value.request_id,
+            trace_id: value.trace_id,
             response_content: Some(value.response_content.into()),
         }
     }
@@ -175,6 +503,7 @@ impl TryFrom<ank_base::Response> for Response {
     fn try_from(value: ank_base::Response) -> Result<Self, Self::Error> {
         Ok(Self {
             request_id: value.request_id,
+            trace_id: value.trace_id,
             response_content: value
                 .response_content
                 .ok_or_else(|| "Response has no content".to_string())?
@@ -189,6 +518,12 @@ pub enum ResponseContent {
     Error(Error),
     CompleteState(Box<CompleteState>),
     UpdateStateSuccess(UpdateStateSuccess),
+    RolloutStatus(RolloutStatus),
+    SupportInfo(SupportInfo),
+    Events(Events),
+    ImpactAnalysis(ImpactAnalysis),
+    DependencyGraph(DependencyGraph),
+    SchedulerQueue(SchedulerQueue),
 }
 
 impl From<ResponseContent> for ank_base::response::ResponseContent {
@@ -203,6 +538,24 @@ impl From<ResponseContent> for ank_base::response::ResponseContent {
             ResponseContent::UpdateStateSuccess(update_state_success) => {
                 ank_base::response::ResponseContent::UpdateStateSuccess(update_state_success.into())
             }
+            ResponseContent::RolloutStatus(rollout_status) => {
+                ank_base::response::ResponseContent::RolloutStatus(rollout_status.into())
+            }
+            ResponseContent::SupportInfo(support_info) => {
+                ank_base::response::ResponseContent::SupportInfo(support_info.into())
+            }
+            ResponseContent::Events(events) => {
+                ank_base::response::ResponseContent::Events(events.into())
+            }
+            ResponseContent::ImpactAnalysis(impact_analysis) => {
+                ank_base::response::ResponseContent::ImpactAnalysis(impact_analysis.into())
+            }
+            ResponseContent::DependencyGraph(dependency_graph) => {
+                ank_base::response::ResponseContent::DependencyGraph(dependency_graph.into())
+            }
+            ResponseContent::SchedulerQueue(scheduler_queue) => {
+                ank_base::response::ResponseContent::SchedulerQueue(scheduler_queue.into())
+            }
         }
     }
 }
@@ -221,6 +574,24 @@ impl TryFrom<ank_base::response::ResponseContent> for ResponseContent {
             ank_base::response::ResponseContent::UpdateStateSuccess(update_state_success) => Ok(
                 ResponseContent::UpdateStateSuccess(update_state_success.into()),
             ),
+            ank_base::response::ResponseContent::RolloutStatus(rollout_status) => {
+                Ok(ResponseContent::RolloutStatus(rollout_status.try_into()?))
+            }
+            ank_base::response::ResponseContent::SupportInfo(support_info) => {
+                Ok(ResponseContent::SupportInfo(support_info.try_into()?))
+            }
+            ank_base::response::ResponseContent::Events(events) => {
+                Ok(ResponseContent::Events(events.try_into()?))
+            }
+            ank_base::response::ResponseContent::ImpactAnalysis(impact_analysis) => {
+                Ok(ResponseContent::ImpactAnalysis(impact_analysis.into()))
+            }
+            ank_base::response::ResponseContent::DependencyGraph(dependency_graph) => {
+                Ok(ResponseContent::DependencyGraph(dependency_graph.try_into()?))
+            }
+            ank_base::response::ResponseContent::SchedulerQueue(scheduler_queue) => {
+                Ok(ResponseContent::SchedulerQueue(scheduler_queue.try_into()?))
+            }
         }
     }
 }
@@ -252,6 +623,7 @@ impl From<Error> for ank_base::Error {
 pub struct UpdateStateSuccess {
     pub added_workloads: Vec<String>,
     pub deleted_workloads: Vec<String>,
+    pub rejected_workloads: Vec<RejectedWorkload>,
 }
 
 impl From<UpdateStateSuccess> for ank_base::UpdateStateSuccess {
@@ -259,6 +631,11 @@ impl From<UpdateStateSuccess> for ank_base::UpdateStateSuccess {
         Self {
             added_workloads: value.added_workloads,
             deleted_workloads: value.deleted_workloads,
+            rejected_workloads: value
+                .rejected_workloads
+                .into_iter()
+                .map(Into::into)
+                .collect(),
         }
     }
 }
@@ -268,16 +645,593 @@ impl From<ank_base::UpdateStateSuccess> for UpdateStateSuccess {
         Self {
             added_workloads: value.added_workloads,
             deleted_workloads: value.deleted_workloads,
+            rejected_workloads: value
+                .rejected_workloads
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+// A workload of an accepted update which is not started by its agent.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RejectedWorkload {
+    pub workload_name: String,
+    pub reason: String,
+}
+
+impl From<RejectedWorkload> for ank_base::RejectedWorkload {
+    fn from(value: RejectedWorkload) -> Self {
+        Self {
+            workload_name: value.workload_name,
+            reason: value.reason,
+        }
+    }
+}
+
+impl From<ank_base::RejectedWorkload> for RejectedWorkload {
+    fn from(value: ank_base::RejectedWorkload) -> Self {
+        Self {
+            workload_name: value.workload_name,
+            reason: value.reason,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RolloutState {
+    #[default]
+    NotStarted = 0,
+    InProgress = 1,
+    Completed = 2,
+    Halted = 3,
+}
+
+impl TryFrom<i32> for RolloutState {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == RolloutState::NotStarted as i32 => Ok(RolloutState::NotStarted),
+            x if x == RolloutState::InProgress as i32 => Ok(RolloutState::InProgress),
+            x if x == RolloutState::Completed as i32 => Ok(RolloutState::Completed),
+            x if x == RolloutState::Halted as i32 => Ok(RolloutState::Halted),
+            _ => Err(format!(
+                "Received an unknown value '{value}' as RolloutState."
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for RolloutState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RolloutState::NotStarted => write!(f, "NotStarted"),
+            RolloutState::InProgress => write!(f, "InProgress"),
+            RolloutState::Completed => write!(f, "Completed"),
+            RolloutState::Halted => write!(f, "Halted"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RolloutGroupStatus {
+    pub name: String,
+    pub state: RolloutState,
+    pub agents: Vec<String>,
+    pub workloads: u32,
+    pub failed_workloads: u32,
+}
+
+impl From<RolloutGroupStatus> for ank_base::RolloutGroupStatus {
+    fn from(value: RolloutGroupStatus) -> Self {
+        Self {
+            name: value.name,
+            state: value.state as i32,
+            agents: value.agents,
+            workloads: value.workloads,
+            failed_workloads: value.failed_workloads,
         }
     }
 }
 
+impl TryFrom<ank_base::RolloutGroupStatus> for RolloutGroupStatus {
+    type Error = String;
+
+    fn try_from(value: ank_base::RolloutGroupStatus) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: value.name,
+            state: value.state.try_into()?,
+            agents: value.agents,
+            workloads: value.workloads,
+            failed_workloads: value.failed_workloads,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RolloutStatus {
+    pub state: RolloutState,
+    pub groups: Vec<RolloutGroupStatus>,
+    pub message: String,
+}
+
+impl From<RolloutStatus> for ank_base::RolloutStatus {
+    fn from(value: RolloutStatus) -> Self {
+        Self {
+            state: value.state as i32,
+            groups: value.groups.into_iter().map(|x| x.into()).collect(),
+            message: value.message,
+        }
+    }
+}
+
+impl TryFrom<ank_base::RolloutStatus> for RolloutStatus {
+    type Error = String;
+
+    fn try_from(value: ank_base::RolloutStatus) -> Result<Self, Self::Error> {
+        Ok(Self {
+            state: value.state.try_into()?,
+            groups: value
+                .groups
+                .into_iter()
+                .map(|x| x.try_into())
+                .collect::<Result<Vec<RolloutGroupStatus>, String>>()?,
+            message: value.message,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SupportInfo {
+    pub server_version: String,
+    pub agents: Vec<AgentInfo>,
+}
+
+impl From<SupportInfo> for ank_base::SupportInfo {
+    fn from(value: SupportInfo) -> Self {
+        Self {
+            server_version: value.server_version,
+            agents: value.agents.into_iter().map(|x| x.into()).collect(),
+        }
+    }
+}
+
+impl TryFrom<ank_base::SupportInfo> for SupportInfo {
+    type Error = String;
+
+    fn try_from(value: ank_base::SupportInfo) -> Result<Self, Self::Error> {
+        Ok(Self {
+            server_version: value.server_version,
+            agents: value
+                .agents
+                .into_iter()
+                .map(|x| x.try_into())
+                .collect::<Result<Vec<AgentInfo>, String>>()?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EventKind {
+    #[default]
+    AgentConnected = 0,
+    AgentDisconnected = 1,
+    DesiredStateUpdated = 2,
+    WorkloadStateChanged = 3,
+    AgentRemoved = 4,
+    WorkloadStateStale = 5,
+    UpdateDeadlineExceeded = 6,
+    ControlInterfaceClosed = 7,
+    AgentIncident = 8,
+    SlowStart = 9,
+}
+
+impl TryFrom<i32> for EventKind {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == EventKind::AgentConnected as i32 => Ok(EventKind::AgentConnected),
+            x if x == EventKind::AgentDisconnected as i32 => Ok(EventKind::AgentDisconnected),
+            x if x == EventKind::DesiredStateUpdated as i32 => Ok(EventKind::DesiredStateUpdated),
+            x if x == EventKind::WorkloadStateChanged as i32 => Ok(EventKind::WorkloadStateChanged),
+            x if x == EventKind::AgentRemoved as i32 => Ok(EventKind::AgentRemoved),
+            x if x == EventKind::WorkloadStateStale as i32 => Ok(EventKind::WorkloadStateStale),
+            x if x == EventKind::UpdateDeadlineExceeded as i32 => {
+                Ok(EventKind::UpdateDeadlineExceeded)
+            }
+            x if x == EventKind::ControlInterfaceClosed as i32 => {
+                Ok(EventKind::ControlInterfaceClosed)
+            }
+            x if x == EventKind::AgentIncident as i32 => Ok(EventKind::AgentIncident),
+            x if x == EventKind::SlowStart as i32 => Ok(EventKind::SlowStart),
+            _ => Err(format!("Received an unknown value '{value}' as EventKind.")),
+        }
+    }
+}
+
+impl std::fmt::Display for EventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EventKind::AgentConnected => write!(f, "AgentConnected"),
+            EventKind::AgentDisconnected => write!(f, "AgentDisconnected"),
+            EventKind::DesiredStateUpdated => write!(f, "DesiredStateUpdated"),
+            EventKind::WorkloadStateChanged => write!(f, "WorkloadStateChanged"),
+            EventKind::AgentRemoved => write!(f, "AgentRemoved"),
+            EventKind::WorkloadStateStale => write!(f, "WorkloadStateStale"),
+            EventKind::UpdateDeadlineExceeded => write!(f, "UpdateDeadlineExceeded"),
+            EventKind::ControlInterfaceClosed => write!(f, "ControlInterfaceClosed"),
+            EventKind::AgentIncident => write!(f, "AgentIncident"),
+            EventKind::SlowStart => write!(f, "SlowStart"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Event {
+    pub timestamp: u64,
+    pub kind: EventKind,
+    pub agent_name: Option<String>,
+    pub workload_name: Option<String>,
+    pub message: String,
+    pub trace_id: Option<String>,
+    // [impl->swdd~common-workload-state-timestamps~1]
+    pub agent_timestamp: Option<u64>,
+}
+
+impl From<Event> for ank_base::Event {
+    fn from(value: Event) -> Self {
+        Self {
+            timestamp: value.timestamp,
+            kind: value.kind as i32,
+            agent_name: value.agent_name.unwrap_or_default(),
+            workload_name: value.workload_name.unwrap_or_default(),
+            message: value.message,
+            trace_id: value.trace_id.unwrap_or_default(),
+            agent_timestamp: value.agent_timestamp.unwrap_or_default(),
+        }
+    }
+}
+
+impl TryFrom<ank_base::Event> for Event {
+    type Error = String;
+
+    fn try_from(value: ank_base::Event) -> Result<Self, Self::Error> {
+        Ok(Self {
+            timestamp: value.timestamp,
+            kind: value.kind.try_into()?,
+            agent_name: Some(value.agent_name).filter(|name| !name.is_empty()),
+            workload_name: Some(value.workload_name).filter(|name| !name.is_empty()),
+            message: value.message,
+            trace_id: Some(value.trace_id).filter(|id| !id.is_empty()),
+            agent_timestamp: Some(value.agent_timestamp).filter(|timestamp| *timestamp != 0),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Events {
+    pub events: Vec<Event>,
+}
+
+impl From<Events> for ank_base::Events {
+    fn from(value: Events) -> Self {
+        Self {
+            events: value.events.into_iter().map(|x| x.into()).collect(),
+        }
+    }
+}
+
+impl TryFrom<ank_base::Events> for Events {
+    type Error = String;
+
+    fn try_from(value: ank_base::Events) -> Result<Self, Self::Error> {
+        Ok(Self {
+            events: value
+                .events
+                .into_iter()
+                .map(|x| x.try_into())
+                .collect::<Result<Vec<Event>, String>>()?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ImpactedWorkload {
+    pub workload_name: String,
+    pub agent: String,
+    pub depends_on: Vec<String>,
+}
+
+impl From<ImpactedWorkload> for ank_base::ImpactedWorkload {
+    fn from(value: ImpactedWorkload) -> Self {
+        Self {
+            workload_name: value.workload_name,
+            agent: value.agent,
+            depends_on: value.depends_on,
+        }
+    }
+}
+
+impl From<ank_base::ImpactedWorkload> for ImpactedWorkload {
+    fn from(value: ank_base::ImpactedWorkload) -> Self {
+        Self {
+            workload_name: value.workload_name,
+            agent: value.agent,
+            depends_on: value.depends_on,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ImpactAnalysis {
+    pub impacted_workloads: Vec<ImpactedWorkload>,
+}
+
+impl From<ImpactAnalysis> for ank_base::ImpactAnalysis {
+    fn from(value: ImpactAnalysis) -> Self {
+        Self {
+            impacted_workloads: value
+                .impacted_workloads
+                .into_iter()
+                .map(|x| x.into())
+                .collect(),
+        }
+    }
+}
+
+impl From<ank_base::ImpactAnalysis> for ImpactAnalysis {
+    fn from(value: ank_base::ImpactAnalysis) -> Self {
+        Self {
+            impacted_workloads: value
+                .impacted_workloads
+                .into_iter()
+                .map(|x| x.into())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DependencyGraphNode {
+    pub workload_name: String,
+    pub agent: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_state: Option<ExecutionState>,
+}
+
+impl From<DependencyGraphNode> for ank_base::DependencyGraphNode {
+    fn from(value: DependencyGraphNode) -> Self {
+        Self {
+            workload_name: value.workload_name,
+            agent: value.agent,
+            execution_state: value.execution_state.map(|x| x.into()),
+        }
+    }
+}
+
+impl From<ank_base::DependencyGraphNode> for DependencyGraphNode {
+    fn from(value: ank_base::DependencyGraphNode) -> Self {
+        Self {
+            workload_name: value.workload_name,
+            agent: value.agent,
+            execution_state: value.execution_state.map(|x| x.into()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraphEdge {
+    pub workload_name: String,
+    pub dependency: String,
+    pub condition: AddCondition,
+}
+
+impl From<DependencyGraphEdge> for ank_base::DependencyGraphEdge {
+    fn from(value: DependencyGraphEdge) -> Self {
+        Self {
+            workload_name: value.workload_name,
+            dependency: value.dependency,
+            condition: value.condition as i32,
+        }
+    }
+}
+
+impl TryFrom<ank_base::DependencyGraphEdge> for DependencyGraphEdge {
+    type Error = String;
+
+    fn try_from(value: ank_base::DependencyGraphEdge) -> Result<Self, Self::Error> {
+        Ok(Self {
+            workload_name: value.workload_name,
+            dependency: value.dependency,
+            condition: value.condition.try_into()?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DependencyGraph {
+    pub nodes: Vec<DependencyGraphNode>,
+    pub edges: Vec<DependencyGraphEdge>,
+}
+
+impl From<DependencyGraph> for ank_base::DependencyGraph {
+    fn from(value: DependencyGraph) -> Self {
+        Self {
+            nodes: value.nodes.into_iter().map(|x| x.into()).collect(),
+            edges: value.edges.into_iter().map(|x| x.into()).collect(),
+        }
+    }
+}
+
+impl TryFrom<ank_base::DependencyGraph> for DependencyGraph {
+    type Error = String;
+
+    fn try_from(value: ank_base::DependencyGraph) -> Result<Self, Self::Error> {
+        Ok(Self {
+            nodes: value.nodes.into_iter().map(|x| x.into()).collect(),
+            edges: value
+                .edges
+                .into_iter()
+                .map(|x| x.try_into())
+                .collect::<Result<Vec<DependencyGraphEdge>, String>>()?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PendingOperation {
+    Create = 0,
+    Update = 1,
+    Delete = 2,
+}
+
+impl TryFrom<i32> for PendingOperation {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == PendingOperation::Create as i32 => Ok(PendingOperation::Create),
+            x if x == PendingOperation::Update as i32 => Ok(PendingOperation::Update),
+            x if x == PendingOperation::Delete as i32 => Ok(PendingOperation::Delete),
+            _ => Err(format!(
+                "Received an unknown value '{value}' as PendingOperation."
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for PendingOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PendingOperation::Create => write!(f, "Create"),
+            PendingOperation::Update => write!(f, "Update"),
+            PendingOperation::Delete => write!(f, "Delete"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnfulfilledDependency {
+    pub workload_name: String,
+    pub condition: String,
+}
+
+impl From<UnfulfilledDependency> for ank_base::UnfulfilledDependency {
+    fn from(value: UnfulfilledDependency) -> Self {
+        Self {
+            workload_name: value.workload_name,
+            condition: value.condition,
+        }
+    }
+}
+
+impl From<ank_base::UnfulfilledDependency> for UnfulfilledDependency {
+    fn from(value: ank_base::UnfulfilledDependency) -> Self {
+        Self {
+            workload_name: value.workload_name,
+            condition: value.condition,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingWorkloadOperation {
+    pub workload_name: String,
+    pub agent: String,
+    pub operation: PendingOperation,
+    pub unfulfilled_dependencies: Vec<UnfulfilledDependency>,
+}
+
+impl From<PendingWorkloadOperation> for ank_base::PendingWorkloadOperation {
+    fn from(value: PendingWorkloadOperation) -> Self {
+        Self {
+            workload_name: value.workload_name,
+            agent: value.agent,
+            operation: value.operation as i32,
+            unfulfilled_dependencies: value
+                .unfulfilled_dependencies
+                .into_iter()
+                .map(|x| x.into())
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<ank_base::PendingWorkloadOperation> for PendingWorkloadOperation {
+    type Error = String;
+
+    fn try_from(value: ank_base::PendingWorkloadOperation) -> Result<Self, Self::Error> {
+        Ok(Self {
+            workload_name: value.workload_name,
+            agent: value.agent,
+            operation: value.operation.try_into()?,
+            unfulfilled_dependencies: value
+                .unfulfilled_dependencies
+                .into_iter()
+                .map(|x| x.into())
+                .collect(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SchedulerQueue {
+    pub pending_operations: Vec<PendingWorkloadOperation>,
+}
+
+impl From<SchedulerQueue> for ank_base::SchedulerQueue {
+    fn from(value: SchedulerQueue) -> Self {
+        Self {
+            pending_operations: value
+                .pending_operations
+                .into_iter()
+                .map(|x| x.into())
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<ank_base::SchedulerQueue> for SchedulerQueue {
+    type Error = String;
+
+    fn try_from(value: ank_base::SchedulerQueue) -> Result<Self, Self::Error> {
+        Ok(Self {
+            pending_operations: value
+                .pending_operations
+                .into_iter()
+                .map(|x| x.try_into())
+                .collect::<Result<Vec<PendingWorkloadOperation>, String>>()?,
+        })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Goodbye {}
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Stop {}
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ServerGone {}
+
 //////////////////////////////////////////////////////////////////////////////
 //                 ########  #######    #########  #########                //
 //                    ##     ##        ##             ##                    //
@@ -294,16 +1248,16 @@ mod tests {
         pub use api::ank_base::{
             execution_state::ExecutionStateEnum, request::RequestContent,
             response::ResponseContent, CompleteState, CompleteStateRequest, Error, ExecutionState,
-            Request, Response, Running, State, UpdateStateRequest, UpdateStateSuccess, Workload,
-            WorkloadInstanceName, WorkloadState,
+            RejectedWorkload, Request, Response, Running, State, UpdateStateRequest,
+            UpdateStateSuccess, Workload, WorkloadInstanceName, WorkloadState,
         };
     }
 
     mod ankaios {
         pub use crate::{
             commands::{
-                CompleteStateRequest, Error, Request, RequestContent, Response, ResponseContent,
-                UpdateStateRequest, UpdateStateSuccess,
+                CompleteStateRequest, Error, RejectedWorkload, Request, RequestContent, Response,
+                ResponseContent, UpdateStateRequest, UpdateStateSuccess,
             },
             objects::{
                 CompleteState, ExecutionState, State, StoredWorkloadSpec, WorkloadInstanceName,
@@ -313,6 +1267,7 @@ mod tests {
     }
 
     const REQUEST_ID: &str = "request_id";
+    const TRACE_ID: &str = "trace_id";
     const FIELD_1: &str = "field_1";
     const FIELD_2: &str = "field_2";
     const AGENT_NAME: &str = "agent_1";
@@ -350,12 +1305,18 @@ mod tests {
             ank_base::RequestContent::UpdateStateRequest(ank_base::UpdateStateRequest {
                 new_state: complete_state!(ank_base).into(),
                 update_mask: vec![FIELD_1.into(), FIELD_2.into()],
+                deadline_ms: 0,
+                rollback_on_deadline_exceeded: false,
+                force: false,
             })
         };
         (ankaios) => {
             ankaios::RequestContent::UpdateStateRequest(Box::new(ankaios::UpdateStateRequest {
                 state: complete_state!(ankaios),
                 update_mask: vec![FIELD_1.into(), FIELD_2.into()],
+                deadline_ms: None,
+                rollback_on_deadline_exceeded: false,
+                force: false,
             }))
         };
     }
@@ -364,6 +1325,7 @@ mod tests {
         ($expression:ident) => {{
             $expression::Response {
                 request_id: REQUEST_ID.into(),
+                trace_id: TRACE_ID.into(),
                 response_content: $expression::ResponseContent::Error($expression::Error {
                     message: ERROR_MESSAGE.into(),
                 })
@@ -376,6 +1338,7 @@ mod tests {
         ($expression:ident) => {{
             $expression::Response {
                 request_id: REQUEST_ID.into(),
+                trace_id: TRACE_ID.into(),
                 response_content: $expression::ResponseContent::CompleteState(
                     complete_state!($expression).into(),
                 )
@@ -392,6 +1355,12 @@ mod tests {
                     workloads: vec![("startup".into(), workload!($expression))]
                         .into_iter()
                         .collect(),
+                    configs: Default::default(),
+                    workload_templates: Default::default(),
+                    modes: Default::default(),
+                    active_mode: Default::default(),
+                    active_profile: Default::default(),
+                    workload_groups: Default::default(),
                 }
                 .into(),
                 desired_state: $expression::State {
@@ -399,9 +1368,16 @@ mod tests {
                     workloads: vec![("desired".into(), workload!($expression))]
                         .into_iter()
                         .collect(),
+                    configs: Default::default(),
+                    workload_templates: Default::default(),
+                    modes: Default::default(),
+                    active_mode: Default::default(),
+                    active_profile: Default::default(),
+                    workload_groups: Default::default(),
                 }
                 .into(),
                 workload_states: vec![workload_state!($expression)],
+                ..Default::default()
             }
         };
     }
@@ -435,6 +1411,8 @@ mod tests {
                     .agent_name(AGENT_NAME)
                     .build(),
                 execution_state: ankaios::ExecutionState::running(),
+                agent_timestamp: None,
+                server_timestamp: None,
             }
         }};
         (ank_base) => {
@@ -453,6 +1431,8 @@ mod tests {
                     ..Default::default()
                 }
                 .into(),
+                agent_timestamp: 0,
+                server_timestamp: 0,
             }
         };
     }
@@ -461,10 +1441,15 @@ mod tests {
         ($expression:ident) => {{
             $expression::Response {
                 request_id: REQUEST_ID.into(),
+                trace_id: TRACE_ID.into(),
                 response_content: $expression::ResponseContent::UpdateStateSuccess(
                     $expression::UpdateStateSuccess {
                         added_workloads: vec![WORKLOAD_NAME_1.into()],
                         deleted_workloads: vec![WORKLOAD_NAME_2.into(), WORKLOAD_NAME_3.into()],
+                        rejected_workloads: vec![$expression::RejectedWorkload {
+                            workload_name: WORKLOAD_NAME_1.into(),
+                            reason: "not enabled".into(),
+                        }],
                     },
                 )
                 .into(),
@@ -805,10 +1790,244 @@ mod tests {
         );
     }
 
+    #[test]
+    fn utest_converts_from_proto_support_info_maps_empty_rollout_group_to_none() {
+        let proto_support_info = api::ank_base::SupportInfo {
+            server_version: "0.4.0".into(),
+            agents: vec![
+                api::ank_base::AgentInfo {
+                    agent_name: AGENT_NAME.into(),
+                    version: "0.4.0".into(),
+                    rollout_group: "".into(),
+                    ..Default::default()
+                },
+                api::ank_base::AgentInfo {
+                    agent_name: "agent_2".into(),
+                    version: "0.3.1".into(),
+                    rollout_group: "canary".into(),
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let support_info = super::SupportInfo::try_from(proto_support_info.clone()).unwrap();
+
+        assert_eq!(support_info.agents[0].rollout_group, None);
+        assert_eq!(
+            support_info.agents[1].rollout_group,
+            Some("canary".to_string())
+        );
+        assert_eq!(
+            api::ank_base::SupportInfo::from(support_info),
+            proto_support_info
+        );
+    }
+
+    #[test]
+    fn utest_converts_from_proto_drain_agent_request_maps_empty_target_agent_to_none() {
+        let proto_drain_agent_request = api::ank_base::DrainAgentRequest {
+            agent_name: AGENT_NAME.into(),
+            target_agent: "".into(),
+            remove: true,
+        };
+
+        let drain_agent_request = super::DrainAgentRequest::from(proto_drain_agent_request.clone());
+
+        assert_eq!(
+            drain_agent_request,
+            super::DrainAgentRequest {
+                agent_name: AGENT_NAME.into(),
+                target_agent: None,
+                remove: true,
+            }
+        );
+        assert_eq!(
+            api::ank_base::DrainAgentRequest::from(drain_agent_request),
+            proto_drain_agent_request
+        );
+    }
+
+    #[test]
+    fn utest_converts_watch_requests_to_and_from_proto() {
+        let watch_request = super::Request {
+            request_id: REQUEST_ID.into(),
+            request_content: super::RequestContent::WatchCompleteStateRequest(
+                super::WatchCompleteStateRequest {
+                    field_mask: vec![FIELD_1.into()],
+                },
+            ),
+        };
+        let cancel_request = super::Request {
+            request_id: REQUEST_ID.into(),
+            request_content: super::RequestContent::CancelWatchRequest(
+                super::CancelWatchRequest {},
+            ),
+        };
+
+        let proto_watch_request = api::ank_base::Request::from(watch_request.clone());
+        assert_eq!(
+            proto_watch_request.request_content,
+            Some(ank_base::RequestContent::WatchCompleteStateRequest(
+                api::ank_base::WatchCompleteStateRequest {
+                    field_mask: vec![FIELD_1.into()],
+                }
+            ))
+        );
+        assert_eq!(
+            super::Request::try_from(proto_watch_request).unwrap(),
+            watch_request
+        );
+        assert_eq!(
+            super::Request::try_from(api::ank_base::Request::from(cancel_request.clone())).unwrap(),
+            cancel_request
+        );
+    }
+
+    #[test]
+    fn utest_converts_impact_analysis_to_and_from_proto() {
+        let request = super::Request {
+            request_id: REQUEST_ID.into(),
+            request_content: super::RequestContent::ImpactAnalysisRequest(
+                super::ImpactAnalysisRequest {
+                    workload_names: vec![WORKLOAD_NAME_1.into()],
+                },
+            ),
+        };
+        let impact_analysis = super::ImpactAnalysis {
+            impacted_workloads: vec![super::ImpactedWorkload {
+                workload_name: WORKLOAD_NAME_2.into(),
+                agent: AGENT_NAME.into(),
+                depends_on: vec![WORKLOAD_NAME_1.into()],
+            }],
+        };
+
+        assert_eq!(
+            super::Request::try_from(api::ank_base::Request::from(request.clone())).unwrap(),
+            request
+        );
+        assert_eq!(
+            super::ImpactAnalysis::from(api::ank_base::ImpactAnalysis::from(
+                impact_analysis.clone()
+            )),
+            impact_analysis
+        );
+    }
+
+    #[test]
+    fn utest_converts_dependency_graph_to_and_from_proto() {
+        let dependency_graph = super::DependencyGraph {
+            nodes: vec![
+                super::DependencyGraphNode {
+                    workload_name: WORKLOAD_NAME_1.into(),
+                    agent: AGENT_NAME.into(),
+                    execution_state: Some(ankaios::ExecutionState::running()),
+                },
+                super::DependencyGraphNode {
+                    workload_name: WORKLOAD_NAME_2.into(),
+                    agent: AGENT_NAME.into(),
+                    execution_state: None,
+                },
+            ],
+            edges: vec![super::DependencyGraphEdge {
+                workload_name: WORKLOAD_NAME_2.into(),
+                dependency: WORKLOAD_NAME_1.into(),
+                condition: crate::objects::AddCondition::AddCondRunning,
+            }],
+        };
+
+        assert_eq!(
+            super::DependencyGraph::try_from(api::ank_base::DependencyGraph::from(
+                dependency_graph.clone()
+            )),
+            Ok(dependency_graph)
+        );
+        assert!(super::DependencyGraph::try_from(api::ank_base::DependencyGraph {
+            edges: vec![api::ank_base::DependencyGraphEdge {
+                condition: 42,
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn utest_converts_scheduler_queue_to_and_from_proto() {
+        let scheduler_queue = super::SchedulerQueue {
+            pending_operations: vec![super::PendingWorkloadOperation {
+                workload_name: WORKLOAD_NAME_2.into(),
+                agent: AGENT_NAME.into(),
+                operation: super::PendingOperation::Create,
+                unfulfilled_dependencies: vec![super::UnfulfilledDependency {
+                    workload_name: WORKLOAD_NAME_1.into(),
+                    condition: "ADD_COND_RUNNING".into(),
+                }],
+            }],
+        };
+
+        assert_eq!(
+            super::SchedulerQueue::try_from(api::ank_base::SchedulerQueue::from(
+                scheduler_queue.clone()
+            )),
+            Ok(scheduler_queue)
+        );
+        assert!(super::SchedulerQueue::try_from(api::ank_base::SchedulerQueue {
+            pending_operations: vec![api::ank_base::PendingWorkloadOperation {
+                operation: 42,
+                ..Default::default()
+            }],
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn utest_converts_from_proto_events_fails_on_unknown_event_kind() {
+        let proto_events = api::ank_base::Events {
+            events: vec![api::ank_base::Event {
+                timestamp: 1,
+                kind: 42,
+                ..Default::default()
+            }],
+        };
+
+        assert_eq!(
+            super::Events::try_from(proto_events).unwrap_err(),
+            "Received an unknown value '42' as EventKind."
+        );
+    }
+
+    // [utest->swdd~common-response-contains-trace-id~1]
+    #[test]
+    fn utest_converts_event_trace_id_to_and_from_proto() {
+        let event = super::Event {
+            timestamp: 1,
+            kind: super::EventKind::DesiredStateUpdated,
+            message: "message".into(),
+            trace_id: Some(TRACE_ID.into()),
+            ..Default::default()
+        };
+
+        let proto_event = api::ank_base::Event::from(event.clone());
+        assert_eq!(proto_event.trace_id, TRACE_ID);
+        assert_eq!(super::Event::try_from(proto_event), Ok(event));
+
+        let proto_event_without_trace_id = api::ank_base::Event {
+            timestamp: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            super::Event::try_from(proto_event_without_trace_id)
+                .unwrap()
+                .trace_id,
+            None
+        );
+    }
+
     #[test]
     fn utest_converts_from_proto_reponse_fails_empty_request_content() {
         let proto_response = ank_base::Response {
             request_id: REQUEST_ID.into(),
+            trace_id: TRACE_ID.into(),
             response_content: None,
         };
 
@@ -0,0 +1,504 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Deserialize;
+
+use crate::{
+    kube_conversion::{PodmanConfig, PODMAN_RUNTIME_NAME},
+    objects::{AddCondition, RestartPolicy, State, StoredWorkloadSpec},
+};
+
+#[derive(Debug, Deserialize)]
+struct ComposeFile {
+    #[serde(default)]
+    services: BTreeMap<String, Service>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Service {
+    image: Option<String>,
+    build: Option<serde_yaml::Value>,
+    command: Option<StringOrList>,
+    entrypoint: Option<StringOrList>,
+    environment: Option<Environment>,
+    #[serde(default)]
+    ports: Vec<serde_yaml::Value>,
+    #[serde(default)]
+    volumes: Vec<serde_yaml::Value>,
+    networks: Option<Networks>,
+    network_mode: Option<String>,
+    depends_on: Option<DependsOn>,
+    restart: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StringOrList {
+    String(String),
+    List(Vec<String>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Environment {
+    Map(BTreeMap<String, Option<serde_yaml::Value>>),
+    List(Vec<String>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Networks {
+    List(Vec<String>),
+    Map(BTreeMap<String, Option<serde_yaml::Value>>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DependsOn {
+    List(Vec<String>),
+    Map(BTreeMap<String, DependsOnCondition>),
+}
+
+#[derive(Debug, Deserialize)]
+struct DependsOnCondition {
+    condition: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LongPort {
+    target: u16,
+    published: Option<serde_yaml::Value>,
+    protocol: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LongVolume {
+    source: Option<String>,
+    target: String,
+    #[serde(default)]
+    read_only: bool,
+}
+
+// [impl->swdd~common-converts-compose-files~1]
+/// Converts the services of a Docker Compose file into an Ankaios state.
+///
+/// Each service results in one workload for the `podman` runtime named after the service and assigned to the given agent.
+/// The `depends_on` entries of a service become the dependencies of the workload.
+pub fn convert_compose_file(compose_file: &str, agent_name: &str) -> Result<State, String> {
+    let compose_file: ComposeFile = serde_yaml::from_str(compose_file)
+        .map_err(|err| format!("Could not parse Compose file: '{err}'"))?;
+
+    let mut state = State::default();
+    for (service_name, service) in compose_file.services {
+        let workload = convert_service(&service_name, service)?;
+        state.workloads.insert(
+            service_name,
+            StoredWorkloadSpec {
+                agent: agent_name.to_owned(),
+                ..workload
+            },
+        );
+    }
+
+    for (workload_name, workload) in &state.workloads {
+        if let Some(unknown) = workload
+            .dependencies
+            .keys()
+            .find(|dependency| !state.workloads.contains_key(*dependency))
+        {
+            return Err(format!(
+                "Service '{workload_name}' depends on the unknown service '{unknown}'."
+            ));
+        }
+    }
+
+    Ok(state)
+}
+
+fn convert_service(service_name: &str, service: Service) -> Result<StoredWorkloadSpec, String> {
+    if service.build.is_some() {
+        return Err(format!(
+            "Service '{service_name}' uses 'build', which can not be converted. Use a prebuilt 'image' instead."
+        ));
+    }
+    let image = service
+        .image
+        .ok_or_else(|| format!("Service '{service_name}' does not specify an image."))?;
+
+    let mut command_options = Vec::new();
+    let mut command_args = Vec::new();
+
+    // as for Docker, setting the entrypoint resets the command of the image
+    if let Some(entrypoint) = service.entrypoint {
+        let mut entrypoint = into_args(entrypoint).into_iter();
+        if let Some(executable) = entrypoint.next() {
+            command_options.push(format!("--entrypoint={executable}"));
+            command_args.extend(entrypoint);
+        }
+    }
+    if let Some(command) = service.command {
+        command_args.extend(into_args(command));
+    }
+
+    match service.environment {
+        Some(Environment::Map(variables)) => {
+            for (name, value) in variables {
+                command_options.push("-e".to_owned());
+                command_options.push(match value {
+                    Some(value) => format!("{name}={}", scalar_to_string(service_name, value)?),
+                    // without a value, the variable is taken over from the host
+                    None => name,
+                });
+            }
+        }
+        Some(Environment::List(variables)) => {
+            for variable in variables {
+                command_options.push("-e".to_owned());
+                command_options.push(variable);
+            }
+        }
+        None => {}
+    }
+
+    for port in service.ports {
+        command_options.push("-p".to_owned());
+        command_options.push(convert_port(service_name, port)?);
+    }
+
+    for volume in service.volumes {
+        command_options.push("-v".to_owned());
+        command_options.push(convert_volume(service_name, volume)?);
+    }
+
+    if let Some(network_mode) = service.network_mode {
+        command_options.push(format!("--network={network_mode}"));
+    }
+    let networks = match service.networks {
+        Some(Networks::List(networks)) => networks,
+        Some(Networks::Map(networks)) => networks.into_keys().collect(),
+        None => Vec::new(),
+    };
+    for network in networks {
+        command_options.push(format!("--network={network}"));
+    }
+
+    let dependencies: HashMap<String, AddCondition> = match service.depends_on {
+        Some(DependsOn::List(services)) => services
+            .into_iter()
+            .map(|service| (service, AddCondition::AddCondRunning))
+            .collect(),
+        Some(DependsOn::Map(services)) => services
+            .into_iter()
+            .map(|(service, condition)| {
+                convert_depends_on_condition(service_name, condition.condition)
+                    .map(|condition| (service, condition))
+            })
+            .collect::<Result<_, _>>()?,
+        None => Default::default(),
+    };
+
+    let runtime_config = serde_yaml::to_string(&PodmanConfig {
+        command_options,
+        image,
+        command_args,
+    })
+    .map_err(|err| format!("Could not create runtime config of '{service_name}': '{err}'"))?;
+
+    Ok(StoredWorkloadSpec {
+        dependencies,
+        restart_policy: convert_restart(service_name, service.restart)?,
+        runtime: PODMAN_RUNTIME_NAME.to_owned(),
+        runtime_config,
+        ..Default::default()
+    })
+}
+
+fn into_args(value: StringOrList) -> Vec<String> {
+    match value {
+        StringOrList::String(command) => split_command(&command),
+        StringOrList::List(args) => args,
+    }
+}
+
+// Compose splits commands given as string like a shell, supporting single and double quotes
+fn split_command(command: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote = None;
+
+    for character in command.chars() {
+        match (quote, character) {
+            (Some(open), _) if open == character => quote = None,
+            (Some(_), _) => current.get_or_insert_with(String::new).push(character),
+            (None, '\'' | '"') => {
+                quote = Some(character);
+                current.get_or_insert_with(String::new);
+            }
+            (None, _) if character.is_whitespace() => args.extend(current.take()),
+            (None, _) => current.get_or_insert_with(String::new).push(character),
+        }
+    }
+    args.extend(current);
+    args
+}
+
+fn scalar_to_string(service_name: &str, value: serde_yaml::Value) -> Result<String, String> {
+    match value {
+        serde_yaml::Value::String(value) => Ok(value),
+        serde_yaml::Value::Number(value) => Ok(value.to_string()),
+        serde_yaml::Value::Bool(value) => Ok(value.to_string()),
+        value => Err(format!(
+            "Service '{service_name}' contains the unsupported value '{value:?}'."
+        )),
+    }
+}
+
+fn convert_port(service_name: &str, port: serde_yaml::Value) -> Result<String, String> {
+    if port.is_mapping() {
+        let port: LongPort = serde_yaml::from_value(port)
+            .map_err(|err| format!("Could not parse port of service '{service_name}': '{err}'"))?;
+        let mut converted = match port.published {
+            Some(published) => format!(
+                "{}:{}",
+                scalar_to_string(service_name, published)?,
+                port.target
+            ),
+            None => port.target.to_string(),
+        };
+        if let Some(protocol) = port.protocol {
+            converted = format!("{converted}/{protocol}");
+        }
+        Ok(converted)
+    } else {
+        scalar_to_string(service_name, port)
+    }
+}
+
+fn convert_volume(service_name: &str, volume: serde_yaml::Value) -> Result<String, String> {
+    if volume.is_mapping() {
+        let volume: LongVolume = serde_yaml::from_value(volume).map_err(|err| {
+            format!("Could not parse volume of service '{service_name}': '{err}'")
+        })?;
+        let mut converted = match volume.source {
+            Some(source) => format!("{source}:{}", volume.target),
+            None => volume.target,
+        };
+        if volume.read_only {
+            converted.push_str(":ro");
+        }
+        Ok(converted)
+    } else {
+        scalar_to_string(service_name, volume)
+    }
+}
+
+fn convert_depends_on_condition(
+    service_name: &str,
+    condition: Option<String>,
+) -> Result<AddCondition, String> {
+    // Ankaios has no health checks, thus a healthy service is treated as a running one
+    match condition.as_deref() {
+        None | Some("service_started") | Some("service_healthy") => {
+            Ok(AddCondition::AddCondRunning)
+        }
+        Some("service_completed_successfully") => Ok(AddCondition::AddCondSucceeded),
+        Some(unknown) => Err(format!(
+            "Service '{service_name}' has the unknown dependency condition '{unknown}'."
+        )),
+    }
+}
+
+fn convert_restart(service_name: &str, restart: Option<String>) -> Result<RestartPolicy, String> {
+    match restart.as_deref() {
+        None | Some("no") => Ok(RestartPolicy::Never),
+        Some("always") | Some("unless-stopped") => Ok(RestartPolicy::Always),
+        Some(on_failure) if on_failure.starts_with("on-failure") => Ok(RestartPolicy::OnFailure),
+        Some(unknown) => Err(format!(
+            "Service '{service_name}' has the unknown restart policy '{unknown}'."
+        )),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{convert_compose_file, split_command};
+    use crate::objects::{AddCondition, RestartPolicy};
+
+    const AGENT_NAME: &str = "agent_A";
+
+    const COMPOSE_FILE: &str = r#"
+services:
+  web:
+    image: docker.io/nginx:latest
+    entrypoint: ["nginx", "-g"]
+    command: "'daemon off;'"
+    environment:
+      MODE: test
+      WORKERS: 4
+    ports:
+      - "8080:80"
+      - target: 443
+        published: 8443
+        protocol: tcp
+    volumes:
+      - ./html:/usr/share/nginx/html:ro
+      - type: volume
+        source: cache
+        target: /var/cache/nginx
+    networks:
+      - frontend
+    depends_on:
+      db:
+        condition: service_healthy
+      init:
+        condition: service_completed_successfully
+    restart: unless-stopped
+  db:
+    image: docker.io/postgres:16
+    environment:
+      - POSTGRES_PASSWORD=secret
+    networks:
+      backend: {}
+  init:
+    image: alpine:latest
+    command: echo "init done"
+    depends_on:
+      - db
+networks:
+  frontend:
+  backend:
+volumes:
+  cache:
+"#;
+
+    fn runtime_config_of(state: &crate::objects::State, name: &str) -> serde_yaml::Value {
+        serde_yaml::from_str(&state.workloads.get(name).unwrap().runtime_config).unwrap()
+    }
+
+    // [utest->swdd~common-converts-compose-files~1]
+    #[test]
+    fn utest_convert_compose_file_services_to_podman_workloads() {
+        let state = convert_compose_file(COMPOSE_FILE, AGENT_NAME).unwrap();
+
+        assert_eq!(state.workloads.len(), 3);
+        assert!(state
+            .workloads
+            .values()
+            .all(|workload| workload.agent == AGENT_NAME && workload.runtime == "podman"));
+
+        let expected_web_config: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+commandOptions:
+  - "--entrypoint=nginx"
+  - "-e"
+  - "MODE=test"
+  - "-e"
+  - "WORKERS=4"
+  - "-p"
+  - "8080:80"
+  - "-p"
+  - "8443:443/tcp"
+  - "-v"
+  - "./html:/usr/share/nginx/html:ro"
+  - "-v"
+  - "cache:/var/cache/nginx"
+  - "--network=frontend"
+image: docker.io/nginx:latest
+commandArgs: ["-g", "daemon off;"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(runtime_config_of(&state, "web"), expected_web_config);
+
+        let expected_db_config: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+commandOptions: ["-e", "POSTGRES_PASSWORD=secret", "--network=backend"]
+image: docker.io/postgres:16
+"#,
+        )
+        .unwrap();
+        assert_eq!(runtime_config_of(&state, "db"), expected_db_config);
+    }
+
+    // [utest->swdd~common-converts-compose-files~1]
+    #[test]
+    fn utest_convert_compose_file_depends_on_and_restart() {
+        let state = convert_compose_file(COMPOSE_FILE, AGENT_NAME).unwrap();
+
+        let web = state.workloads.get("web").unwrap();
+        assert_eq!(web.restart_policy, RestartPolicy::Always);
+        assert_eq!(
+            web.dependencies,
+            [
+                ("db".to_owned(), AddCondition::AddCondRunning),
+                ("init".to_owned(), AddCondition::AddCondSucceeded)
+            ]
+            .into()
+        );
+
+        let init = state.workloads.get("init").unwrap();
+        assert_eq!(init.restart_policy, RestartPolicy::Never);
+        assert_eq!(
+            init.dependencies,
+            [("db".to_owned(), AddCondition::AddCondRunning)].into()
+        );
+    }
+
+    // [utest->swdd~common-converts-compose-files~1]
+    #[test]
+    fn utest_convert_compose_file_fails_on_unknown_dependency() {
+        let compose_file = r#"
+services:
+  app:
+    image: alpine:latest
+    depends_on: [missing]
+"#;
+
+        assert!(convert_compose_file(compose_file, AGENT_NAME)
+            .unwrap_err()
+            .contains("unknown service 'missing'"));
+    }
+
+    // [utest->swdd~common-converts-compose-files~1]
+    #[test]
+    fn utest_convert_compose_file_fails_on_build() {
+        let compose_file = r#"
+services:
+  app:
+    build: .
+"#;
+
+        assert!(convert_compose_file(compose_file, AGENT_NAME)
+            .unwrap_err()
+            .contains("'build'"));
+    }
+
+    #[test]
+    fn utest_split_command_respects_quotes() {
+        assert_eq!(
+            split_command(r#"sh -c "echo 'hello world'"  ''"#),
+            vec!["sh", "-c", "echo 'hello world'", ""]
+        );
+    }
+}
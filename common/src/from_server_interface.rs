@@ -15,6 +15,7 @@
 use crate::commands;
 use crate::objects::{CompleteState, DeletedWorkload, WorkloadSpec, WorkloadState};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use tokio::sync::mpsc::error::SendError;
 #[derive(Debug)]
@@ -32,12 +33,14 @@ impl From<SendError<FromServer>> for FromServerInterfaceError {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum FromServer {
     UpdateWorkload(commands::UpdateWorkload),
     UpdateWorkloadState(commands::UpdateWorkloadState),
+    UpdateConnectedAgents(commands::UpdateConnectedAgents),
     Response(commands::Response),
     Stop(commands::Stop),
+    ServerGone(commands::ServerGone),
 }
 
 // [impl->swdd~from-server-channel~1]
@@ -47,29 +50,77 @@ pub trait FromServerInterface {
         &self,
         added_workloads: Vec<WorkloadSpec>,
         deleted_workloads: Vec<DeletedWorkload>,
+        sequence_number: u64,
+        initial: bool,
+        deadline_ms: Option<u64>,
     ) -> Result<(), FromServerInterfaceError>;
     async fn update_workload_state(
         &self,
         workload_running: Vec<WorkloadState>,
     ) -> Result<(), FromServerInterfaceError>;
+    async fn update_connected_agents(
+        &self,
+        connected_agents: Vec<String>,
+    ) -> Result<(), FromServerInterfaceError>;
     async fn response(&self, response: commands::Response) -> Result<(), FromServerInterfaceError>;
     async fn complete_state(
         &self,
         request_id: String,
+        trace_id: String,
         complete_state: CompleteState,
     ) -> Result<(), FromServerInterfaceError>;
     async fn update_state_success(
         &self,
         request_id: String,
+        trace_id: String,
         added_workloads: Vec<String>,
         deleted_workloads: Vec<String>,
+        rejected_workloads: Vec<commands::RejectedWorkload>,
     ) -> Result<(), FromServerInterfaceError>;
     async fn error(
         &self,
         request_id: String,
+        trace_id: String,
         error: commands::Error,
     ) -> Result<(), FromServerInterfaceError>;
+    async fn rollout_status(
+        &self,
+        request_id: String,
+        trace_id: String,
+        rollout_status: commands::RolloutStatus,
+    ) -> Result<(), FromServerInterfaceError>;
+    async fn support_info(
+        &self,
+        request_id: String,
+        trace_id: String,
+        support_info: commands::SupportInfo,
+    ) -> Result<(), FromServerInterfaceError>;
+    async fn events(
+        &self,
+        request_id: String,
+        trace_id: String,
+        events: commands::Events,
+    ) -> Result<(), FromServerInterfaceError>;
+    async fn impact_analysis(
+        &self,
+        request_id: String,
+        trace_id: String,
+        impact_analysis: commands::ImpactAnalysis,
+    ) -> Result<(), FromServerInterfaceError>;
+    async fn dependency_graph(
+        &self,
+        request_id: String,
+        trace_id: String,
+        dependency_graph: commands::DependencyGraph,
+    ) -> Result<(), FromServerInterfaceError>;
+    async fn scheduler_queue(
+        &self,
+        request_id: String,
+        trace_id: String,
+        scheduler_queue: commands::SchedulerQueue,
+    ) -> Result<(), FromServerInterfaceError>;
     async fn stop(&self) -> Result<(), FromServerInterfaceError>;
+    async fn server_gone(&self) -> Result<(), FromServerInterfaceError>;
 }
 
 pub type FromServerSender = tokio::sync::mpsc::Sender<FromServer>;
@@ -81,11 +132,17 @@ impl FromServerInterface for FromServerSender {
         &self,
         added_workloads: Vec<WorkloadSpec>,
         deleted_workloads: Vec<DeletedWorkload>,
+        sequence_number: u64,
+        initial: bool,
+        deadline_ms: Option<u64>,
     ) -> Result<(), FromServerInterfaceError> {
         Ok(self
             .send(FromServer::UpdateWorkload(commands::UpdateWorkload {
                 added_workloads,
                 deleted_workloads,
+                sequence_number,
+                initial,
+                deadline_ms,
             }))
             .await?)
     }
@@ -101,6 +158,18 @@ impl FromServerInterface for FromServerSender {
             .await?)
     }
 
+    // [impl->swdd~server-distributes-connected-agents~1]
+    async fn update_connected_agents(
+        &self,
+        connected_agents: Vec<String>,
+    ) -> Result<(), FromServerInterfaceError> {
+        Ok(self
+            .send(FromServer::UpdateConnectedAgents(
+                commands::UpdateConnectedAgents { connected_agents },
+            ))
+            .await?)
+    }
+
     async fn response(&self, response: commands::Response) -> Result<(), FromServerInterfaceError> {
         Ok(self.send(FromServer::Response(response)).await?)
     }
@@ -108,11 +177,13 @@ impl FromServerInterface for FromServerSender {
     async fn complete_state(
         &self,
         request_id: String,
+        trace_id: String,
         complete_state: CompleteState,
     ) -> Result<(), FromServerInterfaceError> {
         Ok(self
             .send(FromServer::Response(commands::Response {
                 request_id,
+                trace_id,
                 response_content: commands::ResponseContent::CompleteState(Box::new(
                     complete_state,
                 )),
@@ -123,16 +194,20 @@ impl FromServerInterface for FromServerSender {
     async fn update_state_success(
         &self,
         request_id: String,
+        trace_id: String,
         added_workloads: Vec<String>,
         deleted_workloads: Vec<String>,
+        rejected_workloads: Vec<commands::RejectedWorkload>,
     ) -> Result<(), FromServerInterfaceError> {
         Ok(self
             .send(FromServer::Response(commands::Response {
                 request_id,
+                trace_id,
                 response_content: commands::ResponseContent::UpdateStateSuccess(
                     commands::UpdateStateSuccess {
                         added_workloads,
                         deleted_workloads,
+                        rejected_workloads,
                     },
                 ),
             }))
@@ -142,19 +217,117 @@ impl FromServerInterface for FromServerSender {
     async fn error(
         &self,
         request_id: String,
+        trace_id: String,
         error: commands::Error,
     ) -> Result<(), FromServerInterfaceError> {
         Ok(self
             .send(FromServer::Response(commands::Response {
                 request_id,
+                trace_id,
                 response_content: commands::ResponseContent::Error(error),
             }))
             .await?)
     }
 
+    async fn rollout_status(
+        &self,
+        request_id: String,
+        trace_id: String,
+        rollout_status: commands::RolloutStatus,
+    ) -> Result<(), FromServerInterfaceError> {
+        Ok(self
+            .send(FromServer::Response(commands::Response {
+                request_id,
+                trace_id,
+                response_content: commands::ResponseContent::RolloutStatus(rollout_status),
+            }))
+            .await?)
+    }
+
+    async fn support_info(
+        &self,
+        request_id: String,
+        trace_id: String,
+        support_info: commands::SupportInfo,
+    ) -> Result<(), FromServerInterfaceError> {
+        Ok(self
+            .send(FromServer::Response(commands::Response {
+                request_id,
+                trace_id,
+                response_content: commands::ResponseContent::SupportInfo(support_info),
+            }))
+            .await?)
+    }
+
+    async fn events(
+        &self,
+        request_id: String,
+        trace_id: String,
+        events: commands::Events,
+    ) -> Result<(), FromServerInterfaceError> {
+        Ok(self
+            .send(FromServer::Response(commands::Response {
+                request_id,
+                trace_id,
+                response_content: commands::ResponseContent::Events(events),
+            }))
+            .await?)
+    }
+
+    async fn impact_analysis(
+        &self,
+        request_id: String,
+        trace_id: String,
+        impact_analysis: commands::ImpactAnalysis,
+    ) -> Result<(), FromServerInterfaceError> {
+        Ok(self
+            .send(FromServer::Response(commands::Response {
+                request_id,
+                trace_id,
+                response_content: commands::ResponseContent::ImpactAnalysis(impact_analysis),
+            }))
+            .await?)
+    }
+
+    async fn dependency_graph(
+        &self,
+        request_id: String,
+        trace_id: String,
+        dependency_graph: commands::DependencyGraph,
+    ) -> Result<(), FromServerInterfaceError> {
+        Ok(self
+            .send(FromServer::Response(commands::Response {
+                request_id,
+                trace_id,
+                response_content: commands::ResponseContent::DependencyGraph(dependency_graph),
+            }))
+            .await?)
+    }
+
+    async fn scheduler_queue(
+        &self,
+        request_id: String,
+        trace_id: String,
+        scheduler_queue: commands::SchedulerQueue,
+    ) -> Result<(), FromServerInterfaceError> {
+        Ok(self
+            .send(FromServer::Response(commands::Response {
+                request_id,
+                trace_id,
+                response_content: commands::ResponseContent::SchedulerQueue(scheduler_queue),
+            }))
+            .await?)
+    }
+
     async fn stop(&self) -> Result<(), FromServerInterfaceError> {
         Ok(self.send(FromServer::Stop(commands::Stop {})).await?)
     }
+
+    async fn server_gone(&self) -> Result<(), FromServerInterfaceError> {
+        Ok(self
+            .send(FromServer::ServerGone(commands::ServerGone {}))
+            .await?)
+    }
 }
 
 //////////////////////////////////////////////////////////////////////////////
@@ -181,6 +354,7 @@ mod tests {
     const WORKLOAD_NAME: &str = "X";
     const AGENT_NAME: &str = "agent_A";
     const REQUEST_ID: &str = "emkw489ejf89ml";
+    const TRACE_ID: &str = "a1b2c3d4";
 
     // [utest->swdd~from-server-channel~1]
     #[tokio::test]
@@ -194,7 +368,13 @@ mod tests {
             WORKLOAD_NAME.to_string(),
         )];
         assert!(tx
-            .update_workload(added_workloads.clone(), deleted_workloads.clone())
+            .update_workload(
+                added_workloads.clone(),
+                deleted_workloads.clone(),
+                1,
+                true,
+                Some(2000)
+            )
             .await
             .is_ok());
 
@@ -203,6 +383,9 @@ mod tests {
             FromServer::UpdateWorkload(commands::UpdateWorkload {
                 added_workloads,
                 deleted_workloads,
+                sequence_number: 1,
+                initial: true,
+                deadline_ms: Some(2000),
             })
         )
     }
@@ -227,6 +410,25 @@ mod tests {
         )
     }
 
+    // [utest->swdd~server-distributes-connected-agents~1]
+    #[tokio::test]
+    async fn utest_to_server_send_update_connected_agents() {
+        let (tx, mut rx): (FromServerSender, FromServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        assert!(tx
+            .update_connected_agents(vec![AGENT_NAME.to_string()])
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            FromServer::UpdateConnectedAgents(commands::UpdateConnectedAgents {
+                connected_agents: vec![AGENT_NAME.to_string()],
+            })
+        )
+    }
+
     // [utest->swdd~from-server-channel~1]
     #[tokio::test]
     async fn utest_to_server_send_complete_state() {
@@ -235,7 +437,11 @@ mod tests {
 
         let complete_state = generate_test_complete_state(vec![generate_test_workload_spec()]);
         assert!(tx
-            .complete_state(REQUEST_ID.to_string(), complete_state.clone())
+            .complete_state(
+                REQUEST_ID.to_string(),
+                TRACE_ID.to_string(),
+                complete_state.clone()
+            )
             .await
             .is_ok());
 
@@ -243,6 +449,7 @@ mod tests {
             rx.recv().await.unwrap(),
             FromServer::Response(commands::Response {
                 request_id: REQUEST_ID.to_string(),
+                trace_id: TRACE_ID.to_string(),
                 response_content: commands::ResponseContent::CompleteState(Box::new(
                     complete_state,
                 )),
@@ -258,11 +465,17 @@ mod tests {
 
         let added_workloads = vec!["some_name".to_string(), "some_other_name".to_string()];
         let deleted_workloads = vec!["some_name_1".to_string(), "some_other_name_1".to_string()];
+        let rejected_workloads = vec![commands::RejectedWorkload {
+            workload_name: "some_rejected_name".to_string(),
+            reason: "some reason".to_string(),
+        }];
         assert!(tx
             .update_state_success(
                 REQUEST_ID.to_string(),
+                TRACE_ID.to_string(),
                 added_workloads.clone(),
-                deleted_workloads.clone()
+                deleted_workloads.clone(),
+                rejected_workloads.clone()
             )
             .await
             .is_ok());
@@ -271,10 +484,12 @@ mod tests {
             rx.recv().await.unwrap(),
             FromServer::Response(commands::Response {
                 request_id: REQUEST_ID.to_string(),
+                trace_id: TRACE_ID.to_string(),
                 response_content: commands::ResponseContent::UpdateStateSuccess(
                     commands::UpdateStateSuccess {
                         added_workloads,
                         deleted_workloads,
+                        rejected_workloads,
                     },
                 ),
             })
@@ -291,7 +506,7 @@ mod tests {
             message: "error".to_string(),
         };
         assert!(tx
-            .error(REQUEST_ID.to_string(), error.clone())
+            .error(REQUEST_ID.to_string(), TRACE_ID.to_string(), error.clone())
             .await
             .is_ok());
 
@@ -299,8 +514,211 @@ mod tests {
             rx.recv().await.unwrap(),
             FromServer::Response(commands::Response {
                 request_id: REQUEST_ID.to_string(),
+                trace_id: TRACE_ID.to_string(),
                 response_content: commands::ResponseContent::Error(error),
             })
         )
     }
+
+    // [utest->swdd~from-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_rollout_status() {
+        let (tx, mut rx): (FromServerSender, FromServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let rollout_status = commands::RolloutStatus {
+            state: commands::RolloutState::InProgress,
+            ..Default::default()
+        };
+        assert!(tx
+            .rollout_status(
+                REQUEST_ID.to_string(),
+                TRACE_ID.to_string(),
+                rollout_status.clone()
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            FromServer::Response(commands::Response {
+                request_id: REQUEST_ID.to_string(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: commands::ResponseContent::RolloutStatus(rollout_status),
+            })
+        )
+    }
+
+    // [utest->swdd~from-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_support_info() {
+        let (tx, mut rx): (FromServerSender, FromServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let support_info = commands::SupportInfo {
+            server_version: "0.4.0".to_string(),
+            ..Default::default()
+        };
+        assert!(tx
+            .support_info(
+                REQUEST_ID.to_string(),
+                TRACE_ID.to_string(),
+                support_info.clone()
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            FromServer::Response(commands::Response {
+                request_id: REQUEST_ID.to_string(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: commands::ResponseContent::SupportInfo(support_info),
+            })
+        )
+    }
+
+    // [utest->swdd~from-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_events() {
+        let (tx, mut rx): (FromServerSender, FromServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let events = commands::Events {
+            events: vec![commands::Event {
+                timestamp: 1000,
+                kind: commands::EventKind::AgentConnected,
+                agent_name: Some("agent_A".to_string()),
+                ..Default::default()
+            }],
+        };
+        assert!(tx
+            .events(REQUEST_ID.to_string(), TRACE_ID.to_string(), events.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            FromServer::Response(commands::Response {
+                request_id: REQUEST_ID.to_string(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: commands::ResponseContent::Events(events),
+            })
+        )
+    }
+
+    // [utest->swdd~from-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_impact_analysis() {
+        let (tx, mut rx): (FromServerSender, FromServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let impact_analysis = commands::ImpactAnalysis {
+            impacted_workloads: vec![commands::ImpactedWorkload {
+                workload_name: "workload_B".to_string(),
+                agent: "agent_A".to_string(),
+                depends_on: vec!["workload_A".to_string()],
+            }],
+        };
+        assert!(tx
+            .impact_analysis(
+                REQUEST_ID.to_string(),
+                TRACE_ID.to_string(),
+                impact_analysis.clone()
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            FromServer::Response(commands::Response {
+                request_id: REQUEST_ID.to_string(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: commands::ResponseContent::ImpactAnalysis(impact_analysis),
+            })
+        )
+    }
+
+    // [utest->swdd~from-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_dependency_graph() {
+        let (tx, mut rx): (FromServerSender, FromServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let dependency_graph = commands::DependencyGraph {
+            nodes: vec![commands::DependencyGraphNode {
+                workload_name: "workload_A".to_string(),
+                agent: "agent_A".to_string(),
+                execution_state: Some(ExecutionState::running()),
+            }],
+            edges: vec![],
+        };
+        assert!(tx
+            .dependency_graph(
+                REQUEST_ID.to_string(),
+                TRACE_ID.to_string(),
+                dependency_graph.clone()
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            FromServer::Response(commands::Response {
+                request_id: REQUEST_ID.to_string(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: commands::ResponseContent::DependencyGraph(dependency_graph),
+            })
+        )
+    }
+
+    // [utest->swdd~from-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_scheduler_queue() {
+        let (tx, mut rx): (FromServerSender, FromServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let scheduler_queue = commands::SchedulerQueue {
+            pending_operations: vec![commands::PendingWorkloadOperation {
+                workload_name: "workload_B".to_string(),
+                agent: "agent_A".to_string(),
+                operation: commands::PendingOperation::Create,
+                unfulfilled_dependencies: vec![commands::UnfulfilledDependency {
+                    workload_name: "workload_A".to_string(),
+                    condition: "ADD_COND_RUNNING".to_string(),
+                }],
+            }],
+        };
+        assert!(tx
+            .scheduler_queue(
+                REQUEST_ID.to_string(),
+                TRACE_ID.to_string(),
+                scheduler_queue.clone()
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            FromServer::Response(commands::Response {
+                request_id: REQUEST_ID.to_string(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: commands::ResponseContent::SchedulerQueue(scheduler_queue),
+            })
+        )
+    }
+
+    // [utest->swdd~from-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_server_gone() {
+        let (tx, mut rx): (FromServerSender, FromServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        assert!(tx.server_gone().await.is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            FromServer::ServerGone(commands::ServerGone {})
+        )
+    }
 }
@@ -11,8 +11,7 @@
 // under the License.
 //
 // SPDX-License-Identifier: Apache-2.0
-use serde::{Serialize, Serializer};
-use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 // [impl->swdd~common-helper-methods~1]
 pub fn try_into_vec<S, T, E>(input: Vec<S>) -> Result<Vec<T>, E>
@@ -22,13 +21,10 @@ where
     input.into_iter().map(|x| x.try_into()).collect()
 }
 
-pub fn serialize_to_ordered_map<S, T: Serialize>(
-    value: &HashMap<String, T>,
-    serializer: S,
-) -> Result<S::Ok, S::Error>
-where
-    S: Serializer,
-{
-    let ordered: BTreeMap<_, _> = value.iter().collect();
-    ordered.serialize(serializer)
+// The timestamps exchanged between the server and the agents are milliseconds since the Unix epoch.
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
 }
@@ -0,0 +1,463 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::objects::{RestartPolicy, State, StoredWorkloadSpec};
+
+pub(crate) const PODMAN_RUNTIME_NAME: &str = "podman";
+const PODMAN_KUBE_RUNTIME_NAME: &str = "podman-kube";
+const KIND_POD: &str = "Pod";
+const KIND_DEPLOYMENT: &str = "Deployment";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversionRuntime {
+    Podman,
+    PodmanKube,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KubeObject {
+    kind: String,
+    metadata: KubeMetadata,
+    #[serde(default)]
+    spec: serde_yaml::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct KubeMetadata {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeploymentSpec {
+    template: PodTemplateSpec,
+}
+
+#[derive(Debug, Deserialize)]
+struct PodTemplateSpec {
+    spec: PodSpec,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PodSpec {
+    containers: Vec<Container>,
+    restart_policy: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Container {
+    name: String,
+    image: Option<String>,
+    #[serde(default)]
+    command: Vec<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    env: Vec<EnvVar>,
+    #[serde(default)]
+    ports: Vec<ContainerPort>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EnvVar {
+    name: String,
+    value: Option<String>,
+    value_from: Option<serde_yaml::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContainerPort {
+    container_port: u16,
+    host_port: Option<u16>,
+    protocol: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PodmanConfig {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub command_options: Vec<String>,
+    pub image: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub command_args: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct PodmanKubeConfig {
+    manifest: String,
+}
+
+// [impl->swdd~common-converts-kubernetes-manifests~1]
+/// Converts the Pods and Deployments of a (multi document) Kubernetes manifest into an Ankaios state.
+///
+/// Each Pod or Deployment results in one workload named after the Kubernetes object and assigned to the given agent.
+/// If no runtime is requested, Pods with a single container are converted to `podman` workloads
+/// and Pods with multiple containers to `podman-kube` workloads.
+pub fn convert_kube_manifest(
+    manifest: &str,
+    agent_name: &str,
+    runtime: Option<ConversionRuntime>,
+) -> Result<State, String> {
+    let mut state = State::default();
+
+    for document in serde_yaml::Deserializer::from_str(manifest) {
+        let document = serde_yaml::Value::deserialize(document)
+            .map_err(|err| format!("Could not parse Kubernetes manifest: '{err}'"))?;
+        if document.is_null() {
+            continue;
+        }
+
+        let kube_object: KubeObject = serde_yaml::from_value(document.clone())
+            .map_err(|err| format!("Could not parse Kubernetes object: '{err}'"))?;
+        let workload_name = kube_object.metadata.name.clone();
+
+        let pod_spec = parse_pod_spec(kube_object)?;
+        let runtime = runtime.unwrap_or(if pod_spec.containers.len() == 1 {
+            ConversionRuntime::Podman
+        } else {
+            ConversionRuntime::PodmanKube
+        });
+
+        let workload = match runtime {
+            ConversionRuntime::Podman => convert_to_podman_workload(&workload_name, pod_spec)?,
+            ConversionRuntime::PodmanKube => convert_to_podman_kube_workload(&document)?,
+        };
+
+        if state
+            .workloads
+            .insert(
+                workload_name.clone(),
+                StoredWorkloadSpec {
+                    agent: agent_name.to_owned(),
+                    ..workload
+                },
+            )
+            .is_some()
+        {
+            return Err(format!(
+                "The Kubernetes manifest contains the name '{workload_name}' multiple times."
+            ));
+        }
+    }
+
+    Ok(state)
+}
+
+fn parse_pod_spec(kube_object: KubeObject) -> Result<PodSpec, String> {
+    let name = kube_object.metadata.name;
+    match kube_object.kind.as_str() {
+        KIND_POD => serde_yaml::from_value(kube_object.spec)
+            .map_err(|err| format!("Could not parse the spec of Pod '{name}': '{err}'")),
+        KIND_DEPLOYMENT => serde_yaml::from_value::<DeploymentSpec>(kube_object.spec)
+            .map(|deployment_spec| deployment_spec.template.spec)
+            .map_err(|err| format!("Could not parse the spec of Deployment '{name}': '{err}'")),
+        kind => Err(format!(
+            "Kubernetes object '{name}' has the unsupported kind '{kind}'. Only '{KIND_POD}' and '{KIND_DEPLOYMENT}' can be converted."
+        )),
+    }
+}
+
+fn convert_to_podman_workload(
+    workload_name: &str,
+    mut pod_spec: PodSpec,
+) -> Result<StoredWorkloadSpec, String> {
+    if pod_spec.containers.len() != 1 {
+        return Err(format!(
+            "'{workload_name}' has {} containers, but only a single container can be converted for the '{PODMAN_RUNTIME_NAME}' runtime.",
+            pod_spec.containers.len()
+        ));
+    }
+    let container = pod_spec.containers.remove(0);
+
+    let image = container.image.ok_or_else(|| {
+        format!(
+            "Container '{}' of '{workload_name}' does not specify an image.",
+            container.name
+        )
+    })?;
+
+    let mut command_options = Vec::new();
+    let mut command_args = Vec::new();
+
+    // the Kubernetes 'command' replaces the entrypoint of the image and 'args' are appended to it
+    let mut command = container.command.into_iter();
+    if let Some(entrypoint) = command.next() {
+        command_options.push(format!("--entrypoint={entrypoint}"));
+        command_args.extend(command);
+    }
+    command_args.extend(container.args);
+
+    for env in container.env {
+        if env.value_from.is_some() {
+            return Err(format!(
+                "Environment variable '{}' of '{workload_name}' uses 'valueFrom', which can not be converted.",
+                env.name
+            ));
+        }
+        command_options.push("-e".to_owned());
+        command_options.push(format!("{}={}", env.name, env.value.unwrap_or_default()));
+    }
+
+    // only ports with a host port are reachable from outside of the Pod
+    for port in container.ports {
+        if let Some(host_port) = port.host_port {
+            command_options.push("-p".to_owned());
+            command_options.push(match port.protocol {
+                Some(protocol) => format!(
+                    "{host_port}:{}/{}",
+                    port.container_port,
+                    protocol.to_lowercase()
+                ),
+                None => format!("{host_port}:{}", port.container_port),
+            });
+        }
+    }
+
+    let runtime_config = serde_yaml::to_string(&PodmanConfig {
+        command_options,
+        image,
+        command_args,
+    })
+    .map_err(|err| format!("Could not create runtime config of '{workload_name}': '{err}'"))?;
+
+    Ok(StoredWorkloadSpec {
+        restart_policy: convert_restart_policy(workload_name, pod_spec.restart_policy)?,
+        runtime: PODMAN_RUNTIME_NAME.to_owned(),
+        runtime_config,
+        ..Default::default()
+    })
+}
+
+// Kubernetes restarts containers by default, while Ankaios does not
+fn convert_restart_policy(
+    workload_name: &str,
+    restart_policy: Option<String>,
+) -> Result<RestartPolicy, String> {
+    match restart_policy.as_deref() {
+        None | Some("Always") => Ok(RestartPolicy::Always),
+        Some("OnFailure") => Ok(RestartPolicy::OnFailure),
+        Some("Never") => Ok(RestartPolicy::Never),
+        Some(unknown) => Err(format!(
+            "'{workload_name}' has the unknown restart policy '{unknown}'."
+        )),
+    }
+}
+
+fn convert_to_podman_kube_workload(
+    document: &serde_yaml::Value,
+) -> Result<StoredWorkloadSpec, String> {
+    let manifest = serde_yaml::to_string(document)
+        .map_err(|err| format!("Could not serialize Kubernetes object: '{err}'"))?;
+    let runtime_config = serde_yaml::to_string(&PodmanKubeConfig { manifest })
+        .map_err(|err| format!("Could not create runtime config: '{err}'"))?;
+
+    Ok(StoredWorkloadSpec {
+        runtime: PODMAN_KUBE_RUNTIME_NAME.to_owned(),
+        runtime_config,
+        ..Default::default()
+    })
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{convert_kube_manifest, ConversionRuntime};
+    use crate::objects::RestartPolicy;
+
+    const AGENT_NAME: &str = "agent_A";
+
+    const SINGLE_CONTAINER_DEPLOYMENT: &str = r#"
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: nginx
+spec:
+  replicas: 1
+  template:
+    metadata:
+      labels:
+        app: nginx
+    spec:
+      containers:
+      - name: nginx
+        image: docker.io/nginx:latest
+        command: ["nginx", "-g"]
+        args: ["daemon off;"]
+        env:
+        - name: MODE
+          value: test
+        ports:
+        - containerPort: 80
+          hostPort: 8080
+        - containerPort: 443
+"#;
+
+    const MULTI_CONTAINER_POD: &str = r#"
+apiVersion: v1
+kind: Pod
+metadata:
+  name: sidecar_pod
+spec:
+  restartPolicy: Never
+  containers:
+  - name: app
+    image: alpine:latest
+  - name: sidecar
+    image: busybox:latest
+"#;
+
+    fn runtime_config_of(state: &crate::objects::State, name: &str) -> serde_yaml::Value {
+        serde_yaml::from_str(&state.workloads.get(name).unwrap().runtime_config).unwrap()
+    }
+
+    // [utest->swdd~common-converts-kubernetes-manifests~1]
+    #[test]
+    fn utest_convert_kube_manifest_single_container_deployment_to_podman() {
+        let state = convert_kube_manifest(SINGLE_CONTAINER_DEPLOYMENT, AGENT_NAME, None).unwrap();
+
+        let workload = state.workloads.get("nginx").unwrap();
+        assert_eq!(workload.agent, AGENT_NAME);
+        assert_eq!(workload.runtime, "podman");
+        assert_eq!(workload.restart_policy, RestartPolicy::Always);
+
+        let expected_config: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+commandOptions: ["--entrypoint=nginx", "-e", "MODE=test", "-p", "8080:80"]
+image: docker.io/nginx:latest
+commandArgs: ["-g", "daemon off;"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(runtime_config_of(&state, "nginx"), expected_config);
+    }
+
+    // [utest->swdd~common-converts-kubernetes-manifests~1]
+    #[test]
+    fn utest_convert_kube_manifest_multi_container_pod_to_podman_kube() {
+        let state = convert_kube_manifest(MULTI_CONTAINER_POD, AGENT_NAME, None).unwrap();
+
+        let workload = state.workloads.get("sidecar_pod").unwrap();
+        assert_eq!(workload.agent, AGENT_NAME);
+        assert_eq!(workload.runtime, "podman-kube");
+
+        let manifest = runtime_config_of(&state, "sidecar_pod")["manifest"]
+            .as_str()
+            .unwrap()
+            .to_owned();
+        assert_eq!(
+            serde_yaml::from_str::<serde_yaml::Value>(&manifest).unwrap(),
+            serde_yaml::from_str::<serde_yaml::Value>(MULTI_CONTAINER_POD).unwrap()
+        );
+    }
+
+    // [utest->swdd~common-converts-kubernetes-manifests~1]
+    #[test]
+    fn utest_convert_kube_manifest_requested_runtime_is_used() {
+        let state = convert_kube_manifest(
+            SINGLE_CONTAINER_DEPLOYMENT,
+            AGENT_NAME,
+            Some(ConversionRuntime::PodmanKube),
+        )
+        .unwrap();
+        assert_eq!(state.workloads.get("nginx").unwrap().runtime, "podman-kube");
+
+        assert!(convert_kube_manifest(
+            MULTI_CONTAINER_POD,
+            AGENT_NAME,
+            Some(ConversionRuntime::Podman)
+        )
+        .is_err());
+    }
+
+    // [utest->swdd~common-converts-kubernetes-manifests~1]
+    #[test]
+    fn utest_convert_kube_manifest_multiple_documents() {
+        let manifest = format!("{SINGLE_CONTAINER_DEPLOYMENT}---{MULTI_CONTAINER_POD}---\n");
+
+        let state = convert_kube_manifest(&manifest, AGENT_NAME, None).unwrap();
+
+        assert_eq!(state.workloads.len(), 2);
+        assert_eq!(state.workloads.get("nginx").unwrap().runtime, "podman");
+        assert_eq!(
+            state.workloads.get("sidecar_pod").unwrap().restart_policy,
+            RestartPolicy::Never
+        );
+    }
+
+    // [utest->swdd~common-converts-kubernetes-manifests~1]
+    #[test]
+    fn utest_convert_kube_manifest_fails_on_unsupported_kind() {
+        let manifest = r#"
+apiVersion: v1
+kind: Service
+metadata:
+  name: my_service
+spec:
+  ports:
+  - port: 80
+"#;
+
+        assert!(convert_kube_manifest(manifest, AGENT_NAME, None)
+            .unwrap_err()
+            .contains("unsupported kind 'Service'"));
+    }
+
+    // [utest->swdd~common-converts-kubernetes-manifests~1]
+    #[test]
+    fn utest_convert_kube_manifest_fails_on_env_value_from() {
+        let manifest = r#"
+apiVersion: v1
+kind: Pod
+metadata:
+  name: app
+spec:
+  containers:
+  - name: app
+    image: alpine:latest
+    env:
+    - name: SECRET
+      valueFrom:
+        secretKeyRef:
+          name: my_secret
+          key: password
+"#;
+
+        assert!(convert_kube_manifest(manifest, AGENT_NAME, None)
+            .unwrap_err()
+            .contains("'SECRET'"));
+    }
+
+    // [utest->swdd~common-converts-kubernetes-manifests~1]
+    #[test]
+    fn utest_convert_kube_manifest_fails_on_duplicate_names() {
+        let manifest = format!("{MULTI_CONTAINER_POD}---{MULTI_CONTAINER_POD}");
+
+        assert!(convert_kube_manifest(&manifest, AGENT_NAME, None)
+            .unwrap_err()
+            .contains("'sidecar_pod' multiple times"));
+    }
+}
@@ -15,17 +15,26 @@
 pub const CHANNEL_CAPACITY: usize = 20;
 pub const DEFAULT_SOCKET_ADDRESS: &str = "127.0.0.1:25551";
 pub const DEFAULT_SERVER_ADDRESS: &str = "http://127.0.0.1:25551";
+// The interval in which the agents refresh the states of running and stopping workloads.
+pub const WORKLOAD_STATE_REFRESH_INTERVAL_MS: u64 = 10000;
 
 pub mod commands;
 pub mod communications_client;
 pub mod communications_error;
 pub mod communications_server;
+pub mod compose_conversion;
 pub mod from_server_interface;
 pub mod helpers;
-pub mod objects;
+pub mod kube_conversion;
+pub mod memory_profiling;
+pub mod oci_artifact;
+pub mod persistence;
 pub mod request_id_prepending;
 pub mod state_manipulation;
 pub mod std_extensions;
 #[cfg(feature = "test_utils")]
 pub mod test_utils;
 pub mod to_server_interface;
+
+// The domain model is a crate of its own, such that external tools can use it without this crate.
+pub use ankaios_model::{input_limits, manifest_migration, objects};
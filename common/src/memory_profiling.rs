@@ -0,0 +1,311 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+// The allocations are attributed to the subsystem entered on the current thread.
+// As the scope is thread local, it must not be held across an await point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Subsystem {
+    Other = 0,
+    StateStorage = 1,
+    SchedulerQueues = 2,
+}
+
+const SUBSYSTEMS: [Subsystem; 3] = [
+    Subsystem::Other,
+    Subsystem::StateStorage,
+    Subsystem::SchedulerQueues,
+];
+
+impl fmt::Display for Subsystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Subsystem::Other => write!(f, "other"),
+            Subsystem::StateStorage => write!(f, "state storage"),
+            Subsystem::SchedulerQueues => write!(f, "scheduler queues"),
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT_SUBSYSTEM: Cell<u8> = const { Cell::new(Subsystem::Other as u8) };
+}
+
+struct Counter {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl Counter {
+    const fn new() -> Self {
+        Counter {
+            current: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    fn add(&self, size: usize) {
+        let current = self.current.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn sub(&self, size: usize) {
+        self.current.fetch_sub(size, Ordering::Relaxed);
+    }
+
+    fn usage(&self) -> HeapUsage {
+        HeapUsage {
+            current: self.current.load(Ordering::Relaxed),
+            peak: self.peak.load(Ordering::Relaxed),
+        }
+    }
+}
+
+static TOTAL: Counter = Counter::new();
+static PER_SUBSYSTEM: [Counter; 3] = [Counter::new(), Counter::new(), Counter::new()];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HeapUsage {
+    pub current: usize,
+    pub peak: usize,
+}
+
+impl fmt::Display for HeapUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} B (peak {} B)", self.current, self.peak)
+    }
+}
+
+#[must_use]
+pub struct SubsystemScope {
+    previous: u8,
+}
+
+impl Drop for SubsystemScope {
+    fn drop(&mut self) {
+        let _ = CURRENT_SUBSYSTEM.try_with(|current| current.set(self.previous));
+    }
+}
+
+/// Attributes the allocations of the current thread to the given subsystem until the returned
+/// scope is dropped. The allocations are only counted if the [`TrackingAllocator`] is installed.
+// [impl->swdd~common-tracks-heap-usage-per-subsystem~1]
+pub fn enter(subsystem: Subsystem) -> SubsystemScope {
+    let previous = CURRENT_SUBSYSTEM
+        .try_with(|current| current.replace(subsystem as u8))
+        .unwrap_or(Subsystem::Other as u8);
+    SubsystemScope { previous }
+}
+
+pub fn heap_usage(subsystem: Subsystem) -> HeapUsage {
+    PER_SUBSYSTEM[subsystem as usize].usage()
+}
+
+pub fn total_heap_usage() -> HeapUsage {
+    TOTAL.usage()
+}
+
+// Every allocation is prefixed with a header storing the subsystem it is attributed to,
+// such that the deallocation is booked on the same subsystem regardless where it happens.
+const HEADER_SIZE: usize = 16;
+
+fn with_header(layout: Layout) -> Option<(Layout, usize)> {
+    let offset = layout.align().max(HEADER_SIZE);
+    let size = layout.size().checked_add(offset)?;
+    Layout::from_size_align(size, offset)
+        .ok()
+        .map(|layout| (layout, offset))
+}
+
+/// Global allocator counting the current and peak heap usage in total and per [`Subsystem`].
+/// Installed by the executables if the feature `memory_profiling` is enabled.
+pub struct TrackingAllocator;
+
+// [impl->swdd~common-tracks-heap-usage-per-subsystem~1]
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let Some((outer_layout, offset)) = with_header(layout) else {
+            return std::ptr::null_mut();
+        };
+        let base = System.alloc(outer_layout);
+        if base.is_null() {
+            return base;
+        }
+
+        let subsystem = CURRENT_SUBSYSTEM
+            .try_with(Cell::get)
+            .unwrap_or(Subsystem::Other as u8);
+        *base = subsystem;
+        TOTAL.add(layout.size());
+        PER_SUBSYSTEM[subsystem as usize].add(layout.size());
+        base.add(offset)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // the layout was valid when allocating, hence the header fits as well
+        let Some((outer_layout, offset)) = with_header(layout) else {
+            return;
+        };
+        let base = ptr.sub(offset);
+
+        TOTAL.sub(layout.size());
+        PER_SUBSYSTEM[*base as usize].sub(layout.size());
+        System.dealloc(base, outer_layout);
+    }
+}
+
+// [impl->swdd~common-reports-memory-usage~1]
+/// Logs the heap usage in total and per subsystem together with the given channel usages.
+pub fn log_memory_usage(channel_usages: &[ChannelUsage]) {
+    let subsystems = SUBSYSTEMS
+        .iter()
+        .map(|subsystem| format!("{}: {}", subsystem, heap_usage(*subsystem)))
+        .collect::<Vec<String>>()
+        .join(", ");
+    log::info!("Heap usage: {} ({})", total_heap_usage(), subsystems);
+
+    for channel_usage in channel_usages {
+        log::info!("{}", channel_usage);
+    }
+}
+
+// [impl->swdd~common-reports-memory-usage~1]
+/// Logs the memory usage in the given interval. The channels are probed on every report,
+/// a closed channel is expected to be left out.
+pub async fn report_periodically<F>(interval: Duration, channel_usages: F)
+where
+    F: Fn() -> Vec<ChannelUsage>,
+{
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        interval.tick().await;
+        log_memory_usage(&channel_usages());
+    }
+}
+
+// The messages waiting in a channel are moved into the buffer of the channel.
+// Data owned by the messages, e.g., strings, is counted by the subsystem creating it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelUsage {
+    pub name: &'static str,
+    pub queued: usize,
+    pub capacity: usize,
+    pub queued_bytes: usize,
+}
+
+impl ChannelUsage {
+    pub fn of<T>(name: &'static str, sender: &tokio::sync::mpsc::Sender<T>) -> Self {
+        let queued = sender.max_capacity() - sender.capacity();
+        ChannelUsage {
+            name,
+            queued,
+            capacity: sender.max_capacity(),
+            queued_bytes: queued * std::mem::size_of::<T>(),
+        }
+    }
+}
+
+impl fmt::Display for ChannelUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Channel '{}': {} of {} messages queued ({} B)",
+            self.name, self.queued, self.capacity, self.queued_bytes
+        )
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout};
+
+    use super::{enter, heap_usage, ChannelUsage, HeapUsage, Subsystem, TrackingAllocator};
+
+    // [utest->swdd~common-tracks-heap-usage-per-subsystem~1]
+    #[test]
+    fn utest_tracking_allocator_attributes_allocations_to_entered_subsystem() {
+        let layout = Layout::from_size_align(100, 32).unwrap();
+
+        let ptr = {
+            let _scope = enter(Subsystem::SchedulerQueues);
+            unsafe { TrackingAllocator.alloc(layout) }
+        };
+        assert!(!ptr.is_null());
+        assert_eq!(ptr as usize % 32, 0);
+        assert_eq!(
+            heap_usage(Subsystem::SchedulerQueues),
+            HeapUsage {
+                current: 100,
+                peak: 100
+            }
+        );
+
+        // freed outside of the scope, still booked on the subsystem of the allocation
+        unsafe { TrackingAllocator.dealloc(ptr, layout) };
+        assert_eq!(
+            heap_usage(Subsystem::SchedulerQueues),
+            HeapUsage {
+                current: 0,
+                peak: 100
+            }
+        );
+    }
+
+    // [utest->swdd~common-tracks-heap-usage-per-subsystem~1]
+    #[test]
+    fn utest_subsystem_scope_restores_previous_subsystem() {
+        let layout = Layout::from_size_align(8, 8).unwrap();
+        let _outer = enter(Subsystem::StateStorage);
+        drop(enter(Subsystem::SchedulerQueues));
+
+        let ptr = unsafe { TrackingAllocator.alloc(layout) };
+        assert_eq!(heap_usage(Subsystem::StateStorage).current, 8);
+        unsafe { TrackingAllocator.dealloc(ptr, layout) };
+        assert_eq!(heap_usage(Subsystem::StateStorage).current, 0);
+    }
+
+    // [utest->swdd~common-reports-memory-usage~1]
+    #[tokio::test]
+    async fn utest_channel_usage_counts_queued_messages() {
+        let (sender, _receiver) = tokio::sync::mpsc::channel::<u64>(4);
+        sender.send(1).await.unwrap();
+        sender.send(2).await.unwrap();
+
+        assert_eq!(
+            ChannelUsage::of("test", &sender),
+            ChannelUsage {
+                name: "test",
+                queued: 2,
+                capacity: 4,
+                queued_bytes: 16,
+            }
+        );
+    }
+}
@@ -0,0 +1,270 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Deserialize;
+
+pub const OCI_REFERENCE_PREFIX: &str = "oci://";
+
+const ORAS_CMD: &str = "oras";
+const COSIGN_CMD: &str = "cosign";
+
+#[derive(Debug, Deserialize)]
+struct OciManifest {
+    layers: Vec<OciDescriptor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OciDescriptor {
+    digest: String,
+}
+
+pub fn is_oci_reference(path: &str) -> bool {
+    path.starts_with(OCI_REFERENCE_PREFIX)
+}
+
+// [impl->swdd~common-pulls-manifests-from-oci-registries~1]
+// [impl->swdd~common-verifies-signatures-of-oci-manifests~1]
+/// Pulls an Ankaios manifest stored as single layer OCI artifact, e.g. `oci://registry/app-config:tag`.
+///
+/// A tag is resolved to the digest of the artifact first and all further steps use the pinned digest.
+/// If a public key is given, the signature of the artifact is verified with `cosign` before the content is pulled.
+pub fn pull_manifest(oci_reference: &str, public_key: Option<&str>) -> Result<String, String> {
+    let reference = oci_reference
+        .strip_prefix(OCI_REFERENCE_PREFIX)
+        .ok_or_else(|| format!("'{oci_reference}' is not an OCI reference."))?;
+    let repository = repository_of(reference);
+
+    let digest = match reference.split_once('@') {
+        Some((_, digest)) => digest.to_owned(),
+        None => run_command(ORAS_CMD, &["resolve", reference])
+            .map_err(|err| format!("Could not resolve '{reference}': '{err}'"))?
+            .trim()
+            .to_owned(),
+    };
+    let pinned_reference = format!("{repository}@{digest}");
+    log::debug!("Pulling manifest '{pinned_reference}' for '{oci_reference}'");
+
+    if let Some(public_key) = public_key {
+        run_command(
+            COSIGN_CMD,
+            &["verify", "--key", public_key, pinned_reference.as_str()],
+        )
+        .map_err(|err| format!("Signature verification of '{pinned_reference}' failed: '{err}'"))?;
+    }
+
+    let oci_manifest: OciManifest = serde_yaml::from_str(
+        &run_command(ORAS_CMD, &["manifest", "fetch", pinned_reference.as_str()])
+            .map_err(|err| format!("Could not fetch '{pinned_reference}': '{err}'"))?,
+    )
+    .map_err(|err| format!("Could not parse the OCI manifest of '{pinned_reference}': '{err}'"))?;
+
+    let [layer] = oci_manifest.layers.as_slice() else {
+        return Err(format!(
+            "'{pinned_reference}' has {} layers, but exactly one layer containing the Ankaios manifest is expected.",
+            oci_manifest.layers.len()
+        ));
+    };
+
+    let layer_reference = format!("{repository}@{}", layer.digest);
+    run_command(
+        ORAS_CMD,
+        &["blob", "fetch", "--output", "-", layer_reference.as_str()],
+    )
+    .map_err(|err| format!("Could not fetch '{layer_reference}': '{err}'"))
+}
+
+// removes the tag or digest from the reference, a colon before the last slash belongs to the registry port
+fn repository_of(reference: &str) -> &str {
+    let reference = reference
+        .split_once('@')
+        .map_or(reference, |(repository, _)| repository);
+    let name_start = reference.rfind('/').map_or(0, |index| index + 1);
+    match reference[name_start..].find(':') {
+        Some(tag_start) => &reference[..name_start + tag_start],
+        None => reference,
+    }
+}
+
+#[cfg(not(test))]
+fn run_command(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|err| format!("Could not execute '{program}': '{err}'"))?;
+    if output.status.success() {
+        String::from_utf8(output.stdout).map_err(|err| err.to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_owned())
+    }
+}
+#[cfg(test)]
+use tests::run_command_mock as run_command;
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::VecDeque};
+
+    use super::{is_oci_reference, pull_manifest, repository_of};
+
+    const DIGEST: &str = "sha256:1111";
+    const LAYER_DIGEST: &str = "sha256:2222";
+    const MANIFEST_CONTENT: &str = "apiVersion: v0.1\nworkloads: {}\n";
+
+    type ExpectedCommand = (String, Result<String, String>);
+
+    thread_local! {
+        static EXPECTED_COMMANDS: RefCell<VecDeque<ExpectedCommand>> = const { RefCell::new(VecDeque::new()) };
+    }
+
+    pub fn run_command_mock(program: &str, args: &[&str]) -> Result<String, String> {
+        let command = format!("{program} {}", args.join(" "));
+        let (expected_command, result) = EXPECTED_COMMANDS
+            .with(|commands| commands.borrow_mut().pop_front())
+            .unwrap_or_else(|| panic!("Unexpected command '{command}'"));
+        assert_eq!(command, expected_command);
+        result
+    }
+
+    fn expect_command(command: &str, result: Result<&str, &str>) {
+        EXPECTED_COMMANDS.with(|commands| {
+            commands.borrow_mut().push_back((
+                command.to_owned(),
+                result.map(ToOwned::to_owned).map_err(ToOwned::to_owned),
+            ))
+        });
+    }
+
+    fn assert_all_commands_executed() {
+        EXPECTED_COMMANDS.with(|commands| assert!(commands.borrow().is_empty()));
+    }
+
+    fn oci_manifest_with_layers(layers: &[&str]) -> String {
+        let layers: Vec<String> = layers
+            .iter()
+            .map(|digest| format!(r#"{{"mediaType": "application/yaml", "digest": "{digest}"}}"#))
+            .collect();
+        format!(
+            r#"{{"schemaVersion": 2, "layers": [{}]}}"#,
+            layers.join(", ")
+        )
+    }
+
+    #[test]
+    fn utest_is_oci_reference() {
+        assert!(is_oci_reference("oci://registry/app-config:1.0"));
+        assert!(!is_oci_reference("/etc/ankaios/state.yaml"));
+    }
+
+    #[test]
+    fn utest_repository_of_removes_tag_and_digest() {
+        assert_eq!(
+            repository_of("registry:5000/app-config:1.0"),
+            "registry:5000/app-config"
+        );
+        assert_eq!(
+            repository_of("registry/app-config@sha256:1111"),
+            "registry/app-config"
+        );
+        assert_eq!(
+            repository_of("registry:5000/app-config"),
+            "registry:5000/app-config"
+        );
+    }
+
+    // [utest->swdd~common-pulls-manifests-from-oci-registries~1]
+    #[test]
+    fn utest_pull_manifest_resolves_tag_and_pins_digest() {
+        expect_command("oras resolve registry/app-config:1.0", Ok("sha256:1111\n"));
+        expect_command(
+            "oras manifest fetch registry/app-config@sha256:1111",
+            Ok(&oci_manifest_with_layers(&[LAYER_DIGEST])),
+        );
+        expect_command(
+            "oras blob fetch --output - registry/app-config@sha256:2222",
+            Ok(MANIFEST_CONTENT),
+        );
+
+        assert_eq!(
+            pull_manifest("oci://registry/app-config:1.0", None),
+            Ok(MANIFEST_CONTENT.to_owned())
+        );
+        assert_all_commands_executed();
+    }
+
+    // [utest->swdd~common-pulls-manifests-from-oci-registries~1]
+    // [utest->swdd~common-verifies-signatures-of-oci-manifests~1]
+    #[test]
+    fn utest_pull_manifest_with_digest_verifies_signature() {
+        expect_command(
+            "cosign verify --key cosign.pub registry/app-config@sha256:1111",
+            Ok(""),
+        );
+        expect_command(
+            "oras manifest fetch registry/app-config@sha256:1111",
+            Ok(&oci_manifest_with_layers(&[LAYER_DIGEST])),
+        );
+        expect_command(
+            "oras blob fetch --output - registry/app-config@sha256:2222",
+            Ok(MANIFEST_CONTENT),
+        );
+
+        assert_eq!(
+            pull_manifest(
+                &format!("oci://registry/app-config@{DIGEST}"),
+                Some("cosign.pub")
+            ),
+            Ok(MANIFEST_CONTENT.to_owned())
+        );
+        assert_all_commands_executed();
+    }
+
+    // [utest->swdd~common-verifies-signatures-of-oci-manifests~1]
+    #[test]
+    fn utest_pull_manifest_fails_on_invalid_signature() {
+        expect_command(
+            "cosign verify --key cosign.pub registry/app-config@sha256:1111",
+            Err("no matching signatures"),
+        );
+
+        let result = pull_manifest(
+            &format!("oci://registry/app-config@{DIGEST}"),
+            Some("cosign.pub"),
+        );
+
+        assert!(result.unwrap_err().contains("no matching signatures"));
+        assert_all_commands_executed();
+    }
+
+    // [utest->swdd~common-pulls-manifests-from-oci-registries~1]
+    #[test]
+    fn utest_pull_manifest_fails_on_multiple_layers() {
+        expect_command(
+            "oras manifest fetch registry/app-config@sha256:1111",
+            Ok(&oci_manifest_with_layers(&[LAYER_DIGEST, LAYER_DIGEST])),
+        );
+
+        let result = pull_manifest(&format!("oci://registry/app-config@{DIGEST}"), None);
+
+        assert!(result.unwrap_err().contains("has 2 layers"));
+        assert_all_commands_executed();
+    }
+}
@@ -0,0 +1,413 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fmt, str::FromStr};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+// Persisted files start with a header line naming the version and the format of the content,
+// e.g., "ANKAIOS-PERSISTENCE/1 cbor". Files written before the header was introduced are
+// treated as version 0 in the JSON format.
+const HEADER_PREFIX: &str = "ANKAIOS-PERSISTENCE/";
+pub const CURRENT_VERSION: u32 = 1;
+const LEGACY_VERSION: u32 = 0;
+const RECORD_LENGTH_SIZE: usize = 4;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PersistenceFormat {
+    #[default]
+    Json,
+    Cbor,
+    Bincode,
+}
+
+impl fmt::Display for PersistenceFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistenceFormat::Json => write!(f, "json"),
+            PersistenceFormat::Cbor => write!(f, "cbor"),
+            PersistenceFormat::Bincode => write!(f, "bincode"),
+        }
+    }
+}
+
+impl FromStr for PersistenceFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "json" => Ok(PersistenceFormat::Json),
+            "cbor" => Ok(PersistenceFormat::Cbor),
+            "bincode" => Ok(PersistenceFormat::Bincode),
+            _ => Err(format!(
+                "Unsupported persistence format '{value}', expected 'json', 'cbor' or 'bincode'."
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PersistenceHeader {
+    pub version: u32,
+    pub format: PersistenceFormat,
+}
+
+impl PersistenceHeader {
+    pub fn current(format: PersistenceFormat) -> Self {
+        PersistenceHeader {
+            version: CURRENT_VERSION,
+            format,
+        }
+    }
+
+    // A file needs to be rewritten if it was written by an older version or in another format.
+    pub fn needs_migration_to(&self, format: PersistenceFormat) -> bool {
+        *self != PersistenceHeader::current(format)
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        format!("{HEADER_PREFIX}{} {}\n", self.version, self.format).into_bytes()
+    }
+
+    fn decode(content: &[u8]) -> Result<(PersistenceHeader, &[u8]), String> {
+        if !content.starts_with(HEADER_PREFIX.as_bytes()) {
+            return Ok((
+                PersistenceHeader {
+                    version: LEGACY_VERSION,
+                    format: PersistenceFormat::Json,
+                },
+                content,
+            ));
+        }
+
+        let line_end = content
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .ok_or_else(|| "Incomplete persistence header.".to_string())?;
+        let line = std::str::from_utf8(&content[HEADER_PREFIX.len()..line_end])
+            .map_err(|_| "Invalid persistence header.".to_string())?;
+        let (version, format) = line
+            .split_once(' ')
+            .ok_or_else(|| format!("Invalid persistence header '{line}'."))?;
+        let version: u32 = version
+            .parse()
+            .map_err(|_| format!("Invalid persistence version '{version}'."))?;
+        if version > CURRENT_VERSION {
+            return Err(format!(
+                "Unsupported persistence version '{version}', the latest supported version is '{CURRENT_VERSION}'."
+            ));
+        }
+
+        Ok((
+            PersistenceHeader {
+                version,
+                format: format.parse()?,
+            },
+            &content[line_end + 1..],
+        ))
+    }
+}
+
+// bincode is not self-describing and hence cannot restore structures skipping fields on
+// serialization, e.g., with `skip_serializing_if`. The values are therefore stored in bincode as
+// a tree of tagged values.
+#[derive(Serialize, Deserialize)]
+enum TaggedValue {
+    Null,
+    Bool(bool),
+    Unsigned(u64),
+    Signed(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<TaggedValue>),
+    Object(Vec<(String, TaggedValue)>),
+}
+
+impl From<serde_json::Value> for TaggedValue {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => TaggedValue::Null,
+            serde_json::Value::Bool(value) => TaggedValue::Bool(value),
+            serde_json::Value::Number(number) => {
+                if let Some(value) = number.as_u64() {
+                    TaggedValue::Unsigned(value)
+                } else if let Some(value) = number.as_i64() {
+                    TaggedValue::Signed(value)
+                } else {
+                    TaggedValue::Float(number.as_f64().unwrap_or_default())
+                }
+            }
+            serde_json::Value::String(value) => TaggedValue::String(value),
+            serde_json::Value::Array(values) => {
+                TaggedValue::Array(values.into_iter().map(Into::into).collect())
+            }
+            serde_json::Value::Object(fields) => TaggedValue::Object(
+                fields
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<TaggedValue> for serde_json::Value {
+    fn from(value: TaggedValue) -> Self {
+        match value {
+            TaggedValue::Null => serde_json::Value::Null,
+            TaggedValue::Bool(value) => value.into(),
+            TaggedValue::Unsigned(value) => value.into(),
+            TaggedValue::Signed(value) => value.into(),
+            TaggedValue::Float(value) => value.into(),
+            TaggedValue::String(value) => value.into(),
+            TaggedValue::Array(values) => {
+                serde_json::Value::Array(values.into_iter().map(Into::into).collect())
+            }
+            TaggedValue::Object(fields) => serde_json::Value::Object(
+                fields
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+fn serialize<T: Serialize>(format: PersistenceFormat, value: &T) -> Result<Vec<u8>, String> {
+    match format {
+        PersistenceFormat::Json => serde_json::to_vec(value).map_err(|err| err.to_string()),
+        PersistenceFormat::Cbor => {
+            let mut content = Vec::new();
+            ciborium::into_writer(value, &mut content).map_err(|err| err.to_string())?;
+            Ok(content)
+        }
+        PersistenceFormat::Bincode => {
+            let value: TaggedValue = serde_json::to_value(value)
+                .map_err(|err| err.to_string())?
+                .into();
+            bincode::serialize(&value).map_err(|err| err.to_string())
+        }
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(
+    format: PersistenceFormat,
+    content: &[u8],
+) -> Result<T, String> {
+    match format {
+        PersistenceFormat::Json => serde_json::from_slice(content).map_err(|err| err.to_string()),
+        PersistenceFormat::Cbor => ciborium::from_reader(content).map_err(|err| err.to_string()),
+        PersistenceFormat::Bincode => {
+            let value: TaggedValue =
+                bincode::deserialize(content).map_err(|err| err.to_string())?;
+            serde_json::from_value(value.into()).map_err(|err| err.to_string())
+        }
+    }
+}
+
+// [impl->swdd~common-persists-in-configured-format~1]
+/// Encodes a single value together with the header of the current version.
+pub fn encode<T: Serialize>(format: PersistenceFormat, value: &T) -> Result<Vec<u8>, String> {
+    let mut content = PersistenceHeader::current(format).encode();
+    content.extend(serialize(format, value)?);
+    Ok(content)
+}
+
+// [impl->swdd~common-loads-persisted-content-of-any-format~1]
+/// Decodes a single value in the format and version given in its header.
+pub fn decode<T: DeserializeOwned>(content: &[u8]) -> Result<(T, PersistenceHeader), String> {
+    let (header, payload) = PersistenceHeader::decode(content)?;
+    Ok((deserialize(header.format, payload)?, header))
+}
+
+// [impl->swdd~common-persists-in-configured-format~1]
+/// Encodes the header of a file of records, which are appended with [`encode_record`].
+pub fn encode_records_header(format: PersistenceFormat) -> Vec<u8> {
+    PersistenceHeader::current(format).encode()
+}
+
+// [impl->swdd~common-persists-in-configured-format~1]
+/// Encodes a record to be appended to a file of records. In JSON a record is a line, in the
+/// binary formats it is prefixed with its length.
+pub fn encode_record<T: Serialize>(
+    format: PersistenceFormat,
+    value: &T,
+) -> Result<Vec<u8>, String> {
+    let mut record = serialize(format, value)?;
+    if format == PersistenceFormat::Json {
+        record.push(b'\n');
+        return Ok(record);
+    }
+
+    let length = u32::try_from(record.len()).map_err(|_| "Record too large.".to_string())?;
+    let mut content = length.to_le_bytes().to_vec();
+    content.append(&mut record);
+    Ok(content)
+}
+
+// [impl->swdd~common-loads-persisted-content-of-any-format~1]
+/// Decodes a file of records. An invalid record is returned as error without affecting the
+/// others, except for a truncated binary record, which ends the file.
+pub fn decode_records<T: DeserializeOwned>(
+    content: &[u8],
+) -> Result<(Vec<Result<T, String>>, PersistenceHeader), String> {
+    let (header, mut payload) = PersistenceHeader::decode(content)?;
+
+    let mut records = Vec::new();
+    if header.format == PersistenceFormat::Json {
+        for line in payload.split(|byte| *byte == b'\n') {
+            if !line.iter().all(u8::is_ascii_whitespace) {
+                records.push(deserialize(header.format, line));
+            }
+        }
+        return Ok((records, header));
+    }
+
+    while !payload.is_empty() {
+        if payload.len() < RECORD_LENGTH_SIZE {
+            records.push(Err("Truncated record.".to_string()));
+            break;
+        }
+        let (length, rest) = payload.split_at(RECORD_LENGTH_SIZE);
+        let length = u32::from_le_bytes([length[0], length[1], length[2], length[3]]) as usize;
+        if length > rest.len() {
+            records.push(Err("Truncated record.".to_string()));
+            break;
+        }
+        records.push(deserialize(header.format, &rest[..length]));
+        payload = &rest[length..];
+    }
+    Ok((records, header))
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::{
+        decode, decode_records, encode, encode_record, encode_records_header, PersistenceFormat,
+        PersistenceHeader, CURRENT_VERSION,
+    };
+
+    const FORMATS: [PersistenceFormat; 3] = [
+        PersistenceFormat::Json,
+        PersistenceFormat::Cbor,
+        PersistenceFormat::Bincode,
+    ];
+
+    fn value() -> HashMap<String, Vec<u32>> {
+        HashMap::from([("workload_1".to_string(), vec![1, 2, 3])])
+    }
+
+    // [utest->swdd~common-persists-in-configured-format~1]
+    // [utest->swdd~common-loads-persisted-content-of-any-format~1]
+    #[test]
+    fn utest_persistence_encodes_and_decodes_value_in_all_formats() {
+        for format in FORMATS {
+            let content = encode(format, &value()).unwrap();
+
+            assert_eq!(
+                decode::<HashMap<String, Vec<u32>>>(&content),
+                Ok((value(), PersistenceHeader::current(format)))
+            );
+        }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct WithSkippedFields {
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        skipped: String,
+        kept: i64,
+    }
+
+    // [utest->swdd~common-persists-in-configured-format~1]
+    // [utest->swdd~common-loads-persisted-content-of-any-format~1]
+    #[test]
+    fn utest_persistence_restores_skipped_fields_in_all_formats() {
+        for format in FORMATS {
+            let value = WithSkippedFields {
+                skipped: String::new(),
+                kept: -1,
+            };
+            let content = encode(format, &value).unwrap();
+
+            assert_eq!(decode::<WithSkippedFields>(&content).unwrap().0, value);
+        }
+    }
+
+    // [utest->swdd~common-loads-persisted-content-of-any-format~1]
+    #[test]
+    fn utest_persistence_decodes_legacy_json_without_header() {
+        let (decoded, header) =
+            decode::<HashMap<String, Vec<u32>>>(br#"{"workload_1":[1,2,3]}"#).unwrap();
+
+        assert_eq!(decoded, value());
+        assert_eq!(header.version, 0);
+        assert_eq!(header.format, PersistenceFormat::Json);
+        assert!(header.needs_migration_to(PersistenceFormat::Json));
+    }
+
+    // [utest->swdd~common-loads-persisted-content-of-any-format~1]
+    #[test]
+    fn utest_persistence_rejects_newer_version() {
+        let content = format!("ANKAIOS-PERSISTENCE/{} json\n{{}}", CURRENT_VERSION + 1);
+
+        assert!(decode::<HashMap<String, Vec<u32>>>(content.as_bytes()).is_err());
+    }
+
+    // [utest->swdd~common-persists-in-configured-format~1]
+    // [utest->swdd~common-loads-persisted-content-of-any-format~1]
+    #[test]
+    fn utest_persistence_encodes_and_decodes_records_in_all_formats() {
+        for format in FORMATS {
+            let mut content = encode_records_header(format);
+            content.extend(encode_record(format, &"first".to_string()).unwrap());
+            content.extend(encode_record(format, &"second".to_string()).unwrap());
+
+            let (records, header) = decode_records::<String>(&content).unwrap();
+
+            assert_eq!(header, PersistenceHeader::current(format));
+            assert_eq!(
+                records,
+                vec![Ok("first".to_string()), Ok("second".to_string())]
+            );
+        }
+    }
+
+    // [utest->swdd~common-loads-persisted-content-of-any-format~1]
+    #[test]
+    fn utest_persistence_decodes_truncated_binary_records() {
+        let mut content = encode_records_header(PersistenceFormat::Cbor);
+        content.extend(encode_record(PersistenceFormat::Cbor, &"first".to_string()).unwrap());
+        let second = encode_record(PersistenceFormat::Cbor, &"second".to_string()).unwrap();
+        content.extend(&second[..second.len() - 1]);
+
+        let (records, _) = decode_records::<String>(&content).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0], Ok("first".to_string()));
+        assert!(records[1].is_err());
+    }
+}
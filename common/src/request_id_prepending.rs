@@ -15,6 +15,17 @@ use crate::std_extensions::UnreachableOption;
 
 const SEPARATOR: &str = "@";
 
+// The gRPC server names the connection of each Ankaios CLI with this prefix and a unique id.
+pub const CLI_CONNECTION_PREFIX: &str = "cli-conn-";
+// The cloud connector of the Ankaios server prefixes its request ids with this name.
+pub const CLOUD_CONNECTOR_CONNECTION_NAME: &str = "cloud-connector";
+
+// The connection names assigned by the Ankaios server identify the modifier of the desired
+// state, thus an agent must not connect with such a name.
+pub fn is_reserved_connection_name(name: &str) -> bool {
+    name.starts_with(CLI_CONNECTION_PREFIX) || name == CLOUD_CONNECTOR_CONNECTION_NAME
+}
+
 pub fn prepend_request_id(request_id: &str, agent_name: &str) -> String {
     if request_id.is_empty() {
         return String::from("");
@@ -42,6 +53,14 @@ pub fn detach_prefix_from_request_id(request_id: &str) -> (String, String) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn utest_is_reserved_connection_name() {
+        assert!(is_reserved_connection_name("cli-conn-1234"));
+        assert!(is_reserved_connection_name("cloud-connector"));
+        assert!(!is_reserved_connection_name("agent_A"));
+        assert!(!is_reserved_connection_name("cloud-connector-2"));
+    }
+
     #[test]
     fn utest_prepend_request_id_returns_empty_when_provided_request_id_is_empty() {
         assert_eq!(String::from(""), prepend_request_id("", "don't care"));
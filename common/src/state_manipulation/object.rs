@@ -300,6 +300,7 @@ mod tests {
                 "agent",
                 ExecutionState::running(),
             )],
+            ..Default::default()
         };
 
         let expected = Object {
@@ -326,6 +327,7 @@ mod tests {
                 "agent",
                 ExecutionState::running(),
             )],
+            ..Default::default()
         };
         let actual: CompleteState = object.try_into().unwrap();
 
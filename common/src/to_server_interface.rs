@@ -14,19 +14,26 @@
 
 use crate::{
     commands::{self, RequestContent},
-    objects::CompleteState,
+    objects::{AgentResources, CompleteState, ControlInterfaceMetrics},
 };
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use tokio::sync::mpsc::error::SendError;
 
 #[allow(clippy::large_enum_variant)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub enum ToServer {
     AgentHello(commands::AgentHello),
     AgentGone(commands::AgentGone),
     Request(commands::Request),
     UpdateWorkloadState(commands::UpdateWorkloadState),
+    UpdateWorkloadAck(commands::UpdateWorkloadAck),
+    AgentEvent(commands::AgentEvent),
+    UpdateSchedulerQueue(commands::UpdateSchedulerQueue),
+    SubscribeWorkloadStates(commands::SubscribeWorkloadStates),
+    UpdateControlInterfaceMetrics(commands::UpdateControlInterfaceMetrics),
+    UpdateAgentResources(commands::UpdateAgentResources),
     Stop(commands::Stop),
     Goodbye(commands::Goodbye),
 }
@@ -48,7 +55,7 @@ impl fmt::Display for ToServerError {
 // [impl->swdd~to-server-channel~1]
 #[async_trait]
 pub trait ToServerInterface {
-    async fn agent_hello(&self, agent_name: String) -> Result<(), ToServerError>;
+    async fn agent_hello(&self, agent_hello: commands::AgentHello) -> Result<(), ToServerError>;
     async fn agent_gone(&self, agent_name: String) -> Result<(), ToServerError>;
     async fn update_state(
         &self,
@@ -56,15 +63,73 @@ pub trait ToServerInterface {
         state: CompleteState,
         update_mask: Vec<String>,
     ) -> Result<(), ToServerError>;
+    async fn request_update_state(
+        &self,
+        request_id: String,
+        update_state_request: commands::UpdateStateRequest,
+    ) -> Result<(), ToServerError>;
     async fn update_workload_state(
         &self,
         workload_running: Vec<crate::objects::WorkloadState>,
     ) -> Result<(), ToServerError>;
+    async fn update_workload_ack(
+        &self,
+        agent_name: String,
+        sequence_number: u64,
+    ) -> Result<(), ToServerError>;
+    async fn agent_event(&self, agent_event: commands::AgentEvent) -> Result<(), ToServerError>;
+    async fn update_scheduler_queue(
+        &self,
+        agent_name: String,
+        pending_operations: Vec<commands::PendingWorkloadOperation>,
+    ) -> Result<(), ToServerError>;
+    async fn subscribe_workload_states(
+        &self,
+        workload_names: Vec<String>,
+    ) -> Result<(), ToServerError>;
+    async fn update_control_interface_metrics(
+        &self,
+        metrics: ControlInterfaceMetrics,
+    ) -> Result<(), ToServerError>;
+    async fn update_agent_resources(
+        &self,
+        agent_name: String,
+        resources: AgentResources,
+    ) -> Result<(), ToServerError>;
     async fn request_complete_state(
         &self,
         request_id: String,
         request_complete_state: commands::CompleteStateRequest,
     ) -> Result<(), ToServerError>;
+    async fn request_rollout_status(&self, request_id: String) -> Result<(), ToServerError>;
+    async fn request_support_info(&self, request_id: String) -> Result<(), ToServerError>;
+    async fn request_events(
+        &self,
+        request_id: String,
+        events_request: commands::EventsRequest,
+    ) -> Result<(), ToServerError>;
+    async fn request_drain_agent(
+        &self,
+        request_id: String,
+        drain_agent_request: commands::DrainAgentRequest,
+    ) -> Result<(), ToServerError>;
+    async fn request_watch_complete_state(
+        &self,
+        request_id: String,
+        watch_complete_state_request: commands::WatchCompleteStateRequest,
+    ) -> Result<(), ToServerError>;
+    async fn cancel_watch(&self, request_id: String) -> Result<(), ToServerError>;
+    async fn request_impact_analysis(
+        &self,
+        request_id: String,
+        impact_analysis_request: commands::ImpactAnalysisRequest,
+    ) -> Result<(), ToServerError>;
+    async fn request_dependency_graph(&self, request_id: String) -> Result<(), ToServerError>;
+    async fn request_scheduler_queue(
+        &self,
+        request_id: String,
+        scheduler_queue_request: commands::SchedulerQueueRequest,
+    ) -> Result<(), ToServerError>;
     async fn stop(&self) -> Result<(), ToServerError>;
 }
 
@@ -73,10 +138,8 @@ pub type ToServerReceiver = tokio::sync::mpsc::Receiver<ToServer>;
 
 #[async_trait]
 impl ToServerInterface for ToServerSender {
-    async fn agent_hello(&self, agent_name: String) -> Result<(), ToServerError> {
-        Ok(self
-            .send(ToServer::AgentHello(commands::AgentHello { agent_name }))
-            .await?)
+    async fn agent_hello(&self, agent_hello: commands::AgentHello) -> Result<(), ToServerError> {
+        Ok(self.send(ToServer::AgentHello(agent_hello)).await?)
     }
 
     async fn agent_gone(&self, agent_name: String) -> Result<(), ToServerError> {
@@ -95,7 +158,29 @@ impl ToServerInterface for ToServerSender {
             .send(ToServer::Request(commands::Request {
                 request_id,
                 request_content: commands::RequestContent::UpdateStateRequest(Box::new(
-                    commands::UpdateStateRequest { state, update_mask },
+                    commands::UpdateStateRequest {
+                        state,
+                        update_mask,
+                        deadline_ms: None,
+                        rollback_on_deadline_exceeded: false,
+                        force: false,
+                    },
+                )),
+            }))
+            .await?)
+    }
+
+    // [impl->swdd~server-propagates-update-deadline~1]
+    async fn request_update_state(
+        &self,
+        request_id: String,
+        update_state_request: commands::UpdateStateRequest,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: commands::RequestContent::UpdateStateRequest(Box::new(
+                    update_state_request,
                 )),
             }))
             .await?)
@@ -114,6 +199,75 @@ impl ToServerInterface for ToServerSender {
             .await?)
     }
 
+    async fn update_workload_ack(
+        &self,
+        agent_name: String,
+        sequence_number: u64,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::UpdateWorkloadAck(commands::UpdateWorkloadAck {
+                agent_name,
+                sequence_number,
+            }))
+            .await?)
+    }
+
+    async fn agent_event(&self, agent_event: commands::AgentEvent) -> Result<(), ToServerError> {
+        Ok(self.send(ToServer::AgentEvent(agent_event)).await?)
+    }
+
+    async fn update_scheduler_queue(
+        &self,
+        agent_name: String,
+        pending_operations: Vec<commands::PendingWorkloadOperation>,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::UpdateSchedulerQueue(
+                commands::UpdateSchedulerQueue {
+                    agent_name,
+                    pending_operations,
+                },
+            ))
+            .await?)
+    }
+
+    async fn subscribe_workload_states(
+        &self,
+        workload_names: Vec<String>,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::SubscribeWorkloadStates(
+                commands::SubscribeWorkloadStates { workload_names },
+            ))
+            .await?)
+    }
+
+    async fn update_control_interface_metrics(
+        &self,
+        metrics: ControlInterfaceMetrics,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::UpdateControlInterfaceMetrics(
+                commands::UpdateControlInterfaceMetrics { metrics },
+            ))
+            .await?)
+    }
+
+    async fn update_agent_resources(
+        &self,
+        agent_name: String,
+        resources: AgentResources,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::UpdateAgentResources(
+                commands::UpdateAgentResources {
+                    agent_name,
+                    resources,
+                },
+            ))
+            .await?)
+    }
+
     async fn request_complete_state(
         &self,
         request_id: String,
@@ -131,6 +285,117 @@ impl ToServerInterface for ToServerSender {
             .await?)
     }
 
+    async fn request_rollout_status(&self, request_id: String) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::RolloutStatusRequest(
+                    commands::RolloutStatusRequest {},
+                ),
+            }))
+            .await?)
+    }
+
+    async fn request_support_info(&self, request_id: String) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::SupportInfoRequest(
+                    commands::SupportInfoRequest {},
+                ),
+            }))
+            .await?)
+    }
+
+    async fn request_events(
+        &self,
+        request_id: String,
+        events_request: commands::EventsRequest,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::EventsRequest(events_request),
+            }))
+            .await?)
+    }
+
+    async fn request_drain_agent(
+        &self,
+        request_id: String,
+        drain_agent_request: commands::DrainAgentRequest,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::DrainAgentRequest(drain_agent_request),
+            }))
+            .await?)
+    }
+
+    async fn request_watch_complete_state(
+        &self,
+        request_id: String,
+        watch_complete_state_request: commands::WatchCompleteStateRequest,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::WatchCompleteStateRequest(
+                    watch_complete_state_request,
+                ),
+            }))
+            .await?)
+    }
+
+    async fn cancel_watch(&self, request_id: String) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::CancelWatchRequest(
+                    commands::CancelWatchRequest {},
+                ),
+            }))
+            .await?)
+    }
+
+    async fn request_impact_analysis(
+        &self,
+        request_id: String,
+        impact_analysis_request: commands::ImpactAnalysisRequest,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::ImpactAnalysisRequest(impact_analysis_request),
+            }))
+            .await?)
+    }
+
+    async fn request_dependency_graph(&self, request_id: String) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::DependencyGraphRequest(
+                    commands::DependencyGraphRequest {},
+                ),
+            }))
+            .await?)
+    }
+
+    async fn request_scheduler_queue(
+        &self,
+        request_id: String,
+        scheduler_queue_request: commands::SchedulerQueueRequest,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::SchedulerQueueRequest(scheduler_queue_request),
+            }))
+            .await?)
+    }
+
     async fn stop(&self) -> Result<(), ToServerError> {
         Ok(self.send(ToServer::Stop(commands::Stop {})).await?)
     }
@@ -146,9 +411,14 @@ impl ToServerInterface for ToServerSender {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use crate::{
         commands::{self, RequestContent},
-        objects::{generate_test_workload_spec, generate_test_workload_state, ExecutionState},
+        objects::{
+            generate_test_workload_spec, generate_test_workload_state, AgentResources,
+            ControlInterfaceMetrics, ExecutionState,
+        },
         test_utils::generate_test_complete_state,
         to_server_interface::{ToServer, ToServerInterface},
     };
@@ -160,20 +430,24 @@ mod tests {
     const AGENT_NAME: &str = "agent_A";
     const REQUEST_ID: &str = "emkw489ejf89ml";
     const FIELD_MASK: &str = "desiredState.bla_bla";
+    const ROLLOUT_GROUP: &str = "canary";
 
     #[tokio::test]
     async fn utest_to_server_send_agent_hello() {
         let (tx, mut rx): (ToServerSender, ToServerReceiver) =
             tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
 
-        assert!(tx.agent_hello(AGENT_NAME.to_string()).await.is_ok());
+        let agent_hello = commands::AgentHello {
+            agent_name: AGENT_NAME.to_string(),
+            rollout_group: Some(ROLLOUT_GROUP.to_string()),
+            agent_version: "0.4.0".to_string(),
+            runtimes: vec!["podman".to_string()],
+            attributes: HashMap::from([("camera".to_string(), "true".to_string())]),
+            local_workloads: HashMap::new(),
+        };
+        assert!(tx.agent_hello(agent_hello.clone()).await.is_ok());
 
-        assert_eq!(
-            rx.recv().await.unwrap(),
-            ToServer::AgentHello(commands::AgentHello {
-                agent_name: AGENT_NAME.to_string()
-            })
-        )
+        assert_eq!(rx.recv().await.unwrap(), ToServer::AgentHello(agent_hello))
     }
 
     // [utest->swdd~to-server-channel~1]
@@ -216,13 +490,46 @@ mod tests {
                 request_content: commands::RequestContent::UpdateStateRequest(Box::new(
                     commands::UpdateStateRequest {
                         state: complete_state,
-                        update_mask: vec![FIELD_MASK.to_string()]
+                        update_mask: vec![FIELD_MASK.to_string()],
+                        deadline_ms: None,
+                        rollback_on_deadline_exceeded: false,
+                        force: false,
                     },
                 )),
             })
         )
     }
 
+    // [utest->swdd~to-server-channel~1]
+    // [utest->swdd~server-propagates-update-deadline~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_update_state() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let update_state_request = commands::UpdateStateRequest {
+            state: generate_test_complete_state(vec![generate_test_workload_spec()]),
+            update_mask: vec![FIELD_MASK.to_string()],
+            deadline_ms: Some(5000),
+            rollback_on_deadline_exceeded: true,
+            force: false,
+        };
+        assert!(tx
+            .request_update_state(REQUEST_ID.to_string(), update_state_request.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: commands::RequestContent::UpdateStateRequest(Box::new(
+                    update_state_request
+                )),
+            })
+        )
+    }
+
     // [utest->swdd~to-server-channel~1]
     #[tokio::test]
     async fn utest_to_server_send_update_workload_state() {
@@ -243,6 +550,136 @@ mod tests {
         )
     }
 
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_update_workload_ack() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        assert!(tx
+            .update_workload_ack(AGENT_NAME.to_string(), 42)
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::UpdateWorkloadAck(commands::UpdateWorkloadAck {
+                agent_name: AGENT_NAME.to_string(),
+                sequence_number: 42,
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_agent_event() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let agent_event = commands::AgentEvent {
+            agent_name: AGENT_NAME.to_string(),
+            kind: commands::EventKind::ControlInterfaceClosed,
+            workload_name: WORKLOAD_NAME.to_string(),
+            message: "message".to_string(),
+        };
+        assert!(tx.agent_event(agent_event.clone()).await.is_ok());
+
+        assert_eq!(rx.recv().await.unwrap(), ToServer::AgentEvent(agent_event))
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_update_scheduler_queue() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let pending_operations = vec![commands::PendingWorkloadOperation {
+            workload_name: WORKLOAD_NAME.to_string(),
+            agent: AGENT_NAME.to_string(),
+            operation: commands::PendingOperation::Delete,
+            unfulfilled_dependencies: vec![],
+        }];
+        assert!(tx
+            .update_scheduler_queue(AGENT_NAME.to_string(), pending_operations.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::UpdateSchedulerQueue(commands::UpdateSchedulerQueue {
+                agent_name: AGENT_NAME.to_string(),
+                pending_operations,
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_subscribe_workload_states() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        assert!(tx
+            .subscribe_workload_states(vec![WORKLOAD_NAME.to_string()])
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::SubscribeWorkloadStates(commands::SubscribeWorkloadStates {
+                workload_names: vec![WORKLOAD_NAME.to_string()],
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_update_control_interface_metrics() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let metrics = ControlInterfaceMetrics {
+            workload_name: WORKLOAD_NAME.to_string(),
+            forwarded_requests: 3,
+            ..Default::default()
+        };
+        assert!(tx
+            .update_control_interface_metrics(metrics.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::UpdateControlInterfaceMetrics(commands::UpdateControlInterfaceMetrics {
+                metrics
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_update_agent_resources() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let resources = AgentResources {
+            free_cpu_millis: 1500,
+            free_memory_bytes: 4096,
+        };
+        assert!(tx
+            .update_agent_resources(AGENT_NAME.to_string(), resources)
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::UpdateAgentResources(commands::UpdateAgentResources {
+                agent_name: AGENT_NAME.to_string(),
+                resources,
+            })
+        )
+    }
+
     // [utest->swdd~to-server-channel~1]
     #[tokio::test]
     async fn utest_to_server_send_request_complete_state() {
@@ -266,4 +703,212 @@ mod tests {
             })
         )
     }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_rollout_status() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        assert!(tx
+            .request_rollout_status(REQUEST_ID.to_string())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::RolloutStatusRequest(
+                    commands::RolloutStatusRequest {}
+                ),
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_support_info() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        assert!(tx
+            .request_support_info(REQUEST_ID.to_string())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::SupportInfoRequest(
+                    commands::SupportInfoRequest {}
+                ),
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_events() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let events_request = commands::EventsRequest {
+            since: 1000,
+            limit: 10,
+        };
+        assert!(tx
+            .request_events(REQUEST_ID.to_string(), events_request.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::EventsRequest(events_request),
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_impact_analysis() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let impact_analysis_request = commands::ImpactAnalysisRequest {
+            workload_names: vec![WORKLOAD_NAME.to_string()],
+        };
+        assert!(tx
+            .request_impact_analysis(REQUEST_ID.to_string(), impact_analysis_request.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::ImpactAnalysisRequest(impact_analysis_request),
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_dependency_graph() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        assert!(tx
+            .request_dependency_graph(REQUEST_ID.to_string())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::DependencyGraphRequest(
+                    commands::DependencyGraphRequest {}
+                ),
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_scheduler_queue() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let scheduler_queue_request = commands::SchedulerQueueRequest {
+            agent_name: AGENT_NAME.to_string(),
+        };
+        assert!(tx
+            .request_scheduler_queue(REQUEST_ID.to_string(), scheduler_queue_request.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::SchedulerQueueRequest(scheduler_queue_request),
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_drain_agent() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let drain_agent_request = commands::DrainAgentRequest {
+            agent_name: AGENT_NAME.to_string(),
+            target_agent: Some("agent_B".to_string()),
+            remove: true,
+        };
+        assert!(tx
+            .request_drain_agent(REQUEST_ID.to_string(), drain_agent_request.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::DrainAgentRequest(drain_agent_request),
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_watch_complete_state() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let watch_complete_state_request = commands::WatchCompleteStateRequest {
+            field_mask: vec![FIELD_MASK.to_string()],
+        };
+        assert!(tx
+            .request_watch_complete_state(
+                REQUEST_ID.to_string(),
+                watch_complete_state_request.clone()
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::WatchCompleteStateRequest(
+                    watch_complete_state_request
+                ),
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_cancel_watch() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        assert!(tx.cancel_watch(REQUEST_ID.to_string()).await.is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::CancelWatchRequest(
+                    commands::CancelWatchRequest {}
+                ),
+            })
+        )
+    }
 }
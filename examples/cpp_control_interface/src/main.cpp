@@ -30,47 +30,47 @@ namespace logging
 /* Create the Request containing an UpdateStateRequest
     that contains the details for adding the new workload and
     the update mask to add only the new workload. */
-control_api::ToAnkaios createRequestToAddNewWorkload()
+ank::v1::ToAnkaios createRequestToAddNewWorkload()
 {
-    ank_base::Workload newWorkload;
+    ank::v1::Workload newWorkload;
     newWorkload.set_agent("agent_A");
     newWorkload.set_runtime("podman");
-    newWorkload.set_restartpolicy(ank_base::RestartPolicy::NEVER);
+    newWorkload.set_restartpolicy(ank::v1::RestartPolicy::NEVER);
     newWorkload.set_runtimeconfig("image: docker.io/library/nginx\ncommandOptions: [\"-p\", \"8080:80\"]");
 
-    ank_base::State *state{new ank_base::State};
+    ank::v1::State *state{new ank::v1::State};
     std::string* apiVersion{new std::string("v0.1")};
     state->set_allocated_apiversion(apiVersion);
     state->mutable_workloads()->insert({"dynamic_nginx", std::move(newWorkload)});
 
-    ank_base::CompleteState *completeState{new ank_base::CompleteState};
+    ank::v1::CompleteState *completeState{new ank::v1::CompleteState};
     completeState->set_allocated_desiredstate(state);
 
-    ank_base::UpdateStateRequest *updateStateRequest{new ank_base::UpdateStateRequest};
+    ank::v1::UpdateStateRequest *updateStateRequest{new ank::v1::UpdateStateRequest};
     updateStateRequest->set_allocated_newstate(completeState);
     updateStateRequest->add_updatemask("desiredState.workloads.dynamic_nginx");
 
-    ank_base::Request* request {new ank_base::Request};
+    ank::v1::Request* request {new ank::v1::Request};
     request->set_allocated_updatestaterequest(updateStateRequest);
     request->set_requestid(REQUEST_ID);
 
-    control_api::ToAnkaios toAnkaios;
+    ank::v1::ToAnkaios toAnkaios;
     toAnkaios.set_allocated_request(request);
     return toAnkaios;
 }
 
 /* Create a Request to request the CompleteState
     for querying the workload states. */
-control_api::ToAnkaios createRequestForCompleteState()
+ank::v1::ToAnkaios createRequestForCompleteState()
 {
-    ank_base::CompleteStateRequest* completeStateRequest{new ank_base::CompleteStateRequest};
+    ank::v1::CompleteStateRequest* completeStateRequest{new ank::v1::CompleteStateRequest};
     completeStateRequest->add_fieldmask("workloadStates");
 
-    ank_base::Request* request {new ank_base::Request};
+    ank::v1::Request* request {new ank::v1::Request};
     request->set_allocated_completestaterequest(completeStateRequest);
     request->set_requestid(REQUEST_ID);
 
-    control_api::ToAnkaios toAnkaios;
+    ank::v1::ToAnkaios toAnkaios;
     toAnkaios.set_allocated_request(request);
     return toAnkaios;
 }
@@ -94,7 +94,7 @@ void readFromControlInterface()
     bool result = true;
     do
     {
-        control_api::FromAnkaios fromAnkaios;
+        ank::v1::FromAnkaios fromAnkaios;
         bool clean_eof = false;
         // read length-delimited protobuf message to output the workload states
         result = google::protobuf::util::ParseDelimitedFromZeroCopyStream(&fromAnkaios, &bufferedInputStream, &clean_eof);
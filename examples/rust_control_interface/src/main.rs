@@ -12,13 +12,11 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use api::ank_base::{
-    request::RequestContent, CompleteState, CompleteStateRequest, Request, RestartPolicy, State,
-    Tag, UpdateStateRequest, Workload,
-};
-
-use api::control_api::{
-    from_ankaios::FromAnkaiosEnum, to_ankaios::ToAnkaiosEnum, FromAnkaios, ToAnkaios,
+use api::ank::v1::{
+    from_ankaios::FromAnkaiosEnum, request::RequestContent, to_ankaios::ToAnkaiosEnum,
+    CompleteState, CompleteStateRequest, ControlInterfaceMode, DependencyFailurePolicy,
+    DisconnectPolicy, FromAnkaios, Request, RestartPolicy, State, Tag, ToAnkaios,
+    UpdateStateRequest, UpdateStrategy, Workload,
 };
 
 use prost::Message;
@@ -64,6 +62,33 @@ fn create_request_to_add_new_workload() -> ToAnkaios {
             runtime_config: "image: docker.io/library/nginx\ncommandOptions: [\"-p\", \"8080:80\"]"
                 .to_string(),
             dependencies: HashMap::new(),
+            unknown_state_policies: HashMap::new(),
+            running_for_ms: HashMap::new(),
+            dependency_expression: None,
+            configs: vec![],
+            template: String::new(),
+            template_parameters: HashMap::new(),
+            enabled_if: String::new(),
+            disconnect_policy: DisconnectPolicy::KeepRunning.into(),
+            log_forwarding: vec![],
+            log_level: None,
+            modes: vec![],
+            pre_shutdown_timeout_ms: 0,
+            dependency_timeout_ms: 0,
+            schedule: String::new(),
+            capture_output_bytes: 0,
+            resources: None,
+            on_dependency_failure: DependencyFailurePolicy::Ignore.into(),
+            expected_startup_time_ms: 0,
+            control_interface_requests_per_minute: 0,
+            hooks: None,
+            agent_dependencies: vec![],
+            profile: String::new(),
+            profiles: HashMap::new(),
+            control_interface: ControlInterfaceMode::Enabled.into(),
+            update_strategy: UpdateStrategy::AtMostOnce.into(),
+            priority: 0,
+            managed_by: String::new(),
         },
     )]);
 
@@ -75,10 +100,19 @@ fn create_request_to_add_new_workload() -> ToAnkaios {
                     desired_state: Some(State {
                         api_version: "v0.1".into(),
                         workloads: new_workloads,
+                        configs: HashMap::new(),
+                        workload_templates: HashMap::new(),
+                        modes: vec![],
+                        active_mode: String::new(),
+                        active_profile: String::new(),
+                        workload_groups: HashMap::new(),
                     }),
                     ..Default::default()
                 }),
                 update_mask: vec!["desiredState.workloads.dynamic_nginx".to_string()],
+                deadline_ms: 0,
+                rollback_on_deadline_exceeded: false,
+                force: false,
             })),
         })),
     }
@@ -0,0 +1,43 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+
+use api::control_api::{to_ankaios::ToAnkaiosEnum, ToAnkaios};
+use common::{commands, input_limits};
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+// Reads a length-delimited message from the input like the agent reads the input pipe
+// of a workload and converts the contained request.
+// [impl->swdd~common-provides-fuzz-targets~1]
+fuzz_target!(|data: &[u8]| {
+    let mut input = data;
+    let Ok(size) = prost::encoding::decode_varint(&mut input) else {
+        return;
+    };
+    let Ok(size) = input_limits::check_message_size(size) else {
+        return;
+    };
+    let Some(message) = input.get(..size) else {
+        return;
+    };
+
+    if let Ok(ToAnkaios {
+        to_ankaios_enum: Some(ToAnkaiosEnum::Request(request)),
+    }) = ToAnkaios::decode(message)
+    {
+        let _ = commands::Request::try_from(request);
+    }
+});
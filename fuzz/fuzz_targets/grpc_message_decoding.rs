@@ -0,0 +1,54 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+
+use common::{commands, input_limits::MAX_MESSAGE_SIZE, objects, to_server_interface};
+use grpc::grpc_api::{self, from_server::FromServerEnum};
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+// Decodes the input as messages of the agent and the CLI connection and converts them
+// into the internal objects like the server and the agent do.
+// [impl->swdd~common-provides-fuzz-targets~1]
+fuzz_target!(|data: &[u8]| {
+    // larger messages are rejected by tonic before they are decoded
+    if data.len() > MAX_MESSAGE_SIZE {
+        return;
+    }
+
+    if let Ok(to_server) = grpc_api::ToServer::decode(data) {
+        let _ = to_server_interface::ToServer::try_from(to_server);
+    }
+
+    if let Ok(from_server) = grpc_api::FromServer::decode(data) {
+        match from_server.from_server_enum {
+            Some(FromServerEnum::UpdateWorkload(update_workload)) => {
+                for added_workload in update_workload.added_workloads {
+                    let _ = objects::WorkloadSpec::try_from(added_workload);
+                }
+                for deleted_workload in update_workload.deleted_workloads {
+                    let _ = objects::DeletedWorkload::try_from(deleted_workload);
+                }
+            }
+            Some(FromServerEnum::UpdateWorkloadState(update_workload_state)) => {
+                let _ = commands::UpdateWorkloadState::from(update_workload_state);
+            }
+            Some(FromServerEnum::Response(response)) => {
+                let _ = commands::Response::try_from(response);
+            }
+            Some(FromServerEnum::UpdateConnectedAgents(_)) | None => {}
+        }
+    }
+});
@@ -0,0 +1,33 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+
+use common::{input_limits, objects::State, state_manipulation::Object};
+use libfuzzer_sys::fuzz_target;
+
+// Parses the input like the server parses a startup config and the CLI parses
+// a manifest to apply.
+// [impl->swdd~common-provides-fuzz-targets~1]
+fuzz_target!(|data: &[u8]| {
+    let Ok(manifest) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let _ = input_limits::parse_manifest::<State>(manifest);
+
+    if let Ok(value) = input_limits::parse_manifest::<serde_yaml::Value>(manifest) {
+        let _ = Object::try_from(&value);
+    }
+});
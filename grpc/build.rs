@@ -15,6 +15,7 @@
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     tonic_build::configure()
         .build_server(true)
+        .extern_path(".ank.v1", "::api::ank::v1")
         .compile(
             &["proto/grpc_api.proto"],
             &["proto", "../api/proto"],
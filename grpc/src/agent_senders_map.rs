@@ -12,7 +12,7 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
 use common::std_extensions::IllegalStateResult;
@@ -20,12 +20,17 @@ use tokio::sync::mpsc::Sender;
 use tonic::Status;
 
 use crate::grpc_api::FromServer;
+use crate::to_server_proxy::MessageSequence;
 
 type ShareableHashMap<K, V> = Arc<Mutex<HashMap<K, V>>>;
 
 #[derive(Debug, Clone)]
 pub struct AgentSendersMap {
     agent_senders: ShareableHashMap<String, Sender<Result<FromServer, Status>>>,
+    // agents without a subscription get the workload states of all other agents
+    workload_state_subscriptions: ShareableHashMap<String, HashSet<String>>,
+    // the message sequences of the agents are kept across their connections
+    message_sequences: ShareableHashMap<String, MessageSequence>,
 }
 
 // Beside improving readability by hiding the lock steps, this trait helps improve the
@@ -38,9 +43,28 @@ impl AgentSendersMap {
     pub fn new() -> Self {
         AgentSendersMap {
             agent_senders: Arc::new(Mutex::new(HashMap::new())),
+            workload_state_subscriptions: Arc::new(Mutex::new(HashMap::new())),
+            message_sequences: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    // [impl->swdd~grpc-agent-connection-detects-gaps-in-message-sequence~2]
+    pub fn take_message_sequence(&self, name: &str) -> MessageSequence {
+        self.message_sequences
+            .lock()
+            .unwrap_or_illegal_state()
+            .remove(name)
+            .unwrap_or_default()
+    }
+
+    // [impl->swdd~grpc-agent-connection-detects-gaps-in-message-sequence~2]
+    pub fn store_message_sequence(&self, name: &str, message_sequence: MessageSequence) {
+        self.message_sequences
+            .lock()
+            .unwrap_or_illegal_state()
+            .insert(name.to_owned(), message_sequence);
+    }
+
     pub fn get(&self, name: &str) -> Option<Sender<Result<FromServer, Status>>> {
         self.agent_senders
             .lock()
@@ -50,6 +74,11 @@ impl AgentSendersMap {
     }
 
     pub fn insert(&self, name: &str, sender: Sender<Result<FromServer, Status>>) {
+        // a new connection starts without a subscription
+        self.workload_state_subscriptions
+            .lock()
+            .unwrap_or_illegal_state()
+            .remove(name);
         self.agent_senders
             .lock()
             .unwrap_or_illegal_state()
@@ -80,6 +109,43 @@ impl AgentSendersMap {
             .lock()
             .unwrap_or_illegal_state()
             .remove(name);
+        self.workload_state_subscriptions
+            .lock()
+            .unwrap_or_illegal_state()
+            .remove(name);
+    }
+
+    // [impl->swdd~grpc-server-filters-workload-states-by-subscription~1]
+    // Returns the workload names the agent did not get the workload states of so far. An agent
+    // without a subscription got the workload states of all workloads.
+    pub fn subscribe_workload_states(
+        &self,
+        name: &str,
+        workload_names: Vec<String>,
+    ) -> Vec<String> {
+        let workload_names: HashSet<String> = workload_names.into_iter().collect();
+        let previous_subscription = self
+            .workload_state_subscriptions
+            .lock()
+            .unwrap_or_illegal_state()
+            .insert(name.to_owned(), workload_names.clone());
+
+        previous_subscription
+            .map(|previous_workload_names| {
+                workload_names
+                    .difference(&previous_workload_names)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn get_workload_state_subscription(&self, name: &str) -> Option<HashSet<String>> {
+        self.workload_state_subscriptions
+            .lock()
+            .unwrap_or_illegal_state()
+            .get(name)
+            .cloned()
     }
 }
 
@@ -12,6 +12,8 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
+
 use crate::from_server_proxy;
 use crate::from_server_proxy::GRPCFromServerStreaming;
 use crate::grpc_middleware_error::GrpcMiddlewareError;
@@ -23,7 +25,9 @@ use crate::grpc_api::{
 
 use common::communications_client::CommunicationsClient;
 use common::communications_error::CommunicationMiddlewareError;
-use common::from_server_interface::FromServerSender;
+use common::from_server_interface::{FromServerInterface, FromServerSender};
+use common::input_limits::MAX_MESSAGE_SIZE;
+use common::objects::StoredWorkloadSpec;
 
 use common::to_server_interface::ToServerReceiver;
 
@@ -36,6 +40,7 @@ use async_trait::async_trait;
 use url::Url;
 
 const RECONNECT_TIMEOUT_SECONDS: u64 = 1;
+const GOODBYE_TIMEOUT_MS: u64 = 500;
 
 enum ConnectionType {
     Agent,
@@ -44,23 +49,46 @@ enum ConnectionType {
 
 pub struct GRPCCommunicationsClient {
     name: String,
-    server_address: Url,
+    // the ordered server endpoints, the first one is the primary server
+    server_addresses: Vec<Url>,
+    active_server_index: usize,
     connection_type: ConnectionType,
+    rollout_group: Option<String>,
+    runtimes: Vec<String>,
+    attributes: HashMap<String, String>,
+    local_workloads: HashMap<String, StoredWorkloadSpec>,
 }
 
 impl GRPCCommunicationsClient {
-    pub fn new_agent_communication(name: String, server_address: Url) -> Self {
+    pub fn new_agent_communication(
+        name: String,
+        server_addresses: Vec<Url>,
+        rollout_group: Option<String>,
+        runtimes: Vec<String>,
+        attributes: HashMap<String, String>,
+        local_workloads: HashMap<String, StoredWorkloadSpec>,
+    ) -> Self {
         Self {
             name,
-            server_address,
+            server_addresses,
+            active_server_index: 0,
             connection_type: ConnectionType::Agent,
+            rollout_group,
+            runtimes,
+            attributes,
+            local_workloads,
         }
     }
     pub fn new_cli_communication(name: String, server_address: Url) -> Self {
         Self {
             name,
-            server_address,
+            server_addresses: vec![server_address],
+            active_server_index: 0,
             connection_type: ConnectionType::Cli,
+            rollout_group: None,
+            runtimes: Vec::new(),
+            attributes: HashMap::new(),
+            local_workloads: HashMap::new(),
         }
     }
 }
@@ -74,14 +102,37 @@ impl CommunicationsClient for GRPCCommunicationsClient {
     ) -> Result<(), CommunicationMiddlewareError> {
         log::debug!("gRPC Communication Client starts.");
 
+        // [impl->swdd~grpc-client-numbers-to-server-messages~2]
+        // the numbers continue across the connections to detect messages lost with a connection
+        let mut message_sequence_number: u64 = 0;
+
         // [impl->swdd~grpc-client-retries-connection~2]
         loop {
-            let result = self.run_internal(&mut server_rx, &agent_tx).await;
+            let result = self
+                .run_internal(&mut server_rx, &agent_tx, &mut message_sequence_number)
+                .await;
 
             match self.connection_type {
                 ConnectionType::Agent => {
+                    // [impl->swdd~grpc-client-stops-after-goodbye~1]
+                    if result.is_ok() {
+                        log::debug!("The agent said goodbye to the server.");
+                        break;
+                    }
                     log::warn!("Connection to server interrupted: '{:?}'", result);
 
+                    // [impl->swdd~grpc-client-notifies-agent-about-connection-loss~1]
+                    if let Err(err) = agent_tx.server_gone().await {
+                        log::warn!("Could not notify the agent about the connection loss: '{err}'");
+                    }
+
+                    // [impl->swdd~grpc-client-fails-over-to-next-server~1]
+                    if matches!(result, Err(GrpcMiddlewareError::ServerNotAvailable(_)))
+                        && self.fail_over_to_next_server()
+                    {
+                        continue;
+                    }
+
                     use tokio::time::{sleep, Duration};
                     sleep(Duration::from_secs(RECONNECT_TIMEOUT_SECONDS)).await;
                 }
@@ -92,7 +143,7 @@ impl CommunicationsClient for GRPCCommunicationsClient {
                             log::debug!("No connection to the server: '{err}'");
                             return Err(CommunicationMiddlewareError(format!(
                                 "Could not connect to Ankaios server on '{}'.",
-                                self.server_address
+                                self.server_address()
                             )));
                         }
                         // [impl->swdd~grpc-client-outputs-error-server-connection-loss-for-cli-connection~1]
@@ -116,13 +167,34 @@ impl CommunicationsClient for GRPCCommunicationsClient {
 }
 
 impl GRPCCommunicationsClient {
+    fn server_address(&self) -> &Url {
+        &self.server_addresses[self.active_server_index]
+    }
+
+    /// Switches to the next server endpoint in the configured order. Returns true if the next
+    /// endpoint shall be tried immediately and false if all endpoints have been tried, in which
+    /// case the gRPC Client starts again with the primary server after the reconnect timeout.
+    fn fail_over_to_next_server(&mut self) -> bool {
+        if self.server_addresses.len() < 2 {
+            return false;
+        }
+
+        self.active_server_index = (self.active_server_index + 1) % self.server_addresses.len();
+        log::info!(
+            "Failing over to the Ankaios server on '{}'.",
+            self.server_address()
+        );
+        self.active_server_index != 0
+    }
+
     /// This functions establishes the connection to the gRPC server and starts listening and forwarding messages
-    /// on the two communications channels. The method returns only if the connection could not be established or
-    /// is interrupted.
+    /// on the two communications channels. The method returns only if the connection could not be established,
+    /// is interrupted or the goodbye has been sent to the server.
     async fn run_internal(
         &self,
         server_rx: &mut ToServerReceiver,
         agent_tx: &FromServerSender,
+        message_sequence_number: &mut u64,
     ) -> Result<(), GrpcMiddlewareError> {
         // [impl->swdd~grpc-client-creates-to-server-channel~1]
         let (grpc_tx, grpc_rx) =
@@ -134,7 +206,19 @@ impl GRPCCommunicationsClient {
                     .send(grpc_api::ToServer {
                         to_server_enum: Some(ToServerEnum::AgentHello(AgentHello {
                             agent_name: self.name.to_owned(),
+                            rollout_group: self.rollout_group.clone().unwrap_or_default(),
+                            agent_version: env!("CARGO_PKG_VERSION").to_owned(),
+                            runtimes: self.runtimes.clone(),
+                            attributes: self.attributes.clone(),
+                            // [impl->swdd~grpc-agent-hello-contains-local-workloads~1]
+                            local_workloads: self
+                                .local_workloads
+                                .iter()
+                                .map(|(name, workload)| (name.clone(), workload.clone().into()))
+                                .collect(),
                         })),
+                        // the hello precedes the numbered messages
+                        message_sequence_number: 0,
                     })
                     .await?;
             }
@@ -152,15 +236,41 @@ impl GRPCCommunicationsClient {
         );
 
         // [impl->swdd~grpc-client-forwards-commands-to-grpc-agent-connection~1]
-        let forward_to_server_from_ank_task =
-            to_server_proxy::forward_from_ankaios_to_proto(grpc_tx, server_rx);
+        let forward_to_server_from_ank_task = to_server_proxy::forward_from_ankaios_to_proto(
+            grpc_tx,
+            server_rx,
+            message_sequence_number,
+        );
+
+        tokio::pin!(
+            forward_exec_from_proto_task,
+            forward_to_server_from_ank_task
+        );
 
         select! {
-            _ = forward_exec_from_proto_task => {log::debug!("Forward from server message from proto to Ankaios task completed");}
-            _ = forward_to_server_from_ank_task => {log::debug!("Forward from server message from Ankaios to proto task completed");}
+            result = &mut forward_exec_from_proto_task => {
+                log::debug!("Forward from server message from proto to Ankaios task completed");
+                result?;
+            }
+            // the task completes without an error only after the goodbye has been sent
+            result = &mut forward_to_server_from_ank_task => {
+                log::debug!("Forward from server message from Ankaios to proto task completed");
+                return result;
+            }
         };
 
-        Ok(())
+        // The server also closes the connection as an answer to the goodbye. In this case the
+        // task forwarding the messages to the server completes shortly afterwards.
+        tokio::time::timeout(
+            tokio::time::Duration::from_millis(GOODBYE_TIMEOUT_MS),
+            forward_to_server_from_ank_task,
+        )
+        .await
+        .unwrap_or_else(|_| {
+            Err(GrpcMiddlewareError::ConnectionInterrupted(
+                "The connection was closed by the server.".to_string(),
+            ))
+        })
     }
 
     async fn connect_to_server(
@@ -169,8 +279,10 @@ impl GRPCCommunicationsClient {
     ) -> Result<tonic::Streaming<grpc_api::FromServer>, GrpcMiddlewareError> {
         match self.connection_type {
             ConnectionType::Agent => {
-                let mut client =
-                    AgentConnectionClient::connect(self.server_address.to_string()).await?;
+                // [impl->swdd~common-limits-message-size~1]
+                let mut client = AgentConnectionClient::connect(self.server_address().to_string())
+                    .await?
+                    .max_decoding_message_size(MAX_MESSAGE_SIZE);
 
                 let res = client
                     .connect_agent(ReceiverStream::new(grpc_rx))
@@ -179,8 +291,9 @@ impl GRPCCommunicationsClient {
                 Ok(res)
             }
             ConnectionType::Cli => {
-                let mut client =
-                    CliConnectionClient::connect(self.server_address.to_string()).await?;
+                let mut client = CliConnectionClient::connect(self.server_address().to_string())
+                    .await?
+                    .max_decoding_message_size(MAX_MESSAGE_SIZE);
 
                 let res = client
                     .connect_cli(ReceiverStream::new(grpc_rx))
@@ -15,6 +15,7 @@
 use crate::agent_senders_map::AgentSendersMap;
 use crate::ankaios_streaming::GRPCStreaming;
 use crate::grpc_middleware_error::GrpcMiddlewareError;
+use crate::workload_state_batch::WorkloadStateBatch;
 use api::ank_base;
 use api::ank_base::response::ResponseContent;
 use crate::grpc_api::{self, from_server::FromServerEnum};
@@ -76,6 +77,10 @@ pub async fn forward_from_proto_to_ankaios(
                                 .map(|deleted_workload| deleted_workload.try_into())
                                 .collect::<Result<Vec<DeletedWorkload>, _>>()
                                 .map_err(GrpcMiddlewareError::ConversionError)?,
+                            obj.sequence_number,
+                            obj.initial,
+                            // [impl->swdd~server-propagates-update-deadline~1]
+                            Some(obj.deadline_ms).filter(|deadline_ms| *deadline_ms != 0),
                         )
                         .await?;
                 }
@@ -86,6 +91,11 @@ pub async fn forward_from_proto_to_ankaios(
                         )
                         .await?;
                 }
+                FromServerEnum::UpdateConnectedAgents(obj) => {
+                    agent_tx
+                        .update_connected_agents(obj.connected_agents)
+                        .await?;
+                }
                 FromServerEnum::Response(response) => {
                     // [impl->swdd~agent-adds-workload-prefix-id-control-interface-request~1]
                     let response: Response = response
@@ -111,6 +121,7 @@ pub async fn forward_from_ankaios_to_proto(
     agent_senders: &AgentSendersMap,
     receiver: &mut FromServerReceiver,
 ) {
+    let mut workload_state_batch = WorkloadStateBatch::default();
     while let Some(from_server_msg) = receiver.recv().await {
         match from_server_msg {
             FromServer::UpdateWorkload(method_obj) => {
@@ -120,13 +131,29 @@ pub async fn forward_from_ankaios_to_proto(
                     agent_senders,
                     method_obj.added_workloads,
                     method_obj.deleted_workloads,
+                    method_obj.sequence_number,
+                    method_obj.initial,
+                    method_obj.deadline_ms,
                 )
                 .await;
             }
             FromServer::UpdateWorkloadState(method_obj) => {
                 log::trace!("Received UpdateWorkloadState from server: {:?}", method_obj);
 
-                distribute_workload_states_to_agents(agent_senders, method_obj.workload_states)
+                distribute_workload_states_to_agents(
+                    agent_senders,
+                    &mut workload_state_batch,
+                    method_obj.workload_states,
+                )
+                .await;
+            }
+            FromServer::UpdateConnectedAgents(method_obj) => {
+                log::trace!(
+                    "Received UpdateConnectedAgents from server: {:?}",
+                    method_obj
+                );
+
+                distribute_connected_agents_to_agents(agent_senders, method_obj.connected_agents)
                     .await;
             }
             FromServer::Response(response) => {
@@ -135,8 +162,9 @@ pub async fn forward_from_ankaios_to_proto(
                 if let Some(sender) = agent_senders.get(&agent_name) {
                     let response_content: ResponseContent = response.response_content.into();
                     log::trace!(
-                        "Sending response to agent '{}': {:?}.",
+                        "Sending response to agent '{}' (trace id '{}'): {:?}.",
                         agent_name,
+                        response.trace_id,
                         response_content
                     );
 
@@ -146,6 +174,7 @@ pub async fn forward_from_ankaios_to_proto(
                                 grpc_api::from_server::FromServerEnum::Response(
                                     ank_base::Response {
                                         request_id,
+                                        trace_id: response.trace_id,
                                         response_content: Some(response_content),
                                     },
                                 ),
@@ -164,6 +193,11 @@ pub async fn forward_from_ankaios_to_proto(
                 // TODO: handle the call
                 break;
             }
+            FromServer::ServerGone(_) => {
+                log::warn!(
+                    "Ignoring the internal ServerGone message as it is not intended to be sent over the network."
+                );
+            }
         }
     }
 }
@@ -171,18 +205,18 @@ pub async fn forward_from_ankaios_to_proto(
 // [impl->swdd~grpc-server-forwards-from-server-messages-to-grpc-client~1]
 async fn distribute_workload_states_to_agents(
     agent_senders: &AgentSendersMap,
+    workload_state_batch: &mut WorkloadStateBatch,
     workload_state_collection: Vec<WorkloadState>,
 ) {
     // Workload states are agent related. Sending a flattened set here is not very good for the performance ...
+    workload_state_batch.fill(workload_state_collection);
 
     for agent_name in agent_senders.get_all_agent_names() {
         // Filter the workload states as we don't want to send an agent its own updates
-        let filtered_workload_states: Vec<ank_base::WorkloadState> = workload_state_collection
-            .clone()
-            .into_iter()
-            .filter(|workload_state| workload_state.instance_name.agent_name() != agent_name)
-            .map(|x| x.into())
-            .collect();
+        // [impl->swdd~grpc-server-filters-workload-states-by-subscription~1]
+        let subscription = agent_senders.get_workload_state_subscription(&agent_name);
+        let filtered_workload_states =
+            workload_state_batch.workload_states_for_agent(&agent_name, subscription.as_ref());
         if filtered_workload_states.is_empty() {
             log::trace!(
                 "Skipping sending workload states to agent '{agent_name}'. Nothing to send."
@@ -214,11 +248,42 @@ async fn distribute_workload_states_to_agents(
     }
 }
 
+// [impl->swdd~grpc-server-forwards-from-server-messages-to-grpc-client~1]
+async fn distribute_connected_agents_to_agents(
+    agent_senders: &AgentSendersMap,
+    connected_agents: Vec<String>,
+) {
+    for agent_name in agent_senders.get_all_agent_names() {
+        if let Some(sender) = agent_senders.get(&agent_name) {
+            log::trace!(
+                "Sending connected agents to agent '{}': {:?}.",
+                agent_name,
+                connected_agents
+            );
+            let result = sender
+                .send(Ok(grpc_api::FromServer {
+                    from_server_enum: Some(FromServerEnum::UpdateConnectedAgents(
+                        grpc_api::UpdateConnectedAgents {
+                            connected_agents: connected_agents.clone(),
+                        },
+                    )),
+                }))
+                .await;
+            if result.is_err() {
+                log::warn!("Could not send connected agents to agent '{}'", agent_name);
+            }
+        }
+    }
+}
+
 // [impl->swdd~grpc-server-forwards-from-server-messages-to-grpc-client~1]
 async fn distribute_workloads_to_agents(
     agent_senders: &AgentSendersMap,
     added_workloads: WorkloadCollection,
     deleted_workloads: DeletedWorkloadCollection,
+    sequence_number: u64,
+    initial: bool,
+    deadline_ms: Option<u64>,
 ) {
     // [impl->swdd~grpc-server-sorts-commands-according-agents~1]
     for (agent_name, (added_workload_vector, deleted_workload_vector)) in
@@ -239,6 +304,9 @@ async fn distribute_workloads_to_agents(
                                 .into_iter()
                                 .map(|x| x.into())
                                 .collect(),
+                            sequence_number,
+                            initial,
+                            deadline_ms: deadline_ms.unwrap_or_default(),
                         },
                     )),
                 }))
@@ -274,7 +342,9 @@ mod tests {
     use std::collections::{HashMap, LinkedList};
 
     use super::{forward_from_ankaios_to_proto, forward_from_proto_to_ankaios};
+    use crate::workload_state_batch::WorkloadStateBatch;
     use crate::{agent_senders_map::AgentSendersMap, from_server_proxy::GRPCStreaming};
+    use crate::grpc_api::{self, from_server::FromServerEnum, FromServer, UpdateWorkload};
     use api::ank_base::{self, response};
     use async_trait::async_trait;
     use common::from_server_interface::FromServerInterface;
@@ -284,7 +354,6 @@ mod tests {
     };
     use common::objects::{CompleteState, State, WorkloadSpec};
     use common::test_utils::*;
-    use crate::grpc_api::{self, from_server::FromServerEnum, FromServer, UpdateWorkload};
     use tokio::sync::mpsc::error::TryRecvError;
     use tokio::{
         join,
@@ -300,6 +369,7 @@ mod tests {
     );
 
     const WORKLOAD_NAME: &str = "workload_1";
+    const TRACE_ID: &str = "trace_id";
 
     fn create_test_setup(agent_name: &str) -> TestSetup {
         let (to_manager, manager_receiver) =
@@ -359,6 +429,9 @@ mod tests {
                     agent.to_string(),
                     "workload X".to_string(),
                 )],
+                1,
+                false,
+                None,
             )
             .await;
         assert!(update_workload_result.is_ok());
@@ -464,7 +537,7 @@ mod tests {
                 Some(FromServer {
                     from_server_enum: Some(FromServerEnum::UpdateWorkload(UpdateWorkload {
                         added_workloads: vec![workload],
-                        deleted_workloads: vec![],
+                        ..Default::default()
                     })),
                 }),
                 None,
@@ -503,8 +576,8 @@ mod tests {
             MockGRPCFromServerStreaming::new(LinkedList::from([
                 Some(FromServer {
                     from_server_enum: Some(FromServerEnum::UpdateWorkload(UpdateWorkload {
-                        added_workloads: vec![],
                         deleted_workloads: vec![workload],
+                        ..Default::default()
                     })),
                 }),
                 None,
@@ -591,6 +664,43 @@ mod tests {
         ));
     }
 
+    // [utest->swdd~server-distributes-connected-agents~1]
+    #[tokio::test]
+    async fn utest_from_server_proxy_forward_from_proto_to_ankaios_update_connected_agents() {
+        let (to_agent, mut agent_receiver) =
+            mpsc::channel::<common::from_server_interface::FromServer>(common::CHANNEL_CAPACITY);
+
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCFromServerStreaming::new(LinkedList::from([
+                Some(FromServer {
+                    from_server_enum: Some(FromServerEnum::UpdateConnectedAgents(
+                        grpc_api::UpdateConnectedAgents {
+                            connected_agents: vec!["agent_B".to_string()],
+                        },
+                    )),
+                }),
+                None,
+            ]));
+
+        let forward_result = tokio::spawn(async move {
+            forward_from_proto_to_ankaios(&mut mock_grpc_ex_request_streaming, &to_agent).await
+        })
+        .await;
+        assert!(forward_result.is_ok());
+
+        let result = agent_receiver.recv().await.unwrap();
+
+        assert_eq!(
+            result,
+            common::from_server_interface::FromServer::UpdateConnectedAgents(
+                common::commands::UpdateConnectedAgents {
+                    connected_agents: vec!["agent_B".to_string()],
+                }
+            )
+        );
+    }
+
+    // [utest->swdd~server-propagates-update-deadline~1]
     #[tokio::test]
     async fn utest_distribute_workloads_to_agents_shall_distribute_workloads_to_existing_agents() {
         let agent_name = "agent_X";
@@ -603,7 +713,10 @@ mod tests {
                 "name".to_string(),
                 "workload1".to_string()
             ),],
-            vec![]
+            vec![],
+            5,
+            true,
+            Some(2000)
         ))
         .0;
 
@@ -612,7 +725,12 @@ mod tests {
         // shall receive update workload from server message
         assert!(matches!(
             result.from_server_enum,
-            Some(FromServerEnum::UpdateWorkload(_))
+            Some(FromServerEnum::UpdateWorkload(grpc_api::UpdateWorkload {
+                sequence_number: 5,
+                initial: true,
+                deadline_ms: 2000,
+                ..
+            }))
         ))
     }
 
@@ -629,7 +747,10 @@ mod tests {
                 "name".to_string(),
                 "workload1".to_string()
             ),],
-            vec![]
+            vec![],
+            5,
+            true,
+            None
         ))
         .0;
 
@@ -642,9 +763,11 @@ mod tests {
     ) {
         let agent_name = "agent_X";
         let (_, _, _, mut agent_rx, agent_senders) = create_test_setup(agent_name);
+        let mut batch = WorkloadStateBatch::default();
 
         join!(super::distribute_workload_states_to_agents(
             &agent_senders,
+            &mut batch,
             vec![common::objects::generate_test_workload_state_with_agent(
                 "workload1",
                 "other_agent",
@@ -662,6 +785,30 @@ mod tests {
         ))
     }
 
+    // [utest->swdd~server-distributes-connected-agents~1]
+    #[tokio::test]
+    async fn utest_distribute_connected_agents_to_agents_shall_send_to_all_agents() {
+        let agent_name = "agent_X";
+        let (_, _, _, mut agent_rx, agent_senders) = create_test_setup(agent_name);
+
+        join!(super::distribute_connected_agents_to_agents(
+            &agent_senders,
+            vec![agent_name.to_string(), "agent_Y".to_string()],
+        ))
+        .0;
+
+        let result = agent_rx.recv().await.unwrap().unwrap();
+
+        assert_eq!(
+            result.from_server_enum,
+            Some(FromServerEnum::UpdateConnectedAgents(
+                grpc_api::UpdateConnectedAgents {
+                    connected_agents: vec![agent_name.to_string(), "agent_Y".to_string()],
+                }
+            ))
+        )
+    }
+
     #[tokio::test]
     async fn utest_from_server_proxy_forward_from_ankaios_to_proto_complete_state() {
         let agent_name: &str = "agent_X";
@@ -687,10 +834,15 @@ mod tests {
                 ..Default::default()
             },
             workload_states: vec![],
+            ..Default::default()
         };
 
         let complete_state_result = to_manager
-            .complete_state(prefixed_my_request_id, test_complete_state.clone())
+            .complete_state(
+                prefixed_my_request_id,
+                TRACE_ID.to_owned(),
+                test_complete_state.clone(),
+            )
             .await;
         assert!(complete_state_result.is_ok());
 
@@ -707,12 +859,15 @@ mod tests {
             result.from_server_enum,
             Some(FromServerEnum::Response(ank_base::Response {
                 request_id,
+                trace_id,
                 response_content: Some(ank_base::response::ResponseContent::CompleteState(ank_base::CompleteState{
                     desired_state: Some(desired_state),
                     startup_state: Some(startup_state),
-                    workload_states}))
+                    workload_states,
+                    ..}))
 
             })) if request_id == my_request_id
+            && trace_id == TRACE_ID
             && desired_state == test_complete_state.desired_state.into()
             && startup_state ==test_complete_state.startup_state.into()
             && workload_states == vec![]
@@ -741,7 +896,7 @@ mod tests {
                     .into(),
                     ..Default::default()
                 }),
-                workload_states: vec![],
+                ..Default::default()
             });
 
         // simulate the reception of an update workload state grpc from server message
@@ -750,6 +905,7 @@ mod tests {
                 Some(FromServer {
                     from_server_enum: Some(FromServerEnum::Response(ank_base::Response {
                         request_id: my_request_id,
+                        trace_id: TRACE_ID.to_owned(),
                         response_content: Some(proto_complete_state),
                     })),
                 }),
@@ -790,17 +946,18 @@ mod tests {
         let test_complete_state = CompleteState {
             desired_state: State::default(),
             startup_state: State::default(),
-            workload_states: vec![],
+            ..Default::default()
         };
 
         let proto_complete_state = ank_base::CompleteState {
             desired_state: Some(test_complete_state.desired_state.clone().into()),
             startup_state: Some(test_complete_state.startup_state.clone().into()),
-            workload_states: vec![],
+            ..Default::default()
         };
 
         let proto_response = ank_base::Response {
             request_id: my_request_id.clone(),
+            trace_id: TRACE_ID.to_owned(),
             response_content: Some(response::ResponseContent::CompleteState(
                 proto_complete_state,
             )),
@@ -831,10 +988,12 @@ mod tests {
             result,
             common::from_server_interface::FromServer::Response(common::commands::Response {
                 request_id,
+                trace_id,
                 response_content: common::commands::ResponseContent::CompleteState(
                     boxed_complete_state
                 )
             }) if request_id == my_request_id &&
+            trace_id == TRACE_ID &&
             boxed_complete_state.startup_state == expected_test_complete_state.startup_state &&
             boxed_complete_state.desired_state == expected_test_complete_state.desired_state &&
             boxed_complete_state.workload_states == expected_test_complete_state.workload_states
@@ -21,9 +21,11 @@ use tonic::codegen::futures_core::Stream;
 use tonic::{Request, Response, Status};
 
 use crate::agent_senders_map::AgentSendersMap;
+use crate::grpc_api::{self, agent_connection_server::AgentConnection, to_server::ToServerEnum};
 use crate::to_server_proxy::{forward_from_proto_to_ankaios, GRPCToServerStreaming};
+use common::commands;
+use common::request_id_prepending::is_reserved_connection_name;
 use common::to_server_interface::{self, ToServerInterface};
-use crate::grpc_api::{self, agent_connection_server::AgentConnection, to_server::ToServerEnum};
 
 #[derive(Debug)]
 pub struct GRPCAgentConnection {
@@ -71,44 +73,66 @@ impl AgentConnection for GRPCAgentConnection {
             .to_server_enum
             .ok_or_else(invalid_argument_empty)?
         {
-            ToServerEnum::AgentHello(grpc_api::AgentHello { agent_name }) => {
+            ToServerEnum::AgentHello(agent_hello) => {
+                let agent_hello: commands::AgentHello = agent_hello.into();
+                let agent_name = agent_hello.agent_name.clone();
                 log::trace!("Received a hello from '{}'", agent_name);
 
+                // [impl->swdd~grpc-agent-connection-rejects-reserved-agent-names~1]
+                if is_reserved_connection_name(&agent_name) {
+                    log::warn!(
+                        "Rejected the connection of agent '{}' as its name is reserved.",
+                        agent_name
+                    );
+                    return Err(Status::invalid_argument(format!(
+                        "The agent name '{agent_name}' is reserved"
+                    )));
+                }
+
                 // [impl->swdd~grpc-agent-connection-stores-from-server-channel-tx~1]
                 self.agent_senders
                     .insert(&agent_name, new_agent_sender.to_owned());
                 // [impl->swdd~grpc-agent-connection-forwards-hello-to-ankaios-server~1]
-                if let Err(error) = self.to_ankaios_server.agent_hello(agent_name.clone()).await {
+                if let Err(error) = self.to_ankaios_server.agent_hello(agent_hello).await {
                     log::error!("Could not send agent hello: '{error}'");
                 }
 
                 // [impl->swdd~grpc-agent-connection-forwards-commands-to-server~1]
                 let _x = tokio::spawn(async move {
                     let mut stream = GRPCToServerStreaming::new(stream);
-                    if let Err(error) = forward_from_proto_to_ankaios(
+                    // [impl->swdd~grpc-agent-connection-detects-gaps-in-message-sequence~2]
+                    let mut message_sequence = agent_senders.take_message_sequence(&agent_name);
+                    match forward_from_proto_to_ankaios(
                         agent_name.clone(),
                         &mut stream,
                         ankaios_tx.clone(),
+                        &agent_senders,
+                        &mut message_sequence,
                     )
                     .await
                     {
-                        log::warn!(
-                            "Connection to agent {} interrupted with error: {}",
-                            agent_name,
-                            error
-                        );
-
-                        agent_senders.remove(&agent_name);
-                        log::trace!(
-                            "The connection is interrupted or has been closed. Deleting the agent sender '{}'",
-                            agent_name
-                        );
-                        // inform also the server that the agent is gone
-                        // [impl->swdd~grpc-agent-connection-sends-agent-gone~1]
-                        if let Err(error) = ankaios_tx.agent_gone(agent_name).await {
-                            log::error!("Could not inform server about gone agent: '{}'", error);
+                        Ok(()) => log::debug!("Agent {} said goodbye", agent_name),
+                        Err(error) => {
+                            log::warn!(
+                                "Connection to agent {} interrupted with error: {}",
+                                agent_name,
+                                error
+                            );
+                            // the agent continues its message sequence when it reconnects
+                            agent_senders.store_message_sequence(&agent_name, message_sequence);
                         }
                     }
+
+                    agent_senders.remove(&agent_name);
+                    log::trace!(
+                        "The connection is interrupted or has been closed. Deleting the agent sender '{}'",
+                        agent_name
+                    );
+                    // inform also the server that the agent is gone
+                    // [impl->swdd~grpc-agent-connection-sends-agent-gone~2]
+                    if let Err(error) = ankaios_tx.agent_gone(agent_name).await {
+                        log::error!("Could not inform server about gone agent: '{}'", error);
+                    }
                 });
             }
             _ => {
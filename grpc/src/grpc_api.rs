@@ -25,6 +25,22 @@ impl From<AgentHello> for commands::AgentHello {
     fn from(item: AgentHello) -> Self {
         commands::AgentHello {
             agent_name: item.agent_name,
+            rollout_group: Some(item.rollout_group).filter(|group| !group.is_empty()),
+            agent_version: item.agent_version,
+            runtimes: item.runtimes,
+            attributes: item.attributes,
+            // [impl->swdd~grpc-agent-hello-contains-local-workloads~1]
+            local_workloads: item
+                .local_workloads
+                .into_iter()
+                .filter_map(|(name, workload)| match workload.try_into() {
+                    Ok(workload) => Some((name, workload)),
+                    Err(error) => {
+                        log::warn!("Ignoring invalid local workload '{}': '{}'", name, error);
+                        None
+                    }
+                })
+                .collect(),
         }
     }
 }
@@ -63,6 +79,9 @@ impl TryFrom<from_server_interface::FromServer> for FromServer {
                             .into_iter()
                             .map(|x| x.into())
                             .collect(),
+                        sequence_number: ankaios.sequence_number,
+                        initial: ankaios.initial,
+                        deadline_ms: ankaios.deadline_ms.unwrap_or_default(),
                     },
                 )),
             }),
@@ -71,16 +90,24 @@ impl TryFrom<from_server_interface::FromServer> for FromServer {
                     UpdateWorkloadState {
                         workload_states: ankaios
                             .workload_states
-                            .iter()
-                            .map(|x| x.to_owned().into())
+                            .into_iter()
+                            .map(|x| x.into())
                             .collect(),
                     },
                 )),
             }),
+            from_server_interface::FromServer::UpdateConnectedAgents(ankaios) => Ok(FromServer {
+                from_server_enum: Some(from_server::FromServerEnum::UpdateConnectedAgents(
+                    UpdateConnectedAgents {
+                        connected_agents: ankaios.connected_agents,
+                    },
+                )),
+            }),
             from_server_interface::FromServer::Response(ankaios) => Ok(FromServer {
                 from_server_enum: Some(from_server::FromServerEnum::Response(
                     super::ank_base::Response {
                         request_id: ankaios.request_id,
+                        trace_id: ankaios.trace_id,
                         response_content: Some(ankaios.response_content.into()),
                     },
                 )),
@@ -88,6 +115,9 @@ impl TryFrom<from_server_interface::FromServer> for FromServer {
             from_server_interface::FromServer::Stop(_) => {
                 Err("Stop command not implemented in proto")
             }
+            from_server_interface::FromServer::ServerGone(_) => {
+                Err("ServerGone internal message is not intended to be sent over the network")
+            }
         }
     }
 }
@@ -138,6 +168,49 @@ impl TryFrom<AddedWorkload> for objects::WorkloadSpec {
             instance_name: workload.instance_name.ok_or("No instance name")?.into(),
             tags: workload.tags.into_iter().map(|x| x.into()).collect(),
             runtime_config: workload.runtime_config,
+            unknown_state_policies: workload
+                .unknown_state_policies
+                .into_iter()
+                .map(|(k, v)| Ok((k, v.try_into()?)))
+                .collect::<Result<HashMap<String, objects::UnknownStatePolicy>, String>>()?,
+            running_for_ms: workload.running_for_ms,
+            dependency_expression: workload
+                .dependency_expression
+                .map(TryInto::try_into)
+                .transpose()?,
+            configs: workload
+                .configs
+                .into_iter()
+                .map(|(k, v)| (k, v.data))
+                .collect(),
+            // the condition is evaluated by the server before sending the workload
+            enabled_if: String::new(),
+            disconnect_policy: workload.disconnect_policy.try_into()?,
+            log_forwarding: workload
+                .log_forwarding
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<objects::LogRoute>, String>>()?,
+            log_level: workload.log_level.map(Into::into),
+            pre_shutdown_timeout_ms: Some(workload.pre_shutdown_timeout_ms)
+                .filter(|timeout_ms| *timeout_ms != 0),
+            dependency_timeout_ms: Some(workload.dependency_timeout_ms)
+                .filter(|timeout_ms| *timeout_ms != 0),
+            schedule: Some(workload.schedule).filter(|schedule| !schedule.is_empty()),
+            capture_output_bytes: Some(workload.capture_output_bytes).filter(|bytes| *bytes != 0),
+            resources: workload.resources.map(Into::into),
+            on_dependency_failure: workload.on_dependency_failure.try_into()?,
+            expected_startup_time_ms: Some(workload.expected_startup_time_ms)
+                .filter(|time_ms| *time_ms != 0),
+            control_interface_requests_per_minute: Some(
+                workload.control_interface_requests_per_minute,
+            )
+            .filter(|requests| *requests != 0),
+            hooks: workload.hooks.map(Into::into),
+            agent_dependencies: workload.agent_dependencies,
+            control_interface: workload.control_interface.try_into()?,
+            update_strategy: workload.update_strategy.try_into()?,
+            priority: objects::priority_from_proto(workload.priority)?,
         })
     }
 }
@@ -156,6 +229,40 @@ impl From<objects::WorkloadSpec> for AddedWorkload {
             runtime: workload.runtime,
             runtime_config: workload.runtime_config,
             tags: workload.tags.into_iter().map(|x| x.into()).collect(),
+            unknown_state_policies: workload
+                .unknown_state_policies
+                .into_iter()
+                .map(|(k, v)| (k, v as i32))
+                .collect(),
+            running_for_ms: workload.running_for_ms,
+            dependency_expression: workload.dependency_expression.map(Into::into),
+            configs: workload
+                .configs
+                .into_iter()
+                .map(|(k, v)| (k, super::ank_base::ConfigObject { data: v }))
+                .collect(),
+            disconnect_policy: workload.disconnect_policy as i32,
+            log_forwarding: workload
+                .log_forwarding
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            log_level: workload.log_level.map(Into::into),
+            pre_shutdown_timeout_ms: workload.pre_shutdown_timeout_ms.unwrap_or_default(),
+            dependency_timeout_ms: workload.dependency_timeout_ms.unwrap_or_default(),
+            schedule: workload.schedule.unwrap_or_default(),
+            capture_output_bytes: workload.capture_output_bytes.unwrap_or_default(),
+            resources: workload.resources.map(Into::into),
+            on_dependency_failure: workload.on_dependency_failure as i32,
+            expected_startup_time_ms: workload.expected_startup_time_ms.unwrap_or_default(),
+            control_interface_requests_per_minute: workload
+                .control_interface_requests_per_minute
+                .unwrap_or_default(),
+            hooks: workload.hooks.map(Into::into),
+            agent_dependencies: workload.agent_dependencies,
+            control_interface: workload.control_interface as i32,
+            update_strategy: workload.update_strategy as i32,
+            priority: workload.priority.into(),
         }
     }
 }
@@ -180,6 +287,38 @@ impl TryFrom<ToServer> for to_server_interface::ToServer {
             ToServerEnum::Goodbye(_) => {
                 to_server_interface::ToServer::Goodbye(commands::Goodbye {})
             }
+            ToServerEnum::UpdateWorkloadAck(_) => {
+                return Err(
+                    "UpdateWorkloadAck can only be converted on an agent connection.".to_string(),
+                );
+            }
+            ToServerEnum::AgentEvent(_) => {
+                return Err("AgentEvent can only be converted on an agent connection.".to_string());
+            }
+            ToServerEnum::UpdateSchedulerQueue(_) => {
+                return Err(
+                    "UpdateSchedulerQueue can only be converted on an agent connection."
+                        .to_string(),
+                );
+            }
+            ToServerEnum::SubscribeWorkloadStates(_) => {
+                return Err(
+                    "SubscribeWorkloadStates can only be converted on an agent connection."
+                        .to_string(),
+                );
+            }
+            ToServerEnum::UpdateControlInterfaceMetrics(_) => {
+                return Err(
+                    "UpdateControlInterfaceMetrics can only be converted on an agent connection."
+                        .to_string(),
+                );
+            }
+            ToServerEnum::UpdateAgentResources(_) => {
+                return Err(
+                    "UpdateAgentResources can only be converted on an agent connection."
+                        .to_string(),
+                );
+            }
         })
     }
 }
@@ -240,9 +379,9 @@ mod tests {
         UpdateWorkloadState,
     };
 
-    use api::ank_base;
+    use api::ank_base::{self, dependency_expression::DependencyExpressionEnum};
     use common::{
-        objects::{generate_test_workload_spec, ConfigHash},
+        objects::{generate_test_stored_workload_spec, generate_test_workload_spec, ConfigHash},
         test_utils::generate_test_deleted_workload,
     };
 
@@ -256,6 +395,7 @@ mod tests {
     ///////////////////////////////////////////////////////////////////////////
     // ToServer tests
     ///////////////////////////////////////////////////////////////////////////
+    // [utest->swdd~grpc-agent-hello-contains-local-workloads~1]
     #[test]
     fn utest_convert_proto_to_server_agent_hello() {
         let agent_name = "agent_A".to_string();
@@ -263,10 +403,29 @@ mod tests {
         let proto_request = ToServer {
             to_server_enum: Some(ToServerEnum::AgentHello(AgentHello {
                 agent_name: agent_name.clone(),
+                rollout_group: "canary".to_string(),
+                agent_version: "0.4.0".to_string(),
+                runtimes: vec!["podman".to_string()],
+                attributes: HashMap::from([("camera".to_string(), "true".to_string())]),
+                local_workloads: HashMap::from([(
+                    "watchdog".to_string(),
+                    generate_test_stored_workload_spec("agent_A", "podman").into(),
+                )]),
             })),
+            message_sequence_number: 0,
         };
 
-        let ankaios_command = ankaios::ToServer::AgentHello(ankaios::AgentHello { agent_name });
+        let ankaios_command = ankaios::ToServer::AgentHello(ankaios::AgentHello {
+            agent_name,
+            rollout_group: Some("canary".to_string()),
+            agent_version: "0.4.0".to_string(),
+            runtimes: vec!["podman".to_string()],
+            attributes: HashMap::from([("camera".to_string(), "true".to_string())]),
+            local_workloads: HashMap::from([(
+                "watchdog".to_string(),
+                generate_test_stored_workload_spec("agent_A", "podman"),
+            )]),
+        });
 
         assert_eq!(
             ankaios::ToServer::try_from(proto_request),
@@ -280,6 +439,7 @@ mod tests {
             to_server_enum: Some(ToServerEnum::UpdateWorkloadState(UpdateWorkloadState {
                 workload_states: vec![],
             })),
+            message_sequence_number: 0,
         };
 
         let ankaios_command =
@@ -301,6 +461,9 @@ mod tests {
                 request_content: Some(ank_base::request::RequestContent::UpdateStateRequest(
                     ank_base::UpdateStateRequest {
                         update_mask: vec!["test_update_mask_field".to_owned()],
+                        deadline_ms: 0,
+                        rollback_on_deadline_exceeded: false,
+                        force: false,
                         new_state: Some(ank_base::CompleteState {
                             startup_state: Some(ank_base::State {
                                 api_version: "v0.1".into(),
@@ -315,12 +478,19 @@ mod tests {
                                         ..Default::default()
                                     },
                                 )]),
+                                configs: HashMap::new(),
+                                workload_templates: HashMap::new(),
+                                modes: vec![],
+                                active_mode: String::new(),
+                                active_profile: String::new(),
+                                workload_groups: HashMap::new(),
                             }),
                             ..Default::default()
                         }),
                     },
                 )),
             })),
+            message_sequence_number: 0,
         };
 
         let ankaios_command = ankaios::ToServer::Request(ankaios::Request {
@@ -328,6 +498,9 @@ mod tests {
             request_content: ankaios::RequestContent::UpdateStateRequest(Box::new(
                 ankaios::UpdateStateRequest {
                     update_mask: vec!["test_update_mask_field".to_owned()],
+                    deadline_ms: None,
+                    rollback_on_deadline_exceeded: false,
+                    force: false,
                     state: ankaios::CompleteState {
                         desired_state: ankaios::State {
                             workloads: HashMap::from([(
@@ -359,6 +532,9 @@ mod tests {
                 request_content: Some(ank_base::request::RequestContent::UpdateStateRequest(
                     ank_base::UpdateStateRequest {
                         update_mask: vec!["test_update_mask_field".to_owned()],
+                        deadline_ms: 0,
+                        rollback_on_deadline_exceeded: false,
+                        force: false,
                         new_state: Some(ank_base::CompleteState {
                             desired_state: Some(ank_base::State {
                                 api_version: "v0.1".into(),
@@ -372,12 +548,19 @@ mod tests {
                                         ..Default::default()
                                     },
                                 )]),
+                                configs: HashMap::new(),
+                                workload_templates: HashMap::new(),
+                                modes: vec![],
+                                active_mode: String::new(),
+                                active_profile: String::new(),
+                                workload_groups: HashMap::new(),
                             }),
                             ..Default::default()
                         }),
                     },
                 )),
             })),
+            message_sequence_number: 0,
         };
 
         assert!(ankaios::ToServer::try_from(proto_request).is_err(),);
@@ -397,6 +580,7 @@ mod tests {
                     },
                 )),
             })),
+            message_sequence_number: 0,
         };
 
         let ankaios_command = ankaios::ToServer::Request(ankaios::Request {
@@ -430,6 +614,9 @@ mod tests {
                 "agent".to_string(),
                 "workload X".to_string(),
             )],
+            sequence_number: 3,
+            initial: true,
+            deadline_ms: Some(2000),
         });
         let expected_ex_com = Ok(FromServer {
             from_server_enum: Some(FromServerEnum::UpdateWorkload(UpdateWorkload {
@@ -442,6 +629,9 @@ mod tests {
                     ..Default::default()
                 }],
                 deleted_workloads: vec![generate_test_proto_deleted_workload()],
+                sequence_number: 3,
+                initial: true,
+                deadline_ms: 2000,
             })),
         });
 
@@ -472,12 +662,14 @@ mod tests {
     fn utest_convert_from_server_to_proto_complete_state() {
         let test_ex_com = ankaios::FromServer::Response(ankaios::Response {
             request_id: "req_id".to_owned(),
+            trace_id: "trace_id".to_owned(),
             response_content: ankaios::ResponseContent::CompleteState(Box::default()),
         });
 
         let expected_ex_com = Ok(FromServer {
             from_server_enum: Some(FromServerEnum::Response(ank_base::Response {
                 request_id: "req_id".to_owned(),
+                trace_id: "trace_id".to_owned(),
                 response_content: Some(ank_base::response::ResponseContent::CompleteState(
                     ank_base::CompleteState {
                         desired_state: Some(api::ank_base::State {
@@ -488,7 +680,7 @@ mod tests {
                             api_version: "v0.1".into(),
                             ..Default::default()
                         }),
-                        workload_states: vec![],
+                        ..Default::default()
                     },
                 )),
             })),
@@ -555,6 +747,26 @@ mod tests {
                 key: "key".into(),
                 value: "value".into(),
             }],
+            unknown_state_policies: HashMap::new(),
+            running_for_ms: HashMap::new(),
+            dependency_expression: None,
+            configs: HashMap::new(),
+            disconnect_policy: ank_base::DisconnectPolicy::KeepRunning.into(),
+            log_forwarding: vec![],
+            log_level: None,
+            pre_shutdown_timeout_ms: 0,
+            dependency_timeout_ms: 0,
+            schedule: String::new(),
+            capture_output_bytes: 0,
+            resources: None,
+            on_dependency_failure: ank_base::DependencyFailurePolicy::Ignore.into(),
+            expected_startup_time_ms: 0,
+            control_interface_requests_per_minute: 0,
+            hooks: None,
+            agent_dependencies: vec![],
+            control_interface: ank_base::ControlInterfaceMode::Enabled.into(),
+            update_strategy: ank_base::UpdateStrategy::AtMostOnce.into(),
+            priority: 0,
         };
 
         assert_eq!(AddedWorkload::from(workload_spec), proto_workload);
@@ -581,6 +793,38 @@ mod tests {
                 .build(),
             tags: vec![],
             runtime_config: String::from("some config"),
+            unknown_state_policies: HashMap::from([(
+                String::from("workload A"),
+                ankaios::UnknownStatePolicy::UnknownStateLastKnown,
+            )]),
+            running_for_ms: HashMap::from([(String::from("workload A"), 30000)]),
+            dependency_expression: Some(ankaios::DependencyExpression::Not {
+                not: Box::new(ankaios::DependencyExpression::Condition {
+                    workload: String::from("workload B"),
+                    condition: ankaios::AddCondition::AddCondRunning,
+                }),
+            }),
+            configs: HashMap::from([(
+                String::from("config_1"),
+                HashMap::from([(String::from("key"), String::from("value"))]),
+            )]),
+            enabled_if: String::new(),
+            disconnect_policy: ankaios::DisconnectPolicy::Stop,
+            log_forwarding: vec![],
+            log_level: None,
+            pre_shutdown_timeout_ms: None,
+            dependency_timeout_ms: None,
+            schedule: None,
+            capture_output_bytes: None,
+            resources: None,
+            on_dependency_failure: ankaios::DependencyFailurePolicy::Ignore,
+            expected_startup_time_ms: None,
+            control_interface_requests_per_minute: None,
+            hooks: None,
+            agent_dependencies: vec![],
+            control_interface: ankaios::ControlInterfaceMode::Enabled,
+            update_strategy: ankaios::UpdateStrategy::AtMostOnce,
+            priority: 0,
         };
 
         let proto_workload = AddedWorkload {
@@ -603,6 +847,45 @@ mod tests {
             runtime: String::from("runtime"),
             runtime_config: String::from("some config"),
             tags: vec![],
+            unknown_state_policies: HashMap::from([(
+                String::from("workload A"),
+                ank_base::UnknownStatePolicy::UnknownStateLastKnown.into(),
+            )]),
+            running_for_ms: HashMap::from([(String::from("workload A"), 30000)]),
+            dependency_expression: Some(ank_base::DependencyExpression {
+                dependency_expression_enum: Some(DependencyExpressionEnum::Not(Box::new(
+                    ank_base::DependencyExpression {
+                        dependency_expression_enum: Some(DependencyExpressionEnum::Condition(
+                            ank_base::DependencyCondition {
+                                workload: String::from("workload B"),
+                                condition: ank_base::AddCondition::AddCondRunning.into(),
+                            },
+                        )),
+                    },
+                ))),
+            }),
+            configs: HashMap::from([(
+                String::from("config_1"),
+                ank_base::ConfigObject {
+                    data: HashMap::from([(String::from("key"), String::from("value"))]),
+                },
+            )]),
+            disconnect_policy: ank_base::DisconnectPolicy::Stop.into(),
+            log_forwarding: vec![],
+            log_level: None,
+            pre_shutdown_timeout_ms: 0,
+            dependency_timeout_ms: 0,
+            schedule: String::new(),
+            capture_output_bytes: 0,
+            resources: None,
+            on_dependency_failure: ank_base::DependencyFailurePolicy::Ignore.into(),
+            expected_startup_time_ms: 0,
+            control_interface_requests_per_minute: 0,
+            hooks: None,
+            agent_dependencies: vec![],
+            control_interface: ank_base::ControlInterfaceMode::Enabled.into(),
+            update_strategy: ank_base::UpdateStrategy::AtMostOnce.into(),
+            priority: 0,
         };
 
         assert_eq!(
@@ -633,6 +916,26 @@ mod tests {
             runtime: String::from("runtime"),
             runtime_config: String::from("some config"),
             tags: vec![],
+            unknown_state_policies: HashMap::new(),
+            running_for_ms: HashMap::new(),
+            dependency_expression: None,
+            configs: HashMap::new(),
+            disconnect_policy: ank_base::DisconnectPolicy::KeepRunning.into(),
+            log_forwarding: vec![],
+            log_level: None,
+            pre_shutdown_timeout_ms: 0,
+            dependency_timeout_ms: 0,
+            schedule: String::new(),
+            capture_output_bytes: 0,
+            resources: None,
+            on_dependency_failure: ank_base::DependencyFailurePolicy::Ignore.into(),
+            expected_startup_time_ms: 0,
+            control_interface_requests_per_minute: 0,
+            hooks: None,
+            agent_dependencies: vec![],
+            control_interface: ank_base::ControlInterfaceMode::Enabled.into(),
+            update_strategy: ank_base::UpdateStrategy::AtMostOnce.into(),
+            priority: 0,
         };
 
         assert!(ankaios::WorkloadSpec::try_from(proto_workload).is_err());
@@ -672,6 +975,8 @@ mod tests {
                     .agent_name(AGENT_NAME)
                     .build(),
                 execution_state: ankaios::ExecutionState::running(),
+                agent_timestamp: None,
+                server_timestamp: None,
             }
         }};
         (ank_base) => {
@@ -690,6 +995,8 @@ mod tests {
                     ..Default::default()
                 }
                 .into(),
+                agent_timestamp: 0,
+                server_timestamp: 0,
             }
         };
     }
@@ -14,7 +14,7 @@
 
 use std::pin::Pin;
 
-use common::to_server_interface;
+use common::{request_id_prepending::CLI_CONNECTION_PREFIX, to_server_interface};
 use tokio::sync::mpsc::Sender;
 use tokio_stream::wrappers::ReceiverStream;
 
@@ -22,7 +22,9 @@ use tonic::codegen::futures_core::Stream;
 use tonic::{Request, Response, Status};
 
 use crate::agent_senders_map::AgentSendersMap;
-use crate::to_server_proxy::{forward_from_proto_to_ankaios, GRPCToServerStreaming};
+use crate::to_server_proxy::{
+    forward_from_proto_to_ankaios, GRPCToServerStreaming, MessageSequence,
+};
 use grpc_api::cli_connection_server::CliConnection;
 
 use crate::grpc_api;
@@ -61,7 +63,7 @@ impl CliConnection for GRPCCliConnection {
             Result<grpc_api::FromServer, tonic::Status>,
         >(common::CHANNEL_CAPACITY);
 
-        let cli_connection_name = format!("cli-conn-{}", uuid::Uuid::new_v4());
+        let cli_connection_name = format!("{CLI_CONNECTION_PREFIX}{}", uuid::Uuid::new_v4());
         log::debug!("Connection to CLI (name={}) open.", cli_connection_name);
 
         let ankaios_tx = self.to_ankaios_server.clone();
@@ -73,6 +75,8 @@ impl CliConnection for GRPCCliConnection {
                 cli_connection_name.clone(),
                 &mut stream,
                 ankaios_tx.clone(),
+                &cli_senders,
+                &mut MessageSequence::new(),
             )
             .await;
             if result.is_err() {
@@ -0,0 +1,288 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::pin::Pin;
+
+use api::ank_base::{self, response::ResponseContent};
+use common::request_id_prepending::prepend_request_id;
+use common::to_server_interface::{ToServerInterface, ToServerSender};
+use tokio::sync::mpsc::{Receiver, Sender};
+use tokio_stream::wrappers::ReceiverStream;
+
+use tonic::codegen::futures_core::Stream;
+use tonic::{Request, Response, Status};
+
+use crate::agent_senders_map::AgentSendersMap;
+use crate::grpc_api::{self, from_server::FromServerEnum, state_watch_server::StateWatch};
+
+const WATCH_REQUEST_ID: &str = "watch";
+
+type CompleteStateStreamItem = Result<ank_base::CompleteState, Status>;
+
+#[derive(Debug)]
+pub struct GRPCStateWatch {
+    watch_senders: AgentSendersMap,
+    to_ankaios_server: ToServerSender,
+}
+
+impl GRPCStateWatch {
+    pub fn new(watch_senders: AgentSendersMap, to_ankaios_server: ToServerSender) -> Self {
+        Self {
+            watch_senders,
+            to_ankaios_server,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl StateWatch for GRPCStateWatch {
+    type WatchCompleteStateStream =
+        Pin<Box<dyn Stream<Item = CompleteStateStreamItem> + Send + 'static>>;
+
+    // [impl->swdd~grpc-server-provides-state-watch-stream~1]
+    async fn watch_complete_state(
+        &self,
+        request: Request<ank_base::WatchCompleteStateRequest>,
+    ) -> Result<Response<Self::WatchCompleteStateStream>, Status> {
+        let watch_connection_name = format!("watch-conn-{}", uuid::Uuid::new_v4());
+        let request_id = prepend_request_id(WATCH_REQUEST_ID, &watch_connection_name);
+
+        let (from_server_sender, from_server_receiver) = tokio::sync::mpsc::channel::<
+            Result<grpc_api::FromServer, Status>,
+        >(common::CHANNEL_CAPACITY);
+        let (stream_sender, stream_receiver) =
+            tokio::sync::mpsc::channel::<CompleteStateStreamItem>(common::CHANNEL_CAPACITY);
+
+        self.watch_senders
+            .insert(&watch_connection_name, from_server_sender);
+        if let Err(error) = self
+            .to_ankaios_server
+            .request_watch_complete_state(request_id.clone(), request.into_inner().into())
+            .await
+        {
+            self.watch_senders.remove(&watch_connection_name);
+            return Err(Status::unavailable(error.to_string()));
+        }
+        log::debug!("Watch (name={}) open.", watch_connection_name);
+
+        let watch_senders = self.watch_senders.clone();
+        let to_ankaios_server = self.to_ankaios_server.clone();
+        tokio::spawn(async move {
+            forward_watched_states(from_server_receiver, stream_sender).await;
+            watch_senders.remove(&watch_connection_name);
+            // [impl->swdd~grpc-server-cancels-watch-on-closed-stream~1]
+            if let Err(error) = to_ankaios_server.cancel_watch(request_id).await {
+                log::debug!(
+                    "Could not cancel watch (name={}): '{}'",
+                    watch_connection_name,
+                    error
+                );
+            }
+            log::debug!("Watch (name={}) has been closed.", watch_connection_name);
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(
+            stream_receiver,
+        ))))
+    }
+}
+
+// Returns when the client closed the stream or the server ended the watch.
+async fn forward_watched_states(
+    mut from_server_receiver: Receiver<Result<grpc_api::FromServer, Status>>,
+    stream_sender: Sender<CompleteStateStreamItem>,
+) {
+    loop {
+        let from_server = tokio::select! {
+            from_server = from_server_receiver.recv() => from_server,
+            _ = stream_sender.closed() => break,
+        };
+
+        let response = match from_server {
+            Some(Ok(grpc_api::FromServer {
+                from_server_enum: Some(FromServerEnum::Response(response)),
+            })) => response,
+            // the workload states distributed to all connections are not part of the watch
+            Some(Ok(_)) => continue,
+            Some(Err(status)) => {
+                let _ = stream_sender.send(Err(status)).await;
+                break;
+            }
+            None => break,
+        };
+
+        match response.response_content {
+            Some(ResponseContent::CompleteState(complete_state)) => {
+                if stream_sender.send(Ok(complete_state)).await.is_err() {
+                    break;
+                }
+            }
+            Some(ResponseContent::Error(error)) => {
+                let _ = stream_sender
+                    .send(Err(Status::invalid_argument(error.message)))
+                    .await;
+                break;
+            }
+            response_content => {
+                log::warn!(
+                    "Received unexpected response for a watch: '{:?}'",
+                    response_content
+                );
+            }
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use api::ank_base::{self, response::ResponseContent};
+    use common::commands::{self, RequestContent};
+    use common::to_server_interface::ToServer;
+    use tokio::sync::mpsc;
+    use tokio_stream::StreamExt;
+    use tonic::Request;
+
+    use super::{GRPCStateWatch, StateWatch};
+    use crate::agent_senders_map::AgentSendersMap;
+    use crate::grpc_api::{self, from_server::FromServerEnum};
+
+    fn response(request_id: &str, response_content: ResponseContent) -> grpc_api::FromServer {
+        grpc_api::FromServer {
+            from_server_enum: Some(FromServerEnum::Response(ank_base::Response {
+                request_id: request_id.to_owned(),
+                trace_id: "trace_id".to_owned(),
+                response_content: Some(response_content),
+            })),
+        }
+    }
+
+    // [utest->swdd~grpc-server-provides-state-watch-stream~1]
+    // [utest->swdd~grpc-server-cancels-watch-on-closed-stream~1]
+    #[tokio::test]
+    async fn utest_watch_complete_state_streams_states_and_cancels_on_close() {
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+        let watch_senders = AgentSendersMap::new();
+        let state_watch = GRPCStateWatch::new(watch_senders.clone(), server_tx);
+
+        let mut stream = state_watch
+            .watch_complete_state(Request::new(ank_base::WatchCompleteStateRequest {
+                field_mask: vec!["desiredState".to_owned()],
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(ToServer::Request(commands::Request {
+            request_id,
+            request_content: RequestContent::WatchCompleteStateRequest(watch_request),
+        })) = server_rx.recv().await
+        else {
+            panic!("Expected a WatchCompleteStateRequest");
+        };
+        assert_eq!(watch_request.field_mask, vec!["desiredState".to_owned()]);
+
+        let (watch_connection_name, raw_request_id) = request_id
+            .split_once('@')
+            .map(|(name, id)| (name.to_owned(), id.to_owned()))
+            .unwrap();
+        let sender = watch_senders.get(&watch_connection_name).unwrap();
+        let complete_state = ank_base::CompleteState::default();
+        sender
+            .send(Ok(response(
+                &raw_request_id,
+                ResponseContent::CompleteState(complete_state.clone()),
+            )))
+            .await
+            .unwrap();
+        sender
+            .send(Ok(grpc_api::FromServer {
+                from_server_enum: Some(FromServerEnum::UpdateWorkloadState(
+                    grpc_api::UpdateWorkloadState {
+                        workload_states: vec![],
+                    },
+                )),
+            }))
+            .await
+            .unwrap();
+        sender
+            .send(Ok(response(
+                &raw_request_id,
+                ResponseContent::CompleteState(complete_state.clone()),
+            )))
+            .await
+            .unwrap();
+
+        assert_eq!(stream.next().await.unwrap().unwrap(), complete_state);
+        assert_eq!(stream.next().await.unwrap().unwrap(), complete_state);
+
+        drop(stream);
+
+        assert_eq!(
+            server_rx.recv().await,
+            Some(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::CancelWatchRequest(
+                    commands::CancelWatchRequest {}
+                ),
+            }))
+        );
+        assert!(watch_senders.get(&watch_connection_name).is_none());
+    }
+
+    // [utest->swdd~grpc-server-provides-state-watch-stream~1]
+    #[tokio::test]
+    async fn utest_watch_complete_state_ends_stream_on_error_response() {
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+        let watch_senders = AgentSendersMap::new();
+        let state_watch = GRPCStateWatch::new(watch_senders.clone(), server_tx);
+
+        let mut stream = state_watch
+            .watch_complete_state(Request::new(ank_base::WatchCompleteStateRequest {
+                field_mask: vec!["invalid".to_owned()],
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(ToServer::Request(commands::Request { request_id, .. })) = server_rx.recv().await
+        else {
+            panic!("Expected a request");
+        };
+        let (watch_connection_name, raw_request_id) = request_id.split_once('@').unwrap();
+        watch_senders
+            .get(watch_connection_name)
+            .unwrap()
+            .send(Ok(response(
+                raw_request_id,
+                ResponseContent::Error(ank_base::Error {
+                    message: "invalid field mask".to_owned(),
+                }),
+            )))
+            .await
+            .unwrap();
+
+        let status = stream.next().await.unwrap().unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert_eq!(status.message(), "invalid field mask");
+        assert!(stream.next().await.is_none());
+    }
+}
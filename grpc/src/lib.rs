@@ -27,9 +27,14 @@ mod from_server_proxy;
 mod grpc_agent_connection;
 mod grpc_cli_connection;
 pub mod grpc_middleware_error;
+mod grpc_state_watch;
 pub mod server;
 mod to_server_proxy;
+mod workload_state_batch;
 
 use api::ank_base;
+// prost generates the messages of a oneof inline into its enum, like for the messages of the
+// api crate the variants are not boxed to keep the generated types of the API.
+#[allow(clippy::large_enum_variant)]
 pub mod grpc_api;
 pub use crate::grpc_api::*;
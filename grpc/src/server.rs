@@ -15,6 +15,7 @@
 use crate::grpc_api::cli_connection_server::CliConnectionServer;
 use common::communications_error::CommunicationMiddlewareError;
 use common::communications_server::CommunicationsServer;
+use common::input_limits::MAX_MESSAGE_SIZE;
 
 use tonic::transport::Server;
 
@@ -22,8 +23,10 @@ use std::net::SocketAddr;
 
 use crate::agent_senders_map::AgentSendersMap;
 use crate::grpc_api::agent_connection_server::AgentConnectionServer;
+use crate::grpc_api::state_watch_server::StateWatchServer;
 use crate::grpc_cli_connection::GRPCCliConnection;
 use crate::grpc_middleware_error::GrpcMiddlewareError;
+use crate::grpc_state_watch::GRPCStateWatch;
 
 use crate::from_server_proxy;
 use crate::grpc_agent_connection::GRPCAgentConnection;
@@ -54,15 +57,29 @@ impl CommunicationsServer for GRPCCommunicationsServer {
         let my_cli_connection =
             GRPCCliConnection::new(self.agent_senders.clone(), self.sender.clone());
 
+        // [impl->swdd~grpc-server-provides-state-watch-stream~1]
+        let my_state_watch = GRPCStateWatch::new(self.agent_senders.clone(), self.sender.clone());
+
         let agent_senders_clone = self.agent_senders.clone();
 
         tokio::select! {
             // [impl->swdd~grpc-server-spawns-tonic-service~1]
             // [impl->swdd~grpc-delegate-workflow-to-external-library~1]
             result = Server::builder()
-                .add_service(AgentConnectionServer::new(my_connection))
+                // [impl->swdd~common-limits-message-size~1]
+                .add_service(
+                    AgentConnectionServer::new(my_connection)
+                        .max_decoding_message_size(MAX_MESSAGE_SIZE),
+                )
                 // [impl->swdd~grpc-server-provides-endpoint-for-cli-connection-handling~1]
-                .add_service(CliConnectionServer::new(my_cli_connection))
+                .add_service(
+                    CliConnectionServer::new(my_cli_connection)
+                        .max_decoding_message_size(MAX_MESSAGE_SIZE),
+                )
+                .add_service(
+                    StateWatchServer::new(my_state_watch)
+                        .max_decoding_message_size(MAX_MESSAGE_SIZE),
+                )
                 .serve(addr) => {
                     result.map_err(|err| {
                         GrpcMiddlewareError::StartError(format!("{err:?}"))
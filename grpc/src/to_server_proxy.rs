@@ -12,14 +12,15 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::agent_senders_map::AgentSendersMap;
 use crate::ankaios_streaming::GRPCStreaming;
 use crate::grpc_middleware_error::GrpcMiddlewareError;
 
-use api::ank_base::{
-    self, request::RequestContent, CompleteStateRequest, Request, UpdateStateRequest,
-};
 use crate::grpc_api::{self, to_server::ToServerEnum};
+use api::ank_base::{self, request::RequestContent, CompleteStateRequest, Request};
 
+use common::commands::{AgentEvent, EventKind, PendingWorkloadOperation, UpdateStateRequest};
+use common::objects::{AgentResources, ControlInterfaceMetrics};
 use common::request_id_prepending::prepend_request_id;
 use common::to_server_interface::{ToServer, ToServerInterface, ToServerReceiver, ToServerSender};
 
@@ -45,15 +46,61 @@ impl GRPCStreaming<grpc_api::ToServer> for GRPCToServerStreaming {
     }
 }
 
+// The number of the next message expected from a gRPC client. The numbers of an agent continue
+// across its connections.
+#[derive(Debug)]
+pub struct MessageSequence {
+    next_message_sequence_number: u64,
+}
+
+impl MessageSequence {
+    pub fn new() -> Self {
+        MessageSequence {
+            next_message_sequence_number: 1,
+        }
+    }
+
+    // Returns the expected number if it differs from the received one. A number of 0 is sent by
+    // clients not numbering their messages and is not checked. A restarted client starts again
+    // with the number 1.
+    // [impl->swdd~grpc-agent-connection-detects-gaps-in-message-sequence~2]
+    fn check(&mut self, message_sequence_number: u64) -> Option<u64> {
+        if message_sequence_number == 0 {
+            return None;
+        }
+        let expected = self.next_message_sequence_number;
+        self.next_message_sequence_number = message_sequence_number + 1;
+        (message_sequence_number != expected && message_sequence_number != 1).then_some(expected)
+    }
+}
+
+impl Default for MessageSequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // [impl->swdd~grpc-agent-connection-forwards-commands-to-server~1]
 pub async fn forward_from_proto_to_ankaios(
     agent_name: String,
     grpc_streaming: &mut impl GRPCStreaming<grpc_api::ToServer>,
     sink: ToServerSender,
+    agent_senders: &AgentSendersMap,
+    message_sequence: &mut MessageSequence,
 ) -> Result<(), GrpcMiddlewareError> {
     while let Some(message) = grpc_streaming.message().await? {
         log::trace!("REQUEST={:?}", message);
 
+        // [impl->swdd~grpc-agent-connection-detects-gaps-in-message-sequence~2]
+        if let Some(expected) = message_sequence.check(message.message_sequence_number) {
+            log::warn!(
+                "Detected a gap in the messages from '{}': expected the message {}, received the message {}.",
+                agent_name,
+                expected,
+                message.message_sequence_number
+            );
+        }
+
         match message
             .to_server_enum
             .ok_or(GrpcMiddlewareError::ReceiveError(
@@ -71,14 +118,12 @@ pub async fn forward_from_proto_to_ankaios(
                     "Request content empty for request ID: '{}'",
                     request_id
                 )))? {
-                    RequestContent::UpdateStateRequest(UpdateStateRequest {
-                        new_state,
-                        update_mask,
-                    }) => {
+                    RequestContent::UpdateStateRequest(update_state_request) => {
                         log::debug!("Received UpdateStateRequest from '{}'", agent_name);
-                        match new_state.unwrap_or_default().try_into() {
-                            Ok(new_state) => {
-                                sink.update_state(request_id, new_state, update_mask)
+                        match UpdateStateRequest::try_from(update_state_request) {
+                            Ok(update_state_request) => {
+                                // [impl->swdd~server-propagates-update-deadline~1]
+                                sink.request_update_state(request_id, update_state_request)
                                     .await?;
                             }
                             Err(error) => {
@@ -97,6 +142,50 @@ pub async fn forward_from_proto_to_ankaios(
                         )
                         .await?;
                     }
+                    RequestContent::RolloutStatusRequest(_) => {
+                        log::trace!("Received RolloutStatusRequest from '{}'", agent_name);
+                        sink.request_rollout_status(request_id).await?;
+                    }
+                    RequestContent::SupportInfoRequest(_) => {
+                        log::trace!("Received SupportInfoRequest from '{}'", agent_name);
+                        sink.request_support_info(request_id).await?;
+                    }
+                    RequestContent::EventsRequest(events_request) => {
+                        log::trace!("Received EventsRequest from '{}'", agent_name);
+                        sink.request_events(request_id, events_request.into())
+                            .await?;
+                    }
+                    RequestContent::DrainAgentRequest(drain_agent_request) => {
+                        log::trace!("Received DrainAgentRequest from '{}'", agent_name);
+                        sink.request_drain_agent(request_id, drain_agent_request.into())
+                            .await?;
+                    }
+                    RequestContent::WatchCompleteStateRequest(watch_complete_state_request) => {
+                        log::trace!("Received WatchCompleteStateRequest from '{}'", agent_name);
+                        sink.request_watch_complete_state(
+                            request_id,
+                            watch_complete_state_request.into(),
+                        )
+                        .await?;
+                    }
+                    RequestContent::CancelWatchRequest(_) => {
+                        log::trace!("Received CancelWatchRequest from '{}'", agent_name);
+                        sink.cancel_watch(request_id).await?;
+                    }
+                    RequestContent::ImpactAnalysisRequest(impact_analysis_request) => {
+                        log::trace!("Received ImpactAnalysisRequest from '{}'", agent_name);
+                        sink.request_impact_analysis(request_id, impact_analysis_request.into())
+                            .await?;
+                    }
+                    RequestContent::DependencyGraphRequest(_) => {
+                        log::trace!("Received DependencyGraphRequest from '{}'", agent_name);
+                        sink.request_dependency_graph(request_id).await?;
+                    }
+                    RequestContent::SchedulerQueueRequest(scheduler_queue_request) => {
+                        log::trace!("Received SchedulerQueueRequest from '{}'", agent_name);
+                        sink.request_scheduler_queue(request_id, scheduler_queue_request.into())
+                            .await?;
+                    }
                 }
             }
 
@@ -113,6 +202,119 @@ pub async fn forward_from_proto_to_ankaios(
                 .await?;
             }
 
+            // [impl->swdd~grpc-agent-connection-forwards-update-workload-ack~1]
+            ToServerEnum::UpdateWorkloadAck(update_workload_ack) => {
+                log::trace!("Received UpdateWorkloadAck from '{}'", agent_name);
+
+                sink.update_workload_ack(agent_name.clone(), update_workload_ack.sequence_number)
+                    .await?;
+            }
+
+            // [impl->swdd~grpc-agent-connection-forwards-agent-event~1]
+            ToServerEnum::AgentEvent(agent_event) => {
+                log::trace!("Received AgentEvent from '{}'", agent_name);
+
+                match EventKind::try_from(agent_event.kind) {
+                    Ok(kind) => {
+                        sink.agent_event(AgentEvent {
+                            agent_name: agent_name.clone(),
+                            kind,
+                            workload_name: agent_event.workload_name,
+                            message: agent_event.message,
+                        })
+                        .await?;
+                    }
+                    Err(error) => {
+                        log::warn!(
+                            "Could not convert AgentEvent from '{}': '{}'",
+                            agent_name,
+                            error
+                        );
+                    }
+                }
+            }
+
+            // [impl->swdd~grpc-agent-connection-forwards-scheduler-queue~1]
+            ToServerEnum::UpdateSchedulerQueue(update_scheduler_queue) => {
+                log::trace!("Received UpdateSchedulerQueue from '{}'", agent_name);
+
+                match update_scheduler_queue
+                    .pending_operations
+                    .into_iter()
+                    .map(|x| x.try_into())
+                    .collect::<Result<Vec<PendingWorkloadOperation>, String>>()
+                {
+                    Ok(pending_operations) => {
+                        sink.update_scheduler_queue(agent_name.clone(), pending_operations)
+                            .await?;
+                    }
+                    Err(error) => {
+                        log::warn!(
+                            "Could not convert UpdateSchedulerQueue from '{}': '{}'",
+                            agent_name,
+                            error
+                        );
+                    }
+                }
+            }
+
+            // [impl->swdd~grpc-server-filters-workload-states-by-subscription~1]
+            ToServerEnum::SubscribeWorkloadStates(subscribe_workload_states) => {
+                log::trace!(
+                    "Received SubscribeWorkloadStates from '{}' for '{}' workload(s)",
+                    agent_name,
+                    subscribe_workload_states.workload_names.len()
+                );
+
+                let added_workload_names = agent_senders.subscribe_workload_states(
+                    &agent_name,
+                    subscribe_workload_states.workload_names,
+                );
+                // [impl->swdd~grpc-server-requests-workload-states-of-added-subscriptions~1]
+                if !added_workload_names.is_empty() {
+                    sink.subscribe_workload_states(added_workload_names).await?;
+                }
+            }
+
+            // [impl->swdd~grpc-agent-connection-forwards-control-interface-metrics~1]
+            ToServerEnum::UpdateControlInterfaceMetrics(update_metrics) => {
+                log::trace!(
+                    "Received UpdateControlInterfaceMetrics from '{}'",
+                    agent_name
+                );
+
+                if let Some(metrics) = update_metrics.metrics {
+                    sink.update_control_interface_metrics(ControlInterfaceMetrics {
+                        agent_name: agent_name.clone(),
+                        ..metrics.into()
+                    })
+                    .await?;
+                } else {
+                    log::warn!(
+                        "Received UpdateControlInterfaceMetrics without metrics from '{}'",
+                        agent_name
+                    );
+                }
+            }
+
+            // [impl->swdd~grpc-agent-connection-forwards-agent-resources~1]
+            ToServerEnum::UpdateAgentResources(update_resources) => {
+                log::trace!("Received UpdateAgentResources from '{}'", agent_name);
+
+                if let Some(resources) = update_resources.resources {
+                    sink.update_agent_resources(
+                        agent_name.clone(),
+                        AgentResources::from(resources),
+                    )
+                    .await?;
+                } else {
+                    log::warn!(
+                        "Received UpdateAgentResources without resources from '{}'",
+                        agent_name
+                    );
+                }
+            }
+
             ToServerEnum::Goodbye(_goodbye) => {
                 log::trace!(
                     "Received Goodbye from '{}'. Stopping the control loop.",
@@ -129,35 +331,76 @@ pub async fn forward_from_proto_to_ankaios(
 }
 
 // [impl->swdd~grpc-client-forwards-commands-to-grpc-agent-connection~1]
+// [impl->swdd~grpc-client-numbers-to-server-messages~2]
 pub async fn forward_from_ankaios_to_proto(
     grpc_tx: Sender<grpc_api::ToServer>,
     server_rx: &mut ToServerReceiver,
+    message_sequence_number: &mut u64,
 ) -> Result<(), GrpcMiddlewareError> {
     while let Some(x) = server_rx.recv().await {
-        match x {
+        let to_server_enum = match x {
             ToServer::Request(request) => {
                 log::trace!("Received Request from agent");
-                grpc_tx
-                    .send(grpc_api::ToServer {
-                        to_server_enum: Some(ToServerEnum::Request(request.into())),
-                    })
-                    .await?;
+                ToServerEnum::Request(request.into())
             }
             ToServer::UpdateWorkloadState(method_obj) => {
                 log::trace!("Received UpdateWorkloadState from agent");
-
-                grpc_tx
-                    .send(grpc_api::ToServer {
-                        to_server_enum: Some(
-                            grpc_api::to_server::ToServerEnum::UpdateWorkloadState(
-                                common::commands::UpdateWorkloadState {
-                                    workload_states: method_obj.workload_states,
-                                }
-                                .into(),
-                            ),
-                        ),
-                    })
-                    .await?;
+                ToServerEnum::UpdateWorkloadState(
+                    common::commands::UpdateWorkloadState {
+                        workload_states: method_obj.workload_states,
+                    }
+                    .into(),
+                )
+            }
+            // [impl->swdd~grpc-client-forwards-update-workload-ack~1]
+            ToServer::UpdateWorkloadAck(method_obj) => {
+                log::trace!("Received UpdateWorkloadAck from agent");
+                ToServerEnum::UpdateWorkloadAck(grpc_api::UpdateWorkloadAck {
+                    sequence_number: method_obj.sequence_number,
+                })
+            }
+            // [impl->swdd~grpc-client-forwards-agent-event~1]
+            ToServer::AgentEvent(method_obj) => {
+                log::trace!("Received AgentEvent from agent");
+                ToServerEnum::AgentEvent(grpc_api::AgentEvent {
+                    kind: method_obj.kind as i32,
+                    workload_name: method_obj.workload_name,
+                    message: method_obj.message,
+                })
+            }
+            // [impl->swdd~grpc-client-forwards-scheduler-queue~1]
+            ToServer::UpdateSchedulerQueue(method_obj) => {
+                log::trace!("Received UpdateSchedulerQueue from agent");
+                ToServerEnum::UpdateSchedulerQueue(grpc_api::UpdateSchedulerQueue {
+                    pending_operations: method_obj
+                        .pending_operations
+                        .into_iter()
+                        .map(|x| x.into())
+                        .collect(),
+                })
+            }
+            // [impl->swdd~grpc-client-forwards-workload-state-subscription~1]
+            ToServer::SubscribeWorkloadStates(method_obj) => {
+                log::trace!("Received SubscribeWorkloadStates from agent");
+                ToServerEnum::SubscribeWorkloadStates(grpc_api::SubscribeWorkloadStates {
+                    workload_names: method_obj.workload_names,
+                })
+            }
+            // [impl->swdd~grpc-client-forwards-control-interface-metrics~1]
+            ToServer::UpdateControlInterfaceMetrics(method_obj) => {
+                log::trace!("Received UpdateControlInterfaceMetrics from agent");
+                ToServerEnum::UpdateControlInterfaceMetrics(
+                    grpc_api::UpdateControlInterfaceMetrics {
+                        metrics: Some(method_obj.metrics.into()),
+                    },
+                )
+            }
+            // [impl->swdd~grpc-client-forwards-agent-resources~1]
+            ToServer::UpdateAgentResources(method_obj) => {
+                log::trace!("Received UpdateAgentResources from agent");
+                ToServerEnum::UpdateAgentResources(grpc_api::UpdateAgentResources {
+                    resources: Some(method_obj.resources.into()),
+                })
             }
             ToServer::Stop(_method_obj) => {
                 log::debug!("Received Stop from agent");
@@ -173,7 +416,15 @@ pub async fn forward_from_ankaios_to_proto(
             ToServer::Goodbye(_) => {
                 panic!("Goodbye was not expected at this point.");
             }
-        }
+        };
+
+        *message_sequence_number += 1;
+        grpc_tx
+            .send(grpc_api::ToServer {
+                to_server_enum: Some(to_server_enum),
+                message_sequence_number: *message_sequence_number,
+            })
+            .await?;
     }
 
     grpc_tx
@@ -181,6 +432,7 @@ pub async fn forward_from_ankaios_to_proto(
             to_server_enum: Some(grpc_api::to_server::ToServerEnum::Goodbye(
                 crate::grpc_api::Goodbye {},
             )),
+            message_sequence_number: *message_sequence_number + 1,
         })
         .await?;
     grpc_tx.closed().await;
@@ -199,13 +451,18 @@ pub async fn forward_from_ankaios_to_proto(
 #[cfg(test)]
 mod tests {
 
-    use std::collections::LinkedList;
+    use std::collections::{HashSet, LinkedList};
 
-    use super::{forward_from_ankaios_to_proto, forward_from_proto_to_ankaios, GRPCStreaming};
+    use super::{
+        forward_from_ankaios_to_proto, forward_from_proto_to_ankaios, AgentSendersMap,
+        GRPCStreaming, MessageSequence,
+    };
     use async_trait::async_trait;
     use common::test_utils::generate_test_complete_state;
     use common::{
-        objects::generate_test_workload_spec_with_param,
+        objects::{
+            generate_test_workload_spec_with_param, AgentResources, ControlInterfaceMetrics,
+        },
         to_server_interface::{ToServer, ToServerInterface},
     };
     use tokio::sync::mpsc;
@@ -258,7 +515,7 @@ mod tests {
         assert!(update_state_result.is_ok());
 
         tokio::spawn(async move {
-            let _ = forward_from_ankaios_to_proto(grpc_tx, &mut server_rx).await;
+            let _ = forward_from_ankaios_to_proto(grpc_tx, &mut server_rx, &mut 0).await;
         });
 
         // The receiver in the agent receives the message and terminates the infinite waiting-loop.
@@ -270,7 +527,7 @@ mod tests {
 
         assert!(matches!(
             result.to_server_enum,
-            Some(ToServerEnum::Request(ank_base::Request{request_id, request_content: Some(ank_base::request::RequestContent::UpdateStateRequest(UpdateStateRequest{new_state, update_mask}))}))
+            Some(ToServerEnum::Request(ank_base::Request{request_id, request_content: Some(ank_base::request::RequestContent::UpdateStateRequest(UpdateStateRequest{new_state, update_mask, ..}))}))
             if request_id == "request_id" && new_state == Some(proto_state) && update_mask == update_mask));
     }
 
@@ -292,7 +549,7 @@ mod tests {
         assert!(update_workload_state_result.is_ok());
 
         tokio::spawn(async move {
-            let _ = forward_from_ankaios_to_proto(grpc_tx, &mut server_rx).await;
+            let _ = forward_from_ankaios_to_proto(grpc_tx, &mut server_rx, &mut 0).await;
         });
 
         // The receiver in the agent receives the message and terminates the infinite waiting-loop.
@@ -308,6 +565,203 @@ mod tests {
             if workload_states == vec!(proto_workload_state)));
     }
 
+    // [utest->swdd~grpc-client-forwards-update-workload-ack~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_ankaios_to_proto_update_workload_ack() {
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+        let (grpc_tx, mut grpc_rx) = mpsc::channel::<grpc_api::ToServer>(common::CHANNEL_CAPACITY);
+
+        let update_workload_ack_result = server_tx
+            .update_workload_ack("fake_agent".to_string(), 42)
+            .await;
+        assert!(update_workload_ack_result.is_ok());
+
+        tokio::spawn(async move {
+            let _ = forward_from_ankaios_to_proto(grpc_tx, &mut server_rx, &mut 0).await;
+        });
+
+        drop(server_tx);
+
+        let result = grpc_rx.recv().await.unwrap();
+
+        assert_eq!(
+            result.to_server_enum,
+            Some(ToServerEnum::UpdateWorkloadAck(
+                grpc_api::UpdateWorkloadAck {
+                    sequence_number: 42
+                }
+            ))
+        );
+    }
+
+    // [utest->swdd~grpc-client-forwards-agent-event~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_ankaios_to_proto_agent_event() {
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+        let (grpc_tx, mut grpc_rx) = mpsc::channel::<grpc_api::ToServer>(common::CHANNEL_CAPACITY);
+
+        let agent_event_result = server_tx
+            .agent_event(common::commands::AgentEvent {
+                agent_name: "fake_agent".to_string(),
+                kind: common::commands::EventKind::ControlInterfaceClosed,
+                workload_name: "workload_1".to_string(),
+                message: "message".to_string(),
+            })
+            .await;
+        assert!(agent_event_result.is_ok());
+
+        tokio::spawn(async move {
+            let _ = forward_from_ankaios_to_proto(grpc_tx, &mut server_rx, &mut 0).await;
+        });
+
+        drop(server_tx);
+
+        let result = grpc_rx.recv().await.unwrap();
+
+        assert_eq!(
+            result.to_server_enum,
+            Some(ToServerEnum::AgentEvent(grpc_api::AgentEvent {
+                kind: ank_base::EventKind::ControlInterfaceClosed as i32,
+                workload_name: "workload_1".to_string(),
+                message: "message".to_string(),
+            }))
+        );
+    }
+
+    // [utest->swdd~grpc-client-forwards-scheduler-queue~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_ankaios_to_proto_update_scheduler_queue() {
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+        let (grpc_tx, mut grpc_rx) = mpsc::channel::<grpc_api::ToServer>(common::CHANNEL_CAPACITY);
+
+        let pending_operation = common::commands::PendingWorkloadOperation {
+            workload_name: "workload_1".to_string(),
+            agent: "fake_agent".to_string(),
+            operation: common::commands::PendingOperation::Create,
+            unfulfilled_dependencies: vec![],
+        };
+        let update_result = server_tx
+            .update_scheduler_queue("fake_agent".to_string(), vec![pending_operation.clone()])
+            .await;
+        assert!(update_result.is_ok());
+
+        tokio::spawn(async move {
+            let _ = forward_from_ankaios_to_proto(grpc_tx, &mut server_rx, &mut 0).await;
+        });
+
+        drop(server_tx);
+
+        let result = grpc_rx.recv().await.unwrap();
+
+        assert_eq!(
+            result.to_server_enum,
+            Some(ToServerEnum::UpdateSchedulerQueue(
+                grpc_api::UpdateSchedulerQueue {
+                    pending_operations: vec![pending_operation.into()],
+                }
+            ))
+        );
+    }
+
+    // [utest->swdd~grpc-client-forwards-workload-state-subscription~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_ankaios_to_proto_subscribe_workload_states() {
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+        let (grpc_tx, mut grpc_rx) = mpsc::channel::<grpc_api::ToServer>(common::CHANNEL_CAPACITY);
+
+        let subscribe_result = server_tx
+            .subscribe_workload_states(vec!["workload_1".to_string()])
+            .await;
+        assert!(subscribe_result.is_ok());
+
+        tokio::spawn(async move {
+            let _ = forward_from_ankaios_to_proto(grpc_tx, &mut server_rx, &mut 0).await;
+        });
+
+        drop(server_tx);
+
+        let result = grpc_rx.recv().await.unwrap();
+
+        assert_eq!(
+            result.to_server_enum,
+            Some(ToServerEnum::SubscribeWorkloadStates(
+                grpc_api::SubscribeWorkloadStates {
+                    workload_names: vec!["workload_1".to_string()],
+                }
+            ))
+        );
+    }
+
+    // [utest->swdd~grpc-client-forwards-control-interface-metrics~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_ankaios_to_proto_control_interface_metrics() {
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+        let (grpc_tx, mut grpc_rx) = mpsc::channel::<grpc_api::ToServer>(common::CHANNEL_CAPACITY);
+
+        let metrics = ControlInterfaceMetrics {
+            workload_name: "workload_1".to_string(),
+            forwarded_requests: 3,
+            rejected_requests: 1,
+            mean_latency_ms: 4,
+            max_latency_ms: 8,
+            ..Default::default()
+        };
+        let update_result = server_tx
+            .update_control_interface_metrics(metrics.clone())
+            .await;
+        assert!(update_result.is_ok());
+
+        tokio::spawn(async move {
+            let _ = forward_from_ankaios_to_proto(grpc_tx, &mut server_rx, &mut 0).await;
+        });
+
+        drop(server_tx);
+
+        let result = grpc_rx.recv().await.unwrap();
+
+        assert_eq!(
+            result.to_server_enum,
+            Some(ToServerEnum::UpdateControlInterfaceMetrics(
+                grpc_api::UpdateControlInterfaceMetrics {
+                    metrics: Some(metrics.into()),
+                }
+            ))
+        );
+    }
+
+    // [utest->swdd~grpc-client-forwards-agent-resources~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_ankaios_to_proto_agent_resources() {
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+        let (grpc_tx, mut grpc_rx) = mpsc::channel::<grpc_api::ToServer>(common::CHANNEL_CAPACITY);
+
+        let resources = AgentResources {
+            free_cpu_millis: 1500,
+            free_memory_bytes: 4096,
+        };
+        let update_result = server_tx
+            .update_agent_resources("fake_agent".to_string(), resources)
+            .await;
+        assert!(update_result.is_ok());
+
+        tokio::spawn(async move {
+            let _ = forward_from_ankaios_to_proto(grpc_tx, &mut server_rx, &mut 0).await;
+        });
+
+        drop(server_tx);
+
+        let result = grpc_rx.recv().await.unwrap();
+
+        assert_eq!(
+            result.to_server_enum,
+            Some(ToServerEnum::UpdateAgentResources(
+                grpc_api::UpdateAgentResources {
+                    resources: Some(resources.into()),
+                }
+            ))
+        );
+    }
+
     // [utest->swdd~grpc-agent-connection-forwards-commands-to-server~1]
     #[tokio::test]
     async fn utest_to_server_command_forward_from_proto_to_ankaios_ignores_none() {
@@ -323,6 +777,8 @@ mod tests {
             agent_name.into(),
             &mut mock_grpc_ex_request_streaming,
             server_tx,
+            &AgentSendersMap::new(),
+            &mut MessageSequence::new(),
         )
         .await;
         assert!(forward_result.is_err());
@@ -345,6 +801,7 @@ mod tests {
             MockGRPCToServerStreaming::new(LinkedList::from([
                 Some(grpc_api::ToServer {
                     to_server_enum: None,
+                    message_sequence_number: 0,
                 }),
                 None,
             ]));
@@ -354,6 +811,8 @@ mod tests {
             agent_name.into(),
             &mut mock_grpc_ex_request_streaming,
             server_tx,
+            &AgentSendersMap::new(),
+            &mut MessageSequence::new(),
         )
         .await;
         assert!(forward_result.is_err());
@@ -405,10 +864,14 @@ mod tests {
                                 ank_base::UpdateStateRequest {
                                     new_state: Some(ankaios_state),
                                     update_mask: ankaios_update_mask.clone(),
+                                    deadline_ms: 0,
+                                    rollback_on_deadline_exceeded: false,
+                                    force: false,
                                 },
                             ),
                         ),
                     })),
+                    message_sequence_number: 0,
                 }),
                 None,
             ]));
@@ -418,12 +881,15 @@ mod tests {
             agent_name.into(),
             &mut mock_grpc_ex_request_streaming,
             server_tx,
+            &AgentSendersMap::new(),
+            &mut MessageSequence::new(),
         )
         .await;
         assert!(forward_result.is_err());
     }
 
     // [utest->swdd~grpc-agent-connection-forwards-commands-to-server~1]
+    // [utest->swdd~server-propagates-update-deadline~1]
     #[tokio::test]
     async fn utest_to_server_command_forward_from_proto_to_ankaios_update_workload() {
         let agent_name = "fake_agent";
@@ -449,10 +915,14 @@ mod tests {
                                 ank_base::UpdateStateRequest {
                                     new_state: Some(ankaios_state.clone().into()),
                                     update_mask: ankaios_update_mask.clone(),
+                                    deadline_ms: 5000,
+                                    rollback_on_deadline_exceeded: true,
+                                    force: false,
                                 },
                             ),
                         ),
                     })),
+                    message_sequence_number: 0,
                 }),
                 None,
             ]));
@@ -462,6 +932,8 @@ mod tests {
             agent_name.into(),
             &mut mock_grpc_ex_request_streaming,
             server_tx,
+            &AgentSendersMap::new(),
+            &mut MessageSequence::new(),
         )
         .await;
 
@@ -477,7 +949,8 @@ mod tests {
                 request_id,
                 request_content: common::commands::RequestContent::UpdateStateRequest(update_request),
             })
-            if request_id == expected_prefixed_my_request_id && update_request.state == ankaios_state && update_request.update_mask == ankaios_update_mask));
+            if request_id == expected_prefixed_my_request_id && update_request.state == ankaios_state && update_request.update_mask == ankaios_update_mask
+                && update_request.deadline_ms == Some(5000) && update_request.rollback_on_deadline_exceeded));
     }
 
     // [utest->swdd~grpc-agent-connection-forwards-commands-to-server~1]
@@ -503,6 +976,7 @@ mod tests {
                             workload_states: vec![proto_wl_state.clone()],
                         },
                     )),
+                    message_sequence_number: 0,
                 }),
                 None,
             ]));
@@ -512,6 +986,8 @@ mod tests {
             agent_name.into(),
             &mut mock_grpc_ex_request_streaming,
             server_tx,
+            &AgentSendersMap::new(),
+            &mut MessageSequence::new(),
         )
         .await;
 
@@ -528,6 +1004,359 @@ mod tests {
         ));
     }
 
+    // [utest->swdd~grpc-agent-connection-forwards-update-workload-ack~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_proto_to_ankaios_update_workload_ack() {
+        let agent_name = "fake_agent";
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCToServerStreaming::new(LinkedList::from([
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::UpdateWorkloadAck(
+                        grpc_api::UpdateWorkloadAck {
+                            sequence_number: 42,
+                        },
+                    )),
+                    message_sequence_number: 0,
+                }),
+                None,
+            ]));
+
+        let forward_result = forward_from_proto_to_ankaios(
+            agent_name.into(),
+            &mut mock_grpc_ex_request_streaming,
+            server_tx,
+            &AgentSendersMap::new(),
+            &mut MessageSequence::new(),
+        )
+        .await;
+
+        assert!(forward_result.is_ok());
+
+        assert_eq!(
+            server_rx.recv().await.unwrap(),
+            ToServer::UpdateWorkloadAck(common::commands::UpdateWorkloadAck {
+                agent_name: agent_name.to_string(),
+                sequence_number: 42,
+            })
+        );
+    }
+
+    // [utest->swdd~grpc-agent-connection-forwards-agent-event~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_proto_to_ankaios_agent_event() {
+        let agent_name = "fake_agent";
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCToServerStreaming::new(LinkedList::from([
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::AgentEvent(grpc_api::AgentEvent {
+                        kind: 42,
+                        workload_name: "workload_1".to_string(),
+                        message: "unknown kind".to_string(),
+                    })),
+                    message_sequence_number: 0,
+                }),
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::AgentEvent(grpc_api::AgentEvent {
+                        kind: ank_base::EventKind::ControlInterfaceClosed as i32,
+                        workload_name: "workload_1".to_string(),
+                        message: "message".to_string(),
+                    })),
+                    message_sequence_number: 0,
+                }),
+                None,
+            ]));
+
+        let forward_result = forward_from_proto_to_ankaios(
+            agent_name.into(),
+            &mut mock_grpc_ex_request_streaming,
+            server_tx,
+            &AgentSendersMap::new(),
+            &mut MessageSequence::new(),
+        )
+        .await;
+
+        assert!(forward_result.is_ok());
+
+        assert_eq!(
+            server_rx.recv().await.unwrap(),
+            ToServer::AgentEvent(common::commands::AgentEvent {
+                agent_name: agent_name.to_string(),
+                kind: common::commands::EventKind::ControlInterfaceClosed,
+                workload_name: "workload_1".to_string(),
+                message: "message".to_string(),
+            })
+        );
+        assert!(server_rx.recv().await.is_none());
+    }
+
+    // [utest->swdd~grpc-agent-connection-forwards-scheduler-queue~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_proto_to_ankaios_update_scheduler_queue() {
+        let agent_name = "fake_agent";
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+
+        let pending_operation = ank_base::PendingWorkloadOperation {
+            workload_name: "workload_1".to_string(),
+            agent: agent_name.to_string(),
+            operation: ank_base::PendingOperation::Delete as i32,
+            unfulfilled_dependencies: vec![],
+        };
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCToServerStreaming::new(LinkedList::from([
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::UpdateSchedulerQueue(
+                        grpc_api::UpdateSchedulerQueue {
+                            pending_operations: vec![ank_base::PendingWorkloadOperation {
+                                operation: 42,
+                                ..pending_operation.clone()
+                            }],
+                        },
+                    )),
+                    message_sequence_number: 0,
+                }),
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::UpdateSchedulerQueue(
+                        grpc_api::UpdateSchedulerQueue {
+                            pending_operations: vec![pending_operation],
+                        },
+                    )),
+                    message_sequence_number: 0,
+                }),
+                None,
+            ]));
+
+        let forward_result = forward_from_proto_to_ankaios(
+            agent_name.into(),
+            &mut mock_grpc_ex_request_streaming,
+            server_tx,
+            &AgentSendersMap::new(),
+            &mut MessageSequence::new(),
+        )
+        .await;
+
+        assert!(forward_result.is_ok());
+
+        assert_eq!(
+            server_rx.recv().await.unwrap(),
+            ToServer::UpdateSchedulerQueue(common::commands::UpdateSchedulerQueue {
+                agent_name: agent_name.to_string(),
+                pending_operations: vec![common::commands::PendingWorkloadOperation {
+                    workload_name: "workload_1".to_string(),
+                    agent: agent_name.to_string(),
+                    operation: common::commands::PendingOperation::Delete,
+                    unfulfilled_dependencies: vec![],
+                }],
+            })
+        );
+        assert!(server_rx.recv().await.is_none());
+    }
+
+    // [utest->swdd~grpc-server-filters-workload-states-by-subscription~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_proto_to_ankaios_stores_subscription() {
+        let agent_name = "fake_agent";
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+        let agent_senders = AgentSendersMap::new();
+
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCToServerStreaming::new(LinkedList::from([
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::SubscribeWorkloadStates(
+                        grpc_api::SubscribeWorkloadStates {
+                            workload_names: vec!["workload_1".to_string()],
+                        },
+                    )),
+                    message_sequence_number: 0,
+                }),
+                None,
+            ]));
+
+        let forward_result = forward_from_proto_to_ankaios(
+            agent_name.into(),
+            &mut mock_grpc_ex_request_streaming,
+            server_tx,
+            &agent_senders,
+            &mut MessageSequence::new(),
+        )
+        .await;
+
+        assert!(forward_result.is_ok());
+        assert_eq!(
+            agent_senders.get_workload_state_subscription(agent_name),
+            Some(HashSet::from(["workload_1".to_string()]))
+        );
+        // the first subscription only narrows the workload states sent so far
+        assert!(server_rx.recv().await.is_none());
+    }
+
+    // [utest->swdd~grpc-server-requests-workload-states-of-added-subscriptions~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_proto_to_ankaios_requests_added_subscriptions() {
+        let agent_name = "fake_agent";
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+        let agent_senders = AgentSendersMap::new();
+        agent_senders.subscribe_workload_states(agent_name, vec!["workload_1".to_string()]);
+
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCToServerStreaming::new(LinkedList::from([
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::SubscribeWorkloadStates(
+                        grpc_api::SubscribeWorkloadStates {
+                            workload_names: vec![
+                                "workload_1".to_string(),
+                                "workload_2".to_string(),
+                            ],
+                        },
+                    )),
+                    message_sequence_number: 0,
+                }),
+                None,
+            ]));
+
+        let forward_result = forward_from_proto_to_ankaios(
+            agent_name.into(),
+            &mut mock_grpc_ex_request_streaming,
+            server_tx,
+            &agent_senders,
+            &mut MessageSequence::new(),
+        )
+        .await;
+
+        assert!(forward_result.is_ok());
+        assert_eq!(
+            agent_senders.get_workload_state_subscription(agent_name),
+            Some(HashSet::from([
+                "workload_1".to_string(),
+                "workload_2".to_string()
+            ]))
+        );
+        assert_eq!(
+            server_rx.recv().await.unwrap(),
+            ToServer::SubscribeWorkloadStates(common::commands::SubscribeWorkloadStates {
+                workload_names: vec!["workload_2".to_string()],
+            })
+        );
+        assert!(server_rx.recv().await.is_none());
+    }
+
+    // [utest->swdd~grpc-agent-connection-forwards-agent-resources~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_proto_to_ankaios_agent_resources() {
+        let agent_name = "fake_agent";
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCToServerStreaming::new(LinkedList::from([
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::UpdateAgentResources(
+                        grpc_api::UpdateAgentResources { resources: None },
+                    )),
+                    message_sequence_number: 0,
+                }),
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::UpdateAgentResources(
+                        grpc_api::UpdateAgentResources {
+                            resources: Some(ank_base::AgentResources {
+                                free_cpu_millis: 1500,
+                                free_memory_bytes: 4096,
+                            }),
+                        },
+                    )),
+                    message_sequence_number: 0,
+                }),
+                None,
+            ]));
+
+        let forward_result = forward_from_proto_to_ankaios(
+            agent_name.into(),
+            &mut mock_grpc_ex_request_streaming,
+            server_tx,
+            &AgentSendersMap::new(),
+            &mut MessageSequence::new(),
+        )
+        .await;
+
+        assert!(forward_result.is_ok());
+
+        assert_eq!(
+            server_rx.recv().await.unwrap(),
+            ToServer::UpdateAgentResources(common::commands::UpdateAgentResources {
+                agent_name: agent_name.to_string(),
+                resources: AgentResources {
+                    free_cpu_millis: 1500,
+                    free_memory_bytes: 4096,
+                },
+            })
+        );
+        assert!(server_rx.recv().await.is_none());
+    }
+
+    // [utest->swdd~grpc-agent-connection-forwards-control-interface-metrics~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_proto_to_ankaios_control_interface_metrics() {
+        let agent_name = "fake_agent";
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+
+        let metrics = ank_base::ControlInterfaceMetrics {
+            agent_name: "spoofed_agent".to_string(),
+            workload_name: "workload_1".to_string(),
+            forwarded_requests: 3,
+            rejected_requests: 1,
+            mean_latency_ms: 4,
+            max_latency_ms: 8,
+        };
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCToServerStreaming::new(LinkedList::from([
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::UpdateControlInterfaceMetrics(
+                        grpc_api::UpdateControlInterfaceMetrics { metrics: None },
+                    )),
+                    message_sequence_number: 0,
+                }),
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::UpdateControlInterfaceMetrics(
+                        grpc_api::UpdateControlInterfaceMetrics {
+                            metrics: Some(metrics),
+                        },
+                    )),
+                    message_sequence_number: 0,
+                }),
+                None,
+            ]));
+
+        let forward_result = forward_from_proto_to_ankaios(
+            agent_name.into(),
+            &mut mock_grpc_ex_request_streaming,
+            server_tx,
+            &AgentSendersMap::new(),
+            &mut MessageSequence::new(),
+        )
+        .await;
+
+        assert!(forward_result.is_ok());
+
+        assert_eq!(
+            server_rx.recv().await.unwrap(),
+            ToServer::UpdateControlInterfaceMetrics(
+                common::commands::UpdateControlInterfaceMetrics {
+                    metrics: ControlInterfaceMetrics {
+                        agent_name: agent_name.to_string(),
+                        workload_name: "workload_1".to_string(),
+                        forwarded_requests: 3,
+                        rejected_requests: 1,
+                        mean_latency_ms: 4,
+                        max_latency_ms: 8,
+                    },
+                }
+            )
+        );
+        assert!(server_rx.recv().await.is_none());
+    }
+
     #[tokio::test]
     async fn utest_to_server_command_forward_from_proto_to_ankaios_request_complete_state() {
         let agent_name = "fake_agent";
@@ -545,6 +1374,7 @@ mod tests {
                             ),
                         ),
                     })),
+                    message_sequence_number: 0,
                 }),
                 None,
             ]));
@@ -554,6 +1384,8 @@ mod tests {
             agent_name.into(),
             &mut mock_grpc_ex_request_streaming,
             server_tx,
+            &AgentSendersMap::new(),
+            &mut MessageSequence::new(),
         )
         .await;
         assert!(forward_result.is_ok());
@@ -574,6 +1406,73 @@ mod tests {
         );
     }
 
+    // [utest->swdd~grpc-agent-connection-forwards-commands-to-server~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_proto_to_ankaios_watch_and_cancel_watch() {
+        let agent_name = "fake_agent";
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCToServerStreaming::new(LinkedList::from([
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::Request(ank_base::Request {
+                        request_id: "my_request_id".to_owned(),
+                        request_content: Some(
+                            ank_base::request::RequestContent::WatchCompleteStateRequest(
+                                ank_base::WatchCompleteStateRequest {
+                                    field_mask: vec!["desiredState".to_owned()],
+                                },
+                            ),
+                        ),
+                    })),
+                    message_sequence_number: 0,
+                }),
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::Request(ank_base::Request {
+                        request_id: "my_request_id".to_owned(),
+                        request_content: Some(
+                            ank_base::request::RequestContent::CancelWatchRequest(
+                                ank_base::CancelWatchRequest {},
+                            ),
+                        ),
+                    })),
+                    message_sequence_number: 0,
+                }),
+                None,
+            ]));
+
+        let forward_result = forward_from_proto_to_ankaios(
+            agent_name.into(),
+            &mut mock_grpc_ex_request_streaming,
+            server_tx,
+            &AgentSendersMap::new(),
+            &mut MessageSequence::new(),
+        )
+        .await;
+        assert!(forward_result.is_ok());
+
+        assert_eq!(
+            server_rx.recv().await.unwrap(),
+            ToServer::Request(common::commands::Request {
+                request_id: "fake_agent@my_request_id".to_owned(),
+                request_content: common::commands::RequestContent::WatchCompleteStateRequest(
+                    common::commands::WatchCompleteStateRequest {
+                        field_mask: vec!["desiredState".to_owned()],
+                    }
+                ),
+            })
+        );
+        assert_eq!(
+            server_rx.recv().await.unwrap(),
+            ToServer::Request(common::commands::Request {
+                request_id: "fake_agent@my_request_id".to_owned(),
+                request_content: common::commands::RequestContent::CancelWatchRequest(
+                    common::commands::CancelWatchRequest {}
+                ),
+            })
+        );
+    }
+
     #[tokio::test]
     async fn utest_to_server_command_forward_from_ankaios_to_proto_request_complete_state() {
         let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
@@ -587,7 +1486,7 @@ mod tests {
         assert!(request_complete_state_result.is_ok());
 
         tokio::spawn(async move {
-            let _ = forward_from_ankaios_to_proto(grpc_tx, &mut server_rx).await;
+            let _ = forward_from_ankaios_to_proto(grpc_tx, &mut server_rx, &mut 0).await;
         });
 
         // The receiver in the agent receives the message and terminates the infinite waiting-loop.
@@ -606,4 +1505,84 @@ mod tests {
         }))
         if request_id == "my_request_id" && field_mask == vec![] as Vec<String>));
     }
+
+    // [utest->swdd~grpc-client-numbers-to-server-messages~2]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_ankaios_to_proto_numbers_messages() {
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+        let (grpc_tx, mut grpc_rx) = mpsc::channel::<grpc_api::ToServer>(common::CHANNEL_CAPACITY);
+
+        let wl_state = common::objects::generate_test_workload_state_with_agent(
+            "workload_1",
+            "agent_A",
+            common::objects::ExecutionState::running(),
+        );
+        assert!(server_tx
+            .update_workload_state(vec![wl_state])
+            .await
+            .is_ok());
+        assert!(server_tx
+            .request_complete_state(
+                "my_request_id".to_owned(),
+                common::commands::CompleteStateRequest { field_mask: vec![] }
+            )
+            .await
+            .is_ok());
+
+        // the numbers continue after the 5 messages sent on a previous connection
+        let mut message_sequence_number = 5;
+        tokio::spawn(async move {
+            let _ = forward_from_ankaios_to_proto(
+                grpc_tx,
+                &mut server_rx,
+                &mut message_sequence_number,
+            )
+            .await;
+        });
+
+        drop(server_tx);
+
+        let update_workload_state = grpc_rx.recv().await.unwrap();
+        assert!(matches!(
+            update_workload_state.to_server_enum,
+            Some(ToServerEnum::UpdateWorkloadState(_))
+        ));
+        assert_eq!(update_workload_state.message_sequence_number, 6);
+
+        let request = grpc_rx.recv().await.unwrap();
+        assert!(matches!(
+            request.to_server_enum,
+            Some(ToServerEnum::Request(_))
+        ));
+        assert_eq!(request.message_sequence_number, 7);
+
+        let goodbye = grpc_rx.recv().await.unwrap();
+        assert!(matches!(
+            goodbye.to_server_enum,
+            Some(ToServerEnum::Goodbye(_))
+        ));
+        assert_eq!(goodbye.message_sequence_number, 8);
+    }
+
+    // [utest->swdd~grpc-agent-connection-detects-gaps-in-message-sequence~2]
+    #[test]
+    fn utest_message_sequence_detects_gaps() {
+        let mut message_sequence = MessageSequence::new();
+
+        assert_eq!(message_sequence.check(1), None);
+        assert_eq!(message_sequence.check(2), None);
+        // message 3 is missing
+        assert_eq!(message_sequence.check(4), Some(3));
+        assert_eq!(message_sequence.check(5), None);
+        // unnumbered messages are not checked
+        assert_eq!(message_sequence.check(0), None);
+        assert_eq!(message_sequence.check(6), None);
+        // a repeated message
+        assert_eq!(message_sequence.check(6), Some(7));
+        // the numbers continue after a reconnect, messages lost with the connection are detected
+        assert_eq!(message_sequence.check(10), Some(7));
+        // a restarted client starts again
+        assert_eq!(message_sequence.check(1), None);
+        assert_eq!(message_sequence.check(2), None);
+    }
 }
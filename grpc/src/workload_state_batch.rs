@@ -0,0 +1,201 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use api::ank_base;
+use common::objects::WorkloadState;
+
+// Holds the workload states of one UpdateWorkloadState converted into proto messages.
+// The states are converted once for all agents instead of once per agent and the buffer is only
+// cleared between two updates, thus it grows to the largest update and is not reallocated.
+#[derive(Default)]
+pub struct WorkloadStateBatch {
+    workload_states: Vec<ank_base::WorkloadState>,
+}
+
+impl WorkloadStateBatch {
+    // [impl->swdd~grpc-server-converts-workload-states-once-per-update~1]
+    pub fn fill(&mut self, workload_states: Vec<WorkloadState>) {
+        self.workload_states.clear();
+        self.workload_states.extend(
+            workload_states
+                .into_iter()
+                .map(ank_base::WorkloadState::from),
+        );
+    }
+
+    // [impl->swdd~grpc-server-converts-workload-states-once-per-update~1]
+    // [impl->swdd~grpc-server-filters-workload-states-by-subscription~1]
+    // An agent does not get its own workload states back. An agent with a subscription only
+    // gets the workload states of the subscribed workloads.
+    pub fn workload_states_for_agent(
+        &self,
+        agent_name: &str,
+        subscription: Option<&HashSet<String>>,
+    ) -> Vec<ank_base::WorkloadState> {
+        let is_from_other_agent = |workload_state: &&ank_base::WorkloadState| {
+            workload_state
+                .instance_name
+                .as_ref()
+                .is_none_or(|instance_name| {
+                    instance_name.agent_name != agent_name
+                        && subscription.is_none_or(|workload_names| {
+                            workload_names.contains(&instance_name.workload_name)
+                        })
+                })
+        };
+
+        let count = self
+            .workload_states
+            .iter()
+            .filter(is_from_other_agent)
+            .count();
+        let mut workload_states = Vec::with_capacity(count);
+        workload_states.extend(
+            self.workload_states
+                .iter()
+                .filter(is_from_other_agent)
+                .cloned(),
+        );
+        workload_states
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use api::ank_base;
+    use common::objects::{generate_test_workload_state_with_agent, ExecutionState, WorkloadState};
+    use criterion::{black_box, BatchSize, Criterion};
+
+    use super::WorkloadStateBatch;
+
+    fn workload_states(workload_count: usize, agent_count: usize) -> Vec<WorkloadState> {
+        (0..workload_count)
+            .map(|index| {
+                generate_test_workload_state_with_agent(
+                    &format!("workload_{index}"),
+                    &format!("agent_{}", index % agent_count),
+                    ExecutionState::running(),
+                )
+            })
+            .collect()
+    }
+
+    // [utest->swdd~grpc-server-converts-workload-states-once-per-update~1]
+    #[test]
+    fn utest_workload_state_batch_returns_states_of_other_agents_in_order() {
+        let states = workload_states(6, 3);
+        let mut batch = WorkloadStateBatch::default();
+        batch.fill(states.clone());
+
+        let expected: Vec<ank_base::WorkloadState> = states
+            .into_iter()
+            .filter(|state| state.instance_name.agent_name() != "agent_1")
+            .map(Into::into)
+            .collect();
+        assert_eq!(batch.workload_states_for_agent("agent_1", None), expected);
+    }
+
+    // [utest->swdd~grpc-server-filters-workload-states-by-subscription~1]
+    #[test]
+    fn utest_workload_state_batch_returns_only_subscribed_states() {
+        let states = workload_states(6, 3);
+        let mut batch = WorkloadStateBatch::default();
+        batch.fill(states.clone());
+
+        let subscription = HashSet::from(["workload_0".to_owned(), "workload_1".to_owned()]);
+        let expected: Vec<ank_base::WorkloadState> = vec![states[0].clone().into()];
+        assert_eq!(
+            batch.workload_states_for_agent("agent_1", Some(&subscription)),
+            expected
+        );
+        assert!(batch
+            .workload_states_for_agent("agent_1", Some(&HashSet::new()))
+            .is_empty());
+    }
+
+    // [utest->swdd~grpc-server-converts-workload-states-once-per-update~1]
+    #[test]
+    fn utest_workload_state_batch_reuses_buffer_for_next_update() {
+        let mut batch = WorkloadStateBatch::default();
+        batch.fill(workload_states(10, 2));
+        let capacity = batch.workload_states.capacity();
+
+        batch.fill(workload_states(1, 1));
+
+        assert_eq!(batch.workload_states.capacity(), capacity);
+        assert!(batch.workload_states_for_agent("agent_0", None).is_empty());
+        assert_eq!(batch.workload_states_for_agent("agent_1", None).len(), 1);
+    }
+
+    // Benchmarks the conversion of the workload states per agent against the batch, run with
+    // `cargo test -p grpc --release -- --ignored bench_`
+    #[test]
+    #[ignore]
+    fn bench_workload_state_fan_out() {
+        let mut criterion = Criterion::default().sample_size(20);
+        for (workload_count, agent_count) in [(100, 10), (1000, 50)] {
+            let states = workload_states(workload_count, agent_count);
+            let agent_names: Vec<String> = (0..agent_count)
+                .map(|index| format!("agent_{index}"))
+                .collect();
+
+            criterion.bench_function(
+                &format!("convert {workload_count} states per agent for {agent_count} agents"),
+                |bencher| {
+                    bencher.iter(|| {
+                        for agent_name in &agent_names {
+                            let converted: Vec<ank_base::WorkloadState> = states
+                                .iter()
+                                .filter(|state| state.instance_name.agent_name() != agent_name)
+                                .cloned()
+                                .map(Into::into)
+                                .collect();
+                            black_box(converted);
+                        }
+                    })
+                },
+            );
+
+            let mut batch = WorkloadStateBatch::default();
+            criterion.bench_function(
+                &format!("convert {workload_count} states once for {agent_count} agents"),
+                |bencher| {
+                    bencher.iter_batched(
+                        || states.clone(),
+                        |states| {
+                            batch.fill(states);
+                            for agent_name in &agent_names {
+                                black_box(batch.workload_states_for_agent(agent_name, None));
+                            }
+                        },
+                        BatchSize::SmallInput,
+                    )
+                },
+            );
+        }
+        criterion.final_summary();
+    }
+}
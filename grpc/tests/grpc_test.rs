@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod grpc_tests {
 
-    use std::time::Duration;
+    use std::{collections::HashMap, time::Duration};
 
     use common::{
         commands::{self, CompleteStateRequest, Request, RequestContent},
@@ -12,7 +12,11 @@ mod grpc_tests {
         objects::CompleteState,
         to_server_interface::{ToServer, ToServerInterface, ToServerReceiver, ToServerSender},
     };
-    use grpc::{client::GRPCCommunicationsClient, server::GRPCCommunicationsServer};
+    use grpc::{
+        client::GRPCCommunicationsClient,
+        grpc_api::{self, agent_connection_client::AgentConnectionClient, to_server::ToServerEnum},
+        server::GRPCCommunicationsServer,
+    };
 
     use tokio::time::timeout;
     use url::Url;
@@ -37,9 +41,14 @@ mod grpc_tests {
             CommunicationType::Cli => {
                 GRPCCommunicationsClient::new_cli_communication(test_request_id.to_owned(), url)
             }
-            CommunicationType::Agent => {
-                GRPCCommunicationsClient::new_agent_communication(test_request_id.to_owned(), url)
-            }
+            CommunicationType::Agent => GRPCCommunicationsClient::new_agent_communication(
+                test_request_id.to_owned(),
+                vec![url],
+                None,
+                vec![],
+                HashMap::new(),
+                HashMap::new(),
+            ),
         };
 
         let grpc_client_task = tokio::spawn(async move {
@@ -182,7 +191,96 @@ mod grpc_tests {
 
         assert!(matches!(
             result,
-            Ok(Some(ToServer::AgentHello(commands::AgentHello { agent_name }))) if agent_name == test_agent_name
+            Ok(Some(ToServer::AgentHello(commands::AgentHello { agent_name, .. }))) if agent_name == test_agent_name
+        ));
+    }
+
+    // [itest->swdd~grpc-agent-connection-rejects-reserved-agent-names~1]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)] // set worker_threads = 1 to solve the failing of the test on woodpecker
+    async fn itest_grpc_agent_connection_rejects_reserved_agent_name() {
+        let server_addr = "0.0.0.0:50056";
+        let (_to_grpc_server, grpc_server_receiver) = tokio::sync::mpsc::channel::<FromServer>(20);
+        let (to_server, mut server_receiver) = tokio::sync::mpsc::channel::<ToServer>(20);
+        let mut communications_server = GRPCCommunicationsServer::new(to_server);
+        tokio::spawn(async move {
+            communications_server
+                .start(grpc_server_receiver, server_addr.parse().unwrap())
+                .await
+        });
+
+        let agent_hello = grpc_api::ToServer {
+            to_server_enum: Some(ToServerEnum::AgentHello(grpc_api::AgentHello {
+                agent_name: "cloud-connector".to_owned(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        let result = timeout(Duration::from_millis(10000), async {
+            loop {
+                // the server may not listen yet
+                if let Ok(mut client) =
+                    AgentConnectionClient::connect("http://127.0.0.1:50056").await
+                {
+                    return client
+                        .connect_agent(tokio_stream::iter(vec![agent_hello.clone()]))
+                        .await;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            result,
+            Err(status) if status.code() == tonic::Code::InvalidArgument && status.message().contains("reserved")
+        ));
+        assert!(server_receiver.try_recv().is_err());
+    }
+
+    // [itest->swdd~grpc-client-fails-over-to-next-server~1]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)] // set worker_threads = 1 to solve the failing of the test on woodpecker
+    async fn itest_grpc_communication_client_agent_connection_fails_over_to_next_server() {
+        let test_agent_name = "test_agent_name";
+        let server_addr = "0.0.0.0:50054";
+        let (_to_grpc_server, grpc_server_receiver) = tokio::sync::mpsc::channel::<FromServer>(20);
+        let (to_server, mut server_receiver) = tokio::sync::mpsc::channel::<ToServer>(20);
+
+        let mut communications_server = GRPCCommunicationsServer::new(to_server);
+        let socket_addr: std::net::SocketAddr = server_addr.parse().unwrap();
+        tokio::spawn(async move {
+            communications_server
+                .start(grpc_server_receiver, socket_addr)
+                .await
+        });
+
+        // nothing listens on the primary server endpoint
+        let server_addresses = vec![
+            Url::parse("http://127.0.0.1:50055").unwrap(),
+            Url::parse(&format!("http://{}", server_addr)).unwrap(),
+        ];
+        let mut grpc_communications_client = GRPCCommunicationsClient::new_agent_communication(
+            test_agent_name.to_owned(),
+            server_addresses,
+            None,
+            vec![],
+            HashMap::new(),
+            HashMap::new(),
+        );
+
+        let (_to_grpc_client, grpc_client_receiver) = tokio::sync::mpsc::channel::<ToServer>(20);
+        let (to_agent, _agent_receiver) = tokio::sync::mpsc::channel::<FromServer>(20);
+        tokio::spawn(async move {
+            grpc_communications_client
+                .run(grpc_client_receiver, to_agent)
+                .await
+        });
+
+        let result = timeout(Duration::from_millis(10000), server_receiver.recv()).await;
+
+        assert!(matches!(
+            result,
+            Ok(Some(ToServer::AgentHello(commands::AgentHello { agent_name, .. }))) if agent_name == test_agent_name
         ));
     }
 }
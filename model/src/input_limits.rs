@@ -0,0 +1,478 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fmt, sync::OnceLock};
+
+use serde::de::DeserializeOwned;
+use serde_yaml::Value;
+
+use crate::objects::State;
+
+/// The default maximal size in bytes of a manifest, startup config or fetched desired state.
+pub const MAX_MANIFEST_SIZE: usize = 4 * 1024 * 1024;
+/// The default maximal nesting depth of a manifest.
+pub const MAX_MANIFEST_DEPTH: usize = 32;
+/// The maximal size in bytes of a message received over gRPC or the control interface.
+pub const MAX_MESSAGE_SIZE: usize = 4 * 1024 * 1024;
+
+static CONFIGURED_LIMITS: OnceLock<InputLimits> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputLimitError {
+    ManifestTooLarge { size: usize, max: usize },
+    ManifestTooDeep { depth: usize, max: usize },
+    TooManyWorkloads { count: usize, max: usize },
+    RuntimeConfigTooLong {
+        workload_name: String,
+        length: usize,
+        max: usize,
+    },
+    RuntimeConfigTooDeep {
+        workload_name: String,
+        depth: usize,
+        max: usize,
+    },
+    InvalidManifest(String),
+}
+
+impl fmt::Display for InputLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputLimitError::ManifestTooLarge { size, max } => {
+                write!(f, "The manifest has {size} bytes, the maximum is {max}")
+            }
+            InputLimitError::ManifestTooDeep { depth, max } => {
+                write!(f, "The manifest is nested {depth} levels deep, the maximum is {max}")
+            }
+            InputLimitError::TooManyWorkloads { count, max } => {
+                write!(f, "The state has {count} workloads, the maximum is {max}")
+            }
+            InputLimitError::RuntimeConfigTooLong {
+                workload_name,
+                length,
+                max,
+            } => write!(
+                f,
+                "The runtime config of workload '{workload_name}' has {length} bytes, the maximum is {max}"
+            ),
+            InputLimitError::RuntimeConfigTooDeep {
+                workload_name,
+                depth,
+                max,
+            } => write!(
+                f,
+                "The runtime config of workload '{workload_name}' is nested {depth} levels deep, the maximum is {max}"
+            ),
+            InputLimitError::InvalidManifest(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+/// The limits on the size and structure of manifests and runtime configs.
+///
+/// The limits are configured once per process at startup, e.g. from the command line
+/// of the server or agent. Without a configuration the defaults apply. The limits on the
+/// workloads and runtime configs are opt-in and not enforced by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputLimits {
+    pub max_manifest_size: usize,
+    pub max_manifest_depth: usize,
+    pub max_workloads: Option<usize>,
+    pub max_runtime_config_length: Option<usize>,
+    pub max_runtime_config_depth: Option<usize>,
+}
+
+impl Default for InputLimits {
+    fn default() -> Self {
+        InputLimits {
+            max_manifest_size: MAX_MANIFEST_SIZE,
+            max_manifest_depth: MAX_MANIFEST_DEPTH,
+            max_workloads: None,
+            max_runtime_config_length: None,
+            max_runtime_config_depth: None,
+        }
+    }
+}
+
+impl InputLimits {
+    // [impl->swdd~common-configurable-input-limits~1]
+    pub fn configure(limits: InputLimits) {
+        if CONFIGURED_LIMITS.set(limits).is_err() {
+            log::warn!("The input limits are already configured, ignoring {limits:?}");
+        }
+    }
+
+    pub fn configured() -> InputLimits {
+        CONFIGURED_LIMITS.get().copied().unwrap_or_default()
+    }
+
+    /// Checks that a parsed manifest does not exceed the maximal nesting depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The parsed manifest
+    ///
+    // [impl->swdd~common-limits-manifest-size-and-depth~2]
+    pub fn check_manifest_depth(&self, value: &Value) -> Result<(), InputLimitError> {
+        let depth = depth_of(value);
+        if depth > self.max_manifest_depth {
+            return Err(InputLimitError::ManifestTooDeep {
+                depth,
+                max: self.max_manifest_depth,
+            });
+        }
+        Ok(())
+    }
+
+    /// Parses a YAML manifest after checking its size and nesting depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The content of the manifest
+    ///
+    // [impl->swdd~common-limits-manifest-size-and-depth~2]
+    pub fn parse_manifest<T: DeserializeOwned>(
+        &self,
+        manifest: &str,
+    ) -> Result<T, InputLimitError> {
+        if manifest.len() > self.max_manifest_size {
+            return Err(InputLimitError::ManifestTooLarge {
+                size: manifest.len(),
+                max: self.max_manifest_size,
+            });
+        }
+        let value: Value = serde_yaml::from_str(manifest)
+            .map_err(|err| InputLimitError::InvalidManifest(err.to_string()))?;
+        self.check_manifest_depth(&value)?;
+        serde_yaml::from_value(value)
+            .map_err(|err| InputLimitError::InvalidManifest(err.to_string()))
+    }
+
+    /// Checks the number of workloads and the length of their runtime configs.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The desired state to admit
+    ///
+    // [impl->swdd~common-limits-workloads-and-runtime-config-length~1]
+    pub fn check_state(&self, state: &State) -> Result<(), InputLimitError> {
+        self.check_workload_count(state.workloads.len())?;
+        // sorted to report the same workload for the same state
+        let mut workloads: Vec<_> = state.workloads.iter().collect();
+        workloads.sort_by_key(|(workload_name, _)| *workload_name);
+        workloads
+            .into_iter()
+            .try_for_each(|(workload_name, workload_spec)| {
+                self.check_runtime_config_length(workload_name, &workload_spec.runtime_config)
+            })
+    }
+
+    // [impl->swdd~common-limits-workloads-and-runtime-config-length~1]
+    pub fn check_workload_count(&self, count: usize) -> Result<(), InputLimitError> {
+        match self.max_workloads {
+            Some(max) if count > max => Err(InputLimitError::TooManyWorkloads { count, max }),
+            _ => Ok(()),
+        }
+    }
+
+    // [impl->swdd~common-limits-workloads-and-runtime-config-length~1]
+    pub fn check_runtime_config_length(
+        &self,
+        workload_name: &str,
+        runtime_config: &str,
+    ) -> Result<(), InputLimitError> {
+        match self.max_runtime_config_length {
+            Some(max) if runtime_config.len() > max => Err(InputLimitError::RuntimeConfigTooLong {
+                workload_name: workload_name.to_owned(),
+                length: runtime_config.len(),
+                max,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Parses the runtime config of a workload after checking its length and nesting depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `workload_name` - The name of the workload the runtime config belongs to
+    /// * `runtime_config` - The runtime config as YAML string
+    ///
+    // [impl->swdd~common-limits-workloads-and-runtime-config-length~1]
+    pub fn parse_runtime_config<T: DeserializeOwned>(
+        &self,
+        workload_name: &str,
+        runtime_config: &str,
+    ) -> Result<T, InputLimitError> {
+        self.check_runtime_config_length(workload_name, runtime_config)?;
+        let value: Value = serde_yaml::from_str(runtime_config)
+            .map_err(|err| InputLimitError::InvalidManifest(err.to_string()))?;
+        if let Some(max) = self.max_runtime_config_depth {
+            let depth = depth_of(&value);
+            if depth > max {
+                return Err(InputLimitError::RuntimeConfigTooDeep {
+                    workload_name: workload_name.to_owned(),
+                    depth,
+                    max,
+                });
+            }
+        }
+        serde_yaml::from_value(value)
+            .map_err(|err| InputLimitError::InvalidManifest(err.to_string()))
+    }
+}
+
+fn depth_of(value: &Value) -> usize {
+    // iterative to stay within the stack for arbitrarily deep values
+    let mut max_depth = 0;
+    let mut stack = vec![(value, 0)];
+    while let Some((value, depth)) = stack.pop() {
+        max_depth = max_depth.max(depth);
+        match value {
+            Value::Sequence(sequence) => stack.extend(sequence.iter().map(|x| (x, depth + 1))),
+            Value::Mapping(mapping) => stack.extend(mapping.values().map(|x| (x, depth + 1))),
+            Value::Tagged(tagged) => stack.push((&tagged.value, depth)),
+            _ => {}
+        }
+    }
+    max_depth
+}
+
+/// Checks that a parsed manifest does not exceed the configured maximal nesting depth.
+///
+/// # Arguments
+///
+/// * `value` - The parsed manifest
+///
+// [impl->swdd~common-limits-manifest-size-and-depth~2]
+pub fn check_manifest_depth(value: &Value) -> Result<(), InputLimitError> {
+    InputLimits::configured().check_manifest_depth(value)
+}
+
+/// Parses a YAML manifest after checking its size and nesting depth with the configured limits.
+///
+/// # Arguments
+///
+/// * `manifest` - The content of the manifest
+///
+// [impl->swdd~common-limits-manifest-size-and-depth~2]
+pub fn parse_manifest<T: DeserializeOwned>(manifest: &str) -> Result<T, InputLimitError> {
+    InputLimits::configured().parse_manifest(manifest)
+}
+
+/// Checks the size announced in the length prefix of a message before it is read.
+///
+/// # Arguments
+///
+/// * `size` - The announced size of the message in bytes
+///
+// [impl->swdd~common-limits-message-size~1]
+pub fn check_message_size(size: u64) -> Result<usize, String> {
+    match usize::try_from(size) {
+        Ok(size) if size <= MAX_MESSAGE_SIZE => Ok(size),
+        _ => Err(format!("The message has {size} bytes, the maximum is {MAX_MESSAGE_SIZE}")),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_message_size, parse_manifest, InputLimitError, InputLimits, MAX_MANIFEST_DEPTH,
+        MAX_MANIFEST_SIZE, MAX_MESSAGE_SIZE,
+    };
+    use crate::objects::{generate_test_stored_workload_spec_with_config, State};
+
+    fn state_with_runtime_configs(runtime_configs: &[&str]) -> State {
+        State {
+            workloads: runtime_configs
+                .iter()
+                .enumerate()
+                .map(|(index, runtime_config)| {
+                    (
+                        format!("workload_{index}"),
+                        generate_test_stored_workload_spec_with_config(
+                            "agent_A",
+                            "podman",
+                            runtime_config.to_string(),
+                        ),
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    // [utest->swdd~common-limits-manifest-size-and-depth~2]
+    #[test]
+    fn utest_parse_manifest_parses_state() {
+        let state: State = parse_manifest(
+            "apiVersion: v0.1\nworkloads:\n  nginx:\n    agent: agent_A\n    runtime: podman\n    runtimeConfig: 'image: nginx'\n",
+        )
+        .unwrap();
+
+        assert_eq!(state.workloads["nginx"].agent, "agent_A");
+    }
+
+    // [utest->swdd~common-limits-manifest-size-and-depth~2]
+    #[test]
+    fn utest_parse_manifest_rejects_too_deep_manifest() {
+        let manifest = format!(
+            "{}1{}",
+            "[".repeat(MAX_MANIFEST_DEPTH + 1),
+            "]".repeat(MAX_MANIFEST_DEPTH + 1)
+        );
+
+        assert_eq!(
+            parse_manifest::<serde_yaml::Value>(&manifest),
+            Err(InputLimitError::ManifestTooDeep {
+                depth: MAX_MANIFEST_DEPTH + 1,
+                max: MAX_MANIFEST_DEPTH
+            })
+        );
+
+        let manifest = format!(
+            "{}1{}",
+            "[".repeat(MAX_MANIFEST_DEPTH),
+            "]".repeat(MAX_MANIFEST_DEPTH)
+        );
+        assert!(parse_manifest::<serde_yaml::Value>(&manifest).is_ok());
+    }
+
+    // [utest->swdd~common-limits-manifest-size-and-depth~2]
+    #[test]
+    fn utest_parse_manifest_rejects_too_large_manifest() {
+        let manifest = format!("key: '{}'", "x".repeat(MAX_MANIFEST_SIZE));
+
+        assert!(matches!(
+            parse_manifest::<serde_yaml::Value>(&manifest),
+            Err(InputLimitError::ManifestTooLarge { max, .. }) if max == MAX_MANIFEST_SIZE
+        ));
+    }
+
+    // [utest->swdd~common-configurable-input-limits~1]
+    #[test]
+    fn utest_input_limits_apply_custom_limits() {
+        let limits = InputLimits {
+            max_manifest_size: 10,
+            max_manifest_depth: 1,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            limits.parse_manifest::<serde_yaml::Value>("key: 'value too long'"),
+            Err(InputLimitError::ManifestTooLarge { size: 21, max: 10 })
+        );
+        assert_eq!(
+            limits.parse_manifest::<serde_yaml::Value>("[[1]]"),
+            Err(InputLimitError::ManifestTooDeep { depth: 2, max: 1 })
+        );
+        assert!(matches!(
+            limits.parse_manifest::<serde_yaml::Value>("[1"),
+            Err(InputLimitError::InvalidManifest(_))
+        ));
+        assert!(limits.parse_manifest::<serde_yaml::Value>("[1]").is_ok());
+    }
+
+    // [utest->swdd~common-limits-workloads-and-runtime-config-length~1]
+    #[test]
+    fn utest_input_limits_check_state() {
+        let limits = InputLimits {
+            max_workloads: Some(2),
+            max_runtime_config_length: Some(8),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            limits.check_state(&state_with_runtime_configs(&["a", "b", "c"])),
+            Err(InputLimitError::TooManyWorkloads { count: 3, max: 2 })
+        );
+        assert_eq!(
+            limits.check_state(&state_with_runtime_configs(&["short", "too long!"])),
+            Err(InputLimitError::RuntimeConfigTooLong {
+                workload_name: "workload_1".to_string(),
+                length: 9,
+                max: 8
+            })
+        );
+        assert_eq!(
+            limits.check_state(&state_with_runtime_configs(&["short", "12345678"])),
+            Ok(())
+        );
+    }
+
+    // [utest->swdd~common-limits-workloads-and-runtime-config-length~1]
+    #[test]
+    fn utest_input_limits_parse_runtime_config() {
+        let limits = InputLimits {
+            max_runtime_config_length: Some(16),
+            max_runtime_config_depth: Some(1),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            limits.parse_runtime_config::<serde_yaml::Value>("workload", "image: [[nginx]]"),
+            Err(InputLimitError::RuntimeConfigTooDeep {
+                workload_name: "workload".to_string(),
+                depth: 3,
+                max: 1
+            })
+        );
+        assert_eq!(
+            limits.parse_runtime_config::<serde_yaml::Value>("workload", "image: nginx:latest"),
+            Err(InputLimitError::RuntimeConfigTooLong {
+                workload_name: "workload".to_string(),
+                length: 19,
+                max: 16
+            })
+        );
+        assert!(limits
+            .parse_runtime_config::<serde_yaml::Value>("workload", "image: nginx")
+            .is_ok());
+    }
+
+    // [utest->swdd~common-configurable-input-limits~1]
+    #[test]
+    fn utest_input_limits_do_not_limit_workloads_and_runtime_configs_by_default() {
+        let limits = InputLimits::default();
+        let runtime_config = format!("image: {}{}", "[".repeat(64), "]".repeat(64));
+
+        assert_eq!(
+            limits.check_state(&state_with_runtime_configs(&[runtime_config.as_str(); 1024])),
+            Ok(())
+        );
+        assert!(limits
+            .parse_runtime_config::<serde_yaml::Value>("workload", &runtime_config)
+            .is_ok());
+    }
+
+    // [utest->swdd~common-limits-message-size~1]
+    #[test]
+    fn utest_check_message_size() {
+        assert_eq!(check_message_size(10), Ok(10));
+        assert_eq!(
+            check_message_size(MAX_MESSAGE_SIZE as u64),
+            Ok(MAX_MESSAGE_SIZE)
+        );
+        assert!(check_message_size(MAX_MESSAGE_SIZE as u64 + 1).is_err());
+        assert!(check_message_size(u64::MAX).is_err());
+    }
+}
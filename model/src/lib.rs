@@ -0,0 +1,52 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! The domain model of Eclipse Ankaios: the objects of the desired and the complete state, the
+//! parsing of manifests and the semantic comparison of states.
+//!
+//! The crate does not depend on the internal crates of the server and the agent, such that
+//! external tools, e.g., fleet backends or test frameworks, can read, write and compare Ankaios
+//! manifests and states.
+//!
+//! ```no_run
+//! use ankaios_model::{manifest_migration::parse_state_manifest, objects::diff_states};
+//!
+//! let old = parse_state_manifest(&std::fs::read_to_string("old.yaml").unwrap()).unwrap();
+//! let new = parse_state_manifest(&std::fs::read_to_string("new.yaml").unwrap()).unwrap();
+//! println!("{}", diff_states(&old, &new));
+//! ```
+
+// [impl->swdd~common-domain-model-crate~1]
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Serialize, Serializer};
+
+pub mod input_limits;
+pub mod manifest_migration;
+pub mod objects;
+#[cfg(feature = "test_utils")]
+pub mod test_utils;
+
+/// Serializes the map ordered by its keys, such that the written manifests are reproducible.
+pub fn serialize_to_ordered_map<S, T: Serialize>(
+    value: &HashMap<String, T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let ordered: BTreeMap<_, _> = value.iter().collect();
+    ordered.serialize(serializer)
+}
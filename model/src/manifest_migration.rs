@@ -0,0 +1,197 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_yaml::{Mapping, Value};
+
+use crate::{
+    input_limits::{self, InputLimitError},
+    objects::State,
+};
+
+const API_VERSION_KEY: &str = "apiVersion";
+const WORKLOADS_KEY: &str = "workloads";
+
+// Converts a manifest of the API version `from` into a manifest of the API version `to`.
+// Manifests written before the field `apiVersion` was introduced have no version.
+struct Migration {
+    from: Option<&'static str>,
+    to: &'static str,
+    migrate: fn(&mut Mapping),
+}
+
+// The migrations in the order they are applied, the last one results in the current version.
+const MIGRATIONS: &[Migration] = &[Migration {
+    from: None,
+    to: "v0.1",
+    migrate: migrate_unversioned_manifest,
+}];
+
+/// Migrates a manifest of an older API version to the current API version.
+///
+/// The migrations are applied one after the other until the manifest has the current
+/// API version. Manifests of the current or of an unknown API version are returned unchanged,
+/// an unknown version is rejected when the state is checked.
+///
+/// # Arguments
+///
+/// * `manifest` - The parsed manifest
+///
+// [impl->swdd~common-migrates-manifests-to-current-api-version~1]
+pub fn migrate_manifest(mut manifest: Value) -> Value {
+    let Some(mapping) = manifest.as_mapping_mut() else {
+        return manifest;
+    };
+
+    loop {
+        let api_version = match mapping.get(API_VERSION_KEY) {
+            None => None,
+            Some(Value::String(api_version)) => Some(api_version.as_str()),
+            Some(_) => break,
+        };
+        let Some(migration) = MIGRATIONS
+            .iter()
+            .find(|migration| migration.from == api_version)
+        else {
+            break;
+        };
+
+        log::info!(
+            "Migrating manifest from API version '{}' to '{}'",
+            migration.from.unwrap_or("none"),
+            migration.to
+        );
+        (migration.migrate)(mapping);
+        mapping.insert(API_VERSION_KEY.into(), migration.to.into());
+    }
+    manifest
+}
+
+/// Parses a manifest containing a state and migrates it to the current API version.
+///
+/// # Arguments
+///
+/// * `manifest` - The content of the manifest
+///
+// [impl->swdd~common-migrates-manifests-to-current-api-version~1]
+pub fn parse_state_manifest(manifest: &str) -> Result<State, InputLimitError> {
+    let value: Value = input_limits::parse_manifest(manifest)?;
+    serde_yaml::from_value(migrate_manifest(value))
+        .map_err(|err| InputLimitError::InvalidManifest(err.to_string()))
+}
+
+// Manifests of Ankaios v0.2 have no API version. The boolean field `restart` was replaced by
+// the `restartPolicy`, the fields `accessRights` and `cronjobs` were removed.
+fn migrate_unversioned_manifest(manifest: &mut Mapping) {
+    manifest.remove("cronjobs");
+
+    let Some(Value::Mapping(workloads)) = manifest.get_mut(WORKLOADS_KEY) else {
+        return;
+    };
+    for workload in workloads.values_mut().filter_map(Value::as_mapping_mut) {
+        workload.remove("accessRights");
+        if let Some(restart) = workload.remove("restart") {
+            if !workload.contains_key("restartPolicy") {
+                let restart_policy = if restart.as_bool().unwrap_or_default() {
+                    "ALWAYS"
+                } else {
+                    "NEVER"
+                };
+                workload.insert("restartPolicy".into(), restart_policy.into());
+            }
+        }
+        if workload
+            .get("updateStrategy")
+            .is_some_and(|update_strategy| *update_strategy == "UNSPECIFIED")
+        {
+            workload.remove("updateStrategy");
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::Value;
+
+    use super::{migrate_manifest, parse_state_manifest};
+    use crate::objects::{RestartPolicy, State, UpdateStrategy};
+
+    const UNVERSIONED_MANIFEST: &str = r#"
+workloads:
+  nginx:
+    agent: agent_A
+    runtime: podman
+    restart: true
+    updateStrategy: UNSPECIFIED
+    accessRights:
+      allow: []
+      deny: []
+    runtimeConfig: "image: nginx"
+  sleepy:
+    agent: agent_A
+    runtime: podman
+    restart: false
+    updateStrategy: AT_LEAST_ONCE
+    runtimeConfig: "image: alpine"
+cronjobs: {}
+"#;
+
+    // [utest->swdd~common-migrates-manifests-to-current-api-version~1]
+    #[test]
+    fn utest_parse_state_manifest_migrates_unversioned_manifest() {
+        let state = parse_state_manifest(UNVERSIONED_MANIFEST).unwrap();
+
+        assert_eq!(state.api_version, State::default().api_version);
+        let nginx = &state.workloads["nginx"];
+        assert_eq!(nginx.restart_policy, RestartPolicy::Always);
+        assert_eq!(nginx.update_strategy, UpdateStrategy::AtMostOnce);
+        let sleepy = &state.workloads["sleepy"];
+        assert_eq!(sleepy.restart_policy, RestartPolicy::Never);
+        assert_eq!(sleepy.update_strategy, UpdateStrategy::AtLeastOnce);
+    }
+
+    // [utest->swdd~common-migrates-manifests-to-current-api-version~1]
+    #[test]
+    fn utest_migrate_manifest_keeps_restart_policy_of_unversioned_manifest() {
+        let manifest: Value = serde_yaml::from_str(
+            "workloads:\n  nginx:\n    restart: true\n    restartPolicy: ON_FAILURE\n",
+        )
+        .unwrap();
+
+        let expected: Value = serde_yaml::from_str(
+            "workloads:\n  nginx:\n    restartPolicy: ON_FAILURE\napiVersion: v0.1\n",
+        )
+        .unwrap();
+        assert_eq!(migrate_manifest(manifest), expected);
+    }
+
+    // [utest->swdd~common-migrates-manifests-to-current-api-version~1]
+    #[test]
+    fn utest_migrate_manifest_keeps_current_and_unknown_versions() {
+        for manifest in [
+            "apiVersion: v0.1\nworkloads:\n  nginx:\n    restart: true\n",
+            "apiVersion: v0.2\nworkloads: {}\n",
+        ] {
+            let manifest: Value = serde_yaml::from_str(manifest).unwrap();
+            assert_eq!(migrate_manifest(manifest.clone()), manifest);
+        }
+    }
+}
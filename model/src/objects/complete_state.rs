@@ -15,7 +15,7 @@
 use api::ank_base;
 use serde::{Deserialize, Serialize};
 
-use super::{State, WorkloadState};
+use super::{State, SystemState, WorkloadState};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -26,6 +26,8 @@ pub struct CompleteState {
     pub desired_state: State,
     #[serde(default)]
     pub workload_states: Vec<WorkloadState>,
+    #[serde(default, skip_serializing_if = "SystemState::is_empty")]
+    pub system: SystemState,
 }
 
 impl From<CompleteState> for ank_base::CompleteState {
@@ -34,6 +36,7 @@ impl From<CompleteState> for ank_base::CompleteState {
             startup_state: Some(ank_base::State::from(item.startup_state)),
             desired_state: Some(ank_base::State::from(item.desired_state)),
             workload_states: item.workload_states.into_iter().map(|x| x.into()).collect(),
+            system: (!item.system.is_empty()).then(|| item.system.into()),
         }
     }
 }
@@ -46,6 +49,7 @@ impl TryFrom<ank_base::CompleteState> for CompleteState {
             startup_state: item.startup_state.unwrap_or_default().try_into()?,
             desired_state: item.desired_state.unwrap_or_default().try_into()?,
             workload_states: item.workload_states.into_iter().map(|x| x.into()).collect(),
+            system: item.system.unwrap_or_default().try_into()?,
         })
     }
 }
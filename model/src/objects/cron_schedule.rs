@@ -0,0 +1,310 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+const SECONDS_PER_MINUTE: u64 = 60;
+const SECONDS_PER_DAY: u64 = 24 * 60 * SECONDS_PER_MINUTE;
+// every day of the month and of the week occurs within 8 years, e.g. the 29th of February
+const MAX_SEARCHED_DAYS: u64 = 8 * 366;
+
+// A set of the values of a field as bits, e.g. bit 5 is set if the field matches the value 5.
+type FieldValues = u64;
+
+// [impl->swdd~workload-schedule~1]
+// A cron expression with the five fields minute, hour, day of month, month and day of week,
+// e.g. '*/15 6-18 * * 1-5'. The times are in UTC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: FieldValues,
+    hours: FieldValues,
+    days_of_month: FieldValues,
+    months: FieldValues,
+    days_of_week: FieldValues,
+    // As in cron, a day matches either of both day fields if both are restricted.
+    days_of_month_restricted: bool,
+    days_of_week_restricted: bool,
+}
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields[..] else {
+            return Err(format!(
+                "Invalid schedule '{}': expected the 5 fields minute, hour, day of month, month and day of week.",
+                expression
+            ));
+        };
+
+        let parse = |field: &str, min: u32, max: u32| {
+            parse_field(field, min, max)
+                .map_err(|err| format!("Invalid schedule '{}': {}", expression, err))
+        };
+        let mut days_of_week_values = parse(days_of_week, 0, 7)?;
+        // both 0 and 7 are Sunday
+        if days_of_week_values & (1 << 7) != 0 {
+            days_of_week_values = (days_of_week_values | 1) & !(1 << 7);
+        }
+
+        Ok(CronSchedule {
+            minutes: parse(minutes, 0, 59)?,
+            hours: parse(hours, 0, 23)?,
+            days_of_month: parse(days_of_month, 1, 31)?,
+            months: parse(months, 1, 12)?,
+            days_of_week: days_of_week_values,
+            days_of_month_restricted: !days_of_month.starts_with('*'),
+            days_of_week_restricted: !days_of_week.starts_with('*'),
+        })
+    }
+}
+
+impl CronSchedule {
+    // Returns the first matching time strictly after the given time, both in seconds since the
+    // Unix epoch. Returns None if the schedule never matches, e.g. for the 31st of February.
+    pub fn next_after(&self, unix_seconds: u64) -> Option<u64> {
+        let start = (unix_seconds / SECONDS_PER_MINUTE + 1) * SECONDS_PER_MINUTE;
+        let start_day = start / SECONDS_PER_DAY;
+        let start_minute_of_day = ((start % SECONDS_PER_DAY) / SECONDS_PER_MINUTE) as u32;
+
+        for day in start_day..start_day + MAX_SEARCHED_DAYS {
+            if !self.matches_day(day) {
+                continue;
+            }
+            let (from_hour, from_minute) = if day == start_day {
+                (start_minute_of_day / 60, start_minute_of_day % 60)
+            } else {
+                (0, 0)
+            };
+            let Some(hour) = first_value_from(self.hours, from_hour) else {
+                continue;
+            };
+            let minute = if hour == from_hour {
+                match first_value_from(self.minutes, from_minute) {
+                    Some(minute) => minute,
+                    None => match first_value_from(self.hours, hour + 1) {
+                        Some(next_hour) => {
+                            return Some(to_unix_seconds(day, next_hour, self.first_minute()))
+                        }
+                        None => continue,
+                    },
+                }
+            } else {
+                self.first_minute()
+            };
+            return Some(to_unix_seconds(day, hour, minute));
+        }
+        None
+    }
+
+    fn first_minute(&self) -> u32 {
+        self.minutes.trailing_zeros()
+    }
+
+    fn matches_day(&self, days_since_epoch: u64) -> bool {
+        let (month, day_of_month) = month_and_day_from_days(days_since_epoch);
+        if self.months & (1 << month) == 0 {
+            return false;
+        }
+        // the 1st of January 1970 was a Thursday
+        let day_of_week = (days_since_epoch + 4) % 7;
+        let day_of_month_matches = self.days_of_month & (1 << day_of_month) != 0;
+        let day_of_week_matches = self.days_of_week & (1 << day_of_week) != 0;
+        if self.days_of_month_restricted && self.days_of_week_restricted {
+            day_of_month_matches || day_of_week_matches
+        } else {
+            day_of_month_matches && day_of_week_matches
+        }
+    }
+}
+
+fn first_value_from(values: FieldValues, from: u32) -> Option<u32> {
+    if from >= FieldValues::BITS {
+        return None;
+    }
+    let remaining = values >> from << from;
+    (remaining != 0).then(|| remaining.trailing_zeros())
+}
+
+fn to_unix_seconds(days_since_epoch: u64, hour: u32, minute: u32) -> u64 {
+    days_since_epoch * SECONDS_PER_DAY + (hour as u64 * 60 + minute as u64) * SECONDS_PER_MINUTE
+}
+
+// The conversion of the days since the epoch into the civil calendar by Howard Hinnant.
+fn month_and_day_from_days(days_since_epoch: u64) -> (u32, u32) {
+    let days_since_era_start = days_since_epoch + 719_468;
+    let day_of_era = days_since_era_start % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day_of_month = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    (month as u32, day_of_month as u32)
+}
+
+// A field is a comma separated list of '*', single values or ranges 'a-b', each optionally
+// followed by a step '/n'.
+fn parse_field(field: &str, min: u32, max: u32) -> Result<FieldValues, String> {
+    let parse_value = |value: &str| match value.parse::<u32>() {
+        Ok(value) if (min..=max).contains(&value) => Ok(value),
+        _ => Err(format!(
+            "'{}' is not a value between {} and {}.",
+            value, min, max
+        )),
+    };
+
+    let mut values: FieldValues = 0;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => match step.parse::<u32>() {
+                Ok(step) if step > 0 => (range, step),
+                _ => return Err(format!("'{}' is not a valid step.", step)),
+            },
+            None => (item, 1),
+        };
+        let (first, last) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((first, last)) => (parse_value(first)?, parse_value(last)?),
+                // a single value with a step runs until the maximum as in cron
+                None if item.contains('/') => (parse_value(range)?, max),
+                None => {
+                    let value = parse_value(range)?;
+                    (value, value)
+                }
+            },
+        };
+        if first > last {
+            return Err(format!("'{}' is not a valid range.", range));
+        }
+        for value in (first..=last).step_by(step as usize) {
+            values |= 1 << value;
+        }
+    }
+    Ok(values)
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::CronSchedule;
+
+    // Monday, the 15th of January 2024, 10:30:00 UTC
+    const MONDAY_10_30: u64 = 1_705_314_600;
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+
+    fn next_after(expression: &str, unix_seconds: u64) -> Option<u64> {
+        expression
+            .parse::<CronSchedule>()
+            .unwrap()
+            .next_after(unix_seconds)
+    }
+
+    // [utest->swdd~workload-schedule~1]
+    #[test]
+    fn utest_cron_schedule_next_after_within_the_day() {
+        assert_eq!(
+            next_after("* * * * *", MONDAY_10_30),
+            Some(MONDAY_10_30 + MINUTE)
+        );
+        assert_eq!(
+            next_after("* * * * *", MONDAY_10_30 + 59),
+            Some(MONDAY_10_30 + MINUTE)
+        );
+        assert_eq!(
+            next_after("*/15 * * * *", MONDAY_10_30),
+            Some(MONDAY_10_30 + 15 * MINUTE)
+        );
+        assert_eq!(
+            next_after("0,20 * * * *", MONDAY_10_30),
+            Some(MONDAY_10_30 + 30 * MINUTE)
+        );
+        assert_eq!(
+            next_after("10 12-14 * * *", MONDAY_10_30),
+            Some(MONDAY_10_30 + 100 * MINUTE)
+        );
+    }
+
+    // [utest->swdd~workload-schedule~1]
+    #[test]
+    fn utest_cron_schedule_next_after_on_other_days() {
+        // the next day at 02:00
+        assert_eq!(
+            next_after("0 2 * * *", MONDAY_10_30),
+            Some(MONDAY_10_30 + DAY - 8 * HOUR - 30 * MINUTE)
+        );
+        // Friday at 10:30
+        assert_eq!(
+            next_after("30 10 * * 5", MONDAY_10_30),
+            Some(MONDAY_10_30 + 4 * DAY)
+        );
+        // Sunday given as 7 at 10:30
+        assert_eq!(
+            next_after("30 10 * * 7", MONDAY_10_30),
+            Some(MONDAY_10_30 + 6 * DAY)
+        );
+        // the 1st of February at 00:00
+        assert_eq!(
+            next_after("0 0 1 2 *", MONDAY_10_30),
+            Some(MONDAY_10_30 + 17 * DAY - 10 * HOUR - 30 * MINUTE)
+        );
+        // the 29th of February 2024 at 00:00
+        assert_eq!(
+            next_after("0 0 29 2 *", MONDAY_10_30),
+            Some(MONDAY_10_30 + 45 * DAY - 10 * HOUR - 30 * MINUTE)
+        );
+        // either the 17th or a Tuesday, thus Tuesday the 16th
+        assert_eq!(
+            next_after("30 10 17 * 2", MONDAY_10_30),
+            Some(MONDAY_10_30 + DAY)
+        );
+        assert_eq!(next_after("0 0 31 2 *", MONDAY_10_30), None);
+    }
+
+    // [utest->swdd~workload-schedule~1]
+    #[test]
+    fn utest_cron_schedule_rejects_invalid_expressions() {
+        assert!("* * * *".parse::<CronSchedule>().is_err());
+        assert!("60 * * * *".parse::<CronSchedule>().is_err());
+        assert!("* 24 * * *".parse::<CronSchedule>().is_err());
+        assert!("* * 0 * *".parse::<CronSchedule>().is_err());
+        assert!("* * * 13 *".parse::<CronSchedule>().is_err());
+        assert!("* * * * 8".parse::<CronSchedule>().is_err());
+        assert!("*/0 * * * *".parse::<CronSchedule>().is_err());
+        assert!("5-1 * * * *".parse::<CronSchedule>().is_err());
+        assert_eq!(
+            "@daily".parse::<CronSchedule>(),
+            Err("Invalid schedule '@daily': expected the 5 fields minute, hour, day of month, month and day of week.".to_string())
+        );
+        assert_eq!(
+            "x * * * *".parse::<CronSchedule>(),
+            Err("Invalid schedule 'x * * * *': 'x' is not a value between 0 and 59.".to_string())
+        );
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use api::ank_base::{self, dependency_expression::DependencyExpressionEnum};
+
+use super::AddCondition;
+
+// [impl->swdd~workload-dependency-expression~1]
+// The variants are distinguished by their keys, e.g. a manifest contains
+// `or: [{and: [...]}, {workload: C, condition: ADD_COND_RUNNING}]`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum DependencyExpression {
+    Condition {
+        workload: String,
+        condition: AddCondition,
+    },
+    And {
+        and: Vec<DependencyExpression>,
+    },
+    Or {
+        or: Vec<DependencyExpression>,
+    },
+    Not {
+        not: Box<DependencyExpression>,
+    },
+}
+
+impl DependencyExpression {
+    // An empty 'and' is fulfilled, an empty 'or' is not.
+    pub fn evaluate(&self, condition_fulfilled: &impl Fn(&str, &AddCondition) -> bool) -> bool {
+        match self {
+            DependencyExpression::Condition {
+                workload,
+                condition,
+            } => condition_fulfilled(workload, condition),
+            DependencyExpression::And { and } => and
+                .iter()
+                .all(|expression| expression.evaluate(condition_fulfilled)),
+            DependencyExpression::Or { or } => or
+                .iter()
+                .any(|expression| expression.evaluate(condition_fulfilled)),
+            DependencyExpression::Not { not } => !not.evaluate(condition_fulfilled),
+        }
+    }
+
+    // The conditions of the expression regardless of the operators combining them.
+    pub fn conditions(&self) -> Vec<(&String, &AddCondition)> {
+        match self {
+            DependencyExpression::Condition {
+                workload,
+                condition,
+            } => vec![(workload, condition)],
+            DependencyExpression::And { and: expressions }
+            | DependencyExpression::Or { or: expressions } => expressions
+                .iter()
+                .flat_map(DependencyExpression::conditions)
+                .collect(),
+            DependencyExpression::Not { not } => not.conditions(),
+        }
+    }
+
+    pub fn workload_names(&self) -> Vec<&String> {
+        let mut workload_names: Vec<&String> = self
+            .conditions()
+            .into_iter()
+            .map(|(workload_name, _)| workload_name)
+            .collect();
+        workload_names.sort();
+        workload_names.dedup();
+        workload_names
+    }
+
+    fn fmt_nested(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DependencyExpression::Condition { .. } | DependencyExpression::Not { .. } => {
+                write!(f, "{self}")
+            }
+            _ => write!(f, "({self})"),
+        }
+    }
+}
+
+impl fmt::Display for DependencyExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (operator, expressions) = match self {
+            DependencyExpression::Condition {
+                workload,
+                condition,
+            } => return write!(f, "{workload}: {condition}"),
+            DependencyExpression::Not { not } => {
+                write!(f, "NOT ")?;
+                return not.fmt_nested(f);
+            }
+            DependencyExpression::And { and } => (" AND ", and),
+            DependencyExpression::Or { or } => (" OR ", or),
+        };
+        for (index, expression) in expressions.iter().enumerate() {
+            if index > 0 {
+                write!(f, "{operator}")?;
+            }
+            expression.fmt_nested(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<ank_base::DependencyExpression> for DependencyExpression {
+    type Error = String;
+
+    fn try_from(value: ank_base::DependencyExpression) -> Result<Self, Self::Error> {
+        let try_from_all = |expressions: ank_base::DependencyExpressions| {
+            expressions
+                .expressions
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<DependencyExpression>, String>>()
+        };
+
+        match value.dependency_expression_enum {
+            Some(DependencyExpressionEnum::Condition(condition)) => {
+                Ok(DependencyExpression::Condition {
+                    workload: condition.workload,
+                    condition: condition.condition.try_into()?,
+                })
+            }
+            Some(DependencyExpressionEnum::And(and)) => Ok(DependencyExpression::And {
+                and: try_from_all(and)?,
+            }),
+            Some(DependencyExpressionEnum::Or(or)) => Ok(DependencyExpression::Or {
+                or: try_from_all(or)?,
+            }),
+            Some(DependencyExpressionEnum::Not(not)) => Ok(DependencyExpression::Not {
+                not: Box::new((*not).try_into()?),
+            }),
+            None => Err("Received a dependency expression without content.".to_string()),
+        }
+    }
+}
+
+impl From<DependencyExpression> for ank_base::DependencyExpression {
+    fn from(value: DependencyExpression) -> Self {
+        let from_all = |expressions: Vec<DependencyExpression>| ank_base::DependencyExpressions {
+            expressions: expressions.into_iter().map(Into::into).collect(),
+        };
+
+        let dependency_expression_enum = match value {
+            DependencyExpression::Condition {
+                workload,
+                condition,
+            } => DependencyExpressionEnum::Condition(ank_base::DependencyCondition {
+                workload,
+                condition: condition as i32,
+            }),
+            DependencyExpression::And { and } => DependencyExpressionEnum::And(from_all(and)),
+            DependencyExpression::Or { or } => DependencyExpressionEnum::Or(from_all(or)),
+            DependencyExpression::Not { not } => {
+                DependencyExpressionEnum::Not(Box::new((*not).into()))
+            }
+        };
+        ank_base::DependencyExpression {
+            dependency_expression_enum: Some(dependency_expression_enum),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use api::ank_base;
+
+    use crate::objects::{AddCondition, DependencyExpression};
+
+    fn condition(workload: &str, condition: AddCondition) -> DependencyExpression {
+        DependencyExpression::Condition {
+            workload: workload.to_string(),
+            condition,
+        }
+    }
+
+    // (A running AND B succeeded) OR NOT C running
+    fn generate_test_expression() -> DependencyExpression {
+        DependencyExpression::Or {
+            or: vec![
+                DependencyExpression::And {
+                    and: vec![
+                        condition("A", AddCondition::AddCondRunning),
+                        condition("B", AddCondition::AddCondSucceeded),
+                    ],
+                },
+                DependencyExpression::Not {
+                    not: Box::new(condition("C", AddCondition::AddCondRunning)),
+                },
+            ],
+        }
+    }
+
+    // [utest->swdd~workload-dependency-expression~1]
+    #[test]
+    fn utest_dependency_expression_evaluate() {
+        let expression = generate_test_expression();
+
+        let evaluate = |fulfilled: &[&str]| {
+            expression.evaluate(&|workload_name: &str, _: &AddCondition| {
+                fulfilled.contains(&workload_name)
+            })
+        };
+        assert!(evaluate(&["A", "B", "C"]));
+        assert!(!evaluate(&["A", "C"]));
+        assert!(evaluate(&["A"]));
+        assert!(!evaluate(&["B", "C"]));
+
+        let always = |_: &str, _: &AddCondition| true;
+        assert!(DependencyExpression::And { and: vec![] }.evaluate(&always));
+        assert!(!DependencyExpression::Or { or: vec![] }.evaluate(&always));
+    }
+
+    // [utest->swdd~workload-dependency-expression~1]
+    #[test]
+    fn utest_dependency_expression_workload_names_and_display() {
+        let expression = generate_test_expression();
+
+        assert_eq!(expression.workload_names(), vec!["A", "B", "C"]);
+        assert_eq!(
+            expression.to_string(),
+            "(A: ADD_COND_RUNNING AND B: ADD_COND_SUCCEEDED) OR NOT C: ADD_COND_RUNNING"
+        );
+    }
+
+    // [utest->swdd~workload-dependency-expression~1]
+    #[test]
+    fn utest_dependency_expression_deserialize_from_yaml() {
+        let yaml = r#"
+or:
+  - and:
+      - workload: A
+        condition: ADD_COND_RUNNING
+      - workload: B
+        condition: ADD_COND_SUCCEEDED
+  - not:
+      workload: C
+      condition: ADD_COND_RUNNING
+"#;
+
+        let expression: DependencyExpression = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(expression, generate_test_expression());
+        assert_eq!(
+            serde_yaml::from_str::<DependencyExpression>(
+                &serde_yaml::to_string(&expression).unwrap()
+            )
+            .unwrap(),
+            expression
+        );
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_dependency_expression_to_and_from_proto() {
+        let expression = generate_test_expression();
+
+        let proto_expression = ank_base::DependencyExpression::from(expression.clone());
+        assert_eq!(
+            DependencyExpression::try_from(proto_expression),
+            Ok(expression)
+        );
+        assert!(DependencyExpression::try_from(ank_base::DependencyExpression::default()).is_err());
+    }
+}
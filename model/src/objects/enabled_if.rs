@@ -0,0 +1,187 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+const OR: &str = "||";
+const AND: &str = "&&";
+const EQUALS: &str = "==";
+const NOT_EQUALS: &str = "!=";
+const NOT: char = '!';
+
+#[derive(Debug, PartialEq, Eq)]
+enum Condition<'a> {
+    Exists(&'a str),
+    NotExists(&'a str),
+    Equals(&'a str, &'a str),
+    NotEquals(&'a str, &'a str),
+}
+
+impl Condition<'_> {
+    fn is_met(&self, attributes: &HashMap<String, String>) -> bool {
+        match self {
+            Condition::Exists(key) => attributes.contains_key(*key),
+            Condition::NotExists(key) => !attributes.contains_key(*key),
+            Condition::Equals(key, value) => attributes.get(*key).is_some_and(|v| v == value),
+            Condition::NotEquals(key, value) => attributes.get(*key).is_none_or(|v| v != value),
+        }
+    }
+}
+
+fn parse_key(key: &str) -> Result<&str, String> {
+    let key = key.trim();
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/'))
+    {
+        return Err(format!("Invalid attribute name '{}'.", key));
+    }
+    Ok(key)
+}
+
+fn parse_value(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(unquoted) = value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+        {
+            return unquoted;
+        }
+    }
+    value
+}
+
+fn parse_condition(condition: &str) -> Result<Condition<'_>, String> {
+    if let Some((key, value)) = condition.split_once(NOT_EQUALS) {
+        return Ok(Condition::NotEquals(parse_key(key)?, parse_value(value)));
+    }
+    if let Some((key, value)) = condition.split_once(EQUALS) {
+        return Ok(Condition::Equals(parse_key(key)?, parse_value(value)));
+    }
+    match condition.trim().strip_prefix(NOT) {
+        Some(key) => Ok(Condition::NotExists(parse_key(key)?)),
+        None => Ok(Condition::Exists(parse_key(condition)?)),
+    }
+}
+
+// [impl->swdd~common-evaluates-enabled-if-expression~1]
+// An expression consists of conditions combined with '&&' and '||', where '&&' binds stronger.
+// An empty expression is always met.
+pub fn evaluate_enabled_if(
+    expression: &str,
+    attributes: &HashMap<String, String>,
+) -> Result<bool, String> {
+    if expression.trim().is_empty() {
+        return Ok(true);
+    }
+
+    let mut result = false;
+    for conjunction in expression.split(OR) {
+        let mut conjunction_result = true;
+        for condition in conjunction.split(AND) {
+            conjunction_result &= parse_condition(condition)
+                .map_err(|err| format!("Invalid enabledIf expression '{}': {}", expression, err))?
+                .is_met(attributes);
+        }
+        result |= conjunction_result;
+    }
+    Ok(result)
+}
+
+// [impl->swdd~common-evaluates-enabled-if-expression~1]
+pub fn verify_enabled_if(expression: &str) -> Result<(), String> {
+    evaluate_enabled_if(expression, &HashMap::new()).map(|_| ())
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{evaluate_enabled_if, verify_enabled_if};
+
+    fn attributes() -> HashMap<String, String> {
+        HashMap::from([
+            ("camera".to_string(), "true".to_string()),
+            ("hw.variant".to_string(), "premium".to_string()),
+        ])
+    }
+
+    // [utest->swdd~common-evaluates-enabled-if-expression~1]
+    #[test]
+    fn utest_evaluate_enabled_if_empty_expression_is_met() {
+        assert_eq!(evaluate_enabled_if("", &HashMap::new()), Ok(true));
+        assert_eq!(evaluate_enabled_if("  ", &attributes()), Ok(true));
+    }
+
+    // [utest->swdd~common-evaluates-enabled-if-expression~1]
+    #[test]
+    fn utest_evaluate_enabled_if_conditions() {
+        let attributes = attributes();
+
+        assert_eq!(evaluate_enabled_if("camera == true", &attributes), Ok(true));
+        assert_eq!(evaluate_enabled_if("camera==\"true\"", &attributes), Ok(true));
+        assert_eq!(evaluate_enabled_if("camera == false", &attributes), Ok(false));
+        assert_eq!(evaluate_enabled_if("camera != false", &attributes), Ok(true));
+        assert_eq!(evaluate_enabled_if("lidar != true", &attributes), Ok(true));
+        assert_eq!(evaluate_enabled_if("hw.variant", &attributes), Ok(true));
+        assert_eq!(evaluate_enabled_if("lidar", &attributes), Ok(false));
+        assert_eq!(evaluate_enabled_if("!lidar", &attributes), Ok(true));
+    }
+
+    // [utest->swdd~common-evaluates-enabled-if-expression~1]
+    #[test]
+    fn utest_evaluate_enabled_if_combined_conditions() {
+        let attributes = attributes();
+
+        assert_eq!(
+            evaluate_enabled_if("camera == true && hw.variant == 'premium'", &attributes),
+            Ok(true)
+        );
+        assert_eq!(
+            evaluate_enabled_if("camera == true && lidar", &attributes),
+            Ok(false)
+        );
+        assert_eq!(
+            evaluate_enabled_if("lidar || hw.variant == premium", &attributes),
+            Ok(true)
+        );
+        assert_eq!(
+            evaluate_enabled_if("lidar && camera || hw.variant == basic", &attributes),
+            Ok(false)
+        );
+    }
+
+    // [utest->swdd~common-evaluates-enabled-if-expression~1]
+    #[test]
+    fn utest_verify_enabled_if_rejects_invalid_expression() {
+        assert!(verify_enabled_if("camera == true && hw.variant != basic").is_ok());
+        assert_eq!(
+            verify_enabled_if("camera == true &&"),
+            Err("Invalid enabledIf expression 'camera == true &&': Invalid attribute name ''."
+                .to_string())
+        );
+        assert!(verify_enabled_if("== true").is_err());
+        assert!(verify_enabled_if("camera is true").is_err());
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+// [impl->swdd~workload-log-level~1]
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLevel {
+    // the level is passed to the workload as is, e.g. "debug" or "info"
+    pub level: String,
+    // the workload picks up a changed level at runtime instead of being restarted
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub live_update: bool,
+}
+
+impl From<ank_base::LogLevel> for LogLevel {
+    fn from(item: ank_base::LogLevel) -> Self {
+        LogLevel {
+            level: item.level,
+            live_update: item.live_update,
+        }
+    }
+}
+
+impl From<LogLevel> for ank_base::LogLevel {
+    fn from(item: LogLevel) -> Self {
+        ank_base::LogLevel {
+            level: item.level,
+            live_update: item.live_update,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::objects::LogLevel;
+
+    // [utest->swdd~workload-log-level~1]
+    #[test]
+    fn utest_deserializes_log_level_without_live_update() {
+        let log_level: LogLevel = serde_yaml::from_str("level: debug").unwrap();
+
+        assert_eq!(
+            log_level,
+            LogLevel {
+                level: "debug".to_string(),
+                live_update: false,
+            }
+        );
+        assert_eq!(serde_yaml::to_string(&log_level).unwrap(), "level: debug\n");
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+// [impl->swdd~workload-log-forwarding~1]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LogSink {
+    #[default]
+    Journald = 0,
+    Syslog = 1,
+    File = 2,
+}
+
+impl TryFrom<i32> for LogSink {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == LogSink::Journald as i32 => Ok(LogSink::Journald),
+            x if x == LogSink::Syslog as i32 => Ok(LogSink::Syslog),
+            x if x == LogSink::File as i32 => Ok(LogSink::File),
+            _ => Err(format!("Received an unknown value '{value}' as log sink.")),
+        }
+    }
+}
+
+fn is_zero<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+// [impl->swdd~workload-log-forwarding~1]
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LogRoute {
+    pub sink: LogSink,
+    // the address of the syslog endpoint or the path of the file, unused for journald
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub target: String,
+    // only used for files, zero selects the default of the agent
+    #[serde(skip_serializing_if = "is_zero")]
+    pub max_file_size: u64,
+    #[serde(skip_serializing_if = "is_zero")]
+    pub max_files: u32,
+}
+
+impl TryFrom<ank_base::LogRoute> for LogRoute {
+    type Error = String;
+
+    fn try_from(item: ank_base::LogRoute) -> Result<Self, Self::Error> {
+        Ok(LogRoute {
+            sink: item.sink.try_into()?,
+            target: item.target,
+            max_file_size: item.max_file_size,
+            max_files: item.max_files,
+        })
+    }
+}
+
+impl From<LogRoute> for ank_base::LogRoute {
+    fn from(item: LogRoute) -> Self {
+        ank_base::LogRoute {
+            sink: item.sink as i32,
+            target: item.target,
+            max_file_size: item.max_file_size,
+            max_files: item.max_files,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+// [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+#[cfg(test)]
+mod tests {
+    use crate::objects::{LogRoute, LogSink};
+    use api::ank_base;
+
+    // [utest->swdd~workload-log-forwarding~1]
+    #[test]
+    fn utest_converts_log_route_to_and_from_proto() {
+        let log_route = LogRoute {
+            sink: LogSink::File,
+            target: "/var/log/workload.log".to_string(),
+            max_file_size: 1024,
+            max_files: 2,
+        };
+        let proto_log_route = ank_base::LogRoute {
+            sink: ank_base::LogSink::File as i32,
+            target: "/var/log/workload.log".to_string(),
+            max_file_size: 1024,
+            max_files: 2,
+        };
+
+        assert_eq!(ank_base::LogRoute::from(log_route.clone()), proto_log_route);
+        assert_eq!(LogRoute::try_from(proto_log_route), Ok(log_route));
+        assert!(LogRoute::try_from(ank_base::LogRoute {
+            sink: -1,
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    // [utest->swdd~workload-log-forwarding~1]
+    #[test]
+    fn utest_deserializes_log_route_with_defaults() {
+        let log_route: LogRoute =
+            serde_yaml::from_str("sink: SYSLOG\ntarget: 127.0.0.1:514").unwrap();
+
+        assert_eq!(
+            log_route,
+            LogRoute {
+                sink: LogSink::Syslog,
+                target: "127.0.0.1:514".to_string(),
+                max_file_size: 0,
+                max_files: 0,
+            }
+        );
+        assert_eq!(
+            serde_yaml::to_string(&log_route).unwrap(),
+            "sink: SYSLOG\ntarget: 127.0.0.1:514\n"
+        );
+    }
+}
@@ -17,11 +17,20 @@
 // [impl->swdd~common-conversions-between-ankaios-and-proto~1]
 
 mod state;
-pub use state::State;
+pub use state::{ConfigObject, State};
 
 mod complete_state;
 pub use complete_state::CompleteState;
 
+mod enabled_if;
+pub use enabled_if::{evaluate_enabled_if, verify_enabled_if};
+
+mod workload_template;
+pub use workload_template::WorkloadTemplate;
+
+mod workload_group;
+pub use workload_group::WorkloadGroup;
+
 mod stored_workload_spec;
 #[cfg(any(feature = "test_utils", test))]
 pub use stored_workload_spec::{
@@ -49,13 +58,36 @@ pub use workload_spec::{
 };
 
 pub use workload_spec::{
-    get_workloads_per_agent, AddCondition, DeleteCondition, DeletedWorkload,
-    DeletedWorkloadCollection, FulfilledBy, RestartPolicy, WorkloadCollection, WorkloadSpec,
+    get_workloads_per_agent, priority_from_proto, AddCondition, ControlInterfaceMode,
+    DeleteCondition, DeletedWorkload, DeletedWorkloadCollection, DependencyFailurePolicy,
+    DisconnectPolicy, FulfilledBy, RestartPolicy, UnknownStatePolicy, UpdateStrategy,
+    WorkloadCollection, WorkloadSpec,
 };
 
+mod cron_schedule;
+pub use cron_schedule::CronSchedule;
+
+mod dependency_expression;
+pub use dependency_expression::DependencyExpression;
+
 mod tag;
 pub use tag::Tag;
 
+mod log_level;
+pub use log_level::LogLevel;
+
+mod log_route;
+pub use log_route::{LogRoute, LogSink};
+
+mod resources;
+pub use resources::{ResourceRequests, Resources};
+
+mod workload_hooks;
+pub use workload_hooks::WorkloadHooks;
+
+mod workload_profile;
+pub use workload_profile::WorkloadProfile;
+
 mod workload_instance_name;
 #[cfg(any(feature = "test_utils", test))]
 pub use workload_instance_name::generate_test_workload_instance_name;
@@ -63,3 +95,12 @@ pub use workload_instance_name::{ConfigHash, WorkloadInstanceName, WorkloadInsta
 
 mod agent_name;
 pub use agent_name::AgentName;
+
+mod spec_diff;
+pub use spec_diff::{diff_states, diff_workload_specs, workload_specs_differ, FieldDiff, StateDiff};
+
+mod system_state;
+pub use system_state::{
+    AgentConnectionStatus, AgentInfo, AgentResources, ControlInterfaceMetrics, RequestLaneInfo,
+    ServerInfo, SystemState, TerminatedWorkload,
+};
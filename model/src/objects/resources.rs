@@ -0,0 +1,160 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+const MEMORY_SUFFIXES: [(&str, u64); 8] = [
+    ("Ki", 1 << 10),
+    ("Mi", 1 << 20),
+    ("Gi", 1 << 30),
+    ("Ti", 1 << 40),
+    ("K", 1_000),
+    ("M", 1_000_000),
+    ("G", 1_000_000_000),
+    ("T", 1_000_000_000_000),
+];
+
+// [impl->swdd~workload-resource-requests~1]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Resources {
+    pub requests: ResourceRequests,
+}
+
+// The quantities are kept as given in the manifest, e.g. '500m' cpu or '256Mi' memory.
+// [impl->swdd~workload-resource-requests~1]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ResourceRequests {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<String>,
+}
+
+impl ResourceRequests {
+    // The requested cpu in thousandths of a cpu core, e.g. 500 for '500m' or '0.5'.
+    pub fn cpu_millis(&self) -> Result<u64, String> {
+        let Some(cpu) = &self.cpu else {
+            return Ok(0);
+        };
+        let millis = match cpu.strip_suffix('m') {
+            Some(millis) => millis.parse::<u64>().ok(),
+            None => cpu
+                .parse::<f64>()
+                .ok()
+                .filter(|cores| cores.is_finite() && *cores >= 0.0)
+                .map(|cores| (cores * 1000.0).round() as u64),
+        };
+        millis.ok_or_else(|| format!("Invalid cpu request '{}'.", cpu))
+    }
+
+    // The requested memory in bytes, e.g. 268435456 for '256Mi'.
+    pub fn memory_bytes(&self) -> Result<u64, String> {
+        let Some(memory) = &self.memory else {
+            return Ok(0);
+        };
+        let (value, factor) = MEMORY_SUFFIXES
+            .iter()
+            .find_map(|(suffix, factor)| memory.strip_suffix(suffix).map(|value| (value, *factor)))
+            .unwrap_or((memory.as_str(), 1));
+        value
+            .parse::<u64>()
+            .ok()
+            .and_then(|value| value.checked_mul(factor))
+            .ok_or_else(|| format!("Invalid memory request '{}'.", memory))
+    }
+
+    pub fn verify(&self) -> Result<(), String> {
+        self.cpu_millis()?;
+        self.memory_bytes()?;
+        Ok(())
+    }
+}
+
+impl From<ank_base::Resources> for Resources {
+    fn from(item: ank_base::Resources) -> Self {
+        let requests = item.requests.unwrap_or_default();
+        Resources {
+            requests: ResourceRequests {
+                cpu: Some(requests.cpu).filter(|cpu| !cpu.is_empty()),
+                memory: Some(requests.memory).filter(|memory| !memory.is_empty()),
+            },
+        }
+    }
+}
+
+impl From<Resources> for ank_base::Resources {
+    fn from(item: Resources) -> Self {
+        ank_base::Resources {
+            requests: Some(ank_base::ResourceRequests {
+                cpu: item.requests.cpu.unwrap_or_default(),
+                memory: item.requests.memory.unwrap_or_default(),
+            }),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::ResourceRequests;
+
+    fn requests(cpu: Option<&str>, memory: Option<&str>) -> ResourceRequests {
+        ResourceRequests {
+            cpu: cpu.map(ToOwned::to_owned),
+            memory: memory.map(ToOwned::to_owned),
+        }
+    }
+
+    // [utest->swdd~workload-resource-requests~1]
+    #[test]
+    fn utest_resource_requests_parses_quantities() {
+        assert_eq!(requests(Some("500m"), None).cpu_millis(), Ok(500));
+        assert_eq!(requests(Some("0.25"), None).cpu_millis(), Ok(250));
+        assert_eq!(requests(Some("2"), None).cpu_millis(), Ok(2000));
+        assert_eq!(requests(None, None).cpu_millis(), Ok(0));
+
+        assert_eq!(requests(None, Some("256Mi")).memory_bytes(), Ok(256 << 20));
+        assert_eq!(requests(None, Some("1G")).memory_bytes(), Ok(1_000_000_000));
+        assert_eq!(requests(None, Some("4096")).memory_bytes(), Ok(4096));
+        assert_eq!(requests(None, None).memory_bytes(), Ok(0));
+    }
+
+    // [utest->swdd~workload-resource-requests~1]
+    #[test]
+    fn utest_resource_requests_rejects_invalid_quantities() {
+        assert_eq!(
+            requests(Some("half"), None).verify(),
+            Err("Invalid cpu request 'half'.".to_string())
+        );
+        assert_eq!(
+            requests(Some("-1"), None).verify(),
+            Err("Invalid cpu request '-1'.".to_string())
+        );
+        assert_eq!(
+            requests(None, Some("1.5Gi")).verify(),
+            Err("Invalid memory request '1.5Gi'.".to_string())
+        );
+    }
+}
@@ -0,0 +1,381 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+};
+
+use serde_yaml::{Mapping, Value};
+
+use super::{workload_instance_name::canonicalize_runtime_config, State, StoredWorkloadSpec};
+
+const RUNTIME_CONFIG_FIELD: &str = "runtimeConfig";
+const NOT_SET: &str = "<not set>";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    pub field: String,
+    pub old: String,
+    pub new: String,
+}
+
+// Brings a workload into a form in which semantically equal workloads are equal:
+// - the order of tags and config references is irrelevant
+// - the runtime config is compared in its canonical form, as for the config hash
+// - omitted fields are equal to their defaults, as the workload is compared after deserialization
+fn normalize(workload: &StoredWorkloadSpec) -> Mapping {
+    let mut workload = workload.clone();
+    workload
+        .tags
+        .sort_by(|a, b| (&a.key, &a.value).cmp(&(&b.key, &b.value)));
+    workload.configs.sort();
+    workload.configs.dedup();
+
+    let Ok(Value::Mapping(mut fields)) = serde_yaml::to_value(&workload) else {
+        return Mapping::new();
+    };
+    let canonical_runtime_config = canonicalize_runtime_config(&workload.runtime_config);
+    if let Ok(runtime_config) = serde_yaml::from_str::<Value>(&canonical_runtime_config) {
+        fields.insert(RUNTIME_CONFIG_FIELD.into(), runtime_config);
+    }
+    fields
+}
+
+fn render(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => NOT_SET.to_string(),
+        Some(Value::String(value)) => value.clone(),
+        Some(value) => serde_json::to_string(value).unwrap_or_else(|_| format!("{value:?}")),
+    }
+}
+
+// [impl->swdd~common-diffs-workloads-semantically~1]
+/// Returns the fields in which the two workloads differ semantically, sorted by field name.
+pub fn diff_workload_specs(old: &StoredWorkloadSpec, new: &StoredWorkloadSpec) -> Vec<FieldDiff> {
+    let old_fields = normalize(old);
+    let new_fields = normalize(new);
+
+    let field_names: BTreeSet<&str> = old_fields
+        .keys()
+        .chain(new_fields.keys())
+        .filter_map(Value::as_str)
+        .collect();
+
+    field_names
+        .into_iter()
+        .filter_map(|field| {
+            let old_value = old_fields.get(field);
+            let new_value = new_fields.get(field);
+            if old_value == new_value {
+                return None;
+            }
+            let field_diff = FieldDiff {
+                field: field.to_string(),
+                old: render(old_value),
+                new: render(new_value),
+            };
+            if field == RUNTIME_CONFIG_FIELD {
+                // the parsed runtime config is not meant for the reader
+                return Some(FieldDiff {
+                    old: old.runtime_config.clone(),
+                    new: new.runtime_config.clone(),
+                    ..field_diff
+                });
+            }
+            Some(field_diff)
+        })
+        .collect()
+}
+
+// [impl->swdd~common-diffs-workloads-semantically~1]
+pub fn workload_specs_differ(old: &StoredWorkloadSpec, new: &StoredWorkloadSpec) -> bool {
+    normalize(old) != normalize(new)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    pub added_workloads: Vec<String>,
+    pub deleted_workloads: Vec<String>,
+    pub changed_workloads: BTreeMap<String, Vec<FieldDiff>>,
+    pub added_configs: Vec<String>,
+    pub deleted_configs: Vec<String>,
+    pub changed_configs: Vec<String>,
+    pub added_templates: Vec<String>,
+    pub deleted_templates: Vec<String>,
+    pub changed_templates: Vec<String>,
+}
+
+impl StateDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == StateDiff::default()
+    }
+}
+
+// Returns the sorted names of the added, deleted and changed entries.
+fn diff_maps<T>(
+    old: &HashMap<String, T>,
+    new: &HashMap<String, T>,
+    differ: impl Fn(&T, &T) -> bool,
+) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let mut added: Vec<String> = new
+        .keys()
+        .filter(|name| !old.contains_key(*name))
+        .cloned()
+        .collect();
+    let mut deleted: Vec<String> = old
+        .keys()
+        .filter(|name| !new.contains_key(*name))
+        .cloned()
+        .collect();
+    let mut changed: Vec<String> = old
+        .iter()
+        .filter(|(name, old_entry)| {
+            new.get(*name).is_some_and(|new_entry| differ(old_entry, new_entry))
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+    added.sort();
+    deleted.sort();
+    changed.sort();
+    (added, deleted, changed)
+}
+
+// [impl->swdd~common-diffs-states-semantically~1]
+pub fn diff_states(old: &State, new: &State) -> StateDiff {
+    let (added_workloads, deleted_workloads, changed_workload_names) =
+        diff_maps(&old.workloads, &new.workloads, workload_specs_differ);
+    let changed_workloads = changed_workload_names
+        .into_iter()
+        .map(|name| {
+            let field_diffs = diff_workload_specs(&old.workloads[&name], &new.workloads[&name]);
+            (name, field_diffs)
+        })
+        .collect();
+    let (added_configs, deleted_configs, changed_configs) =
+        diff_maps(&old.configs, &new.configs, |old, new| old != new);
+    let (added_templates, deleted_templates, changed_templates) = diff_maps(
+        &old.workload_templates,
+        &new.workload_templates,
+        |old, new| old != new,
+    );
+
+    StateDiff {
+        added_workloads,
+        deleted_workloads,
+        changed_workloads,
+        added_configs,
+        deleted_configs,
+        changed_configs,
+        added_templates,
+        deleted_templates,
+        changed_templates,
+    }
+}
+
+impl fmt::Display for StateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes.");
+        }
+
+        for name in &self.added_workloads {
+            writeln!(f, "+ workload '{name}'")?;
+        }
+        for (name, field_diffs) in &self.changed_workloads {
+            writeln!(f, "~ workload '{name}'")?;
+            for field_diff in field_diffs {
+                writeln!(
+                    f,
+                    "    {}: '{}' -> '{}'",
+                    field_diff.field,
+                    field_diff.old.trim_end(),
+                    field_diff.new.trim_end()
+                )?;
+            }
+        }
+        for name in &self.deleted_workloads {
+            writeln!(f, "- workload '{name}'")?;
+        }
+
+        for (kind, added, changed, deleted) in [
+            (
+                "config",
+                &self.added_configs,
+                &self.changed_configs,
+                &self.deleted_configs,
+            ),
+            (
+                "template",
+                &self.added_templates,
+                &self.changed_templates,
+                &self.deleted_templates,
+            ),
+        ] {
+            for name in added {
+                writeln!(f, "+ {kind} '{name}'")?;
+            }
+            for name in changed {
+                writeln!(f, "~ {kind} '{name}'")?;
+            }
+            for name in deleted {
+                writeln!(f, "- {kind} '{name}'")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::objects::{
+        generate_test_stored_workload_spec, RestartPolicy, State, StoredWorkloadSpec, Tag,
+    };
+
+    use super::{diff_states, diff_workload_specs, workload_specs_differ, FieldDiff, StateDiff};
+
+    const AGENT_A: &str = "agent_A";
+    const RUNTIME: &str = "podman";
+
+    fn tag(key: &str, value: &str) -> Tag {
+        Tag {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    // [utest->swdd~common-diffs-workloads-semantically~1]
+    #[test]
+    fn utest_diff_workload_specs_ignores_order_and_formatting() {
+        let mut old = generate_test_stored_workload_spec(AGENT_A, RUNTIME);
+        old.tags = vec![tag("owner", "team_a"), tag("tier", "backend")];
+        old.configs = vec!["config_1".to_string(), "config_2".to_string()];
+        old.runtime_config = "image: alpine:latest\ncommandOptions: [\"--rm\"]\n".to_string();
+
+        let mut new = old.clone();
+        new.tags.reverse();
+        new.configs.reverse();
+        new.runtime_config =
+            "# the same config\ncommandOptions:\n  - --rm\nimage: 'alpine:latest'".to_string();
+
+        assert!(!workload_specs_differ(&old, &new));
+        assert!(diff_workload_specs(&old, &new).is_empty());
+    }
+
+    // [utest->swdd~common-diffs-workloads-semantically~1]
+    #[test]
+    fn utest_diff_workload_specs_treats_omitted_fields_as_default() {
+        let old: StoredWorkloadSpec =
+            serde_yaml::from_str("agent: agent_A\nruntime: podman\nruntimeConfig: 'image: a'")
+                .unwrap();
+        let new: StoredWorkloadSpec = serde_yaml::from_str(
+            "agent: agent_A\nruntime: podman\nrestartPolicy: NEVER\ntemplate: ''\nruntimeConfig: \"image: a\\nports: []\"",
+        )
+        .unwrap();
+
+        assert!(!workload_specs_differ(&old, &new));
+    }
+
+    // [utest->swdd~common-diffs-workloads-semantically~1]
+    #[test]
+    fn utest_diff_workload_specs_reports_changed_fields() {
+        let old = generate_test_stored_workload_spec(AGENT_A, RUNTIME);
+        let mut new = old.clone();
+        new.restart_policy = RestartPolicy::OnFailure;
+        new.runtime_config = "image: alpine:3.19".to_string();
+        new.template = "base".to_string();
+
+        assert_eq!(
+            diff_workload_specs(&old, &new),
+            vec![
+                FieldDiff {
+                    field: "restartPolicy".to_string(),
+                    old: "ALWAYS".to_string(),
+                    new: "ON_FAILURE".to_string(),
+                },
+                FieldDiff {
+                    field: "runtimeConfig".to_string(),
+                    old: old.runtime_config.clone(),
+                    new: "image: alpine:3.19".to_string(),
+                },
+                FieldDiff {
+                    field: "template".to_string(),
+                    old: "<not set>".to_string(),
+                    new: "base".to_string(),
+                },
+            ]
+        );
+    }
+
+    // [utest->swdd~common-diffs-states-semantically~1]
+    #[test]
+    fn utest_diff_states_reports_added_deleted_and_changed_workloads() {
+        let unchanged = generate_test_stored_workload_spec(AGENT_A, RUNTIME);
+        let mut changed = unchanged.clone();
+        changed.agent = "agent_B".to_string();
+
+        let old = State {
+            workloads: [
+                ("unchanged".to_string(), unchanged.clone()),
+                ("changed".to_string(), unchanged.clone()),
+                ("deleted".to_string(), unchanged.clone()),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        let new = State {
+            workloads: [
+                ("unchanged".to_string(), unchanged.clone()),
+                ("changed".to_string(), changed),
+                ("added".to_string(), unchanged),
+            ]
+            .into(),
+            ..Default::default()
+        };
+
+        let state_diff = diff_states(&old, &new);
+
+        assert_eq!(
+            state_diff,
+            StateDiff {
+                added_workloads: vec!["added".to_string()],
+                deleted_workloads: vec!["deleted".to_string()],
+                changed_workloads: BTreeMap::from([(
+                    "changed".to_string(),
+                    vec![FieldDiff {
+                        field: "agent".to_string(),
+                        old: AGENT_A.to_string(),
+                        new: "agent_B".to_string(),
+                    }]
+                )]),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            state_diff.to_string(),
+            "+ workload 'added'\n~ workload 'changed'\n    agent: 'agent_A' -> 'agent_B'\n- workload 'deleted'\n"
+        );
+        assert!(diff_states(&old, &old).is_empty());
+    }
+}
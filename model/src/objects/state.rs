@@ -0,0 +1,630 @@
+// Copyright (c) 2023 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+
+use crate::serialize_to_ordered_map;
+use crate::objects::{StoredWorkloadSpec, WorkloadGroup, WorkloadTemplate};
+
+use api::ank_base;
+
+const CURRENT_API_VERSION: &str = "v0.1";
+
+// [impl->swdd~common-config-objects-in-state~1]
+pub type ConfigObject = HashMap<String, String>;
+
+// [impl->swdd~common-object-representation~1]#[accessible_by_field_name]
+// [impl->swdd~common-object-serialization~1]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct State {
+    pub api_version: String,
+    #[serde(default, serialize_with = "serialize_to_ordered_map")]
+    pub workloads: HashMap<String, StoredWorkloadSpec>,
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub configs: HashMap<String, ConfigObject>,
+    // [impl->swdd~common-workload-templates-in-state~1]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub workload_templates: HashMap<String, WorkloadTemplate>,
+    // [impl->swdd~common-system-modes-in-state~1]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modes: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub active_mode: String,
+    // [impl->swdd~workload-profiles~1]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub active_profile: String,
+    // [impl->swdd~common-workload-groups-in-state~1]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub workload_groups: HashMap<String, WorkloadGroup>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            api_version: CURRENT_API_VERSION.into(),
+            workloads: Default::default(),
+            configs: Default::default(),
+            workload_templates: Default::default(),
+            modes: Default::default(),
+            active_mode: Default::default(),
+            active_profile: Default::default(),
+            workload_groups: Default::default(),
+        }
+    }
+}
+
+impl From<State> for ank_base::State {
+    fn from(item: State) -> Self {
+        ank_base::State {
+            api_version: item.api_version,
+            workloads: item
+                .workloads
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+            configs: item
+                .configs
+                .into_iter()
+                .map(|(k, v)| (k, ank_base::ConfigObject { data: v }))
+                .collect(),
+            workload_templates: item
+                .workload_templates
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+            modes: item.modes,
+            active_mode: item.active_mode,
+            active_profile: item.active_profile,
+            workload_groups: item
+                .workload_groups
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<ank_base::State> for State {
+    type Error = String;
+
+    fn try_from(item: ank_base::State) -> Result<Self, Self::Error> {
+        Ok(State {
+            api_version: item.api_version,
+            workloads: item
+                .workloads
+                .into_iter()
+                .map(|(k, v)| Ok((k.to_owned(), v.try_into()?)))
+                .collect::<Result<HashMap<String, StoredWorkloadSpec>, String>>()?,
+            configs: item.configs.into_iter().map(|(k, v)| (k, v.data)).collect(),
+            workload_templates: item
+                .workload_templates
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+            modes: item.modes,
+            active_mode: item.active_mode,
+            active_profile: item.active_profile,
+            workload_groups: item
+                .workload_groups
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+        })
+    }
+}
+
+impl State {
+    pub fn is_compatible_format(api_version: &String) -> bool {
+        api_version == CURRENT_API_VERSION
+    }
+
+    // [impl->swdd~common-config-objects-in-state~1]
+    pub fn get_configs_of_workload(
+        &self,
+        workload: &StoredWorkloadSpec,
+    ) -> Result<HashMap<String, ConfigObject>, String> {
+        workload
+            .configs
+            .iter()
+            .map(|config_name| {
+                self.configs
+                    .get(config_name)
+                    .map(|config| (config_name.clone(), config.clone()))
+                    .ok_or_else(|| format!("Config '{}' does not exist.", config_name))
+            })
+            .collect()
+    }
+
+    // [impl->swdd~common-system-modes-in-state~1]
+    // A workload without modes runs in every mode, also if no mode is active.
+    pub fn is_active_in_mode(&self, workload: &StoredWorkloadSpec) -> bool {
+        workload.modes.is_empty() || workload.modes.contains(&self.active_mode)
+    }
+
+    // [impl->swdd~common-resolves-members-of-workload-groups~1]
+    pub fn is_stopped_by_group(&self, workload_name: &str, workload: &StoredWorkloadSpec) -> bool {
+        self.workload_groups
+            .values()
+            .any(|group| group.stopped && group.contains(workload_name, workload))
+    }
+
+    // [impl->swdd~common-resolves-members-of-workload-groups~1]
+    // Returns the sorted names of the workloads of the group or None if the group does not exist.
+    pub fn members_of_group(&self, group_name: &str) -> Option<Vec<String>> {
+        let group = self.workload_groups.get(group_name)?;
+        let mut members: Vec<String> = self
+            .workloads
+            .iter()
+            .filter(|(workload_name, workload)| group.contains(workload_name, workload))
+            .map(|(workload_name, _)| workload_name.clone())
+            .collect();
+        members.sort();
+        Some(members)
+    }
+
+    // [impl->swdd~common-resolves-workload-profiles~1]
+    // The profile selected by the workload takes precedence over the active profile.
+    fn selected_profile<'a>(&'a self, workload: &'a StoredWorkloadSpec) -> &'a str {
+        if workload.profile.is_empty() {
+            &self.active_profile
+        } else {
+            &workload.profile
+        }
+    }
+
+    // [impl->swdd~common-expands-workload-templates~1]
+    // [impl->swdd~common-resolves-workload-profiles~1]
+    // Settings of the workload take precedence over the ones of the referenced template. The
+    // profile is resolved first, such that it can override the template parameters.
+    pub fn expand_workload(
+        &self,
+        workload: &StoredWorkloadSpec,
+    ) -> Result<StoredWorkloadSpec, String> {
+        let mut expanded_workload = workload.clone();
+        if let Some(profile) = workload.profiles.get(self.selected_profile(workload)) {
+            profile.apply_to(&mut expanded_workload);
+        }
+        expanded_workload.profile.clear();
+        expanded_workload.profiles.clear();
+
+        if expanded_workload.template.is_empty() {
+            return Ok(expanded_workload);
+        }
+
+        let template = self
+            .workload_templates
+            .get(&workload.template)
+            .ok_or_else(|| format!("Workload template '{}' does not exist.", workload.template))?;
+
+        if expanded_workload.runtime.is_empty() {
+            expanded_workload.runtime = template.runtime.clone();
+        }
+        if expanded_workload.runtime_config.is_empty() {
+            expanded_workload.runtime_config =
+                template.render_runtime_config(&expanded_workload.template_parameters)?;
+        }
+        expanded_workload.template.clear();
+        expanded_workload.template_parameters.clear();
+
+        Ok(expanded_workload)
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+// [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+// [utest->swdd~common-object-representation~1]
+// [utest->swdd~common-object-serialization~1]
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashMap;
+
+    use api::ank_base;
+
+    use crate::{
+        objects::{
+            generate_test_stored_workload_spec, State, Tag, WorkloadGroup, WorkloadProfile,
+            WorkloadTemplate,
+        },
+        test_utils::{generate_test_proto_state, generate_test_state},
+    };
+
+    #[test]
+    fn utest_converts_to_proto_state() {
+        let ankaios_state = generate_test_state();
+        let proto_state = generate_test_proto_state();
+
+        assert_eq!(ank_base::State::from(ankaios_state), proto_state);
+    }
+
+    #[test]
+    fn utest_converts_to_ankaios_state() {
+        let ankaios_state = generate_test_state();
+        let proto_state = generate_test_proto_state();
+
+        assert_eq!(State::try_from(proto_state), Ok(ankaios_state));
+    }
+
+    // [utest->swdd~common-config-objects-in-state~1]
+    #[test]
+    fn utest_converts_configs_to_and_from_proto_state() {
+        let mut ankaios_state = generate_test_state();
+        ankaios_state.configs = HashMap::from([(
+            "config_1".to_string(),
+            HashMap::from([("key".to_string(), "value".to_string())]),
+        )]);
+        let mut proto_state = generate_test_proto_state();
+        proto_state.configs = HashMap::from([(
+            "config_1".to_string(),
+            ank_base::ConfigObject {
+                data: HashMap::from([("key".to_string(), "value".to_string())]),
+            },
+        )]);
+
+        assert_eq!(ank_base::State::from(ankaios_state.clone()), proto_state);
+        assert_eq!(State::try_from(proto_state), Ok(ankaios_state));
+    }
+
+    // [utest->swdd~common-config-objects-in-state~1]
+    #[test]
+    fn utest_get_configs_of_workload() {
+        let config = HashMap::from([("key".to_string(), "value".to_string())]);
+        let state = State {
+            configs: HashMap::from([
+                ("config_1".to_string(), config.clone()),
+                ("config_2".to_string(), config.clone()),
+            ]),
+            ..Default::default()
+        };
+        let mut workload = generate_test_stored_workload_spec("agent", "runtime");
+        workload.configs = vec!["config_1".to_string()];
+
+        assert_eq!(
+            state.get_configs_of_workload(&workload),
+            Ok(HashMap::from([("config_1".to_string(), config)]))
+        );
+
+        workload.configs.push("config_3".to_string());
+        assert_eq!(
+            state.get_configs_of_workload(&workload),
+            Err("Config 'config_3' does not exist.".to_string())
+        );
+    }
+
+    // [utest->swdd~common-workload-templates-in-state~1]
+    #[test]
+    fn utest_converts_workload_templates_to_and_from_proto_state() {
+        let template = WorkloadTemplate {
+            runtime: "podman".to_string(),
+            runtime_config: "image: sensor:{{tag}}".to_string(),
+            parameters: HashMap::from([("tag".to_string(), "latest".to_string())]),
+        };
+        let mut ankaios_state = generate_test_state();
+        ankaios_state.workload_templates =
+            HashMap::from([("template_1".to_string(), template.clone())]);
+        let mut proto_state = generate_test_proto_state();
+        proto_state.workload_templates =
+            HashMap::from([("template_1".to_string(), template.into())]);
+
+        assert_eq!(ank_base::State::from(ankaios_state.clone()), proto_state);
+        assert_eq!(State::try_from(proto_state), Ok(ankaios_state));
+    }
+
+    // [utest->swdd~common-system-modes-in-state~1]
+    #[test]
+    fn utest_workload_is_active_in_mode() {
+        let state = State {
+            modes: vec!["parked".to_string(), "driving".to_string()],
+            active_mode: "parked".to_string(),
+            ..Default::default()
+        };
+        let mut workload = generate_test_stored_workload_spec("agent", "runtime");
+        assert!(state.is_active_in_mode(&workload));
+
+        workload.modes = vec!["driving".to_string(), "parked".to_string()];
+        assert!(state.is_active_in_mode(&workload));
+
+        workload.modes = vec!["driving".to_string()];
+        assert!(!state.is_active_in_mode(&workload));
+        assert!(!State::default().is_active_in_mode(&workload));
+    }
+
+    // [utest->swdd~common-system-modes-in-state~1]
+    #[test]
+    fn utest_converts_modes_to_and_from_proto_state() {
+        let mut ankaios_state = generate_test_state();
+        ankaios_state.modes = vec!["parked".to_string()];
+        ankaios_state.active_mode = "parked".to_string();
+
+        let mut proto_state = generate_test_proto_state();
+        proto_state.modes = vec!["parked".to_string()];
+        proto_state.active_mode = "parked".to_string();
+
+        assert_eq!(ank_base::State::from(ankaios_state.clone()), proto_state);
+        assert_eq!(State::try_from(proto_state), Ok(ankaios_state));
+    }
+
+    // [utest->swdd~common-workload-groups-in-state~1]
+    #[test]
+    fn utest_converts_workload_groups_to_and_from_proto_state() {
+        let mut ankaios_state = generate_test_state();
+        ankaios_state.workload_groups = HashMap::from([(
+            "group".to_string(),
+            WorkloadGroup {
+                workloads: vec!["workload_name_1".to_string()],
+                selector: HashMap::from([("key".to_string(), "value".to_string())]),
+                stopped: true,
+            },
+        )]);
+
+        let mut proto_state = generate_test_proto_state();
+        proto_state.workload_groups = HashMap::from([(
+            "group".to_string(),
+            ank_base::WorkloadGroup {
+                workloads: vec!["workload_name_1".to_string()],
+                selector: HashMap::from([("key".to_string(), "value".to_string())]),
+                stopped: true,
+            },
+        )]);
+
+        assert_eq!(ank_base::State::from(ankaios_state.clone()), proto_state);
+        assert_eq!(State::try_from(proto_state), Ok(ankaios_state));
+    }
+
+    // [utest->swdd~common-resolves-members-of-workload-groups~1]
+    #[test]
+    fn utest_members_of_group_and_stopped_workloads() {
+        let mut state = generate_test_state();
+        let mut selected_workload = generate_test_stored_workload_spec("agent", "runtime");
+        selected_workload.tags = vec![Tag {
+            key: "tier".to_string(),
+            value: "backend".to_string(),
+        }];
+        state
+            .workloads
+            .insert("selected".to_string(), selected_workload.clone());
+        state.workload_groups = HashMap::from([(
+            "group".to_string(),
+            WorkloadGroup {
+                workloads: vec!["workload_name_2".to_string()],
+                selector: HashMap::from([("tier".to_string(), "backend".to_string())]),
+                stopped: true,
+            },
+        )]);
+
+        assert_eq!(
+            state.members_of_group("group"),
+            Some(vec!["selected".to_string(), "workload_name_2".to_string()])
+        );
+        assert_eq!(state.members_of_group("unknown"), None);
+
+        assert!(state.is_stopped_by_group("selected", &selected_workload));
+        let other_workload = state.workloads.get("workload_name_1").unwrap();
+        assert!(!state.is_stopped_by_group("workload_name_1", other_workload));
+
+        state.workload_groups.get_mut("group").unwrap().stopped = false;
+        assert!(!state.is_stopped_by_group("selected", &selected_workload));
+    }
+
+    // [utest->swdd~common-expands-workload-templates~1]
+    #[test]
+    fn utest_expand_workload_with_template() {
+        let state = State {
+            workload_templates: HashMap::from([(
+                "template_1".to_string(),
+                WorkloadTemplate {
+                    runtime: "podman".to_string(),
+                    runtime_config: "image: sensor:{{tag}}".to_string(),
+                    parameters: HashMap::from([("tag".to_string(), "latest".to_string())]),
+                },
+            )]),
+            ..Default::default()
+        };
+        let mut workload = generate_test_stored_workload_spec("agent", "");
+        workload.runtime_config = String::new();
+        workload.template = "template_1".to_string();
+        workload.template_parameters = HashMap::from([("tag".to_string(), "1.0".to_string())]);
+
+        let mut expected_workload = generate_test_stored_workload_spec("agent", "podman");
+        expected_workload.runtime_config = "image: sensor:1.0".to_string();
+
+        assert_eq!(state.expand_workload(&workload), Ok(expected_workload));
+    }
+
+    // [utest->swdd~common-expands-workload-templates~1]
+    #[test]
+    fn utest_expand_workload_keeps_settings_of_workload() {
+        let state = State {
+            workload_templates: HashMap::from([(
+                "template_1".to_string(),
+                WorkloadTemplate {
+                    runtime: "podman".to_string(),
+                    runtime_config: "image: sensor:{{tag}}".to_string(),
+                    parameters: HashMap::new(),
+                },
+            )]),
+            ..Default::default()
+        };
+        let expected_workload = generate_test_stored_workload_spec("agent", "podman-kube");
+        let mut workload = expected_workload.clone();
+        workload.template = "template_1".to_string();
+
+        assert_eq!(state.expand_workload(&workload), Ok(expected_workload));
+    }
+
+    // [utest->swdd~common-expands-workload-templates~1]
+    #[test]
+    fn utest_expand_workload_without_template_is_unchanged() {
+        let workload = generate_test_stored_workload_spec("agent", "runtime");
+
+        assert_eq!(
+            State::default().expand_workload(&workload),
+            Ok(workload.clone())
+        );
+    }
+
+    // [utest->swdd~common-expands-workload-templates~1]
+    #[test]
+    fn utest_expand_workload_fails_on_unknown_template() {
+        let mut workload = generate_test_stored_workload_spec("agent", "runtime");
+        workload.template = "template_1".to_string();
+
+        assert_eq!(
+            State::default().expand_workload(&workload),
+            Err("Workload template 'template_1' does not exist.".to_string())
+        );
+    }
+
+    // [utest->swdd~common-resolves-workload-profiles~1]
+    #[test]
+    fn utest_expand_workload_resolves_active_profile_before_template() {
+        let mut state = State {
+            workload_templates: HashMap::from([(
+                "template_1".to_string(),
+                WorkloadTemplate {
+                    runtime: "podman".to_string(),
+                    runtime_config: "image: sensor:{{tag}}".to_string(),
+                    parameters: HashMap::from([("tag".to_string(), "1.0".to_string())]),
+                },
+            )]),
+            active_profile: "dev".to_string(),
+            ..Default::default()
+        };
+        let mut workload = generate_test_stored_workload_spec("agent", "");
+        workload.runtime_config = String::new();
+        workload.template = "template_1".to_string();
+        workload.profiles = HashMap::from([(
+            "dev".to_string(),
+            WorkloadProfile {
+                template_parameters: HashMap::from([("tag".to_string(), "debug".to_string())]),
+                ..Default::default()
+            },
+        )]);
+
+        let mut expected_workload = generate_test_stored_workload_spec("agent", "podman");
+        expected_workload.runtime_config = "image: sensor:debug".to_string();
+        assert_eq!(state.expand_workload(&workload), Ok(expected_workload));
+
+        state.active_profile = "prod".to_string();
+        let mut expected_workload = generate_test_stored_workload_spec("agent", "podman");
+        expected_workload.runtime_config = "image: sensor:1.0".to_string();
+        assert_eq!(state.expand_workload(&workload), Ok(expected_workload));
+    }
+
+    // [utest->swdd~common-resolves-workload-profiles~1]
+    #[test]
+    fn utest_expand_workload_prefers_profile_selected_by_workload() {
+        let state = State {
+            active_profile: "prod".to_string(),
+            ..Default::default()
+        };
+        let mut workload = generate_test_stored_workload_spec("agent", "runtime");
+        workload.profile = "dev".to_string();
+        workload.profiles = HashMap::from([
+            (
+                "dev".to_string(),
+                WorkloadProfile {
+                    runtime_config: Some("image: sensor:debug".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "prod".to_string(),
+                WorkloadProfile {
+                    runtime_config: Some("image: sensor:1.0".to_string()),
+                    ..Default::default()
+                },
+            ),
+        ]);
+
+        let mut expected_workload = generate_test_stored_workload_spec("agent", "runtime");
+        expected_workload.runtime_config = "image: sensor:debug".to_string();
+        assert_eq!(state.expand_workload(&workload), Ok(expected_workload));
+    }
+
+    #[test]
+    fn utest_serialize_state_into_ordered_output() {
+        // input: random sorted state
+        let ankaios_state = generate_test_state();
+
+        // serialize to sorted output
+        let sorted_state_string = serde_yaml::to_string(&ankaios_state).unwrap();
+
+        let index_workload1 = sorted_state_string.find("workload_name_1").unwrap();
+        let index_workload2 = sorted_state_string.find("workload_name_2").unwrap();
+        assert!(
+            index_workload1 < index_workload2,
+            "expected sorted workloads."
+        );
+    }
+
+    #[test]
+    fn utest_state_accepts_compatible_state() {
+        let state_compatible_version = State {
+            ..Default::default()
+        };
+        assert!(State::is_compatible_format(
+            &state_compatible_version.api_version
+        ));
+    }
+
+    #[test]
+    fn utest_state_rejects_incompatible_state() {
+        let state_incompatible_version = State {
+            api_version: "incompatible_version".to_string(),
+            ..Default::default()
+        };
+        assert!(!State::is_compatible_format(
+            &state_incompatible_version.api_version
+        ));
+    }
+
+    #[test]
+    fn utest_state_rejects_state_without_api_version() {
+        let state_proto_no_version = ank_base::State {
+            ..Default::default()
+        };
+        let state_ankaios_no_version = State::try_from(state_proto_no_version).unwrap();
+
+        assert_eq!(state_ankaios_no_version.api_version, "".to_string());
+
+        let file_without_api_version = "";
+        let deserialization_result = serde_yaml::from_str::<State>(file_without_api_version)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(deserialization_result, "missing field `apiVersion`");
+    }
+}
@@ -0,0 +1,847 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use api::ank_base;
+use serde::{Deserialize, Serialize};
+
+use crate::serialize_to_ordered_map;
+
+use super::workload_spec::{is_default_priority, priority_from_proto};
+use super::{
+    AddCondition, ControlInterfaceMode, DependencyExpression, DependencyFailurePolicy,
+    DisconnectPolicy, LogLevel, LogRoute, Resources, RestartPolicy, Tag, UnknownStatePolicy,
+    UpdateStrategy, WorkloadHooks, WorkloadInstanceName, WorkloadProfile, WorkloadSpec,
+};
+
+#[derive(Debug, Serialize, Default, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredWorkloadSpec {
+    pub agent: String,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    #[serde(default, serialize_with = "serialize_to_ordered_map")]
+    pub dependencies: HashMap<String, AddCondition>,
+    #[serde(default)]
+    pub restart_policy: RestartPolicy,
+    // [impl->swdd~workload-references-workload-template~1]
+    // runtime and runtime config can be omitted if they are provided by the template
+    #[serde(default)]
+    pub runtime: String,
+    #[serde(default)]
+    pub runtime_config: String,
+    // [impl->swdd~workload-unknown-state-policies-for-dependencies~1]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub unknown_state_policies: HashMap<String, UnknownStatePolicy>,
+    // [impl->swdd~workload-running-for-add-condition~1]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub running_for_ms: HashMap<String, u64>,
+    // [impl->swdd~workload-dependency-expression~1]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependency_expression: Option<DependencyExpression>,
+    // [impl->swdd~workload-references-config-objects~1]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub configs: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub template: String,
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub template_parameters: HashMap<String, String>,
+    // [impl->swdd~workload-enabled-if-agent-attributes~1]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub enabled_if: String,
+    // [impl->swdd~workload-disconnect-policy~1]
+    #[serde(default, skip_serializing_if = "DisconnectPolicy::is_keep_running")]
+    pub disconnect_policy: DisconnectPolicy,
+    // [impl->swdd~workload-log-forwarding~1]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub log_forwarding: Vec<LogRoute>,
+    // [impl->swdd~workload-log-level~1]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<LogLevel>,
+    // [impl->swdd~workload-runs-in-system-modes~1]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modes: Vec<String>,
+    // [impl->swdd~workload-pre-shutdown-timeout~1]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_shutdown_timeout_ms: Option<u64>,
+    // [impl->swdd~workload-dependency-timeout~1]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependency_timeout_ms: Option<u64>,
+    // [impl->swdd~workload-schedule~1]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<String>,
+    // [impl->swdd~workload-capture-output~1]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_output_bytes: Option<u64>,
+    // [impl->swdd~workload-resource-requests~1]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<Resources>,
+    // [impl->swdd~workload-dependency-failure-policy~1]
+    #[serde(default, skip_serializing_if = "DependencyFailurePolicy::is_ignore")]
+    pub on_dependency_failure: DependencyFailurePolicy,
+    // [impl->swdd~workload-expected-startup-time~1]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_startup_time_ms: Option<u64>,
+    // [impl->swdd~workload-control-interface-request-quota~1]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub control_interface_requests_per_minute: Option<u32>,
+    // [impl->swdd~workload-hooks~1]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<WorkloadHooks>,
+    // [impl->swdd~workload-agent-dependencies~1]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agent_dependencies: Vec<String>,
+    // [impl->swdd~workload-profiles~1]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub profile: String,
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub profiles: HashMap<String, WorkloadProfile>,
+    // [impl->swdd~workload-control-interface-mode~1]
+    #[serde(default, skip_serializing_if = "ControlInterfaceMode::is_enabled")]
+    pub control_interface: ControlInterfaceMode,
+    // [impl->swdd~workload-update-strategy~1]
+    #[serde(default, skip_serializing_if = "UpdateStrategy::is_at_most_once")]
+    pub update_strategy: UpdateStrategy,
+    // [impl->swdd~workload-priority~1]
+    #[serde(default, skip_serializing_if = "is_default_priority")]
+    pub priority: u8,
+    // [impl->swdd~workload-managed-by~1]
+    // set by the server to the identity that created or last modified the workload
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub managed_by: String,
+}
+
+impl TryFrom<ank_base::Workload> for StoredWorkloadSpec {
+    type Error = String;
+
+    fn try_from(value: ank_base::Workload) -> Result<Self, String> {
+        Ok(StoredWorkloadSpec {
+            agent: value.agent,
+            tags: value.tags.into_iter().map(|x| x.into()).collect(),
+            dependencies: value
+                .dependencies
+                .into_iter()
+                .map(|(k, v)| Ok((k, v.try_into()?)))
+                .collect::<Result<HashMap<String, AddCondition>, String>>()?,
+            restart_policy: value.restart_policy.try_into()?,
+            runtime: value.runtime,
+            runtime_config: value.runtime_config,
+            unknown_state_policies: value
+                .unknown_state_policies
+                .into_iter()
+                .map(|(k, v)| Ok((k, v.try_into()?)))
+                .collect::<Result<HashMap<String, UnknownStatePolicy>, String>>()?,
+            running_for_ms: value.running_for_ms,
+            dependency_expression: value
+                .dependency_expression
+                .map(TryInto::try_into)
+                .transpose()?,
+            configs: value.configs,
+            template: value.template,
+            template_parameters: value.template_parameters,
+            enabled_if: value.enabled_if,
+            disconnect_policy: value.disconnect_policy.try_into()?,
+            log_forwarding: value
+                .log_forwarding
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<LogRoute>, String>>()?,
+            log_level: value.log_level.map(Into::into),
+            modes: value.modes,
+            pre_shutdown_timeout_ms: Some(value.pre_shutdown_timeout_ms)
+                .filter(|timeout_ms| *timeout_ms != 0),
+            dependency_timeout_ms: Some(value.dependency_timeout_ms)
+                .filter(|timeout_ms| *timeout_ms != 0),
+            schedule: Some(value.schedule).filter(|schedule| !schedule.is_empty()),
+            capture_output_bytes: Some(value.capture_output_bytes).filter(|bytes| *bytes != 0),
+            resources: value.resources.map(Into::into),
+            on_dependency_failure: value.on_dependency_failure.try_into()?,
+            expected_startup_time_ms: Some(value.expected_startup_time_ms)
+                .filter(|time_ms| *time_ms != 0),
+            control_interface_requests_per_minute: Some(
+                value.control_interface_requests_per_minute,
+            )
+            .filter(|requests| *requests != 0),
+            hooks: value.hooks.map(Into::into),
+            agent_dependencies: value.agent_dependencies,
+            profile: value.profile,
+            profiles: value
+                .profiles
+                .into_iter()
+                .map(|(name, profile)| (name, profile.into()))
+                .collect(),
+            control_interface: value.control_interface.try_into()?,
+            update_strategy: value.update_strategy.try_into()?,
+            priority: priority_from_proto(value.priority)?,
+            managed_by: value.managed_by,
+        })
+    }
+}
+
+impl From<StoredWorkloadSpec> for ank_base::Workload {
+    fn from(workload: StoredWorkloadSpec) -> Self {
+        ank_base::Workload {
+            agent: workload.agent,
+            dependencies: workload
+                .dependencies
+                .into_iter()
+                .map(|(k, v)| (k, v as i32))
+                .collect(),
+            restart_policy: workload.restart_policy as i32,
+            runtime: workload.runtime,
+            runtime_config: workload.runtime_config,
+            tags: workload.tags.into_iter().map(|x| x.into()).collect(),
+            unknown_state_policies: workload
+                .unknown_state_policies
+                .into_iter()
+                .map(|(k, v)| (k, v as i32))
+                .collect(),
+            running_for_ms: workload.running_for_ms,
+            dependency_expression: workload.dependency_expression.map(Into::into),
+            configs: workload.configs,
+            template: workload.template,
+            template_parameters: workload.template_parameters,
+            enabled_if: workload.enabled_if,
+            disconnect_policy: workload.disconnect_policy as i32,
+            log_forwarding: workload
+                .log_forwarding
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            log_level: workload.log_level.map(Into::into),
+            modes: workload.modes,
+            pre_shutdown_timeout_ms: workload.pre_shutdown_timeout_ms.unwrap_or_default(),
+            dependency_timeout_ms: workload.dependency_timeout_ms.unwrap_or_default(),
+            schedule: workload.schedule.unwrap_or_default(),
+            capture_output_bytes: workload.capture_output_bytes.unwrap_or_default(),
+            resources: workload.resources.map(Into::into),
+            on_dependency_failure: workload.on_dependency_failure as i32,
+            expected_startup_time_ms: workload.expected_startup_time_ms.unwrap_or_default(),
+            control_interface_requests_per_minute: workload
+                .control_interface_requests_per_minute
+                .unwrap_or_default(),
+            hooks: workload.hooks.map(Into::into),
+            agent_dependencies: workload.agent_dependencies,
+            profile: workload.profile,
+            profiles: workload
+                .profiles
+                .into_iter()
+                .map(|(name, profile)| (name, profile.into()))
+                .collect(),
+            control_interface: workload.control_interface as i32,
+            update_strategy: workload.update_strategy as i32,
+            priority: workload.priority.into(),
+            managed_by: workload.managed_by,
+        }
+    }
+}
+
+impl From<(String, StoredWorkloadSpec)> for WorkloadSpec {
+    fn from((name, spec): (String, StoredWorkloadSpec)) -> Self {
+        WorkloadSpec {
+            instance_name: WorkloadInstanceName::builder()
+                .workload_name(name)
+                .agent_name(spec.agent)
+                .config(&spec.runtime_config)
+                .build(),
+            tags: spec.tags,
+            dependencies: spec.dependencies,
+            restart_policy: spec.restart_policy,
+            runtime: spec.runtime,
+            runtime_config: spec.runtime_config,
+            unknown_state_policies: spec.unknown_state_policies,
+            running_for_ms: spec.running_for_ms,
+            dependency_expression: spec.dependency_expression,
+            configs: HashMap::new(),
+            enabled_if: spec.enabled_if,
+            disconnect_policy: spec.disconnect_policy,
+            log_forwarding: spec.log_forwarding,
+            log_level: spec.log_level,
+            pre_shutdown_timeout_ms: spec.pre_shutdown_timeout_ms,
+            dependency_timeout_ms: spec.dependency_timeout_ms,
+            schedule: spec.schedule,
+            capture_output_bytes: spec.capture_output_bytes,
+            resources: spec.resources,
+            on_dependency_failure: spec.on_dependency_failure,
+            expected_startup_time_ms: spec.expected_startup_time_ms,
+            control_interface_requests_per_minute: spec.control_interface_requests_per_minute,
+            hooks: spec.hooks,
+            agent_dependencies: spec.agent_dependencies,
+            control_interface: spec.control_interface,
+            update_strategy: spec.update_strategy,
+            priority: spec.priority,
+        }
+    }
+}
+
+impl From<WorkloadSpec> for StoredWorkloadSpec {
+    fn from(value: WorkloadSpec) -> Self {
+        StoredWorkloadSpec {
+            runtime: value.runtime,
+            agent: value.instance_name.agent_name().to_owned(),
+            restart_policy: value.restart_policy,
+            dependencies: value.dependencies,
+            tags: value.tags,
+            runtime_config: value.runtime_config,
+            unknown_state_policies: value.unknown_state_policies,
+            running_for_ms: value.running_for_ms,
+            dependency_expression: value.dependency_expression,
+            configs: {
+                let mut configs: Vec<String> = value.configs.into_keys().collect();
+                configs.sort();
+                configs
+            },
+            // the workload spec is always expanded
+            template: String::new(),
+            template_parameters: HashMap::new(),
+            enabled_if: value.enabled_if,
+            disconnect_policy: value.disconnect_policy,
+            log_forwarding: value.log_forwarding,
+            log_level: value.log_level,
+            // the modes are only evaluated by the server and not part of the workload spec
+            modes: Vec::new(),
+            pre_shutdown_timeout_ms: value.pre_shutdown_timeout_ms,
+            dependency_timeout_ms: value.dependency_timeout_ms,
+            schedule: value.schedule,
+            capture_output_bytes: value.capture_output_bytes,
+            resources: value.resources,
+            on_dependency_failure: value.on_dependency_failure,
+            expected_startup_time_ms: value.expected_startup_time_ms,
+            control_interface_requests_per_minute: value.control_interface_requests_per_minute,
+            hooks: value.hooks,
+            agent_dependencies: value.agent_dependencies,
+            // the workload spec is always resolved with its profile
+            profile: String::new(),
+            profiles: HashMap::new(),
+            control_interface: value.control_interface,
+            update_strategy: value.update_strategy,
+            priority: value.priority,
+            // the identity is only recorded by the server and not part of the workload spec
+            managed_by: String::new(),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(any(feature = "test_utils", test))]
+pub fn generate_test_stored_workload_spec_with_config(
+    agent: impl Into<String>,
+    runtime_name: impl Into<String>,
+    runtime_config: impl Into<String>,
+) -> crate::objects::StoredWorkloadSpec {
+    StoredWorkloadSpec {
+        agent: agent.into(),
+        dependencies: HashMap::from([
+            (String::from("workload A"), AddCondition::AddCondRunning),
+            (String::from("workload C"), AddCondition::AddCondSucceeded),
+        ]),
+        restart_policy: RestartPolicy::Always,
+        runtime: runtime_name.into(),
+        tags: vec![Tag {
+            key: "key".into(),
+            value: "value".into(),
+        }],
+        runtime_config: runtime_config.into(),
+        unknown_state_policies: HashMap::new(),
+        running_for_ms: HashMap::new(),
+        dependency_expression: None,
+        configs: vec![],
+        template: String::new(),
+        template_parameters: HashMap::new(),
+        enabled_if: String::new(),
+        disconnect_policy: DisconnectPolicy::KeepRunning,
+        log_forwarding: vec![],
+        log_level: None,
+        modes: vec![],
+        pre_shutdown_timeout_ms: None,
+        dependency_timeout_ms: None,
+        schedule: None,
+        capture_output_bytes: None,
+        resources: None,
+        on_dependency_failure: DependencyFailurePolicy::Ignore,
+        expected_startup_time_ms: None,
+        control_interface_requests_per_minute: None,
+        hooks: None,
+        agent_dependencies: vec![],
+        profile: String::new(),
+        profiles: HashMap::new(),
+        control_interface: ControlInterfaceMode::Enabled,
+        update_strategy: UpdateStrategy::AtMostOnce,
+        priority: 0,
+        managed_by: String::new(),
+    }
+}
+
+#[cfg(any(feature = "test_utils", test))]
+pub fn generate_test_stored_workload_spec(
+    agent: impl Into<String>,
+    runtime_name: impl Into<String>,
+) -> crate::objects::StoredWorkloadSpec {
+    generate_test_stored_workload_spec_with_config(
+        agent,
+        runtime_name,
+        "generalOptions: [\"--version\"]\ncommandOptions: [\"--network=host\"]\nimage: alpine:latest\ncommandArgs: [\"bash\"]\n"
+        .to_owned()
+    )
+}
+
+// [utest->swdd~common-object-serialization~1]
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use api::ank_base;
+
+    use crate::objects::{
+        generate_test_stored_workload_spec, generate_test_workload_spec, AddCondition,
+        ControlInterfaceMode, DependencyExpression, DependencyFailurePolicy, DisconnectPolicy,
+        StoredWorkloadSpec, UnknownStatePolicy, UpdateStrategy, WorkloadHooks,
+    };
+    use crate::test_utils::generate_test_proto_workload;
+
+    // [utest->swdd~workload-unknown-state-policies-for-dependencies~1]
+    #[test]
+    fn utest_converts_unknown_state_policies_to_and_from_proto() {
+        let mut stored_workload_spec = generate_test_stored_workload_spec("agent", "runtime");
+        stored_workload_spec.unknown_state_policies = HashMap::from([(
+            "workload A".to_string(),
+            UnknownStatePolicy::UnknownStateLastKnown,
+        )]);
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.unknown_state_policies = HashMap::from([(
+            "workload A".to_string(),
+            ank_base::UnknownStatePolicy::UnknownStateLastKnown as i32,
+        )]);
+
+        assert_eq!(
+            ank_base::Workload::from(stored_workload_spec.clone()),
+            proto_workload
+        );
+        assert_eq!(
+            StoredWorkloadSpec::try_from(proto_workload),
+            Ok(stored_workload_spec)
+        );
+    }
+
+    // [utest->swdd~workload-unknown-state-policies-for-dependencies~1]
+    #[test]
+    fn utest_converts_from_proto_fails_on_invalid_unknown_state_policy() {
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.unknown_state_policies = HashMap::from([("workload A".to_string(), -1)]);
+
+        assert!(StoredWorkloadSpec::try_from(proto_workload).is_err());
+    }
+
+    // [utest->swdd~workload-running-for-add-condition~1]
+    #[test]
+    fn utest_converts_running_for_to_and_from_proto() {
+        let mut stored_workload_spec = generate_test_stored_workload_spec("agent", "runtime");
+        stored_workload_spec.dependencies =
+            HashMap::from([("workload A".to_string(), AddCondition::AddCondRunningFor)]);
+        stored_workload_spec.running_for_ms = HashMap::from([("workload A".to_string(), 30000)]);
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.dependencies = HashMap::from([(
+            "workload A".to_string(),
+            ank_base::AddCondition::AddCondRunningFor as i32,
+        )]);
+        proto_workload.running_for_ms = HashMap::from([("workload A".to_string(), 30000)]);
+
+        assert_eq!(
+            ank_base::Workload::from(stored_workload_spec.clone()),
+            proto_workload
+        );
+        assert_eq!(
+            StoredWorkloadSpec::try_from(proto_workload),
+            Ok(stored_workload_spec)
+        );
+    }
+
+    // [utest->swdd~workload-dependency-expression~1]
+    #[test]
+    fn utest_converts_dependency_expression_to_and_from_proto() {
+        let expression = DependencyExpression::Or {
+            or: vec![DependencyExpression::Condition {
+                workload: "workload B".to_string(),
+                condition: AddCondition::AddCondRunning,
+            }],
+        };
+        let mut stored_workload_spec = generate_test_stored_workload_spec("agent", "runtime");
+        stored_workload_spec.dependency_expression = Some(expression.clone());
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.dependency_expression = Some(expression.into());
+
+        assert_eq!(
+            ank_base::Workload::from(stored_workload_spec.clone()),
+            proto_workload
+        );
+        assert_eq!(
+            StoredWorkloadSpec::try_from(proto_workload),
+            Ok(stored_workload_spec)
+        );
+    }
+
+    // [utest->swdd~workload-dependency-expression~1]
+    #[test]
+    fn utest_converts_from_proto_fails_on_invalid_dependency_expression() {
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.dependency_expression = Some(ank_base::DependencyExpression::default());
+
+        assert!(StoredWorkloadSpec::try_from(proto_workload).is_err());
+    }
+
+    // [utest->swdd~workload-disconnect-policy~1]
+    #[test]
+    fn utest_converts_disconnect_policy_to_and_from_proto() {
+        let mut stored_workload_spec = generate_test_stored_workload_spec("agent", "runtime");
+        stored_workload_spec.disconnect_policy = DisconnectPolicy::Fallback;
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.disconnect_policy = ank_base::DisconnectPolicy::Fallback as i32;
+
+        assert_eq!(
+            ank_base::Workload::from(stored_workload_spec.clone()),
+            proto_workload
+        );
+        assert_eq!(
+            StoredWorkloadSpec::try_from(proto_workload),
+            Ok(stored_workload_spec)
+        );
+    }
+
+    // [utest->swdd~workload-disconnect-policy~1]
+    #[test]
+    fn utest_converts_from_proto_fails_on_invalid_disconnect_policy() {
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.disconnect_policy = -1;
+
+        assert!(StoredWorkloadSpec::try_from(proto_workload).is_err());
+    }
+
+    // [utest->swdd~workload-control-interface-mode~1]
+    #[test]
+    fn utest_converts_control_interface_mode_to_and_from_proto() {
+        let mut stored_workload_spec = generate_test_stored_workload_spec("agent", "runtime");
+        stored_workload_spec.control_interface = ControlInterfaceMode::Disabled;
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.control_interface = ank_base::ControlInterfaceMode::Disabled as i32;
+
+        assert_eq!(
+            ank_base::Workload::from(stored_workload_spec.clone()),
+            proto_workload
+        );
+        assert_eq!(
+            StoredWorkloadSpec::try_from(proto_workload),
+            Ok(stored_workload_spec)
+        );
+    }
+
+    // [utest->swdd~workload-control-interface-mode~1]
+    #[test]
+    fn utest_control_interface_mode_is_parsed_lowercase_and_defaults_to_enabled() {
+        let stored_workload_spec: StoredWorkloadSpec = serde_yaml::from_str(
+            "agent: agent\nruntime: runtime\nruntimeConfig: ''\ncontrolInterface: disabled\n",
+        )
+        .unwrap();
+        assert_eq!(
+            stored_workload_spec.control_interface,
+            ControlInterfaceMode::Disabled
+        );
+
+        let mut default_workload_spec = generate_test_stored_workload_spec("agent", "runtime");
+        let serialized = serde_yaml::to_string(&default_workload_spec).unwrap();
+        assert!(!serialized.contains("controlInterface"));
+        default_workload_spec.control_interface = ControlInterfaceMode::Disabled;
+        let serialized = serde_yaml::to_string(&default_workload_spec).unwrap();
+        assert!(serialized.contains("controlInterface: disabled"));
+    }
+
+    // [utest->swdd~workload-dependency-failure-policy~1]
+    #[test]
+    fn utest_converts_dependency_failure_policy_to_and_from_proto_and_yaml() {
+        let mut stored_workload_spec = generate_test_stored_workload_spec("agent", "runtime");
+        stored_workload_spec.on_dependency_failure = DependencyFailurePolicy::Restart;
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.on_dependency_failure = ank_base::DependencyFailurePolicy::Restart as i32;
+
+        assert_eq!(
+            ank_base::Workload::from(stored_workload_spec.clone()),
+            proto_workload
+        );
+        assert_eq!(
+            StoredWorkloadSpec::try_from(proto_workload),
+            Ok(stored_workload_spec)
+        );
+
+        let parsed_workload_spec: StoredWorkloadSpec = serde_yaml::from_str(
+            "agent: agent\nruntime: runtime\nruntimeConfig: ''\nonDependencyFailure: stop\n",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed_workload_spec.on_dependency_failure,
+            DependencyFailurePolicy::Stop
+        );
+        let serialized =
+            serde_yaml::to_string(&generate_test_stored_workload_spec("agent", "runtime")).unwrap();
+        assert!(!serialized.contains("onDependencyFailure"));
+    }
+
+    // [utest->swdd~workload-expected-startup-time~1]
+    #[test]
+    fn utest_converts_expected_startup_time_to_and_from_proto() {
+        let mut stored_workload_spec = generate_test_stored_workload_spec("agent", "runtime");
+        stored_workload_spec.expected_startup_time_ms = Some(5000);
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.expected_startup_time_ms = 5000;
+
+        assert_eq!(
+            ank_base::Workload::from(stored_workload_spec.clone()),
+            proto_workload
+        );
+        assert_eq!(
+            StoredWorkloadSpec::try_from(proto_workload),
+            Ok(stored_workload_spec)
+        );
+
+        // zero means no expectation
+        assert_eq!(
+            StoredWorkloadSpec::try_from(generate_test_proto_workload())
+                .map(|workload_spec| workload_spec.expected_startup_time_ms),
+            Ok(None)
+        );
+    }
+
+    // [utest->swdd~workload-control-interface-request-quota~1]
+    #[test]
+    fn utest_converts_control_interface_request_quota_to_and_from_proto() {
+        let mut stored_workload_spec = generate_test_stored_workload_spec("agent", "runtime");
+        stored_workload_spec.control_interface_requests_per_minute = Some(60);
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.control_interface_requests_per_minute = 60;
+
+        assert_eq!(
+            ank_base::Workload::from(stored_workload_spec.clone()),
+            proto_workload
+        );
+        assert_eq!(
+            StoredWorkloadSpec::try_from(proto_workload),
+            Ok(stored_workload_spec)
+        );
+
+        // zero means no quota
+        assert_eq!(
+            StoredWorkloadSpec::try_from(generate_test_proto_workload())
+                .map(|workload_spec| workload_spec.control_interface_requests_per_minute),
+            Ok(None)
+        );
+    }
+
+    // [utest->swdd~workload-hooks~1]
+    #[test]
+    fn utest_converts_hooks_to_and_from_proto() {
+        let mut stored_workload_spec = generate_test_stored_workload_spec("agent", "runtime");
+        stored_workload_spec.hooks = Some(WorkloadHooks {
+            pre_create: Some("mount /data".to_string()),
+            ..Default::default()
+        });
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.hooks = Some(ank_base::WorkloadHooks {
+            pre_create: "mount /data".to_string(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            ank_base::Workload::from(stored_workload_spec.clone()),
+            proto_workload
+        );
+        assert_eq!(
+            StoredWorkloadSpec::try_from(proto_workload),
+            Ok(stored_workload_spec)
+        );
+    }
+
+    // [utest->swdd~workload-agent-dependencies~1]
+    #[test]
+    fn utest_converts_agent_dependencies_to_and_from_proto_and_yaml() {
+        let mut stored_workload_spec = generate_test_stored_workload_spec("agent", "runtime");
+        stored_workload_spec.agent_dependencies = vec!["agent_B".to_string()];
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.agent_dependencies = vec!["agent_B".to_string()];
+
+        assert_eq!(
+            ank_base::Workload::from(stored_workload_spec.clone()),
+            proto_workload
+        );
+        assert_eq!(
+            StoredWorkloadSpec::try_from(proto_workload),
+            Ok(stored_workload_spec)
+        );
+
+        let parsed_workload_spec: StoredWorkloadSpec = serde_yaml::from_str(
+            "agent: agent\nruntime: runtime\nruntimeConfig: ''\nagentDependencies: [agent_B]\n",
+        )
+        .unwrap();
+        assert_eq!(parsed_workload_spec.agent_dependencies, vec!["agent_B"]);
+        let serialized =
+            serde_yaml::to_string(&generate_test_stored_workload_spec("agent", "runtime")).unwrap();
+        assert!(!serialized.contains("agentDependencies"));
+    }
+
+    // [utest->swdd~workload-update-strategy~1]
+    #[test]
+    fn utest_converts_update_strategy_to_and_from_proto_and_yaml() {
+        let mut stored_workload_spec = generate_test_stored_workload_spec("agent", "runtime");
+        stored_workload_spec.update_strategy = UpdateStrategy::AtLeastOnce;
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.update_strategy = ank_base::UpdateStrategy::AtLeastOnce as i32;
+
+        assert_eq!(
+            ank_base::Workload::from(stored_workload_spec.clone()),
+            proto_workload
+        );
+        assert_eq!(
+            StoredWorkloadSpec::try_from(proto_workload),
+            Ok(stored_workload_spec)
+        );
+
+        let parsed_workload_spec: StoredWorkloadSpec = serde_yaml::from_str(
+            "agent: agent\nruntime: runtime\nruntimeConfig: ''\nupdateStrategy: AT_LEAST_ONCE\n",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed_workload_spec.update_strategy,
+            UpdateStrategy::AtLeastOnce
+        );
+    }
+
+    // [utest->swdd~workload-priority~1]
+    #[test]
+    fn utest_converts_priority_to_and_from_proto() {
+        let mut stored_workload_spec = generate_test_stored_workload_spec("agent", "runtime");
+        stored_workload_spec.priority = 200;
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.priority = 200;
+
+        assert_eq!(
+            ank_base::Workload::from(stored_workload_spec.clone()),
+            proto_workload
+        );
+        assert_eq!(
+            StoredWorkloadSpec::try_from(proto_workload.clone()),
+            Ok(stored_workload_spec)
+        );
+
+        proto_workload.priority = 256;
+        assert!(StoredWorkloadSpec::try_from(proto_workload).is_err());
+    }
+
+    // [utest->swdd~workload-references-config-objects~1]
+    #[test]
+    fn utest_converts_config_references_to_and_from_proto() {
+        let mut stored_workload_spec = generate_test_stored_workload_spec("agent", "runtime");
+        stored_workload_spec.configs = vec!["config_1".to_string()];
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.configs = vec!["config_1".to_string()];
+
+        assert_eq!(
+            ank_base::Workload::from(stored_workload_spec.clone()),
+            proto_workload
+        );
+        assert_eq!(
+            StoredWorkloadSpec::try_from(proto_workload),
+            Ok(stored_workload_spec)
+        );
+    }
+
+    // [utest->swdd~workload-references-config-objects~1]
+    #[test]
+    fn utest_keeps_config_references_of_workload_spec() {
+        let mut workload_spec = generate_test_workload_spec();
+        workload_spec.configs = HashMap::from([
+            ("config_2".to_string(), HashMap::new()),
+            ("config_1".to_string(), HashMap::new()),
+        ]);
+
+        let stored_workload_spec = StoredWorkloadSpec::from(workload_spec);
+
+        assert_eq!(
+            stored_workload_spec.configs,
+            vec!["config_1".to_string(), "config_2".to_string()]
+        );
+    }
+
+    // [utest->swdd~workload-references-workload-template~1]
+    #[test]
+    fn utest_converts_template_reference_to_and_from_proto() {
+        let mut stored_workload_spec = generate_test_stored_workload_spec("agent", "");
+        stored_workload_spec.runtime_config = String::new();
+        stored_workload_spec.template = "template_1".to_string();
+        stored_workload_spec.template_parameters =
+            HashMap::from([("tag".to_string(), "1.0".to_string())]);
+        let mut proto_workload = generate_test_proto_workload();
+        proto_workload.runtime = String::new();
+        proto_workload.runtime_config = String::new();
+        proto_workload.template = "template_1".to_string();
+        proto_workload.template_parameters =
+            HashMap::from([("tag".to_string(), "1.0".to_string())]);
+
+        assert_eq!(
+            ank_base::Workload::from(stored_workload_spec.clone()),
+            proto_workload
+        );
+        assert_eq!(
+            StoredWorkloadSpec::try_from(proto_workload),
+            Ok(stored_workload_spec)
+        );
+    }
+
+    // [utest->swdd~workload-references-workload-template~1]
+    #[test]
+    fn utest_parses_workload_with_template_reference_without_runtime() {
+        let stored_workload_spec: StoredWorkloadSpec = serde_yaml::from_str(
+            "agent: agent_A\ntemplate: sensor\ntemplateParameters:\n  tag: \"1.0\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(stored_workload_spec.template, "sensor");
+        assert_eq!(
+            stored_workload_spec.template_parameters,
+            HashMap::from([("tag".to_string(), "1.0".to_string())])
+        );
+        assert!(stored_workload_spec.runtime.is_empty());
+        assert!(stored_workload_spec.runtime_config.is_empty());
+    }
+}
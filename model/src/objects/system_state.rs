@@ -0,0 +1,500 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+use crate::serialize_to_ordered_map;
+
+use super::{StoredWorkloadSpec, WorkloadState};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub enum AgentConnectionStatus {
+    #[default]
+    Connected = 0,
+    Disconnected = 1,
+}
+
+impl TryFrom<i32> for AgentConnectionStatus {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == AgentConnectionStatus::Connected as i32 => {
+                Ok(AgentConnectionStatus::Connected)
+            }
+            x if x == AgentConnectionStatus::Disconnected as i32 => {
+                Ok(AgentConnectionStatus::Disconnected)
+            }
+            _ => Err(format!(
+                "Received an unknown value '{value}' as AgentConnectionStatus."
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for AgentConnectionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AgentConnectionStatus::Connected => write!(f, "Connected"),
+            AgentConnectionStatus::Disconnected => write!(f, "Disconnected"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AgentInfo {
+    pub agent_name: String,
+    pub version: String,
+    pub rollout_group: Option<String>,
+    pub connection_status: AgentConnectionStatus,
+    pub last_heartbeat: u64,
+    pub runtimes: Vec<String>,
+    #[serde(
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub attributes: HashMap<String, String>,
+    pub last_acknowledged_update: u64,
+    #[serde(
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub local_workloads: HashMap<String, StoredWorkloadSpec>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resources: Option<AgentResources>,
+}
+
+impl From<AgentInfo> for ank_base::AgentInfo {
+    fn from(item: AgentInfo) -> Self {
+        ank_base::AgentInfo {
+            agent_name: item.agent_name,
+            version: item.version,
+            rollout_group: item.rollout_group.unwrap_or_default(),
+            connection_status: item.connection_status as i32,
+            last_heartbeat: item.last_heartbeat,
+            runtimes: item.runtimes,
+            attributes: item.attributes,
+            last_acknowledged_update: item.last_acknowledged_update,
+            local_workloads: item
+                .local_workloads
+                .into_iter()
+                .map(|(name, workload)| (name, workload.into()))
+                .collect(),
+            resources: item.resources.map(|resources| resources.into()),
+        }
+    }
+}
+
+impl TryFrom<ank_base::AgentInfo> for AgentInfo {
+    type Error = String;
+
+    fn try_from(item: ank_base::AgentInfo) -> Result<Self, Self::Error> {
+        Ok(AgentInfo {
+            agent_name: item.agent_name,
+            version: item.version,
+            rollout_group: Some(item.rollout_group).filter(|group| !group.is_empty()),
+            connection_status: item.connection_status.try_into()?,
+            last_heartbeat: item.last_heartbeat,
+            runtimes: item.runtimes,
+            attributes: item.attributes,
+            last_acknowledged_update: item.last_acknowledged_update,
+            local_workloads: item
+                .local_workloads
+                .into_iter()
+                .map(|(name, workload)| Ok((name, workload.try_into()?)))
+                .collect::<Result<_, String>>()?,
+            resources: item.resources.map(|resources| resources.into()),
+        })
+    }
+}
+
+// [impl->swdd~server-provides-agent-resources~1]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AgentResources {
+    pub free_cpu_millis: u64,
+    pub free_memory_bytes: u64,
+}
+
+impl From<AgentResources> for ank_base::AgentResources {
+    fn from(item: AgentResources) -> Self {
+        ank_base::AgentResources {
+            free_cpu_millis: item.free_cpu_millis,
+            free_memory_bytes: item.free_memory_bytes,
+        }
+    }
+}
+
+impl From<ank_base::AgentResources> for AgentResources {
+    fn from(item: ank_base::AgentResources) -> Self {
+        AgentResources {
+            free_cpu_millis: item.free_cpu_millis,
+            free_memory_bytes: item.free_memory_bytes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RequestLaneInfo {
+    pub lane: String,
+    pub depth: u64,
+    pub max_depth: u64,
+    pub processed: u64,
+}
+
+impl From<RequestLaneInfo> for ank_base::RequestLaneInfo {
+    fn from(item: RequestLaneInfo) -> Self {
+        ank_base::RequestLaneInfo {
+            lane: item.lane,
+            depth: item.depth,
+            max_depth: item.max_depth,
+            processed: item.processed,
+        }
+    }
+}
+
+impl From<ank_base::RequestLaneInfo> for RequestLaneInfo {
+    fn from(item: ank_base::RequestLaneInfo) -> Self {
+        RequestLaneInfo {
+            lane: item.lane,
+            depth: item.depth,
+            max_depth: item.max_depth,
+            processed: item.processed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ServerInfo {
+    pub version: String,
+    pub uptime: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub request_lanes: Vec<RequestLaneInfo>,
+}
+
+impl From<ServerInfo> for ank_base::ServerInfo {
+    fn from(item: ServerInfo) -> Self {
+        ank_base::ServerInfo {
+            version: item.version,
+            uptime: item.uptime,
+            request_lanes: item.request_lanes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ank_base::ServerInfo> for ServerInfo {
+    fn from(item: ank_base::ServerInfo) -> Self {
+        ServerInfo {
+            version: item.version,
+            uptime: item.uptime,
+            request_lanes: item.request_lanes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+// [impl->swdd~server-retains-terminated-workload-states~1]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TerminatedWorkload {
+    pub final_state: WorkloadState,
+    pub removed_at: u64,
+}
+
+impl From<TerminatedWorkload> for ank_base::TerminatedWorkload {
+    fn from(item: TerminatedWorkload) -> Self {
+        ank_base::TerminatedWorkload {
+            final_state: Some(item.final_state.into()),
+            removed_at: item.removed_at,
+        }
+    }
+}
+
+impl TryFrom<ank_base::TerminatedWorkload> for TerminatedWorkload {
+    type Error = String;
+
+    fn try_from(item: ank_base::TerminatedWorkload) -> Result<Self, Self::Error> {
+        Ok(TerminatedWorkload {
+            final_state: item
+                .final_state
+                .filter(|final_state| final_state.instance_name.is_some())
+                .ok_or("Received a terminated workload without final state.")?
+                .into(),
+            removed_at: item.removed_at,
+        })
+    }
+}
+
+// [impl->swdd~server-provides-control-interface-metrics~1]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ControlInterfaceMetrics {
+    pub agent_name: String,
+    pub workload_name: String,
+    pub forwarded_requests: u64,
+    pub rejected_requests: u64,
+    pub mean_latency_ms: u64,
+    pub max_latency_ms: u64,
+}
+
+impl From<ControlInterfaceMetrics> for ank_base::ControlInterfaceMetrics {
+    fn from(item: ControlInterfaceMetrics) -> Self {
+        ank_base::ControlInterfaceMetrics {
+            agent_name: item.agent_name,
+            workload_name: item.workload_name,
+            forwarded_requests: item.forwarded_requests,
+            rejected_requests: item.rejected_requests,
+            mean_latency_ms: item.mean_latency_ms,
+            max_latency_ms: item.max_latency_ms,
+        }
+    }
+}
+
+impl From<ank_base::ControlInterfaceMetrics> for ControlInterfaceMetrics {
+    fn from(item: ank_base::ControlInterfaceMetrics) -> Self {
+        ControlInterfaceMetrics {
+            agent_name: item.agent_name,
+            workload_name: item.workload_name,
+            forwarded_requests: item.forwarded_requests,
+            rejected_requests: item.rejected_requests,
+            mean_latency_ms: item.mean_latency_ms,
+            max_latency_ms: item.max_latency_ms,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SystemState {
+    pub server: ServerInfo,
+    pub agents: Vec<AgentInfo>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub terminated_workloads: Vec<TerminatedWorkload>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub control_interface_metrics: Vec<ControlInterfaceMetrics>,
+}
+
+impl SystemState {
+    pub fn is_empty(&self) -> bool {
+        self == &SystemState::default()
+    }
+}
+
+impl From<SystemState> for ank_base::SystemState {
+    fn from(item: SystemState) -> Self {
+        ank_base::SystemState {
+            server: Some(item.server.into()),
+            agents: item.agents.into_iter().map(|x| x.into()).collect(),
+            terminated_workloads: item
+                .terminated_workloads
+                .into_iter()
+                .map(|x| x.into())
+                .collect(),
+            control_interface_metrics: item
+                .control_interface_metrics
+                .into_iter()
+                .map(|x| x.into())
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<ank_base::SystemState> for SystemState {
+    type Error = String;
+
+    fn try_from(item: ank_base::SystemState) -> Result<Self, Self::Error> {
+        Ok(SystemState {
+            server: item.server.unwrap_or_default().into(),
+            agents: item
+                .agents
+                .into_iter()
+                .map(|x| x.try_into())
+                .collect::<Result<Vec<AgentInfo>, String>>()?,
+            terminated_workloads: item
+                .terminated_workloads
+                .into_iter()
+                .map(|x| x.try_into())
+                .collect::<Result<Vec<TerminatedWorkload>, String>>()?,
+            control_interface_metrics: item
+                .control_interface_metrics
+                .into_iter()
+                .map(|x| x.into())
+                .collect(),
+        })
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+// [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+// [utest->swdd~common-object-representation~1]
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::objects::*;
+    use api::ank_base;
+
+    fn generate_test_system_state() -> SystemState {
+        SystemState {
+            server: ServerInfo {
+                version: "0.4.0".to_string(),
+                uptime: 42,
+                request_lanes: vec![RequestLaneInfo {
+                    lane: "reads".to_string(),
+                    depth: 1,
+                    max_depth: 3,
+                    processed: 12,
+                }],
+            },
+            agents: vec![AgentInfo {
+                agent_name: "agent_A".to_string(),
+                version: "0.4.0".to_string(),
+                rollout_group: None,
+                connection_status: AgentConnectionStatus::Disconnected,
+                last_heartbeat: 1000,
+                runtimes: vec!["podman".to_string()],
+                attributes: HashMap::from([("camera".to_string(), "true".to_string())]),
+                last_acknowledged_update: 7,
+                local_workloads: HashMap::from([(
+                    "watchdog".to_string(),
+                    generate_test_stored_workload_spec("agent_A", "podman"),
+                )]),
+                resources: Some(AgentResources {
+                    free_cpu_millis: 1500,
+                    free_memory_bytes: 4096,
+                }),
+            }],
+            terminated_workloads: vec![TerminatedWorkload {
+                final_state: generate_test_workload_state_with_agent(
+                    "sleepy",
+                    "agent_A",
+                    ExecutionState::succeeded(),
+                ),
+                removed_at: 2000,
+            }],
+            control_interface_metrics: vec![ControlInterfaceMetrics {
+                agent_name: "agent_A".to_string(),
+                workload_name: "watchdog".to_string(),
+                forwarded_requests: 10,
+                rejected_requests: 2,
+                mean_latency_ms: 5,
+                max_latency_ms: 20,
+            }],
+        }
+    }
+
+    fn generate_test_proto_system_state() -> ank_base::SystemState {
+        ank_base::SystemState {
+            server: Some(ank_base::ServerInfo {
+                version: "0.4.0".to_string(),
+                uptime: 42,
+                request_lanes: vec![ank_base::RequestLaneInfo {
+                    lane: "reads".to_string(),
+                    depth: 1,
+                    max_depth: 3,
+                    processed: 12,
+                }],
+            }),
+            agents: vec![ank_base::AgentInfo {
+                agent_name: "agent_A".to_string(),
+                version: "0.4.0".to_string(),
+                rollout_group: "".to_string(),
+                connection_status: ank_base::AgentConnectionStatus::Disconnected as i32,
+                last_heartbeat: 1000,
+                runtimes: vec!["podman".to_string()],
+                attributes: HashMap::from([("camera".to_string(), "true".to_string())]),
+                last_acknowledged_update: 7,
+                local_workloads: HashMap::from([(
+                    "watchdog".to_string(),
+                    generate_test_stored_workload_spec("agent_A", "podman").into(),
+                )]),
+                resources: Some(ank_base::AgentResources {
+                    free_cpu_millis: 1500,
+                    free_memory_bytes: 4096,
+                }),
+            }],
+            terminated_workloads: vec![ank_base::TerminatedWorkload {
+                final_state: Some(
+                    generate_test_workload_state_with_agent(
+                        "sleepy",
+                        "agent_A",
+                        ExecutionState::succeeded(),
+                    )
+                    .into(),
+                ),
+                removed_at: 2000,
+            }],
+            control_interface_metrics: vec![ank_base::ControlInterfaceMetrics {
+                agent_name: "agent_A".to_string(),
+                workload_name: "watchdog".to_string(),
+                forwarded_requests: 10,
+                rejected_requests: 2,
+                mean_latency_ms: 5,
+                max_latency_ms: 20,
+            }],
+        }
+    }
+
+    #[test]
+    fn utest_converts_to_proto_system_state() {
+        assert_eq!(
+            ank_base::SystemState::from(generate_test_system_state()),
+            generate_test_proto_system_state()
+        );
+    }
+
+    #[test]
+    fn utest_converts_to_ankaios_system_state() {
+        assert_eq!(
+            SystemState::try_from(generate_test_proto_system_state()),
+            Ok(generate_test_system_state())
+        );
+    }
+
+    #[test]
+    fn utest_converts_to_ankaios_system_state_fails_on_unknown_connection_status() {
+        let mut proto_system_state = generate_test_proto_system_state();
+        proto_system_state.agents[0].connection_status = 42;
+
+        assert!(SystemState::try_from(proto_system_state).is_err());
+    }
+
+    // [utest->swdd~server-retains-terminated-workload-states~1]
+    #[test]
+    fn utest_converts_to_ankaios_system_state_fails_on_terminated_workload_without_state() {
+        let mut proto_system_state = generate_test_proto_system_state();
+        proto_system_state.terminated_workloads[0].final_state = None;
+
+        assert!(SystemState::try_from(proto_system_state).is_err());
+    }
+
+    #[test]
+    fn utest_system_state_is_empty() {
+        assert!(SystemState::default().is_empty());
+        assert!(!generate_test_system_state().is_empty());
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use api::ank_base;
+use serde::{Deserialize, Serialize};
+
+use crate::serialize_to_ordered_map;
+use crate::objects::StoredWorkloadSpec;
+
+// [impl->swdd~common-workload-groups-in-state~1]
+#[derive(Debug, Serialize, Default, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadGroup {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workloads: Vec<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub selector: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stopped: bool,
+}
+
+impl WorkloadGroup {
+    // [impl->swdd~common-resolves-members-of-workload-groups~1]
+    // An empty selector selects no workloads, otherwise a group without explicit members
+    // would contain all workloads.
+    pub fn contains(&self, workload_name: &str, workload: &StoredWorkloadSpec) -> bool {
+        self.workloads.iter().any(|member| member == workload_name)
+            || (!self.selector.is_empty()
+                && self.selector.iter().all(|(key, value)| {
+                    workload
+                        .tags
+                        .iter()
+                        .any(|tag| tag.key == *key && tag.value == *value)
+                }))
+    }
+}
+
+impl From<WorkloadGroup> for ank_base::WorkloadGroup {
+    fn from(item: WorkloadGroup) -> Self {
+        ank_base::WorkloadGroup {
+            workloads: item.workloads,
+            selector: item.selector,
+            stopped: item.stopped,
+        }
+    }
+}
+
+impl From<ank_base::WorkloadGroup> for WorkloadGroup {
+    fn from(item: ank_base::WorkloadGroup) -> Self {
+        WorkloadGroup {
+            workloads: item.workloads,
+            selector: item.selector,
+            stopped: item.stopped,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::WorkloadGroup;
+    use crate::objects::{generate_test_stored_workload_spec, Tag};
+
+    // [utest->swdd~common-resolves-members-of-workload-groups~1]
+    #[test]
+    fn utest_workload_group_contains_explicit_and_selected_workloads() {
+        let group = WorkloadGroup {
+            workloads: vec!["explicit".to_string()],
+            selector: HashMap::from([("tier".to_string(), "backend".to_string())]),
+            ..Default::default()
+        };
+        let mut selected_workload = generate_test_stored_workload_spec("agent", "runtime");
+        selected_workload.tags = vec![
+            Tag {
+                key: "tier".to_string(),
+                value: "backend".to_string(),
+            },
+            Tag {
+                key: "owner".to_string(),
+                value: "team".to_string(),
+            },
+        ];
+        let mut other_workload = generate_test_stored_workload_spec("agent", "runtime");
+        other_workload.tags = vec![Tag {
+            key: "tier".to_string(),
+            value: "frontend".to_string(),
+        }];
+
+        assert!(group.contains("explicit", &other_workload));
+        assert!(group.contains("selected", &selected_workload));
+        assert!(!group.contains("other", &other_workload));
+    }
+
+    // [utest->swdd~common-resolves-members-of-workload-groups~1]
+    #[test]
+    fn utest_workload_group_with_empty_selector_selects_no_workloads() {
+        let group = WorkloadGroup::default();
+        let workload = generate_test_stored_workload_spec("agent", "runtime");
+
+        assert!(!group.contains("workload", &workload));
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+// The commands the agent executes with 'sh -c' around the operations of the workload.
+// [impl->swdd~workload-hooks~1]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WorkloadHooks {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_create: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_create: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_delete: Option<String>,
+}
+
+impl From<ank_base::WorkloadHooks> for WorkloadHooks {
+    fn from(item: ank_base::WorkloadHooks) -> Self {
+        WorkloadHooks {
+            pre_create: Some(item.pre_create).filter(|command| !command.is_empty()),
+            post_create: Some(item.post_create).filter(|command| !command.is_empty()),
+            pre_delete: Some(item.pre_delete).filter(|command| !command.is_empty()),
+        }
+    }
+}
+
+impl From<WorkloadHooks> for ank_base::WorkloadHooks {
+    fn from(item: WorkloadHooks) -> Self {
+        ank_base::WorkloadHooks {
+            pre_create: item.pre_create.unwrap_or_default(),
+            post_create: item.post_create.unwrap_or_default(),
+            pre_delete: item.pre_delete.unwrap_or_default(),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use api::ank_base;
+
+    use super::WorkloadHooks;
+
+    // [utest->swdd~workload-hooks~1]
+    #[test]
+    fn utest_workload_hooks_converts_to_and_from_proto() {
+        let hooks = WorkloadHooks {
+            pre_create: Some("mount /dev/mapper/data /data".to_string()),
+            post_create: None,
+            pre_delete: Some("umount /data".to_string()),
+        };
+        let proto_hooks = ank_base::WorkloadHooks {
+            pre_create: "mount /dev/mapper/data /data".to_string(),
+            post_create: String::new(),
+            pre_delete: "umount /data".to_string(),
+        };
+
+        assert_eq!(ank_base::WorkloadHooks::from(hooks.clone()), proto_hooks);
+        assert_eq!(WorkloadHooks::from(proto_hooks), hooks);
+    }
+
+    // [utest->swdd~workload-hooks~1]
+    #[test]
+    fn utest_workload_hooks_deserializes_from_yaml() {
+        let hooks: WorkloadHooks =
+            serde_yaml::from_str("preCreate: cryptsetup open /dev/sda3 data\npostCreate: 'true'")
+                .unwrap();
+
+        assert_eq!(
+            hooks,
+            WorkloadHooks {
+                pre_create: Some("cryptsetup open /dev/sda3 data".to_string()),
+                post_create: Some("true".to_string()),
+                pre_delete: None,
+            }
+        );
+    }
+}
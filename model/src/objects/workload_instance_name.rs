@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     fmt::Display,
     path::{Path, PathBuf},
 };
@@ -12,9 +13,68 @@ pub trait ConfigHash {
     fn hash_config(&self) -> String;
 }
 
+// [impl->swdd~common-hashes-canonical-runtime-config~1]
 impl ConfigHash for String {
     fn hash_config(&self) -> String {
-        sha256::digest(self.as_str())
+        sha256::digest(canonicalize_runtime_config(self).as_ref())
+    }
+}
+
+// Brings a runtime config into a form in which semantically identical configs are equal:
+// the keys are sorted, formatting and comments are dropped and entries with a null or empty
+// value are omitted. Configs which are no YAML mapping or sequence are kept as they are.
+pub fn canonicalize_runtime_config(runtime_config: &str) -> Cow<'_, str> {
+    match serde_yaml::from_str::<serde_yaml::Value>(runtime_config) {
+        Ok(value @ (serde_yaml::Value::Mapping(_) | serde_yaml::Value::Sequence(_))) => {
+            serde_json::to_string(&canonical_value(value))
+                .map_or(Cow::Borrowed(runtime_config), Cow::Owned)
+        }
+        _ => Cow::Borrowed(runtime_config),
+    }
+}
+
+fn canonical_value(value: serde_yaml::Value) -> serde_json::Value {
+    use serde_json::Value as JsonValue;
+    match value {
+        serde_yaml::Value::Null => JsonValue::Null,
+        serde_yaml::Value::Bool(value) => JsonValue::Bool(value),
+        serde_yaml::Value::Number(value) => {
+            serde_json::to_value(value).unwrap_or(JsonValue::Null)
+        }
+        serde_yaml::Value::String(value) => JsonValue::String(value),
+        serde_yaml::Value::Sequence(values) => {
+            JsonValue::Array(values.into_iter().map(canonical_value).collect())
+        }
+        serde_yaml::Value::Mapping(mapping) => {
+            let mut entries: Vec<(String, JsonValue)> = mapping
+                .into_iter()
+                .map(|(key, value)| (canonical_key(key), canonical_value(value)))
+                .filter(|(_, value)| !is_empty(value))
+                .collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            JsonValue::Object(entries.into_iter().collect())
+        }
+        serde_yaml::Value::Tagged(tagged) => JsonValue::Object(
+            [(tagged.tag.to_string(), canonical_value(tagged.value))]
+                .into_iter()
+                .collect(),
+        ),
+    }
+}
+
+fn canonical_key(key: serde_yaml::Value) -> String {
+    match key {
+        serde_yaml::Value::String(key) => key,
+        key => canonical_value(key).to_string(),
+    }
+}
+
+fn is_empty(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => true,
+        serde_json::Value::Array(values) => values.is_empty(),
+        serde_json::Value::Object(entries) => entries.is_empty(),
+        _ => false,
     }
 }
 
@@ -191,12 +251,16 @@ pub fn generate_test_workload_instance_name(name: impl Into<String>) -> Workload
 
 #[cfg(test)]
 mod tests {
-    use super::WorkloadInstanceName;
+    use super::{canonicalize_runtime_config, ConfigHash, WorkloadInstanceName};
 
     const AGENT_NAME: &str = "agent";
     const WORKLOAD_NAME: &str = "workload";
     const CONFIG: &str = "config";
     const EXPECTED_HASH: &str = "b79606fb3afea5bd1609ed40b622142f1c98125abcfe89a76a661b0e8e343910";
+    const STRUCTURED_CONFIG: &str =
+        "image: alpine:latest\ncommandOptions: [\"--rm\"]\ncommandArgs: [sleep, '10']\n";
+    const EXPECTED_STRUCTURED_CONFIG_HASH: &str =
+        "12c67d5545f5711228a8492a02bf99d8991ff6c6cb960419a151525f37a6dfd9";
 
     // [utest->swdd~common-workload-execution-instance-naming~1]
     #[test]
@@ -214,4 +278,54 @@ mod tests {
             format!("{WORKLOAD_NAME}.{EXPECTED_HASH}.{AGENT_NAME}")
         )
     }
+
+    // [utest->swdd~common-hashes-canonical-runtime-config~1]
+    #[test]
+    fn utest_config_hash_independent_of_key_order_comments_and_empty_values() {
+        let runtime_config = "image: alpine:latest\ncommandOptions: [\"--rm\"]\ncommandArgs: [sleep, '10']\n"
+            .to_string();
+        let reordered_runtime_config = "# reordered\ncommandArgs:\n  - sleep\n  - \"10\"\ncommandOptions:\n  - --rm\nimage: 'alpine:latest'\nenv: {}\nports: []\nlabels: ~\n"
+            .to_string();
+
+        assert_eq!(
+            canonicalize_runtime_config(&runtime_config),
+            r#"{"commandArgs":["sleep","10"],"commandOptions":["--rm"],"image":"alpine:latest"}"#
+        );
+        assert_eq!(
+            runtime_config.hash_config(),
+            reordered_runtime_config.hash_config()
+        );
+    }
+
+    // the hash is part of the instance names of running workloads, a change restarts them
+    // [utest->swdd~common-hashes-canonical-runtime-config~1]
+    #[test]
+    fn utest_config_hash_of_known_config_is_stable() {
+        assert_eq!(
+            STRUCTURED_CONFIG.to_string().hash_config(),
+            EXPECTED_STRUCTURED_CONFIG_HASH
+        );
+        assert_eq!(CONFIG.to_string().hash_config(), EXPECTED_HASH);
+    }
+
+    // [utest->swdd~common-hashes-canonical-runtime-config~1]
+    #[test]
+    fn utest_config_hash_detects_changed_values() {
+        let runtime_config = "image: alpine:latest\ncommandArgs: [sleep, 10]".to_string();
+        let changed_type = "image: alpine:latest\ncommandArgs: [sleep, '10']".to_string();
+        let changed_order = "image: alpine:latest\ncommandArgs: [10, sleep]".to_string();
+
+        assert_ne!(runtime_config.hash_config(), changed_type.hash_config());
+        assert_ne!(runtime_config.hash_config(), changed_order.hash_config());
+    }
+
+    // [utest->swdd~common-hashes-canonical-runtime-config~1]
+    #[test]
+    fn utest_config_hash_keeps_unstructured_config() {
+        assert_eq!(canonicalize_runtime_config(CONFIG), CONFIG);
+        assert_eq!(
+            canonicalize_runtime_config("image: [unclosed"),
+            "image: [unclosed"
+        );
+    }
 }
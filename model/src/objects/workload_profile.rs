@@ -0,0 +1,150 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+use crate::serialize_to_ordered_map;
+
+use super::{LogLevel, StoredWorkloadSpec};
+
+// The settings of a workload overridden while the profile is selected, e.g. 'dev' or 'prod'.
+// [impl->swdd~workload-profiles~1]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WorkloadProfile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runtime_config: Option<String>,
+    #[serde(
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub template_parameters: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<LogLevel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_startup_time_ms: Option<u64>,
+}
+
+impl WorkloadProfile {
+    // [impl->swdd~common-resolves-workload-profiles~1]
+    // The template parameters of the profile are merged into the ones of the workload, the
+    // other settings replace the ones of the workload if they are set.
+    pub fn apply_to(&self, workload: &mut StoredWorkloadSpec) {
+        if let Some(runtime_config) = &self.runtime_config {
+            workload.runtime_config = runtime_config.clone();
+        }
+        workload.template_parameters.extend(
+            self.template_parameters
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone())),
+        );
+        if self.log_level.is_some() {
+            workload.log_level = self.log_level.clone();
+        }
+        if self.expected_startup_time_ms.is_some() {
+            workload.expected_startup_time_ms = self.expected_startup_time_ms;
+        }
+    }
+}
+
+impl From<ank_base::WorkloadProfile> for WorkloadProfile {
+    fn from(item: ank_base::WorkloadProfile) -> Self {
+        WorkloadProfile {
+            runtime_config: Some(item.runtime_config).filter(|config| !config.is_empty()),
+            template_parameters: item.template_parameters,
+            log_level: item.log_level.map(Into::into),
+            expected_startup_time_ms: Some(item.expected_startup_time_ms)
+                .filter(|startup_time| *startup_time != 0),
+        }
+    }
+}
+
+impl From<WorkloadProfile> for ank_base::WorkloadProfile {
+    fn from(item: WorkloadProfile) -> Self {
+        ank_base::WorkloadProfile {
+            runtime_config: item.runtime_config.unwrap_or_default(),
+            template_parameters: item.template_parameters,
+            log_level: item.log_level.map(Into::into),
+            expected_startup_time_ms: item.expected_startup_time_ms.unwrap_or_default(),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use api::ank_base;
+
+    use super::WorkloadProfile;
+    use crate::objects::generate_test_stored_workload_spec;
+
+    // [utest->swdd~workload-profiles~1]
+    #[test]
+    fn utest_workload_profile_converts_to_and_from_proto() {
+        let profile = WorkloadProfile {
+            runtime_config: Some("image: navigation:debug".to_string()),
+            template_parameters: HashMap::from([("RUST_LOG".to_string(), "debug".to_string())]),
+            log_level: None,
+            expected_startup_time_ms: Some(20000),
+        };
+        let proto_profile = ank_base::WorkloadProfile {
+            runtime_config: "image: navigation:debug".to_string(),
+            template_parameters: HashMap::from([("RUST_LOG".to_string(), "debug".to_string())]),
+            log_level: None,
+            expected_startup_time_ms: 20000,
+        };
+
+        assert_eq!(
+            ank_base::WorkloadProfile::from(profile.clone()),
+            proto_profile
+        );
+        assert_eq!(WorkloadProfile::from(proto_profile), profile);
+    }
+
+    // [utest->swdd~common-resolves-workload-profiles~1]
+    #[test]
+    fn utest_workload_profile_overrides_the_set_settings_of_the_workload() {
+        let mut workload = generate_test_stored_workload_spec("agent_A", "podman");
+        workload.runtime_config = "image: navigation:1.0".to_string();
+        workload.template_parameters = HashMap::from([
+            ("tag".to_string(), "1.0".to_string()),
+            ("RUST_LOG".to_string(), "info".to_string()),
+        ]);
+        workload.expected_startup_time_ms = Some(5000);
+        let profile: WorkloadProfile =
+            serde_yaml::from_str("templateParameters:\n  RUST_LOG: debug\n").unwrap();
+
+        let mut expected_workload = workload.clone();
+        expected_workload.template_parameters = HashMap::from([
+            ("tag".to_string(), "1.0".to_string()),
+            ("RUST_LOG".to_string(), "debug".to_string()),
+        ]);
+        profile.apply_to(&mut workload);
+
+        assert_eq!(workload, expected_workload);
+    }
+}
@@ -0,0 +1,957 @@
+// Copyright (c) 2023 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, time::Duration};
+
+use crate::serialize_to_ordered_map;
+use crate::objects::Tag;
+
+use super::ConfigObject;
+use super::DependencyExpression;
+use super::{LogLevel, LogRoute, Resources, WorkloadHooks};
+use super::ExecutionState;
+use super::WorkloadInstanceName;
+
+pub type WorkloadCollection = Vec<WorkloadSpec>;
+pub type DeletedWorkloadCollection = Vec<DeletedWorkload>;
+// [impl->swdd~common-object-serialization~1]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct DeletedWorkload {
+    pub instance_name: WorkloadInstanceName,
+    #[serde(serialize_with = "serialize_to_ordered_map")]
+    pub dependencies: HashMap<String, DeleteCondition>,
+}
+
+// [impl->swdd~common-object-serialization~1]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WorkloadSpec {
+    pub instance_name: WorkloadInstanceName,
+    pub tags: Vec<Tag>,
+    #[serde(serialize_with = "serialize_to_ordered_map")]
+    pub dependencies: HashMap<String, AddCondition>,
+    pub restart_policy: RestartPolicy,
+    pub runtime: String,
+    pub runtime_config: String,
+    #[serde(
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub unknown_state_policies: HashMap<String, UnknownStatePolicy>,
+    // [impl->swdd~workload-running-for-add-condition~1]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub running_for_ms: HashMap<String, u64>,
+    // [impl->swdd~workload-dependency-expression~1]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependency_expression: Option<DependencyExpression>,
+    // [impl->swdd~workload-references-config-objects~1]
+    #[serde(
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub configs: HashMap<String, ConfigObject>,
+    // [impl->swdd~workload-enabled-if-agent-attributes~1]
+    // only evaluated by the server and not sent to the agents
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub enabled_if: String,
+    // [impl->swdd~workload-disconnect-policy~1]
+    #[serde(skip_serializing_if = "DisconnectPolicy::is_keep_running")]
+    pub disconnect_policy: DisconnectPolicy,
+    // [impl->swdd~workload-log-forwarding~1]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub log_forwarding: Vec<LogRoute>,
+    // [impl->swdd~workload-log-level~1]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<LogLevel>,
+    // [impl->swdd~workload-pre-shutdown-timeout~1]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_shutdown_timeout_ms: Option<u64>,
+    // [impl->swdd~workload-dependency-timeout~1]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependency_timeout_ms: Option<u64>,
+    // [impl->swdd~workload-schedule~1]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<String>,
+    // [impl->swdd~workload-capture-output~1]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capture_output_bytes: Option<u64>,
+    // [impl->swdd~workload-resource-requests~1]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resources: Option<Resources>,
+    // [impl->swdd~workload-dependency-failure-policy~1]
+    #[serde(skip_serializing_if = "DependencyFailurePolicy::is_ignore")]
+    pub on_dependency_failure: DependencyFailurePolicy,
+    // [impl->swdd~workload-expected-startup-time~1]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_startup_time_ms: Option<u64>,
+    // [impl->swdd~workload-control-interface-request-quota~1]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub control_interface_requests_per_minute: Option<u32>,
+    // [impl->swdd~workload-hooks~1]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<WorkloadHooks>,
+    // [impl->swdd~workload-agent-dependencies~1]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub agent_dependencies: Vec<String>,
+    // [impl->swdd~workload-control-interface-mode~1]
+    #[serde(skip_serializing_if = "ControlInterfaceMode::is_enabled")]
+    pub control_interface: ControlInterfaceMode,
+    // [impl->swdd~workload-update-strategy~1]
+    #[serde(skip_serializing_if = "UpdateStrategy::is_at_most_once")]
+    pub update_strategy: UpdateStrategy,
+    // [impl->swdd~workload-priority~1]
+    #[serde(skip_serializing_if = "is_default_priority")]
+    pub priority: u8,
+}
+
+impl WorkloadSpec {
+    pub fn get_unknown_state_policy(&self, dependency_name: &str) -> UnknownStatePolicy {
+        self.unknown_state_policies
+            .get(dependency_name)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // [impl->swdd~workload-running-for-add-condition~1]
+    // A dependency without a configured time is fulfilled as soon as it is running.
+    pub fn get_running_for(&self, dependency_name: &str) -> Duration {
+        Duration::from_millis(
+            self.running_for_ms
+                .get(dependency_name)
+                .copied()
+                .unwrap_or_default(),
+        )
+    }
+
+    // [impl->swdd~workload-dependency-expression~1]
+    // The conditions of the dependencies followed by the ones of the dependency expression.
+    pub fn add_conditions(&self) -> Vec<(&String, &AddCondition)> {
+        self.dependencies
+            .iter()
+            .chain(
+                self.dependency_expression
+                    .iter()
+                    .flat_map(DependencyExpression::conditions),
+            )
+            .collect()
+    }
+
+    // A workload that opted in to live log level updates keeps running if only its
+    // log level changes.
+    // [impl->swdd~workload-log-level~1]
+    pub fn is_live_log_level_update_of(&self, current: &WorkloadSpec) -> bool {
+        let opted_in = |spec: &WorkloadSpec| {
+            spec.log_level
+                .as_ref()
+                .is_some_and(|log_level| log_level.live_update)
+        };
+        opted_in(self)
+            && opted_in(current)
+            && self.log_level != current.log_level
+            && *self
+                == WorkloadSpec {
+                    log_level: self.log_level.clone(),
+                    ..current.clone()
+                }
+    }
+}
+
+pub type AgentWorkloadMap = HashMap<String, (WorkloadCollection, DeletedWorkloadCollection)>;
+
+pub fn get_workloads_per_agent(
+    added_workloads: WorkloadCollection,
+    deleted_workloads: DeletedWorkloadCollection,
+) -> AgentWorkloadMap {
+    let mut agent_workloads: AgentWorkloadMap = HashMap::new();
+
+    for added_workload in added_workloads {
+        if let Some((added_workload_vector, _)) =
+            agent_workloads.get_mut(added_workload.instance_name.agent_name())
+        {
+            added_workload_vector.push(added_workload);
+        } else if !added_workload.instance_name.agent_name().is_empty() {
+            agent_workloads.insert(
+                added_workload.instance_name.agent_name().to_owned(),
+                (vec![added_workload], vec![]),
+            );
+        }
+    }
+
+    for deleted_workload in deleted_workloads {
+        if let Some((_, deleted_workload_vector)) =
+            agent_workloads.get_mut(deleted_workload.instance_name.agent_name())
+        {
+            deleted_workload_vector.push(deleted_workload);
+        } else {
+            agent_workloads.insert(
+                deleted_workload.instance_name.agent_name().to_owned(),
+                (vec![], vec![deleted_workload]),
+            );
+        }
+    }
+
+    agent_workloads
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+// [impl->swdd~agent-supports-restart-policies~1]
+pub enum RestartPolicy {
+    #[default]
+    Never,
+    OnFailure,
+    Always,
+}
+
+impl std::fmt::Display for RestartPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RestartPolicy::Never => write!(f, "Never"),
+            RestartPolicy::OnFailure => write!(f, "OnFailure"),
+            RestartPolicy::Always => write!(f, "Always"),
+        }
+    }
+}
+
+impl TryFrom<i32> for RestartPolicy {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == RestartPolicy::Never as i32 => Ok(RestartPolicy::Never),
+            x if x == RestartPolicy::OnFailure as i32 => Ok(RestartPolicy::OnFailure),
+            x if x == RestartPolicy::Always as i32 => Ok(RestartPolicy::Always),
+            _ => Err(format!(
+                "Received an unknown value '{value}' as restart policy."
+            )),
+        }
+    }
+}
+
+// [impl->swdd~workload-disconnect-policy~1]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DisconnectPolicy {
+    #[default]
+    KeepRunning = 0,
+    Stop = 1,
+    Fallback = 2,
+}
+
+impl DisconnectPolicy {
+    pub fn is_keep_running(&self) -> bool {
+        *self == DisconnectPolicy::KeepRunning
+    }
+}
+
+impl TryFrom<i32> for DisconnectPolicy {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == DisconnectPolicy::KeepRunning as i32 => Ok(DisconnectPolicy::KeepRunning),
+            x if x == DisconnectPolicy::Stop as i32 => Ok(DisconnectPolicy::Stop),
+            x if x == DisconnectPolicy::Fallback as i32 => Ok(DisconnectPolicy::Fallback),
+            _ => Err(format!(
+                "Received an unknown value '{value}' as disconnect policy."
+            )),
+        }
+    }
+}
+
+// [impl->swdd~workload-control-interface-mode~1]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ControlInterfaceMode {
+    #[default]
+    Enabled = 0,
+    Disabled = 1,
+}
+
+impl ControlInterfaceMode {
+    pub fn is_enabled(&self) -> bool {
+        *self == ControlInterfaceMode::Enabled
+    }
+}
+
+impl TryFrom<i32> for ControlInterfaceMode {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == ControlInterfaceMode::Enabled as i32 => Ok(ControlInterfaceMode::Enabled),
+            x if x == ControlInterfaceMode::Disabled as i32 => Ok(ControlInterfaceMode::Disabled),
+            _ => Err(format!(
+                "Received an unknown value '{value}' as control interface mode."
+            )),
+        }
+    }
+}
+
+// [impl->swdd~workload-dependency-failure-policy~1]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyFailurePolicy {
+    #[default]
+    Ignore = 0,
+    Restart = 1,
+    Stop = 2,
+}
+
+impl DependencyFailurePolicy {
+    pub fn is_ignore(&self) -> bool {
+        *self == DependencyFailurePolicy::Ignore
+    }
+}
+
+impl TryFrom<i32> for DependencyFailurePolicy {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == DependencyFailurePolicy::Ignore as i32 => Ok(DependencyFailurePolicy::Ignore),
+            x if x == DependencyFailurePolicy::Restart as i32 => {
+                Ok(DependencyFailurePolicy::Restart)
+            }
+            x if x == DependencyFailurePolicy::Stop as i32 => Ok(DependencyFailurePolicy::Stop),
+            _ => Err(format!(
+                "Received an unknown value '{value}' as dependency failure policy."
+            )),
+        }
+    }
+}
+
+// [impl->swdd~workload-update-strategy~1]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum UpdateStrategy {
+    #[default]
+    AtMostOnce = 0,
+    AtLeastOnce = 1,
+}
+
+impl UpdateStrategy {
+    pub fn is_at_most_once(&self) -> bool {
+        *self == UpdateStrategy::AtMostOnce
+    }
+}
+
+impl TryFrom<i32> for UpdateStrategy {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == UpdateStrategy::AtMostOnce as i32 => Ok(UpdateStrategy::AtMostOnce),
+            x if x == UpdateStrategy::AtLeastOnce as i32 => Ok(UpdateStrategy::AtLeastOnce),
+            _ => Err(format!("Received an unknown value '{value}' as update strategy.")),
+        }
+    }
+}
+
+pub(crate) fn is_default_priority(priority: &u8) -> bool {
+    *priority == 0
+}
+
+// [impl->swdd~workload-priority~1]
+pub fn priority_from_proto(priority: u32) -> Result<u8, String> {
+    u8::try_from(priority)
+        .map_err(|_| format!("The priority '{priority}' exceeds the maximum of {}.", u8::MAX))
+}
+
+pub trait FulfilledBy<T> {
+    fn fulfilled_by(&self, other: &T) -> bool;
+}
+
+// [impl->swdd~workload-add-conditions-for-dependencies~1]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AddCondition {
+    AddCondRunning = 0,
+    AddCondSucceeded = 1,
+    AddCondFailed = 2,
+    AddCondRunningFor = 3,
+    AddCondNotRunning = 4,
+}
+
+impl FulfilledBy<ExecutionState> for AddCondition {
+    // [impl->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~1]
+    fn fulfilled_by(&self, other: &ExecutionState) -> bool {
+        match self {
+            AddCondition::AddCondRunning => (*other).is_running(),
+            AddCondition::AddCondSucceeded => (*other).is_succeeded(),
+            AddCondition::AddCondFailed => (*other).is_failed(),
+            // the time the dependency is running is evaluated by the agent
+            AddCondition::AddCondRunningFor => (*other).is_running(),
+            // [impl->swdd~workload-not-running-add-condition~1]
+            AddCondition::AddCondNotRunning => {
+                !(*other).is_starting() && !(*other).is_running() && !(*other).is_stopping()
+            }
+        }
+    }
+}
+
+impl TryFrom<i32> for AddCondition {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == AddCondition::AddCondRunning as i32 => Ok(AddCondition::AddCondRunning),
+            x if x == AddCondition::AddCondSucceeded as i32 => Ok(AddCondition::AddCondSucceeded),
+            x if x == AddCondition::AddCondFailed as i32 => Ok(AddCondition::AddCondFailed),
+            x if x == AddCondition::AddCondRunningFor as i32 => Ok(AddCondition::AddCondRunningFor),
+            x if x == AddCondition::AddCondNotRunning as i32 => Ok(AddCondition::AddCondNotRunning),
+            _ => Err(format!(
+                "Received an unknown value '{value}' as AddCondition."
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for AddCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AddCondition::AddCondRunning => write!(f, "ADD_COND_RUNNING"),
+            AddCondition::AddCondSucceeded => write!(f, "ADD_COND_SUCCEEDED"),
+            AddCondition::AddCondFailed => write!(f, "ADD_COND_FAILED"),
+            AddCondition::AddCondRunningFor => write!(f, "ADD_COND_RUNNING_FOR"),
+            AddCondition::AddCondNotRunning => write!(f, "ADD_COND_NOT_RUNNING"),
+        }
+    }
+}
+
+// [impl->swdd~workload-unknown-state-policies-for-dependencies~1]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum UnknownStatePolicy {
+    #[default]
+    UnknownStateUnfulfilled = 0,
+    UnknownStateLastKnown = 1,
+}
+
+impl TryFrom<i32> for UnknownStatePolicy {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == UnknownStatePolicy::UnknownStateUnfulfilled as i32 => {
+                Ok(UnknownStatePolicy::UnknownStateUnfulfilled)
+            }
+            x if x == UnknownStatePolicy::UnknownStateLastKnown as i32 => {
+                Ok(UnknownStatePolicy::UnknownStateLastKnown)
+            }
+            _ => Err(format!(
+                "Received an unknown value '{value}' as UnknownStatePolicy."
+            )),
+        }
+    }
+}
+
+// [impl->swdd~workload-delete-conditions-for-dependencies~1]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DeleteCondition {
+    DelCondRunning = 0,
+    DelCondNotPendingNorRunning = 1,
+}
+
+impl FulfilledBy<ExecutionState> for DeleteCondition {
+    // [impl->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~1]
+    fn fulfilled_by(&self, other: &ExecutionState) -> bool {
+        if other.is_waiting_to_start() {
+            return true;
+        }
+
+        match self {
+            DeleteCondition::DelCondNotPendingNorRunning => (*other).is_not_pending_nor_running(),
+            DeleteCondition::DelCondRunning => (*other).is_running(),
+        }
+    }
+}
+
+impl TryFrom<i32> for DeleteCondition {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == DeleteCondition::DelCondRunning as i32 => Ok(DeleteCondition::DelCondRunning),
+            x if x == DeleteCondition::DelCondNotPendingNorRunning as i32 => {
+                Ok(DeleteCondition::DelCondNotPendingNorRunning)
+            }
+            _ => Err(format!(
+                "Received an unknown value '{value}' as DeleteCondition."
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for DeleteCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeleteCondition::DelCondRunning => write!(f, "DEL_COND_RUNNING"),
+            DeleteCondition::DelCondNotPendingNorRunning => {
+                write!(f, "DEL_COND_NOT_PENDING_NOR_RUNNING")
+            }
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(any(feature = "test_utils", test))]
+fn generate_test_dependencies() -> HashMap<String, AddCondition> {
+    HashMap::from([
+        (String::from("workload A"), AddCondition::AddCondRunning),
+        (String::from("workload C"), AddCondition::AddCondSucceeded),
+    ])
+}
+
+#[cfg(any(feature = "test_utils", test))]
+pub fn generate_test_workload_spec_with_param(
+    agent_name: String,
+    workload_name: String,
+    runtime_name: String,
+) -> crate::objects::WorkloadSpec {
+    let runtime_config =
+        "generalOptions: [\"--version\"]\ncommandOptions: [\"--network=host\"]\nimage: alpine:latest\ncommandArgs: [\"bash\"]\n"
+        .to_owned();
+
+    generate_test_workload_spec_with_runtime_config(
+        agent_name,
+        workload_name,
+        runtime_name,
+        runtime_config,
+    )
+}
+
+#[cfg(any(feature = "test_utils", test))]
+pub fn generate_test_workload_spec_with_runtime_config(
+    agent_name: String,
+    workload_name: String,
+    runtime_name: String,
+    runtime_config: String,
+) -> crate::objects::WorkloadSpec {
+    let instance_name = WorkloadInstanceName::builder()
+        .agent_name(agent_name)
+        .workload_name(workload_name)
+        .config(&runtime_config)
+        .build();
+
+    WorkloadSpec {
+        instance_name,
+        dependencies: generate_test_dependencies(),
+        restart_policy: RestartPolicy::Always,
+        runtime: runtime_name,
+        tags: vec![Tag {
+            key: "key".into(),
+            value: "value".into(),
+        }],
+        runtime_config,
+        unknown_state_policies: HashMap::new(),
+        running_for_ms: HashMap::new(),
+        dependency_expression: None,
+        configs: HashMap::new(),
+        enabled_if: String::new(),
+        disconnect_policy: DisconnectPolicy::KeepRunning,
+        log_forwarding: vec![],
+        log_level: None,
+        pre_shutdown_timeout_ms: None,
+        dependency_timeout_ms: None,
+        schedule: None,
+        capture_output_bytes: None,
+        resources: None,
+        on_dependency_failure: DependencyFailurePolicy::Ignore,
+        expected_startup_time_ms: None,
+        control_interface_requests_per_minute: None,
+        hooks: None,
+        agent_dependencies: vec![],
+        control_interface: ControlInterfaceMode::Enabled,
+        update_strategy: UpdateStrategy::AtMostOnce,
+        priority: 0,
+    }
+}
+
+#[cfg(any(feature = "test_utils", test))]
+pub fn generate_test_workload_spec() -> WorkloadSpec {
+    generate_test_workload_spec_with_param(
+        "agent".to_string(),
+        "name".to_string(),
+        "runtime".to_string(),
+    )
+}
+
+#[cfg(any(feature = "test_utils", test))]
+pub fn generate_test_workload_spec_with_dependencies(
+    agent_name: &str,
+    workload_name: &str,
+    runtime_name: &str,
+    dependencies: HashMap<String, AddCondition>,
+) -> WorkloadSpec {
+    let mut workload_spec = generate_test_workload_spec_with_param(
+        agent_name.to_owned(),
+        workload_name.to_owned(),
+        runtime_name.to_owned(),
+    );
+    workload_spec.dependencies = dependencies;
+    workload_spec
+}
+
+// [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+// [utest->swdd~common-object-representation~1]
+// [utest->swdd~common-object-serialization~1]
+#[cfg(test)]
+mod tests {
+
+    use crate::objects::*;
+    use crate::test_utils::*;
+    use std::collections::HashMap;
+    use std::time::Duration;
+    #[test]
+    fn utest_get_workloads_per_agent_one_agent_one_workload() {
+        let added_workloads = vec![
+            generate_test_workload_spec_with_param(
+                "agent1".to_string(),
+                "name 1".to_string(),
+                "runtime1".to_string(),
+            ),
+            generate_test_workload_spec_with_param(
+                "agent1".to_string(),
+                "name 2".to_string(),
+                "runtime2".to_string(),
+            ),
+            generate_test_workload_spec_with_param(
+                "agent2".to_string(),
+                "name 3".to_string(),
+                "runtime3".to_string(),
+            ),
+        ];
+
+        let deleted_workloads = vec![
+            generate_test_deleted_workload("agent1".to_string(), "workload 8".to_string()),
+            generate_test_deleted_workload("agent4".to_string(), "workload 9".to_string()),
+        ];
+
+        let workload_map = get_workloads_per_agent(added_workloads, deleted_workloads);
+        assert_eq!(workload_map.len(), 3);
+
+        let (agent1_added_workloads, agent1_deleted_workloads) =
+            workload_map.get("agent1").unwrap();
+        assert_eq!(agent1_added_workloads.len(), 2);
+        assert_eq!(agent1_deleted_workloads.len(), 1);
+
+        let workload1 = &agent1_added_workloads[0];
+        let workload2 = &agent1_added_workloads[1];
+        assert_eq!(workload1.instance_name.agent_name(), "agent1");
+        assert_eq!(workload1.runtime, "runtime1");
+        assert_eq!(workload2.instance_name.agent_name(), "agent1");
+        assert_eq!(workload2.runtime, "runtime2");
+
+        let deleted_workload1 = &agent1_deleted_workloads[0];
+        assert_eq!(deleted_workload1.instance_name.agent_name(), "agent1");
+        assert_eq!(
+            deleted_workload1.instance_name.workload_name(),
+            "workload 8"
+        );
+
+        let (agent2_added_workloads, agent2_deleted_workloads) =
+            workload_map.get("agent2").unwrap();
+        assert_eq!(agent2_added_workloads.len(), 1);
+        assert_eq!(agent2_deleted_workloads.len(), 0);
+
+        let workload3 = &agent2_added_workloads[0];
+        assert_eq!(workload3.instance_name.agent_name(), "agent2");
+        assert_eq!(workload3.runtime, "runtime3");
+
+        assert!(workload_map.get("agent3").is_none());
+
+        let (agent4_added_workloads, agent4_deleted_workloads) =
+            workload_map.get("agent4").unwrap();
+        assert_eq!(agent4_added_workloads.len(), 0);
+        assert_eq!(agent4_deleted_workloads.len(), 1);
+
+        let workload3 = &agent4_deleted_workloads[0];
+        assert_eq!(workload3.instance_name.agent_name(), "agent4");
+        assert_eq!(workload3.instance_name.workload_name(), "workload 9");
+    }
+
+    // [utest->swdd~workload-add-conditions-for-dependencies~1]
+    #[test]
+    fn utest_add_condition_from_int() {
+        assert_eq!(
+            AddCondition::try_from(0).unwrap(),
+            AddCondition::AddCondRunning
+        );
+        assert_eq!(
+            AddCondition::try_from(1).unwrap(),
+            AddCondition::AddCondSucceeded
+        );
+        assert_eq!(
+            AddCondition::try_from(2).unwrap(),
+            AddCondition::AddCondFailed
+        );
+        assert_eq!(
+            AddCondition::try_from(3).unwrap(),
+            AddCondition::AddCondRunningFor
+        );
+        assert_eq!(
+            AddCondition::try_from(4).unwrap(),
+            AddCondition::AddCondNotRunning
+        );
+        assert_eq!(
+            AddCondition::try_from(100),
+            Err::<AddCondition, String>(
+                "Received an unknown value '100' as AddCondition.".to_string()
+            )
+        );
+    }
+
+    // [utest->swdd~workload-delete-conditions-for-dependencies~1]
+    #[test]
+    fn utest_delete_condition_from_int() {
+        assert_eq!(
+            DeleteCondition::try_from(0).unwrap(),
+            DeleteCondition::DelCondRunning
+        );
+        assert_eq!(
+            DeleteCondition::try_from(1).unwrap(),
+            DeleteCondition::DelCondNotPendingNorRunning
+        );
+        assert_eq!(
+            DeleteCondition::try_from(100),
+            Err::<DeleteCondition, String>(
+                "Received an unknown value '100' as DeleteCondition.".to_string()
+            )
+        );
+    }
+
+    // [utest->swdd~workload-unknown-state-policies-for-dependencies~1]
+    #[test]
+    fn utest_unknown_state_policy_from_int() {
+        assert_eq!(
+            UnknownStatePolicy::try_from(0).unwrap(),
+            UnknownStatePolicy::UnknownStateUnfulfilled
+        );
+        assert_eq!(
+            UnknownStatePolicy::try_from(1).unwrap(),
+            UnknownStatePolicy::UnknownStateLastKnown
+        );
+        assert_eq!(
+            UnknownStatePolicy::try_from(100),
+            Err::<UnknownStatePolicy, String>(
+                "Received an unknown value '100' as UnknownStatePolicy.".to_string()
+            )
+        );
+    }
+
+    // [utest->swdd~workload-disconnect-policy~1]
+    #[test]
+    fn utest_disconnect_policy_from_int() {
+        assert_eq!(
+            DisconnectPolicy::try_from(0).unwrap(),
+            DisconnectPolicy::KeepRunning
+        );
+        assert_eq!(
+            DisconnectPolicy::try_from(1).unwrap(),
+            DisconnectPolicy::Stop
+        );
+        assert_eq!(
+            DisconnectPolicy::try_from(2).unwrap(),
+            DisconnectPolicy::Fallback
+        );
+        assert_eq!(
+            DisconnectPolicy::try_from(100),
+            Err::<DisconnectPolicy, String>(
+                "Received an unknown value '100' as disconnect policy.".to_string()
+            )
+        );
+    }
+
+    // [utest->swdd~workload-dependency-failure-policy~1]
+    #[test]
+    fn utest_dependency_failure_policy_from_int() {
+        assert_eq!(
+            DependencyFailurePolicy::try_from(0).unwrap(),
+            DependencyFailurePolicy::Ignore
+        );
+        assert_eq!(
+            DependencyFailurePolicy::try_from(1).unwrap(),
+            DependencyFailurePolicy::Restart
+        );
+        assert_eq!(
+            DependencyFailurePolicy::try_from(2).unwrap(),
+            DependencyFailurePolicy::Stop
+        );
+        assert_eq!(
+            DependencyFailurePolicy::try_from(100),
+            Err::<DependencyFailurePolicy, String>(
+                "Received an unknown value '100' as dependency failure policy.".to_string()
+            )
+        );
+    }
+
+    // [utest->swdd~workload-unknown-state-policies-for-dependencies~1]
+    #[test]
+    fn utest_get_unknown_state_policy_defaults_to_unfulfilled() {
+        let mut workload_spec = generate_test_workload_spec();
+        workload_spec.unknown_state_policies = HashMap::from([(
+            "workload A".to_string(),
+            UnknownStatePolicy::UnknownStateLastKnown,
+        )]);
+
+        assert_eq!(
+            workload_spec.get_unknown_state_policy("workload A"),
+            UnknownStatePolicy::UnknownStateLastKnown
+        );
+        assert_eq!(
+            workload_spec.get_unknown_state_policy("workload C"),
+            UnknownStatePolicy::UnknownStateUnfulfilled
+        );
+    }
+
+    // [utest->swdd~workload-running-for-add-condition~1]
+    #[test]
+    fn utest_get_running_for_defaults_to_zero() {
+        let mut workload_spec = generate_test_workload_spec();
+        workload_spec.running_for_ms = HashMap::from([("workload A".to_string(), 30000)]);
+
+        assert_eq!(
+            workload_spec.get_running_for("workload A"),
+            Duration::from_secs(30)
+        );
+        assert_eq!(workload_spec.get_running_for("workload C"), Duration::ZERO);
+    }
+
+    #[test]
+    fn utest_serialize_deleted_workload_into_ordered_output() {
+        let mut deleted_workload =
+            generate_test_deleted_workload("agent X".to_string(), "workload X".to_string());
+
+        deleted_workload.dependencies.insert(
+            "workload C".to_string(),
+            DeleteCondition::DelCondNotPendingNorRunning,
+        );
+
+        let serialized_deleted_workload = serde_yaml::to_string(&deleted_workload).unwrap();
+        let indices = [
+            serialized_deleted_workload.find("workload A").unwrap(),
+            serialized_deleted_workload.find("workload C").unwrap(),
+        ];
+        assert!(
+            indices.windows(2).all(|window| window[0] < window[1]),
+            "expected ordered dependencies."
+        );
+    }
+
+    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~1]
+    #[test]
+    fn utest_add_condition_fulfilled_by_fulfilled() {
+        let add_condition = AddCondition::AddCondRunning;
+        assert!(add_condition.fulfilled_by(&ExecutionState::running()));
+
+        let add_condition = AddCondition::AddCondSucceeded;
+        assert!(add_condition.fulfilled_by(&ExecutionState::succeeded()));
+
+        let add_condition = AddCondition::AddCondFailed;
+        assert!(add_condition.fulfilled_by(&ExecutionState::failed("some failure".to_string())));
+
+        let add_condition = AddCondition::AddCondRunningFor;
+        assert!(add_condition.fulfilled_by(&ExecutionState::running()));
+        assert!(!add_condition.fulfilled_by(&ExecutionState::succeeded()));
+    }
+
+    // [utest->swdd~workload-not-running-add-condition~1]
+    #[test]
+    fn utest_add_condition_not_running_fulfilled_by() {
+        let add_condition = AddCondition::AddCondNotRunning;
+        assert!(add_condition.fulfilled_by(&ExecutionState::succeeded()));
+        assert!(add_condition.fulfilled_by(&ExecutionState::failed("some failure")));
+        assert!(add_condition.fulfilled_by(&ExecutionState::waiting_to_start()));
+        assert!(!add_condition.fulfilled_by(&ExecutionState::starting("some info")));
+        assert!(!add_condition.fulfilled_by(&ExecutionState::running()));
+        assert!(!add_condition.fulfilled_by(&ExecutionState::stopping("some info")));
+    }
+
+    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~1]
+    #[test]
+    fn utest_delete_condition_fulfilled_by() {
+        let delete_condition = DeleteCondition::DelCondNotPendingNorRunning;
+        assert!(delete_condition.fulfilled_by(&ExecutionState::succeeded()));
+
+        let delete_condition = DeleteCondition::DelCondRunning;
+        assert!(delete_condition.fulfilled_by(&ExecutionState::running()));
+
+        let delete_condition = DeleteCondition::DelCondNotPendingNorRunning;
+        assert!(delete_condition.fulfilled_by(&ExecutionState::waiting_to_start()));
+    }
+
+    // [utest->swdd~agent-supports-restart-policies~1]
+    #[test]
+    fn utest_restart_to_int() {
+        assert_eq!(RestartPolicy::try_from(0).unwrap(), RestartPolicy::Never);
+        assert_eq!(
+            RestartPolicy::try_from(1).unwrap(),
+            RestartPolicy::OnFailure
+        );
+        assert_eq!(RestartPolicy::try_from(2).unwrap(), RestartPolicy::Always);
+
+        assert_eq!(
+            RestartPolicy::try_from(100),
+            Err::<RestartPolicy, String>(
+                "Received an unknown value '100' as restart policy.".to_string()
+            )
+        );
+    }
+
+    // [utest->swdd~workload-log-level~1]
+    #[test]
+    fn utest_is_live_log_level_update_only_for_opted_in_log_level_changes() {
+        let current = TestWorkloadSpecBuilder::new("workload_A")
+            .log_level("info", true)
+            .build();
+
+        let new_level = TestWorkloadSpecBuilder::new("workload_A")
+            .log_level("debug", true)
+            .build();
+        assert!(new_level.is_live_log_level_update_of(&current));
+
+        let other_change = TestWorkloadSpecBuilder::new("workload_A")
+            .restart_policy(RestartPolicy::Never)
+            .log_level("debug", true)
+            .build();
+        assert!(!other_change.is_live_log_level_update_of(&current));
+
+        let not_opted_in = TestWorkloadSpecBuilder::new("workload_A")
+            .log_level("debug", false)
+            .build();
+        assert!(!not_opted_in.is_live_log_level_update_of(&current));
+        assert!(!new_level.is_live_log_level_update_of(&not_opted_in));
+    }
+
+    #[test]
+    fn utest_restart_display() {
+        assert_eq!(RestartPolicy::Never.to_string(), "Never");
+        assert_eq!(RestartPolicy::OnFailure.to_string(), "OnFailure");
+        assert_eq!(RestartPolicy::Always.to_string(), "Always");
+    }
+}
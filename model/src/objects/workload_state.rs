@@ -14,16 +14,16 @@
 
 use std::fmt::Display;
 
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use api::ank_base;
 
-use crate::std_extensions::UnreachableOption;
-
 use super::WorkloadInstanceName;
 
 const TRIGGERED_MSG: &str = "Triggered at runtime.";
 pub const NO_MORE_RETRIES_MSG: &str = "No more retries.";
+pub const STALE_MSG: &str = "stale";
+const WAITING_FOR_DEGRADED_MODE_MSG: &str = "Waiting for the agent to enter the degraded mode.";
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum PendingSubstate {
@@ -31,6 +31,12 @@ pub enum PendingSubstate {
     WaitingToStart = 1,
     Starting = 2,
     StartingFailed = 8,
+    DeadlineExceeded = 9,
+    DependencyTimeout = 10,
+    DependencyCycle = 11,
+    WaitingForSchedule = 12,
+    InsufficientResources = 13,
+    StartingSlow = 14,
 }
 
 impl From<i32> for PendingSubstate {
@@ -39,6 +45,20 @@ impl From<i32> for PendingSubstate {
             x if x == PendingSubstate::Initial as i32 => PendingSubstate::Initial,
             x if x == PendingSubstate::WaitingToStart as i32 => PendingSubstate::WaitingToStart,
             x if x == PendingSubstate::Starting as i32 => PendingSubstate::Starting,
+            x if x == PendingSubstate::DeadlineExceeded as i32 => {
+                PendingSubstate::DeadlineExceeded
+            }
+            x if x == PendingSubstate::DependencyTimeout as i32 => {
+                PendingSubstate::DependencyTimeout
+            }
+            x if x == PendingSubstate::DependencyCycle as i32 => PendingSubstate::DependencyCycle,
+            x if x == PendingSubstate::WaitingForSchedule as i32 => {
+                PendingSubstate::WaitingForSchedule
+            }
+            x if x == PendingSubstate::InsufficientResources as i32 => {
+                PendingSubstate::InsufficientResources
+            }
+            x if x == PendingSubstate::StartingSlow as i32 => PendingSubstate::StartingSlow,
             _ => PendingSubstate::StartingFailed,
         }
     }
@@ -51,6 +71,12 @@ impl Display for PendingSubstate {
             PendingSubstate::WaitingToStart => write!(f, "WaitingToStart"),
             PendingSubstate::Starting => write!(f, "Starting"),
             PendingSubstate::StartingFailed => write!(f, "StartingFailed"),
+            PendingSubstate::DeadlineExceeded => write!(f, "DeadlineExceeded"),
+            PendingSubstate::DependencyTimeout => write!(f, "DependencyTimeout"),
+            PendingSubstate::DependencyCycle => write!(f, "DependencyCycle"),
+            PendingSubstate::WaitingForSchedule => write!(f, "WaitingForSchedule"),
+            PendingSubstate::InsufficientResources => write!(f, "InsufficientResources"),
+            PendingSubstate::StartingSlow => write!(f, "StartingSlow"),
         }
     }
 }
@@ -154,7 +180,11 @@ impl Display for FailedSubstate {
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(tag = "state", content = "subState")]
+#[serde(
+    tag = "state",
+    content = "subState",
+    from = "RawExecutionStateEnum"
+)]
 pub enum ExecutionStateEnum {
     AgentDisconnected,
     Pending(PendingSubstate),
@@ -165,6 +195,49 @@ pub enum ExecutionStateEnum {
     #[default]
     NotScheduled,
     Removed,
+    // A state or substate reported by a newer Ankaios version, the raw state is retained.
+    Unrecognized(String),
+}
+
+// Decodes the states of newer Ankaios versions instead of failing.
+#[derive(Deserialize)]
+struct RawExecutionStateEnum {
+    state: String,
+    #[serde(rename = "subState", default)]
+    sub_state: Option<serde_json::Value>,
+}
+
+fn parse_substate<T: DeserializeOwned>(sub_state: Option<&serde_json::Value>) -> Option<T> {
+    sub_state.and_then(|sub_state| T::deserialize(sub_state).ok())
+}
+
+// [impl->swdd~common-decodes-unrecognized-execution-states~1]
+impl From<RawExecutionStateEnum> for ExecutionStateEnum {
+    fn from(raw: RawExecutionStateEnum) -> Self {
+        let sub_state = raw.sub_state.as_ref();
+        let state = match raw.state.as_str() {
+            "AgentDisconnected" => Some(ExecutionStateEnum::AgentDisconnected),
+            "Pending" => parse_substate(sub_state).map(ExecutionStateEnum::Pending),
+            "Running" => parse_substate(sub_state).map(ExecutionStateEnum::Running),
+            "Stopping" => parse_substate(sub_state).map(ExecutionStateEnum::Stopping),
+            "Succeeded" => parse_substate(sub_state).map(ExecutionStateEnum::Succeeded),
+            "Failed" => parse_substate(sub_state).map(ExecutionStateEnum::Failed),
+            "NotScheduled" => Some(ExecutionStateEnum::NotScheduled),
+            "Removed" => Some(ExecutionStateEnum::Removed),
+            "Unrecognized" => parse_substate(sub_state).map(ExecutionStateEnum::Unrecognized),
+            _ => None,
+        };
+
+        state.unwrap_or_else(|| {
+            let raw_state = match sub_state {
+                None => raw.state,
+                Some(serde_json::Value::String(sub_state)) => format!("{}({sub_state})", raw.state),
+                Some(sub_state) => format!("{}({sub_state})", raw.state),
+            };
+            log::debug!("Received the unrecognized execution state '{}'.", raw_state);
+            ExecutionStateEnum::Unrecognized(raw_state)
+        })
+    }
 }
 
 // [impl->swdd~common-workload-state-transitions~1]
@@ -187,6 +260,18 @@ impl ExecutionState {
                 );
                 self.clone()
             }
+            // [impl->swdd~agent-reports-slow-workload-starts~1]
+            // a slow start reported after the workload has left the pending state is outdated
+            (current, ExecutionStateEnum::Pending(PendingSubstate::StartingSlow))
+                if !matches!(current, ExecutionStateEnum::Pending(_)) =>
+            {
+                log::trace!(
+                    "Skipping transition from '{}' to '{}' state.",
+                    self,
+                    incoming
+                );
+                self.clone()
+            }
             _ => incoming,
         }
     }
@@ -223,36 +308,56 @@ impl From<ExecutionStateEnum> for ank_base::execution_state::ExecutionStateEnum
             ExecutionStateEnum::Stopping(value) => {
                 ank_base::execution_state::ExecutionStateEnum::Stopping(value as i32)
             }
+            ExecutionStateEnum::Unrecognized(raw_state) => {
+                ank_base::execution_state::ExecutionStateEnum::Unrecognized(raw_state)
+            }
         }
     }
 }
 
+// [impl->swdd~common-decodes-unrecognized-execution-states~1]
 impl From<ank_base::execution_state::ExecutionStateEnum> for ExecutionStateEnum {
     fn from(item: ank_base::execution_state::ExecutionStateEnum) -> Self {
+        use ank_base::execution_state::ExecutionStateEnum as ProtoExecutionStateEnum;
         match item {
-            ank_base::execution_state::ExecutionStateEnum::AgentDisconnected(_) => {
-                ExecutionStateEnum::AgentDisconnected
-            }
-            ank_base::execution_state::ExecutionStateEnum::Pending(value) => {
+            ProtoExecutionStateEnum::AgentDisconnected(_) => ExecutionStateEnum::AgentDisconnected,
+            ProtoExecutionStateEnum::Pending(value)
+                if ank_base::Pending::from_i32(value).is_some() =>
+            {
                 ExecutionStateEnum::Pending(value.into())
             }
-            ank_base::execution_state::ExecutionStateEnum::Running(value) => {
+            ProtoExecutionStateEnum::Running(value)
+                if ank_base::Running::from_i32(value).is_some() =>
+            {
                 ExecutionStateEnum::Running(value.into())
             }
-            ank_base::execution_state::ExecutionStateEnum::Stopping(value) => {
+            ProtoExecutionStateEnum::Stopping(value)
+                if ank_base::Stopping::from_i32(value).is_some() =>
+            {
                 ExecutionStateEnum::Stopping(value.into())
             }
-            ank_base::execution_state::ExecutionStateEnum::Succeeded(value) => {
+            ProtoExecutionStateEnum::Succeeded(value)
+                if ank_base::Succeeded::from_i32(value).is_some() =>
+            {
                 ExecutionStateEnum::Succeeded(value.into())
             }
-            ank_base::execution_state::ExecutionStateEnum::Failed(value) => {
+            ProtoExecutionStateEnum::Failed(value)
+                if ank_base::Failed::from_i32(value).is_some() =>
+            {
                 ExecutionStateEnum::Failed(value.into())
             }
-            ank_base::execution_state::ExecutionStateEnum::NotScheduled(_) => {
-                ExecutionStateEnum::NotScheduled
+            ProtoExecutionStateEnum::NotScheduled(_) => ExecutionStateEnum::NotScheduled,
+            ProtoExecutionStateEnum::Removed(_) => ExecutionStateEnum::Removed,
+            ProtoExecutionStateEnum::Unrecognized(raw_state) => {
+                ExecutionStateEnum::Unrecognized(raw_state)
             }
-            ank_base::execution_state::ExecutionStateEnum::Removed(_) => {
-                ExecutionStateEnum::Removed
+            // a substate added in a newer version, e.g., "Pending(9)"
+            unrecognized => {
+                log::debug!(
+                    "Received the unrecognized execution state '{:?}'.",
+                    unrecognized
+                );
+                ExecutionStateEnum::Unrecognized(format!("{unrecognized:?}"))
             }
         }
     }
@@ -281,6 +386,14 @@ impl ExecutionState {
         ExecutionStateEnum::Running(RunningSubstate::Ok) == self.state
     }
 
+    // A slow start is still a start the agent waits for.
+    pub fn is_starting(&self) -> bool {
+        matches!(
+            self.state,
+            ExecutionStateEnum::Pending(PendingSubstate::Starting | PendingSubstate::StartingSlow)
+        )
+    }
+
     pub fn is_succeeded(&self) -> bool {
         ExecutionStateEnum::Succeeded(SucceededSubstate::Ok) == self.state
     }
@@ -289,6 +402,17 @@ impl ExecutionState {
         ExecutionStateEnum::Failed(FailedSubstate::ExecFailed) == self.state
     }
 
+    // The actual state of the workload is not known, e.g. because the agent is disconnected
+    // or the state has not been refreshed in time.
+    pub fn is_unknown(&self) -> bool {
+        matches!(
+            self.state,
+            ExecutionStateEnum::AgentDisconnected
+                | ExecutionStateEnum::Failed(FailedSubstate::Unknown)
+                | ExecutionStateEnum::Unrecognized(_)
+        )
+    }
+
     pub fn is_not_pending_nor_running(&self) -> bool {
         !self.is_pending() && !self.is_running()
     }
@@ -297,6 +421,10 @@ impl ExecutionState {
         ExecutionStateEnum::Pending(PendingSubstate::WaitingToStart) == self.state
     }
 
+    pub fn is_stopping(&self) -> bool {
+        matches!(self.state, ExecutionStateEnum::Stopping(_))
+    }
+
     pub fn is_waiting_to_stop(&self) -> bool {
         ExecutionStateEnum::Stopping(StoppingSubstate::WaitingToStop) == self.state
     }
@@ -336,6 +464,17 @@ impl ExecutionState {
         }
     }
 
+    pub fn unrecognized(raw_state: impl ToString) -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Unrecognized(raw_state.to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn stale() -> Self {
+        ExecutionState::unknown(STALE_MSG)
+    }
+
     pub fn starting(additional_info: impl ToString) -> Self {
         ExecutionState {
             state: ExecutionStateEnum::Pending(PendingSubstate::Starting),
@@ -406,6 +545,58 @@ impl ExecutionState {
         }
     }
 
+    pub fn waiting_for_degraded_mode() -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Pending(PendingSubstate::WaitingToStart),
+            additional_info: WAITING_FOR_DEGRADED_MODE_MSG.to_string(),
+        }
+    }
+
+    pub fn deadline_exceeded() -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Pending(PendingSubstate::DeadlineExceeded),
+            ..Default::default()
+        }
+    }
+
+    pub fn dependency_timeout() -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Pending(PendingSubstate::DependencyTimeout),
+            ..Default::default()
+        }
+    }
+
+    pub fn dependency_cycle(cycle: &str) -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Pending(PendingSubstate::DependencyCycle),
+            additional_info: cycle.to_string(),
+        }
+    }
+
+    pub fn waiting_for_schedule() -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Pending(PendingSubstate::WaitingForSchedule),
+            ..Default::default()
+        }
+    }
+
+    pub fn insufficient_resources(missing_resources: &str) -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Pending(PendingSubstate::InsufficientResources),
+            additional_info: missing_resources.to_string(),
+        }
+    }
+
+    // [impl->swdd~agent-reports-slow-workload-starts~1]
+    pub fn starting_slow(expected_startup_time_ms: u64) -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Pending(PendingSubstate::StartingSlow),
+            additional_info: format!(
+                "Not running after the expected startup time of {expected_startup_time_ms} ms."
+            ),
+        }
+    }
+
     pub fn waiting_to_stop() -> Self {
         ExecutionState {
             state: ExecutionStateEnum::Stopping(StoppingSubstate::WaitingToStop),
@@ -441,12 +632,11 @@ impl From<ank_base::ExecutionState> for ExecutionState {
     fn from(item: ank_base::ExecutionState) -> Self {
         ExecutionState {
             additional_info: item.additional_info,
+            // [impl->swdd~common-decodes-unrecognized-execution-states~1]
+            // a state added in a newer version is dropped by the decoder
             state: item
                 .execution_state_enum
-                .unwrap_or(ank_base::execution_state::ExecutionStateEnum::Failed(
-                    ank_base::Failed::Unknown as i32,
-                ))
-                .into(),
+                .map_or(ExecutionStateEnum::Unrecognized(String::new()), Into::into),
         }
     }
 }
@@ -464,6 +654,10 @@ impl Display for ExecutionStateEnum {
             ExecutionStateEnum::Failed(substate) => write!(f, "Failed({substate})"),
             ExecutionStateEnum::NotScheduled => write!(f, "NotScheduled"),
             ExecutionStateEnum::Removed => write!(f, "Removed"),
+            ExecutionStateEnum::Unrecognized(raw_state) if raw_state.is_empty() => {
+                write!(f, "Unrecognized")
+            }
+            ExecutionStateEnum::Unrecognized(raw_state) => write!(f, "Unrecognized({raw_state})"),
         }
     }
 }
@@ -478,12 +672,26 @@ impl Display for ExecutionState {
     }
 }
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize, Eq)]
 #[serde(default, rename_all = "camelCase")]
 pub struct WorkloadState {
     // [impl->swdd~common-workload-state-identification~1s]
     pub instance_name: WorkloadInstanceName,
     pub execution_state: ExecutionState,
+    // [impl->swdd~common-workload-state-timestamps~1]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_timestamp: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_timestamp: Option<u64>,
+}
+
+// The timestamps describe when a state was reported and received, but not the state itself.
+// A state reported again with a later timestamp is therefore still the same state.
+// [impl->swdd~common-workload-state-timestamps~1]
+impl PartialEq for WorkloadState {
+    fn eq(&self, other: &Self) -> bool {
+        self.instance_name == other.instance_name && self.execution_state == other.execution_state
+    }
 }
 
 impl From<WorkloadState> for ank_base::WorkloadState {
@@ -491,6 +699,8 @@ impl From<WorkloadState> for ank_base::WorkloadState {
         ank_base::WorkloadState {
             instance_name: Some(item.instance_name.into()),
             execution_state: Some(item.execution_state.into()),
+            agent_timestamp: item.agent_timestamp.unwrap_or_default(),
+            server_timestamp: item.server_timestamp.unwrap_or_default(),
         }
     }
 }
@@ -498,7 +708,10 @@ impl From<WorkloadState> for ank_base::WorkloadState {
 impl From<ank_base::WorkloadState> for WorkloadState {
     fn from(item: ank_base::WorkloadState) -> Self {
         WorkloadState {
-            instance_name: item.instance_name.unwrap_or_unreachable().into(),
+            instance_name: item
+                .instance_name
+                .unwrap_or_else(|| unreachable!())
+                .into(),
             execution_state: item
                 .execution_state
                 .unwrap_or(ank_base::ExecutionState {
@@ -510,6 +723,8 @@ impl From<ank_base::WorkloadState> for WorkloadState {
                     ),
                 })
                 .into(),
+            agent_timestamp: Some(item.agent_timestamp).filter(|timestamp| *timestamp != 0),
+            server_timestamp: Some(item.server_timestamp).filter(|timestamp| *timestamp != 0),
         }
     }
 }
@@ -535,6 +750,8 @@ pub fn generate_test_workload_state_with_agent(
             .config(&"config".to_string())
             .build(),
         execution_state,
+        agent_timestamp: None,
+        server_timestamp: None,
     }
 }
 #[cfg(any(feature = "test_utils", test))]
@@ -545,6 +762,8 @@ pub fn generate_test_workload_state_with_workload_spec(
     WorkloadState {
         instance_name: workload_spec.instance_name.clone(),
         execution_state,
+        agent_timestamp: None,
+        server_timestamp: None,
     }
 }
 
@@ -563,7 +782,8 @@ mod tests {
     use api::ank_base::{self};
 
     use crate::objects::{
-        workload_state::NO_MORE_RETRIES_MSG, ExecutionState, WorkloadInstanceName, WorkloadState,
+        workload_state::NO_MORE_RETRIES_MSG, ExecutionState, ExecutionStateEnum,
+        WorkloadInstanceName, WorkloadState,
     };
 
     // [utest->swdd~common-workload-state-transitions~1]
@@ -612,6 +832,111 @@ mod tests {
         );
     }
 
+    #[test]
+    fn utest_execution_state_is_unknown() {
+        assert!(ExecutionState::stale().is_unknown());
+        assert!(ExecutionState::unknown("no state").is_unknown());
+        assert!(ExecutionState::agent_disconnected().is_unknown());
+        assert!(!ExecutionState::running().is_unknown());
+        assert!(!ExecutionState::failed("crashed").is_unknown());
+        assert!(ExecutionState::unrecognized("Hibernating").is_unknown());
+    }
+
+    // [utest->swdd~agent-reports-slow-workload-starts~1]
+    #[test]
+    fn utest_execution_state_starting_slow() {
+        let starting_slow = ExecutionState::starting_slow(5000);
+        assert!(starting_slow.is_starting());
+        assert!(starting_slow.is_pending());
+
+        let proto_starting_slow = ank_base::ExecutionState {
+            additional_info: "Not running after the expected startup time of 5000 ms.".to_string(),
+            execution_state_enum: Some(ank_base::execution_state::ExecutionStateEnum::Pending(
+                ank_base::Pending::StartingSlow.into(),
+            )),
+        };
+        assert_eq!(
+            ank_base::ExecutionState::from(starting_slow.clone()),
+            proto_starting_slow
+        );
+        assert_eq!(ExecutionState::from(proto_starting_slow), starting_slow);
+
+        assert_eq!(
+            ExecutionState::starting("").transition(starting_slow.clone()),
+            starting_slow
+        );
+        assert_eq!(
+            ExecutionState::running().transition(starting_slow),
+            ExecutionState::running()
+        );
+    }
+
+    // [utest->swdd~common-decodes-unrecognized-execution-states~1]
+    #[test]
+    fn utest_execution_state_deserializes_unrecognized_states() {
+        let known: ExecutionState = serde_json::from_str(
+            r#"{"state":"Pending","subState":"WaitingToStart","additionalInfo":""}"#,
+        )
+        .unwrap();
+        assert_eq!(known, ExecutionState::waiting_to_start());
+
+        let unrecognized_state: ExecutionState = serde_json::from_str(
+            r#"{"state":"Hibernating","subState":"Deep","additionalInfo":"zzz"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            unrecognized_state,
+            ExecutionState {
+                state: ExecutionStateEnum::Unrecognized("Hibernating(Deep)".to_string()),
+                additional_info: "zzz".to_string(),
+            }
+        );
+
+        let unrecognized_substate: ExecutionState =
+            serde_yaml::from_str("state: Running\nsubState: Degraded\n").unwrap();
+        assert_eq!(
+            unrecognized_substate,
+            ExecutionState::unrecognized("Running(Degraded)")
+        );
+        assert_eq!(
+            unrecognized_substate.to_string(),
+            "Unrecognized(Running(Degraded))"
+        );
+
+        // the raw state survives the round trip
+        let round_trip: ExecutionState =
+            serde_json::from_str(&serde_json::to_string(&unrecognized_substate).unwrap()).unwrap();
+        assert_eq!(round_trip, unrecognized_substate);
+    }
+
+    // [utest->swdd~common-decodes-unrecognized-execution-states~1]
+    #[test]
+    fn utest_execution_state_from_proto_with_unrecognized_states() {
+        assert_eq!(
+            ExecutionState::from(ank_base::ExecutionState {
+                additional_info: "".to_string(),
+                execution_state_enum: Some(ank_base::execution_state::ExecutionStateEnum::Pending(
+                    42
+                )),
+            }),
+            ExecutionState::unrecognized("Pending(42)")
+        );
+        assert_eq!(
+            ExecutionState::from(ank_base::ExecutionState {
+                additional_info: "".to_string(),
+                execution_state_enum: None,
+            }),
+            ExecutionState::unrecognized("")
+        );
+
+        let proto_state: ank_base::ExecutionState =
+            ExecutionState::unrecognized("Hibernating(Deep)").into();
+        assert_eq!(
+            ExecutionState::from(proto_state),
+            ExecutionState::unrecognized("Hibernating(Deep)")
+        );
+    }
+
     // [utest->swdd~common-workload-state-identification~1]
     #[test]
     fn utest_converts_to_proto_workload_state() {
@@ -622,6 +947,8 @@ mod tests {
                 .workload_name("john")
                 .agent_name("strange")
                 .build(),
+            agent_timestamp: None,
+            server_timestamp: None,
         };
 
         let proto_wl_state = ank_base::WorkloadState {
@@ -636,6 +963,8 @@ mod tests {
                 agent_name: "strange".to_string(),
                 ..Default::default()
             }),
+            agent_timestamp: 0,
+            server_timestamp: 0,
         };
 
         assert_eq!(
@@ -653,6 +982,8 @@ mod tests {
                 .workload_name("john")
                 .agent_name("strange")
                 .build(),
+            agent_timestamp: None,
+            server_timestamp: None,
         };
 
         let proto_wl_state = ank_base::WorkloadState {
@@ -667,11 +998,49 @@ mod tests {
                 agent_name: "strange".to_string(),
                 ..Default::default()
             }),
+            agent_timestamp: 0,
+            server_timestamp: 0,
         };
 
         assert_eq!(WorkloadState::from(proto_wl_state), ankaios_wl_state);
     }
 
+    // [utest->swdd~common-workload-state-timestamps~1]
+    #[test]
+    fn utest_workload_state_timestamps_converted_and_ignored_in_comparison() {
+        let ankaios_wl_state = WorkloadState {
+            execution_state: ExecutionState::running(),
+            instance_name: WorkloadInstanceName::builder()
+                .workload_name("john")
+                .agent_name("strange")
+                .build(),
+            agent_timestamp: Some(1000),
+            server_timestamp: None,
+        };
+
+        let proto_wl_state = ank_base::WorkloadState::from(ankaios_wl_state.clone());
+        assert_eq!(proto_wl_state.agent_timestamp, 1000);
+        assert_eq!(proto_wl_state.server_timestamp, 0);
+
+        let converted_wl_state = WorkloadState::from(proto_wl_state);
+        assert_eq!(converted_wl_state.agent_timestamp, Some(1000));
+        assert_eq!(converted_wl_state.server_timestamp, None);
+
+        let received_wl_state = WorkloadState {
+            agent_timestamp: Some(500),
+            server_timestamp: Some(2000),
+            ..ankaios_wl_state.clone()
+        };
+        assert_eq!(received_wl_state, ankaios_wl_state);
+        assert_ne!(
+            WorkloadState {
+                execution_state: ExecutionState::succeeded(),
+                ..received_wl_state
+            },
+            ankaios_wl_state
+        );
+    }
+
     // [utest->swdd~common-workload-state-additional-information~1]
     // [utest->swdd~common-workload-states-supported-states~1]
     #[test]
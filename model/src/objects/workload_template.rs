@@ -0,0 +1,174 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use api::ank_base;
+use serde::{Deserialize, Serialize};
+
+use crate::serialize_to_ordered_map;
+
+const PLACEHOLDER_START: &str = "{{";
+const PLACEHOLDER_END: &str = "}}";
+
+// [impl->swdd~common-workload-templates-in-state~1]
+#[derive(Debug, Serialize, Default, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadTemplate {
+    pub runtime: String,
+    pub runtime_config: String,
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub parameters: HashMap<String, String>,
+}
+
+impl WorkloadTemplate {
+    // [impl->swdd~common-renders-runtime-config-of-workload-template~1]
+    // The given parameters take precedence over the default values of the template.
+    pub fn render_runtime_config(
+        &self,
+        parameters: &HashMap<String, String>,
+    ) -> Result<String, String> {
+        let mut rendered = String::with_capacity(self.runtime_config.len());
+        let mut remaining = self.runtime_config.as_str();
+        while let Some(start) = remaining.find(PLACEHOLDER_START) {
+            rendered.push_str(&remaining[..start]);
+            let after_start = &remaining[start + PLACEHOLDER_START.len()..];
+            let end = after_start
+                .find(PLACEHOLDER_END)
+                .ok_or_else(|| "Unterminated placeholder in the runtime config.".to_string())?;
+            let name = after_start[..end].trim();
+            let value = parameters
+                .get(name)
+                .or_else(|| self.parameters.get(name))
+                .ok_or_else(|| format!("Template parameter '{}' is not set.", name))?;
+            rendered.push_str(value);
+            remaining = &after_start[end + PLACEHOLDER_END.len()..];
+        }
+        rendered.push_str(remaining);
+        Ok(rendered)
+    }
+}
+
+impl From<WorkloadTemplate> for ank_base::WorkloadTemplate {
+    fn from(item: WorkloadTemplate) -> Self {
+        ank_base::WorkloadTemplate {
+            runtime: item.runtime,
+            runtime_config: item.runtime_config,
+            parameters: item.parameters,
+        }
+    }
+}
+
+impl From<ank_base::WorkloadTemplate> for WorkloadTemplate {
+    fn from(item: ank_base::WorkloadTemplate) -> Self {
+        WorkloadTemplate {
+            runtime: item.runtime,
+            runtime_config: item.runtime_config,
+            parameters: item.parameters,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use api::ank_base;
+
+    use super::WorkloadTemplate;
+
+    fn generate_test_workload_template() -> WorkloadTemplate {
+        WorkloadTemplate {
+            runtime: "podman".to_string(),
+            runtime_config:
+                "image: sensor:{{tag}}\ncommandOptions: [\"-e\", \"SENSOR={{ sensor }}\"]"
+                    .to_string(),
+            parameters: HashMap::from([("tag".to_string(), "latest".to_string())]),
+        }
+    }
+
+    // [utest->swdd~common-renders-runtime-config-of-workload-template~1]
+    #[test]
+    fn utest_render_runtime_config_uses_parameters_and_defaults() {
+        let template = generate_test_workload_template();
+
+        assert_eq!(
+            template.render_runtime_config(&HashMap::from([(
+                "sensor".to_string(),
+                "front".to_string()
+            )])),
+            Ok("image: sensor:latest\ncommandOptions: [\"-e\", \"SENSOR=front\"]".to_string())
+        );
+        assert_eq!(
+            template.render_runtime_config(&HashMap::from([
+                ("sensor".to_string(), "rear".to_string()),
+                ("tag".to_string(), "1.0".to_string()),
+            ])),
+            Ok("image: sensor:1.0\ncommandOptions: [\"-e\", \"SENSOR=rear\"]".to_string())
+        );
+    }
+
+    // [utest->swdd~common-renders-runtime-config-of-workload-template~1]
+    #[test]
+    fn utest_render_runtime_config_fails_on_missing_parameter() {
+        let template = generate_test_workload_template();
+
+        assert_eq!(
+            template.render_runtime_config(&HashMap::new()),
+            Err("Template parameter 'sensor' is not set.".to_string())
+        );
+    }
+
+    // [utest->swdd~common-renders-runtime-config-of-workload-template~1]
+    #[test]
+    fn utest_render_runtime_config_fails_on_unterminated_placeholder() {
+        let template = WorkloadTemplate {
+            runtime_config: "image: sensor:{{tag".to_string(),
+            ..Default::default()
+        };
+
+        assert!(template
+            .render_runtime_config(&HashMap::from([("tag".to_string(), "1.0".to_string())]))
+            .is_err());
+    }
+
+    // [utest->swdd~common-workload-templates-in-state~1]
+    #[test]
+    fn utest_converts_workload_template_to_and_from_proto() {
+        let template = generate_test_workload_template();
+        let proto_template = ank_base::WorkloadTemplate {
+            runtime: template.runtime.clone(),
+            runtime_config: template.runtime_config.clone(),
+            parameters: template.parameters.clone(),
+        };
+
+        assert_eq!(
+            ank_base::WorkloadTemplate::from(template.clone()),
+            proto_template
+        );
+        assert_eq!(WorkloadTemplate::from(proto_template), template);
+    }
+}
@@ -0,0 +1,455 @@
+// Copyright (c) 2023 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use api::ank_base;
+
+use crate::objects::{
+    generate_test_workload_spec_with_param, AddCondition, AgentInfo, CompleteState, ConfigObject,
+    DeleteCondition, DeletedWorkload, ExecutionState, LogLevel, RestartPolicy, State,
+    StoredWorkloadSpec, Tag, WorkloadInstanceName, WorkloadSpec, WorkloadState,
+};
+
+const TEST_API_VERSION: &str = "v0.1";
+const TEST_AGENT_NAME: &str = "agent";
+const TEST_RUNTIME_NAME: &str = "runtime";
+
+// [impl->swdd~common-provides-test-data-builders~1]
+/// Fluent builder for workload specs used in tests. The values not set explicitly are the ones
+/// of [`generate_test_workload_spec_with_param`].
+pub struct TestWorkloadSpecBuilder {
+    workload_name: String,
+    agent_name: String,
+    workload_spec: WorkloadSpec,
+}
+
+impl TestWorkloadSpecBuilder {
+    pub fn new(workload_name: impl Into<String>) -> Self {
+        let workload_name = workload_name.into();
+        TestWorkloadSpecBuilder {
+            workload_spec: generate_test_workload_spec_with_param(
+                TEST_AGENT_NAME.to_owned(),
+                workload_name.clone(),
+                TEST_RUNTIME_NAME.to_owned(),
+            ),
+            agent_name: TEST_AGENT_NAME.to_owned(),
+            workload_name,
+        }
+    }
+
+    pub fn agent(mut self, agent_name: impl Into<String>) -> Self {
+        self.agent_name = agent_name.into();
+        self
+    }
+
+    pub fn runtime(mut self, runtime: impl Into<String>) -> Self {
+        self.workload_spec.runtime = runtime.into();
+        self
+    }
+
+    pub fn runtime_config(mut self, runtime_config: impl Into<String>) -> Self {
+        self.workload_spec.runtime_config = runtime_config.into();
+        self
+    }
+
+    pub fn restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.workload_spec.restart_policy = restart_policy;
+        self
+    }
+
+    pub fn dependency(mut self, workload_name: impl Into<String>, condition: AddCondition) -> Self {
+        self.workload_spec
+            .dependencies
+            .insert(workload_name.into(), condition);
+        self
+    }
+
+    pub fn without_dependencies(mut self) -> Self {
+        self.workload_spec.dependencies.clear();
+        self
+    }
+
+    pub fn tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.workload_spec.tags.push(Tag {
+            key: key.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    pub fn without_tags(mut self) -> Self {
+        self.workload_spec.tags.clear();
+        self
+    }
+
+    pub fn config(mut self, config_name: impl Into<String>, config: ConfigObject) -> Self {
+        self.workload_spec
+            .configs
+            .insert(config_name.into(), config);
+        self
+    }
+
+    pub fn log_level(mut self, level: impl Into<String>, live_update: bool) -> Self {
+        self.workload_spec.log_level = Some(LogLevel {
+            level: level.into(),
+            live_update,
+        });
+        self
+    }
+
+    pub fn build(self) -> WorkloadSpec {
+        let mut workload_spec = self.workload_spec;
+        workload_spec.instance_name = WorkloadInstanceName::builder()
+            .agent_name(self.agent_name)
+            .workload_name(self.workload_name)
+            .config(&workload_spec.runtime_config)
+            .build();
+        workload_spec
+    }
+
+    pub fn build_stored(self) -> StoredWorkloadSpec {
+        self.build().into()
+    }
+}
+
+// [impl->swdd~common-provides-test-data-builders~1]
+/// Fluent builder for complete states used in tests.
+pub struct TestCompleteStateBuilder {
+    complete_state: CompleteState,
+}
+
+impl Default for TestCompleteStateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestCompleteStateBuilder {
+    pub fn new() -> Self {
+        let mut complete_state = CompleteState::default();
+        complete_state.desired_state.api_version = TEST_API_VERSION.into();
+        TestCompleteStateBuilder { complete_state }
+    }
+
+    /// Adds the workload to the desired state without an execution state.
+    pub fn workload(mut self, workload_spec: WorkloadSpec) -> Self {
+        let workload_name = workload_spec.instance_name.workload_name().to_owned();
+        self.complete_state
+            .desired_state
+            .workloads
+            .insert(workload_name, workload_spec.into());
+        self
+    }
+
+    /// Adds the workload to the desired state together with the execution state running.
+    pub fn running_workload(self, workload_spec: WorkloadSpec) -> Self {
+        let instance_name = workload_spec.instance_name.clone();
+        self.workload(workload_spec)
+            .workload_state(instance_name, ExecutionState::running())
+    }
+
+    pub fn workload_state(
+        mut self,
+        instance_name: WorkloadInstanceName,
+        execution_state: ExecutionState,
+    ) -> Self {
+        self.complete_state.workload_states.push(WorkloadState {
+            instance_name,
+            execution_state,
+            agent_timestamp: None,
+            server_timestamp: None,
+        });
+        self
+    }
+
+    pub fn config(mut self, config_name: impl Into<String>, config: ConfigObject) -> Self {
+        self.complete_state
+            .desired_state
+            .configs
+            .insert(config_name.into(), config);
+        self
+    }
+
+    pub fn agent(mut self, agent_name: impl Into<String>) -> Self {
+        self.complete_state.system.agents.push(AgentInfo {
+            agent_name: agent_name.into(),
+            ..Default::default()
+        });
+        self
+    }
+
+    pub fn build(self) -> CompleteState {
+        self.complete_state
+    }
+}
+
+pub fn generate_test_state_from_workloads(workloads: Vec<WorkloadSpec>) -> State {
+    workloads
+        .into_iter()
+        .fold(
+            TestCompleteStateBuilder::new(),
+            TestCompleteStateBuilder::workload,
+        )
+        .build()
+        .desired_state
+}
+
+pub fn generate_test_complete_state(workloads: Vec<WorkloadSpec>) -> CompleteState {
+    workloads
+        .into_iter()
+        .fold(
+            TestCompleteStateBuilder::new(),
+            TestCompleteStateBuilder::running_workload,
+        )
+        .build()
+}
+
+pub fn generate_test_state() -> State {
+    let workload_name_1 = "workload_name_1".to_string();
+    let workload_name_2 = "workload_name_2".to_string();
+
+    let mut ankaios_workloads = HashMap::new();
+
+    let workload_1 = generate_test_workload_spec_with_param(
+        "agent".to_owned(),
+        "workload_name_1".to_owned(),
+        "runtime".to_owned(),
+    );
+
+    let workload_2 = generate_test_workload_spec_with_param(
+        "agent".to_owned(),
+        "workload_name_2".to_owned(),
+        "runtime".to_owned(),
+    );
+
+    ankaios_workloads.insert(workload_name_1, workload_1.into());
+    ankaios_workloads.insert(workload_name_2, workload_2.into());
+
+    State {
+        api_version: "v0.1".into(),
+        workloads: ankaios_workloads,
+        configs: HashMap::new(),
+        workload_templates: HashMap::new(),
+        modes: vec![],
+        active_mode: String::new(),
+        active_profile: String::new(),
+        workload_groups: HashMap::new(),
+    }
+}
+
+pub fn generate_test_proto_state() -> ank_base::State {
+    let workload_name_1 = "workload_name_1".to_string();
+    let workload_name_2 = "workload_name_2".to_string();
+
+    let mut proto_workloads = HashMap::new();
+    proto_workloads.insert(workload_name_1, generate_test_proto_workload());
+    proto_workloads.insert(workload_name_2, generate_test_proto_workload());
+
+    ank_base::State {
+        api_version: "v0.1".into(),
+        workloads: proto_workloads,
+        configs: HashMap::new(),
+        workload_templates: HashMap::new(),
+        modes: vec![],
+        active_mode: String::new(),
+        active_profile: String::new(),
+        workload_groups: HashMap::new(),
+    }
+}
+
+fn generate_test_proto_dependencies() -> HashMap<String, i32> {
+    HashMap::from([
+        (
+            String::from("workload A"),
+            ank_base::AddCondition::AddCondRunning.into(),
+        ),
+        (
+            String::from("workload C"),
+            ank_base::AddCondition::AddCondSucceeded.into(),
+        ),
+    ])
+}
+
+fn generate_test_delete_dependencies() -> HashMap<String, DeleteCondition> {
+    HashMap::from([(
+        String::from("workload A"),
+        DeleteCondition::DelCondNotPendingNorRunning,
+    )])
+}
+
+pub fn generate_test_proto_workload() -> ank_base::Workload {
+    ank_base::Workload {
+        agent: String::from("agent"),
+        dependencies: generate_test_proto_dependencies(),
+        restart_policy: ank_base::RestartPolicy::Always.into(),
+        runtime: String::from("runtime"),
+        runtime_config: "generalOptions: [\"--version\"]\ncommandOptions: [\"--network=host\"]\nimage: alpine:latest\ncommandArgs: [\"bash\"]\n"
+            .to_string(),
+        tags: vec![ank_base::Tag {
+            key: "key".into(),
+            value: "value".into(),
+        }],
+        unknown_state_policies: HashMap::new(),
+        running_for_ms: HashMap::new(),
+        dependency_expression: None,
+        configs: vec![],
+        template: String::new(),
+        template_parameters: HashMap::new(),
+        enabled_if: String::new(),
+        disconnect_policy: ank_base::DisconnectPolicy::KeepRunning.into(),
+        log_forwarding: vec![],
+        log_level: None,
+        modes: vec![],
+        pre_shutdown_timeout_ms: 0,
+        dependency_timeout_ms: 0,
+        schedule: String::new(),
+        capture_output_bytes: 0,
+        resources: None,
+        on_dependency_failure: ank_base::DependencyFailurePolicy::Ignore.into(),
+        expected_startup_time_ms: 0,
+        control_interface_requests_per_minute: 0,
+        hooks: None,
+        agent_dependencies: vec![],
+        profile: String::new(),
+        profiles: HashMap::new(),
+        control_interface: ank_base::ControlInterfaceMode::Enabled.into(),
+        update_strategy: ank_base::UpdateStrategy::AtMostOnce.into(),
+        priority: 0,
+        managed_by: String::new(),
+    }
+}
+
+pub fn generate_test_deleted_workload(
+    agent: String,
+    name: String,
+) -> crate::objects::DeletedWorkload {
+    let instance_name = WorkloadInstanceName::builder()
+        .agent_name(agent)
+        .workload_name(name)
+        .config(&String::from("config"))
+        .build();
+    DeletedWorkload {
+        instance_name,
+        dependencies: generate_test_delete_dependencies(),
+    }
+}
+
+pub fn generate_test_deleted_workload_with_dependencies(
+    agent: String,
+    name: String,
+    dependencies: HashMap<String, DeleteCondition>,
+) -> crate::objects::DeletedWorkload {
+    let mut deleted_workload = generate_test_deleted_workload(agent, name);
+    deleted_workload.dependencies = dependencies;
+    deleted_workload
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::objects::{AddCondition, ExecutionState, RestartPolicy, Tag};
+
+    use super::{TestCompleteStateBuilder, TestWorkloadSpecBuilder};
+
+    // [utest->swdd~common-provides-test-data-builders~1]
+    #[test]
+    fn utest_test_workload_spec_builder_sets_fields() {
+        let workload_spec = TestWorkloadSpecBuilder::new("workload_1")
+            .agent("agent_A")
+            .runtime("podman")
+            .runtime_config("image: alpine:latest")
+            .restart_policy(RestartPolicy::Never)
+            .without_dependencies()
+            .dependency("workload_2", AddCondition::AddCondSucceeded)
+            .without_tags()
+            .tag("owner", "team_a")
+            .build();
+
+        assert_eq!(workload_spec.instance_name.workload_name(), "workload_1");
+        assert_eq!(workload_spec.instance_name.agent_name(), "agent_A");
+        assert_eq!(workload_spec.runtime, "podman");
+        assert_eq!(workload_spec.runtime_config, "image: alpine:latest");
+        assert_eq!(workload_spec.restart_policy, RestartPolicy::Never);
+        assert_eq!(
+            workload_spec.dependencies,
+            HashMap::from([("workload_2".to_owned(), AddCondition::AddCondSucceeded)])
+        );
+        assert_eq!(
+            workload_spec.tags,
+            vec![Tag {
+                key: "owner".to_owned(),
+                value: "team_a".to_owned(),
+            }]
+        );
+
+        // the instance name matches the one of the workload built by the server
+        let expected_instance_name = crate::objects::WorkloadInstanceName::builder()
+            .agent_name("agent_A")
+            .workload_name("workload_1")
+            .config(&"image: alpine:latest".to_owned())
+            .build();
+        assert_eq!(workload_spec.instance_name, expected_instance_name);
+    }
+
+    // [utest->swdd~common-provides-test-data-builders~1]
+    #[test]
+    fn utest_test_complete_state_builder_adds_workloads_and_states() {
+        let running = TestWorkloadSpecBuilder::new("running").build();
+        let pending = TestWorkloadSpecBuilder::new("pending").build();
+
+        let complete_state = TestCompleteStateBuilder::new()
+            .running_workload(running.clone())
+            .workload(pending.clone())
+            .workload_state(
+                pending.instance_name.clone(),
+                ExecutionState::waiting_to_start(),
+            )
+            .config(
+                "config_1",
+                HashMap::from([("key".to_owned(), "value".to_owned())]),
+            )
+            .agent("agent")
+            .build();
+
+        assert_eq!(complete_state.desired_state.api_version, "v0.1");
+        assert_eq!(
+            complete_state.desired_state.workloads.get("running"),
+            Some(&running.clone().into())
+        );
+        assert!(complete_state
+            .desired_state
+            .workloads
+            .contains_key("pending"));
+        assert!(complete_state
+            .desired_state
+            .configs
+            .contains_key("config_1"));
+        assert_eq!(complete_state.workload_states.len(), 2);
+        assert_eq!(
+            complete_state.workload_states[0].instance_name,
+            running.instance_name
+        );
+        assert_eq!(complete_state.system.agents[0].agent_name, "agent");
+    }
+}
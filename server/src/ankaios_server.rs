@@ -12,26 +12,64 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+mod agent_registry;
+mod config_check;
+mod control_interface_metrics;
 mod cycle_check;
+mod dependency_graph;
 mod delete_graph;
+mod impact_analysis;
+mod incident_detector;
+mod managed_by;
+mod request_lanes;
+mod rollout;
+mod scheduler_queues;
 mod server_state;
-
-use common::commands::{Request, UpdateWorkload};
+mod stale_state_reaper;
+mod state_watchers;
+mod update_deadline;
+mod update_workload_log;
+
+pub use config_check::check_config;
+pub use rollout::RolloutConfig;
+pub use stale_state_reaper::validate_stale_state_timeout;
+
+use common::commands::{
+    CompleteStateRequest, DrainAgentRequest, EventKind, RejectedWorkload, Request,
+    UpdateStateRequest, UpdateStateSuccess, WatchCompleteStateRequest,
+};
 use common::from_server_interface::{FromServerReceiver, FromServerSender};
-use common::objects::{CompleteState, DeletedWorkload, ExecutionState, State, WorkloadState};
+use common::objects::{
+    get_workloads_per_agent, CompleteState, DeletedWorkload, ExecutionState, ServerInfo, State,
+    StoredWorkloadSpec, SystemState, WorkloadSpec, WorkloadState,
+};
 
 use common::std_extensions::IllegalStateResult;
 use common::to_server_interface::{ToServerReceiver, ToServerSender};
 
+use agent_registry::AgentRegistry;
+use control_interface_metrics::ControlInterfaceMetricsStore;
+use incident_detector::IncidentDetector;
+use managed_by::Modifier;
+use request_lanes::RequestLanes;
+use rollout::RolloutManager;
+use scheduler_queues::SchedulerQueues;
 #[cfg_attr(test, mockall_double::double)]
 use server_state::ServerState;
+use stale_state_reaper::StaleStateReaper;
+use state_watchers::StateWatchers;
+use update_deadline::UpdateDeadlines;
+use update_workload_log::UpdateWorkloadLog;
 
+use crate::event_store::EventStore;
 use crate::workload_state_db::WorkloadStateDB;
 use common::{
     from_server_interface::{FromServer, FromServerInterface},
     to_server_interface::ToServer,
 };
 
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc::channel;
 
 pub type ToServerChannel = (ToServerSender, ToServerReceiver);
@@ -44,22 +82,148 @@ pub fn create_from_server_channel(capacity: usize) -> FromServerChannel {
     channel::<FromServer>(capacity)
 }
 
+// [impl->swdd~server-assigns-trace-id-to-requests~1]
+#[cfg(not(test))]
+fn generate_trace_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+#[cfg(test)]
+use tests::generate_trace_id_mock as generate_trace_id;
+
+const SYSTEM_FIELD: &str = "system";
+
+// [impl->swdd~server-provides-system-state~2]
+// An empty field mask requests the whole complete state.
+fn requests_system_state(field_mask: &[String]) -> bool {
+    field_mask.is_empty()
+        || field_mask
+            .iter()
+            .any(|mask| mask.split('.').next() == Some(SYSTEM_FIELD))
+}
+
 pub struct AnkaiosServer {
     // [impl->swdd~server-uses-async-channels~1]
     receiver: ToServerReceiver,
+    request_lanes: RequestLanes,
     // [impl->swdd~communication-to-from-server-middleware~1]
     to_agents: FromServerSender,
     server_state: ServerState,
     workload_state_db: WorkloadStateDB,
+    rollout_manager: RolloutManager,
+    agent_registry: AgentRegistry,
+    event_store: EventStore,
+    incident_detector: IncidentDetector,
+    stale_state_reaper: StaleStateReaper,
+    state_watchers: StateWatchers,
+    update_deadlines: UpdateDeadlines,
+    scheduler_queues: SchedulerQueues,
+    control_interface_metrics: ControlInterfaceMetricsStore,
+    start_time: Instant,
+    update_workload_log: UpdateWorkloadLog,
+    protect_managed_workloads: bool,
 }
 
 impl AnkaiosServer {
     pub fn new(receiver: ToServerReceiver, to_agents: FromServerSender) -> Self {
         AnkaiosServer {
             receiver,
+            request_lanes: RequestLanes::default(),
             to_agents,
             server_state: ServerState::default(),
             workload_state_db: WorkloadStateDB::default(),
+            rollout_manager: RolloutManager::default(),
+            agent_registry: AgentRegistry::default(),
+            event_store: EventStore::default(),
+            incident_detector: IncidentDetector::default(),
+            stale_state_reaper: StaleStateReaper::default(),
+            state_watchers: StateWatchers::default(),
+            update_deadlines: UpdateDeadlines::default(),
+            scheduler_queues: SchedulerQueues::default(),
+            control_interface_metrics: ControlInterfaceMetricsStore::default(),
+            start_time: Instant::now(),
+            update_workload_log: UpdateWorkloadLog::default(),
+            protect_managed_workloads: false,
+        }
+    }
+
+    // [impl->swdd~server-protects-managed-workloads~1]
+    pub fn protect_managed_workloads(&mut self) {
+        self.protect_managed_workloads = true;
+    }
+
+    // [impl->swdd~server-applies-staged-rollout-per-rollout-group~1]
+    pub fn enable_staged_rollout(&mut self, rollout_config: RolloutConfig) {
+        self.rollout_manager = RolloutManager::new(Some(rollout_config));
+    }
+
+    // [impl->swdd~server-persists-events~1]
+    pub fn set_event_store(&mut self, event_store: EventStore) {
+        self.event_store = event_store;
+    }
+
+    // [impl->swdd~server-standby-promotion~1]
+    pub fn restore_agents(&mut self, agents: Vec<common::objects::AgentInfo>) {
+        for agent_info in agents {
+            self.agent_registry.agent_restored(agent_info);
+        }
+    }
+
+    // [impl->swdd~server-retains-terminated-workload-states~1]
+    pub fn retain_terminated_workloads(&mut self, retention: Duration) {
+        self.workload_state_db.retain_terminated_workloads(retention);
+    }
+
+    // [impl->swdd~server-correlates-workload-failures-to-agent-incidents~1]
+    pub fn enable_incident_detection(&mut self, window: Duration, min_failed_workloads: usize) {
+        self.incident_detector = IncidentDetector::new(window, min_failed_workloads);
+    }
+
+    // [impl->swdd~server-reaps-stale-workload-states~2]
+    pub fn enable_stale_state_reaper(&mut self, timeout: Duration) {
+        self.stale_state_reaper = StaleStateReaper::new(timeout);
+    }
+
+    // [impl->swdd~server-numbers-update-workload~2]
+    // Every agent receives its own message, as the sequence numbers are counted per agent.
+    async fn send_update_workload(
+        &mut self,
+        added_workloads: Vec<WorkloadSpec>,
+        deleted_workloads: Vec<DeletedWorkload>,
+        deadline_ms: Option<u64>,
+    ) {
+        let workloads_per_agent: BTreeMap<_, _> =
+            get_workloads_per_agent(added_workloads, deleted_workloads)
+                .into_iter()
+                .collect();
+        let connected_agent_names = self.agent_registry.connected_agent_names();
+        for (agent_name, (added_workloads, deleted_workloads)) in workloads_per_agent {
+            let agent_connected = connected_agent_names.contains(&agent_name);
+            let update_workload = self.update_workload_log.next_update(
+                &agent_name,
+                added_workloads,
+                deleted_workloads,
+                false,
+                deadline_ms,
+                agent_connected,
+            );
+            self.to_agents
+                .send(FromServer::UpdateWorkload(update_workload))
+                .await
+                .unwrap_or_illegal_state();
+        }
+    }
+
+    fn get_system_state(&self) -> SystemState {
+        SystemState {
+            server: ServerInfo {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                uptime: self.start_time.elapsed().as_secs(),
+                request_lanes: self.request_lanes.get_lane_infos(),
+            },
+            agents: self.agent_registry.get_agents(),
+            terminated_workloads: self.workload_state_db.get_terminated_workloads(),
+            // [impl->swdd~server-provides-control-interface-metrics~1]
+            control_interface_metrics: self.control_interface_metrics.get_all(),
         }
     }
 
@@ -79,15 +243,9 @@ impl AnkaiosServer {
                     // [impl->swdd~server-sets-state-of-new-workloads-to-pending~1]
                     self.workload_state_db.initial_state(&added_workloads);
 
-                    let from_server_command = FromServer::UpdateWorkload(UpdateWorkload {
-                        added_workloads,
-                        deleted_workloads,
-                    });
                     log::info!("Starting...");
-                    self.to_agents
-                        .send(from_server_command)
-                        .await
-                        .unwrap_or_illegal_state();
+                    self.send_update_workload(added_workloads, deleted_workloads, None)
+                        .await;
                 }
                 Ok(None) => log::info!("No initial workloads to send to agents."),
                 Err(err) => {
@@ -110,11 +268,17 @@ impl AnkaiosServer {
     ) -> Vec<DeletedWorkload> {
         let mut deleted_states = vec![];
         deleted_workloads.retain(|deleted_wl| {
-            if deleted_wl.instance_name.agent_name().is_empty() {
-                self.workload_state_db.remove(&deleted_wl.instance_name);
+            let agent_name = deleted_wl.instance_name.agent_name();
+            // a disconnected agent cleans up its workloads on reconnect
+            // [impl->swdd~server-drains-agent~1]
+            if agent_name.is_empty() || self.agent_registry.is_disconnected(agent_name) {
+                // [impl->swdd~server-retains-terminated-workload-states~1]
+                self.workload_state_db.terminate(&deleted_wl.instance_name);
                 deleted_states.push(WorkloadState {
                     instance_name: deleted_wl.instance_name.clone(),
                     execution_state: ExecutionState::removed(),
+                    agent_timestamp: None,
+                    server_timestamp: None,
                 });
 
                 return false;
@@ -131,12 +295,594 @@ impl AnkaiosServer {
         deleted_workloads
     }
 
+    // Returns false if the update has been rejected.
+    async fn update_desired_state(
+        &mut self,
+        request_id: String,
+        trace_id: String,
+        update_state_request: UpdateStateRequest,
+        modifier: Option<Modifier>,
+    ) -> bool {
+        match self
+            .apply_desired_state(
+                &trace_id,
+                update_state_request.state,
+                update_state_request.update_mask,
+                update_state_request.deadline_ms,
+                update_state_request.rollback_on_deadline_exceeded,
+                modifier.as_ref(),
+            )
+            .await
+        {
+            Ok(update_state_success) => {
+                log::debug!(
+                    "Send UpdateStateSuccess for request '{}' (trace id '{}')",
+                    request_id,
+                    trace_id
+                );
+                // [impl->swdd~server-update-state-success-response~1]
+                // [impl->swdd~server-reports-rejected-workloads~1]
+                self.to_agents
+                    .update_state_success(
+                        request_id,
+                        trace_id,
+                        update_state_success.added_workloads,
+                        update_state_success.deleted_workloads,
+                        update_state_success.rejected_workloads,
+                    )
+                    .await
+                    .unwrap_or_illegal_state();
+                true
+            }
+            Err(message) => {
+                self.to_agents
+                    .error(request_id, trace_id, common::commands::Error { message })
+                    .await
+                    .unwrap_or_illegal_state();
+                false
+            }
+        }
+    }
+
+    // Returns the names of the added, deleted and rejected workloads or the reason for the
+    // rejection of the complete update.
+    async fn apply_desired_state(
+        &mut self,
+        trace_id: &str,
+        new_state: CompleteState,
+        update_mask: Vec<String>,
+        deadline_ms: Option<u64>,
+        rollback_on_deadline_exceeded: bool,
+        modifier: Option<&Modifier>,
+    ) -> Result<UpdateStateSuccess, String> {
+        // [impl->swdd~server-applies-staged-rollout-per-rollout-group~1]
+        if self.rollout_manager.is_in_progress() {
+            log::warn!(
+                "A staged rollout is in progress -> rejecting the update (trace id '{}').",
+                trace_id
+            );
+            return Err("Update rejected: a staged rollout is in progress.".to_string());
+        }
+
+        // [impl->swdd~server-rolls-back-update-on-exceeded-deadline~1]
+        // [impl->swdd~server-keeps-pending-rollout-groups-out-of-desired-state~1]
+        let previous_state = if (deadline_ms.is_some() && rollback_on_deadline_exceeded)
+            || self.rollout_manager.is_enabled()
+        {
+            self.get_stored_state()
+        } else {
+            None
+        };
+
+        // [impl->swdd~update-desired-state-with-update-mask~1]
+        // [impl->swdd~update-desired-state-empty-update-mask~1]
+        // [impl->swdd~server-state-records-managed-by~1]
+        let update_result = match modifier {
+            Some(modifier) => self
+                .server_state
+                .update_by(new_state, update_mask.clone(), modifier),
+            None => self.server_state.update(new_state, update_mask.clone()),
+        };
+        match update_result {
+            Ok(Some((mut added_workloads, mut deleted_workloads))) => {
+                // [impl->swdd~server-distributes-workloads-enabled-on-agent~1]
+                // [impl->swdd~server-reports-rejected-workloads~1]
+                let mut rejected_workloads = Vec::new();
+                added_workloads.retain(|workload| {
+                    let Some(reason) = self.agent_registry.rejection_reason(workload) else {
+                        return true;
+                    };
+                    log::warn!(
+                        "Workload '{}' is not started: {} (trace id '{}')",
+                        workload.instance_name.workload_name(),
+                        reason,
+                        trace_id
+                    );
+                    rejected_workloads.push(RejectedWorkload {
+                        workload_name: workload.instance_name.workload_name().to_string(),
+                        reason,
+                    });
+                    false
+                });
+
+                log::info!(
+                    "The update has {} new or updated workloads, {} workloads to delete (trace id '{}')",
+                    added_workloads.len(),
+                    deleted_workloads.len(),
+                    trace_id
+                );
+                // [impl->swdd~server-records-events~1]
+                // [impl->swdd~server-records-trace-id-in-events~1]
+                self.event_store.record_for_request(
+                    trace_id,
+                    EventKind::DesiredStateUpdated,
+                    None,
+                    format!(
+                        "The update has {} new or updated workloads, {} workloads to delete",
+                        added_workloads.len(),
+                        deleted_workloads.len()
+                    ),
+                );
+
+                let added_workloads_names = added_workloads
+                    .iter()
+                    .map(|x| x.instance_name.to_string())
+                    .collect();
+                let deleted_workloads_names = deleted_workloads
+                    .iter()
+                    .map(|x| x.instance_name.to_string())
+                    .collect();
+
+                // [impl->swdd~server-rolls-back-update-on-exceeded-deadline~1]
+                self.update_deadlines.cancel();
+                if let (Some(deadline_ms), Some(previous_state), true) = (
+                    deadline_ms,
+                    previous_state.as_ref(),
+                    rollback_on_deadline_exceeded,
+                ) {
+                    self.update_deadlines.track(
+                        Duration::from_millis(deadline_ms),
+                        trace_id.to_string(),
+                        previous_state.clone(),
+                        update_mask,
+                        added_workloads
+                            .iter()
+                            .map(|workload| workload.instance_name.clone())
+                            .collect(),
+                    );
+                }
+
+                // [impl->swdd~server-handles-deleted-workload-for-empty-agent~1]
+                deleted_workloads = self
+                    .handle_unscheduled_deleted_workloads(deleted_workloads)
+                    .await;
+
+                // [impl->swdd~server-applies-staged-rollout-per-rollout-group~1]
+                let (added_workloads, deleted_workloads) = if self.rollout_manager.is_enabled() {
+                    let new_state = self.get_stored_state().unwrap_or_default();
+                    let first_group_changes =
+                        self.rollout_manager
+                            .start(added_workloads, deleted_workloads, new_state);
+                    self.keep_pending_rollout_groups_out_of_desired_state(previous_state);
+                    first_group_changes
+                } else {
+                    (added_workloads, deleted_workloads)
+                };
+
+                // [impl->swdd~server-sets-state-of-new-workloads-to-pending~1]
+                self.workload_state_db.initial_state(&added_workloads);
+
+                // [impl->swdd~server-propagates-update-deadline~1]
+                self.send_update_workload(added_workloads, deleted_workloads, deadline_ms)
+                    .await;
+                Ok(UpdateStateSuccess {
+                    added_workloads: added_workloads_names,
+                    deleted_workloads: deleted_workloads_names,
+                    rejected_workloads,
+                })
+            }
+            Ok(None) => {
+                log::debug!(
+                    "The current state and new state are identical -> nothing to do (trace id '{}')",
+                    trace_id
+                );
+                Ok(UpdateStateSuccess::default())
+            }
+            Err(error_msg) => {
+                // [impl->swdd~server-continues-on-invalid-updated-state~1]
+                log::error!("Update rejected: '{error_msg}' (trace id '{trace_id}')");
+                Err(format!("Update rejected: '{error_msg}'"))
+            }
+        }
+    }
+
+    // [impl->swdd~server-rolls-back-update-on-exceeded-deadline~1]
+    async fn roll_back_update_on_exceeded_deadline(&mut self) {
+        let Some(rollback) = self.update_deadlines.take_rollback(&self.workload_state_db) else {
+            return;
+        };
+
+        for instance_name in &rollback.pending_workloads {
+            log::warn!(
+                "Workload '{}' has not been started before the deadline of the update (trace id '{}').",
+                instance_name.workload_name(),
+                rollback.trace_id
+            );
+            // [impl->swdd~server-records-events~1]
+            self.event_store.record_for_request(
+                &rollback.trace_id,
+                EventKind::UpdateDeadlineExceeded,
+                Some(instance_name.agent_name().to_string()),
+                format!(
+                    "Workload '{}' has not been started before the deadline of the update",
+                    instance_name.workload_name()
+                ),
+            );
+        }
+
+        log::info!(
+            "Rolling back the update with trace id '{}'.",
+            rollback.trace_id
+        );
+        if let Err(message) = self
+            .apply_desired_state(
+                &rollback.trace_id,
+                rollback.previous_state,
+                rollback.update_mask,
+                None,
+                false,
+                None,
+            )
+            .await
+        {
+            log::error!(
+                "Could not roll back the update with trace id '{}': {}",
+                rollback.trace_id,
+                message
+            );
+        }
+    }
+
+    // [impl->swdd~server-drains-agent~1]
+    async fn drain_agent(
+        &mut self,
+        request_id: String,
+        trace_id: String,
+        drain_agent_request: DrainAgentRequest,
+    ) {
+        let agent_name = drain_agent_request.agent_name;
+        // workloads without a target agent are unscheduled
+        let target_agent = drain_agent_request.target_agent.unwrap_or_default();
+
+        let mut new_state = CompleteState::default();
+        let mut update_mask = Vec::new();
+        for workload in self.server_state.get_workloads_for_agent(&agent_name) {
+            let workload_name = workload.instance_name.workload_name().to_owned();
+            let mut stored_workload: StoredWorkloadSpec = workload.into();
+            stored_workload.agent = target_agent.clone();
+            update_mask.push(format!("desiredState.workloads.{workload_name}"));
+            new_state
+                .desired_state
+                .workloads
+                .insert(workload_name, stored_workload);
+        }
+
+        // an empty update mask would replace the complete desired state
+        if update_mask.is_empty() {
+            self.to_agents
+                .update_state_success(request_id, trace_id.clone(), vec![], vec![], vec![])
+                .await
+                .unwrap_or_illegal_state();
+        } else if !self
+            .update_desired_state(
+                request_id,
+                trace_id.clone(),
+                UpdateStateRequest {
+                    state: new_state,
+                    update_mask,
+                    deadline_ms: None,
+                    rollback_on_deadline_exceeded: false,
+                    force: false,
+                },
+                None,
+            )
+            .await
+        {
+            return;
+        }
+
+        if drain_agent_request.remove && self.agent_registry.agent_removed(&agent_name) {
+            log::info!("Removed agent '{}' (trace id '{}')", agent_name, trace_id);
+            self.update_workload_log.remove_of_agent(&agent_name);
+            // [impl->swdd~server-records-events~1]
+            // [impl->swdd~server-records-trace-id-in-events~1]
+            self.event_store.record_for_request(
+                &trace_id,
+                EventKind::AgentRemoved,
+                Some(agent_name),
+                "Agent removed".to_string(),
+            );
+        }
+    }
+
+    fn get_stored_state(&self) -> Option<CompleteState> {
+        self.server_state
+            .get_complete_state_by_field_mask(
+                &CompleteStateRequest { field_mask: vec![] },
+                &self.workload_state_db,
+            )
+            .ok()
+            .map(|complete_state| CompleteState {
+                startup_state: complete_state.startup_state,
+                desired_state: complete_state.desired_state,
+                ..Default::default()
+            })
+    }
+
+    // [impl->swdd~server-keeps-pending-rollout-groups-out-of-desired-state~1]
+    fn keep_pending_rollout_groups_out_of_desired_state(
+        &mut self,
+        previous_state: Option<CompleteState>,
+    ) {
+        let pending_update_mask = self.rollout_manager.pending_update_mask();
+        if pending_update_mask.is_empty() {
+            return;
+        }
+        let Some(previous_state) = previous_state else {
+            log::error!("The previous state is unknown, the desired state contains rollout groups that are not started.");
+            return;
+        };
+        // the changes were already checked, the result only restores the previous workloads
+        if let Err(error) = self
+            .server_state
+            .update(previous_state, pending_update_mask)
+        {
+            log::error!(
+                "Could not keep the rollout groups that are not started out of the desired state: '{}'",
+                error
+            );
+        }
+    }
+
+    // [impl->swdd~server-halts-staged-rollout-on-elevated-failure-rate~1]
+    async fn continue_staged_rollout(&mut self) {
+        if let Some((added_workloads, deleted_workloads)) =
+            self.rollout_manager.advance(&self.workload_state_db)
+        {
+            // [impl->swdd~server-keeps-pending-rollout-groups-out-of-desired-state~1]
+            if let Some((new_state, update_mask)) = self.rollout_manager.current_group_update() {
+                if let Err(error) = self.server_state.update(new_state, update_mask) {
+                    log::error!(
+                        "Could not apply the rollout group to the desired state: '{}'",
+                        error
+                    );
+                }
+            }
+            // [impl->swdd~server-sets-state-of-new-workloads-to-pending~1]
+            self.workload_state_db.initial_state(&added_workloads);
+
+            self.send_update_workload(added_workloads, deleted_workloads, None)
+                .await;
+        }
+    }
+
+    // [impl->swdd~server-reaps-stale-workload-states~2]
+    async fn reap_stale_workload_states(&mut self) {
+        let stale_states = self.stale_state_reaper.reap(&mut self.workload_state_db);
+        if stale_states.is_empty() {
+            return;
+        }
+
+        for workload_state in &stale_states {
+            log::warn!(
+                "The execution state of workload '{}' has not been refreshed in time.",
+                workload_state.instance_name
+            );
+            // [impl->swdd~server-records-events~1]
+            self.event_store.record(
+                EventKind::WorkloadStateStale,
+                Some(workload_state.instance_name.agent_name().to_string()),
+                Some(workload_state.instance_name.workload_name().to_string()),
+                "The execution state has not been refreshed in time".to_string(),
+            );
+        }
+
+        self.to_agents
+            .update_workload_state(stale_states)
+            .await
+            .unwrap_or_illegal_state();
+    }
+
+    // [impl->swdd~server-correlates-workload-failures-to-agent-incidents~1]
+    fn record_agent_incident(&mut self, workload_state: &WorkloadState) {
+        let Some(affected_workloads) = self
+            .incident_detector
+            .record(workload_state, Instant::now())
+        else {
+            return;
+        };
+
+        let agent_name = workload_state.instance_name.agent_name();
+        let message = format!(
+            "{} workloads failed within {}s: '{}'",
+            affected_workloads.len(),
+            self.incident_detector.window().as_secs(),
+            affected_workloads.join("', '")
+        );
+        log::warn!("Incident on agent '{}': {}", agent_name, message);
+        self.event_store.record(
+            EventKind::AgentIncident,
+            Some(agent_name.to_string()),
+            None,
+            message,
+        );
+    }
+
+    // [impl->swdd~server-provides-state-watch~1]
+    async fn watch_complete_state(
+        &mut self,
+        request_id: String,
+        trace_id: String,
+        watch_request: WatchCompleteStateRequest,
+    ) {
+        // the system state is not watched as the uptime of the server changes every second
+        match self.server_state.get_complete_state_by_field_mask(
+            &watch_request.clone().into(),
+            &self.workload_state_db,
+        ) {
+            Ok(complete_state) => {
+                self.state_watchers.add(
+                    request_id.clone(),
+                    trace_id.clone(),
+                    watch_request.field_mask,
+                    complete_state.clone(),
+                );
+                self.to_agents
+                    .complete_state(request_id, trace_id, complete_state)
+                    .await
+                    .unwrap_or_illegal_state();
+            }
+            Err(error) => {
+                log::error!(
+                    "Failed to watch complete state: '{}' (trace id '{}')",
+                    error,
+                    trace_id
+                );
+                self.to_agents
+                    .error(
+                        request_id,
+                        trace_id,
+                        common::commands::Error {
+                            message: format!("Failed to watch complete state: '{error}'"),
+                        },
+                    )
+                    .await
+                    .unwrap_or_illegal_state();
+            }
+        }
+    }
+
+    // [impl->swdd~server-notifies-state-watchers-on-change~1]
+    async fn notify_state_watchers(&mut self) {
+        if self.state_watchers.is_empty() {
+            return;
+        }
+
+        let state_changes = self
+            .state_watchers
+            .changed_states(|complete_state_request| {
+                self.server_state.get_complete_state_by_field_mask(
+                    complete_state_request,
+                    &self.workload_state_db,
+                )
+            });
+        for state_change in state_changes {
+            match state_change.state {
+                Ok(complete_state) => {
+                    log::debug!(
+                        "Notifying watch '{}' about a state change (trace id '{}')",
+                        state_change.request_id,
+                        state_change.trace_id
+                    );
+                    self.to_agents
+                        .complete_state(
+                            state_change.request_id,
+                            state_change.trace_id,
+                            complete_state,
+                        )
+                        .await
+                        .unwrap_or_illegal_state();
+                }
+                Err(error) => {
+                    log::error!(
+                        "Ending watch '{}': '{}' (trace id '{}')",
+                        state_change.request_id,
+                        error,
+                        state_change.trace_id
+                    );
+                    self.to_agents
+                        .error(
+                            state_change.request_id,
+                            state_change.trace_id,
+                            common::commands::Error {
+                                message: format!("Failed to watch complete state: '{error}'"),
+                            },
+                        )
+                        .await
+                        .unwrap_or_illegal_state();
+                }
+            }
+        }
+    }
+
     async fn listen_to_agents(&mut self) {
         log::debug!("Start listening to agents...");
-        while let Some(to_server_command) = self.receiver.recv().await {
+        loop {
+            // [impl->swdd~server-queues-messages-in-priority-lanes~2]
+            while self.request_lanes.has_room() {
+                let Ok(to_server_command) = self.receiver.try_recv() else {
+                    break;
+                };
+                self.request_lanes.push(to_server_command);
+            }
+
+            // [impl->swdd~server-processes-priority-lanes-fairly~2]
+            let to_server_command = match self.request_lanes.pop() {
+                Some(to_server_command) => to_server_command,
+                None => {
+                    tokio::select! {
+                        to_server_command = self.receiver.recv() => match to_server_command {
+                            Some(to_server_command) => self.request_lanes.push(to_server_command),
+                            None => break,
+                        },
+                        _ = self.rollout_manager.soak_time_elapsed() => {
+                            self.continue_staged_rollout().await;
+                            self.notify_state_watchers().await;
+                        }
+                        _ = self.stale_state_reaper.check_due() => {
+                            self.reap_stale_workload_states().await;
+                            self.notify_state_watchers().await;
+                        }
+                        // [impl->swdd~server-rolls-back-update-on-exceeded-deadline~1]
+                        _ = self.update_deadlines.expired() => {
+                            self.roll_back_update_on_exceeded_deadline().await;
+                            self.notify_state_watchers().await;
+                        }
+                    }
+                    continue;
+                }
+            };
+
             match to_server_command {
                 ToServer::AgentHello(method_obj) => {
-                    log::info!("Received AgentHello from '{}'", method_obj.agent_name);
+                    log::info!(
+                        "Received AgentHello from '{}' with version '{}'",
+                        method_obj.agent_name,
+                        method_obj.agent_version
+                    );
+                    // [impl->swdd~server-reports-local-workloads-of-agents~1]
+                    self.agent_registry
+                        .agent_connected(common::objects::AgentInfo {
+                            agent_name: method_obj.agent_name.clone(),
+                            version: method_obj.agent_version.clone(),
+                            rollout_group: method_obj.rollout_group.clone(),
+                            runtimes: method_obj.runtimes.clone(),
+                            attributes: method_obj.attributes.clone(),
+                            local_workloads: method_obj.local_workloads.clone(),
+                            ..Default::default()
+                        });
+                    // [impl->swdd~server-records-events~1]
+                    self.event_store.record(
+                        EventKind::AgentConnected,
+                        Some(method_obj.agent_name.clone()),
+                        None,
+                        format!(
+                            "Agent connected with version '{}'",
+                            method_obj.agent_version
+                        ),
+                    );
+                    self.rollout_manager
+                        .set_agent_group(&method_obj.agent_name, method_obj.rollout_group);
 
                     // [impl->swdd~server-informs-a-newly-connected-agent-workload-states~1]
                     let workload_states = self
@@ -160,9 +906,20 @@ impl AnkaiosServer {
 
                     // Send this agent all workloads in the current state which are assigned to him
                     // [impl->swdd~agent-from-agent-field~1]
-                    let added_workloads = self
+                    // [impl->swdd~server-distributes-workloads-enabled-on-agent~1]
+                    let (added_workloads, disabled_workloads): (Vec<_>, Vec<_>) = self
                         .server_state
-                        .get_workloads_for_agent(&method_obj.agent_name);
+                        .get_workloads_for_agent(&method_obj.agent_name)
+                        .into_iter()
+                        .partition(|workload| self.agent_registry.is_workload_enabled(workload));
+                    for workload in disabled_workloads {
+                        log::debug!(
+                            "Workload '{}' is not enabled on agent '{}'",
+                            workload.instance_name.workload_name(),
+                            method_obj.agent_name
+                        );
+                        self.workload_state_db.remove(&workload.instance_name);
+                    }
 
                     log::debug!(
                         "Sending initial UpdateWorkload to agent '{}' with added workloads: '{:?}'",
@@ -170,21 +927,67 @@ impl AnkaiosServer {
                         added_workloads,
                     );
 
+                    // [impl->swdd~server-re-sends-unacknowledged-update-workload~1]
+                    // The initial workload list is sent last as it reflects the current state.
+                    for update_workload in self
+                        .update_workload_log
+                        .unacknowledged(&method_obj.agent_name)
+                    {
+                        log::debug!(
+                            "Re-sending unacknowledged UpdateWorkload '{}' to agent '{}'",
+                            update_workload.sequence_number,
+                            method_obj.agent_name
+                        );
+                        self.to_agents
+                            .send(FromServer::UpdateWorkload(update_workload))
+                            .await
+                            .unwrap_or_illegal_state();
+                    }
+
                     // [impl->swdd~server-sends-all-workloads-on-start~1]
+                    // [impl->swdd~server-numbers-update-workload~2]
+                    let update_workload = self.update_workload_log.next_update(
+                        &method_obj.agent_name,
+                        added_workloads,
+                        // It's a newly connected agent, no need to delete anything.
+                        vec![],
+                        true,
+                        None,
+                        true,
+                    );
                     self.to_agents
-                        .update_workload(
-                            added_workloads,
-                            // It's a newly connected agent, no need to delete anything.
-                            vec![],
-                        )
+                        .send(FromServer::UpdateWorkload(update_workload))
+                        .await
+                        .unwrap_or_illegal_state();
+
+                    // [impl->swdd~server-distributes-connected-agents~1]
+                    self.to_agents
+                        .update_connected_agents(self.agent_registry.connected_agent_names())
                         .await
                         .unwrap_or_illegal_state();
                 }
                 ToServer::AgentGone(method_obj) => {
                     log::debug!("Received AgentGone from '{}'", method_obj.agent_name);
+                    self.agent_registry
+                        .agent_disconnected(&method_obj.agent_name);
+                    // [impl->swdd~server-records-events~1]
+                    self.event_store.record(
+                        EventKind::AgentDisconnected,
+                        Some(method_obj.agent_name.clone()),
+                        None,
+                        "Agent disconnected".to_string(),
+                    );
                     // [impl->swdd~server-set-workload-state-on-disconnect~1]
                     self.workload_state_db
                         .agent_disconnected(&method_obj.agent_name);
+                    // [impl->swdd~server-removes-state-watchers-of-disconnected-agent~1]
+                    self.state_watchers.remove_of_agent(&method_obj.agent_name);
+                    // [impl->swdd~server-stores-scheduler-queues-of-agents~1]
+                    self.scheduler_queues
+                        .remove_of_agent(&method_obj.agent_name);
+                    // [impl->swdd~server-stores-control-interface-metrics~1]
+                    self.control_interface_metrics
+                        .remove_of_agent(&method_obj.agent_name);
 
                     // communicate the workload execution states to other agents
                     // [impl->swdd~server-distribute-workload-state-on-disconnect~1]
@@ -195,159 +998,321 @@ impl AnkaiosServer {
                         )
                         .await
                         .unwrap_or_illegal_state();
+
+                    // [impl->swdd~server-distributes-connected-agents~1]
+                    self.to_agents
+                        .update_connected_agents(self.agent_registry.connected_agent_names())
+                        .await
+                        .unwrap_or_illegal_state();
                 }
                 // [impl->swdd~server-provides-update-desired-state-interface~1]
                 ToServer::Request(Request {
                     request_id,
                     request_content,
-                }) => match request_content {
-                    // [impl->swdd~server-provides-interface-get-complete-state~1]
-                    // [impl->swdd~server-includes-id-in-control-interface-response~1]
-                    common::commands::RequestContent::CompleteStateRequest(
-                        complete_state_request,
-                    ) => {
-                        log::debug!(
-                            "Received CompleteStateRequest with id '{}' and field mask: '{:?}'",
-                            request_id,
-                            complete_state_request.field_mask
-                        );
-                        match self.server_state.get_complete_state_by_field_mask(
-                            &complete_state_request,
-                            &self.workload_state_db,
-                        ) {
-                            Ok(complete_state) => self
-                                .to_agents
-                                .complete_state(request_id, complete_state)
-                                .await
-                                .unwrap_or_illegal_state(),
-                            Err(error) => {
-                                log::error!("Failed to get complete state: '{}'", error);
+                }) => {
+                    // [impl->swdd~server-assigns-trace-id-to-requests~1]
+                    let trace_id = generate_trace_id();
+                    match request_content {
+                        // [impl->swdd~server-provides-interface-get-complete-state~1]
+                        // [impl->swdd~server-includes-id-in-control-interface-response~1]
+                        common::commands::RequestContent::CompleteStateRequest(
+                            complete_state_request,
+                        ) => {
+                            log::debug!(
+                                "Received CompleteStateRequest with id '{}', trace id '{}' and field mask: '{:?}'",
+                                request_id,
+                                trace_id,
+                                complete_state_request.field_mask
+                            );
+                            match self.server_state.get_complete_state_by_field_mask(
+                                &complete_state_request,
+                                &self.workload_state_db,
+                            ) {
+                                Ok(mut complete_state) => {
+                                    // [impl->swdd~server-provides-system-state~2]
+                                    if requests_system_state(&complete_state_request.field_mask) {
+                                        complete_state.system = self.get_system_state();
+                                    }
+                                    self.to_agents
+                                        .complete_state(request_id, trace_id, complete_state)
+                                        .await
+                                        .unwrap_or_illegal_state()
+                                }
+                                Err(error) => {
+                                    log::error!(
+                                        "Failed to get complete state: '{}' (trace id '{}')",
+                                        error,
+                                        trace_id
+                                    );
+                                    self.to_agents
+                                        .complete_state(
+                                            request_id,
+                                            trace_id,
+                                            common::objects::CompleteState {
+                                                ..Default::default()
+                                            },
+                                        )
+                                        .await
+                                        .unwrap_or_illegal_state();
+                                }
+                            }
+                        }
+
+                        // [impl->swdd~server-provides-update-desired-state-interface~1]
+                        common::commands::RequestContent::UpdateStateRequest(
+                            update_state_request,
+                        ) => {
+                            log::debug!(
+                                "Received UpdateState with id '{}' and trace id '{}'. State '{:?}', update mask '{:?}'",
+                                request_id,
+                                trace_id,
+                                update_state_request.state,
+                                update_state_request.update_mask
+                            );
+
+                            // [impl->swdd~update-desired-state-with-invalid-version~1]
+                            // [impl->swdd~update-desired-state-with-missing-version~1]
+                            if !State::is_compatible_format(
+                                &update_state_request.state.desired_state.api_version,
+                            ) {
+                                log::warn!("The CompleteState in the request has wrong format. Received '{}', expected '{}' -> ignoring the request.",
+                                update_state_request.state.desired_state.api_version, State::default().api_version);
+
                                 self.to_agents
-                                    .complete_state(
+                                    .error(
                                         request_id,
-                                        common::objects::CompleteState {
-                                            ..Default::default()
+                                        trace_id,
+                                        common::commands::Error {
+                                            message: format!(
+                                            "Unsupported API version. Received '{}', expected '{}'",
+                                            update_state_request.state.desired_state.api_version,
+                                            State::default().api_version
+                                        ),
                                         },
                                     )
                                     .await
                                     .unwrap_or_illegal_state();
+                                continue;
                             }
+
+                            // [impl->swdd~server-identifies-modifier-of-request~2]
+                            // [impl->swdd~server-protects-managed-workloads~1]
+                            let force =
+                                update_state_request.force || !self.protect_managed_workloads;
+                            let modifier = managed_by::identity_of_request(&request_id)
+                                .map(|identity| Modifier { identity, force });
+                            self.update_desired_state(
+                                request_id,
+                                trace_id,
+                                *update_state_request,
+                                modifier,
+                            )
+                            .await;
                         }
-                    }
 
-                    // [impl->swdd~server-provides-update-desired-state-interface~1]
-                    common::commands::RequestContent::UpdateStateRequest(update_state_request) => {
-                        log::debug!(
-                            "Received UpdateState. State '{:?}', update mask '{:?}'",
-                            update_state_request.state,
-                            update_state_request.update_mask
-                        );
+                        // [impl->swdd~server-drains-agent~1]
+                        common::commands::RequestContent::DrainAgentRequest(
+                            drain_agent_request,
+                        ) => {
+                            log::debug!(
+                                "Received DrainAgentRequest with id '{}' and trace id '{}': '{:?}'",
+                                request_id,
+                                trace_id,
+                                drain_agent_request
+                            );
+                            self.drain_agent(request_id, trace_id, drain_agent_request)
+                                .await;
+                        }
 
-                        // [impl->swdd~update-desired-state-with-invalid-version~1]
-                        // [impl->swdd~update-desired-state-with-missing-version~1]
-                        if !State::is_compatible_format(
-                            &update_state_request.state.desired_state.api_version,
-                        ) {
-                            log::warn!("The CompleteState in the request has wrong format. Received '{}', expected '{}' -> ignoring the request.",
-                                update_state_request.state.desired_state.api_version, State::default().api_version);
+                        // [impl->swdd~server-provides-rollout-status~1]
+                        common::commands::RequestContent::RolloutStatusRequest(_) => {
+                            log::debug!(
+                                "Received RolloutStatusRequest with id '{}' and trace id '{}'",
+                                request_id,
+                                trace_id
+                            );
+                            self.to_agents
+                                .rollout_status(
+                                    request_id,
+                                    trace_id,
+                                    self.rollout_manager.status(&self.workload_state_db),
+                                )
+                                .await
+                                .unwrap_or_illegal_state();
+                        }
 
+                        // [impl->swdd~server-provides-support-info~1]
+                        common::commands::RequestContent::SupportInfoRequest(_) => {
+                            log::debug!(
+                                "Received SupportInfoRequest with id '{}' and trace id '{}'",
+                                request_id,
+                                trace_id
+                            );
                             self.to_agents
-                                .error(
+                                .support_info(
                                     request_id,
-                                    common::commands::Error {
-                                        message: format!(
-                                            "Unsupported API version. Received '{}', expected '{}'",
-                                            update_state_request.state.desired_state.api_version,
-                                            State::default().api_version
-                                        ),
+                                    trace_id,
+                                    common::commands::SupportInfo {
+                                        server_version: env!("CARGO_PKG_VERSION").to_string(),
+                                        agents: self.agent_registry.get_agents(),
                                     },
                                 )
                                 .await
                                 .unwrap_or_illegal_state();
-                            continue;
                         }
 
-                        // [impl->swdd~update-desired-state-with-update-mask~1]
-                        // [impl->swdd~update-desired-state-empty-update-mask~1]
-                        match self
-                            .server_state
-                            .update(update_state_request.state, update_state_request.update_mask)
-                        {
-                            Ok(Some((added_workloads, mut deleted_workloads))) => {
-                                log::info!(
-                                        "The update has {} new or updated workloads, {} workloads to delete",
-                                        added_workloads.len(),
-                                        deleted_workloads.len()
-                                    );
-
-                                // [impl->swdd~server-sets-state-of-new-workloads-to-pending~1]
-                                self.workload_state_db.initial_state(&added_workloads);
-
-                                let added_workloads_names = added_workloads
-                                    .iter()
-                                    .map(|x| x.instance_name.to_string())
-                                    .collect();
-                                let deleted_workloads_names = deleted_workloads
-                                    .iter()
-                                    .map(|x| x.instance_name.to_string())
-                                    .collect();
-
-                                // [impl->swdd~server-handles-deleted-workload-for-empty-agent~1]
-                                deleted_workloads = self
-                                    .handle_unscheduled_deleted_workloads(deleted_workloads)
-                                    .await;
-
-                                let from_server_command =
-                                    FromServer::UpdateWorkload(UpdateWorkload {
-                                        added_workloads,
-                                        deleted_workloads,
-                                    });
-                                self.to_agents
-                                    .send(from_server_command)
-                                    .await
-                                    .unwrap_or_illegal_state();
-                                log::debug!("Send UpdateStateSuccess for request '{}'", request_id);
-                                // [impl->swdd~server-update-state-success-response~1]
-                                self.to_agents
-                                    .update_state_success(
-                                        request_id,
-                                        added_workloads_names,
-                                        deleted_workloads_names,
-                                    )
-                                    .await
-                                    .unwrap_or_illegal_state();
-                            }
-                            Ok(None) => {
-                                log::debug!(
-                                "The current state and new state are identical -> nothing to do"
+                        // [impl->swdd~server-provides-events~1]
+                        common::commands::RequestContent::EventsRequest(events_request) => {
+                            log::debug!(
+                                "Received EventsRequest with id '{}' and trace id '{}': '{:?}'",
+                                request_id,
+                                trace_id,
+                                events_request
                             );
-                                self.to_agents
-                                    .update_state_success(request_id, vec![], vec![])
-                                    .await
-                                    .unwrap_or_illegal_state();
-                            }
-                            Err(error_msg) => {
-                                // [impl->swdd~server-continues-on-invalid-updated-state~1]
-                                log::error!("Update rejected: '{error_msg}'",);
-                                self.to_agents
-                                    .error(
-                                        request_id,
-                                        common::commands::Error {
-                                            message: format!("Update rejected: '{error_msg}'"),
-                                        },
-                                    )
-                                    .await
-                                    .unwrap_or_illegal_state();
+                            self.to_agents
+                                .events(
+                                    request_id,
+                                    trace_id,
+                                    common::commands::Events {
+                                        events: self
+                                            .event_store
+                                            .get_events(events_request.since, events_request.limit),
+                                    },
+                                )
+                                .await
+                                .unwrap_or_illegal_state();
+                        }
+
+                        // [impl->swdd~server-provides-state-watch~1]
+                        common::commands::RequestContent::WatchCompleteStateRequest(
+                            watch_request,
+                        ) => {
+                            log::debug!(
+                                "Received WatchCompleteStateRequest with id '{}', trace id '{}' and field mask: '{:?}'",
+                                request_id,
+                                trace_id,
+                                watch_request.field_mask
+                            );
+                            self.watch_complete_state(request_id, trace_id, watch_request)
+                                .await;
+                        }
+
+                        // [impl->swdd~server-provides-impact-analysis~1]
+                        common::commands::RequestContent::ImpactAnalysisRequest(
+                            impact_analysis_request,
+                        ) => {
+                            log::debug!(
+                                "Received ImpactAnalysisRequest with id '{}' and trace id '{}': '{:?}'",
+                                request_id,
+                                trace_id,
+                                impact_analysis_request
+                            );
+                            self.to_agents
+                                .impact_analysis(
+                                    request_id,
+                                    trace_id,
+                                    self.server_state.get_impact_analysis(
+                                        &impact_analysis_request.workload_names,
+                                    ),
+                                )
+                                .await
+                                .unwrap_or_illegal_state();
+                        }
+
+                        // [impl->swdd~server-provides-dependency-graph~1]
+                        common::commands::RequestContent::DependencyGraphRequest(_) => {
+                            log::debug!(
+                                "Received DependencyGraphRequest with id '{}' and trace id '{}'",
+                                request_id,
+                                trace_id
+                            );
+                            self.to_agents
+                                .dependency_graph(
+                                    request_id,
+                                    trace_id,
+                                    self.server_state
+                                        .get_dependency_graph(&self.workload_state_db),
+                                )
+                                .await
+                                .unwrap_or_illegal_state();
+                        }
+
+                        // [impl->swdd~server-provides-scheduler-queues~1]
+                        common::commands::RequestContent::SchedulerQueueRequest(
+                            scheduler_queue_request,
+                        ) => {
+                            log::debug!(
+                                "Received SchedulerQueueRequest with id '{}' and trace id '{}': '{:?}'",
+                                request_id,
+                                trace_id,
+                                scheduler_queue_request
+                            );
+                            self.to_agents
+                                .scheduler_queue(
+                                    request_id,
+                                    trace_id,
+                                    self.scheduler_queues
+                                        .get(&scheduler_queue_request.agent_name),
+                                )
+                                .await
+                                .unwrap_or_illegal_state();
+                        }
+
+                        // [impl->swdd~server-provides-state-watch~1]
+                        common::commands::RequestContent::CancelWatchRequest(_) => {
+                            if self.state_watchers.remove(&request_id) {
+                                log::debug!(
+                                    "Cancelled watch '{}' (trace id '{}')",
+                                    request_id,
+                                    trace_id
+                                );
+                            } else {
+                                log::debug!(
+                                    "No watch '{}' to cancel (trace id '{}')",
+                                    request_id,
+                                    trace_id
+                                );
                             }
                         }
                     }
-                },
+                }
                 ToServer::UpdateWorkloadState(method_obj) => {
                     log::debug!(
                         "Received UpdateWorkloadState: '{:?}'",
                         method_obj.workload_states
                     );
 
+                    // [impl->swdd~server-tracks-agent-state~1]
+                    for workload_state in &method_obj.workload_states {
+                        self.agent_registry
+                            .heartbeat(workload_state.instance_name.agent_name());
+                    }
+
+                    // [impl->swdd~server-records-events~1]
+                    for workload_state in &method_obj.workload_states {
+                        if self
+                            .workload_state_db
+                            .get_execution_state(&workload_state.instance_name)
+                            != Some(&workload_state.execution_state)
+                        {
+                            self.event_store
+                                .record_workload_state_change(workload_state);
+                            self.record_agent_incident(workload_state);
+                        }
+                    }
+
+                    // [impl->swdd~server-stores-control-interface-metrics~1]
+                    method_obj
+                        .workload_states
+                        .iter()
+                        .filter(|workload_state| workload_state.execution_state.is_removed())
+                        .for_each(|workload_state| {
+                            self.control_interface_metrics.remove_of_workload(
+                                workload_state.instance_name.agent_name(),
+                                workload_state.instance_name.workload_name(),
+                            )
+                        });
+
                     // [impl->swdd~server-stores-workload-state~1]
                     self.workload_state_db
                         .process_new_states(method_obj.workload_states.clone());
@@ -361,6 +1326,83 @@ impl AnkaiosServer {
                         .await
                         .unwrap_or_illegal_state();
                 }
+                // [impl->swdd~server-tracks-acknowledged-update-workload~1]
+                ToServer::UpdateWorkloadAck(method_obj) => {
+                    log::trace!(
+                        "Agent '{}' acknowledged UpdateWorkload '{}'",
+                        method_obj.agent_name,
+                        method_obj.sequence_number
+                    );
+                    self.agent_registry.update_workload_acknowledged(
+                        &method_obj.agent_name,
+                        method_obj.sequence_number,
+                    );
+                    self.update_workload_log
+                        .acknowledged(&method_obj.agent_name, method_obj.sequence_number);
+                }
+                // [impl->swdd~server-records-agent-events~1]
+                ToServer::AgentEvent(method_obj) => {
+                    log::info!(
+                        "Agent '{}' reported '{}' for workload '{}': '{}'",
+                        method_obj.agent_name,
+                        method_obj.kind,
+                        method_obj.workload_name,
+                        method_obj.message
+                    );
+                    self.event_store.record(
+                        method_obj.kind,
+                        Some(method_obj.agent_name),
+                        Some(method_obj.workload_name),
+                        method_obj.message,
+                    );
+                }
+                // [impl->swdd~server-stores-scheduler-queues-of-agents~1]
+                ToServer::UpdateSchedulerQueue(method_obj) => {
+                    log::trace!(
+                        "Agent '{}' reported '{}' pending workload operation(s)",
+                        method_obj.agent_name,
+                        method_obj.pending_operations.len()
+                    );
+                    self.scheduler_queues
+                        .update(method_obj.agent_name, method_obj.pending_operations);
+                }
+                // [impl->swdd~server-sends-workload-states-of-subscribed-workloads~1]
+                ToServer::SubscribeWorkloadStates(method_obj) => {
+                    let workload_states = self
+                        .workload_state_db
+                        .get_workload_states_of_workloads(&method_obj.workload_names);
+
+                    if !workload_states.is_empty() {
+                        log::debug!(
+                            "Sending UpdateWorkloadState for the subscribed workloads: '{:?}'",
+                            workload_states,
+                        );
+
+                        self.to_agents
+                            .update_workload_state(workload_states)
+                            .await
+                            .unwrap_or_illegal_state();
+                    }
+                }
+                // [impl->swdd~server-stores-control-interface-metrics~1]
+                ToServer::UpdateControlInterfaceMetrics(method_obj) => {
+                    log::trace!(
+                        "Agent '{}' reported control interface metrics of workload '{}'",
+                        method_obj.metrics.agent_name,
+                        method_obj.metrics.workload_name
+                    );
+                    self.control_interface_metrics.update(method_obj.metrics);
+                }
+                // [impl->swdd~server-stores-agent-resources~1]
+                ToServer::UpdateAgentResources(method_obj) => {
+                    log::trace!(
+                        "Agent '{}' reported its free resources: {:?}",
+                        method_obj.agent_name,
+                        method_obj.resources
+                    );
+                    self.agent_registry
+                        .update_resources(&method_obj.agent_name, method_obj.resources);
+                }
                 ToServer::Stop(_method_obj) => {
                     log::debug!("Received Stop from communications server");
                     // TODO: handle the call
@@ -373,6 +1415,8 @@ impl AnkaiosServer {
                     );
                 }
             }
+
+            self.notify_state_watchers().await;
         }
     }
 }
@@ -394,14 +1438,15 @@ mod tests {
     use crate::ankaios_server::{create_from_server_channel, create_to_server_channel};
 
     use common::commands::{
-        self, CompleteStateRequest, Response, ResponseContent, UpdateStateSuccess, UpdateWorkload,
-        UpdateWorkloadState,
+        self, CompleteStateRequest, Response, ResponseContent, UpdateConnectedAgents,
+        UpdateStateSuccess, UpdateWorkload, UpdateWorkloadState,
     };
-    use common::from_server_interface::FromServer;
+    use common::from_server_interface::{FromServer, FromServerReceiver};
     use common::objects::{
-        generate_test_stored_workload_spec, generate_test_workload_spec_with_param, CompleteState,
-        DeletedWorkload, ExecutionState, ExecutionStateEnum, PendingSubstate, State,
-        WorkloadInstanceName, WorkloadState,
+        generate_test_stored_workload_spec, generate_test_workload_spec_with_param,
+        AgentConnectionStatus, AgentInfo, CompleteState, ControlInterfaceMetrics, DeletedWorkload,
+        ExecutionState, ExecutionStateEnum, PendingSubstate, RequestLaneInfo, ServerInfo, State,
+        StoredWorkloadSpec, SystemState, WorkloadInstanceName, WorkloadState,
     };
 
     use common::to_server_interface::ToServerInterface;
@@ -413,6 +1458,11 @@ mod tests {
     const WORKLOAD_NAME_3: &str = "workload_3";
     const RUNTIME_NAME: &str = "runtime";
     const REQUEST_ID_A: &str = "agent_A@id1";
+    const TRACE_ID: &str = "trace_id";
+
+    pub fn generate_trace_id_mock() -> String {
+        TRACE_ID.to_string()
+    }
 
     // [utest->swdd~server-uses-async-channels~1]
     // [utest->swdd~server-fails-on-invalid-startup-state~1]
@@ -566,8 +1616,9 @@ mod tests {
             comm_middle_ware_receiver.recv().await.unwrap(),
             FromServer::Response(Response {
                 request_id,
+                trace_id,
                 response_content: ResponseContent::Error(_)
-            }) if request_id == REQUEST_ID_A
+            }) if request_id == REQUEST_ID_A && trace_id == TRACE_ID
         ));
 
         // send the update with the new clean state again
@@ -581,6 +1632,9 @@ mod tests {
         let expected_from_server_command = FromServer::UpdateWorkload(UpdateWorkload {
             added_workloads,
             deleted_workloads,
+            sequence_number: 1,
+            initial: false,
+            deadline_ms: None,
         });
         assert_eq!(from_server_command, expected_from_server_command);
 
@@ -588,9 +1642,11 @@ mod tests {
             comm_middle_ware_receiver.recv().await.unwrap(),
             FromServer::Response(Response {
                 request_id: REQUEST_ID_A.into(),
+                trace_id: TRACE_ID.to_string(),
                 response_content: ResponseContent::UpdateStateSuccess(UpdateStateSuccess {
                     added_workloads: vec![updated_workload.instance_name.to_string()],
                     deleted_workloads: Vec::new(),
+                    rejected_workloads: vec![],
                 }),
             })
         );
@@ -657,6 +1713,9 @@ mod tests {
         let expected_from_server_command = FromServer::UpdateWorkload(UpdateWorkload {
             added_workloads,
             deleted_workloads,
+            sequence_number: 1,
+            initial: false,
+            deadline_ms: None,
         });
         assert_eq!(from_server_command, expected_from_server_command);
 
@@ -669,7 +1728,9 @@ mod tests {
                 execution_state: ExecutionState {
                     state: ExecutionStateEnum::Pending(PendingSubstate::Initial),
                     additional_info: Default::default()
-                }
+                },
+                agent_timestamp: None,
+                server_timestamp: None,
             }]
         );
 
@@ -724,7 +1785,12 @@ mod tests {
         let server_task = tokio::spawn(async move { server.start(None).await });
 
         // first agent connects to the server
-        let agent_hello_result = to_server.agent_hello(AGENT_A.to_string()).await;
+        let agent_hello_result = to_server
+            .agent_hello(commands::AgentHello {
+                agent_name: AGENT_A.to_string(),
+                ..Default::default()
+            })
+            .await;
         assert!(agent_hello_result.is_ok());
 
         let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
@@ -733,6 +1799,19 @@ mod tests {
             FromServer::UpdateWorkload(UpdateWorkload {
                 added_workloads: vec![w1],
                 deleted_workloads: vec![],
+                sequence_number: 1,
+                initial: true,
+                deadline_ms: None,
+            }),
+            from_server_command
+        );
+
+        // [utest->swdd~server-distributes-connected-agents~1]
+        let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
+
+        assert_eq!(
+            FromServer::UpdateConnectedAgents(UpdateConnectedAgents {
+                connected_agents: vec![AGENT_A.to_string()]
             }),
             from_server_command
         );
@@ -758,7 +1837,12 @@ mod tests {
             from_server_command
         );
 
-        let agent_hello_result = to_server.agent_hello(AGENT_B.to_owned()).await;
+        let agent_hello_result = to_server
+            .agent_hello(commands::AgentHello {
+                agent_name: AGENT_B.to_string(),
+                ..Default::default()
+            })
+            .await;
         assert!(agent_hello_result.is_ok());
 
         let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
@@ -775,7 +1859,19 @@ mod tests {
         assert_eq!(
             FromServer::UpdateWorkload(UpdateWorkload {
                 added_workloads: vec![w2],
-                deleted_workloads: vec![]
+                deleted_workloads: vec![],
+                sequence_number: 1,
+                initial: true,
+                deadline_ms: None,
+            }),
+            from_server_command
+        );
+
+        let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
+
+        assert_eq!(
+            FromServer::UpdateConnectedAgents(UpdateConnectedAgents {
+                connected_agents: vec![AGENT_A.to_string(), AGENT_B.to_string()]
             }),
             from_server_command
         );
@@ -883,6 +1979,9 @@ mod tests {
             FromServer::UpdateWorkload(UpdateWorkload {
                 added_workloads: added_workloads.clone(),
                 deleted_workloads: deleted_workloads.clone(),
+                sequence_number: 1,
+                initial: false,
+                deadline_ms: None,
             }),
             update_workload_message
         );
@@ -891,6 +1990,7 @@ mod tests {
         assert_eq!(
             FromServer::Response(Response {
                 request_id: REQUEST_ID_A.to_string(),
+                trace_id: TRACE_ID.to_string(),
                 response_content: common::commands::ResponseContent::UpdateStateSuccess(
                     UpdateStateSuccess {
                         added_workloads: added_workloads
@@ -900,7 +2000,8 @@ mod tests {
                         deleted_workloads: deleted_workloads
                             .into_iter()
                             .map(|x| x.instance_name.to_string())
-                            .collect()
+                            .collect(),
+                        rejected_workloads: vec![],
                     }
                 )
             }),
@@ -911,6 +2012,69 @@ mod tests {
         assert!(comm_middle_ware_receiver.try_recv().is_err());
     }
 
+    // [utest->swdd~server-propagates-update-deadline~1]
+    #[tokio::test]
+    async fn utest_server_forwards_update_deadline_to_agents() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let w1 = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let update_state = CompleteState {
+            desired_state: State {
+                workloads: vec![(WORKLOAD_NAME_1.to_owned(), w1.clone().into())]
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let update_mask = vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_1)];
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_update()
+            .once()
+            .return_const(Ok(Some((vec![w1.clone()], vec![]))));
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        let update_state_result = to_server
+            .request_update_state(
+                REQUEST_ID_A.to_string(),
+                common::commands::UpdateStateRequest {
+                    state: update_state,
+                    update_mask,
+                    deadline_ms: Some(3000),
+                    rollback_on_deadline_exceeded: false,
+                    force: false,
+                },
+            )
+            .await;
+        assert!(update_state_result.is_ok());
+
+        let update_workload_message = comm_middle_ware_receiver.recv().await.unwrap();
+        assert_eq!(
+            FromServer::UpdateWorkload(UpdateWorkload {
+                added_workloads: vec![w1],
+                deleted_workloads: vec![],
+                sequence_number: 1,
+                initial: false,
+                deadline_ms: Some(3000),
+            }),
+            update_workload_message
+        );
+
+        server_task.abort();
+    }
+
     // [utest->swdd~server-uses-async-channels~1]
     // [utest->swdd~server-provides-update-desired-state-interface~1]
     // [utest->swdd~server-starts-without-startup-config~1]
@@ -959,11 +2123,13 @@ mod tests {
             comm_middle_ware_receiver.recv().await.unwrap(),
             FromServer::Response(Response {
                 request_id,
+                trace_id,
                 response_content: ResponseContent::UpdateStateSuccess(UpdateStateSuccess {
                     added_workloads,
-                    deleted_workloads
+                    deleted_workloads,
+                    rejected_workloads,
                 })
-            }) if request_id == REQUEST_ID_A && added_workloads.is_empty() && deleted_workloads.is_empty()
+            }) if request_id == REQUEST_ID_A && trace_id == TRACE_ID && added_workloads.is_empty() && deleted_workloads.is_empty() && rejected_workloads.is_empty()
         ));
 
         assert!(tokio::time::timeout(
@@ -1024,8 +2190,9 @@ mod tests {
             comm_middle_ware_receiver.recv().await.unwrap(),
             FromServer::Response(common::commands::Response {
                 request_id,
+                trace_id,
                 response_content: common::commands::ResponseContent::Error(_)
-            }) if request_id == REQUEST_ID_A
+            }) if request_id == REQUEST_ID_A && trace_id == TRACE_ID
         ));
 
         assert!(tokio::time::timeout(
@@ -1043,6 +2210,7 @@ mod tests {
     // [utest->swdd~server-provides-interface-get-complete-state~1]
     // [utest->swdd~server-includes-id-in-control-interface-response~1]
     // [utest->swdd~server-starts-without-startup-config~1]
+    // [utest->swdd~server-provides-system-state~2]
     #[tokio::test]
     async fn utest_server_returns_complete_state_when_received_request_complete_state() {
         let _ = env_logger::builder().is_test(true).try_init();
@@ -1097,12 +2265,42 @@ mod tests {
 
         let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
 
+        let expected_complete_state = CompleteState {
+            system: SystemState {
+                server: ServerInfo {
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    uptime: 0,
+                    request_lanes: vec![
+                        RequestLaneInfo {
+                            lane: "agentUpdates".to_string(),
+                            ..Default::default()
+                        },
+                        RequestLaneInfo {
+                            lane: "writes".to_string(),
+                            ..Default::default()
+                        },
+                        RequestLaneInfo {
+                            lane: "reads".to_string(),
+                            depth: 0,
+                            max_depth: 1,
+                            processed: 1,
+                        },
+                    ],
+                },
+                agents: vec![],
+                terminated_workloads: vec![],
+                control_interface_metrics: vec![],
+            },
+            ..current_complete_state
+        };
+
         assert_eq!(
             from_server_command,
             common::from_server_interface::FromServer::Response(common::commands::Response {
                 request_id,
+                trace_id: TRACE_ID.to_string(),
                 response_content: common::commands::ResponseContent::CompleteState(Box::new(
-                    current_complete_state
+                    expected_complete_state
                 ))
             })
         );
@@ -1111,6 +2309,54 @@ mod tests {
         assert!(comm_middle_ware_receiver.try_recv().is_err());
     }
 
+    // [utest->swdd~server-provides-system-state~2]
+    #[tokio::test]
+    async fn utest_server_provides_system_state_only_if_requested_by_field_mask() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_complete_state_by_field_mask()
+            .times(2)
+            .return_const(Ok(CompleteState::default()));
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        for (field_mask, expect_system_state) in
+            [("desiredState.workloads", false), ("system.agents", true)]
+        {
+            assert!(to_server
+                .request_complete_state(
+                    REQUEST_ID_A.to_string(),
+                    CompleteStateRequest {
+                        field_mask: vec![field_mask.to_string()]
+                    }
+                )
+                .await
+                .is_ok());
+
+            let FromServer::Response(Response {
+                response_content: ResponseContent::CompleteState(complete_state),
+                ..
+            }) = comm_middle_ware_receiver.recv().await.unwrap()
+            else {
+                panic!("Expected a CompleteState response");
+            };
+            assert_eq!(
+                complete_state.system.server.version == env!("CARGO_PKG_VERSION"),
+                expect_system_state,
+                "field mask '{field_mask}'"
+            );
+        }
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
     // [utest->swdd~server-uses-async-channels~1]
     // [utest->swdd~server-provides-interface-get-complete-state~1]
     // [utest->swdd~server-includes-id-in-control-interface-response~1]
@@ -1158,6 +2404,7 @@ mod tests {
             from_server_command,
             common::from_server_interface::FromServer::Response(common::commands::Response {
                 request_id,
+                trace_id: TRACE_ID.to_string(),
                 response_content: common::commands::ResponseContent::CompleteState(Box::new(
                     expected_complete_state
                 ))
@@ -1236,34 +2483,233 @@ mod tests {
             }),
             from_server_command
         );
+
+        // [utest->swdd~server-distributes-connected-agents~1]
+        let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
+        assert_eq!(
+            FromServer::UpdateConnectedAgents(UpdateConnectedAgents {
+                connected_agents: vec![]
+            }),
+            from_server_command
+        );
         assert!(comm_middle_ware_receiver.try_recv().is_err());
     }
 
-    // [utest->swdd~server-sets-state-of-new-workloads-to-pending~1]
-    // [utest->swdd~server-uses-async-channels~1]
-    // [utest->swdd~server-starts-without-startup-config~1]
+    // [utest->swdd~server-sends-workload-states-of-subscribed-workloads~1]
     #[tokio::test]
-    async fn utest_server_start_calls_agents_in_update_state_command() {
+    async fn utest_server_sends_fulfilled_dependency_on_later_subscription() {
         let _ = env_logger::builder().is_test(true).try_init();
         let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
         let (to_agents, mut comm_middle_ware_receiver) =
             create_from_server_channel(common::CHANNEL_CAPACITY);
 
-        let w1 = generate_test_workload_spec_with_param(
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_cleanup_state()
+            .once()
+            .return_const(());
+
+        server.server_state = mock_server_state;
+
+        // the dependency is already running before an agent subscribes to it
+        let test_wl_1_state_running = common::objects::generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::running(),
+        );
+        let update_workload_state_result = to_server
+            .update_workload_state(vec![test_wl_1_state_running.clone()])
+            .await;
+        assert!(update_workload_state_result.is_ok());
+
+        let subscribe_result = to_server
+            .subscribe_workload_states(vec![WORKLOAD_NAME_1.to_owned(), WORKLOAD_NAME_2.to_owned()])
+            .await;
+        assert!(subscribe_result.is_ok());
+
+        let server_handle = server.start(None);
+
+        // The receiver in the server receives the messages and terminates the infinite waiting-loop
+        drop(to_server);
+        tokio::join!(server_handle).0.unwrap();
+
+        let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
+        assert_eq!(
+            FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                workload_states: vec![test_wl_1_state_running.clone()]
+            }),
+            from_server_command
+        );
+
+        let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
+        assert_eq!(
+            FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                workload_states: vec![test_wl_1_state_running]
+            }),
+            from_server_command
+        );
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-numbers-update-workload~2]
+    // [utest->swdd~server-re-sends-unacknowledged-update-workload~1]
+    // [utest->swdd~server-tracks-acknowledged-update-workload~1]
+    #[tokio::test]
+    async fn utest_server_re_sends_unacknowledged_update_workload_per_agent() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let workload_on_agent_a = generate_test_workload_spec_with_param(
             AGENT_A.to_owned(),
             WORKLOAD_NAME_1.to_owned(),
             RUNTIME_NAME.to_string(),
         );
-
-        let w2 = generate_test_workload_spec_with_param(
+        let workload_on_agent_b = generate_test_workload_spec_with_param(
             AGENT_B.to_owned(),
             WORKLOAD_NAME_2.to_owned(),
             RUNTIME_NAME.to_string(),
         );
 
-        let mut updated_w1 = w1.clone();
-        updated_w1.instance_name = WorkloadInstanceName::builder()
-            .workload_name(w1.instance_name.workload_name())
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_workloads_for_agent()
+            .return_const(vec![]);
+        mock_server_state
+            .expect_update()
+            .once()
+            .return_const(Ok(Some((
+                vec![workload_on_agent_a.clone(), workload_on_agent_b.clone()],
+                vec![],
+            ))));
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        // the sequence numbers and initial flags of the UpdateWorkload messages per agent
+        // until the server sends the given last message
+        async fn receive_update_workloads(
+            receiver: &mut FromServerReceiver,
+            is_last: fn(&FromServer) -> bool,
+        ) -> Vec<(String, u64, bool)> {
+            let mut update_workloads = Vec::new();
+            loop {
+                match receiver.recv().await.unwrap() {
+                    FromServer::UpdateWorkload(update_workload) => {
+                        let agent_name = update_workload
+                            .added_workloads
+                            .iter()
+                            .map(|workload| workload.instance_name.agent_name().to_owned())
+                            .next()
+                            .unwrap_or_default();
+                        update_workloads.push((
+                            agent_name,
+                            update_workload.sequence_number,
+                            update_workload.initial,
+                        ));
+                    }
+                    from_server if is_last(&from_server) => return update_workloads,
+                    _ => {}
+                }
+            }
+        }
+        let both_agents_connected = |from_server: &FromServer| {
+            matches!(from_server, FromServer::UpdateConnectedAgents(UpdateConnectedAgents {
+                connected_agents
+            }) if connected_agents == &[AGENT_A, AGENT_B])
+        };
+
+        for agent_name in [AGENT_A, AGENT_B] {
+            assert!(to_server
+                .agent_hello(commands::AgentHello {
+                    agent_name: agent_name.to_string(),
+                    ..Default::default()
+                })
+                .await
+                .is_ok());
+        }
+        assert_eq!(
+            receive_update_workloads(&mut comm_middle_ware_receiver, both_agents_connected).await,
+            vec![(String::new(), 1, true), (String::new(), 1, true)]
+        );
+
+        assert!(to_server
+            .update_state(
+                REQUEST_ID_A.to_string(),
+                CompleteState::default(),
+                vec!["desiredState.workloads".to_string()],
+            )
+            .await
+            .is_ok());
+        // every agent receives its own message with its own sequence number
+        assert_eq!(
+            receive_update_workloads(&mut comm_middle_ware_receiver, |from_server| {
+                matches!(from_server, FromServer::Response(_))
+            })
+            .await,
+            vec![
+                (AGENT_A.to_string(), 2, false),
+                (AGENT_B.to_string(), 2, false)
+            ]
+        );
+
+        assert!(to_server
+            .update_workload_ack(AGENT_A.to_string(), 2)
+            .await
+            .is_ok());
+        for agent_name in [AGENT_A, AGENT_B] {
+            assert!(to_server.agent_gone(agent_name.to_string()).await.is_ok());
+        }
+
+        // only the update not acknowledged by agent B is re-sent before the initial lists
+        for agent_name in [AGENT_A, AGENT_B] {
+            assert!(to_server
+                .agent_hello(commands::AgentHello {
+                    agent_name: agent_name.to_string(),
+                    ..Default::default()
+                })
+                .await
+                .is_ok());
+        }
+        assert_eq!(
+            receive_update_workloads(&mut comm_middle_ware_receiver, both_agents_connected).await,
+            vec![
+                (String::new(), 3, true),
+                (AGENT_B.to_string(), 2, false),
+                (String::new(), 3, true)
+            ]
+        );
+
+        server_task.abort();
+    }
+
+    // [utest->swdd~server-sets-state-of-new-workloads-to-pending~1]
+    // [utest->swdd~server-uses-async-channels~1]
+    // [utest->swdd~server-starts-without-startup-config~1]
+    #[tokio::test]
+    async fn utest_server_start_calls_agents_in_update_state_command() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let w1 = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let w2 = generate_test_workload_spec_with_param(
+            AGENT_B.to_owned(),
+            WORKLOAD_NAME_2.to_owned(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let mut updated_w1 = w1.clone();
+        updated_w1.instance_name = WorkloadInstanceName::builder()
+            .workload_name(w1.instance_name.workload_name())
             .agent_name(w1.instance_name.agent_name())
             .config(&String::from("changed"))
             .build();
@@ -1312,10 +2758,20 @@ mod tests {
             .return_const(Ok(Some((added_workloads, deleted_workloads))));
         server.server_state = mock_server_state;
 
-        let agent_hello1_result = to_server.agent_hello(AGENT_A.to_owned()).await;
+        let agent_hello1_result = to_server
+            .agent_hello(commands::AgentHello {
+                agent_name: AGENT_A.to_string(),
+                ..Default::default()
+            })
+            .await;
         assert!(agent_hello1_result.is_ok());
 
-        let agent_hello2_result = to_server.agent_hello(AGENT_B.to_owned()).await;
+        let agent_hello2_result = to_server
+            .agent_hello(commands::AgentHello {
+                agent_name: AGENT_B.to_string(),
+                ..Default::default()
+            })
+            .await;
         assert!(agent_hello2_result.is_ok());
 
         let update_state_result = to_server
@@ -1333,20 +2789,36 @@ mod tests {
         assert_eq!(
             FromServer::UpdateWorkload(UpdateWorkload {
                 added_workloads: vec![w1.clone()],
-                deleted_workloads: vec![]
+                deleted_workloads: vec![],
+                sequence_number: 1,
+                initial: true,
+                deadline_ms: None,
             }),
             from_server_command
         );
 
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateConnectedAgents(_)
+        ));
+
         let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
         assert_eq!(
             FromServer::UpdateWorkload(UpdateWorkload {
                 added_workloads: vec![w2],
-                deleted_workloads: vec![]
+                deleted_workloads: vec![],
+                sequence_number: 1,
+                initial: true,
+                deadline_ms: None,
             }),
             from_server_command
         );
 
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateConnectedAgents(_)
+        ));
+
         let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
         assert_eq!(
             FromServer::UpdateWorkload(UpdateWorkload {
@@ -1354,7 +2826,10 @@ mod tests {
                 deleted_workloads: vec![DeletedWorkload {
                     instance_name: w1.instance_name.clone(),
                     dependencies: HashMap::new(),
-                }]
+                }],
+                sequence_number: 2,
+                initial: false,
+                deadline_ms: None,
             }),
             from_server_command
         );
@@ -1363,11 +2838,13 @@ mod tests {
             comm_middle_ware_receiver.recv().await.unwrap(),
             FromServer::Response(Response {
                 request_id,
+                trace_id,
                 response_content: ResponseContent::UpdateStateSuccess(UpdateStateSuccess {
                     added_workloads,
-                    deleted_workloads
+                    deleted_workloads,
+                    rejected_workloads,
                 })
-            }) if request_id == REQUEST_ID_A && added_workloads == vec![updated_w1.instance_name.to_string()] && deleted_workloads == vec![w1.instance_name.to_string()]
+            }) if request_id == REQUEST_ID_A && trace_id == TRACE_ID && added_workloads == vec![updated_w1.instance_name.to_string()] && deleted_workloads == vec![w1.instance_name.to_string()] && rejected_workloads.is_empty()
         ));
 
         assert_eq!(
@@ -1379,7 +2856,9 @@ mod tests {
                 execution_state: ExecutionState {
                     state: ExecutionStateEnum::Pending(PendingSubstate::Initial),
                     additional_info: Default::default()
-                }
+                },
+                agent_timestamp: None,
+                server_timestamp: None,
             }]
         );
 
@@ -1444,6 +2923,7 @@ mod tests {
         assert_eq!(
             FromServer::Response(Response {
                 request_id: REQUEST_ID_A.to_string(),
+                trace_id: TRACE_ID.to_string(),
                 response_content: ResponseContent::Error(commands::Error {
                     message: error_message
                 }),
@@ -1490,6 +2970,7 @@ mod tests {
         assert_eq!(
             FromServer::Response(Response {
                 request_id: REQUEST_ID_A.to_string(),
+                trace_id: TRACE_ID.to_string(),
                 response_content: ResponseContent::Error(commands::Error {
                     message: error_message
                 }),
@@ -1532,6 +3013,110 @@ mod tests {
         server_task.abort();
     }
 
+    // [utest->swdd~server-provides-state-watch~1]
+    // [utest->swdd~server-notifies-state-watchers-on-change~1]
+    #[tokio::test]
+    async fn utest_server_notifies_state_watch_until_cancelled() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let initial_state = CompleteState::default();
+        let changed_state = CompleteState {
+            desired_state: State {
+                workloads: HashMap::from([(
+                    WORKLOAD_NAME_1.to_owned(),
+                    generate_test_stored_workload_spec(
+                        AGENT_A.to_owned(),
+                        RUNTIME_NAME.to_string(),
+                    ),
+                )]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let workload_states = vec![common::objects::generate_test_workload_state(
+            WORKLOAD_NAME_1,
+            ExecutionState::running(),
+        )];
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state.expect_cleanup_state().return_const(());
+        // the state is provided for the watch request and after each handled message
+        // until the watch is cancelled
+        let mut provided_states = vec![
+            initial_state.clone(),
+            initial_state.clone(),
+            changed_state.clone(),
+        ]
+        .into_iter();
+        mock_server_state
+            .expect_get_complete_state_by_field_mask()
+            .with(
+                mockall::predicate::eq(CompleteStateRequest {
+                    field_mask: vec!["desiredState".to_string()],
+                }),
+                mockall::predicate::always(),
+            )
+            .times(3)
+            .returning(move |_, _| Ok(provided_states.next().unwrap()));
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        let request_id = format!("{AGENT_A}@{WORKLOAD_NAME_1}@my_request_id");
+        assert!(to_server
+            .request_watch_complete_state(
+                request_id.clone(),
+                commands::WatchCompleteStateRequest {
+                    field_mask: vec!["desiredState".to_string()],
+                },
+            )
+            .await
+            .is_ok());
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(Response {
+                request_id: request_id.clone(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: ResponseContent::CompleteState(Box::new(initial_state)),
+            })
+        );
+
+        assert!(to_server
+            .update_workload_state(workload_states.clone())
+            .await
+            .is_ok());
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                workload_states: workload_states.clone(),
+            })
+        );
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(Response {
+                request_id: request_id.clone(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: ResponseContent::CompleteState(Box::new(changed_state)),
+            })
+        );
+
+        assert!(to_server.cancel_watch(request_id).await.is_ok());
+        assert!(to_server
+            .update_workload_state(workload_states.clone())
+            .await
+            .is_ok());
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkloadState(UpdateWorkloadState { workload_states })
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
     // [utest->swdd~server-handles-deleted-workload-for-empty-agent~1]
     #[tokio::test]
     async fn utest_server_handles_deleted_workload_on_empty_agent() {
@@ -1595,7 +3180,9 @@ mod tests {
             FromServer::UpdateWorkloadState(UpdateWorkloadState {
                 workload_states: vec![WorkloadState {
                     instance_name: workload_without_agent.instance_name,
-                    execution_state: ExecutionState::removed()
+                    execution_state: ExecutionState::removed(),
+                    agent_timestamp: None,
+                    server_timestamp: None,
                 }]
             }),
             from_server_command
@@ -1607,6 +3194,9 @@ mod tests {
             FromServer::UpdateWorkload(UpdateWorkload {
                 added_workloads: vec![],
                 deleted_workloads: vec![deleted_workload_with_agent.clone()],
+                sequence_number: 1,
+                initial: false,
+                deadline_ms: None,
             }),
             from_server_command
         );
@@ -1619,4 +3209,1085 @@ mod tests {
 
         assert!(comm_middle_ware_receiver.try_recv().is_err());
     }
+
+    // [utest->swdd~server-provides-rollout-status~1]
+    #[tokio::test]
+    async fn utest_server_returns_rollout_status_when_received_rollout_status_request() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        let request_rollout_status_result = to_server
+            .request_rollout_status(REQUEST_ID_A.to_string())
+            .await;
+        assert!(request_rollout_status_result.is_ok());
+
+        let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
+
+        assert_eq!(
+            from_server_command,
+            FromServer::Response(Response {
+                request_id: REQUEST_ID_A.to_string(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: ResponseContent::RolloutStatus(commands::RolloutStatus {
+                    message: "Staged rollouts are disabled.".to_string(),
+                    ..Default::default()
+                }),
+            })
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-provides-impact-analysis~1]
+    #[tokio::test]
+    async fn utest_server_returns_impact_analysis_when_received_impact_analysis_request() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let impact_analysis = commands::ImpactAnalysis {
+            impacted_workloads: vec![commands::ImpactedWorkload {
+                workload_name: WORKLOAD_NAME_2.to_string(),
+                agent: AGENT_A.to_string(),
+                depends_on: vec![WORKLOAD_NAME_1.to_string()],
+            }],
+        };
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_impact_analysis()
+            .with(mockall::predicate::eq(vec![WORKLOAD_NAME_1.to_string()]))
+            .once()
+            .return_const(impact_analysis.clone());
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .request_impact_analysis(
+                REQUEST_ID_A.to_string(),
+                commands::ImpactAnalysisRequest {
+                    workload_names: vec![WORKLOAD_NAME_1.to_string()],
+                },
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(Response {
+                request_id: REQUEST_ID_A.to_string(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: ResponseContent::ImpactAnalysis(impact_analysis),
+            })
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-provides-dependency-graph~1]
+    #[tokio::test]
+    async fn utest_server_returns_dependency_graph_when_received_dependency_graph_request() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let dependency_graph = commands::DependencyGraph {
+            nodes: vec![commands::DependencyGraphNode {
+                workload_name: WORKLOAD_NAME_1.to_string(),
+                agent: AGENT_A.to_string(),
+                execution_state: None,
+            }],
+            edges: vec![],
+        };
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_dependency_graph()
+            .once()
+            .return_const(dependency_graph.clone());
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .request_dependency_graph(REQUEST_ID_A.to_string())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(Response {
+                request_id: REQUEST_ID_A.to_string(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: ResponseContent::DependencyGraph(dependency_graph),
+            })
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-stores-scheduler-queues-of-agents~1]
+    // [utest->swdd~server-provides-scheduler-queues~1]
+    #[tokio::test]
+    async fn utest_server_returns_scheduler_queue_reported_by_agent() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let pending_operation = commands::PendingWorkloadOperation {
+            workload_name: WORKLOAD_NAME_1.to_string(),
+            agent: AGENT_A.to_string(),
+            operation: commands::PendingOperation::Create,
+            unfulfilled_dependencies: vec![commands::UnfulfilledDependency {
+                workload_name: WORKLOAD_NAME_2.to_string(),
+                condition: "ADD_COND_RUNNING".to_string(),
+            }],
+        };
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .update_scheduler_queue(AGENT_A.to_string(), vec![pending_operation.clone()])
+            .await
+            .is_ok());
+        assert!(to_server
+            .request_scheduler_queue(
+                REQUEST_ID_A.to_string(),
+                commands::SchedulerQueueRequest::default()
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(Response {
+                request_id: REQUEST_ID_A.to_string(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: ResponseContent::SchedulerQueue(commands::SchedulerQueue {
+                    pending_operations: vec![pending_operation],
+                }),
+            })
+        );
+
+        assert!(to_server.agent_gone(AGENT_A.to_string()).await.is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkloadState(_)
+        ));
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateConnectedAgents(_)
+        ));
+        assert!(to_server
+            .request_scheduler_queue(
+                REQUEST_ID_A.to_string(),
+                commands::SchedulerQueueRequest {
+                    agent_name: AGENT_A.to_string(),
+                }
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(Response {
+                request_id: REQUEST_ID_A.to_string(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: ResponseContent::SchedulerQueue(
+                    commands::SchedulerQueue::default()
+                ),
+            })
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-stores-control-interface-metrics~1]
+    // [utest->swdd~server-provides-control-interface-metrics~1]
+    #[tokio::test]
+    async fn utest_server_provides_control_interface_metrics_until_workload_removed() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let metrics = ControlInterfaceMetrics {
+            agent_name: AGENT_A.to_string(),
+            workload_name: WORKLOAD_NAME_1.to_string(),
+            forwarded_requests: 3,
+            rejected_requests: 1,
+            mean_latency_ms: 4,
+            max_latency_ms: 8,
+        };
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_complete_state_by_field_mask()
+            .times(2)
+            .return_const(Ok(CompleteState::default()));
+        mock_server_state.expect_cleanup_state().return_const(());
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .update_control_interface_metrics(metrics.clone())
+            .await
+            .is_ok());
+        assert!(to_server
+            .request_complete_state(
+                REQUEST_ID_A.to_string(),
+                CompleteStateRequest { field_mask: vec![] }
+            )
+            .await
+            .is_ok());
+
+        let FromServer::Response(Response {
+            response_content: ResponseContent::CompleteState(complete_state),
+            ..
+        }) = comm_middle_ware_receiver.recv().await.unwrap()
+        else {
+            panic!("Expected a CompleteState response");
+        };
+        assert_eq!(
+            complete_state.system.control_interface_metrics,
+            vec![metrics]
+        );
+
+        assert!(to_server
+            .update_workload_state(vec![
+                common::objects::generate_test_workload_state_with_agent(
+                    WORKLOAD_NAME_1,
+                    AGENT_A,
+                    ExecutionState::removed(),
+                )
+            ])
+            .await
+            .is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkloadState(_)
+        ));
+        assert!(to_server
+            .request_complete_state(
+                REQUEST_ID_A.to_string(),
+                CompleteStateRequest { field_mask: vec![] }
+            )
+            .await
+            .is_ok());
+
+        let FromServer::Response(Response {
+            response_content: ResponseContent::CompleteState(complete_state),
+            ..
+        }) = comm_middle_ware_receiver.recv().await.unwrap()
+        else {
+            panic!("Expected a CompleteState response");
+        };
+        assert!(complete_state.system.control_interface_metrics.is_empty());
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-provides-support-info~1]
+    // [utest->swdd~server-assigns-trace-id-to-requests~1]
+    // [utest->swdd~server-tracks-acknowledged-update-workload~1]
+    // [utest->swdd~server-reports-local-workloads-of-agents~1]
+    #[tokio::test]
+    async fn utest_server_returns_support_info_with_connected_agents() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_workloads_for_agent()
+            .return_const(vec![]);
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .agent_hello(commands::AgentHello {
+                agent_name: AGENT_A.to_string(),
+                rollout_group: Some("canary".to_string()),
+                agent_version: "0.3.1".to_string(),
+                runtimes: vec![RUNTIME_NAME.to_string()],
+                attributes: HashMap::from([("camera".to_string(), "true".to_string())]),
+                local_workloads: HashMap::from([(
+                    "watchdog".to_string(),
+                    generate_test_stored_workload_spec(AGENT_A, RUNTIME_NAME),
+                )]),
+            })
+            .await
+            .is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkload(UpdateWorkload {
+                sequence_number: 1,
+                initial: true,
+                deadline_ms: None,
+                ..
+            })
+        ));
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateConnectedAgents(_)
+        ));
+        assert!(to_server
+            .update_workload_ack(AGENT_A.to_string(), 1)
+            .await
+            .is_ok());
+
+        assert!(to_server
+            .request_support_info(REQUEST_ID_A.to_string())
+            .await
+            .is_ok());
+
+        let Some(FromServer::Response(Response {
+            request_id,
+            trace_id,
+            response_content: ResponseContent::SupportInfo(support_info),
+        })) = comm_middle_ware_receiver.recv().await
+        else {
+            panic!("Expected a SupportInfo response");
+        };
+        assert_eq!(request_id, REQUEST_ID_A);
+        assert_eq!(trace_id, TRACE_ID);
+        assert_eq!(support_info.server_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            support_info.agents,
+            vec![AgentInfo {
+                agent_name: AGENT_A.to_string(),
+                version: "0.3.1".to_string(),
+                rollout_group: Some("canary".to_string()),
+                connection_status: AgentConnectionStatus::Connected,
+                last_heartbeat: support_info.agents[0].last_heartbeat,
+                runtimes: vec![RUNTIME_NAME.to_string()],
+                attributes: HashMap::from([("camera".to_string(), "true".to_string())]),
+                last_acknowledged_update: 1,
+                local_workloads: HashMap::from([(
+                    "watchdog".to_string(),
+                    generate_test_stored_workload_spec(AGENT_A, RUNTIME_NAME),
+                )]),
+                resources: None,
+            }]
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-records-events~1]
+    // [utest->swdd~server-provides-events~1]
+    #[tokio::test]
+    async fn utest_server_returns_recorded_events() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_workloads_for_agent()
+            .return_const(vec![]);
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .agent_hello(commands::AgentHello {
+                agent_name: AGENT_A.to_string(),
+                agent_version: "0.4.0".to_string(),
+                ..Default::default()
+            })
+            .await
+            .is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkload(_)
+        ));
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateConnectedAgents(_)
+        ));
+        assert!(to_server.agent_gone(AGENT_A.to_string()).await.is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkloadState(_)
+        ));
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateConnectedAgents(_)
+        ));
+
+        assert!(to_server
+            .request_events(REQUEST_ID_A.to_string(), commands::EventsRequest::default())
+            .await
+            .is_ok());
+
+        let Some(FromServer::Response(Response {
+            request_id,
+            trace_id,
+            response_content: ResponseContent::Events(events),
+        })) = comm_middle_ware_receiver.recv().await
+        else {
+            panic!("Expected an Events response");
+        };
+        assert_eq!(request_id, REQUEST_ID_A);
+        assert_eq!(trace_id, TRACE_ID);
+        let recorded: Vec<(commands::EventKind, Option<String>)> = events
+            .events
+            .into_iter()
+            .map(|event| (event.kind, event.agent_name))
+            .collect();
+        assert_eq!(
+            recorded,
+            vec![
+                (
+                    commands::EventKind::AgentConnected,
+                    Some(AGENT_A.to_string())
+                ),
+                (
+                    commands::EventKind::AgentDisconnected,
+                    Some(AGENT_A.to_string())
+                ),
+            ]
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-applies-staged-rollout-per-rollout-group~1]
+    #[tokio::test]
+    async fn utest_server_applies_update_state_to_first_rollout_group_only() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let w1 = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME_NAME.to_string(),
+        );
+        let w2 = generate_test_workload_spec_with_param(
+            AGENT_B.to_owned(),
+            WORKLOAD_NAME_2.to_owned(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let update_state = CompleteState::default();
+        let update_mask = vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_1)];
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        server.enable_staged_rollout(super::RolloutConfig {
+            soak_time: std::time::Duration::from_secs(3600),
+            max_failure_rate: 0.0,
+        });
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_workloads_for_agent()
+            .return_const(vec![]);
+        mock_server_state
+            .expect_get_complete_state_by_field_mask()
+            .return_const(Ok(CompleteState::default()));
+        mock_server_state
+            .expect_update()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(update_mask.clone()),
+            )
+            .once()
+            .return_const(Ok(Some((vec![w1.clone(), w2.clone()], vec![]))));
+        // [utest->swdd~server-keeps-pending-rollout-groups-out-of-desired-state~1]
+        mock_server_state
+            .expect_update()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_2)]),
+            )
+            .once()
+            .return_const(Ok(None));
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .agent_hello(commands::AgentHello {
+                agent_name: AGENT_A.to_string(),
+                rollout_group: Some("canary".to_string()),
+                ..Default::default()
+            })
+            .await
+            .is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkload(_)
+        ));
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateConnectedAgents(_)
+        ));
+
+        assert!(to_server
+            .update_state(
+                REQUEST_ID_A.to_string(),
+                update_state.clone(),
+                update_mask.clone()
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkload(UpdateWorkload {
+                added_workloads: vec![w1],
+                deleted_workloads: vec![],
+                sequence_number: 2,
+                initial: false,
+                deadline_ms: None,
+            })
+        );
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(Response {
+                response_content: ResponseContent::UpdateStateSuccess(_),
+                ..
+            })
+        ));
+
+        assert!(to_server
+            .update_state(REQUEST_ID_A.to_string(), update_state, update_mask)
+            .await
+            .is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(Response {
+                response_content: ResponseContent::Error(_),
+                ..
+            })
+        ));
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-drains-agent~1]
+    #[tokio::test]
+    async fn utest_server_drain_agent_moves_workloads_to_target_agent() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let workload_on_agent_a = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME_NAME.to_string(),
+        );
+        let workload_on_agent_b = generate_test_workload_spec_with_param(
+            AGENT_B.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME_NAME.to_string(),
+        );
+        let deleted_workload = DeletedWorkload {
+            instance_name: workload_on_agent_a.instance_name.clone(),
+            dependencies: HashMap::new(),
+        };
+
+        let mut moved_workload: StoredWorkloadSpec = workload_on_agent_a.clone().into();
+        moved_workload.agent = AGENT_B.to_string();
+        let expected_state = CompleteState {
+            desired_state: State {
+                workloads: HashMap::from([(WORKLOAD_NAME_1.to_owned(), moved_workload)]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_workloads_for_agent()
+            .with(mockall::predicate::eq(AGENT_A.to_string()))
+            .once()
+            .return_const(vec![workload_on_agent_a]);
+        mock_server_state
+            .expect_update()
+            .with(
+                mockall::predicate::eq(expected_state),
+                mockall::predicate::eq(vec![format!("desiredState.workloads.{WORKLOAD_NAME_1}")]),
+            )
+            .once()
+            .return_const(Ok(Some((
+                vec![workload_on_agent_b.clone()],
+                vec![deleted_workload.clone()],
+            ))));
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .request_drain_agent(
+                REQUEST_ID_A.to_string(),
+                commands::DrainAgentRequest {
+                    agent_name: AGENT_A.to_string(),
+                    target_agent: Some(AGENT_B.to_string()),
+                    remove: false,
+                },
+            )
+            .await
+            .is_ok());
+
+        // every agent receives its own message
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkload(UpdateWorkload {
+                added_workloads: vec![],
+                deleted_workloads: vec![deleted_workload.clone()],
+                sequence_number: 1,
+                initial: false,
+                deadline_ms: None,
+            })
+        );
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkload(UpdateWorkload {
+                added_workloads: vec![workload_on_agent_b.clone()],
+                deleted_workloads: vec![],
+                sequence_number: 1,
+                initial: false,
+                deadline_ms: None,
+            })
+        );
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(Response {
+                request_id: REQUEST_ID_A.to_string(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: ResponseContent::UpdateStateSuccess(UpdateStateSuccess {
+                    added_workloads: vec![workload_on_agent_b.instance_name.to_string()],
+                    deleted_workloads: vec![deleted_workload.instance_name.to_string()],
+                    rejected_workloads: vec![],
+                }),
+            })
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-drains-agent~1]
+    // [utest->swdd~server-records-trace-id-in-events~1]
+    #[tokio::test]
+    async fn utest_server_drain_agent_removes_disconnected_agent() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let workload_on_agent_a = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME_NAME.to_string(),
+        );
+        let unscheduled_workload = generate_test_workload_spec_with_param(
+            "".to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME_NAME.to_string(),
+        );
+        let deleted_workload = DeletedWorkload {
+            instance_name: workload_on_agent_a.instance_name.clone(),
+            dependencies: HashMap::new(),
+        };
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_workloads_for_agent()
+            .return_const(vec![workload_on_agent_a.clone()]);
+        mock_server_state
+            .expect_update()
+            .once()
+            .return_const(Ok(Some((
+                vec![unscheduled_workload.clone()],
+                vec![deleted_workload.clone()],
+            ))));
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .agent_hello(commands::AgentHello {
+                agent_name: AGENT_A.to_string(),
+                ..Default::default()
+            })
+            .await
+            .is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkload(_)
+        ));
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateConnectedAgents(_)
+        ));
+        assert!(to_server.agent_gone(AGENT_A.to_string()).await.is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkloadState(_)
+        ));
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateConnectedAgents(_)
+        ));
+
+        assert!(to_server
+            .request_drain_agent(
+                REQUEST_ID_A.to_string(),
+                commands::DrainAgentRequest {
+                    agent_name: AGENT_A.to_string(),
+                    target_agent: None,
+                    remove: true,
+                },
+            )
+            .await
+            .is_ok());
+
+        // the workload on the disconnected agent is removed by the server itself
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                workload_states: vec![WorkloadState {
+                    instance_name: workload_on_agent_a.instance_name.clone(),
+                    execution_state: ExecutionState::removed(),
+                    agent_timestamp: None,
+                    server_timestamp: None,
+                }],
+            })
+        );
+        // the unscheduled workload is not sent to any agent
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(Response {
+                response_content: ResponseContent::UpdateStateSuccess(_),
+                ..
+            })
+        ));
+
+        assert!(to_server
+            .request_events(REQUEST_ID_A.to_string(), commands::EventsRequest::default())
+            .await
+            .is_ok());
+        let Some(FromServer::Response(Response {
+            response_content: ResponseContent::Events(events),
+            ..
+        })) = comm_middle_ware_receiver.recv().await
+        else {
+            panic!("Expected an Events response");
+        };
+        assert_eq!(
+            events
+                .events
+                .last()
+                .map(|event| (event.kind, event.trace_id.clone())),
+            Some((
+                commands::EventKind::AgentRemoved,
+                Some(TRACE_ID.to_string())
+            ))
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-reaps-stale-workload-states~2]
+    #[tokio::test]
+    async fn utest_server_reaps_stale_workload_states() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        server.enable_stale_state_reaper(std::time::Duration::from_millis(10));
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state.expect_cleanup_state().return_const(());
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        let test_wl_1_state_running = common::objects::generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::running(),
+        );
+        assert!(to_server
+            .update_workload_state(vec![test_wl_1_state_running.clone()])
+            .await
+            .is_ok());
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                workload_states: vec![test_wl_1_state_running],
+            })
+        );
+
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                workload_states: vec![common::objects::generate_test_workload_state_with_agent(
+                    WORKLOAD_NAME_1,
+                    AGENT_A,
+                    ExecutionState::stale(),
+                )],
+            })
+        );
+
+        assert!(to_server
+            .request_events(REQUEST_ID_A.to_string(), commands::EventsRequest::default())
+            .await
+            .is_ok());
+        let Some(FromServer::Response(Response {
+            response_content: ResponseContent::Events(events),
+            ..
+        })) = comm_middle_ware_receiver.recv().await
+        else {
+            panic!("Expected an Events response");
+        };
+        assert_eq!(
+            events.events.last().map(|event| event.kind),
+            Some(commands::EventKind::WorkloadStateStale)
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-records-agent-events~1]
+    #[tokio::test]
+    async fn utest_server_records_agent_events() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        server.server_state = MockServerState::new();
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .agent_event(commands::AgentEvent {
+                agent_name: AGENT_A.to_string(),
+                kind: commands::EventKind::ControlInterfaceClosed,
+                workload_name: WORKLOAD_NAME_1.to_string(),
+                message: "message".to_string(),
+            })
+            .await
+            .is_ok());
+        assert!(to_server
+            .request_events(REQUEST_ID_A.to_string(), commands::EventsRequest::default())
+            .await
+            .is_ok());
+        let Some(FromServer::Response(Response {
+            response_content: ResponseContent::Events(events),
+            ..
+        })) = comm_middle_ware_receiver.recv().await
+        else {
+            panic!("Expected an Events response");
+        };
+        assert_eq!(
+            events.events.last().map(|event| (
+                event.kind,
+                event.agent_name.clone(),
+                event.workload_name.clone(),
+                event.message.clone()
+            )),
+            Some((
+                commands::EventKind::ControlInterfaceClosed,
+                Some(AGENT_A.to_string()),
+                Some(WORKLOAD_NAME_1.to_string()),
+                "message".to_string()
+            ))
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-correlates-workload-failures-to-agent-incidents~1]
+    #[tokio::test]
+    async fn utest_server_records_agent_incident_on_correlated_failures() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state.expect_cleanup_state().return_const(());
+        server.server_state = mock_server_state;
+        server.enable_incident_detection(std::time::Duration::from_secs(60), 2);
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        let failed_states = vec![
+            common::objects::generate_test_workload_state_with_agent(
+                WORKLOAD_NAME_1,
+                AGENT_A,
+                ExecutionState::failed("error"),
+            ),
+            common::objects::generate_test_workload_state_with_agent(
+                WORKLOAD_NAME_2,
+                AGENT_A,
+                ExecutionState::lost(),
+            ),
+        ];
+        assert!(to_server
+            .update_workload_state(failed_states.clone())
+            .await
+            .is_ok());
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await,
+            Some(FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                workload_states: failed_states
+            }))
+        );
+
+        assert!(to_server
+            .request_events(REQUEST_ID_A.to_string(), commands::EventsRequest::default())
+            .await
+            .is_ok());
+        let Some(FromServer::Response(Response {
+            response_content: ResponseContent::Events(events),
+            ..
+        })) = comm_middle_ware_receiver.recv().await
+        else {
+            panic!("Expected an Events response");
+        };
+        assert_eq!(
+            events.events.last().map(|event| (
+                event.kind,
+                event.agent_name.clone(),
+                event.workload_name.clone(),
+                event.message.clone()
+            )),
+            Some((
+                commands::EventKind::AgentIncident,
+                Some(AGENT_A.to_string()),
+                None,
+                format!("2 workloads failed within 60s: '{WORKLOAD_NAME_1}', '{WORKLOAD_NAME_2}'")
+            ))
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-distributes-workloads-enabled-on-agent~1]
+    // [utest->swdd~server-reports-rejected-workloads~1]
+    #[tokio::test]
+    async fn utest_server_sends_only_workloads_enabled_on_agent() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+
+        let mut w1 = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME_NAME.to_string(),
+        );
+        w1.enabled_if = "camera == true".to_string();
+        let mut w2 = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_2.to_owned(),
+            RUNTIME_NAME.to_string(),
+        );
+        w2.enabled_if = "lidar".to_string();
+
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_workloads_for_agent()
+            .with(mockall::predicate::eq(AGENT_A.to_string()))
+            .once()
+            .return_const(vec![w1.clone(), w2.clone()]);
+        mock_server_state
+            .expect_update()
+            .once()
+            .return_const(Ok(Some((vec![w1.clone(), w2.clone()], vec![]))));
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .agent_hello(commands::AgentHello {
+                agent_name: AGENT_A.to_string(),
+                attributes: HashMap::from([("camera".to_string(), "true".to_string())]),
+                ..Default::default()
+            })
+            .await
+            .is_ok());
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkload(UpdateWorkload {
+                added_workloads: vec![w1.clone()],
+                deleted_workloads: vec![],
+                sequence_number: 1,
+                initial: true,
+                deadline_ms: None,
+            })
+        );
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateConnectedAgents(_)
+        ));
+
+        assert!(to_server
+            .update_state(REQUEST_ID_A.to_string(), CompleteState::default(), vec![])
+            .await
+            .is_ok());
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkload(UpdateWorkload {
+                added_workloads: vec![w1.clone()],
+                deleted_workloads: vec![],
+                sequence_number: 2,
+                initial: false,
+                deadline_ms: None,
+            })
+        );
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(Response {
+                request_id: REQUEST_ID_A.to_string(),
+                trace_id: TRACE_ID.to_string(),
+                response_content: ResponseContent::UpdateStateSuccess(UpdateStateSuccess {
+                    added_workloads: vec![w1.instance_name.to_string()],
+                    deleted_workloads: vec![],
+                    rejected_workloads: vec![commands::RejectedWorkload {
+                        workload_name: WORKLOAD_NAME_2.to_string(),
+                        reason: "The enabledIf expression 'lidar' is not met by agent 'agent_A'."
+                            .to_string(),
+                    }],
+                }),
+            })
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
 }
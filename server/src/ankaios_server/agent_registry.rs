@@ -0,0 +1,397 @@
+// Copyright (c) 2023 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use common::helpers::now_ms;
+use common::objects::{
+    evaluate_enabled_if, AgentConnectionStatus, AgentInfo, AgentResources, WorkloadSpec,
+};
+use std::collections::HashMap;
+
+type AgentName = String;
+
+// Agents stay in the registry after disconnecting such that the system state
+// can still show when an agent was seen for the last time.
+#[derive(Default)]
+pub struct AgentRegistry {
+    agents: HashMap<AgentName, AgentInfo>,
+}
+
+impl AgentRegistry {
+    // [impl->swdd~server-tracks-agent-state~1]
+    // The sequence numbers of the workload updates continue over reconnects, thus the last
+    // acknowledged one is kept.
+    pub fn agent_connected(&mut self, agent_info: AgentInfo) {
+        let last_acknowledged_update = self
+            .agents
+            .get(&agent_info.agent_name)
+            .map(|known_agent_info| known_agent_info.last_acknowledged_update)
+            .unwrap_or_default();
+        self.agents.insert(
+            agent_info.agent_name.clone(),
+            AgentInfo {
+                connection_status: AgentConnectionStatus::Connected,
+                last_heartbeat: now_ms(),
+                last_acknowledged_update,
+                ..agent_info
+            },
+        );
+    }
+
+    // [impl->swdd~server-standby-promotion~1]
+    pub fn agent_restored(&mut self, agent_info: AgentInfo) {
+        self.agents.insert(
+            agent_info.agent_name.clone(),
+            AgentInfo {
+                connection_status: AgentConnectionStatus::Disconnected,
+                ..agent_info
+            },
+        );
+    }
+
+    // [impl->swdd~server-tracks-agent-state~1]
+    pub fn agent_disconnected(&mut self, agent_name: &str) {
+        if let Some(agent_info) = self.agents.get_mut(agent_name) {
+            agent_info.connection_status = AgentConnectionStatus::Disconnected;
+        }
+    }
+
+    // [impl->swdd~server-drains-agent~1]
+    pub fn agent_removed(&mut self, agent_name: &str) -> bool {
+        self.agents.remove(agent_name).is_some()
+    }
+
+    pub fn is_disconnected(&self, agent_name: &str) -> bool {
+        self.agents.get(agent_name).is_some_and(|agent_info| {
+            agent_info.connection_status == AgentConnectionStatus::Disconnected
+        })
+    }
+
+    // [impl->swdd~server-tracks-agent-state~1]
+    pub fn heartbeat(&mut self, agent_name: &str) {
+        if let Some(agent_info) = self.agents.get_mut(agent_name) {
+            agent_info.last_heartbeat = now_ms();
+        }
+    }
+
+    // [impl->swdd~server-stores-agent-resources~1]
+    pub fn update_resources(&mut self, agent_name: &str, resources: AgentResources) {
+        if let Some(agent_info) = self.agents.get_mut(agent_name) {
+            agent_info.resources = Some(resources);
+            agent_info.last_heartbeat = now_ms();
+        }
+    }
+
+    // [impl->swdd~server-tracks-acknowledged-update-workload~1]
+    pub fn update_workload_acknowledged(&mut self, agent_name: &str, sequence_number: u64) {
+        if let Some(agent_info) = self.agents.get_mut(agent_name) {
+            agent_info.last_acknowledged_update =
+                agent_info.last_acknowledged_update.max(sequence_number);
+            agent_info.last_heartbeat = now_ms();
+        }
+    }
+
+    // [impl->swdd~server-distributes-workloads-enabled-on-agent~1]
+    // The workloads of unknown agents are kept as they are evaluated again when the agent connects.
+    pub fn is_workload_enabled(&self, workload: &WorkloadSpec) -> bool {
+        self.rejection_reason(workload).is_none()
+    }
+
+    // Returns why the agent of the workload will not start it. The reason is only known for
+    // agents which have connected at least once.
+    // [impl->swdd~server-reports-rejected-workloads~1]
+    pub fn rejection_reason(&self, workload: &WorkloadSpec) -> Option<String> {
+        let agent_name = workload.instance_name.agent_name();
+        let agent_info = self.agents.get(agent_name)?;
+        match evaluate_enabled_if(&workload.enabled_if, &agent_info.attributes) {
+            Ok(true) => {}
+            Ok(false) => {
+                return Some(format!(
+                    "The enabledIf expression '{}' is not met by agent '{}'.",
+                    workload.enabled_if, agent_name
+                ))
+            }
+            Err(err) => return Some(err),
+        }
+        // agents of older versions do not report their runtimes
+        if !agent_info.runtimes.is_empty() && !agent_info.runtimes.contains(&workload.runtime) {
+            return Some(format!(
+                "The runtime '{}' is not supported by agent '{}'.",
+                workload.runtime, agent_name
+            ));
+        }
+        None
+    }
+
+    // [impl->swdd~server-distributes-connected-agents~1]
+    pub fn connected_agent_names(&self) -> Vec<String> {
+        let mut agent_names: Vec<String> = self
+            .agents
+            .values()
+            .filter(|agent_info| agent_info.connection_status == AgentConnectionStatus::Connected)
+            .map(|agent_info| agent_info.agent_name.clone())
+            .collect();
+        agent_names.sort();
+        agent_names
+    }
+
+    // [impl->swdd~server-provides-support-info~1]
+    pub fn get_agents(&self) -> Vec<AgentInfo> {
+        let mut agents: Vec<AgentInfo> = self.agents.values().cloned().collect();
+        agents.sort_by(|a, b| a.agent_name.cmp(&b.agent_name));
+        agents
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::AgentRegistry;
+    use common::objects::{
+        generate_test_workload_spec_with_param, AgentConnectionStatus, AgentInfo, AgentResources,
+    };
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+
+    fn agent_info(agent_name: &str) -> AgentInfo {
+        AgentInfo {
+            agent_name: agent_name.to_string(),
+            version: "0.4.0".to_string(),
+            runtimes: vec!["podman".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn agent_names(registry: &AgentRegistry) -> Vec<String> {
+        registry
+            .get_agents()
+            .into_iter()
+            .map(|agent| agent.agent_name)
+            .collect()
+    }
+
+    // [utest->swdd~server-provides-support-info~1]
+    #[test]
+    fn utest_agent_registry_returns_agents_sorted_by_name() {
+        let mut registry = AgentRegistry::default();
+        registry.agent_connected(agent_info(AGENT_B));
+        registry.agent_connected(agent_info(AGENT_A));
+
+        assert_eq!(agent_names(&registry), vec![AGENT_A, AGENT_B]);
+    }
+
+    // [utest->swdd~server-tracks-agent-state~1]
+    #[test]
+    fn utest_agent_registry_sets_connection_status_and_heartbeat() {
+        let mut registry = AgentRegistry::default();
+        registry.agent_connected(agent_info(AGENT_A));
+        registry.agent_connected(agent_info(AGENT_B));
+
+        registry.agent_disconnected(AGENT_A);
+
+        let agents = registry.get_agents();
+        assert_eq!(
+            agents[0].connection_status,
+            AgentConnectionStatus::Disconnected
+        );
+        assert_eq!(
+            agents[1].connection_status,
+            AgentConnectionStatus::Connected
+        );
+        assert!(agents.iter().all(|agent| agent.last_heartbeat > 0));
+        assert_eq!(agents[0].runtimes, vec!["podman".to_string()]);
+    }
+
+    // [utest->swdd~server-distributes-connected-agents~1]
+    #[test]
+    fn utest_agent_registry_returns_sorted_names_of_connected_agents() {
+        let mut registry = AgentRegistry::default();
+        registry.agent_connected(agent_info(AGENT_B));
+        registry.agent_connected(agent_info(AGENT_A));
+        registry.agent_restored(agent_info("agent_C"));
+        assert_eq!(registry.connected_agent_names(), vec![AGENT_A, AGENT_B]);
+
+        registry.agent_disconnected(AGENT_A);
+        assert_eq!(registry.connected_agent_names(), vec![AGENT_B]);
+    }
+
+    // [utest->swdd~server-standby-promotion~1]
+    #[test]
+    fn utest_agent_registry_restores_agents_as_disconnected() {
+        let mut registry = AgentRegistry::default();
+        registry.agent_restored(AgentInfo {
+            connection_status: AgentConnectionStatus::Connected,
+            last_heartbeat: 1000,
+            ..agent_info(AGENT_A)
+        });
+
+        let agents = registry.get_agents();
+        assert_eq!(
+            agents[0].connection_status,
+            AgentConnectionStatus::Disconnected
+        );
+        assert_eq!(agents[0].last_heartbeat, 1000);
+        assert!(registry.is_disconnected(AGENT_A));
+    }
+
+    // [utest->swdd~server-tracks-agent-state~1]
+    #[test]
+    fn utest_agent_registry_ignores_heartbeat_of_unknown_agent() {
+        let mut registry = AgentRegistry::default();
+        registry.heartbeat(AGENT_A);
+
+        assert!(registry.get_agents().is_empty());
+    }
+
+    // [utest->swdd~server-stores-agent-resources~1]
+    // [utest->swdd~server-provides-agent-resources~1]
+    #[test]
+    fn utest_agent_registry_stores_latest_resources_until_reconnect() {
+        let mut registry = AgentRegistry::default();
+        registry.agent_connected(agent_info(AGENT_A));
+
+        let resources = AgentResources {
+            free_cpu_millis: 1500,
+            free_memory_bytes: 4096,
+        };
+        registry.update_resources(AGENT_A, resources);
+        registry.update_resources(AGENT_B, resources);
+
+        let agents = registry.get_agents();
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].resources, Some(resources));
+
+        registry.agent_connected(agent_info(AGENT_A));
+        assert_eq!(registry.get_agents()[0].resources, None);
+    }
+
+    // [utest->swdd~server-tracks-acknowledged-update-workload~1]
+    #[test]
+    fn utest_agent_registry_tracks_last_acknowledged_update() {
+        let mut registry = AgentRegistry::default();
+        registry.agent_connected(agent_info(AGENT_A));
+
+        registry.update_workload_acknowledged(AGENT_A, 5);
+        registry.update_workload_acknowledged(AGENT_A, 3);
+        registry.update_workload_acknowledged(AGENT_B, 7);
+
+        let agents = registry.get_agents();
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].last_acknowledged_update, 5);
+
+        registry.agent_disconnected(AGENT_A);
+        registry.agent_connected(agent_info(AGENT_A));
+        assert_eq!(registry.get_agents()[0].last_acknowledged_update, 5);
+    }
+
+    // [utest->swdd~server-distributes-workloads-enabled-on-agent~1]
+    #[test]
+    fn utest_agent_registry_evaluates_enabled_if_against_agent_attributes() {
+        let mut registry = AgentRegistry::default();
+        registry.agent_connected(AgentInfo {
+            attributes: HashMap::from([("camera".to_string(), "true".to_string())]),
+            ..agent_info(AGENT_A)
+        });
+        registry.agent_connected(agent_info(AGENT_B));
+
+        let mut workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_string(),
+            "camera_pipeline".to_string(),
+            "podman".to_string(),
+        );
+        assert!(registry.is_workload_enabled(&workload));
+
+        workload.enabled_if = "camera == true".to_string();
+        assert!(registry.is_workload_enabled(&workload));
+
+        let mut workload_on_agent_b = generate_test_workload_spec_with_param(
+            AGENT_B.to_string(),
+            "camera_pipeline".to_string(),
+            "podman".to_string(),
+        );
+        workload_on_agent_b.enabled_if = "camera == true".to_string();
+        assert!(!registry.is_workload_enabled(&workload_on_agent_b));
+
+        workload.enabled_if = "camera is true".to_string();
+        assert!(!registry.is_workload_enabled(&workload));
+    }
+
+    // [utest->swdd~server-distributes-workloads-enabled-on-agent~1]
+    #[test]
+    fn utest_agent_registry_keeps_workloads_of_unknown_agents() {
+        let registry = AgentRegistry::default();
+        let mut workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_string(),
+            "camera_pipeline".to_string(),
+            "podman".to_string(),
+        );
+        workload.enabled_if = "camera == true".to_string();
+
+        assert!(registry.is_workload_enabled(&workload));
+    }
+
+    // [utest->swdd~server-reports-rejected-workloads~1]
+    #[test]
+    fn utest_agent_registry_returns_rejection_reason() {
+        let mut registry = AgentRegistry::default();
+        registry.agent_connected(agent_info(AGENT_A));
+
+        let mut workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_string(),
+            "camera_pipeline".to_string(),
+            "podman".to_string(),
+        );
+        assert_eq!(registry.rejection_reason(&workload), None);
+
+        workload.enabled_if = "camera == true".to_string();
+        assert_eq!(
+            registry.rejection_reason(&workload),
+            Some("The enabledIf expression 'camera == true' is not met by agent 'agent_A'.".into())
+        );
+
+        workload.enabled_if = String::new();
+        workload.runtime = "podman-kube".to_string();
+        assert_eq!(
+            registry.rejection_reason(&workload),
+            Some("The runtime 'podman-kube' is not supported by agent 'agent_A'.".into())
+        );
+        assert!(!registry.is_workload_enabled(&workload));
+    }
+
+    // [utest->swdd~server-drains-agent~1]
+    #[test]
+    fn utest_agent_registry_removes_agent() {
+        let mut registry = AgentRegistry::default();
+        registry.agent_connected(agent_info(AGENT_A));
+        registry.agent_connected(agent_info(AGENT_B));
+        registry.agent_disconnected(AGENT_A);
+
+        assert!(registry.is_disconnected(AGENT_A));
+        assert!(!registry.is_disconnected(AGENT_B));
+
+        assert!(registry.agent_removed(AGENT_A));
+        assert!(!registry.agent_removed(AGENT_A));
+        assert!(!registry.is_disconnected(AGENT_A));
+        assert_eq!(agent_names(&registry), vec![AGENT_B]);
+    }
+}
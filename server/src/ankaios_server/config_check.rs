@@ -0,0 +1,590 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use ankaios_model::input_limits;
+use ankaios_model::manifest_migration;
+use common::objects::{verify_enabled_if, CronSchedule, State};
+use serde::{Deserialize, Serialize};
+
+use super::cycle_check;
+
+// The runtimes supported by the Ankaios agent.
+const KNOWN_RUNTIMES: [&str; 2] = ["podman", "podman-kube"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckKind {
+    Parse,
+    Schema,
+    Dependencies,
+    Configs,
+    Templates,
+    Placement,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Finding {
+    pub severity: Severity,
+    pub check: CheckKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workload: Option<String>,
+    pub message: String,
+}
+
+impl Finding {
+    fn error(check: CheckKind, workload: Option<&str>, message: String) -> Self {
+        Finding {
+            severity: Severity::Error,
+            check,
+            workload: workload.map(ToOwned::to_owned),
+            message,
+        }
+    }
+
+    fn warning(check: CheckKind, workload: Option<&str>, message: String) -> Self {
+        Finding {
+            severity: Severity::Warning,
+            check,
+            workload: workload.map(ToOwned::to_owned),
+            message,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct CheckConfigReport {
+    pub valid: bool,
+    pub findings: Vec<Finding>,
+}
+
+// [impl->swdd~server-check-config-parses-manifest~1]
+// The agents are only checked if at least one available agent is given.
+pub fn check_config(manifest: &str, available_agents: &[String]) -> CheckConfigReport {
+    let findings = match parse_state(manifest) {
+        Ok(state) => {
+            let mut findings = Vec::new();
+            findings.extend(check_schema(&state));
+            findings.extend(check_dependencies(&state));
+            findings.extend(check_configs(&state));
+            findings.extend(check_templates(&state));
+            findings.extend(check_placement(&state, available_agents));
+            findings
+        }
+        Err(finding) => vec![finding],
+    };
+
+    CheckConfigReport {
+        valid: findings
+            .iter()
+            .all(|finding| finding.severity != Severity::Error),
+        findings,
+    }
+}
+
+// [impl->swdd~common-limits-manifest-size-and-depth~2]
+fn parse_state(manifest: &str) -> Result<State, Finding> {
+    let value: serde_yaml::Value = input_limits::parse_manifest(manifest)
+        .map_err(|err| Finding::error(CheckKind::Parse, None, err.to_string()))?;
+    // [impl->swdd~common-migrates-manifests-to-current-api-version~1]
+    State::deserialize(manifest_migration::migrate_manifest(value))
+        .map_err(|err| Finding::error(CheckKind::Schema, None, err.to_string()))
+}
+
+// sorted to have a stable order of the findings
+fn sorted_workload_names(state: &State) -> Vec<&str> {
+    let mut workload_names: Vec<&str> = state.workloads.keys().map(String::as_str).collect();
+    workload_names.sort();
+    workload_names
+}
+
+// [impl->swdd~server-check-config-validates-schema~3]
+fn check_schema(state: &State) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    if !State::is_compatible_format(&state.api_version) {
+        findings.push(Finding::error(
+            CheckKind::Schema,
+            None,
+            format!("Unsupported API version '{}'", state.api_version),
+        ));
+    }
+
+    for workload_name in sorted_workload_names(state) {
+        if workload_name.is_empty()
+            || !workload_name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            findings.push(Finding::error(
+                CheckKind::Schema,
+                Some(workload_name),
+                "Workload names may only contain the characters 'a-z', 'A-Z', '0-9', '_' and '-'"
+                    .to_owned(),
+            ));
+        }
+
+        if let Err(err) = verify_enabled_if(&state.workloads[workload_name].enabled_if) {
+            findings.push(Finding::error(CheckKind::Schema, Some(workload_name), err));
+        }
+
+        if let Some(Err(err)) = state.workloads[workload_name]
+            .schedule
+            .as_deref()
+            .map(str::parse::<CronSchedule>)
+        {
+            findings.push(Finding::error(CheckKind::Schema, Some(workload_name), err));
+        }
+
+        if let Some(Err(err)) = state.workloads[workload_name]
+            .resources
+            .as_ref()
+            .map(|resources| resources.requests.verify())
+        {
+            findings.push(Finding::error(CheckKind::Schema, Some(workload_name), err));
+        }
+    }
+    findings
+}
+
+// [impl->swdd~server-check-config-analyzes-dependency-graph~1]
+fn check_dependencies(state: &State) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = cycle_check::unknown_dependencies(state, None)
+        .into_iter()
+        .map(|(workload_name, dependency)| {
+            Finding::error(
+                CheckKind::Dependencies,
+                Some(&workload_name),
+                format!("Dependency on unknown workload '{dependency}'"),
+            )
+        })
+        .collect();
+
+    if let Some(workload_part_of_cycle) = cycle_check::dfs(state, None) {
+        findings.push(Finding::error(
+            CheckKind::Dependencies,
+            Some(&workload_part_of_cycle),
+            format!("Workload '{workload_part_of_cycle}' is part of a dependency cycle"),
+        ));
+    }
+    findings
+}
+
+// [impl->swdd~server-check-config-verifies-config-references~1]
+fn check_configs(state: &State) -> Vec<Finding> {
+    sorted_workload_names(state)
+        .into_iter()
+        .filter_map(|workload_name| {
+            state
+                .get_configs_of_workload(&state.workloads[workload_name])
+                .err()
+                .map(|err| Finding::error(CheckKind::Configs, Some(workload_name), err))
+        })
+        .collect()
+}
+
+// [impl->swdd~server-check-config-expands-workload-templates~1]
+fn check_templates(state: &State) -> Vec<Finding> {
+    sorted_workload_names(state)
+        .into_iter()
+        .filter_map(|workload_name| {
+            match state.expand_workload(&state.workloads[workload_name]) {
+                Ok(workload) if workload.runtime.is_empty() => Some("No runtime is set".to_owned()),
+                Ok(_) => None,
+                Err(err) => Some(err),
+            }
+            .map(|message| Finding::error(CheckKind::Templates, Some(workload_name), message))
+        })
+        .collect()
+}
+
+// [impl->swdd~server-check-config-checks-placement~1]
+fn check_placement(state: &State, available_agents: &[String]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for workload_name in sorted_workload_names(state) {
+        // failed expansions are already reported by the template check
+        let workload = state
+            .expand_workload(&state.workloads[workload_name])
+            .unwrap_or_else(|_| state.workloads[workload_name].clone());
+        if workload.agent.is_empty() {
+            findings.push(Finding::error(
+                CheckKind::Placement,
+                Some(workload_name),
+                "No agent is assigned".to_owned(),
+            ));
+        } else if !available_agents.is_empty() && !available_agents.contains(&workload.agent) {
+            findings.push(Finding::error(
+                CheckKind::Placement,
+                Some(workload_name),
+                format!("The agent '{}' is not available", workload.agent),
+            ));
+        }
+
+        if !workload.runtime.is_empty() && !KNOWN_RUNTIMES.contains(&workload.runtime.as_str()) {
+            findings.push(Finding::warning(
+                CheckKind::Placement,
+                Some(workload_name),
+                format!(
+                    "The runtime '{}' is not supported by the Ankaios agent",
+                    workload.runtime
+                ),
+            ));
+        }
+    }
+    findings
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{check_config, CheckConfigReport, CheckKind, Finding, Severity};
+
+    const VALID_MANIFEST: &str = r#"
+apiVersion: v0.1
+workloads:
+  databroker:
+    runtime: podman
+    agent: agent_A
+    runtimeConfig: |
+      image: ghcr.io/eclipse/kuksa.val/databroker:0.4.1
+  speed-provider:
+    runtime: podman-kube
+    agent: agent_B
+    dependencies:
+      databroker: ADD_COND_RUNNING
+    configs:
+      - speed
+    runtimeConfig: |
+      manifest: ""
+configs:
+  speed:
+    value: "50"
+"#;
+
+    fn error(check: CheckKind, workload: &str, message: &str) -> Finding {
+        Finding {
+            severity: Severity::Error,
+            check,
+            workload: Some(workload.to_owned()),
+            message: message.to_owned(),
+        }
+    }
+
+    // [utest->swdd~server-check-config-parses-manifest~1]
+    #[test]
+    fn utest_check_config_valid_manifest() {
+        assert_eq!(
+            check_config(
+                VALID_MANIFEST,
+                &["agent_A".to_owned(), "agent_B".to_owned()]
+            ),
+            CheckConfigReport {
+                valid: true,
+                findings: vec![],
+            }
+        );
+    }
+
+    // [utest->swdd~server-check-config-parses-manifest~1]
+    #[test]
+    fn utest_check_config_invalid_yaml() {
+        let report = check_config("workloads: [", &[]);
+
+        assert!(!report.valid);
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].check, CheckKind::Parse);
+    }
+
+    // [utest->swdd~server-check-config-validates-schema~3]
+    #[test]
+    fn utest_check_config_schema_violations() {
+        let report = check_config(
+            "apiVersion: v0.1\nworkloads:\n  nginx:\n    agent: agent_A\n    restartPolicy: SOMETIMES\n",
+            &[],
+        );
+        assert!(!report.valid);
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].check, CheckKind::Schema);
+
+        let manifest = VALID_MANIFEST
+            .replace("apiVersion: v0.1", "apiVersion: v0.2")
+            .replace("  databroker:\n", "  data.broker:\n");
+        let report = check_config(&manifest, &[]);
+        assert!(!report.valid);
+        assert_eq!(
+            &report.findings[..2],
+            &[
+                Finding {
+                    severity: Severity::Error,
+                    check: CheckKind::Schema,
+                    workload: None,
+                    message: "Unsupported API version 'v0.2'".to_owned(),
+                },
+                error(
+                    CheckKind::Schema,
+                    "data.broker",
+                    "Workload names may only contain the characters 'a-z', 'A-Z', '0-9', '_' and '-'"
+                ),
+            ]
+        );
+    }
+
+    // [utest->swdd~server-check-config-validates-schema~3]
+    #[test]
+    fn utest_check_config_invalid_enabled_if() {
+        let manifest = VALID_MANIFEST.replace(
+            "    agent: agent_A\n",
+            "    agent: agent_A\n    enabledIf: camera = true\n",
+        );
+
+        assert_eq!(
+            check_config(&manifest, &[]),
+            CheckConfigReport {
+                valid: false,
+                findings: vec![error(
+                    CheckKind::Schema,
+                    "databroker",
+                    "Invalid enabledIf expression 'camera = true': Invalid attribute name 'camera = true'."
+                )],
+            }
+        );
+    }
+
+    // [utest->swdd~server-check-config-validates-schema~3]
+    #[test]
+    fn utest_check_config_invalid_schedule() {
+        let manifest = VALID_MANIFEST.replace(
+            "    agent: agent_A\n",
+            "    agent: agent_A\n    schedule: \"0 2 * *\"\n",
+        );
+
+        assert_eq!(
+            check_config(&manifest, &[]),
+            CheckConfigReport {
+                valid: false,
+                findings: vec![error(
+                    CheckKind::Schema,
+                    "databroker",
+                    "Invalid schedule '0 2 * *': expected the 5 fields minute, hour, day of month, month and day of week."
+                )],
+            }
+        );
+    }
+
+    // [utest->swdd~server-check-config-validates-schema~3]
+    #[test]
+    fn utest_check_config_invalid_resource_requests() {
+        let manifest = VALID_MANIFEST.replace(
+            "    agent: agent_A\n",
+            "    agent: agent_A\n    resources:\n      requests:\n        memory: 256MB\n",
+        );
+
+        assert_eq!(
+            check_config(&manifest, &[]),
+            CheckConfigReport {
+                valid: false,
+                findings: vec![error(
+                    CheckKind::Schema,
+                    "databroker",
+                    "Invalid memory request '256MB'."
+                )],
+            }
+        );
+    }
+
+    // [utest->swdd~server-check-config-analyzes-dependency-graph~1]
+    #[test]
+    fn utest_check_config_unknown_dependency() {
+        let manifest = VALID_MANIFEST.replace(
+            "      databroker: ADD_COND_RUNNING",
+            "      broker: ADD_COND_RUNNING",
+        );
+
+        assert_eq!(
+            check_config(&manifest, &[]),
+            CheckConfigReport {
+                valid: false,
+                findings: vec![error(
+                    CheckKind::Dependencies,
+                    "speed-provider",
+                    "Dependency on unknown workload 'broker'"
+                )],
+            }
+        );
+    }
+
+    // [utest->swdd~server-check-config-analyzes-dependency-graph~1]
+    #[test]
+    fn utest_check_config_dependency_cycle() {
+        let manifest = VALID_MANIFEST.replace(
+            "    agent: agent_A\n",
+            "    agent: agent_A\n    dependencies:\n      speed-provider: ADD_COND_RUNNING\n",
+        );
+
+        let report = check_config(&manifest, &[]);
+        assert!(!report.valid);
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].check, CheckKind::Dependencies);
+        assert!(report.findings[0]
+            .message
+            .ends_with("is part of a dependency cycle"));
+    }
+
+    // [utest->swdd~server-check-config-verifies-config-references~1]
+    #[test]
+    fn utest_check_config_unknown_config_reference() {
+        let manifest = VALID_MANIFEST.replace("      - speed", "      - velocity");
+
+        assert_eq!(
+            check_config(&manifest, &[]),
+            CheckConfigReport {
+                valid: false,
+                findings: vec![error(
+                    CheckKind::Configs,
+                    "speed-provider",
+                    "Config 'velocity' does not exist."
+                )],
+            }
+        );
+    }
+
+    // [utest->swdd~server-check-config-checks-placement~1]
+    #[test]
+    fn utest_check_config_placement() {
+        let manifest = VALID_MANIFEST.replace("runtime: podman-kube", "runtime: containerd");
+
+        assert_eq!(
+            check_config(&manifest, &["agent_A".to_owned()]),
+            CheckConfigReport {
+                valid: false,
+                findings: vec![
+                    error(
+                        CheckKind::Placement,
+                        "speed-provider",
+                        "The agent 'agent_B' is not available"
+                    ),
+                    Finding {
+                        severity: Severity::Warning,
+                        check: CheckKind::Placement,
+                        workload: Some("speed-provider".to_owned()),
+                        message: "The runtime 'containerd' is not supported by the Ankaios agent"
+                            .to_owned(),
+                    },
+                ],
+            }
+        );
+    }
+
+    // [utest->swdd~server-check-config-expands-workload-templates~1]
+    #[test]
+    fn utest_check_config_workload_templates() {
+        let manifest = VALID_MANIFEST
+            .replace(
+                "    runtime: podman\n    agent: agent_A\n    runtimeConfig: |\n      image: ghcr.io/eclipse/kuksa.val/databroker:0.4.1\n",
+                "    agent: agent_A\n    template: broker\n    templateParameters:\n      tag: 0.4.1\n",
+            )
+            .replace(
+                "configs:\n  speed:",
+                "workloadTemplates:\n  broker:\n    runtime: podman\n    runtimeConfig: |\n      image: ghcr.io/eclipse/kuksa.val/databroker:{{tag}}\nconfigs:\n  speed:",
+            );
+        assert_eq!(
+            check_config(&manifest, &[]),
+            CheckConfigReport {
+                valid: true,
+                findings: vec![],
+            }
+        );
+
+        let report = check_config(
+            &manifest.replace("template: broker", "template: server"),
+            &[],
+        );
+        assert_eq!(
+            report,
+            CheckConfigReport {
+                valid: false,
+                findings: vec![error(
+                    CheckKind::Templates,
+                    "databroker",
+                    "Workload template 'server' does not exist."
+                )],
+            }
+        );
+
+        let report = check_config(
+            &manifest.replace("      tag: 0.4.1\n", "      version: 0.4.1\n"),
+            &[],
+        );
+        assert_eq!(
+            report,
+            CheckConfigReport {
+                valid: false,
+                findings: vec![error(
+                    CheckKind::Templates,
+                    "databroker",
+                    "Template parameter 'tag' is not set."
+                )],
+            }
+        );
+    }
+
+    // [utest->swdd~server-check-config-expands-workload-templates~1]
+    #[test]
+    fn utest_check_config_workload_without_runtime() {
+        let manifest = VALID_MANIFEST.replace("    runtime: podman\n", "");
+
+        assert_eq!(
+            check_config(&manifest, &[]),
+            CheckConfigReport {
+                valid: false,
+                findings: vec![error(
+                    CheckKind::Templates,
+                    "databroker",
+                    "No runtime is set"
+                )],
+            }
+        );
+    }
+
+    #[test]
+    fn utest_check_config_report_serializes_to_json() {
+        let report = CheckConfigReport {
+            valid: true,
+            findings: vec![Finding {
+                severity: Severity::Warning,
+                check: CheckKind::Placement,
+                workload: Some("nginx".to_owned()),
+                message: "message".to_owned(),
+            }],
+        };
+
+        assert_eq!(
+            serde_json::to_string(&report).unwrap(),
+            r#"{"valid":true,"findings":[{"severity":"warning","check":"placement","workload":"nginx","message":"message"}]}"#
+        );
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use common::objects::ControlInterfaceMetrics;
+
+// The last control interface request metrics reported for the workloads of the connected agents.
+// The metrics are ordered by agent and workload name.
+#[derive(Default)]
+pub struct ControlInterfaceMetricsStore {
+    metrics: BTreeMap<(String, String), ControlInterfaceMetrics>,
+}
+
+impl ControlInterfaceMetricsStore {
+    // [impl->swdd~server-stores-control-interface-metrics~1]
+    pub fn update(&mut self, metrics: ControlInterfaceMetrics) {
+        self.metrics.insert(
+            (metrics.agent_name.clone(), metrics.workload_name.clone()),
+            metrics,
+        );
+    }
+
+    // [impl->swdd~server-stores-control-interface-metrics~1]
+    pub fn remove_of_workload(&mut self, agent_name: &str, workload_name: &str) {
+        self.metrics
+            .remove(&(agent_name.to_owned(), workload_name.to_owned()));
+    }
+
+    // [impl->swdd~server-stores-control-interface-metrics~1]
+    pub fn remove_of_agent(&mut self, agent_name: &str) {
+        self.metrics
+            .retain(|(metrics_agent_name, _), _| metrics_agent_name != agent_name);
+    }
+
+    // [impl->swdd~server-provides-control-interface-metrics~1]
+    pub fn get_all(&self) -> Vec<ControlInterfaceMetrics> {
+        self.metrics.values().cloned().collect()
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::ControlInterfaceMetrics;
+
+    use super::ControlInterfaceMetricsStore;
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+
+    fn metrics(agent_name: &str, workload_name: &str, requests: u64) -> ControlInterfaceMetrics {
+        ControlInterfaceMetrics {
+            agent_name: agent_name.to_string(),
+            workload_name: workload_name.to_string(),
+            forwarded_requests: requests,
+            ..Default::default()
+        }
+    }
+
+    // [utest->swdd~server-stores-control-interface-metrics~1]
+    // [utest->swdd~server-provides-control-interface-metrics~1]
+    #[test]
+    fn utest_control_interface_metrics_replaces_and_sorts_metrics() {
+        let mut metrics_store = ControlInterfaceMetricsStore::default();
+        metrics_store.update(metrics(AGENT_B, "workload_1", 1));
+        metrics_store.update(metrics(AGENT_A, "workload_3", 2));
+        metrics_store.update(metrics(AGENT_A, "workload_2", 3));
+        metrics_store.update(metrics(AGENT_A, "workload_3", 4));
+
+        assert_eq!(
+            metrics_store.get_all(),
+            vec![
+                metrics(AGENT_A, "workload_2", 3),
+                metrics(AGENT_A, "workload_3", 4),
+                metrics(AGENT_B, "workload_1", 1),
+            ]
+        );
+    }
+
+    // [utest->swdd~server-stores-control-interface-metrics~1]
+    #[test]
+    fn utest_control_interface_metrics_removes_metrics_of_workload_and_agent() {
+        let mut metrics_store = ControlInterfaceMetricsStore::default();
+        metrics_store.update(metrics(AGENT_A, "workload_1", 1));
+        metrics_store.update(metrics(AGENT_A, "workload_2", 2));
+        metrics_store.update(metrics(AGENT_B, "workload_1", 3));
+
+        metrics_store.remove_of_workload(AGENT_A, "workload_1");
+        assert_eq!(
+            metrics_store.get_all(),
+            vec![
+                metrics(AGENT_A, "workload_2", 2),
+                metrics(AGENT_B, "workload_1", 3),
+            ]
+        );
+
+        metrics_store.remove_of_agent(AGENT_B);
+        assert_eq!(
+            metrics_store.get_all(),
+            vec![metrics(AGENT_A, "workload_2", 2)]
+        );
+    }
+}
@@ -11,7 +11,7 @@
 // under the License.
 //
 // SPDX-License-Identifier: Apache-2.0
-use common::objects::State;
+use common::objects::{AddCondition, State, StoredWorkloadSpec};
 use std::collections::{HashSet, VecDeque};
 
 /// Returns an Option containing the workload dependency that is part of a cycle
@@ -107,6 +107,65 @@ pub fn dfs(state: &State, start_nodes: Option<Vec<&str>>) -> Option<String> {
     None
 }
 
+/// Returns the pairs of workload and dependency for all dependencies
+/// on workloads that are not part of the state, sorted by the workload names
+///
+/// # Arguments
+///
+/// * `state` - The State with workloads representing the directed graph to check
+/// * `updated_workloads` - Check only the passed workloads and the dependents of the removed ones,
+///   if [`None`] all workloads of the state are checked
+///
+// [impl->swdd~server-state-warns-about-dependencies-on-unknown-workloads~2]
+pub fn unknown_dependencies(
+    state: &State,
+    updated_workloads: Option<&[String]>,
+) -> Vec<(String, String)> {
+    let workloads_to_check: Vec<(&String, &StoredWorkloadSpec)> = match updated_workloads {
+        None => state.workloads.iter().collect(),
+        Some(updated_workloads) => {
+            let mut workloads_to_check: Vec<_> = updated_workloads
+                .iter()
+                .filter_map(|workload_name| state.workloads.get_key_value(workload_name))
+                .collect();
+            // only the dependents of a removed workload get a new unknown dependency
+            let removed_workloads: HashSet<&str> = updated_workloads
+                .iter()
+                .map(String::as_str)
+                .filter(|workload_name| !state.workloads.contains_key(*workload_name))
+                .collect();
+            if !removed_workloads.is_empty() {
+                workloads_to_check.extend(state.workloads.iter().filter(|(_, workload_spec)| {
+                    workload_spec
+                        .dependencies
+                        .keys()
+                        .any(|dependency| removed_workloads.contains(dependency.as_str()))
+                }));
+            }
+            workloads_to_check
+        }
+    };
+
+    let mut unknown_dependencies: Vec<(String, String)> = workloads_to_check
+        .into_iter()
+        .flat_map(|(workload_name, workload_spec)| {
+            workload_spec
+                .dependencies
+                .iter()
+                // a workload that is not part of the state fulfills a not running condition
+                .filter(|(dependency, add_condition)| {
+                    **add_condition != AddCondition::AddCondNotRunning
+                        && !state.workloads.contains_key(*dependency)
+                })
+                .map(move |(dependency, _)| (workload_name.clone(), dependency.clone()))
+        })
+        .collect();
+    unknown_dependencies.sort();
+    // an updated workload can also be a dependent of a removed one
+    unknown_dependencies.dedup();
+    unknown_dependencies
+}
+
 //////////////////////////////////////////////////////////////////////////////
 //                 ########  #######    #########  #########                //
 //                    ##     ##        ##             ##                    //
@@ -590,6 +649,56 @@ mod tests {
         assert_no_cycle!(builder, &workloads);
     }
 
+    // [utest->swdd~server-state-warns-about-dependencies-on-unknown-workloads~2]
+    #[test]
+    fn utest_unknown_dependencies() {
+        let state = StateBuilder::default()
+            .with_workloads(&["A", "B"])
+            .workload_dependency("B", "A", AddCondition::AddCondRunning)
+            .workload_dependency("B", "D", AddCondition::AddCondRunning)
+            .workload_dependency("A", "C", AddCondition::AddCondSucceeded)
+            .workload_dependency("A", "E", AddCondition::AddCondNotRunning)
+            .build();
+
+        assert_eq!(
+            unknown_dependencies(&state, None),
+            vec![
+                ("A".to_string(), "C".to_string()),
+                ("B".to_string(), "D".to_string())
+            ]
+        );
+    }
+
+    // [utest->swdd~server-state-warns-about-dependencies-on-unknown-workloads~2]
+    // [utest->swdd~server-state-updates-masked-workloads-incrementally~1]
+    #[test]
+    fn utest_unknown_dependencies_of_updated_workloads() {
+        let state = StateBuilder::default()
+            .with_workloads(&["A", "B", "F"])
+            .workload_dependency("A", "C", AddCondition::AddCondSucceeded)
+            .workload_dependency("B", "D", AddCondition::AddCondRunning)
+            .workload_dependency("B", "E", AddCondition::AddCondRunning)
+            .workload_dependency("F", "E", AddCondition::AddCondRunning)
+            .build();
+
+        assert_eq!(
+            unknown_dependencies(&state, Some(&["B".to_string()])),
+            vec![
+                ("B".to_string(), "D".to_string()),
+                ("B".to_string(), "E".to_string())
+            ]
+        );
+        // the removed workload E is unknown to its dependents B and F
+        assert_eq!(
+            unknown_dependencies(&state, Some(&["B".to_string(), "E".to_string()])),
+            vec![
+                ("B".to_string(), "D".to_string()),
+                ("B".to_string(), "E".to_string()),
+                ("F".to_string(), "E".to_string())
+            ]
+        );
+    }
+
     #[derive(Clone)]
     struct StateBuilder(State);
     impl StateBuilder {
@@ -26,13 +26,16 @@ pub struct DeleteGraph {
 
 #[cfg_attr(test, automock)]
 impl DeleteGraph {
-    // [impl->swdd~server-state-stores-delete-condition~1]
+    // [impl->swdd~server-state-stores-delete-condition~2]
     pub fn insert(&mut self, new_workloads: &[WorkloadSpec]) {
         for workload_spec in new_workloads {
             for (dependency_name, add_condition) in workload_spec.dependencies.iter() {
-                /* currently for other add conditions besides AddCondRunning
+                /* currently for other add conditions besides the running ones
                 the workload can be deleted immediately and does not need a delete condition */
-                if add_condition == &AddCondition::AddCondRunning {
+                if matches!(
+                    add_condition,
+                    AddCondition::AddCondRunning | AddCondition::AddCondRunningFor
+                ) {
                     let workload_name = workload_spec.instance_name.workload_name().to_owned();
                     self.delete_graph
                         .entry(dependency_name.clone())
@@ -98,7 +101,7 @@ mod tests {
     const WORKLOAD_NAME_6: &str = "workload_6";
     const RUNTIME: &str = "runtime";
 
-    // [utest->swdd~server-state-stores-delete-condition~1]
+    // [utest->swdd~server-state-stores-delete-condition~2]
     #[test]
     fn utest_delete_graph_insert() {
         /*
@@ -107,13 +110,15 @@ mod tests {
             R = ADD_COND_RUNNING
             S = ADD_COND_SUCCEEDED
             F = ADD_COND_FAILED
+            RF = ADD_COND_RUNNING_FOR
 
                                           =>    2 --> 1 (DelCondNotPendingNorRunning)
             4 --> 1 --> 2                       5 --> 3 (DelCondNotPendingNorRunning)
-               F     R
+               F     R                          5 --> 6 (DelCondNotPendingNorRunning)
             3 --> 5
                R
-            6 (workload without dependencies)
+            6 --> 5
+               RF
         */
         let _ = env_logger::builder().is_test(true).try_init();
 
@@ -171,7 +176,10 @@ mod tests {
         )]);
 
         workload_5.dependencies.clear();
-        workload_6.dependencies.clear();
+        workload_6.dependencies = HashMap::from([(
+            workload_5.instance_name.workload_name().to_owned(),
+            AddCondition::AddCondRunningFor,
+        )]);
 
         let mut delete_graph = DeleteGraph::default();
         delete_graph.insert(&vec![
@@ -193,17 +201,23 @@ mod tests {
             ),
             (
                 workload_5.instance_name.workload_name().to_owned(),
-                HashMap::from([(
-                    workload_3.instance_name.workload_name().to_owned(),
-                    DeleteCondition::DelCondNotPendingNorRunning,
-                )]),
+                HashMap::from([
+                    (
+                        workload_3.instance_name.workload_name().to_owned(),
+                        DeleteCondition::DelCondNotPendingNorRunning,
+                    ),
+                    (
+                        workload_6.instance_name.workload_name().to_owned(),
+                        DeleteCondition::DelCondNotPendingNorRunning,
+                    ),
+                ]),
             ),
         ]);
 
         assert_eq!(expected_delete_graph, delete_graph.delete_graph);
     }
 
-    // [utest->swdd~server-state-stores-delete-condition~1]
+    // [utest->swdd~server-state-stores-delete-condition~2]
     // [utest->swdd~server-state-adds-delete-conditions-to-deleted-workload~1]
     #[test]
     fn utest_delete_graph_apply_delete_conditions() {
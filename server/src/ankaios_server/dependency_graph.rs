@@ -0,0 +1,165 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use common::{
+    commands::{DependencyGraph, DependencyGraphEdge, DependencyGraphNode},
+    objects::{ExecutionState, State, WorkloadState},
+};
+use std::collections::HashMap;
+
+/// Returns the workloads of the state as nodes and their dependencies as edges.
+///
+/// The nodes are ordered by the workload name and carry the current execution state
+/// of the workload on its agent, if a state has been reported. The edges are ordered
+/// by the dependent workload and the dependency. Dependencies on workloads that are
+/// not part of the state are kept as edges without a node.
+///
+/// # Arguments
+///
+/// * `state` - The State with the workloads to export
+/// * `workload_states` - The current execution states of the workloads
+///
+// [impl->swdd~server-provides-dependency-graph~1]
+pub fn build(state: &State, workload_states: &[WorkloadState]) -> DependencyGraph {
+    let execution_states: HashMap<(&str, &str), &ExecutionState> = workload_states
+        .iter()
+        .filter(|workload_state| !workload_state.execution_state.is_removed())
+        .map(|workload_state| {
+            (
+                (
+                    workload_state.instance_name.workload_name(),
+                    workload_state.instance_name.agent_name(),
+                ),
+                &workload_state.execution_state,
+            )
+        })
+        .collect();
+
+    let mut nodes: Vec<DependencyGraphNode> = Vec::with_capacity(state.workloads.len());
+    let mut edges: Vec<DependencyGraphEdge> = Vec::new();
+    for (workload_name, workload_spec) in &state.workloads {
+        nodes.push(DependencyGraphNode {
+            workload_name: workload_name.clone(),
+            agent: workload_spec.agent.clone(),
+            execution_state: execution_states
+                .get(&(workload_name.as_str(), workload_spec.agent.as_str()))
+                .map(|execution_state| (*execution_state).clone()),
+        });
+        edges.extend(
+            workload_spec
+                .dependencies
+                .iter()
+                .map(|(dependency, condition)| DependencyGraphEdge {
+                    workload_name: workload_name.clone(),
+                    dependency: dependency.clone(),
+                    condition: *condition,
+                }),
+        );
+    }
+
+    // the workloads are stored in a HashMap, sort for a stable output
+    nodes.sort_by(|a, b| a.workload_name.cmp(&b.workload_name));
+    edges.sort_by(|a, b| {
+        (&a.workload_name, &a.dependency).cmp(&(&b.workload_name, &b.dependency))
+    });
+
+    DependencyGraph { nodes, edges }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::build;
+    use common::{
+        commands::{DependencyGraph, DependencyGraphEdge, DependencyGraphNode},
+        objects::{
+            generate_test_stored_workload_spec, generate_test_workload_state_with_agent,
+            AddCondition, ExecutionState, State,
+        },
+    };
+    use std::collections::HashMap;
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+    const RUNTIME: &str = "runtime X";
+
+    // [utest->swdd~server-provides-dependency-graph~1]
+    #[test]
+    fn utest_dependency_graph_contains_workloads_dependencies_and_states() {
+        let mut workload_a = generate_test_stored_workload_spec(AGENT_A, RUNTIME);
+        workload_a.dependencies.clear();
+        let mut workload_b = generate_test_stored_workload_spec(AGENT_B, RUNTIME);
+        workload_b.dependencies = HashMap::from([
+            ("workload_A".to_string(), AddCondition::AddCondRunning),
+            ("workload_X".to_string(), AddCondition::AddCondSucceeded),
+        ]);
+        let state = State {
+            workloads: HashMap::from([
+                ("workload_B".to_string(), workload_b),
+                ("workload_A".to_string(), workload_a),
+            ]),
+            ..Default::default()
+        };
+        let workload_states = vec![
+            generate_test_workload_state_with_agent(
+                "workload_A",
+                AGENT_A,
+                ExecutionState::running(),
+            ),
+            // a state of the workload on another agent is not the state of the node
+            generate_test_workload_state_with_agent(
+                "workload_B",
+                AGENT_A,
+                ExecutionState::succeeded(),
+            ),
+        ];
+
+        assert_eq!(
+            build(&state, &workload_states),
+            DependencyGraph {
+                nodes: vec![
+                    DependencyGraphNode {
+                        workload_name: "workload_A".to_string(),
+                        agent: AGENT_A.to_string(),
+                        execution_state: Some(ExecutionState::running()),
+                    },
+                    DependencyGraphNode {
+                        workload_name: "workload_B".to_string(),
+                        agent: AGENT_B.to_string(),
+                        execution_state: None,
+                    },
+                ],
+                edges: vec![
+                    DependencyGraphEdge {
+                        workload_name: "workload_B".to_string(),
+                        dependency: "workload_A".to_string(),
+                        condition: AddCondition::AddCondRunning,
+                    },
+                    DependencyGraphEdge {
+                        workload_name: "workload_B".to_string(),
+                        dependency: "workload_X".to_string(),
+                        condition: AddCondition::AddCondSucceeded,
+                    },
+                ],
+            }
+        );
+    }
+}
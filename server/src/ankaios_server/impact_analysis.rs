@@ -0,0 +1,167 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use common::{
+    commands::{ImpactAnalysis, ImpactedWorkload},
+    objects::State,
+};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Returns the workloads of the state that depend directly or transitively
+/// on one of the workloads to be removed, ordered by the workload name.
+///
+/// The removed workloads themselves are not part of the result. For every affected
+/// workload the direct dependencies on removed or affected workloads are listed.
+///
+/// # Arguments
+///
+/// * `state` - The State with workloads representing the directed dependency graph
+/// * `removed_workloads` - The names of the workloads to be removed from the state
+///
+// [impl->swdd~server-provides-impact-analysis~1]
+pub fn analyze(state: &State, removed_workloads: &[String]) -> ImpactAnalysis {
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (workload_name, workload_spec) in &state.workloads {
+        for dependency in workload_spec.dependencies.keys() {
+            dependents
+                .entry(dependency.as_str())
+                .or_default()
+                .push(workload_name.as_str());
+        }
+    }
+
+    let removed: HashSet<&str> = removed_workloads.iter().map(String::as_str).collect();
+    let mut impacted: BTreeSet<&str> = BTreeSet::new();
+    let mut queue: VecDeque<&str> = removed.iter().copied().collect();
+    while let Some(workload_name) = queue.pop_front() {
+        for &dependent in dependents.get(workload_name).into_iter().flatten() {
+            if !removed.contains(dependent) && impacted.insert(dependent) {
+                queue.push_back(dependent);
+            }
+        }
+    }
+
+    let impacted_workloads = impacted
+        .iter()
+        .filter_map(|workload_name| {
+            let workload_spec = state.workloads.get(*workload_name)?;
+            let mut depends_on: Vec<String> = workload_spec
+                .dependencies
+                .keys()
+                .filter(|dependency| {
+                    removed.contains(dependency.as_str())
+                        || impacted.contains(dependency.as_str())
+                })
+                .cloned()
+                .collect();
+            depends_on.sort();
+            Some(ImpactedWorkload {
+                workload_name: workload_name.to_string(),
+                agent: workload_spec.agent.clone(),
+                depends_on,
+            })
+        })
+        .collect();
+
+    ImpactAnalysis { impacted_workloads }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::analyze;
+    use common::{
+        commands::{ImpactAnalysis, ImpactedWorkload},
+        objects::{generate_test_stored_workload_spec, AddCondition, State},
+    };
+    use std::collections::HashMap;
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+    const RUNTIME: &str = "runtime X";
+
+    // workload_B -> workload_A, workload_C -> workload_B, workload_D without dependencies
+    fn generate_test_state() -> State {
+        let mut workload_a = generate_test_stored_workload_spec(AGENT_A, RUNTIME);
+        workload_a.dependencies.clear();
+        let mut workload_b = generate_test_stored_workload_spec(AGENT_A, RUNTIME);
+        workload_b.dependencies =
+            HashMap::from([("workload_A".to_string(), AddCondition::AddCondRunning)]);
+        let mut workload_c = generate_test_stored_workload_spec(AGENT_B, RUNTIME);
+        workload_c.dependencies =
+            HashMap::from([("workload_B".to_string(), AddCondition::AddCondSucceeded)]);
+        let mut workload_d = generate_test_stored_workload_spec(AGENT_B, RUNTIME);
+        workload_d.dependencies.clear();
+
+        State {
+            workloads: HashMap::from([
+                ("workload_A".to_string(), workload_a),
+                ("workload_B".to_string(), workload_b),
+                ("workload_C".to_string(), workload_c),
+                ("workload_D".to_string(), workload_d),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    // [utest->swdd~server-provides-impact-analysis~1]
+    #[test]
+    fn utest_impact_analysis_contains_transitive_dependents() {
+        let impact_analysis = analyze(&generate_test_state(), &["workload_A".to_string()]);
+
+        assert_eq!(
+            impact_analysis,
+            ImpactAnalysis {
+                impacted_workloads: vec![
+                    ImpactedWorkload {
+                        workload_name: "workload_B".to_string(),
+                        agent: AGENT_A.to_string(),
+                        depends_on: vec!["workload_A".to_string()],
+                    },
+                    ImpactedWorkload {
+                        workload_name: "workload_C".to_string(),
+                        agent: AGENT_B.to_string(),
+                        depends_on: vec!["workload_B".to_string()],
+                    },
+                ]
+            }
+        );
+    }
+
+    // [utest->swdd~server-provides-impact-analysis~1]
+    #[test]
+    fn utest_impact_analysis_excludes_removed_and_independent_workloads() {
+        let state = generate_test_state();
+
+        assert_eq!(
+            analyze(&state, &["workload_B".to_string(), "workload_C".to_string()]),
+            ImpactAnalysis::default()
+        );
+        assert_eq!(
+            analyze(&state, &["workload_D".to_string()]),
+            ImpactAnalysis::default()
+        );
+        assert_eq!(
+            analyze(&state, &["unknown_workload".to_string()]),
+            ImpactAnalysis::default()
+        );
+    }
+}
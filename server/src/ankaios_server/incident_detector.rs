@@ -0,0 +1,205 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use common::objects::{ExecutionStateEnum, WorkloadState};
+
+struct IncidentDetectorConfig {
+    window: Duration,
+    min_failed_workloads: usize,
+}
+
+// Correlates the failures of the workloads of an agent. Several workloads failing on the same
+// agent within a short window rather point to a problem of the node, e.g., an out of memory
+// situation or a crashed runtime, than to bugs of the applications.
+#[derive(Default)]
+pub struct IncidentDetector {
+    config: Option<IncidentDetectorConfig>,
+    // the recent failures per agent as the time of the failure and the name of the workload
+    failures: HashMap<String, Vec<(Instant, String)>>,
+}
+
+impl IncidentDetector {
+    pub fn new(window: Duration, min_failed_workloads: usize) -> Self {
+        IncidentDetector {
+            config: Some(IncidentDetectorConfig {
+                window,
+                min_failed_workloads: min_failed_workloads.max(1),
+            }),
+            failures: HashMap::new(),
+        }
+    }
+
+    // Returns the sorted names of the workloads affected by an incident if the given workload
+    // state completes one. The failures of an incident are forgotten, thus every failure is
+    // reported in at most one incident.
+    // [impl->swdd~server-correlates-workload-failures-to-agent-incidents~1]
+    pub fn record(&mut self, workload_state: &WorkloadState, now: Instant) -> Option<Vec<String>> {
+        let config = self.config.as_ref()?;
+        if !matches!(
+            workload_state.execution_state.state,
+            ExecutionStateEnum::Failed(_)
+        ) {
+            return None;
+        }
+
+        let workload_name = workload_state.instance_name.workload_name();
+        let failures = self
+            .failures
+            .entry(workload_state.instance_name.agent_name().to_string())
+            .or_default();
+        failures.retain(|(failure_time, failed_workload)| {
+            now.saturating_duration_since(*failure_time) <= config.window
+                && failed_workload != workload_name
+        });
+        failures.push((now, workload_name.to_string()));
+
+        if failures.len() < config.min_failed_workloads {
+            return None;
+        }
+        let mut affected_workloads: Vec<String> = failures
+            .drain(..)
+            .map(|(_, workload_name)| workload_name)
+            .collect();
+        affected_workloads.sort();
+        Some(affected_workloads)
+    }
+
+    pub fn window(&self) -> Duration {
+        self.config
+            .as_ref()
+            .map(|config| config.window)
+            .unwrap_or_default()
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use common::objects::{generate_test_workload_state_with_agent, ExecutionState};
+
+    use super::IncidentDetector;
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+    const WORKLOAD_NAME_1: &str = "workload_1";
+    const WORKLOAD_NAME_2: &str = "workload_2";
+    const WORKLOAD_NAME_3: &str = "workload_3";
+
+    // [utest->swdd~server-correlates-workload-failures-to-agent-incidents~1]
+    #[test]
+    fn utest_incident_detector_disabled_reports_no_incident() {
+        let mut detector = IncidentDetector::default();
+        let now = Instant::now();
+
+        for workload_name in [WORKLOAD_NAME_1, WORKLOAD_NAME_2, WORKLOAD_NAME_3] {
+            assert!(detector
+                .record(
+                    &generate_test_workload_state_with_agent(
+                        workload_name,
+                        AGENT_A,
+                        ExecutionState::failed("error"),
+                    ),
+                    now,
+                )
+                .is_none());
+        }
+    }
+
+    // [utest->swdd~server-correlates-workload-failures-to-agent-incidents~1]
+    #[test]
+    fn utest_incident_detector_reports_failures_of_one_agent_within_window() {
+        let mut detector = IncidentDetector::new(Duration::from_secs(10), 2);
+        let now = Instant::now();
+
+        let failed_1 = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::failed("error"),
+        );
+        let failed_2_on_other_agent = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_2,
+            AGENT_B,
+            ExecutionState::failed("error"),
+        );
+        let running_2 = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_2,
+            AGENT_A,
+            ExecutionState::running(),
+        );
+        let failed_3 = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_3,
+            AGENT_A,
+            ExecutionState::lost(),
+        );
+
+        assert!(detector.record(&failed_1, now).is_none());
+        assert!(detector.record(&failed_1, now).is_none());
+        assert!(detector.record(&failed_2_on_other_agent, now).is_none());
+        assert!(detector.record(&running_2, now).is_none());
+        assert_eq!(
+            detector.record(&failed_3, now + Duration::from_secs(5)),
+            Some(vec![
+                WORKLOAD_NAME_1.to_string(),
+                WORKLOAD_NAME_3.to_string()
+            ])
+        );
+
+        // the failures of the incident are not reported again
+        assert!(detector
+            .record(&failed_1, now + Duration::from_secs(6))
+            .is_none());
+    }
+
+    // [utest->swdd~server-correlates-workload-failures-to-agent-incidents~1]
+    #[test]
+    fn utest_incident_detector_ignores_failures_outside_window() {
+        let mut detector = IncidentDetector::new(Duration::from_secs(10), 2);
+        let now = Instant::now();
+
+        assert!(detector
+            .record(
+                &generate_test_workload_state_with_agent(
+                    WORKLOAD_NAME_1,
+                    AGENT_A,
+                    ExecutionState::failed("error"),
+                ),
+                now,
+            )
+            .is_none());
+        assert!(detector
+            .record(
+                &generate_test_workload_state_with_agent(
+                    WORKLOAD_NAME_2,
+                    AGENT_A,
+                    ExecutionState::failed("error"),
+                ),
+                now + Duration::from_secs(11),
+            )
+            .is_none());
+    }
+}
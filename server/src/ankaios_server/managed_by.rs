@@ -0,0 +1,364 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use common::{
+    objects::{State, StoredWorkloadSpec},
+    request_id_prepending::{CLI_CONNECTION_PREFIX, CLOUD_CONNECTOR_CONNECTION_NAME},
+};
+
+const CLI_IDENTITY: &str = "cli";
+const WORKLOAD_IDENTITY_PREFIX: &str = "workload:";
+const REQUEST_ID_SEPARATOR: char = '@';
+
+// The identity requesting an update of the desired state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Modifier {
+    pub identity: String,
+    pub force: bool,
+}
+
+/// Returns the identity that sent the request with the given request id.
+///
+/// The identity is derived only from the prefixes added while forwarding the request and not
+/// from the request id chosen by the sender:
+/// * `cli-conn-<id>@<request id>` is a request on a connection the gRPC server assigned to a CLI
+/// * `cloud-connector@<request id>` is a request of the cloud connector of the server
+/// * `<agent>@<workload>@<request id>` is a request of a workload via the control interface,
+///   the agent prefix is the name the agent connected with, the workload prefix is added by the agent
+///
+/// The gRPC server rejects agents connecting with the names of the CLI connections or of the
+/// cloud connector, thus a request cannot claim another kind of identity.
+///
+/// # Arguments
+///
+/// * `request_id` - The request id with the prefixes added while forwarding the request
+///
+// [impl->swdd~server-identifies-modifier-of-request~2]
+pub fn identity_of_request(request_id: &str) -> Option<String> {
+    let parts: Vec<&str> = request_id.splitn(3, REQUEST_ID_SEPARATOR).collect();
+    match parts.as_slice() {
+        [connection, _, ..] if connection.starts_with(CLI_CONNECTION_PREFIX) => {
+            Some(CLI_IDENTITY.to_owned())
+        }
+        [CLOUD_CONNECTOR_CONNECTION_NAME, _, ..] => {
+            Some(CLOUD_CONNECTOR_CONNECTION_NAME.to_owned())
+        }
+        [agent_name, workload_name, _] => Some(format!(
+            "{WORKLOAD_IDENTITY_PREFIX}{agent_name}/{workload_name}"
+        )),
+        _ => None,
+    }
+}
+
+fn is_equal_ignoring_managed_by(a: &StoredWorkloadSpec, b: &StoredWorkloadSpec) -> bool {
+    *a == StoredWorkloadSpec {
+        managed_by: a.managed_by.clone(),
+        ..b.clone()
+    }
+}
+
+/// Records the identity of the modifier for the added or changed workloads of the new state.
+///
+/// Unchanged workloads keep their recorded identity. Without a modifier, e.g. for updates of
+/// the server itself, a changed workload keeps its given identity or else the identity
+/// of the current workload.
+///
+/// # Arguments
+///
+/// * `current_state` - The current desired state
+/// * `new_state` - The new desired state
+/// * `modifier` - The identity requesting the update, if known
+///
+// [impl->swdd~server-state-records-managed-by~1]
+pub fn record_managed_by(
+    current_state: &State,
+    new_state: &mut State,
+    modifier: Option<&Modifier>,
+) {
+    for (workload_name, new_workload) in new_state.workloads.iter_mut() {
+        let current_workload = current_state.workloads.get(workload_name);
+        new_workload.managed_by = match (current_workload, modifier) {
+            (Some(current), _) if is_equal_ignoring_managed_by(current, new_workload) => {
+                current.managed_by.clone()
+            }
+            (_, Some(modifier)) => modifier.identity.clone(),
+            (Some(current), None) if new_workload.managed_by.is_empty() => {
+                current.managed_by.clone()
+            }
+            (_, None) => std::mem::take(&mut new_workload.managed_by),
+        };
+    }
+}
+
+/// Returns the first workload the modifier is not allowed to change or delete.
+///
+/// A workload managed by another identity is only changed or deleted if the update is forced.
+/// The recorded identities of the new state are expected to be set with [`record_managed_by`].
+///
+/// # Arguments
+///
+/// * `current_state` - The current desired state
+/// * `new_state` - The new desired state
+/// * `modifier` - The identity requesting the update
+///
+// [impl->swdd~server-protects-managed-workloads~1]
+pub fn find_protected_workload<'a>(
+    current_state: &'a State,
+    new_state: &State,
+    modifier: &Modifier,
+) -> Option<(&'a String, &'a StoredWorkloadSpec)> {
+    if modifier.force {
+        return None;
+    }
+    current_state
+        .workloads
+        .iter()
+        .filter(|(_, current)| {
+            !current.managed_by.is_empty() && current.managed_by != modifier.identity
+        })
+        .find(|(workload_name, current)| new_state.workloads.get(*workload_name) != Some(*current))
+}
+
+/// Returns the first workload of the new state whose hooks the modifier is not allowed to set.
+///
+/// The hooks are executed on the host of the agent, thus a workload must not add or change
+/// hooks via the control interface. Unchanged hooks of the current state are accepted.
+///
+/// # Arguments
+///
+/// * `current_state` - The current desired state
+/// * `new_state` - The new desired state
+/// * `modifier` - The identity requesting the update
+///
+// [impl->swdd~server-rejects-hooks-from-control-interface~1]
+pub fn find_workload_with_changed_hooks<'a>(
+    current_state: &State,
+    new_state: &'a State,
+    modifier: &Modifier,
+) -> Option<&'a String> {
+    if !modifier.identity.starts_with(WORKLOAD_IDENTITY_PREFIX) {
+        return None;
+    }
+    new_state
+        .workloads
+        .iter()
+        .filter(|(workload_name, new_workload)| {
+            new_workload.hooks.is_some()
+                && current_state
+                    .workloads
+                    .get(*workload_name)
+                    .map(|current_workload| &current_workload.hooks)
+                    != Some(&new_workload.hooks)
+        })
+        .map(|(workload_name, _)| workload_name)
+        // the workloads are stored in a HashMap, report the same workload for the same state
+        .min()
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use common::objects::{
+        generate_test_stored_workload_spec, State, StoredWorkloadSpec, WorkloadHooks,
+    };
+
+    use super::{
+        find_protected_workload, find_workload_with_changed_hooks, identity_of_request,
+        record_managed_by, Modifier,
+    };
+
+    const AGENT_A: &str = "agent_A";
+    const RUNTIME: &str = "runtime";
+    const WORKLOAD_NAME_1: &str = "workload_1";
+    const WORKLOAD_NAME_2: &str = "workload_2";
+    const CLI: &str = "cli";
+    const WORKLOAD: &str = "workload:agent_A/workload_1";
+
+    fn workload(managed_by: &str) -> StoredWorkloadSpec {
+        let mut workload = generate_test_stored_workload_spec(AGENT_A, RUNTIME);
+        workload.managed_by = managed_by.to_owned();
+        workload
+    }
+
+    fn state(workloads: Vec<(&str, StoredWorkloadSpec)>) -> State {
+        State {
+            workloads: workloads
+                .into_iter()
+                .map(|(name, workload)| (name.to_owned(), workload))
+                .collect::<HashMap<_, _>>(),
+            ..Default::default()
+        }
+    }
+
+    fn modifier(identity: &str, force: bool) -> Modifier {
+        Modifier {
+            identity: identity.to_owned(),
+            force,
+        }
+    }
+
+    // [utest->swdd~server-identifies-modifier-of-request~2]
+    #[test]
+    fn utest_identity_of_request() {
+        assert_eq!(
+            identity_of_request("cli-conn-1234@5678"),
+            Some(CLI.to_owned())
+        );
+        assert_eq!(
+            identity_of_request("cli-conn-1234@alice@5678"),
+            Some(CLI.to_owned())
+        );
+        assert_eq!(
+            identity_of_request("cli-conn-1234@agent_A@workload_1@5678"),
+            Some(CLI.to_owned())
+        );
+        assert_eq!(
+            identity_of_request("cloud-connector@5678"),
+            Some("cloud-connector".to_owned())
+        );
+        assert_eq!(
+            identity_of_request("agent_A@workload_1@5678"),
+            Some(WORKLOAD.to_owned())
+        );
+        assert_eq!(identity_of_request("agent_A@5678"), None);
+        assert_eq!(identity_of_request("5678"), None);
+    }
+
+    // [utest->swdd~server-state-records-managed-by~1]
+    #[test]
+    fn utest_record_managed_by_sets_modifier_only_for_added_and_changed_workloads() {
+        let current_state = state(vec![
+            (WORKLOAD_NAME_1, workload(CLI)),
+            (WORKLOAD_NAME_2, workload(CLI)),
+        ]);
+        let mut changed_workload = workload("");
+        changed_workload.runtime_config = "changed".to_owned();
+        let mut new_state = state(vec![
+            (WORKLOAD_NAME_1, workload("")),
+            (WORKLOAD_NAME_2, changed_workload),
+            ("workload_3", workload("")),
+        ]);
+
+        record_managed_by(
+            &current_state,
+            &mut new_state,
+            Some(&modifier(WORKLOAD, false)),
+        );
+
+        assert_eq!(new_state.workloads[WORKLOAD_NAME_1].managed_by, CLI);
+        assert_eq!(new_state.workloads[WORKLOAD_NAME_2].managed_by, WORKLOAD);
+        assert_eq!(new_state.workloads["workload_3"].managed_by, WORKLOAD);
+    }
+
+    // [utest->swdd~server-state-records-managed-by~1]
+    #[test]
+    fn utest_record_managed_by_keeps_identity_without_modifier() {
+        let current_state = state(vec![
+            (WORKLOAD_NAME_1, workload(CLI)),
+            (WORKLOAD_NAME_2, workload(CLI)),
+        ]);
+        let mut changed_workload = workload("");
+        changed_workload.runtime_config = "changed".to_owned();
+        // e.g. a rollback restoring a previous workload together with its identity
+        let mut restored_workload = workload(WORKLOAD);
+        restored_workload.runtime_config = "previous".to_owned();
+        let mut new_state = state(vec![
+            (WORKLOAD_NAME_1, changed_workload),
+            (WORKLOAD_NAME_2, restored_workload),
+        ]);
+
+        record_managed_by(&current_state, &mut new_state, None);
+
+        assert_eq!(new_state.workloads[WORKLOAD_NAME_1].managed_by, CLI);
+        assert_eq!(new_state.workloads[WORKLOAD_NAME_2].managed_by, WORKLOAD);
+    }
+
+    // [utest->swdd~server-protects-managed-workloads~1]
+    #[test]
+    fn utest_find_protected_workload_on_change_and_delete_by_other_identity() {
+        let current_state = state(vec![(WORKLOAD_NAME_1, workload(CLI))]);
+        let mut changed_workload = workload(WORKLOAD);
+        changed_workload.runtime_config = "changed".to_owned();
+        let changed_state = state(vec![(WORKLOAD_NAME_1, changed_workload)]);
+        let deleted_state = state(vec![]);
+
+        for new_state in [&changed_state, &deleted_state] {
+            assert_eq!(
+                find_protected_workload(&current_state, new_state, &modifier(WORKLOAD, false))
+                    .map(|(workload_name, _)| workload_name.as_str()),
+                Some(WORKLOAD_NAME_1)
+            );
+            assert!(
+                find_protected_workload(&current_state, new_state, &modifier(WORKLOAD, true))
+                    .is_none()
+            );
+            assert!(
+                find_protected_workload(&current_state, new_state, &modifier(CLI, false)).is_none()
+            );
+        }
+        assert!(find_protected_workload(
+            &current_state,
+            &current_state,
+            &modifier(WORKLOAD, false)
+        )
+        .is_none());
+    }
+
+    // [utest->swdd~server-rejects-hooks-from-control-interface~1]
+    #[test]
+    fn utest_find_workload_with_changed_hooks_only_for_workloads() {
+        const WORKLOAD_IDENTITY: &str = "workload:agent_A/workload_3";
+        let mut hooked_workload = workload(CLI);
+        hooked_workload.hooks = Some(WorkloadHooks {
+            pre_create: Some("/opt/hooks/mount-data.sh".to_owned()),
+            ..Default::default()
+        });
+        let current_state = state(vec![(WORKLOAD_NAME_1, hooked_workload.clone())]);
+        let new_state = state(vec![
+            (WORKLOAD_NAME_1, hooked_workload.clone()),
+            (WORKLOAD_NAME_2, hooked_workload),
+        ]);
+
+        assert_eq!(
+            find_workload_with_changed_hooks(
+                &current_state,
+                &new_state,
+                &modifier(WORKLOAD_IDENTITY, true)
+            )
+            .map(String::as_str),
+            Some(WORKLOAD_NAME_2)
+        );
+        assert!(find_workload_with_changed_hooks(
+            &current_state,
+            &new_state,
+            &modifier(CLI, false)
+        )
+        .is_none());
+        assert!(find_workload_with_changed_hooks(
+            &current_state,
+            &current_state,
+            &modifier(WORKLOAD_IDENTITY, false)
+        )
+        .is_none());
+    }
+}
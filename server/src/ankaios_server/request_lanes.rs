@@ -0,0 +1,391 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::VecDeque;
+
+use common::{
+    commands::RequestContent,
+    memory_profiling::{self, Subsystem},
+    objects::RequestLaneInfo,
+    request_id_prepending::detach_prefix_from_request_id,
+    to_server_interface::ToServer,
+    CHANNEL_CAPACITY,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lane {
+    AgentUpdates = 0,
+    Writes = 1,
+    Reads = 2,
+}
+
+const LANES: [Lane; 3] = [Lane::AgentUpdates, Lane::Writes, Lane::Reads];
+
+// The number of messages taken from a lane per round. A lane with a lower priority gets its
+// share as soon as the lanes with higher priority used up their budget of the current round.
+const LANE_WEIGHTS: [usize; 3] = [4, 2, 1];
+
+// The maximal number of messages queued in a lane. No further messages are taken from the
+// ToServerChannel while a lane is full, thus its senders wait until the server catches up.
+const LANE_CAPACITY: usize = CHANNEL_CAPACITY;
+
+impl Lane {
+    fn of(to_server_command: &ToServer) -> Lane {
+        match to_server_command {
+            ToServer::Request(request) => match request.request_content {
+                RequestContent::UpdateStateRequest(_) | RequestContent::DrainAgentRequest(_) => {
+                    Lane::Writes
+                }
+                RequestContent::CompleteStateRequest(_)
+                | RequestContent::RolloutStatusRequest(_)
+                | RequestContent::SupportInfoRequest(_)
+                | RequestContent::EventsRequest(_)
+                | RequestContent::ImpactAnalysisRequest(_)
+                | RequestContent::DependencyGraphRequest(_)
+                | RequestContent::SchedulerQueueRequest(_)
+                | RequestContent::WatchCompleteStateRequest(_)
+                | RequestContent::CancelWatchRequest(_) => Lane::Reads,
+            },
+            ToServer::AgentHello(_)
+            | ToServer::AgentGone(_)
+            | ToServer::UpdateWorkloadState(_)
+            | ToServer::UpdateWorkloadAck(_)
+            | ToServer::AgentEvent(_)
+            | ToServer::UpdateSchedulerQueue(_)
+            | ToServer::SubscribeWorkloadStates(_)
+            | ToServer::UpdateControlInterfaceMetrics(_)
+            | ToServer::UpdateAgentResources(_)
+            | ToServer::Stop(_)
+            | ToServer::Goodbye(_) => Lane::AgentUpdates,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Lane::AgentUpdates => "agentUpdates",
+            Lane::Writes => "writes",
+            Lane::Reads => "reads",
+        }
+    }
+}
+
+// The connection a message was received on, the agent name for agents and the workloads of
+// the agent and the connection name for other requests.
+// Returns None if the message does not tell its connection.
+fn connection_of(to_server_command: &ToServer) -> Option<String> {
+    match to_server_command {
+        ToServer::Request(request) => {
+            let (connection, _) = detach_prefix_from_request_id(&request.request_id);
+            Some(connection)
+        }
+        ToServer::AgentHello(agent_hello) => Some(agent_hello.agent_name.clone()),
+        ToServer::AgentGone(agent_gone) => Some(agent_gone.agent_name.clone()),
+        ToServer::UpdateWorkloadAck(ack) => Some(ack.agent_name.clone()),
+        ToServer::AgentEvent(agent_event) => Some(agent_event.agent_name.clone()),
+        ToServer::UpdateSchedulerQueue(queue) => Some(queue.agent_name.clone()),
+        ToServer::UpdateControlInterfaceMetrics(metrics) => {
+            Some(metrics.metrics.agent_name.clone())
+        }
+        ToServer::UpdateAgentResources(resources) => Some(resources.agent_name.clone()),
+        ToServer::UpdateWorkloadState(update) => update
+            .workload_states
+            .first()
+            .map(|workload_state| workload_state.instance_name.agent_name().to_owned()),
+        ToServer::SubscribeWorkloadStates(_) | ToServer::Stop(_) | ToServer::Goodbye(_) => None,
+    }
+}
+
+struct QueuedMessage {
+    sequence_number: u64,
+    connection: Option<String>,
+    to_server_command: ToServer,
+}
+
+impl QueuedMessage {
+    // A message without a known connection is kept in order with all other messages.
+    fn is_ordered_after(&self, other: &QueuedMessage) -> bool {
+        other.sequence_number < self.sequence_number
+            && (self.connection.is_none()
+                || other.connection.is_none()
+                || self.connection == other.connection)
+    }
+}
+
+#[derive(Default)]
+struct LaneQueue {
+    messages: VecDeque<QueuedMessage>,
+    budget: usize,
+    max_depth: usize,
+    processed: u64,
+}
+
+// Queues the messages received by the server in bounded priority lanes:
+// agent updates > control interface and CLI writes > reads.
+// The order of the messages within a lane and of the messages of a connection is kept.
+#[derive(Default)]
+pub struct RequestLanes {
+    lanes: [LaneQueue; 3],
+    next_sequence_number: u64,
+}
+
+impl RequestLanes {
+    // [impl->swdd~server-queues-messages-in-priority-lanes~2]
+    pub fn has_room(&self) -> bool {
+        self.lanes
+            .iter()
+            .all(|lane| lane.messages.len() < LANE_CAPACITY)
+    }
+
+    // [impl->swdd~server-queues-messages-in-priority-lanes~2]
+    pub fn push(&mut self, to_server_command: ToServer) {
+        // [impl->swdd~server-attributes-heap-usage-to-subsystems~1]
+        let _memory_scope = memory_profiling::enter(Subsystem::SchedulerQueues);
+        let lane = &mut self.lanes[Lane::of(&to_server_command) as usize];
+        lane.messages.push_back(QueuedMessage {
+            sequence_number: self.next_sequence_number,
+            connection: connection_of(&to_server_command),
+            to_server_command,
+        });
+        lane.max_depth = lane.max_depth.max(lane.messages.len());
+        self.next_sequence_number += 1;
+    }
+
+    // [impl->swdd~server-processes-priority-lanes-fairly~2]
+    pub fn pop(&mut self) -> Option<ToServer> {
+        if self.lanes.iter().all(|lane| lane.messages.is_empty()) {
+            return None;
+        }
+
+        loop {
+            // The oldest queued message is never ordered after another one, thus a lane can
+            // always be taken after the budgets are reset.
+            if let Some(index) = (0..self.lanes.len()).find(|index| {
+                let lane = &self.lanes[*index];
+                lane.budget > 0
+                    && lane
+                        .messages
+                        .front()
+                        .is_some_and(|message| !self.waits_for_other_lane(*index, message))
+            }) {
+                let lane = &mut self.lanes[index];
+                lane.budget -= 1;
+                lane.processed += 1;
+                return lane
+                    .messages
+                    .pop_front()
+                    .map(|message| message.to_server_command);
+            }
+
+            // all lanes with waiting messages used up their budget, start a new round
+            for (lane, weight) in self.lanes.iter_mut().zip(LANE_WEIGHTS) {
+                lane.budget = weight;
+            }
+        }
+    }
+
+    // Checks if an earlier message of the same connection is queued in another lane.
+    fn waits_for_other_lane(&self, lane_index: usize, message: &QueuedMessage) -> bool {
+        self.lanes
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != lane_index)
+            .any(|(_, lane)| {
+                lane.messages
+                    .iter()
+                    .take_while(|other| other.sequence_number < message.sequence_number)
+                    .any(|other| message.is_ordered_after(other))
+            })
+    }
+
+    // [impl->swdd~server-provides-request-lane-metrics~1]
+    pub fn get_lane_infos(&self) -> Vec<RequestLaneInfo> {
+        LANES
+            .iter()
+            .map(|lane| {
+                let queue = &self.lanes[*lane as usize];
+                RequestLaneInfo {
+                    lane: lane.name().to_owned(),
+                    depth: queue.messages.len() as u64,
+                    max_depth: queue.max_depth as u64,
+                    processed: queue.processed,
+                }
+            })
+            .collect()
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        commands::{
+            AgentGone, CompleteStateRequest, Request, RequestContent, UpdateStateRequest,
+        },
+        objects::{CompleteState, RequestLaneInfo},
+        request_id_prepending::detach_prefix_from_request_id,
+        to_server_interface::ToServer,
+    };
+
+    use super::{RequestLanes, LANE_CAPACITY};
+
+    const REQUEST_ID: &str = "request_id";
+
+    fn agent_update(id: &str) -> ToServer {
+        ToServer::AgentGone(AgentGone {
+            agent_name: id.to_owned(),
+        })
+    }
+
+    // the id is the connection of the request
+    fn write(id: &str) -> ToServer {
+        ToServer::Request(Request {
+            request_id: format!("{id}@{REQUEST_ID}"),
+            request_content: RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
+                state: CompleteState::default(),
+                update_mask: vec![],
+                deadline_ms: None,
+                rollback_on_deadline_exceeded: false,
+                force: false,
+            })),
+        })
+    }
+
+    fn read(id: &str) -> ToServer {
+        ToServer::Request(Request {
+            request_id: format!("{id}@{REQUEST_ID}"),
+            request_content: RequestContent::CompleteStateRequest(CompleteStateRequest {
+                field_mask: vec![],
+            }),
+        })
+    }
+
+    fn id_of(to_server_command: ToServer) -> String {
+        match to_server_command {
+            ToServer::AgentGone(AgentGone { agent_name }) => agent_name,
+            ToServer::Request(Request { request_id, .. }) => {
+                detach_prefix_from_request_id(&request_id).0
+            }
+            _ => panic!("unexpected message"),
+        }
+    }
+
+    // [utest->swdd~server-queues-messages-in-priority-lanes~2]
+    #[test]
+    fn utest_request_lanes_prefer_agent_updates_over_writes_over_reads() {
+        let mut lanes = RequestLanes::default();
+        lanes.push(read("read_1"));
+        lanes.push(write("write_1"));
+        lanes.push(agent_update("agent_1"));
+
+        let order: Vec<String> = std::iter::from_fn(|| lanes.pop()).map(id_of).collect();
+        assert_eq!(order, vec!["agent_1", "write_1", "read_1"]);
+    }
+
+    // [utest->swdd~server-processes-priority-lanes-fairly~2]
+    #[test]
+    fn utest_request_lanes_do_not_starve_lower_priority_lanes() {
+        let mut lanes = RequestLanes::default();
+        for index in 0..6 {
+            lanes.push(agent_update(&format!("agent_{index}")));
+        }
+        lanes.push(write("write_1"));
+        lanes.push(read("read_1"));
+
+        let order: Vec<String> = std::iter::from_fn(|| lanes.pop()).map(id_of).collect();
+        assert_eq!(
+            order,
+            vec![
+                "agent_0", "agent_1", "agent_2", "agent_3", "write_1", "read_1", "agent_4",
+                "agent_5"
+            ]
+        );
+    }
+
+    // [utest->swdd~server-processes-priority-lanes-fairly~2]
+    #[test]
+    fn utest_request_lanes_keep_order_of_messages_of_a_connection() {
+        let mut lanes = RequestLanes::default();
+        lanes.push(read("conn_1"));
+        lanes.push(write("conn_1"));
+        lanes.push(read("conn_2"));
+        lanes.push(agent_update("conn_2"));
+        lanes.push(write("conn_3"));
+
+        let order: Vec<String> = std::iter::from_fn(|| lanes.pop()).map(id_of).collect();
+        assert_eq!(
+            order,
+            vec!["conn_1", "conn_1", "conn_3", "conn_2", "conn_2"]
+        );
+    }
+
+    // [utest->swdd~server-queues-messages-in-priority-lanes~2]
+    #[test]
+    fn utest_request_lanes_have_no_room_if_a_lane_is_full() {
+        let mut lanes = RequestLanes::default();
+        for index in 0..LANE_CAPACITY - 1 {
+            lanes.push(read(&format!("read_{index}")));
+        }
+        lanes.push(write("write_1"));
+        assert!(lanes.has_room());
+
+        lanes.push(read("read_last"));
+        assert!(!lanes.has_room());
+
+        assert!(lanes.pop().is_some());
+        assert!(lanes.pop().is_some());
+        assert!(lanes.has_room());
+    }
+
+    // [utest->swdd~server-provides-request-lane-metrics~1]
+    #[test]
+    fn utest_request_lanes_provide_metrics() {
+        let mut lanes = RequestLanes::default();
+        lanes.push(read("read_1"));
+        lanes.push(read("read_2"));
+        lanes.push(write("write_1"));
+        assert!(lanes.pop().is_some());
+        assert!(lanes.pop().is_some());
+
+        assert_eq!(
+            lanes.get_lane_infos(),
+            vec![
+                RequestLaneInfo {
+                    lane: "agentUpdates".to_owned(),
+                    depth: 0,
+                    max_depth: 0,
+                    processed: 0,
+                },
+                RequestLaneInfo {
+                    lane: "writes".to_owned(),
+                    depth: 0,
+                    max_depth: 1,
+                    processed: 1,
+                },
+                RequestLaneInfo {
+                    lane: "reads".to_owned(),
+                    depth: 1,
+                    max_depth: 2,
+                    processed: 1,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,609 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::Duration;
+
+use common::commands::{RolloutGroupStatus, RolloutState, RolloutStatus};
+use common::objects::{
+    CompleteState, DeletedWorkload, ExecutionState, ExecutionStateEnum, PendingSubstate,
+    WorkloadInstanceName, WorkloadSpec,
+};
+use tokio::time::Instant;
+
+use crate::workload_state_db::WorkloadStateDB;
+
+pub const DEFAULT_ROLLOUT_GROUP: &str = "default";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RolloutConfig {
+    pub soak_time: Duration,
+    pub max_failure_rate: f64,
+}
+
+type WorkloadChanges = (Vec<WorkloadSpec>, Vec<DeletedWorkload>);
+
+struct RolloutGroup {
+    name: String,
+    agents: BTreeSet<String>,
+    workload_names: BTreeSet<String>,
+    added_workloads: Vec<WorkloadSpec>,
+    deleted_workloads: Vec<DeletedWorkload>,
+    added_instance_names: Vec<WorkloadInstanceName>,
+    state: RolloutState,
+    failed_workloads: u32,
+}
+
+impl RolloutGroup {
+    fn new(name: String) -> Self {
+        RolloutGroup {
+            name,
+            agents: BTreeSet::new(),
+            workload_names: BTreeSet::new(),
+            added_workloads: Vec::new(),
+            deleted_workloads: Vec::new(),
+            added_instance_names: Vec::new(),
+            state: RolloutState::NotStarted,
+            failed_workloads: 0,
+        }
+    }
+
+    fn take_changes(&mut self) -> WorkloadChanges {
+        self.state = RolloutState::InProgress;
+        (
+            std::mem::take(&mut self.added_workloads),
+            std::mem::take(&mut self.deleted_workloads),
+        )
+    }
+
+    fn update_mask(&self) -> Vec<String> {
+        self.workload_names
+            .iter()
+            .map(|workload_name| format!("desiredState.workloads.{}", workload_name))
+            .collect()
+    }
+
+    fn count_failed_workloads(&self, workload_state_db: &WorkloadStateDB) -> u32 {
+        self.added_instance_names
+            .iter()
+            .filter_map(|instance_name| workload_state_db.get_execution_state(instance_name))
+            .filter(|execution_state| is_failed_for_rollout(execution_state))
+            .count() as u32
+    }
+}
+
+fn is_failed_for_rollout(execution_state: &ExecutionState) -> bool {
+    matches!(
+        execution_state.state,
+        ExecutionStateEnum::Failed(_)
+            | ExecutionStateEnum::Pending(PendingSubstate::StartingFailed)
+            | ExecutionStateEnum::Pending(PendingSubstate::DependencyTimeout)
+            | ExecutionStateEnum::Pending(PendingSubstate::DependencyCycle)
+    )
+}
+
+struct Rollout {
+    groups: Vec<RolloutGroup>,
+    // the desired state after the rollout, the groups are taken from it when they are applied
+    new_state: CompleteState,
+    current_group: usize,
+    soak_deadline: Option<Instant>,
+    state: RolloutState,
+    message: String,
+}
+
+// [impl->swdd~server-applies-staged-rollout-per-rollout-group~1]
+#[derive(Default)]
+pub struct RolloutManager {
+    config: Option<RolloutConfig>,
+    agent_groups: HashMap<String, String>,
+    rollout: Option<Rollout>,
+}
+
+impl RolloutManager {
+    pub fn new(config: Option<RolloutConfig>) -> Self {
+        RolloutManager {
+            config,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_agent_group(&mut self, agent_name: &str, rollout_group: Option<String>) {
+        match rollout_group {
+            Some(rollout_group) => {
+                self.agent_groups
+                    .insert(agent_name.to_owned(), rollout_group);
+            }
+            None => {
+                self.agent_groups.remove(agent_name);
+            }
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.is_some()
+    }
+
+    pub fn is_in_progress(&self) -> bool {
+        self.rollout
+            .as_ref()
+            .is_some_and(|rollout| rollout.state == RolloutState::InProgress)
+    }
+
+    // Splits the changes into rollout groups and returns the changes of the first group.
+    // If staged rollouts are disabled all changes are returned unchanged.
+    pub fn start(
+        &mut self,
+        added_workloads: Vec<WorkloadSpec>,
+        deleted_workloads: Vec<DeletedWorkload>,
+        new_state: CompleteState,
+    ) -> WorkloadChanges {
+        let Some(config) = &self.config else {
+            return (added_workloads, deleted_workloads);
+        };
+
+        // the groups are applied in the lexicographical order of their names
+        let mut groups: BTreeMap<String, RolloutGroup> = BTreeMap::new();
+        // all changes of a workload belong to one group, a moved workload to the group of its new agent
+        let mut group_of_workload: HashMap<String, String> = HashMap::new();
+        for workload in added_workloads {
+            let agent_name = workload.instance_name.agent_name().to_owned();
+            let workload_name = workload.instance_name.workload_name().to_owned();
+            let group_name = group_of_workload
+                .entry(workload_name.clone())
+                .or_insert_with(|| self.group_of(&agent_name))
+                .clone();
+            let group = groups
+                .entry(group_name)
+                .or_insert_with_key(|name| RolloutGroup::new(name.clone()));
+            group.agents.insert(agent_name);
+            group.workload_names.insert(workload_name);
+            group
+                .added_instance_names
+                .push(workload.instance_name.clone());
+            group.added_workloads.push(workload);
+        }
+        for workload in deleted_workloads {
+            let agent_name = workload.instance_name.agent_name().to_owned();
+            let workload_name = workload.instance_name.workload_name().to_owned();
+            let group_name = group_of_workload
+                .entry(workload_name.clone())
+                .or_insert_with(|| self.group_of(&agent_name))
+                .clone();
+            let group = groups
+                .entry(group_name)
+                .or_insert_with_key(|name| RolloutGroup::new(name.clone()));
+            group.agents.insert(agent_name);
+            group.workload_names.insert(workload_name);
+            group.deleted_workloads.push(workload);
+        }
+
+        let mut groups: Vec<RolloutGroup> = groups.into_values().collect();
+        let group_count = groups.len();
+        let Some(first_group) = groups.first_mut() else {
+            return (Vec::new(), Vec::new());
+        };
+
+        log::info!(
+            "Starting staged rollout with {} rollout group(s), applying rollout group '{}'.",
+            group_count,
+            first_group.name
+        );
+        let first_changes = first_group.take_changes();
+
+        self.rollout = Some(Rollout {
+            groups,
+            new_state,
+            current_group: 0,
+            soak_deadline: Some(Instant::now() + config.soak_time),
+            state: RolloutState::InProgress,
+            message: String::new(),
+        });
+
+        first_changes
+    }
+
+    // Returns the update mask of the workloads of the rollout groups that are not started yet.
+    // Their previous version stays in the desired state until their rollout group is applied.
+    // [impl->swdd~server-keeps-pending-rollout-groups-out-of-desired-state~1]
+    pub fn pending_update_mask(&self) -> Vec<String> {
+        self.rollout
+            .iter()
+            .flat_map(|rollout| rollout.groups.iter())
+            .filter(|group| group.state == RolloutState::NotStarted)
+            .flat_map(RolloutGroup::update_mask)
+            .collect()
+    }
+
+    // Returns the desired state after the rollout and the update mask of the workloads of the
+    // rollout group currently applied.
+    // [impl->swdd~server-keeps-pending-rollout-groups-out-of-desired-state~1]
+    pub fn current_group_update(&self) -> Option<(CompleteState, Vec<String>)> {
+        let rollout = self.rollout.as_ref()?;
+        let group = rollout.groups.get(rollout.current_group)?;
+        Some((rollout.new_state.clone(), group.update_mask()))
+    }
+
+    // Resolves when the soak time of the current rollout group has elapsed.
+    // Never resolves if no rollout is in progress.
+    pub async fn soak_time_elapsed(&self) {
+        match self
+            .rollout
+            .as_ref()
+            .and_then(|rollout| rollout.soak_deadline)
+        {
+            Some(soak_deadline) => tokio::time::sleep_until(soak_deadline).await,
+            None => std::future::pending().await,
+        }
+    }
+
+    // Evaluates the failure rate of the current rollout group and returns the changes
+    // of the next group if the rollout can be continued.
+    // [impl->swdd~server-halts-staged-rollout-on-elevated-failure-rate~1]
+    pub fn advance(&mut self, workload_state_db: &WorkloadStateDB) -> Option<WorkloadChanges> {
+        let config = self.config.as_ref()?;
+        let rollout = self.rollout.as_mut()?;
+        if rollout.state != RolloutState::InProgress {
+            return None;
+        }
+
+        let group = rollout.groups.get_mut(rollout.current_group)?;
+        group.failed_workloads = group.count_failed_workloads(workload_state_db);
+        let workload_count = group.added_instance_names.len();
+        let failure_rate = if workload_count == 0 {
+            0.0
+        } else {
+            group.failed_workloads as f64 / workload_count as f64
+        };
+
+        if failure_rate > config.max_failure_rate {
+            group.state = RolloutState::Halted;
+            rollout.state = RolloutState::Halted;
+            rollout.soak_deadline = None;
+            rollout.message = format!(
+                "Rollout halted in rollout group '{}': {} of {} workload(s) failed.",
+                group.name, group.failed_workloads, workload_count
+            );
+            // [impl->swdd~server-keeps-pending-rollout-groups-out-of-desired-state~1]
+            let pending_groups: Vec<&str> = rollout
+                .groups
+                .iter()
+                .filter(|group| group.state == RolloutState::NotStarted)
+                .map(|group| group.name.as_str())
+                .collect();
+            if !pending_groups.is_empty() {
+                rollout.message.push_str(&format!(
+                    " The desired state keeps the previous workloads of rollout group(s) '{}'.",
+                    pending_groups.join("', '")
+                ));
+            }
+            log::warn!("{}", rollout.message);
+            return None;
+        }
+
+        group.state = RolloutState::Completed;
+        rollout.current_group += 1;
+
+        match rollout.groups.get_mut(rollout.current_group) {
+            Some(next_group) => {
+                log::info!(
+                    "Continuing staged rollout with rollout group '{}'.",
+                    next_group.name
+                );
+                rollout.soak_deadline = Some(Instant::now() + config.soak_time);
+                Some(next_group.take_changes())
+            }
+            None => {
+                log::info!("Staged rollout completed.");
+                rollout.state = RolloutState::Completed;
+                rollout.soak_deadline = None;
+                None
+            }
+        }
+    }
+
+    // [impl->swdd~server-provides-rollout-status~1]
+    pub fn status(&self, workload_state_db: &WorkloadStateDB) -> RolloutStatus {
+        let Some(rollout) = &self.rollout else {
+            return RolloutStatus {
+                message: if self.config.is_some() {
+                    "No staged rollout has been started yet.".to_string()
+                } else {
+                    "Staged rollouts are disabled.".to_string()
+                },
+                ..Default::default()
+            };
+        };
+
+        RolloutStatus {
+            state: rollout.state,
+            groups: rollout
+                .groups
+                .iter()
+                .map(|group| RolloutGroupStatus {
+                    name: group.name.clone(),
+                    state: group.state,
+                    agents: group.agents.iter().cloned().collect(),
+                    workloads: group.added_instance_names.len() as u32,
+                    failed_workloads: if group.state == RolloutState::InProgress {
+                        group.count_failed_workloads(workload_state_db)
+                    } else {
+                        group.failed_workloads
+                    },
+                })
+                .collect(),
+            message: rollout.message.clone(),
+        }
+    }
+
+    fn group_of(&self, agent_name: &str) -> String {
+        self.agent_groups
+            .get(agent_name)
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_ROLLOUT_GROUP.to_owned())
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common::commands::RolloutState;
+    use common::objects::{
+        generate_test_workload_spec_with_param, generate_test_workload_state_with_workload_spec,
+        CompleteState, DeletedWorkload, ExecutionState,
+    };
+    use common::test_utils::generate_test_complete_state;
+
+    use super::{RolloutConfig, RolloutManager, DEFAULT_ROLLOUT_GROUP};
+    use crate::workload_state_db::WorkloadStateDB;
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+    const AGENT_C: &str = "agent_C";
+    const WORKLOAD_NAME_1: &str = "workload_1";
+    const WORKLOAD_NAME_2: &str = "workload_2";
+    const WORKLOAD_NAME_3: &str = "workload_3";
+    const RUNTIME: &str = "runtime";
+    const GROUP_CANARY: &str = "canary";
+    const GROUP_FLEET: &str = "fleet";
+
+    fn create_rollout_manager(max_failure_rate: f64) -> RolloutManager {
+        let mut rollout_manager = RolloutManager::new(Some(RolloutConfig {
+            soak_time: Duration::from_secs(10),
+            max_failure_rate,
+        }));
+        rollout_manager.set_agent_group(AGENT_A, Some(GROUP_CANARY.to_string()));
+        rollout_manager.set_agent_group(AGENT_B, Some(GROUP_FLEET.to_string()));
+        rollout_manager
+    }
+
+    // [utest->swdd~server-applies-staged-rollout-per-rollout-group~1]
+    #[test]
+    fn utest_rollout_disabled_returns_all_changes() {
+        let mut rollout_manager = RolloutManager::new(None);
+
+        let workload_1 = generate_test_workload_spec_with_param(
+            AGENT_A.into(),
+            WORKLOAD_NAME_1.into(),
+            RUNTIME.into(),
+        );
+        let workload_2 = generate_test_workload_spec_with_param(
+            AGENT_B.into(),
+            WORKLOAD_NAME_2.into(),
+            RUNTIME.into(),
+        );
+
+        let (added_workloads, deleted_workloads) = rollout_manager.start(
+            vec![workload_1.clone(), workload_2.clone()],
+            vec![],
+            CompleteState::default(),
+        );
+
+        assert_eq!(added_workloads, vec![workload_1, workload_2]);
+        assert!(deleted_workloads.is_empty());
+        assert!(!rollout_manager.is_in_progress());
+    }
+
+    // [utest->swdd~server-applies-staged-rollout-per-rollout-group~1]
+    #[test]
+    fn utest_rollout_applies_changes_group_by_group() {
+        let mut rollout_manager = create_rollout_manager(0.0);
+        let workload_state_db = WorkloadStateDB::default();
+
+        let workload_1 = generate_test_workload_spec_with_param(
+            AGENT_A.into(),
+            WORKLOAD_NAME_1.into(),
+            RUNTIME.into(),
+        );
+        let workload_2 = generate_test_workload_spec_with_param(
+            AGENT_B.into(),
+            WORKLOAD_NAME_2.into(),
+            RUNTIME.into(),
+        );
+        let workload_3 = generate_test_workload_spec_with_param(
+            AGENT_C.into(),
+            WORKLOAD_NAME_3.into(),
+            RUNTIME.into(),
+        );
+
+        let (added_workloads, _) = rollout_manager.start(
+            vec![workload_2.clone(), workload_3.clone(), workload_1.clone()],
+            vec![],
+            CompleteState::default(),
+        );
+        assert_eq!(added_workloads, vec![workload_1]);
+        assert!(rollout_manager.is_in_progress());
+
+        let (added_workloads, _) = rollout_manager.advance(&workload_state_db).unwrap();
+        assert_eq!(added_workloads, vec![workload_3]);
+
+        let (added_workloads, _) = rollout_manager.advance(&workload_state_db).unwrap();
+        assert_eq!(added_workloads, vec![workload_2]);
+
+        assert!(rollout_manager.advance(&workload_state_db).is_none());
+        assert!(!rollout_manager.is_in_progress());
+
+        let rollout_status = rollout_manager.status(&workload_state_db);
+        assert_eq!(rollout_status.state, RolloutState::Completed);
+        assert_eq!(
+            rollout_status
+                .groups
+                .iter()
+                .map(|group| group.name.as_str())
+                .collect::<Vec<_>>(),
+            vec![GROUP_CANARY, DEFAULT_ROLLOUT_GROUP, GROUP_FLEET]
+        );
+    }
+
+    // [utest->swdd~server-halts-staged-rollout-on-elevated-failure-rate~1]
+    #[test]
+    fn utest_rollout_halts_on_elevated_failure_rate() {
+        let mut rollout_manager = create_rollout_manager(0.4);
+        let mut workload_state_db = WorkloadStateDB::default();
+
+        let workload_1 = generate_test_workload_spec_with_param(
+            AGENT_A.into(),
+            WORKLOAD_NAME_1.into(),
+            RUNTIME.into(),
+        );
+        let workload_2 = generate_test_workload_spec_with_param(
+            AGENT_A.into(),
+            WORKLOAD_NAME_2.into(),
+            RUNTIME.into(),
+        );
+        let workload_3 = generate_test_workload_spec_with_param(
+            AGENT_B.into(),
+            WORKLOAD_NAME_3.into(),
+            RUNTIME.into(),
+        );
+
+        rollout_manager.start(
+            vec![workload_1.clone(), workload_2.clone(), workload_3],
+            vec![],
+            CompleteState::default(),
+        );
+
+        workload_state_db.process_new_states(vec![
+            generate_test_workload_state_with_workload_spec(
+                &workload_1,
+                ExecutionState::failed("some error"),
+            ),
+            generate_test_workload_state_with_workload_spec(&workload_2, ExecutionState::running()),
+        ]);
+
+        assert!(rollout_manager.advance(&workload_state_db).is_none());
+        assert!(!rollout_manager.is_in_progress());
+
+        let rollout_status = rollout_manager.status(&workload_state_db);
+        assert_eq!(rollout_status.state, RolloutState::Halted);
+        assert_eq!(rollout_status.groups[0].state, RolloutState::Halted);
+        assert_eq!(rollout_status.groups[0].failed_workloads, 1);
+        assert_eq!(rollout_status.groups[1].state, RolloutState::NotStarted);
+        assert!(rollout_status.message.contains(GROUP_FLEET));
+    }
+
+    // [utest->swdd~server-keeps-pending-rollout-groups-out-of-desired-state~1]
+    #[test]
+    fn utest_rollout_provides_update_masks_of_pending_and_current_groups() {
+        let mut rollout_manager = create_rollout_manager(0.0);
+        let workload_state_db = WorkloadStateDB::default();
+
+        let workload_1 = generate_test_workload_spec_with_param(
+            AGENT_A.into(),
+            WORKLOAD_NAME_1.into(),
+            RUNTIME.into(),
+        );
+        let workload_2 = generate_test_workload_spec_with_param(
+            AGENT_B.into(),
+            WORKLOAD_NAME_2.into(),
+            RUNTIME.into(),
+        );
+        // workload 3 is moved from agent A to agent B and thus belongs to the group of agent B
+        let moved_workload = generate_test_workload_spec_with_param(
+            AGENT_B.into(),
+            WORKLOAD_NAME_3.into(),
+            RUNTIME.into(),
+        );
+        let deleted_workload = DeletedWorkload {
+            instance_name: generate_test_workload_spec_with_param(
+                AGENT_A.into(),
+                WORKLOAD_NAME_3.into(),
+                RUNTIME.into(),
+            )
+            .instance_name,
+            ..Default::default()
+        };
+        let new_state = generate_test_complete_state(vec![workload_1.clone()]);
+
+        let (added_workloads, deleted_workloads) = rollout_manager.start(
+            vec![workload_1.clone(), workload_2, moved_workload],
+            vec![deleted_workload],
+            new_state.clone(),
+        );
+
+        assert_eq!(added_workloads, vec![workload_1]);
+        assert!(deleted_workloads.is_empty());
+        assert_eq!(
+            rollout_manager.pending_update_mask(),
+            vec![
+                format!("desiredState.workloads.{}", WORKLOAD_NAME_2),
+                format!("desiredState.workloads.{}", WORKLOAD_NAME_3),
+            ]
+        );
+        assert_eq!(
+            rollout_manager.current_group_update(),
+            Some((
+                new_state.clone(),
+                vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_1)]
+            ))
+        );
+
+        let (added_workloads, deleted_workloads) =
+            rollout_manager.advance(&workload_state_db).unwrap();
+
+        assert_eq!(added_workloads.len(), 2);
+        assert_eq!(deleted_workloads.len(), 1);
+        assert!(rollout_manager.pending_update_mask().is_empty());
+        assert_eq!(
+            rollout_manager.current_group_update(),
+            Some((
+                new_state,
+                vec![
+                    format!("desiredState.workloads.{}", WORKLOAD_NAME_2),
+                    format!("desiredState.workloads.{}", WORKLOAD_NAME_3),
+                ]
+            ))
+        );
+    }
+
+    // [utest->swdd~server-provides-rollout-status~1]
+    #[test]
+    fn utest_rollout_status_without_rollout() {
+        let rollout_manager = RolloutManager::new(None);
+
+        let rollout_status = rollout_manager.status(&WorkloadStateDB::default());
+
+        assert_eq!(rollout_status.state, RolloutState::NotStarted);
+        assert!(rollout_status.groups.is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use common::commands::{PendingWorkloadOperation, SchedulerQueue};
+
+// The last pending workload operations reported by each connected agent.
+// An agent only reports its queue when it changed, thus an empty queue is not stored.
+#[derive(Default)]
+pub struct SchedulerQueues {
+    queues: BTreeMap<String, Vec<PendingWorkloadOperation>>,
+}
+
+impl SchedulerQueues {
+    // [impl->swdd~server-stores-scheduler-queues-of-agents~1]
+    pub fn update(
+        &mut self,
+        agent_name: String,
+        pending_operations: Vec<PendingWorkloadOperation>,
+    ) {
+        if pending_operations.is_empty() {
+            self.queues.remove(&agent_name);
+        } else {
+            self.queues.insert(agent_name, pending_operations);
+        }
+    }
+
+    // [impl->swdd~server-stores-scheduler-queues-of-agents~1]
+    pub fn remove_of_agent(&mut self, agent_name: &str) {
+        self.queues.remove(agent_name);
+    }
+
+    // [impl->swdd~server-provides-scheduler-queues~1]
+    // Returns the pending operations of the given agent or of all agents if the name is empty.
+    pub fn get(&self, agent_name: &str) -> SchedulerQueue {
+        let mut pending_operations: Vec<PendingWorkloadOperation> = self
+            .queues
+            .iter()
+            .filter(|(queue_agent_name, _)| {
+                agent_name.is_empty() || *queue_agent_name == agent_name
+            })
+            .flat_map(|(_, pending_operations)| pending_operations.iter().cloned())
+            .collect();
+        pending_operations
+            .sort_by(|a, b| (&a.agent, &a.workload_name).cmp(&(&b.agent, &b.workload_name)));
+        SchedulerQueue { pending_operations }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::commands::{PendingOperation, PendingWorkloadOperation};
+
+    use super::SchedulerQueues;
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+
+    fn pending_operation(agent: &str, workload_name: &str) -> PendingWorkloadOperation {
+        PendingWorkloadOperation {
+            workload_name: workload_name.to_string(),
+            agent: agent.to_string(),
+            operation: PendingOperation::Create,
+            unfulfilled_dependencies: vec![],
+        }
+    }
+
+    // [utest->swdd~server-provides-scheduler-queues~1]
+    #[test]
+    fn utest_scheduler_queues_get_filters_by_agent_and_sorts() {
+        let mut scheduler_queues = SchedulerQueues::default();
+        scheduler_queues.update(
+            AGENT_B.to_string(),
+            vec![pending_operation(AGENT_B, "workload_1")],
+        );
+        scheduler_queues.update(
+            AGENT_A.to_string(),
+            vec![
+                pending_operation(AGENT_A, "workload_3"),
+                pending_operation(AGENT_A, "workload_2"),
+            ],
+        );
+
+        assert_eq!(
+            scheduler_queues.get("").pending_operations,
+            vec![
+                pending_operation(AGENT_A, "workload_2"),
+                pending_operation(AGENT_A, "workload_3"),
+                pending_operation(AGENT_B, "workload_1"),
+            ]
+        );
+        assert_eq!(
+            scheduler_queues.get(AGENT_B).pending_operations,
+            vec![pending_operation(AGENT_B, "workload_1")]
+        );
+        assert!(scheduler_queues
+            .get("unknown")
+            .pending_operations
+            .is_empty());
+    }
+
+    // [utest->swdd~server-stores-scheduler-queues-of-agents~1]
+    #[test]
+    fn utest_scheduler_queues_replaces_and_removes_queue_of_agent() {
+        let mut scheduler_queues = SchedulerQueues::default();
+        scheduler_queues.update(
+            AGENT_A.to_string(),
+            vec![pending_operation(AGENT_A, "workload_1")],
+        );
+        scheduler_queues.update(
+            AGENT_A.to_string(),
+            vec![pending_operation(AGENT_A, "workload_2")],
+        );
+        scheduler_queues.update(
+            AGENT_B.to_string(),
+            vec![pending_operation(AGENT_B, "workload_3")],
+        );
+
+        assert_eq!(
+            scheduler_queues.get(AGENT_A).pending_operations,
+            vec![pending_operation(AGENT_A, "workload_2")]
+        );
+
+        scheduler_queues.update(AGENT_A.to_string(), vec![]);
+        scheduler_queues.remove_of_agent(AGENT_B);
+
+        assert!(scheduler_queues.queues.is_empty());
+    }
+}
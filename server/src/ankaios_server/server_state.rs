@@ -13,16 +13,24 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::cycle_check;
+use super::dependency_graph;
+use super::impact_analysis;
+use super::managed_by::{self, Modifier};
 #[cfg_attr(test, mockall_double::double)]
 use super::delete_graph::DeleteGraph;
 use crate::workload_state_db::WorkloadStateDB;
-use common::objects::{WorkloadInstanceName, WorkloadState};
+use ankaios_model::input_limits::{InputLimitError, InputLimits};
+use ankaios_model::objects::workload_specs_differ;
+use common::objects::{
+    verify_enabled_if, CronSchedule, StoredWorkloadSpec, WorkloadInstanceName, WorkloadState,
+};
 use common::{
-    commands::CompleteStateRequest,
+    commands::{CompleteStateRequest, DependencyGraph, ImpactAnalysis},
+    memory_profiling::{self, Subsystem},
     objects::{CompleteState, DeletedWorkload, State, WorkloadSpec},
     state_manipulation::{Object, Path},
 };
-use std::fmt::Display;
+use std::{collections::HashMap, fmt::Display};
 
 #[cfg(test)]
 use mockall::automock;
@@ -65,48 +73,203 @@ fn update_state(
     }
 }
 
-fn extract_added_and_deleted_workloads(
-    desired_state: &State,
-    new_state: &State,
-) -> Option<(Vec<WorkloadSpec>, Vec<DeletedWorkload>)> {
-    let mut added_workloads: Vec<WorkloadSpec> = Vec::new();
-    let mut deleted_workloads: Vec<DeletedWorkload> = Vec::new();
+// [impl->swdd~server-expands-workload-templates~1]
+fn expand_workload(state: &State, workload: &StoredWorkloadSpec) -> StoredWorkloadSpec {
+    // The templates are verified before a new state is accepted.
+    let mut expanded_workload = state
+        .expand_workload(workload)
+        .unwrap_or_else(|_| workload.clone());
+    // [impl->swdd~server-deploys-workloads-of-active-system-mode~1]
+    // the modes only decide if the workload is deployed, changing them does not restart it
+    expanded_workload.modes.clear();
+    expanded_workload
+}
 
-    // find updated or deleted workloads
-    desired_state.workloads.iter().for_each(|(wl_name, wls)| {
-        if let Some(new_wls) = new_state.workloads.get(wl_name) {
+// [impl->swdd~server-resolves-config-references-of-workloads~1]
+fn create_workload_spec(
+    state: &State,
+    workload_name: &str,
+    workload: &StoredWorkloadSpec,
+) -> WorkloadSpec {
+    let mut workload_spec =
+        WorkloadSpec::from((workload_name.to_owned(), expand_workload(state, workload)));
+    // The references are verified before a new state is accepted.
+    workload_spec.configs = state.get_configs_of_workload(workload).unwrap_or_default();
+    workload_spec
+}
+
+// The workloads of a state to verify or to compare, all workloads or only the updated ones.
+type Workloads<'a> = [(&'a String, &'a StoredWorkloadSpec)];
+
+// [impl->swdd~server-state-rejects-state-with-unknown-config-references~1]
+fn verify_config_references(state: &State, workloads: &Workloads) -> Result<(), UpdateStateError> {
+    workloads.iter().try_for_each(|(workload_name, workload)| {
+        state
+            .get_configs_of_workload(workload)
+            .map(|_| ())
+            .map_err(|err| {
+                UpdateStateError::ResultInvalid(format!(
+                    "Workload '{}' references an unknown config: {}",
+                    workload_name, err
+                ))
+            })
+    })
+}
+
+// [impl->swdd~server-state-rejects-state-with-invalid-workload-template~1]
+fn verify_workload_templates(state: &State, workloads: &Workloads) -> Result<(), UpdateStateError> {
+    workloads.iter().try_for_each(|(workload_name, workload)| {
+        let expanded_workload = state.expand_workload(workload).map_err(|err| {
+            UpdateStateError::ResultInvalid(format!(
+                "Workload '{}' cannot be expanded: {}",
+                workload_name, err
+            ))
+        })?;
+        if expanded_workload.runtime.is_empty() {
+            return Err(UpdateStateError::ResultInvalid(format!(
+                "Workload '{}' has no runtime.",
+                workload_name
+            )));
+        }
+        Ok(())
+    })
+}
+
+// [impl->swdd~server-state-rejects-state-with-invalid-enabled-if~1]
+fn verify_enabled_if_expressions(workloads: &Workloads) -> Result<(), UpdateStateError> {
+    workloads.iter().try_for_each(|(workload_name, workload)| {
+        verify_enabled_if(&workload.enabled_if).map_err(|err| {
+            UpdateStateError::ResultInvalid(format!("Workload '{}': {}", workload_name, err))
+        })
+    })
+}
+
+// [impl->swdd~server-state-rejects-state-with-invalid-schedules~1]
+fn verify_schedules(workloads: &Workloads) -> Result<(), UpdateStateError> {
+    workloads.iter().try_for_each(|(workload_name, workload)| {
+        match workload.schedule.as_deref().map(str::parse::<CronSchedule>) {
+            Some(Err(err)) => Err(UpdateStateError::ResultInvalid(format!(
+                "Workload '{}': {}",
+                workload_name, err
+            ))),
+            _ => Ok(()),
+        }
+    })
+}
+
+// [impl->swdd~server-state-rejects-state-with-invalid-resource-requests~1]
+fn verify_resources(workloads: &Workloads) -> Result<(), UpdateStateError> {
+    workloads.iter().try_for_each(|(workload_name, workload)| {
+        match workload
+            .resources
+            .as_ref()
+            .map(|resources| resources.requests.verify())
+        {
+            Some(Err(err)) => Err(UpdateStateError::ResultInvalid(format!(
+                "Workload '{}': {}",
+                workload_name, err
+            ))),
+            _ => Ok(()),
+        }
+    })
+}
+
+// [impl->swdd~server-state-rejects-state-with-unknown-system-modes~1]
+fn verify_system_modes(state: &State, workloads: &Workloads) -> Result<(), UpdateStateError> {
+    if !state.active_mode.is_empty() && !state.modes.contains(&state.active_mode) {
+        return Err(UpdateStateError::ResultInvalid(format!(
+            "The active mode '{}' is not one of the modes of the state.",
+            state.active_mode
+        )));
+    }
+
+    workloads.iter().try_for_each(|(workload_name, workload)| {
+        match workload
+            .modes
+            .iter()
+            .find(|mode| !state.modes.contains(mode))
+        {
+            Some(unknown_mode) => Err(UpdateStateError::ResultInvalid(format!(
+                "Workload '{}' runs in the unknown mode '{}'.",
+                workload_name, unknown_mode
+            ))),
+            None => Ok(()),
+        }
+    })
+}
+
+fn verify_workloads(state: &State, workloads: &Workloads) -> Result<(), UpdateStateError> {
+    verify_config_references(state, workloads)?;
+    verify_workload_templates(state, workloads)?;
+    verify_enabled_if_expressions(workloads)?;
+    verify_schedules(workloads)?;
+    verify_resources(workloads)?;
+    verify_system_modes(state, workloads)
+}
+
+// [impl->swdd~server-deploys-workloads-of-active-system-mode~1]
+// [impl->swdd~server-does-not-deploy-workloads-of-stopped-groups~1]
+fn is_deployed(state: &State, workload_name: &str, workload: &StoredWorkloadSpec) -> bool {
+    state.is_active_in_mode(workload) && !state.is_stopped_by_group(workload_name, workload)
+}
+
+fn deployed_workloads(state: &State) -> HashMap<&String, &StoredWorkloadSpec> {
+    state
+        .workloads
+        .iter()
+        .filter(|(workload_name, workload)| is_deployed(state, workload_name, workload))
+        .collect()
+}
+
+// Compares the deployed current and new version of a workload and adds the resulting
+// operations to the added and deleted workloads.
+fn diff_workload(
+    (desired_state, workload): (&State, Option<&StoredWorkloadSpec>),
+    (new_state, new_workload): (&State, Option<&StoredWorkloadSpec>),
+    workload_name: &str,
+    added_workloads: &mut Vec<WorkloadSpec>,
+    deleted_workloads: &mut Vec<DeletedWorkload>,
+) {
+    match (workload, new_workload) {
+        (Some(wls), Some(new_wls)) => {
+            // [impl->swdd~server-expands-workload-templates~1]
+            let wls = &expand_workload(desired_state, wls);
+            let new_wls = &expand_workload(new_state, new_wls);
             // The new workload is identical with existing or updated. Lets check if it is an update.
-            if wls != new_wls {
+            // [impl->swdd~server-detects-changed-config-of-workload~1]
+            // [impl->swdd~server-detects-changed-workload-semantically~1]
+            if workload_specs_differ(wls, new_wls)
+                || desired_state.get_configs_of_workload(wls)
+                    != new_state.get_configs_of_workload(new_wls)
+            {
                 // [impl->swdd~server-detects-changed-workload~1]
-                added_workloads.push(WorkloadSpec::from((wl_name.to_owned(), new_wls.clone())));
+                added_workloads.push(create_workload_spec(new_state, workload_name, new_wls));
                 deleted_workloads.push(DeletedWorkload {
-                    instance_name: WorkloadInstanceName::from((wl_name.to_owned(), wls)),
+                    instance_name: WorkloadInstanceName::from((workload_name.to_owned(), wls)),
                     ..Default::default()
                 });
             }
-        } else {
+        }
+        (Some(wls), None) => {
             // [impl->swdd~server-detects-deleted-workload~1]
+            let wls = &expand_workload(desired_state, wls);
             deleted_workloads.push(DeletedWorkload {
-                instance_name: WorkloadInstanceName::from((wl_name.to_owned(), wls)),
+                instance_name: WorkloadInstanceName::from((workload_name.to_owned(), wls)),
                 ..Default::default()
             });
         }
-    });
-
-    // find new workloads
-    // [impl->swdd~server-detects-new-workload~1]
-    new_state
-        .workloads
-        .iter()
-        .for_each(|(new_wl_name, new_wls)| {
-            if !desired_state.workloads.contains_key(new_wl_name) {
-                added_workloads.push(WorkloadSpec::from((
-                    new_wl_name.to_owned(),
-                    new_wls.clone(),
-                )));
-            }
-        });
+        (None, Some(new_wls)) => {
+            // [impl->swdd~server-detects-new-workload~1]
+            added_workloads.push(create_workload_spec(new_state, workload_name, new_wls));
+        }
+        (None, None) => {}
+    }
+}
 
+fn to_added_and_deleted_workloads(
+    added_workloads: Vec<WorkloadSpec>,
+    deleted_workloads: Vec<DeletedWorkload>,
+) -> Option<(Vec<WorkloadSpec>, Vec<DeletedWorkload>)> {
     if added_workloads.is_empty() && deleted_workloads.is_empty() {
         return None;
     }
@@ -114,11 +277,84 @@ fn extract_added_and_deleted_workloads(
     Some((added_workloads, deleted_workloads))
 }
 
+fn extract_added_and_deleted_workloads(
+    desired_state: &State,
+    new_state: &State,
+) -> Option<(Vec<WorkloadSpec>, Vec<DeletedWorkload>)> {
+    let mut added_workloads: Vec<WorkloadSpec> = Vec::new();
+    let mut deleted_workloads: Vec<DeletedWorkload> = Vec::new();
+
+    // [impl->swdd~server-deploys-workloads-of-active-system-mode~1]
+    // [impl->swdd~server-does-not-deploy-workloads-of-stopped-groups~1]
+    // a workload leaving or entering the active mode or a stopped group is handled as a
+    // deleted or new workload
+    let desired_workloads = deployed_workloads(desired_state);
+    let new_workloads = deployed_workloads(new_state);
+
+    // find updated or deleted workloads
+    desired_workloads.iter().for_each(|(wl_name, wls)| {
+        diff_workload(
+            (desired_state, Some(*wls)),
+            (new_state, new_workloads.get(wl_name).copied()),
+            wl_name,
+            &mut added_workloads,
+            &mut deleted_workloads,
+        );
+    });
+
+    // find new workloads
+    new_workloads.iter().for_each(|(new_wl_name, new_wls)| {
+        if !desired_workloads.contains_key(new_wl_name) {
+            diff_workload(
+                (desired_state, None),
+                (new_state, Some(new_wls)),
+                new_wl_name,
+                &mut added_workloads,
+                &mut deleted_workloads,
+            );
+        }
+    });
+
+    to_added_and_deleted_workloads(added_workloads, deleted_workloads)
+}
+
+// [impl->swdd~server-state-updates-masked-workloads-incrementally~1]
+// Returns the names of the workloads if the update mask only replaces or removes complete
+// workloads of the desired state.
+fn workload_names_of_update_mask(update_mask: &[String]) -> Option<Vec<String>> {
+    if update_mask.is_empty() {
+        return None;
+    }
+    let mut workload_names = update_mask
+        .iter()
+        .map(|field| {
+            let path = Path::from(field.as_str());
+            match path.parts().as_slice() {
+                [desired_state, workloads, workload_name]
+                    if desired_state == "desiredState" && workloads == "workloads" =>
+                {
+                    Some(workload_name.to_owned())
+                }
+                _ => None,
+            }
+        })
+        .collect::<Option<Vec<String>>>()?;
+    workload_names.sort();
+    workload_names.dedup();
+    Some(workload_names)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum UpdateStateError {
     FieldNotFound(String),
     ResultInvalid(String),
     CycleInDependencies(String),
+    LimitExceeded(InputLimitError),
+    ManagedByOtherIdentity {
+        workload_name: String,
+        managed_by: String,
+    },
+    HooksNotAllowed(String),
 }
 
 impl Display for UpdateStateError {
@@ -137,6 +373,26 @@ impl Display for UpdateStateError {
                     workload_part_of_cycle
                 )
             }
+            UpdateStateError::LimitExceeded(limit_error) => {
+                write!(f, "Resulting State exceeds a limit: '{}'", limit_error)
+            }
+            UpdateStateError::ManagedByOtherIdentity {
+                workload_name,
+                managed_by,
+            } => {
+                write!(
+                    f,
+                    "workload '{}' is managed by '{}', force the update to modify it.",
+                    workload_name, managed_by
+                )
+            }
+            UpdateStateError::HooksNotAllowed(workload_name) => {
+                write!(
+                    f,
+                    "the hooks of workload '{}' cannot be set via the control interface.",
+                    workload_name
+                )
+            }
         }
     }
 }
@@ -162,6 +418,7 @@ impl ServerState {
             desired_state: self.state.desired_state.clone(),
             startup_state: self.state.startup_state.clone(),
             workload_states: workload_state_db.get_all_workload_states(),
+            ..Default::default()
         };
 
         // TODO: filtering is missing here
@@ -176,48 +433,140 @@ impl ServerState {
             .workloads
             .iter()
             .filter(|(_, workload)| workload.agent.eq(agent_name))
+            // [impl->swdd~server-deploys-workloads-of-active-system-mode~1]
+            // [impl->swdd~server-does-not-deploy-workloads-of-stopped-groups~1]
+            .filter(|(workload_name, workload)| {
+                is_deployed(&self.state.desired_state, workload_name, workload)
+            })
             .map(|(workload_name, workload)| {
-                WorkloadSpec::from((workload_name.clone(), workload.clone()))
+                create_workload_spec(&self.state.desired_state, workload_name, workload)
             })
             .collect()
     }
 
+    // [impl->swdd~server-provides-impact-analysis~1]
+    pub fn get_impact_analysis(&self, workload_names: &[String]) -> ImpactAnalysis {
+        impact_analysis::analyze(&self.state.desired_state, workload_names)
+    }
+
+    // [impl->swdd~server-provides-dependency-graph~1]
+    pub fn get_dependency_graph(&self, workload_state_db: &WorkloadStateDB) -> DependencyGraph {
+        dependency_graph::build(
+            &self.state.desired_state,
+            &workload_state_db.get_all_workload_states(),
+        )
+    }
+
     pub fn update(
         &mut self,
         new_state: CompleteState,
         update_mask: Vec<String>,
     ) -> Result<AddedDeletedWorkloads, UpdateStateError> {
+        self.apply_update(new_state, update_mask, None)
+    }
+
+    // [impl->swdd~server-state-records-managed-by~1]
+    // [impl->swdd~server-protects-managed-workloads~1]
+    pub fn update_by(
+        &mut self,
+        new_state: CompleteState,
+        update_mask: Vec<String>,
+        modifier: &Modifier,
+    ) -> Result<AddedDeletedWorkloads, UpdateStateError> {
+        self.apply_update(new_state, update_mask, Some(modifier))
+    }
+
+    // [impl->swdd~server-cleans-up-state~1]
+    pub fn cleanup_state(&mut self, new_workload_states: &[WorkloadState]) {
+        // [impl->swdd~server-removes-obsolete-delete-graph-entires~1]
+        self.delete_graph
+            .remove_deleted_workloads_from_delete_graph(new_workload_states);
+    }
+}
+
+impl ServerState {
+    fn apply_update(
+        &mut self,
+        new_state: CompleteState,
+        update_mask: Vec<String>,
+        modifier: Option<&Modifier>,
+    ) -> Result<AddedDeletedWorkloads, UpdateStateError> {
+        // [impl->swdd~server-attributes-heap-usage-to-subsystems~1]
+        let _memory_scope = memory_profiling::enter(Subsystem::StateStorage);
+
+        // [impl->swdd~server-state-updates-masked-workloads-incrementally~1]
+        if let Some(workload_names) = workload_names_of_update_mask(&update_mask) {
+            return self.apply_workload_update(new_state, workload_names, modifier);
+        }
+
         // [impl->swdd~update-desired-state-with-update-mask~1]
         // [impl->swdd~update-desired-state-empty-update-mask~1]
         match update_state(&self.state, new_state, update_mask) {
-            Ok(new_state) => {
+            Ok(mut new_state) => {
+                // [impl->swdd~server-state-records-managed-by~1]
+                managed_by::record_managed_by(
+                    &self.state.desired_state,
+                    &mut new_state.desired_state,
+                    modifier,
+                );
+                // [impl->swdd~server-protects-managed-workloads~1]
+                if let Some((workload_name, workload)) = modifier.and_then(|modifier| {
+                    managed_by::find_protected_workload(
+                        &self.state.desired_state,
+                        &new_state.desired_state,
+                        modifier,
+                    )
+                }) {
+                    return Err(UpdateStateError::ManagedByOtherIdentity {
+                        workload_name: workload_name.clone(),
+                        managed_by: workload.managed_by.clone(),
+                    });
+                }
+                // [impl->swdd~server-rejects-hooks-from-control-interface~1]
+                if let Some(workload_name) = modifier.and_then(|modifier| {
+                    managed_by::find_workload_with_changed_hooks(
+                        &self.state.desired_state,
+                        &new_state.desired_state,
+                        modifier,
+                    )
+                }) {
+                    return Err(UpdateStateError::HooksNotAllowed(workload_name.clone()));
+                }
+
+                // [impl->swdd~server-state-rejects-state-exceeding-input-limits~1]
+                InputLimits::configured()
+                    .check_state(&new_state.desired_state)
+                    .map_err(UpdateStateError::LimitExceeded)?;
+                let workloads: Vec<_> = new_state.desired_state.workloads.iter().collect();
+                verify_workloads(&new_state.desired_state, &workloads)?;
+
+                // [impl->swdd~server-state-rejects-state-with-cyclic-dependencies~2]
+                if let Some(workload_part_of_cycle) =
+                    cycle_check::dfs(&new_state.desired_state, None)
+                {
+                    return Err(UpdateStateError::CycleInDependencies(
+                        workload_part_of_cycle,
+                    ));
+                }
+
+                // [impl->swdd~server-state-warns-about-dependencies-on-unknown-workloads~2]
+                for (workload_name, dependency) in
+                    cycle_check::unknown_dependencies(&new_state.desired_state, None)
+                {
+                    log::warn!(
+                        "Workload '{}' depends on workload '{}' which is not part of the desired state.",
+                        workload_name,
+                        dependency
+                    );
+                }
+
                 let cmd = extract_added_and_deleted_workloads(
                     &self.state.desired_state,
                     &new_state.desired_state,
                 );
 
                 if let Some((added_workloads, mut deleted_workloads)) = cmd {
-                    let start_nodes: Vec<&str> = added_workloads
-                        .iter()
-                        .filter_map(|w| {
-                            if !w.dependencies.is_empty() {
-                                Some(w.instance_name.workload_name())
-                            } else {
-                                None
-                            }
-                        })
-                        .collect();
-
-                    // [impl->swdd~server-state-rejects-state-with-cyclic-dependencies~1]
-                    if let Some(workload_part_of_cycle) =
-                        cycle_check::dfs(&new_state.desired_state, Some(start_nodes))
-                    {
-                        return Err(UpdateStateError::CycleInDependencies(
-                            workload_part_of_cycle,
-                        ));
-                    }
-
-                    // [impl->swdd~server-state-stores-delete-condition~1]
+                    // [impl->swdd~server-state-stores-delete-condition~2]
                     self.delete_graph.insert(&added_workloads);
 
                     // [impl->swdd~server-state-adds-delete-conditions-to-deleted-workload~1]
@@ -227,6 +576,8 @@ impl ServerState {
                     self.state = new_state;
                     Ok(Some((added_workloads, deleted_workloads)))
                 } else {
+                    // keeps changes not affecting any workload, e.g., new configs
+                    self.state = new_state;
                     Ok(None)
                 }
             }
@@ -234,11 +585,156 @@ impl ServerState {
         }
     }
 
-    // [impl->swdd~server-cleans-up-state~1]
-    pub fn cleanup_state(&mut self, new_workload_states: &[WorkloadState]) {
-        // [impl->swdd~server-removes-obsolete-delete-graph-entires~1]
-        self.delete_graph
-            .remove_deleted_workloads_from_delete_graph(new_workload_states);
+    // [impl->swdd~server-state-updates-masked-workloads-incrementally~1]
+    // Replaces the masked workloads in place, thus only they are checked and compared. The
+    // rest of the desired state is unchanged and was already checked when it was accepted.
+    fn apply_workload_update(
+        &mut self,
+        mut new_state: CompleteState,
+        workload_names: Vec<String>,
+        modifier: Option<&Modifier>,
+    ) -> Result<AddedDeletedWorkloads, UpdateStateError> {
+        let mut current_workloads = State::default();
+        let mut new_workloads = State::default();
+        for workload_name in &workload_names {
+            if let Some(workload) = self.state.desired_state.workloads.get(workload_name) {
+                current_workloads
+                    .workloads
+                    .insert(workload_name.clone(), workload.clone());
+            }
+            if let Some(workload) = new_state.desired_state.workloads.remove(workload_name) {
+                new_workloads
+                    .workloads
+                    .insert(workload_name.clone(), workload);
+            }
+        }
+
+        // [impl->swdd~server-state-records-managed-by~1]
+        managed_by::record_managed_by(&current_workloads, &mut new_workloads, modifier);
+        // [impl->swdd~server-protects-managed-workloads~1]
+        if let Some((workload_name, workload)) = modifier.and_then(|modifier| {
+            managed_by::find_protected_workload(&current_workloads, &new_workloads, modifier)
+        }) {
+            return Err(UpdateStateError::ManagedByOtherIdentity {
+                workload_name: workload_name.clone(),
+                managed_by: workload.managed_by.clone(),
+            });
+        }
+        // [impl->swdd~server-rejects-hooks-from-control-interface~1]
+        if let Some(workload_name) = modifier.and_then(|modifier| {
+            managed_by::find_workload_with_changed_hooks(
+                &current_workloads,
+                &new_workloads,
+                modifier,
+            )
+        }) {
+            return Err(UpdateStateError::HooksNotAllowed(workload_name.clone()));
+        }
+
+        self.replace_workloads(&workload_names, new_workloads.workloads);
+        if let Err(error) = self.verify_updated_workloads(&workload_names) {
+            self.replace_workloads(&workload_names, current_workloads.workloads);
+            return Err(error);
+        }
+
+        let desired_state = &self.state.desired_state;
+        let mut added_workloads: Vec<WorkloadSpec> = Vec::new();
+        let mut deleted_workloads: Vec<DeletedWorkload> = Vec::new();
+        for workload_name in &workload_names {
+            // [impl->swdd~server-deploys-workloads-of-active-system-mode~1]
+            // [impl->swdd~server-does-not-deploy-workloads-of-stopped-groups~1]
+            // the modes and groups are not changed by the update
+            let deployed = |workload: &&StoredWorkloadSpec| {
+                is_deployed(desired_state, workload_name, workload)
+            };
+            diff_workload(
+                (
+                    desired_state,
+                    current_workloads
+                        .workloads
+                        .get(workload_name)
+                        .filter(deployed),
+                ),
+                (
+                    desired_state,
+                    desired_state.workloads.get(workload_name).filter(deployed),
+                ),
+                workload_name,
+                &mut added_workloads,
+                &mut deleted_workloads,
+            );
+        }
+
+        if let Some((added_workloads, mut deleted_workloads)) =
+            to_added_and_deleted_workloads(added_workloads, deleted_workloads)
+        {
+            // [impl->swdd~server-state-stores-delete-condition~2]
+            self.delete_graph.insert(&added_workloads);
+
+            // [impl->swdd~server-state-adds-delete-conditions-to-deleted-workload~1]
+            self.delete_graph
+                .apply_delete_conditions_to(&mut deleted_workloads);
+            Ok(Some((added_workloads, deleted_workloads)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn replace_workloads(
+        &mut self,
+        workload_names: &[String],
+        workloads: HashMap<String, StoredWorkloadSpec>,
+    ) {
+        let desired_workloads = &mut self.state.desired_state.workloads;
+        for workload_name in workload_names {
+            desired_workloads.remove(workload_name);
+        }
+        desired_workloads.extend(workloads);
+    }
+
+    // Verifies the updated workloads against the desired state they are already part of.
+    fn verify_updated_workloads(&self, workload_names: &[String]) -> Result<(), UpdateStateError> {
+        let desired_state = &self.state.desired_state;
+        let updated_workloads: Vec<_> = workload_names
+            .iter()
+            .filter_map(|workload_name| desired_state.workloads.get_key_value(workload_name))
+            .collect();
+
+        // [impl->swdd~server-state-rejects-state-exceeding-input-limits~1]
+        let input_limits = InputLimits::configured();
+        input_limits
+            .check_workload_count(desired_state.workloads.len())
+            .map_err(UpdateStateError::LimitExceeded)?;
+        updated_workloads
+            .iter()
+            .try_for_each(|(workload_name, workload)| {
+                input_limits.check_runtime_config_length(workload_name, &workload.runtime_config)
+            })
+            .map_err(UpdateStateError::LimitExceeded)?;
+        verify_workloads(desired_state, &updated_workloads)?;
+
+        // [impl->swdd~server-state-rejects-state-with-cyclic-dependencies~2]
+        // a new cycle contains at least one of the updated workloads
+        if let Some(workload_part_of_cycle) = cycle_check::dfs(
+            desired_state,
+            Some(workload_names.iter().map(String::as_str).collect()),
+        ) {
+            return Err(UpdateStateError::CycleInDependencies(
+                workload_part_of_cycle,
+            ));
+        }
+
+        // [impl->swdd~server-state-warns-about-dependencies-on-unknown-workloads~2]
+        for (workload_name, dependency) in
+            cycle_check::unknown_dependencies(desired_state, Some(workload_names))
+        {
+            log::warn!(
+                "Workload '{}' depends on workload '{}' which is not part of the desired state.",
+                workload_name,
+                dependency
+            );
+        }
+        Ok(())
     }
 }
 
@@ -253,11 +749,18 @@ impl ServerState {
 mod tests {
     use std::collections::HashMap;
 
+    use criterion::{BatchSize, Criterion};
+
     use common::{
-        commands::CompleteStateRequest,
+        commands::{
+            CompleteStateRequest, DependencyGraph, DependencyGraphEdge, DependencyGraphNode,
+            ImpactAnalysis, ImpactedWorkload,
+        },
         objects::{
-            generate_test_stored_workload_spec, generate_test_workload_spec_with_param,
-            CompleteState, DeletedWorkload, State, WorkloadSpec,
+            generate_test_stored_workload_spec, generate_test_workload_spec_with_dependencies,
+            generate_test_workload_spec_with_param, AddCondition, CompleteState, DeletedWorkload,
+            ResourceRequests, Resources, State, WorkloadGroup, WorkloadHooks, WorkloadProfile,
+            WorkloadSpec, WorkloadTemplate,
         },
         test_utils::generate_test_complete_state,
     };
@@ -267,7 +770,7 @@ mod tests {
         workload_state_db::WorkloadStateDB,
     };
 
-    use super::ServerState;
+    use super::{workload_names_of_update_mask, Modifier, ServerState};
     const AGENT_A: &str = "agent_A";
     const AGENT_B: &str = "agent_B";
     const WORKLOAD_NAME_1: &str = "workload_1";
@@ -275,6 +778,8 @@ mod tests {
     const WORKLOAD_NAME_3: &str = "workload_3";
     const WORKLOAD_NAME_4: &str = "workload_4";
     const RUNTIME: &str = "runtime";
+    const CONFIG_NAME: &str = "config_1";
+    const TEMPLATE_NAME: &str = "template_1";
 
     // [utest->swdd~server-provides-interface-get-complete-state~1]
     // [utest->swdd~server-filters-get-complete-state-result~2]
@@ -374,6 +879,84 @@ mod tests {
         assert_eq!(expected_complete_state, complete_state);
     }
 
+    // [utest->swdd~server-provides-impact-analysis~1]
+    #[test]
+    fn utest_server_state_get_impact_analysis_of_desired_state() {
+        let w1 = generate_test_workload_spec_with_dependencies(
+            AGENT_A,
+            WORKLOAD_NAME_1,
+            RUNTIME,
+            HashMap::new(),
+        );
+        let w2 = generate_test_workload_spec_with_dependencies(
+            AGENT_B,
+            WORKLOAD_NAME_2,
+            RUNTIME,
+            HashMap::from([(WORKLOAD_NAME_1.to_string(), AddCondition::AddCondRunning)]),
+        );
+
+        let server_state = ServerState {
+            state: generate_test_complete_state(vec![w1, w2]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            server_state.get_impact_analysis(&[WORKLOAD_NAME_1.to_string()]),
+            ImpactAnalysis {
+                impacted_workloads: vec![ImpactedWorkload {
+                    workload_name: WORKLOAD_NAME_2.to_string(),
+                    agent: AGENT_B.to_string(),
+                    depends_on: vec![WORKLOAD_NAME_1.to_string()],
+                }]
+            }
+        );
+    }
+
+    // [utest->swdd~server-provides-dependency-graph~1]
+    #[test]
+    fn utest_server_state_get_dependency_graph_of_desired_state() {
+        let w1 = generate_test_workload_spec_with_dependencies(
+            AGENT_A,
+            WORKLOAD_NAME_1,
+            RUNTIME,
+            HashMap::new(),
+        );
+        let w2 = generate_test_workload_spec_with_dependencies(
+            AGENT_B,
+            WORKLOAD_NAME_2,
+            RUNTIME,
+            HashMap::from([(WORKLOAD_NAME_1.to_string(), AddCondition::AddCondRunning)]),
+        );
+
+        let server_state = ServerState {
+            state: generate_test_complete_state(vec![w1, w2]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            server_state.get_dependency_graph(&WorkloadStateDB::default()),
+            DependencyGraph {
+                nodes: vec![
+                    DependencyGraphNode {
+                        workload_name: WORKLOAD_NAME_1.to_string(),
+                        agent: AGENT_A.to_string(),
+                        execution_state: None,
+                    },
+                    DependencyGraphNode {
+                        workload_name: WORKLOAD_NAME_2.to_string(),
+                        agent: AGENT_B.to_string(),
+                        execution_state: None,
+                    },
+                ],
+                edges: vec![DependencyGraphEdge {
+                    workload_name: WORKLOAD_NAME_2.to_string(),
+                    dependency: WORKLOAD_NAME_1.to_string(),
+                    condition: AddCondition::AddCondRunning,
+                }],
+            }
+        );
+    }
+
     // [utest->swdd~agent-from-agent-field~1]
     #[test]
     fn utest_server_state_get_workloads_per_agent() {
@@ -415,7 +998,7 @@ mod tests {
         assert_eq!(workloads.len(), 0);
     }
 
-    // [utest->swdd~server-state-rejects-state-with-cyclic-dependencies~1]
+    // [utest->swdd~server-state-rejects-state-with-cyclic-dependencies~2]
     #[test]
     fn utest_server_state_update_state_reject_state_with_cyclic_dependencies() {
         let _ = env_logger::builder().is_test(true).try_init();
@@ -472,6 +1055,51 @@ mod tests {
         assert_eq!(old_state, server_state.state);
     }
 
+    // [utest->swdd~server-state-rejects-state-with-cyclic-dependencies~2]
+    #[test]
+    fn utest_server_state_update_state_reject_cycle_across_agents_via_unchanged_workload() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut workload_1 =
+            generate_test_stored_workload_spec(AGENT_A.to_string(), RUNTIME.to_string());
+        workload_1.dependencies =
+            HashMap::from([(WORKLOAD_NAME_2.to_string(), AddCondition::AddCondRunning)]);
+
+        let old_state = CompleteState {
+            desired_state: State {
+                workloads: HashMap::from([(WORKLOAD_NAME_1.to_string(), workload_1)]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // the new workload on another agent closes the cycle
+        let mut workload_2 =
+            generate_test_stored_workload_spec(AGENT_B.to_string(), RUNTIME.to_string());
+        workload_2.dependencies =
+            HashMap::from([(WORKLOAD_NAME_1.to_string(), AddCondition::AddCondRunning)]);
+        let mut new_state = old_state.clone();
+        new_state
+            .desired_state
+            .workloads
+            .insert(WORKLOAD_NAME_2.to_string(), workload_2);
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().never();
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            delete_graph: delete_graph_mock,
+        };
+
+        let result = server_state.update(new_state, vec![]);
+        assert!(matches!(
+            result,
+            Err(UpdateStateError::CycleInDependencies(_))
+        ));
+        assert_eq!(old_state, server_state.state);
+    }
+
     // [utest->swdd~update-desired-state-empty-update-mask~1]
     #[test]
     fn utest_server_state_update_state_replace_all_if_update_mask_empty() {
@@ -576,60 +1204,172 @@ mod tests {
         assert_eq!(expected, server_state.state);
     }
 
-    // [utest->swdd~update-desired-state-with-update-mask~1]
+    // [utest->swdd~server-state-records-managed-by~1]
     #[test]
-    fn utest_server_state_update_state_remove_workload() {
+    fn utest_server_state_update_by_records_managed_by_of_added_workload() {
         let old_state = generate_test_old_state();
         let update_state = generate_test_update_state();
-        let update_mask = vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_2)];
-
-        let mut expected = old_state.clone();
-        expected
-            .desired_state
-            .workloads
-            .remove(WORKLOAD_NAME_2)
-            .unwrap();
+        let update_mask = vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_4)];
 
         let mut delete_graph_mock = MockDeleteGraph::new();
         delete_graph_mock.expect_insert().once().return_const(());
-
         delete_graph_mock
             .expect_apply_delete_conditions_to()
             .once()
             .return_const(());
 
         let mut server_state = ServerState {
-            state: old_state.clone(),
+            state: old_state,
             delete_graph: delete_graph_mock,
         };
-        server_state.update(update_state, update_mask).unwrap();
+        let modifier = Modifier {
+            identity: "cli".to_owned(),
+            force: false,
+        };
+        server_state
+            .update_by(update_state, update_mask, &modifier)
+            .unwrap();
 
-        assert_eq!(expected, server_state.state);
+        assert_eq!(
+            server_state.state.desired_state.workloads[WORKLOAD_NAME_4].managed_by,
+            "cli"
+        );
+        assert!(server_state.state.desired_state.workloads[WORKLOAD_NAME_1]
+            .managed_by
+            .is_empty());
     }
 
-    // [utest->swdd~update-desired-state-with-update-mask~1]
+    // [utest->swdd~server-protects-managed-workloads~1]
     #[test]
-    fn utest_server_state_update_state_remove_non_existing_workload() {
-        let old_state = generate_test_old_state();
+    fn utest_server_state_update_by_rejects_change_of_workload_managed_by_other_identity() {
+        let mut old_state = generate_test_old_state();
+        old_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .managed_by = "cli".to_owned();
         let update_state = generate_test_update_state();
-        let update_mask = vec!["desiredState.workloads.workload_5".into()];
-
-        let expected = &old_state;
+        let update_mask = vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_1)];
 
         let mut delete_graph_mock = MockDeleteGraph::new();
         delete_graph_mock.expect_insert().never();
-        delete_graph_mock
-            .expect_apply_delete_conditions_to()
-            .never();
 
         let mut server_state = ServerState {
             state: old_state.clone(),
             delete_graph: delete_graph_mock,
         };
-        server_state.update(update_state, update_mask).unwrap();
+        let modifier = Modifier {
+            identity: "cloud-connector".to_owned(),
+            force: false,
+        };
+        let result = server_state.update_by(update_state, update_mask, &modifier);
 
-        assert_eq!(*expected, server_state.state);
-    }
+        assert_eq!(
+            result,
+            Err(UpdateStateError::ManagedByOtherIdentity {
+                workload_name: WORKLOAD_NAME_1.to_owned(),
+                managed_by: "cli".to_owned(),
+            })
+        );
+        assert_eq!(old_state, server_state.state);
+    }
+
+    // [utest->swdd~server-rejects-hooks-from-control-interface~1]
+    #[test]
+    fn utest_server_state_update_by_workload_rejects_hooks() {
+        let old_state = generate_test_old_state();
+        let mut update_state = generate_test_update_state();
+        update_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .hooks = Some(WorkloadHooks {
+            pre_create: Some("rm -rf /".to_owned()),
+            ..Default::default()
+        });
+        let modifier = Modifier {
+            identity: "workload:agent_A/workload_2".to_owned(),
+            force: true,
+        };
+
+        for update_mask in [
+            vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_1)],
+            vec![],
+        ] {
+            let mut delete_graph_mock = MockDeleteGraph::new();
+            delete_graph_mock.expect_insert().never();
+
+            let mut server_state = ServerState {
+                state: old_state.clone(),
+                delete_graph: delete_graph_mock,
+            };
+
+            assert_eq!(
+                server_state.update_by(update_state.clone(), update_mask, &modifier),
+                Err(UpdateStateError::HooksNotAllowed(
+                    WORKLOAD_NAME_1.to_owned()
+                ))
+            );
+            assert_eq!(old_state, server_state.state);
+        }
+    }
+
+    // [utest->swdd~update-desired-state-with-update-mask~1]
+    #[test]
+    fn utest_server_state_update_state_remove_workload() {
+        let old_state = generate_test_old_state();
+        let update_state = generate_test_update_state();
+        let update_mask = vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_2)];
+
+        let mut expected = old_state.clone();
+        expected
+            .desired_state
+            .workloads
+            .remove(WORKLOAD_NAME_2)
+            .unwrap();
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().once().return_const(());
+
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .once()
+            .return_const(());
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            delete_graph: delete_graph_mock,
+        };
+        server_state.update(update_state, update_mask).unwrap();
+
+        assert_eq!(expected, server_state.state);
+    }
+
+    // [utest->swdd~update-desired-state-with-update-mask~1]
+    #[test]
+    fn utest_server_state_update_state_remove_non_existing_workload() {
+        let old_state = generate_test_old_state();
+        let update_state = generate_test_update_state();
+        let update_mask = vec!["desiredState.workloads.workload_5".into()];
+
+        let expected = &old_state;
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().never();
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .never();
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            delete_graph: delete_graph_mock,
+        };
+        server_state.update(update_state, update_mask).unwrap();
+
+        assert_eq!(*expected, server_state.state);
+    }
 
     // [utest->swdd~update-desired-state-with-update-mask~1]
     #[test]
@@ -806,6 +1546,45 @@ mod tests {
         assert_eq!(server_state.state, CompleteState::default());
     }
 
+    // [utest->swdd~server-detects-changed-workload-semantically~1]
+    #[test]
+    fn utest_server_state_update_state_reformatted_workload_not_updated() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut current_complete_state = generate_test_old_state();
+        let mut new_complete_state = current_complete_state.clone();
+        current_complete_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .runtime_config = "image: alpine:latest\ncommandArgs: [sleep, '10']".to_string();
+        new_complete_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .runtime_config =
+            "# same config\ncommandArgs:\n  - sleep\n  - '10'\nimage: alpine:latest\n".to_string();
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().never();
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .never();
+
+        let mut server_state = ServerState {
+            state: current_complete_state,
+            delete_graph: delete_graph_mock,
+        };
+
+        let added_deleted_workloads = server_state
+            .update(new_complete_state.clone(), vec![])
+            .unwrap();
+        assert!(added_deleted_workloads.is_none());
+        assert_eq!(server_state.state, new_complete_state);
+    }
+
     // [utest->swdd~update-desired-state-empty-update-mask~1]
     // [utest->swdd~server-detects-changed-workload~1]
     #[test]
@@ -864,58 +1643,61 @@ mod tests {
         assert_eq!(server_state.state, new_complete_state);
     }
 
-    // [utest->swdd~server-state-stores-delete-condition~1]
-    // [utest->swdd~server-state-adds-delete-conditions-to-deleted-workload~1]
+    fn generate_test_state_with_config(config_value: &str) -> CompleteState {
+        let mut complete_state = generate_test_old_state();
+        complete_state.desired_state.configs = HashMap::from([(
+            CONFIG_NAME.to_string(),
+            HashMap::from([("key".to_string(), config_value.to_string())]),
+        )]);
+        complete_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .configs = vec![CONFIG_NAME.to_string()];
+        complete_state
+    }
+
+    // [utest->swdd~server-state-rejects-state-with-unknown-config-references~1]
     #[test]
-    fn utest_server_state_update_state_store_and_add_delete_conditions() {
-        let workload = generate_test_workload_spec_with_param(
-            AGENT_A.to_string(),
-            WORKLOAD_NAME_1.to_string(),
-            RUNTIME.to_string(),
-        );
+    fn utest_server_state_update_state_reject_state_with_unknown_config_reference() {
+        let old_state = generate_test_old_state();
+        let mut rejected_new_state = generate_test_state_with_config("value");
+        rejected_new_state.desired_state.configs.clear();
 
-        let current_complete_state = CompleteState {
-            desired_state: State {
-                workloads: HashMap::from([(
-                    workload.instance_name.workload_name().to_owned(),
-                    workload.clone().into(),
-                )]),
-                ..Default::default()
-            },
-            ..Default::default()
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().never();
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .never();
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            delete_graph: delete_graph_mock,
         };
 
-        let new_workload = generate_test_workload_spec_with_param(
-            AGENT_B.to_string(),
-            workload.instance_name.workload_name().to_owned(),
-            RUNTIME.to_string(),
+        let result = server_state.update(rejected_new_state, vec![]);
+        assert_eq!(
+            result,
+            Err(UpdateStateError::ResultInvalid(format!(
+                "Workload '{}' references an unknown config: Config '{}' does not exist.",
+                WORKLOAD_NAME_1, CONFIG_NAME
+            )))
         );
+        assert_eq!(server_state.state, old_state);
+    }
 
-        let new_complete_state = CompleteState {
-            desired_state: State {
-                workloads: HashMap::from([(
-                    new_workload.instance_name.workload_name().to_owned(),
-                    new_workload.clone().into(),
-                )]),
-                ..Default::default()
-            },
-            ..Default::default()
-        };
-
-        let update_mask = vec![];
+    // [utest->swdd~server-resolves-config-references-of-workloads~1]
+    // [utest->swdd~server-detects-changed-config-of-workload~1]
+    #[test]
+    fn utest_server_state_update_state_changed_config_updates_workload() {
+        let current_complete_state = generate_test_state_with_config("old value");
+        let new_complete_state = generate_test_state_with_config("new value");
 
         let mut delete_graph_mock = MockDeleteGraph::new();
-        delete_graph_mock
-            .expect_insert()
-            .with(mockall::predicate::eq(vec![new_workload]))
-            .once()
-            .return_const(());
+        delete_graph_mock.expect_insert().once().return_const(());
         delete_graph_mock
             .expect_apply_delete_conditions_to()
-            .with(mockall::predicate::eq(vec![DeletedWorkload {
-                instance_name: workload.instance_name,
-                dependencies: HashMap::new(),
-            }]))
             .once()
             .return_const(());
 
@@ -924,29 +1706,861 @@ mod tests {
             delete_graph: delete_graph_mock,
         };
 
-        let added_deleted_workloads = server_state
-            .update(new_complete_state.clone(), update_mask)
+        let (added_workloads, deleted_workloads) = server_state
+            .update(new_complete_state.clone(), vec![])
+            .unwrap()
             .unwrap();
-        assert!(added_deleted_workloads.is_some());
+
+        assert_eq!(added_workloads.len(), 1);
+        assert_eq!(
+            added_workloads[0].instance_name.workload_name(),
+            WORKLOAD_NAME_1
+        );
+        assert_eq!(
+            added_workloads[0].configs,
+            new_complete_state.desired_state.configs
+        );
+        assert_eq!(deleted_workloads.len(), 1);
+        assert_eq!(server_state.state, new_complete_state);
     }
 
-    // [utest->swdd~server-removes-obsolete-delete-graph-entires~1]
+    // [utest->swdd~server-resolves-config-references-of-workloads~1]
     #[test]
-    fn utest_remove_deleted_workloads_from_delete_graph() {
-        let mut mock_delete_graph = MockDeleteGraph::default();
-        mock_delete_graph
-            .expect_remove_deleted_workloads_from_delete_graph()
-            .once()
-            .return_const(());
+    fn utest_server_state_get_workloads_for_agent_resolves_configs() {
+        let complete_state = generate_test_state_with_config("value");
 
-        let mut server_state = ServerState {
-            delete_graph: mock_delete_graph,
+        let server_state = ServerState {
+            state: complete_state.clone(),
             ..Default::default()
         };
 
-        let workload_states = vec![];
+        let workloads = server_state.get_workloads_for_agent(&AGENT_A.to_string());
+        let workload_1 = workloads
+            .iter()
+            .find(|workload| workload.instance_name.workload_name() == WORKLOAD_NAME_1)
+            .unwrap();
+        assert_eq!(workload_1.configs, complete_state.desired_state.configs);
+    }
 
-        server_state.cleanup_state(&workload_states);
+    fn generate_test_state_with_template(image_tag: &str) -> CompleteState {
+        let mut complete_state = generate_test_old_state();
+        complete_state.desired_state.workload_templates = HashMap::from([(
+            TEMPLATE_NAME.to_string(),
+            WorkloadTemplate {
+                runtime: RUNTIME.to_string(),
+                runtime_config: "image: alpine:{{tag}}".to_string(),
+                parameters: HashMap::from([("tag".to_string(), "latest".to_string())]),
+            },
+        )]);
+        let workload = complete_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap();
+        workload.runtime.clear();
+        workload.runtime_config.clear();
+        workload.template = TEMPLATE_NAME.to_string();
+        workload.template_parameters = HashMap::from([("tag".to_string(), image_tag.to_string())]);
+        complete_state
+    }
+
+    // [utest->swdd~server-state-rejects-state-with-invalid-workload-template~1]
+    #[test]
+    fn utest_server_state_update_state_reject_state_with_unknown_workload_template() {
+        let old_state = generate_test_old_state();
+        let mut rejected_new_state = generate_test_state_with_template("1.0");
+        rejected_new_state.desired_state.workload_templates.clear();
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().never();
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .never();
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            delete_graph: delete_graph_mock,
+        };
+
+        let result = server_state.update(rejected_new_state, vec![]);
+        assert_eq!(
+            result,
+            Err(UpdateStateError::ResultInvalid(format!(
+                "Workload '{}' cannot be expanded: Workload template '{}' does not exist.",
+                WORKLOAD_NAME_1, TEMPLATE_NAME
+            )))
+        );
+        assert_eq!(server_state.state, old_state);
+    }
+
+    // [utest->swdd~server-state-rejects-state-with-invalid-workload-template~1]
+    #[test]
+    fn utest_server_state_update_state_reject_workload_without_runtime() {
+        let old_state = generate_test_old_state();
+        let mut rejected_new_state = old_state.clone();
+        rejected_new_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .runtime
+            .clear();
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().never();
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .never();
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            delete_graph: delete_graph_mock,
+        };
+
+        let result = server_state.update(rejected_new_state, vec![]);
+        assert_eq!(
+            result,
+            Err(UpdateStateError::ResultInvalid(format!(
+                "Workload '{}' has no runtime.",
+                WORKLOAD_NAME_1
+            )))
+        );
+        assert_eq!(server_state.state, old_state);
+    }
+
+    // [utest->swdd~server-state-rejects-state-with-invalid-enabled-if~1]
+    #[test]
+    fn utest_server_state_update_state_reject_state_with_invalid_enabled_if() {
+        let old_state = generate_test_old_state();
+        let mut rejected_new_state = old_state.clone();
+        rejected_new_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .enabled_if = "camera is true".to_string();
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().never();
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .never();
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            delete_graph: delete_graph_mock,
+        };
+
+        let result = server_state.update(rejected_new_state, vec![]);
+        assert_eq!(
+            result,
+            Err(UpdateStateError::ResultInvalid(format!(
+                "Workload '{}': Invalid enabledIf expression 'camera is true': Invalid attribute name 'camera is true'.",
+                WORKLOAD_NAME_1
+            )))
+        );
+        assert_eq!(server_state.state, old_state);
+    }
+
+    // [utest->swdd~server-state-rejects-state-with-invalid-schedules~1]
+    #[test]
+    fn utest_server_state_update_state_reject_state_with_invalid_schedule() {
+        let old_state = generate_test_old_state();
+        let mut rejected_new_state = old_state.clone();
+        rejected_new_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .schedule = Some("0 25 * * *".to_string());
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().never();
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .never();
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            delete_graph: delete_graph_mock,
+        };
+
+        let result = server_state.update(rejected_new_state, vec![]);
+        assert_eq!(
+            result,
+            Err(UpdateStateError::ResultInvalid(format!(
+                "Workload '{}': Invalid schedule '0 25 * * *': '25' is not a value between 0 and 23.",
+                WORKLOAD_NAME_1
+            )))
+        );
+        assert_eq!(server_state.state, old_state);
+    }
+
+    // [utest->swdd~server-state-rejects-state-with-invalid-resource-requests~1]
+    #[test]
+    fn utest_server_state_update_state_reject_state_with_invalid_resource_requests() {
+        let old_state = generate_test_old_state();
+        let mut rejected_new_state = old_state.clone();
+        rejected_new_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .resources = Some(Resources {
+            requests: ResourceRequests {
+                cpu: Some("one".to_string()),
+                memory: None,
+            },
+        });
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().never();
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .never();
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            delete_graph: delete_graph_mock,
+        };
+
+        let result = server_state.update(rejected_new_state, vec![]);
+        assert_eq!(
+            result,
+            Err(UpdateStateError::ResultInvalid(format!(
+                "Workload '{}': Invalid cpu request 'one'.",
+                WORKLOAD_NAME_1
+            )))
+        );
+        assert_eq!(server_state.state, old_state);
+    }
+
+    // [utest->swdd~server-state-rejects-state-with-unknown-system-modes~1]
+    #[test]
+    fn utest_server_state_update_state_reject_state_with_unknown_system_mode() {
+        let old_state = generate_test_old_state();
+        let mut rejected_new_state = old_state.clone();
+        rejected_new_state.desired_state.modes = vec!["parked".to_string()];
+        rejected_new_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .modes = vec!["driving".to_string()];
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().never();
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            delete_graph: delete_graph_mock,
+        };
+
+        assert_eq!(
+            server_state.update(rejected_new_state.clone(), vec![]),
+            Err(UpdateStateError::ResultInvalid(format!(
+                "Workload '{}' runs in the unknown mode 'driving'.",
+                WORKLOAD_NAME_1
+            )))
+        );
+
+        rejected_new_state.desired_state.workloads.clear();
+        rejected_new_state.desired_state.active_mode = "driving".to_string();
+        assert_eq!(
+            server_state.update(rejected_new_state, vec![]),
+            Err(UpdateStateError::ResultInvalid(
+                "The active mode 'driving' is not one of the modes of the state.".to_string()
+            ))
+        );
+        assert_eq!(server_state.state, old_state);
+    }
+
+    // [utest->swdd~server-state-rejects-state-exceeding-input-limits~1]
+    // [utest->swdd~common-configurable-input-limits~1]
+    #[test]
+    fn utest_server_state_update_state_accepts_long_runtime_config_without_configured_limit() {
+        let old_state = generate_test_old_state();
+        let mut new_state = old_state.clone();
+        new_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .runtime_config = "x".repeat(1024 * 1024);
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().once().return_const(());
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .once()
+            .return_const(());
+
+        let mut server_state = ServerState {
+            state: old_state,
+            delete_graph: delete_graph_mock,
+        };
+
+        server_state.update(new_state.clone(), vec![]).unwrap();
+
+        assert_eq!(server_state.state, new_state);
+    }
+
+    // [utest->swdd~server-deploys-workloads-of-active-system-mode~1]
+    #[test]
+    fn utest_server_state_update_state_switching_mode_starts_and_stops_workload_delta() {
+        let mut current_complete_state = generate_test_old_state();
+        let desired_state = &mut current_complete_state.desired_state;
+        desired_state.modes = vec!["parked".to_string(), "driving".to_string()];
+        desired_state.active_mode = "parked".to_string();
+        // workload_1 runs in every mode
+        desired_state.workloads.get_mut(WORKLOAD_NAME_2).unwrap().modes =
+            vec!["parked".to_string()];
+        desired_state.workloads.get_mut(WORKLOAD_NAME_3).unwrap().modes =
+            vec!["driving".to_string()];
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().once().return_const(());
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .once()
+            .return_const(());
+
+        let server_state_with_mode = ServerState {
+            state: current_complete_state.clone(),
+            ..Default::default()
+        };
+        assert_eq!(
+            server_state_with_mode
+                .get_workloads_for_agent(&AGENT_B.to_string())
+                .len(),
+            0
+        );
+
+        let mut server_state = ServerState {
+            state: current_complete_state.clone(),
+            delete_graph: delete_graph_mock,
+        };
+
+        let mut new_complete_state = current_complete_state.clone();
+        new_complete_state.desired_state.active_mode = "driving".to_string();
+        let (added_workloads, deleted_workloads) = server_state
+            .update(
+                new_complete_state.clone(),
+                vec!["desiredState.activeMode".to_string()],
+            )
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            added_workloads
+                .iter()
+                .map(|workload| workload.instance_name.workload_name())
+                .collect::<Vec<_>>(),
+            vec![WORKLOAD_NAME_3]
+        );
+        assert_eq!(
+            deleted_workloads
+                .iter()
+                .map(|workload| workload.instance_name.workload_name())
+                .collect::<Vec<_>>(),
+            vec![WORKLOAD_NAME_2]
+        );
+        assert_eq!(server_state.state, new_complete_state);
+    }
+
+    // [utest->swdd~server-expands-workload-templates~1]
+    #[test]
+    fn utest_server_state_update_state_switching_profile_recreates_overridden_workload() {
+        let mut current_complete_state = generate_test_old_state();
+        current_complete_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .profiles = HashMap::from([(
+            "dev".to_string(),
+            WorkloadProfile {
+                runtime_config: Some("image: debug".to_string()),
+                ..Default::default()
+            },
+        )]);
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().once().return_const(());
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .once()
+            .return_const(());
+
+        let mut server_state = ServerState {
+            state: current_complete_state.clone(),
+            delete_graph: delete_graph_mock,
+        };
+
+        let mut new_complete_state = current_complete_state.clone();
+        new_complete_state.desired_state.active_profile = "dev".to_string();
+        let (added_workloads, deleted_workloads) = server_state
+            .update(
+                new_complete_state.clone(),
+                vec!["desiredState.activeProfile".to_string()],
+            )
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(added_workloads.len(), 1);
+        assert_eq!(
+            added_workloads[0].instance_name.workload_name(),
+            WORKLOAD_NAME_1
+        );
+        assert_eq!(added_workloads[0].runtime_config, "image: debug");
+        assert_eq!(
+            deleted_workloads
+                .iter()
+                .map(|workload| workload.instance_name.workload_name())
+                .collect::<Vec<_>>(),
+            vec![WORKLOAD_NAME_1]
+        );
+        assert_eq!(server_state.state, new_complete_state);
+    }
+
+    // [utest->swdd~server-does-not-deploy-workloads-of-stopped-groups~1]
+    #[test]
+    fn utest_server_state_update_state_stopping_group_deletes_members() {
+        let mut current_complete_state = generate_test_old_state();
+        current_complete_state.desired_state.workload_groups = HashMap::from([(
+            "group".to_string(),
+            WorkloadGroup {
+                workloads: vec![WORKLOAD_NAME_1.to_string(), WORKLOAD_NAME_2.to_string()],
+                ..Default::default()
+            },
+        )]);
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().once().return_const(());
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .once()
+            .return_const(());
+
+        let mut server_state = ServerState {
+            state: current_complete_state.clone(),
+            delete_graph: delete_graph_mock,
+        };
+
+        let mut new_complete_state = current_complete_state.clone();
+        new_complete_state
+            .desired_state
+            .workload_groups
+            .get_mut("group")
+            .unwrap()
+            .stopped = true;
+        let (added_workloads, deleted_workloads) = server_state
+            .update(
+                new_complete_state.clone(),
+                vec!["desiredState.workloadGroups.group.stopped".to_string()],
+            )
+            .unwrap()
+            .unwrap();
+
+        assert!(added_workloads.is_empty());
+        let mut deleted_workload_names: Vec<&str> = deleted_workloads
+            .iter()
+            .map(|workload| workload.instance_name.workload_name())
+            .collect();
+        deleted_workload_names.sort();
+        assert_eq!(
+            deleted_workload_names,
+            vec![WORKLOAD_NAME_1, WORKLOAD_NAME_2]
+        );
+        assert_eq!(server_state.state, new_complete_state);
+        assert!(server_state
+            .get_workloads_for_agent(&AGENT_A.to_string())
+            .is_empty());
+    }
+
+    // [utest->swdd~server-expands-workload-templates~1]
+    #[test]
+    fn utest_server_state_update_state_changed_template_parameter_updates_workload() {
+        let current_complete_state = generate_test_state_with_template("1.0");
+        let new_complete_state = generate_test_state_with_template("2.0");
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().once().return_const(());
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .once()
+            .return_const(());
+
+        let mut server_state = ServerState {
+            state: current_complete_state.clone(),
+            delete_graph: delete_graph_mock,
+        };
+
+        let (added_workloads, deleted_workloads) = server_state
+            .update(new_complete_state.clone(), vec![])
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(added_workloads.len(), 1);
+        assert_eq!(
+            added_workloads[0].instance_name.workload_name(),
+            WORKLOAD_NAME_1
+        );
+        assert_eq!(added_workloads[0].runtime, RUNTIME);
+        assert_eq!(added_workloads[0].runtime_config, "image: alpine:2.0");
+
+        let expanded_old_workload = current_complete_state
+            .desired_state
+            .expand_workload(&current_complete_state.desired_state.workloads[WORKLOAD_NAME_1])
+            .unwrap();
+        assert_eq!(
+            deleted_workloads,
+            vec![DeletedWorkload {
+                instance_name: (WORKLOAD_NAME_1.to_string(), &expanded_old_workload).into(),
+                dependencies: HashMap::new(),
+            }]
+        );
+        assert_eq!(server_state.state, new_complete_state);
+    }
+
+    // [utest->swdd~server-expands-workload-templates~1]
+    #[test]
+    fn utest_server_state_update_state_unchanged_expanded_workload_is_not_updated() {
+        let current_complete_state = generate_test_state_with_template("1.0");
+        let mut new_complete_state = current_complete_state.clone();
+        let workload = new_complete_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap();
+        *workload = current_complete_state
+            .desired_state
+            .expand_workload(workload)
+            .unwrap();
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().never();
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .never();
+
+        let mut server_state = ServerState {
+            state: current_complete_state,
+            delete_graph: delete_graph_mock,
+        };
+
+        let added_deleted_workloads = server_state
+            .update(new_complete_state.clone(), vec![])
+            .unwrap();
+        assert!(added_deleted_workloads.is_none());
+        assert_eq!(server_state.state, new_complete_state);
+    }
+
+    // [utest->swdd~server-expands-workload-templates~1]
+    #[test]
+    fn utest_server_state_get_workloads_for_agent_expands_templates() {
+        let complete_state = generate_test_state_with_template("1.0");
+
+        let server_state = ServerState {
+            state: complete_state,
+            ..Default::default()
+        };
+
+        let workloads = server_state.get_workloads_for_agent(&AGENT_A.to_string());
+        let workload_1 = workloads
+            .iter()
+            .find(|workload| workload.instance_name.workload_name() == WORKLOAD_NAME_1)
+            .unwrap();
+        assert_eq!(workload_1.runtime, RUNTIME);
+        assert_eq!(workload_1.runtime_config, "image: alpine:1.0");
+    }
+
+    #[test]
+    fn utest_server_state_update_state_stores_unreferenced_configs() {
+        let current_complete_state = generate_test_old_state();
+        let mut new_complete_state = current_complete_state.clone();
+        new_complete_state.desired_state.configs = HashMap::from([(
+            CONFIG_NAME.to_string(),
+            HashMap::from([("key".to_string(), "value".to_string())]),
+        )]);
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().never();
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .never();
+
+        let mut server_state = ServerState {
+            state: current_complete_state,
+            delete_graph: delete_graph_mock,
+        };
+
+        let added_deleted_workloads = server_state
+            .update(new_complete_state.clone(), vec![])
+            .unwrap();
+        assert!(added_deleted_workloads.is_none());
+        assert_eq!(server_state.state, new_complete_state);
+    }
+
+    // [utest->swdd~server-state-stores-delete-condition~2]
+    // [utest->swdd~server-state-adds-delete-conditions-to-deleted-workload~1]
+    #[test]
+    fn utest_server_state_update_state_store_and_add_delete_conditions() {
+        let workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_string(),
+            WORKLOAD_NAME_1.to_string(),
+            RUNTIME.to_string(),
+        );
+
+        let current_complete_state = CompleteState {
+            desired_state: State {
+                workloads: HashMap::from([(
+                    workload.instance_name.workload_name().to_owned(),
+                    workload.clone().into(),
+                )]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let new_workload = generate_test_workload_spec_with_param(
+            AGENT_B.to_string(),
+            workload.instance_name.workload_name().to_owned(),
+            RUNTIME.to_string(),
+        );
+
+        let new_complete_state = CompleteState {
+            desired_state: State {
+                workloads: HashMap::from([(
+                    new_workload.instance_name.workload_name().to_owned(),
+                    new_workload.clone().into(),
+                )]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let update_mask = vec![];
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock
+            .expect_insert()
+            .with(mockall::predicate::eq(vec![new_workload]))
+            .once()
+            .return_const(());
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .with(mockall::predicate::eq(vec![DeletedWorkload {
+                instance_name: workload.instance_name,
+                dependencies: HashMap::new(),
+            }]))
+            .once()
+            .return_const(());
+
+        let mut server_state = ServerState {
+            state: current_complete_state,
+            delete_graph: delete_graph_mock,
+        };
+
+        let added_deleted_workloads = server_state
+            .update(new_complete_state.clone(), update_mask)
+            .unwrap();
+        assert!(added_deleted_workloads.is_some());
+    }
+
+    // [utest->swdd~server-removes-obsolete-delete-graph-entires~1]
+    #[test]
+    fn utest_remove_deleted_workloads_from_delete_graph() {
+        let mut mock_delete_graph = MockDeleteGraph::default();
+        mock_delete_graph
+            .expect_remove_deleted_workloads_from_delete_graph()
+            .once()
+            .return_const(());
+
+        let mut server_state = ServerState {
+            delete_graph: mock_delete_graph,
+            ..Default::default()
+        };
+
+        let workload_states = vec![];
+
+        server_state.cleanup_state(&workload_states);
+    }
+
+    // [utest->swdd~server-state-updates-masked-workloads-incrementally~1]
+    #[test]
+    fn utest_workload_names_of_update_mask() {
+        assert_eq!(
+            workload_names_of_update_mask(&[
+                "desiredState.workloads.workload_2".to_string(),
+                "desiredState.workloads.workload_1".to_string(),
+                "desiredState.workloads.workload_2".to_string(),
+            ]),
+            Some(vec![
+                WORKLOAD_NAME_1.to_string(),
+                WORKLOAD_NAME_2.to_string()
+            ])
+        );
+        for update_mask in [
+            vec![],
+            vec!["desiredState.workloads".to_string()],
+            vec!["desiredState.workloads.workload_1.agent".to_string()],
+            vec![
+                "desiredState.workloads.workload_1".to_string(),
+                "desiredState.configs.config_1".to_string(),
+            ],
+        ] {
+            assert_eq!(workload_names_of_update_mask(&update_mask), None);
+        }
+    }
+
+    // [utest->swdd~server-state-updates-masked-workloads-incrementally~1]
+    #[test]
+    fn utest_server_state_update_state_replaces_only_masked_workloads() {
+        let old_state = generate_test_old_state();
+        let update_state = generate_test_update_state();
+        let update_mask = vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_3)];
+
+        let mut expected = old_state.clone();
+        expected.desired_state.workloads.insert(
+            WORKLOAD_NAME_3.to_string(),
+            update_state.desired_state.workloads[WORKLOAD_NAME_3].clone(),
+        );
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().once().return_const(());
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .once()
+            .return_const(());
+
+        let mut server_state = ServerState {
+            state: old_state,
+            delete_graph: delete_graph_mock,
+        };
+        let (added_workloads, deleted_workloads) = server_state
+            .update(update_state, update_mask)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            added_workloads
+                .iter()
+                .map(|workload| workload.instance_name.workload_name())
+                .collect::<Vec<_>>(),
+            vec![WORKLOAD_NAME_3]
+        );
+        assert_eq!(
+            deleted_workloads
+                .iter()
+                .map(|workload| workload.instance_name.workload_name())
+                .collect::<Vec<_>>(),
+            vec![WORKLOAD_NAME_3]
+        );
+        assert_eq!(server_state.state, expected);
+    }
+
+    // [utest->swdd~server-state-updates-masked-workloads-incrementally~1]
+    // [utest->swdd~server-state-rejects-state-with-unknown-config-references~1]
+    #[test]
+    fn utest_server_state_update_state_restores_workloads_of_rejected_workload_update() {
+        let old_state = generate_test_state_with_config("value");
+        let mut rejected_new_state = old_state.clone();
+        rejected_new_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .configs = vec!["unknown".to_string()];
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().never();
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .never();
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            delete_graph: delete_graph_mock,
+        };
+
+        let result = server_state.update(
+            rejected_new_state,
+            vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_1)],
+        );
+        assert_eq!(
+            result,
+            Err(UpdateStateError::ResultInvalid(format!(
+                "Workload '{}' references an unknown config: Config 'unknown' does not exist.",
+                WORKLOAD_NAME_1
+            )))
+        );
+        assert_eq!(server_state.state, old_state);
+    }
+
+    fn generate_test_state_with_workloads(workload_count: usize) -> CompleteState {
+        generate_test_complete_state(
+            (0..workload_count)
+                .map(|index| {
+                    generate_test_workload_spec_with_param(
+                        format!("agent_{}", index % 100),
+                        format!("workload_{index}"),
+                        RUNTIME.into(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    // Benchmarks the update of a single workload in states with many workloads, run with
+    // `cargo test -p ank-server --release -- --ignored bench_`
+    #[test]
+    #[ignore]
+    fn bench_server_state_update_single_workload() {
+        let mut criterion = Criterion::default().sample_size(20);
+        for workload_count in [1_000, 10_000] {
+            let mut delete_graph_mock = MockDeleteGraph::new();
+            delete_graph_mock.expect_insert().return_const(());
+            delete_graph_mock
+                .expect_apply_delete_conditions_to()
+                .return_const(());
+            let mut server_state = ServerState {
+                state: generate_test_state_with_workloads(workload_count),
+                delete_graph: delete_graph_mock,
+            };
+
+            for update_mask in [
+                "desiredState.workloads.workload_0",
+                "desiredState.workloads.workload_0.runtimeConfig",
+            ] {
+                let mut image_tag = 0;
+                criterion.bench_function(
+                    &format!("update '{update_mask}' of {workload_count} workloads"),
+                    |bencher| {
+                        bencher.iter_batched(
+                            || {
+                                image_tag += 1;
+                                let mut update_state = generate_test_state_with_workloads(1);
+                                update_state
+                                    .desired_state
+                                    .workloads
+                                    .get_mut("workload_0")
+                                    .unwrap()
+                                    .runtime_config = format!("image: alpine:{image_tag}");
+                                update_state
+                            },
+                            |update_state| {
+                                server_state
+                                    .update(update_state, vec![update_mask.to_string()])
+                                    .unwrap()
+                            },
+                            BatchSize::SmallInput,
+                        )
+                    },
+                );
+            }
+        }
+        criterion.final_summary();
     }
 
     fn generate_test_old_state() -> CompleteState {
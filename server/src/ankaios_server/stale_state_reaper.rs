@@ -0,0 +1,144 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use common::{objects::WorkloadState, WORKLOAD_STATE_REFRESH_INTERVAL_MS};
+use tokio::time::{interval, Interval, MissedTickBehavior};
+
+use crate::workload_state_db::WorkloadStateDB;
+
+// The workload states are checked twice per timeout, thus a stale state is detected
+// at most half of the timeout late.
+const CHECKS_PER_TIMEOUT: u32 = 2;
+const MIN_CHECK_INTERVAL: Duration = Duration::from_millis(1);
+
+// [impl->swdd~server-rejects-stale-state-timeout-within-refresh-interval~1]
+pub fn validate_stale_state_timeout(timeout: Duration) -> Result<Duration, String> {
+    let refresh_interval = Duration::from_millis(WORKLOAD_STATE_REFRESH_INTERVAL_MS);
+    if timeout <= refresh_interval {
+        return Err(format!(
+            "The stale state timeout of {}s must be longer than the {}s in which the agents refresh the workload states",
+            timeout.as_secs_f64(),
+            refresh_interval.as_secs_f64()
+        ));
+    }
+    Ok(timeout)
+}
+
+#[derive(Default)]
+pub struct StaleStateReaper {
+    check: Option<(Duration, Interval)>,
+}
+
+impl StaleStateReaper {
+    pub fn new(timeout: Duration) -> Self {
+        let mut check_interval = interval((timeout / CHECKS_PER_TIMEOUT).max(MIN_CHECK_INTERVAL));
+        check_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        StaleStateReaper {
+            check: Some((timeout, check_interval)),
+        }
+    }
+
+    // Resolves when the workload states shall be checked.
+    // Never resolves if the reaper is not enabled.
+    pub async fn check_due(&mut self) {
+        match self.check.as_mut() {
+            Some((_, check_interval)) => {
+                check_interval.tick().await;
+            }
+            None => std::future::pending().await,
+        }
+    }
+
+    // [impl->swdd~server-reaps-stale-workload-states~2]
+    pub fn reap(&self, workload_state_db: &mut WorkloadStateDB) -> Vec<WorkloadState> {
+        match &self.check {
+            Some((timeout, _)) => workload_state_db.mark_stale_states(*timeout),
+            None => Vec::new(),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common::objects::{generate_test_workload_state_with_agent, ExecutionState};
+
+    use super::{validate_stale_state_timeout, StaleStateReaper};
+    use crate::workload_state_db::WorkloadStateDB;
+
+    const AGENT_A: &str = "agent_A";
+    const WORKLOAD_NAME_1: &str = "workload_1";
+
+    // [utest->swdd~server-reaps-stale-workload-states~2]
+    #[tokio::test]
+    async fn utest_stale_state_reaper_disabled_does_not_reap() {
+        let mut workload_state_db = WorkloadStateDB::default();
+        workload_state_db.process_new_states(vec![generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::running(),
+        )]);
+
+        let reaper = StaleStateReaper::default();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert!(reaper.reap(&mut workload_state_db).is_empty());
+    }
+
+    // [utest->swdd~server-reaps-stale-workload-states~2]
+    #[tokio::test]
+    async fn utest_stale_state_reaper_reaps_outdated_states() {
+        let mut workload_state_db = WorkloadStateDB::default();
+        workload_state_db.process_new_states(vec![generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::running(),
+        )]);
+
+        let mut reaper = StaleStateReaper::new(Duration::from_millis(1));
+        reaper.check_due().await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        reaper.check_due().await;
+
+        assert_eq!(
+            reaper.reap(&mut workload_state_db),
+            vec![generate_test_workload_state_with_agent(
+                WORKLOAD_NAME_1,
+                AGENT_A,
+                ExecutionState::stale(),
+            )]
+        );
+    }
+
+    // [utest->swdd~server-rejects-stale-state-timeout-within-refresh-interval~1]
+    #[test]
+    fn utest_validate_stale_state_timeout_rejects_timeouts_within_the_refresh_interval() {
+        assert!(validate_stale_state_timeout(Duration::from_secs(5)).is_err());
+        assert!(validate_stale_state_timeout(Duration::from_secs(10)).is_err());
+        assert_eq!(
+            validate_stale_state_timeout(Duration::from_secs(11)),
+            Ok(Duration::from_secs(11))
+        );
+    }
+}
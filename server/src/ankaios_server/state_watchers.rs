@@ -0,0 +1,208 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use common::commands::CompleteStateRequest;
+use common::objects::CompleteState;
+
+struct StateWatcher {
+    trace_id: String,
+    field_mask: Vec<String>,
+    last_state: CompleteState,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct StateChange {
+    pub request_id: String,
+    pub trace_id: String,
+    pub state: Result<CompleteState, String>,
+}
+
+// The watchers are identified by the request id of the watch request,
+// which starts with the name of the agent or connection the request came from.
+#[derive(Default)]
+pub struct StateWatchers {
+    watchers: BTreeMap<String, StateWatcher>,
+}
+
+impl StateWatchers {
+    pub fn is_empty(&self) -> bool {
+        self.watchers.is_empty()
+    }
+
+    pub fn add(
+        &mut self,
+        request_id: String,
+        trace_id: String,
+        field_mask: Vec<String>,
+        state: CompleteState,
+    ) {
+        self.watchers.insert(
+            request_id,
+            StateWatcher {
+                trace_id,
+                field_mask,
+                last_state: state,
+            },
+        );
+    }
+
+    pub fn remove(&mut self, request_id: &str) -> bool {
+        self.watchers.remove(request_id).is_some()
+    }
+
+    // [impl->swdd~server-removes-state-watchers-of-disconnected-agent~1]
+    pub fn remove_of_agent(&mut self, agent_name: &str) {
+        let prefix = format!("{agent_name}@");
+        self.watchers
+            .retain(|request_id, _| !request_id.starts_with(&prefix));
+    }
+
+    // [impl->swdd~server-notifies-state-watchers-on-change~1]
+    // Returns the changes of the watchers whose watched state differs from the last sent one.
+    // A watcher whose state cannot be provided anymore is removed and gets the error.
+    pub fn changed_states(
+        &mut self,
+        mut get_state: impl FnMut(&CompleteStateRequest) -> Result<CompleteState, String>,
+    ) -> Vec<StateChange> {
+        let mut changed_states = Vec::new();
+        self.watchers.retain(|request_id, watcher| {
+            match get_state(&CompleteStateRequest {
+                field_mask: watcher.field_mask.clone(),
+            }) {
+                Ok(state) if state == watcher.last_state => true,
+                Ok(state) => {
+                    watcher.last_state = state.clone();
+                    changed_states.push(StateChange {
+                        request_id: request_id.clone(),
+                        trace_id: watcher.trace_id.clone(),
+                        state: Ok(state),
+                    });
+                    true
+                }
+                Err(error) => {
+                    changed_states.push(StateChange {
+                        request_id: request_id.clone(),
+                        trace_id: watcher.trace_id.clone(),
+                        state: Err(error),
+                    });
+                    false
+                }
+            }
+        });
+        changed_states
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::{generate_test_workload_spec_with_param, CompleteState};
+    use common::test_utils::generate_test_complete_state;
+
+    use super::{StateChange, StateWatchers};
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+    const FIELD_MASK: &str = "desiredState";
+    const TRACE_ID: &str = "trace_id";
+
+    // [utest->swdd~server-notifies-state-watchers-on-change~1]
+    #[test]
+    fn utest_changed_states_returns_only_changed_states() {
+        let mut state_watchers = StateWatchers::default();
+        state_watchers.add(
+            "agent_A@workload_1@id".to_string(),
+            TRACE_ID.to_string(),
+            vec![FIELD_MASK.to_string()],
+            CompleteState::default(),
+        );
+
+        let new_state = generate_test_complete_state(vec![generate_test_workload_spec_with_param(
+            AGENT_A.to_string(),
+            "workload_1".to_string(),
+            "runtime".to_string(),
+        )]);
+        let changed_states = state_watchers.changed_states(|request| {
+            assert_eq!(request.field_mask, vec![FIELD_MASK.to_string()]);
+            Ok(new_state.clone())
+        });
+        assert_eq!(
+            changed_states,
+            vec![StateChange {
+                request_id: "agent_A@workload_1@id".to_string(),
+                trace_id: TRACE_ID.to_string(),
+                state: Ok(new_state.clone()),
+            }]
+        );
+
+        assert!(state_watchers
+            .changed_states(|_| Ok(new_state.clone()))
+            .is_empty());
+    }
+
+    // [utest->swdd~server-notifies-state-watchers-on-change~1]
+    #[test]
+    fn utest_changed_states_removes_watcher_on_error() {
+        let mut state_watchers = StateWatchers::default();
+        state_watchers.add(
+            "cli-conn@id".to_string(),
+            TRACE_ID.to_string(),
+            vec![],
+            CompleteState::default(),
+        );
+
+        assert_eq!(
+            state_watchers.changed_states(|_| Err("error".to_string())),
+            vec![StateChange {
+                request_id: "cli-conn@id".to_string(),
+                trace_id: TRACE_ID.to_string(),
+                state: Err("error".to_string()),
+            }]
+        );
+        assert!(state_watchers.is_empty());
+    }
+
+    // [utest->swdd~server-removes-state-watchers-of-disconnected-agent~1]
+    #[test]
+    fn utest_remove_of_agent_keeps_watchers_of_other_agents() {
+        let mut state_watchers = StateWatchers::default();
+        state_watchers.add(
+            format!("{AGENT_A}@workload_1@id"),
+            TRACE_ID.to_string(),
+            vec![],
+            CompleteState::default(),
+        );
+        state_watchers.add(
+            format!("{AGENT_B}@workload_1@id"),
+            TRACE_ID.to_string(),
+            vec![],
+            CompleteState::default(),
+        );
+
+        state_watchers.remove_of_agent(AGENT_A);
+
+        assert!(!state_watchers.remove(&format!("{AGENT_A}@workload_1@id")));
+        assert!(state_watchers.remove(&format!("{AGENT_B}@workload_1@id")));
+        assert!(state_watchers.is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use common::objects::{CompleteState, WorkloadInstanceName};
+use tokio::time::Instant;
+
+use crate::workload_state_db::WorkloadStateDB;
+
+struct TrackedUpdate {
+    deadline: Instant,
+    trace_id: String,
+    previous_state: CompleteState,
+    update_mask: Vec<String>,
+    added_workloads: Vec<WorkloadInstanceName>,
+}
+
+pub struct Rollback {
+    pub trace_id: String,
+    pub previous_state: CompleteState,
+    pub update_mask: Vec<String>,
+    pub pending_workloads: Vec<WorkloadInstanceName>,
+}
+
+// Tracks the deadline of the last update requesting a rollback on an exceeded deadline.
+// A later update replaces the tracked update, as a rollback would revert it as well.
+// [impl->swdd~server-rolls-back-update-on-exceeded-deadline~1]
+#[derive(Default)]
+pub struct UpdateDeadlines {
+    tracked_update: Option<TrackedUpdate>,
+}
+
+impl UpdateDeadlines {
+    pub fn track(
+        &mut self,
+        deadline: Duration,
+        trace_id: String,
+        previous_state: CompleteState,
+        update_mask: Vec<String>,
+        added_workloads: Vec<WorkloadInstanceName>,
+    ) {
+        self.tracked_update = Some(TrackedUpdate {
+            deadline: Instant::now() + deadline,
+            trace_id,
+            previous_state,
+            update_mask,
+            added_workloads,
+        });
+    }
+
+    pub fn cancel(&mut self) {
+        if let Some(tracked_update) = self.tracked_update.take() {
+            log::info!(
+                "The rollback of the update with trace id '{}' is canceled by a newer update.",
+                tracked_update.trace_id
+            );
+        }
+    }
+
+    // Resolves when the deadline of the tracked update has expired.
+    // Never resolves if no update is tracked.
+    pub async fn expired(&self) {
+        match &self.tracked_update {
+            Some(tracked_update) => tokio::time::sleep_until(tracked_update.deadline).await,
+            None => std::future::pending().await,
+        }
+    }
+
+    // Ends the tracking and returns the rollback if an added workload is still pending.
+    pub fn take_rollback(&mut self, workload_state_db: &WorkloadStateDB) -> Option<Rollback> {
+        let tracked_update = self.tracked_update.take()?;
+        let pending_workloads: Vec<WorkloadInstanceName> = tracked_update
+            .added_workloads
+            .into_iter()
+            .filter(|instance_name| {
+                workload_state_db
+                    .get_execution_state(instance_name)
+                    .is_some_and(|execution_state| execution_state.is_pending())
+            })
+            .collect();
+
+        if pending_workloads.is_empty() {
+            return None;
+        }
+
+        Some(Rollback {
+            trace_id: tracked_update.trace_id,
+            previous_state: tracked_update.previous_state,
+            update_mask: tracked_update.update_mask,
+            pending_workloads,
+        })
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common::objects::{
+        generate_test_workload_state_with_agent, CompleteState, ExecutionState,
+        WorkloadInstanceName,
+    };
+
+    use super::UpdateDeadlines;
+    use crate::workload_state_db::WorkloadStateDB;
+
+    const AGENT_A: &str = "agent_A";
+    const TRACE_ID: &str = "trace_id";
+    const WORKLOAD_NAME_1: &str = "workload_1";
+    const WORKLOAD_NAME_2: &str = "workload_2";
+
+    fn instance_name(workload_name: &str) -> WorkloadInstanceName {
+        generate_test_workload_state_with_agent(workload_name, AGENT_A, ExecutionState::running())
+            .instance_name
+    }
+
+    // [utest->swdd~server-rolls-back-update-on-exceeded-deadline~1]
+    #[tokio::test]
+    async fn utest_update_deadlines_returns_rollback_with_pending_workloads() {
+        let mut workload_state_db = WorkloadStateDB::default();
+        workload_state_db.process_new_states(vec![
+            generate_test_workload_state_with_agent(
+                WORKLOAD_NAME_1,
+                AGENT_A,
+                ExecutionState::running(),
+            ),
+            generate_test_workload_state_with_agent(
+                WORKLOAD_NAME_2,
+                AGENT_A,
+                ExecutionState::deadline_exceeded(),
+            ),
+        ]);
+
+        let mut update_deadlines = UpdateDeadlines::default();
+        update_deadlines.track(
+            Duration::from_millis(1),
+            TRACE_ID.to_string(),
+            CompleteState::default(),
+            vec!["desiredState.workloads".to_string()],
+            vec![instance_name(WORKLOAD_NAME_1), instance_name(WORKLOAD_NAME_2)],
+        );
+        update_deadlines.expired().await;
+
+        let rollback = update_deadlines.take_rollback(&workload_state_db).unwrap();
+        assert_eq!(rollback.trace_id, TRACE_ID);
+        assert_eq!(rollback.update_mask, vec!["desiredState.workloads"]);
+        assert_eq!(
+            rollback.pending_workloads,
+            vec![instance_name(WORKLOAD_NAME_2)]
+        );
+        assert!(update_deadlines.take_rollback(&workload_state_db).is_none());
+    }
+
+    // [utest->swdd~server-rolls-back-update-on-exceeded-deadline~1]
+    #[test]
+    fn utest_update_deadlines_no_rollback_if_started_or_canceled() {
+        let mut workload_state_db = WorkloadStateDB::default();
+        workload_state_db.process_new_states(vec![generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::succeeded(),
+        )]);
+
+        let mut update_deadlines = UpdateDeadlines::default();
+        update_deadlines.track(
+            Duration::ZERO,
+            TRACE_ID.to_string(),
+            CompleteState::default(),
+            vec![],
+            vec![instance_name(WORKLOAD_NAME_1)],
+        );
+        assert!(update_deadlines.take_rollback(&workload_state_db).is_none());
+
+        update_deadlines.track(
+            Duration::ZERO,
+            TRACE_ID.to_string(),
+            CompleteState::default(),
+            vec![],
+            vec![instance_name(WORKLOAD_NAME_2)],
+        );
+        update_deadlines.cancel();
+        assert!(update_deadlines.take_rollback(&workload_state_db).is_none());
+    }
+}
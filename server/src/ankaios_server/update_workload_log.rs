@@ -0,0 +1,195 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, VecDeque};
+
+use common::commands::UpdateWorkload;
+use common::objects::{DeletedWorkload, WorkloadSpec};
+
+// An agent which never acknowledges, e.g. of an older version, must not let the log grow without
+// bounds. The dropped messages are covered by the initial workload list sent on the reconnect.
+const MAX_UNACKNOWLEDGED_UPDATES: usize = 100;
+
+#[derive(Default)]
+struct AgentUpdates {
+    last_sequence_number: u64,
+    unacknowledged: VecDeque<UpdateWorkload>,
+}
+
+// The UpdateWorkload messages sent to each agent, numbered per agent.
+// The numbers continue over reconnects, such that an agent can skip the re-delivered messages.
+#[derive(Default)]
+pub struct UpdateWorkloadLog {
+    agents: HashMap<String, AgentUpdates>,
+}
+
+impl UpdateWorkloadLog {
+    // [impl->swdd~server-numbers-update-workload~2]
+    // [impl->swdd~server-re-sends-unacknowledged-update-workload~1]
+    // Only the messages to a connected agent are kept, as a disconnected agent receives the
+    // initial workload list on its reconnect anyway.
+    pub fn next_update(
+        &mut self,
+        agent_name: &str,
+        added_workloads: Vec<WorkloadSpec>,
+        deleted_workloads: Vec<DeletedWorkload>,
+        initial: bool,
+        deadline_ms: Option<u64>,
+        agent_connected: bool,
+    ) -> UpdateWorkload {
+        let agent_updates = self.agents.entry(agent_name.to_owned()).or_default();
+        agent_updates.last_sequence_number += 1;
+        let update_workload = UpdateWorkload {
+            added_workloads,
+            deleted_workloads,
+            sequence_number: agent_updates.last_sequence_number,
+            initial,
+            deadline_ms,
+        };
+
+        // a lost initial workload list is replaced by the one of the next reconnect
+        if agent_connected && !initial {
+            if agent_updates.unacknowledged.len() == MAX_UNACKNOWLEDGED_UPDATES {
+                log::warn!(
+                    "Agent '{}' does not acknowledge the workload updates, the oldest one is not re-sent.",
+                    agent_name
+                );
+                agent_updates.unacknowledged.pop_front();
+            }
+            agent_updates
+                .unacknowledged
+                .push_back(update_workload.clone());
+        }
+        update_workload
+    }
+
+    // [impl->swdd~server-tracks-acknowledged-update-workload~1]
+    pub fn acknowledged(&mut self, agent_name: &str, sequence_number: u64) {
+        if let Some(agent_updates) = self.agents.get_mut(agent_name) {
+            agent_updates
+                .unacknowledged
+                .retain(|update_workload| update_workload.sequence_number > sequence_number);
+        }
+    }
+
+    // [impl->swdd~server-re-sends-unacknowledged-update-workload~1]
+    pub fn unacknowledged(&self, agent_name: &str) -> Vec<UpdateWorkload> {
+        self.agents
+            .get(agent_name)
+            .map(|agent_updates| agent_updates.unacknowledged.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn remove_of_agent(&mut self, agent_name: &str) {
+        self.agents.remove(agent_name);
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::generate_test_workload_spec_with_param;
+
+    use super::{UpdateWorkloadLog, MAX_UNACKNOWLEDGED_UPDATES};
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+
+    fn sequence_numbers(update_workload_log: &UpdateWorkloadLog, agent_name: &str) -> Vec<u64> {
+        update_workload_log
+            .unacknowledged(agent_name)
+            .iter()
+            .map(|update_workload| update_workload.sequence_number)
+            .collect()
+    }
+
+    // [utest->swdd~server-numbers-update-workload~2]
+    #[test]
+    fn utest_update_workload_log_numbers_per_agent() {
+        let mut update_workload_log = UpdateWorkloadLog::default();
+        let workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            "workload_1".to_owned(),
+            "runtime".to_owned(),
+        );
+
+        let update_a = update_workload_log.next_update(
+            AGENT_A,
+            vec![workload.clone()],
+            vec![],
+            true,
+            None,
+            true,
+        );
+        let update_b = update_workload_log.next_update(AGENT_B, vec![], vec![], false, None, true);
+        let update_a_2 =
+            update_workload_log.next_update(AGENT_A, vec![], vec![], false, Some(500), true);
+
+        assert_eq!(update_a.sequence_number, 1);
+        assert!(update_a.initial);
+        assert_eq!(update_a.added_workloads, vec![workload]);
+        assert_eq!(update_b.sequence_number, 1);
+        assert_eq!(update_a_2.sequence_number, 2);
+        assert_eq!(update_a_2.deadline_ms, Some(500));
+    }
+
+    // [utest->swdd~server-re-sends-unacknowledged-update-workload~1]
+    // [utest->swdd~server-tracks-acknowledged-update-workload~1]
+    #[test]
+    fn utest_update_workload_log_keeps_unacknowledged_updates_per_agent() {
+        let mut update_workload_log = UpdateWorkloadLog::default();
+        update_workload_log.next_update(AGENT_A, vec![], vec![], true, None, true);
+        for _ in 0..3 {
+            update_workload_log.next_update(AGENT_A, vec![], vec![], false, None, true);
+            update_workload_log.next_update(AGENT_B, vec![], vec![], false, None, true);
+        }
+        // not kept for a disconnected agent, but still numbered
+        update_workload_log.next_update(AGENT_B, vec![], vec![], false, None, false);
+
+        update_workload_log.acknowledged(AGENT_A, 3);
+        update_workload_log.acknowledged(AGENT_B, 1);
+
+        assert_eq!(sequence_numbers(&update_workload_log, AGENT_A), vec![4]);
+        assert_eq!(sequence_numbers(&update_workload_log, AGENT_B), vec![2, 3]);
+        assert_eq!(
+            update_workload_log
+                .next_update(AGENT_B, vec![], vec![], false, None, true)
+                .sequence_number,
+            5
+        );
+
+        update_workload_log.remove_of_agent(AGENT_A);
+        assert!(update_workload_log.unacknowledged(AGENT_A).is_empty());
+    }
+
+    // [utest->swdd~server-re-sends-unacknowledged-update-workload~1]
+    #[test]
+    fn utest_update_workload_log_drops_oldest_unacknowledged_update() {
+        let mut update_workload_log = UpdateWorkloadLog::default();
+        for _ in 0..=MAX_UNACKNOWLEDGED_UPDATES {
+            update_workload_log.next_update(AGENT_A, vec![], vec![], false, None, true);
+        }
+
+        let sequence_numbers = sequence_numbers(&update_workload_log, AGENT_A);
+        assert_eq!(sequence_numbers.len(), MAX_UNACKNOWLEDGED_UPDATES);
+        assert_eq!(sequence_numbers[0], 2);
+    }
+}
@@ -12,10 +12,17 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use clap::Parser;
-use common::DEFAULT_SOCKET_ADDRESS;
+use ankaios_model::input_limits::{MAX_MANIFEST_DEPTH, MAX_MANIFEST_SIZE};
+use clap::{Parser, Subcommand};
+use common::{persistence::PersistenceFormat, DEFAULT_SOCKET_ADDRESS};
 use std::{env, net::SocketAddr};
 
+use crate::event_store::DEFAULT_MAX_EVENTS;
+
+const DEFAULT_CLOUD_POLL_INTERVAL_SECS: u64 = 60;
+const DEFAULT_STANDBY_SYNC_INTERVAL_SECS: u64 = 1;
+const DEFAULT_INCIDENT_MIN_FAILED_WORKLOADS: usize = 3;
+
 pub fn parse() -> Arguments {
     Arguments::parse()
 }
@@ -25,12 +32,123 @@ pub fn parse() -> Arguments {
         version=env!("CARGO_PKG_VERSION"),
         about="Ankaios - your friendly automotive workload orchestrator.\nWhat can the server do for you?")]
 pub struct Arguments {
+    #[clap(subcommand)]
+    pub command: Option<Commands>,
     #[clap(short = 'c', long = "startup-config")]
-    /// The path to the startup config yaml.
+    /// The path to the startup config yaml or an OCI reference to it, e.g. 'oci://registry/app-config:tag'.
     pub path: Option<String>,
+    #[clap(long = "startup-config-public-key")]
+    /// The path to the public key used to verify the signature of a startup config pulled from an OCI registry.
+    pub startup_config_public_key: Option<String>,
+    #[clap(long = "profile")]
+    /// The workload profile activated at start, e.g. 'dev' or 'prod'. It overwrites the 'activeProfile' of the startup config.
+    pub profile: Option<String>,
     #[clap(short = 'a', long = "address", default_value_t = DEFAULT_SOCKET_ADDRESS.parse().unwrap())]
     /// The address, including the port, the server shall listen at.
     pub addr: SocketAddr,
+    #[clap(long = "rollout-soak-time")]
+    /// Enables staged rollouts. Updates are applied rollout group by rollout group waiting the given soak time in seconds between the groups.
+    pub rollout_soak_time_secs: Option<u64>,
+    #[clap(long = "rollout-max-failure-rate", default_value_t = 0.0)]
+    /// The maximal ratio (0.0 - 1.0) of failed workloads in a rollout group before a staged rollout is halted.
+    pub rollout_max_failure_rate: f64,
+    #[clap(long = "event-store")]
+    /// The path to the file in which the events are persisted. Without this option the events are kept in memory only.
+    pub event_store_path: Option<String>,
+    #[clap(long = "event-store-max-events", default_value_t = DEFAULT_MAX_EVENTS)]
+    /// The maximal number of events kept by the server. The oldest events are dropped first.
+    pub event_store_max_events: usize,
+    #[clap(long = "event-retention")]
+    /// The time in seconds after which events are dropped. Without this option events are only dropped when the maximal number of events is reached.
+    pub event_retention_secs: Option<u64>,
+    #[clap(long = "persistence-format", default_value_t = PersistenceFormat::Json)]
+    /// The format of the persisted files, one of 'json', 'cbor' or 'bincode'. Files in another format or of an older version are migrated on load.
+    pub persistence_format: PersistenceFormat,
+    #[clap(long = "stale-state-timeout")]
+    /// Enables the detection of stale workload states. Execution states not refreshed by the agents within the given time in seconds are set to 'unknown(stale)'. The agents refresh the states every 10 seconds, thus the timeout must be longer than 10 seconds.
+    pub stale_state_timeout_secs: Option<u64>,
+    #[clap(long = "incident-window")]
+    /// Enables the detection of agent incidents. An 'AgentIncident' event is recorded if several workloads of an agent fail within the given time in seconds.
+    pub incident_window_secs: Option<u64>,
+    #[clap(long = "incident-min-failed-workloads", default_value_t = DEFAULT_INCIDENT_MIN_FAILED_WORKLOADS)]
+    /// The minimal number of workloads of an agent failing within the incident window to record an 'AgentIncident' event.
+    pub incident_min_failed_workloads: usize,
+    #[clap(long = "terminated-workload-retention")]
+    /// Keeps the final state of removed workloads for the given time in seconds, e.g., to show them with 'ank get workloads --show-terminated'. Without this option the states are dropped on removal.
+    pub terminated_workload_retention_secs: Option<u64>,
+    #[clap(long = "cloud-endpoint")]
+    /// Enables the cloud connector. The desired state is periodically fetched from the given HTTPS endpoint and applied when it changed.
+    pub cloud_endpoint: Option<String>,
+    #[clap(long = "cloud-poll-interval", default_value_t = DEFAULT_CLOUD_POLL_INTERVAL_SECS)]
+    /// The time in seconds between two fetches of the desired state from the cloud endpoint.
+    pub cloud_poll_interval_secs: u64,
+    #[clap(long = "cloud-public-key")]
+    /// The path to the public key used to verify the signature of the desired state fetched from the cloud endpoint.
+    pub cloud_public_key: Option<String>,
+    #[clap(long = "standby-of")]
+    /// Starts the server as polling snapshot standby of the primary server at the given url. The standby polls the complete state of the primary and accepts agents only after it has been promoted with SIGUSR1. Stop the primary server before promoting the standby.
+    pub standby_of: Option<url::Url>,
+    #[clap(long = "standby-sync-interval", default_value_t = DEFAULT_STANDBY_SYNC_INTERVAL_SECS)]
+    /// The time in seconds between two snapshots of the state of the primary server.
+    pub standby_sync_interval_secs: u64,
+    #[clap(long = "max-manifest-size", default_value_t = MAX_MANIFEST_SIZE)]
+    /// The maximal size in bytes of the startup config and of a desired state fetched from the cloud endpoint.
+    pub max_manifest_size: usize,
+    #[clap(long = "max-manifest-depth", default_value_t = MAX_MANIFEST_DEPTH)]
+    /// The maximal nesting depth of the mappings and sequences of a manifest.
+    pub max_manifest_depth: usize,
+    #[clap(long = "max-workloads")]
+    /// The maximal number of workloads in the desired state. Updates exceeding it are rejected. Without this option the number of workloads is not limited.
+    pub max_workloads: Option<usize>,
+    #[clap(long = "max-runtime-config-length")]
+    /// The maximal length in bytes of the runtime config of a workload. Updates exceeding it are rejected. Without this option the length is not limited.
+    pub max_runtime_config_length: Option<usize>,
+    #[clap(long = "rest-gateway")]
+    /// Enables the read-only REST gateway at the given loopback address, including the port. It provides the state, workloads, agents and events as JSON.
+    pub rest_gateway: Option<SocketAddr>,
+    #[clap(long = "protect-managed-workloads")]
+    /// Rejects updates modifying or deleting a workload created or last modified by another identity, e.g. another CLI user, unless the update is forced.
+    pub protect_managed_workloads: bool,
+    #[cfg(feature = "traffic_recording")]
+    #[clap(long = "record-traffic")]
+    /// Records the messages received and sent by the server as JSON lines to the given file. The recording can be replayed with the 'replay' subcommand.
+    pub record_traffic: Option<String>,
+}
+
+/// Supported actions besides starting the server
+#[derive(Debug, Subcommand)]
+pub enum Commands {
+    CheckConfig(CheckConfigArgs),
+    #[cfg(feature = "traffic_recording")]
+    Replay(ReplayArgs),
+}
+
+/// Check an Ankaios manifest offline and print the findings as JSON
+#[derive(clap::Args, Debug)]
+pub struct CheckConfigArgs {
+    #[arg(value_name = "Ankaios manifest file")]
+    pub manifest_file: String,
+    /// The names of the agents available for the placement of the workloads, e.g. "--agent agent_A --agent agent_B".
+    /// If not specified, the agents of the workloads are not checked.
+    #[arg(long = "agent")]
+    pub agents: Vec<String>,
+}
+/// Replay a traffic recording into a fresh server and print the comparison as JSON,
+/// or serve the recorded messages to connecting agents
+#[cfg(feature = "traffic_recording")]
+#[derive(clap::Args, Debug)]
+pub struct ReplayArgs {
+    #[arg(value_name = "Traffic recording file")]
+    pub recording: String,
+    /// The path to the startup config yaml the recorded server was started with.
+    #[arg(short = 'c', long = "startup-config")]
+    pub startup_config: Option<String>,
+    /// Serves the recorded messages to the agents connecting at the given address instead of replaying the recording into a fresh server.
+    #[arg(long = "serve")]
+    pub serve: Option<SocketAddr>,
+    /// Replays the messages as fast as possible instead of keeping the recorded time between them.
+    #[arg(long = "no-delay")]
+    pub no_delay: bool,
 }
 // Note: this code is intentionally without unit tests.
 // There is no business logic which can be tested, here we have only a config and a call of "clap" crate.
@@ -0,0 +1,584 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{path::PathBuf, time::Duration};
+
+use ankaios_model::manifest_migration;
+use common::{
+    commands::{Response, ResponseContent},
+    from_server_interface::{FromServer, FromServerReceiver},
+    objects::{CompleteState, State},
+    request_id_prepending::{
+        detach_prefix_from_request_id, prepend_request_id, CLOUD_CONNECTOR_CONNECTION_NAME,
+    },
+    to_server_interface::{ToServerInterface, ToServerSender},
+};
+use tokio::{
+    sync::mpsc,
+    time::{interval, MissedTickBehavior},
+};
+
+const CURL_CMD: &str = "curl";
+const COSIGN_CMD: &str = "cosign";
+const SIGNATURE_SUFFIX: &str = ".sig";
+const DESIRED_STATE_MASK: &str = "desiredState";
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+// curl appends the status and the ETag of the final response to the body, thus
+// the responses of redirects and informational responses are not parsed
+const CURL_WRITE_OUT: &str = "\n%{http_code} %header{etag}";
+const HTTP_STATUS_NOT_MODIFIED: &str = "304";
+
+#[derive(Debug, Clone)]
+pub struct CloudConnectorConfig {
+    pub endpoint: String,
+    pub poll_interval: Duration,
+    pub public_key: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum FetchResult {
+    NotModified,
+    Modified { body: String, etag: Option<String> },
+}
+
+pub struct CloudConnector {
+    config: CloudConnectorConfig,
+    to_server: ToServerSender,
+    responses: FromServerReceiver,
+    etag: Option<String>,
+}
+
+impl CloudConnector {
+    pub fn new(
+        config: CloudConnectorConfig,
+        to_server: ToServerSender,
+        responses: FromServerReceiver,
+    ) -> Self {
+        CloudConnector {
+            config,
+            to_server,
+            responses,
+            etag: None,
+        }
+    }
+
+    // [impl->swdd~server-cloud-connector-polls-desired-state~2]
+    pub async fn run(mut self) {
+        let mut poll_interval = interval(self.config.poll_interval);
+        poll_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            poll_interval.tick().await;
+            if let Err(error) = self.sync().await {
+                log::warn!(
+                    "Could not sync the desired state from '{}': '{}'",
+                    self.config.endpoint,
+                    error
+                );
+            }
+        }
+    }
+
+    async fn sync(&mut self) -> Result<(), String> {
+        // [impl->swdd~server-cloud-connector-detects-changes-with-etag~2]
+        let (body, etag) = match fetch(&self.config.endpoint, self.etag.as_deref()).await? {
+            FetchResult::NotModified => {
+                log::debug!(
+                    "The desired state at '{}' did not change.",
+                    self.config.endpoint
+                );
+                return Ok(());
+            }
+            FetchResult::Modified { body, etag } => (body, etag),
+        };
+
+        // [impl->swdd~server-cloud-connector-verifies-signature~1]
+        if let Some(public_key) = &self.config.public_key {
+            let signature_url = format!("{}{SIGNATURE_SUFFIX}", self.config.endpoint);
+            let signature = match fetch(&signature_url, None).await? {
+                FetchResult::Modified { body, .. } => body,
+                FetchResult::NotModified => {
+                    return Err(format!("Unexpected response for '{signature_url}'."))
+                }
+            };
+            verify_signature(&body, &signature, public_key).await?;
+        }
+
+        // [impl->swdd~common-limits-manifest-size-and-depth~2]
+        // [impl->swdd~common-migrates-manifests-to-current-api-version~1]
+        let desired_state: State = manifest_migration::parse_state_manifest(&body)
+            .map_err(|err| format!("Could not parse the desired state: '{err}'"))?;
+
+        // [impl->swdd~server-cloud-connector-applies-desired-state~1]
+        log::info!(
+            "Applying the changed desired state from '{}'",
+            self.config.endpoint
+        );
+        let request_id = prepend_request_id(
+            &uuid::Uuid::new_v4().to_string(),
+            CLOUD_CONNECTOR_CONNECTION_NAME,
+        );
+        self.to_server
+            .update_state(
+                request_id.clone(),
+                CompleteState {
+                    desired_state,
+                    ..Default::default()
+                },
+                vec![DESIRED_STATE_MASK.to_owned()],
+            )
+            .await
+            .map_err(|err| err.to_string())?;
+        self.wait_for_update_success(&request_id).await?;
+
+        // the etag is only stored after the state was applied, thus a failed sync is retried with the next poll
+        self.etag = etag;
+        Ok(())
+    }
+
+    async fn wait_for_update_success(&mut self, request_id: &str) -> Result<(), String> {
+        let receive_response = async {
+            loop {
+                match self.responses.recv().await {
+                    Some(FromServer::Response(Response {
+                        request_id: received_request_id,
+                        response_content,
+                        ..
+                    })) if received_request_id == request_id => {
+                        return match response_content {
+                            ResponseContent::UpdateStateSuccess(_) => Ok(()),
+                            ResponseContent::Error(error) => Err(format!(
+                                "The server rejected the desired state: '{}'",
+                                error.message
+                            )),
+                            _ => Err("Received an unexpected response from the server.".to_owned()),
+                        };
+                    }
+                    // responses of earlier requests that timed out
+                    Some(_) => continue,
+                    None => return Err("The server closed the connection.".to_owned()),
+                }
+            }
+        };
+        tokio::time::timeout(RESPONSE_TIMEOUT, receive_response)
+            .await
+            .unwrap_or_else(|_| Err("The server did not respond in time.".to_owned()))
+    }
+}
+
+/// Forwards the responses for the CloudConnector to the returned receiver of the responses.
+///
+/// Returns the receiver of the other messages to be used by the communications server and
+/// the receiver of the responses for the CloudConnector.
+///
+/// # Arguments
+///
+/// * `agents_receiver` - The receiver of the FromServer channel
+///
+// [impl->swdd~server-cloud-connector-detects-changes-with-etag~2]
+pub fn route_responses(
+    mut agents_receiver: FromServerReceiver,
+) -> (FromServerReceiver, FromServerReceiver) {
+    let (agents_sender, routed_agents_receiver) = mpsc::channel(common::CHANNEL_CAPACITY);
+    let (responses_sender, responses_receiver) = mpsc::channel(common::CHANNEL_CAPACITY);
+    tokio::spawn(async move {
+        while let Some(message) = agents_receiver.recv().await {
+            match message {
+                FromServer::Response(Response { ref request_id, .. })
+                    if detach_prefix_from_request_id(request_id).0
+                        == CLOUD_CONNECTOR_CONNECTION_NAME =>
+                {
+                    // the connector may be gone, which does not affect the other messages
+                    let _ = responses_sender.send(message).await;
+                }
+                message => {
+                    if agents_sender.send(message).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+    (routed_agents_receiver, responses_receiver)
+}
+
+async fn fetch(url: &str, etag: Option<&str>) -> Result<FetchResult, String> {
+    let if_none_match = etag.map(|etag| format!("If-None-Match: {etag}"));
+    let mut args = vec![
+        "--silent",
+        "--show-error",
+        "--fail",
+        "--location",
+        "--proto",
+        "=https",
+        "--proto-redir",
+        "=https",
+        "--write-out",
+        CURL_WRITE_OUT,
+    ];
+    if let Some(if_none_match) = &if_none_match {
+        args.extend(["--header", if_none_match.as_str()]);
+    }
+    args.push(url);
+
+    let output = run_command(CURL_CMD, &args).await?;
+    parse_curl_output(&output)
+}
+
+// the output of curl contains the body followed by a line with the status and the etag
+fn parse_curl_output(output: &str) -> Result<FetchResult, String> {
+    let (body, write_out) = output
+        .rsplit_once('\n')
+        .ok_or_else(|| "Received an incomplete HTTP response.".to_owned())?;
+    let (status, etag) = write_out.split_once(' ').unwrap_or((write_out, ""));
+    if status.is_empty() {
+        return Err("Received an HTTP response without status.".to_owned());
+    }
+    if status == HTTP_STATUS_NOT_MODIFIED {
+        return Ok(FetchResult::NotModified);
+    }
+
+    let etag = etag.trim();
+    Ok(FetchResult::Modified {
+        body: body.to_owned(),
+        etag: (!etag.is_empty()).then(|| etag.to_owned()),
+    })
+}
+
+async fn verify_signature(content: &str, signature: &str, public_key: &str) -> Result<(), String> {
+    let file_prefix =
+        std::env::temp_dir().join(format!("ankaios-cloud-state-{}", uuid::Uuid::new_v4()));
+    let content_file = PathBuf::from(format!("{}.yaml", file_prefix.display()));
+    let signature_file = PathBuf::from(format!("{}{SIGNATURE_SUFFIX}", file_prefix.display()));
+
+    let result = async {
+        tokio::fs::write(&content_file, content)
+            .await
+            .map_err(|err| err.to_string())?;
+        tokio::fs::write(&signature_file, signature.trim())
+            .await
+            .map_err(|err| err.to_string())?;
+        run_command(
+            COSIGN_CMD,
+            &[
+                "verify-blob",
+                "--key",
+                public_key,
+                "--signature",
+                &signature_file.to_string_lossy(),
+                &content_file.to_string_lossy(),
+            ],
+        )
+        .await
+    }
+    .await;
+
+    let _ = tokio::fs::remove_file(&content_file).await;
+    let _ = tokio::fs::remove_file(&signature_file).await;
+    result
+        .map(|_| ())
+        .map_err(|err| format!("Signature verification failed: '{err}'"))
+}
+
+#[cfg(not(test))]
+async fn run_command(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = tokio::process::Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|err| format!("Could not execute '{program}': '{err}'"))?;
+    if output.status.success() {
+        String::from_utf8(output.stdout).map_err(|err| err.to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_owned())
+    }
+}
+#[cfg(test)]
+use tests::run_command_mock as run_command;
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::VecDeque, time::Duration};
+
+    use common::{
+        commands::{Request, RequestContent, Response, ResponseContent, UpdateStateSuccess},
+        from_server_interface::{FromServer, FromServerSender},
+        to_server_interface::{ToServer, ToServerReceiver},
+    };
+
+    use super::{
+        parse_curl_output, route_responses, CloudConnector, CloudConnectorConfig, FetchResult,
+        DESIRED_STATE_MASK,
+    };
+
+    const ENDPOINT: &str = "https://example.com/state.yaml";
+    const ETAG: &str = "\"v1\"";
+    const STATE: &str = "apiVersion: v0.1\nworkloads: {}\n";
+    const CURL_COMMAND: &str = "curl --silent --show-error --fail --location --proto =https --proto-redir =https --write-out \n%{http_code} %header{etag}";
+
+    type ExpectedCommand = (String, Result<String, String>);
+
+    thread_local! {
+        static EXPECTED_COMMANDS: RefCell<VecDeque<ExpectedCommand>> = const { RefCell::new(VecDeque::new()) };
+    }
+
+    // the commands are compared by prefix as the signature check uses generated file names
+    pub async fn run_command_mock(program: &str, args: &[&str]) -> Result<String, String> {
+        let command = format!("{program} {}", args.join(" "));
+        let (expected_command, result) = EXPECTED_COMMANDS
+            .with(|commands| commands.borrow_mut().pop_front())
+            .unwrap_or_else(|| panic!("Unexpected command '{command}'"));
+        assert!(
+            command.starts_with(&expected_command),
+            "'{command}' does not start with '{expected_command}'"
+        );
+        result
+    }
+
+    fn expect_command(command: &str, result: Result<&str, &str>) {
+        EXPECTED_COMMANDS.with(|commands| {
+            commands.borrow_mut().push_back((
+                command.to_owned(),
+                result.map(ToOwned::to_owned).map_err(ToOwned::to_owned),
+            ))
+        });
+    }
+
+    fn assert_all_commands_executed() {
+        EXPECTED_COMMANDS.with(|commands| assert!(commands.borrow().is_empty()));
+    }
+
+    fn curl_output(status: &str, etag: Option<&str>, body: &str) -> String {
+        format!("{body}\n{status} {}", etag.unwrap_or_default())
+    }
+
+    fn create_connector(
+        public_key: Option<&str>,
+    ) -> (CloudConnector, ToServerReceiver, FromServerSender) {
+        let (to_server, server_receiver) = tokio::sync::mpsc::channel(common::CHANNEL_CAPACITY);
+        let (responses_sender, responses) = tokio::sync::mpsc::channel(common::CHANNEL_CAPACITY);
+        (
+            CloudConnector::new(
+                CloudConnectorConfig {
+                    endpoint: ENDPOINT.to_owned(),
+                    poll_interval: Duration::from_secs(60),
+                    public_key: public_key.map(ToOwned::to_owned),
+                },
+                to_server,
+                responses,
+            ),
+            server_receiver,
+            responses_sender,
+        )
+    }
+
+    // Syncs the connector, which must send an update state request, and answers the request
+    // with the given response.
+    async fn sync_with_response(
+        connector: &mut CloudConnector,
+        server_receiver: &mut ToServerReceiver,
+        responses_sender: &FromServerSender,
+        response_content: ResponseContent,
+    ) -> (Result<(), String>, ToServer) {
+        let respond = async {
+            let request = server_receiver.recv().await.unwrap();
+            let ToServer::Request(Request { request_id, .. }) = &request else {
+                panic!("Expected a request, got '{request:?}'");
+            };
+            responses_sender
+                .send(FromServer::Response(Response {
+                    request_id: request_id.clone(),
+                    trace_id: String::new(),
+                    response_content,
+                }))
+                .await
+                .unwrap();
+            request
+        };
+        tokio::join!(connector.sync(), respond)
+    }
+
+    #[test]
+    fn utest_parse_curl_output() {
+        assert_eq!(
+            parse_curl_output(&curl_output("200", Some(ETAG), STATE)),
+            Ok(FetchResult::Modified {
+                body: STATE.to_owned(),
+                etag: Some(ETAG.to_owned())
+            })
+        );
+        assert_eq!(
+            parse_curl_output(&curl_output("200", None, "HTTP/1.1 100 Continue\r\n\r\n")),
+            Ok(FetchResult::Modified {
+                body: "HTTP/1.1 100 Continue\r\n\r\n".to_owned(),
+                etag: None
+            })
+        );
+        assert_eq!(
+            parse_curl_output(&curl_output("304", Some(ETAG), "")),
+            Ok(FetchResult::NotModified)
+        );
+        assert!(parse_curl_output("garbage").is_err());
+        assert!(parse_curl_output("garbage\n").is_err());
+    }
+
+    // [utest->swdd~server-cloud-connector-applies-desired-state~1]
+    // [utest->swdd~server-cloud-connector-detects-changes-with-etag~2]
+    #[tokio::test]
+    async fn utest_cloud_connector_applies_changed_state_only() {
+        let (mut connector, mut server_receiver, responses_sender) = create_connector(None);
+
+        expect_command(
+            &format!("{CURL_COMMAND} {ENDPOINT}"),
+            Ok(&curl_output("200", Some(ETAG), STATE)),
+        );
+        let (result, request) = sync_with_response(
+            &mut connector,
+            &mut server_receiver,
+            &responses_sender,
+            ResponseContent::UpdateStateSuccess(UpdateStateSuccess::default()),
+        )
+        .await;
+        assert!(result.is_ok());
+        assert!(matches!(
+            request,
+            ToServer::Request(Request {
+                request_id,
+                request_content: RequestContent::UpdateStateRequest(update_state_request),
+            }) if request_id.starts_with("cloud-connector@")
+                && update_state_request.update_mask == vec![DESIRED_STATE_MASK.to_owned()]
+                && update_state_request.state.desired_state.workloads.is_empty()
+        ));
+
+        expect_command(
+            &format!("{CURL_COMMAND} --header If-None-Match: {ETAG} {ENDPOINT}"),
+            Ok(&curl_output("304", Some(ETAG), "")),
+        );
+        assert!(connector.sync().await.is_ok());
+
+        assert!(server_receiver.try_recv().is_err());
+        assert_all_commands_executed();
+    }
+
+    // [utest->swdd~server-cloud-connector-detects-changes-with-etag~2]
+    #[tokio::test]
+    async fn utest_cloud_connector_keeps_etag_of_rejected_state() {
+        let (mut connector, mut server_receiver, responses_sender) = create_connector(None);
+
+        expect_command("curl", Ok(&curl_output("200", Some(ETAG), STATE)));
+        let (result, _) = sync_with_response(
+            &mut connector,
+            &mut server_receiver,
+            &responses_sender,
+            ResponseContent::Error(common::commands::Error {
+                message: "invalid workload".to_owned(),
+            }),
+        )
+        .await;
+        assert!(result.unwrap_err().contains("invalid workload"));
+
+        // as the etag was not stored, the state is requested without 'If-None-Match'
+        expect_command(
+            &format!("{CURL_COMMAND} {ENDPOINT}"),
+            Err("connection refused"),
+        );
+        assert!(connector.sync().await.is_err());
+        assert_all_commands_executed();
+    }
+
+    // [utest->swdd~server-cloud-connector-verifies-signature~1]
+    #[tokio::test]
+    async fn utest_cloud_connector_verifies_signature() {
+        let (mut connector, mut server_receiver, responses_sender) =
+            create_connector(Some("cosign.pub"));
+
+        expect_command("curl", Ok(&curl_output("200", Some(ETAG), STATE)));
+        expect_command(
+            &format!("{CURL_COMMAND} {ENDPOINT}.sig"),
+            Ok(&curl_output("200", None, "c2lnbmF0dXJl")),
+        );
+        expect_command("cosign verify-blob --key cosign.pub --signature", Ok(""));
+        let (result, _) = sync_with_response(
+            &mut connector,
+            &mut server_receiver,
+            &responses_sender,
+            ResponseContent::UpdateStateSuccess(UpdateStateSuccess::default()),
+        )
+        .await;
+        assert!(result.is_ok());
+        assert_all_commands_executed();
+    }
+
+    // [utest->swdd~server-cloud-connector-verifies-signature~1]
+    // [utest->swdd~server-cloud-connector-detects-changes-with-etag~2]
+    #[tokio::test]
+    async fn utest_cloud_connector_rejects_invalid_signature_and_retries() {
+        let (mut connector, mut server_receiver, _responses_sender) =
+            create_connector(Some("cosign.pub"));
+
+        expect_command("curl", Ok(&curl_output("200", Some(ETAG), STATE)));
+        expect_command("curl", Ok(&curl_output("200", None, "invalid")));
+        expect_command("cosign verify-blob", Err("invalid signature"));
+        assert!(connector
+            .sync()
+            .await
+            .unwrap_err()
+            .contains("invalid signature"));
+        assert!(server_receiver.try_recv().is_err());
+
+        // as the etag was not stored, the state is requested without 'If-None-Match'
+        expect_command(
+            &format!("{CURL_COMMAND} {ENDPOINT}"),
+            Err("connection refused"),
+        );
+        assert!(connector.sync().await.is_err());
+        assert_all_commands_executed();
+    }
+
+    // [utest->swdd~server-cloud-connector-detects-changes-with-etag~2]
+    #[tokio::test]
+    async fn utest_route_responses_of_cloud_connector() {
+        let (to_agents, agents_receiver) = tokio::sync::mpsc::channel(common::CHANNEL_CAPACITY);
+        let (mut agents_receiver, mut responses) = route_responses(agents_receiver);
+
+        for request_id in ["cloud-connector@1", "cli-conn-1@2"] {
+            to_agents
+                .send(FromServer::Response(Response {
+                    request_id: request_id.to_owned(),
+                    trace_id: String::new(),
+                    response_content: ResponseContent::UpdateStateSuccess(
+                        UpdateStateSuccess::default(),
+                    ),
+                }))
+                .await
+                .unwrap();
+        }
+
+        assert!(matches!(
+            responses.recv().await,
+            Some(FromServer::Response(Response { request_id, .. })) if request_id == "cloud-connector@1"
+        ));
+        assert!(matches!(
+            agents_receiver.recv().await,
+            Some(FromServer::Response(Response { request_id, .. })) if request_id == "cli-conn-1@2"
+        ));
+    }
+}
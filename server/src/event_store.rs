@@ -0,0 +1,519 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use common::{
+    commands::{Event, EventKind},
+    helpers::now_ms,
+    objects::WorkloadState,
+    persistence::{self, PersistenceFormat},
+};
+use std::{
+    collections::VecDeque,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+pub const DEFAULT_MAX_EVENTS: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventStoreConfig {
+    pub max_events: usize,
+    pub retention: Option<Duration>,
+    pub path: Option<PathBuf>,
+    pub format: PersistenceFormat,
+}
+
+impl Default for EventStoreConfig {
+    fn default() -> Self {
+        Self {
+            max_events: DEFAULT_MAX_EVENTS,
+            retention: None,
+            path: None,
+            format: PersistenceFormat::default(),
+        }
+    }
+}
+
+// The events are kept in memory as a ring buffer. If a path is configured, every event is
+// additionally appended as a record in the configured format to the file. The file is compacted
+// to the content of the ring buffer as soon as it holds twice the maximal number of events, which
+// keeps it bounded.
+#[derive(Default)]
+pub struct EventStore {
+    config: EventStoreConfig,
+    events: VecDeque<Event>,
+    records_in_file: usize,
+}
+
+impl EventStore {
+    pub fn new(config: EventStoreConfig) -> Self {
+        Self {
+            config,
+            events: VecDeque::new(),
+            records_in_file: 0,
+        }
+    }
+
+    // [impl->swdd~server-loads-persisted-events~1]
+    // [impl->swdd~server-migrates-persisted-events-on-load~1]
+    pub fn load(config: EventStoreConfig) -> Result<Self, String> {
+        let mut event_store = Self::new(config);
+        let Some(path) = event_store.config.path.clone() else {
+            return Ok(event_store);
+        };
+
+        match fs::read(&path) {
+            Ok(content) => {
+                let (records, header) =
+                    persistence::decode_records::<Event>(&content).map_err(|err| {
+                        format!(
+                            "Could not parse the event store '{}': '{}'",
+                            path.display(),
+                            err
+                        )
+                    })?;
+                for record in records {
+                    match record {
+                        Ok(event) => event_store.events.push_back(event),
+                        Err(err) => {
+                            log::warn!("Skipping invalid event in '{}': '{}'", path.display(), err)
+                        }
+                    }
+                }
+                if header.needs_migration_to(event_store.config.format) {
+                    log::info!(
+                        "Migrating the event store '{}' from version '{}' in format '{}' to the current version in format '{}'.",
+                        path.display(),
+                        header.version,
+                        header.format,
+                        event_store.config.format
+                    );
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => {
+                return Err(format!(
+                    "Could not read the event store '{}': '{}'",
+                    path.display(),
+                    err
+                ))
+            }
+        }
+
+        event_store.apply_retention_policy();
+        // rewrites the file with the current header, which also migrates it
+        event_store.compact().map_err(|err| {
+            format!(
+                "Could not write the event store '{}': '{}'",
+                path.display(),
+                err
+            )
+        })?;
+        Ok(event_store)
+    }
+
+    // [impl->swdd~server-stores-events-in-bounded-ring-buffer~1]
+    pub fn record(
+        &mut self,
+        kind: EventKind,
+        agent_name: Option<String>,
+        workload_name: Option<String>,
+        message: String,
+    ) {
+        self.push(Event {
+            timestamp: now_ms(),
+            kind,
+            agent_name,
+            workload_name,
+            message,
+            trace_id: None,
+            agent_timestamp: None,
+        });
+    }
+
+    // The event is ordered by the time the server received the state. The time reported by the
+    // agent is only kept for information as the clocks of the agents can be skewed.
+    // [impl->swdd~server-records-agent-timestamp-in-events~1]
+    pub fn record_workload_state_change(&mut self, workload_state: &WorkloadState) {
+        self.push(Event {
+            timestamp: now_ms(),
+            kind: EventKind::WorkloadStateChanged,
+            agent_name: Some(workload_state.instance_name.agent_name().to_string()),
+            workload_name: Some(workload_state.instance_name.workload_name().to_string()),
+            message: workload_state.execution_state.to_string(),
+            trace_id: None,
+            agent_timestamp: workload_state.agent_timestamp,
+        });
+    }
+
+    // [impl->swdd~server-records-trace-id-in-events~1]
+    pub fn record_for_request(
+        &mut self,
+        trace_id: &str,
+        kind: EventKind,
+        agent_name: Option<String>,
+        message: String,
+    ) {
+        self.push(Event {
+            timestamp: now_ms(),
+            kind,
+            agent_name,
+            workload_name: None,
+            message,
+            trace_id: Some(trace_id.to_string()),
+            agent_timestamp: None,
+        });
+    }
+
+    // [impl->swdd~server-provides-events~1]
+    pub fn get_events(&self, since: u64, limit: u32) -> Vec<Event> {
+        let cutoff = self.retention_cutoff().max(since);
+        let matching: Vec<&Event> = self
+            .events
+            .iter()
+            .filter(|event| event.timestamp >= cutoff)
+            .collect();
+        let skip = match limit as usize {
+            0 => 0,
+            limit => matching.len().saturating_sub(limit),
+        };
+        matching.into_iter().skip(skip).cloned().collect()
+    }
+
+    fn push(&mut self, event: Event) {
+        let record = persistence::encode_record(self.config.format, &event);
+        self.events.push_back(event);
+        self.apply_retention_policy();
+
+        let Some(path) = self.config.path.clone() else {
+            return;
+        };
+
+        // [impl->swdd~server-persists-events~1]
+        // [impl->swdd~server-persists-events-in-configured-format~1]
+        let format = self.config.format;
+        let result = record
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            .and_then(|record| {
+                let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+                if file.metadata()?.len() == 0 {
+                    file.write_all(&persistence::encode_records_header(format))?;
+                }
+                file.write_all(&record)
+            });
+        match result {
+            Ok(()) => self.records_in_file += 1,
+            Err(err) => log::warn!("Could not persist event to '{}': '{}'", path.display(), err),
+        }
+
+        if self.records_in_file > 2 * self.config.max_events {
+            if let Err(err) = self.compact() {
+                log::warn!(
+                    "Could not compact the event store '{}': '{}'",
+                    path.display(),
+                    err
+                );
+            }
+        }
+    }
+
+    fn retention_cutoff(&self) -> u64 {
+        self.config
+            .retention
+            .map(|retention| now_ms().saturating_sub(retention.as_millis() as u64))
+            .unwrap_or_default()
+    }
+
+    fn apply_retention_policy(&mut self) {
+        while self.events.len() > self.config.max_events {
+            self.events.pop_front();
+        }
+
+        let cutoff = self.retention_cutoff();
+        while self
+            .events
+            .front()
+            .is_some_and(|event| event.timestamp < cutoff)
+        {
+            self.events.pop_front();
+        }
+    }
+
+    fn compact(&mut self) -> io::Result<()> {
+        let Some(path) = &self.config.path else {
+            return Ok(());
+        };
+
+        let mut content = persistence::encode_records_header(self.config.format);
+        for event in &self.events {
+            content.extend(
+                persistence::encode_record(self.config.format, event)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+            );
+        }
+
+        // write to a temporary file first to not lose the events if the server stops in between
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, content)?;
+        fs::rename(tmp_path, path)?;
+        self.records_in_file = self.events.len();
+        Ok(())
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{now_ms, EventStore, EventStoreConfig};
+    use common::{
+        commands::{Event, EventKind},
+        objects::{generate_test_workload_state_with_agent, ExecutionState, WorkloadState},
+        persistence::PersistenceFormat,
+    };
+    use std::time::Duration;
+
+    const AGENT_A: &str = "agent_A";
+
+    fn event(timestamp: u64, message: &str) -> Event {
+        Event {
+            timestamp,
+            kind: EventKind::AgentConnected,
+            agent_name: Some(AGENT_A.to_string()),
+            workload_name: None,
+            message: message.to_string(),
+            trace_id: None,
+            agent_timestamp: None,
+        }
+    }
+
+    // [utest->swdd~server-stores-events-in-bounded-ring-buffer~1]
+    #[test]
+    fn utest_event_store_drops_oldest_events_when_full() {
+        let mut event_store = EventStore::new(EventStoreConfig {
+            max_events: 2,
+            ..Default::default()
+        });
+
+        event_store.push(event(1, "first"));
+        event_store.push(event(2, "second"));
+        event_store.push(event(3, "third"));
+
+        assert_eq!(
+            event_store.get_events(0, 0),
+            vec![event(2, "second"), event(3, "third")]
+        );
+    }
+
+    // [utest->swdd~server-records-trace-id-in-events~1]
+    #[test]
+    fn utest_event_store_records_trace_id_of_request() {
+        let mut event_store = EventStore::default();
+
+        event_store.record_for_request(
+            "trace_id",
+            EventKind::AgentRemoved,
+            Some(AGENT_A.to_string()),
+            "Agent removed".to_string(),
+        );
+
+        let events = event_store.get_events(0, 0);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].trace_id, Some("trace_id".to_string()));
+        assert_eq!(events[0].agent_name, Some(AGENT_A.to_string()));
+    }
+
+    // [utest->swdd~server-records-agent-timestamp-in-events~1]
+    #[test]
+    fn utest_event_store_records_agent_timestamp_of_workload_state_change() {
+        let mut event_store = EventStore::default();
+        // the clock of the agent is far behind the clock of the server
+        let workload_state = WorkloadState {
+            agent_timestamp: Some(1000),
+            ..generate_test_workload_state_with_agent(
+                "workload_A",
+                AGENT_A,
+                ExecutionState::running(),
+            )
+        };
+
+        let before = now_ms();
+        event_store.record_workload_state_change(&workload_state);
+
+        let events = event_store.get_events(before, 0);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, EventKind::WorkloadStateChanged);
+        assert_eq!(events[0].workload_name, Some("workload_A".to_string()));
+        assert_eq!(events[0].message, ExecutionState::running().to_string());
+        assert_eq!(events[0].agent_timestamp, Some(1000));
+        assert!(events[0].timestamp >= before);
+    }
+
+    // [utest->swdd~server-stores-events-in-bounded-ring-buffer~1]
+    #[test]
+    fn utest_event_store_drops_events_older_than_retention() {
+        let mut event_store = EventStore::new(EventStoreConfig {
+            retention: Some(Duration::from_secs(3600)),
+            ..Default::default()
+        });
+        let now = now_ms();
+
+        event_store.push(event(now - 7_200_000, "outdated"));
+        event_store.push(event(now, "recent"));
+
+        assert_eq!(event_store.get_events(0, 0), vec![event(now, "recent")]);
+    }
+
+    // [utest->swdd~server-provides-events~1]
+    #[test]
+    fn utest_event_store_get_events_filters_by_since_and_limit() {
+        let mut event_store = EventStore::default();
+        event_store.config.max_events = 10;
+        for timestamp in 1..=5 {
+            event_store.push(event(timestamp, "event"));
+        }
+
+        assert_eq!(
+            event_store.get_events(3, 0),
+            vec![event(3, "event"), event(4, "event"), event(5, "event")]
+        );
+        assert_eq!(
+            event_store.get_events(0, 2),
+            vec![event(4, "event"), event(5, "event")]
+        );
+    }
+
+    // [utest->swdd~server-persists-events~1]
+    // [utest->swdd~server-loads-persisted-events~1]
+    #[test]
+    fn utest_event_store_restores_persisted_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = EventStoreConfig {
+            max_events: 2,
+            path: Some(dir.path().join("events.jsonl")),
+            ..Default::default()
+        };
+
+        let mut event_store = EventStore::load(config.clone()).unwrap();
+        event_store.push(event(1, "first"));
+        event_store.push(event(2, "second"));
+        event_store.push(event(3, "third"));
+        drop(event_store);
+
+        let event_store = EventStore::load(config).unwrap();
+        assert_eq!(
+            event_store.get_events(0, 0),
+            vec![event(2, "second"), event(3, "third")]
+        );
+    }
+
+    // [utest->swdd~server-persists-events~1]
+    #[test]
+    fn utest_event_store_compacts_file_when_exceeding_twice_the_capacity() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let mut event_store = EventStore::load(EventStoreConfig {
+            max_events: 2,
+            path: Some(path.clone()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        for timestamp in 1..=5 {
+            event_store.push(event(timestamp, "event"));
+        }
+
+        // the header and two events
+        let content = std::fs::read_to_string(path).unwrap();
+        assert_eq!(content.lines().count(), 3);
+    }
+
+    // [utest->swdd~server-loads-persisted-events~1]
+    #[test]
+    fn utest_event_store_skips_invalid_lines_on_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        std::fs::write(
+            &path,
+            format!(
+                "not an event\n{}\n",
+                serde_json::to_string(&event(1, "valid")).unwrap()
+            ),
+        )
+        .unwrap();
+
+        let event_store = EventStore::load(EventStoreConfig {
+            path: Some(path),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(event_store.get_events(0, 0), vec![event(1, "valid")]);
+    }
+
+    // [utest->swdd~server-persists-events-in-configured-format~1]
+    // [utest->swdd~server-loads-persisted-events~1]
+    #[test]
+    fn utest_event_store_restores_events_persisted_in_binary_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = EventStoreConfig {
+            path: Some(dir.path().join("events")),
+            format: PersistenceFormat::Bincode,
+            ..Default::default()
+        };
+
+        let mut event_store = EventStore::load(config.clone()).unwrap();
+        event_store.push(event(1, "first"));
+        event_store.push(event(2, "second"));
+        drop(event_store);
+
+        let event_store = EventStore::load(config).unwrap();
+        assert_eq!(
+            event_store.get_events(0, 0),
+            vec![event(1, "first"), event(2, "second")]
+        );
+    }
+
+    // [utest->swdd~server-migrates-persisted-events-on-load~1]
+    #[test]
+    fn utest_event_store_migrates_legacy_json_lines_to_configured_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events");
+        std::fs::write(
+            &path,
+            format!("{}\n", serde_json::to_string(&event(1, "legacy")).unwrap()),
+        )
+        .unwrap();
+
+        let event_store = EventStore::load(EventStoreConfig {
+            path: Some(path.clone()),
+            format: PersistenceFormat::Cbor,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(event_store.get_events(0, 0), vec![event(1, "legacy")]);
+        assert!(std::fs::read(path)
+            .unwrap()
+            .starts_with(b"ANKAIOS-PERSISTENCE/1 cbor\n"));
+    }
+}
@@ -14,25 +14,104 @@
 
 mod ankaios_server;
 mod cli;
+mod cloud_connector;
+mod event_store;
+mod rest_gateway;
+mod standby_replicator;
+#[cfg(feature = "traffic_recording")]
+mod traffic_recording;
 mod workload_state_db;
 
+use ankaios_model::input_limits::InputLimits;
+use ankaios_model::manifest_migration;
 use common::objects::CompleteState;
+use common::oci_artifact;
 use std::fs;
 
 use common::communications_server::CommunicationsServer;
 use common::objects::State;
-use common::std_extensions::GracefulExitResult;
+use common::std_extensions::{GracefulExitResult, IllegalStateResult};
 
-use ankaios_server::{create_from_server_channel, create_to_server_channel, AnkaiosServer};
+use cloud_connector::{route_responses, CloudConnector, CloudConnectorConfig};
+use event_store::{EventStore, EventStoreConfig};
+use rest_gateway::{GrpcServerConnection, RestGateway};
+use standby_replicator::{GrpcPrimaryConnection, StandbyConfig, StandbyReplicator};
+
+use ankaios_server::{
+    check_config, create_from_server_channel, create_to_server_channel,
+    validate_stale_state_timeout, AnkaiosServer, RolloutConfig,
+};
 
 use grpc::server::GRPCCommunicationsServer;
 
+// [impl->swdd~server-tracks-heap-usage~1]
+#[cfg(feature = "memory_profiling")]
+#[global_allocator]
+static ALLOCATOR: common::memory_profiling::TrackingAllocator =
+    common::memory_profiling::TrackingAllocator;
+#[cfg(feature = "memory_profiling")]
+const MEMORY_REPORT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
 #[tokio::main]
 async fn main() {
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
 
     let args = cli::parse();
 
+    // [impl->swdd~common-configurable-input-limits~1]
+    InputLimits::configure(InputLimits {
+        max_manifest_size: args.max_manifest_size,
+        max_manifest_depth: args.max_manifest_depth,
+        max_workloads: args.max_workloads,
+        max_runtime_config_length: args.max_runtime_config_length,
+        ..Default::default()
+    });
+
+    // [impl->swdd~server-check-config-parses-manifest~1]
+    if let Some(cli::Commands::CheckConfig(check_config_args)) = args.command {
+        let manifest = fs::read_to_string(check_config_args.manifest_file)
+            .unwrap_or_exit("Could not read the manifest");
+        let report = check_config(&manifest, &check_config_args.agents);
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_illegal_state()
+        );
+        std::process::exit(if report.valid { 0 } else { 1 });
+    }
+
+    #[cfg(feature = "traffic_recording")]
+    if let Some(cli::Commands::Replay(replay_args)) = args.command {
+        let records =
+            traffic_recording::read_recording(std::path::Path::new(&replay_args.recording))
+                .unwrap_or_exit("Could not read the traffic recording");
+        let realtime = !replay_args.no_delay;
+
+        // [impl->swdd~server-replays-recorded-traffic-to-agents~1]
+        if let Some(address) = replay_args.serve {
+            traffic_recording::replay_to_agents(records, address, realtime)
+                .await
+                .unwrap_or_exit("Could not replay the traffic recording");
+            std::process::exit(0);
+        }
+
+        // [impl->swdd~server-replays-recorded-traffic-into-server~1]
+        let startup_state = replay_args.startup_config.map(|config_path| {
+            let data =
+                fs::read_to_string(config_path).unwrap_or_exit("Could not read the startup config");
+            CompleteState {
+                desired_state: manifest_migration::parse_state_manifest(&data)
+                    .unwrap_or_exit("Parsing start config failed with error"),
+                ..Default::default()
+            }
+        });
+        let report = traffic_recording::replay_into_server(records, startup_state, realtime).await;
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_illegal_state()
+        );
+        std::process::exit(if report.is_reproduced() { 0 } else { 1 });
+    }
+
     log::debug!(
         "Starting the Ankaios server with \n\tserver address: '{}', \n\tstartup config path: '{}'",
         args.addr,
@@ -41,13 +120,21 @@ async fn main() {
             .unwrap_or("[no config file provided]".to_string()),
     );
 
-    let startup_state = match args.path {
+    let mut startup_state = match args.path {
         Some(config_path) => {
-            let data =
-                fs::read_to_string(config_path).unwrap_or_exit("Could not read the startup config");
+            let data = if oci_artifact::is_oci_reference(&config_path) {
+                // [impl->swdd~server-loads-startup-state-from-oci-registry~1]
+                log::info!("Pulling the startup config from '{}'", config_path);
+                oci_artifact::pull_manifest(&config_path, args.startup_config_public_key.as_deref())
+                    .unwrap_or_exit("Could not pull the startup config")
+            } else {
+                fs::read_to_string(config_path).unwrap_or_exit("Could not read the startup config")
+            };
             // [impl->swdd~server-state-in-memory~1]
             // [impl->swdd~server-loads-startup-state-file~2]
-            let state: State = serde_yaml::from_str(&data)
+            // [impl->swdd~common-limits-manifest-size-and-depth~2]
+            // [impl->swdd~common-migrates-manifests-to-current-api-version~1]
+            let state: State = manifest_migration::parse_state_manifest(&data)
                 .unwrap_or_exit("Parsing start config failed with error");
             log::trace!(
                 "The state is initialized with the following workloads: {:?}",
@@ -62,11 +149,187 @@ async fn main() {
         _ => None,
     };
 
+    // [impl->swdd~server-selects-profile-at-start~1]
+    if let Some(profile) = args.profile {
+        log::info!("Activating the workload profile '{}'", profile);
+        startup_state
+            .get_or_insert_with(Default::default)
+            .desired_state
+            .active_profile = profile;
+    }
+
+    // [impl->swdd~server-standby-mirrors-primary-state~1]
+    // [impl->swdd~server-standby-promotion~1]
+    let mut restored_agents = Vec::new();
+    if let Some(primary) = args.standby_of {
+        log::info!(
+            "Starting as polling snapshot standby of the primary server '{}'",
+            primary
+        );
+        let mut promotion_signal =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::user_defined1())
+                .unwrap_or_illegal_state();
+        let replicator = StandbyReplicator::new(
+            StandbyConfig {
+                sync_interval: std::time::Duration::from_secs(args.standby_sync_interval_secs),
+            },
+            GrpcPrimaryConnection::new(primary),
+        );
+        let mirrored_state = replicator
+            .run_until_promoted(async {
+                promotion_signal.recv().await;
+            })
+            .await;
+
+        if let Some(mirrored_state) = mirrored_state {
+            restored_agents = mirrored_state.system.agents;
+            startup_state = Some(CompleteState {
+                desired_state: mirrored_state.desired_state,
+                ..Default::default()
+            });
+        } else {
+            log::warn!("Promoted without a mirrored state of the primary server.");
+        }
+    }
+
     let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
     let (to_agents, agents_receiver) = create_from_server_channel(common::CHANNEL_CAPACITY);
 
+    // [impl->swdd~server-records-traffic~1]
+    #[cfg(feature = "traffic_recording")]
+    let (server_receiver, agents_receiver) = match args.record_traffic {
+        Some(recording_path) => {
+            log::info!("Recording the traffic to '{}'", recording_path);
+            let recorder =
+                traffic_recording::TrafficRecorder::create(std::path::Path::new(&recording_path))
+                    .unwrap_or_exit("Could not create the traffic recording");
+            traffic_recording::record_traffic(recorder, server_receiver, agents_receiver)
+        }
+        None => (server_receiver, agents_receiver),
+    };
+
+    // [impl->swdd~server-tracks-heap-usage~1]
+    #[cfg(feature = "memory_profiling")]
+    {
+        use common::memory_profiling::{report_periodically, ChannelUsage};
+        // weak senders do not keep the channels open on shutdown
+        let to_server = to_server.downgrade();
+        let to_agents = to_agents.downgrade();
+        tokio::spawn(report_periodically(MEMORY_REPORT_INTERVAL, move || {
+            [
+                to_server
+                    .upgrade()
+                    .map(|sender| ChannelUsage::of("to server", &sender)),
+                to_agents
+                    .upgrade()
+                    .map(|sender| ChannelUsage::of("to agents", &sender)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect()
+        }));
+    }
+
     let mut communications_server = GRPCCommunicationsServer::new(to_server.clone());
     let mut server = AnkaiosServer::new(server_receiver, to_agents.clone());
+    server.restore_agents(restored_agents);
+    if let Some(soak_time_secs) = args.rollout_soak_time_secs {
+        log::info!(
+            "Staged rollouts enabled with a soak time of {}s and a maximal failure rate of {}",
+            soak_time_secs,
+            args.rollout_max_failure_rate
+        );
+        server.enable_staged_rollout(RolloutConfig {
+            soak_time: std::time::Duration::from_secs(soak_time_secs),
+            max_failure_rate: args.rollout_max_failure_rate,
+        });
+    }
+
+    // [impl->swdd~server-protects-managed-workloads~1]
+    if args.protect_managed_workloads {
+        log::info!("Workloads are protected from modifications by other identities");
+        server.protect_managed_workloads();
+    }
+
+    if let Some(stale_state_timeout_secs) = args.stale_state_timeout_secs {
+        // [impl->swdd~server-rejects-stale-state-timeout-within-refresh-interval~1]
+        let stale_state_timeout =
+            validate_stale_state_timeout(std::time::Duration::from_secs(stale_state_timeout_secs))
+                .unwrap_or_exit("Invalid stale state timeout");
+        log::info!(
+            "Stale workload states are detected after {}s",
+            stale_state_timeout_secs
+        );
+        server.enable_stale_state_reaper(stale_state_timeout);
+    }
+
+    if let Some(incident_window_secs) = args.incident_window_secs {
+        log::info!(
+            "Agent incidents are detected if {} workloads of an agent fail within {}s",
+            args.incident_min_failed_workloads,
+            incident_window_secs
+        );
+        server.enable_incident_detection(
+            std::time::Duration::from_secs(incident_window_secs),
+            args.incident_min_failed_workloads,
+        );
+    }
+
+    if let Some(retention_secs) = args.terminated_workload_retention_secs {
+        log::info!(
+            "The final states of removed workloads are kept for {}s",
+            retention_secs
+        );
+        server.retain_terminated_workloads(std::time::Duration::from_secs(retention_secs));
+    }
+
+    // [impl->swdd~server-loads-persisted-events~1]
+    let event_store = EventStore::load(EventStoreConfig {
+        max_events: args.event_store_max_events,
+        retention: args
+            .event_retention_secs
+            .map(std::time::Duration::from_secs),
+        path: args.event_store_path.map(Into::into),
+        // [impl->swdd~server-persists-events-in-configured-format~1]
+        format: args.persistence_format,
+    })
+    .unwrap_or_exit("Could not load the event store");
+    server.set_event_store(event_store);
+
+    let agents_receiver = match args.cloud_endpoint {
+        Some(endpoint) => {
+            log::info!(
+                "Cloud connector enabled, fetching the desired state from '{}' every {}s",
+                endpoint,
+                args.cloud_poll_interval_secs
+            );
+            let (agents_receiver, cloud_connector_responses) = route_responses(agents_receiver);
+            let cloud_connector = CloudConnector::new(
+                CloudConnectorConfig {
+                    endpoint,
+                    poll_interval: std::time::Duration::from_secs(args.cloud_poll_interval_secs),
+                    public_key: args.cloud_public_key,
+                },
+                to_server.clone(),
+                cloud_connector_responses,
+            );
+            tokio::spawn(cloud_connector.run());
+            agents_receiver
+        }
+        None => agents_receiver,
+    };
+
+    // [impl->swdd~server-rest-gateway-provides-read-only-state~1]
+    if let Some(gateway_address) = args.rest_gateway {
+        log::info!("REST gateway enabled at '{}'", gateway_address);
+        let server_url = url::Url::parse(&format!("http://{}", args.addr))
+            .unwrap_or_exit("Could not build the address of the server for the REST gateway");
+        let rest_gateway = RestGateway::bind(gateway_address, move || {
+            GrpcServerConnection::new(server_url.clone())
+        })
+        .unwrap_or_exit("Could not start the REST gateway");
+        tokio::spawn(rest_gateway.run());
+    }
 
     tokio::select! {
         // [impl->swdd~server-default-communication-grpc~1]
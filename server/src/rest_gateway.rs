@@ -0,0 +1,887 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+mod websocket;
+
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use axum::{
+    extract::{self, ConnectInfo, State},
+    http::{header, Request, StatusCode, Uri},
+    middleware::{self, Next},
+    response::{self, IntoResponse},
+    routing::get,
+    Router,
+};
+use common::{
+    commands::{
+        CompleteStateRequest, Event, EventsRequest, Response, ResponseContent,
+        WatchCompleteStateRequest,
+    },
+    communications_client::CommunicationsClient,
+    communications_error::CommunicationMiddlewareError,
+    from_server_interface::{FromServer, FromServerReceiver},
+    objects::{AgentInfo, CompleteState, WorkloadState},
+    to_server_interface::{ToServerInterface, ToServerSender},
+};
+use grpc::client::GRPCCommunicationsClient;
+use serde::Serialize;
+use tokio::{sync::Mutex, task::JoinHandle};
+use url::Url;
+
+const GATEWAY_CONNECTION_NAME: &str = "ank-server-rest-gateway";
+const REQUEST_ID_PREFIX: &str = "rest-gateway@";
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(3);
+const WORKLOAD_STATES_MASK: &str = "workloadStates";
+const SYSTEM_MASK: &str = "system";
+
+#[async_trait]
+pub trait ServerConnection: Send {
+    async fn request_complete_state(
+        &mut self,
+        field_mask: Vec<String>,
+    ) -> Result<CompleteState, String>;
+    async fn request_events(&mut self, events_request: EventsRequest)
+        -> Result<Vec<Event>, String>;
+    // returns the request id of the watch
+    async fn watch_complete_state(&mut self, field_mask: Vec<String>) -> Result<String, String>;
+    async fn next_watched_state(&mut self, request_id: &str) -> Result<CompleteState, String>;
+    async fn cancel_watch(&mut self, request_id: &str) -> Result<(), String>;
+}
+
+type ConnectFn<C> = dyn Fn() -> C + Send + Sync;
+
+type ServerConnectionTask = JoinHandle<Result<(), CommunicationMiddlewareError>>;
+
+// The gateway requests the state over a dedicated CLI connection to its own server. Thus, its
+// requests take the same path and are subject to the same checks as the requests of the CLI.
+pub struct GrpcServerConnection {
+    server: Url,
+    connection: Option<(ToServerSender, FromServerReceiver, ServerConnectionTask)>,
+}
+
+impl GrpcServerConnection {
+    pub fn new(server: Url) -> Self {
+        GrpcServerConnection {
+            server,
+            connection: None,
+        }
+    }
+
+    fn connect(&mut self) -> Result<(&mut ToServerSender, &mut FromServerReceiver), String> {
+        if self
+            .connection
+            .as_ref()
+            .is_none_or(|(_, _, task)| task.is_finished())
+        {
+            let (to_server, server_receiver) = tokio::sync::mpsc::channel(common::CHANNEL_CAPACITY);
+            let (from_server_sender, from_server) =
+                tokio::sync::mpsc::channel(common::CHANNEL_CAPACITY);
+            let mut client = GRPCCommunicationsClient::new_cli_communication(
+                GATEWAY_CONNECTION_NAME.to_owned(),
+                self.server.clone(),
+            );
+            let task =
+                tokio::spawn(async move { client.run(server_receiver, from_server_sender).await });
+            self.connection = Some((to_server, from_server, task));
+        }
+        self.connection
+            .as_mut()
+            .map(|(to_server, from_server, _)| (to_server, from_server))
+            .ok_or_else(|| "No connection to the server.".to_owned())
+    }
+
+    async fn receive_response(
+        from_server: &mut FromServerReceiver,
+        request_id: &str,
+    ) -> Result<ResponseContent, String> {
+        loop {
+            match from_server.recv().await {
+                Some(FromServer::Response(Response {
+                    request_id: received_request_id,
+                    response_content: ResponseContent::Error(error),
+                    ..
+                })) if received_request_id == request_id => return Err(error.message),
+                Some(FromServer::Response(Response {
+                    request_id: received_request_id,
+                    response_content,
+                    ..
+                })) if received_request_id == request_id => return Ok(response_content),
+                Some(_) => continue,
+                None => return Err("The server closed the connection.".to_owned()),
+            }
+        }
+    }
+
+    async fn wait_for_response(
+        from_server: &mut FromServerReceiver,
+        request_id: &str,
+    ) -> Result<ResponseContent, String> {
+        tokio::time::timeout(
+            RESPONSE_TIMEOUT,
+            Self::receive_response(from_server, request_id),
+        )
+        .await
+        .unwrap_or_else(|_| Err("The server did not respond in time.".to_owned()))
+    }
+}
+
+#[async_trait]
+impl ServerConnection for GrpcServerConnection {
+    async fn request_complete_state(
+        &mut self,
+        field_mask: Vec<String>,
+    ) -> Result<CompleteState, String> {
+        let (to_server, from_server) = self.connect()?;
+        let request_id = format!("{REQUEST_ID_PREFIX}{}", uuid::Uuid::new_v4());
+        to_server
+            .request_complete_state(request_id.clone(), CompleteStateRequest { field_mask })
+            .await
+            .map_err(|err| err.to_string())?;
+
+        match Self::wait_for_response(from_server, &request_id).await? {
+            ResponseContent::CompleteState(complete_state) => Ok(*complete_state),
+            _ => Err("Received an unexpected response from the server.".to_owned()),
+        }
+    }
+
+    async fn request_events(
+        &mut self,
+        events_request: EventsRequest,
+    ) -> Result<Vec<Event>, String> {
+        let (to_server, from_server) = self.connect()?;
+        let request_id = format!("{REQUEST_ID_PREFIX}{}", uuid::Uuid::new_v4());
+        to_server
+            .request_events(request_id.clone(), events_request)
+            .await
+            .map_err(|err| err.to_string())?;
+
+        match Self::wait_for_response(from_server, &request_id).await? {
+            ResponseContent::Events(events) => Ok(events.events),
+            _ => Err("Received an unexpected response from the server.".to_owned()),
+        }
+    }
+
+    async fn watch_complete_state(&mut self, field_mask: Vec<String>) -> Result<String, String> {
+        let (to_server, _) = self.connect()?;
+        let request_id = format!("{REQUEST_ID_PREFIX}{}", uuid::Uuid::new_v4());
+        to_server
+            .request_watch_complete_state(
+                request_id.clone(),
+                WatchCompleteStateRequest { field_mask },
+            )
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(request_id)
+    }
+
+    // the watched states are sent whenever they change, thus there is no response timeout
+    async fn next_watched_state(&mut self, request_id: &str) -> Result<CompleteState, String> {
+        let Some((_, from_server, _)) = self.connection.as_mut() else {
+            return Err("No connection to the server.".to_owned());
+        };
+        match Self::receive_response(from_server, request_id).await? {
+            ResponseContent::CompleteState(complete_state) => Ok(*complete_state),
+            _ => Err("Received an unexpected response from the server.".to_owned()),
+        }
+    }
+
+    async fn cancel_watch(&mut self, request_id: &str) -> Result<(), String> {
+        let Some((to_server, _, _)) = self.connection.as_mut() else {
+            return Err("No connection to the server.".to_owned());
+        };
+        to_server
+            .cancel_watch(request_id.to_owned())
+            .await
+            .map_err(|err| err.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct HttpResponse {
+    status: StatusCode,
+    body: String,
+}
+
+impl HttpResponse {
+    fn ok(content: &impl Serialize) -> Self {
+        match serde_json::to_string(content) {
+            Ok(body) => HttpResponse {
+                status: StatusCode::OK,
+                body,
+            },
+            Err(err) => HttpResponse::error(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string()),
+        }
+    }
+
+    fn error(status: StatusCode, message: &str) -> Self {
+        HttpResponse {
+            status,
+            body: serde_json::json!({ "error": message }).to_string(),
+        }
+    }
+}
+
+impl IntoResponse for HttpResponse {
+    fn into_response(self) -> response::Response {
+        (
+            self.status,
+            [(header::CONTENT_TYPE, "application/json")],
+            self.body,
+        )
+            .into_response()
+    }
+}
+
+struct Query(Vec<(String, String)>);
+
+impl Query {
+    fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn get_all(&self, name: &str) -> Vec<String> {
+        self.0
+            .iter()
+            .filter(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+            .collect()
+    }
+
+    fn get_number<T: std::str::FromStr + Default>(&self, name: &str) -> Result<T, HttpResponse> {
+        self.get(name).map_or(Ok(T::default()), |value| {
+            value.parse().map_err(|_| {
+                HttpResponse::error(
+                    StatusCode::BAD_REQUEST,
+                    &format!("The parameter '{name}' is not a number."),
+                )
+            })
+        })
+    }
+
+    // an absent filter matches all values
+    fn matches(&self, name: &str, value: Option<&str>) -> bool {
+        self.get(name)
+            .is_none_or(|filter| value.is_some_and(|value| value == filter))
+    }
+}
+
+// The execution state is matched by its name only, e.g. 'running' matches 'Running(Ok)'.
+fn matches_execution_state(query: &Query, workload_state: &WorkloadState) -> bool {
+    query.get("state").is_none_or(|filter| {
+        let execution_state = workload_state.execution_state.state.to_string();
+        let state_name = execution_state
+            .split_once('(')
+            .map_or(execution_state.as_str(), |(name, _)| name);
+        state_name.eq_ignore_ascii_case(filter)
+    })
+}
+
+fn filter_workload_states(
+    workload_states: Vec<WorkloadState>,
+    query: &Query,
+) -> Vec<WorkloadState> {
+    workload_states
+        .into_iter()
+        .filter(|workload_state| {
+            let instance_name = &workload_state.instance_name;
+            query.matches("agent", Some(instance_name.agent_name()))
+                && query.matches("workload", Some(instance_name.workload_name()))
+                && matches_execution_state(query, workload_state)
+        })
+        .collect()
+}
+
+fn filter_agents(agents: Vec<AgentInfo>, query: &Query) -> Vec<AgentInfo> {
+    agents
+        .into_iter()
+        .filter(|agent| {
+            query.matches("name", Some(&agent.agent_name))
+                && query.get("status").is_none_or(|filter| {
+                    format!("{:?}", agent.connection_status).eq_ignore_ascii_case(filter)
+                })
+        })
+        .collect()
+}
+
+fn filter_events(events: Vec<Event>, query: &Query) -> Vec<Event> {
+    events
+        .into_iter()
+        .filter(|event| {
+            query.matches("kind", Some(&event.kind.to_string()))
+                && query.matches("agent", event.agent_name.as_deref())
+                && query.matches("workload", event.workload_name.as_deref())
+        })
+        .collect()
+}
+
+// [impl->swdd~server-rest-gateway-provides-read-only-state~1]
+async fn handle_request<C: ServerConnection>(
+    connection: &Mutex<C>,
+    path: &str,
+    query: Query,
+) -> HttpResponse {
+    let result = match path {
+        "/state" => connection
+            .lock()
+            .await
+            .request_complete_state(query.get_all("mask"))
+            .await
+            .map(|complete_state| HttpResponse::ok(&complete_state)),
+        "/workloads" => connection
+            .lock()
+            .await
+            .request_complete_state(vec![WORKLOAD_STATES_MASK.to_owned()])
+            .await
+            .map(|complete_state| {
+                HttpResponse::ok(&filter_workload_states(
+                    complete_state.workload_states,
+                    &query,
+                ))
+            }),
+        "/agents" => connection
+            .lock()
+            .await
+            .request_complete_state(vec![SYSTEM_MASK.to_owned()])
+            .await
+            .map(|complete_state| {
+                HttpResponse::ok(&filter_agents(complete_state.system.agents, &query))
+            }),
+        "/events" => {
+            let events_request = match (query.get_number("since"), query.get_number("limit")) {
+                (Ok(since), Ok(limit)) => EventsRequest { since, limit },
+                (Err(response), _) | (_, Err(response)) => return response,
+            };
+            connection
+                .lock()
+                .await
+                .request_events(events_request)
+                .await
+                .map(|events| HttpResponse::ok(&filter_events(events, &query)))
+        }
+        path => {
+            return HttpResponse::error(
+                StatusCode::NOT_FOUND,
+                &format!("The path '{path}' is unknown."),
+            )
+        }
+    };
+
+    result.unwrap_or_else(|err| HttpResponse::error(StatusCode::BAD_GATEWAY, &err))
+}
+
+struct GatewayState<C> {
+    connection: Mutex<C>,
+    connect: Box<ConnectFn<C>>,
+}
+
+async fn get_route<C: ServerConnection + 'static>(
+    State(gateway): State<Arc<GatewayState<C>>>,
+    uri: Uri,
+    extract::Query(query): extract::Query<Vec<(String, String)>>,
+) -> HttpResponse {
+    handle_request(&gateway.connection, uri.path(), Query(query)).await
+}
+
+async fn unknown_route(uri: Uri) -> HttpResponse {
+    HttpResponse::error(
+        StatusCode::NOT_FOUND,
+        &format!("The path '{}' is unknown.", uri.path()),
+    )
+}
+
+// The gateway has no authentication, thus it only serves clients on the same host.
+// [impl->swdd~server-rest-gateway-serves-local-clients-only~1]
+async fn check_access<B>(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    request: Request<B>,
+    next: Next<B>,
+) -> response::Response {
+    if !peer.ip().is_loopback() {
+        log::warn!("Rejected the REST gateway request of '{}'.", peer);
+        return HttpResponse::error(
+            StatusCode::FORBIDDEN,
+            "The REST gateway only serves local clients.",
+        )
+        .into_response();
+    }
+    next.run(request).await
+}
+
+fn router<C: ServerConnection + 'static>(gateway: Arc<GatewayState<C>>) -> Router {
+    Router::new()
+        .route("/state", get(get_route::<C>))
+        .route("/workloads", get(get_route::<C>))
+        .route("/agents", get(get_route::<C>))
+        .route("/events", get(get_route::<C>))
+        .route(websocket::WATCH_PATH, get(websocket::watch::<C>))
+        .fallback(unknown_route)
+        .layer(middleware::from_fn(check_access))
+        .with_state(gateway)
+}
+
+pub struct RestGateway<C: ServerConnection> {
+    listener: std::net::TcpListener,
+    gateway: Arc<GatewayState<C>>,
+}
+
+impl<C: ServerConnection + 'static> RestGateway<C> {
+    /// Binds the REST gateway to the given loopback address.
+    ///
+    /// The gateway has no authentication and no TLS, thus other addresses are rejected.
+    /// The requests are sent over one shared connection to the server, while each
+    /// WebSocket watching the state gets a dedicated connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The loopback address including the port the gateway shall listen at
+    /// * `connect` - Creates a new connection to the server
+    ///
+    // [impl->swdd~server-rest-gateway-serves-local-clients-only~1]
+    pub fn bind(
+        address: SocketAddr,
+        connect: impl Fn() -> C + Send + Sync + 'static,
+    ) -> Result<Self, String> {
+        if !address.ip().is_loopback() {
+            return Err(format!(
+                "The REST gateway has no authentication and only listens at a loopback address, '{address}' is none."
+            ));
+        }
+        let listener = std::net::TcpListener::bind(address)
+            .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
+            .map_err(|err| format!("Could not listen at '{address}': '{err}'"))?;
+        Ok(RestGateway {
+            listener,
+            gateway: Arc::new(GatewayState {
+                connection: Mutex::new(connect()),
+                connect: Box::new(connect),
+            }),
+        })
+    }
+
+    #[cfg(test)]
+    fn local_addr(&self) -> SocketAddr {
+        self.listener.local_addr().unwrap()
+    }
+
+    // [impl->swdd~server-rest-gateway-provides-read-only-state~1]
+    pub async fn run(self) {
+        let server = match axum::Server::from_tcp(self.listener) {
+            Ok(server) => server,
+            Err(err) => {
+                log::error!("Could not start the REST gateway: '{}'", err);
+                return;
+            }
+        };
+        if let Err(err) = server
+            .serve(router(self.gateway).into_make_service_with_connect_info::<SocketAddr>())
+            .await
+        {
+            log::error!("The REST gateway stopped: '{}'", err);
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::SocketAddr,
+        sync::{Arc, Mutex as StdMutex},
+    };
+
+    use async_trait::async_trait;
+    use axum::{
+        body::Body,
+        extract::ConnectInfo,
+        http::{header, Method, Request, StatusCode},
+    };
+    use common::{
+        commands::{Event, EventKind, EventsRequest},
+        objects::{
+            generate_test_workload_spec_with_param,
+            generate_test_workload_state_with_workload_spec, AgentConnectionStatus, AgentInfo,
+            CompleteState, ExecutionState, SystemState,
+        },
+    };
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::sync::Mutex;
+    use tokio_tungstenite::tungstenite::Message;
+    use tower::ServiceExt;
+
+    use super::{
+        handle_request, router, GatewayState, HttpResponse, Query, RestGateway, ServerConnection,
+    };
+
+    const WATCH_REQUEST_ID: &str = "rest-gateway@watch";
+
+    #[derive(Default)]
+    struct FakeServerConnection {
+        complete_state: CompleteState,
+        events: Vec<Event>,
+        field_masks: Vec<Vec<String>>,
+        events_requests: Vec<EventsRequest>,
+        watched_states: Vec<CompleteState>,
+        // the watches are shared as the gateway creates a connection for every watch
+        watches: Arc<StdMutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl ServerConnection for FakeServerConnection {
+        async fn request_complete_state(
+            &mut self,
+            field_mask: Vec<String>,
+        ) -> Result<CompleteState, String> {
+            self.field_masks.push(field_mask);
+            Ok(self.complete_state.clone())
+        }
+
+        async fn request_events(
+            &mut self,
+            events_request: EventsRequest,
+        ) -> Result<Vec<Event>, String> {
+            self.events_requests.push(events_request);
+            Ok(self.events.clone())
+        }
+
+        async fn watch_complete_state(
+            &mut self,
+            field_mask: Vec<String>,
+        ) -> Result<String, String> {
+            self.watches
+                .lock()
+                .unwrap()
+                .push(format!("watch {}", field_mask.join(",")));
+            Ok(WATCH_REQUEST_ID.to_owned())
+        }
+
+        async fn next_watched_state(&mut self, _request_id: &str) -> Result<CompleteState, String> {
+            match self.watched_states.pop() {
+                Some(complete_state) => Ok(complete_state),
+                None => std::future::pending().await,
+            }
+        }
+
+        async fn cancel_watch(&mut self, request_id: &str) -> Result<(), String> {
+            self.watches
+                .lock()
+                .unwrap()
+                .push(format!("cancel {request_id}"));
+            Ok(())
+        }
+    }
+
+    fn fake_server_connection() -> FakeServerConnection {
+        let complete_state = CompleteState {
+            workload_states: vec![
+                generate_test_workload_state_with_workload_spec(
+                    &generate_test_workload_spec_with_param(
+                        "agent_A".to_owned(),
+                        "workload_1".to_owned(),
+                        "podman".to_owned(),
+                    ),
+                    ExecutionState::running(),
+                ),
+                generate_test_workload_state_with_workload_spec(
+                    &generate_test_workload_spec_with_param(
+                        "agent_B".to_owned(),
+                        "workload_2".to_owned(),
+                        "podman".to_owned(),
+                    ),
+                    ExecutionState::failed("error".to_owned()),
+                ),
+            ],
+            system: SystemState {
+                agents: vec![
+                    AgentInfo {
+                        agent_name: "agent_A".to_owned(),
+                        ..Default::default()
+                    },
+                    AgentInfo {
+                        agent_name: "agent_B".to_owned(),
+                        connection_status: AgentConnectionStatus::Disconnected,
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        FakeServerConnection {
+            complete_state,
+            events: vec![
+                Event {
+                    kind: EventKind::AgentConnected,
+                    agent_name: Some("agent_A".to_owned()),
+                    ..Default::default()
+                },
+                Event {
+                    kind: EventKind::DesiredStateUpdated,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn fake_connection() -> Mutex<FakeServerConnection> {
+        Mutex::new(fake_server_connection())
+    }
+
+    fn query(query: &str) -> Query {
+        Query(
+            url::form_urlencoded::parse(query.as_bytes())
+                .into_owned()
+                .collect(),
+        )
+    }
+
+    fn json_body(response: HttpResponse) -> serde_json::Value {
+        assert_eq!(response.status, StatusCode::OK, "{}", response.body);
+        serde_json::from_str(&response.body).unwrap()
+    }
+
+    // Returns the status of the response and the watches requested for the request.
+    async fn send_request_to_router(
+        mut request: Request<Body>,
+        peer: &str,
+    ) -> (StatusCode, Vec<String>) {
+        let watches = Arc::new(StdMutex::new(Vec::new()));
+        let connection_watches = watches.clone();
+        let gateway = Arc::new(GatewayState {
+            connection: fake_connection(),
+            connect: Box::new(move || FakeServerConnection {
+                watches: connection_watches.clone(),
+                ..fake_server_connection()
+            }),
+        });
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(peer.parse::<SocketAddr>().unwrap()));
+        let status = router(gateway).oneshot(request).await.unwrap().status();
+        let watches = watches.lock().unwrap().clone();
+        (status, watches)
+    }
+
+    async fn send_to_router(method: Method, uri: &str, peer: &str) -> StatusCode {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        send_request_to_router(request, peer).await.0
+    }
+
+    // [utest->swdd~server-rest-gateway-provides-read-only-state~1]
+    #[tokio::test]
+    async fn utest_rest_gateway_provides_state_with_field_masks() {
+        let connection = fake_connection();
+
+        let body = json_body(
+            handle_request(
+                &connection,
+                "/state",
+                query("mask=desiredState&mask=workloadStates"),
+            )
+            .await,
+        );
+
+        assert!(body["workloadStates"].is_array());
+        assert_eq!(
+            connection.lock().await.field_masks,
+            vec![vec!["desiredState".to_owned(), "workloadStates".to_owned()]]
+        );
+    }
+
+    // [utest->swdd~server-rest-gateway-provides-read-only-state~1]
+    #[tokio::test]
+    async fn utest_rest_gateway_filters_workloads_and_agents() {
+        let connection = fake_connection();
+
+        let workloads =
+            json_body(handle_request(&connection, "/workloads", query("state=running")).await);
+        assert_eq!(workloads.as_array().unwrap().len(), 1);
+        assert_eq!(
+            workloads[0]["instanceName"]["workloadName"],
+            serde_json::json!("workload_1")
+        );
+
+        let workloads =
+            json_body(handle_request(&connection, "/workloads", query("agent=agent_B")).await);
+        assert_eq!(workloads.as_array().unwrap().len(), 1);
+        assert_eq!(
+            workloads[0]["instanceName"]["workloadName"],
+            serde_json::json!("workload_2")
+        );
+
+        let agents =
+            json_body(handle_request(&connection, "/agents", query("status=disconnected")).await);
+        assert_eq!(agents.as_array().unwrap().len(), 1);
+        assert_eq!(agents[0]["agentName"], serde_json::json!("agent_B"));
+    }
+
+    // [utest->swdd~server-rest-gateway-provides-read-only-state~1]
+    #[tokio::test]
+    async fn utest_rest_gateway_filters_events() {
+        let connection = fake_connection();
+
+        let events = json_body(
+            handle_request(
+                &connection,
+                "/events",
+                query("since=5&limit=10&kind=AgentConnected"),
+            )
+            .await,
+        );
+
+        assert_eq!(events.as_array().unwrap().len(), 1);
+        assert_eq!(
+            connection.lock().await.events_requests,
+            vec![EventsRequest {
+                since: 5,
+                limit: 10
+            }]
+        );
+    }
+
+    // [utest->swdd~server-rest-gateway-provides-read-only-state~1]
+    #[tokio::test]
+    async fn utest_rest_gateway_rejects_invalid_requests() {
+        const LOCAL_PEER: &str = "127.0.0.1:40000";
+
+        assert_eq!(
+            send_to_router(Method::GET, "/workloads", LOCAL_PEER).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            send_to_router(Method::POST, "/state", LOCAL_PEER).await,
+            StatusCode::METHOD_NOT_ALLOWED
+        );
+        assert_eq!(
+            send_to_router(Method::GET, "/unknown", LOCAL_PEER).await,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            send_to_router(Method::GET, "/events?since=yesterday", LOCAL_PEER).await,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            send_to_router(Method::GET, "/watch", LOCAL_PEER).await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    // [utest->swdd~server-rest-gateway-serves-local-clients-only~1]
+    #[tokio::test]
+    async fn utest_rest_gateway_serves_local_clients_only() {
+        assert!(RestGateway::bind("0.0.0.0:0".parse().unwrap(), fake_server_connection).is_err());
+
+        assert_eq!(
+            send_to_router(Method::GET, "/state", "192.168.1.2:40000").await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            send_to_router(Method::GET, "/state", "[::1]:40000").await,
+            StatusCode::OK
+        );
+    }
+
+    // [utest->swdd~server-rest-gateway-serves-local-clients-only~1]
+    #[tokio::test]
+    async fn utest_rest_gateway_checks_access_before_websocket_upgrade() {
+        let websocket_request = Request::builder()
+            .uri("/watch?mask=workloadStates")
+            .header(header::CONNECTION, "Upgrade")
+            .header(header::UPGRADE, "websocket")
+            .header(header::SEC_WEBSOCKET_VERSION, "13")
+            .header(header::SEC_WEBSOCKET_KEY, "dGhlIHNhbXBsZSBub25jZQ==")
+            .body(Body::empty())
+            .unwrap();
+
+        assert_eq!(
+            send_request_to_router(websocket_request, "192.168.1.2:40000").await,
+            (StatusCode::FORBIDDEN, vec![])
+        );
+    }
+
+    // [utest->swdd~server-rest-gateway-streams-state-over-websocket~2]
+    #[tokio::test]
+    async fn utest_rest_gateway_streams_watched_state_over_websocket() {
+        let watches = Arc::new(StdMutex::new(Vec::new()));
+        let connection_watches = watches.clone();
+        let rest_gateway = RestGateway::bind("127.0.0.1:0".parse().unwrap(), move || {
+            let mut connection = fake_server_connection();
+            connection.watched_states = vec![connection.complete_state.clone()];
+            connection.watches = connection_watches.clone();
+            connection
+        })
+        .unwrap();
+        let address = rest_gateway.local_addr();
+        let gateway_task = tokio::spawn(rest_gateway.run());
+
+        let stream = tokio::net::TcpStream::connect(address).await.unwrap();
+        let (mut websocket, _) = tokio_tungstenite::client_async(
+            format!("ws://{address}/watch?mask=workloadStates"),
+            stream,
+        )
+        .await
+        .unwrap();
+
+        let Some(Ok(Message::Text(json))) = websocket.next().await else {
+            panic!("Expected the watched state as text message.");
+        };
+        let state: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(state["workloadStates"].is_array());
+
+        websocket
+            .send(Message::Ping(b"ping".to_vec()))
+            .await
+            .unwrap();
+        assert_eq!(
+            websocket.next().await.unwrap().unwrap(),
+            Message::Pong(b"ping".to_vec())
+        );
+
+        websocket.close(None).await.unwrap();
+        while websocket.next().await.is_some() {}
+
+        // the watch is cancelled after the WebSocket is closed
+        tokio::time::timeout(std::time::Duration::from_secs(1), async {
+            while watches.lock().unwrap().len() < 2 {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            *watches.lock().unwrap(),
+            vec![
+                "watch workloadStates".to_owned(),
+                format!("cancel {WATCH_REQUEST_ID}")
+            ]
+        );
+        gateway_task.abort();
+    }
+}
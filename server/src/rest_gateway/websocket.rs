@@ -0,0 +1,97 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        self,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    http::StatusCode,
+    response::{self, IntoResponse},
+};
+
+use super::{GatewayState, HttpResponse, Query, ServerConnection};
+
+pub(super) const WATCH_PATH: &str = "/watch";
+
+/// Streams the watched complete state to a WebSocket client.
+///
+/// The watch is requested before the WebSocket handshake is completed. Every state received
+/// for the watch is sent as JSON text message. The watch is cancelled when the client closes
+/// the WebSocket or the connection to the server fails.
+///
+// [impl->swdd~server-rest-gateway-streams-state-over-websocket~2]
+pub(super) async fn watch<C: ServerConnection + 'static>(
+    State(gateway): State<Arc<GatewayState<C>>>,
+    extract::Query(query): extract::Query<Vec<(String, String)>>,
+    websocket_upgrade: Option<WebSocketUpgrade>,
+) -> response::Response {
+    let Some(websocket_upgrade) = websocket_upgrade else {
+        return HttpResponse::error(
+            StatusCode::BAD_REQUEST,
+            "Watching the state requires a WebSocket upgrade.",
+        )
+        .into_response();
+    };
+
+    let mut connection = (gateway.connect)();
+    let request_id = match connection
+        .watch_complete_state(Query(query).get_all("mask"))
+        .await
+    {
+        Ok(request_id) => request_id,
+        Err(err) => return HttpResponse::error(StatusCode::BAD_GATEWAY, &err).into_response(),
+    };
+
+    websocket_upgrade.on_upgrade(|websocket| async move {
+        if let Err(err) = stream_states(websocket, &mut connection, &request_id).await {
+            log::debug!("Could not stream the watched state: '{}'", err);
+        }
+        if let Err(err) = connection.cancel_watch(&request_id).await {
+            log::debug!("Could not cancel the watch '{}': '{}'", request_id, err);
+        }
+    })
+}
+
+// Pings are answered and closes are acknowledged by the WebSocket library while receiving.
+async fn stream_states<C: ServerConnection>(
+    mut websocket: WebSocket,
+    connection: &mut C,
+    request_id: &str,
+) -> Result<(), axum::Error> {
+    loop {
+        tokio::select! {
+            state = connection.next_watched_state(request_id) => match state {
+                Ok(state) => {
+                    let json = serde_json::to_string(&state).map_err(axum::Error::new)?;
+                    websocket.send(Message::Text(json)).await?;
+                }
+                Err(err) => {
+                    let json = serde_json::json!({ "error": err }).to_string();
+                    websocket.send(Message::Text(json)).await?;
+                    return websocket.close().await;
+                }
+            },
+            message = websocket.recv() => match message {
+                Some(message) => {
+                    message?;
+                }
+                None => return Ok(()),
+            }
+        }
+    }
+}
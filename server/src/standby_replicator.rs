@@ -0,0 +1,258 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{future::Future, time::Duration};
+
+use async_trait::async_trait;
+use common::{
+    commands::{CompleteStateRequest, Response, ResponseContent},
+    communications_client::CommunicationsClient,
+    communications_error::CommunicationMiddlewareError,
+    from_server_interface::{FromServer, FromServerReceiver},
+    objects::CompleteState,
+    to_server_interface::{ToServerInterface, ToServerSender},
+};
+use grpc::client::GRPCCommunicationsClient;
+use tokio::{
+    task::JoinHandle,
+    time::{interval, MissedTickBehavior},
+};
+use url::Url;
+
+const STANDBY_CONNECTION_NAME: &str = "ank-server-standby";
+const REQUEST_ID_PREFIX: &str = "standby@";
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(3);
+
+// The standby is only promoted on request. An unreachable primary might still be running and
+// serving agents, e.g., during a network partition, and there is no fencing of the old primary.
+#[derive(Debug, Clone)]
+pub struct StandbyConfig {
+    pub sync_interval: Duration,
+}
+
+#[async_trait]
+pub trait PrimaryConnection {
+    async fn request_complete_state(&mut self) -> Result<CompleteState, String>;
+}
+
+type PrimaryConnectionTask = JoinHandle<Result<(), CommunicationMiddlewareError>>;
+
+// Polls snapshots of the state of the primary server over a CLI connection of its own, which is
+// re-established if the primary closes it.
+pub struct GrpcPrimaryConnection {
+    primary: Url,
+    connection: Option<(ToServerSender, FromServerReceiver, PrimaryConnectionTask)>,
+}
+
+impl GrpcPrimaryConnection {
+    pub fn new(primary: Url) -> Self {
+        GrpcPrimaryConnection {
+            primary,
+            connection: None,
+        }
+    }
+
+    fn connect(&mut self) {
+        let (to_primary, primary_receiver) = tokio::sync::mpsc::channel(common::CHANNEL_CAPACITY);
+        let (from_primary_sender, from_primary) =
+            tokio::sync::mpsc::channel(common::CHANNEL_CAPACITY);
+        let mut client = GRPCCommunicationsClient::new_cli_communication(
+            STANDBY_CONNECTION_NAME.to_owned(),
+            self.primary.clone(),
+        );
+        let task =
+            tokio::spawn(async move { client.run(primary_receiver, from_primary_sender).await });
+        self.connection = Some((to_primary, from_primary, task));
+    }
+}
+
+#[async_trait]
+impl PrimaryConnection for GrpcPrimaryConnection {
+    async fn request_complete_state(&mut self) -> Result<CompleteState, String> {
+        if self
+            .connection
+            .as_ref()
+            .map_or(true, |(_, _, task)| task.is_finished())
+        {
+            self.connect();
+        }
+        let Some((to_primary, from_primary, _)) = self.connection.as_mut() else {
+            return Err("No connection to the primary server.".to_owned());
+        };
+
+        let request_id = format!("{REQUEST_ID_PREFIX}{}", uuid::Uuid::new_v4());
+        to_primary
+            .request_complete_state(
+                request_id.clone(),
+                CompleteStateRequest { field_mask: vec![] },
+            )
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let wait_for_response = async {
+            loop {
+                match from_primary.recv().await {
+                    Some(FromServer::Response(Response {
+                        request_id: received_request_id,
+                        response_content: ResponseContent::CompleteState(complete_state),
+                        ..
+                    })) if received_request_id == request_id => return Ok(*complete_state),
+                    Some(_) => continue,
+                    None => return Err("The primary server closed the connection.".to_owned()),
+                }
+            }
+        };
+        tokio::time::timeout(RESPONSE_TIMEOUT, wait_for_response)
+            .await
+            .unwrap_or_else(|_| Err("The primary server did not respond in time.".to_owned()))
+    }
+}
+
+pub struct StandbyReplicator<C: PrimaryConnection> {
+    config: StandbyConfig,
+    primary_connection: C,
+    mirrored_state: Option<CompleteState>,
+}
+
+impl<C: PrimaryConnection> StandbyReplicator<C> {
+    pub fn new(config: StandbyConfig, primary_connection: C) -> Self {
+        StandbyReplicator {
+            config,
+            primary_connection,
+            mirrored_state: None,
+        }
+    }
+
+    // [impl->swdd~server-standby-mirrors-primary-state~1]
+    // [impl->swdd~server-standby-promotion~1]
+    /// Mirrors the state of the primary server until the standby is promoted on request.
+    /// Returns the last mirrored state, if any.
+    pub async fn run_until_promoted(
+        mut self,
+        promotion_request: impl Future<Output = ()>,
+    ) -> Option<CompleteState> {
+        let mut sync_interval = interval(self.config.sync_interval);
+        sync_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        tokio::pin!(promotion_request);
+
+        loop {
+            tokio::select! {
+                _ = &mut promotion_request => {
+                    log::info!("Promoting the standby server on request.");
+                    break;
+                }
+                _ = sync_interval.tick() => {
+                    match self.primary_connection.request_complete_state().await {
+                        Ok(complete_state) => {
+                            log::trace!("Mirrored the state of the primary server.");
+                            self.mirrored_state = Some(complete_state);
+                        }
+                        Err(error) => {
+                            log::warn!("Could not mirror the state of the primary server: '{}'", error);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.mirrored_state
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::VecDeque, time::Duration};
+
+    use async_trait::async_trait;
+    use common::objects::{generate_test_stored_workload_spec, AgentInfo, CompleteState};
+
+    use super::{PrimaryConnection, StandbyConfig, StandbyReplicator};
+
+    struct FakePrimaryConnection {
+        responses: VecDeque<Result<CompleteState, String>>,
+    }
+
+    #[async_trait]
+    impl PrimaryConnection for FakePrimaryConnection {
+        async fn request_complete_state(&mut self) -> Result<CompleteState, String> {
+            self.responses
+                .pop_front()
+                .unwrap_or_else(|| Err("unreachable".to_owned()))
+        }
+    }
+
+    fn primary_state() -> CompleteState {
+        let mut complete_state = CompleteState::default();
+        complete_state.desired_state.workloads.insert(
+            "workload_1".to_owned(),
+            generate_test_stored_workload_spec("agent_A", "podman"),
+        );
+        complete_state.system.agents.push(AgentInfo {
+            agent_name: "agent_A".to_owned(),
+            ..Default::default()
+        });
+        complete_state
+    }
+
+    // [utest->swdd~server-standby-mirrors-primary-state~1]
+    // [utest->swdd~server-standby-promotion~1]
+    #[tokio::test]
+    async fn utest_standby_promoted_on_request() {
+        let replicator = StandbyReplicator::new(
+            StandbyConfig {
+                sync_interval: Duration::from_secs(60),
+            },
+            FakePrimaryConnection {
+                responses: VecDeque::from([Ok(primary_state())]),
+            },
+        );
+
+        let mirrored_state = tokio::time::timeout(
+            Duration::from_secs(5),
+            replicator.run_until_promoted(tokio::time::sleep(Duration::from_millis(20))),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(mirrored_state, Some(primary_state()));
+    }
+
+    // [utest->swdd~server-standby-promotion~1]
+    #[tokio::test]
+    async fn utest_standby_not_promoted_if_primary_unreachable() {
+        let replicator = StandbyReplicator::new(
+            StandbyConfig {
+                sync_interval: Duration::from_millis(1),
+            },
+            FakePrimaryConnection {
+                responses: VecDeque::from([Ok(primary_state())]),
+            },
+        );
+
+        assert!(tokio::time::timeout(
+            Duration::from_millis(50),
+            replicator.run_until_promoted(std::future::pending())
+        )
+        .await
+        .is_err());
+    }
+}
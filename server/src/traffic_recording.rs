@@ -0,0 +1,433 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use common::{
+    communications_server::CommunicationsServer,
+    from_server_interface::{FromServer, FromServerReceiver},
+    objects::CompleteState,
+    to_server_interface::{ToServer, ToServerReceiver},
+};
+use grpc::server::GRPCCommunicationsServer;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    net::SocketAddr,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{sync::mpsc, time::Instant};
+
+use crate::ankaios_server::{create_from_server_channel, create_to_server_channel, AnkaiosServer};
+
+// The time the replayed server gets to answer the last replayed message before it is stopped.
+const REPLAY_SETTLE_TIME: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum TrafficMessage {
+    ToServer(ToServer),
+    FromServer(FromServer),
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficRecord {
+    // the time since the start of the recording
+    pub offset_ms: u64,
+    pub message: TrafficMessage,
+}
+
+#[derive(Debug, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayReport {
+    pub replayed_to_server: usize,
+    pub recorded_from_server: usize,
+    pub replayed_from_server: usize,
+    // the positions of the FromServer messages differing between the recording and the replay
+    pub diverging_from_server: Vec<usize>,
+}
+
+impl ReplayReport {
+    pub fn is_reproduced(&self) -> bool {
+        self.recorded_from_server == self.replayed_from_server
+            && self.diverging_from_server.is_empty()
+    }
+}
+
+// Writes the traffic as JSON lines. Every line is flushed on its own, such that the recording
+// is usable up to the last message even if the server crashes.
+pub struct TrafficRecorder {
+    start: Instant,
+    file: Mutex<BufWriter<File>>,
+}
+
+impl TrafficRecorder {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Ok(TrafficRecorder {
+            start: Instant::now(),
+            file: Mutex::new(BufWriter::new(File::create(path)?)),
+        })
+    }
+
+    fn record(&self, message: TrafficMessage) {
+        let record = TrafficRecord {
+            offset_ms: self.start.elapsed().as_millis() as u64,
+            message,
+        };
+        let result = serde_json::to_string(&record)
+            .map_err(|err| err.to_string())
+            .and_then(|line| {
+                let mut file = self.file.lock().map_err(|err| err.to_string())?;
+                writeln!(file, "{line}")
+                    .and_then(|_| file.flush())
+                    .map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            log::warn!("Could not record the traffic: '{}'", err);
+        }
+    }
+}
+
+// Spawns a task forwarding the messages of the receiver to the returned receiver
+// and recording every forwarded message.
+fn tap<T, F>(
+    recorder: Arc<TrafficRecorder>,
+    mut receiver: mpsc::Receiver<T>,
+    to_traffic_message: F,
+) -> mpsc::Receiver<T>
+where
+    T: Clone + Send + 'static,
+    F: Fn(T) -> TrafficMessage + Send + 'static,
+{
+    let (sender, tapped_receiver) = mpsc::channel(common::CHANNEL_CAPACITY);
+    tokio::spawn(async move {
+        while let Some(message) = receiver.recv().await {
+            recorder.record(to_traffic_message(message.clone()));
+            if sender.send(message).await.is_err() {
+                break;
+            }
+        }
+    });
+    tapped_receiver
+}
+
+/// Records the messages received by the server and the messages sent to the agents and the CLI.
+///
+/// Returns the receivers to be used by the server and the communications server instead.
+///
+/// # Arguments
+///
+/// * `recorder` - The recorder writing the traffic to the recording
+/// * `server_receiver` - The receiver of the ToServer channel
+/// * `agents_receiver` - The receiver of the FromServer channel
+///
+// [impl->swdd~server-records-traffic~1]
+pub fn record_traffic(
+    recorder: TrafficRecorder,
+    server_receiver: ToServerReceiver,
+    agents_receiver: FromServerReceiver,
+) -> (ToServerReceiver, FromServerReceiver) {
+    let recorder = Arc::new(recorder);
+    (
+        tap(recorder.clone(), server_receiver, TrafficMessage::ToServer),
+        tap(recorder, agents_receiver, TrafficMessage::FromServer),
+    )
+}
+
+pub fn read_recording(path: &Path) -> Result<Vec<TrafficRecord>, String> {
+    fs::read_to_string(path)
+        .map_err(|err| format!("Could not read the recording: '{err}'"))?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map_err(|err| format!("Invalid record in line {}: '{}'", index + 1, err))
+        })
+        .collect()
+}
+
+async fn wait_for_offset(start: Instant, offset_ms: u64, realtime: bool) {
+    if realtime {
+        tokio::time::sleep_until(start + Duration::from_millis(offset_ms)).await;
+    }
+}
+
+/// Feeds the recorded ToServer messages into a fresh server and compares the FromServer
+/// messages sent by the fresh server with the recorded ones.
+///
+/// # Arguments
+///
+/// * `records` - The recorded traffic
+/// * `startup_state` - The startup state of the recorded server
+/// * `realtime` - Keeps the recorded time between the ToServer messages if set
+///
+// [impl->swdd~server-replays-recorded-traffic-into-server~1]
+pub async fn replay_into_server(
+    records: Vec<TrafficRecord>,
+    startup_state: Option<CompleteState>,
+    realtime: bool,
+) -> ReplayReport {
+    let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+    let (to_agents, mut agents_receiver) = create_from_server_channel(common::CHANNEL_CAPACITY);
+    let mut server = AnkaiosServer::new(server_receiver, to_agents);
+    let server_task = tokio::spawn(async move {
+        if let Err(err) = server.start(startup_state).await {
+            log::warn!("The replayed server stopped with an error: '{}'", err);
+        }
+    });
+    // the channel is closed when the replayed server stops
+    let collector_task = tokio::spawn(async move {
+        let mut replayed_from_server = Vec::new();
+        while let Some(message) = agents_receiver.recv().await {
+            replayed_from_server.push(message);
+        }
+        replayed_from_server
+    });
+
+    let mut recorded_from_server = Vec::new();
+    let mut report = ReplayReport::default();
+    let start = Instant::now();
+    for record in records {
+        match record.message {
+            TrafficMessage::ToServer(message) => {
+                wait_for_offset(start, record.offset_ms, realtime).await;
+                if to_server.send(message).await.is_err() {
+                    log::warn!("The replayed server stopped before the end of the recording.");
+                    break;
+                }
+                report.replayed_to_server += 1;
+            }
+            TrafficMessage::FromServer(message) => recorded_from_server.push(message),
+        }
+    }
+
+    tokio::time::sleep(REPLAY_SETTLE_TIME).await;
+    drop(to_server);
+    server_task.await.unwrap_or_default();
+    let replayed_from_server = collector_task.await.unwrap_or_default();
+
+    report.recorded_from_server = recorded_from_server.len();
+    report.replayed_from_server = replayed_from_server.len();
+    report.diverging_from_server =
+        diverging_positions(&recorded_from_server, &replayed_from_server);
+    report
+}
+
+// The workload states are compared without their timestamps, see the equality of WorkloadState.
+fn diverging_positions(recorded: &[FromServer], replayed: &[FromServer]) -> Vec<usize> {
+    recorded
+        .iter()
+        .zip(replayed.iter())
+        .enumerate()
+        .filter(|(_, (recorded, replayed))| recorded != replayed)
+        .map(|(position, (recorded, replayed))| {
+            log::info!(
+                "FromServer message {} diverges:\n\trecorded: {:?}\n\treplayed: {:?}",
+                position,
+                recorded,
+                replayed
+            );
+            position
+        })
+        .collect()
+}
+
+/// Serves the recorded FromServer messages to the agents connecting at the given address.
+///
+/// The replay starts as soon as the first agent has connected. The ToServer messages
+/// sent by the agents are logged.
+///
+/// # Arguments
+///
+/// * `records` - The recorded traffic
+/// * `address` - The address the agents connect to
+/// * `realtime` - Keeps the recorded time between the FromServer messages if set
+///
+// [impl->swdd~server-replays-recorded-traffic-to-agents~1]
+pub async fn replay_to_agents(
+    records: Vec<TrafficRecord>,
+    address: SocketAddr,
+    realtime: bool,
+) -> Result<(), String> {
+    let (to_server, mut server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+    let (to_agents, agents_receiver) = create_from_server_channel(common::CHANNEL_CAPACITY);
+    let mut communications_server = GRPCCommunicationsServer::new(to_server);
+    let communications_task = tokio::spawn(async move {
+        communications_server
+            .start(agents_receiver, address)
+            .await
+            .map_err(|err| err.to_string())
+    });
+
+    log::info!("Waiting for an agent to connect at '{}'", address);
+    loop {
+        match server_receiver.recv().await {
+            Some(ToServer::AgentHello(agent_hello)) => {
+                log::info!("Agent '{}' connected", agent_hello.agent_name);
+                break;
+            }
+            Some(message) => log::info!("Received from the agents: '{:?}'", message),
+            None => return Err("The communications server stopped".to_string()),
+        }
+    }
+    tokio::spawn(async move {
+        while let Some(message) = server_receiver.recv().await {
+            log::info!("Received from the agents: '{:?}'", message);
+        }
+    });
+
+    let start = Instant::now();
+    let mut first_offset_ms = None;
+    for record in records {
+        if let TrafficMessage::FromServer(message) = record.message {
+            let first_offset_ms = *first_offset_ms.get_or_insert(record.offset_ms);
+            let relative_offset_ms = record.offset_ms.saturating_sub(first_offset_ms);
+            wait_for_offset(start, relative_offset_ms, realtime).await;
+            to_agents
+                .send(message)
+                .await
+                .map_err(|err| format!("Could not replay a message: '{err}'"))?;
+        }
+    }
+
+    tokio::time::sleep(REPLAY_SETTLE_TIME).await;
+    communications_task.abort();
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        commands::{AgentHello, Goodbye, ServerGone, Stop},
+        from_server_interface::FromServer,
+        to_server_interface::ToServer,
+    };
+    use tokio::sync::mpsc;
+
+    use super::{
+        diverging_positions, read_recording, record_traffic, ReplayReport, TrafficMessage,
+        TrafficRecorder,
+    };
+
+    const AGENT_A: &str = "agent_A";
+
+    fn agent_hello() -> ToServer {
+        ToServer::AgentHello(AgentHello {
+            agent_name: AGENT_A.to_string(),
+            ..Default::default()
+        })
+    }
+
+    // [utest->swdd~server-records-traffic~1]
+    #[tokio::test]
+    async fn utest_record_traffic_forwards_and_records_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("traffic.jsonl");
+
+        let (to_server, server_receiver) = mpsc::channel(1);
+        let (to_agents, agents_receiver) = mpsc::channel(1);
+        let (mut server_receiver, mut agents_receiver) = record_traffic(
+            TrafficRecorder::create(&path).unwrap(),
+            server_receiver,
+            agents_receiver,
+        );
+
+        to_server.send(agent_hello()).await.unwrap();
+        assert_eq!(server_receiver.recv().await, Some(agent_hello()));
+        to_agents
+            .send(FromServer::Stop(Stop {}))
+            .await
+            .unwrap();
+        assert_eq!(
+            agents_receiver.recv().await,
+            Some(FromServer::Stop(Stop {}))
+        );
+        to_server
+            .send(ToServer::Goodbye(Goodbye {}))
+            .await
+            .unwrap();
+        server_receiver.recv().await;
+
+        let messages: Vec<TrafficMessage> = read_recording(&path)
+            .unwrap()
+            .into_iter()
+            .map(|record| record.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                TrafficMessage::ToServer(agent_hello()),
+                TrafficMessage::FromServer(FromServer::Stop(Stop {})),
+                TrafficMessage::ToServer(ToServer::Goodbye(Goodbye {})),
+            ]
+        );
+    }
+
+    // [utest->swdd~server-replays-recorded-traffic-into-server~1]
+    #[test]
+    fn utest_diverging_positions_compares_recorded_and_replayed_messages() {
+        let stop = FromServer::Stop(Stop {});
+        let server_gone = FromServer::ServerGone(ServerGone {});
+
+        assert_eq!(
+            diverging_positions(
+                &[stop.clone(), server_gone.clone(), stop.clone()],
+                &[stop.clone(), stop.clone(), stop.clone(), server_gone]
+            ),
+            vec![1]
+        );
+        assert!(diverging_positions(&[stop.clone()], &[stop]).is_empty());
+    }
+
+    // [utest->swdd~server-replays-recorded-traffic-into-server~1]
+    #[test]
+    fn utest_replay_report_is_reproduced_only_without_missing_messages() {
+        let report = ReplayReport {
+            replayed_to_server: 2,
+            recorded_from_server: 3,
+            replayed_from_server: 3,
+            diverging_from_server: vec![],
+        };
+        assert!(report.is_reproduced());
+
+        assert!(!ReplayReport {
+            replayed_from_server: 2,
+            ..report
+        }
+        .is_reproduced());
+    }
+
+    // [utest->swdd~server-replays-recorded-traffic-into-server~1]
+    #[test]
+    fn utest_read_recording_reports_invalid_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("traffic.jsonl");
+        std::fs::write(&path, "{\"offsetMs\": 1}\n").unwrap();
+
+        assert!(read_recording(&path)
+            .unwrap_err()
+            .starts_with("Invalid record in line 1"));
+    }
+}
@@ -12,8 +12,13 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use common::objects::{ExecutionState, WorkloadInstanceName, WorkloadSpec, WorkloadState};
-use std::collections::HashMap;
+use common::helpers::now_ms;
+use common::objects::{
+    ExecutionState, ExecutionStateEnum, TerminatedWorkload, WorkloadInstanceName, WorkloadSpec,
+    WorkloadState,
+};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
 
 type AgentName = String;
 
@@ -22,15 +27,43 @@ type AgentWorkloadStates = HashMap<AgentName, WorkloadStatesMap>;
 
 pub struct WorkloadStateDB {
     stored_states: AgentWorkloadStates,
+    last_refreshes: HashMap<WorkloadInstanceName, Instant>,
+    terminated_workload_retention: Option<Duration>,
+    // The state a stopping workload had before it was stopped, which is its final state.
+    states_before_stopping: WorkloadStatesMap,
+    // Ordered by the removal time, the oldest first.
+    terminated_workloads: VecDeque<TerminatedWorkload>,
 }
 
 impl WorkloadStateDB {
     pub fn new() -> Self {
         Self {
             stored_states: HashMap::new(),
+            last_refreshes: HashMap::new(),
+            terminated_workload_retention: None,
+            states_before_stopping: HashMap::new(),
+            terminated_workloads: VecDeque::new(),
         }
     }
 
+    // [impl->swdd~server-retains-terminated-workload-states~1]
+    pub fn retain_terminated_workloads(&mut self, retention: Duration) {
+        self.terminated_workload_retention = Some(retention);
+    }
+
+    // [impl->swdd~server-retains-terminated-workload-states~1]
+    pub fn get_terminated_workloads(&self) -> Vec<TerminatedWorkload> {
+        let Some(retention) = self.terminated_workload_retention else {
+            return Vec::new();
+        };
+        let oldest_removal = now_ms().saturating_sub(retention.as_millis() as u64);
+        self.terminated_workloads
+            .iter()
+            .filter(|terminated_workload| terminated_workload.removed_at >= oldest_removal)
+            .cloned()
+            .collect()
+    }
+
     // [impl->swdd~server-provides-interface-get-complete-state~1]
     pub fn get_all_workload_states(&self) -> Vec<WorkloadState> {
         self.stored_states
@@ -67,6 +100,33 @@ impl WorkloadStateDB {
             .collect()
     }
 
+    // [impl->swdd~server-sends-workload-states-of-subscribed-workloads~1]
+    pub fn get_workload_states_of_workloads(
+        &self,
+        workload_names: &[String],
+    ) -> Vec<WorkloadState> {
+        self.stored_states
+            .values()
+            .flat_map(|workload_states| workload_states.values())
+            .filter(|workload_state| {
+                workload_names.iter().any(|workload_name| {
+                    workload_name == workload_state.instance_name.workload_name()
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_execution_state(
+        &self,
+        instance_name: &WorkloadInstanceName,
+    ) -> Option<&ExecutionState> {
+        self.stored_states
+            .get(instance_name.agent_name())
+            .and_then(|agent_states| agent_states.get(instance_name))
+            .map(|workload_state| &workload_state.execution_state)
+    }
+
     // [impl->swdd~server-set-workload-state-on-disconnect~1]
     pub fn agent_disconnected(&mut self, agent_name: &str) {
         if let Some(agent_states) = self.stored_states.get_mut(agent_name) {
@@ -79,6 +139,9 @@ impl WorkloadStateDB {
     // [impl->swdd~server-sets-state-of-new-workloads-to-pending~1]
     pub fn initial_state(&mut self, workload_specs: &Vec<WorkloadSpec>) {
         for spec in workload_specs {
+            self.last_refreshes
+                .entry(spec.instance_name.to_owned())
+                .or_insert_with(Instant::now);
             self.stored_states
                 .entry(spec.instance_name.agent_name().to_owned())
                 .or_default()
@@ -90,23 +153,65 @@ impl WorkloadStateDB {
                     } else {
                         ExecutionState::initial()
                     },
+                    agent_timestamp: None,
+                    server_timestamp: None,
                 });
         }
     }
 
     // [impl->swdd~server-deletes-removed-workload-state~1]
-    pub fn remove(&mut self, instance_name: &WorkloadInstanceName) {
-        if let Some(agent_states) = self.stored_states.get_mut(instance_name.agent_name()) {
-            agent_states.remove(instance_name);
+    pub fn remove(&mut self, instance_name: &WorkloadInstanceName) -> Option<WorkloadState> {
+        self.last_refreshes.remove(instance_name);
+        let state_before_stopping = self.states_before_stopping.remove(instance_name);
+        self.stored_states
+            .get_mut(instance_name.agent_name())
+            .and_then(|agent_states| agent_states.remove(instance_name))
+            .map(|stored_state| state_before_stopping.unwrap_or(stored_state))
+    }
+
+    // Removes the state of a workload deleted from Ankaios and keeps its final state for the
+    // retention period.
+    // [impl->swdd~server-deletes-removed-workload-state~1]
+    // [impl->swdd~server-retains-terminated-workload-states~1]
+    pub fn terminate(&mut self, instance_name: &WorkloadInstanceName) {
+        let final_state = self.remove(instance_name);
+        let Some(retention) = self.terminated_workload_retention else {
+            return;
+        };
+
+        let removed_at = now_ms();
+        let oldest_removal = removed_at.saturating_sub(retention.as_millis() as u64);
+        while self
+            .terminated_workloads
+            .front()
+            .is_some_and(|terminated_workload| terminated_workload.removed_at < oldest_removal)
+        {
+            self.terminated_workloads.pop_front();
+        }
+
+        if let Some(final_state) = final_state {
+            self.terminated_workloads.push_back(TerminatedWorkload {
+                final_state,
+                removed_at,
+            });
         }
     }
 
+    // The receive time is taken from the clock of the server, the refresh time used for
+    // detecting stale states from the monotonic clock. The time reported by the agent is kept
+    // as it is, as it is not comparable with the times of other agents.
     // [impl->swdd~server-stores-workload-state~1]
+    // [impl->swdd~server-stamps-received-workload-states~1]
     pub fn process_new_states(&mut self, workload_states: Vec<WorkloadState>) {
-        workload_states.into_iter().for_each(|workload_state| {
+        let received_at = now_ms();
+        workload_states.into_iter().for_each(|mut workload_state| {
             if workload_state.execution_state.is_removed() {
-                self.remove(&workload_state.instance_name);
+                self.terminate(&workload_state.instance_name);
             } else {
+                workload_state.server_timestamp = Some(received_at);
+                self.last_refreshes
+                    .insert(workload_state.instance_name.to_owned(), Instant::now());
+                self.remember_state_before_stopping(&workload_state);
                 self.stored_states
                     .entry(workload_state.instance_name.agent_name().to_owned())
                     .or_default()
@@ -114,6 +219,53 @@ impl WorkloadStateDB {
             }
         });
     }
+
+    // [impl->swdd~server-retains-terminated-workload-states~1]
+    fn remember_state_before_stopping(&mut self, new_state: &WorkloadState) {
+        if self.terminated_workload_retention.is_none() {
+            return;
+        }
+        if !new_state.execution_state.is_stopping() {
+            self.states_before_stopping.remove(&new_state.instance_name);
+            return;
+        }
+        if let Some(stored_state) = self
+            .stored_states
+            .get(new_state.instance_name.agent_name())
+            .and_then(|agent_states| agent_states.get(&new_state.instance_name))
+            .filter(|stored_state| !stored_state.execution_state.is_stopping())
+        {
+            self.states_before_stopping
+                .insert(stored_state.instance_name.clone(), stored_state.clone());
+        }
+    }
+
+    // Only the states of running and stopping workloads are refreshed by the agents. Pending and
+    // terminal states, the states of disconnected agents and unscheduled workloads and states
+    // which are already unknown are not expected to be refreshed.
+    // [impl->swdd~server-reaps-stale-workload-states~2]
+    pub fn mark_stale_states(&mut self, max_age: Duration) -> Vec<WorkloadState> {
+        let mut stale_states = Vec::new();
+        for workload_state in self
+            .stored_states
+            .values_mut()
+            .flat_map(|agent_states| agent_states.values_mut())
+        {
+            let is_outdated = self
+                .last_refreshes
+                .get(&workload_state.instance_name)
+                .is_some_and(|last_refresh| last_refresh.elapsed() > max_age);
+            let is_refreshed = matches!(
+                workload_state.execution_state.state,
+                ExecutionStateEnum::Running(_) | ExecutionStateEnum::Stopping(_)
+            );
+            if is_outdated && is_refreshed {
+                workload_state.execution_state = ExecutionState::stale();
+                stale_states.push(workload_state.clone());
+            }
+        }
+        stale_states
+    }
 }
 
 impl Default for WorkloadStateDB {
@@ -132,6 +284,7 @@ impl Default for WorkloadStateDB {
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
+    use std::time::{Duration, Instant};
 
     use common::objects::{
         generate_test_workload_spec_with_runtime_config, generate_test_workload_state_with_agent,
@@ -212,6 +365,28 @@ mod tests {
         )
     }
 
+    #[test]
+    fn utest_get_execution_state_of_workload() {
+        let wls_db = create_test_setup();
+
+        let wl_3_state = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_3,
+            AGENT_B,
+            ExecutionState::running(),
+        );
+        let wl_4_state = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_4,
+            AGENT_B,
+            ExecutionState::running(),
+        );
+
+        assert_eq!(
+            wls_db.get_execution_state(&wl_3_state.instance_name),
+            Some(&ExecutionState::running())
+        );
+        assert_eq!(wls_db.get_execution_state(&wl_4_state.instance_name), None);
+    }
+
     // [utest->swdd~server-stores-workload-state~1]
     #[test]
     fn utest_workload_states_store_new() {
@@ -255,6 +430,29 @@ mod tests {
         )
     }
 
+    // [utest->swdd~server-stamps-received-workload-states~1]
+    #[test]
+    fn utest_workload_states_stamped_with_receive_time() {
+        let mut wls_db = WorkloadStateDB::default();
+
+        let mut wl_state = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::running(),
+        );
+        wl_state.agent_timestamp = Some(1000);
+
+        let before = super::now_ms();
+        wls_db.process_new_states(vec![wl_state]);
+
+        let stored_states = wls_db.get_all_workload_states();
+        assert_eq!(stored_states.len(), 1);
+        assert_eq!(stored_states[0].agent_timestamp, Some(1000));
+        assert!(stored_states[0]
+            .server_timestamp
+            .is_some_and(|server_timestamp| server_timestamp >= before));
+    }
+
     // [utest->swdd~server-stores-workload-state~1]
     #[test]
     fn utest_workload_states_store_update() {
@@ -322,6 +520,39 @@ mod tests {
         )
     }
 
+    // [utest->swdd~server-sends-workload-states-of-subscribed-workloads~1]
+    #[test]
+    fn utest_get_workload_states_of_workloads_returns_correct() {
+        let wls_db = create_test_setup();
+
+        let mut wls_res = wls_db.get_workload_states_of_workloads(&[
+            WORKLOAD_NAME_1.to_string(),
+            WORKLOAD_NAME_3.to_string(),
+            WORKLOAD_NAME_4.to_string(),
+        ]);
+        wls_res.sort_by(|a, b| {
+            a.instance_name
+                .workload_name()
+                .cmp(b.instance_name.workload_name())
+        });
+
+        assert_eq!(
+            wls_res,
+            vec![
+                generate_test_workload_state_with_agent(
+                    WORKLOAD_NAME_1,
+                    AGENT_A,
+                    ExecutionState::succeeded()
+                ),
+                generate_test_workload_state_with_agent(
+                    WORKLOAD_NAME_3,
+                    AGENT_B,
+                    ExecutionState::running()
+                ),
+            ]
+        )
+    }
+
     // [utest->swdd~server-set-workload-state-on-disconnect~1]
     #[test]
     fn utest_mark_all_workload_state_for_agent_disconnected() {
@@ -439,6 +670,79 @@ mod tests {
         )
     }
 
+    // [utest->swdd~server-retains-terminated-workload-states~1]
+    #[test]
+    fn utest_workload_states_retains_final_state_of_removed_workload() {
+        let mut wls_db = create_test_setup();
+        wls_db.retain_terminated_workloads(Duration::from_secs(60));
+
+        let wl_state_1 = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::failed("exit code 1"),
+        );
+        let mut stopping_state = wl_state_1.clone();
+        stopping_state.execution_state = ExecutionState::stopping_requested();
+        let mut removed_state = wl_state_1.clone();
+        removed_state.execution_state = ExecutionState::removed();
+
+        let before = super::now_ms();
+        wls_db.process_new_states(vec![wl_state_1.clone(), stopping_state, removed_state]);
+
+        let terminated_workloads = wls_db.get_terminated_workloads();
+        assert_eq!(terminated_workloads.len(), 1);
+        assert_eq!(terminated_workloads[0].final_state, wl_state_1);
+        assert!(terminated_workloads[0]
+            .final_state
+            .server_timestamp
+            .is_some_and(|server_timestamp| server_timestamp >= before));
+        assert!(terminated_workloads[0].removed_at >= before);
+        assert_eq!(wls_db.get_execution_state(&wl_state_1.instance_name), None);
+    }
+
+    // [utest->swdd~server-retains-terminated-workload-states~1]
+    #[test]
+    fn utest_workload_states_drops_terminated_workloads_after_retention() {
+        let mut wls_db = create_test_setup();
+        wls_db.retain_terminated_workloads(Duration::ZERO);
+
+        wls_db.process_new_states(vec![generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::removed(),
+        )]);
+        std::thread::sleep(Duration::from_millis(2));
+        wls_db.process_new_states(vec![generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_3,
+            AGENT_B,
+            ExecutionState::removed(),
+        )]);
+
+        assert_eq!(wls_db.terminated_workloads.len(), 1);
+        assert_eq!(
+            wls_db.terminated_workloads[0]
+                .final_state
+                .instance_name
+                .workload_name(),
+            WORKLOAD_NAME_3
+        );
+    }
+
+    // [utest->swdd~server-retains-terminated-workload-states~1]
+    #[test]
+    fn utest_workload_states_drops_removed_state_without_retention() {
+        let mut wls_db = create_test_setup();
+
+        wls_db.process_new_states(vec![generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::removed(),
+        )]);
+
+        assert!(wls_db.terminated_workloads.is_empty());
+        assert!(wls_db.get_terminated_workloads().is_empty());
+    }
+
     // [utest->swdd~server-sets-state-of-new-workloads-to-pending~1]
     #[test]
     fn utest_workload_states_initial_state() {
@@ -482,4 +786,86 @@ mod tests {
             ]
         )
     }
+
+    // [utest->swdd~server-reaps-stale-workload-states~2]
+    #[test]
+    fn utest_mark_stale_states_of_outdated_workloads() {
+        const WORKLOAD_NAME_5: &str = "workload_5";
+        let mut wls_db = create_test_setup();
+        wls_db.agent_disconnected(AGENT_B);
+
+        let wl_state_4 = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_4,
+            AGENT_A,
+            ExecutionState::running(),
+        );
+        let wl_state_5 = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_5,
+            AGENT_A,
+            ExecutionState::running(),
+        );
+        wls_db.process_new_states(vec![wl_state_4.clone(), wl_state_5.clone()]);
+
+        // the succeeded workload 1 and the pending workload 2 are not refreshed by the agent
+        let outdated = Instant::now() - Duration::from_secs(10);
+        for workload_name in [WORKLOAD_NAME_1, WORKLOAD_NAME_2, WORKLOAD_NAME_5] {
+            let instance_name = generate_test_workload_state_with_agent(
+                workload_name,
+                AGENT_A,
+                ExecutionState::running(),
+            )
+            .instance_name;
+            wls_db.last_refreshes.insert(instance_name, outdated);
+        }
+        let wl_state_3 = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_3,
+            AGENT_B,
+            ExecutionState::agent_disconnected(),
+        );
+        wls_db
+            .last_refreshes
+            .insert(wl_state_3.instance_name.clone(), outdated);
+
+        let stale_states = wls_db.mark_stale_states(Duration::from_secs(5));
+
+        let expected_stale_states = vec![generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_5,
+            AGENT_A,
+            ExecutionState::stale(),
+        )];
+        assert_eq!(stale_states, expected_stale_states);
+        assert_eq!(
+            wls_db.get_execution_state(
+                &generate_test_workload_state_with_agent(
+                    WORKLOAD_NAME_1,
+                    AGENT_A,
+                    ExecutionState::succeeded(),
+                )
+                .instance_name
+            ),
+            Some(&ExecutionState::succeeded())
+        );
+        assert_eq!(
+            wls_db.get_execution_state(
+                &generate_test_workload_state_with_agent(
+                    WORKLOAD_NAME_2,
+                    AGENT_A,
+                    ExecutionState::succeeded(),
+                )
+                .instance_name
+            ),
+            Some(&ExecutionState::starting("additional_info"))
+        );
+        assert_eq!(
+            wls_db.get_execution_state(&wl_state_4.instance_name),
+            Some(&ExecutionState::running())
+        );
+        assert_eq!(
+            wls_db.get_execution_state(&wl_state_3.instance_name),
+            Some(&ExecutionState::agent_disconnected())
+        );
+
+        // stale states are not reported again
+        assert!(wls_db.mark_stale_states(Duration::from_secs(5)).is_empty());
+    }
 }
@@ -17,6 +17,9 @@ tar -cvzf "${DIST_DIR}/"coverage-report.tar.gz --directory="${DIST_DIR}/coverage
 
 echo "Exporting control api protos"
 cp "${ROOT_DIR}"/api/proto/*.proto "${DIST_DIR}"
+mkdir -p "${DIST_DIR}/compat"
+cp "${ROOT_DIR}"/api/proto/compat/*.proto "${DIST_DIR}/compat"
+sed 's/^package ank\.v1;$/package ank_base;\noption deprecated = true;/' "${ROOT_DIR}"/api/proto/ank_base.proto > "${DIST_DIR}/compat/ank_base.proto"
 
 echo "Exporting install script"
 cp "${ROOT_DIR}"/tools/install.sh "${DIST_DIR}"